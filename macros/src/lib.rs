@@ -0,0 +1,120 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitInt, meta::ParseNestedMeta, parse_macro_input};
+
+/// Derive `crate::core::validation::Validate` from `#[validate(...)]` field attributes:
+///
+/// - `required`: present and not blank
+/// - `email`: a well-formed email address
+/// - `length(min = .., max = ..)`: number of characters
+/// - `range(min = .., max = ..)`: numeric bounds
+/// - `password`: meets the password strength policy
+///
+/// Rules other than `required` skip fields that are `None`.
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn derive_validate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Validate can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Validate requires named fields",
+        ));
+    };
+
+    let mut checks = Vec::new();
+    let mut constraints = Vec::new();
+    for field in &fields.named {
+        let mut rules = Vec::new();
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("validate")) {
+            attr.parse_nested_meta(|meta| {
+                rules.push(parse_rule(&meta)?);
+                Ok(())
+            })?;
+        }
+        if rules.is_empty() {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("named field");
+        let name = ident.to_string().trim_start_matches("r#").to_string();
+        checks.push(quote! {
+            errors.check(#name, &self.#ident, &[#(#rules),*], locale);
+        });
+        constraints.push(quote! {
+            crate::core::validation::FieldConstraints {
+                field: #name,
+                rules: &[#(#rules),*],
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics crate::core::validation::Validate for #ident #ty_generics #where_clause {
+            fn validate(
+                &self,
+                locale: &str,
+            ) -> ::std::result::Result<(), crate::core::validation::ValidationErrors> {
+                let mut errors = crate::core::validation::ValidationErrors::default();
+                #(#checks)*
+                errors.into_result()
+            }
+
+            fn constraints() -> &'static [crate::core::validation::FieldConstraints] {
+                &[#(#constraints),*]
+            }
+        }
+    })
+}
+
+fn parse_rule(meta: &ParseNestedMeta) -> syn::Result<TokenStream2> {
+    let rule = quote!(crate::core::validation::Rule);
+    if meta.path.is_ident("required") {
+        Ok(quote!(#rule::Required))
+    } else if meta.path.is_ident("email") {
+        Ok(quote!(#rule::Email))
+    } else if meta.path.is_ident("password") {
+        Ok(quote!(#rule::Password))
+    } else if meta.path.is_ident("length") {
+        let (min, max) = parse_bounds(meta)?;
+        Ok(quote!(#rule::Length { min: #min, max: #max }))
+    } else if meta.path.is_ident("range") {
+        let (min, max) = parse_bounds(meta)?;
+        Ok(quote!(#rule::Range { min: #min, max: #max }))
+    } else {
+        Err(meta.error("unknown validation rule"))
+    }
+}
+
+/// `min` and `max` of a `length(..)` or `range(..)` rule, as `Option` expressions
+fn parse_bounds(meta: &ParseNestedMeta) -> syn::Result<(TokenStream2, TokenStream2)> {
+    let mut min = quote!(::std::option::Option::None);
+    let mut max = quote!(::std::option::Option::None);
+    meta.parse_nested_meta(|bound| {
+        let value: LitInt = bound.value()?.parse()?;
+        let value = quote!(::std::option::Option::Some(#value));
+        if bound.path.is_ident("min") {
+            min = value;
+        } else if bound.path.is_ident("max") {
+            max = value;
+        } else {
+            return Err(bound.error("expected `min` or `max`"));
+        }
+        Ok(())
+    })?;
+    Ok((min, max))
+}
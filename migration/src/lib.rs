@@ -4,6 +4,41 @@ mod m20220101_000001_create_table;
 mod m20251108_000002_add_refresh_token_table;
 mod m20251130_000003_add_password_reset_token_table;
 mod m20260412_000004_add_user_role;
+mod m20261016_000005_add_file_table;
+mod m20261016_000006_add_file_variants;
+mod m20261016_000007_add_signing_key_table;
+mod m20261017_000008_add_device_token_table;
+mod m20261017_000009_add_notification_preference_table;
+mod m20261017_000010_add_notification_table;
+mod m20261017_000011_add_phone_verification;
+mod m20261017_000012_hash_stored_tokens;
+mod m20261017_000013_add_user_locale;
+mod m20261017_000014_add_user_timezone;
+mod m20261017_000015_add_user_deactivated_at;
+mod m20261017_000016_add_task_event_log_table;
+mod m20261017_000017_add_security_event_table;
+mod m20261017_000018_add_refresh_token_expiration_policy;
+mod m20261017_000019_add_dead_letter_table;
+mod m20261017_000020_add_user_normalized_email;
+mod m20261017_000021_add_task_pause_table;
+mod m20261017_000022_add_broadcast_event_table;
+mod m20261017_000023_add_audit_log_table;
+mod m20261017_000024_add_avatar_moderation;
+mod m20261017_000025_add_oauth_account_table;
+mod m20261017_000026_add_rbac_tables;
+mod m20261017_000027_add_refresh_token_rotation;
+mod m20261017_000028_add_user_email_table;
+mod m20261017_000029_add_email_verification;
+mod m20261017_000030_add_login_attempt;
+mod m20261017_000031_add_worker_heartbeat;
+mod m20261017_000032_add_oauth_provider_tables;
+mod m20261017_000033_add_impersonation_table;
+mod m20261017_000034_add_user_deleted_at;
+mod m20261017_000035_add_outbox_table;
+mod m20261018_000036_count_login_attempts_by_email;
+mod m20261018_000037_add_impersonate_permission;
+mod m20261018_000038_add_user_restore_and_hard_delete_permissions;
+mod m20261018_000039_track_authorization_code_use;
 
 pub struct Migrator;
 
@@ -15,6 +50,41 @@ impl MigratorTrait for Migrator {
             Box::new(m20251108_000002_add_refresh_token_table::Migration),
             Box::new(m20251130_000003_add_password_reset_token_table::Migration),
             Box::new(m20260412_000004_add_user_role::Migration),
+            Box::new(m20261016_000005_add_file_table::Migration),
+            Box::new(m20261016_000006_add_file_variants::Migration),
+            Box::new(m20261016_000007_add_signing_key_table::Migration),
+            Box::new(m20261017_000008_add_device_token_table::Migration),
+            Box::new(m20261017_000009_add_notification_preference_table::Migration),
+            Box::new(m20261017_000010_add_notification_table::Migration),
+            Box::new(m20261017_000011_add_phone_verification::Migration),
+            Box::new(m20261017_000012_hash_stored_tokens::Migration),
+            Box::new(m20261017_000013_add_user_locale::Migration),
+            Box::new(m20261017_000014_add_user_timezone::Migration),
+            Box::new(m20261017_000015_add_user_deactivated_at::Migration),
+            Box::new(m20261017_000016_add_task_event_log_table::Migration),
+            Box::new(m20261017_000017_add_security_event_table::Migration),
+            Box::new(m20261017_000018_add_refresh_token_expiration_policy::Migration),
+            Box::new(m20261017_000019_add_dead_letter_table::Migration),
+            Box::new(m20261017_000020_add_user_normalized_email::Migration),
+            Box::new(m20261017_000021_add_task_pause_table::Migration),
+            Box::new(m20261017_000022_add_broadcast_event_table::Migration),
+            Box::new(m20261017_000023_add_audit_log_table::Migration),
+            Box::new(m20261017_000024_add_avatar_moderation::Migration),
+            Box::new(m20261017_000025_add_oauth_account_table::Migration),
+            Box::new(m20261017_000026_add_rbac_tables::Migration),
+            Box::new(m20261017_000027_add_refresh_token_rotation::Migration),
+            Box::new(m20261017_000028_add_user_email_table::Migration),
+            Box::new(m20261017_000029_add_email_verification::Migration),
+            Box::new(m20261017_000030_add_login_attempt::Migration),
+            Box::new(m20261017_000031_add_worker_heartbeat::Migration),
+            Box::new(m20261017_000032_add_oauth_provider_tables::Migration),
+            Box::new(m20261017_000033_add_impersonation_table::Migration),
+            Box::new(m20261017_000034_add_user_deleted_at::Migration),
+            Box::new(m20261017_000035_add_outbox_table::Migration),
+            Box::new(m20261018_000036_count_login_attempts_by_email::Migration),
+            Box::new(m20261018_000037_add_impersonate_permission::Migration),
+            Box::new(m20261018_000038_add_user_restore_and_hard_delete_permissions::Migration),
+            Box::new(m20261018_000039_track_authorization_code_use::Migration),
         ]
     }
 }
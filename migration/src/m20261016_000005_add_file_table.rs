@@ -0,0 +1,72 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut foreign_key = ForeignKey::create()
+            .name("fk-file-user_id")
+            .from(File::Table, File::UserId)
+            .to(User::Table, User::Id)
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::NoAction)
+            .to_owned();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(File::Table)
+                    .if_not_exists()
+                    .col(pk_auto(File::Id))
+                    .col(integer(File::UserId).not_null())
+                    .col(string_len(File::Key, 512).not_null().unique_key())
+                    .col(string_len(File::Name, 255).not_null())
+                    .col(string_len_null(File::ContentType, 128))
+                    .col(big_integer(File::Size).not_null().default(0))
+                    .col(string_len(File::Status, 16).not_null().default("pending"))
+                    .col(timestamp_null(File::CreatedAt))
+                    .col(timestamp_null(File::UpdatedAt))
+                    .foreign_key(&mut foreign_key)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("ix_file_user_id")
+                    .table(File::Table)
+                    .col(File::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(File::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum File {
+    Table,
+    Id,
+    UserId,
+    Key,
+    Name,
+    ContentType,
+    Size,
+    Status,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
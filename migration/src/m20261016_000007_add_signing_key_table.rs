@@ -0,0 +1,39 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SigningKey::Table)
+                    .if_not_exists()
+                    .col(pk_auto(SigningKey::Id))
+                    .col(string_len(SigningKey::Kid, 64).not_null().unique_key())
+                    .col(string(SigningKey::Secret).not_null())
+                    .col(timestamp_null(SigningKey::RetiresAt))
+                    .col(timestamp_null(SigningKey::CreatedAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SigningKey::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SigningKey {
+    Table,
+    Id,
+    Kid,
+    Secret,
+    RetiresAt,
+    CreatedAt,
+}
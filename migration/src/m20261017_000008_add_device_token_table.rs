@@ -0,0 +1,66 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut foreign_key = ForeignKey::create()
+            .name("fk-device_token-user_id")
+            .from(DeviceToken::Table, DeviceToken::UserId)
+            .to(User::Table, User::Id)
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::NoAction)
+            .to_owned();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(DeviceToken::Table)
+                    .if_not_exists()
+                    .col(pk_auto(DeviceToken::Id))
+                    .col(integer(DeviceToken::UserId).not_null())
+                    .col(string_len(DeviceToken::Token, 512).not_null().unique_key())
+                    .col(string_len(DeviceToken::Platform, 16).not_null())
+                    .col(timestamp_null(DeviceToken::CreatedAt))
+                    .col(timestamp_null(DeviceToken::UpdatedAt))
+                    .foreign_key(&mut foreign_key)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("ix_device_token_user_id")
+                    .table(DeviceToken::Table)
+                    .col(DeviceToken::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DeviceToken::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DeviceToken {
+    Table,
+    Id,
+    UserId,
+    Token,
+    Platform,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
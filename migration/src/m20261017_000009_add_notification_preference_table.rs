@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut foreign_key = ForeignKey::create()
+            .name("fk-notification_preference-user_id")
+            .from(
+                NotificationPreference::Table,
+                NotificationPreference::UserId,
+            )
+            .to(User::Table, User::Id)
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::NoAction)
+            .to_owned();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationPreference::Table)
+                    .if_not_exists()
+                    .col(pk_auto(NotificationPreference::Id))
+                    .col(integer(NotificationPreference::UserId).not_null())
+                    .col(string_len(NotificationPreference::Category, 16).not_null())
+                    .col(json(NotificationPreference::Channels).not_null())
+                    .col(timestamp_null(NotificationPreference::CreatedAt))
+                    .col(timestamp_null(NotificationPreference::UpdatedAt))
+                    .foreign_key(&mut foreign_key)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("ux_notification_preference_user_id_category")
+                    .table(NotificationPreference::Table)
+                    .col(NotificationPreference::UserId)
+                    .col(NotificationPreference::Category)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(NotificationPreference::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NotificationPreference {
+    Table,
+    Id,
+    UserId,
+    Category,
+    Channels,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
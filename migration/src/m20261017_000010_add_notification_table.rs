@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut foreign_key = ForeignKey::create()
+            .name("fk-notification-user_id")
+            .from(Notification::Table, Notification::UserId)
+            .to(User::Table, User::Id)
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::NoAction)
+            .to_owned();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notification::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Notification::Id))
+                    .col(integer(Notification::UserId).not_null())
+                    .col(string_len(Notification::Category, 16).not_null())
+                    .col(string_len(Notification::Title, 255).not_null())
+                    .col(text(Notification::Body).not_null())
+                    .col(json(Notification::Data).not_null())
+                    .col(timestamp_null(Notification::ReadAt))
+                    .col(timestamp_null(Notification::DigestedAt))
+                    .col(timestamp_null(Notification::CreatedAt))
+                    .col(timestamp_null(Notification::UpdatedAt))
+                    .foreign_key(&mut foreign_key)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notification_user_id_read_at")
+                    .table(Notification::Table)
+                    .col(Notification::UserId)
+                    .col(Notification::ReadAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Notification::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Notification {
+    Table,
+    Id,
+    UserId,
+    Category,
+    Title,
+    Body,
+    Data,
+    ReadAt,
+    DigestedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,92 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(timestamp_null(User::PhoneVerifiedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut foreign_key = ForeignKey::create()
+            .name("fk-phone_verification_token-user_id")
+            .from(
+                PhoneVerificationToken::Table,
+                PhoneVerificationToken::UserId,
+            )
+            .to(User::Table, User::Id)
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::NoAction)
+            .to_owned();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PhoneVerificationToken::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PhoneVerificationToken::Id))
+                    .col(
+                        integer(PhoneVerificationToken::UserId)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(string_len(PhoneVerificationToken::Phone, 32).not_null())
+                    .col(string_len(PhoneVerificationToken::Token, 6).not_null())
+                    .col(
+                        integer(PhoneVerificationToken::RetryCount)
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(timestamp(PhoneVerificationToken::ExpiresAt).not_null())
+                    .col(timestamp_null(PhoneVerificationToken::CreatedAt))
+                    .foreign_key(&mut foreign_key)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(PhoneVerificationToken::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::PhoneVerifiedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PhoneVerificationToken {
+    Table,
+    Id,
+    UserId,
+    Phone,
+    Token,
+    RetryCount,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+    PhoneVerifiedAt,
+}
@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend};
+
+/// Refresh tokens and reset OTPs are now stored as HMAC-SHA256 hex digests.
+/// Existing rows hold plaintext values that can't be hashed here without the application
+/// secret, so they are discarded: users sign in again and request a new OTP.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        delete_stored_tokens(manager).await?;
+
+        // SQLite doesn't enforce VARCHAR lengths nor support altering columns
+        if manager.get_connection().get_database_backend() == DbBackend::Postgres {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(PasswordResetToken::Table)
+                        .modify_column(ColumnDef::new(PasswordResetToken::Token).string_len(64))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Digests are useless to a version that looks tokens up in plaintext
+        delete_stored_tokens(manager).await?;
+
+        if manager.get_connection().get_database_backend() == DbBackend::Postgres {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(PasswordResetToken::Table)
+                        .modify_column(ColumnDef::new(PasswordResetToken::Token).string_len(6))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+async fn delete_stored_tokens(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    manager
+        .exec_stmt(Query::delete().from_table(RefreshToken::Table).to_owned())
+        .await?;
+    manager
+        .exec_stmt(
+            Query::delete()
+                .from_table(PasswordResetToken::Table)
+                .to_owned(),
+        )
+        .await
+}
+
+#[derive(DeriveIden)]
+enum RefreshToken {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum PasswordResetToken {
+    Table,
+    Token,
+}
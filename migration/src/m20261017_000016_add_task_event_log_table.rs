@@ -0,0 +1,67 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskEventLog::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskEventLog::Id))
+                    .col(string_len(TaskEventLog::TaskId, 64).not_null())
+                    .col(string_len_null(TaskEventLog::Reference, 64))
+                    .col(string_len_null(TaskEventLog::TaskType, 64))
+                    .col(string_len(TaskEventLog::Status, 16).not_null())
+                    .col(integer(TaskEventLog::Attempt).not_null())
+                    .col(string_len_null(TaskEventLog::WorkerId, 64))
+                    .col(text_null(TaskEventLog::Error))
+                    .col(timestamp_null(TaskEventLog::CreatedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_task_event_log_task_id")
+                    .table(TaskEventLog::Table)
+                    .col(TaskEventLog::TaskId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_task_event_log_reference")
+                    .table(TaskEventLog::Table)
+                    .col(TaskEventLog::Reference)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskEventLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskEventLog {
+    Table,
+    Id,
+    TaskId,
+    Reference,
+    TaskType,
+    Status,
+    Attempt,
+    WorkerId,
+    Error,
+    CreatedAt,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut foreign_key = ForeignKey::create()
+            .name("fk-security_event-user_id")
+            .from(SecurityEvent::Table, SecurityEvent::UserId)
+            .to(User::Table, User::Id)
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::NoAction)
+            .to_owned();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SecurityEvent::Table)
+                    .if_not_exists()
+                    .col(pk_auto(SecurityEvent::Id))
+                    .col(integer(SecurityEvent::UserId).not_null())
+                    .col(string_len(SecurityEvent::Kind, 32).not_null())
+                    .col(string_len_null(SecurityEvent::DeviceInfo, 512))
+                    .col(string_len_null(SecurityEvent::IpAddress, 45))
+                    .col(string_len_null(SecurityEvent::ReportToken, 64).unique_key())
+                    .col(timestamp_null(SecurityEvent::ReportedAt))
+                    .col(timestamp_null(SecurityEvent::CreatedAt))
+                    .foreign_key(&mut foreign_key)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_security_event_user_id")
+                    .table(SecurityEvent::Table)
+                    .col(SecurityEvent::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SecurityEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SecurityEvent {
+    Table,
+    Id,
+    UserId,
+    Kind,
+    DeviceInfo,
+    IpAddress,
+    ReportToken,
+    ReportedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,58 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DeadLetter::Table)
+                    .if_not_exists()
+                    .col(pk_auto(DeadLetter::Id))
+                    .col(string_len(DeadLetter::TaskId, 64).not_null())
+                    .col(string_len_null(DeadLetter::TaskType, 64))
+                    .col(string_len(DeadLetter::Topic, 255).not_null())
+                    .col(json(DeadLetter::Event).not_null())
+                    .col(integer(DeadLetter::Attempts).not_null())
+                    .col(text(DeadLetter::Error).not_null())
+                    .col(string_len_null(DeadLetter::WorkerId, 64))
+                    .col(timestamp(DeadLetter::FailedAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_dead_letter_task_type_failed_at")
+                    .table(DeadLetter::Table)
+                    .col(DeadLetter::TaskType)
+                    .col(DeadLetter::FailedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DeadLetter::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DeadLetter {
+    Table,
+    Id,
+    TaskId,
+    TaskType,
+    Topic,
+    Event,
+    Attempts,
+    Error,
+    WorkerId,
+    FailedAt,
+}
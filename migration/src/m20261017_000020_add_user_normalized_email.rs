@@ -0,0 +1,93 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+/// Users become unique by their normalized email. Existing rows are normalized by lowercasing,
+/// the default `EMAIL_*` settings; addresses that only differ in case have to be merged or
+/// renamed before this runs.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(string(User::NormalizedEmail).not_null().default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(User::Table)
+                    .value(User::NormalizedEmail, Func::lower(Expr::col(User::Email)))
+                    .to_owned(),
+            )
+            .await?;
+
+        let connection = manager.get_connection();
+        let duplicates = connection
+            .query_all_raw(
+                connection.get_database_backend().build(
+                    Query::select()
+                        .column(User::NormalizedEmail)
+                        .from(User::Table)
+                        .group_by_col(User::NormalizedEmail)
+                        .and_having(Expr::col(User::Id).count().gt(1)),
+                ),
+            )
+            .await?
+            .iter()
+            .map(|row| row.try_get::<String>("", "normalized_email"))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !duplicates.is_empty() {
+            return Err(DbErr::Migration(format!(
+                "Users share an email differing only in case, resolve them first: {}",
+                duplicates.join(", ")
+            )));
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_normalized_email")
+                    .table(User::Table)
+                    .col(User::NormalizedEmail)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_user_normalized_email")
+                    .table(User::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::NormalizedEmail)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+    Email,
+    NormalizedEmail,
+}
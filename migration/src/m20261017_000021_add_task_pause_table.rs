@@ -0,0 +1,39 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskPause::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskPause::Id))
+                    .col(string_len(TaskPause::TaskType, 64).not_null().unique_key())
+                    .col(string_len_null(TaskPause::Reason, 255))
+                    .col(integer_null(TaskPause::PausedByUserId))
+                    .col(timestamp(TaskPause::PausedAt).not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskPause::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaskPause {
+    Table,
+    Id,
+    TaskType,
+    Reason,
+    PausedByUserId,
+    PausedAt,
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BroadcastEvent::Table)
+                    .if_not_exists()
+                    .col(pk_auto(BroadcastEvent::Id))
+                    .col(string_len(BroadcastEvent::EventType, 64).not_null())
+                    .col(string_len_null(BroadcastEvent::TaskId, 64))
+                    .col(integer_null(BroadcastEvent::UserId))
+                    .col(json(BroadcastEvent::Data).not_null())
+                    .col(timestamp(BroadcastEvent::PublishedAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_broadcast_event_task_id")
+                    .table(BroadcastEvent::Table)
+                    .col(BroadcastEvent::TaskId)
+                    .col(BroadcastEvent::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_broadcast_event_user_id")
+                    .table(BroadcastEvent::Table)
+                    .col(BroadcastEvent::UserId)
+                    .col(BroadcastEvent::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_broadcast_event_published_at")
+                    .table(BroadcastEvent::Table)
+                    .col(BroadcastEvent::PublishedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BroadcastEvent::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum BroadcastEvent {
+    Table,
+    Id,
+    EventType,
+    TaskId,
+    UserId,
+    Data,
+    PublishedAt,
+}
@@ -0,0 +1,83 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(File::Table)
+                    .add_column(string_len_null(File::ModerationStatus, 16))
+                    .add_column(string_len_null(File::RejectionReason, 255))
+                    .add_column(timestamp_null(File::ModeratedAt))
+                    .add_column(integer_null(File::ModeratedUserId))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(integer_null(User::AvatarFileId))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Avatars uploaded before moderation existed stay in use
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"UPDATE "user" SET avatar_file_id = (
+                    SELECT MAX(file.id) FROM file
+                    WHERE file.user_id = "user".id
+                        AND file.status = 'available'
+                        AND file.key LIKE 'avatars/%'
+                )"#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::AvatarFileId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(File::Table)
+                    .drop_column(File::ModerationStatus)
+                    .drop_column(File::RejectionReason)
+                    .drop_column(File::ModeratedAt)
+                    .drop_column(File::ModeratedUserId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum File {
+    Table,
+    ModerationStatus,
+    RejectionReason,
+    ModeratedAt,
+    ModeratedUserId,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    AvatarFileId,
+}
@@ -0,0 +1,79 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut foreign_key = ForeignKey::create()
+            .name("fk-oauth_account-user_id")
+            .from(OAuthAccount::Table, OAuthAccount::UserId)
+            .to(User::Table, User::Id)
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::NoAction)
+            .to_owned();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OAuthAccount::Table)
+                    .if_not_exists()
+                    .col(pk_auto(OAuthAccount::Id))
+                    .col(integer(OAuthAccount::UserId).not_null())
+                    .col(string_len(OAuthAccount::Provider, 16).not_null())
+                    .col(string_len(OAuthAccount::Subject, 255).not_null())
+                    .col(string_len_null(OAuthAccount::Email, 255))
+                    .col(timestamp_null(OAuthAccount::CreatedAt))
+                    .foreign_key(&mut foreign_key)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("ux_oauth_account_provider_subject")
+                    .table(OAuthAccount::Table)
+                    .col(OAuthAccount::Provider)
+                    .col(OAuthAccount::Subject)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_oauth_account_user_id")
+                    .table(OAuthAccount::Table)
+                    .col(OAuthAccount::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OAuthAccount::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OAuthAccount {
+    #[sea_orm(iden = "oauth_account")]
+    Table,
+    Id,
+    UserId,
+    Provider,
+    Subject,
+    Email,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
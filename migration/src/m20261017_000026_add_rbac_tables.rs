@@ -0,0 +1,173 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Named permissions, roles granting them and the roles of each user. The join table of users
+/// and roles can't be called `user_role`, which on PostgreSQL is the type of `user.role`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Permissions the routes and use cases check, with what they allow
+const PERMISSIONS: &[(&str, &str)] = &[
+    ("user.list", "Search users"),
+    ("user.create", "Create users"),
+    ("user.read", "Read any user"),
+    ("user.update", "Update any user"),
+    ("user.delete", "Delete any user"),
+    ("user.bulk", "Apply bulk user operations"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Role::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Role::Id))
+                    .col(string_len_uniq(Role::Name, 64).not_null())
+                    .col(string_len_null(Role::Description, 255))
+                    .col(timestamp_null(Role::CreatedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Permission::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Permission::Id))
+                    .col(string_len_uniq(Permission::Name, 64).not_null())
+                    .col(string_len_null(Permission::Description, 255))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RolePermission::Table)
+                    .if_not_exists()
+                    .col(integer(RolePermission::RoleId).not_null())
+                    .col(integer(RolePermission::PermissionId).not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(RolePermission::RoleId)
+                            .col(RolePermission::PermissionId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-role_permission-role_id")
+                            .from(RolePermission::Table, RolePermission::RoleId)
+                            .to(Role::Table, Role::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-role_permission-permission_id")
+                            .from(RolePermission::Table, RolePermission::PermissionId)
+                            .to(Permission::Table, Permission::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserRoleAssignment::Table)
+                    .if_not_exists()
+                    .col(integer(UserRoleAssignment::UserId).not_null())
+                    .col(integer(UserRoleAssignment::RoleId).not_null())
+                    .col(timestamp_null(UserRoleAssignment::CreatedAt))
+                    .primary_key(
+                        Index::create()
+                            .col(UserRoleAssignment::UserId)
+                            .col(UserRoleAssignment::RoleId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-user_role_assignment-user_id")
+                            .from(UserRoleAssignment::Table, UserRoleAssignment::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-user_role_assignment-role_id")
+                            .from(UserRoleAssignment::Table, UserRoleAssignment::RoleId)
+                            .to(Role::Table, Role::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut insert = Query::insert()
+            .into_table(Permission::Table)
+            .columns([Permission::Name, Permission::Description])
+            .to_owned();
+        for (name, description) in PERMISSIONS {
+            insert.values_panic([(*name).into(), (*description).into()]);
+        }
+        manager.exec_stmt(insert).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserRoleAssignment::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(RolePermission::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Permission::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Role::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Role {
+    Table,
+    Id,
+    Name,
+    Description,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Permission {
+    Table,
+    Id,
+    Name,
+    Description,
+}
+
+#[derive(DeriveIden)]
+enum RolePermission {
+    Table,
+    RoleId,
+    PermissionId,
+}
+
+#[derive(DeriveIden)]
+enum UserRoleAssignment {
+    Table,
+    UserId,
+    RoleId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
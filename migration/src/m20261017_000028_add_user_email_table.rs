@@ -0,0 +1,91 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut foreign_key = ForeignKey::create()
+            .name("fk-user_email-user_id")
+            .from(UserEmail::Table, UserEmail::UserId)
+            .to(User::Table, User::Id)
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::NoAction)
+            .to_owned();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserEmail::Table)
+                    .if_not_exists()
+                    .col(pk_auto(UserEmail::Id))
+                    .col(integer(UserEmail::UserId).not_null())
+                    .col(string(UserEmail::Email).not_null())
+                    .col(string(UserEmail::NormalizedEmail).not_null())
+                    .col(timestamp_null(UserEmail::VerifiedAt))
+                    .col(
+                        boolean(UserEmail::ReceivesNotifications)
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(string_len_null(UserEmail::Token, 6))
+                    .col(integer(UserEmail::RetryCount).not_null().default(0))
+                    .col(timestamp_null(UserEmail::TokenExpiresAt))
+                    .col(timestamp_null(UserEmail::CreatedAt))
+                    .col(timestamp_null(UserEmail::UpdatedAt))
+                    .foreign_key(&mut foreign_key)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("ux_user_email_normalized_email")
+                    .table(UserEmail::Table)
+                    .col(UserEmail::NormalizedEmail)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_email_user_id")
+                    .table(UserEmail::Table)
+                    .col(UserEmail::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserEmail::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserEmail {
+    Table,
+    Id,
+    UserId,
+    Email,
+    NormalizedEmail,
+    VerifiedAt,
+    ReceivesNotifications,
+    Token,
+    RetryCount,
+    TokenExpiresAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,105 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Accounts record when their email was verified. Existing accounts are marked verified, so
+/// requiring verification to sign in doesn't lock them out.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(timestamp_null(User::VerifiedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(User::Table)
+                    .value(User::VerifiedAt, Expr::current_timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut foreign_key = ForeignKey::create()
+            .name("fk-email_verification_token-user_id")
+            .from(
+                EmailVerificationToken::Table,
+                EmailVerificationToken::UserId,
+            )
+            .to(User::Table, User::Id)
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::NoAction)
+            .to_owned();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(EmailVerificationToken::Table)
+                    .if_not_exists()
+                    .col(pk_auto(EmailVerificationToken::Id))
+                    .col(integer(EmailVerificationToken::UserId).not_null())
+                    .col(
+                        string(EmailVerificationToken::Token)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(timestamp(EmailVerificationToken::ExpiresAt).not_null())
+                    .col(timestamp_null(EmailVerificationToken::CreatedAt))
+                    .foreign_key(&mut foreign_key)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_email_verification_token_user_id")
+                    .table(EmailVerificationToken::Table)
+                    .col(EmailVerificationToken::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(EmailVerificationToken::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::VerifiedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EmailVerificationToken {
+    Table,
+    Id,
+    UserId,
+    Token,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+    VerifiedAt,
+}
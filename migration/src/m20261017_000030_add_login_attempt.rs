@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Failed sign-ins, counted against the account and the client IP to lock them out
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut foreign_key = ForeignKey::create()
+            .name("fk-login_attempt-user_id")
+            .from(LoginAttempt::Table, LoginAttempt::UserId)
+            .to(User::Table, User::Id)
+            .on_delete(ForeignKeyAction::Cascade)
+            .on_update(ForeignKeyAction::NoAction)
+            .to_owned();
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LoginAttempt::Table)
+                    .if_not_exists()
+                    .col(pk_auto(LoginAttempt::Id))
+                    .col(integer_null(LoginAttempt::UserId))
+                    .col(string_null(LoginAttempt::IpAddress))
+                    .col(timestamp(LoginAttempt::CreatedAt).not_null())
+                    .foreign_key(&mut foreign_key)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_login_attempt_user_id_created_at")
+                    .table(LoginAttempt::Table)
+                    .col(LoginAttempt::UserId)
+                    .col(LoginAttempt::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_login_attempt_ip_address_created_at")
+                    .table(LoginAttempt::Table)
+                    .col(LoginAttempt::IpAddress)
+                    .col(LoginAttempt::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LoginAttempt::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LoginAttempt {
+    Table,
+    Id,
+    UserId,
+    IpAddress,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,95 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Workers report they are alive and lease the tasks they run, so the tasks of a worker that
+/// stopped mid-processing can be enqueued again
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WorkerHeartbeat::Table)
+                    .if_not_exists()
+                    .col(pk_auto(WorkerHeartbeat::Id))
+                    .col(
+                        string_len(WorkerHeartbeat::WorkerId, 64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(timestamp(WorkerHeartbeat::StartedAt).not_null())
+                    .col(timestamp(WorkerHeartbeat::LastSeenAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TaskLease::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TaskLease::Id))
+                    .col(string_len(TaskLease::TaskId, 64).not_null().unique_key())
+                    .col(string_len_null(TaskLease::TaskType, 64))
+                    .col(string_len(TaskLease::WorkerId, 64).not_null())
+                    .col(json(TaskLease::Event).not_null())
+                    .col(timestamp(TaskLease::LeasedUntil).not_null())
+                    .col(timestamp(TaskLease::CreatedAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_task_lease_worker_id")
+                    .table(TaskLease::Table)
+                    .col(TaskLease::WorkerId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_task_lease_leased_until")
+                    .table(TaskLease::Table)
+                    .col(TaskLease::LeasedUntil)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TaskLease::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(WorkerHeartbeat::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WorkerHeartbeat {
+    Table,
+    Id,
+    WorkerId,
+    StartedAt,
+    LastSeenAt,
+}
+
+#[derive(DeriveIden)]
+enum TaskLease {
+    Table,
+    Id,
+    TaskId,
+    TaskType,
+    WorkerId,
+    Event,
+    LeasedUntil,
+    CreatedAt,
+}
@@ -0,0 +1,271 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Clients of the OAuth2 provider, the authorization codes and access tokens issued to them,
+/// and the scopes each user consented to. Codes, tokens and client secrets are stored hashed.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Permission of the admin routes registering clients
+const MANAGE_CLIENTS: (&str, &str) = ("oauth_client.manage", "Register and delete OAuth2 clients");
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OAuthClient::Table)
+                    .if_not_exists()
+                    .col(pk_auto(OAuthClient::Id))
+                    .col(string_len_uniq(OAuthClient::ClientId, 64).not_null())
+                    .col(string_len(OAuthClient::ClientSecret, 255).not_null())
+                    .col(string_len(OAuthClient::Name, 255).not_null())
+                    .col(text(OAuthClient::RedirectUris).not_null())
+                    .col(string_len(OAuthClient::Scopes, 255).not_null())
+                    .col(string_len(OAuthClient::GrantTypes, 64).not_null())
+                    .col(boolean(OAuthClient::Trusted).not_null().default(false))
+                    .col(integer_null(OAuthClient::CreatedBy))
+                    .col(timestamp_null(OAuthClient::CreatedAt))
+                    .col(timestamp_null(OAuthClient::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-oauth_client-created_by")
+                            .from(OAuthClient::Table, OAuthClient::CreatedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OAuthAuthorizationCode::Table)
+                    .if_not_exists()
+                    .col(pk_auto(OAuthAuthorizationCode::Id))
+                    .col(string_len_uniq(OAuthAuthorizationCode::Code, 255).not_null())
+                    .col(integer(OAuthAuthorizationCode::ClientId).not_null())
+                    .col(integer(OAuthAuthorizationCode::UserId).not_null())
+                    .col(text(OAuthAuthorizationCode::RedirectUri).not_null())
+                    .col(string_len(OAuthAuthorizationCode::Scope, 255).not_null())
+                    .col(string_len_null(OAuthAuthorizationCode::CodeChallenge, 128))
+                    .col(timestamp(OAuthAuthorizationCode::ExpiresAt).not_null())
+                    .col(timestamp_null(OAuthAuthorizationCode::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-oauth_authorization_code-client_id")
+                            .from(
+                                OAuthAuthorizationCode::Table,
+                                OAuthAuthorizationCode::ClientId,
+                            )
+                            .to(OAuthClient::Table, OAuthClient::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-oauth_authorization_code-user_id")
+                            .from(
+                                OAuthAuthorizationCode::Table,
+                                OAuthAuthorizationCode::UserId,
+                            )
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OAuthAccessToken::Table)
+                    .if_not_exists()
+                    .col(pk_auto(OAuthAccessToken::Id))
+                    .col(string_len_uniq(OAuthAccessToken::Token, 255).not_null())
+                    .col(integer(OAuthAccessToken::ClientId).not_null())
+                    .col(integer_null(OAuthAccessToken::UserId))
+                    .col(string_len(OAuthAccessToken::Scope, 255).not_null())
+                    .col(timestamp(OAuthAccessToken::ExpiresAt).not_null())
+                    .col(timestamp_null(OAuthAccessToken::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-oauth_access_token-client_id")
+                            .from(OAuthAccessToken::Table, OAuthAccessToken::ClientId)
+                            .to(OAuthClient::Table, OAuthClient::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-oauth_access_token-user_id")
+                            .from(OAuthAccessToken::Table, OAuthAccessToken::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_oauth_access_token_expires_at")
+                    .table(OAuthAccessToken::Table)
+                    .col(OAuthAccessToken::ExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OAuthConsent::Table)
+                    .if_not_exists()
+                    .col(pk_auto(OAuthConsent::Id))
+                    .col(integer(OAuthConsent::UserId).not_null())
+                    .col(integer(OAuthConsent::ClientId).not_null())
+                    .col(string_len(OAuthConsent::Scope, 255).not_null())
+                    .col(timestamp_null(OAuthConsent::CreatedAt))
+                    .col(timestamp_null(OAuthConsent::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-oauth_consent-user_id")
+                            .from(OAuthConsent::Table, OAuthConsent::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-oauth_consent-client_id")
+                            .from(OAuthConsent::Table, OAuthConsent::ClientId)
+                            .to(OAuthClient::Table, OAuthClient::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("ux_oauth_consent_user_id_client_id")
+                    .table(OAuthConsent::Table)
+                    .col(OAuthConsent::UserId)
+                    .col(OAuthConsent::ClientId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        let (name, description) = MANAGE_CLIENTS;
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Permission::Table)
+                    .columns([Permission::Name, Permission::Description])
+                    .values_panic([name.into(), description.into()])
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Permission::Table)
+                    .and_where(Expr::col(Permission::Name).eq(MANAGE_CLIENTS.0))
+                    .to_owned(),
+            )
+            .await?;
+
+        for table in [
+            OAuthConsent::Table.into_iden(),
+            OAuthAccessToken::Table.into_iden(),
+            OAuthAuthorizationCode::Table.into_iden(),
+            OAuthClient::Table.into_iden(),
+        ] {
+            manager
+                .drop_table(Table::drop().table(table).to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum OAuthClient {
+    #[sea_orm(iden = "oauth_client")]
+    Table,
+    Id,
+    ClientId,
+    ClientSecret,
+    Name,
+    RedirectUris,
+    Scopes,
+    GrantTypes,
+    Trusted,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum OAuthAuthorizationCode {
+    #[sea_orm(iden = "oauth_authorization_code")]
+    Table,
+    Id,
+    Code,
+    ClientId,
+    UserId,
+    RedirectUri,
+    Scope,
+    CodeChallenge,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum OAuthAccessToken {
+    #[sea_orm(iden = "oauth_access_token")]
+    Table,
+    Id,
+    Token,
+    ClientId,
+    UserId,
+    Scope,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum OAuthConsent {
+    #[sea_orm(iden = "oauth_consent")]
+    Table,
+    Id,
+    UserId,
+    ClientId,
+    Scope,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Permission {
+    Table,
+    Name,
+    Description,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
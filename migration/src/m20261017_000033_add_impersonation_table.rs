@@ -0,0 +1,78 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Admins acting as another user, each with the id of the access token it was issued, so
+/// ending an impersonation revokes its token before it expires
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Impersonation::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Impersonation::Id))
+                    .col(integer(Impersonation::AdminId).not_null())
+                    .col(integer(Impersonation::UserId).not_null())
+                    .col(string_len_uniq(Impersonation::TokenId, 64).not_null())
+                    .col(timestamp(Impersonation::ExpiresAt).not_null())
+                    .col(timestamp_null(Impersonation::EndedAt))
+                    .col(timestamp_null(Impersonation::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-impersonation-admin_id")
+                            .from(Impersonation::Table, Impersonation::AdminId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-impersonation-user_id")
+                            .from(Impersonation::Table, Impersonation::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_impersonation_admin_id_user_id")
+                    .table(Impersonation::Table)
+                    .col(Impersonation::AdminId)
+                    .col(Impersonation::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Impersonation::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Impersonation {
+    Table,
+    Id,
+    AdminId,
+    UserId,
+    TokenId,
+    ExpiresAt,
+    EndedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Tasks are written to the outbox in the transaction that enqueues them and published once
+/// it commits, so a rollback can't publish a task and a broker outage can't lose one
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Outbox::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Outbox::Id))
+                    .col(
+                        string_len(Outbox::IdempotencyKey, 64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(string_len_null(Outbox::Destination, 128))
+                    .col(text(Outbox::Payload).not_null())
+                    .col(integer(Outbox::Attempts).not_null().default(0))
+                    .col(text_null(Outbox::LastError))
+                    .col(timestamp(Outbox::NextAttemptAt).not_null())
+                    .col(timestamp_null(Outbox::SentAt))
+                    .col(timestamp(Outbox::CreatedAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_outbox_sent_at_next_attempt_at")
+                    .table(Outbox::Table)
+                    .col(Outbox::SentAt)
+                    .col(Outbox::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Outbox::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Outbox {
+    Table,
+    Id,
+    IdempotencyKey,
+    Destination,
+    Payload,
+    Attempts,
+    LastError,
+    NextAttemptAt,
+    SentAt,
+    CreatedAt,
+}
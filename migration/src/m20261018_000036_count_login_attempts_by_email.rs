@@ -0,0 +1,98 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Failed sign-ins are counted against the normalized email they were for, whether or not it
+/// matches an account, so a lockout doesn't tell which emails have one
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_login_attempt_user_id_created_at")
+                    .table(LoginAttempt::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LoginAttempt::Table)
+                    .drop_foreign_key("fk-login_attempt-user_id")
+                    .drop_column(LoginAttempt::UserId)
+                    .add_column(string_null(LoginAttempt::Email))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_login_attempt_email_created_at")
+                    .table(LoginAttempt::Table)
+                    .col(LoginAttempt::Email)
+                    .col(LoginAttempt::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_login_attempt_email_created_at")
+                    .table(LoginAttempt::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LoginAttempt::Table)
+                    .drop_column(LoginAttempt::Email)
+                    .add_column(integer_null(LoginAttempt::UserId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-login_attempt-user_id")
+                            .from_tbl(LoginAttempt::Table)
+                            .from_col(LoginAttempt::UserId)
+                            .to_tbl(User::Table)
+                            .to_col(User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_login_attempt_user_id_created_at")
+                    .table(LoginAttempt::Table)
+                    .col(LoginAttempt::UserId)
+                    .col(LoginAttempt::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LoginAttempt {
+    Table,
+    UserId,
+    Email,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+/// Permission of starting an impersonation, which admins hold like every other
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const IMPERSONATE: (&str, &str) = ("user.impersonate", "Act as another user");
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let (name, description) = IMPERSONATE;
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Permission::Table)
+                    .columns([Permission::Name, Permission::Description])
+                    .values_panic([name.into(), description.into()])
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Permission::Table)
+                    .and_where(Expr::col(Permission::Name).eq(IMPERSONATE.0))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Permission {
+    Table,
+    Name,
+    Description,
+}
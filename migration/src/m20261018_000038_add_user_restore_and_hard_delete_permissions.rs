@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+/// Permissions of the admin routes restoring and hard deleting users
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const PERMISSIONS: [(&str, &str); 2] = [
+    ("user.restore", "Bring back soft deleted users"),
+    ("user.hard_delete", "Remove users for good"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut insert = Query::insert()
+            .into_table(Permission::Table)
+            .columns([Permission::Name, Permission::Description])
+            .to_owned();
+        for (name, description) in PERMISSIONS {
+            insert.values_panic([name.into(), description.into()]);
+        }
+        manager.exec_stmt(insert).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Permission::Table)
+                    .and_where(Expr::col(Permission::Name).is_in(PERMISSIONS.map(|(name, _)| name)))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Permission {
+    Table,
+    Name,
+    Description,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+/// Exchanged authorization codes are kept as used until they expire, and access tokens
+/// remember the code they were issued for, so a replayed code revokes them
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OAuthAuthorizationCode::Table)
+                    .add_column(timestamp_null(OAuthAuthorizationCode::UsedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OAuthAccessToken::Table)
+                    .add_column(integer_null(OAuthAccessToken::AuthorizationCodeId))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_oauth_access_token_authorization_code_id")
+                    .table(OAuthAccessToken::Table)
+                    .col(OAuthAccessToken::AuthorizationCodeId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_oauth_access_token_authorization_code_id")
+                    .table(OAuthAccessToken::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OAuthAccessToken::Table)
+                    .drop_column(OAuthAccessToken::AuthorizationCodeId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OAuthAuthorizationCode::Table)
+                    .drop_column(OAuthAuthorizationCode::UsedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OAuthAuthorizationCode {
+    #[sea_orm(iden = "oauth_authorization_code")]
+    Table,
+    UsedAt,
+}
+
+#[derive(DeriveIden)]
+enum OAuthAccessToken {
+    #[sea_orm(iden = "oauth_access_token")]
+    Table,
+    AuthorizationCodeId,
+}
@@ -0,0 +1,65 @@
+//! Cost of preparing a task event for the broker, comparing the former path (serialize to a
+//! `String`, then parse it again in the producer to log the event id) with `EncodedMessage`.
+//! Run with `cargo bench -p pkg --bench publish`.
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use pkg::messaging::{EncodedMessage, TaskEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const ITERATIONS: u32 = 20_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SendEmail {
+    to: String,
+    subject: String,
+    html_body: String,
+}
+
+fn sample_event() -> TaskEvent<SendEmail> {
+    TaskEvent::new(SendEmail {
+        to: "user@example.com".to_string(),
+        subject: "Welcome".to_string(),
+        html_body: "<p>Hello</p>".repeat(200),
+    })
+}
+
+fn measure(name: &str, mut publish: impl FnMut()) -> Duration {
+    // Warm up allocator and caches
+    for _ in 0..ITERATIONS / 10 {
+        publish();
+    }
+
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        publish();
+    }
+    let per_call = started.elapsed() / ITERATIONS;
+    println!("{:<28} {:>10.2?} per event", name, per_call);
+    per_call
+}
+
+fn main() {
+    let event = sample_event();
+
+    let string_path = measure("to_string + re-parse id", || {
+        let json = serde_json::to_string(black_box(&event)).unwrap();
+        let id = serde_json::from_str::<Value>(&json)
+            .ok()
+            .and_then(|value| value.get("id").and_then(Value::as_str).map(str::to_owned));
+        black_box((json.into_bytes(), id));
+    });
+
+    let encoded_path = measure("EncodedMessage::from_event", || {
+        let message = EncodedMessage::from_event(black_box(&event)).unwrap();
+        black_box((message.payload().clone(), message.id()));
+    });
+
+    println!(
+        "speedup: {:.2}x",
+        string_path.as_secs_f64() / encoded_path.as_secs_f64()
+    );
+}
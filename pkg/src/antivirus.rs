@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+const CLAMAV_CHUNK_SIZE: usize = 8192;
+const CLAMAV_DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Result of scanning a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    Clean,
+    Infected { signature: String },
+}
+
+impl ScanVerdict {
+    pub fn is_clean(&self) -> bool {
+        matches!(self, ScanVerdict::Clean)
+    }
+}
+
+/// Pluggable malware scanner for uploaded content
+#[async_trait]
+pub trait VirusScanner: Send + Sync {
+    async fn scan(&self, data: &[u8]) -> anyhow::Result<ScanVerdict>;
+}
+
+/// ClamAV scanner speaking the clamd `INSTREAM` protocol over TCP
+#[derive(Debug, Clone)]
+pub struct ClamAvScanner {
+    address: String,
+    timeout: Duration,
+}
+
+impl ClamAvScanner {
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            timeout: CLAMAV_DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    async fn scan_stream(&self, data: &[u8]) -> anyhow::Result<String> {
+        let mut stream = TcpStream::connect(&self.address)
+            .await
+            .with_context(|| format!("Failed to connect to clamd at {}", self.address))?;
+
+        stream.write_all(b"zINSTREAM\0").await?;
+        for chunk in data.chunks(CLAMAV_CHUNK_SIZE) {
+            stream
+                .write_all(&(chunk.len() as u32).to_be_bytes())
+                .await?;
+            stream.write_all(chunk).await?;
+        }
+        stream.write_all(&0u32.to_be_bytes()).await?;
+        stream.flush().await?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await?;
+
+        Ok(String::from_utf8_lossy(&response)
+            .trim_end_matches('\0')
+            .trim()
+            .to_string())
+    }
+}
+
+#[async_trait]
+impl VirusScanner for ClamAvScanner {
+    async fn scan(&self, data: &[u8]) -> anyhow::Result<ScanVerdict> {
+        let response = timeout(self.timeout, self.scan_stream(data))
+            .await
+            .map_err(|_| anyhow::anyhow!("clamd scan timed out after {:?}", self.timeout))??;
+
+        parse_clamd_response(&response)
+    }
+}
+
+/// Parse a clamd reply such as `stream: OK` or `stream: Eicar-Signature FOUND`
+pub fn parse_clamd_response(response: &str) -> anyhow::Result<ScanVerdict> {
+    let result = response
+        .split_once(": ")
+        .map(|(_, result)| result)
+        .unwrap_or(response);
+
+    if result == "OK" {
+        return Ok(ScanVerdict::Clean);
+    }
+
+    if let Some(signature) = result.strip_suffix(" FOUND") {
+        return Ok(ScanVerdict::Infected {
+            signature: signature.to_string(),
+        });
+    }
+
+    Err(anyhow::anyhow!("Unexpected clamd response: {}", response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    async fn spawn_fake_clamd(reply: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut command = [0u8; 10];
+            socket.read_exact(&mut command).await.unwrap();
+            assert_eq!(&command, b"zINSTREAM\0");
+
+            loop {
+                let mut length = [0u8; 4];
+                socket.read_exact(&mut length).await.unwrap();
+                let length = u32::from_be_bytes(length) as usize;
+                if length == 0 {
+                    break;
+                }
+                let mut chunk = vec![0u8; length];
+                socket.read_exact(&mut chunk).await.unwrap();
+            }
+
+            socket.write_all(reply.as_bytes()).await.unwrap();
+        });
+
+        address
+    }
+
+    #[test]
+    fn parses_clean_response() {
+        assert_eq!(
+            parse_clamd_response("stream: OK").unwrap(),
+            ScanVerdict::Clean
+        );
+    }
+
+    #[test]
+    fn parses_infected_response() {
+        assert_eq!(
+            parse_clamd_response("stream: Eicar-Test-Signature FOUND").unwrap(),
+            ScanVerdict::Infected {
+                signature: "Eicar-Test-Signature".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_error_response() {
+        assert!(parse_clamd_response("INSTREAM size limit exceeded. ERROR").is_err());
+    }
+
+    #[tokio::test]
+    async fn scans_over_tcp() {
+        let address = spawn_fake_clamd("stream: Win.Test.EICAR_HDB-1 FOUND\0").await;
+        let scanner = ClamAvScanner::new(address);
+
+        let verdict = scanner.scan(&vec![7u8; CLAMAV_CHUNK_SIZE * 2 + 1]).await;
+
+        assert_eq!(
+            verdict.unwrap(),
+            ScanVerdict::Infected {
+                signature: "Win.Test.EICAR_HDB-1".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_when_clamd_unreachable() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+
+        let scanner = ClamAvScanner::new(address).with_timeout(Duration::from_secs(1));
+
+        assert!(scanner.scan(b"data").await.is_err());
+    }
+}
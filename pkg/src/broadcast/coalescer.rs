@@ -0,0 +1,381 @@
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{
+    sync::Mutex,
+    time::{Instant, sleep_until},
+};
+
+use super::websocket::BroadcastMessage;
+use crate::messaging::{EncodedMessage, MessageProducer};
+
+const TERMINAL_STATUSES: [&str; 4] = ["completed", "failed", "error", "cancelled"];
+const TERMINAL_EVENT_SUFFIXES: [&str; 4] = ["_complete", "_completed", "_failed", "_error"];
+
+/// Receives the messages a coalescer lets through
+pub type BroadcastSink = Arc<dyn Fn(BroadcastMessage) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Whether `message` ends its task, so it must never be dropped or delayed
+pub fn is_terminal(message: &BroadcastMessage) -> bool {
+    let status = message.data.get("status").and_then(|value| value.as_str());
+    status.is_some_and(|status| TERMINAL_STATUSES.contains(&status))
+        || TERMINAL_EVENT_SUFFIXES
+            .iter()
+            .any(|suffix| message.event_type.ends_with(suffix))
+}
+
+fn task_id(message: &BroadcastMessage) -> Option<&str> {
+    message.data.get("task_id").and_then(|value| value.as_str())
+}
+
+#[derive(Default)]
+struct TaskState {
+    /// Latest progress held back until the window ends
+    pending: Option<BroadcastMessage>,
+    /// End of the current window, during which further progress is held back
+    window_end: Option<Instant>,
+}
+
+/// Throttles task progress: within each `window`, only the first and the latest progress
+/// message of a task are delivered. Terminal events are delivered right away and drop any
+/// progress still held back, so nothing arrives after them. Messages without a `task_id`
+/// (e.g. notifications) pass through untouched.
+#[derive(Clone)]
+pub struct BroadcastCoalescer {
+    window: Duration,
+    sink: BroadcastSink,
+    tasks: Arc<Mutex<HashMap<String, TaskState>>>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl BroadcastCoalescer {
+    pub fn new(window: Duration, sink: BroadcastSink) -> Self {
+        Self {
+            window,
+            sink,
+            tasks: Arc::new(Mutex::new(HashMap::new())),
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Progress messages replaced by a later one before they were delivered
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    pub async fn submit(&self, message: BroadcastMessage) {
+        let Some(task_id) = task_id(&message).map(str::to_string) else {
+            (self.sink)(message).await;
+            return;
+        };
+
+        // Delivering under the lock keeps each task's messages in order
+        let mut tasks = self.tasks.lock().await;
+
+        if is_terminal(&message) {
+            if let Some(state) = tasks.remove(&task_id)
+                && state.pending.is_some()
+            {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            (self.sink)(message).await;
+            return;
+        }
+
+        let state = tasks.entry(task_id.clone()).or_default();
+        if state.window_end.is_some() {
+            if state.pending.replace(message).is_some() {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            return;
+        }
+
+        let window_end = Instant::now() + self.window;
+        state.window_end = Some(window_end);
+        (self.sink)(message).await;
+        self.schedule_flush(task_id, window_end);
+    }
+
+    fn schedule_flush(&self, task_id: String, at: Instant) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            sleep_until(at).await;
+            this.flush(task_id).await;
+        });
+    }
+
+    /// End the window of `task_id`, delivering the progress held back during it
+    async fn flush(&self, task_id: String) {
+        let mut tasks = self.tasks.lock().await;
+        let Some(state) = tasks.get_mut(&task_id) else {
+            // A terminal event closed the task meanwhile
+            return;
+        };
+
+        match state.pending.take() {
+            Some(message) => {
+                let window_end = Instant::now() + self.window;
+                state.window_end = Some(window_end);
+                (self.sink)(message).await;
+                self.schedule_flush(task_id, window_end);
+            }
+            None => {
+                tasks.remove(&task_id);
+            }
+        }
+    }
+
+    /// Deliver the progress held back for every task now, ending their windows, e.g. before
+    /// the WebSockets it is meant for are closed
+    pub async fn flush_all(&self) {
+        let mut tasks = self.tasks.lock().await;
+        for (_, state) in tasks.drain() {
+            if let Some(message) = state.pending {
+                (self.sink)(message).await;
+            }
+        }
+    }
+}
+
+/// Producer that coalesces progress published to `destination` before it reaches the
+/// broker; everything else is passed to `inner` unchanged.
+/// Coalesced messages are published in the background, so their failures are only logged.
+pub struct CoalescingProducer {
+    inner: Arc<Box<dyn MessageProducer>>,
+    destination: String,
+    coalescer: BroadcastCoalescer,
+}
+
+impl CoalescingProducer {
+    pub fn new(inner: Arc<Box<dyn MessageProducer>>, destination: &str, window: Duration) -> Self {
+        let publisher = inner.clone();
+        let target = destination.to_string();
+        let sink: BroadcastSink = Arc::new(move |message: BroadcastMessage| {
+            let publisher = publisher.clone();
+            let target = target.clone();
+            Box::pin(async move {
+                let result = match EncodedMessage::encode(&message) {
+                    Ok(encoded) => publisher.publish(&encoded, Some(&target)).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = result {
+                    tracing::error!("Failed to publish coalesced broadcast: {:?}", e);
+                }
+            })
+        });
+
+        Self {
+            inner,
+            destination: destination.to_string(),
+            coalescer: BroadcastCoalescer::new(window, sink),
+        }
+    }
+}
+
+#[async_trait]
+impl MessageProducer for CoalescingProducer {
+    async fn publish(
+        &self,
+        message: &EncodedMessage,
+        destination: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if destination != Some(self.destination.as_str()) {
+            return self.inner.publish(message, destination).await;
+        }
+
+        match serde_json::from_slice::<BroadcastMessage>(message.payload()) {
+            Ok(broadcast) => {
+                self.coalescer.submit(broadcast).await;
+                Ok(())
+            }
+            Err(_) => self.inner.publish(message, destination).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use std::sync::Mutex as StdMutex;
+
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(100);
+
+    fn recording_sink() -> (BroadcastSink, Arc<StdMutex<Vec<BroadcastMessage>>>) {
+        let delivered = Arc::new(StdMutex::new(Vec::new()));
+        let recorded = delivered.clone();
+        let sink: BroadcastSink = Arc::new(move |message| {
+            recorded.lock().unwrap().push(message);
+            Box::pin(async {})
+        });
+        (sink, delivered)
+    }
+
+    fn progress(task_id: &str, progress: u8) -> BroadcastMessage {
+        BroadcastMessage {
+            event_type: "avatar_upload_progress".to_string(),
+            data: json!({ "task_id": task_id, "progress": progress, "status": "processing" }),
+        }
+    }
+
+    fn progress_values(messages: &[BroadcastMessage]) -> Vec<u64> {
+        messages
+            .iter()
+            .map(|message| message.data["progress"].as_u64().unwrap())
+            .collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn delivers_first_and_latest_progress_per_window() {
+        let (sink, delivered) = recording_sink();
+        let coalescer = BroadcastCoalescer::new(WINDOW, sink);
+
+        for value in 1..=50 {
+            coalescer.submit(progress("task", value)).await;
+        }
+        tokio::time::sleep(WINDOW * 3).await;
+
+        assert_eq!(progress_values(&delivered.lock().unwrap()), vec![1, 50]);
+        assert_eq!(coalescer.dropped(), 48);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn terminal_events_are_immediate_and_drop_pending_progress() {
+        let (sink, delivered) = recording_sink();
+        let coalescer = BroadcastCoalescer::new(WINDOW, sink);
+
+        coalescer.submit(progress("task", 10)).await;
+        coalescer.submit(progress("task", 90)).await;
+        coalescer
+            .submit(BroadcastMessage {
+                event_type: "avatar_upload_complete".to_string(),
+                data: json!({ "task_id": "task", "progress": 100, "status": "completed" }),
+            })
+            .await;
+        tokio::time::sleep(WINDOW * 3).await;
+
+        let delivered = delivered.lock().unwrap();
+        assert_eq!(progress_values(&delivered), vec![10, 100]);
+        assert_eq!(delivered[1].event_type, "avatar_upload_complete");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn tasks_are_coalesced_independently() {
+        let (sink, delivered) = recording_sink();
+        let coalescer = BroadcastCoalescer::new(WINDOW, sink);
+
+        coalescer.submit(progress("a", 1)).await;
+        coalescer.submit(progress("b", 2)).await;
+        coalescer.submit(progress("a", 3)).await;
+        tokio::time::sleep(WINDOW * 3).await;
+
+        assert_eq!(progress_values(&delivered.lock().unwrap()), vec![1, 2, 3]);
+        assert!(coalescer.tasks.lock().await.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn flush_all_delivers_held_back_progress_at_once() {
+        let (sink, delivered) = recording_sink();
+        let coalescer = BroadcastCoalescer::new(WINDOW, sink);
+
+        coalescer.submit(progress("a", 10)).await;
+        coalescer.submit(progress("a", 20)).await;
+        coalescer.submit(progress("b", 30)).await;
+        coalescer.flush_all().await;
+
+        assert_eq!(
+            progress_values(&delivered.lock().unwrap()),
+            vec![10, 30, 20]
+        );
+        // The windows ended, so nothing is delivered twice when their timers fire
+        tokio::time::sleep(WINDOW * 3).await;
+        assert_eq!(delivered.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn messages_without_task_pass_through() {
+        let (sink, delivered) = recording_sink();
+        let coalescer = BroadcastCoalescer::new(WINDOW, sink);
+        let notification = BroadcastMessage {
+            event_type: "notification".to_string(),
+            data: json!({ "user_id": 1 }),
+        };
+
+        coalescer.submit(notification.clone()).await;
+        coalescer.submit(notification).await;
+
+        assert_eq!(delivered.lock().unwrap().len(), 2);
+    }
+
+    type Published = Arc<StdMutex<Vec<Option<String>>>>;
+
+    /// Records the destination of every published message
+    struct RecordingProducer(Published);
+
+    #[async_trait]
+    impl MessageProducer for RecordingProducer {
+        async fn publish(
+            &self,
+            _message: &EncodedMessage,
+            destination: Option<&str>,
+        ) -> anyhow::Result<()> {
+            self.0.lock().unwrap().push(destination.map(str::to_string));
+            Ok(())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn producer_coalesces_only_its_destination() {
+        let published = Published::default();
+        let producer = CoalescingProducer::new(
+            Arc::new(Box::new(RecordingProducer(published.clone()))),
+            "broadcasts",
+            WINDOW,
+        );
+
+        for value in 1..=3 {
+            let json = serde_json::to_string(&progress("task", value)).unwrap();
+            producer
+                .publish_event_json(&json, Some("broadcasts"))
+                .await
+                .unwrap();
+        }
+        producer
+            .publish_event_json("{}", Some("tasks"))
+            .await
+            .unwrap();
+        tokio::time::sleep(WINDOW * 3).await;
+
+        let destinations: Vec<_> = published
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|destination| destination.clone().unwrap())
+            .collect();
+        assert_eq!(destinations, vec!["broadcasts", "tasks", "broadcasts"]);
+    }
+
+    #[test]
+    fn detects_terminal_events() {
+        let by_status = BroadcastMessage {
+            event_type: "thumbnail_progress".to_string(),
+            data: json!({ "status": "completed" }),
+        };
+        let by_event = BroadcastMessage {
+            event_type: "import_failed".to_string(),
+            data: json!({}),
+        };
+
+        assert!(is_terminal(&by_status));
+        assert!(is_terminal(&by_event));
+        assert!(!is_terminal(&progress("task", 1)));
+    }
+}
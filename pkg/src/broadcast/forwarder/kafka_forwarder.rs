@@ -4,48 +4,82 @@ use rdkafka::{
     Message,
     admin::{AdminClient, AdminOptions, NewTopic, TopicReplication},
     client::DefaultClientContext,
-    config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
+    error::KafkaError,
 };
 use tracing::{error, info, warn};
 
-use super::{MessageForwarder, ShutdownSignal, forward_message_to_websocket};
+use super::{MessageForwarder, ShutdownSignal, forward_message_to_websocket, shutdown_requested};
+use crate::messaging::{
+    failover::{BrokerEndpoints, FailoverPolicy},
+    security::BrokerSecurity,
+};
 
 /// Kafka consumer that forwards progress updates to WebSockets
 pub struct KafkaForwarder {
+    endpoints: BrokerEndpoints,
     consumer: StreamConsumer,
     topic: String,
+    consumer_group: String,
+    security: BrokerSecurity,
 }
 
 impl KafkaForwarder {
-    /// Create a new Kafka forwarder
-    pub async fn new(brokers: &str, topic: &str, consumer_group: &str) -> anyhow::Result<Self> {
+    /// Create a new Kafka forwarder on the first reachable bootstrap set listed in `brokers`
+    pub async fn new(
+        brokers: &str,
+        topic: &str,
+        consumer_group: &str,
+        failover: &FailoverPolicy,
+        security: &BrokerSecurity,
+    ) -> anyhow::Result<Self> {
+        let endpoints = BrokerEndpoints::new("kafka forwarder", brokers, failover.clone());
+        let consumer = endpoints
+            .connect(|brokers| Self::subscribe(brokers, topic, consumer_group, security))
+            .await?;
+        info!("✓ Kafka forwarder initialized for topic: {}", topic);
+
+        Ok(Self {
+            endpoints,
+            consumer,
+            topic: topic.to_string(),
+            consumer_group: consumer_group.to_string(),
+            security: security.clone(),
+        })
+    }
+
+    /// Create a consumer on the bootstrap set `brokers` subscribed to `topic`, which also checks
+    /// that the set is reachable
+    async fn subscribe(
+        brokers: String,
+        topic: &str,
+        consumer_group: &str,
+        security: &BrokerSecurity,
+    ) -> anyhow::Result<StreamConsumer> {
         // Ensure topic exists
-        Self::ensure_topic_exists(brokers, topic).await?;
+        Self::ensure_topic_exists(&brokers, topic, security).await?;
 
-        let consumer: StreamConsumer = ClientConfig::new()
-            .set("bootstrap.servers", brokers)
+        let consumer: StreamConsumer = security
+            .kafka_client_config(&brokers)
             .set("group.id", consumer_group)
             .set("enable.auto.commit", "true")
             .set("auto.offset.reset", "latest")
             .create()?;
 
         consumer.subscribe(&[topic])?;
-        info!("✓ Kafka forwarder initialized for topic: {}", topic);
-
-        Ok(Self {
-            consumer,
-            topic: topic.to_string(),
-        })
+        Ok(consumer)
     }
 
     /// Ensure a Kafka topic exists, create it if it doesn't
-    async fn ensure_topic_exists(brokers: &str, topic: &str) -> anyhow::Result<()> {
+    async fn ensure_topic_exists(
+        brokers: &str,
+        topic: &str,
+        security: &BrokerSecurity,
+    ) -> anyhow::Result<()> {
         info!("Checking if Kafka topic exists: {}", topic);
 
-        let admin_client: AdminClient<DefaultClientContext> = ClientConfig::new()
-            .set("bootstrap.servers", brokers)
-            .create()?;
+        let admin_client: AdminClient<DefaultClientContext> =
+            security.kafka_client_config(brokers).create()?;
 
         let new_topic = NewTopic::new(
             topic,
@@ -76,45 +110,70 @@ impl KafkaForwarder {
 impl MessageForwarder for KafkaForwarder {
     async fn start_forwarding(self: Box<Self>, mut shutdown: ShutdownSignal) -> anyhow::Result<()> {
         let this = *self;
-        info!("✓ Kafka forwarder subscribed to topic: {}", this.topic);
-
-        let mut stream = this.consumer.stream();
+        let mut consumer = this.consumer;
 
+        // Start over, on the next bootstrap set if any, whenever the client gives up on its set
         loop {
-            tokio::select! {
-                changed = shutdown.changed() => {
-                    if changed.is_err() || *shutdown.borrow() {
-                        info!("Shutting down Kafka forwarder");
-                        break;
+            info!("✓ Kafka forwarder subscribed to topic: {}", this.topic);
+            let mut stream = consumer.stream();
+
+            let lost = loop {
+                tokio::select! {
+                    changed = shutdown.changed() => {
+                        if changed.is_err() || *shutdown.borrow() {
+                            info!("Shutting down Kafka forwarder");
+                            break None;
+                        }
                     }
-                }
-                message = stream.next() => {
-                    match message {
-                        Some(Ok(msg)) => {
-                            if let Some(payload) = msg.payload_view::<str>() {
-                                match payload {
-                                    Ok(p) => {
-                                        forward_message_to_websocket(p).await;
-                                    }
-                                    Err(e) => {
-                                        error!("Failed to decode Kafka message payload: {:?}", e);
+                    message = stream.next() => {
+                        match message {
+                            Some(Ok(msg)) => {
+                                if let Some(payload) = msg.payload_view::<str>() {
+                                    match payload {
+                                        Ok(p) => {
+                                            forward_message_to_websocket(p).await;
+                                        }
+                                        Err(e) => {
+                                            error!("Failed to decode Kafka message payload: {:?}", e);
+                                        }
                                     }
                                 }
                             }
-                        }
-                        Some(Err(e)) => {
-                            error!("Kafka consumer error: {}", e);
-                        }
-                        None => {
-                            error!("Kafka consumer stream ended");
-                            break;
+                            Some(Err(KafkaError::Global(code))) => {
+                                error!("Kafka consumer error: {}", code);
+                                break Some(code.to_string());
+                            }
+                            Some(Err(e)) => {
+                                error!("Kafka consumer error: {}", e);
+                            }
+                            None => {
+                                error!("Kafka consumer stream ended");
+                                break Some("Consumer stream ended".to_string());
+                            }
                         }
                     }
                 }
-            }
+            };
+
+            drop(stream);
+            let Some(reason) = lost else {
+                break;
+            };
+
+            let (topic, consumer_group, security) =
+                (&this.topic, &this.consumer_group, &this.security);
+            consumer = tokio::select! {
+                _ = shutdown_requested(&mut shutdown) => {
+                    info!("Shutting down Kafka forwarder");
+                    return Ok(());
+                }
+                consumer = this.endpoints.reconnect(reason, |brokers| {
+                    Self::subscribe(brokers, topic, consumer_group, security)
+                }) => consumer,
+            };
         }
 
-        this.consumer.unsubscribe();
+        consumer.unsubscribe();
 
         Ok(())
     }
@@ -6,26 +6,69 @@ pub use kafka_forwarder::KafkaForwarder;
 pub use rabbitmq_forwarder::RabbitMQForwarder;
 pub use redis_forwarder::RedisForwarder;
 
-use super::websocket::{BroadcastMessage, broadcast_to_task, broadcast_to_user};
+use super::{
+    coalescer::BroadcastCoalescer,
+    schema::{self, MessageDirection, SchemaViolation, rejected_message},
+    websocket::{BroadcastMessage, broadcast_to_task, broadcast_to_user},
+};
+use crate::messaging::{
+    failover::FailoverPolicy,
+    security::BrokerSecurity,
+    stats::{WORKER_STATS_EVENT, WorkerStats, record_worker_stats},
+};
 use async_trait::async_trait;
+use serde_json::Value;
+use std::{
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 use tokio::sync::watch;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
-/// Configuration for creating message forwarders
+/// Coalescer applied to forwarded messages once `enable_coalescing` is called
+static COALESCER: OnceLock<BroadcastCoalescer> = OnceLock::new();
+
+/// Coalesce task progress forwarded to WebSockets within `window` (see `BroadcastCoalescer`).
+/// Only the first call takes effect.
+pub fn enable_coalescing(window: Duration) {
+    let coalescer = BroadcastCoalescer::new(
+        window,
+        Arc::new(|message| Box::pin(deliver_to_websocket(message))),
+    );
+    if COALESCER.set(coalescer).is_ok() {
+        tracing::info!("✓ Broadcast coalescing enabled ({:?} window)", window);
+    }
+}
+
+/// Deliver the progress the coalescer still holds back, if coalescing is enabled
+pub async fn flush_coalesced() {
+    if let Some(coalescer) = COALESCER.get() {
+        coalescer.flush_all().await;
+    }
+}
+
+/// Configuration for creating message forwarders. `brokers` and `url` may list several
+/// endpoints separated by `;` to fail over between; Redis follows the failover of its shared
+/// connection.
 #[derive(Debug, Clone)]
 pub enum ForwarderConfig {
     Kafka {
         brokers: String,
         consumer_group: String,
         topic: String,
+        failover: FailoverPolicy,
+        security: BrokerSecurity,
     },
     Redis {
         url: String,
         channel: String,
+        security: BrokerSecurity,
     },
     RabbitMQ {
         url: String,
         queue: String,
+        failover: FailoverPolicy,
+        security: BrokerSecurity,
     },
 }
 
@@ -36,22 +79,62 @@ impl ForwarderConfig {
             brokers,
             consumer_group,
             topic,
+            failover: FailoverPolicy::default(),
+            security: BrokerSecurity::default(),
         }
     }
 
     /// Create Redis forwarder configuration
     pub fn redis(url: String, channel: String) -> Self {
-        ForwarderConfig::Redis { url, channel }
+        ForwarderConfig::Redis {
+            url,
+            channel,
+            security: BrokerSecurity::default(),
+        }
     }
 
     /// Create RabbitMQ forwarder configuration
     pub fn rabbitmq(url: String, queue: String) -> Self {
-        ForwarderConfig::RabbitMQ { url, queue }
+        ForwarderConfig::RabbitMQ {
+            url,
+            queue,
+            failover: FailoverPolicy::default(),
+            security: BrokerSecurity::default(),
+        }
+    }
+
+    /// Use `policy` to reconnect and fail over between the listed endpoints
+    pub fn with_failover(mut self, policy: FailoverPolicy) -> Self {
+        match &mut self {
+            ForwarderConfig::Kafka { failover, .. }
+            | ForwarderConfig::RabbitMQ { failover, .. } => *failover = policy,
+            ForwarderConfig::Redis { .. } => {}
+        }
+        self
+    }
+
+    /// Connect over TLS and authenticate as configured in `settings`
+    pub fn with_security(mut self, settings: BrokerSecurity) -> Self {
+        match &mut self {
+            ForwarderConfig::Kafka { security, .. }
+            | ForwarderConfig::Redis { security, .. }
+            | ForwarderConfig::RabbitMQ { security, .. } => *security = settings,
+        }
+        self
     }
 }
 
 pub type ShutdownSignal = watch::Receiver<bool>;
 
+/// Resolve once `shutdown` is signalled or its sender is gone
+async fn shutdown_requested(shutdown: &mut ShutdownSignal) {
+    while !*shutdown.borrow_and_update() {
+        if shutdown.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
 /// Trait for message forwarders that receive broadcast messages from a message queue
 /// and forward them to WebSocket connections
 #[async_trait]
@@ -80,16 +163,65 @@ fn websocket_target(message: &BroadcastMessage) -> Option<WebSocketTarget> {
         })
 }
 
+/// Parse a forwarded payload and check its data against the schema of its event type.
+/// Failures come back as the `message_rejected` event to deliver in its place.
+fn parse_broadcast(payload: &str) -> Result<BroadcastMessage, BroadcastMessage> {
+    let value: Value = serde_json::from_str(payload).map_err(|e| {
+        rejected_message(
+            &Value::Null,
+            None,
+            vec![SchemaViolation::new("", e.to_string())],
+        )
+    })?;
+    let route = value.get("data").cloned().unwrap_or_default();
+    let broadcast_msg: BroadcastMessage = serde_json::from_value(value).map_err(|e| {
+        rejected_message(&route, None, vec![SchemaViolation::new("", e.to_string())])
+    })?;
+
+    schema::validate(
+        MessageDirection::Outbound,
+        &broadcast_msg.event_type,
+        &broadcast_msg.data,
+    )
+    .map_err(|errors| rejected_message(&route, Some(&broadcast_msg.event_type), errors))?;
+
+    Ok(broadcast_msg)
+}
+
 /// Helper function to process and forward a broadcast message to the appropriate task
 pub async fn forward_message_to_websocket(payload: &str) {
-    let broadcast_msg: BroadcastMessage = match serde_json::from_str(payload) {
+    let broadcast_msg = match parse_broadcast(payload) {
         Ok(m) => m,
-        Err(e) => {
-            error!("Failed to parse broadcast message: {}", e);
+        Err(rejection) => {
+            warn!(
+                rejected = ?rejection.data.get("rejected"),
+                errors = %rejection.data["errors"],
+                "Rejected malformed broadcast message"
+            );
+            // Without a task_id or user_id there is no WebSocket to tell
+            if websocket_target(&rejection).is_some() {
+                deliver_to_websocket(rejection).await;
+            }
             return;
         }
     };
 
+    // Workers report their queue stats on the broadcast channel; keep them for the API
+    if broadcast_msg.event_type == WORKER_STATS_EVENT {
+        match serde_json::from_value::<WorkerStats>(broadcast_msg.data) {
+            Ok(stats) => record_worker_stats(stats),
+            Err(e) => error!("Failed to parse worker stats: {}", e),
+        }
+        return;
+    }
+
+    match COALESCER.get() {
+        Some(coalescer) => coalescer.submit(broadcast_msg).await,
+        None => deliver_to_websocket(broadcast_msg).await,
+    }
+}
+
+async fn deliver_to_websocket(broadcast_msg: BroadcastMessage) {
     match websocket_target(&broadcast_msg) {
         Some(WebSocketTarget::Task(task_id)) => {
             debug!(
@@ -119,22 +251,38 @@ pub async fn create_forwarder(
     config: ForwarderConfig,
 ) -> anyhow::Result<Box<dyn MessageForwarder>> {
     match config {
-        ForwarderConfig::Redis { url, channel } => {
-            let forwarder = RedisForwarder::new(&url, &channel).await?;
+        ForwarderConfig::Redis {
+            url,
+            channel,
+            security,
+        } => {
+            let forwarder = RedisForwarder::new(&url, &channel, &security).await?;
             Ok(Box::new(forwarder) as Box<dyn MessageForwarder>)
         }
         ForwarderConfig::Kafka {
             brokers,
             consumer_group,
             topic,
+            failover,
+            security,
         } => {
-            let forwarder =
-                KafkaForwarder::new(&brokers, &topic, &format!("{}_forwarder", consumer_group))
-                    .await?;
+            let forwarder = KafkaForwarder::new(
+                &brokers,
+                &topic,
+                &format!("{}_forwarder", consumer_group),
+                &failover,
+                &security,
+            )
+            .await?;
             Ok(Box::new(forwarder) as Box<dyn MessageForwarder>)
         }
-        ForwarderConfig::RabbitMQ { url, queue } => {
-            let forwarder = RabbitMQForwarder::new(&url, &queue).await?;
+        ForwarderConfig::RabbitMQ {
+            url,
+            queue,
+            failover,
+            security,
+        } => {
+            let forwarder = RabbitMQForwarder::new(&url, &queue, &failover, &security).await?;
             Ok(Box::new(forwarder) as Box<dyn MessageForwarder>)
         }
     }
@@ -1,39 +1,61 @@
 use async_trait::async_trait;
 use futures::StreamExt;
-use lapin::{Connection, ConnectionProperties, options::*, types::FieldTable};
+use lapin::{Connection, options::*, types::FieldTable};
 use tracing::{error, info, warn};
 
-use super::{MessageForwarder, ShutdownSignal, forward_message_to_websocket};
+use super::{MessageForwarder, ShutdownSignal, forward_message_to_websocket, shutdown_requested};
+use crate::messaging::{
+    failover::{BrokerEndpoints, FailoverPolicy},
+    security::BrokerSecurity,
+};
 
 /// RabbitMQ consumer that forwards progress updates to WebSockets
 pub struct RabbitMQForwarder {
+    endpoints: BrokerEndpoints,
+    security: BrokerSecurity,
     connection: Connection,
     queue: String,
 }
 
 impl RabbitMQForwarder {
-    /// Create a new RabbitMQ forwarder
-    pub async fn new(url: &str, queue: &str) -> anyhow::Result<Self> {
-        let connection = Connection::connect(url, ConnectionProperties::default()).await?;
+    /// Create a new RabbitMQ forwarder on the first reachable URL listed in `url`
+    pub async fn new(
+        url: &str,
+        queue: &str,
+        failover: &FailoverPolicy,
+        security: &BrokerSecurity,
+    ) -> anyhow::Result<Self> {
+        let endpoints = BrokerEndpoints::new("rabbitmq forwarder", url, failover.clone());
+        let connection = endpoints
+            .connect(|url| Self::connect(url, security))
+            .await?;
         info!("✓ RabbitMQ forwarder initialized for queue: {}", queue);
 
         Ok(Self {
+            endpoints,
+            security: security.clone(),
             connection,
             queue: queue.to_string(),
         })
     }
-}
 
-#[async_trait]
-impl MessageForwarder for RabbitMQForwarder {
-    async fn start_forwarding(self: Box<Self>, mut shutdown: ShutdownSignal) -> anyhow::Result<()> {
-        let this = *self;
-        let channel = this.connection.create_channel().await?;
+    async fn connect(url: String, security: &BrokerSecurity) -> anyhow::Result<Connection> {
+        security.connect_rabbitmq(&url).await
+    }
+
+    /// Forward from the queue on `connection` until shutdown, returning `None`, or until the
+    /// connection is lost, returning why
+    async fn forward(
+        queue: &str,
+        connection: &Connection,
+        shutdown: &mut ShutdownSignal,
+    ) -> anyhow::Result<Option<String>> {
+        let channel = connection.create_channel().await?;
 
         // Declare queue if it doesn't exist
         channel
             .queue_declare(
-                this.queue.as_str().into(),
+                queue.into(),
                 QueueDeclareOptions {
                     durable: true,
                     ..Default::default()
@@ -44,21 +66,21 @@ impl MessageForwarder for RabbitMQForwarder {
 
         let mut consumer = channel
             .basic_consume(
-                this.queue.as_str().into(),
+                queue.into(),
                 "websocket_forwarder".into(),
                 BasicConsumeOptions::default(),
                 FieldTable::default(),
             )
             .await?;
 
-        info!("✓ RabbitMQ forwarder subscribed to queue: {}", this.queue);
+        info!("✓ RabbitMQ forwarder subscribed to queue: {}", queue);
 
-        loop {
+        let lost = loop {
             tokio::select! {
                 changed = shutdown.changed() => {
                     if changed.is_err() || *shutdown.borrow() {
                         info!("Shutting down RabbitMQ forwarder");
-                        break;
+                        break None;
                     }
                 }
                 message = consumer.next() => {
@@ -77,20 +99,55 @@ impl MessageForwarder for RabbitMQForwarder {
                         }
                         Some(Err(e)) => {
                             error!("RabbitMQ consumer error: {}", e);
+                            if !connection.status().connected() {
+                                break Some(e.to_string());
+                            }
                         }
                         None => {
                             error!("RabbitMQ consumer stream ended");
-                            break;
+                            break Some("Consumer stream ended".to_string());
                         }
                     }
                 }
             }
-        }
+        };
 
-        if let Err(error) = channel.close(0, "Normal shutdown".into()).await {
+        if lost.is_none()
+            && let Err(error) = channel.close(0, "Normal shutdown".into()).await
+        {
             warn!("Failed to close RabbitMQ forwarder channel: {}", error);
         }
-        if let Err(error) = this.connection.close(0, "Normal shutdown".into()).await {
+
+        Ok(lost)
+    }
+}
+
+#[async_trait]
+impl MessageForwarder for RabbitMQForwarder {
+    async fn start_forwarding(self: Box<Self>, mut shutdown: ShutdownSignal) -> anyhow::Result<()> {
+        let this = *self;
+        let mut connection = this.connection;
+
+        // Connect again, to the next URL if any, whenever the connection is lost
+        loop {
+            let reason = match Self::forward(&this.queue, &connection, &mut shutdown).await {
+                Ok(None) => break,
+                Ok(Some(reason)) => reason,
+                Err(e) => e.to_string(),
+            };
+
+            connection = tokio::select! {
+                _ = shutdown_requested(&mut shutdown) => {
+                    info!("Shutting down RabbitMQ forwarder");
+                    return Ok(());
+                }
+                connection = this.endpoints.reconnect(reason, |url| {
+                    Self::connect(url, &this.security)
+                }) => connection,
+            };
+        }
+
+        if let Err(error) = connection.close(0, "Normal shutdown".into()).await {
             warn!("Failed to close RabbitMQ forwarder connection: {}", error);
         }
 
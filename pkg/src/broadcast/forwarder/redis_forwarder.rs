@@ -1,29 +1,40 @@
 use async_trait::async_trait;
 use futures::StreamExt;
-use redis::{Client, aio::PubSub};
 use tracing::{error, info, warn};
 
 use super::{MessageForwarder, ShutdownSignal, forward_message_to_websocket};
+use crate::{
+    messaging::security::BrokerSecurity,
+    redis::{RedisConnectionManager, RedisPoolConfig},
+};
 
 /// Redis subscriber that forwards progress updates to WebSockets
 pub struct RedisForwarder {
-    client: Client,
+    manager: RedisConnectionManager,
     channel: String,
 }
 
 impl RedisForwarder {
-    /// Create a new Redis forwarder
-    pub async fn new(redis_url: &str, channel: &str) -> anyhow::Result<Self> {
-        let client = Client::open(redis_url)?;
+    /// Create a new Redis forwarder on the process-wide connection for `redis_url`
+    pub async fn new(
+        redis_url: &str,
+        channel: &str,
+        security: &BrokerSecurity,
+    ) -> anyhow::Result<Self> {
+        let config = RedisPoolConfig {
+            security: security.clone(),
+            ..Default::default()
+        };
+        let manager = RedisConnectionManager::shared(redis_url, &config).await?;
+        Ok(Self::from_manager(manager, channel))
+    }
 
-        // Test connection
-        let _conn = client.get_multiplexed_async_connection().await?;
+    pub fn from_manager(manager: RedisConnectionManager, channel: &str) -> Self {
         info!("✓ Redis forwarder initialized for channel: {}", channel);
-
-        Ok(Self {
-            client,
+        Self {
+            manager,
             channel: channel.to_string(),
-        })
+        }
     }
 }
 
@@ -31,52 +42,59 @@ impl RedisForwarder {
 impl MessageForwarder for RedisForwarder {
     async fn start_forwarding(self: Box<Self>, mut shutdown: ShutdownSignal) -> anyhow::Result<()> {
         let this = *self;
-        let mut pubsub: PubSub = this.client.get_async_pubsub().await?;
-
-        pubsub.subscribe(&this.channel).await?;
-        info!("✓ Redis forwarder subscribed to channel: {}", this.channel);
-
-        let mut stream = pubsub.on_message();
 
+        // Subscribe again whenever the pub/sub connection drops, until shutdown
         loop {
-            tokio::select! {
-                changed = shutdown.changed() => {
-                    if changed.is_err() || *shutdown.borrow() {
-                        info!("Shutting down Redis forwarder");
-                        break;
-                    }
-                }
-                message = stream.next() => {
-                    match message {
-                        Some(msg) => {
-                            let payload: String = match msg.get_payload() {
-                                Ok(p) => p,
-                                Err(e) => {
-                                    error!("Failed to get payload from Redis message: {}", e);
-                                    continue;
-                                }
-                            };
+            let mut pubsub = this.manager.pubsub().await?;
+            pubsub.subscribe(&this.channel).await?;
+            info!("✓ Redis forwarder subscribed to channel: {}", this.channel);
 
-                            forward_message_to_websocket(&payload).await;
+            let mut stream = pubsub.on_message();
+            let stopped = loop {
+                tokio::select! {
+                    changed = shutdown.changed() => {
+                        if changed.is_err() || *shutdown.borrow() {
+                            info!("Shutting down Redis forwarder");
+                            break true;
                         }
-                        None => {
-                            error!("Redis pubsub stream ended");
-                            break;
+                    }
+                    message = stream.next() => {
+                        match message {
+                            Some(msg) => {
+                                let payload: String = match msg.get_payload() {
+                                    Ok(p) => p,
+                                    Err(e) => {
+                                        error!("Failed to get payload from Redis message: {}", e);
+                                        continue;
+                                    }
+                                };
+
+                                forward_message_to_websocket(&payload).await;
+                            }
+                            None => {
+                                error!("Redis pubsub stream ended, resubscribing");
+                                break false;
+                            }
                         }
                     }
                 }
+            };
+
+            drop(stream);
+
+            if !stopped {
+                this.manager.record_reconnect();
+                continue;
             }
-        }
 
-        drop(stream);
+            if let Err(error) = pubsub.unsubscribe(&this.channel).await {
+                warn!(
+                    "Failed to unsubscribe Redis forwarder from {}: {}",
+                    this.channel, error
+                );
+            }
 
-        if let Err(error) = pubsub.unsubscribe(&this.channel).await {
-            warn!(
-                "Failed to unsubscribe Redis forwarder from {}: {}",
-                this.channel, error
-            );
+            return Ok(());
         }
-
-        Ok(())
     }
 }
@@ -1,2 +1,6 @@
+pub mod coalescer;
 pub mod forwarder;
+pub mod protocol;
+pub mod replay;
+pub mod schema;
 pub mod websocket;
@@ -0,0 +1,132 @@
+use axum::extract::ws::{Message, WebSocketUpgrade};
+use serde::Serialize;
+
+use super::websocket::BroadcastMessage;
+
+/// Format of the broadcast messages a WebSocket receives, negotiated during the handshake
+/// through `Sec-WebSocket-Protocol`. Clients that ask for no protocol get `V1`, so the format
+/// can evolve without breaking them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WsProtocol {
+    /// `{"event_type": .., "data": ..}`
+    #[default]
+    V1,
+    /// `{"version": 2, "seq": .., "event_type": .., "data": ..}`. Task broadcasts carry the
+    /// `seq` the long-polling endpoint numbers them with, so a client that reconnects can
+    /// fetch what it missed.
+    V2,
+}
+
+#[derive(Serialize)]
+struct V2Frame<'a> {
+    version: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seq: Option<u64>,
+    #[serde(flatten)]
+    message: &'a BroadcastMessage,
+}
+
+impl WsProtocol {
+    /// Subprotocol names the server accepts, most preferred first
+    pub const SUPPORTED: [&'static str; 2] = ["my-axum.v2", "my-axum.v1"];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::V1 => "my-axum.v1",
+            Self::V2 => "my-axum.v2",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "my-axum.v1" => Some(Self::V1),
+            "my-axum.v2" => Some(Self::V2),
+            _ => None,
+        }
+    }
+
+    /// Accept the most preferred protocol the client offers, `V1` when it offers none we know
+    pub fn negotiate(ws: WebSocketUpgrade) -> (WebSocketUpgrade, Self) {
+        let ws = ws.protocols(Self::SUPPORTED);
+        let protocol = ws
+            .selected_protocol()
+            .and_then(|name| name.to_str().ok())
+            .and_then(Self::from_name)
+            .unwrap_or_default();
+        (ws, protocol)
+    }
+
+    /// Frame of `message` in this format. `seq` is its number among the task's broadcasts,
+    /// if it has one.
+    pub fn encode(self, message: &BroadcastMessage, seq: Option<u64>) -> Option<Message> {
+        let encoded = match self {
+            Self::V1 => serde_json::to_string(message),
+            Self::V2 => serde_json::to_string(&V2Frame {
+                version: 2,
+                seq,
+                message,
+            }),
+        };
+        match encoded {
+            Ok(text) => Some(Message::Text(text.into())),
+            Err(error) => {
+                tracing::error!("Failed to serialize broadcast message: {}", error);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::extract::ws::Message;
+    use serde_json::{Value, json};
+
+    use super::WsProtocol;
+    use crate::broadcast::websocket::BroadcastMessage;
+
+    fn decode(message: Option<Message>) -> Value {
+        let Some(Message::Text(text)) = message else {
+            panic!("expected a text frame");
+        };
+        serde_json::from_str(text.as_str()).unwrap()
+    }
+
+    fn progress() -> BroadcastMessage {
+        BroadcastMessage {
+            event_type: "progress".to_string(),
+            data: json!({"percent": 50}),
+        }
+    }
+
+    #[test]
+    fn parses_protocol_names() {
+        for protocol in [WsProtocol::V1, WsProtocol::V2] {
+            assert_eq!(WsProtocol::from_name(protocol.name()), Some(protocol));
+        }
+        assert_eq!(WsProtocol::from_name("graphql-ws"), None);
+        assert_eq!(WsProtocol::SUPPORTED[0], WsProtocol::V2.name());
+    }
+
+    #[test]
+    fn v1_keeps_the_original_format() {
+        let frame = decode(WsProtocol::V1.encode(&progress(), Some(3)));
+
+        assert_eq!(
+            frame,
+            json!({"event_type": "progress", "data": {"percent": 50}})
+        );
+    }
+
+    #[test]
+    fn v2_adds_version_and_sequence_number() {
+        let frame = decode(WsProtocol::V2.encode(&progress(), Some(3)));
+        assert_eq!(
+            frame,
+            json!({"version": 2, "seq": 3, "event_type": "progress", "data": {"percent": 50}})
+        );
+
+        let frame = decode(WsProtocol::V2.encode(&progress(), None));
+        assert!(frame.get("seq").is_none());
+    }
+}
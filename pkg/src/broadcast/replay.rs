@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::Notify;
+
+use super::websocket::BroadcastMessage;
+
+/// Messages kept per task; older ones are dropped first
+const TASK_CAPACITY: usize = 100;
+/// How long a task's messages are kept after its last broadcast
+const TASK_RETENTION: Duration = Duration::from_secs(3600);
+
+/// Broadcast message numbered within its task, starting at 1
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequencedMessage {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub message: BroadcastMessage,
+}
+
+struct TaskBuffer {
+    messages: VecDeque<SequencedMessage>,
+    last_seq: u64,
+    updated_at: Instant,
+    notify: Arc<Notify>,
+}
+
+impl TaskBuffer {
+    fn new() -> Self {
+        Self {
+            messages: VecDeque::new(),
+            last_seq: 0,
+            updated_at: Instant::now(),
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    fn since(&self, since: u64) -> Vec<SequencedMessage> {
+        self.messages
+            .iter()
+            .filter(|message| message.seq > since)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Recent task broadcasts, so clients that cannot hold a WebSocket open can poll for
+/// what they missed
+static REPLAY_STORE: LazyLock<Mutex<HashMap<String, TaskBuffer>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Buffer `message` for `task_id` and wake its pollers. Returns its sequence number.
+pub fn record(task_id: &str, message: BroadcastMessage) -> u64 {
+    let mut store = REPLAY_STORE.lock().unwrap();
+    store.retain(|_, buffer| buffer.updated_at.elapsed() < TASK_RETENTION);
+
+    let buffer = store
+        .entry(task_id.to_string())
+        .or_insert_with(TaskBuffer::new);
+    buffer.last_seq += 1;
+    buffer.updated_at = Instant::now();
+    buffer.messages.push_back(SequencedMessage {
+        seq: buffer.last_seq,
+        message,
+    });
+    if buffer.messages.len() > TASK_CAPACITY {
+        buffer.messages.pop_front();
+    }
+    buffer.notify.notify_waiters();
+
+    buffer.last_seq
+}
+
+/// Buffered messages of `task_id` after sequence number `since`
+pub fn messages_since(task_id: &str, since: u64) -> Vec<SequencedMessage> {
+    let store = REPLAY_STORE.lock().unwrap();
+    store
+        .get(task_id)
+        .map(|buffer| buffer.since(since))
+        .unwrap_or_default()
+}
+
+/// Messages of `task_id` after `since`, waiting up to `timeout` for one to arrive when
+/// there are none yet. Empty when the wait times out.
+pub async fn wait_for_messages(
+    task_id: &str,
+    since: u64,
+    timeout: Duration,
+) -> Vec<SequencedMessage> {
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    loop {
+        let notify = REPLAY_STORE
+            .lock()
+            .unwrap()
+            .entry(task_id.to_string())
+            .or_insert_with(TaskBuffer::new)
+            .notify
+            .clone();
+
+        // Registered before checking, so a message recorded in between is not missed
+        let notified = notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        let messages = messages_since(task_id, since);
+        if !messages.is_empty() {
+            return messages;
+        }
+
+        if tokio::time::timeout_at(deadline, notified).await.is_err() {
+            return Vec::new();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn progress(percent: u64) -> BroadcastMessage {
+        BroadcastMessage {
+            event_type: "progress".to_string(),
+            data: json!({"percent": percent}),
+        }
+    }
+
+    #[test]
+    fn numbers_messages_per_task() {
+        let task_id = Uuid::new_v4().to_string();
+
+        assert_eq!(record(&task_id, progress(10)), 1);
+        assert_eq!(record(&task_id, progress(20)), 2);
+
+        let messages = messages_since(&task_id, 1);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].seq, 2);
+        assert_eq!(messages[0].message.data["percent"], 20);
+        assert!(messages_since(&Uuid::new_v4().to_string(), 0).is_empty());
+    }
+
+    #[test]
+    fn keeps_only_the_latest_messages() {
+        let task_id = Uuid::new_v4().to_string();
+        for percent in 0..(TASK_CAPACITY as u64 + 5) {
+            record(&task_id, progress(percent));
+        }
+
+        let messages = messages_since(&task_id, 0);
+        assert_eq!(messages.len(), TASK_CAPACITY);
+        assert_eq!(messages[0].seq, 6);
+    }
+
+    #[tokio::test]
+    async fn wakes_pollers_on_new_messages() {
+        let task_id = Uuid::new_v4().to_string();
+        let poller = tokio::spawn({
+            let task_id = task_id.clone();
+            async move { wait_for_messages(&task_id, 0, Duration::from_secs(5)).await }
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        record(&task_id, progress(50));
+
+        let messages = poller.await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].seq, 1);
+    }
+
+    #[tokio::test]
+    async fn returns_nothing_when_the_wait_times_out() {
+        let task_id = Uuid::new_v4().to_string();
+
+        let messages = wait_for_messages(&task_id, 0, Duration::from_millis(20)).await;
+        assert!(messages.is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+use jsonschema::Validator;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use super::websocket::BroadcastMessage;
+
+/// Event type of the message sent in place of one that failed validation
+pub const MESSAGE_REJECTED_EVENT: &str = "message_rejected";
+
+/// Which side of a WebSocket a schema applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageDirection {
+    /// Broadcasts forwarded to WebSockets, keyed by `event_type`. Event types without a
+    /// schema are forwarded as they are.
+    Outbound,
+    /// Commands clients send over a WebSocket, keyed by `action`. Actions without a schema
+    /// are rejected.
+    Inbound,
+}
+
+/// One reason a message didn't match its schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value, empty for the message itself
+    pub path: String,
+    pub message: String,
+}
+
+impl SchemaViolation {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+type SchemaRegistry = RwLock<HashMap<(MessageDirection, String), Arc<Validator>>>;
+
+/// Compiled schemas, keyed by direction and event type or action
+static SCHEMAS: OnceLock<SchemaRegistry> = OnceLock::new();
+
+fn get_registry() -> &'static SchemaRegistry {
+    SCHEMAS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Compile `schema` and validate `name` messages going in `direction` against it from now on,
+/// replacing any schema registered before
+pub fn register_schema(
+    direction: MessageDirection,
+    name: &str,
+    schema: &Value,
+) -> anyhow::Result<()> {
+    let validator = jsonschema::validator_for(schema)
+        .map_err(|e| anyhow::anyhow!("Invalid JSON Schema for {}: {}", name, e))?;
+    get_registry()
+        .write()
+        .unwrap()
+        .insert((direction, name.to_string()), Arc::new(validator));
+    Ok(())
+}
+
+pub fn is_registered(direction: MessageDirection, name: &str) -> bool {
+    get_registry()
+        .read()
+        .unwrap()
+        .contains_key(&(direction, name.to_string()))
+}
+
+/// Validate the payload of a `name` message against its schema
+pub fn validate(
+    direction: MessageDirection,
+    name: &str,
+    payload: &Value,
+) -> Result<(), Vec<SchemaViolation>> {
+    let validator = get_registry()
+        .read()
+        .unwrap()
+        .get(&(direction, name.to_string()))
+        .cloned();
+    let Some(validator) = validator else {
+        return match direction {
+            MessageDirection::Outbound => Ok(()),
+            MessageDirection::Inbound => Err(vec![SchemaViolation::new(
+                "/action",
+                format!("Unknown action \"{}\"", name),
+            )]),
+        };
+    };
+
+    let violations: Vec<SchemaViolation> = validator
+        .iter_errors(payload)
+        .map(|error| SchemaViolation::new(error.instance_path().to_string(), error.to_string()))
+        .collect();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Parse a command a client sent over a WebSocket and check it against the schema of its
+/// `action`. Failures come back as the `message_rejected` event to answer with, routed
+/// like `route`.
+pub fn validate_command(route: &Value, text: &str) -> Result<Value, BroadcastMessage> {
+    let command: Value = serde_json::from_str(text).map_err(|e| {
+        rejected_message(route, None, vec![SchemaViolation::new("", e.to_string())])
+    })?;
+    let Some(action) = command.get("action").and_then(Value::as_str) else {
+        return Err(rejected_message(
+            route,
+            None,
+            vec![SchemaViolation::new(
+                "/action",
+                "\"action\" must be a string",
+            )],
+        ));
+    };
+
+    validate(MessageDirection::Inbound, action, &command)
+        .map_err(|errors| rejected_message(route, Some(action), errors))?;
+
+    Ok(command)
+}
+
+/// The `message_rejected` event reporting why a message was dropped. `route` is the
+/// rejected message's data, whose `task_id` or `user_id` is kept so it reaches the same
+/// WebSocket.
+pub fn rejected_message(
+    route: &Value,
+    rejected: Option<&str>,
+    errors: Vec<SchemaViolation>,
+) -> BroadcastMessage {
+    let mut data = Map::new();
+    for key in ["task_id", "user_id"] {
+        if let Some(value) = route.get(key) {
+            data.insert(key.to_string(), value.clone());
+        }
+    }
+    data.insert("rejected".to_string(), rejected.into());
+    data.insert("errors".to_string(), serde_json::json!(errors));
+
+    BroadcastMessage {
+        event_type: MESSAGE_REJECTED_EVENT.to_string(),
+        data: Value::Object(data),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn progress_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["task_id", "progress"],
+            "properties": {
+                "task_id": {"type": "string"},
+                "progress": {"type": "integer", "minimum": 0, "maximum": 100}
+            }
+        })
+    }
+
+    #[test]
+    fn validates_registered_outbound_events() {
+        register_schema(
+            MessageDirection::Outbound,
+            "schema_test_progress",
+            &progress_schema(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            validate(
+                MessageDirection::Outbound,
+                "schema_test_progress",
+                &json!({"task_id": "t", "progress": 50})
+            ),
+            Ok(())
+        );
+
+        let errors = validate(
+            MessageDirection::Outbound,
+            "schema_test_progress",
+            &json!({"task_id": "t", "progress": 150}),
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "/progress");
+    }
+
+    #[test]
+    fn passes_unregistered_events_and_rejects_unknown_actions() {
+        assert_eq!(
+            validate(
+                MessageDirection::Outbound,
+                "schema_test_unknown",
+                &json!(null)
+            ),
+            Ok(())
+        );
+
+        let errors =
+            validate(MessageDirection::Inbound, "schema_test_unknown", &json!({})).unwrap_err();
+        assert_eq!(errors[0].path, "/action");
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        let error = register_schema(
+            MessageDirection::Outbound,
+            "schema_test_invalid",
+            &json!({"type": "not-a-type"}),
+        )
+        .unwrap_err();
+
+        assert!(error.to_string().contains("schema_test_invalid"));
+        assert!(!is_registered(
+            MessageDirection::Outbound,
+            "schema_test_invalid"
+        ));
+    }
+
+    #[test]
+    fn validates_commands_by_action() {
+        register_schema(
+            MessageDirection::Inbound,
+            "schema_test_subscribe",
+            &json!({
+                "type": "object",
+                "required": ["action", "channel"],
+                "properties": {"channel": {"type": "string"}}
+            }),
+        )
+        .unwrap();
+        let route = json!({"task_id": "t"});
+
+        let command = validate_command(
+            &route,
+            r#"{"action":"schema_test_subscribe","channel":"news"}"#,
+        )
+        .unwrap();
+        assert_eq!(command["channel"], "news");
+
+        let rejection =
+            validate_command(&route, r#"{"action":"schema_test_subscribe"}"#).unwrap_err();
+        assert_eq!(rejection.data["task_id"], "t");
+        assert_eq!(rejection.data["rejected"], "schema_test_subscribe");
+
+        let rejection = validate_command(&route, "not json").unwrap_err();
+        assert!(rejection.data["rejected"].is_null());
+        assert_eq!(rejection.data["errors"][0]["path"], "");
+
+        let rejection = validate_command(&route, r#"{"channel":"news"}"#).unwrap_err();
+        assert_eq!(rejection.data["errors"][0]["path"], "/action");
+    }
+
+    #[test]
+    fn rejected_message_keeps_the_route() {
+        let message = rejected_message(
+            &json!({"task_id": "t", "progress": 150}),
+            Some("progress"),
+            vec![SchemaViolation::new("/progress", "too large")],
+        );
+
+        assert_eq!(message.event_type, MESSAGE_REJECTED_EVENT);
+        assert_eq!(message.data["task_id"], "t");
+        assert_eq!(message.data["rejected"], "progress");
+        assert_eq!(message.data["errors"][0]["path"], "/progress");
+        assert!(message.data.get("user_id").is_none());
+    }
+}
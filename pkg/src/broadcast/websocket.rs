@@ -4,6 +4,8 @@ use std::collections::HashMap;
 use std::sync::{Arc, OnceLock};
 use tokio::sync::{RwLock, mpsc};
 
+use super::{protocol::WsProtocol, replay};
+
 /// Message structure for broadcasting
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BroadcastMessage {
@@ -11,8 +13,14 @@ pub struct BroadcastMessage {
     pub data: serde_json::Value,
 }
 
+/// Open websocket and the protocol its messages are encoded with
+struct WebSocketConnection {
+    tx: mpsc::UnboundedSender<Message>,
+    protocol: WsProtocol,
+}
+
 /// Type alias for the websocket registry
-type WebSocketRegistry = Arc<RwLock<HashMap<String, mpsc::UnboundedSender<Message>>>>;
+type WebSocketRegistry = Arc<RwLock<HashMap<String, WebSocketConnection>>>;
 
 /// Global registry of task websocket connections
 /// Maps task_id to a channel sender for broadcasting messages
@@ -26,20 +34,14 @@ fn user_registry_key(user_id: i32) -> String {
     format!("user-{}", user_id)
 }
 
-fn serialize_message(message: &BroadcastMessage) -> Option<String> {
-    match serde_json::to_string(message) {
-        Ok(message) => Some(message),
-        Err(error) => {
-            tracing::error!("Failed to serialize broadcast message: {}", error);
-            None
-        }
-    }
-}
-
-/// Register a websocket connection for a task
-pub async fn register_task_websocket(task_id: String, tx: mpsc::UnboundedSender<Message>) {
+/// Register a websocket connection for a task, receiving messages encoded with `protocol`
+pub async fn register_task_websocket(
+    task_id: String,
+    tx: mpsc::UnboundedSender<Message>,
+    protocol: WsProtocol,
+) {
     let mut registry = get_registry().write().await;
-    registry.insert(task_id.clone(), tx);
+    registry.insert(task_id.clone(), WebSocketConnection { tx, protocol });
     tracing::info!("Registered websocket for task_id: {}", task_id);
 }
 
@@ -50,19 +52,24 @@ pub async fn unregister_task_websocket(task_id: String) {
     tracing::info!("Unregistered websocket for task_id: {}", task_id);
 }
 
-/// Broadcast a message to a specific task
+/// Broadcast a message to a specific task, also buffering it for pollers (see `replay`)
 pub async fn broadcast_to_task(task_id: &str, message: BroadcastMessage) {
+    let seq = replay::record(task_id, message.clone());
+    send_to_registered(task_id, message, Some(seq)).await;
+}
+
+async fn send_to_registered(task_id: &str, message: BroadcastMessage, seq: Option<u64>) {
     let registry = get_registry().read().await;
-    let Some(tx) = registry.get(task_id) else {
+    let Some(connection) = registry.get(task_id) else {
         tracing::debug!("No active websocket for task_id: {}", task_id);
         return;
     };
 
-    let Some(json_str) = serialize_message(&message) else {
+    let Some(frame) = connection.protocol.encode(&message, seq) else {
         return;
     };
 
-    if let Err(e) = tx.send(Message::Text(json_str.into())) {
+    if let Err(e) = connection.tx.send(frame) {
         tracing::error!("Failed to send message to task {}: {}", task_id, e);
     } else {
         tracing::debug!("Broadcast message sent to task {}", task_id);
@@ -73,12 +80,11 @@ pub async fn broadcast_to_task(task_id: &str, message: BroadcastMessage) {
 pub async fn broadcast_to_all(message: BroadcastMessage) {
     let registry = get_registry().read().await;
 
-    let Some(json_str) = serialize_message(&message) else {
-        return;
-    };
-
-    for (task_id, tx) in registry.iter() {
-        if let Err(e) = tx.send(Message::Text(json_str.clone().into())) {
+    for (task_id, connection) in registry.iter() {
+        let Some(frame) = connection.protocol.encode(&message, None) else {
+            continue;
+        };
+        if let Err(e) = connection.tx.send(frame) {
             tracing::error!("Failed to broadcast to task {}: {}", task_id, e);
         }
     }
@@ -86,10 +92,19 @@ pub async fn broadcast_to_all(message: BroadcastMessage) {
     tracing::debug!("Broadcast message sent to {} tasks", registry.len());
 }
 
+/// Number of open websocket connections, task and user ones alike
+pub async fn active_connections() -> usize {
+    get_registry().read().await.len()
+}
+
 // Backward compatibility: user_id based functions
 /// Register a websocket connection for a user (backward compatibility)
-pub async fn register_user_websocket(user_id: i32, tx: mpsc::UnboundedSender<Message>) {
-    register_task_websocket(user_registry_key(user_id), tx).await;
+pub async fn register_user_websocket(
+    user_id: i32,
+    tx: mpsc::UnboundedSender<Message>,
+    protocol: WsProtocol,
+) {
+    register_task_websocket(user_registry_key(user_id), tx, protocol).await;
 }
 
 /// Unregister a websocket connection for a user (backward compatibility)
@@ -99,7 +114,7 @@ pub async fn unregister_user_websocket(user_id: i32) {
 
 /// Broadcast a message to a specific user (backward compatibility)
 pub async fn broadcast_to_user(user_id: i32, message: BroadcastMessage) {
-    broadcast_to_task(&user_registry_key(user_id), message).await;
+    send_to_registered(&user_registry_key(user_id), message, None).await;
 }
 
 /// Clear all websocket registrations (for testing only)
@@ -130,7 +145,7 @@ mod tests {
         let task_id = unique_task_id("register-test");
         let (tx, _rx) = mpsc::unbounded_channel();
 
-        register_task_websocket(task_id.clone(), tx).await;
+        register_task_websocket(task_id.clone(), tx, WsProtocol::V1).await;
 
         let registry = get_registry().read().await;
         assert!(registry.contains_key(&task_id));
@@ -147,7 +162,7 @@ mod tests {
         let task_id = unique_task_id("broadcast-success");
         let (tx, mut rx) = mpsc::unbounded_channel();
 
-        register_task_websocket(task_id.clone(), tx).await;
+        register_task_websocket(task_id.clone(), tx, WsProtocol::V1).await;
 
         let message = BroadcastMessage {
             event_type: "progress".to_string(),
@@ -162,6 +177,32 @@ mod tests {
         unregister_task_websocket(task_id).await;
     }
 
+    #[tokio::test]
+    async fn test_broadcast_to_task_encodes_with_connection_protocol() {
+        let task_id = unique_task_id("broadcast-v2");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        register_task_websocket(task_id.clone(), tx, WsProtocol::V2).await;
+
+        for percent in [10, 20] {
+            let message = BroadcastMessage {
+                event_type: "progress".to_string(),
+                data: json!({"percent": percent}),
+            };
+            broadcast_to_task(&task_id, message).await;
+        }
+
+        for seq in [1, 2] {
+            let Some(Message::Text(text)) = rx.recv().await else {
+                panic!("expected a text frame");
+            };
+            let frame: serde_json::Value = serde_json::from_str(text.as_str()).unwrap();
+            assert_eq!(frame["version"], 2);
+            assert_eq!(frame["seq"], seq);
+        }
+
+        unregister_task_websocket(task_id).await;
+    }
+
     #[tokio::test]
     async fn test_broadcast_to_task_no_connection() {
         let task_id = unique_task_id("no-connection");
@@ -183,8 +224,8 @@ mod tests {
         let (tx1, mut rx1) = mpsc::unbounded_channel();
         let (tx2, mut rx2) = mpsc::unbounded_channel();
 
-        register_task_websocket(task_id_a.clone(), tx1).await;
-        register_task_websocket(task_id_b.clone(), tx2).await;
+        register_task_websocket(task_id_a.clone(), tx1, WsProtocol::V1).await;
+        register_task_websocket(task_id_b.clone(), tx2, WsProtocol::V1).await;
 
         let message = BroadcastMessage {
             event_type: "broadcast".to_string(),
@@ -206,7 +247,7 @@ mod tests {
         let expected_key = format!("user-{}", user_id);
 
         let (tx, _rx) = mpsc::unbounded_channel();
-        register_user_websocket(user_id, tx).await;
+        register_user_websocket(user_id, tx, WsProtocol::V1).await;
 
         let registry = get_registry().read().await;
         assert!(registry.contains_key(&expected_key));
@@ -223,7 +264,7 @@ mod tests {
         let user_id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst) as i32 + 20000;
 
         let (tx, mut rx) = mpsc::unbounded_channel();
-        register_user_websocket(user_id, tx).await;
+        register_user_websocket(user_id, tx, WsProtocol::V1).await;
 
         let message = BroadcastMessage {
             event_type: "user_event".to_string(),
@@ -0,0 +1,207 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use crate::redis::{RedisConnection, RedisConnectionManager, RedisPoolConfig};
+
+const LOGIN_FAILURES_KEY_PREFIX: &str = "login:failures:";
+
+/// Failed sign-ins of a subject (a login email or a client IP) within the window of the store
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoginFailures {
+    pub count: u64,
+    pub last_failed_at: Option<DateTime<Utc>>,
+}
+
+/// Counts failed sign-ins over a sliding window, so repeated guesses can be locked out
+#[async_trait]
+pub trait LoginAttemptStore: Send + Sync {
+    /// Record a failed sign-in of `subject`, returning its failures including this one
+    async fn record_failure(&self, subject: &str) -> Result<LoginFailures>;
+
+    /// Failures of `subject` within the window
+    async fn failures(&self, subject: &str) -> Result<LoginFailures>;
+
+    /// Forget the failures of `subject`, e.g. once it signed in
+    async fn clear(&self, subject: &str) -> Result<()>;
+}
+
+/// Drops failures older than the window, adds this one and counts the rest in one step, so
+/// concurrent guesses are all counted
+const RECORD_SCRIPT: &str = r"
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', ARGV[1])
+redis.call('ZADD', KEYS[1], ARGV[2], ARGV[3])
+redis.call('PEXPIRE', KEYS[1], ARGV[4])
+return redis.call('ZCARD', KEYS[1])
+";
+
+fn failures_key(subject: &str) -> String {
+    format!("{}{}", LOGIN_FAILURES_KEY_PREFIX, subject)
+}
+
+/// Failed sign-ins shared by every server instance through Redis
+#[derive(Clone)]
+pub struct RedisLoginAttemptStore {
+    connection: RedisConnection,
+    window: Duration,
+}
+
+impl RedisLoginAttemptStore {
+    pub async fn new(redis_url: &str, window: Duration) -> Result<Self> {
+        let manager =
+            RedisConnectionManager::shared(redis_url, &RedisPoolConfig::default()).await?;
+        Ok(Self::from_manager(&manager, window))
+    }
+
+    pub fn from_manager(manager: &RedisConnectionManager, window: Duration) -> Self {
+        Self {
+            connection: manager.connection(),
+            window,
+        }
+    }
+
+    fn window_millis(&self) -> i64 {
+        self.window.as_millis().max(1) as i64
+    }
+}
+
+#[async_trait]
+impl LoginAttemptStore for RedisLoginAttemptStore {
+    async fn record_failure(&self, subject: &str) -> Result<LoginFailures> {
+        let mut connection = self.connection.clone();
+        let now = Utc::now();
+
+        let count: u64 = redis::Script::new(RECORD_SCRIPT)
+            .key(failures_key(subject))
+            .arg(now.timestamp_millis() - self.window_millis())
+            .arg(now.timestamp_millis())
+            .arg(uuid::Uuid::new_v4().to_string())
+            .arg(self.window_millis())
+            .invoke_async(&mut connection)
+            .await
+            .context("Failed to record failed sign-in in Redis")?;
+
+        Ok(LoginFailures {
+            count,
+            last_failed_at: Some(now),
+        })
+    }
+
+    async fn failures(&self, subject: &str) -> Result<LoginFailures> {
+        let mut connection = self.connection.clone();
+        let key = failures_key(subject);
+        let since = Utc::now().timestamp_millis() - self.window_millis();
+
+        let (count, last): (u64, Vec<(String, f64)>) = redis::pipe()
+            .cmd("ZCOUNT")
+            .arg(&key)
+            .arg(format!("({}", since))
+            .arg("+inf")
+            .cmd("ZRANGE")
+            .arg(&key)
+            .arg(-1)
+            .arg(-1)
+            .arg("WITHSCORES")
+            .query_async(&mut connection)
+            .await
+            .context("Failed to read failed sign-ins from Redis")?;
+
+        Ok(LoginFailures {
+            count,
+            last_failed_at: last
+                .first()
+                .filter(|_| count > 0)
+                .and_then(|(_, score)| Utc.timestamp_millis_opt(*score as i64).single()),
+        })
+    }
+
+    async fn clear(&self, subject: &str) -> Result<()> {
+        let mut connection = self.connection.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(failures_key(subject))
+            .query_async(&mut connection)
+            .await
+            .context("Failed to clear failed sign-ins in Redis")?;
+        Ok(())
+    }
+}
+
+/// Process-local failed sign-ins, for single-instance development and tests
+pub struct InMemoryLoginAttemptStore {
+    failures: Mutex<HashMap<String, Vec<DateTime<Utc>>>>,
+    window: Duration,
+}
+
+impl InMemoryLoginAttemptStore {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            failures: Mutex::default(),
+            window,
+        }
+    }
+
+    fn summarize(&self, failures: &mut Vec<DateTime<Utc>>) -> LoginFailures {
+        let since = Utc::now() - TimeDelta::from_std(self.window).unwrap_or_default();
+        failures.retain(|failed_at| *failed_at > since);
+        LoginFailures {
+            count: failures.len() as u64,
+            last_failed_at: failures.last().copied(),
+        }
+    }
+}
+
+#[async_trait]
+impl LoginAttemptStore for InMemoryLoginAttemptStore {
+    async fn record_failure(&self, subject: &str) -> Result<LoginFailures> {
+        let mut failures = self.failures.lock().unwrap();
+        let subject_failures = failures.entry(subject.to_string()).or_default();
+        subject_failures.push(Utc::now());
+        Ok(self.summarize(subject_failures))
+    }
+
+    async fn failures(&self, subject: &str) -> Result<LoginFailures> {
+        let mut failures = self.failures.lock().unwrap();
+        Ok(failures
+            .get_mut(subject)
+            .map(|subject_failures| self.summarize(subject_failures))
+            .unwrap_or_default())
+    }
+
+    async fn clear(&self, subject: &str) -> Result<()> {
+        self.failures.lock().unwrap().remove(subject);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{InMemoryLoginAttemptStore, LoginAttemptStore};
+
+    #[tokio::test]
+    async fn in_memory_counts_failures_per_subject() {
+        let store = InMemoryLoginAttemptStore::new(Duration::from_secs(60));
+
+        store.record_failure("user:1").await.unwrap();
+        let failures = store.record_failure("user:1").await.unwrap();
+        assert_eq!(failures.count, 2);
+        assert!(failures.last_failed_at.is_some());
+        assert_eq!(store.failures("user:1").await.unwrap(), failures);
+        assert_eq!(store.failures("user:2").await.unwrap().count, 0);
+
+        store.clear("user:1").await.unwrap();
+        assert_eq!(store.failures("user:1").await.unwrap().count, 0);
+    }
+
+    #[tokio::test]
+    async fn in_memory_failures_expire() {
+        let store = InMemoryLoginAttemptStore::new(Duration::ZERO);
+
+        store.record_failure("ip:10.0.0.1").await.unwrap();
+        let failures = store.failures("ip:10.0.0.1").await.unwrap();
+        assert_eq!(failures.count, 0);
+        assert_eq!(failures.last_failed_at, None);
+    }
+}
@@ -1,3 +1,19 @@
+mod login_attempts;
+mod nonce_store;
+mod response_cache;
 mod task_cache;
+mod task_dedup;
+mod task_quota;
 
+pub use login_attempts::{
+    InMemoryLoginAttemptStore, LoginAttemptStore, LoginFailures, RedisLoginAttemptStore,
+};
+pub use nonce_store::{InMemoryNonceStore, NonceStore, RedisNonceStore};
+pub use response_cache::{InMemoryResponseCache, RedisResponseCache, ResponseCache};
 pub use task_cache::{TaskStatusCache, cache_task_status, get_cached_task_status};
+pub use task_dedup::{
+    InMemoryTaskDeduplicator, RedisTaskDeduplicator, TaskDeduplicator, task_dedup_key,
+};
+pub use task_quota::{
+    InMemoryTaskQuota, QuotaDecision, QuotaLimits, QuotaUsage, RedisTaskQuota, TaskQuota,
+};
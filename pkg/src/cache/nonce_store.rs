@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::redis::{RedisConnection, RedisConnectionManager};
+
+const NONCE_KEY_PREFIX: &str = "request:nonce:";
+
+/// Remembers the nonces of signed requests, so a captured request can't be replayed
+#[async_trait]
+pub trait NonceStore: Send + Sync {
+    /// Record `nonce` for `ttl`. Returns `false` when it was already recorded.
+    async fn claim(&self, nonce: &str, ttl: Duration) -> Result<bool>;
+}
+
+/// Nonces shared by every server instance through Redis
+#[derive(Clone)]
+pub struct RedisNonceStore {
+    connection: RedisConnection,
+}
+
+impl RedisNonceStore {
+    pub fn from_manager(manager: &RedisConnectionManager) -> Self {
+        Self {
+            connection: manager.connection(),
+        }
+    }
+}
+
+#[async_trait]
+impl NonceStore for RedisNonceStore {
+    async fn claim(&self, nonce: &str, ttl: Duration) -> Result<bool> {
+        let mut connection = self.connection.clone();
+        let claimed: Option<String> = redis::cmd("SET")
+            .arg(format!("{}{}", NONCE_KEY_PREFIX, nonce))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async(&mut connection)
+            .await
+            .context("Failed to record request nonce in Redis")?;
+        Ok(claimed.is_some())
+    }
+}
+
+/// Process-local nonces, for single-instance deployments and tests
+#[derive(Default)]
+pub struct InMemoryNonceStore {
+    nonces: Mutex<HashMap<String, Instant>>,
+}
+
+impl InMemoryNonceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl NonceStore for InMemoryNonceStore {
+    async fn claim(&self, nonce: &str, ttl: Duration) -> Result<bool> {
+        let now = Instant::now();
+        let mut nonces = self.nonces.lock().unwrap();
+        nonces.retain(|_, expires_at| *expires_at > now);
+        if nonces.contains_key(nonce) {
+            return Ok(false);
+        }
+
+        nonces.insert(nonce.to_string(), now + ttl);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{InMemoryNonceStore, NonceStore};
+
+    #[tokio::test]
+    async fn claims_each_nonce_once_until_it_expires() {
+        let store = InMemoryNonceStore::new();
+
+        assert!(store.claim("a", Duration::from_secs(60)).await.unwrap());
+        assert!(!store.claim("a", Duration::from_secs(60)).await.unwrap());
+        assert!(store.claim("b", Duration::from_secs(60)).await.unwrap());
+        assert!(store.claim("c", Duration::ZERO).await.unwrap());
+        assert!(store.claim("c", Duration::ZERO).await.unwrap());
+    }
+}
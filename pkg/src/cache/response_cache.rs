@@ -0,0 +1,191 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::redis::{RedisConnection, RedisConnectionManager, RedisPoolConfig};
+
+const RESPONSE_KEY_PREFIX: &str = "response:";
+const RESPONSE_TAG_KEY_PREFIX: &str = "response:tag:";
+
+/// Store for cached HTTP response bodies. Every entry belongs to a tag (e.g. `user`) so
+/// all responses built from the same data can be dropped at once when it changes.
+#[async_trait]
+pub trait ResponseCache: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    async fn set(&self, tag: &str, key: &str, body: &[u8], ttl: Duration) -> Result<()>;
+
+    /// Drop every entry stored under `tag`
+    async fn invalidate(&self, tag: &str) -> Result<()>;
+}
+
+/// Response cache shared by every server instance through Redis
+#[derive(Clone)]
+pub struct RedisResponseCache {
+    connection: RedisConnection,
+}
+
+impl RedisResponseCache {
+    pub async fn new(redis_url: &str) -> Result<Self> {
+        let manager =
+            RedisConnectionManager::shared(redis_url, &RedisPoolConfig::default()).await?;
+        Ok(Self::from_manager(&manager))
+    }
+
+    pub fn from_manager(manager: &RedisConnectionManager) -> Self {
+        Self {
+            connection: manager.connection(),
+        }
+    }
+}
+
+#[async_trait]
+impl ResponseCache for RedisResponseCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut connection = self.connection.clone();
+        redis::cmd("GET")
+            .arg(format!("{}{}", RESPONSE_KEY_PREFIX, key))
+            .query_async(&mut connection)
+            .await
+            .context("Failed to get cached response from Redis")
+    }
+
+    async fn set(&self, tag: &str, key: &str, body: &[u8], ttl: Duration) -> Result<()> {
+        let mut connection = self.connection.clone();
+        let key = format!("{}{}", RESPONSE_KEY_PREFIX, key);
+        let tag_key = format!("{}{}", RESPONSE_TAG_KEY_PREFIX, tag);
+        let ttl = ttl.as_secs().max(1);
+
+        // The tag set outlives its newest entry, so invalidation always sees every key
+        let _: () = redis::pipe()
+            .atomic()
+            .cmd("SET")
+            .arg(&key)
+            .arg(body)
+            .arg("EX")
+            .arg(ttl)
+            .ignore()
+            .cmd("SADD")
+            .arg(&tag_key)
+            .arg(&key)
+            .ignore()
+            .cmd("EXPIRE")
+            .arg(&tag_key)
+            .arg(ttl)
+            .ignore()
+            .query_async(&mut connection)
+            .await
+            .context("Failed to cache response in Redis")?;
+        Ok(())
+    }
+
+    async fn invalidate(&self, tag: &str) -> Result<()> {
+        let mut connection = self.connection.clone();
+        let tag_key = format!("{}{}", RESPONSE_TAG_KEY_PREFIX, tag);
+
+        let keys: Vec<String> = redis::cmd("SMEMBERS")
+            .arg(&tag_key)
+            .query_async(&mut connection)
+            .await
+            .context("Failed to read cached responses from Redis")?;
+
+        let _: () = redis::cmd("DEL")
+            .arg(&keys)
+            .arg(&tag_key)
+            .query_async(&mut connection)
+            .await
+            .context("Failed to invalidate cached responses in Redis")?;
+
+        tracing::debug!("Invalidated {} cached response(s) for {}", keys.len(), tag);
+        Ok(())
+    }
+}
+
+/// Tag, body and expiry of an in-memory entry
+type InMemoryEntry = (String, Vec<u8>, Instant);
+
+/// Process-local response cache, for single-instance development and tests
+#[derive(Default)]
+pub struct InMemoryResponseCache {
+    entries: Mutex<HashMap<String, InMemoryEntry>>,
+}
+
+#[async_trait]
+impl ResponseCache for InMemoryResponseCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((_, body, expires_at)) if *expires_at > Instant::now() => Ok(Some(body.clone())),
+            Some(_) => {
+                entries.remove(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, tag: &str, key: &str, body: &[u8], ttl: Duration) -> Result<()> {
+        self.entries.lock().unwrap().insert(
+            key.to_string(),
+            (tag.to_string(), body.to_vec(), Instant::now() + ttl),
+        );
+        Ok(())
+    }
+
+    async fn invalidate(&self, tag: &str) -> Result<()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|_, (entry_tag, _, _)| entry_tag != tag);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{InMemoryResponseCache, ResponseCache};
+
+    #[tokio::test]
+    async fn in_memory_cache_returns_fresh_entries() {
+        let cache = InMemoryResponseCache::default();
+
+        cache
+            .set("user", "a", b"body", Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        assert_eq!(cache.get("a").await.unwrap(), Some(b"body".to_vec()));
+        assert_eq!(cache.get("b").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn in_memory_cache_expires_entries() {
+        let cache = InMemoryResponseCache::default();
+
+        cache
+            .set("user", "a", b"body", Duration::ZERO)
+            .await
+            .unwrap();
+
+        assert_eq!(cache.get("a").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn in_memory_cache_invalidates_by_tag() {
+        let cache = InMemoryResponseCache::default();
+        let ttl = Duration::from_secs(60);
+        cache.set("user", "a", b"1", ttl).await.unwrap();
+        cache.set("file", "b", b"2", ttl).await.unwrap();
+
+        cache.invalidate("user").await.unwrap();
+
+        assert_eq!(cache.get("a").await.unwrap(), None);
+        assert_eq!(cache.get("b").await.unwrap(), Some(b"2".to_vec()));
+    }
+}
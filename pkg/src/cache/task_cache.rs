@@ -1,25 +1,33 @@
 use anyhow::{Context, Result};
-use redis::{Client, aio::MultiplexedConnection};
 use serde::{Serialize, de::DeserializeOwned};
 
+use crate::redis::{RedisConnection, RedisConnectionManager, RedisPoolConfig};
+
 const TASK_STATUS_KEY_PREFIX: &str = "task:status:";
 const TASK_STATUS_TTL: i64 = 3600; // 1 hour TTL
 
 /// Helper struct for caching task status in Redis
 pub struct TaskStatusCache {
-    connection: MultiplexedConnection,
+    connection: RedisConnection,
 }
 
 impl TaskStatusCache {
-    /// Create a new TaskStatusCache
+    /// Create a new TaskStatusCache on the process-wide connection for `redis_url`.
+    /// Caching is best effort, so when that connection isn't open yet an unreachable Redis
+    /// fails right away instead of being retried.
     pub async fn new(redis_url: &str) -> Result<Self> {
-        let client = Client::open(redis_url).context("Failed to create Redis client")?;
-        let connection = client
-            .get_multiplexed_async_connection()
-            .await
-            .context("Failed to connect to Redis")?;
+        let config = RedisPoolConfig {
+            max_retries: 0,
+            ..Default::default()
+        };
+        let manager = RedisConnectionManager::shared(redis_url, &config).await?;
+        Ok(Self::from_manager(&manager))
+    }
 
-        Ok(Self { connection })
+    pub fn from_manager(manager: &RedisConnectionManager) -> Self {
+        Self {
+            connection: manager.connection(),
+        }
     }
 
     /// Cache task status in Redis
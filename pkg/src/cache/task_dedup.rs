@@ -0,0 +1,172 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::redis::{RedisConnection, RedisConnectionManager, RedisPoolConfig};
+
+const TASK_DEDUP_KEY_PREFIX: &str = "task:dedup:";
+
+/// Remembers the task a user action started, so the same action repeated within a window
+/// (e.g. a double-clicked upload) is answered with that task instead of enqueueing another
+#[async_trait]
+pub trait TaskDeduplicator: Send + Sync {
+    /// Record `task_id` under `key` for the window of the store, unless a task already holds
+    /// the key. Returns the id of that earlier task.
+    async fn claim(&self, key: &str, task_id: &str) -> Result<Option<String>>;
+
+    /// Forget `key`, e.g. when the task it points to could not be enqueued
+    async fn release(&self, key: &str) -> Result<()>;
+}
+
+/// Key of an action of `user_id` enqueueing a `task_type` task for `payload`
+pub fn task_dedup_key(user_id: i32, task_type: &str, payload: &[u8]) -> String {
+    format!(
+        "{}:{}:{}",
+        user_id,
+        task_type,
+        hex::encode(Sha256::digest(payload))
+    )
+}
+
+/// Task deduplication shared by every server instance through Redis
+#[derive(Clone)]
+pub struct RedisTaskDeduplicator {
+    connection: RedisConnection,
+    window: Duration,
+}
+
+impl RedisTaskDeduplicator {
+    pub async fn new(redis_url: &str, window: Duration) -> Result<Self> {
+        let manager =
+            RedisConnectionManager::shared(redis_url, &RedisPoolConfig::default()).await?;
+        Ok(Self::from_manager(&manager, window))
+    }
+
+    pub fn from_manager(manager: &RedisConnectionManager, window: Duration) -> Self {
+        Self {
+            connection: manager.connection(),
+            window,
+        }
+    }
+}
+
+#[async_trait]
+impl TaskDeduplicator for RedisTaskDeduplicator {
+    async fn claim(&self, key: &str, task_id: &str) -> Result<Option<String>> {
+        let mut connection = self.connection.clone();
+        let key = format!("{}{}", TASK_DEDUP_KEY_PREFIX, key);
+
+        let claimed: Option<String> = redis::cmd("SET")
+            .arg(&key)
+            .arg(task_id)
+            .arg("NX")
+            .arg("EX")
+            .arg(self.window.as_secs().max(1))
+            .query_async(&mut connection)
+            .await
+            .context("Failed to claim task in Redis")?;
+        if claimed.is_some() {
+            return Ok(None);
+        }
+
+        redis::cmd("GET")
+            .arg(&key)
+            .query_async(&mut connection)
+            .await
+            .context("Failed to read claimed task from Redis")
+    }
+
+    async fn release(&self, key: &str) -> Result<()> {
+        let mut connection = self.connection.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(format!("{}{}", TASK_DEDUP_KEY_PREFIX, key))
+            .query_async(&mut connection)
+            .await
+            .context("Failed to release task claim in Redis")?;
+        Ok(())
+    }
+}
+
+/// Process-local task deduplication, for single-instance development and tests
+pub struct InMemoryTaskDeduplicator {
+    claims: Mutex<HashMap<String, (String, Instant)>>,
+    window: Duration,
+}
+
+impl InMemoryTaskDeduplicator {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            claims: Mutex::default(),
+            window,
+        }
+    }
+}
+
+#[async_trait]
+impl TaskDeduplicator for InMemoryTaskDeduplicator {
+    async fn claim(&self, key: &str, task_id: &str) -> Result<Option<String>> {
+        let mut claims = self.claims.lock().unwrap();
+        match claims.get(key) {
+            Some((existing, expires_at)) if *expires_at > Instant::now() => {
+                Ok(Some(existing.clone()))
+            }
+            _ => {
+                claims.insert(
+                    key.to_string(),
+                    (task_id.to_string(), Instant::now() + self.window),
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    async fn release(&self, key: &str) -> Result<()> {
+        self.claims.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{InMemoryTaskDeduplicator, TaskDeduplicator, task_dedup_key};
+
+    #[test]
+    fn keys_actions_by_user_type_and_payload() {
+        let key = task_dedup_key(7, "ProcessAvatarUpload", b"a.png");
+
+        assert!(key.starts_with("7:ProcessAvatarUpload:"));
+        assert_eq!(key, task_dedup_key(7, "ProcessAvatarUpload", b"a.png"));
+        assert_ne!(key, task_dedup_key(8, "ProcessAvatarUpload", b"a.png"));
+        assert_ne!(key, task_dedup_key(7, "ProcessAvatarUpload", b"b.png"));
+    }
+
+    #[tokio::test]
+    async fn in_memory_returns_task_claimed_within_window() {
+        let dedup = InMemoryTaskDeduplicator::new(Duration::from_secs(60));
+
+        assert_eq!(dedup.claim("k", "task-1").await.unwrap(), None);
+        assert_eq!(
+            dedup.claim("k", "task-2").await.unwrap(),
+            Some("task-1".to_string())
+        );
+        assert_eq!(dedup.claim("other", "task-3").await.unwrap(), None);
+
+        dedup.release("k").await.unwrap();
+        assert_eq!(dedup.claim("k", "task-4").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn in_memory_claims_expire() {
+        let dedup = InMemoryTaskDeduplicator::new(Duration::ZERO);
+
+        dedup.claim("k", "task-1").await.unwrap();
+        assert_eq!(dedup.claim("k", "task-2").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,353 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::redis::{RedisConnection, RedisConnectionManager, RedisPoolConfig};
+
+const TASK_QUOTA_KEY_PREFIX: &str = "task:quota:";
+
+/// How long a running slot is held when its task never reports finishing, e.g. because the
+/// worker crashed
+const RUNNING_LEASE: Duration = Duration::from_secs(60 * 60);
+
+/// Daily counters are kept a day longer than needed so the stats of a day stay readable
+const DAILY_TTL_SECONDS: u64 = 2 * 24 * 60 * 60;
+
+/// Limits on the tasks a single user has enqueued; `0` lifts a limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuotaLimits {
+    /// Tasks of a user enqueued or running at the same time
+    pub max_running: u64,
+    /// Tasks a user may enqueue per UTC day
+    pub max_daily: u64,
+}
+
+/// Outcome of asking for a slot for a new task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaDecision {
+    Granted,
+    /// The user already has `max_running` tasks that haven't finished
+    RunningExceeded,
+    /// The user already enqueued `max_daily` tasks today
+    DailyExceeded,
+}
+
+/// Slots a user holds, as reported by `/api/v1/admin/stats/`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub user_id: i32,
+    pub running: u64,
+    pub enqueued_today: u64,
+}
+
+/// Counts the tasks each user has enqueued, so one user can't flood the workers
+#[async_trait]
+pub trait TaskQuota: Send + Sync {
+    fn limits(&self) -> QuotaLimits;
+
+    /// Take a running slot and a daily slot of `user_id` for `task_id`, unless either
+    /// limit is reached
+    async fn acquire(&self, user_id: i32, task_id: &str) -> Result<QuotaDecision>;
+
+    /// Free the running slot of `task_id`, once it finished or could not be enqueued.
+    /// Tasks that hold no slot are ignored.
+    async fn release(&self, task_id: &str) -> Result<()>;
+
+    /// Usage of every user who enqueued a task today, by user id
+    async fn usage(&self) -> Result<Vec<QuotaUsage>>;
+}
+
+fn running_key(user_id: i32) -> String {
+    format!("{}running:{}", TASK_QUOTA_KEY_PREFIX, user_id)
+}
+
+fn daily_key(user_id: i32, day: NaiveDate) -> String {
+    format!("{}daily:{}:{}", TASK_QUOTA_KEY_PREFIX, user_id, day)
+}
+
+fn users_key(day: NaiveDate) -> String {
+    format!("{}users:{}", TASK_QUOTA_KEY_PREFIX, day)
+}
+
+fn task_key(task_id: &str) -> String {
+    format!("{}task:{}", TASK_QUOTA_KEY_PREFIX, task_id)
+}
+
+/// Checks both limits and takes both slots in one step, so concurrent requests of a user
+/// can't overshoot them
+const ACQUIRE_SCRIPT: &str = r"
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', ARGV[1])
+if tonumber(ARGV[3]) > 0 and redis.call('ZCARD', KEYS[1]) >= tonumber(ARGV[3]) then
+    return 1
+end
+if tonumber(ARGV[4]) > 0 and tonumber(redis.call('GET', KEYS[2]) or '0') >= tonumber(ARGV[4]) then
+    return 2
+end
+redis.call('ZADD', KEYS[1], ARGV[2], ARGV[5])
+redis.call('EXPIRE', KEYS[1], ARGV[7])
+redis.call('INCR', KEYS[2])
+redis.call('EXPIRE', KEYS[2], ARGV[8])
+redis.call('SADD', KEYS[3], ARGV[6])
+redis.call('EXPIRE', KEYS[3], ARGV[8])
+redis.call('SET', KEYS[4], ARGV[6], 'EX', ARGV[7])
+return 0
+";
+
+/// Task quotas shared by every server instance and worker through Redis
+#[derive(Clone)]
+pub struct RedisTaskQuota {
+    connection: RedisConnection,
+    limits: QuotaLimits,
+}
+
+impl RedisTaskQuota {
+    pub async fn new(redis_url: &str, limits: QuotaLimits) -> Result<Self> {
+        let manager =
+            RedisConnectionManager::shared(redis_url, &RedisPoolConfig::default()).await?;
+        Ok(Self::from_manager(&manager, limits))
+    }
+
+    pub fn from_manager(manager: &RedisConnectionManager, limits: QuotaLimits) -> Self {
+        Self {
+            connection: manager.connection(),
+            limits,
+        }
+    }
+}
+
+#[async_trait]
+impl TaskQuota for RedisTaskQuota {
+    fn limits(&self) -> QuotaLimits {
+        self.limits
+    }
+
+    async fn acquire(&self, user_id: i32, task_id: &str) -> Result<QuotaDecision> {
+        let mut connection = self.connection.clone();
+        let now = Utc::now();
+        let today = now.date_naive();
+
+        let outcome: u8 = redis::Script::new(ACQUIRE_SCRIPT)
+            .key(running_key(user_id))
+            .key(daily_key(user_id, today))
+            .key(users_key(today))
+            .key(task_key(task_id))
+            .arg(now.timestamp())
+            .arg(now.timestamp() + RUNNING_LEASE.as_secs() as i64)
+            .arg(self.limits.max_running)
+            .arg(self.limits.max_daily)
+            .arg(task_id)
+            .arg(user_id)
+            .arg(RUNNING_LEASE.as_secs())
+            .arg(DAILY_TTL_SECONDS)
+            .invoke_async(&mut connection)
+            .await
+            .context("Failed to acquire task quota in Redis")?;
+
+        Ok(match outcome {
+            1 => QuotaDecision::RunningExceeded,
+            2 => QuotaDecision::DailyExceeded,
+            _ => QuotaDecision::Granted,
+        })
+    }
+
+    async fn release(&self, task_id: &str) -> Result<()> {
+        let mut connection = self.connection.clone();
+        let key = task_key(task_id);
+
+        let user_id: Option<i32> = redis::cmd("GET")
+            .arg(&key)
+            .query_async(&mut connection)
+            .await
+            .context("Failed to read task quota slot from Redis")?;
+        let Some(user_id) = user_id else {
+            return Ok(());
+        };
+
+        let _: () = redis::pipe()
+            .atomic()
+            .cmd("ZREM")
+            .arg(running_key(user_id))
+            .arg(task_id)
+            .ignore()
+            .cmd("DEL")
+            .arg(&key)
+            .ignore()
+            .query_async(&mut connection)
+            .await
+            .context("Failed to release task quota slot in Redis")?;
+        Ok(())
+    }
+
+    async fn usage(&self) -> Result<Vec<QuotaUsage>> {
+        let mut connection = self.connection.clone();
+        let now = Utc::now();
+        let today = now.date_naive();
+
+        let mut user_ids: Vec<i32> = redis::cmd("SMEMBERS")
+            .arg(users_key(today))
+            .query_async(&mut connection)
+            .await
+            .context("Failed to read task quota users from Redis")?;
+        user_ids.sort_unstable();
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut pipe = redis::pipe();
+        for &user_id in &user_ids {
+            pipe.cmd("ZCOUNT")
+                .arg(running_key(user_id))
+                .arg(format!("({}", now.timestamp()))
+                .arg("+inf")
+                .cmd("GET")
+                .arg(daily_key(user_id, today));
+        }
+        let counts: Vec<Option<u64>> = pipe
+            .query_async(&mut connection)
+            .await
+            .context("Failed to read task quota usage from Redis")?;
+
+        Ok(user_ids
+            .into_iter()
+            .zip(counts.chunks(2))
+            .map(|(user_id, counts)| QuotaUsage {
+                user_id,
+                running: counts[0].unwrap_or(0),
+                enqueued_today: counts[1].unwrap_or(0),
+            })
+            .collect())
+    }
+}
+
+/// Process-local task quotas, for single-instance development and tests
+pub struct InMemoryTaskQuota {
+    limits: QuotaLimits,
+    /// Owner and lease expiry of every running slot, by task id
+    running: Mutex<HashMap<String, (i32, Instant)>>,
+    daily: Mutex<HashMap<(i32, NaiveDate), u64>>,
+}
+
+impl InMemoryTaskQuota {
+    pub fn new(limits: QuotaLimits) -> Self {
+        Self {
+            limits,
+            running: Mutex::default(),
+            daily: Mutex::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl TaskQuota for InMemoryTaskQuota {
+    fn limits(&self) -> QuotaLimits {
+        self.limits
+    }
+
+    async fn acquire(&self, user_id: i32, task_id: &str) -> Result<QuotaDecision> {
+        let mut running = self.running.lock().unwrap();
+        let mut daily = self.daily.lock().unwrap();
+        let now = Instant::now();
+        running.retain(|_, (_, expires_at)| *expires_at > now);
+
+        let held = running.values().filter(|(owner, _)| *owner == user_id);
+        if self.limits.max_running > 0 && held.count() as u64 >= self.limits.max_running {
+            return Ok(QuotaDecision::RunningExceeded);
+        }
+        let enqueued = daily.entry((user_id, Utc::now().date_naive())).or_default();
+        if self.limits.max_daily > 0 && *enqueued >= self.limits.max_daily {
+            return Ok(QuotaDecision::DailyExceeded);
+        }
+
+        *enqueued += 1;
+        running.insert(task_id.to_string(), (user_id, now + RUNNING_LEASE));
+        Ok(QuotaDecision::Granted)
+    }
+
+    async fn release(&self, task_id: &str) -> Result<()> {
+        self.running.lock().unwrap().remove(task_id);
+        Ok(())
+    }
+
+    async fn usage(&self) -> Result<Vec<QuotaUsage>> {
+        let running = self.running.lock().unwrap();
+        let daily = self.daily.lock().unwrap();
+        let (now, today) = (Instant::now(), Utc::now().date_naive());
+
+        let mut usage: BTreeMap<i32, QuotaUsage> = BTreeMap::new();
+        for (&(user_id, day), &enqueued_today) in daily.iter() {
+            if day == today {
+                usage.entry(user_id).or_default().enqueued_today = enqueued_today;
+            }
+        }
+        for &(user_id, expires_at) in running.values() {
+            if expires_at > now {
+                usage.entry(user_id).or_default().running += 1;
+            }
+        }
+        Ok(usage
+            .into_iter()
+            .map(|(user_id, usage)| QuotaUsage { user_id, ..usage })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InMemoryTaskQuota, QuotaDecision, QuotaLimits, QuotaUsage, TaskQuota};
+
+    #[tokio::test]
+    async fn in_memory_limits_running_tasks_until_released() {
+        let quota = InMemoryTaskQuota::new(QuotaLimits {
+            max_running: 1,
+            max_daily: 0,
+        });
+
+        assert_eq!(
+            quota.acquire(1, "task-1").await.unwrap(),
+            QuotaDecision::Granted
+        );
+        assert_eq!(
+            quota.acquire(1, "task-2").await.unwrap(),
+            QuotaDecision::RunningExceeded
+        );
+        assert_eq!(
+            quota.acquire(2, "task-3").await.unwrap(),
+            QuotaDecision::Granted
+        );
+
+        quota.release("task-1").await.unwrap();
+        assert_eq!(
+            quota.acquire(1, "task-4").await.unwrap(),
+            QuotaDecision::Granted
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_limits_tasks_per_day_and_reports_usage() {
+        let quota = InMemoryTaskQuota::new(QuotaLimits {
+            max_running: 0,
+            max_daily: 2,
+        });
+        quota.acquire(1, "task-1").await.unwrap();
+        quota.acquire(1, "task-2").await.unwrap();
+        quota.release("task-1").await.unwrap();
+
+        assert_eq!(
+            quota.acquire(1, "task-3").await.unwrap(),
+            QuotaDecision::DailyExceeded
+        );
+        assert_eq!(
+            quota.usage().await.unwrap(),
+            vec![QuotaUsage {
+                user_id: 1,
+                running: 1,
+                enqueued_today: 2,
+            }]
+        );
+    }
+}
@@ -0,0 +1,473 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{Display, Write},
+    future::Future,
+    sync::{Arc, LazyLock, Mutex, RwLock},
+    time::Duration,
+};
+use tokio::time::Instant;
+
+/// Calls a breaker remembers to compute the health score of its dependency
+const SCORE_WINDOW: usize = 20;
+
+/// Policy of the breakers created after the last [`configure`]
+static POLICY: LazyLock<RwLock<BreakerPolicy>> =
+    LazyLock::new(|| RwLock::new(BreakerPolicy::default()));
+
+/// Breakers of every outbound dependency of this process, by name
+static BREAKERS: LazyLock<Mutex<HashMap<String, CircuitBreaker>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// When a breaker stops calling its dependency and for how long
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakerPolicy {
+    /// Failures in a row that open the breaker, `0` to never open it
+    pub failure_threshold: u32,
+    /// How long an open breaker fails calls fast before letting a probe through
+    pub open_duration: Duration,
+}
+
+impl Default for BreakerPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    /// Calls go through
+    Closed,
+    /// Calls fail fast with [`CircuitOpenError`] without reaching the dependency
+    Open,
+    /// A single probe call is let through; its outcome closes or reopens the breaker
+    HalfOpen,
+}
+
+impl BreakerState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+
+    /// Value of the `dependency_circuit_state` gauge
+    pub fn as_gauge(&self) -> u8 {
+        match self {
+            Self::Closed => 0,
+            Self::HalfOpen => 1,
+            Self::Open => 2,
+        }
+    }
+}
+
+/// Point-in-time view of a breaker, as reported in metrics and readiness checks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakerSnapshot {
+    pub name: String,
+    pub state: BreakerState,
+    /// Share of the last calls that succeeded, from `0.0` to `1.0` (`1.0` before any call)
+    pub health_score: f64,
+    pub consecutive_failures: u32,
+    /// Times the breaker opened since the process started
+    pub opened_total: u64,
+    /// Calls failed fast while the breaker was open
+    pub rejected_total: u64,
+}
+
+/// Returned instead of calling a dependency whose breaker is open
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitOpenError {
+    pub name: String,
+}
+
+impl Display for CircuitOpenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Circuit breaker {} is open", self.name)
+    }
+}
+
+impl std::error::Error for CircuitOpenError {}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: BreakerState,
+    consecutive_failures: u32,
+    /// When the breaker last opened or let its half-open probe through
+    changed_at: Instant,
+    outcomes: VecDeque<bool>,
+    opened_total: u64,
+    rejected_total: u64,
+}
+
+/// Stops calling a dependency after `failure_threshold` failures in a row, so callers fail
+/// fast instead of waiting on timeouts. After `open_duration` one probe call is let through
+/// and closes the breaker again when it succeeds. Clones share their state.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    name: String,
+    policy: BreakerPolicy,
+    inner: Arc<Mutex<BreakerInner>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: impl Into<String>, policy: BreakerPolicy) -> Self {
+        Self {
+            name: name.into(),
+            policy,
+            inner: Arc::new(Mutex::new(BreakerInner {
+                state: BreakerState::Closed,
+                consecutive_failures: 0,
+                changed_at: Instant::now(),
+                outcomes: VecDeque::with_capacity(SCORE_WINDOW),
+                opened_total: 0,
+                rejected_total: 0,
+            })),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Ask to call the dependency. Each successful acquire must be followed by a
+    /// [`record`](Self::record) of the call's outcome.
+    pub fn try_acquire(&self) -> Result<(), CircuitOpenError> {
+        let mut inner = self.inner.lock().unwrap();
+        let waited_enough = inner.changed_at.elapsed() >= self.policy.open_duration;
+        match inner.state {
+            BreakerState::Closed => return Ok(()),
+            // The probe is let through once per `open_duration`, so a probe whose caller was
+            // cancelled does not keep the breaker half-open forever
+            BreakerState::Open | BreakerState::HalfOpen if waited_enough => {
+                inner.state = BreakerState::HalfOpen;
+                inner.changed_at = Instant::now();
+                return Ok(());
+            }
+            BreakerState::Open | BreakerState::HalfOpen => {}
+        }
+
+        inner.rejected_total += 1;
+        Err(CircuitOpenError {
+            name: self.name.clone(),
+        })
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        push_outcome(&mut inner.outcomes, true);
+        inner.consecutive_failures = 0;
+        if inner.state != BreakerState::Closed {
+            tracing::info!("Circuit breaker {} closed", self.name);
+            inner.state = BreakerState::Closed;
+        }
+    }
+
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        push_outcome(&mut inner.outcomes, false);
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+
+        let should_open = match inner.state {
+            BreakerState::Closed => {
+                self.policy.failure_threshold > 0
+                    && inner.consecutive_failures >= self.policy.failure_threshold
+            }
+            BreakerState::HalfOpen => true,
+            BreakerState::Open => false,
+        };
+        if should_open {
+            tracing::warn!(
+                "Circuit breaker {} opened after {} failures in a row",
+                self.name,
+                inner.consecutive_failures
+            );
+            inner.state = BreakerState::Open;
+            inner.changed_at = Instant::now();
+            inner.opened_total += 1;
+        }
+    }
+
+    /// Run `call` unless the breaker is open, recording whether it failed
+    pub async fn call<T, F>(&self, call: F) -> anyhow::Result<T>
+    where
+        F: Future<Output = anyhow::Result<T>>,
+    {
+        self.try_acquire()?;
+        let result = call.await;
+        self.record(result.is_ok());
+        result
+    }
+
+    /// Record the outcome of a call made after [`try_acquire`](Self::try_acquire)
+    pub fn record(&self, succeeded: bool) {
+        if succeeded {
+            self.record_success();
+        } else {
+            self.record_failure();
+        }
+    }
+
+    /// State the next call would see, without letting a probe through
+    pub fn state(&self) -> BreakerState {
+        let inner = self.inner.lock().unwrap();
+        match inner.state {
+            BreakerState::Open if inner.changed_at.elapsed() >= self.policy.open_duration => {
+                BreakerState::HalfOpen
+            }
+            state => state,
+        }
+    }
+
+    pub fn snapshot(&self) -> BreakerSnapshot {
+        let state = self.state();
+        let inner = self.inner.lock().unwrap();
+        let health_score = match inner.outcomes.len() {
+            0 => 1.0,
+            calls => {
+                inner
+                    .outcomes
+                    .iter()
+                    .filter(|succeeded| **succeeded)
+                    .count() as f64
+                    / calls as f64
+            }
+        };
+
+        BreakerSnapshot {
+            name: self.name.clone(),
+            state,
+            health_score,
+            consecutive_failures: inner.consecutive_failures,
+            opened_total: inner.opened_total,
+            rejected_total: inner.rejected_total,
+        }
+    }
+}
+
+fn push_outcome(outcomes: &mut VecDeque<bool>, succeeded: bool) {
+    if outcomes.len() == SCORE_WINDOW {
+        outcomes.pop_front();
+    }
+    outcomes.push_back(succeeded);
+}
+
+/// Set the policy of the breakers [`breaker`] creates from now on
+pub fn configure(policy: BreakerPolicy) {
+    *POLICY.write().unwrap() = policy;
+}
+
+/// Breaker of the dependency `name`, shared by every client of it in this process
+pub fn breaker(name: &str) -> CircuitBreaker {
+    BREAKERS
+        .lock()
+        .unwrap()
+        .entry(name.to_string())
+        .or_insert_with(|| CircuitBreaker::new(name, POLICY.read().unwrap().clone()))
+        .clone()
+}
+
+/// Breaker of the dependency `name`, `None` when nothing in this process called it yet
+pub fn find(name: &str) -> Option<CircuitBreaker> {
+    BREAKERS.lock().unwrap().get(name).cloned()
+}
+
+/// Snapshots of the breakers of this process, by name
+pub fn snapshots() -> Vec<BreakerSnapshot> {
+    let mut snapshots: Vec<BreakerSnapshot> = BREAKERS
+        .lock()
+        .unwrap()
+        .values()
+        .map(CircuitBreaker::snapshot)
+        .collect();
+    snapshots.sort_by(|a, b| a.name.cmp(&b.name));
+    snapshots
+}
+
+/// Name, type, help text and value of a breaker metric
+type BreakerMetric = (
+    &'static str,
+    &'static str,
+    &'static str,
+    fn(&BreakerSnapshot) -> String,
+);
+
+/// Render breaker snapshots in the Prometheus text exposition format. Each entry pairs the
+/// `process` label (e.g. `api` or a worker id) with the breakers of that process.
+pub fn render_metrics(processes: &[(String, Vec<BreakerSnapshot>)]) -> String {
+    let metrics: [BreakerMetric; 4] = [
+        (
+            "dependency_circuit_state",
+            "gauge",
+            "Circuit breaker state (0 closed, 1 half-open, 2 open)",
+            |snapshot| snapshot.state.as_gauge().to_string(),
+        ),
+        (
+            "dependency_health_score",
+            "gauge",
+            "Share of the last calls to the dependency that succeeded",
+            |snapshot| snapshot.health_score.to_string(),
+        ),
+        (
+            "dependency_circuit_opened_total",
+            "counter",
+            "Times the circuit breaker opened",
+            |snapshot| snapshot.opened_total.to_string(),
+        ),
+        (
+            "dependency_circuit_rejected_total",
+            "counter",
+            "Calls failed fast while the circuit breaker was open",
+            |snapshot| snapshot.rejected_total.to_string(),
+        ),
+    ];
+
+    let mut output = String::new();
+    for (name, kind, help, value) in metrics {
+        let _ = writeln!(output, "# HELP {} {}", name, help);
+        let _ = writeln!(output, "# TYPE {} {}", name, kind);
+        for (process, snapshots) in processes {
+            for snapshot in snapshots {
+                let _ = writeln!(
+                    output,
+                    "{}{{process=\"{}\",dependency=\"{}\"}} {}",
+                    name,
+                    escape_label(process),
+                    escape_label(&snapshot.name),
+                    value(snapshot)
+                );
+            }
+        }
+    }
+    output
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> BreakerPolicy {
+        BreakerPolicy {
+            failure_threshold: 2,
+            open_duration: Duration::from_secs(10),
+        }
+    }
+
+    async fn fail(breaker: &CircuitBreaker) -> anyhow::Result<()> {
+        breaker
+            .call(async { Err::<(), _>(anyhow::anyhow!("unreachable")) })
+            .await
+    }
+
+    async fn succeed(breaker: &CircuitBreaker) -> anyhow::Result<()> {
+        breaker.call(async { Ok(()) }).await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn opens_after_failures_in_a_row() {
+        let breaker = CircuitBreaker::new("smtp", policy());
+
+        fail(&breaker).await.unwrap_err();
+        succeed(&breaker).await.unwrap();
+        fail(&breaker).await.unwrap_err();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        fail(&breaker).await.unwrap_err();
+
+        assert_eq!(breaker.state(), BreakerState::Open);
+        let error = succeed(&breaker).await.unwrap_err();
+        assert!(error.downcast_ref::<CircuitOpenError>().is_some());
+        let snapshot = breaker.snapshot();
+        assert_eq!(snapshot.opened_total, 1);
+        assert_eq!(snapshot.rejected_total, 1);
+        assert_eq!(snapshot.health_score, 0.25);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lets_one_probe_through_when_half_open() {
+        let breaker = CircuitBreaker::new("smtp", policy());
+        fail(&breaker).await.unwrap_err();
+        fail(&breaker).await.unwrap_err();
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        breaker.try_acquire().unwrap();
+        assert!(breaker.try_acquire().is_err());
+        breaker.record_success();
+
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        succeed(&breaker).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reopens_when_probe_fails() {
+        let breaker = CircuitBreaker::new("smtp", policy());
+        fail(&breaker).await.unwrap_err();
+        fail(&breaker).await.unwrap_err();
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        fail(&breaker).await.unwrap_err();
+
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert_eq!(breaker.snapshot().opened_total, 2);
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert!(succeed(&breaker).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn zero_threshold_never_opens() {
+        let breaker = CircuitBreaker::new(
+            "smtp",
+            BreakerPolicy {
+                failure_threshold: 0,
+                ..policy()
+            },
+        );
+
+        for _ in 0..10 {
+            fail(&breaker).await.unwrap_err();
+        }
+
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+
+    #[test]
+    fn shares_breakers_by_name() {
+        let name = uuid::Uuid::new_v4().to_string();
+
+        breaker(&name).record_failure();
+
+        let snapshot = snapshots()
+            .into_iter()
+            .find(|snapshot| snapshot.name == name)
+            .unwrap();
+        assert_eq!(snapshot.consecutive_failures, 1);
+    }
+
+    #[test]
+    fn renders_prometheus_metrics() {
+        let snapshot = CircuitBreaker::new("smtp", policy()).snapshot();
+
+        let metrics = render_metrics(&[("worker-\"1\"".to_string(), vec![snapshot])]);
+
+        assert!(metrics.contains("# TYPE dependency_circuit_state gauge\n"));
+        assert!(metrics.contains(
+            "dependency_circuit_state{process=\"worker-\\\"1\\\"\",dependency=\"smtp\"} 0\n"
+        ));
+        assert!(metrics.contains(
+            "dependency_health_score{process=\"worker-\\\"1\\\"\",dependency=\"smtp\"} 1\n"
+        ));
+    }
+}
@@ -0,0 +1,262 @@
+use anyhow::{Context, Result};
+use reqwest::{Client, Proxy, RequestBuilder, Response, StatusCode, header::RETRY_AFTER};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+use tokio::time::sleep;
+
+/// Client used by integrations that are not given one explicitly
+static DEFAULT: LazyLock<HttpClient> = LazyLock::new(|| {
+    HttpClient::new(&HttpClientConfig::default()).expect("Default HTTP client must build")
+});
+
+/// Timeouts, proxy, connection pool and retry policy of an [`HttpClient`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpClientConfig {
+    /// Whole request, including reading the response
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    /// Idle connections kept open per host for reuse
+    pub pool_max_idle_per_host: usize,
+    pub pool_idle_timeout: Duration,
+    /// Proxy for every outbound request, e.g. `http://proxy:3128`
+    pub proxy: Option<String>,
+    /// Attempts after the first one when a request fails transiently
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on each retry up to `max_retry_delay`
+    pub retry_delay: Duration,
+    pub max_retry_delay: Duration,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(5),
+            pool_max_idle_per_host: 16,
+            pool_idle_timeout: Duration::from_secs(90),
+            proxy: None,
+            max_retries: 2,
+            retry_delay: Duration::from_millis(200),
+            max_retry_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl HttpClientConfig {
+    /// Delay before retry number `attempt` (starting at 1)
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.retry_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_retry_delay)
+    }
+}
+
+/// Outbound HTTP client shared by every integration of a process (push, SMS, ...), so they
+/// reuse one connection pool. Cheap to clone.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: Client,
+    config: Arc<HttpClientConfig>,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        DEFAULT.clone()
+    }
+}
+
+impl HttpClient {
+    pub fn new(config: &HttpClientConfig) -> Result<Self> {
+        let mut builder = Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.connect_timeout)
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .pool_idle_timeout(config.pool_idle_timeout)
+            .user_agent(concat!("my-axum/", env!("CARGO_PKG_VERSION")));
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(Proxy::all(proxy).context("Invalid HTTP proxy")?);
+        }
+
+        Ok(Self {
+            client: builder.build().context("Failed to build HTTP client")?,
+            config: Arc::new(config.clone()),
+        })
+    }
+
+    pub fn get(&self, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    /// Send `request`, retrying with backoff on connection errors, timeouts and `429`/`5xx`
+    /// gateway responses. A `Retry-After` header, when present, overrides the backoff.
+    /// Requests with streaming bodies can't be replayed and are sent once.
+    pub async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let retryable = if attempt < self.config.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+            let Some(retryable) = retryable else {
+                return request.send().await;
+            };
+
+            let delay = match retryable.send().await {
+                Ok(response) if is_transient_status(response.status()) => {
+                    tracing::warn!("HTTP request got {}, retrying", response.status());
+                    retry_after(&response)
+                }
+                Ok(response) => return Ok(response),
+                Err(e) if e.is_connect() || e.is_timeout() => {
+                    tracing::warn!("HTTP request failed, retrying: {}", e);
+                    None
+                }
+                Err(e) => return Err(e),
+            };
+
+            attempt += 1;
+            let delay = delay
+                .unwrap_or_else(|| self.config.backoff(attempt))
+                .min(self.config.max_retry_delay);
+            sleep(delay).await;
+        }
+    }
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Delay requested by a `Retry-After: <seconds>` header
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{Router, http::StatusCode as AxumStatus, routing::post};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Fake upstream answering with `statuses` in turn, then `200`
+    async fn spawn_flaky_server(statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let app = Router::new().route(
+            "/",
+            post(move || {
+                let call = counter.fetch_add(1, Ordering::SeqCst);
+                let status = statuses.get(call).copied().unwrap_or(200);
+                async move { AxumStatus::from_u16(status).unwrap() }
+            }),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (url, calls)
+    }
+
+    fn client(max_retries: u32) -> HttpClient {
+        HttpClient::new(&HttpClientConfig {
+            max_retries,
+            retry_delay: Duration::from_millis(1),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn retries_transient_responses() {
+        let (url, calls) = spawn_flaky_server(vec![503, 429]).await;
+        let client = client(2);
+
+        let response = client
+            .send(client.post(&url).body("payload"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn returns_last_response_when_retries_run_out() {
+        let (url, calls) = spawn_flaky_server(vec![503, 503, 503]).await;
+        let client = client(1);
+
+        let response = client.send(client.post(&url)).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_client_errors() {
+        let (url, calls) = spawn_flaky_server(vec![400]).await;
+        let client = client(2);
+
+        let response = client.send(client.post(&url)).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_connection_errors() {
+        let client = client(1);
+
+        let result = client.send(client.post("http://127.0.0.1:1/")).await;
+
+        assert!(result.unwrap_err().is_connect());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let config = HttpClientConfig {
+            retry_delay: Duration::from_millis(100),
+            max_retry_delay: Duration::from_millis(300),
+            ..Default::default()
+        };
+
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(30), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn rejects_invalid_proxy() {
+        let config = HttpClientConfig {
+            proxy: Some("not a url".to_string()),
+            ..Default::default()
+        };
+
+        assert!(HttpClient::new(&config).is_err());
+    }
+}
@@ -9,6 +9,28 @@ pub struct Claims {
     pub iat: u64,
     pub exp: u64,
     pub jti: String,
+    /// Who is acting as `sub`, for impersonation tokens (RFC 8693 `act` claim)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Actor {
+    pub sub: i32,
+}
+
+impl Claims {
+    /// Claims of a token for `sub` expiring in `expires_delta`, with a random `jti`
+    pub fn new(sub: i32, expires_delta: chrono::Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            sub,
+            iat: now.timestamp() as u64,
+            exp: (now + expires_delta).timestamp() as u64,
+            jti: Uuid::new_v4().to_string(),
+            act: None,
+        }
+    }
 }
 
 pub fn encode_token(
@@ -16,18 +38,26 @@ pub fn encode_token(
     expires_delta: chrono::Duration,
     secret: &str,
 ) -> errors::Result<String> {
-    let now = Utc::now();
-    let claims = Claims {
-        sub,
-        iat: now.timestamp() as u64,
-        exp: (now + expires_delta).timestamp() as u64,
-        jti: Uuid::new_v4().to_string(),
+    encode_token_with_kid(sub, expires_delta, secret, None)
+}
+
+/// Encode a token whose header names the signing key, so verifiers can pick it after rotation
+pub fn encode_token_with_kid(
+    sub: i32,
+    expires_delta: chrono::Duration,
+    secret: &str,
+    kid: Option<&str>,
+) -> errors::Result<String> {
+    encode_claims(&Claims::new(sub, expires_delta), secret, kid)
+}
+
+/// Encode `claims` as they are, signed like `encode_token_with_kid`
+pub fn encode_claims(claims: &Claims, secret: &str, kid: Option<&str>) -> errors::Result<String> {
+    let header = Header {
+        kid: kid.map(str::to_string),
+        ..Header::default()
     };
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret.as_ref()),
-    )
+    encode(&header, claims, &EncodingKey::from_secret(secret.as_ref()))
 }
 
 pub fn decode_token(token: &str, secret: &str) -> errors::Result<Claims> {
@@ -39,11 +69,18 @@ pub fn decode_token(token: &str, secret: &str) -> errors::Result<Claims> {
     .map(|data| data.claims)
 }
 
+/// The `kid` header of a token, without verifying its signature
+pub fn decode_kid(token: &str) -> errors::Result<Option<String>> {
+    jsonwebtoken::decode_header(token).map(|header| header.kid)
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::Duration;
 
-    use super::{decode_token, encode_token};
+    use super::{
+        Actor, Claims, decode_kid, decode_token, encode_claims, encode_token, encode_token_with_kid,
+    };
 
     #[test]
     fn encodes_and_decodes_token() {
@@ -53,6 +90,19 @@ mod tests {
         assert_eq!(claims.sub, 123);
         assert!(claims.exp > claims.iat);
         assert!(!claims.jti.is_empty());
+        assert_eq!(claims.act, None);
+    }
+
+    #[test]
+    fn keeps_actor_claim() {
+        let mut claims = Claims::new(123, Duration::minutes(15));
+        claims.act = Some(Actor { sub: 1 });
+        let token = encode_claims(&claims, "secret", None).unwrap();
+        let decoded = decode_token(&token, "secret").unwrap();
+
+        assert_eq!(decoded.sub, 123);
+        assert_eq!(decoded.act, Some(Actor { sub: 1 }));
+        assert_eq!(decoded.jti, claims.jti);
     }
 
     #[test]
@@ -66,4 +116,15 @@ mod tests {
         let token = encode_token(123, Duration::hours(-1), "secret").unwrap();
         assert!(decode_token(&token, "secret").is_err());
     }
+
+    #[test]
+    fn exposes_signing_key_id() {
+        let token =
+            encode_token_with_kid(123, Duration::minutes(30), "secret", Some("k1")).unwrap();
+        assert_eq!(decode_kid(&token).unwrap().as_deref(), Some("k1"));
+        assert_eq!(decode_token(&token, "secret").unwrap().sub, 123);
+
+        let token = encode_token(123, Duration::minutes(30), "secret").unwrap();
+        assert_eq!(decode_kid(&token).unwrap(), None);
+    }
 }
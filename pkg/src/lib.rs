@@ -1,8 +1,22 @@
+pub mod antivirus;
 pub mod broadcast;
 pub mod cache;
+pub mod circuit_breaker;
 pub mod cors;
+pub mod http_client;
 pub mod jwt;
 pub mod messaging;
+pub mod mtls;
+pub mod oauth;
 pub mod password;
+pub mod pkce;
+pub mod push;
+pub mod redis;
+pub mod request_signing;
+pub mod sms;
 pub mod smtp;
+pub mod storage;
+pub mod thumbnail;
+pub mod token_hash;
 pub mod url;
+pub mod webhook_verify;
@@ -0,0 +1,271 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::{Semaphore, mpsc::UnboundedReceiver};
+use tracing::{error, info, warn};
+
+use crate::messaging::{MessageProducer, TaskEvent, TaskHandler};
+
+use super::MessageConsumer;
+use super::task_queue::{
+    SharedPriorityQueue, enqueue_task, new_priority_queue, spawn_priority_processor,
+};
+
+/// In-process consumer fed by a channel of serialized task events.
+/// Uses the same priority queue and retry handling as the broker-backed consumers,
+/// so it can stand in for them in tests and single-process setups.
+pub struct ChannelConsumer<T>
+where
+    T: Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    receiver: Option<UnboundedReceiver<String>>,
+    task_handler: Arc<dyn TaskHandler<T>>,
+    semaphore: Arc<Semaphore>,
+    priority_queue: SharedPriorityQueue<T>,
+    producer: Arc<Box<dyn MessageProducer>>,
+}
+
+impl<T> ChannelConsumer<T>
+where
+    T: Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    pub fn new(
+        receiver: UnboundedReceiver<String>,
+        task_handler: Arc<dyn TaskHandler<T>>,
+        semaphore: Arc<Semaphore>,
+        producer: Arc<Box<dyn MessageProducer>>,
+    ) -> Self {
+        Self {
+            receiver: Some(receiver),
+            task_handler,
+            semaphore,
+            priority_queue: new_priority_queue(),
+            producer,
+        }
+    }
+}
+
+#[async_trait]
+impl<T> MessageConsumer for ChannelConsumer<T>
+where
+    T: Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
+{
+    async fn connect(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn consume(&mut self) -> anyhow::Result<()> {
+        let mut receiver = self
+            .receiver
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Channel consumer is already consuming"))?;
+
+        spawn_priority_processor(
+            self.priority_queue.clone(),
+            self.task_handler.clone(),
+            self.semaphore.clone(),
+            self.producer.clone(),
+        );
+
+        while let Some(payload) = receiver.recv().await {
+            let event: TaskEvent<T> = match serde_json::from_str(&payload) {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse task event from channel: {:?}", e);
+                    continue;
+                }
+            };
+
+            info!(
+                "Received task event: {} with priority {:?} from channel",
+                event.id, event.priority
+            );
+            enqueue_task(&self.priority_queue, &self.task_handler, event).await;
+        }
+
+        warn!("Task channel closed");
+        Ok(())
+    }
+
+    fn broker_type(&self) -> &str {
+        "Channel"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+    use tokio::sync::{Semaphore, mpsc};
+
+    use super::ChannelConsumer;
+    use crate::messaging::{
+        EncodedMessage, MessageConsumer, MessageProducer, TaskEvent, TaskHandler, TaskLifecycle,
+    };
+
+    struct NoopProducer;
+
+    #[async_trait]
+    impl MessageProducer for NoopProducer {
+        async fn publish(&self, _: &EncodedMessage, _: Option<&str>) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct ForwardingHandler(mpsc::UnboundedSender<String>);
+
+    #[async_trait]
+    impl TaskHandler<String> for ForwardingHandler {
+        async fn handle_task(&self, event: &TaskEvent<String>) -> anyhow::Result<()> {
+            self.0.send(event.task.clone())?;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn handles_events_sent_on_the_channel() {
+        let (task_tx, task_rx) = mpsc::unbounded_channel();
+        let (handled_tx, mut handled_rx) = mpsc::unbounded_channel();
+        let mut consumer = ChannelConsumer::new(
+            task_rx,
+            Arc::new(ForwardingHandler(handled_tx)),
+            Arc::new(Semaphore::new(1)),
+            Arc::new(Box::new(NoopProducer) as Box<dyn MessageProducer>),
+        );
+        tokio::spawn(async move { consumer.consume().await });
+
+        task_tx.send("not json".to_string()).unwrap();
+        let event = TaskEvent::new("hello".to_string());
+        task_tx
+            .send(serde_json::to_string(&event).unwrap())
+            .unwrap();
+
+        let handled = tokio::time::timeout(Duration::from_secs(2), handled_rx.recv())
+            .await
+            .unwrap();
+        assert_eq!(handled.as_deref(), Some("hello"));
+    }
+
+    struct LifecycleHandler(mpsc::UnboundedSender<(String, TaskLifecycle)>);
+
+    #[async_trait]
+    impl TaskHandler<String> for LifecycleHandler {
+        async fn handle_task(&self, event: &TaskEvent<String>) -> anyhow::Result<()> {
+            anyhow::ensure!(event.task != "fail", "task asked to fail");
+            Ok(())
+        }
+
+        async fn on_lifecycle(&self, event: &TaskEvent<String>, lifecycle: &TaskLifecycle) {
+            self.0
+                .send((event.task.clone(), lifecycle.clone()))
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_each_lifecycle_stage_to_the_handler() {
+        let (task_tx, task_rx) = mpsc::unbounded_channel();
+        let (lifecycle_tx, mut lifecycle_rx) = mpsc::unbounded_channel();
+        let mut consumer = ChannelConsumer::new(
+            task_rx,
+            Arc::new(LifecycleHandler(lifecycle_tx)),
+            Arc::new(Semaphore::new(1)),
+            Arc::new(Box::new(NoopProducer) as Box<dyn MessageProducer>),
+        );
+        tokio::spawn(async move { consumer.consume().await });
+
+        let mut failing = TaskEvent::new("fail".to_string());
+        failing.max_retries = 0;
+        for event in [TaskEvent::new("ok".to_string()), failing] {
+            task_tx
+                .send(serde_json::to_string(&event).unwrap())
+                .unwrap();
+        }
+
+        let mut stages = Vec::new();
+        while stages.len() < 6 {
+            let stage = tokio::time::timeout(Duration::from_secs(2), lifecycle_rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            stages.push(stage);
+        }
+        let stages_of = |task: &str| {
+            stages
+                .iter()
+                .filter(|(name, _)| name == task)
+                .map(|(_, lifecycle)| lifecycle.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            stages_of("ok"),
+            vec![
+                TaskLifecycle::Enqueued,
+                TaskLifecycle::Started,
+                TaskLifecycle::Completed
+            ]
+        );
+        assert_eq!(
+            stages_of("fail"),
+            vec![
+                TaskLifecycle::Enqueued,
+                TaskLifecycle::Started,
+                TaskLifecycle::Failed {
+                    error: "task asked to fail".to_string()
+                }
+            ]
+        );
+    }
+
+    struct PausingHandler {
+        paused: Arc<AtomicBool>,
+        handled: mpsc::UnboundedSender<String>,
+    }
+
+    #[async_trait]
+    impl TaskHandler<String> for PausingHandler {
+        async fn handle_task(&self, event: &TaskEvent<String>) -> anyhow::Result<()> {
+            self.handled.send(event.task.clone())?;
+            Ok(())
+        }
+
+        async fn is_paused(&self, event: &TaskEvent<String>) -> bool {
+            event.task == "paused" && self.paused.load(Ordering::Relaxed)
+        }
+    }
+
+    #[tokio::test]
+    async fn holds_paused_tasks_without_blocking_others() {
+        let (task_tx, task_rx) = mpsc::unbounded_channel();
+        let (handled_tx, mut handled_rx) = mpsc::unbounded_channel();
+        let paused = Arc::new(AtomicBool::new(true));
+        let mut consumer = ChannelConsumer::new(
+            task_rx,
+            Arc::new(PausingHandler {
+                paused: paused.clone(),
+                handled: handled_tx,
+            }),
+            Arc::new(Semaphore::new(1)),
+            Arc::new(Box::new(NoopProducer) as Box<dyn MessageProducer>),
+        );
+        tokio::spawn(async move { consumer.consume().await });
+
+        for task in ["paused", "running"] {
+            let event = TaskEvent::new(task.to_string());
+            task_tx
+                .send(serde_json::to_string(&event).unwrap())
+                .unwrap();
+        }
+        let mut next_handled = async || {
+            tokio::time::timeout(Duration::from_secs(3), handled_rx.recv())
+                .await
+                .unwrap()
+        };
+
+        assert_eq!(next_handled().await.as_deref(), Some("running"));
+        paused.store(false, Ordering::Relaxed);
+        assert_eq!(next_handled().await.as_deref(), Some("paused"));
+    }
+}
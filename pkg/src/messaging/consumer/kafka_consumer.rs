@@ -1,7 +1,6 @@
 use anyhow::{Context as AnyhowContext, Result};
 use async_trait::async_trait;
 use rdkafka::{
-    config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
     error::KafkaError,
     message::Message,
@@ -11,7 +10,11 @@ use std::time::Duration;
 use tokio::sync::Semaphore;
 use tracing::{error, info, warn};
 
-use crate::messaging::{MessageProducer, TaskEvent, TaskHandler, ensure_topics_exist};
+use crate::messaging::{
+    MessageProducer, TaskEvent, TaskHandler, ensure_topics_exist,
+    failover::{BrokerEndpoints, FailoverPolicy},
+    security::BrokerSecurity,
+};
 use serde::{Deserialize, Serialize};
 
 use super::MessageConsumer;
@@ -24,6 +27,10 @@ pub struct KafkaConsumer<T>
 where
     T: Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
 {
+    endpoints: BrokerEndpoints,
+    group_id: String,
+    topics: Vec<String>,
+    security: BrokerSecurity,
     consumer: StreamConsumer,
     task_handler: Arc<dyn TaskHandler<T>>,
     semaphore: Arc<Semaphore>,
@@ -35,23 +42,52 @@ impl<T> KafkaConsumer<T>
 where
     T: Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
 {
-    /// Create a new Kafka consumer
+    /// Create a new Kafka consumer on the first reachable bootstrap set listed in `brokers`
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         brokers: &str,
         group_id: &str,
         topics: &[String],
+        failover: &FailoverPolicy,
+        security: &BrokerSecurity,
         task_handler: Arc<dyn TaskHandler<T>>,
         semaphore: Arc<Semaphore>,
         producer: Arc<Box<dyn MessageProducer>>,
     ) -> Result<Self> {
+        let endpoints = BrokerEndpoints::new("kafka consumer", brokers, failover.clone());
+        let consumer = endpoints
+            .connect(|brokers| Self::subscribe(brokers, group_id, topics, security))
+            .await?;
+
+        Ok(Self {
+            endpoints,
+            group_id: group_id.to_string(),
+            topics: topics.to_vec(),
+            security: security.clone(),
+            consumer,
+            task_handler,
+            semaphore,
+            priority_queue: new_priority_queue(),
+            producer,
+        })
+    }
+
+    /// Create a consumer on the bootstrap set `brokers` subscribed to `topics`, which also
+    /// checks that the set is reachable
+    async fn subscribe(
+        brokers: String,
+        group_id: &str,
+        topics: &[String],
+        security: &BrokerSecurity,
+    ) -> Result<StreamConsumer> {
         // Ensure Kafka topics exist if using Kafka
         info!("Ensuring Kafka topics exist...");
         let topic_refs: Vec<&str> = topics.iter().map(|s| s.as_str()).collect();
-        ensure_topics_exist(brokers, &topic_refs).await?;
+        ensure_topics_exist(&brokers, &topic_refs, security).await?;
 
-        let consumer: StreamConsumer = ClientConfig::new()
+        let consumer: StreamConsumer = security
+            .kafka_client_config(&brokers)
             .set("group.id", group_id)
-            .set("bootstrap.servers", brokers)
             .set("enable.partition.eof", "false")
             .set("session.timeout.ms", "6000")
             .set("enable.auto.commit", "true")
@@ -61,20 +97,13 @@ where
             .context("Failed to create Kafka consumer")?;
 
         // Subscribe to topics
-        let topic_refs: Vec<&str> = topics.iter().map(|s| s.as_str()).collect();
         consumer
             .subscribe(&topic_refs)
             .context("Failed to subscribe to topics")?;
 
         info!("Kafka consumer subscribed to topics: {:?}", topics);
 
-        Ok(Self {
-            consumer,
-            task_handler,
-            semaphore,
-            priority_queue: new_priority_queue(),
-            producer,
-        })
+        Ok(consumer)
     }
 
     /// Start consuming messages from Kafka
@@ -89,17 +118,8 @@ where
         );
 
         loop {
-            match self.consumer.recv().await {
-                Err(e) => {
-                    error!("Kafka consumer error: {:?}", e);
-                    if matches!(e, KafkaError::Global(_)) {
-                        // Fatal error, break the loop
-                        return Err(anyhow::anyhow!("Fatal Kafka error: {:?}", e));
-                    }
-                    // For non-fatal errors, continue consuming
-                    tokio::time::sleep(Duration::from_secs(1)).await;
-                    continue;
-                }
+            let error = match self.consumer.recv().await {
+                Err(e) => e,
                 Ok(message) => {
                     // Get message payload
                     let payload = match message.payload() {
@@ -127,9 +147,25 @@ where
                     );
 
                     // Add to priority queue instead of processing immediately
-                    enqueue_task(&self.priority_queue, event).await;
+                    enqueue_task(&self.priority_queue, &self.task_handler, event).await;
+                    continue;
                 }
+            };
+
+            error!("Kafka consumer error: {:?}", error);
+            if matches!(error, KafkaError::Global(_)) {
+                // The client gave up on its bootstrap set; start over, on the next one if any
+                let (group_id, topics, security) = (&self.group_id, &self.topics, &self.security);
+                self.consumer = self
+                    .endpoints
+                    .reconnect(format!("{:?}", error), |brokers| {
+                        Self::subscribe(brokers, group_id, topics, security)
+                    })
+                    .await;
+                continue;
             }
+            // For non-fatal errors, continue consuming
+            tokio::time::sleep(Duration::from_secs(1)).await;
         }
     }
 }
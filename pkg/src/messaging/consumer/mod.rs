@@ -1,4 +1,5 @@
 // Consumer implementations
+mod channel_consumer;
 mod kafka_consumer;
 mod rabbitmq_consumer;
 mod redis_consumer;
@@ -8,7 +9,11 @@ use async_trait::async_trait;
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 
-use crate::messaging::{MessageProducer, TaskHandler};
+use crate::messaging::{
+    MessageProducer, TaskHandler, failover::FailoverPolicy, security::BrokerSecurity,
+};
+
+pub use channel_consumer::ChannelConsumer;
 
 /// Message consumer abstraction trait
 /// Allows worker to support different message brokers (Kafka, Redis, RabbitMQ)
@@ -29,21 +34,27 @@ pub trait MessageConsumer: Send + Sync {
     }
 }
 
-/// Configuration for message consumer
+/// Configuration for message consumer. `brokers` and `url` may list several endpoints
+/// separated by `;` to fail over between; Redis follows the failover of its shared connection.
 #[derive(Debug, Clone)]
 pub enum ConsumerConfig {
     Kafka {
         brokers: String,
         consumer_group: String,
         topics: Vec<String>,
+        failover: FailoverPolicy,
+        security: BrokerSecurity,
     },
     Redis {
         url: String,
         channels: Vec<String>,
+        security: BrokerSecurity,
     },
     RabbitMQ {
         url: String,
         queues: Vec<String>,
+        failover: FailoverPolicy,
+        security: BrokerSecurity,
     },
 }
 
@@ -54,17 +65,49 @@ impl ConsumerConfig {
             brokers,
             consumer_group,
             topics,
+            failover: FailoverPolicy::default(),
+            security: BrokerSecurity::default(),
         }
     }
 
     /// Create Redis consumer configuration
     pub fn redis(url: String, channels: Vec<String>) -> Self {
-        ConsumerConfig::Redis { url, channels }
+        ConsumerConfig::Redis {
+            url,
+            channels,
+            security: BrokerSecurity::default(),
+        }
     }
 
     /// Create RabbitMQ consumer configuration
     pub fn rabbitmq(url: String, queues: Vec<String>) -> Self {
-        ConsumerConfig::RabbitMQ { url, queues }
+        ConsumerConfig::RabbitMQ {
+            url,
+            queues,
+            failover: FailoverPolicy::default(),
+            security: BrokerSecurity::default(),
+        }
+    }
+
+    /// Use `policy` to reconnect and fail over between the listed endpoints
+    pub fn with_failover(mut self, policy: FailoverPolicy) -> Self {
+        match &mut self {
+            ConsumerConfig::Kafka { failover, .. } | ConsumerConfig::RabbitMQ { failover, .. } => {
+                *failover = policy
+            }
+            ConsumerConfig::Redis { .. } => {}
+        }
+        self
+    }
+
+    /// Connect over TLS and authenticate as configured in `settings`
+    pub fn with_security(mut self, settings: BrokerSecurity) -> Self {
+        match &mut self {
+            ConsumerConfig::Kafka { security, .. }
+            | ConsumerConfig::Redis { security, .. }
+            | ConsumerConfig::RabbitMQ { security, .. } => *security = settings,
+        }
+        self
     }
 }
 
@@ -83,12 +126,16 @@ where
             brokers,
             consumer_group,
             topics,
+            failover,
+            security,
         } => {
             use kafka_consumer::KafkaConsumer;
             let consumer = KafkaConsumer::new(
                 &brokers,
                 &consumer_group,
                 &topics,
+                &failover,
+                &security,
                 task_handler,
                 semaphore,
                 producer,
@@ -96,16 +143,40 @@ where
             .await?;
             Ok(Box::new(consumer))
         }
-        ConsumerConfig::Redis { url, channels } => {
+        ConsumerConfig::Redis {
+            url,
+            channels,
+            security,
+        } => {
             use redis_consumer::RedisConsumer;
-            let consumer =
-                RedisConsumer::new(&url, &channels, task_handler, semaphore, producer).await?;
+            let consumer = RedisConsumer::new(
+                &url,
+                &channels,
+                &security,
+                task_handler,
+                semaphore,
+                producer,
+            )
+            .await?;
             Ok(Box::new(consumer))
         }
-        ConsumerConfig::RabbitMQ { url, queues } => {
+        ConsumerConfig::RabbitMQ {
+            url,
+            queues,
+            failover,
+            security,
+        } => {
             use rabbitmq_consumer::RabbitMQConsumer;
-            let consumer =
-                RabbitMQConsumer::new(&url, &queues, task_handler, semaphore, producer).await?;
+            let consumer = RabbitMQConsumer::new(
+                &url,
+                &queues,
+                &failover,
+                &security,
+                task_handler,
+                semaphore,
+                producer,
+            )
+            .await?;
             Ok(Box::new(consumer))
         }
     }
@@ -1,14 +1,17 @@
 use anyhow::{Context as AnyhowContext, Result};
 use async_trait::async_trait;
-use futures_util::StreamExt;
-use lapin::{
-    Channel, Connection, ConnectionProperties, message::Delivery, options::*, types::FieldTable,
-};
+use futures_util::{StreamExt, future::select_all};
+use lapin::{Channel, Connection, message::Delivery, options::*, types::FieldTable};
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 use tracing::{error, info};
 
-use crate::messaging::{MessageProducer, TaskEvent, TaskHandler};
+use crate::messaging::{
+    MessageProducer, TaskEvent, TaskHandler,
+    failover::{BrokerEndpoints, FailoverPolicy},
+    security::BrokerSecurity,
+};
+use crate::url::mask_url;
 use serde::{Deserialize, Serialize};
 
 use super::MessageConsumer;
@@ -21,7 +24,8 @@ pub struct RabbitMQConsumer<T>
 where
     T: Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
 {
-    url: String,
+    endpoints: BrokerEndpoints,
+    security: BrokerSecurity,
     queues: Vec<String>,
     task_handler: Arc<dyn TaskHandler<T>>,
     semaphore: Arc<Semaphore>,
@@ -35,10 +39,12 @@ impl<T> RabbitMQConsumer<T>
 where
     T: Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
 {
-    /// Create a new RabbitMQ consumer
+    /// Create a new RabbitMQ consumer for the URLs listed in `url`
     pub async fn new(
         url: &str,
         queues: &[String],
+        failover: &FailoverPolicy,
+        security: &BrokerSecurity,
         task_handler: Arc<dyn TaskHandler<T>>,
         semaphore: Arc<Semaphore>,
         producer: Arc<Box<dyn MessageProducer>>,
@@ -46,7 +52,8 @@ where
         info!("RabbitMQ consumer initialized for queues: {:?}", queues);
 
         Ok(Self {
-            url: url.to_string(),
+            endpoints: BrokerEndpoints::new("rabbitmq consumer", url, failover.clone()),
+            security: security.clone(),
             queues: queues.to_vec(),
             task_handler,
             semaphore,
@@ -57,15 +64,58 @@ where
         })
     }
 
+    async fn open(url: String, security: &BrokerSecurity) -> Result<(Connection, Channel)> {
+        info!("🔌 Connecting to RabbitMQ at: {}", mask_url(&url));
+
+        let connection = security
+            .connect_rabbitmq(&url)
+            .await
+            .context("Failed to connect to RabbitMQ")?;
+
+        let channel = connection
+            .create_channel()
+            .await
+            .context("Failed to create RabbitMQ channel")?;
+
+        info!("✓ RabbitMQ connection established");
+        Ok((connection, channel))
+    }
+
     /// Internal consume implementation
     async fn consume_messages(&mut self) -> anyhow::Result<()> {
         info!("🔄 Starting RabbitMQ message consumption...");
 
-        let channel = self
+        let mut channel = self
             .channel
-            .as_ref()
+            .clone()
             .ok_or_else(|| anyhow::anyhow!("Channel not initialized. Call connect() first."))?;
 
+        spawn_priority_processor(
+            self.priority_queue.clone(),
+            self.task_handler.clone(),
+            self.semaphore.clone(),
+            self.producer.clone(),
+        );
+
+        // Consume until the connection is lost, then start over on a new one
+        loop {
+            let reason = match self.consume_channel(&channel).await {
+                Ok(reason) => reason,
+                Err(e) => format!("{:#}", e),
+            };
+
+            let (connection, new_channel) = self
+                .endpoints
+                .reconnect(reason, |url| Self::open(url, &self.security))
+                .await;
+            self.connection = Some(connection);
+            self.channel = Some(new_channel.clone());
+            channel = new_channel;
+        }
+    }
+
+    /// Consume every queue on `channel`, returning why consumption stopped
+    async fn consume_channel(&self, channel: &Channel) -> anyhow::Result<String> {
         // Declare and consume from all queues
         for queue_name in &self.queues {
             // Declare queue (idempotent)
@@ -87,13 +137,6 @@ where
         // Set QoS - prefetch count
         channel.basic_qos(10, BasicQosOptions::default()).await?;
 
-        spawn_priority_processor(
-            self.priority_queue.clone(),
-            self.task_handler.clone(),
-            self.semaphore.clone(),
-            self.producer.clone(),
-        );
-
         // Create consumers for all queues
         let mut handles = vec![];
 
@@ -112,6 +155,7 @@ where
             info!("✓ Started consuming from queue: {}", queue);
 
             let priority_queue = self.priority_queue.clone();
+            let task_handler = self.task_handler.clone();
             let channel_clone = channel.clone();
 
             let handle = tokio::spawn(async move {
@@ -122,6 +166,7 @@ where
                             if let Err(e) = Self::process_delivery(
                                 delivery,
                                 priority_queue.clone(),
+                                task_handler.clone(),
                                 channel_clone.clone(),
                             )
                             .await
@@ -131,28 +176,31 @@ where
                         }
                         Err(e) => {
                             error!("Consumer error: {:?}", e);
-                            break;
+                            return format!("Consumer of queue {} failed: {}", queue, e);
                         }
                     }
                 }
+                format!("Consumer of queue {} ended", queue)
             });
 
             handles.push(handle);
         }
 
-        // Wait for all consumers
-        for handle in handles {
-            handle.await?;
-        }
-
         info!("🎯 RabbitMQ consumer is now listening for messages...");
 
-        Ok(())
+        // The queues share the connection, so one consumer stopping means all of them will
+        let (reason, _, others) = select_all(handles).await;
+        for handle in others {
+            handle.abort();
+        }
+
+        Ok(reason?)
     }
 
     async fn process_delivery(
         delivery: Delivery,
         priority_queue: SharedPriorityQueue<T>,
+        task_handler: Arc<dyn TaskHandler<T>>,
         _channel: Channel,
     ) -> anyhow::Result<()> {
         let payload = String::from_utf8(delivery.data.clone())?;
@@ -176,7 +224,7 @@ where
         );
 
         // Add to priority queue
-        enqueue_task(&priority_queue, event).await;
+        enqueue_task(&priority_queue, &task_handler, event).await;
 
         // Acknowledge message immediately since we've queued it
         delivery.ack(BasicAckOptions::default()).await?;
@@ -191,18 +239,10 @@ where
     T: Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
 {
     async fn connect(&mut self) -> anyhow::Result<()> {
-        info!("🔌 Connecting to RabbitMQ at: {}", self.url);
-
-        let connection = Connection::connect(&self.url, ConnectionProperties::default())
-            .await
-            .context("Failed to connect to RabbitMQ")?;
-
-        let channel = connection
-            .create_channel()
-            .await
-            .context("Failed to create RabbitMQ channel")?;
-
-        info!("✓ RabbitMQ connection established");
+        let (connection, channel) = self
+            .endpoints
+            .connect(|url| Self::open(url, &self.security))
+            .await?;
 
         self.connection = Some(connection);
         self.channel = Some(channel);
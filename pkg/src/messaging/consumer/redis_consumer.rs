@@ -1,12 +1,12 @@
 use anyhow::{Context as AnyhowContext, Result};
 use async_trait::async_trait;
 use futures_util::StreamExt; // For stream.next().await
-use redis::Client;
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 use tracing::{error, info, warn};
 
-use crate::messaging::{MessageProducer, TaskEvent, TaskHandler};
+use crate::messaging::{MessageProducer, TaskEvent, TaskHandler, security::BrokerSecurity};
+use crate::redis::{RedisConnectionManager, RedisPoolConfig};
 use serde::{Deserialize, Serialize};
 
 use super::MessageConsumer;
@@ -19,7 +19,7 @@ pub struct RedisConsumer<T>
 where
     T: Clone + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static,
 {
-    client: Client,
+    manager: RedisConnectionManager,
     channels: Vec<String>,
     task_handler: Arc<dyn TaskHandler<T>>,
     semaphore: Arc<Semaphore>,
@@ -35,16 +35,21 @@ where
     pub async fn new(
         redis_url: &str,
         channels: &[String],
+        security: &BrokerSecurity,
         task_handler: Arc<dyn TaskHandler<T>>,
         semaphore: Arc<Semaphore>,
         producer: Arc<Box<dyn MessageProducer>>,
     ) -> Result<Self> {
-        let client = Client::open(redis_url).context("Failed to create Redis client")?;
+        let config = RedisPoolConfig {
+            security: security.clone(),
+            ..Default::default()
+        };
+        let manager = RedisConnectionManager::shared(redis_url, &config).await?;
 
         info!("Redis consumer initialized for channels: {:?}", channels);
 
         Ok(Self {
-            client,
+            manager,
             channels: channels.to_vec(),
             task_handler,
             semaphore,
@@ -53,17 +58,14 @@ where
         })
     }
 
-    /// Internal consume implementation  
-    async fn consume_messages(&mut self) -> anyhow::Result<()> {
-        info!("🔄 Starting Redis Pub/Sub consumption...");
-
+    /// Open a pub/sub connection subscribed to every channel
+    async fn subscribe(&self) -> anyhow::Result<redis::aio::PubSub> {
         let mut pubsub = self
-            .client
-            .get_async_pubsub()
+            .manager
+            .pubsub()
             .await
             .context("Failed to get Redis pub/sub connection")?;
 
-        // Subscribe to all channels
         for channel in &self.channels {
             pubsub
                 .subscribe(channel)
@@ -72,6 +74,15 @@ where
             info!("✓ Subscribed to Redis channel: {}", channel);
         }
 
+        Ok(pubsub)
+    }
+
+    /// Internal consume implementation  
+    async fn consume_messages(&mut self) -> anyhow::Result<()> {
+        info!("🔄 Starting Redis Pub/Sub consumption...");
+
+        let mut pubsub = self.subscribe().await?;
+
         info!("🎯 Redis consumer is now listening for messages...");
 
         // Spawn task processor
@@ -82,39 +93,37 @@ where
             self.producer.clone(),
         );
 
-        let mut stream = pubsub.on_message();
-
+        // Subscribe again whenever the pub/sub connection drops
         loop {
-            match stream.next().await {
-                Some(msg) => {
-                    let payload: String = msg.get_payload()?;
-                    let channel_name = msg.get_channel_name();
-
-                    // Parse task event
-                    let event: TaskEvent<T> = match serde_json::from_str(&payload) {
-                        Ok(e) => e,
-                        Err(e) => {
-                            error!("Failed to parse task event from Redis: {:?}", e);
-                            continue;
-                        }
-                    };
-
-                    info!(
-                        "Received task event: {} with priority {:?} from channel: {}",
-                        event.id, event.priority, channel_name
-                    );
-
-                    // Add to priority queue instead of processing immediately
-                    enqueue_task(&self.priority_queue, event).await;
-                }
-                None => {
-                    warn!("Redis Pub/Sub stream ended");
-                    break;
-                }
+            let mut stream = pubsub.on_message();
+
+            while let Some(msg) = stream.next().await {
+                let payload: String = msg.get_payload()?;
+                let channel_name = msg.get_channel_name();
+
+                // Parse task event
+                let event: TaskEvent<T> = match serde_json::from_str(&payload) {
+                    Ok(e) => e,
+                    Err(e) => {
+                        error!("Failed to parse task event from Redis: {:?}", e);
+                        continue;
+                    }
+                };
+
+                info!(
+                    "Received task event: {} with priority {:?} from channel: {}",
+                    event.id, event.priority, channel_name
+                );
+
+                // Add to priority queue instead of processing immediately
+                enqueue_task(&self.priority_queue, &self.task_handler, event).await;
             }
-        }
 
-        Ok(())
+            drop(stream);
+            warn!("Redis Pub/Sub stream ended, resubscribing");
+            self.manager.record_reconnect();
+            pubsub = self.subscribe().await?;
+        }
     }
 }
 
@@ -126,8 +135,8 @@ where
     async fn connect(&mut self) -> anyhow::Result<()> {
         // Test connection by creating a pubsub connection
         let mut pubsub = self
-            .client
-            .get_async_pubsub()
+            .manager
+            .pubsub()
             .await
             .context("Failed to connect to Redis for pub/sub")?;
 
@@ -2,11 +2,14 @@ use serde::Serialize;
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, Semaphore};
 use tracing::{error, info, warn};
 
-use crate::messaging::{MessageProducer, TaskEvent, TaskHandler};
+use crate::messaging::{
+    MessageProducer, TaskEvent, TaskHandler, TaskLifecycle,
+    stats::{self, TaskOutcome},
+};
 
 #[derive(Clone)]
 pub(super) struct PriorityTask<T>
@@ -49,6 +52,9 @@ where
     }
 }
 
+/// How often tasks held back because they are paused are checked again
+const PAUSED_RECHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 pub(super) type SharedPriorityQueue<T> = Arc<Mutex<BinaryHeap<PriorityTask<T>>>>;
 
 pub(super) fn new_priority_queue<T>() -> SharedPriorityQueue<T>
@@ -58,12 +64,19 @@ where
     Arc::new(Mutex::new(BinaryHeap::new()))
 }
 
-pub(super) async fn enqueue_task<T>(priority_queue: &SharedPriorityQueue<T>, event: TaskEvent<T>)
-where
+pub(super) async fn enqueue_task<T>(
+    priority_queue: &SharedPriorityQueue<T>,
+    task_handler: &Arc<dyn TaskHandler<T>>,
+    event: TaskEvent<T>,
+) where
     T: Clone + Send + Sync,
 {
+    task_handler
+        .on_lifecycle(&event, &TaskLifecycle::Enqueued)
+        .await;
     let mut queue = priority_queue.lock().await;
     queue.push(PriorityTask { event });
+    stats::record_enqueued();
 }
 
 pub(super) fn spawn_priority_processor<T>(
@@ -87,11 +100,48 @@ async fn run_priority_processor<T>(
 ) where
     T: Clone + Send + Sync + Serialize + 'static,
 {
+    // Paused tasks, set aside so they don't block the tasks behind them
+    let mut held: Vec<PriorityTask<T>> = Vec::new();
+    let mut last_recheck = Instant::now();
+
     loop {
+        if !held.is_empty() && last_recheck.elapsed() >= PAUSED_RECHECK_INTERVAL {
+            let mut still_paused = Vec::new();
+            let mut resumed = Vec::new();
+            for priority_task in held.drain(..) {
+                if task_handler.is_paused(&priority_task.event).await {
+                    still_paused.push(priority_task);
+                } else {
+                    resumed.push(priority_task);
+                }
+            }
+            held = still_paused;
+            stats::record_paused(held.len() as u64);
+            priority_queue.lock().await.extend(resumed);
+            last_recheck = Instant::now();
+        }
+
         let task = {
             let mut queue = priority_queue.lock().await;
             queue.pop()
         };
+        if let Some(priority_task) = &task
+            && task_handler.is_paused(&priority_task.event).await
+        {
+            held.extend(task);
+            stats::record_paused(held.len() as u64);
+            continue;
+        }
+
+        if let Some(priority_task) = &task
+            && task_handler.is_processed(&priority_task.event).await
+        {
+            warn!(
+                "Skipping task {}, which was already processed",
+                priority_task.event.id
+            );
+            continue;
+        }
 
         match task {
             Some(priority_task) => {
@@ -110,13 +160,35 @@ async fn run_priority_processor<T>(
                 };
                 let handler = task_handler.clone();
                 let producer_clone = producer.clone();
+                stats::record_started(event.created_at);
 
                 tokio::spawn(async move {
                     let _permit = permit;
 
+                    handler.on_lifecycle(&event, &TaskLifecycle::Started).await;
                     match handler.handle_task(&event).await {
-                        Ok(_) => info!("Task {} completed successfully", event.id),
-                        Err(error) => handle_task_failure(event, producer_clone, error).await,
+                        Ok(_) => {
+                            stats::record_finished(TaskOutcome::Succeeded);
+                            handler
+                                .on_lifecycle(&event, &TaskLifecycle::Completed)
+                                .await;
+                            info!("Task {} completed successfully", event.id)
+                        }
+                        Err(error) => {
+                            let lifecycle = if event.should_retry() {
+                                stats::record_finished(TaskOutcome::Retried);
+                                TaskLifecycle::Retrying {
+                                    error: format!("{:#}", error),
+                                }
+                            } else {
+                                stats::record_finished(TaskOutcome::Failed);
+                                TaskLifecycle::Failed {
+                                    error: format!("{:#}", error),
+                                }
+                            };
+                            handler.on_lifecycle(&event, &lifecycle).await;
+                            handle_task_failure(event, producer_clone, error).await
+                        }
                     }
                 });
             }
@@ -0,0 +1,441 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    future::Future,
+    sync::{
+        LazyLock,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{sync::broadcast, time::sleep};
+
+use crate::url::mask_url;
+
+/// Separates the endpoints of a broker setting. Kafka bootstrap sets keep their commas, so
+/// `kafka-a:9092,kafka-b:9092;kafka-dr:9092` lists two sets.
+pub const ENDPOINT_SEPARATOR: char = ';';
+
+/// Health events kept for subscribers that fall behind
+const HEALTH_EVENT_CAPACITY: usize = 64;
+
+static HEALTH_EVENTS: LazyLock<broadcast::Sender<BrokerHealthEvent>> =
+    LazyLock::new(|| broadcast::channel(HEALTH_EVENT_CAPACITY).0);
+
+/// Failovers of every broker client in this process
+static FAILOVERS: AtomicU64 = AtomicU64::new(0);
+
+/// Backoff between rounds over the endpoints of a [`BrokerEndpoints`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailoverPolicy {
+    /// Delay after the first round in which every endpoint failed, doubled after each further
+    /// round up to `max_backoff`
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Rounds tried before connecting gives up. Reconnecting a consumer that lost its
+    /// connection keeps trying.
+    pub connect_rounds: u32,
+}
+
+impl Default for FailoverPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            connect_rounds: 3,
+        }
+    }
+}
+
+impl FailoverPolicy {
+    /// Delay after failed round number `round` (starting at 1)
+    fn backoff(&self, round: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(round.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+}
+
+/// Change in the connection of a broker client, published to [`subscribe_health_events`].
+/// Endpoints have their passwords masked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BrokerHealthEvent {
+    /// The connection to `endpoint` was lost
+    Disconnected {
+        client: String,
+        endpoint: String,
+        error: String,
+    },
+    /// The client moved from the endpoint `from` to `to`
+    FailedOver {
+        client: String,
+        from: String,
+        to: String,
+    },
+    /// The client connected to `endpoint` again after losing it
+    Reconnected { client: String, endpoint: String },
+    /// Every endpoint failed in `rounds` rounds in a row
+    Unavailable { client: String, rounds: u32 },
+}
+
+/// Receive the health events of every broker client in this process from now on
+pub fn subscribe_health_events() -> broadcast::Receiver<BrokerHealthEvent> {
+    HEALTH_EVENTS.subscribe()
+}
+
+/// Failovers of the broker clients in this process since it started
+pub fn failover_count() -> u64 {
+    FAILOVERS.load(Ordering::Relaxed)
+}
+
+fn emit(event: BrokerHealthEvent) {
+    match &event {
+        BrokerHealthEvent::Reconnected { client, endpoint } => {
+            tracing::info!(client, endpoint, "✓ Broker connection restored");
+        }
+        BrokerHealthEvent::FailedOver { client, from, to } => {
+            FAILOVERS.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(client, from, to, "Broker client failed over");
+        }
+        BrokerHealthEvent::Disconnected {
+            client,
+            endpoint,
+            error,
+        } => {
+            tracing::warn!(client, endpoint, error, "Broker connection lost");
+        }
+        BrokerHealthEvent::Unavailable { client, rounds } => {
+            tracing::error!(client, rounds, "No broker endpoint reachable");
+        }
+    }
+    // Nobody listening is fine
+    let _ = HEALTH_EVENTS.send(event);
+}
+
+/// Endpoints a broker client can connect to, tried in order from the one in use
+#[derive(Debug)]
+pub struct BrokerEndpoints {
+    client: String,
+    endpoints: Vec<String>,
+    active: AtomicUsize,
+    policy: FailoverPolicy,
+}
+
+impl BrokerEndpoints {
+    /// Endpoints of `client` (e.g. `"kafka producer"`) listed in `endpoints`, separated by
+    /// [`ENDPOINT_SEPARATOR`]
+    pub fn new(client: impl Into<String>, endpoints: &str, policy: FailoverPolicy) -> Self {
+        let mut parsed: Vec<String> = endpoints
+            .split(ENDPOINT_SEPARATOR)
+            .map(str::trim)
+            .filter(|endpoint| !endpoint.is_empty())
+            .map(str::to_string)
+            .collect();
+        if parsed.is_empty() {
+            // Leave reporting the missing endpoint to the broker client
+            parsed.push(endpoints.trim().to_string());
+        }
+
+        Self {
+            client: client.into(),
+            endpoints: parsed,
+            active: AtomicUsize::new(0),
+            policy,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.endpoints.iter().map(String::as_str)
+    }
+
+    /// Endpoint of the current (or last) connection
+    pub fn active(&self) -> &str {
+        &self.endpoints[self.active.load(Ordering::Relaxed)]
+    }
+
+    /// Connect to the endpoint in use or, when it fails, to the ones after it in turn,
+    /// giving up after `connect_rounds` rounds
+    pub async fn connect<T, F, Fut>(&self, connect: F) -> anyhow::Result<T>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let active = self.active.load(Ordering::Relaxed);
+        self.try_rounds(
+            active,
+            active,
+            false,
+            Some(self.policy.connect_rounds),
+            connect,
+        )
+        .await
+    }
+
+    /// Connect again after losing the connection to the endpoint in use with `error`,
+    /// starting from the next endpoint and giving up after `connect_rounds` rounds
+    pub async fn failover<T, F, Fut>(&self, error: impl Display, connect: F) -> anyhow::Result<T>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let active = self.disconnected(error);
+        self.try_rounds(
+            active,
+            (active + 1) % self.len(),
+            true,
+            Some(self.policy.connect_rounds),
+            connect,
+        )
+        .await
+    }
+
+    /// Like [`Self::failover`], but keeps trying until an endpoint accepts
+    pub async fn reconnect<T, F, Fut>(&self, error: impl Display, connect: F) -> T
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let active = self.disconnected(error);
+        match self
+            .try_rounds(active, (active + 1) % self.len(), true, None, connect)
+            .await
+        {
+            Ok(connection) => connection,
+            Err(_) => unreachable!("reconnecting without a round limit never gives up"),
+        }
+    }
+
+    fn disconnected(&self, error: impl Display) -> usize {
+        let active = self.active.load(Ordering::Relaxed);
+        emit(BrokerHealthEvent::Disconnected {
+            client: self.client.clone(),
+            endpoint: mask_url(&self.endpoints[active]),
+            error: error.to_string(),
+        });
+        active
+    }
+
+    async fn try_rounds<T, F, Fut>(
+        &self,
+        previous: usize,
+        start: usize,
+        reconnecting: bool,
+        max_rounds: Option<u32>,
+        mut connect: F,
+    ) -> anyhow::Result<T>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let mut round = 0;
+        loop {
+            let mut last_error = None;
+            for offset in 0..self.len() {
+                let index = (start + offset) % self.len();
+                match connect(self.endpoints[index].clone()).await {
+                    Ok(connection) => {
+                        self.connected(previous, index, reconnecting);
+                        return Ok(connection);
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            client = self.client,
+                            endpoint = mask_url(&self.endpoints[index]),
+                            "Failed to connect to broker: {:#}",
+                            e
+                        );
+                        last_error = Some(e);
+                    }
+                }
+            }
+
+            round += 1;
+            emit(BrokerHealthEvent::Unavailable {
+                client: self.client.clone(),
+                rounds: round,
+            });
+            if max_rounds.is_some_and(|max_rounds| round >= max_rounds) {
+                let error = last_error.unwrap_or_else(|| anyhow::anyhow!("No endpoint"));
+                return Err(error.context(format!("No {} endpoint reachable", self.client)));
+            }
+            sleep(self.policy.backoff(round)).await;
+        }
+    }
+
+    fn connected(&self, previous: usize, index: usize, reconnecting: bool) {
+        self.active.store(index, Ordering::Relaxed);
+        if index != previous {
+            emit(BrokerHealthEvent::FailedOver {
+                client: self.client.clone(),
+                from: mask_url(&self.endpoints[previous]),
+                to: mask_url(&self.endpoints[index]),
+            });
+        } else if reconnecting {
+            emit(BrokerHealthEvent::Reconnected {
+                client: self.client.clone(),
+                endpoint: mask_url(&self.endpoints[index]),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn policy() -> FailoverPolicy {
+        FailoverPolicy {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(2),
+            connect_rounds: 2,
+        }
+    }
+
+    /// Connector that fails for the endpoints in `down`, recording every attempt
+    fn connector<'a>(
+        down: &'static [&'static str],
+        attempts: &'a Mutex<Vec<String>>,
+    ) -> impl FnMut(String) -> std::future::Ready<anyhow::Result<String>> + 'a {
+        move |endpoint| {
+            attempts.lock().unwrap().push(endpoint.clone());
+            std::future::ready(if down.contains(&endpoint.as_str()) {
+                Err(anyhow::anyhow!("{} is down", endpoint))
+            } else {
+                Ok(endpoint)
+            })
+        }
+    }
+
+    #[test]
+    fn parses_endpoint_lists() {
+        let endpoints = BrokerEndpoints::new(
+            "kafka",
+            " a:9092,b:9092 ; c:9092;",
+            FailoverPolicy::default(),
+        );
+
+        assert_eq!(
+            endpoints.iter().collect::<Vec<_>>(),
+            ["a:9092,b:9092", "c:9092"]
+        );
+        assert_eq!(endpoints.active(), "a:9092,b:9092");
+        assert_eq!(
+            BrokerEndpoints::new("redis", "", FailoverPolicy::default()).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let policy = FailoverPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+            connect_rounds: 3,
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+        assert_eq!(policy.backoff(40), Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn connect_skips_unreachable_endpoints() {
+        let endpoints = BrokerEndpoints::new("test connect", "a;b;c", policy());
+        let attempts = Mutex::new(Vec::new());
+        let mut events = subscribe_health_events();
+
+        let connected = endpoints
+            .connect(connector(&["a"], &attempts))
+            .await
+            .unwrap();
+
+        assert_eq!(connected, "b");
+        assert_eq!(endpoints.active(), "b");
+        assert_eq!(*attempts.lock().unwrap(), ["a", "b"]);
+        let event = loop {
+            let event = events.recv().await.unwrap();
+            if matches!(&event, BrokerHealthEvent::FailedOver { client, .. } if client == "test connect")
+            {
+                break event;
+            }
+        };
+        assert_eq!(
+            event,
+            BrokerHealthEvent::FailedOver {
+                client: "test connect".to_string(),
+                from: "a".to_string(),
+                to: "b".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn failover_starts_from_the_next_endpoint() {
+        let endpoints = BrokerEndpoints::new("test failover", "a;b;c", policy());
+        let attempts = Mutex::new(Vec::new());
+        let failovers = failover_count();
+
+        let connected = endpoints
+            .failover("connection reset", connector(&["b"], &attempts))
+            .await
+            .unwrap();
+
+        assert_eq!(connected, "c");
+        assert_eq!(*attempts.lock().unwrap(), ["b", "c"]);
+        assert!(failover_count() > failovers);
+    }
+
+    #[tokio::test]
+    async fn failover_gives_up_after_the_configured_rounds() {
+        let endpoints = BrokerEndpoints::new("test give up", "a;b", policy());
+        let attempts = Mutex::new(Vec::new());
+
+        let result = endpoints
+            .failover("connection reset", connector(&["a", "b"], &attempts))
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(*attempts.lock().unwrap(), ["b", "a", "b", "a"]);
+        assert_eq!(endpoints.active(), "a");
+    }
+
+    #[tokio::test]
+    async fn reconnect_to_the_only_endpoint_is_not_a_failover() {
+        let endpoints = BrokerEndpoints::new("test reconnect", "a", policy());
+        let attempts = Mutex::new(Vec::new());
+        let mut events = subscribe_health_events();
+
+        let connected = endpoints
+            .reconnect("connection reset", connector(&[], &attempts))
+            .await;
+
+        assert_eq!(connected, "a");
+        let mut seen = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            match event {
+                BrokerHealthEvent::Disconnected { client, .. } if client == "test reconnect" => {
+                    seen.push("disconnected")
+                }
+                BrokerHealthEvent::Reconnected { client, .. } if client == "test reconnect" => {
+                    seen.push("reconnected")
+                }
+                BrokerHealthEvent::FailedOver { client, .. } if client == "test reconnect" => {
+                    seen.push("failed over")
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(seen, ["disconnected", "reconnected"]);
+    }
+}
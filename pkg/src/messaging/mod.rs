@@ -6,14 +6,33 @@ mod util;
 // Task module - contains interfaces/traits for task handling
 pub mod task;
 
+// Task queue counters reported by workers
+pub mod stats;
+
+// Broker endpoint lists, failover and health events
+pub mod failover;
+
+// TLS and credentials of broker connections
+pub mod security;
+
 // Re-export consumer types
-pub use consumer::{ConsumerConfig, MessageConsumer, create_consumer};
+pub use consumer::{ChannelConsumer, ConsumerConfig, MessageConsumer, create_consumer};
 
 // Re-export Kafka utilities
-pub use util::kafka_util::ensure_topics_exist;
+pub use util::kafka_util::{check_brokers, ensure_topics_exist};
+
+// Re-export failover types
+pub use failover::{BrokerEndpoints, BrokerHealthEvent, FailoverPolicy, subscribe_health_events};
+
+// Re-export security types
+pub use security::{BrokerCredentials, BrokerSecurity, BrokerTls, SaslMechanism};
 
 // Re-export producer types
-pub use producer::{MessageProducer, ProducerConfig, create_producer};
+pub use producer::{
+    ANY_TASK_TYPE, BROKER_BREAKER, CircuitBreakingProducer, DestinationRouter, EncodedMessage,
+    KafkaAcks, KafkaCompression, KafkaProducerTuning, MessageProducer, ProducerConfig,
+    RedisProducer, RoutingProducer, create_producer,
+};
 
 // Re-export task types
-pub use task::{TaskEvent, TaskHandler, TaskPriority};
+pub use task::{TaskEvent, TaskHandler, TaskLifecycle, TaskPriority};
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{EncodedMessage, MessageProducer};
+use crate::circuit_breaker::{self, CircuitBreaker};
+
+/// Name of the circuit breaker guarding publishes to the message broker
+pub const BROKER_BREAKER: &str = "broker";
+
+/// Producer failing fast while the broker behind `inner` keeps failing, instead of making
+/// every publish wait out its own failover and timeouts
+pub struct CircuitBreakingProducer {
+    inner: Arc<Box<dyn MessageProducer>>,
+    breaker: CircuitBreaker,
+}
+
+impl CircuitBreakingProducer {
+    pub fn new(inner: Arc<Box<dyn MessageProducer>>) -> Self {
+        Self {
+            inner,
+            breaker: circuit_breaker::breaker(BROKER_BREAKER),
+        }
+    }
+}
+
+#[async_trait]
+impl MessageProducer for CircuitBreakingProducer {
+    async fn publish(
+        &self,
+        message: &EncodedMessage,
+        destination: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.breaker
+            .call(self.inner.publish(message, destination))
+            .await
+    }
+}
@@ -4,51 +4,276 @@ use rdkafka::{
     ClientConfig,
     producer::{FutureProducer, FutureRecord},
 };
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
 
-use super::{MessageProducer, event_id_from_json};
+use super::{EncodedMessage, MessageProducer};
+use crate::messaging::{
+    failover::{BrokerEndpoints, FailoverPolicy},
+    security::BrokerSecurity,
+    util::kafka_util::check_brokers,
+};
+
+/// Compression codec of produced message batches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KafkaCompression {
+    None,
+    Gzip,
+    Snappy,
+    Lz4,
+    Zstd,
+}
+
+impl KafkaCompression {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "gzip" => Some(Self::Gzip),
+            "snappy" => Some(Self::Snappy),
+            "lz4" => Some(Self::Lz4),
+            "zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Gzip => "gzip",
+            Self::Snappy => "snappy",
+            Self::Lz4 => "lz4",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+/// Broker acknowledgements a message waits for before it counts as delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KafkaAcks {
+    /// Fire and forget
+    None,
+    /// The partition leader only
+    Leader,
+    /// Every in-sync replica
+    All,
+}
+
+impl KafkaAcks {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "0" | "none" => Some(Self::None),
+            "1" | "leader" => Some(Self::Leader),
+            "-1" | "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "0",
+            Self::Leader => "1",
+            Self::All => "all",
+        }
+    }
+}
+
+/// Batching and delivery settings of a [`KafkaProducer`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KafkaProducerTuning {
+    /// How long messages wait for more of their batch before being sent
+    pub linger: Duration,
+    /// Upper bound of a batch in bytes
+    pub batch_size: usize,
+    pub compression: KafkaCompression,
+    pub acks: KafkaAcks,
+    /// How long a message may take to be delivered, retries included, before it fails
+    pub delivery_timeout: Duration,
+}
+
+impl Default for KafkaProducerTuning {
+    fn default() -> Self {
+        Self {
+            linger: Duration::from_millis(5),
+            batch_size: 1_000_000,
+            compression: KafkaCompression::Lz4,
+            acks: KafkaAcks::All,
+            delivery_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl KafkaProducerTuning {
+    fn apply(&self, config: &mut ClientConfig) {
+        config
+            .set("linger.ms", self.linger.as_millis().to_string())
+            .set("batch.size", self.batch_size.to_string())
+            .set("compression.type", self.compression.as_str())
+            .set("acks", self.acks.as_str())
+            .set(
+                "delivery.timeout.ms",
+                self.delivery_timeout.as_millis().to_string(),
+            );
+    }
+}
 
 /// Kafka producer implementation
 pub struct KafkaProducer {
-    producer: FutureProducer,
+    endpoints: BrokerEndpoints,
+    producer: RwLock<FutureProducer>,
+    /// Held while switching to another bootstrap set
+    failing_over: Mutex<()>,
     default_topic: String,
+    tuning: KafkaProducerTuning,
+    security: BrokerSecurity,
 }
 
 impl KafkaProducer {
-    /// Create a new Kafka producer
-    pub async fn new(brokers: &str, default_topic: &str) -> Result<Self> {
-        let producer: FutureProducer = ClientConfig::new()
-            .set("bootstrap.servers", brokers)
-            .set("message.timeout.ms", "5000")
-            .set("compression.type", "lz4")
-            .create()
-            .context("Failed to create Kafka producer")?;
+    /// Create a new Kafka producer for the bootstrap sets listed in `brokers`
+    pub async fn new(
+        brokers: &str,
+        default_topic: &str,
+        tuning: &KafkaProducerTuning,
+        failover: &FailoverPolicy,
+        security: &BrokerSecurity,
+    ) -> Result<Self> {
+        let endpoints = BrokerEndpoints::new("kafka producer", brokers, failover.clone());
+        // librdkafka connects lazily and reconnects within a set by itself, so a single set is
+        // only checked once there is another one to fall back to
+        let producer = if endpoints.len() > 1 {
+            endpoints
+                .connect(|brokers| Self::connect(brokers, tuning, security))
+                .await?
+        } else {
+            Self::create(endpoints.active(), tuning, security)?
+        };
 
         Ok(Self {
-            producer,
+            endpoints,
+            producer: RwLock::new(producer),
+            failing_over: Mutex::new(()),
             default_topic: default_topic.to_string(),
+            tuning: tuning.clone(),
+            security: security.clone(),
         })
     }
+
+    fn create(
+        brokers: &str,
+        tuning: &KafkaProducerTuning,
+        security: &BrokerSecurity,
+    ) -> Result<FutureProducer> {
+        let mut config = security.kafka_client_config(brokers);
+        tuning.apply(&mut config);
+
+        config.create().context("Failed to create Kafka producer")
+    }
+
+    async fn connect(
+        brokers: String,
+        tuning: &KafkaProducerTuning,
+        security: &BrokerSecurity,
+    ) -> Result<FutureProducer> {
+        check_brokers(&brokers, tuning.delivery_timeout, security).await?;
+        Self::create(&brokers, tuning, security)
+    }
+
+    async fn send(
+        producer: &FutureProducer,
+        message: &EncodedMessage,
+        topic: &str,
+        timeout: Duration,
+    ) -> Result<()> {
+        // rdkafka copies straight from the payload buffer into its own queue
+        let record = FutureRecord::to(topic)
+            .key("default")
+            .payload(message.payload().as_ref());
+
+        producer
+            .send(record, timeout)
+            .await
+            .map_err(|(err, _)| anyhow::anyhow!("Failed to send message to Kafka: {:?}", err))?;
+        Ok(())
+    }
+
+    /// Switch to the next reachable bootstrap set after `brokers` failed with `error`,
+    /// unless another publish already did
+    async fn fail_over(&self, brokers: &str, error: &anyhow::Error) -> Result<FutureProducer> {
+        let _guard = self.failing_over.lock().await;
+        if self.endpoints.active() == brokers {
+            let producer = self
+                .endpoints
+                .failover(error, |brokers| {
+                    Self::connect(brokers, &self.tuning, &self.security)
+                })
+                .await?;
+            *self.producer.write().await = producer;
+        }
+        Ok(self.producer.read().await.clone())
+    }
 }
 
 #[async_trait]
 impl MessageProducer for KafkaProducer {
-    async fn publish_event_json(
+    async fn publish(
         &self,
-        event_json: &str,
+        message: &EncodedMessage,
         destination: Option<&str>,
     ) -> anyhow::Result<()> {
         let topic = destination.unwrap_or(&self.default_topic);
 
-        let event_id = event_id_from_json(event_json);
-        let record = FutureRecord::to(topic).key("default").payload(event_json);
+        let event_id = message.id();
+        let brokers = self.endpoints.active().to_string();
+        let producer = self.producer.read().await.clone();
 
-        self.producer
-            .send(record, Duration::from_secs(5))
-            .await
-            .map_err(|(err, _)| anyhow::anyhow!("Failed to send message to Kafka: {:?}", err))?;
+        match Self::send(&producer, message, topic, self.tuning.delivery_timeout).await {
+            Err(e) if self.endpoints.len() > 1 => {
+                let producer = self.fail_over(&brokers, &e).await?;
+                Self::send(&producer, message, topic, self.tuning.delivery_timeout).await?;
+            }
+            result => result?,
+        }
 
         tracing::info!("Published task event {} to Kafka topic {}", event_id, topic);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_compression_and_acks() {
+        assert_eq!(
+            KafkaCompression::parse("ZSTD"),
+            Some(KafkaCompression::Zstd)
+        );
+        assert_eq!(KafkaCompression::parse("brotli"), None);
+        assert_eq!(KafkaAcks::parse("1"), Some(KafkaAcks::Leader));
+        assert_eq!(KafkaAcks::parse("all"), Some(KafkaAcks::All));
+        assert_eq!(KafkaAcks::parse("2"), None);
+    }
+
+    #[test]
+    fn applies_tuning_to_client_config() {
+        let tuning = KafkaProducerTuning {
+            linger: Duration::from_millis(20),
+            batch_size: 65_536,
+            compression: KafkaCompression::Zstd,
+            acks: KafkaAcks::Leader,
+            delivery_timeout: Duration::from_secs(30),
+        };
+        let mut config = ClientConfig::new();
+
+        tuning.apply(&mut config);
+
+        assert_eq!(config.get("linger.ms"), Some("20"));
+        assert_eq!(config.get("batch.size"), Some("65536"));
+        assert_eq!(config.get("compression.type"), Some("zstd"));
+        assert_eq!(config.get("acks"), Some("1"));
+        assert_eq!(config.get("delivery.timeout.ms"), Some("30000"));
+    }
+}
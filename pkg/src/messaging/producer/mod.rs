@@ -1,52 +1,178 @@
 // Producer implementations
+mod circuit_breaking;
 mod kafka_producer;
 mod rabbitmq_producer;
 mod redis_producer;
+mod routing;
+
+pub use circuit_breaking::{BROKER_BREAKER, CircuitBreakingProducer};
+pub use kafka_producer::{KafkaAcks, KafkaCompression, KafkaProducerTuning};
+pub use redis_producer::RedisProducer;
+pub use routing::{ANY_TASK_TYPE, DestinationRouter, RoutingProducer};
 
 use async_trait::async_trait;
-use serde_json::Value;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::messaging::{TaskEvent, failover::FailoverPolicy, security::BrokerSecurity};
+
+/// A message serialized once by the caller and handed to the broker as-is, without
+/// being copied or parsed again on the way
+#[derive(Debug, Clone)]
+pub struct EncodedMessage {
+    id: Option<String>,
+    payload: Bytes,
+}
+
+impl EncodedMessage {
+    /// Serialize `event`, keeping its id for broker keys and logs
+    pub fn from_event<T>(event: &TaskEvent<T>) -> anyhow::Result<Self>
+    where
+        T: Clone + Send + Sync + Serialize,
+    {
+        Ok(Self {
+            id: Some(event.id.clone()),
+            ..Self::encode(event)?
+        })
+    }
+
+    /// Serialize any value, e.g. a broadcast message
+    pub fn encode<T: Serialize>(value: &T) -> anyhow::Result<Self> {
+        let payload = serde_json::to_vec(value)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize event: {}", e))?;
+        Ok(Self {
+            id: None,
+            payload: Bytes::from(payload),
+        })
+    }
+
+    /// Wrap JSON that is already serialized
+    pub fn from_json(json: &str) -> Self {
+        Self {
+            id: None,
+            payload: Bytes::copy_from_slice(json.as_bytes()),
+        }
+    }
+
+    /// Id of the event, read from the payload when it wasn't built from a `TaskEvent`
+    pub fn id(&self) -> String {
+        #[derive(Deserialize)]
+        struct EventId {
+            id: Option<String>,
+        }
+
+        self.id
+            .clone()
+            .or_else(|| {
+                serde_json::from_slice::<EventId>(&self.payload)
+                    .ok()
+                    .and_then(|event| event.id)
+            })
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+    }
+
+    /// `type` tag of the task carried by the payload, `None` for other messages
+    pub fn task_type(&self) -> Option<String> {
+        #[derive(Deserialize)]
+        struct TaggedTask {
+            r#type: String,
+        }
+        #[derive(Deserialize)]
+        struct EventTask {
+            task: TaggedTask,
+        }
+
+        serde_json::from_slice::<EventTask>(&self.payload)
+            .ok()
+            .map(|event| event.task.r#type)
+    }
+
+    pub fn payload(&self) -> &Bytes {
+        &self.payload
+    }
+
+    /// The payload as JSON text
+    pub fn as_str(&self) -> &str {
+        // Every constructor produces JSON, which is always valid UTF-8
+        std::str::from_utf8(&self.payload).unwrap_or_default()
+    }
+}
 
 /// Generic message producer trait for publishing task events to different message brokers
 /// Works with any task type T that is serializable
 #[async_trait]
 pub trait MessageProducer: Send + Sync {
-    /// Publish a generic task event to the broker (serializes to JSON)
+    /// Publish an already serialized message to the broker
+    async fn publish(
+        &self,
+        message: &EncodedMessage,
+        destination: Option<&str>,
+    ) -> anyhow::Result<()>;
+
+    /// Publish JSON built by the caller (e.g. a broadcast message)
     async fn publish_event_json(
         &self,
         event_json: &str,
         destination: Option<&str>,
-    ) -> anyhow::Result<()>;
+    ) -> anyhow::Result<()> {
+        self.publish(&EncodedMessage::from_json(event_json), destination)
+            .await
+    }
 }
 
-/// Producer configuration enum
+/// Producer configuration enum. `brokers` and `url` may list several endpoints separated by
+/// `;` to fail over between; Redis follows the failover of its shared connection.
 #[derive(Debug, Clone)]
 pub enum ProducerConfig {
     Kafka {
         brokers: String,
         default_topic: String,
+        tuning: KafkaProducerTuning,
+        failover: FailoverPolicy,
+        security: BrokerSecurity,
     },
     RabbitMQ {
         url: String,
         default_queue: String,
+        failover: FailoverPolicy,
+        security: BrokerSecurity,
     },
     Redis {
         url: String,
         default_channel: String,
+        security: BrokerSecurity,
     },
 }
 
 impl ProducerConfig {
     /// Create Kafka producer configuration
     pub fn kafka(brokers: String, default_topic: String) -> Self {
+        Self::kafka_with_tuning(brokers, default_topic, KafkaProducerTuning::default())
+    }
+
+    /// Create Kafka producer configuration with explicit batching and delivery settings
+    pub fn kafka_with_tuning(
+        brokers: String,
+        default_topic: String,
+        tuning: KafkaProducerTuning,
+    ) -> Self {
         ProducerConfig::Kafka {
             brokers,
             default_topic,
+            tuning,
+            failover: FailoverPolicy::default(),
+            security: BrokerSecurity::default(),
         }
     }
 
     /// Create RabbitMQ producer configuration
     pub fn rabbitmq(url: String, default_queue: String) -> Self {
-        ProducerConfig::RabbitMQ { url, default_queue }
+        ProducerConfig::RabbitMQ {
+            url,
+            default_queue,
+            failover: FailoverPolicy::default(),
+            security: BrokerSecurity::default(),
+        }
     }
 
     /// Create Redis producer configuration
@@ -54,15 +180,30 @@ impl ProducerConfig {
         ProducerConfig::Redis {
             url,
             default_channel,
+            security: BrokerSecurity::default(),
         }
     }
-}
 
-fn event_id_from_json(event_json: &str) -> String {
-    serde_json::from_str::<Value>(event_json)
-        .ok()
-        .and_then(|value| value.get("id").and_then(Value::as_str).map(str::to_owned))
-        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+    /// Use `policy` to reconnect and fail over between the listed endpoints
+    pub fn with_failover(mut self, policy: FailoverPolicy) -> Self {
+        match &mut self {
+            ProducerConfig::Kafka { failover, .. } | ProducerConfig::RabbitMQ { failover, .. } => {
+                *failover = policy
+            }
+            ProducerConfig::Redis { .. } => {}
+        }
+        self
+    }
+
+    /// Connect over TLS and authenticate as configured in `settings`
+    pub fn with_security(mut self, settings: BrokerSecurity) -> Self {
+        match &mut self {
+            ProducerConfig::Kafka { security, .. }
+            | ProducerConfig::RabbitMQ { security, .. }
+            | ProducerConfig::Redis { security, .. } => *security = settings,
+        }
+        self
+    }
 }
 
 /// Create a message producer based on configuration (async version)
@@ -71,22 +212,84 @@ pub async fn create_producer(config: ProducerConfig) -> anyhow::Result<Box<dyn M
         ProducerConfig::Kafka {
             brokers,
             default_topic,
+            tuning,
+            failover,
+            security,
         } => {
             use kafka_producer::KafkaProducer;
             Ok(Box::new(
-                KafkaProducer::new(&brokers, &default_topic).await?,
+                KafkaProducer::new(&brokers, &default_topic, &tuning, &failover, &security).await?,
             ))
         }
-        ProducerConfig::RabbitMQ { url, default_queue } => {
+        ProducerConfig::RabbitMQ {
+            url,
+            default_queue,
+            failover,
+            security,
+        } => {
             use rabbitmq_producer::RabbitMQProducer;
-            Ok(Box::new(RabbitMQProducer::new(&url, &default_queue).await?))
+            Ok(Box::new(
+                RabbitMQProducer::new(&url, &default_queue, &failover, &security).await?,
+            ))
         }
         ProducerConfig::Redis {
             url,
             default_channel,
+            security,
         } => {
             use redis_producer::RedisProducer;
-            Ok(Box::new(RedisProducer::new(&url, &default_channel).await?))
+            Ok(Box::new(
+                RedisProducer::new(&url, &default_channel, &security).await?,
+            ))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_events_once_with_their_id() {
+        let event = TaskEvent::new("task".to_string());
+
+        let message = EncodedMessage::from_event(&event).unwrap();
+
+        assert_eq!(message.id(), event.id);
+        let decoded: TaskEvent<String> = serde_json::from_slice(message.payload()).unwrap();
+        assert_eq!(decoded.task, "task");
+    }
+
+    #[test]
+    fn reads_the_id_of_raw_json() {
+        let message = EncodedMessage::from_json(r#"{"id":"abc","task":null}"#);
+
+        assert_eq!(message.id(), "abc");
+        assert_eq!(message.as_str(), r#"{"id":"abc","task":null}"#);
+    }
+
+    #[test]
+    fn reads_the_task_type_of_task_events() {
+        let event = TaskEvent::new(serde_json::json!({ "type": "SendEmail" }));
+
+        assert_eq!(
+            EncodedMessage::from_event(&event)
+                .unwrap()
+                .task_type()
+                .as_deref(),
+            Some("SendEmail")
+        );
+        assert_eq!(
+            EncodedMessage::from_json(r#"{"event_type":"x"}"#).task_type(),
+            None
+        );
+    }
+
+    #[test]
+    fn clones_share_the_payload() {
+        let message = EncodedMessage::encode(&serde_json::json!({ "a": 1 })).unwrap();
+        let clone = message.clone();
+
+        assert_eq!(message.payload().as_ptr(), clone.payload().as_ptr());
+    }
+}
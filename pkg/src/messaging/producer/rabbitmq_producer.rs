@@ -1,44 +1,64 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use lapin::{
-    BasicProperties, Connection, ConnectionProperties,
+    BasicProperties, Connection,
     options::{BasicPublishOptions, QueueDeclareOptions},
     types::FieldTable,
 };
+use std::sync::{Arc, RwLock};
+use tokio::sync::Mutex;
 
-use super::{MessageProducer, event_id_from_json};
+use super::{EncodedMessage, MessageProducer};
+use crate::messaging::{
+    failover::{BrokerEndpoints, FailoverPolicy},
+    security::BrokerSecurity,
+};
 
 /// RabbitMQ producer implementation
 pub struct RabbitMQProducer {
-    connection: Connection,
+    endpoints: BrokerEndpoints,
+    security: BrokerSecurity,
+    connection: RwLock<Arc<Connection>>,
+    /// Held while reconnecting
+    failing_over: Mutex<()>,
     default_queue: String,
 }
 
 impl RabbitMQProducer {
-    pub async fn new(url: &str, default_queue: &str) -> Result<Self> {
-        let connection = Connection::connect(url, ConnectionProperties::default())
-            .await
-            .context("Failed to connect to RabbitMQ")?;
+    /// Connect to the first reachable URL listed in `url`
+    pub async fn new(
+        url: &str,
+        default_queue: &str,
+        failover: &FailoverPolicy,
+        security: &BrokerSecurity,
+    ) -> Result<Self> {
+        let endpoints = BrokerEndpoints::new("rabbitmq producer", url, failover.clone());
+        let connection = endpoints
+            .connect(|url| Self::connect(url, security))
+            .await?;
 
         Ok(Self {
-            connection,
+            endpoints,
+            security: security.clone(),
+            connection: RwLock::new(Arc::new(connection)),
+            failing_over: Mutex::new(()),
             default_queue: default_queue.to_string(),
         })
     }
-}
 
-#[async_trait]
-impl MessageProducer for RabbitMQProducer {
-    async fn publish_event_json(
-        &self,
-        event_json: &str,
-        destination: Option<&str>,
-    ) -> anyhow::Result<()> {
-        let queue = destination.unwrap_or(&self.default_queue);
+    async fn connect(url: String, security: &BrokerSecurity) -> Result<Connection> {
+        security
+            .connect_rabbitmq(&url)
+            .await
+            .context("Failed to connect to RabbitMQ")
+    }
 
-        let event_id = event_id_from_json(event_json);
-        let channel = self
-            .connection
+    fn current(&self) -> Arc<Connection> {
+        self.connection.read().unwrap().clone()
+    }
+
+    async fn send(connection: &Connection, message: &EncodedMessage, queue: &str) -> Result<()> {
+        let channel = connection
             .create_channel()
             .await
             .context("Failed to create RabbitMQ channel")?;
@@ -60,7 +80,7 @@ impl MessageProducer for RabbitMQProducer {
                 "".into(),
                 queue.into(),
                 BasicPublishOptions::default(),
-                event_json.as_bytes(),
+                message.payload(),
                 BasicProperties::default()
                     .with_delivery_mode(2) // Persistent
                     .with_content_type("application/json".into()),
@@ -69,6 +89,43 @@ impl MessageProducer for RabbitMQProducer {
             .context("Failed to publish message to RabbitMQ")?
             .await
             .context("Failed to confirm message publish to RabbitMQ")?;
+        Ok(())
+    }
+
+    /// Connect again, to the next reachable URL, after `failed` was lost with `error`,
+    /// unless another publish already did
+    async fn fail_over(&self, failed: &Arc<Connection>, error: &anyhow::Error) -> Result<()> {
+        let _guard = self.failing_over.lock().await;
+        if Arc::ptr_eq(failed, &self.current()) {
+            let connection = self
+                .endpoints
+                .failover(error, |url| Self::connect(url, &self.security))
+                .await?;
+            *self.connection.write().unwrap() = Arc::new(connection);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MessageProducer for RabbitMQProducer {
+    async fn publish(
+        &self,
+        message: &EncodedMessage,
+        destination: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let queue = destination.unwrap_or(&self.default_queue);
+
+        let event_id = message.id();
+        let connection = self.current();
+
+        match Self::send(&connection, message, queue).await {
+            Err(e) if !connection.status().connected() => {
+                self.fail_over(&connection, &e).await?;
+                Self::send(&self.current(), message, queue).await?;
+            }
+            result => result?,
+        }
 
         tracing::info!(
             "✓ Published task event {} to RabbitMQ queue: {}",
@@ -1,45 +1,52 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use redis::{AsyncCommands, Client, aio::MultiplexedConnection};
+use redis::AsyncCommands;
 
-use super::{MessageProducer, event_id_from_json};
+use super::{EncodedMessage, MessageProducer};
+use crate::{
+    messaging::security::BrokerSecurity,
+    redis::{RedisConnection, RedisConnectionManager, RedisPoolConfig},
+};
 
 /// Redis producer implementation (Pub/Sub)
 pub struct RedisProducer {
-    connection: MultiplexedConnection,
+    connection: RedisConnection,
     default_channel: String,
 }
 
 impl RedisProducer {
-    pub async fn new(url: &str, default_channel: &str) -> Result<Self> {
-        let client = Client::open(url).context("Failed to create Redis client")?;
-
-        let connection = client
-            .get_multiplexed_async_connection()
-            .await
-            .context("Failed to connect to Redis")?;
+    /// Publish on the process-wide connection for `url`
+    pub async fn new(url: &str, default_channel: &str, security: &BrokerSecurity) -> Result<Self> {
+        let config = RedisPoolConfig {
+            security: security.clone(),
+            ..Default::default()
+        };
+        let manager = RedisConnectionManager::shared(url, &config).await?;
+        Ok(Self::from_manager(&manager, default_channel))
+    }
 
-        Ok(Self {
-            connection,
+    pub fn from_manager(manager: &RedisConnectionManager, default_channel: &str) -> Self {
+        Self {
+            connection: manager.connection(),
             default_channel: default_channel.to_string(),
-        })
+        }
     }
 }
 
 #[async_trait]
 impl MessageProducer for RedisProducer {
-    async fn publish_event_json(
+    async fn publish(
         &self,
-        event_json: &str,
+        message: &EncodedMessage,
         destination: Option<&str>,
     ) -> anyhow::Result<()> {
         let channel = destination.unwrap_or(&self.default_channel);
 
-        let event_id = event_id_from_json(event_json);
+        let event_id = message.id();
         let mut conn = self.connection.clone();
 
         let subscriber_count: i32 = conn
-            .publish(channel, event_json)
+            .publish(channel, message.payload().as_ref())
             .await
             .context("Failed to publish message to Redis")?;
 
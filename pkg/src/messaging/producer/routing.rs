@@ -0,0 +1,235 @@
+use std::{collections::HashMap, sync::Arc, sync::Mutex};
+
+use async_trait::async_trait;
+
+use super::{EncodedMessage, MessageProducer};
+
+/// Task type matched by a route that applies to every task without a route of its own
+pub const ANY_TASK_TYPE: &str = "*";
+
+/// Destinations sharing the traffic of one task type by weight, in smooth weighted
+/// round-robin: with weights 3 and 1, every four messages go `a a b a` rather than `a a a b`
+#[derive(Debug)]
+struct WeightedDestinations {
+    destinations: Vec<(String, i64)>,
+    current: Mutex<Vec<i64>>,
+}
+
+impl WeightedDestinations {
+    fn new(destinations: Vec<(String, i64)>) -> Self {
+        let current = vec![0; destinations.len()];
+        Self {
+            destinations,
+            current: Mutex::new(current),
+        }
+    }
+
+    fn next(&self) -> &str {
+        let total: i64 = self.destinations.iter().map(|(_, weight)| weight).sum();
+        let mut current = self.current.lock().unwrap();
+        let mut best = 0;
+        for (index, (_, weight)) in self.destinations.iter().enumerate() {
+            current[index] += weight;
+            if current[index] > current[best] {
+                best = index;
+            }
+        }
+        current[best] -= total;
+        &self.destinations[best].0
+    }
+}
+
+/// Destinations of messages published without one, by the `type` tag of their task
+#[derive(Debug, Default)]
+pub struct DestinationRouter {
+    routes: HashMap<String, WeightedDestinations>,
+}
+
+impl DestinationRouter {
+    /// Parse routes such as `SendEmail=emails:3,emails_v2:1;*=tasks`: `;` separates task
+    /// types, `,` their destinations and `:` an optional weight (1 by default). `*` routes
+    /// the task types without a route of their own.
+    pub fn parse(routes: &str) -> anyhow::Result<Self> {
+        let mut router = Self::default();
+        for route in routes.split(';').map(str::trim).filter(|r| !r.is_empty()) {
+            let (task_type, destinations) = route
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Route {} is not TYPE=DESTINATIONS", route))?;
+            let destinations = destinations
+                .split(',')
+                .map(str::trim)
+                .filter(|destination| !destination.is_empty())
+                .map(|destination| match destination.split_once(':') {
+                    Some((name, weight)) => weight
+                        .trim()
+                        .parse::<u32>()
+                        .map(|weight| (name.trim().to_string(), weight as i64))
+                        .map_err(|_| anyhow::anyhow!("Invalid weight in route {}", route)),
+                    None => Ok((destination.to_string(), 1)),
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+                .into_iter()
+                .filter(|(_, weight)| *weight > 0)
+                .collect::<Vec<_>>();
+            if destinations.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Route {} has no destination with a positive weight",
+                    route
+                ));
+            }
+            router.routes.insert(
+                task_type.trim().to_string(),
+                WeightedDestinations::new(destinations),
+            );
+        }
+        Ok(router)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Destination of the next task of `task_type`, `None` when no route applies
+    pub fn resolve(&self, task_type: Option<&str>) -> Option<&str> {
+        task_type
+            .and_then(|task_type| self.routes.get(task_type))
+            .or_else(|| self.routes.get(ANY_TASK_TYPE))
+            .map(WeightedDestinations::next)
+    }
+}
+
+/// Producer sending messages published without a destination to the one `router` picks
+/// for their task type; the others keep the default destination of `inner`
+pub struct RoutingProducer {
+    inner: Arc<Box<dyn MessageProducer>>,
+    router: DestinationRouter,
+}
+
+impl RoutingProducer {
+    pub fn new(inner: Arc<Box<dyn MessageProducer>>, router: DestinationRouter) -> Self {
+        Self { inner, router }
+    }
+}
+
+#[async_trait]
+impl MessageProducer for RoutingProducer {
+    async fn publish(
+        &self,
+        message: &EncodedMessage,
+        destination: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if destination.is_some() {
+            return self.inner.publish(message, destination).await;
+        }
+
+        let task_type = message.task_type();
+        let destination = self.router.resolve(task_type.as_deref());
+        self.inner.publish(message, destination).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingProducer {
+        destinations: Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait]
+    impl MessageProducer for Arc<RecordingProducer> {
+        async fn publish(
+            &self,
+            _message: &EncodedMessage,
+            destination: Option<&str>,
+        ) -> anyhow::Result<()> {
+            self.destinations
+                .lock()
+                .unwrap()
+                .push(destination.map(str::to_string));
+            Ok(())
+        }
+    }
+
+    fn task(task_type: &str) -> EncodedMessage {
+        EncodedMessage::from_json(&format!(
+            r#"{{"id":"1","task":{{"type":"{}"}}}}"#,
+            task_type
+        ))
+    }
+
+    #[test]
+    fn spreads_traffic_by_weight_smoothly() {
+        let router = DestinationRouter::parse("SendEmail=emails:3,emails_v2:1").unwrap();
+
+        let picked: Vec<&str> = (0..8)
+            .map(|_| router.resolve(Some("SendEmail")).unwrap())
+            .collect();
+
+        assert_eq!(
+            picked,
+            vec![
+                "emails",
+                "emails",
+                "emails_v2",
+                "emails",
+                "emails",
+                "emails",
+                "emails_v2",
+                "emails"
+            ]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_wildcard_route() {
+        let router = DestinationRouter::parse("SendEmail=emails; *=tasks").unwrap();
+
+        assert_eq!(router.resolve(Some("SendEmail")), Some("emails"));
+        assert_eq!(router.resolve(Some("GenerateReport")), Some("tasks"));
+        assert_eq!(router.resolve(None), Some("tasks"));
+        assert_eq!(
+            DestinationRouter::parse("SendEmail=emails")
+                .unwrap()
+                .resolve(Some("GenerateReport")),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_routes() {
+        assert!(DestinationRouter::parse("SendEmail").is_err());
+        assert!(DestinationRouter::parse("SendEmail=emails:many").is_err());
+        assert!(DestinationRouter::parse("SendEmail=emails:0").is_err());
+        assert!(DestinationRouter::parse("").unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn routes_only_messages_without_a_destination() {
+        let recording = Arc::new(RecordingProducer::default());
+        let producer = RoutingProducer::new(
+            Arc::new(Box::new(recording.clone())),
+            DestinationRouter::parse("SendEmail=emails_v2").unwrap(),
+        );
+
+        producer.publish(&task("SendEmail"), None).await.unwrap();
+        producer
+            .publish(&task("SendEmail"), Some("emails"))
+            .await
+            .unwrap();
+        producer
+            .publish_event_json(r#"{"task":{"type":"GenerateReport"}}"#, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *recording.destinations.lock().unwrap(),
+            vec![
+                Some("emails_v2".to_string()),
+                Some("emails".to_string()),
+                None
+            ]
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::circuit_breaker::{BreakerSnapshot, BreakerState};
+
+/// Event type of the broadcasts workers report their [`WorkerStats`] with
+pub const WORKER_STATS_EVENT: &str = "worker_stats";
+
+/// Task queue counters of one worker process
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkerStats {
+    pub worker_id: String,
+    /// Tasks received but waiting for a free worker slot
+    pub queued: u64,
+    /// Tasks being handled right now
+    pub running: u64,
+    pub succeeded: u64,
+    /// Tasks that failed for good, after their last retry
+    pub failed: u64,
+    pub retried: u64,
+    /// Milliseconds between publishing and starting the most recent task
+    pub lag_ms: u64,
+    /// Times the worker's broker clients failed over to another endpoint
+    #[serde(default)]
+    pub broker_failovers: u64,
+    /// Queued tasks held back because their task type is paused
+    #[serde(default)]
+    pub paused: u64,
+    /// Tasks that finished, successfully or not, over the last minute
+    #[serde(default)]
+    pub processed_last_minute: u64,
+    /// Circuit breakers of the dependencies the worker called, such as SMTP
+    #[serde(default)]
+    pub circuit_breakers: Vec<BreakerSnapshot>,
+}
+
+#[derive(Default)]
+struct Counters {
+    queued: AtomicU64,
+    running: AtomicU64,
+    succeeded: AtomicU64,
+    failed: AtomicU64,
+    retried: AtomicU64,
+    lag_ms: AtomicU64,
+    paused: AtomicU64,
+    /// When the tasks of the last minute finished
+    finished_at: Mutex<VecDeque<Instant>>,
+}
+
+/// Window of `WorkerStats::processed_last_minute`
+const PROCESSING_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Counters of the task queues in this process
+static COUNTERS: LazyLock<Counters> = LazyLock::new(Counters::default);
+
+/// Latest stats reported by each worker, as seen by the API
+static REPORTED: LazyLock<Mutex<HashMap<String, (WorkerStats, Instant)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) fn record_enqueued() {
+    COUNTERS.queued.fetch_add(1, Ordering::Relaxed);
+}
+
+pub(crate) fn record_started(created_at: chrono::DateTime<chrono::Utc>) {
+    let lag = (chrono::Utc::now() - created_at).num_milliseconds().max(0) as u64;
+    COUNTERS.lag_ms.store(lag, Ordering::Relaxed);
+    COUNTERS
+        .queued
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+        .ok();
+    COUNTERS.running.fetch_add(1, Ordering::Relaxed);
+}
+
+pub(crate) fn record_finished(outcome: TaskOutcome) {
+    COUNTERS
+        .running
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+        .ok();
+    let counter = match outcome {
+        TaskOutcome::Succeeded => &COUNTERS.succeeded,
+        TaskOutcome::Retried => &COUNTERS.retried,
+        TaskOutcome::Failed => &COUNTERS.failed,
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+
+    let mut finished_at = COUNTERS.finished_at.lock().unwrap();
+    finished_at.push_back(Instant::now());
+    prune_finished(&mut finished_at);
+}
+
+pub(crate) fn record_paused(count: u64) {
+    COUNTERS.paused.store(count, Ordering::Relaxed);
+}
+
+fn prune_finished(finished_at: &mut VecDeque<Instant>) {
+    while finished_at
+        .front()
+        .is_some_and(|at| at.elapsed() > PROCESSING_RATE_WINDOW)
+    {
+        finished_at.pop_front();
+    }
+}
+
+pub(crate) enum TaskOutcome {
+    Succeeded,
+    Retried,
+    Failed,
+}
+
+/// Stats of the task queues in this process, reported as `worker_id`
+pub fn local_worker_stats(worker_id: &str) -> WorkerStats {
+    let processed_last_minute = {
+        let mut finished_at = COUNTERS.finished_at.lock().unwrap();
+        prune_finished(&mut finished_at);
+        finished_at.len() as u64
+    };
+
+    WorkerStats {
+        worker_id: worker_id.to_string(),
+        queued: COUNTERS.queued.load(Ordering::Relaxed),
+        running: COUNTERS.running.load(Ordering::Relaxed),
+        succeeded: COUNTERS.succeeded.load(Ordering::Relaxed),
+        failed: COUNTERS.failed.load(Ordering::Relaxed),
+        retried: COUNTERS.retried.load(Ordering::Relaxed),
+        lag_ms: COUNTERS.lag_ms.load(Ordering::Relaxed),
+        broker_failovers: super::failover::failover_count(),
+        paused: COUNTERS.paused.load(Ordering::Relaxed),
+        processed_last_minute,
+        circuit_breakers: crate::circuit_breaker::snapshots(),
+    }
+}
+
+/// Keep the stats a worker reported, replacing its previous report
+pub fn record_worker_stats(stats: WorkerStats) {
+    REPORTED
+        .lock()
+        .unwrap()
+        .insert(stats.worker_id.clone(), (stats, Instant::now()));
+}
+
+/// Reports older than this many worker stats intervals are from workers that stopped
+pub const STALE_REPORT_INTERVALS: u32 = 3;
+
+/// Latest stats of the workers that reported within `max_age`, by worker id
+pub fn reported_worker_stats(max_age: Duration) -> Vec<WorkerStats> {
+    let mut reported = REPORTED.lock().unwrap();
+    reported.retain(|_, (_, received_at)| received_at.elapsed() <= max_age);
+
+    let mut stats: Vec<WorkerStats> = reported.values().map(|(stats, _)| stats.clone()).collect();
+    stats.sort_by(|a, b| a.worker_id.cmp(&b.worker_id));
+    stats
+}
+
+/// Whether every worker that reported within `max_age` has the breaker of the dependency
+/// `name` open, so none of them can reach it. `false` when no worker reported.
+pub fn reported_dependency_down(name: &str, max_age: Duration) -> bool {
+    dependency_down(&reported_worker_stats(max_age), name)
+}
+
+fn dependency_down(workers: &[WorkerStats], name: &str) -> bool {
+    !workers.is_empty()
+        && workers.iter().all(|worker| {
+            worker
+                .circuit_breakers
+                .iter()
+                .any(|breaker| breaker.name == name && breaker.state == BreakerState::Open)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_task_lifecycle() {
+        let before = local_worker_stats("local");
+
+        record_enqueued();
+        record_started(chrono::Utc::now() - chrono::Duration::milliseconds(250));
+        record_finished(TaskOutcome::Succeeded);
+        record_enqueued();
+        record_started(chrono::Utc::now());
+        record_finished(TaskOutcome::Failed);
+
+        let after = local_worker_stats("local");
+        assert!(after.succeeded > before.succeeded);
+        assert!(after.failed > before.failed);
+        assert!(after.processed_last_minute >= 2);
+    }
+
+    #[test]
+    fn keeps_latest_report_per_worker() {
+        let worker_id = uuid::Uuid::new_v4().to_string();
+        let report = |succeeded| WorkerStats {
+            worker_id: worker_id.clone(),
+            succeeded,
+            ..Default::default()
+        };
+
+        record_worker_stats(report(1));
+        record_worker_stats(report(2));
+
+        let reported: Vec<WorkerStats> = reported_worker_stats(Duration::from_secs(60))
+            .into_iter()
+            .filter(|stats| stats.worker_id == worker_id)
+            .collect();
+        assert_eq!(reported, vec![report(2)]);
+        assert!(
+            !reported_worker_stats(Duration::ZERO)
+                .iter()
+                .any(|stats| stats.worker_id == worker_id)
+        );
+    }
+
+    #[test]
+    fn dependency_is_down_when_every_worker_has_its_breaker_open() {
+        let breaker = |state| BreakerSnapshot {
+            name: "smtp".to_string(),
+            state,
+            health_score: 0.0,
+            consecutive_failures: 5,
+            opened_total: 1,
+            rejected_total: 0,
+        };
+        let worker = |breakers| WorkerStats {
+            circuit_breakers: breakers,
+            ..Default::default()
+        };
+
+        let open = worker(vec![breaker(BreakerState::Open)]);
+        let half_open = worker(vec![breaker(BreakerState::HalfOpen)]);
+        assert!(dependency_down(std::slice::from_ref(&open), "smtp"));
+        assert!(!dependency_down(std::slice::from_ref(&open), "broker"));
+        assert!(!dependency_down(&[open.clone(), half_open], "smtp"));
+        assert!(!dependency_down(&[open, worker(vec![])], "smtp"));
+        assert!(!dependency_down(&[], "smtp"));
+    }
+}
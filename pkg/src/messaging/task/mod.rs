@@ -3,4 +3,4 @@ pub mod task_handler;
 
 // Re-export commonly used types
 pub use task_event::{TaskEvent, TaskPriority};
-pub use task_handler::TaskHandler;
+pub use task_handler::{TaskHandler, TaskLifecycle};
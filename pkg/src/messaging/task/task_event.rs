@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::messaging::MessageProducer;
+use crate::messaging::{EncodedMessage, MessageProducer};
 
 /// Priority level for task execution
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
@@ -64,6 +64,21 @@ where
         self.retry_count += 1;
     }
 
+    /// Copy of this event's metadata carrying a different payload
+    pub fn with_task<U>(&self, task: U) -> TaskEvent<U>
+    where
+        U: Clone + Send + Sync,
+    {
+        TaskEvent {
+            id: self.id.clone(),
+            task,
+            created_at: self.created_at,
+            retry_count: self.retry_count,
+            max_retries: self.max_retries,
+            priority: self.priority,
+        }
+    }
+
     /// Helper method to publish this event using a producer
     pub async fn publish_with_producer(
         &self,
@@ -73,9 +88,9 @@ where
     where
         T: serde::Serialize,
     {
-        let event_json = serde_json::to_string(self)
-            .map_err(|e| anyhow::anyhow!("Failed to serialize event: {}", e))?;
-        producer.publish_event_json(&event_json, destination).await
+        producer
+            .publish(&EncodedMessage::from_event(self)?, destination)
+            .await
     }
 }
 
@@ -158,6 +173,24 @@ mod tests {
         assert_eq!(event.retry_count, 2);
     }
 
+    #[test]
+    fn test_task_event_with_task_keeps_metadata() {
+        let mut event = TaskEvent::with_priority(
+            MockTask {
+                name: "original".to_string(),
+            },
+            TaskPriority::High,
+        );
+        event.increment_retry();
+
+        let converted = event.with_task(event.task.name.len());
+
+        assert_eq!(converted.id, event.id);
+        assert_eq!(converted.task, 8);
+        assert_eq!(converted.retry_count, 1);
+        assert_eq!(converted.priority, TaskPriority::High);
+    }
+
     #[test]
     fn test_task_event_serialization() {
         let task = MockTask {
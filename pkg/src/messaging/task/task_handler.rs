@@ -2,6 +2,44 @@ use async_trait::async_trait;
 
 use super::TaskEvent;
 
+/// Stage a task reached in a worker, reported to [`TaskHandler::on_lifecycle`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskLifecycle {
+    /// Received from the broker, waiting for a free worker slot
+    Enqueued,
+    /// Handed to the handler
+    Started,
+    Completed,
+    /// Failed, and will be republished for another attempt
+    Retrying {
+        error: String,
+    },
+    /// Failed after its last attempt
+    Failed {
+        error: String,
+    },
+}
+
+impl TaskLifecycle {
+    /// Snake case name of the stage, e.g. `retrying`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Enqueued => "enqueued",
+            Self::Started => "started",
+            Self::Completed => "completed",
+            Self::Retrying { .. } => "retrying",
+            Self::Failed { .. } => "failed",
+        }
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Self::Retrying { error } | Self::Failed { error } => Some(error),
+            _ => None,
+        }
+    }
+}
+
 /// Trait for handling task events
 /// T is the application-specific task type
 #[async_trait]
@@ -11,4 +49,20 @@ where
 {
     /// Process a task event
     async fn handle_task(&self, event: &TaskEvent<T>) -> anyhow::Result<()>;
+
+    /// Called by the consumer as `event` moves through the worker, so handlers can
+    /// intercept its lifecycle (e.g. to keep a history). Does nothing by default.
+    async fn on_lifecycle(&self, _event: &TaskEvent<T>, _lifecycle: &TaskLifecycle) {}
+
+    /// Whether `event` has to wait before being handled, e.g. because an admin paused its
+    /// task type. Paused tasks stay queued and are checked again periodically. Never by default.
+    async fn is_paused(&self, _event: &TaskEvent<T>) -> bool {
+        false
+    }
+
+    /// Whether `event` already completed or failed for good, e.g. because it was published
+    /// twice. Such duplicates are dropped instead of handled. Never by default.
+    async fn is_processed(&self, _event: &TaskEvent<T>) -> bool {
+        false
+    }
 }
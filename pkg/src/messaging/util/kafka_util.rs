@@ -2,16 +2,42 @@ use anyhow::Context;
 use rdkafka::{
     admin::{AdminClient, AdminOptions, NewTopic, TopicReplication},
     client::DefaultClientContext,
-    config::ClientConfig,
+    consumer::{BaseConsumer, Consumer},
 };
+use std::time::Duration;
 use tracing::{info, warn};
 
+use crate::messaging::security::BrokerSecurity;
+
+/// Check that a broker of the bootstrap set `brokers` answers within `timeout`.
+/// Kafka clients connect lazily, so this is how an unreachable set is told apart.
+pub async fn check_brokers(
+    brokers: &str,
+    timeout: Duration,
+    security: &BrokerSecurity,
+) -> anyhow::Result<()> {
+    let client: BaseConsumer = security
+        .kafka_client_config(brokers)
+        .create()
+        .context("Failed to create Kafka client")?;
+
+    tokio::task::spawn_blocking(move || client.fetch_metadata(None, timeout))
+        .await
+        .context("Kafka metadata request panicked")?
+        .context("Kafka brokers unreachable")?;
+    Ok(())
+}
+
 /// Ensure Kafka topics exist, create them if they don't
-pub async fn ensure_topics_exist(brokers: &str, topics: &[&str]) -> anyhow::Result<()> {
+pub async fn ensure_topics_exist(
+    brokers: &str,
+    topics: &[&str],
+    security: &BrokerSecurity,
+) -> anyhow::Result<()> {
     info!("Checking if Kafka topics exist: {:?}", topics);
 
-    let admin_client: AdminClient<DefaultClientContext> = ClientConfig::new()
-        .set("bootstrap.servers", brokers)
+    let admin_client: AdminClient<DefaultClientContext> = security
+        .kafka_client_config(brokers)
         .create()
         .context("Failed to create Kafka admin client")?;
 
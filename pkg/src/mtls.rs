@@ -0,0 +1,180 @@
+use std::{io, net::SocketAddr, path::Path, sync::Arc, time::Duration};
+
+use anyhow::Context;
+use axum::{
+    extract::connect_info::Connected,
+    serve::{IncomingStream, Listener},
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    task::JoinSet,
+};
+use tokio_rustls::{
+    TlsAcceptor,
+    rustls::{
+        RootCertStore, ServerConfig,
+        crypto::ring,
+        pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+        server::WebPkiClientVerifier,
+    },
+    server::TlsStream,
+};
+use x509_parser::{extensions::GeneralName, prelude::parse_x509_certificate};
+
+/// How long a client may take to complete the TLS handshake before it is dropped
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// TLS configuration of a server that only accepts clients presenting a certificate signed by
+/// the CA bundle at `client_ca_path`. Every path points to a PEM file.
+pub fn server_config(
+    cert_path: &Path,
+    key_path: &Path,
+    client_ca_path: &Path,
+) -> anyhow::Result<ServerConfig> {
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .with_context(|| format!("Failed to read {}", cert_path.display()))?
+        .collect::<Result<Vec<_>, _>>()?;
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .with_context(|| format!("Failed to read {}", key_path.display()))?;
+
+    let mut roots = RootCertStore::empty();
+    for ca in CertificateDer::pem_file_iter(client_ca_path)
+        .with_context(|| format!("Failed to read {}", client_ca_path.display()))?
+    {
+        roots.add(ca?)?;
+    }
+
+    let provider = Arc::new(ring::default_provider());
+    let verifier =
+        WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider.clone()).build()?;
+    let mut config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(certs, key)?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// Listener serving connections only once their TLS handshake, client certificate included,
+/// succeeded. Handshakes run concurrently so a slow client doesn't hold up the others.
+pub struct MtlsListener {
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    handshakes: JoinSet<(io::Result<TlsStream<TcpStream>>, SocketAddr)>,
+}
+
+impl MtlsListener {
+    pub fn new(listener: TcpListener, config: ServerConfig) -> Self {
+        Self {
+            listener,
+            acceptor: TlsAcceptor::from(Arc::new(config)),
+            handshakes: JoinSet::new(),
+        }
+    }
+}
+
+impl Listener for MtlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        loop {
+            tokio::select! {
+                accepted = self.listener.accept() => match accepted {
+                    Ok((stream, addr)) => {
+                        let acceptor = self.acceptor.clone();
+                        self.handshakes.spawn(async move {
+                            let handshake =
+                                tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream))
+                                    .await
+                                    .unwrap_or_else(|_| {
+                                        Err(io::Error::new(
+                                            io::ErrorKind::TimedOut,
+                                            "TLS handshake timed out",
+                                        ))
+                                    });
+                            (handshake, addr)
+                        });
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to accept mTLS connection: {}", e);
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                    }
+                },
+                Some(joined) = self.handshakes.join_next() => match joined {
+                    Ok((Ok(stream), addr)) => return (stream, addr),
+                    Ok((Err(e), addr)) => {
+                        tracing::debug!("TLS handshake with {} failed: {}", addr, e);
+                    }
+                    Err(e) => tracing::warn!("TLS handshake task failed: {}", e),
+                },
+            }
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        self.listener.local_addr()
+    }
+}
+
+/// Identity of the client certificate a connection was opened with, available to handlers
+/// through `ConnectInfo<ClientIdentity>`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientIdentity {
+    /// DNS names, URIs (e.g. SPIFFE IDs) and e-mail addresses of the subject alternative names
+    pub sans: Vec<String>,
+}
+
+impl ClientIdentity {
+    /// Identity of the leaf certificate of a DER-encoded chain
+    pub fn from_certificates(certificates: &[CertificateDer<'_>]) -> Self {
+        let sans = certificates
+            .first()
+            .and_then(|leaf| parse_x509_certificate(leaf.as_ref()).ok())
+            .and_then(|(_, certificate)| {
+                let names = certificate.subject_alternative_name().ok().flatten()?;
+                Some(
+                    names
+                        .value
+                        .general_names
+                        .iter()
+                        .filter_map(|name| match name {
+                            GeneralName::DNSName(name)
+                            | GeneralName::URI(name)
+                            | GeneralName::RFC822Name(name) => Some(name.to_string()),
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            })
+            .unwrap_or_default();
+        Self { sans }
+    }
+}
+
+impl Connected<IncomingStream<'_, MtlsListener>> for ClientIdentity {
+    fn connect_info(stream: IncomingStream<'_, MtlsListener>) -> Self {
+        let (_, connection) = stream.io().get_ref();
+        connection
+            .peer_certificates()
+            .map(Self::from_certificates)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClientIdentity;
+
+    #[test]
+    fn identity_of_an_unparsable_chain_has_no_sans() {
+        assert_eq!(
+            ClientIdentity::from_certificates(&[]),
+            ClientIdentity::default()
+        );
+        assert_eq!(
+            ClientIdentity::from_certificates(&[b"not a certificate".to_vec().into()]),
+            ClientIdentity::default()
+        );
+    }
+}
@@ -0,0 +1,374 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{circuit_breaker, http_client::HttpClient};
+
+const GOOGLE_AUTHORIZE_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const GOOGLE_USERINFO_URL: &str = "https://openidconnect.googleapis.com/v1/userinfo";
+const GITHUB_AUTHORIZE_URL: &str = "https://github.com/login/oauth/authorize";
+const GITHUB_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Names of the circuit breakers guarding calls to each identity provider
+pub const GOOGLE_OAUTH_BREAKER: &str = "google-oauth";
+pub const GITHUB_OAUTH_BREAKER: &str = "github-oauth";
+
+/// Who signed in with an identity provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthIdentity {
+    /// Stable id of the account at the provider, which unlike the email never changes
+    pub subject: String,
+    pub email: Option<String>,
+    /// Whether the provider confirmed the user owns `email`
+    pub email_verified: bool,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+}
+
+/// Identity provider signing users in with the OAuth2 authorization-code flow
+#[async_trait]
+pub trait OAuthProvider: Send + Sync {
+    /// Page the user is sent to to sign in, coming back to `redirect_uri` with a code and `state`
+    fn authorization_url(&self, redirect_uri: &str, state: &str) -> String;
+
+    /// Exchange the `code` the provider redirected back with for the identity it vouches for
+    async fn fetch_identity(&self, code: &str, redirect_uri: &str)
+    -> anyhow::Result<OAuthIdentity>;
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+/// Send `request` through the circuit breaker named `breaker`, failing on unsuccessful statuses
+async fn send(
+    client: &HttpClient,
+    breaker: &str,
+    request: reqwest::RequestBuilder,
+) -> anyhow::Result<reqwest::Response> {
+    let breaker = circuit_breaker::breaker(breaker);
+    breaker.try_acquire()?;
+    let response = client.send(request).await;
+    // Codes refused as invalid or expired don't count against the provider
+    breaker.record(
+        response
+            .as_ref()
+            .is_ok_and(|response| !response.status().is_server_error()),
+    );
+    let response =
+        response.map_err(|e| anyhow::anyhow!("Failed to reach {}: {}", breaker.name(), e))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    Err(anyhow::anyhow!(
+        "{} answered {}: {}",
+        breaker.name(),
+        status,
+        response.text().await.unwrap_or_default()
+    ))
+}
+
+fn authorization_url(base: &str, params: &[(&str, &str)]) -> String {
+    let query = serde_urlencoded::to_string(params).unwrap_or_default();
+    format!("{}?{}", base, query)
+}
+
+/// Google sign-in through OpenID Connect
+#[derive(Debug, Clone)]
+pub struct GoogleOAuthClient {
+    client: HttpClient,
+    client_id: String,
+    client_secret: String,
+    authorize_url: String,
+    token_url: String,
+    userinfo_url: String,
+}
+
+impl GoogleOAuthClient {
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            client: HttpClient::default(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            authorize_url: GOOGLE_AUTHORIZE_URL.to_string(),
+            token_url: GOOGLE_TOKEN_URL.to_string(),
+            userinfo_url: GOOGLE_USERINFO_URL.to_string(),
+        }
+    }
+
+    /// Exchange codes and fetch profiles at `url` instead of Google (e.g. a local fake in tests)
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        let url = url.into();
+        self.token_url = format!("{}/token", url);
+        self.userinfo_url = format!("{}/userinfo", url);
+        self
+    }
+
+    /// Send through `client` instead of the process-wide default one
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl OAuthProvider for GoogleOAuthClient {
+    fn authorization_url(&self, redirect_uri: &str, state: &str) -> String {
+        authorization_url(
+            &self.authorize_url,
+            &[
+                ("client_id", self.client_id.as_str()),
+                ("redirect_uri", redirect_uri),
+                ("response_type", "code"),
+                ("scope", "openid email profile"),
+                ("state", state),
+            ],
+        )
+    }
+
+    async fn fetch_identity(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+    ) -> anyhow::Result<OAuthIdentity> {
+        let token: TokenResponse = send(
+            &self.client,
+            GOOGLE_OAUTH_BREAKER,
+            self.client
+                .post(&self.token_url)
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(serde_urlencoded::to_string([
+                    ("code", code),
+                    ("client_id", &self.client_id),
+                    ("client_secret", &self.client_secret),
+                    ("redirect_uri", redirect_uri),
+                    ("grant_type", "authorization_code"),
+                ])?),
+        )
+        .await?
+        .json()
+        .await?;
+
+        let profile: Value = send(
+            &self.client,
+            GOOGLE_OAUTH_BREAKER,
+            self.client
+                .get(&self.userinfo_url)
+                .bearer_auth(&token.access_token),
+        )
+        .await?
+        .json()
+        .await?;
+
+        let subject = profile["sub"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Google profile has no subject"))?;
+        Ok(OAuthIdentity {
+            subject: subject.to_string(),
+            email: profile["email"].as_str().map(str::to_string),
+            email_verified: profile["email_verified"].as_bool().unwrap_or(false),
+            first_name: profile["given_name"].as_str().map(str::to_string),
+            last_name: profile["family_name"].as_str().map(str::to_string),
+        })
+    }
+}
+
+/// GitHub sign-in through a GitHub OAuth app
+#[derive(Debug, Clone)]
+pub struct GitHubOAuthClient {
+    client: HttpClient,
+    client_id: String,
+    client_secret: String,
+    authorize_url: String,
+    token_url: String,
+    api_url: String,
+}
+
+impl GitHubOAuthClient {
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            client: HttpClient::default(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            authorize_url: GITHUB_AUTHORIZE_URL.to_string(),
+            token_url: GITHUB_TOKEN_URL.to_string(),
+            api_url: GITHUB_API_URL.to_string(),
+        }
+    }
+
+    /// Exchange codes and call the API at `url` instead of GitHub (e.g. a local fake in tests)
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        let url = url.into();
+        self.token_url = format!("{}/access_token", url);
+        self.api_url = url;
+        self
+    }
+
+    /// Send through `client` instead of the process-wide default one
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl OAuthProvider for GitHubOAuthClient {
+    fn authorization_url(&self, redirect_uri: &str, state: &str) -> String {
+        authorization_url(
+            &self.authorize_url,
+            &[
+                ("client_id", self.client_id.as_str()),
+                ("redirect_uri", redirect_uri),
+                ("scope", "read:user user:email"),
+                ("state", state),
+            ],
+        )
+    }
+
+    async fn fetch_identity(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+    ) -> anyhow::Result<OAuthIdentity> {
+        let token: TokenResponse = send(
+            &self.client,
+            GITHUB_OAUTH_BREAKER,
+            self.client
+                .post(&self.token_url)
+                .header("accept", "application/json")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(serde_urlencoded::to_string([
+                    ("code", code),
+                    ("client_id", &self.client_id),
+                    ("client_secret", &self.client_secret),
+                    ("redirect_uri", redirect_uri),
+                ])?),
+        )
+        .await?
+        .json()
+        .await?;
+
+        let profile: Value = send(
+            &self.client,
+            GITHUB_OAUTH_BREAKER,
+            self.client
+                .get(format!("{}/user", self.api_url))
+                .header("accept", "application/vnd.github+json")
+                .bearer_auth(&token.access_token),
+        )
+        .await?
+        .json()
+        .await?;
+        // The profile only shows a public email, so the verified primary one is asked for
+        let emails: Vec<Value> = send(
+            &self.client,
+            GITHUB_OAUTH_BREAKER,
+            self.client
+                .get(format!("{}/user/emails", self.api_url))
+                .header("accept", "application/vnd.github+json")
+                .bearer_auth(&token.access_token),
+        )
+        .await?
+        .json()
+        .await?;
+        let primary = emails
+            .iter()
+            .find(|email| email["primary"].as_bool().unwrap_or(false));
+
+        let subject = profile["id"]
+            .as_i64()
+            .ok_or_else(|| anyhow::anyhow!("GitHub profile has no id"))?;
+        // GitHub has a single display name, split at its first space
+        let (first_name, last_name) = match profile["name"].as_str().map(str::trim) {
+            Some(name) if !name.is_empty() => match name.split_once(' ') {
+                Some((first, last)) => (Some(first.to_string()), Some(last.trim().to_string())),
+                None => (Some(name.to_string()), None),
+            },
+            _ => (None, None),
+        };
+        Ok(OAuthIdentity {
+            subject: subject.to_string(),
+            email: primary
+                .and_then(|email| email["email"].as_str())
+                .map(str::to_string),
+            email_verified: primary
+                .and_then(|email| email["verified"].as_bool())
+                .unwrap_or(false),
+            first_name,
+            last_name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Json, Router,
+        routing::{get, post},
+    };
+    use serde_json::json;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    async fn spawn_fake_github() -> String {
+        let app = Router::new()
+            .route(
+                "/access_token",
+                post(|| async { Json(json!({ "access_token": "gho_token" })) }),
+            )
+            .route(
+                "/user",
+                get(|| async { Json(json!({ "id": 42, "name": "Ada King Lovelace" })) }),
+            )
+            .route(
+                "/user/emails",
+                get(|| async {
+                    Json(json!([
+                        { "email": "old@example.com", "primary": false, "verified": true },
+                        { "email": "ada@example.com", "primary": true, "verified": true },
+                    ]))
+                }),
+            );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[test]
+    fn authorization_url_carries_client_redirect_and_state() {
+        let client = GoogleOAuthClient::new("client-id", "secret");
+
+        let url = client.authorization_url("http://localhost:8000/callback/", "s1");
+
+        assert!(url.starts_with(GOOGLE_AUTHORIZE_URL));
+        assert!(url.contains("client_id=client-id"));
+        assert!(url.contains("redirect_uri=http%3A%2F%2Flocalhost%3A8000%2Fcallback%2F"));
+        assert!(url.contains("state=s1"));
+        assert!(!url.contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn github_identity_uses_verified_primary_email() {
+        let url = spawn_fake_github().await;
+        let client = GitHubOAuthClient::new("client-id", "secret").with_url(url);
+
+        let identity = client
+            .fetch_identity("code", "http://localhost/callback/")
+            .await
+            .unwrap();
+
+        assert_eq!(identity.subject, "42");
+        assert_eq!(identity.email.as_deref(), Some("ada@example.com"));
+        assert!(identity.email_verified);
+        assert_eq!(identity.first_name.as_deref(), Some("Ada"));
+        assert_eq!(identity.last_name.as_deref(), Some("King Lovelace"));
+    }
+}
@@ -1,20 +1,263 @@
 use argon2::password_hash::{SaltString, rand_core::OsRng};
-use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::{
+    Algorithm, Argon2, Params, PasswordHash, PasswordHasher as _, PasswordVerifier, Version,
+};
+use serde::Deserialize;
+use tokio::task::spawn_blocking;
 
-/// Configuration for password hashing
-#[derive(Debug, Clone)]
+/// Block size and parallelism of scrypt hashes, which only vary their cost through `log_n`
+const SCRYPT_BLOCK_SIZE: u32 = 8;
+const SCRYPT_PARALLELISM: u32 = 1;
+
+/// Algorithm of a password hash. Every hash carries the tag of its scheme, so hashes of
+/// all schemes verify side by side whatever the configured one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordScheme {
+    /// PHC string tagged `$argon2id$`. `$argon2i$` and `$argon2d$` hashes verify as well.
+    #[default]
+    Argon2id,
+    /// Modular crypt string tagged `$2a$`, `$2b$` or `$2y$`, as stored by most other stacks.
+    /// Only the first 72 bytes of a password count.
+    Bcrypt,
+    /// PHC string tagged `$scrypt$`
+    Scrypt,
+}
+
+impl PasswordScheme {
+    /// Scheme of `hash`, read from its tag
+    pub fn of_hash(hash: &str) -> Option<Self> {
+        let tag = hash.strip_prefix('$')?.split('$').next()?;
+        match tag {
+            "argon2id" | "argon2i" | "argon2d" => Some(Self::Argon2id),
+            "2a" | "2b" | "2y" => Some(Self::Bcrypt),
+            "scrypt" => Some(Self::Scrypt),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Argon2id => "argon2id",
+            Self::Bcrypt => "bcrypt",
+            Self::Scrypt => "scrypt",
+        }
+    }
+}
+
+impl std::str::FromStr for PasswordScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "argon2id" => Ok(Self::Argon2id),
+            "bcrypt" => Ok(Self::Bcrypt),
+            "scrypt" => Ok(Self::Scrypt),
+            _ => Err(anyhow::anyhow!(
+                "Unknown password hashing scheme: {}",
+                value
+            )),
+        }
+    }
+}
+
+/// Scheme and parameters of new password hashes
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct PasswordConfig {
+    pub scheme: PasswordScheme,
+    /// Argon2 memory in KiB
     pub memory_cost: u32,
+    /// Argon2 number of iterations
     pub time_cost: u32,
+    /// Argon2 degree of parallelism (lanes)
     pub parallelism: u32,
+    /// bcrypt cost, the base 2 logarithm of its rounds
+    pub bcrypt_cost: u32,
+    /// scrypt cost, the base 2 logarithm of its `N`
+    pub scrypt_log_n: u8,
 }
 
 impl Default for PasswordConfig {
     fn default() -> Self {
         Self {
+            scheme: PasswordScheme::default(),
             memory_cost: 4096,
             time_cost: 3,
             parallelism: 1,
+            bcrypt_cost: 12,
+            scrypt_log_n: 15,
+        }
+    }
+}
+
+impl PasswordConfig {
+    /// Argon2 parameters, failing when they are out of the supported range
+    pub fn params(&self) -> anyhow::Result<Params> {
+        Params::new(self.memory_cost, self.time_cost, self.parallelism, None)
+            .map_err(|e| anyhow::anyhow!("Failed to create Argon2 params: {}", e))
+    }
+
+    /// scrypt parameters, failing when they are out of the supported range
+    pub fn scrypt_params(&self) -> anyhow::Result<scrypt::Params> {
+        scrypt::Params::new(
+            self.scrypt_log_n,
+            SCRYPT_BLOCK_SIZE,
+            SCRYPT_PARALLELISM,
+            scrypt::Params::RECOMMENDED_LEN,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to create scrypt params: {}", e))
+    }
+
+    /// Check the parameters of the configured scheme
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.hasher().map(|_| ())
+    }
+
+    /// Hasher of the configured scheme, failing when its parameters are invalid
+    pub fn hasher(&self) -> anyhow::Result<Box<dyn PasswordHasher>> {
+        Ok(match self.scheme {
+            PasswordScheme::Argon2id => Box::new(Argon2idHasher {
+                params: self.params()?,
+            }),
+            PasswordScheme::Bcrypt => {
+                if !(4..=31).contains(&self.bcrypt_cost) {
+                    return Err(anyhow::anyhow!("bcrypt cost must be between 4 and 31"));
+                }
+                Box::new(BcryptHasher {
+                    cost: self.bcrypt_cost,
+                })
+            }
+            PasswordScheme::Scrypt => Box::new(ScryptHasher {
+                params: self.scrypt_params()?,
+            }),
+        })
+    }
+}
+
+/// A password hashing algorithm with the parameters of new hashes. Calls block for as long
+/// as the algorithm is tuned to take, so async code goes through [`hash_password_with_config`]
+/// and [`verify_password`].
+pub trait PasswordHasher: Send + Sync {
+    fn scheme(&self) -> PasswordScheme;
+
+    /// Hash `password` with a fresh salt, tagging the hash with the scheme
+    fn hash(&self, password: &str) -> anyhow::Result<String>;
+
+    /// Check `password` against a `hash` of this scheme, using the parameters stored in it
+    fn verify(&self, password: &str, hash: &str) -> anyhow::Result<()>;
+
+    /// Whether a `hash` of this scheme was made with other parameters than this hasher's
+    fn is_outdated(&self, hash: &str) -> bool;
+}
+
+pub struct Argon2idHasher {
+    params: Params,
+}
+
+impl PasswordHasher for Argon2idHasher {
+    fn scheme(&self) -> PasswordScheme {
+        PasswordScheme::Argon2id
+    }
+
+    fn hash(&self, password: &str) -> anyhow::Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> anyhow::Result<()> {
+        let parsed_hash = PasswordHash::new(hash)
+            .map_err(|e| anyhow::anyhow!("Failed to parse password hash: {}", e))?;
+        Argon2::default()
+            .verify_password(password.as_bytes(), &parsed_hash)
+            .map_err(|e| anyhow::anyhow!("Password verification failed: {}", e))
+    }
+
+    fn is_outdated(&self, hash: &str) -> bool {
+        let Ok(parsed_hash) = PasswordHash::new(hash) else {
+            return true;
+        };
+        if parsed_hash.algorithm != Algorithm::Argon2id.ident() {
+            return true;
+        }
+
+        match Params::try_from(&parsed_hash) {
+            Ok(params) => {
+                params.m_cost() != self.params.m_cost()
+                    || params.t_cost() != self.params.t_cost()
+                    || params.p_cost() != self.params.p_cost()
+            }
+            Err(_) => true,
+        }
+    }
+}
+
+pub struct BcryptHasher {
+    cost: u32,
+}
+
+impl PasswordHasher for BcryptHasher {
+    fn scheme(&self) -> PasswordScheme {
+        PasswordScheme::Bcrypt
+    }
+
+    fn hash(&self, password: &str) -> anyhow::Result<String> {
+        bcrypt::hash(password, self.cost)
+            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> anyhow::Result<()> {
+        match bcrypt::verify(password, hash) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(anyhow::anyhow!("Password verification failed")),
+            Err(e) => Err(anyhow::anyhow!("Failed to parse password hash: {}", e)),
+        }
+    }
+
+    fn is_outdated(&self, hash: &str) -> bool {
+        // The cost is the number between the second and third `$`
+        hash.split('$').nth(2).and_then(|cost| cost.parse().ok()) != Some(self.cost)
+    }
+}
+
+pub struct ScryptHasher {
+    params: scrypt::Params,
+}
+
+impl PasswordHasher for ScryptHasher {
+    fn scheme(&self) -> PasswordScheme {
+        PasswordScheme::Scrypt
+    }
+
+    fn hash(&self, password: &str) -> anyhow::Result<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        scrypt::Scrypt
+            .hash_password_customized(password.as_bytes(), None, None, self.params, &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))
+    }
+
+    fn verify(&self, password: &str, hash: &str) -> anyhow::Result<()> {
+        let parsed_hash = PasswordHash::new(hash)
+            .map_err(|e| anyhow::anyhow!("Failed to parse password hash: {}", e))?;
+        scrypt::Scrypt
+            .verify_password(password.as_bytes(), &parsed_hash)
+            .map_err(|e| anyhow::anyhow!("Password verification failed: {}", e))
+    }
+
+    fn is_outdated(&self, hash: &str) -> bool {
+        let Ok(parsed_hash) = PasswordHash::new(hash) else {
+            return true;
+        };
+        match scrypt::Params::try_from(&parsed_hash) {
+            Ok(params) => {
+                params.log_n() != self.params.log_n()
+                    || params.r() != self.params.r()
+                    || params.p() != self.params.p()
+            }
+            Err(_) => true,
         }
     }
 }
@@ -24,47 +267,48 @@ pub async fn hash_password(password: &str) -> anyhow::Result<String> {
     hash_password_with_config(password, &PasswordConfig::default()).await
 }
 
-/// Hash a password with custom configuration
+/// Hash a password with the scheme and parameters of `config`.
+/// Runs on the blocking pool since hashing is deliberately slow and would stall the executor.
 pub async fn hash_password_with_config(
     password: &str,
     config: &PasswordConfig,
 ) -> anyhow::Result<String> {
-    let password_bytes = password.as_bytes();
-    let salt = SaltString::generate(&mut OsRng);
-
-    let argon2 = Argon2::new(
-        argon2::Algorithm::Argon2id,
-        argon2::Version::V0x13,
-        argon2::Params::new(
-            config.memory_cost,
-            config.time_cost,
-            config.parallelism,
-            None,
-        )
-        .map_err(|e| anyhow::anyhow!("Failed to create Argon2 params: {}", e))?,
-    );
+    let password = password.to_string();
+    let hasher = config.hasher()?;
 
-    let password_hash = argon2
-        .hash_password(password_bytes, &salt)
-        .map_err(|e| anyhow::anyhow!("Failed to hash password: {}", e))?
-        .to_string();
-
-    Ok(password_hash)
+    spawn_blocking(move || hasher.hash(&password))
+        .await
+        .map_err(|e| anyhow::anyhow!("Password hashing task failed: {}", e))?
 }
 
-/// Verify a password against a hash
+/// Verify a password against a hash of any scheme, using the parameters stored in the hash.
+/// Runs on the blocking pool, like hashing.
 pub async fn verify_password(password: &str, hash: &str) -> anyhow::Result<()> {
-    let password_bytes = password.as_bytes();
-    let parsed_hash = PasswordHash::new(hash)
-        .map_err(|e| anyhow::anyhow!("Failed to parse password hash: {}", e))?;
-
-    let argon2 = Argon2::default();
+    let password = password.to_string();
+    let hash = hash.to_string();
+    let scheme = PasswordScheme::of_hash(&hash)
+        .ok_or_else(|| anyhow::anyhow!("Unknown password hash scheme"))?;
+    // Verification reads its parameters from the hash, so the defaults of the scheme do
+    let hasher = PasswordConfig {
+        scheme,
+        ..Default::default()
+    }
+    .hasher()?;
 
-    argon2
-        .verify_password(password_bytes, &parsed_hash)
-        .map_err(|e| anyhow::anyhow!("Password verification failed: {}", e))?;
+    spawn_blocking(move || hasher.verify(&password, &hash))
+        .await
+        .map_err(|e| anyhow::anyhow!("Password verification task failed: {}", e))?
+}
 
-    Ok(())
+/// Whether `hash` was made with another scheme or other parameters than `config`,
+/// so it should be replaced the next time the plain password is known
+pub fn needs_rehash(hash: &str, config: &PasswordConfig) -> bool {
+    if PasswordScheme::of_hash(hash) != Some(config.scheme) {
+        return true;
+    }
+    config
+        .hasher()
+        .map_or(true, |hasher| hasher.is_outdated(hash))
 }
 
 /// Hash a plain string password
@@ -112,13 +356,207 @@ pub fn validate_password_strength(password: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Passwords too common to resist a guessing attack, compared case-insensitively
+const COMMON_PASSWORDS: &[&str] = &[
+    "000000",
+    "111111",
+    "112233",
+    "121212",
+    "123123",
+    "123321",
+    "1234",
+    "12345",
+    "123456",
+    "1234567",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "123qwe",
+    "1q2w3e",
+    "1q2w3e4r",
+    "1q2w3e4r5t",
+    "1qaz2wsx",
+    "654321",
+    "666666",
+    "696969",
+    "7777777",
+    "888888",
+    "987654321",
+    "aa123456",
+    "abc123",
+    "abcd1234",
+    "access",
+    "admin",
+    "admin123",
+    "andrew",
+    "asdf1234",
+    "asdfgh",
+    "asdfghjkl",
+    "ashley",
+    "azerty",
+    "bailey",
+    "baseball",
+    "batman",
+    "charlie",
+    "changeme",
+    "chocolate",
+    "dragon",
+    "football",
+    "freedom",
+    "hello",
+    "hello123",
+    "hottie",
+    "iloveyou",
+    "jennifer",
+    "jordan",
+    "letmein",
+    "login",
+    "lovely",
+    "master",
+    "michael",
+    "monkey",
+    "mustang",
+    "ninja",
+    "passw0rd",
+    "password",
+    "password1",
+    "password12",
+    "password123",
+    "password1234",
+    "p@ssw0rd",
+    "p@ssword",
+    "princess",
+    "qazwsx",
+    "qwe123",
+    "qwerty",
+    "qwerty123",
+    "qwertyuiop",
+    "secret",
+    "shadow",
+    "sunshine",
+    "superman",
+    "trustno1",
+    "welcome",
+    "welcome1",
+    "welcome123",
+    "whatever",
+    "zaq12wsx",
+];
+
+/// A rule of [`PasswordPolicy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordRule {
+    MinLength,
+    Uppercase,
+    Lowercase,
+    Digit,
+    Special,
+    /// Not one of the most common passwords
+    Common,
+    /// Not containing the email address of the account, nor its local part
+    Email,
+}
+
+impl PasswordRule {
+    /// Stable name clients can branch on
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MinLength => "min_length",
+            Self::Uppercase => "uppercase",
+            Self::Lowercase => "lowercase",
+            Self::Digit => "digit",
+            Self::Special => "special",
+            Self::Common => "common",
+            Self::Email => "email",
+        }
+    }
+}
+
+/// Strength rules new passwords must follow
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PasswordPolicy {
+    /// Minimum number of characters
+    pub min_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    /// Require a character that is neither a letter nor a digit
+    pub require_special: bool,
+    /// Reject the most common passwords
+    pub deny_common: bool,
+    /// Reject passwords containing the account's email address
+    pub deny_email: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            require_uppercase: false,
+            require_lowercase: false,
+            require_digit: false,
+            require_special: false,
+            deny_common: true,
+            deny_email: true,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Every rule `password` breaks, in declaration order. `email` is the address of the
+    /// account the password is for.
+    pub fn check(&self, password: &str, email: Option<&str>) -> Vec<PasswordRule> {
+        let lowercase = password.to_lowercase();
+        let contains_email = email.is_some_and(|email| {
+            let email = email.to_lowercase();
+            let local_part = email.split('@').next().unwrap_or_default();
+            lowercase.contains(&email) || (local_part.len() >= 3 && lowercase.contains(local_part))
+        });
+
+        [
+            (
+                PasswordRule::MinLength,
+                password.chars().count() < self.min_length,
+            ),
+            (
+                PasswordRule::Uppercase,
+                self.require_uppercase && !password.chars().any(char::is_uppercase),
+            ),
+            (
+                PasswordRule::Lowercase,
+                self.require_lowercase && !password.chars().any(char::is_lowercase),
+            ),
+            (
+                PasswordRule::Digit,
+                self.require_digit && !password.chars().any(char::is_numeric),
+            ),
+            (
+                PasswordRule::Special,
+                self.require_special && password.chars().all(char::is_alphanumeric),
+            ),
+            (
+                PasswordRule::Common,
+                self.deny_common && COMMON_PASSWORDS.contains(&lowercase.as_str()),
+            ),
+            (PasswordRule::Email, self.deny_email && contains_email),
+        ]
+        .into_iter()
+        .filter_map(|(rule, broken)| broken.then_some(rule))
+        .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        PasswordConfig, generate_salt, hash_password_string, hash_password_with_config,
-        validate_password_strength, verify_password,
+        PasswordConfig, PasswordPolicy, PasswordRule, PasswordScheme, generate_salt,
+        hash_password_string, hash_password_with_config, needs_rehash, validate_password_strength,
+        verify_password,
     };
 
+    /// bcrypt hash of `U*U` from the crypt_blowfish test vectors
+    const BCRYPT_HASH: &str = "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW";
+
     #[tokio::test]
     async fn hashes_and_verifies_password() {
         let hash = hash_password_string("StrongP@ss123").await.unwrap();
@@ -133,6 +571,7 @@ mod tests {
             memory_cost: 2048,
             time_cost: 2,
             parallelism: 1,
+            ..Default::default()
         };
 
         let hash = hash_password_with_config("StrongP@ss123", &config)
@@ -141,6 +580,175 @@ mod tests {
         assert!(verify_password("StrongP@ss123", &hash).await.is_ok());
     }
 
+    #[tokio::test]
+    async fn verifies_bcrypt_hashes_from_other_systems() {
+        assert!(verify_password("U*U", BCRYPT_HASH).await.is_ok());
+        assert!(verify_password("U*V", BCRYPT_HASH).await.is_err());
+
+        let config = PasswordConfig::default();
+        assert!(needs_rehash(BCRYPT_HASH, &config));
+        assert!(!needs_rehash(
+            BCRYPT_HASH,
+            &PasswordConfig {
+                scheme: PasswordScheme::Bcrypt,
+                bcrypt_cost: 5,
+                ..config
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn hashes_with_configured_scheme() {
+        for scheme in [PasswordScheme::Bcrypt, PasswordScheme::Scrypt] {
+            let config = PasswordConfig {
+                scheme,
+                bcrypt_cost: 4,
+                scrypt_log_n: 10,
+                ..Default::default()
+            };
+
+            let hash = hash_password_with_config("StrongP@ss123", &config)
+                .await
+                .unwrap();
+            assert_eq!(config.hasher().unwrap().scheme(), scheme);
+            assert_eq!(PasswordScheme::of_hash(&hash), Some(scheme));
+            assert!(verify_password("StrongP@ss123", &hash).await.is_ok());
+            assert!(verify_password("wrong", &hash).await.is_err());
+            assert!(!needs_rehash(&hash, &config));
+            assert!(needs_rehash(&hash, &PasswordConfig::default()));
+        }
+    }
+
+    #[tokio::test]
+    async fn detects_outdated_parameters() {
+        let config = PasswordConfig::default();
+        let hash = hash_password_with_config("StrongP@ss123", &config)
+            .await
+            .unwrap();
+
+        assert!(!needs_rehash(&hash, &config));
+        assert!(needs_rehash(
+            &hash,
+            &PasswordConfig {
+                time_cost: 4,
+                ..config.clone()
+            }
+        ));
+        assert!(needs_rehash("not-a-hash", &config));
+    }
+
+    #[test]
+    fn bcrypt_hash_is_outdated_when_cost_changes() {
+        let hasher = |bcrypt_cost| {
+            PasswordConfig {
+                scheme: PasswordScheme::Bcrypt,
+                bcrypt_cost,
+                ..Default::default()
+            }
+            .hasher()
+            .unwrap()
+        };
+
+        assert!(!hasher(5).is_outdated(BCRYPT_HASH));
+        assert!(hasher(4).is_outdated(BCRYPT_HASH));
+        assert!(hasher(6).is_outdated(BCRYPT_HASH));
+    }
+
+    #[test]
+    fn scrypt_hash_is_outdated_when_log_n_changes() {
+        let hasher = |scrypt_log_n| {
+            PasswordConfig {
+                scheme: PasswordScheme::Scrypt,
+                scrypt_log_n,
+                ..Default::default()
+            }
+            .hasher()
+            .unwrap()
+        };
+        let hash = hasher(10).hash("StrongP@ss123").unwrap();
+
+        assert!(!hasher(10).is_outdated(&hash));
+        assert!(hasher(9).is_outdated(&hash));
+        assert!(hasher(11).is_outdated(&hash));
+    }
+
+    #[test]
+    fn argon2_hash_is_outdated_when_parameters_change() {
+        let config = PasswordConfig {
+            memory_cost: 1024,
+            time_cost: 1,
+            parallelism: 1,
+            ..Default::default()
+        };
+        let hash = config.hasher().unwrap().hash("StrongP@ss123").unwrap();
+
+        assert!(!config.hasher().unwrap().is_outdated(&hash));
+        for changed in [
+            PasswordConfig {
+                memory_cost: 2048,
+                ..config.clone()
+            },
+            PasswordConfig {
+                time_cost: 2,
+                ..config.clone()
+            },
+            PasswordConfig {
+                parallelism: 2,
+                ..config.clone()
+            },
+        ] {
+            assert!(changed.hasher().unwrap().is_outdated(&hash));
+        }
+    }
+
+    #[test]
+    fn hash_of_another_scheme_is_outdated() {
+        let configs = [
+            PasswordScheme::Argon2id,
+            PasswordScheme::Bcrypt,
+            PasswordScheme::Scrypt,
+        ]
+        .map(|scheme| PasswordConfig {
+            scheme,
+            memory_cost: 1024,
+            time_cost: 1,
+            bcrypt_cost: 4,
+            scrypt_log_n: 10,
+            ..Default::default()
+        });
+        let hashes = configs
+            .each_ref()
+            .map(|config| config.hasher().unwrap().hash("StrongP@ss123").unwrap());
+
+        for config in &configs {
+            let hasher = config.hasher().unwrap();
+            for hash in &hashes {
+                let other_scheme = PasswordScheme::of_hash(hash) != Some(config.scheme);
+                assert_eq!(hasher.is_outdated(hash), other_scheme, "{hash}");
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let config = PasswordConfig {
+            memory_cost: 1,
+            ..Default::default()
+        };
+
+        assert!(config.params().is_err());
+        assert!(config.validate().is_err());
+        assert!(
+            PasswordConfig {
+                scheme: PasswordScheme::Bcrypt,
+                bcrypt_cost: 40,
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
     #[test]
     fn generates_unique_salts() {
         let salt1 = generate_salt();
@@ -158,4 +766,33 @@ mod tests {
         assert!(validate_password_strength("lowercase123!").is_err());
         assert!(validate_password_strength("UPPERCASE123!").is_err());
     }
+
+    #[test]
+    fn checks_password_policy() {
+        let policy = PasswordPolicy::default();
+        assert!(
+            policy
+                .check("correct horse battery", Some("neo@example.com"))
+                .is_empty()
+        );
+        assert_eq!(policy.check("Password1", None), [PasswordRule::Common]);
+        assert_eq!(
+            policy.check("trinity-42", Some("Trinity@example.com")),
+            [PasswordRule::Email]
+        );
+        assert_eq!(policy.check("abc", None), [PasswordRule::MinLength]);
+
+        let strict = PasswordPolicy {
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_special: true,
+            ..policy
+        };
+        assert_eq!(
+            strict.check("lowercase only", None),
+            [PasswordRule::Uppercase, PasswordRule::Digit,]
+        );
+        assert!(strict.check("Str0ng!pass", None).is_empty());
+    }
 }
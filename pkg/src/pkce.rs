@@ -0,0 +1,43 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use sha2::{Digest, Sha256};
+
+/// The `S256` challenge of a PKCE code verifier (RFC 7636): its base64url SHA-256 digest
+pub fn s256_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// Whether `verifier` is a valid code verifier whose `S256` challenge is `challenge`
+pub fn verify_s256(verifier: &str, challenge: &str) -> bool {
+    is_valid_verifier(verifier) && s256_challenge(verifier) == challenge
+}
+
+/// Code verifiers are 43 to 128 characters of `[A-Za-z0-9-._~]`
+fn is_valid_verifier(verifier: &str) -> bool {
+    (43..=128).contains(&verifier.len())
+        && verifier
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"-._~".contains(&byte))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{s256_challenge, verify_s256};
+
+    // Example of RFC 7636, appendix B
+    const VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    const CHALLENGE: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+    #[test]
+    fn computes_s256_challenge() {
+        assert_eq!(s256_challenge(VERIFIER), CHALLENGE);
+    }
+
+    #[test]
+    fn verifies_only_matching_well_formed_verifiers() {
+        assert!(verify_s256(VERIFIER, CHALLENGE));
+        assert!(!verify_s256(&VERIFIER.replace('d', "e"), CHALLENGE));
+        assert!(!verify_s256("short", &s256_challenge("short")));
+        let invalid = format!("{}!", "a".repeat(43));
+        assert!(!verify_s256(&invalid, &s256_challenge(&invalid)));
+    }
+}
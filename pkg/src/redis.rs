@@ -0,0 +1,546 @@
+use anyhow::{Context, Result};
+use redis::{
+    Client, Cmd, Pipeline, RedisError, RedisFuture, Value,
+    aio::{ConnectionLike, ConnectionManager, ConnectionManagerConfig, PubSub},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, LazyLock, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{
+    sync::Mutex,
+    time::{sleep, timeout},
+};
+
+use crate::{
+    messaging::{
+        failover::{BrokerEndpoints, FailoverPolicy},
+        security::BrokerSecurity,
+    },
+    url::mask_url,
+};
+
+/// Scheme of endpoints that name a master to look up on Redis Sentinels:
+/// `redis+sentinel://[user:password@]sentinel-a:26379,sentinel-b:26379/master-name[/db]`
+const SENTINEL_SCHEME: &str = "redis+sentinel://";
+
+/// Managers opened through [`RedisConnectionManager::shared`], one per URL list
+static SHARED: LazyLock<Mutex<HashMap<String, RedisConnectionManager>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Timeouts, reconnect policy and TLS of a [`RedisConnectionManager`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedisPoolConfig {
+    pub connection_timeout: Duration,
+    pub response_timeout: Duration,
+    /// Attempts after the first failed one when (re)connecting
+    pub max_retries: usize,
+    /// Delay before the first retry, doubled on each retry up to `max_retry_delay`
+    pub retry_delay: Duration,
+    pub max_retry_delay: Duration,
+    /// Applied to each endpoint, or to the master sentinels name
+    pub security: BrokerSecurity,
+}
+
+impl Default for RedisPoolConfig {
+    fn default() -> Self {
+        Self {
+            connection_timeout: Duration::from_secs(2),
+            response_timeout: Duration::from_secs(5),
+            max_retries: 3,
+            retry_delay: Duration::from_millis(100),
+            max_retry_delay: Duration::from_secs(2),
+            security: BrokerSecurity::default(),
+        }
+    }
+}
+
+impl RedisPoolConfig {
+    fn manager_config(&self) -> ConnectionManagerConfig {
+        ConnectionManagerConfig::new()
+            .set_connection_timeout(Some(self.connection_timeout))
+            .set_response_timeout(Some(self.response_timeout))
+            .set_number_of_retries(self.max_retries)
+            .set_min_delay(self.retry_delay)
+            .set_max_delay(self.max_retry_delay)
+    }
+
+    /// Delay before retry number `attempt` (starting at 1)
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.retry_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_retry_delay)
+    }
+
+    /// Each endpoint is already retried `max_retries` times, so one round over them is enough
+    fn failover_policy(&self) -> FailoverPolicy {
+        FailoverPolicy {
+            initial_backoff: self.retry_delay,
+            max_backoff: self.max_retry_delay,
+            connect_rounds: 1,
+        }
+    }
+}
+
+/// A `redis+sentinel://` endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SentinelEndpoint {
+    sentinels: Vec<String>,
+    master: String,
+    /// `user:password` of the master
+    credentials: Option<String>,
+    db: Option<String>,
+}
+
+impl SentinelEndpoint {
+    fn parse(endpoint: &str) -> Option<Self> {
+        let rest = endpoint.strip_prefix(SENTINEL_SCHEME)?;
+        let (credentials, rest) = match rest.rsplit_once('@') {
+            Some((credentials, rest)) => (Some(credentials.to_string()), rest),
+            None => (None, rest),
+        };
+        let (hosts, path) = rest.split_once('/')?;
+        let mut path = path.split('/').filter(|segment| !segment.is_empty());
+        let master = path.next()?.to_string();
+
+        Some(Self {
+            sentinels: hosts
+                .split(',')
+                .map(str::trim)
+                .filter(|host| !host.is_empty())
+                .map(str::to_string)
+                .collect(),
+            master,
+            credentials,
+            db: path.next().map(str::to_string),
+        })
+    }
+
+    fn master_url(&self, host: &str, port: &str) -> String {
+        let credentials = self
+            .credentials
+            .as_ref()
+            .map(|credentials| format!("{}@", credentials))
+            .unwrap_or_default();
+        let db = self
+            .db
+            .as_ref()
+            .map(|db| format!("/{}", db))
+            .unwrap_or_default();
+        format!("redis://{}{}:{}{}", credentials, host, port, db)
+    }
+
+    /// URL of the current master, from the first sentinel that knows it
+    async fn resolve(&self, config: &RedisPoolConfig) -> Result<String> {
+        for sentinel in &self.sentinels {
+            let lookup = async {
+                let client = Client::open(format!("redis://{}", sentinel))?;
+                let mut connection = client.get_multiplexed_async_connection().await?;
+                redis::cmd("SENTINEL")
+                    .arg("get-master-addr-by-name")
+                    .arg(&self.master)
+                    .query_async::<Option<(String, String)>>(&mut connection)
+                    .await
+            };
+            match timeout(config.connection_timeout, lookup).await {
+                Ok(Ok(Some((host, port)))) => return Ok(self.master_url(&host, &port)),
+                Ok(Ok(None)) => {
+                    tracing::warn!("Sentinel {} doesn't know master {}", sentinel, self.master)
+                }
+                Ok(Err(e)) => tracing::warn!("Sentinel {} unreachable: {}", sentinel, e),
+                Err(_) => tracing::warn!("Sentinel {} timed out", sentinel),
+            }
+        }
+        anyhow::bail!("No sentinel knows master {}", self.master)
+    }
+}
+
+/// Connection to the endpoint a [`RedisConnectionManager`] currently uses
+#[derive(Clone)]
+struct ActiveConnection {
+    client: Client,
+    connection: ConnectionManager,
+    /// Bumped on each failover, so errors of a replaced connection don't trigger another
+    generation: u64,
+}
+
+impl ActiveConnection {
+    async fn open(endpoint: String, config: &RedisPoolConfig, generation: u64) -> Result<Self> {
+        let url = match SentinelEndpoint::parse(&endpoint) {
+            Some(sentinel) => sentinel.resolve(config).await?,
+            None => endpoint,
+        };
+        let url = config.security.secure_url(&url);
+        let client = config.security.redis_client(&url)?;
+        let connection =
+            ConnectionManager::new_with_config(client.clone(), config.manager_config())
+                .await
+                .context("Failed to connect to Redis")?;
+        tracing::debug!("Connected to Redis at {}", mask_url(&url));
+
+        Ok(Self {
+            client,
+            connection,
+            generation,
+        })
+    }
+}
+
+/// Counters of a [`RedisConnectionManager`] since it was opened
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedisPoolMetrics {
+    /// Connections established, including dedicated pub/sub connections
+    pub connections_opened: u64,
+    pub pubsub_connections: u64,
+    pub commands: u64,
+    pub command_errors: u64,
+    /// Connection losses that made the manager reconnect
+    pub reconnects: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    connections_opened: AtomicU64,
+    pubsub_connections: AtomicU64,
+    commands: AtomicU64,
+    command_errors: AtomicU64,
+    reconnects: AtomicU64,
+}
+
+impl Counters {
+    fn record_result<T>(&self, result: &Result<T, RedisError>) {
+        self.commands.fetch_add(1, Ordering::Relaxed);
+        if let Err(e) = result {
+            self.command_errors.fetch_add(1, Ordering::Relaxed);
+            // The manager swaps in a new connection after these
+            if e.is_io_error() || e.is_connection_dropped() {
+                self.reconnects.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!("Redis connection lost, reconnecting: {}", e);
+            }
+        }
+    }
+}
+
+/// One multiplexed Redis connection shared by every subsystem of a process (producer,
+/// caches, ...), reconnecting in the background when it drops.
+/// Pub/sub needs a connection of its own, so subscribers get one from [`Self::pubsub`].
+///
+/// The URL may list several endpoints separated by `;`, and endpoints may be
+/// `redis+sentinel://` ones. When the endpoint in use stops answering, the manager fails over
+/// to the next reachable one, or to the master the sentinels currently name.
+#[derive(Clone)]
+pub struct RedisConnectionManager {
+    endpoints: Arc<BrokerEndpoints>,
+    active: Arc<RwLock<ActiveConnection>>,
+    /// Held while failing over
+    failing_over: Arc<Mutex<()>>,
+    config: RedisPoolConfig,
+    counters: Arc<Counters>,
+}
+
+impl RedisConnectionManager {
+    /// Open a new manager for `redis_url`, failing when none of its endpoints can be reached
+    pub async fn connect(redis_url: &str, config: &RedisPoolConfig) -> Result<Self> {
+        let endpoints = BrokerEndpoints::new("redis", redis_url, config.failover_policy());
+        let active = endpoints
+            .connect(|endpoint| ActiveConnection::open(endpoint, config, 0))
+            .await?;
+
+        let counters = Arc::new(Counters::default());
+        counters.connections_opened.fetch_add(1, Ordering::Relaxed);
+
+        Ok(Self {
+            endpoints: Arc::new(endpoints),
+            active: Arc::new(RwLock::new(active)),
+            failing_over: Arc::new(Mutex::new(())),
+            config: config.clone(),
+            counters,
+        })
+    }
+
+    /// Manager shared by the whole process for `redis_url`, opened on first use.
+    /// `config` only applies to that first call.
+    pub async fn shared(redis_url: &str, config: &RedisPoolConfig) -> Result<Self> {
+        let mut shared = SHARED.lock().await;
+        if let Some(manager) = shared.get(redis_url) {
+            return Ok(manager.clone());
+        }
+
+        let manager = Self::connect(redis_url, config).await?;
+        tracing::info!("✓ Shared Redis connection opened");
+        shared.insert(redis_url.to_string(), manager.clone());
+        Ok(manager)
+    }
+
+    fn active(&self) -> ActiveConnection {
+        self.active.read().unwrap().clone()
+    }
+
+    /// Whether there is anywhere else to go when the endpoint in use fails: another endpoint,
+    /// or sentinels that may name a new master
+    fn fails_over(&self) -> bool {
+        self.endpoints.len() > 1
+            || self
+                .endpoints
+                .iter()
+                .any(|endpoint| endpoint.starts_with(SENTINEL_SCHEME))
+    }
+
+    /// Replace the connection of `generation`, which failed with `error`, by one to the next
+    /// reachable endpoint, unless that already happened
+    async fn fail_over(&self, generation: u64, error: &str) {
+        let _guard = self.failing_over.lock().await;
+        if self.active().generation != generation {
+            return;
+        }
+
+        let config = &self.config;
+        match self
+            .endpoints
+            .failover(error, |endpoint| {
+                ActiveConnection::open(endpoint, config, generation + 1)
+            })
+            .await
+        {
+            Ok(active) => {
+                self.counters
+                    .connections_opened
+                    .fetch_add(1, Ordering::Relaxed);
+                *self.active.write().unwrap() = active;
+            }
+            Err(e) => tracing::error!("Redis failover failed: {:#}", e),
+        }
+    }
+
+    /// Handle to the shared connection; cheap to clone and to hold
+    pub fn connection(&self) -> RedisConnection {
+        RedisConnection {
+            manager: self.clone(),
+        }
+    }
+
+    /// Dedicated pub/sub connection, retried with backoff while Redis is unreachable and then
+    /// failed over when possible
+    pub async fn pubsub(&self) -> Result<PubSub> {
+        let active = self.active();
+        match self.open_pubsub(&active.client).await {
+            Err(e) if self.fails_over() => {
+                self.fail_over(active.generation, &format!("{:#}", e)).await;
+                self.open_pubsub(&self.active().client).await
+            }
+            result => result,
+        }
+    }
+
+    async fn open_pubsub(&self, client: &Client) -> Result<PubSub> {
+        let mut attempt = 0;
+        loop {
+            match client.get_async_pubsub().await {
+                Ok(pubsub) => {
+                    self.counters
+                        .connections_opened
+                        .fetch_add(1, Ordering::Relaxed);
+                    self.counters
+                        .pubsub_connections
+                        .fetch_add(1, Ordering::Relaxed);
+                    return Ok(pubsub);
+                }
+                Err(e) if attempt >= self.config.max_retries => {
+                    return Err(e).context("Failed to open Redis pub/sub connection");
+                }
+                Err(e) => {
+                    attempt += 1;
+                    tracing::warn!("Redis pub/sub attempt {} failed, retrying: {}", attempt, e);
+                    sleep(self.config.backoff(attempt as u32)).await;
+                }
+            }
+        }
+    }
+
+    /// Record that a subscriber lost its pub/sub connection and is opening a new one
+    pub fn record_reconnect(&self) {
+        self.counters.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub async fn ping(&self) -> Result<()> {
+        let _: String = redis::cmd("PING")
+            .query_async(&mut self.connection())
+            .await
+            .context("Failed to ping Redis")?;
+        Ok(())
+    }
+
+    pub fn metrics(&self) -> RedisPoolMetrics {
+        let counters = &self.counters;
+        RedisPoolMetrics {
+            connections_opened: counters.connections_opened.load(Ordering::Relaxed),
+            pubsub_connections: counters.pubsub_connections.load(Ordering::Relaxed),
+            commands: counters.commands.load(Ordering::Relaxed),
+            command_errors: counters.command_errors.load(Ordering::Relaxed),
+            reconnects: counters.reconnects.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Connection handed out by [`RedisConnectionManager`], counting commands for its metrics and
+/// following its failovers
+#[derive(Clone)]
+pub struct RedisConnection {
+    manager: RedisConnectionManager,
+}
+
+impl RedisConnection {
+    fn record_result<T>(&self, generation: u64, result: &Result<T, RedisError>) {
+        self.manager.counters.record_result(result);
+        if let Err(e) = result
+            && (e.is_io_error() || e.is_connection_dropped())
+            && self.manager.fails_over()
+        {
+            let manager = self.manager.clone();
+            let error = e.to_string();
+            tokio::spawn(async move { manager.fail_over(generation, &error).await });
+        }
+    }
+}
+
+impl ConnectionLike for RedisConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        Box::pin(async move {
+            let mut active = self.manager.active();
+            let result = active.connection.req_packed_command(cmd).await;
+            self.record_result(active.generation, &result);
+            result
+        })
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        Box::pin(async move {
+            let mut active = self.manager.active();
+            let result = active
+                .connection
+                .req_packed_commands(cmd, offset, count)
+                .await;
+            self.record_result(active.generation, &result);
+            result
+        })
+    }
+
+    fn get_db(&self) -> i64 {
+        self.manager.active().connection.get_db()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let config = RedisPoolConfig {
+            retry_delay: Duration::from_millis(100),
+            max_retry_delay: Duration::from_millis(300),
+            ..Default::default()
+        };
+
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(3), Duration::from_millis(300));
+        assert_eq!(config.backoff(30), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn parses_sentinel_endpoints() {
+        let endpoint =
+            SentinelEndpoint::parse("redis+sentinel://:secret@s1:26379, s2:26379/mymaster/2")
+                .unwrap();
+
+        assert_eq!(endpoint.sentinels, ["s1:26379", "s2:26379"]);
+        assert_eq!(endpoint.master, "mymaster");
+        assert_eq!(
+            endpoint.master_url("10.0.0.5", "6380"),
+            "redis://:secret@10.0.0.5:6380/2"
+        );
+        assert_eq!(
+            SentinelEndpoint::parse("redis+sentinel://s1:26379/mymaster")
+                .unwrap()
+                .master_url("10.0.0.5", "6379"),
+            "redis://10.0.0.5:6379"
+        );
+        assert!(SentinelEndpoint::parse("redis+sentinel://s1:26379").is_none());
+        assert!(SentinelEndpoint::parse("redis://localhost:6379").is_none());
+    }
+
+    #[tokio::test]
+    async fn connect_fails_when_no_endpoint_is_reachable() {
+        let config = RedisPoolConfig {
+            max_retries: 0,
+            ..Default::default()
+        };
+
+        let result =
+            RedisConnectionManager::connect("redis://127.0.0.1:1;redis://127.0.0.1:2", &config)
+                .await;
+
+        let Err(e) = result else {
+            panic!("Expected both endpoints to be unreachable");
+        };
+        assert!(format!("{:#}", e).contains("No redis endpoint reachable"));
+    }
+
+    #[test]
+    fn counters_track_errors_and_reconnects() {
+        let counters = Counters::default();
+        let dropped = RedisError::from(std::io::Error::from(std::io::ErrorKind::BrokenPipe));
+        let rejected = RedisError::from((redis::ErrorKind::Client, "rejected"));
+
+        counters.record_result(&Ok::<_, RedisError>(()));
+        counters.record_result(&Err::<(), _>(dropped));
+        counters.record_result(&Err::<(), _>(rejected));
+
+        assert_eq!(counters.commands.load(Ordering::Relaxed), 3);
+        assert_eq!(counters.command_errors.load(Ordering::Relaxed), 2);
+        assert_eq!(counters.reconnects.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn connect_gives_up_after_the_configured_retries() {
+        let config = RedisPoolConfig {
+            max_retries: 1,
+            retry_delay: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let started = Instant::now();
+
+        let result = RedisConnectionManager::connect("redis://127.0.0.1:1", &config).await;
+
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn shared_does_not_keep_failed_connections() {
+        let config = RedisPoolConfig {
+            max_retries: 0,
+            ..Default::default()
+        };
+
+        assert!(
+            RedisConnectionManager::shared("redis://127.0.0.1:1", &config)
+                .await
+                .is_err()
+        );
+        assert!(!SHARED.lock().await.contains_key("redis://127.0.0.1:1"));
+    }
+}
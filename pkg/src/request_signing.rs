@@ -0,0 +1,253 @@
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Hex HMAC-SHA256 of the canonical request
+pub const SIGNATURE_HEADER: &str = "x-signature";
+/// Unix seconds the request was signed at
+pub const TIMESTAMP_HEADER: &str = "x-signature-timestamp";
+/// Random value the client never reuses, so a captured request can't be sent again
+pub const NONCE_HEADER: &str = "x-signature-nonce";
+
+/// Nonces longer than this are rejected rather than stored
+const MAX_NONCE_LENGTH: usize = 128;
+
+/// Why a signed request was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestSignatureError {
+    MissingSignature,
+    MalformedSignature,
+    InvalidSignature,
+    /// The signed timestamp is outside the tolerance
+    Expired,
+}
+
+impl std::fmt::Display for RequestSignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Self::MissingSignature => "Request signature is missing",
+            Self::MalformedSignature => "Request signature is malformed",
+            Self::InvalidSignature => "Request signature is invalid",
+            Self::Expired => "Request signature has expired",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for RequestSignatureError {}
+
+/// Headers of a signed request, read before its signature is checked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedRequest<'a> {
+    pub signature: &'a str,
+    pub timestamp: i64,
+    pub nonce: &'a str,
+}
+
+impl<'a> SignedRequest<'a> {
+    pub fn from_headers(headers: &'a HeaderMap) -> Result<Self, RequestSignatureError> {
+        let header = |name| {
+            headers
+                .get(name)
+                .ok_or(RequestSignatureError::MissingSignature)?
+                .to_str()
+                .map_err(|_| RequestSignatureError::MalformedSignature)
+        };
+        let nonce = header(NONCE_HEADER)?;
+        if nonce.is_empty() || nonce.len() > MAX_NONCE_LENGTH {
+            return Err(RequestSignatureError::MalformedSignature);
+        }
+
+        Ok(Self {
+            signature: header(SIGNATURE_HEADER)?,
+            timestamp: header(TIMESTAMP_HEADER)?
+                .parse()
+                .map_err(|_| RequestSignatureError::MalformedSignature)?,
+            nonce,
+        })
+    }
+}
+
+/// Signs and verifies client requests with a shared secret. The signature covers the
+/// timestamp, nonce, method, path with query and a SHA-256 digest of the body, one per line.
+#[derive(Clone)]
+pub struct RequestSigner {
+    secret: Vec<u8>,
+    tolerance: Duration,
+}
+
+impl std::fmt::Debug for RequestSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestSigner")
+            .field("tolerance", &self.tolerance)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RequestSigner {
+    /// Signatures older or newer than `tolerance` are rejected
+    pub fn new(secret: impl AsRef<[u8]>, tolerance: Duration) -> Self {
+        Self {
+            secret: secret.as_ref().to_vec(),
+            tolerance,
+        }
+    }
+
+    /// How long a nonce must be remembered to catch every replay of a valid signature
+    pub fn nonce_ttl(&self) -> Duration {
+        self.tolerance * 2
+    }
+
+    /// Signature of a request, as a client computes it
+    pub fn sign(
+        &self,
+        timestamp: i64,
+        nonce: &str,
+        method: &str,
+        path_and_query: &str,
+        body: &[u8],
+    ) -> String {
+        hex::encode(
+            self.mac(timestamp, nonce, method, path_and_query, body)
+                .finalize()
+                .into_bytes(),
+        )
+    }
+
+    /// Check the signature of `request` as of `now` (Unix seconds). The nonce is not
+    /// checked for reuse here; callers remember it for [`nonce_ttl`](Self::nonce_ttl).
+    pub fn verify_at(
+        &self,
+        request: &SignedRequest,
+        method: &str,
+        path_and_query: &str,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), RequestSignatureError> {
+        if now.abs_diff(request.timestamp) > self.tolerance.as_secs() {
+            return Err(RequestSignatureError::Expired);
+        }
+
+        let signature = hex::decode(request.signature)
+            .map_err(|_| RequestSignatureError::MalformedSignature)?;
+        // Constant-time comparison
+        self.mac(
+            request.timestamp,
+            request.nonce,
+            method,
+            path_and_query,
+            body,
+        )
+        .verify_slice(&signature)
+        .map_err(|_| RequestSignatureError::InvalidSignature)
+    }
+
+    fn mac(
+        &self,
+        timestamp: i64,
+        nonce: &str,
+        method: &str,
+        path_and_query: &str,
+        body: &[u8],
+    ) -> HmacSha256 {
+        let canonical = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            timestamp,
+            nonce,
+            method.to_uppercase(),
+            path_and_query,
+            hex::encode(Sha256::digest(body))
+        );
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(canonical.as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{HeaderMap, HeaderValue};
+
+    use super::*;
+
+    const NOW: i64 = 1_760_000_000;
+
+    fn signer() -> RequestSigner {
+        RequestSigner::new("secret", Duration::from_secs(300))
+    }
+
+    fn headers(signature: &str, timestamp: i64, nonce: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, HeaderValue::from_str(signature).unwrap());
+        headers.insert(
+            TIMESTAMP_HEADER,
+            HeaderValue::from_str(&timestamp.to_string()).unwrap(),
+        );
+        headers.insert(NONCE_HEADER, HeaderValue::from_str(nonce).unwrap());
+        headers
+    }
+
+    fn verify(headers: &HeaderMap, path: &str, body: &[u8]) -> Result<(), RequestSignatureError> {
+        signer().verify_at(
+            &SignedRequest::from_headers(headers)?,
+            "POST",
+            path,
+            body,
+            NOW,
+        )
+    }
+
+    #[test]
+    fn accepts_valid_signature() {
+        let signature = signer().sign(NOW - 10, "n1", "post", "/api/v1/x/?a=1", b"{}");
+
+        assert_eq!(
+            verify(
+                &headers(&signature, NOW - 10, "n1"),
+                "/api/v1/x/?a=1",
+                b"{}"
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_requests() {
+        let signature = signer().sign(NOW, "n1", "POST", "/api/v1/x/", b"{}");
+
+        assert_eq!(
+            verify(&headers(&signature, NOW, "n1"), "/api/v1/y/", b"{}"),
+            Err(RequestSignatureError::InvalidSignature)
+        );
+        assert_eq!(
+            verify(&headers(&signature, NOW, "n1"), "/api/v1/x/", b"{\"a\":1}"),
+            Err(RequestSignatureError::InvalidSignature)
+        );
+        assert_eq!(
+            verify(&headers(&signature, NOW, "n2"), "/api/v1/x/", b"{}"),
+            Err(RequestSignatureError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn rejects_stale_and_malformed_signatures() {
+        let stale = signer().sign(NOW - 301, "n1", "POST", "/", b"");
+
+        assert_eq!(
+            verify(&headers(&stale, NOW - 301, "n1"), "/", b""),
+            Err(RequestSignatureError::Expired)
+        );
+        assert_eq!(
+            verify(&headers("not-hex", NOW, "n1"), "/", b""),
+            Err(RequestSignatureError::MalformedSignature)
+        );
+        assert_eq!(
+            verify(&HeaderMap::new(), "/", b""),
+            Err(RequestSignatureError::MissingSignature)
+        );
+    }
+}
@@ -0,0 +1,387 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::{circuit_breaker, http_client::HttpClient};
+
+const TWILIO_ENDPOINT: &str = "https://api.twilio.com/2010-04-01/Accounts";
+
+/// Names of the circuit breakers guarding calls to each SMS provider
+pub const TWILIO_BREAKER: &str = "twilio";
+pub const SNS_BREAKER: &str = "sns";
+
+/// Pluggable SMS provider.
+/// `to` is an E.164 phone number (e.g. `+84901234567`); failures are returned as
+/// errors so callers can retry them.
+#[async_trait]
+pub trait SmsSender: Send + Sync {
+    async fn send(&self, to: &str, body: &str) -> anyhow::Result<()>;
+}
+
+/// Twilio Programmable Messaging client
+#[derive(Debug, Clone)]
+pub struct TwilioClient {
+    client: HttpClient,
+    url: String,
+    account_sid: String,
+    auth_token: String,
+    from: String,
+}
+
+impl TwilioClient {
+    /// `from` is a Twilio phone number or messaging service sender
+    pub fn new(
+        account_sid: impl Into<String>,
+        auth_token: impl Into<String>,
+        from: impl Into<String>,
+    ) -> Self {
+        let account_sid = account_sid.into();
+        Self {
+            client: HttpClient::default(),
+            url: format!("{}/{}/Messages.json", TWILIO_ENDPOINT, account_sid),
+            account_sid,
+            auth_token: auth_token.into(),
+            from: from.into(),
+        }
+    }
+
+    /// Send to `url` instead of the Twilio endpoint (e.g. a local fake in tests)
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Send through `client` instead of the process-wide default one
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl SmsSender for TwilioClient {
+    async fn send(&self, to: &str, body: &str) -> anyhow::Result<()> {
+        let form = serde_urlencoded::to_string([("To", to), ("From", &self.from), ("Body", body)])?;
+
+        let request = self
+            .client
+            .post(&self.url)
+            .basic_auth(&self.account_sid, Some(&self.auth_token))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(form);
+        let breaker = circuit_breaker::breaker(TWILIO_BREAKER);
+        breaker.try_acquire()?;
+        let response = self.client.send(request).await;
+        // Messages refused for their content or number don't count against the provider
+        breaker.record(
+            response
+                .as_ref()
+                .is_ok_and(|response| !response.status().is_server_error()),
+        );
+        let response = response.map_err(|e| anyhow::anyhow!("Failed to reach Twilio: {}", e))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body: Value = response.json().await.unwrap_or_default();
+        Err(anyhow::anyhow!(
+            "Twilio rejected SMS with {}: {}",
+            status,
+            body["message"].as_str().unwrap_or_default()
+        ))
+    }
+}
+
+/// Amazon SNS client publishing directly to phone numbers, signed with AWS Signature V4
+#[derive(Debug, Clone)]
+pub struct SnsClient {
+    client: HttpClient,
+    url: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    sender_id: Option<String>,
+}
+
+impl SnsClient {
+    pub fn new(
+        region: impl Into<String>,
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> Self {
+        let region = region.into();
+        Self {
+            client: HttpClient::default(),
+            url: format!("https://sns.{}.amazonaws.com/", region),
+            region,
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            sender_id: None,
+        }
+    }
+
+    /// Alphanumeric sender shown to recipients, where the destination country supports it
+    pub fn with_sender_id(mut self, sender_id: Option<String>) -> Self {
+        self.sender_id = sender_id;
+        self
+    }
+
+    /// Send to `url` instead of the SNS endpoint (e.g. a local fake in tests)
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Send through `client` instead of the process-wide default one
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    fn form(&self, to: &str, body: &str) -> anyhow::Result<String> {
+        let mut params = vec![
+            ("Action", "Publish"),
+            ("Message", body),
+            ("PhoneNumber", to),
+            ("Version", "2010-03-31"),
+        ];
+        if let Some(sender_id) = &self.sender_id {
+            params.extend([
+                ("MessageAttributes.entry.1.Name", "AWS.SNS.SMS.SenderID"),
+                ("MessageAttributes.entry.1.Value.DataType", "String"),
+                ("MessageAttributes.entry.1.Value.StringValue", sender_id),
+            ]);
+        }
+        Ok(serde_urlencoded::to_string(params)?)
+    }
+
+    /// `Authorization` header value for a form POST to `host` at `now`
+    fn authorization(&self, host: &str, form: &str, now: DateTime<Utc>) -> String {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{}/{}/sns/aws4_request", date, self.region);
+        let signed_headers = "content-type;host;x-amz-date";
+
+        let canonical_request = format!(
+            "POST\n/\n\ncontent-type:application/x-www-form-urlencoded\nhost:{}\nx-amz-date:{}\n\n{}\n{}",
+            host,
+            amz_date,
+            signed_headers,
+            hex::encode(Sha256::digest(form.as_bytes()))
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let key = [date.as_str(), self.region.as_str(), "sns", "aws4_request"]
+            .iter()
+            .fold(
+                format!("AWS4{}", self.secret_access_key).into_bytes(),
+                |key, part| hmac_sha256(&key, part.as_bytes()),
+            );
+        let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, signature
+        )
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[async_trait]
+impl SmsSender for SnsClient {
+    async fn send(&self, to: &str, body: &str) -> anyhow::Result<()> {
+        let url = reqwest::Url::parse(&self.url)?;
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let form = self.form(to, body)?;
+        let now = Utc::now();
+
+        let request = self
+            .client
+            .post(url)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .header("x-amz-date", now.format("%Y%m%dT%H%M%SZ").to_string())
+            .header("authorization", self.authorization(&host, &form, now))
+            .body(form);
+        let breaker = circuit_breaker::breaker(SNS_BREAKER);
+        breaker.try_acquire()?;
+        let response = self.client.send(request).await;
+        // Messages refused for their content or number don't count against the provider
+        breaker.record(
+            response
+                .as_ref()
+                .is_ok_and(|response| !response.status().is_server_error()),
+        );
+        let response = response.map_err(|e| anyhow::anyhow!("Failed to reach SNS: {}", e))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        Err(anyhow::anyhow!(
+            "SNS rejected SMS with {}: {}",
+            status,
+            response.text().await.unwrap_or_default()
+        ))
+    }
+}
+
+/// Development sink that logs messages instead of sending them, keeping the most recent
+/// ones for inspection
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleSmsSender {
+    sent: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl ConsoleSmsSender {
+    /// Messages "sent" so far as `(to, body)` pairs
+    pub fn sent(&self) -> Vec<(String, String)> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl SmsSender for ConsoleSmsSender {
+    async fn send(&self, to: &str, body: &str) -> anyhow::Result<()> {
+        tracing::info!("📱 SMS to {}: {}", to, body);
+        let mut sent = self.sent.lock().unwrap();
+        // Bounded so a long-running dev server doesn't grow without limit
+        if sent.len() >= 100 {
+            sent.remove(0);
+        }
+        sent.push((to.to_string(), body.to_string()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+    };
+    use chrono::TimeZone;
+    use serde_json::json;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    type Requests = Arc<Mutex<Vec<(HeaderMap, String)>>>;
+
+    async fn spawn_fake_provider(path: &'static str, status: StatusCode) -> (String, Requests) {
+        let requests = Requests::default();
+        let recorded = requests.clone();
+        let app = Router::new().route(
+            path,
+            post(move |headers: HeaderMap, body: String| {
+                let recorded = recorded.clone();
+                async move {
+                    recorded.lock().unwrap().push((headers, body));
+                    (status, axum::Json(json!({ "message": "rejected" })))
+                }
+            }),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn twilio_sends_form_with_basic_auth() {
+        let (url, requests) = spawn_fake_provider("/messages", StatusCode::CREATED).await;
+        let client = TwilioClient::new("AC123", "secret", "+15550000000")
+            .with_url(format!("{url}/messages"));
+
+        client.send("+15551234567", "Code: 123456").await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let (headers, body) = &requests[0];
+        assert!(
+            headers["authorization"]
+                .to_str()
+                .unwrap()
+                .starts_with("Basic ")
+        );
+        assert!(body.contains("To=%2B15551234567"));
+        assert!(body.contains("Body=Code%3A+123456"));
+    }
+
+    #[tokio::test]
+    async fn twilio_surfaces_rejections() {
+        let (url, _) = spawn_fake_provider("/messages", StatusCode::BAD_REQUEST).await;
+        let client = TwilioClient::new("AC123", "secret", "+15550000000")
+            .with_url(format!("{url}/messages"));
+
+        let error = client.send("+1", "Hi").await.unwrap_err();
+
+        assert!(error.to_string().contains("rejected"));
+    }
+
+    #[tokio::test]
+    async fn sns_publishes_signed_request() {
+        let (url, requests) = spawn_fake_provider("/", StatusCode::OK).await;
+        let client = SnsClient::new("us-east-1", "AKID", "secret")
+            .with_sender_id(Some("MyAxum".to_string()))
+            .with_url(format!("{url}/"));
+
+        client.send("+15551234567", "Hello").await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let (headers, body) = &requests[0];
+        let authorization = headers["authorization"].to_str().unwrap();
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKID/"));
+        assert!(authorization.contains("/us-east-1/sns/aws4_request"));
+        assert!(body.contains("Action=Publish"));
+        assert!(body.contains("PhoneNumber=%2B15551234567"));
+        assert!(body.contains("StringValue=MyAxum"));
+    }
+
+    #[test]
+    fn sns_signature_is_deterministic() {
+        let client = SnsClient::new("us-east-1", "AKID", "secret");
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+
+        let first = client.authorization("sns.us-east-1.amazonaws.com", "a=1", now);
+        let second = client.authorization("sns.us-east-1.amazonaws.com", "a=1", now);
+        let other_body = client.authorization("sns.us-east-1.amazonaws.com", "a=2", now);
+
+        assert_eq!(first, second);
+        assert_ne!(first, other_body);
+        assert!(first.contains("Credential=AKID/20261017/us-east-1/sns/aws4_request"));
+    }
+
+    #[tokio::test]
+    async fn console_sender_records_messages() {
+        let sender = ConsoleSmsSender::default();
+
+        sender.send("+15551234567", "Hello").await.unwrap();
+
+        assert_eq!(
+            sender.sent(),
+            vec![("+15551234567".to_string(), "Hello".to_string())]
+        );
+    }
+}
@@ -1,6 +1,14 @@
+use std::sync::Arc;
+
 use lettre::message::header::ContentType;
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use tokio::sync::Mutex;
+
+use crate::circuit_breaker;
+
+/// Name of the circuit breaker guarding deliveries to the SMTP server
+pub const SMTP_BREAKER: &str = "smtp";
 
 #[derive(Clone, Debug)]
 pub struct SmtpConfig {
@@ -47,9 +55,51 @@ impl SmtpConfig {
     }
 }
 
+/// An email recorded by a [`MailCapture`] instead of being delivered
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedEmail {
+    pub from: String,
+    pub to: String,
+    pub subject: String,
+    pub text_body: Option<String>,
+    pub html_body: Option<String>,
+}
+
+/// In-memory mail sink for tests and local development
+#[derive(Clone, Debug, Default)]
+pub struct MailCapture {
+    emails: Arc<Mutex<Vec<CapturedEmail>>>,
+}
+
+impl MailCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All captured emails, in send order
+    pub async fn emails(&self) -> Vec<CapturedEmail> {
+        self.emails.lock().await.clone()
+    }
+
+    /// Discard all captured emails
+    pub async fn clear(&self) {
+        self.emails.lock().await.clear();
+    }
+
+    async fn record(&self, email: CapturedEmail) {
+        self.emails.lock().await.push(email);
+    }
+}
+
+#[derive(Clone, Debug)]
+enum MailTransport {
+    Smtp(AsyncSmtpTransport<Tokio1Executor>),
+    Capture(MailCapture),
+}
+
 #[derive(Clone, Debug)]
 pub struct SmtpClient {
-    transport: AsyncSmtpTransport<Tokio1Executor>,
+    transport: MailTransport,
     sender: String,
 }
 
@@ -73,11 +123,32 @@ impl SmtpClient {
         };
 
         Ok(Self {
-            transport,
+            transport: MailTransport::Smtp(transport),
             sender: config.username,
         })
     }
 
+    /// Create a client that records emails in `capture` instead of sending them
+    pub fn capture(sender: impl Into<String>, capture: MailCapture) -> Self {
+        Self {
+            transport: MailTransport::Capture(capture),
+            sender: sender.into(),
+        }
+    }
+
+    async fn deliver(&self, email: Message, captured: CapturedEmail) -> anyhow::Result<()> {
+        match &self.transport {
+            MailTransport::Smtp(transport) => {
+                circuit_breaker::breaker(SMTP_BREAKER)
+                    .call(async { Ok(transport.send(email).await?) })
+                    .await?;
+            }
+            MailTransport::Capture(capture) => capture.record(captured).await,
+        }
+
+        Ok(())
+    }
+
     pub fn from_params(
         host: String,
         port: u16,
@@ -106,12 +177,20 @@ impl SmtpClient {
                 .parse()
                 .map_err(|e| anyhow::anyhow!("Invalid recipient email {}: {}", to, e))?)
             .subject(subject)
-            .header(content_type)
-            .body(body)
+            .header(content_type.clone())
+            .body(body.clone())
             .map_err(|e| anyhow::anyhow!("Failed to build email message: {}", e))?;
 
-        self.transport
-            .send(email)
+        let is_html = content_type == ContentType::TEXT_HTML;
+        let captured = CapturedEmail {
+            from: self.sender.clone(),
+            to: to.to_string(),
+            subject: subject.to_string(),
+            text_body: (!is_html).then(|| body.clone()),
+            html_body: is_html.then_some(body),
+        };
+
+        self.deliver(email, captured)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to send email: {}", e))?;
 
@@ -162,18 +241,25 @@ impl SmtpClient {
                     .singlepart(
                         SinglePart::builder()
                             .header(ContentType::TEXT_PLAIN)
-                            .body(text_body),
+                            .body(text_body.clone()),
                     )
                     .singlepart(
                         SinglePart::builder()
                             .header(ContentType::TEXT_HTML)
-                            .body(html_body),
+                            .body(html_body.clone()),
                     ),
             )
             .map_err(|e| anyhow::anyhow!("Failed to build multipart email: {}", e))?;
 
-        self.transport
-            .send(email)
+        let captured = CapturedEmail {
+            from: self.sender.clone(),
+            to: to.to_string(),
+            subject: subject.to_string(),
+            text_body: Some(text_body),
+            html_body: Some(html_body),
+        };
+
+        self.deliver(email, captured)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to send multipart email: {}", e))?;
 
@@ -181,8 +267,12 @@ impl SmtpClient {
     }
 
     pub async fn test_connection(&self) -> anyhow::Result<()> {
+        let MailTransport::Smtp(transport) = &self.transport else {
+            return Ok(());
+        };
+
         // Test connection by sending NOOP command
-        self.transport
+        transport
             .test_connection()
             .await
             .map_err(|e| anyhow::anyhow!("SMTP connection test failed: {}", e))?;
@@ -193,7 +283,7 @@ impl SmtpClient {
 
 #[cfg(test)]
 mod tests {
-    use super::{SmtpClient, SmtpConfig};
+    use super::{MailCapture, SmtpClient, SmtpConfig};
 
     #[test]
     fn builds_common_configs() {
@@ -231,4 +321,37 @@ mod tests {
 
         assert!(error.to_string().contains("Invalid recipient email"));
     }
+
+    #[tokio::test]
+    async fn capture_records_sent_emails() {
+        let capture = MailCapture::new();
+        let client = SmtpClient::capture("noreply@example.com", capture.clone());
+
+        client
+            .send_text_mail("a@example.com", "Plain", "hello".to_string())
+            .await
+            .unwrap();
+        client
+            .send_multipart_mail(
+                "b@example.com",
+                "Multi",
+                "text".to_string(),
+                "<p>html</p>".to_string(),
+            )
+            .await
+            .unwrap();
+        assert!(client.test_connection().await.is_ok());
+
+        let emails = capture.emails().await;
+        assert_eq!(emails.len(), 2);
+        assert_eq!(emails[0].from, "noreply@example.com");
+        assert_eq!(emails[0].to, "a@example.com");
+        assert_eq!(emails[0].text_body.as_deref(), Some("hello"));
+        assert!(emails[0].html_body.is_none());
+        assert_eq!(emails[1].subject, "Multi");
+        assert_eq!(emails[1].html_body.as_deref(), Some("<p>html</p>"));
+
+        capture.clear().await;
+        assert!(capture.emails().await.is_empty());
+    }
 }
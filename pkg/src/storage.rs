@@ -0,0 +1,447 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::circuit_breaker;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Name of the circuit breaker guarding reads and writes of the object storage
+pub const STORAGE_BREAKER: &str = "storage";
+
+/// Metadata about a stored object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectInfo {
+    pub key: String,
+    pub size: u64,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+/// Key-addressed object storage used for user uploads and generated artifacts
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    /// Store an object, replacing any existing object with the same key
+    async fn put(&self, key: &str, data: &[u8]) -> anyhow::Result<()>;
+
+    /// Read an object, returning `None` if it does not exist
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Delete an object (no-op if it does not exist)
+    async fn delete(&self, key: &str) -> anyhow::Result<()>;
+
+    /// Move an object to a new key
+    async fn rename(&self, from: &str, to: &str) -> anyhow::Result<()>;
+
+    /// List all objects whose key starts with `prefix` (empty prefix lists everything)
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<ObjectInfo>>;
+
+    /// Check whether an object exists
+    async fn exists(&self, key: &str) -> anyhow::Result<bool> {
+        Ok(self.get(key).await?.is_some())
+    }
+
+    /// URL downloading the object without credentials until `expires_in` has passed,
+    /// or `None` if the storage can't hand out such links
+    async fn presigned_url(
+        &self,
+        _key: &str,
+        _expires_in: Duration,
+    ) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+/// Signs time-limited download URLs for storages that can't presign them themselves.
+/// The endpoint at `base_url` serves the object once `verify` accepts the query parameters.
+#[derive(Clone)]
+pub struct UrlSigner {
+    base_url: String,
+    secret: Vec<u8>,
+}
+
+impl std::fmt::Debug for UrlSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UrlSigner")
+            .field("base_url", &self.base_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl UrlSigner {
+    pub fn new(base_url: impl Into<String>, secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            secret: secret.as_ref().to_vec(),
+        }
+    }
+
+    fn mac(&self, key: &str, expires: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(key.as_bytes());
+        mac.update(b"\n");
+        mac.update(expires.to_string().as_bytes());
+        mac
+    }
+
+    /// URL of `key` with its `expires` timestamp and `signature` as query parameters
+    pub fn sign(&self, key: &str, expires_at: DateTime<Utc>) -> anyhow::Result<String> {
+        let expires = expires_at.timestamp();
+        let signature = hex::encode(self.mac(key, expires).finalize().into_bytes());
+        let query = serde_urlencoded::to_string([
+            ("key", key),
+            ("expires", &expires.to_string()),
+            ("signature", &signature),
+        ])?;
+        Ok(format!("{}?{}", self.base_url, query))
+    }
+
+    /// URL of `path` under `base_url`, e.g. `/media/avatars/1/64`, signed for endpoints that
+    /// serve something else than a storage key. `verify` checks it with `path` as the key; no
+    /// storage key starts with `/`, so the signature can't be passed off as a download link.
+    pub fn sign_path(&self, path: &str, expires_at: DateTime<Utc>) -> anyhow::Result<String> {
+        let expires = expires_at.timestamp();
+        let signature = hex::encode(self.mac(path, expires).finalize().into_bytes());
+        let query = serde_urlencoded::to_string([
+            ("expires", expires.to_string()),
+            ("signature", signature),
+        ])?;
+        Ok(format!(
+            "{}{}?{}",
+            self.base_url.trim_end_matches('/'),
+            path,
+            query
+        ))
+    }
+
+    /// Whether `signature` was issued for `key` and `expires` is still ahead of `now`
+    pub fn verify(&self, key: &str, expires: i64, signature: &str, now: DateTime<Utc>) -> bool {
+        if expires <= now.timestamp() {
+            return false;
+        }
+        match hex::decode(signature) {
+            Ok(expected) => self.mac(key, expires).verify_slice(&expected).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Filesystem-backed storage rooted at a local directory
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+    signer: Option<UrlSigner>,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            signer: None,
+        }
+    }
+
+    /// Hand out presigned URLs signed by `signer`
+    pub fn with_signer(mut self, signer: UrlSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolve a key to a path under the root, rejecting keys that escape it
+    fn resolve(&self, key: &str) -> anyhow::Result<PathBuf> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+
+        if !is_safe {
+            return Err(anyhow::anyhow!("Invalid storage key: {}", key));
+        }
+
+        Ok(self.root.join(relative))
+    }
+
+    /// Walk the root for the objects whose key starts with `prefix`
+    async fn list_objects(&self, prefix: &str) -> anyhow::Result<Vec<ObjectInfo>> {
+        let mut objects = Vec::new();
+        let mut directories = vec![self.root.clone()];
+
+        while let Some(directory) = directories.pop() {
+            let mut entries = match tokio::fs::read_dir(&directory).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(anyhow::anyhow!("Failed to list {:?}: {}", directory, e)),
+            };
+
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                if metadata.is_dir() {
+                    directories.push(entry.path());
+                    continue;
+                }
+
+                let key = entry
+                    .path()
+                    .strip_prefix(&self.root)?
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if key.starts_with(prefix) {
+                    objects.push(ObjectInfo {
+                        key,
+                        size: metadata.len(),
+                        last_modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+                    });
+                }
+            }
+        }
+
+        objects.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(objects)
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for LocalStorage {
+    async fn put(&self, key: &str, data: &[u8]) -> anyhow::Result<()> {
+        let path = self.resolve(key)?;
+        circuit_breaker::breaker(STORAGE_BREAKER)
+            .call(async {
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent)
+                        .await
+                        .with_context(|| format!("Failed to create directory for {}", key))?;
+                }
+
+                tokio::fs::write(&path, data)
+                    .await
+                    .with_context(|| format!("Failed to write object {}", key))
+            })
+            .await
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let path = self.resolve(key)?;
+        circuit_breaker::breaker(STORAGE_BREAKER)
+            .call(async {
+                match tokio::fs::read(&path).await {
+                    Ok(data) => Ok(Some(data)),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                    Err(e) => Err(anyhow::anyhow!("Failed to read object {}: {}", key, e)),
+                }
+            })
+            .await
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        let path = self.resolve(key)?;
+        circuit_breaker::breaker(STORAGE_BREAKER)
+            .call(async {
+                match tokio::fs::remove_file(&path).await {
+                    Ok(()) => Ok(()),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                    Err(e) => Err(anyhow::anyhow!("Failed to delete object {}: {}", key, e)),
+                }
+            })
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> anyhow::Result<()> {
+        let source = self.resolve(from)?;
+        let target = self.resolve(to)?;
+        circuit_breaker::breaker(STORAGE_BREAKER)
+            .call(async {
+                if let Some(parent) = target.parent() {
+                    tokio::fs::create_dir_all(parent)
+                        .await
+                        .with_context(|| format!("Failed to create directory for {}", to))?;
+                }
+
+                tokio::fs::rename(&source, &target)
+                    .await
+                    .with_context(|| format!("Failed to move object {} to {}", from, to))
+            })
+            .await
+    }
+
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<ObjectInfo>> {
+        circuit_breaker::breaker(STORAGE_BREAKER)
+            .call(self.list_objects(prefix))
+            .await
+    }
+
+    async fn exists(&self, key: &str) -> anyhow::Result<bool> {
+        let path = self.resolve(key)?;
+        Ok(tokio::fs::try_exists(&path).await.unwrap_or(false))
+    }
+
+    async fn presigned_url(
+        &self,
+        key: &str,
+        expires_in: Duration,
+    ) -> anyhow::Result<Option<String>> {
+        self.resolve(key)?;
+        self.signer
+            .as_ref()
+            .map(|signer| signer.sign(key, Utc::now() + expires_in))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::{LocalStorage, ObjectStorage, UrlSigner};
+
+    fn temp_storage() -> LocalStorage {
+        let root = std::env::temp_dir().join(format!("storage-test-{}", uuid::Uuid::new_v4()));
+        LocalStorage::new(root)
+    }
+
+    #[tokio::test]
+    async fn put_get_and_delete_round_trip() {
+        let storage = temp_storage();
+
+        storage.put("avatars/1/a.png", b"data").await.unwrap();
+
+        assert_eq!(
+            storage.get("avatars/1/a.png").await.unwrap().as_deref(),
+            Some(&b"data"[..])
+        );
+        assert!(storage.exists("avatars/1/a.png").await.unwrap());
+
+        storage.delete("avatars/1/a.png").await.unwrap();
+
+        assert!(storage.get("avatars/1/a.png").await.unwrap().is_none());
+        assert!(!storage.exists("avatars/1/a.png").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn rename_moves_object() {
+        let storage = temp_storage();
+        storage.put("avatars/1/a.png", b"data").await.unwrap();
+
+        storage
+            .rename("avatars/1/a.png", "quarantine/avatars/1/a.png")
+            .await
+            .unwrap();
+
+        assert!(storage.get("avatars/1/a.png").await.unwrap().is_none());
+        assert!(
+            storage
+                .get("quarantine/avatars/1/a.png")
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn lists_objects_by_prefix() {
+        let storage = temp_storage();
+        storage.put("avatars/1/a.png", b"a").await.unwrap();
+        storage.put("avatars/2/b.png", b"bb").await.unwrap();
+        storage.put("reports/r.csv", b"ccc").await.unwrap();
+
+        let all = storage.list("").await.unwrap();
+        let avatars = storage.list("avatars/").await.unwrap();
+
+        assert_eq!(all.len(), 3);
+        assert_eq!(
+            avatars.iter().map(|o| o.key.as_str()).collect::<Vec<_>>(),
+            vec!["avatars/1/a.png", "avatars/2/b.png"]
+        );
+        assert_eq!(avatars[1].size, 2);
+        assert!(avatars[0].last_modified.is_some());
+    }
+
+    #[tokio::test]
+    async fn lists_nothing_for_missing_root() {
+        assert!(temp_storage().list("").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_keys_escaping_root() {
+        let storage = temp_storage();
+
+        assert!(storage.put("../outside.txt", b"data").await.is_err());
+        assert!(storage.get("/etc/passwd").await.is_err());
+        assert!(storage.delete("").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn presigns_urls_only_with_a_signer() {
+        let signer = UrlSigner::new("http://localhost/files/", "secret");
+        let storage = temp_storage();
+
+        assert!(
+            storage
+                .presigned_url("avatars/1/a.png", Duration::minutes(5))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let url = storage
+            .with_signer(signer)
+            .presigned_url("avatars/1/a b.png", Duration::minutes(5))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(url.starts_with("http://localhost/files/?key=avatars%2F1%2Fa+b.png&expires="));
+        assert!(url.contains("&signature="));
+    }
+
+    #[test]
+    fn verifies_signed_urls_until_they_expire() {
+        let signer = UrlSigner::new("http://localhost/files/", "secret");
+        let now = Utc::now();
+        let expires_at = now + Duration::minutes(5);
+        let url = signer.sign("avatars/1/a.png", expires_at).unwrap();
+        let signature = url.rsplit_once("signature=").unwrap().1;
+        let expires = expires_at.timestamp();
+
+        assert!(signer.verify("avatars/1/a.png", expires, signature, now));
+        assert!(!signer.verify("avatars/2/a.png", expires, signature, now));
+        assert!(!signer.verify("avatars/1/a.png", expires + 1, signature, now));
+        assert!(!signer.verify("avatars/1/a.png", expires, "not-hex", now));
+        assert!(!signer.verify(
+            "avatars/1/a.png",
+            expires,
+            signature,
+            expires_at + Duration::seconds(1)
+        ));
+        assert!(!UrlSigner::new("http://localhost/files/", "other").verify(
+            "avatars/1/a.png",
+            expires,
+            signature,
+            now
+        ));
+    }
+
+    #[test]
+    fn signs_paths_apart_from_keys() {
+        let signer = UrlSigner::new("http://localhost/", "secret");
+        let now = Utc::now();
+        let expires_at = now + Duration::minutes(5);
+        let url = signer.sign_path("/media/avatars/1/64", expires_at).unwrap();
+        let signature = url.rsplit_once("signature=").unwrap().1;
+        let expires = expires_at.timestamp();
+
+        assert!(url.starts_with("http://localhost/media/avatars/1/64?expires="));
+        assert!(signer.verify("/media/avatars/1/64", expires, signature, now));
+        assert!(!signer.verify("media/avatars/1/64", expires, signature, now));
+        assert!(!signer.verify("/media/avatars/2/64", expires, signature, now));
+    }
+}
@@ -0,0 +1,47 @@
+use std::io::Cursor;
+
+use image::ImageFormat;
+
+/// Resize an image so it fits within `max_size` x `max_size`, preserving aspect ratio.
+/// The result is always encoded as PNG.
+pub fn generate_thumbnail(data: &[u8], max_size: u32) -> anyhow::Result<Vec<u8>> {
+    let image = image::load_from_memory(data)
+        .map_err(|e| anyhow::anyhow!("Failed to decode image: {}", e))?;
+
+    let thumbnail = image.thumbnail(max_size, max_size);
+
+    let mut output = Cursor::new(Vec::new());
+    thumbnail
+        .write_to(&mut output, ImageFormat::Png)
+        .map_err(|e| anyhow::anyhow!("Failed to encode thumbnail: {}", e))?;
+
+    Ok(output.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_thumbnail;
+    use image::{GenericImageView, ImageFormat, RgbImage};
+    use std::io::Cursor;
+
+    fn sample_png(width: u32, height: u32) -> Vec<u8> {
+        let mut output = Cursor::new(Vec::new());
+        RgbImage::new(width, height)
+            .write_to(&mut output, ImageFormat::Png)
+            .unwrap();
+        output.into_inner()
+    }
+
+    #[test]
+    fn resizes_within_bounds_preserving_aspect_ratio() {
+        let thumbnail = generate_thumbnail(&sample_png(400, 200), 100).unwrap();
+
+        let decoded = image::load_from_memory(&thumbnail).unwrap();
+        assert_eq!(decoded.dimensions(), (100, 50));
+    }
+
+    #[test]
+    fn rejects_non_image_data() {
+        assert!(generate_thumbnail(b"not an image", 64).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Hashes secrets that are looked up by value (refresh tokens, OTPs, ...) before they are
+/// stored, so a database leak doesn't expose them. The digest is an HMAC-SHA256 keyed with
+/// an application secret: unlike a plain hash, short values such as 6-digit OTPs can't be
+/// brute-forced from a leaked table alone.
+#[derive(Clone)]
+pub struct TokenHasher {
+    key: Vec<u8>,
+}
+
+impl std::fmt::Debug for TokenHasher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenHasher").finish_non_exhaustive()
+    }
+}
+
+impl TokenHasher {
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: key.as_ref().to_vec(),
+        }
+    }
+
+    fn mac(&self, token: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any size");
+        mac.update(token.as_bytes());
+        mac
+    }
+
+    /// Hex digest of `token`, deterministic so it can be used in lookups
+    pub fn hash(&self, token: &str) -> String {
+        hex::encode(self.mac(token).finalize().into_bytes())
+    }
+
+    /// Whether `hash` is the digest of `token`, compared in constant time
+    pub fn verify(&self, token: &str, hash: &str) -> bool {
+        match hex::decode(hash) {
+            Ok(expected) => self.mac(token).verify_slice(&expected).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_deterministic_hex() {
+        let hasher = TokenHasher::new("secret");
+
+        let hash = hasher.hash("123456");
+
+        assert_eq!(hash, hasher.hash("123456"));
+        assert_eq!(hash.len(), 64);
+        assert_ne!(hash, "123456");
+    }
+
+    #[test]
+    fn hash_depends_on_the_key() {
+        assert_ne!(
+            TokenHasher::new("one").hash("token"),
+            TokenHasher::new("two").hash("token")
+        );
+    }
+
+    #[test]
+    fn verifies_only_the_matching_token() {
+        let hasher = TokenHasher::new("secret");
+        let hash = hasher.hash("token");
+
+        assert!(hasher.verify("token", &hash));
+        assert!(!hasher.verify("other", &hash));
+        assert!(!hasher.verify("token", "not-hex"));
+        assert!(!TokenHasher::new("other").verify("token", &hash));
+    }
+}
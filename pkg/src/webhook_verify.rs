@@ -0,0 +1,398 @@
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use sha2::Sha256;
+use std::{marker::PhantomData, time::Duration};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How a webhook provider signs its callbacks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookScheme {
+    /// Hex HMAC-SHA256 of the raw body in `header`, after an optional `prefix`
+    HmacSha256 {
+        header: String,
+        prefix: Option<String>,
+    },
+    /// GitHub style: `X-Hub-Signature-256: sha256=<hex>`
+    GitHub,
+    /// Stripe style: `Stripe-Signature: t=<unix>,v1=<hex>,...`, signing `<t>.<body>`.
+    /// Signatures older or newer than `tolerance` are rejected to prevent replays.
+    Stripe { tolerance: Duration },
+}
+
+/// Why a webhook was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookError {
+    MissingSignature,
+    MalformedSignature,
+    InvalidSignature,
+    /// The signed timestamp is outside the scheme's tolerance
+    Expired,
+}
+
+impl std::fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Self::MissingSignature => "Webhook signature is missing",
+            Self::MalformedSignature => "Webhook signature is malformed",
+            Self::InvalidSignature => "Webhook signature is invalid",
+            Self::Expired => "Webhook signature has expired",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for WebhookError {}
+
+/// Checks that a callback was signed by the provider sharing `secret`
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    scheme: WebhookScheme,
+    secret: Vec<u8>,
+}
+
+impl std::fmt::Debug for WebhookVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookVerifier")
+            .field("scheme", &self.scheme)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WebhookVerifier {
+    pub fn new(scheme: WebhookScheme, secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            scheme,
+            secret: secret.as_ref().to_vec(),
+        }
+    }
+
+    pub fn hmac_sha256(header: &str, secret: impl AsRef<[u8]>) -> Self {
+        Self::new(
+            WebhookScheme::HmacSha256 {
+                header: header.to_string(),
+                prefix: None,
+            },
+            secret,
+        )
+    }
+
+    pub fn github(secret: impl AsRef<[u8]>) -> Self {
+        Self::new(WebhookScheme::GitHub, secret)
+    }
+
+    /// Stripe scheme with Stripe's default tolerance of 5 minutes
+    pub fn stripe(secret: impl AsRef<[u8]>) -> Self {
+        Self::new(
+            WebhookScheme::Stripe {
+                tolerance: Duration::from_secs(300),
+            },
+            secret,
+        )
+    }
+
+    pub fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), WebhookError> {
+        self.verify_at(headers, body, chrono::Utc::now().timestamp())
+    }
+
+    /// Verify as of `now` (Unix seconds), for the schemes signing a timestamp
+    pub fn verify_at(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), WebhookError> {
+        match &self.scheme {
+            WebhookScheme::HmacSha256 { header, prefix } => {
+                self.verify_hex(header_value(headers, header)?, prefix.as_deref(), body)
+            }
+            WebhookScheme::GitHub => self.verify_hex(
+                header_value(headers, "x-hub-signature-256")?,
+                Some("sha256="),
+                body,
+            ),
+            WebhookScheme::Stripe { tolerance } => self.verify_stripe(
+                header_value(headers, "stripe-signature")?,
+                body,
+                *tolerance,
+                now,
+            ),
+        }
+    }
+
+    /// Signature of `payload` as the provider computes it, e.g. to sign test requests
+    pub fn sign(&self, payload: &[u8]) -> String {
+        hex::encode(self.mac(&[payload]).finalize().into_bytes())
+    }
+
+    fn mac(&self, parts: &[&[u8]]) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        for part in parts {
+            mac.update(part);
+        }
+        mac
+    }
+
+    fn verify_hex(
+        &self,
+        value: &str,
+        prefix: Option<&str>,
+        body: &[u8],
+    ) -> Result<(), WebhookError> {
+        let signature = match prefix {
+            Some(prefix) => value
+                .strip_prefix(prefix)
+                .ok_or(WebhookError::MalformedSignature)?,
+            None => value,
+        };
+        let signature = hex::decode(signature).map_err(|_| WebhookError::MalformedSignature)?;
+
+        // Constant-time comparison
+        self.mac(&[body])
+            .verify_slice(&signature)
+            .map_err(|_| WebhookError::InvalidSignature)
+    }
+
+    fn verify_stripe(
+        &self,
+        value: &str,
+        body: &[u8],
+        tolerance: Duration,
+        now: i64,
+    ) -> Result<(), WebhookError> {
+        let mut timestamp = None;
+        let mut signatures = Vec::new();
+        for item in value.split(',') {
+            match item.trim().split_once('=') {
+                Some(("t", t)) => timestamp = t.parse::<i64>().ok(),
+                Some(("v1", signature)) => signatures.push(signature),
+                _ => {}
+            }
+        }
+        let timestamp = timestamp.ok_or(WebhookError::MalformedSignature)?;
+        if signatures.is_empty() {
+            return Err(WebhookError::MalformedSignature);
+        }
+        if now.abs_diff(timestamp) > tolerance.as_secs() {
+            return Err(WebhookError::Expired);
+        }
+
+        // Several signatures are sent while the provider rolls its secret
+        let signed = [timestamp.to_string().as_bytes(), b".", body].concat();
+        let valid = signatures.iter().any(|signature| {
+            hex::decode(signature)
+                .is_ok_and(|signature| self.mac(&[&signed]).verify_slice(&signature).is_ok())
+        });
+        if valid {
+            Ok(())
+        } else {
+            Err(WebhookError::InvalidSignature)
+        }
+    }
+}
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str, WebhookError> {
+    headers
+        .get(name)
+        .ok_or(WebhookError::MissingSignature)?
+        .to_str()
+        .map_err(|_| WebhookError::MalformedSignature)
+}
+
+/// Source of the verifier for the callbacks of one provider, given the router state
+pub trait WebhookProvider<S>: Send + Sync + 'static {
+    fn verifier(state: &S) -> WebhookVerifier;
+}
+
+/// Raw body of a callback whose signature `P` verified. Extracting it rejects forged or
+/// replayed requests with `401` before the handler runs:
+///
+/// ```ignore
+/// async fn payment_callback(webhook: VerifiedWebhook<Payments>) -> StatusCode {
+///     let event: PaymentEvent = webhook.json()?;
+///     ...
+/// }
+/// ```
+pub struct VerifiedWebhook<P> {
+    pub body: Bytes,
+    provider: PhantomData<P>,
+}
+
+impl<P> VerifiedWebhook<P> {
+    pub fn json<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_slice(&self.body)
+    }
+}
+
+/// Rejection of [`VerifiedWebhook`]
+#[derive(Debug)]
+pub enum WebhookRejection {
+    Unverified(WebhookError),
+    /// The body couldn't be read
+    Body(String),
+}
+
+impl IntoResponse for WebhookRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Unverified(e) => (StatusCode::UNAUTHORIZED, e.to_string()).into_response(),
+            Self::Body(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+        }
+    }
+}
+
+impl<S, P> FromRequest<S> for VerifiedWebhook<P>
+where
+    S: Send + Sync,
+    P: WebhookProvider<S>,
+{
+    type Rejection = WebhookRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let headers = req.headers().clone();
+        // The signature covers the exact bytes sent, so the body is checked before parsing
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|e| WebhookRejection::Body(e.body_text()))?;
+
+        P::verifier(state).verify(&headers, &body).map_err(|e| {
+            tracing::warn!("Rejected webhook: {}", e);
+            WebhookRejection::Unverified(e)
+        })?;
+
+        Ok(Self {
+            body,
+            provider: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, routing::post};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const NOW: i64 = 1_760_000_000;
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn verifies_plain_hmac_signatures() {
+        let verifier = WebhookVerifier::hmac_sha256("x-signature", "secret");
+        let signature = verifier.sign(b"payload");
+
+        assert_eq!(
+            verifier.verify(&headers("x-signature", &signature), b"payload"),
+            Ok(())
+        );
+        assert_eq!(
+            verifier.verify(&headers("x-signature", &signature), b"tampered"),
+            Err(WebhookError::InvalidSignature)
+        );
+        assert_eq!(
+            verifier.verify(&HeaderMap::new(), b"payload"),
+            Err(WebhookError::MissingSignature)
+        );
+        assert_eq!(
+            verifier.verify(&headers("x-signature", "not-hex"), b"payload"),
+            Err(WebhookError::MalformedSignature)
+        );
+    }
+
+    #[test]
+    fn verifies_github_signatures() {
+        let verifier = WebhookVerifier::github("secret");
+        let signature = format!("sha256={}", verifier.sign(b"{}"));
+
+        assert_eq!(
+            verifier.verify(&headers("x-hub-signature-256", &signature), b"{}"),
+            Ok(())
+        );
+        assert_eq!(
+            verifier.verify(
+                &headers("x-hub-signature-256", &verifier.sign(b"{}")),
+                b"{}"
+            ),
+            Err(WebhookError::MalformedSignature)
+        );
+        assert_eq!(
+            WebhookVerifier::github("other")
+                .verify(&headers("x-hub-signature-256", &signature), b"{}"),
+            Err(WebhookError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn verifies_stripe_signatures_within_tolerance() {
+        let verifier = WebhookVerifier::stripe("whsec");
+        let signature = verifier.sign(format!("{}.{{}}", NOW).as_bytes());
+        let header = format!("t={},v1=deadbeef,v1={}", NOW, signature);
+
+        assert_eq!(
+            verifier.verify_at(&headers("stripe-signature", &header), b"{}", NOW + 60),
+            Ok(())
+        );
+        assert_eq!(
+            verifier.verify_at(&headers("stripe-signature", &header), b"{}", NOW + 301),
+            Err(WebhookError::Expired)
+        );
+        assert_eq!(
+            verifier.verify_at(&headers("stripe-signature", &header), b"{ }", NOW),
+            Err(WebhookError::InvalidSignature)
+        );
+        assert_eq!(
+            verifier.verify_at(&headers("stripe-signature", "v1=abc"), b"{}", NOW),
+            Err(WebhookError::MalformedSignature)
+        );
+    }
+
+    struct TestProvider;
+
+    impl WebhookProvider<()> for TestProvider {
+        fn verifier(_state: &()) -> WebhookVerifier {
+            WebhookVerifier::github("secret")
+        }
+    }
+
+    async fn status_of(request: Request) -> StatusCode {
+        let app = Router::new().route(
+            "/",
+            post(|webhook: VerifiedWebhook<TestProvider>| async move {
+                let event: serde_json::Value = webhook.json().unwrap();
+                event["type"].as_str().unwrap().to_string()
+            }),
+        );
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn extractor_rejects_unverified_requests() {
+        let body = r#"{"type":"bounce"}"#;
+        let signature = format!(
+            "sha256={}",
+            WebhookVerifier::github("secret").sign(body.as_bytes())
+        );
+
+        let signed = Request::post("/")
+            .header("x-hub-signature-256", signature)
+            .body(Body::from(body))
+            .unwrap();
+        let unsigned = Request::post("/").body(Body::from(body)).unwrap();
+
+        assert_eq!(status_of(signed).await, StatusCode::OK);
+        assert_eq!(status_of(unsigned).await, StatusCode::UNAUTHORIZED);
+    }
+}
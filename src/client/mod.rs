@@ -0,0 +1,816 @@
+//! Typed HTTP client for the REST API, built on the same DTOs as the handlers.
+//! Enabled with the `client` feature:
+//!
+//! ```ignore
+//! let client = ApiClient::new("http://localhost:8000");
+//! let tokens = client.login(&LoginDTO { email, password }).await?;
+//! let users = client
+//!     .with_access_token(tokens.access)
+//!     .search_users(&UserSearchParamsDTO::default())
+//!     .await?;
+//! ```
+
+use reqwest::{
+    Client, Method, RequestBuilder, StatusCode,
+    header::{ACCEPT_LANGUAGE, COOKIE},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    common::dto::{
+        audit_log_dto::{AuditLogPageDTO, AuditLogSearchParamsDTO},
+        broadcast_dto::{BroadcastEventListDTO, BroadcastEventSearchParamsDTO},
+        dead_letter_dto::{
+            DeadLetterListDTO, DeadLetterPurgeDTO, DeadLetterReplayDTO, DeadLetterSearchParamsDTO,
+            DeadLetterSelectionDTO,
+        },
+        deprecation_dto::DeprecationReportDTO,
+        health_dto::{HealthDTO, ReadinessDTO},
+        stats_dto::StatsDTO,
+        task_dto::{TaskHistoryDTO, TaskPollDTO, TaskPollParamsDTO},
+        worker_dto::{PauseTaskTypeDTO, TaskPauseDTO, WorkerScalingDTO},
+    },
+    core::dto::runbook_dto::{RunRunbookRequestDTO, RunRunbookResponseDTO, RunbookListDTO},
+    file::dto::file_dto::{PendingAvatarListDTO, RejectAvatarDTO, UserFileListDTO},
+    notification::{
+        dto::{
+            device_token_dto::{DeviceTokenCreateDTO, DeviceTokenDTO, DeviceTokenListDTO},
+            notification_dto::{NotificationListDTO, NotificationSearchParamsDTO},
+            notification_preference_dto::{
+                NotificationPreferenceDTO, NotificationPreferenceListDTO,
+                NotificationPreferenceUpdateDTO,
+            },
+        },
+        entity::sea_orm_active_enums::NotificationCategory,
+    },
+    user::dto::{
+        auth_dto::{
+            ChangePasswordDTO, ConfirmPhoneDTO, ConfirmResetPasswordDTO, ForgotPasswordDTO,
+            ImpersonationDTO, LoginDTO, ProfileDTO, RefreshTokenDTO, RegisterDTO, ReportSignInDTO,
+            ResendVerificationDTO, ResetLinkDTO, ResetPasswordDTO, SessionListDTO, TokenPairDTO,
+            UpdateProfileDTO, VerifyEmailDTO,
+        },
+        avatar_dto::{UploadAvatarDTO, UploadAvatarResponseDTO},
+        bulk_user_dto::{BulkUserRequestDTO, BulkUserResponseDTO},
+        user_dto::{UserCreateDTO, UserDTO, UserListDTO, UserSearchParamsDTO, UserUpdateDTO},
+        user_email_dto::{
+            AddUserEmailDTO, ConfirmUserEmailDTO, UpdateUserEmailDTO, UserEmailDTO,
+            UserEmailListDTO,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The request couldn't be sent or its response couldn't be decoded
+    Request(reqwest::Error),
+    /// Query parameters couldn't be encoded
+    Query(serde_urlencoded::ser::Error),
+    /// The API answered with an error status; `code` is the stable reason from the error
+    /// catalog, empty when the body wasn't an error of the API
+    Api {
+        status: StatusCode,
+        code: String,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Request(e) => write!(f, "Request failed: {}", e),
+            Self::Query(e) => write!(f, "Invalid query parameters: {}", e),
+            Self::Api {
+                status, message, ..
+            } => write!(f, "<{}> {}", status.as_u16(), message),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Request(err)
+    }
+}
+
+impl ClientError {
+    /// Status of an API error, `None` when no response was received
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Request(e) => e.status(),
+            Self::Query(_) => None,
+            Self::Api { status, .. } => Some(*status),
+        }
+    }
+
+    /// Error code of an API error, e.g. `AUTH_INVALID_CREDENTIALS`
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            Self::Api { code, .. } if !code.is_empty() => Some(code),
+            _ => None,
+        }
+    }
+}
+
+/// Body of error responses, see `ErrorDTO`
+#[derive(Deserialize)]
+struct ErrorBody {
+    #[serde(default)]
+    code: String,
+    message: String,
+}
+
+/// One function per endpoint; requests are sent as the user whose access token is set
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    http: Client,
+    base_url: String,
+    access_token: Option<String>,
+    locale: Option<String>,
+}
+
+impl ApiClient {
+    /// Client of the API served at `base_url`, e.g. `http://localhost:8000`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            access_token: None,
+            locale: None,
+        }
+    }
+
+    /// Send requests through `http`, e.g. the shared client with timeouts and retries
+    pub fn with_http_client(mut self, http: Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    pub fn with_access_token(mut self, access_token: impl Into<String>) -> Self {
+        self.access_token = Some(access_token.into());
+        self
+    }
+
+    /// Language of error messages, sent as `Accept-Language`
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let mut request = self.http.request(method, self.url(path));
+        if let Some(access_token) = &self.access_token {
+            request = request.bearer_auth(access_token);
+        }
+        if let Some(locale) = &self.locale {
+            request = request.header(ACCEPT_LANGUAGE, locale);
+        }
+        request
+    }
+
+    fn request_with_query(
+        &self,
+        method: Method,
+        path: &str,
+        params: &impl Serialize,
+    ) -> Result<RequestBuilder, ClientError> {
+        let query = serde_urlencoded::to_string(params).map_err(ClientError::Query)?;
+        let path = if query.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}?{}", path, query)
+        };
+        Ok(self.request(method, &path))
+    }
+
+    async fn send(request: RequestBuilder) -> Result<reqwest::Response, ClientError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let error = serde_json::from_str::<ErrorBody>(&body).unwrap_or(ErrorBody {
+            code: String::new(),
+            message: body,
+        });
+        Err(ClientError::Api {
+            status,
+            code: error.code,
+            message: error.message,
+        })
+    }
+
+    async fn send_json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, ClientError> {
+        Ok(Self::send(request).await?.json::<T>().await?)
+    }
+
+    async fn send_empty(request: RequestBuilder) -> Result<(), ClientError> {
+        Self::send(request).await.map(|_| ())
+    }
+
+    // Auth
+
+    pub async fn login(&self, dto: &LoginDTO) -> Result<TokenPairDTO, ClientError> {
+        Self::send_json(self.request(Method::POST, "/api/v1/auth/login/").json(dto)).await
+    }
+
+    pub async fn register(&self, dto: &RegisterDTO) -> Result<TokenPairDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::POST, "/api/v1/auth/register/")
+                .json(dto),
+        )
+        .await
+    }
+
+    pub async fn refresh_token(&self, dto: &RefreshTokenDTO) -> Result<TokenPairDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::POST, "/api/v1/auth/refresh-token/")
+                .json(dto),
+        )
+        .await
+    }
+
+    /// Revoke `refresh_token`, which the API reads from the `refresh_token` cookie
+    pub async fn logout(&self, refresh_token: &str) -> Result<(), ClientError> {
+        Self::send_empty(
+            self.request(Method::POST, "/api/v1/auth/logout/")
+                .header(COOKIE, format!("refresh_token={}", refresh_token)),
+        )
+        .await
+    }
+
+    pub async fn forgot_password(&self, dto: &ForgotPasswordDTO) -> Result<(), ClientError> {
+        Self::send_empty(
+            self.request(Method::POST, "/api/v1/auth/forgot-password/")
+                .json(dto),
+        )
+        .await
+    }
+
+    pub async fn reset_password(&self, dto: &ResetPasswordDTO) -> Result<(), ClientError> {
+        Self::send_empty(
+            self.request(Method::POST, "/api/v1/auth/reset-password/")
+                .json(dto),
+        )
+        .await
+    }
+
+    /// Account the password reset link `token` is for, while it is still usable
+    pub async fn verify_reset_link(&self, token: &str) -> Result<ResetLinkDTO, ClientError> {
+        Self::send_json(self.request(
+            Method::GET,
+            &format!("/api/v1/auth/reset-password/confirm/{}/", token),
+        ))
+        .await
+    }
+
+    /// Set a new password through the password reset link `token`
+    pub async fn confirm_reset_password(
+        &self,
+        token: &str,
+        dto: &ConfirmResetPasswordDTO,
+    ) -> Result<(), ClientError> {
+        Self::send_empty(
+            self.request(
+                Method::POST,
+                &format!("/api/v1/auth/reset-password/confirm/{}/", token),
+            )
+            .json(dto),
+        )
+        .await
+    }
+
+    /// Report the sign-in of a new sign-in alert as not the user's
+    pub async fn report_sign_in(&self, dto: &ReportSignInDTO) -> Result<(), ClientError> {
+        Self::send_empty(
+            self.request(Method::POST, "/api/v1/auth/sign-ins/report/")
+                .json(dto),
+        )
+        .await
+    }
+
+    /// Verify the account's email with the token of its verification link
+    pub async fn verify_email(&self, dto: &VerifyEmailDTO) -> Result<(), ClientError> {
+        Self::send_empty(
+            self.request(Method::POST, "/api/v1/auth/verify-email/")
+                .json(dto),
+        )
+        .await
+    }
+
+    pub async fn resend_verification(
+        &self,
+        dto: &ResendVerificationDTO,
+    ) -> Result<(), ClientError> {
+        Self::send_empty(
+            self.request(Method::POST, "/api/v1/auth/resend-verification/")
+                .json(dto),
+        )
+        .await
+    }
+
+    pub async fn change_password(&self, dto: &ChangePasswordDTO) -> Result<(), ClientError> {
+        Self::send_empty(
+            self.request(Method::POST, "/api/v1/auth/change-password/")
+                .json(dto),
+        )
+        .await
+    }
+
+    /// Devices signed in to the account; the one holding `refresh_token` is marked current
+    pub async fn search_sessions(
+        &self,
+        refresh_token: &str,
+    ) -> Result<SessionListDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::GET, "/api/v1/auth/sessions/")
+                .header(COOKIE, format!("refresh_token={}", refresh_token)),
+        )
+        .await
+    }
+
+    /// Sign out every device but the one holding `refresh_token`
+    pub async fn revoke_other_sessions(&self, refresh_token: &str) -> Result<(), ClientError> {
+        Self::send_empty(
+            self.request(Method::DELETE, "/api/v1/auth/sessions/")
+                .header(COOKIE, format!("refresh_token={}", refresh_token)),
+        )
+        .await
+    }
+
+    pub async fn revoke_session(&self, id: i32) -> Result<(), ClientError> {
+        Self::send_empty(self.request(Method::DELETE, &format!("/api/v1/auth/sessions/{}/", id)))
+            .await
+    }
+
+    // Profile
+
+    pub async fn get_profile(&self) -> Result<ProfileDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/user/profile/")).await
+    }
+
+    pub async fn update_profile(&self, dto: &UpdateProfileDTO) -> Result<ProfileDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::PATCH, "/api/v1/user/profile/")
+                .json(dto),
+        )
+        .await
+    }
+
+    pub async fn send_phone_verification(&self) -> Result<(), ClientError> {
+        Self::send_empty(self.request(Method::POST, "/api/v1/user/profile/phone/verify/")).await
+    }
+
+    pub async fn confirm_phone_verification(
+        &self,
+        dto: &ConfirmPhoneDTO,
+    ) -> Result<ProfileDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::POST, "/api/v1/user/profile/phone/confirm/")
+                .json(dto),
+        )
+        .await
+    }
+
+    pub async fn search_user_emails(&self) -> Result<UserEmailListDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/user/profile/emails/")).await
+    }
+
+    /// Add a secondary email address, which is emailed a code to confirm it with
+    pub async fn add_user_email(&self, dto: &AddUserEmailDTO) -> Result<UserEmailDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::POST, "/api/v1/user/profile/emails/")
+                .json(dto),
+        )
+        .await
+    }
+
+    pub async fn update_user_email(
+        &self,
+        id: i32,
+        dto: &UpdateUserEmailDTO,
+    ) -> Result<UserEmailDTO, ClientError> {
+        Self::send_json(
+            self.request(
+                Method::PATCH,
+                &format!("/api/v1/user/profile/emails/{}/", id),
+            )
+            .json(dto),
+        )
+        .await
+    }
+
+    pub async fn delete_user_email(&self, id: i32) -> Result<(), ClientError> {
+        Self::send_empty(self.request(
+            Method::DELETE,
+            &format!("/api/v1/user/profile/emails/{}/", id),
+        ))
+        .await
+    }
+
+    pub async fn send_user_email_verification(&self, id: i32) -> Result<(), ClientError> {
+        Self::send_empty(self.request(
+            Method::POST,
+            &format!("/api/v1/user/profile/emails/{}/verify/", id),
+        ))
+        .await
+    }
+
+    pub async fn confirm_user_email(
+        &self,
+        id: i32,
+        dto: &ConfirmUserEmailDTO,
+    ) -> Result<UserEmailDTO, ClientError> {
+        Self::send_json(
+            self.request(
+                Method::POST,
+                &format!("/api/v1/user/profile/emails/{}/confirm/", id),
+            )
+            .json(dto),
+        )
+        .await
+    }
+
+    /// Swap a verified secondary address with the primary one
+    pub async fn make_primary_user_email(&self, id: i32) -> Result<ProfileDTO, ClientError> {
+        Self::send_json(self.request(
+            Method::POST,
+            &format!("/api/v1/user/profile/emails/{}/primary/", id),
+        ))
+        .await
+    }
+
+    pub async fn upload_avatar(
+        &self,
+        dto: &UploadAvatarDTO,
+    ) -> Result<UploadAvatarResponseDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::POST, "/api/v1/user/upload-avatar/")
+                .json(dto),
+        )
+        .await
+    }
+
+    /// Files of the user, with presigned download links valid for a limited time
+    pub async fn search_files(&self) -> Result<UserFileListDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/users/me/files/")).await
+    }
+
+    /// Delete a file of the user along with its stored objects
+    pub async fn delete_file(&self, id: i32) -> Result<(), ClientError> {
+        Self::send_empty(self.request(Method::DELETE, &format!("/api/v1/users/me/files/{}/", id)))
+            .await
+    }
+
+    // Users
+
+    pub async fn search_users(
+        &self,
+        params: &UserSearchParamsDTO,
+    ) -> Result<UserListDTO, ClientError> {
+        Self::send_json(self.request_with_query(Method::GET, "/api/v1/user/", params)?).await
+    }
+
+    pub async fn create_user(&self, dto: &UserCreateDTO) -> Result<UserDTO, ClientError> {
+        Self::send_json(self.request(Method::POST, "/api/v1/user/").json(dto)).await
+    }
+
+    pub async fn get_user(&self, id: i32) -> Result<UserDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, &format!("/api/v1/user/{}/", id))).await
+    }
+
+    pub async fn update_user(&self, id: i32, dto: &UserUpdateDTO) -> Result<UserDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::PATCH, &format!("/api/v1/user/{}/", id))
+                .json(dto),
+        )
+        .await
+    }
+
+    pub async fn delete_user(&self, id: i32) -> Result<(), ClientError> {
+        Self::send_empty(self.request(Method::DELETE, &format!("/api/v1/user/{}/", id))).await
+    }
+
+    /// Bring back a deleted user; admins only
+    pub async fn restore_user(&self, id: i32) -> Result<UserDTO, ClientError> {
+        Self::send_json(self.request(Method::POST, &format!("/api/v1/users/{}/restore/", id))).await
+    }
+
+    /// Remove a user for good; admins only
+    pub async fn hard_delete_user(&self, id: i32) -> Result<(), ClientError> {
+        Self::send_empty(self.request(Method::DELETE, &format!("/api/v1/admin/users/{}/", id)))
+            .await
+    }
+
+    // Notifications
+
+    pub async fn search_notifications(
+        &self,
+        params: &NotificationSearchParamsDTO,
+    ) -> Result<NotificationListDTO, ClientError> {
+        Self::send_json(self.request_with_query(Method::GET, "/api/v1/notification/", params)?)
+            .await
+    }
+
+    pub async fn read_notification(&self, id: i32) -> Result<(), ClientError> {
+        Self::send_empty(self.request(Method::POST, &format!("/api/v1/notification/{}/read/", id)))
+            .await
+    }
+
+    pub async fn search_device_tokens(&self) -> Result<DeviceTokenListDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/notification/device-token/")).await
+    }
+
+    pub async fn create_device_token(
+        &self,
+        dto: &DeviceTokenCreateDTO,
+    ) -> Result<DeviceTokenDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::POST, "/api/v1/notification/device-token/")
+                .json(dto),
+        )
+        .await
+    }
+
+    pub async fn delete_device_token(&self, id: i32) -> Result<(), ClientError> {
+        Self::send_empty(self.request(
+            Method::DELETE,
+            &format!("/api/v1/notification/device-token/{}/", id),
+        ))
+        .await
+    }
+
+    pub async fn search_notification_preferences(
+        &self,
+    ) -> Result<NotificationPreferenceListDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/notification/preference/")).await
+    }
+
+    pub async fn update_notification_preference(
+        &self,
+        category: NotificationCategory,
+        dto: &NotificationPreferenceUpdateDTO,
+    ) -> Result<NotificationPreferenceDTO, ClientError> {
+        Self::send_json(
+            self.request(
+                Method::PUT,
+                &format!("/api/v1/notification/preference/{}/", category.as_ref()),
+            )
+            .json(dto),
+        )
+        .await
+    }
+
+    pub async fn delete_notification_preference(
+        &self,
+        category: NotificationCategory,
+    ) -> Result<(), ClientError> {
+        Self::send_empty(self.request(
+            Method::DELETE,
+            &format!("/api/v1/notification/preference/{}/", category.as_ref()),
+        ))
+        .await
+    }
+
+    // Runbooks
+
+    pub async fn list_runbooks(&self) -> Result<RunbookListDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/runbook/")).await
+    }
+
+    pub async fn run_runbook(
+        &self,
+        dto: &RunRunbookRequestDTO,
+    ) -> Result<RunRunbookResponseDTO, ClientError> {
+        Self::send_json(self.request(Method::POST, "/api/v1/runbook/run/").json(dto)).await
+    }
+
+    // Admin
+
+    /// Liveness of the API, answered even while it sheds load
+    pub async fn health(&self) -> Result<HealthDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/health/")).await
+    }
+
+    /// Readiness of the API and the circuit breakers of its dependencies; fails while the
+    /// database is unreachable
+    pub async fn ready(&self) -> Result<ReadinessDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/ready/")).await
+    }
+
+    /// Operational stats of the API and its workers; admins only
+    pub async fn get_stats(&self) -> Result<StatsDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/admin/stats/")).await
+    }
+
+    /// Deactivate, delete or change the role of many users; admins only. Large batches
+    /// come back with a `task_id` instead of a report.
+    pub async fn bulk_user_operations(
+        &self,
+        dto: &BulkUserRequestDTO,
+    ) -> Result<BulkUserResponseDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::POST, "/api/v1/admin/users/bulk/")
+                .json(dto),
+        )
+        .await
+    }
+
+    /// Access token acting as `user_id`, which can't be refreshed; admins only
+    pub async fn impersonate_user(&self, user_id: i32) -> Result<ImpersonationDTO, ClientError> {
+        Self::send_json(self.request(
+            Method::POST,
+            &format!("/api/v1/admin/impersonate/{}/", user_id),
+        ))
+        .await
+    }
+
+    /// Revoke the tokens impersonating `user_id`, with the admin's token or the
+    /// impersonation token
+    pub async fn end_impersonation(&self, user_id: i32) -> Result<(), ClientError> {
+        Self::send_empty(self.request(
+            Method::DELETE,
+            &format!("/api/v1/admin/impersonate/{}/", user_id),
+        ))
+        .await
+    }
+
+    /// Deprecated routes and the clients still calling them; admins only
+    pub async fn get_deprecation_report(&self) -> Result<DeprecationReportDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/admin/deprecations/")).await
+    }
+
+    /// Content of a generated report, named as in the link of its email; admins only
+    pub async fn download_report(&self, name: &str) -> Result<Vec<u8>, ClientError> {
+        let response =
+            Self::send(self.request(Method::GET, &format!("/api/v1/admin/reports/{}/", name)))
+                .await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Avatars waiting for review, oldest first; admins only
+    pub async fn search_pending_avatars(&self) -> Result<PendingAvatarListDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/admin/avatars/pending/")).await
+    }
+
+    /// Show a pending avatar on its owner's profile; admins only
+    pub async fn approve_avatar(&self, id: i32) -> Result<(), ClientError> {
+        Self::send_empty(self.request(
+            Method::POST,
+            &format!("/api/v1/admin/avatars/{}/approve/", id),
+        ))
+        .await
+    }
+
+    /// Turn down a pending avatar and notify its owner; admins only
+    pub async fn reject_avatar(&self, id: i32, dto: &RejectAvatarDTO) -> Result<(), ClientError> {
+        Self::send_empty(
+            self.request(
+                Method::POST,
+                &format!("/api/v1/admin/avatars/{}/reject/", id),
+            )
+            .json(dto),
+        )
+        .await
+    }
+
+    /// Audit trail of domain events and record changes, newest first; admins only
+    pub async fn search_audit_logs(
+        &self,
+        params: &AuditLogSearchParamsDTO,
+    ) -> Result<AuditLogPageDTO, ClientError> {
+        Self::send_json(self.request_with_query(
+            Method::GET,
+            "/api/v1/admin/audit-logs/",
+            params,
+        )?)
+        .await
+    }
+
+    /// Archived broadcasts, in the order they were published; admins only
+    pub async fn search_broadcasts(
+        &self,
+        params: &BroadcastEventSearchParamsDTO,
+    ) -> Result<BroadcastEventListDTO, ClientError> {
+        Self::send_json(self.request_with_query(
+            Method::GET,
+            "/api/v1/admin/broadcasts/",
+            params,
+        )?)
+        .await
+    }
+
+    /// Tasks that failed their last attempt, with their attempt history; admins only
+    pub async fn search_dead_letters(
+        &self,
+        params: &DeadLetterSearchParamsDTO,
+    ) -> Result<DeadLetterListDTO, ClientError> {
+        Self::send_json(self.request_with_query(
+            Method::GET,
+            "/api/v1/admin/dead-letters/",
+            params,
+        )?)
+        .await
+    }
+
+    /// Publish the selected dead letters again on their original topic; admins only
+    pub async fn replay_dead_letters(
+        &self,
+        dto: &DeadLetterSelectionDTO,
+    ) -> Result<DeadLetterReplayDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::POST, "/api/v1/admin/dead-letters/replay/")
+                .json(dto),
+        )
+        .await
+    }
+
+    /// Drop the selected dead letters; admins only
+    pub async fn purge_dead_letters(
+        &self,
+        dto: &DeadLetterSelectionDTO,
+    ) -> Result<DeadLetterPurgeDTO, ClientError> {
+        Self::send_json(
+            self.request(Method::POST, "/api/v1/admin/dead-letters/purge/")
+                .json(dto),
+        )
+        .await
+    }
+
+    /// Queue depth and processing rate across the workers, for autoscalers; admins only
+    pub async fn get_worker_scaling(&self) -> Result<WorkerScalingDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/admin/workers/scaling/")).await
+    }
+
+    /// Task types whose tasks workers keep queued; admins only
+    pub async fn list_task_pauses(&self) -> Result<Vec<TaskPauseDTO>, ClientError> {
+        Self::send_json(self.request(Method::GET, "/api/v1/admin/task-types/paused/")).await
+    }
+
+    /// Stop workers from starting tasks of `task_type` until it is resumed; admins only
+    pub async fn pause_task_type(
+        &self,
+        task_type: &str,
+        dto: &PauseTaskTypeDTO,
+    ) -> Result<TaskPauseDTO, ClientError> {
+        Self::send_json(
+            self.request(
+                Method::POST,
+                &format!("/api/v1/admin/task-types/{}/pause/", task_type),
+            )
+            .json(dto),
+        )
+        .await
+    }
+
+    /// Let workers start the queued tasks of `task_type` again; admins only
+    pub async fn resume_task_type(&self, task_type: &str) -> Result<(), ClientError> {
+        Self::send_empty(self.request(
+            Method::POST,
+            &format!("/api/v1/admin/task-types/{}/resume/", task_type),
+        ))
+        .await
+    }
+
+    // Tasks
+
+    /// WebSocket URL streaming the progress of task `task_id`. The upgrade request carries
+    /// the access token as a Bearer header or a `token` query parameter.
+    pub fn task_progress_url(&self, task_id: &str) -> String {
+        let url = self.url(&format!("/ws/v1/task/{}/", task_id));
+        match url.split_once("://") {
+            Some(("https", rest)) => format!("wss://{}", rest),
+            Some((_, rest)) => format!("ws://{}", rest),
+            None => url,
+        }
+    }
+
+    /// Progress messages of task `task_id` after `params.since`, waiting for new ones like
+    /// the WebSocket would. Make sure the HTTP client's timeout outlasts `params.timeout`.
+    pub async fn poll_task_progress(
+        &self,
+        task_id: &str,
+        params: &TaskPollParamsDTO,
+    ) -> Result<TaskPollDTO, ClientError> {
+        Self::send_json(self.request_with_query(
+            Method::GET,
+            &format!("/api/v1/tasks/{}/poll/", task_id),
+            params,
+        )?)
+        .await
+    }
+
+    /// Lifecycle stages workers recorded for task `task_id`; admins only
+    pub async fn get_task_history(&self, task_id: &str) -> Result<TaskHistoryDTO, ClientError> {
+        Self::send_json(self.request(Method::GET, &format!("/api/v1/tasks/{}/history/", task_id)))
+            .await
+    }
+}
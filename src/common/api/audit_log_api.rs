@@ -0,0 +1,52 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{
+    Extension,
+    extract::{Query, State},
+};
+
+use crate::{
+    common::{
+        dto::audit_log_dto::{AuditLogPageDTO, AuditLogSearchParamsDTO},
+        use_case::audit_log::{export_audit_log_use_case, search_audit_log_use_case},
+    },
+    config::app::AppState,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        export::CsvExport,
+    },
+};
+
+/// Audit trail of domain events and record changes, newest first, paged with `cursor`
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/audit-logs/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(AuditLogSearchParamsDTO),
+    responses((status = StatusCode::OK, body = AuditLogPageDTO)),
+)]
+pub async fn search_audit_log(
+    Extension(context): Extension<Context>,
+    Query(dto): Query<AuditLogSearchParamsDTO>,
+) -> Result<ResponseDTO<AuditLogPageDTO>, ErrorDTO> {
+    search_audit_log_use_case::execute(&context, dto).await
+}
+
+/// Every audit log entry matching the filters, streamed as CSV
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/audit-logs/export/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(AuditLogSearchParamsDTO),
+    responses((status = StatusCode::OK, content_type = "text/csv", body = String)),
+)]
+pub async fn export_audit_log(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+    Query(dto): Query<AuditLogSearchParamsDTO>,
+) -> Result<CsvExport, ErrorDTO> {
+    export_audit_log_use_case::execute(&context, app_state.db.clone(), dto).await
+}
@@ -0,0 +1,30 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{Extension, extract::Query};
+
+use crate::{
+    common::{
+        dto::broadcast_dto::{BroadcastEventListDTO, BroadcastEventSearchParamsDTO},
+        use_case::broadcast::search_broadcast_event_use_case,
+    },
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+};
+
+/// Archived broadcasts in the order they were published, to see what a task or user was sent
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/broadcasts/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(BroadcastEventSearchParamsDTO),
+    responses((status = StatusCode::OK, body = BroadcastEventListDTO)),
+)]
+pub async fn search_broadcast_event(
+    Extension(context): Extension<Context>,
+    Query(dto): Query<BroadcastEventSearchParamsDTO>,
+) -> Result<ResponseDTO<BroadcastEventListDTO>, ErrorDTO> {
+    search_broadcast_event_use_case::execute(&context, dto).await
+}
@@ -0,0 +1,67 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{Extension, Json, extract::Query};
+
+use crate::{
+    common::{
+        dto::dead_letter_dto::{
+            DeadLetterListDTO, DeadLetterPurgeDTO, DeadLetterReplayDTO, DeadLetterSearchParamsDTO,
+            DeadLetterSelectionDTO,
+        },
+        use_case::dead_letter::{
+            purge_dead_letter_use_case, replay_dead_letter_use_case, search_dead_letter_use_case,
+        },
+    },
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+};
+
+/// Tasks that failed their last attempt, newest first, with their error and attempt history
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/dead-letters/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(DeadLetterSearchParamsDTO),
+    responses((status = StatusCode::OK, body = DeadLetterListDTO)),
+)]
+pub async fn search_dead_letter(
+    Extension(context): Extension<Context>,
+    Query(dto): Query<DeadLetterSearchParamsDTO>,
+) -> Result<ResponseDTO<DeadLetterListDTO>, ErrorDTO> {
+    search_dead_letter_use_case::execute(&context, dto).await
+}
+
+/// Publish the selected tasks again on their original topic, with a fresh retry budget
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/dead-letters/replay/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    request_body(content = DeadLetterSelectionDTO),
+    responses((status = StatusCode::OK, body = DeadLetterReplayDTO)),
+)]
+pub async fn replay_dead_letter(
+    Extension(context): Extension<Context>,
+    Json(dto): Json<DeadLetterSelectionDTO>,
+) -> Result<ResponseDTO<DeadLetterReplayDTO>, ErrorDTO> {
+    replay_dead_letter_use_case::execute(&context, dto).await
+}
+
+/// Drop the selected tasks from the dead-letter queue
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/dead-letters/purge/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    request_body(content = DeadLetterSelectionDTO),
+    responses((status = StatusCode::OK, body = DeadLetterPurgeDTO)),
+)]
+pub async fn purge_dead_letter(
+    Extension(context): Extension<Context>,
+    Json(dto): Json<DeadLetterSelectionDTO>,
+) -> Result<ResponseDTO<DeadLetterPurgeDTO>, ErrorDTO> {
+    purge_dead_letter_use_case::execute(&context, dto).await
+}
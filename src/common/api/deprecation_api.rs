@@ -0,0 +1,31 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{Extension, extract::State};
+
+use crate::{
+    common::{
+        dto::deprecation_dto::DeprecationReportDTO,
+        use_case::deprecation::get_deprecation_report_use_case,
+    },
+    config::app::AppState,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+};
+
+/// Deprecated routes with their sunset and the clients still calling them, counted by this
+/// API process, to tell when a route can be removed
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/deprecations/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = DeprecationReportDTO)),
+)]
+pub async fn get_deprecation_report(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<DeprecationReportDTO>, ErrorDTO> {
+    get_deprecation_report_use_case::execute(&context, &app_state.deprecations).await
+}
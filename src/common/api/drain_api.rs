@@ -0,0 +1,29 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{Extension, extract::State};
+
+use crate::{
+    common::{dto::drain_dto::DrainStatusDTO, use_case::drain::drain_server_use_case},
+    config::app::AppState,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+};
+
+/// Drain this instance before it is stopped, e.g. from a pre-stop hook: it reports itself
+/// unready, refuses new WebSockets and tells open ones to reconnect elsewhere, delivers the
+/// buffered broadcasts, closes the WebSockets and then stops HTTP
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/drain/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::ACCEPTED, body = DrainStatusDTO)),
+)]
+pub async fn drain_server(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<DrainStatusDTO>, ErrorDTO> {
+    drain_server_use_case::execute(&context, &app_state.drain)
+}
@@ -0,0 +1,63 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    common::{
+        dto::health_dto::{HealthDTO, ReadinessDTO},
+        use_case::health::{get_metrics_use_case, get_readiness_use_case},
+    },
+    config::app::AppState,
+    core::dto::response_dto::ResponseDTO,
+};
+
+/// Liveness probe for load balancers and orchestrators; never shed under load
+#[utoipa::path(
+    get,
+    path = "/health/",
+    tags = ["Health"],
+    responses((status = StatusCode::OK, body = HealthDTO)),
+)]
+pub async fn health() -> ResponseDTO<HealthDTO> {
+    ResponseDTO::new(
+        StatusCode::OK,
+        HealthDTO {
+            status: "ok".to_string(),
+        },
+    )
+}
+
+/// Readiness probe: fails while the database is unreachable or the server drains.
+/// Dependencies whose circuit breaker tripped are listed and mark the instance `degraded`.
+#[utoipa::path(
+    get,
+    path = "/ready/",
+    tags = ["Health"],
+    responses(
+        (status = StatusCode::OK, body = ReadinessDTO),
+        (status = StatusCode::SERVICE_UNAVAILABLE, body = ReadinessDTO),
+    ),
+)]
+pub async fn ready(State(app_state): State<AppState>) -> ResponseDTO<ReadinessDTO> {
+    get_readiness_use_case::execute(&app_state.setting, &app_state.db, &app_state.drain).await
+}
+
+/// Health scores and circuit breaker states of outbound dependencies, for Prometheus
+#[utoipa::path(
+    get,
+    path = "/metrics/",
+    tags = ["Health"],
+    responses((status = StatusCode::OK, content_type = "text/plain", body = String)),
+)]
+pub async fn metrics(State(app_state): State<AppState>) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        get_metrics_use_case::execute(&app_state.setting),
+    )
+        .into_response()
+}
@@ -8,6 +8,7 @@ pub fn service(app_state: &AppState) -> StreamableHttpService<McpServer, LocalSe
     let mut session_manager = LocalSessionManager::default();
     session_manager.session_config.sse_retry = None;
     let api_base_url = api_base_url(app_state);
+    let http_client = app_state.http_client.clone();
     let mut config = StreamableHttpServerConfig::default()
         .with_stateful_mode(true)
         .with_sse_retry(None)
@@ -21,7 +22,7 @@ pub fn service(app_state: &AppState) -> StreamableHttpService<McpServer, LocalSe
     }
 
     StreamableHttpService::new(
-        move || Ok(McpServer::new(api_base_url.clone())),
+        move || Ok(McpServer::new(http_client.clone(), api_base_url.clone())),
         session_manager.into(),
         config,
     )
@@ -1,3 +1,12 @@
+pub mod audit_log_api;
+pub mod broadcast_api;
+pub mod dead_letter_api;
+pub mod deprecation_api;
+pub mod drain_api;
+pub mod health_api;
 pub mod mcp_api;
 pub mod runbook_api;
+pub mod stats_api;
+pub mod task_api;
 pub mod task_ws;
+pub mod worker_api;
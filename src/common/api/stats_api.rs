@@ -0,0 +1,27 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{Extension, extract::State};
+
+use crate::{
+    common::{dto::stats_dto::StatsDTO, use_case::stats::get_stats_use_case},
+    config::app::AppState,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+};
+
+/// Snapshot of this API process and the workers reporting to it, for dashboards
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/stats/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = StatsDTO)),
+)]
+pub async fn get_stats(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<StatsDTO>, ErrorDTO> {
+    get_stats_use_case::execute(&context, &app_state.setting, &app_state.db).await
+}
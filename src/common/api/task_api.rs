@@ -0,0 +1,52 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{
+    Extension,
+    extract::{Path, Query, State},
+};
+
+use crate::{
+    common::{
+        dto::task_dto::{TaskHistoryDTO, TaskPollDTO, TaskPollParamsDTO},
+        use_case::task::{get_task_history_use_case, poll_task_progress_use_case},
+    },
+    config::app::AppState,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+};
+
+/// Long-polling fallback of `/ws/v1/task/{task_id}/` for clients without WebSocket support
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{task_id}/poll/",
+    tags = ["Task"],
+    security(("bearer_auth" = [])),
+    params(("task_id" = String, Path), TaskPollParamsDTO),
+    responses((status = StatusCode::OK, body = TaskPollDTO)),
+)]
+pub async fn poll_task_progress(
+    State(app_state): State<AppState>,
+    Path(task_id): Path<String>,
+    Query(dto): Query<TaskPollParamsDTO>,
+) -> Result<ResponseDTO<TaskPollDTO>, ErrorDTO> {
+    poll_task_progress_use_case::execute(&app_state.setting, task_id, dto).await
+}
+
+/// Lifecycle stages recorded by workers for a task, by task event id or payload `task_id`,
+/// for debugging stuck jobs
+#[utoipa::path(
+    get,
+    path = "/api/v1/tasks/{task_id}/history/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("task_id" = String, Path)),
+    responses((status = StatusCode::OK, body = TaskHistoryDTO)),
+)]
+pub async fn get_task_history(
+    Extension(context): Extension<Context>,
+    Path(task_id): Path<String>,
+) -> Result<ResponseDTO<TaskHistoryDTO>, ErrorDTO> {
+    get_task_history_use_case::execute(&context, &task_id).await
+}
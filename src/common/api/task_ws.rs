@@ -1,17 +1,48 @@
 use crate::common::use_case::task::get_task_progress_use_case;
+use crate::config::app::AppState;
+use crate::config::drain::draining_error;
+use crate::core::layer::lang_layer::RequestLocale;
+use crate::core::module::MessageSchema;
+use crate::pkg::broadcast::protocol::WsProtocol;
 use crate::user::entity::user;
 #[allow(unused_imports)]
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::{
     Extension,
-    extract::{Path, WebSocketUpgrade},
+    extract::{Path, State, WebSocketUpgrade},
 };
+use serde_json::json;
 
 pub async fn get_task_progress(
     ws: WebSocketUpgrade,
     Path(task_id): Path<String>,
+    State(app_state): State<AppState>,
     Extension(current_user): Extension<user::Model>,
+    Extension(locale): Extension<RequestLocale>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| get_task_progress_use_case::execute(socket, task_id, current_user))
+    // A draining instance takes no new WebSockets, the client retries on another one
+    if app_state.drain.is_draining() {
+        return draining_error(locale.as_str()).into_response();
+    }
+
+    let drain = app_state.drain.subscribe();
+    let (ws, protocol) = WsProtocol::negotiate(ws);
+    ws.on_upgrade(move |socket| {
+        get_task_progress_use_case::execute(socket, task_id, current_user, protocol, drain)
+    })
+    .into_response()
+}
+
+/// Commands clients may send on the task progress WebSocket
+pub fn command_schemas() -> Vec<MessageSchema> {
+    vec![MessageSchema::inbound(
+        "ping",
+        json!({
+            "type": "object",
+            "properties": {"action": {"const": "ping"}},
+            "required": ["action"],
+            "additionalProperties": false
+        }),
+    )]
 }
@@ -0,0 +1,84 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+};
+
+use crate::{
+    common::{
+        dto::worker_dto::{PauseTaskTypeDTO, TaskPauseDTO, WorkerScalingDTO},
+        use_case::worker::{
+            get_worker_scaling_use_case, list_task_pauses_use_case, pause_task_type_use_case,
+            resume_task_type_use_case,
+        },
+    },
+    config::app::AppState,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+};
+
+/// Queue depth and processing rate across the workers, for autoscalers
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/workers/scaling/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = WorkerScalingDTO)),
+)]
+pub async fn get_worker_scaling(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<WorkerScalingDTO>, ErrorDTO> {
+    get_worker_scaling_use_case::execute(&context, &app_state.setting).await
+}
+
+/// Task types whose tasks workers keep queued
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/task-types/paused/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = Vec<TaskPauseDTO>)),
+)]
+pub async fn list_task_pauses(
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<Vec<TaskPauseDTO>>, ErrorDTO> {
+    list_task_pauses_use_case::execute(&context).await
+}
+
+/// Stop workers from starting tasks of `task_type`; they stay queued until it is resumed
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/task-types/{task_type}/pause/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("task_type" = String, Path, description = "`type` tag of the tasks, e.g. `SendEmail`")),
+    request_body(content = PauseTaskTypeDTO),
+    responses((status = StatusCode::OK, body = TaskPauseDTO)),
+)]
+pub async fn pause_task_type(
+    Extension(context): Extension<Context>,
+    Path(task_type): Path<String>,
+    Json(dto): Json<PauseTaskTypeDTO>,
+) -> Result<ResponseDTO<TaskPauseDTO>, ErrorDTO> {
+    pause_task_type_use_case::execute(&context, &task_type, dto).await
+}
+
+/// Let workers start the queued tasks of `task_type` again
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/task-types/{task_type}/resume/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("task_type" = String, Path, description = "`type` tag of the tasks, e.g. `SendEmail`")),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn resume_task_type(
+    Extension(context): Extension<Context>,
+    Path(task_type): Path<String>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    resume_task_type_use_case::execute(&context, &task_type).await
+}
@@ -0,0 +1,102 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    common::entity::audit_log,
+    core::{dto::datetime, export::CsvRecord, validation::Validate},
+};
+
+/// Domain event or record change in the audit trail
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuditLogDTO {
+    pub id: i32,
+    /// `user:<id>`, `user:<admin id> as user:<id>` while impersonating, `service:<name>` or
+    /// `anonymous`
+    pub actor: String,
+    pub action: String,
+    pub resource_type: String,
+    pub resource_id: Option<String>,
+    pub payload: Value,
+    #[serde(with = "crate::core::dto::datetime")]
+    pub created_at: NaiveDateTime,
+}
+
+impl From<audit_log::Model> for AuditLogDTO {
+    fn from(model: audit_log::Model) -> Self {
+        Self {
+            id: model.id,
+            actor: model.actor,
+            action: model.action,
+            resource_type: model.resource_type,
+            resource_id: model.resource_id,
+            payload: model.payload,
+            created_at: model.created_at,
+        }
+    }
+}
+
+impl CsvRecord for AuditLogDTO {
+    fn header() -> &'static [&'static str] {
+        &[
+            "id",
+            "created_at",
+            "actor",
+            "action",
+            "resource_type",
+            "resource_id",
+            "payload",
+        ]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            datetime::format(&self.created_at),
+            self.actor.clone(),
+            self.action.clone(),
+            self.resource_type.clone(),
+            self.resource_id.clone().unwrap_or_default(),
+            self.payload.to_string(),
+        ]
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuditLogPageDTO {
+    /// Newest first
+    pub items: Vec<AuditLogDTO>,
+    /// Pass as `cursor` for the next page, `None` on the last one
+    pub next_cursor: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct AuditLogSearchParamsDTO {
+    /// `user:<id>`, `user:<admin id> as user:<id>` while impersonating, `service:<name>` or
+    /// `anonymous`
+    #[validate(length(max = 128))]
+    pub actor: Option<String>,
+    /// Only entries caused by this user, including those of impersonations by or of them
+    pub user_id: Option<i32>,
+    /// Event name, e.g. `role_assigned`, or `create`, `update` or `delete` for record changes
+    #[validate(length(max = 64))]
+    pub action: Option<String>,
+    /// Entity the entry is about, e.g. `user`
+    #[validate(length(max = 32))]
+    pub resource_type: Option<String>,
+    #[validate(length(max = 64))]
+    pub resource_id: Option<String>,
+    /// Only entries recorded at or after this time
+    #[serde(default, with = "crate::core::dto::datetime::option")]
+    pub created_from: Option<NaiveDateTime>,
+    /// Only entries recorded before this time
+    #[serde(default, with = "crate::core::dto::datetime::option")]
+    pub created_to: Option<NaiveDateTime>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<i32>,
+    #[param(default = 50)]
+    #[validate(range(min = 1, max = 500))]
+    pub limit: Option<u64>,
+}
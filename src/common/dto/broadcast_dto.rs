@@ -0,0 +1,61 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{common::entity::broadcast_event, core::validation::Validate};
+
+/// Broadcast published to WebSocket clients, as archived
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BroadcastEventDTO {
+    pub id: i32,
+    pub event_type: String,
+    pub task_id: Option<String>,
+    pub user_id: Option<i32>,
+    pub data: Value,
+    #[serde(with = "crate::core::dto::datetime")]
+    pub published_at: NaiveDateTime,
+}
+
+impl From<broadcast_event::Model> for BroadcastEventDTO {
+    fn from(model: broadcast_event::Model) -> Self {
+        Self {
+            id: model.id,
+            event_type: model.event_type,
+            task_id: model.task_id,
+            user_id: model.user_id,
+            data: model.data,
+            published_at: model.published_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BroadcastEventListDTO {
+    pub items: Vec<BroadcastEventDTO>,
+    pub count: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct BroadcastEventSearchParamsDTO {
+    /// Broadcasts reporting on this task
+    #[validate(length(max = 64))]
+    pub task_id: Option<String>,
+    /// Broadcasts addressed to this user outside a task, e.g. notifications
+    pub user_id: Option<i32>,
+    #[validate(length(max = 64))]
+    pub event_type: Option<String>,
+    /// Only broadcasts published at or after this time
+    #[serde(default, with = "crate::core::dto::datetime::option")]
+    pub published_from: Option<NaiveDateTime>,
+    /// Only broadcasts published before this time
+    #[serde(default, with = "crate::core::dto::datetime::option")]
+    pub published_to: Option<NaiveDateTime>,
+    #[param(default = 1)]
+    #[validate(range(min = 1))]
+    pub page: Option<u64>,
+    #[param(default = 10)]
+    #[validate(range(min = 1))]
+    pub page_size: Option<u64>,
+}
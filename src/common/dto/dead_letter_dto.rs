@@ -0,0 +1,107 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    common::{dto::task_dto::TaskEventLogDTO, entity::dead_letter},
+    core::validation::Validate,
+};
+
+/// Task that failed its last attempt, with the lifecycle stages recorded for it
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterDTO {
+    pub id: i32,
+    pub task_id: String,
+    pub task_type: Option<String>,
+    /// Destination the task is published to when replayed
+    pub topic: String,
+    /// Task payload, tagged with its `type`
+    pub task: Value,
+    pub attempts: i32,
+    /// Error of the last attempt
+    pub error: String,
+    pub worker_id: Option<String>,
+    #[serde(with = "crate::core::dto::datetime")]
+    pub failed_at: NaiveDateTime,
+    /// Oldest first
+    pub history: Vec<TaskEventLogDTO>,
+}
+
+impl From<dead_letter::Model> for DeadLetterDTO {
+    fn from(model: dead_letter::Model) -> Self {
+        Self {
+            id: model.id,
+            task_id: model.task_id,
+            task_type: model.task_type,
+            topic: model.topic,
+            task: model.event.get("task").cloned().unwrap_or_default(),
+            attempts: model.attempts,
+            error: model.error,
+            worker_id: model.worker_id,
+            failed_at: model.failed_at,
+            history: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterListDTO {
+    pub items: Vec<DeadLetterDTO>,
+    pub count: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct DeadLetterSearchParamsDTO {
+    /// `type` tag of the task, e.g. `SendEmail`
+    #[validate(length(max = 64))]
+    pub task_type: Option<String>,
+    /// Only tasks that failed at or after this time
+    #[serde(default, with = "crate::core::dto::datetime::option")]
+    pub failed_from: Option<NaiveDateTime>,
+    /// Only tasks that failed before this time
+    #[serde(default, with = "crate::core::dto::datetime::option")]
+    pub failed_to: Option<NaiveDateTime>,
+    #[param(default = 1)]
+    #[validate(range(min = 1))]
+    pub page: Option<u64>,
+    #[param(default = 10)]
+    #[validate(range(min = 1))]
+    pub page_size: Option<u64>,
+}
+
+/// Dead letters to replay or purge: the listed `ids`, or every one matching the filters.
+/// At least one criterion is required; all of those set must match.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema, Validate)]
+pub struct DeadLetterSelectionDTO {
+    pub ids: Option<Vec<i32>>,
+    #[validate(length(max = 64))]
+    pub task_type: Option<String>,
+    #[serde(default, with = "crate::core::dto::datetime::option")]
+    pub failed_from: Option<NaiveDateTime>,
+    #[serde(default, with = "crate::core::dto::datetime::option")]
+    pub failed_to: Option<NaiveDateTime>,
+}
+
+impl DeadLetterSelectionDTO {
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_none()
+            && self.task_type.is_none()
+            && self.failed_from.is_none()
+            && self.failed_to.is_none()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterReplayDTO {
+    /// Tasks published again and removed from the dead-letter queue
+    pub replayed: usize,
+    /// Dead letters kept because publishing them failed
+    pub failed_ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterPurgeDTO {
+    pub purged: u64,
+}
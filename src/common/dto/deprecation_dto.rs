@@ -0,0 +1,67 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::core::layer::deprecation_layer::DeprecatedRouteUsage;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeprecatedRouteClientDTO {
+    pub client_ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub calls: u64,
+    #[serde(with = "crate::core::dto::datetime")]
+    pub last_used_at: NaiveDateTime,
+}
+
+/// A deprecated route and the clients that called it since this API process started
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeprecatedRouteDTO {
+    /// `null` when every method is deprecated
+    pub method: Option<String>,
+    /// Path pattern, `*` matching one path segment
+    pub path: String,
+    #[serde(with = "crate::core::dto::datetime")]
+    pub deprecated_at: NaiveDateTime,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub sunset: Option<NaiveDateTime>,
+    pub replacement: Option<String>,
+    /// Past its sunset and answered with `410 Gone`
+    pub retired: bool,
+    pub calls: u64,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub last_used_at: Option<NaiveDateTime>,
+    /// Most calls first
+    pub clients: Vec<DeprecatedRouteClientDTO>,
+}
+
+impl From<DeprecatedRouteUsage> for DeprecatedRouteDTO {
+    fn from(usage: DeprecatedRouteUsage) -> Self {
+        let deprecation = usage.deprecation;
+        Self {
+            method: deprecation.method.as_ref().map(ToString::to_string),
+            retired: deprecation.is_retired(Utc::now()),
+            path: deprecation.path,
+            deprecated_at: deprecation.deprecated_at.naive_utc(),
+            sunset: deprecation.sunset.map(|sunset| sunset.naive_utc()),
+            replacement: deprecation.replacement,
+            calls: usage.calls,
+            last_used_at: usage.last_used_at,
+            clients: usage
+                .clients
+                .into_iter()
+                .map(|(client, calls)| DeprecatedRouteClientDTO {
+                    client_ip: client.client_ip,
+                    user_agent: client.user_agent,
+                    calls: calls.calls,
+                    last_used_at: calls.last_used_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeprecationReportDTO {
+    pub items: Vec<DeprecatedRouteDTO>,
+    pub count: usize,
+}
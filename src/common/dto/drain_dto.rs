@@ -0,0 +1,10 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::config::drain::DrainPhase;
+
+/// Where the drain of this instance stands
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DrainStatusDTO {
+    pub phase: DrainPhase,
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::pkg::circuit_breaker::BreakerSnapshot;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HealthDTO {
+    pub status: String,
+}
+
+/// Circuit breaker of an outbound dependency, as seen by one process
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DependencyHealthDTO {
+    /// `api`, or the id of the worker that reported the breaker
+    pub process: String,
+    /// Dependency guarded by the breaker, e.g. `smtp` or `broker`
+    pub name: String,
+    /// `closed`, `half_open` or `open`
+    pub state: String,
+    /// Share of the last calls that succeeded, from `0.0` to `1.0`
+    pub health_score: f64,
+    pub consecutive_failures: u32,
+}
+
+impl DependencyHealthDTO {
+    pub fn new(process: &str, snapshot: BreakerSnapshot) -> Self {
+        Self {
+            process: process.to_string(),
+            name: snapshot.name,
+            state: snapshot.state.as_str().to_string(),
+            health_score: snapshot.health_score,
+            consecutive_failures: snapshot.consecutive_failures,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReadinessDTO {
+    /// `ready`, `degraded` while a dependency's circuit breaker is not closed,
+    /// `unavailable` while the database can't be reached, or `draining` once the server
+    /// started shutting down
+    pub status: String,
+    pub database: bool,
+    pub dependencies: Vec<DependencyHealthDTO>,
+}
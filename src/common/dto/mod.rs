@@ -1 +1,10 @@
+pub mod audit_log_dto;
+pub mod broadcast_dto;
+pub mod dead_letter_dto;
+pub mod deprecation_dto;
+pub mod drain_dto;
+pub mod health_dto;
 pub mod mcp_dto;
+pub mod stats_dto;
+pub mod task_dto;
+pub mod worker_dto;
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    core::{db::connection::PoolStats, layer::request_stats_layer::RequestStats},
+    pkg::{
+        cache::{QuotaLimits, QuotaUsage},
+        messaging::stats::WorkerStats,
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RequestStatsDTO {
+    pub total: u64,
+    /// Responses with a 4xx status
+    pub client_errors: u64,
+    /// Responses with a 5xx status
+    pub server_errors: u64,
+    /// Responses with a 503 from load shedding, also counted in `server_errors`
+    pub shed: u64,
+    /// Average over the last minute
+    pub per_second: f64,
+}
+
+impl From<RequestStats> for RequestStatsDTO {
+    fn from(stats: RequestStats) -> Self {
+        Self {
+            total: stats.total,
+            client_errors: stats.client_errors,
+            server_errors: stats.server_errors,
+            shed: stats.shed,
+            per_second: stats.per_second,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DbPoolStatsDTO {
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+    pub max: u32,
+}
+
+impl From<PoolStats> for DbPoolStatsDTO {
+    fn from(stats: PoolStats) -> Self {
+        Self {
+            size: stats.size,
+            idle: stats.idle,
+            in_use: stats.size.saturating_sub(stats.idle),
+            max: stats.max,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QueueStatsDTO {
+    pub worker_id: String,
+    /// Tasks received but waiting for a free worker slot
+    pub depth: u64,
+    /// Queued tasks held back because their task type is paused
+    pub paused: u64,
+    /// Tasks that finished over the last minute
+    pub processed_last_minute: u64,
+    /// Milliseconds between publishing and starting the most recent task
+    pub lag_ms: u64,
+    /// Times the worker's broker clients failed over to another endpoint
+    pub broker_failovers: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct TaskStatsDTO {
+    pub running: u64,
+    pub succeeded: u64,
+    pub failed: u64,
+    pub retried: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskQuotaUsageDTO {
+    pub user_id: i32,
+    /// Tasks enqueued or running
+    pub running: u64,
+    /// Tasks started since midnight UTC
+    pub enqueued_today: u64,
+}
+
+impl From<QuotaUsage> for TaskQuotaUsageDTO {
+    fn from(usage: QuotaUsage) -> Self {
+        Self {
+            user_id: usage.user_id,
+            running: usage.running,
+            enqueued_today: usage.enqueued_today,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskQuotaStatsDTO {
+    /// `0` when unlimited
+    pub max_running: u64,
+    /// `0` when unlimited
+    pub max_daily: u64,
+    /// Every user who started a task today
+    pub users: Vec<TaskQuotaUsageDTO>,
+}
+
+impl TaskQuotaStatsDTO {
+    pub fn new(limits: QuotaLimits, usage: Vec<QuotaUsage>) -> Self {
+        Self {
+            max_running: limits.max_running,
+            max_daily: limits.max_daily,
+            users: usage.into_iter().map(TaskQuotaUsageDTO::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StatsDTO {
+    pub uptime_seconds: u64,
+    pub requests: RequestStatsDTO,
+    pub websocket_connections: usize,
+    /// `None` when the database is not behind a connection pool
+    pub db_pool: Option<DbPoolStatsDTO>,
+    /// One entry per worker that reported recently
+    pub queues: Vec<QueueStatsDTO>,
+    /// Totals across those workers, since each started
+    pub tasks: TaskStatsDTO,
+    /// Times the broker clients of this process failed over to another endpoint
+    pub broker_failovers: u64,
+    /// `None` when task quotas are disabled or their usage could not be read
+    pub task_quota: Option<TaskQuotaStatsDTO>,
+}
+
+impl StatsDTO {
+    pub fn new(
+        requests: RequestStats,
+        websocket_connections: usize,
+        db_pool: Option<PoolStats>,
+        workers: Vec<WorkerStats>,
+        broker_failovers: u64,
+        task_quota: Option<TaskQuotaStatsDTO>,
+    ) -> Self {
+        let tasks = workers
+            .iter()
+            .fold(TaskStatsDTO::default(), |tasks, worker| TaskStatsDTO {
+                running: tasks.running + worker.running,
+                succeeded: tasks.succeeded + worker.succeeded,
+                failed: tasks.failed + worker.failed,
+                retried: tasks.retried + worker.retried,
+            });
+        let queues = workers
+            .into_iter()
+            .map(|worker| QueueStatsDTO {
+                worker_id: worker.worker_id,
+                depth: worker.queued,
+                paused: worker.paused,
+                processed_last_minute: worker.processed_last_minute,
+                lag_ms: worker.lag_ms,
+                broker_failovers: worker.broker_failovers,
+            })
+            .collect();
+
+        Self {
+            uptime_seconds: requests.uptime_seconds,
+            requests: requests.into(),
+            websocket_connections,
+            db_pool: db_pool.map(DbPoolStatsDTO::from),
+            queues,
+            tasks,
+            broker_failovers,
+            task_quota,
+        }
+    }
+}
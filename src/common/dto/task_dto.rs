@@ -0,0 +1,77 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{common::entity::task_event_log, pkg::broadcast::replay::SequencedMessage};
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskPollParamsDTO {
+    /// Sequence number of the last message already received
+    #[param(default = 0)]
+    pub since: Option<u64>,
+    /// Seconds to wait for new messages, capped by the server
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskMessageDTO {
+    pub seq: u64,
+    pub event_type: String,
+    pub data: serde_json::Value,
+}
+
+impl From<SequencedMessage> for TaskMessageDTO {
+    fn from(message: SequencedMessage) -> Self {
+        Self {
+            seq: message.seq,
+            event_type: message.message.event_type,
+            data: message.message.data,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskPollDTO {
+    pub messages: Vec<TaskMessageDTO>,
+    /// Sequence number to poll from next
+    pub last_seq: u64,
+}
+
+/// One lifecycle stage of a task as recorded by a worker
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskEventLogDTO {
+    pub task_id: String,
+    /// `task_id` of the task payload, used by the progress WebSocket and polling endpoints
+    pub reference: Option<String>,
+    pub task_type: Option<String>,
+    /// `enqueued`, `started`, `retrying`, `completed` or `failed`
+    pub status: String,
+    pub attempt: i32,
+    pub worker_id: Option<String>,
+    pub error: Option<String>,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl From<task_event_log::Model> for TaskEventLogDTO {
+    fn from(entry: task_event_log::Model) -> Self {
+        Self {
+            task_id: entry.task_id,
+            reference: entry.reference,
+            task_type: entry.task_type,
+            status: entry.status,
+            attempt: entry.attempt,
+            worker_id: entry.worker_id,
+            error: entry.error,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskHistoryDTO {
+    /// Oldest first
+    pub items: Vec<TaskEventLogDTO>,
+    pub count: usize,
+}
@@ -0,0 +1,73 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    common::entity::task_pause, core::validation::Validate, pkg::messaging::stats::WorkerStats,
+};
+
+/// Task type whose tasks workers keep queued until it is resumed
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskPauseDTO {
+    /// `type` tag of the paused tasks, e.g. `SendEmail`
+    pub task_type: String,
+    pub reason: Option<String>,
+    pub paused_by_user_id: Option<i32>,
+    #[serde(with = "crate::core::dto::datetime")]
+    pub paused_at: NaiveDateTime,
+}
+
+impl From<task_pause::Model> for TaskPauseDTO {
+    fn from(model: task_pause::Model) -> Self {
+        Self {
+            task_type: model.task_type,
+            reason: model.reason,
+            paused_by_user_id: model.paused_by_user_id,
+            paused_at: model.paused_at,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema, Validate)]
+pub struct PauseTaskTypeDTO {
+    /// Why the task type is paused, shown to other admins
+    #[validate(length(max = 255))]
+    pub reason: Option<String>,
+}
+
+/// Task queue signals of the workers that reported recently, for autoscalers
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkerScalingDTO {
+    pub workers: usize,
+    /// Tasks received but not started yet, paused ones included
+    pub queue_depth: u64,
+    /// Queued tasks held back because their task type is paused
+    pub paused: u64,
+    pub running: u64,
+    /// Tasks that finished, successfully or not, over the last minute
+    pub processed_last_minute: u64,
+    /// Highest milliseconds between publishing and starting a worker's most recent task
+    pub max_lag_ms: u64,
+    pub paused_task_types: Vec<String>,
+}
+
+impl WorkerScalingDTO {
+    pub fn new(workers: &[WorkerStats], paused_task_types: Vec<String>) -> Self {
+        Self {
+            workers: workers.len(),
+            queue_depth: workers.iter().map(|worker| worker.queued).sum(),
+            paused: workers.iter().map(|worker| worker.paused).sum(),
+            running: workers.iter().map(|worker| worker.running).sum(),
+            processed_last_minute: workers
+                .iter()
+                .map(|worker| worker.processed_last_minute)
+                .sum(),
+            max_lag_ms: workers
+                .iter()
+                .map(|worker| worker.lag_ms)
+                .max()
+                .unwrap_or_default(),
+            paused_task_types,
+        }
+    }
+}
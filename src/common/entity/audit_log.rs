@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+
+/// Domain event or record change kept for the admin audit trail
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Who caused the event: `user:<id>`, `user:<admin id> as user:<id>` while impersonating,
+    /// `service:<name>` or `anonymous`
+    pub actor: String,
+    /// Snake case name of the event, e.g. `role_assigned`, or `create`, `update` or
+    /// `delete` for changes recorded by repositories
+    pub action: String,
+    /// Kind of record the event is about, e.g. `user`
+    pub resource_type: String,
+    pub resource_id: Option<String>,
+    /// The event, or `{"field": {"from": .., "to": ..}}` of the fields a change touched
+    pub payload: Json,
+    pub created_at: DateTime,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+
+/// Broadcast published to WebSocket clients, kept for debugging what users were sent
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "broadcast_event")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub event_type: String,
+    /// Task the broadcast reports on, `None` for user notifications
+    pub task_id: Option<String>,
+    /// User the broadcast is addressed to, when it isn't tied to a task
+    pub user_id: Option<i32>,
+    pub data: Json,
+    pub published_at: DateTime,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+
+/// Task event that failed its last attempt, kept for admins to replay or purge
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "dead_letter")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Id of the task event, shared with its `task_event_log` entries
+    pub task_id: String,
+    pub task_type: Option<String>,
+    /// Destination the event is published to when replayed
+    pub topic: String,
+    /// The whole task event as last consumed
+    pub event: Json,
+    pub attempts: i32,
+    #[sea_orm(column_type = "Text")]
+    pub error: String,
+    pub worker_id: Option<String>,
+    pub failed_at: DateTime,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
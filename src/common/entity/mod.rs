@@ -0,0 +1,9 @@
+pub mod audit_log;
+pub mod broadcast_event;
+pub mod dead_letter;
+pub mod outbox;
+pub mod prelude;
+pub mod task_event_log;
+pub mod task_lease;
+pub mod task_pause;
+pub mod worker_heartbeat;
@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+
+/// A task written in the transaction that enqueued it, waiting to be published
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "outbox")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Id of the task event, the key brokers and workers deduplicate republished tasks by
+    #[sea_orm(unique)]
+    pub idempotency_key: String,
+    /// Topic or queue, `None` for the default one
+    pub destination: Option<String>,
+    /// The task event as published
+    pub payload: String,
+    /// Failed publish attempts so far
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    /// When the relay may publish the entry, pushed back after each failed attempt
+    pub next_attempt_at: DateTime,
+    pub sent_at: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
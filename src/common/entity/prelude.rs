@@ -0,0 +1,8 @@
+pub use super::audit_log::Entity as AuditLog;
+pub use super::broadcast_event::Entity as BroadcastEvent;
+pub use super::dead_letter::Entity as DeadLetter;
+pub use super::outbox::Entity as Outbox;
+pub use super::task_event_log::Entity as TaskEventLog;
+pub use super::task_lease::Entity as TaskLease;
+pub use super::task_pause::Entity as TaskPause;
+pub use super::worker_heartbeat::Entity as WorkerHeartbeat;
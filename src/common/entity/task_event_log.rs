@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+
+/// One lifecycle transition of a task, written by the worker's consumer
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "task_event_log")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Id of the task event, shared by all of its attempts
+    pub task_id: String,
+    /// `task_id` carried by the task payload, the id clients track progress with
+    pub reference: Option<String>,
+    pub task_type: Option<String>,
+    /// `enqueued`, `started`, `retrying`, `completed` or `failed`
+    pub status: String,
+    pub attempt: i32,
+    pub worker_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: Option<DateTime>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,21 @@
+use sea_orm::entity::prelude::*;
+
+/// A task a worker is running, held for as long as the worker's heartbeat renews it
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "task_lease")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Id of the task event
+    #[sea_orm(unique)]
+    pub task_id: String,
+    pub task_type: Option<String>,
+    pub worker_id: String,
+    /// The whole task event as consumed, enqueued again if the lease runs out
+    pub event: Json,
+    pub leased_until: DateTime,
+    pub created_at: DateTime,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
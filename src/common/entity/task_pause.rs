@@ -0,0 +1,18 @@
+use sea_orm::entity::prelude::*;
+
+/// Task type an admin paused; workers keep its tasks queued until it is resumed
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "task_pause")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// `type` tag of the paused tasks, e.g. `SendEmail`
+    #[sea_orm(unique)]
+    pub task_type: String,
+    pub reason: Option<String>,
+    pub paused_by_user_id: Option<i32>,
+    pub paused_at: DateTime,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
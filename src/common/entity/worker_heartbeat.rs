@@ -0,0 +1,16 @@
+use sea_orm::entity::prelude::*;
+
+/// A running worker, as last reported by its heartbeat
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "worker_heartbeat")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub worker_id: String,
+    pub started_at: DateTime,
+    pub last_seen_at: DateTime,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,4 +1,6 @@
 pub mod api;
 pub mod dto;
+pub mod entity;
+pub mod repository;
 pub mod use_case;
 pub mod util;
@@ -0,0 +1,80 @@
+use chrono::NaiveDateTime;
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{common::entity::audit_log, core::context::Context};
+
+/// Audit log entries matched by every criterion that is set
+#[derive(Default)]
+pub struct AuditLogSearchParams<'a> {
+    pub actor: Option<&'a str>,
+    /// Entries caused by user `user_id`, including those of impersonations by or of them
+    pub user_id: Option<i32>,
+    pub action: Option<&'a str>,
+    pub resource_type: Option<&'a str>,
+    pub resource_id: Option<&'a str>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+}
+
+pub async fn create(
+    context: &Context,
+    mut audit_log: audit_log::ActiveModel,
+) -> Result<audit_log::Model, DbErr> {
+    audit_log.created_at = Set(chrono::Utc::now().naive_utc());
+
+    audit_log.insert(context.txn()).await
+}
+
+/// Up to `limit` matching entries older than the entry `before`, newest first. Paging by id
+/// instead of offset keeps pages stable while new entries are written.
+pub async fn search(
+    context: &Context,
+    params: &AuditLogSearchParams<'_>,
+    before: Option<i32>,
+    limit: u64,
+) -> Result<Vec<audit_log::Model>, DbErr> {
+    let mut query = build_search_query(params);
+    if let Some(before) = before {
+        query = query.filter(audit_log::Column::Id.lt(before));
+    }
+
+    query
+        .order_by_desc(audit_log::Column::Id)
+        .limit(limit)
+        .all(context.txn())
+        .await
+}
+
+fn build_search_query(params: &AuditLogSearchParams<'_>) -> Select<audit_log::Entity> {
+    let mut query = audit_log::Entity::find();
+
+    if let Some(actor) = params.actor {
+        query = query.filter(audit_log::Column::Actor.eq(actor));
+    }
+    if let Some(user_id) = params.user_id {
+        let actor = format!("user:{}", user_id);
+        query = query.filter(
+            Condition::any()
+                .add(audit_log::Column::Actor.eq(actor.as_str()))
+                .add(audit_log::Column::Actor.starts_with(format!("{} as ", actor)))
+                .add(audit_log::Column::Actor.ends_with(format!(" as {}", actor))),
+        );
+    }
+    if let Some(action) = params.action {
+        query = query.filter(audit_log::Column::Action.eq(action));
+    }
+    if let Some(resource_type) = params.resource_type {
+        query = query.filter(audit_log::Column::ResourceType.eq(resource_type));
+    }
+    if let Some(resource_id) = params.resource_id {
+        query = query.filter(audit_log::Column::ResourceId.eq(resource_id));
+    }
+    if let Some(created_from) = params.created_from {
+        query = query.filter(audit_log::Column::CreatedAt.gte(created_from));
+    }
+    if let Some(created_to) = params.created_to {
+        query = query.filter(audit_log::Column::CreatedAt.lt(created_to));
+    }
+
+    query
+}
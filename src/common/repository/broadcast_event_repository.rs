@@ -0,0 +1,77 @@
+use chrono::NaiveDateTime;
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    common::entity::broadcast_event,
+    core::{context::Context, db::query::paginate},
+};
+
+/// Archived broadcasts matched by every criterion that is set
+#[derive(Default)]
+pub struct BroadcastEventSearchParams<'a> {
+    pub task_id: Option<&'a str>,
+    pub user_id: Option<i32>,
+    pub event_type: Option<&'a str>,
+    pub published_from: Option<NaiveDateTime>,
+    pub published_to: Option<NaiveDateTime>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+pub async fn create(
+    context: &Context,
+    mut broadcast_event: broadcast_event::ActiveModel,
+) -> Result<broadcast_event::Model, DbErr> {
+    broadcast_event.published_at = Set(chrono::Utc::now().naive_utc());
+
+    broadcast_event.insert(context.txn()).await
+}
+
+/// Matching broadcasts in the order they were published, and how many match in total
+pub async fn search(
+    context: &Context,
+    params: &BroadcastEventSearchParams<'_>,
+) -> Result<(Vec<broadcast_event::Model>, usize), DbErr> {
+    let total_count = build_search_query(params).count(context.txn()).await? as usize;
+    let query = paginate(
+        build_search_query(params).order_by_asc(broadcast_event::Column::Id),
+        params.page,
+        params.page_size,
+    );
+
+    Ok((query.all(context.txn()).await?, total_count))
+}
+
+/// Drop broadcasts published before `cutoff`, returning how many were deleted
+pub async fn delete_published_before(
+    context: &Context,
+    cutoff: NaiveDateTime,
+) -> Result<u64, DbErr> {
+    let result = broadcast_event::Entity::delete_many()
+        .filter(broadcast_event::Column::PublishedAt.lt(cutoff))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
+
+fn build_search_query(params: &BroadcastEventSearchParams<'_>) -> Select<broadcast_event::Entity> {
+    let mut query = broadcast_event::Entity::find();
+
+    if let Some(task_id) = params.task_id {
+        query = query.filter(broadcast_event::Column::TaskId.eq(task_id));
+    }
+    if let Some(user_id) = params.user_id {
+        query = query.filter(broadcast_event::Column::UserId.eq(user_id));
+    }
+    if let Some(event_type) = params.event_type {
+        query = query.filter(broadcast_event::Column::EventType.eq(event_type));
+    }
+    if let Some(published_from) = params.published_from {
+        query = query.filter(broadcast_event::Column::PublishedAt.gte(published_from));
+    }
+    if let Some(published_to) = params.published_to {
+        query = query.filter(broadcast_event::Column::PublishedAt.lt(published_to));
+    }
+
+    query
+}
@@ -0,0 +1,71 @@
+use chrono::NaiveDateTime;
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    common::entity::dead_letter,
+    core::{context::Context, db::query::paginate},
+};
+
+/// Dead letters matched by every criterion that is set
+#[derive(Default)]
+pub struct DeadLetterSearchParams<'a> {
+    pub ids: Option<&'a [i32]>,
+    pub task_type: Option<&'a str>,
+    pub failed_from: Option<NaiveDateTime>,
+    pub failed_to: Option<NaiveDateTime>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+pub async fn create(
+    context: &Context,
+    mut dead_letter: dead_letter::ActiveModel,
+) -> Result<dead_letter::Model, DbErr> {
+    dead_letter.failed_at = Set(chrono::Utc::now().naive_utc());
+
+    dead_letter.insert(context.txn()).await
+}
+
+/// Matching dead letters, newest first, and how many match in total
+pub async fn search(
+    context: &Context,
+    params: &DeadLetterSearchParams<'_>,
+) -> Result<(Vec<dead_letter::Model>, usize), DbErr> {
+    let total_count = build_search_query(params).count(context.txn()).await? as usize;
+    let query = paginate(
+        build_search_query(params)
+            .order_by_desc(dead_letter::Column::FailedAt)
+            .order_by_desc(dead_letter::Column::Id),
+        params.page,
+        params.page_size,
+    );
+
+    Ok((query.all(context.txn()).await?, total_count))
+}
+
+pub async fn delete_by_ids(context: &Context, ids: &[i32]) -> Result<u64, DbErr> {
+    let result = dead_letter::Entity::delete_many()
+        .filter(dead_letter::Column::Id.is_in(ids.iter().copied()))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
+
+fn build_search_query(params: &DeadLetterSearchParams<'_>) -> Select<dead_letter::Entity> {
+    let mut query = dead_letter::Entity::find();
+
+    if let Some(ids) = params.ids {
+        query = query.filter(dead_letter::Column::Id.is_in(ids.to_vec()));
+    }
+    if let Some(task_type) = params.task_type {
+        query = query.filter(dead_letter::Column::TaskType.eq(task_type));
+    }
+    if let Some(failed_from) = params.failed_from {
+        query = query.filter(dead_letter::Column::FailedAt.gte(failed_from));
+    }
+    if let Some(failed_to) = params.failed_to {
+        query = query.filter(dead_letter::Column::FailedAt.lt(failed_to));
+    }
+
+    query
+}
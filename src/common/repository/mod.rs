@@ -0,0 +1,8 @@
+pub mod audit_log_repository;
+pub mod broadcast_event_repository;
+pub mod dead_letter_repository;
+pub mod outbox_repository;
+pub mod task_event_log_repository;
+pub mod task_lease_repository;
+pub mod task_pause_repository;
+pub mod worker_heartbeat_repository;
@@ -0,0 +1,87 @@
+use chrono::NaiveDateTime;
+use sea_orm::{DbErr, entity::*, query::*, sea_query::Expr};
+
+use crate::{common::entity::outbox, core::context::Context};
+
+pub async fn create(
+    context: &Context,
+    mut entry: outbox::ActiveModel,
+) -> Result<outbox::Model, DbErr> {
+    entry.created_at = Set(chrono::Utc::now().naive_utc());
+
+    entry.insert(context.txn()).await
+}
+
+/// Up to `limit` unsent entries due at `now`, oldest first
+pub async fn find_due(
+    context: &Context,
+    now: NaiveDateTime,
+    limit: u64,
+) -> Result<Vec<outbox::Model>, DbErr> {
+    outbox::Entity::find()
+        .filter(outbox::Column::SentAt.is_null())
+        .filter(outbox::Column::NextAttemptAt.lte(now))
+        .order_by_asc(outbox::Column::Id)
+        .limit(limit)
+        .all(context.txn())
+        .await
+}
+
+/// Hold entry `id` until `until` if it is still unsent and due at `now`, returning whether
+/// it was. Only one of several relays picking the same entry gets `true`.
+pub async fn claim(
+    context: &Context,
+    id: i32,
+    now: NaiveDateTime,
+    until: NaiveDateTime,
+) -> Result<bool, DbErr> {
+    let result = outbox::Entity::update_many()
+        .col_expr(outbox::Column::NextAttemptAt, Expr::value(until))
+        .filter(outbox::Column::Id.eq(id))
+        .filter(outbox::Column::SentAt.is_null())
+        .filter(outbox::Column::NextAttemptAt.lte(now))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+pub async fn mark_sent(context: &Context, id: i32, sent_at: NaiveDateTime) -> Result<(), DbErr> {
+    outbox::Entity::update_many()
+        .col_expr(outbox::Column::SentAt, Expr::value(sent_at))
+        .filter(outbox::Column::Id.eq(id))
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
+
+/// Count a failed publish of entry `id` and put the next one off until `next_attempt_at`
+pub async fn record_failure(
+    context: &Context,
+    id: i32,
+    attempts: i32,
+    error: &str,
+    next_attempt_at: NaiveDateTime,
+) -> Result<(), DbErr> {
+    outbox::Entity::update_many()
+        .col_expr(outbox::Column::Attempts, Expr::value(attempts))
+        .col_expr(outbox::Column::LastError, Expr::value(error))
+        .col_expr(outbox::Column::NextAttemptAt, Expr::value(next_attempt_at))
+        .filter(outbox::Column::Id.eq(id))
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_by_id(context: &Context, id: i32) -> Result<(), DbErr> {
+    outbox::Entity::delete_by_id(id).exec(context.txn()).await?;
+    Ok(())
+}
+
+/// Delete the entries published before `before`, returning how many were
+pub async fn delete_sent_before(context: &Context, before: NaiveDateTime) -> Result<u64, DbErr> {
+    let result = outbox::Entity::delete_many()
+        .filter(outbox::Column::SentAt.lt(before))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
@@ -0,0 +1,50 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{common::entity::task_event_log, core::context::Context};
+
+pub async fn create(
+    context: &Context,
+    mut entry: task_event_log::ActiveModel,
+) -> Result<task_event_log::Model, DbErr> {
+    entry.created_at = Set(Some(chrono::Utc::now().naive_utc()));
+
+    entry.insert(context.txn()).await
+}
+
+/// Entries of a task, matched by its event id or the `task_id` of its payload, oldest first
+pub async fn find_by_task_id(
+    context: &Context,
+    task_id: &str,
+) -> Result<Vec<task_event_log::Model>, DbErr> {
+    task_event_log::Entity::find()
+        .filter(
+            Condition::any()
+                .add(task_event_log::Column::TaskId.eq(task_id))
+                .add(task_event_log::Column::Reference.eq(task_id)),
+        )
+        .order_by_asc(task_event_log::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+/// Whether the task with event id `task_id` completed or failed after its last attempt
+pub async fn is_finished(context: &Context, task_id: &str) -> Result<bool, DbErr> {
+    let count = task_event_log::Entity::find()
+        .filter(task_event_log::Column::TaskId.eq(task_id))
+        .filter(task_event_log::Column::Status.is_in(["completed", "failed"]))
+        .count(context.txn())
+        .await?;
+    Ok(count > 0)
+}
+
+/// Entries of the tasks with the given event ids, oldest first
+pub async fn find_by_task_ids(
+    context: &Context,
+    task_ids: &[String],
+) -> Result<Vec<task_event_log::Model>, DbErr> {
+    task_event_log::Entity::find()
+        .filter(task_event_log::Column::TaskId.is_in(task_ids.iter().cloned()))
+        .order_by_asc(task_event_log::Column::Id)
+        .all(context.txn())
+        .await
+}
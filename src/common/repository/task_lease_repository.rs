@@ -0,0 +1,71 @@
+use chrono::NaiveDateTime;
+use sea_orm::{DbErr, entity::*, query::*, sea_query::Expr};
+
+use crate::{common::entity::task_lease, core::context::Context};
+
+/// Lease the task of `task_lease` to its worker, taking it over from any earlier holder
+pub async fn claim(
+    context: &Context,
+    mut task_lease: task_lease::ActiveModel,
+) -> Result<task_lease::Model, DbErr> {
+    if let ActiveValue::Set(task_id) = &task_lease.task_id {
+        task_lease::Entity::delete_many()
+            .filter(task_lease::Column::TaskId.eq(task_id.as_str()))
+            .exec(context.txn())
+            .await?;
+    }
+    task_lease.created_at = Set(chrono::Utc::now().naive_utc());
+
+    task_lease.insert(context.txn()).await
+}
+
+/// Give up the lease `worker_id` holds on `task_id`, leaving a lease another worker took over
+pub async fn release(context: &Context, task_id: &str, worker_id: &str) -> Result<(), DbErr> {
+    task_lease::Entity::delete_many()
+        .filter(task_lease::Column::TaskId.eq(task_id))
+        .filter(task_lease::Column::WorkerId.eq(worker_id))
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
+
+/// Extend every lease of `worker_id` to `leased_until`
+pub async fn renew_by_worker_id(
+    context: &Context,
+    worker_id: &str,
+    leased_until: NaiveDateTime,
+) -> Result<u64, DbErr> {
+    let result = task_lease::Entity::update_many()
+        .col_expr(task_lease::Column::LeasedUntil, Expr::value(leased_until))
+        .filter(task_lease::Column::WorkerId.eq(worker_id))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// Leases that ran out before `now`, oldest first
+pub async fn find_expired(
+    context: &Context,
+    now: NaiveDateTime,
+) -> Result<Vec<task_lease::Model>, DbErr> {
+    task_lease::Entity::find()
+        .filter(task_lease::Column::LeasedUntil.lt(now))
+        .order_by_asc(task_lease::Column::LeasedUntil)
+        .all(context.txn())
+        .await
+}
+
+/// Delete lease `id` if it is still expired at `now`, returning whether it was. Only one of
+/// several instances recovering the same lease gets `true`.
+pub async fn delete_expired_by_id(
+    context: &Context,
+    id: i32,
+    now: NaiveDateTime,
+) -> Result<bool, DbErr> {
+    let result = task_lease::Entity::delete_many()
+        .filter(task_lease::Column::Id.eq(id))
+        .filter(task_lease::Column::LeasedUntil.lt(now))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected > 0)
+}
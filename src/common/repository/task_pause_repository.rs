@@ -0,0 +1,41 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{common::entity::task_pause, core::context::Context};
+
+/// Paused task types, in alphabetical order
+pub async fn find_all(context: &Context) -> Result<Vec<task_pause::Model>, DbErr> {
+    task_pause::Entity::find()
+        .order_by_asc(task_pause::Column::TaskType)
+        .all(context.txn())
+        .await
+}
+
+pub async fn find_by_task_type(
+    context: &Context,
+    task_type: &str,
+) -> Result<Option<task_pause::Model>, DbErr> {
+    task_pause::Entity::find()
+        .filter(task_pause::Column::TaskType.eq(task_type))
+        .one(context.txn())
+        .await
+}
+
+pub async fn create(
+    context: &Context,
+    mut task_pause: task_pause::ActiveModel,
+) -> Result<task_pause::Model, DbErr> {
+    task_pause.paused_at = Set(chrono::Utc::now().naive_utc());
+    task_pause.paused_by_user_id = Set(context.user.as_ref().map(|user| user.id));
+
+    task_pause.insert(context.txn()).await
+}
+
+/// Resume `task_type`, returning whether it was paused
+pub async fn delete_by_task_type(context: &Context, task_type: &str) -> Result<bool, DbErr> {
+    let result = task_pause::Entity::delete_many()
+        .filter(task_pause::Column::TaskType.eq(task_type))
+        .exec(context.txn())
+        .await?;
+
+    Ok(result.rows_affected > 0)
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+use sea_orm::{DbErr, entity::*, query::*, sea_query::Expr};
+
+use crate::{common::entity::worker_heartbeat, core::context::Context};
+
+/// Record that `worker_id` is alive at `now`, registering it on its first heartbeat
+pub async fn beat(context: &Context, worker_id: &str, now: NaiveDateTime) -> Result<(), DbErr> {
+    let result = worker_heartbeat::Entity::update_many()
+        .col_expr(worker_heartbeat::Column::LastSeenAt, Expr::value(now))
+        .filter(worker_heartbeat::Column::WorkerId.eq(worker_id))
+        .exec(context.txn())
+        .await?;
+    if result.rows_affected == 0 {
+        worker_heartbeat::ActiveModel {
+            worker_id: Set(worker_id.to_string()),
+            started_at: Set(now),
+            last_seen_at: Set(now),
+            ..Default::default()
+        }
+        .insert(context.txn())
+        .await?;
+    }
+    Ok(())
+}
+
+pub async fn delete_by_worker_id(context: &Context, worker_id: &str) -> Result<(), DbErr> {
+    worker_heartbeat::Entity::delete_many()
+        .filter(worker_heartbeat::Column::WorkerId.eq(worker_id))
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
+
+/// Forget workers whose last heartbeat is before `before`, returning how many
+pub async fn delete_seen_before(context: &Context, before: NaiveDateTime) -> Result<u64, DbErr> {
+    let result = worker_heartbeat::Entity::delete_many()
+        .filter(worker_heartbeat::Column::LastSeenAt.lt(before))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
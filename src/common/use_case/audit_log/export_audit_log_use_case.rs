@@ -0,0 +1,50 @@
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    common::{
+        dto::audit_log_dto::{AuditLogDTO, AuditLogSearchParamsDTO},
+        repository::audit_log_repository,
+    },
+    core::{context::Context, dto::error_dto::ErrorDTO, export::CsvExport, validation::Validate},
+};
+
+use super::{AUDIT_READ_SCOPE, search_params};
+
+const EXPORT_PAGE_SIZE: u64 = 500;
+
+/// Every matching entry as CSV, newest first, starting after `cursor` when set.
+/// `limit` is ignored: the export pages through the whole trail on `db`.
+pub async fn execute(
+    context: &Context,
+    db: DatabaseConnection,
+    dto: AuditLogSearchParamsDTO,
+) -> Result<CsvExport, ErrorDTO> {
+    context.authorize_admin_or_scope(AUDIT_READ_SCOPE)?;
+    dto.validate(&context.locale)?;
+
+    let file_name = format!(
+        "audit-log-{}.csv",
+        chrono::Utc::now().format("%Y%m%dT%H%M%SZ")
+    );
+    Ok(CsvExport::new(file_name, move |cursor: Option<i32>| {
+        let (db, dto) = (db.clone(), dto.clone());
+        async move {
+            let context = Context::read_only(db).build();
+            let entries = audit_log_repository::search(
+                &context,
+                &search_params(&dto),
+                cursor.or(dto.cursor),
+                EXPORT_PAGE_SIZE,
+            )
+            .await?;
+            let next_cursor = match entries.last() {
+                Some(last) if entries.len() as u64 == EXPORT_PAGE_SIZE => Some(last.id),
+                _ => None,
+            };
+            Ok::<_, anyhow::Error>((
+                entries.into_iter().map(AuditLogDTO::from).collect(),
+                next_cursor,
+            ))
+        }
+    }))
+}
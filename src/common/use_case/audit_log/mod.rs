@@ -0,0 +1,22 @@
+pub mod export_audit_log_use_case;
+pub mod search_audit_log_use_case;
+
+use crate::common::{
+    dto::audit_log_dto::AuditLogSearchParamsDTO,
+    repository::audit_log_repository::AuditLogSearchParams,
+};
+
+/// Scope service accounts need to read the audit trail
+const AUDIT_READ_SCOPE: &str = "audit.read";
+
+fn search_params(dto: &AuditLogSearchParamsDTO) -> AuditLogSearchParams<'_> {
+    AuditLogSearchParams {
+        actor: dto.actor.as_deref(),
+        user_id: dto.user_id,
+        action: dto.action.as_deref(),
+        resource_type: dto.resource_type.as_deref(),
+        resource_id: dto.resource_id.as_deref(),
+        created_from: dto.created_from,
+        created_to: dto.created_to,
+    }
+}
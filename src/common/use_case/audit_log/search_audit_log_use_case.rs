@@ -0,0 +1,46 @@
+use axum::http::StatusCode;
+
+use crate::{
+    common::{
+        dto::audit_log_dto::{AuditLogDTO, AuditLogPageDTO, AuditLogSearchParamsDTO},
+        repository::audit_log_repository,
+    },
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        validation::Validate,
+    },
+};
+
+use super::{AUDIT_READ_SCOPE, search_params};
+
+const DEFAULT_LIMIT: u64 = 50;
+
+pub async fn execute(
+    context: &Context,
+    dto: AuditLogSearchParamsDTO,
+) -> Result<ResponseDTO<AuditLogPageDTO>, ErrorDTO> {
+    context.authorize_admin_or_scope(AUDIT_READ_SCOPE)?;
+    dto.validate(&context.locale)?;
+
+    // One entry past the page tells whether another page follows
+    let limit = dto.limit.unwrap_or(DEFAULT_LIMIT);
+    let mut entries =
+        audit_log_repository::search(context, &search_params(&dto), dto.cursor, limit + 1)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+    let next_cursor = if entries.len() as u64 > limit {
+        entries.truncate(limit as usize);
+        entries.last().map(|entry| entry.id)
+    } else {
+        None
+    };
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        AuditLogPageDTO {
+            items: entries.into_iter().map(AuditLogDTO::from).collect(),
+            next_cursor,
+        },
+    ))
+}
@@ -0,0 +1 @@
+pub mod search_broadcast_event_use_case;
@@ -0,0 +1,57 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    common::{
+        dto::broadcast_dto::{
+            BroadcastEventDTO, BroadcastEventListDTO, BroadcastEventSearchParamsDTO,
+        },
+        repository::broadcast_event_repository::{self, BroadcastEventSearchParams},
+    },
+    core::{
+        context::Context,
+        db::query::PageRequest,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        layer::auth_layer::authorize_role,
+        validation::Validate,
+    },
+    user::entity::sea_orm_active_enums::UserRole,
+};
+
+pub async fn execute(
+    context: &Context,
+    dto: BroadcastEventSearchParamsDTO,
+) -> Result<ResponseDTO<BroadcastEventListDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+    dto.validate(&context.locale)?;
+    let page = PageRequest::new(dto.page, dto.page_size);
+
+    let (events, total_count) = broadcast_event_repository::search(
+        context,
+        &BroadcastEventSearchParams {
+            task_id: dto.task_id.as_deref(),
+            user_id: dto.user_id,
+            event_type: dto.event_type.as_deref(),
+            published_from: dto.published_from,
+            published_to: dto.published_to,
+            page: Some(page.page),
+            page_size: Some(page.page_size),
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        BroadcastEventListDTO {
+            items: events.into_iter().map(BroadcastEventDTO::from).collect(),
+            count: total_count,
+        },
+    ))
+}
@@ -0,0 +1,3 @@
+pub mod purge_dead_letter_use_case;
+pub mod replay_dead_letter_use_case;
+pub mod search_dead_letter_use_case;
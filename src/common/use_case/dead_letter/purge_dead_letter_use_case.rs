@@ -0,0 +1,66 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    common::{
+        dto::dead_letter_dto::{DeadLetterPurgeDTO, DeadLetterSelectionDTO},
+        repository::dead_letter_repository::{self, DeadLetterSearchParams},
+    },
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        layer::auth_layer::authorize_role,
+        validation::Validate,
+    },
+    user::entity::sea_orm_active_enums::UserRole,
+};
+
+pub async fn execute(
+    context: &Context,
+    dto: DeadLetterSelectionDTO,
+) -> Result<ResponseDTO<DeadLetterPurgeDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+    dto.validate(&context.locale)?;
+    if dto.is_empty() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::TaskDeadLetterSelectionEmpty,
+            t!(
+                "common.dead_letter_selection_empty",
+                locale = &context.locale
+            )
+            .to_string(),
+        ));
+    }
+
+    let (dead_letters, _) = dead_letter_repository::search(
+        context,
+        &DeadLetterSearchParams {
+            ids: dto.ids.as_deref(),
+            task_type: dto.task_type.as_deref(),
+            failed_from: dto.failed_from,
+            failed_to: dto.failed_to,
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+    let ids: Vec<i32> = dead_letters
+        .iter()
+        .map(|dead_letter| dead_letter.id)
+        .collect();
+
+    let purged = dead_letter_repository::delete_by_ids(context, &ids)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        DeadLetterPurgeDTO { purged },
+    ))
+}
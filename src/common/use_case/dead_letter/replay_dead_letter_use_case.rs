@@ -0,0 +1,99 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use serde_json::Value;
+
+use crate::{
+    common::{
+        dto::dead_letter_dto::{DeadLetterReplayDTO, DeadLetterSelectionDTO},
+        repository::dead_letter_repository::{self, DeadLetterSearchParams},
+    },
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        layer::auth_layer::authorize_role,
+        validation::Validate,
+    },
+    pkg::messaging::TaskEvent,
+    user::entity::sea_orm_active_enums::UserRole,
+};
+
+pub async fn execute(
+    context: &Context,
+    dto: DeadLetterSelectionDTO,
+) -> Result<ResponseDTO<DeadLetterReplayDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+    dto.validate(&context.locale)?;
+    if dto.is_empty() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::TaskDeadLetterSelectionEmpty,
+            t!(
+                "common.dead_letter_selection_empty",
+                locale = &context.locale
+            )
+            .to_string(),
+        ));
+    }
+
+    let producer = context
+        .producer
+        .as_ref()
+        .ok_or_else(|| ErrorDTO::map_internal_error(anyhow::anyhow!("Producer not available")))?;
+
+    let (dead_letters, _) = dead_letter_repository::search(
+        context,
+        &DeadLetterSearchParams {
+            ids: dto.ids.as_deref(),
+            task_type: dto.task_type.as_deref(),
+            failed_from: dto.failed_from,
+            failed_to: dto.failed_to,
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    let mut replayed_ids = Vec::new();
+    let mut failed_ids = Vec::new();
+    for dead_letter in dead_letters {
+        // A replayed task starts over with its full retry budget, under the same event id
+        let result: anyhow::Result<()> = async {
+            let mut event: TaskEvent<Value> = serde_json::from_value(dead_letter.event.clone())?;
+            event.retry_count = 0;
+            event
+                .publish_with_producer(producer.as_ref().as_ref(), Some(&dead_letter.topic))
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(_) => replayed_ids.push(dead_letter.id),
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to replay dead letter {} (task {}): {:?}",
+                    dead_letter.id,
+                    dead_letter.task_id,
+                    e
+                );
+                failed_ids.push(dead_letter.id);
+            }
+        }
+    }
+
+    dead_letter_repository::delete_by_ids(context, &replayed_ids)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        DeadLetterReplayDTO {
+            replayed: replayed_ids.len(),
+            failed_ids,
+        },
+    ))
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    common::{
+        dto::{
+            dead_letter_dto::{DeadLetterDTO, DeadLetterListDTO, DeadLetterSearchParamsDTO},
+            task_dto::TaskEventLogDTO,
+        },
+        repository::{
+            dead_letter_repository::{self, DeadLetterSearchParams},
+            task_event_log_repository,
+        },
+    },
+    core::{
+        context::Context,
+        db::query::PageRequest,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        layer::auth_layer::authorize_role,
+        validation::Validate,
+    },
+    user::entity::sea_orm_active_enums::UserRole,
+};
+
+pub async fn execute(
+    context: &Context,
+    dto: DeadLetterSearchParamsDTO,
+) -> Result<ResponseDTO<DeadLetterListDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+    dto.validate(&context.locale)?;
+    let page = PageRequest::new(dto.page, dto.page_size);
+
+    let (dead_letters, total_count) = dead_letter_repository::search(
+        context,
+        &DeadLetterSearchParams {
+            task_type: dto.task_type.as_deref(),
+            failed_from: dto.failed_from,
+            failed_to: dto.failed_to,
+            page: Some(page.page),
+            page_size: Some(page.page_size),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    let task_ids: Vec<String> = dead_letters
+        .iter()
+        .map(|dead_letter| dead_letter.task_id.clone())
+        .collect();
+    let mut history: HashMap<String, Vec<TaskEventLogDTO>> = HashMap::new();
+    for entry in task_event_log_repository::find_by_task_ids(context, &task_ids)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+    {
+        history
+            .entry(entry.task_id.clone())
+            .or_default()
+            .push(TaskEventLogDTO::from(entry));
+    }
+
+    let items = dead_letters
+        .into_iter()
+        .map(|dead_letter| {
+            let entries = history.remove(&dead_letter.task_id).unwrap_or_default();
+            DeadLetterDTO {
+                history: entries,
+                ..DeadLetterDTO::from(dead_letter)
+            }
+        })
+        .collect();
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        DeadLetterListDTO {
+            items,
+            count: total_count,
+        },
+    ))
+}
@@ -0,0 +1,39 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    common::dto::deprecation_dto::{DeprecatedRouteDTO, DeprecationReportDTO},
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        layer::{auth_layer::authorize_role, deprecation_layer::DeprecationRegistry},
+    },
+    user::entity::sea_orm_active_enums::UserRole,
+};
+
+pub async fn execute(
+    context: &Context,
+    deprecations: &DeprecationRegistry,
+) -> Result<ResponseDTO<DeprecationReportDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+
+    let items: Vec<DeprecatedRouteDTO> = deprecations
+        .usage()
+        .into_iter()
+        .map(DeprecatedRouteDTO::from)
+        .collect();
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        DeprecationReportDTO {
+            count: items.len(),
+            items,
+        },
+    ))
+}
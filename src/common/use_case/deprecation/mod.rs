@@ -0,0 +1 @@
+pub mod get_deprecation_report_use_case;
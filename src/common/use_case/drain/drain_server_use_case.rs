@@ -0,0 +1,27 @@
+use axum::http::StatusCode;
+
+use crate::{
+    common::dto::drain_dto::DrainStatusDTO,
+    config::drain::Drain,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+};
+
+/// Start draining this instance; the shutdown sequence then runs as on a shutdown signal.
+/// Asking again while it drains only reports the phase.
+pub fn execute(context: &Context, drain: &Drain) -> Result<ResponseDTO<DrainStatusDTO>, ErrorDTO> {
+    context.authorize_admin_or_scope("server.drain")?;
+
+    if drain.start() {
+        tracing::info!(actor = %context.actor(), "Drain requested");
+    }
+
+    Ok(ResponseDTO::new(
+        StatusCode::ACCEPTED,
+        DrainStatusDTO {
+            phase: drain.phase(),
+        },
+    ))
+}
@@ -0,0 +1 @@
+pub mod drain_server_use_case;
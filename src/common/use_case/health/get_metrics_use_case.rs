@@ -0,0 +1,8 @@
+use crate::{config::setting::Setting, pkg::circuit_breaker::render_metrics};
+
+use super::breakers_by_process;
+
+/// Circuit breaker gauges and counters in the Prometheus text format
+pub fn execute(setting: &Setting) -> String {
+    render_metrics(&breakers_by_process(setting))
+}
@@ -0,0 +1,58 @@
+use axum::http::StatusCode;
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    common::dto::health_dto::{DependencyHealthDTO, ReadinessDTO},
+    config::{drain::Drain, setting::Setting},
+    core::dto::response_dto::ResponseDTO,
+    pkg::circuit_breaker::BreakerState,
+};
+
+use super::breakers_by_process;
+
+pub async fn execute(
+    setting: &Setting,
+    db: &DatabaseConnection,
+    drain: &Drain,
+) -> ResponseDTO<ReadinessDTO> {
+    let database = match db.ping().await {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("Readiness check could not reach the database: {:?}", e);
+            false
+        }
+    };
+
+    let breakers = breakers_by_process(setting);
+    // A tripped breaker only turns off the features of its dependency, so the instance
+    // keeps taking traffic
+    let degraded = breakers
+        .iter()
+        .flat_map(|(_, snapshots)| snapshots)
+        .any(|snapshot| snapshot.state != BreakerState::Closed);
+    let dependencies = breakers
+        .into_iter()
+        .flat_map(|(process, snapshots)| {
+            snapshots
+                .into_iter()
+                .map(move |snapshot| DependencyHealthDTO::new(&process, snapshot))
+        })
+        .collect();
+
+    // A draining instance stays healthy but takes no new traffic, so clients reconnect elsewhere
+    let (status, label) = match (database, degraded) {
+        _ if drain.is_draining() => (StatusCode::SERVICE_UNAVAILABLE, "draining"),
+        (false, _) => (StatusCode::SERVICE_UNAVAILABLE, "unavailable"),
+        (true, true) => (StatusCode::OK, "degraded"),
+        (true, false) => (StatusCode::OK, "ready"),
+    };
+
+    ResponseDTO::new(
+        status,
+        ReadinessDTO {
+            status: label.to_string(),
+            database,
+            dependencies,
+        },
+    )
+}
@@ -0,0 +1,30 @@
+pub mod get_metrics_use_case;
+pub mod get_readiness_use_case;
+
+use crate::{
+    config::setting::Setting,
+    pkg::{
+        circuit_breaker::{self, BreakerSnapshot},
+        messaging::stats::{STALE_REPORT_INTERVALS, reported_worker_stats},
+    },
+};
+
+/// Process label of the breakers of this API process
+pub const API_PROCESS: &str = "api";
+
+/// Circuit breakers of this process and of the workers that reported recently, by process
+pub fn breakers_by_process(setting: &Setting) -> Vec<(String, Vec<BreakerSnapshot>)> {
+    let workers = setting
+        .messaging
+        .worker_stats_interval()
+        .map(|interval| reported_worker_stats(interval * STALE_REPORT_INTERVALS))
+        .unwrap_or_default();
+
+    std::iter::once((API_PROCESS.to_string(), circuit_breaker::snapshots()))
+        .chain(
+            workers
+                .into_iter()
+                .map(|worker| (worker.worker_id, worker.circuit_breakers)),
+        )
+        .collect()
+}
@@ -11,6 +11,7 @@ use crate::common::dto::mcp_dto::{
     GetUserParams, ProfileOutput, SearchUsersParams, UserListOutput, UserOutput,
 };
 use crate::common::util::mcp_util::api_client::{HttpApiClient, internal_mcp_error};
+use crate::pkg::http_client::HttpClient;
 
 #[derive(Clone)]
 pub struct McpServer {
@@ -33,9 +34,9 @@ impl ServerHandler for McpServer {
 
 #[tool_router]
 impl McpServer {
-    pub fn new(api_base_url: String) -> Self {
+    pub fn new(http_client: HttpClient, api_base_url: String) -> Self {
         Self {
-            api_client: HttpApiClient::new(api_base_url),
+            api_client: HttpApiClient::new(http_client, api_base_url),
             tool_router: Self::tool_router(),
         }
     }
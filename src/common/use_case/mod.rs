@@ -1,2 +1,10 @@
+pub mod audit_log;
+pub mod broadcast;
+pub mod dead_letter;
+pub mod deprecation;
+pub mod drain;
+pub mod health;
 pub mod mcp;
+pub mod stats;
 pub mod task;
+pub mod worker;
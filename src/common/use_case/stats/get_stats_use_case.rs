@@ -0,0 +1,65 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    common::dto::stats_dto::{StatsDTO, TaskQuotaStatsDTO},
+    config::setting::Setting,
+    core::{
+        context::Context,
+        db::connection::pool_stats,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        layer::{auth_layer::authorize_role, request_stats_layer::request_stats},
+    },
+    pkg::{
+        broadcast::websocket::active_connections,
+        messaging::{
+            failover::failover_count,
+            stats::{STALE_REPORT_INTERVALS, reported_worker_stats},
+        },
+    },
+    user::entity::sea_orm_active_enums::UserRole,
+};
+
+pub async fn execute(
+    context: &Context,
+    setting: &Setting,
+    db: &DatabaseConnection,
+) -> Result<ResponseDTO<StatsDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+
+    let workers = setting
+        .messaging
+        .worker_stats_interval()
+        .map(|interval| reported_worker_stats(interval * STALE_REPORT_INTERVALS))
+        .unwrap_or_default();
+
+    let task_quota = match &context.task_quota {
+        Some(quota) => match quota.usage().await {
+            Ok(usage) => Some(TaskQuotaStatsDTO::new(quota.limits(), usage)),
+            Err(e) => {
+                tracing::warn!("Failed to read task quota usage: {:?}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        StatsDTO::new(
+            request_stats(),
+            active_connections().await,
+            pool_stats(db),
+            workers,
+            failover_count(),
+            task_quota,
+        ),
+    ))
+}
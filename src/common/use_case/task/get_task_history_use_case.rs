@@ -0,0 +1,52 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    common::{
+        dto::task_dto::{TaskEventLogDTO, TaskHistoryDTO},
+        repository::task_event_log_repository,
+    },
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        layer::auth_layer::authorize_role,
+    },
+    user::entity::sea_orm_active_enums::UserRole,
+};
+
+pub async fn execute(
+    context: &Context,
+    task_id: &str,
+) -> Result<ResponseDTO<TaskHistoryDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+
+    let entries = task_event_log_repository::find_by_task_id(context, task_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    if entries.is_empty() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::TaskHistoryNotFound,
+            t!(
+                "common.task_history_not_found",
+                locale = &context.locale,
+                task_id = task_id
+            )
+            .to_string(),
+        ));
+    }
+
+    let items: Vec<TaskEventLogDTO> = entries.into_iter().map(TaskEventLogDTO::from).collect();
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        TaskHistoryDTO {
+            count: items.len(),
+            items,
+        },
+    ))
+}
@@ -4,32 +4,44 @@ use futures::sink::SinkExt;
 use futures::stream::StreamExt;
 use tokio::sync::mpsc;
 
-use crate::config::setting::Setting;
-use crate::pkg::broadcast::websocket::{
-    BroadcastMessage, register_task_websocket, unregister_task_websocket,
+use crate::config::{
+    drain::{DrainPhase, DrainPhases, close_frame, draining_notice, next_phase},
+    setting::Setting,
+};
+use crate::pkg::broadcast::{
+    protocol::WsProtocol,
+    schema::validate_command,
+    websocket::{BroadcastMessage, register_task_websocket, unregister_task_websocket},
 };
 use crate::pkg::cache::get_cached_task_status;
 use crate::user::entity::user;
 
-pub async fn execute(socket: WebSocket, task_id: String, current_user: user::Model) {
+pub async fn execute(
+    socket: WebSocket,
+    task_id: String,
+    current_user: user::Model,
+    protocol: WsProtocol,
+    mut drain: DrainPhases,
+) {
     let (mut sender, mut receiver) = socket.split();
     let (tx, mut rx) = mpsc::unbounded_channel();
 
     // Register this websocket connection for the task
-    register_task_websocket(task_id.clone(), tx).await;
+    register_task_websocket(task_id.clone(), tx.clone(), protocol).await;
 
     tracing::info!(
-        "Progress updates websocket connected for task_id: {} (user_id: {})",
+        "Progress updates websocket connected for task_id: {} (user_id: {}, protocol: {})",
         task_id,
-        current_user.id
+        current_user.id,
+        protocol.name()
     );
 
     // Try to retrieve cached task status from Redis
     let setting = Setting::new();
     match get_cached_task_status::<BroadcastMessage>(&setting.redis_url, &task_id).await {
         Ok(Some(cached_msg)) => {
-            if let Ok(json_str) = serde_json::to_string(&cached_msg)
-                && sender.send(Message::Text(json_str.into())).await.is_ok()
+            if let Some(frame) = protocol.encode(&cached_msg, None)
+                && sender.send(frame).await.is_ok()
             {
                 tracing::info!("Sent cached task status to client for task_id: {}", task_id);
             }
@@ -56,12 +68,48 @@ pub async fn execute(socket: WebSocket, task_id: String, current_user: user::Mod
         }
     });
 
-    // Handle incoming messages (mostly for keep-alive)
-    while let Some(msg) = receiver.next().await {
-        match msg {
-            Ok(Message::Text(text)) => {
-                tracing::debug!("Received ping for task {}: {}", task_id, text);
+    // Handle incoming commands (mostly `ping` for keep-alive), until the server drains
+    let route = serde_json::json!({ "task_id": task_id });
+    let mut closing = false;
+    loop {
+        let msg = tokio::select! {
+            msg = receiver.next() => msg,
+            phase = next_phase(&mut drain) => {
+                match phase {
+                    // Broadcasts keep flowing until the forwarder flushed, only then we close
+                    DrainPhase::Draining => {
+                        if let Some(frame) = protocol.encode(&draining_notice(route.clone()), None) {
+                            tx.send(frame).ok();
+                        }
+                    }
+                    DrainPhase::Closing => {
+                        tx.send(close_frame()).ok();
+                        closing = true;
+                        break;
+                    }
+                    DrainPhase::Serving => {}
+                }
+                continue;
             }
+        };
+        let Some(msg) = msg else {
+            break;
+        };
+
+        match msg {
+            Ok(Message::Text(text)) => match validate_command(&route, &text) {
+                Ok(_) => tracing::debug!("Received command for task {}: {}", task_id, text),
+                Err(rejection) => {
+                    tracing::warn!(
+                        errors = %rejection.data["errors"],
+                        "Rejected command for task {}",
+                        task_id
+                    );
+                    if let Some(frame) = protocol.encode(&rejection, None) {
+                        tx.send(frame).ok();
+                    }
+                }
+            },
             Ok(Message::Close(_)) => {
                 tracing::info!(
                     "Client closed progress updates connection: task_id={}",
@@ -77,9 +125,14 @@ pub async fn execute(socket: WebSocket, task_id: String, current_user: user::Mod
         }
     }
 
-    // Cleanup
-    send_task.abort();
+    // Cleanup; a drained socket sends what is queued up to the close frame before it goes
     unregister_task_websocket(task_id_clone).await;
+    if closing {
+        drop(tx);
+        let _ = send_task.await;
+    } else {
+        send_task.abort();
+    }
     tracing::info!(
         "Progress updates websocket disconnected: task_id={}",
         task_id
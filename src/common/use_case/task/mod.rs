@@ -1 +1,3 @@
+pub mod get_task_history_use_case;
 pub mod get_task_progress_use_case;
+pub mod poll_task_progress_use_case;
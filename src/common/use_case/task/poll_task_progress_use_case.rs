@@ -0,0 +1,31 @@
+use axum::http::StatusCode;
+use std::time::Duration;
+
+use crate::{
+    common::dto::task_dto::{TaskMessageDTO, TaskPollDTO, TaskPollParamsDTO},
+    config::setting::Setting,
+    core::dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    pkg::broadcast::replay,
+};
+
+pub async fn execute(
+    setting: &Setting,
+    task_id: String,
+    dto: TaskPollParamsDTO,
+) -> Result<ResponseDTO<TaskPollDTO>, ErrorDTO> {
+    let since = dto.since.unwrap_or(0);
+    let max_wait = setting.messaging.task_poll_max_wait_seconds;
+    let timeout = Duration::from_secs(dto.timeout.unwrap_or(max_wait).min(max_wait));
+
+    let messages: Vec<TaskMessageDTO> = replay::wait_for_messages(&task_id, since, timeout)
+        .await
+        .into_iter()
+        .map(TaskMessageDTO::from)
+        .collect();
+    let last_seq = messages.last().map_or(since, |message| message.seq);
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        TaskPollDTO { messages, last_seq },
+    ))
+}
@@ -0,0 +1,35 @@
+use axum::http::StatusCode;
+
+use crate::{
+    common::{dto::worker_dto::WorkerScalingDTO, repository::task_pause_repository},
+    config::setting::Setting,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    pkg::messaging::stats::{STALE_REPORT_INTERVALS, reported_worker_stats},
+};
+
+pub async fn execute(
+    context: &Context,
+    setting: &Setting,
+) -> Result<ResponseDTO<WorkerScalingDTO>, ErrorDTO> {
+    context.authorize_admin_or_scope("workers.read")?;
+
+    let workers = setting
+        .messaging
+        .worker_stats_interval()
+        .map(|interval| reported_worker_stats(interval * STALE_REPORT_INTERVALS))
+        .unwrap_or_default();
+    let paused_task_types = task_pause_repository::find_all(context)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .into_iter()
+        .map(|pause| pause.task_type)
+        .collect();
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        WorkerScalingDTO::new(&workers, paused_task_types),
+    ))
+}
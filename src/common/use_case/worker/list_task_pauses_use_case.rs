@@ -0,0 +1,22 @@
+use axum::http::StatusCode;
+
+use crate::{
+    common::{dto::worker_dto::TaskPauseDTO, repository::task_pause_repository},
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+};
+
+pub async fn execute(context: &Context) -> Result<ResponseDTO<Vec<TaskPauseDTO>>, ErrorDTO> {
+    context.authorize_admin_or_scope("workers.read")?;
+
+    let pauses = task_pause_repository::find_all(context)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        pauses.into_iter().map(TaskPauseDTO::from).collect(),
+    ))
+}
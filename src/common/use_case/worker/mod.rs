@@ -0,0 +1,4 @@
+pub mod get_worker_scaling_use_case;
+pub mod list_task_pauses_use_case;
+pub mod pause_task_type_use_case;
+pub mod resume_task_type_use_case;
@@ -0,0 +1,60 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::ActiveValue::Set;
+
+use crate::{
+    common::{
+        dto::worker_dto::{PauseTaskTypeDTO, TaskPauseDTO},
+        entity::task_pause,
+        repository::task_pause_repository,
+    },
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        validation::Validate,
+    },
+};
+
+/// Longest `type` tag a task can be paused by, the size of `task_pause.task_type`
+const MAX_TASK_TYPE_LENGTH: usize = 64;
+
+pub async fn execute(
+    context: &Context,
+    task_type: &str,
+    dto: PauseTaskTypeDTO,
+) -> Result<ResponseDTO<TaskPauseDTO>, ErrorDTO> {
+    context.authorize_admin_or_scope("workers.write")?;
+    dto.validate(&context.locale)?;
+    if task_type.is_empty() || task_type.len() > MAX_TASK_TYPE_LENGTH {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::TaskTypeInvalid,
+            t!("common.task_type_invalid", locale = &context.locale).to_string(),
+        ));
+    }
+
+    // Pausing again keeps the original pause, so retries during an incident are harmless
+    if let Some(pause) = task_pause_repository::find_by_task_type(context, task_type)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+    {
+        return Ok(ResponseDTO::new(StatusCode::OK, pause.into()));
+    }
+
+    let pause = task_pause_repository::create(
+        context,
+        task_pause::ActiveModel {
+            task_type: Set(task_type.to_string()),
+            reason: Set(dto.reason),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+    tracing::warn!(
+        task_type,
+        paused_by = %context.actor(),
+        "Task type paused, workers keep its tasks queued"
+    );
+
+    Ok(ResponseDTO::new(StatusCode::OK, pause.into()))
+}
@@ -0,0 +1,32 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    common::repository::task_pause_repository,
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+};
+
+pub async fn execute(context: &Context, task_type: &str) -> Result<ResponseDTO<()>, ErrorDTO> {
+    context.authorize_admin_or_scope("workers.write")?;
+
+    let resumed = task_pause_repository::delete_by_task_type(context, task_type)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    if !resumed {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::TaskTypeNotPaused,
+            t!(
+                "common.task_type_not_paused",
+                task_type = task_type,
+                locale = &context.locale
+            )
+            .to_string(),
+        ));
+    }
+    tracing::info!(task_type, resumed_by = %context.actor(), "Task type resumed");
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -2,7 +2,7 @@ use axum::http::{StatusCode, header};
 use rmcp::{ErrorData as McpError, RoleServer, service::RequestContext};
 use serde_json::{Value, json};
 
-use crate::pkg::url::url_encode;
+use crate::pkg::{http_client::HttpClient, url::url_encode};
 
 use super::error::mcp_error_code_from_status;
 use http::request::Parts;
@@ -10,15 +10,12 @@ use http::request::Parts;
 #[derive(Clone)]
 pub struct HttpApiClient {
     base_url: String,
-    client: reqwest::Client,
+    client: HttpClient,
 }
 
 impl HttpApiClient {
-    pub fn new(base_url: String) -> Self {
-        Self {
-            base_url,
-            client: reqwest::Client::new(),
-        }
+    pub fn new(client: HttpClient, base_url: String) -> Self {
+        Self { base_url, client }
     }
 
     pub async fn get(
@@ -49,7 +46,11 @@ impl HttpApiClient {
 
         request = request.header(header::ACCEPT.as_str(), "application/json");
 
-        let response = request.send().await.map_err(internal_mcp_error)?;
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(internal_mcp_error)?;
         let status = response.status();
         let body = response.text().await.map_err(internal_mcp_error)?;
 
@@ -1,24 +1,75 @@
 use std::{sync::Arc, time::Duration};
 
+use anyhow::Context;
+
 use crate::{
+    common::api::task_ws,
     config::{
+        diagnostics::StartupDiagnostics,
+        drain::{Drain, drain_connections},
         setting::{MessageBrokerType, Setting},
         shutdown::wait_for_shutdown_signal,
     },
     core::{
-        api::route::{OPENAPI_JSON_PATH, SWAGGER_UI_PATH, get_route},
+        api::{
+            frontend::with_frontend,
+            route::{OPENAPI_JSON_PATH, SWAGGER_UI_PATH, get_route},
+        },
+        r#async::{
+            ArchivingProducer, Outbox, PeriodicJob, PurgeBroadcastEvents, RecoverExpiredTaskLeases,
+            RelayOutbox, Scheduler, TaskRegistry, worker,
+        },
         db::connection::get_db,
-        layer::{cors_layer::get_cors_layer, trace_layer::get_trace_layer},
+        event::{EventBus, EventSubscriber},
+        event_type,
+        id::{IdGenerator, RandomIdGenerator},
+        layer::{
+            cors_layer::get_cors_layer,
+            deprecation_layer::{DeprecationRegistry, deprecation_middleware},
+            load_shed_layer::{LoadShedder, load_shed_middleware},
+            request_signing_layer::{RequestSigning, request_signing_middleware},
+            request_stats_layer::{request_stats_middleware, start_clock},
+            route_group_layer::route_group_middleware,
+            service_auth_layer::service_auth_middleware,
+            statement_budget_layer::{record_statement, statement_budget_middleware},
+            timeout_layer::request_timeout_middleware,
+            trace_layer::get_trace_layer,
+        },
+        lifecycle::{LifecycleHook, ordered, run_shutdown_hooks, run_startup_hooks},
+        module::{Module, ScheduledJob},
+        service_account::ServiceAccounts,
     },
+    file::FileModule,
+    notification::NotificationModule,
+    oauth_provider::OAuthProviderModule,
     pkg::{
-        broadcast::forwarder::{ForwarderConfig, ShutdownSignal, create_forwarder},
-        messaging::{MessageProducer, create_producer},
+        broadcast::forwarder::{
+            ForwarderConfig, MessageForwarder, RedisForwarder, ShutdownSignal, create_forwarder,
+            enable_coalescing,
+        },
+        cache::{
+            InMemoryNonceStore, LoginAttemptStore, NonceStore, RedisLoginAttemptStore,
+            RedisNonceStore, RedisResponseCache, RedisTaskDeduplicator, RedisTaskQuota,
+            ResponseCache, TaskDeduplicator, TaskQuota,
+        },
+        circuit_breaker,
+        http_client::HttpClient,
+        messaging::{
+            CircuitBreakingProducer, MessageProducer, ProducerConfig, RedisProducer,
+            RoutingProducer, TaskHandler, create_producer,
+        },
+        mtls::{self, ClientIdentity, MtlsListener},
+        redis::RedisConnectionManager,
         url::UrlBuilder,
     },
+    report::ReportModule,
+    user::UserModule,
 };
 use axum::Router;
+use http::Extensions;
 use sea_orm::DatabaseConnection;
-use tokio::{net::TcpListener, sync::watch, task::JoinHandle, time::sleep};
+use serde::de::DeserializeOwned;
+use tokio::{net::TcpListener, sync::watch, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
 
 #[derive(Clone)]
@@ -27,12 +78,511 @@ pub struct AppState {
     pub setting: Setting,
     pub producer: Option<Arc<Box<dyn MessageProducer>>>,
     pub shutdown_token: CancellationToken,
+    pub id_generator: Arc<dyn IdGenerator>,
+    /// Cache behind routes wrapped with `response_cache_layer`, `None` when disabled
+    pub response_cache: Option<Arc<dyn ResponseCache>>,
+    /// Store of the tasks recent user actions started, `None` when deduplication is disabled
+    pub task_dedup: Option<Arc<dyn TaskDeduplicator>>,
+    /// Tasks each user has running and started today, `None` when quotas are disabled
+    pub task_quota: Option<Arc<dyn TaskQuota>>,
+    /// Failed sign-ins kept in Redis, `None` when they are kept in the `login_attempt` table
+    pub login_attempts: Option<Arc<dyn LoginAttemptStore>>,
+    /// Where tasks published in a transaction wait for it to commit, `None` when disabled or
+    /// without a producer
+    pub outbox: Option<Arc<Outbox>>,
+    /// Nonces of the signed requests already served, `None` when request signing is disabled
+    pub nonce_store: Option<Arc<dyn NonceStore>>,
+    /// Redis connection shared by the producer, forwarder and caches, `None` when unused
+    pub redis: Option<RedisConnectionManager>,
+    /// Outbound HTTP client shared by integrations calling third-party APIs
+    pub http_client: HttpClient,
+    /// Values registered with `AppBuilder::add_state`
+    pub extensions: Arc<Extensions>,
+    /// Subscribers of the domain events emitted by use cases
+    pub event_bus: Arc<EventBus>,
+    /// Routes declared deprecated by modules, with their usage
+    pub deprecations: Arc<DeprecationRegistry>,
+    /// Drain of the WebSocket and HTTP layers, started on shutdown or by an admin
+    pub drain: Arc<Drain>,
+}
+
+impl AppState {
+    /// State registered with `AppBuilder::add_state`, if any
+    pub fn state<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions.get::<T>()
+    }
 }
 
 pub struct App {
     listener: TcpListener,
     pub base_url: String,
+    /// Address of the listener service accounts authenticate to by client certificate,
+    /// `None` unless `MTLS_ENABLED`
+    pub mtls_base_url: Option<String>,
+    mtls: Option<(MtlsListener, Arc<ServiceAccounts>)>,
     pub app_state: AppState,
+    routers: Vec<Router<AppState>>,
+    modules: Vec<Arc<dyn Module>>,
+    shutdown_hooks: Vec<Arc<dyn LifecycleHook>>,
+}
+
+/// Assembles an `App`, letting downstream projects add routes, state and task handlers
+/// without modifying this module
+pub struct AppBuilder {
+    setting: Setting,
+    db: Option<DatabaseConnection>,
+    producer: Option<Arc<Box<dyn MessageProducer>>>,
+    id_generator: Arc<dyn IdGenerator>,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    task_dedup: Option<Arc<dyn TaskDeduplicator>>,
+    task_quota: Option<Arc<dyn TaskQuota>>,
+    login_attempts: Option<Arc<dyn LoginAttemptStore>>,
+    nonce_store: Option<Arc<dyn NonceStore>>,
+    redis: Option<RedisConnectionManager>,
+    http_client: Option<HttpClient>,
+    routers: Vec<Router<AppState>>,
+    modules: Vec<Arc<dyn Module>>,
+    tasks: TaskRegistry,
+    extensions: Extensions,
+    event_bus: EventBus,
+    startup_hooks: Vec<Arc<dyn LifecycleHook>>,
+    shutdown_hooks: Vec<Arc<dyn LifecycleHook>>,
+}
+
+impl AppBuilder {
+    /// Use an existing database connection instead of connecting to `database_url`
+    pub fn db(mut self, db: DatabaseConnection) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Use this message producer instead of the configured broker (e.g. an in-process one in tests)
+    pub fn producer(mut self, producer: Arc<Box<dyn MessageProducer>>) -> Self {
+        self.producer = Some(producer);
+        self
+    }
+
+    /// Replace the task id / OTP generator (e.g. with a deterministic one in tests)
+    pub fn id_generator(mut self, id_generator: Arc<dyn IdGenerator>) -> Self {
+        self.id_generator = id_generator;
+        self
+    }
+
+    /// Use this response cache instead of the Redis one enabled by `RESPONSE_CACHE_ENABLED`
+    pub fn response_cache(mut self, response_cache: Arc<dyn ResponseCache>) -> Self {
+        self.response_cache = Some(response_cache);
+        self
+    }
+
+    /// Use this task deduplication store instead of the Redis one enabled by
+    /// `TASK_DEDUP_WINDOW_SECONDS`
+    pub fn task_dedup(mut self, task_dedup: Arc<dyn TaskDeduplicator>) -> Self {
+        self.task_dedup = Some(task_dedup);
+        self
+    }
+
+    /// Use this task quota store instead of the Redis one enabled by `TASK_QUOTA_MAX_RUNNING`
+    /// or `TASK_QUOTA_MAX_DAILY`
+    pub fn task_quota(mut self, task_quota: Arc<dyn TaskQuota>) -> Self {
+        self.task_quota = Some(task_quota);
+        self
+    }
+
+    /// Count failed sign-ins in this store instead of the `login_attempt` table or the Redis
+    /// store enabled by `LOGIN_ATTEMPT_STORE=redis`
+    pub fn login_attempts(mut self, login_attempts: Arc<dyn LoginAttemptStore>) -> Self {
+        self.login_attempts = Some(login_attempts);
+        self
+    }
+
+    /// Use this nonce store instead of the Redis one enabled by `REQUEST_SIGNING_SECRET`
+    pub fn nonce_store(mut self, nonce_store: Arc<dyn NonceStore>) -> Self {
+        self.nonce_store = Some(nonce_store);
+        self
+    }
+
+    /// Use this Redis connection instead of opening the shared one for `REDIS_URL`
+    pub fn redis(mut self, redis: RedisConnectionManager) -> Self {
+        self.redis = Some(redis);
+        self
+    }
+
+    /// Use this HTTP client instead of building one from the `HTTP_CLIENT_*` settings
+    pub fn http_client(mut self, http_client: HttpClient) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Serve additional routes alongside the built-in API
+    pub fn merge_router(mut self, router: Router<AppState>) -> Self {
+        self.routers.push(router);
+        self
+    }
+
+    /// Register a module's routes, migrations, task handlers and scheduled jobs
+    pub fn module(mut self, module: impl Module + 'static) -> Self {
+        module.register_tasks(&mut self.tasks);
+        for subscriber in module.event_subscribers() {
+            self.event_bus = self.event_bus.with(subscriber);
+        }
+        self.modules.push(Arc::new(module));
+        self
+    }
+
+    /// Handle tasks tagged `task_type` in the worker; see `TaskRegistry::register`
+    pub fn add_task_handler<T, H>(mut self, task_type: impl Into<String>, handler: H) -> Self
+    where
+        T: Clone + Send + Sync + DeserializeOwned + 'static,
+        H: TaskHandler<T> + 'static,
+    {
+        self.tasks.register(task_type, handler);
+        self
+    }
+
+    /// React to the domain events use cases emit, after the built-in subscribers
+    pub fn event_subscriber(mut self, subscriber: Arc<dyn EventSubscriber>) -> Self {
+        self.event_bus = self.event_bus.with(subscriber);
+        self
+    }
+
+    /// Run `hook` once `build` has initialized shared state, after the module hooks of the
+    /// same order; a failing hook makes `build` fail
+    pub fn on_startup(mut self, hook: Arc<dyn LifecycleHook>) -> Self {
+        self.startup_hooks.push(hook);
+        self
+    }
+
+    /// Run `hook` after the server stops accepting connections, before the database closes
+    pub fn on_shutdown(mut self, hook: Arc<dyn LifecycleHook>) -> Self {
+        self.shutdown_hooks.push(hook);
+        self
+    }
+
+    /// Make a value available to handlers through `AppState::state`
+    pub fn add_state<T: Clone + Send + Sync + 'static>(mut self, state: T) -> Self {
+        self.extensions.insert(state);
+        self
+    }
+
+    pub fn setting(&self) -> &Setting {
+        &self.setting
+    }
+
+    pub fn modules(&self) -> &[Arc<dyn Module>] {
+        &self.modules
+    }
+
+    /// Jobs scheduled by every registered module
+    pub fn scheduled_jobs(&self) -> Vec<ScheduledJob> {
+        self.modules
+            .iter()
+            .flat_map(|module| module.scheduled_jobs())
+            .collect()
+    }
+
+    /// Bind the listener and initialize shared state for the HTTP server
+    pub async fn build(self) -> Result<App, anyhow::Error> {
+        let Self {
+            mut setting,
+            db,
+            producer,
+            id_generator,
+            response_cache,
+            task_dedup,
+            task_quota,
+            login_attempts,
+            nonce_store,
+            redis,
+            http_client,
+            routers,
+            modules,
+            tasks: _,
+            extensions,
+            event_bus,
+            startup_hooks,
+            shutdown_hooks,
+        } = self;
+
+        let mut db = match db {
+            Some(db) => db,
+            None => get_db(&setting.database_url).await?,
+        };
+        db.set_metric_callback(record_statement);
+
+        // Forwarded broadcasts and WebSocket commands are checked against these schemas
+        for schema in task_ws::command_schemas()
+            .into_iter()
+            .chain(event_type::message_schemas())
+            .chain(modules.iter().flat_map(|module| module.message_schemas()))
+        {
+            schema.register()?;
+        }
+
+        // Bind using the configured host/port, then persist the actual socket address.
+        let base_url = UrlBuilder::new(&setting.app_host)
+            .port(setting.app_port)
+            .build();
+        let listener = TcpListener::bind(base_url.as_str()).await?;
+        let local_addr = listener.local_addr()?;
+        setting.app_host = local_addr.ip().to_string();
+        setting.app_port = local_addr.port();
+
+        // Internal callers reach the same routes over mutual TLS, as service principals
+        let mtls = if setting.mtls.enabled {
+            let config = mtls::server_config(
+                setting
+                    .mtls
+                    .cert_path
+                    .as_deref()
+                    .context("MTLS_CERT_PATH is not set")?,
+                setting
+                    .mtls
+                    .key_path
+                    .as_deref()
+                    .context("MTLS_KEY_PATH is not set")?,
+                setting
+                    .mtls
+                    .client_ca_path
+                    .as_deref()
+                    .context("MTLS_CLIENT_CA_PATH is not set")?,
+            )?;
+            let mtls_url = UrlBuilder::new(&setting.app_host)
+                .port(setting.mtls.port)
+                .build();
+            let listener = TcpListener::bind(mtls_url.as_str()).await?;
+            let mtls_addr = listener.local_addr()?;
+            setting.mtls.port = mtls_addr.port();
+            Some((
+                MtlsListener::new(listener, config),
+                Arc::new(setting.mtls.service_accounts()?),
+            ))
+        } else {
+            None
+        };
+        let mtls_base_url = mtls
+            .as_ref()
+            .map(|_| format!("{}:{}", setting.app_host, setting.mtls.port));
+
+        // One Redis connection for everything in this process that talks to Redis
+        let uses_redis = setting.response_cache.enabled
+            || setting.task_dedup.window_seconds > 0
+            || setting.task_quota.is_enabled()
+            || setting.login_lockout.uses_redis()
+            || setting.request_signing.signer().is_some()
+            || setting.messaging.message_broker == Some(MessageBrokerType::Redis);
+        let redis = match redis {
+            Some(redis) => Some(redis),
+            None if uses_redis => match setting.get_redis().await {
+                Ok(redis) => Some(redis),
+                Err(e) => {
+                    tracing::warn!("Redis unavailable: {:?}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // Initialize message producer (optional)
+        circuit_breaker::configure(setting.circuit_breaker.clone());
+        let producer = match (producer, setting.to_producer_config(), &redis) {
+            (Some(producer), _, _) => Some(producer),
+            (
+                None,
+                Some(ProducerConfig::Redis {
+                    default_channel, ..
+                }),
+                Some(redis),
+            ) => {
+                let p: Box<dyn MessageProducer> =
+                    Box::new(RedisProducer::from_manager(redis, &default_channel));
+                let p: Box<dyn MessageProducer> =
+                    Box::new(CircuitBreakingProducer::new(Arc::new(p)));
+                tracing::info!("Message producer initialized successfully");
+                Some(Arc::new(p))
+            }
+            (None, Some(producer_config), _) => {
+                let p = create_producer(producer_config).await?;
+                let p: Box<dyn MessageProducer> =
+                    Box::new(CircuitBreakingProducer::new(Arc::new(p)));
+                tracing::info!("Message producer initialized successfully");
+                Some(Arc::new(p))
+            }
+            (None, None, _) => {
+                tracing::info!("Message producer disabled (no broker configured)");
+                None
+            }
+        };
+        let producer = match producer {
+            Some(producer) if setting.broadcast_archive.enabled => {
+                tracing::info!("Broadcasts archived to broadcast_event");
+                let p: Box<dyn MessageProducer> =
+                    Box::new(ArchivingProducer::new(producer, db.clone()));
+                Some(Arc::new(p))
+            }
+            producer => producer,
+        };
+        let producer = match (producer, setting.messaging.destination_router()?) {
+            (Some(producer), Some(router)) => {
+                tracing::info!("Task destinations routed by MESSAGE_ROUTES");
+                let p: Box<dyn MessageProducer> = Box::new(RoutingProducer::new(producer, router));
+                Some(Arc::new(p))
+            }
+            (producer, _) => producer,
+        };
+
+        let http_client = match http_client {
+            Some(http_client) => http_client,
+            None => setting.get_http_client()?,
+        };
+
+        // A cache that cannot be reached only costs performance, so serve without it
+        let response_cache = match response_cache {
+            Some(response_cache) => Some(response_cache),
+            None if setting.response_cache.enabled => match &redis {
+                Some(redis) => {
+                    tracing::info!("Response cache initialized successfully");
+                    Some(Arc::new(RedisResponseCache::from_manager(redis)) as Arc<dyn ResponseCache>)
+                }
+                None => {
+                    tracing::warn!("Response cache disabled: Redis is unavailable");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // Without the store duplicate actions enqueue duplicate tasks, as they did before
+        let task_dedup = match task_dedup {
+            Some(task_dedup) => Some(task_dedup),
+            None if setting.task_dedup.window_seconds > 0 => match &redis {
+                Some(redis) => Some(Arc::new(RedisTaskDeduplicator::from_manager(
+                    redis,
+                    Duration::from_secs(setting.task_dedup.window_seconds),
+                )) as Arc<dyn TaskDeduplicator>),
+                None => {
+                    tracing::warn!("Task deduplication disabled: Redis is unavailable");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // Without the store users start as many tasks as they like, as they did before
+        let task_quota = match task_quota {
+            Some(task_quota) => Some(task_quota),
+            None if setting.task_quota.is_enabled() => match &redis {
+                Some(redis) => Some(Arc::new(RedisTaskQuota::from_manager(
+                    redis,
+                    setting.task_quota.limits(),
+                )) as Arc<dyn TaskQuota>),
+                None => {
+                    tracing::warn!("Task quotas disabled: Redis is unavailable");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // Failed sign-ins fall back to the `login_attempt` table, so lockouts stay enforced
+        let login_attempts = match login_attempts {
+            Some(login_attempts) => Some(login_attempts),
+            None if setting.login_lockout.uses_redis() => match &redis {
+                Some(redis) => Some(Arc::new(RedisLoginAttemptStore::from_manager(
+                    redis,
+                    setting.login_lockout.cooldown(),
+                )) as Arc<dyn LoginAttemptStore>),
+                None => {
+                    tracing::warn!("Failed sign-ins counted in the database: Redis is unavailable");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // Replays are still caught per instance when Redis is down, so signing stays enforced
+        let nonce_store = match nonce_store {
+            Some(nonce_store) => Some(nonce_store),
+            None if setting.request_signing.signer().is_some() => match &redis {
+                Some(redis) => {
+                    Some(Arc::new(RedisNonceStore::from_manager(redis)) as Arc<dyn NonceStore>)
+                }
+                None => {
+                    tracing::warn!(
+                        "Request nonces tracked in memory: Redis is unavailable, replays to other instances go unnoticed"
+                    );
+                    Some(Arc::new(InMemoryNonceStore::new()) as Arc<dyn NonceStore>)
+                }
+            },
+            None => None,
+        };
+
+        let deprecations = DeprecationRegistry::new(
+            modules
+                .iter()
+                .flat_map(|module| module.deprecated_routes())
+                .collect(),
+        );
+
+        // Without it, tasks are published as they are enqueued, even if the transaction then
+        // rolls back
+        let outbox = producer
+            .clone()
+            .filter(|_| setting.outbox.enabled)
+            .map(|producer| Arc::new(Outbox::new(db.clone(), producer, setting.outbox.clone())));
+
+        let app_state = AppState {
+            db,
+            setting,
+            producer,
+            outbox,
+            shutdown_token: CancellationToken::new(),
+            id_generator,
+            response_cache,
+            task_dedup,
+            task_quota,
+            login_attempts,
+            nonce_store,
+            redis,
+            http_client,
+            extensions: Arc::new(extensions),
+            event_bus: Arc::new(event_bus),
+            deprecations: Arc::new(deprecations),
+            drain: Arc::default(),
+        };
+
+        let startup_hooks = ordered(
+            modules
+                .iter()
+                .flat_map(|module| module.startup_hooks())
+                .chain(startup_hooks)
+                .collect(),
+        );
+        run_startup_hooks(&startup_hooks, &app_state).await?;
+
+        let shutdown_hooks = ordered(
+            modules
+                .iter()
+                .flat_map(|module| module.shutdown_hooks())
+                .chain(shutdown_hooks)
+                .collect(),
+        );
+
+        Ok(App {
+            listener,
+            base_url: local_addr.to_string(),
+            mtls_base_url,
+            mtls,
+            app_state,
+            routers,
+            modules,
+            shutdown_hooks,
+        })
+    }
+
+    /// Run the background worker with the built-in and registered task handlers
+    pub async fn run_worker(self) -> anyhow::Result<()> {
+        worker::run_with_tasks(self.setting, self.tasks).await
+    }
 }
 
 fn print_startup_banner(server_url: &str) {
@@ -74,71 +624,83 @@ fn print_startup_banner(server_url: &str) {
 }
 
 impl App {
+    pub fn builder(setting: Setting) -> AppBuilder {
+        let report = ReportModule::new(&setting.report);
+        AppBuilder {
+            setting,
+            db: None,
+            producer: None,
+            id_generator: Arc::new(RandomIdGenerator),
+            response_cache: None,
+            task_dedup: None,
+            task_quota: None,
+            login_attempts: None,
+            nonce_store: None,
+            redis: None,
+            http_client: None,
+            routers: Vec::new(),
+            modules: Vec::new(),
+            tasks: TaskRegistry::default(),
+            extensions: Extensions::new(),
+            event_bus: EventBus::default(),
+            startup_hooks: Vec::new(),
+            shutdown_hooks: Vec::new(),
+        }
+        .module(UserModule)
+        .module(FileModule)
+        .module(NotificationModule)
+        .module(OAuthProviderModule)
+        .module(report)
+    }
+
     pub async fn new(setting: Setting) -> Result<Self, anyhow::Error> {
-        let db = get_db(&setting.database_url).await?;
-        Self::new_with_db(setting, db).await
+        Self::builder(setting).build().await
     }
 
     pub async fn new_with_db(
         setting: Setting,
         db: DatabaseConnection,
     ) -> Result<Self, anyhow::Error> {
-        // Bind using the configured host/port, then persist the actual socket address.
-        let base_url = UrlBuilder::new(&setting.app_host)
-            .port(setting.app_port)
-            .build();
-        let listener = TcpListener::bind(base_url.as_str()).await?;
-        let local_addr = listener.local_addr()?;
-        let mut setting = setting;
-        setting.app_host = local_addr.ip().to_string();
-        setting.app_port = local_addr.port();
-
-        // Initialize message producer (optional)
-        let producer = if let Some(producer_config) = setting.to_producer_config() {
-            let p = create_producer(producer_config).await?;
-            tracing::info!("Message producer initialized successfully");
-            Some(Arc::new(p))
-        } else {
-            tracing::info!("Message producer disabled (no broker configured)");
-            None
-        };
+        Self::builder(setting).db(db).build().await
+    }
 
-        Ok(Self {
-            listener,
-            base_url: local_addr.to_string(),
-            app_state: AppState {
-                db,
-                setting,
-                producer,
-                shutdown_token: CancellationToken::new(),
-            },
-        })
+    fn forwarder_config(setting: &Setting, broker_type: MessageBrokerType) -> ForwarderConfig {
+        match broker_type {
+            MessageBrokerType::Kafka => ForwarderConfig::kafka(
+                setting.messaging.kafka_brokers.clone(),
+                "broadcasts".to_string(),
+                setting.messaging.kafka_consumer_group.clone(),
+            ),
+            MessageBrokerType::Redis => ForwarderConfig::redis(
+                setting.messaging.redis_url.clone(),
+                "broadcasts".to_string(),
+            ),
+            MessageBrokerType::RabbitMQ => ForwarderConfig::rabbitmq(
+                setting.messaging.rabbitmq_url.clone(),
+                "broadcasts".to_string(),
+            ),
+        }
+        .with_failover(setting.messaging.failover.clone())
+        .with_security(setting.messaging.security.clone())
     }
 
     fn spawn_message_forwarder(
         setting: Setting,
+        redis: Option<RedisConnectionManager>,
         shutdown: ShutdownSignal,
     ) -> Option<JoinHandle<()>> {
         let broker_type = setting.messaging.message_broker.clone()?;
 
         Some(tokio::spawn(async move {
-            let forwarder_config = match broker_type {
-                MessageBrokerType::Kafka => ForwarderConfig::kafka(
-                    setting.messaging.kafka_brokers.clone(),
-                    "broadcasts".to_string(),
-                    setting.messaging.kafka_consumer_group.clone(),
-                ),
-                MessageBrokerType::Redis => ForwarderConfig::redis(
-                    setting.messaging.redis_url.clone(),
-                    "broadcasts".to_string(),
-                ),
-                MessageBrokerType::RabbitMQ => ForwarderConfig::rabbitmq(
-                    setting.messaging.rabbitmq_url.clone(),
-                    "broadcasts".to_string(),
-                ),
+            let forwarder = match (&broker_type, redis) {
+                (MessageBrokerType::Redis, Some(redis)) => {
+                    Ok(Box::new(RedisForwarder::from_manager(redis, "broadcasts"))
+                        as Box<dyn MessageForwarder>)
+                }
+                _ => create_forwarder(Self::forwarder_config(&setting, broker_type)).await,
             };
 
-            match create_forwarder(forwarder_config).await {
+            match forwarder {
                 Ok(forwarder) => {
                     tracing::info!("✓ Message forwarder started for progress updates");
                     if let Err(e) = forwarder.start_forwarding(shutdown).await {
@@ -156,30 +718,130 @@ impl App {
         let Self {
             listener,
             base_url,
+            mtls_base_url,
+            mtls,
             app_state,
+            routers,
+            modules,
+            shutdown_hooks,
         } = self;
 
+        start_clock();
+        if let Some(window) = app_state.setting.messaging.broadcast_coalesce_window() {
+            enable_coalescing(window);
+        }
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
-        let forwarder_handle =
-            Self::spawn_message_forwarder(app_state.setting.clone(), shutdown_rx);
+        let forwarder_handle = Self::spawn_message_forwarder(
+            app_state.setting.clone(),
+            app_state.redis.clone(),
+            shutdown_rx,
+        );
 
         let server_url = format!("http://{}", base_url);
         print_startup_banner(&server_url);
+        StartupDiagnostics::collect(&app_state.setting, &base_url, &modules, &app_state.db)
+            .await
+            .log();
+
+        let periodic_jobs = modules
+            .iter()
+            .flat_map(|module| module.periodic_jobs())
+            .chain([
+                Arc::new(PurgeBroadcastEvents) as Arc<dyn PeriodicJob>,
+                Arc::new(RecoverExpiredTaskLeases),
+                Arc::new(RelayOutbox),
+            ])
+            .collect();
+        let scheduler_handle = Scheduler::new(periodic_jobs, app_state.setting.scheduler.clone())
+            .spawn(app_state.clone());
 
         let db = app_state.db.clone();
+        let redis = app_state.redis.clone();
+        let hook_state = app_state.clone();
+        let drain = app_state.drain.clone();
+        let drain_timeout = Duration::from_secs(app_state.setting.drain_timeout_seconds);
         let shutdown_token = app_state.shutdown_token.clone();
-        let app = Router::new()
-            .merge(get_route(app_state.clone()))
-            .with_state(app_state)
+        let scheduler_shutdown_token = shutdown_token.clone();
+        let load_shedder = Arc::new(LoadShedder::new(&app_state.setting.load_shed));
+        let request_timeout = Arc::new(app_state.setting.request_timeout.clone());
+        let deprecations = app_state.deprecations.clone();
+        let statement_budget = Arc::new(app_state.setting.statement_budget.clone());
+        let request_signing = app_state.nonce_store.clone().and_then(|nonce_store| {
+            RequestSigning::new(&app_state.setting.request_signing, nonce_store).map(Arc::new)
+        });
+        let frontend = app_state.setting.frontend.clone();
+        let disabled_route_groups = Arc::new(app_state.setting.route_groups.disabled_groups());
+        let app = modules
+            .iter()
+            .map(|module| module.routes(&app_state))
+            .chain(routers)
+            .fold(get_route(app_state.clone()), Router::merge);
+        let app = with_frontend(app, &frontend).with_state(app_state).layer(
+            axum::middleware::from_fn_with_state(statement_budget, statement_budget_middleware),
+        );
+        let app = match request_signing {
+            Some(request_signing) => app.layer(axum::middleware::from_fn_with_state(
+                request_signing,
+                request_signing_middleware,
+            )),
+            None => app,
+        };
+        let app = if disabled_route_groups.is_empty() {
+            app
+        } else {
+            app.layer(axum::middleware::from_fn_with_state(
+                disabled_route_groups,
+                route_group_middleware,
+            ))
+        };
+        let app = app
+            .layer(axum::middleware::from_fn_with_state(
+                deprecations,
+                deprecation_middleware,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                request_timeout,
+                request_timeout_middleware,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                load_shedder,
+                load_shed_middleware,
+            ))
+            .layer(axum::middleware::from_fn(request_stats_middleware))
             .layer(get_cors_layer())
             .layer(get_trace_layer());
 
+        let mtls_handle = mtls.map(|(listener, accounts)| {
+            tracing::info!(
+                "✓ Serving service accounts over mTLS on {}",
+                mtls_base_url.unwrap_or_default()
+            );
+            let app = app.clone().layer(axum::middleware::from_fn_with_state(
+                accounts,
+                service_auth_middleware,
+            ));
+            let shutdown = scheduler_shutdown_token.clone().cancelled_owned();
+            tokio::spawn(async move {
+                axum::serve(
+                    listener,
+                    app.into_make_service_with_connect_info::<ClientIdentity>(),
+                )
+                .with_graceful_shutdown(shutdown)
+                .await
+            })
+        });
+
+        // WebSockets are drained first, so their clients get the broadcasts still buffered
+        // and a notice to reconnect before HTTP stops
         let shutdown_server = {
             let shutdown_tx = shutdown_tx.clone();
             async move {
-                wait_for_shutdown_signal().await;
+                tokio::select! {
+                    _ = wait_for_shutdown_signal() => {}
+                    _ = drain.started() => {}
+                }
+                drain_connections(&drain, &shutdown_tx, forwarder_handle, drain_timeout).await;
                 shutdown_token.cancel();
-                let _ = shutdown_tx.send(true);
             }
         };
 
@@ -188,25 +850,29 @@ impl App {
             .await;
 
         let _ = shutdown_tx.send(true);
-
-        if let Some(mut handle) = forwarder_handle {
-            tokio::select! {
-                join_result = &mut handle => {
-                    if let Err(error) = join_result {
-                        tracing::warn!("Message forwarder task ended unexpectedly: {}", error);
-                    }
-                }
-                _ = sleep(Duration::from_secs(5)) => {
-                    tracing::warn!("Message forwarder did not stop in time; aborting task");
-                    handle.abort();
-                    if let Err(error) = handle.await
-                        && !error.is_cancelled()
-                    {
-                        tracing::warn!("Message forwarder task ended unexpectedly: {}", error);
-                    }
+        scheduler_shutdown_token.cancel();
+        if let Some(handle) = mtls_handle {
+            match tokio::time::timeout(Duration::from_secs(5), handle).await {
+                Ok(Ok(Err(error))) => tracing::warn!("mTLS listener failed: {}", error),
+                Ok(Err(error)) => {
+                    tracing::warn!("mTLS listener task ended unexpectedly: {}", error)
                 }
+                Err(_) => tracing::warn!("mTLS listener did not stop in time"),
+                Ok(Ok(Ok(()))) => {}
             }
         }
+        if tokio::time::timeout(Duration::from_secs(5), scheduler_handle)
+            .await
+            .is_err()
+        {
+            tracing::warn!("Periodic jobs did not stop in time");
+        }
+
+        run_shutdown_hooks(&shutdown_hooks, &hook_state).await;
+
+        if let Some(redis) = redis {
+            tracing::info!(metrics = ?redis.metrics(), "Redis connection pool closing");
+        }
 
         if let Err(error) = db.close().await {
             tracing::warn!("Failed to close database connection cleanly: {}", error);
@@ -0,0 +1,316 @@
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use migration::MigratorTrait;
+
+use crate::{
+    config::{
+        app::{App, AppBuilder},
+        doctor::{render_report, run_checks},
+        schema,
+        setting::Setting,
+        telemetry::{get_subscriber, init_subscriber},
+    },
+    core::{
+        r#async::cron::init_cron_job,
+        db::{connection::get_db, migrator::AppMigrator},
+        event_type,
+        runbook::{create_admin, rotate_jwt_secret},
+    },
+};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "my-axum",
+    about = "Run the My Axum server and its operational commands"
+)]
+pub struct Cli {
+    /// Defaults to `serve` when omitted.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the HTTP server.
+    Serve,
+    /// Run the background worker and cron jobs.
+    Worker,
+    /// Apply or roll back database migrations.
+    Migrate {
+        #[command(subcommand)]
+        command: Option<MigrateCommand>,
+    },
+    /// Create an admin user, or reset the password and role of an existing one.
+    CreateAdmin {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        password: String,
+    },
+    /// Sign new tokens with a fresh key and schedule the previous key's retirement.
+    RotateJwtSecret {
+        /// Seconds the previous key keeps verifying tokens; defaults to the refresh token lifetime.
+        #[arg(long)]
+        grace_seconds: Option<i64>,
+    },
+    /// Inspect the resolved configuration.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Print the event types broadcast to WebSocket clients and the schemas of their data.
+    EventTypes {
+        /// Print a JSON Schema instead of a Markdown reference.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Validate the configuration and test connections to external services.
+    Doctor {
+        /// Seconds to wait for each connection attempt.
+        #[arg(long, default_value_t = 5)]
+        timeout: u64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateCommand {
+    /// Apply pending migrations (default).
+    Up {
+        /// Number of pending migrations to apply; all when omitted.
+        #[arg(short = 'n', long)]
+        steps: Option<u32>,
+    },
+    /// Roll back applied migrations.
+    Down {
+        /// Number of applied migrations to roll back.
+        #[arg(short = 'n', long, default_value_t = 1)]
+        steps: u32,
+    },
+    /// Show the status of every migration.
+    Status,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Validate the configuration without starting anything.
+    Check,
+    /// Print a JSON Schema of every configuration variable.
+    Schema,
+}
+
+impl Cli {
+    /// Run the selected command against the shared `Setting`
+    pub async fn run(self, setting: Setting) -> anyhow::Result<()> {
+        self.run_with(App::builder(setting)).await
+    }
+
+    /// Run the selected command with a customized app, so downstream projects can
+    /// reuse the CLI with their own routes, state and task handlers
+    pub async fn run_with(self, builder: AppBuilder) -> anyhow::Result<()> {
+        let setting = builder.setting().clone();
+        match self.command.unwrap_or(Command::Serve) {
+            Command::Serve => serve(builder).await,
+            Command::Worker => run_worker(builder).await,
+            Command::Migrate { command } => {
+                AppMigrator::register_modules(builder.modules().to_vec());
+                migrate(
+                    &setting,
+                    command.unwrap_or(MigrateCommand::Up { steps: None }),
+                )
+                .await
+            }
+            Command::CreateAdmin { email, password } => {
+                let (admin, created) = create_admin(&setting, &email, &password).await?;
+                if created {
+                    println!("Created admin user '{}' (id {})", admin.email, admin.id);
+                } else {
+                    println!("Updated admin user '{}' (id {})", admin.email, admin.id);
+                }
+                Ok(())
+            }
+            Command::RotateJwtSecret { grace_seconds } => {
+                let grace = chrono::Duration::seconds(
+                    grace_seconds
+                        .unwrap_or(setting.jwt_refresh_token_expires)
+                        .max(0),
+                );
+                let rotation = rotate_jwt_secret(&setting, grace).await?;
+                println!(
+                    "Signing new tokens with key '{}'; {} previous key(s) retire at {}",
+                    rotation.key.kid, rotation.retired, rotation.retires_at
+                );
+                Ok(())
+            }
+            Command::Config { command } => match command {
+                ConfigCommand::Check => check_config(&setting),
+                ConfigCommand::Schema => {
+                    println!("{}", serde_json::to_string_pretty(&schema::json_schema())?);
+                    Ok(())
+                }
+            },
+            Command::EventTypes { json } => {
+                if json {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&event_type::json_schema())?
+                    );
+                } else {
+                    print!("{}", event_type::markdown());
+                }
+                Ok(())
+            }
+            Command::Doctor { timeout } => doctor(&setting, Duration::from_secs(timeout)).await,
+        }
+    }
+}
+
+/// Run the HTTP server until a shutdown signal is received
+pub async fn serve(builder: AppBuilder) -> anyhow::Result<()> {
+    init_subscriber(get_subscriber("logs/axum"));
+
+    let app = builder.build().await?;
+    app.run_until_stopped().await?;
+    Ok(())
+}
+
+/// Run cron jobs and consume tasks from the configured broker
+pub async fn run_worker(builder: AppBuilder) -> anyhow::Result<()> {
+    init_subscriber(get_subscriber("logs/worker"));
+
+    init_cron_job(builder.setting(), builder.scheduled_jobs()).await?;
+    builder.run_worker().await
+}
+
+async fn migrate(setting: &Setting, command: MigrateCommand) -> anyhow::Result<()> {
+    init_subscriber(get_subscriber("logs/migration"));
+
+    let db = get_db(&setting.database_url).await?;
+    match command {
+        MigrateCommand::Up { steps } => AppMigrator::up(&db, steps).await?,
+        MigrateCommand::Down { steps } => AppMigrator::down(&db, Some(steps)).await?,
+        MigrateCommand::Status => AppMigrator::status(&db).await?,
+    }
+    db.close().await?;
+    Ok(())
+}
+
+fn check_config(setting: &Setting) -> anyhow::Result<()> {
+    let issues = setting.validate();
+    if issues.is_empty() {
+        println!("Configuration is valid");
+        return Ok(());
+    }
+
+    println!("Configuration has {} issue(s):", issues.len());
+    for issue in &issues {
+        println!("  - {}", issue);
+    }
+    Err(anyhow::anyhow!("Invalid configuration"))
+}
+
+async fn doctor(setting: &Setting, timeout: Duration) -> anyhow::Result<()> {
+    let results = run_checks(setting, timeout).await;
+    print!("{}", render_report(&results));
+
+    let failures = results.iter().filter(|result| result.is_failure()).count();
+    if failures > 0 {
+        return Err(anyhow::anyhow!("{} check(s) failed", failures));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::{Cli, Command, ConfigCommand, MigrateCommand};
+
+    #[test]
+    fn defaults_to_serve_without_subcommand() {
+        let cli = Cli::try_parse_from(["my-axum"]).unwrap();
+
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn parses_create_admin_arguments() {
+        let cli = Cli::try_parse_from([
+            "my-axum",
+            "create-admin",
+            "--email",
+            "root@example.com",
+            "--password",
+            "secret123@",
+        ])
+        .unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::CreateAdmin { email, password })
+                if email == "root@example.com" && password == "secret123@"
+        ));
+    }
+
+    #[test]
+    fn parses_migrate_and_config_subcommands() {
+        let migrate = Cli::try_parse_from(["my-axum", "migrate", "down", "-n", "2"]).unwrap();
+        assert!(matches!(
+            migrate.command,
+            Some(Command::Migrate {
+                command: Some(MigrateCommand::Down { steps: 2 })
+            })
+        ));
+
+        let config = Cli::try_parse_from(["my-axum", "config", "check"]).unwrap();
+        assert!(matches!(
+            config.command,
+            Some(Command::Config {
+                command: ConfigCommand::Check
+            })
+        ));
+
+        let schema = Cli::try_parse_from(["my-axum", "config", "schema"]).unwrap();
+        assert!(matches!(
+            schema.command,
+            Some(Command::Config {
+                command: ConfigCommand::Schema
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_doctor_timeout() {
+        let cli = Cli::try_parse_from(["my-axum", "doctor", "--timeout", "2"]).unwrap();
+
+        assert!(matches!(cli.command, Some(Command::Doctor { timeout: 2 })));
+    }
+
+    #[test]
+    fn parses_event_types_format() {
+        let cli = Cli::try_parse_from(["my-axum", "event-types", "--json"]).unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::EventTypes { json: true })
+        ));
+    }
+
+    #[test]
+    fn parses_rotate_jwt_secret_grace() {
+        let cli =
+            Cli::try_parse_from(["my-axum", "rotate-jwt-secret", "--grace-seconds", "60"]).unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::RotateJwtSecret {
+                grace_seconds: Some(60)
+            })
+        ));
+    }
+
+    #[test]
+    fn requires_email_for_create_admin() {
+        assert!(Cli::try_parse_from(["my-axum", "create-admin", "--password", "x"]).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use std::{fmt, path::Path, sync::Arc};
+
+use migration::{MigratorTrait, SchemaManager};
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    config::setting::{MessageBrokerType, Setting},
+    core::{db::migrator::AppMigrator, module::Module},
+    pkg::url::mask_url,
+};
+
+const TEMPLATE_DIR: &str = "src/core/template";
+
+/// What the server resolved at boot, safe to log: credentials are masked
+#[derive(Debug, Clone)]
+pub struct StartupDiagnostics {
+    pub listen_address: String,
+    pub database_backend: &'static str,
+    pub database_url: String,
+    pub brokers: Vec<String>,
+    pub features: Vec<String>,
+    pub migrations: MigrationStatus,
+    pub email_transport: String,
+    pub templates: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationStatus {
+    UpToDate,
+    Pending(usize),
+    NotInitialized,
+    Unknown(String),
+}
+
+impl fmt::Display for MigrationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UpToDate => write!(f, "up to date"),
+            Self::Pending(count) => write!(f, "{} pending", count),
+            Self::NotInitialized => write!(f, "not initialized"),
+            Self::Unknown(error) => write!(f, "unknown ({})", error),
+        }
+    }
+}
+
+impl StartupDiagnostics {
+    /// Collect the summary without the migration status, which needs the database
+    pub fn from_setting(
+        setting: &Setting,
+        listen_address: &str,
+        modules: &[Arc<dyn Module>],
+    ) -> Self {
+        Self {
+            listen_address: listen_address.to_string(),
+            database_backend: database_backend(&setting.database_url),
+            database_url: mask_url(&setting.database_url),
+            brokers: brokers(setting),
+            features: features(setting, modules),
+            migrations: MigrationStatus::Unknown("not checked".to_string()),
+            email_transport: email_transport(setting),
+            templates: templates(),
+        }
+    }
+
+    /// Collect the full summary, checking the migration status against `db`
+    pub async fn collect(
+        setting: &Setting,
+        listen_address: &str,
+        modules: &[Arc<dyn Module>],
+        db: &DatabaseConnection,
+    ) -> Self {
+        Self {
+            migrations: migration_status(db).await,
+            ..Self::from_setting(setting, listen_address, modules)
+        }
+    }
+
+    /// Emit the summary as one structured log event
+    pub fn log(&self) {
+        tracing::info!(
+            listen_address = %self.listen_address,
+            database_backend = self.database_backend,
+            database_url = %self.database_url,
+            brokers = ?self.brokers,
+            features = ?self.features,
+            migrations = %self.migrations,
+            email_transport = %self.email_transport,
+            templates = %self.templates,
+            "Startup diagnostics"
+        );
+    }
+}
+
+fn database_backend(database_url: &str) -> &'static str {
+    match database_url.split_once(':').map(|(scheme, _)| scheme) {
+        Some("postgres" | "postgresql") => "postgres",
+        Some("sqlite") => "sqlite",
+        _ => "unknown",
+    }
+}
+
+fn brokers(setting: &Setting) -> Vec<String> {
+    let messaging = &setting.messaging;
+    let broker = match &messaging.message_broker {
+        Some(MessageBrokerType::Kafka) => format!("kafka ({})", messaging.kafka_brokers),
+        Some(MessageBrokerType::Redis) => format!("redis ({})", mask_url(&messaging.redis_url)),
+        Some(MessageBrokerType::RabbitMQ) => {
+            format!("rabbitmq ({})", mask_url(&messaging.rabbitmq_url))
+        }
+        None => "disabled".to_string(),
+    };
+    vec![
+        format!("tasks: {}", broker),
+        format!("broadcasts: {}", broker),
+    ]
+}
+
+fn features(setting: &Setting, modules: &[Arc<dyn Module>]) -> Vec<String> {
+    let mut features: Vec<String> = modules
+        .iter()
+        .map(|module| format!("module:{}", module.name()))
+        .collect();
+    if setting.scheduler.enabled {
+        features.push("scheduler".to_string());
+    }
+    if setting.response_cache.enabled {
+        features.push("response-cache".to_string());
+    }
+    if setting.task_dedup.window_seconds > 0 {
+        features.push("task-dedup".to_string());
+    }
+    if setting.task_quota.is_enabled() {
+        features.push("task-quota".to_string());
+    }
+    if setting.clamav_address.is_some() {
+        features.push("antivirus".to_string());
+    }
+    if setting.push.fcm_project_id.is_some() {
+        features.push("push:fcm".to_string());
+    }
+    if setting.push.apns_key_path.is_some() {
+        features.push("push:apns".to_string());
+    }
+    if let Some(provider) = &setting.sms.provider {
+        features.push(format!("sms:{:?}", provider).to_lowercase());
+    }
+    if let Some(limit) = setting.page_size_limit {
+        features.push(format!("page-size-limit:{}", limit));
+    }
+    features
+}
+
+fn email_transport(setting: &Setting) -> String {
+    if setting.smtp_user.is_none() || setting.smtp_password.is_none() {
+        return "disabled (SMTP credentials not set)".to_string();
+    }
+    format!(
+        "smtp {}:{} ({})",
+        setting.smtp_host,
+        setting.smtp_port,
+        if setting.smtp_tls { "tls" } else { "plain" }
+    )
+}
+
+fn templates() -> String {
+    if Path::new(TEMPLATE_DIR).is_dir() {
+        format!("filesystem ({})", TEMPLATE_DIR)
+    } else {
+        format!("missing ({} not found)", TEMPLATE_DIR)
+    }
+}
+
+/// Compare applied migrations with the registered ones, without creating the
+/// migration table when the database has never been migrated
+async fn migration_status(db: &DatabaseConnection) -> MigrationStatus {
+    match SchemaManager::new(db).has_table("seaql_migrations").await {
+        Ok(false) => return MigrationStatus::NotInitialized,
+        Ok(true) => {}
+        Err(e) => return MigrationStatus::Unknown(e.to_string()),
+    }
+
+    match AppMigrator::get_pending_migrations(db).await {
+        Ok(pending) if pending.is_empty() => MigrationStatus::UpToDate,
+        Ok(pending) => MigrationStatus::Pending(pending.len()),
+        Err(e) => MigrationStatus::Unknown(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MigrationStatus, StartupDiagnostics};
+    use crate::config::setting::{MessageBrokerType, Setting};
+
+    #[test]
+    fn masks_credentials_in_summary() {
+        let mut setting = Setting::new();
+        setting.database_url = "postgresql://postgres:hunter2@db:5432/my_axum".to_string();
+        setting.messaging.message_broker = Some(MessageBrokerType::RabbitMQ);
+        setting.messaging.rabbitmq_url = "amqp://guest:hunter2@mq:5672/%2f".to_string();
+        setting.smtp_user = Some("mailer".to_string());
+        setting.smtp_password = Some("hunter2".to_string());
+
+        let diagnostics = StartupDiagnostics::from_setting(&setting, "127.0.0.1:8000", &[]);
+
+        assert_eq!(diagnostics.database_backend, "postgres");
+        assert_eq!(
+            diagnostics.database_url,
+            "postgresql://postgres:***@db:5432/my_axum"
+        );
+        assert!(
+            diagnostics
+                .brokers
+                .iter()
+                .all(|broker| broker.contains("guest:***@mq") && !broker.contains("hunter2"))
+        );
+        assert!(!format!("{:?}", diagnostics).contains("hunter2"));
+    }
+
+    #[test]
+    fn reports_disabled_transports() {
+        let mut setting = Setting::new();
+        setting.database_url = "sqlite::memory:".to_string();
+        setting.messaging.message_broker = None;
+        setting.smtp_user = None;
+
+        let diagnostics = StartupDiagnostics::from_setting(&setting, "127.0.0.1:8000", &[]);
+
+        assert_eq!(diagnostics.database_backend, "sqlite");
+        assert_eq!(
+            diagnostics.brokers,
+            vec!["tasks: disabled", "broadcasts: disabled"]
+        );
+        assert!(diagnostics.email_transport.starts_with("disabled"));
+        assert_eq!(MigrationStatus::Pending(2).to_string(), "2 pending");
+    }
+}
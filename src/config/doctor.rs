@@ -0,0 +1,219 @@
+use std::{future::Future, time::Duration};
+
+use tokio::{net::TcpStream, time::timeout};
+
+use crate::{
+    config::setting::{MessageBrokerType, Setting},
+    core::db::connection::get_db,
+    pkg::messaging::create_producer,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail(String),
+    Skip(String),
+}
+
+/// Outcome of one `my-axum doctor` check, with a hint shown when it fails
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub hint: &'static str,
+}
+
+impl CheckResult {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, CheckStatus::Fail(_))
+    }
+}
+
+/// Validate the configuration and try every external dependency, each bounded by `limit`
+pub async fn run_checks(setting: &Setting, limit: Duration) -> Vec<CheckResult> {
+    vec![
+        check_config(setting),
+        check_database(setting, limit).await,
+        check_broker(setting, limit).await,
+        check_redis(setting, limit).await,
+        check_smtp(setting, limit).await,
+    ]
+}
+
+fn check_config(setting: &Setting) -> CheckResult {
+    let issues = setting.validate();
+    CheckResult {
+        name: "Configuration",
+        status: if issues.is_empty() {
+            CheckStatus::Pass
+        } else {
+            CheckStatus::Fail(issues.join("; "))
+        },
+        hint: "Fix the listed variables in .env; see the Environment section of the README",
+    }
+}
+
+async fn check_database(setting: &Setting, limit: Duration) -> CheckResult {
+    let status = probe(limit, async {
+        let db = get_db(&setting.database_url).await?;
+        db.ping().await?;
+        db.close().await?;
+        Ok(())
+    })
+    .await;
+
+    CheckResult {
+        name: "Database",
+        status,
+        hint: "Check DATABASE_URL and that the database is running (`make docker-dev`)",
+    }
+}
+
+async fn check_broker(setting: &Setting, limit: Duration) -> CheckResult {
+    let messaging = &setting.messaging;
+    let status = match &messaging.message_broker {
+        None => CheckStatus::Skip("MESSAGE_BROKER is not set".to_string()),
+        // Creating a Kafka producer does not open a connection, so reach a broker directly
+        Some(MessageBrokerType::Kafka) => {
+            probe(limit, async {
+                let broker = messaging
+                    .kafka_brokers
+                    .split([',', ';'])
+                    .next()
+                    .unwrap_or_default()
+                    .trim();
+                TcpStream::connect(broker).await?;
+                Ok(())
+            })
+            .await
+        }
+        Some(_) => {
+            probe(limit, async {
+                let config = setting
+                    .to_producer_config()
+                    .ok_or_else(|| anyhow::anyhow!("Message broker is not configured"))?;
+                create_producer(config).await?;
+                Ok(())
+            })
+            .await
+        }
+    };
+
+    CheckResult {
+        name: "Message broker",
+        status,
+        hint: "Check MESSAGE_BROKER and the matching KAFKA_BROKERS / REDIS_URL / RABBITMQ_URL",
+    }
+}
+
+async fn check_redis(setting: &Setting, limit: Duration) -> CheckResult {
+    let status = probe(limit, async { setting.get_redis().await?.ping().await }).await;
+
+    CheckResult {
+        name: "Redis",
+        status,
+        hint: "Check REDIS_URL; task status polling needs Redis",
+    }
+}
+
+async fn check_smtp(setting: &Setting, limit: Duration) -> CheckResult {
+    let status = match setting.get_smtp_client() {
+        Err(_) => CheckStatus::Skip("SMTP_USER / SMTP_PASSWORD are not set".to_string()),
+        Ok(client) => probe(limit, client.test_connection()).await,
+    };
+
+    CheckResult {
+        name: "SMTP",
+        status,
+        hint: "Check SMTP_HOST, SMTP_PORT, SMTP_TLS and the SMTP credentials",
+    }
+}
+
+async fn probe<F>(limit: Duration, check: F) -> CheckStatus
+where
+    F: Future<Output = anyhow::Result<()>>,
+{
+    match timeout(limit, check).await {
+        Ok(Ok(())) => CheckStatus::Pass,
+        Ok(Err(e)) => CheckStatus::Fail(e.to_string()),
+        Err(_) => CheckStatus::Fail(format!("timed out after {:?}", limit)),
+    }
+}
+
+/// Render results as a table, followed by remediation hints for failed checks
+pub fn render_report(results: &[CheckResult]) -> String {
+    let name_width = results
+        .iter()
+        .map(|result| result.name.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut report = String::new();
+    for result in results {
+        let (label, detail) = match &result.status {
+            CheckStatus::Pass => ("PASS", ""),
+            CheckStatus::Fail(reason) => ("FAIL", reason.as_str()),
+            CheckStatus::Skip(reason) => ("SKIP", reason.as_str()),
+        };
+        let line = format!("{:<name_width$}  {}  {}", result.name, label, detail);
+        report.push_str(line.trim_end());
+        report.push('\n');
+    }
+
+    let failures: Vec<&CheckResult> = results.iter().filter(|r| r.is_failure()).collect();
+    if !failures.is_empty() {
+        report.push_str("\nHints:\n");
+        for result in failures {
+            report.push_str(&format!("  - {}: {}\n", result.name, result.hint));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{CheckResult, CheckStatus, probe, render_report};
+
+    #[tokio::test]
+    async fn probe_fails_on_timeout() {
+        let status = probe(Duration::from_millis(10), async {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            Ok(())
+        })
+        .await;
+
+        assert!(matches!(status, CheckStatus::Fail(reason) if reason.contains("timed out")));
+    }
+
+    #[test]
+    fn report_lists_hints_for_failures_only() {
+        let results = vec![
+            CheckResult {
+                name: "Database",
+                status: CheckStatus::Pass,
+                hint: "database hint",
+            },
+            CheckResult {
+                name: "Redis",
+                status: CheckStatus::Fail("connection refused".to_string()),
+                hint: "redis hint",
+            },
+            CheckResult {
+                name: "SMTP",
+                status: CheckStatus::Skip("not configured".to_string()),
+                hint: "smtp hint",
+            },
+        ];
+
+        let report = render_report(&results);
+
+        assert!(report.contains("Database  PASS"));
+        assert!(report.contains("Redis     FAIL  connection refused"));
+        assert!(report.contains("SMTP      SKIP  not configured"));
+        assert!(report.contains("Redis: redis hint"));
+        assert!(!report.contains("database hint"));
+        assert!(!report.contains("smtp hint"));
+    }
+}
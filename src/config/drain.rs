@@ -0,0 +1,249 @@
+use std::time::Duration;
+
+use axum::extract::ws::{CloseFrame, Message, close_code};
+use rust_i18n::t;
+use serde::Serialize;
+use serde_json::json;
+use tokio::{
+    sync::watch,
+    task::JoinHandle,
+    time::{Instant, sleep},
+};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    core::{
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+        event_type,
+    },
+    pkg::broadcast::{
+        forwarder::flush_coalesced,
+        websocket::{BroadcastMessage, active_connections},
+    },
+};
+
+/// How long the message forwarder gets to deliver what it received before it is aborted
+const FORWARDER_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where the server is in shutting down its connections, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DrainPhase {
+    /// Accepting WebSockets and requests
+    Serving,
+    /// New WebSockets are refused and open ones were told to reconnect elsewhere. They keep
+    /// receiving broadcasts until the forwarder delivered the ones still buffered.
+    Draining,
+    /// Open WebSockets are being closed, after which HTTP stops
+    Closing,
+}
+
+/// Drain of the HTTP and WebSocket layers, started by a shutdown signal or
+/// `POST /api/v1/admin/drain/`
+#[derive(Debug)]
+pub struct Drain {
+    phase: watch::Sender<DrainPhase>,
+}
+
+impl Default for Drain {
+    fn default() -> Self {
+        Self {
+            phase: watch::Sender::new(DrainPhase::Serving),
+        }
+    }
+}
+
+impl Drain {
+    pub fn phase(&self) -> DrainPhase {
+        *self.phase.borrow()
+    }
+
+    /// Whether new WebSockets are refused and the instance reports itself unready
+    pub fn is_draining(&self) -> bool {
+        self.phase() != DrainPhase::Serving
+    }
+
+    /// Start draining; `false` when it already started
+    pub fn start(&self) -> bool {
+        self.advance(DrainPhase::Draining)
+    }
+
+    /// Resolve once draining started
+    pub async fn started(&self) {
+        let mut phase = self.phase.subscribe();
+        let _ = phase.wait_for(|phase| *phase != DrainPhase::Serving).await;
+    }
+
+    /// Phase changes, for WebSockets to react to
+    pub fn subscribe(&self) -> DrainPhases {
+        let receiver = self.phase.subscribe();
+        let seen = *receiver.borrow();
+        DrainPhases { receiver, seen }
+    }
+
+    fn advance(&self, to: DrainPhase) -> bool {
+        self.phase.send_if_modified(|phase| {
+            let advanced = rank(*phase) < rank(to);
+            if advanced {
+                *phase = to;
+            }
+            advanced
+        })
+    }
+}
+
+/// Phases a WebSocket goes through, see `next_phase`
+#[derive(Debug)]
+pub struct DrainPhases {
+    receiver: watch::Receiver<DrainPhase>,
+    seen: DrainPhase,
+}
+
+fn rank(phase: DrainPhase) -> u8 {
+    match phase {
+        DrainPhase::Serving => 0,
+        DrainPhase::Draining => 1,
+        DrainPhase::Closing => 2,
+    }
+}
+
+/// Next phase the drain moved to, every phase in turn even when the drain went through
+/// several since the last call; never resolves once the drain is gone
+pub async fn next_phase(phases: &mut DrainPhases) -> DrainPhase {
+    loop {
+        let latest = *phases.receiver.borrow_and_update();
+        if rank(latest) > rank(phases.seen) {
+            phases.seen = match phases.seen {
+                DrainPhase::Serving => DrainPhase::Draining,
+                _ => DrainPhase::Closing,
+            };
+            return phases.seen;
+        }
+        if phases.receiver.changed().await.is_err() {
+            return std::future::pending().await;
+        }
+    }
+}
+
+/// Notice sent to WebSocket clients once draining started; `route` is merged into its data,
+/// e.g. the `task_id` of a task WebSocket
+pub fn draining_notice(route: serde_json::Value) -> BroadcastMessage {
+    let mut data = json!({ "reconnect": true });
+    if let (Some(data), serde_json::Value::Object(route)) = (data.as_object_mut(), route) {
+        data.extend(route);
+    }
+    BroadcastMessage {
+        event_type: event_type::SERVER_DRAINING.name.to_string(),
+        data,
+    }
+}
+
+/// Error refusing a WebSocket upgrade while draining
+pub fn draining_error(locale: &str) -> ErrorDTO {
+    ErrorDTO::from_code(
+        ErrorCode::ServerDraining,
+        t!("common.server_draining", locale = locale).to_string(),
+    )
+}
+
+/// Frame closing a WebSocket once drained, `1012` telling clients the service restarts
+pub fn close_frame() -> Message {
+    Message::Close(Some(CloseFrame {
+        code: close_code::RESTART,
+        reason: "server draining".into(),
+    }))
+}
+
+/// Drain the WebSocket layer before HTTP stops: refuse new WebSockets and tell open ones to
+/// reconnect elsewhere, stop the forwarder once it delivered the broadcasts it received and
+/// those held back for coalescing, then close the WebSockets, waiting up to `timeout` for
+/// them to go
+pub async fn drain_connections(
+    drain: &Drain,
+    forwarder_shutdown: &watch::Sender<bool>,
+    forwarder: Option<JoinHandle<()>>,
+    timeout: Duration,
+) {
+    drain.start();
+    let websockets = active_connections().await;
+    info!(websockets, "Draining connections");
+
+    let _ = forwarder_shutdown.send(true);
+    if let Some(handle) = forwarder {
+        stop_forwarder(handle).await;
+    }
+    flush_coalesced().await;
+
+    drain.advance(DrainPhase::Closing);
+    let deadline = Instant::now() + timeout;
+    while active_connections().await > 0 && Instant::now() < deadline {
+        sleep(Duration::from_millis(50)).await;
+    }
+    match active_connections().await {
+        0 => info!("✓ WebSockets drained"),
+        open => warn!(open, "WebSockets still open after {:?}", timeout),
+    }
+}
+
+async fn stop_forwarder(mut handle: JoinHandle<()>) {
+    tokio::select! {
+        join_result = &mut handle => {
+            if let Err(error) = join_result {
+                warn!("Message forwarder task ended unexpectedly: {}", error);
+            }
+        }
+        _ = sleep(FORWARDER_STOP_TIMEOUT) => {
+            warn!("Message forwarder did not stop in time; aborting task");
+            handle.abort();
+            if let Err(error) = handle.await
+                && !error.is_cancelled()
+            {
+                warn!("Message forwarder task ended unexpectedly: {}", error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Drain, DrainPhase, draining_notice, next_phase};
+
+    #[tokio::test]
+    async fn advances_through_phases_once() {
+        let drain = Drain::default();
+        let mut phase = drain.subscribe();
+        assert!(!drain.is_draining());
+
+        assert!(drain.start());
+        assert!(!drain.start());
+        assert_eq!(next_phase(&mut phase).await, DrainPhase::Draining);
+        drain.started().await;
+
+        assert!(drain.advance(DrainPhase::Closing));
+        assert!(!drain.advance(DrainPhase::Draining));
+        assert_eq!(drain.phase(), DrainPhase::Closing);
+    }
+
+    #[tokio::test]
+    async fn reports_phases_skipped_between_calls() {
+        let drain = Drain::default();
+        let mut phase = drain.subscribe();
+
+        drain.start();
+        drain.advance(DrainPhase::Closing);
+
+        assert_eq!(next_phase(&mut phase).await, DrainPhase::Draining);
+        assert_eq!(next_phase(&mut phase).await, DrainPhase::Closing);
+    }
+
+    #[test]
+    fn notice_carries_route() {
+        let notice = draining_notice(json!({ "task_id": "t1" }));
+
+        assert_eq!(notice.event_type, "server_draining");
+        assert_eq!(notice.data, json!({ "reconnect": true, "task_id": "t1" }));
+    }
+}
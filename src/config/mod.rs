@@ -1,4 +1,10 @@
 pub mod app;
+pub mod cli;
+pub mod diagnostics;
+pub mod doctor;
+pub mod drain;
+pub mod redaction;
+pub mod schema;
 pub mod setting;
 pub mod shutdown;
 pub mod telemetry;
@@ -0,0 +1,268 @@
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use std::{borrow::Cow, io, str::FromStr, sync::Arc, sync::LazyLock};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Terminal color codes the console formatter puts around field names and values
+const ANSI: &str = r"(?:\x1b\[[0-9;]*m)*";
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b([A-Za-z0-9._%+-])[A-Za-z0-9._%+-]*@([A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,})\b")
+        .unwrap()
+});
+static PHONE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\+\d{6,13}(\d{2})\b").unwrap());
+static JWT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\beyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]*").unwrap());
+static BEARER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(bearer\s+)[A-Za-z0-9._~+/=-]+").unwrap());
+/// `token=...` fields and query parameters, and `"token":"..."` JSON fields
+static SECRET_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r#"(?i)(\w*(?:token|password|secret|otp){ANSI}(?:=|":\s*"){ANSI})[^\s"&,;\x1b]+"#
+    ))
+    .unwrap()
+});
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Kind of personal data masked in logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PiiKind {
+    Email,
+    Phone,
+    /// JWTs, bearer credentials, and token/password/secret/OTP fields
+    Token,
+}
+
+impl FromStr for PiiKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "email" => Ok(Self::Email),
+            "phone" => Ok(Self::Phone),
+            "token" => Ok(Self::Token),
+            other => Err(format!("Unknown PII kind: {}", other)),
+        }
+    }
+}
+
+/// Masks emails, phone numbers and tokens in formatted log lines. Emails and phone numbers
+/// keep a few characters so lines about the same person can still be correlated.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    /// Kinds left untouched, for debugging environments
+    allowlist: Vec<PiiKind>,
+}
+
+impl Redactor {
+    pub fn new(allowlist: &[PiiKind]) -> Self {
+        Self {
+            allowlist: allowlist.to_vec(),
+        }
+    }
+
+    fn masks(&self, kind: PiiKind) -> bool {
+        !self.allowlist.contains(&kind)
+    }
+
+    pub fn redact<'a>(&self, line: &'a str) -> Cow<'a, str> {
+        let mut line = Cow::Borrowed(line);
+        if self.masks(PiiKind::Token) {
+            line = replace(line, &JWT, |_| REDACTED.to_string());
+            line = replace(line, &BEARER, |caps| format!("{}{}", &caps[1], REDACTED));
+            line = replace(line, &SECRET_FIELD, |caps| {
+                format!("{}{}", &caps[1], REDACTED)
+            });
+        }
+        if self.masks(PiiKind::Email) {
+            line = replace(line, &EMAIL, |caps| format!("{}***@{}", &caps[1], &caps[2]));
+        }
+        if self.masks(PiiKind::Phone) {
+            line = replace(line, &PHONE, |caps| format!("+***{}", &caps[1]));
+        }
+        line
+    }
+}
+
+/// Replace every match of `regex`, only allocating when there is one
+fn replace<'a>(
+    line: Cow<'a, str>,
+    regex: &Regex,
+    replacement: impl Fn(&Captures) -> String,
+) -> Cow<'a, str> {
+    if !regex.is_match(&line) {
+        return line;
+    }
+    Cow::Owned(
+        regex
+            .replace_all(&line, |caps: &Captures| replacement(caps))
+            .into_owned(),
+    )
+}
+
+/// Writer factory passing every formatted log line through a [`Redactor`], so messages,
+/// event fields and span fields are all masked whatever the output format
+#[derive(Clone)]
+pub struct RedactingMakeWriter<M> {
+    inner: M,
+    redactor: Arc<Redactor>,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    pub fn new(inner: M, redactor: Redactor) -> Self {
+        Self {
+            inner,
+            redactor: Arc::new(redactor),
+        }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter {
+            inner: self.inner.make_writer(),
+            redactor: self.redactor.clone(),
+        }
+    }
+}
+
+pub struct RedactingWriter<W> {
+    inner: W,
+    redactor: Arc<Redactor>,
+}
+
+impl<W: io::Write> io::Write for RedactingWriter<W> {
+    // The formatter hands over each event as one complete buffer
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line = String::from_utf8_lossy(buf);
+        self.inner
+            .write_all(self.redactor.redact(&line).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn masks_emails_keeping_the_first_letter_and_domain() {
+        let redactor = Redactor::default();
+
+        assert_eq!(
+            redactor.redact("Password reset requested for john.doe@example.com"),
+            "Password reset requested for j***@example.com"
+        );
+    }
+
+    #[test]
+    fn masks_phone_numbers_keeping_the_last_digits() {
+        let redactor = Redactor::default();
+
+        assert_eq!(redactor.redact("SMS to +84912345678"), "SMS to +***78");
+        assert_eq!(redactor.redact("user_id=12345678"), "user_id=12345678");
+    }
+
+    #[test]
+    fn masks_tokens() {
+        let redactor = Redactor::default();
+
+        assert_eq!(
+            redactor.redact("authorization: Bearer abc.def-ghi"),
+            "authorization: Bearer [REDACTED]"
+        );
+        assert_eq!(
+            redactor.redact("refresh eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOjF9.c2ln done"),
+            "refresh [REDACTED] done"
+        );
+        assert_eq!(
+            redactor.redact(r#"{"refresh_token":"abc123","status":200}"#),
+            r#"{"refresh_token":"[REDACTED]","status":200}"#
+        );
+        assert_eq!(
+            redactor.redact("uri=/reset?token=abc123&lang=en otp=123456"),
+            "uri=/reset?token=[REDACTED]&lang=en otp=[REDACTED]"
+        );
+        assert_eq!(
+            redactor.redact("\x1b[3mpassword\x1b[0m\x1b[2m=\x1b[0mhunter2"),
+            "\x1b[3mpassword\x1b[0m\x1b[2m=\x1b[0m[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn leaves_ordinary_lines_untouched() {
+        let redactor = Redactor::default();
+        let line = "Starting cleanup of expired refresh tokens at 2026-10-17T10:00:00Z";
+
+        assert!(matches!(redactor.redact(line), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn allowlisted_kinds_are_not_masked() {
+        let redactor = Redactor::new(&[PiiKind::Email]);
+
+        assert_eq!(
+            redactor.redact("email=jane@example.com token=abc"),
+            "email=jane@example.com token=[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn parses_pii_kinds() {
+        assert_eq!(" Phone ".parse(), Ok(PiiKind::Phone));
+        assert!("address".parse::<PiiKind>().is_err());
+    }
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn subscriber_output_is_redacted() {
+        let buffer = Buffer::default();
+        let writer = RedactingMakeWriter::new(
+            {
+                let buffer = buffer.clone();
+                move || buffer.clone()
+            },
+            Redactor::default(),
+        );
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_writer(writer)
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", uri = "/verify?otp=123456");
+            let _entered = span.enter();
+            tracing::info!(phone = "+84912345678", "Welcome jane@example.com");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("j***@example.com"));
+        assert!(output.contains("+***78"));
+        assert!(output.contains("otp=[REDACTED]"));
+        assert!(!output.contains("jane@"));
+        assert!(!output.contains("otp=123456"));
+    }
+}
@@ -2,18 +2,27 @@ use tracing::Subscriber;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::{EnvFilter, Registry, fmt};
 
+use super::{
+    redaction::{RedactingMakeWriter, Redactor},
+    setting::Setting,
+};
+
 pub fn get_subscriber(log_dir: &str) -> impl Subscriber + Sync + Send {
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    // Personal data is masked in every output, except the kinds allowlisted for debugging
+    let redactor = Redactor::new(&Setting::new().log_pii_allowlist);
 
     // Keep console logs compact for local readability; file logs retain structured details.
     let console_layer = fmt::layer()
         .compact()
         .with_target(true)
-        .with_writer(std::io::stdout);
+        .with_writer(RedactingMakeWriter::new(std::io::stdout, redactor.clone()));
 
     // File output with JSON format
     let file_writer = tracing_appender::rolling::daily(log_dir, "app.log");
-    let file_layer = fmt::layer().json().with_writer(file_writer);
+    let file_layer = fmt::layer()
+        .json()
+        .with_writer(RedactingMakeWriter::new(file_writer, redactor));
 
     Registry::default()
         .with(env_filter)
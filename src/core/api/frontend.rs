@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use axum::{
+    Router,
+    body::Body,
+    extract::Request,
+    http::{HeaderValue, Method, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use tower::ServiceExt;
+use tower_http::services::{ServeDir, ServeFile};
+
+use crate::config::{app::AppState, setting::FrontendSetting};
+
+/// Path prefixes owned by the API, answered with 404 rather than the app's `index.html`
+const API_PREFIXES: &[&str] = &["/api/", "/ws/", "/media/", "/mcp", "/docs"];
+
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Serve the bundled single-page app at `FRONTEND_DIST_DIR` for paths no route handles.
+/// Paths without a file extension fall back to `index.html`, so the app's client-side
+/// router resolves them.
+pub fn with_frontend(router: Router<AppState>, setting: &FrontendSetting) -> Router<AppState> {
+    let Some(dist_dir) = setting.dist_dir.clone() else {
+        return router;
+    };
+    let immutable_prefix = setting.immutable_prefix.clone();
+
+    router.fallback(move |request: Request| async move {
+        serve_frontend(&dist_dir, &immutable_prefix, request).await
+    })
+}
+
+async fn serve_frontend(dist_dir: &Path, immutable_prefix: &str, request: Request) -> Response {
+    let path = request.uri().path().to_string();
+    if !matches!(*request.method(), Method::GET | Method::HEAD)
+        || API_PREFIXES.iter().any(|prefix| path.starts_with(prefix))
+    {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    let serve_dir = ServeDir::new(dist_dir);
+    let result = if has_extension(&path) {
+        serve_dir.oneshot(request).await
+    } else {
+        serve_dir
+            .fallback(ServeFile::new(dist_dir.join("index.html")))
+            .oneshot(request)
+            .await
+    };
+    let mut response = match result {
+        Ok(response) => response.map(Body::new),
+        Err(error) => match error {},
+    };
+
+    if !response.status().is_client_error() && !response.status().is_server_error() {
+        // Hashed assets never change under the same name; everything else, `index.html`
+        // included, is revalidated so a deploy is picked up on the next load
+        let cache_control = if path.starts_with(immutable_prefix) && has_extension(&path) {
+            HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL)
+        } else {
+            HeaderValue::from_static("no-cache")
+        };
+        response
+            .headers_mut()
+            .insert(header::CACHE_CONTROL, cache_control);
+    }
+    response
+}
+
+/// Whether the last segment of `path` names a file, like `app-3f2a.js`
+fn has_extension(path: &str) -> bool {
+    path.rsplit('/')
+        .next()
+        .is_some_and(|segment| segment.contains('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::has_extension;
+
+    #[test]
+    fn detects_file_paths_by_extension() {
+        assert!(has_extension("/assets/app-3f2a.js"));
+        assert!(has_extension("/favicon.ico"));
+        assert!(!has_extension("/"));
+        assert!(!has_extension("/users/42"));
+        assert!(!has_extension("/assets/"));
+    }
+}
@@ -1,2 +1,3 @@
+pub mod frontend;
 pub mod openapi;
 pub mod route;
@@ -1,9 +1,44 @@
 use crate::{
-    common::api::runbook_api,
-    user::api::{auth_api, user_api},
+    common::{
+        api::{
+            audit_log_api, broadcast_api, dead_letter_api, deprecation_api, drain_api, health_api,
+            runbook_api, stats_api, task_api, worker_api,
+        },
+        dto::{
+            audit_log_dto::AuditLogSearchParamsDTO,
+            broadcast_dto::BroadcastEventSearchParamsDTO,
+            dead_letter_dto::{DeadLetterSearchParamsDTO, DeadLetterSelectionDTO},
+            worker_dto::PauseTaskTypeDTO,
+        },
+    },
+    core::{
+        api::route::RouteGroup,
+        validation::{self, Validate},
+    },
+    file::{
+        api::{avatar_api, file_api},
+        dto::file_dto::RejectAvatarDTO,
+    },
+    notification::api::{device_token_api, notification_api, notification_preference_api},
+    oauth_provider::{
+        api::{authorization_api, oauth_client_api, token_api},
+        dto::oauth_client_dto::OAuthClientCreateDTO,
+    },
+    report::api::report_api,
+    user::{
+        api::{auth_api, user_api, user_email_api},
+        dto::{
+            auth_dto::{
+                ConfirmResetPasswordDTO, ForgotPasswordDTO, RegisterDTO, ResendVerificationDTO,
+                ResetPasswordDTO,
+            },
+            user_dto::{UserCreateDTO, UserSearchParamsDTO, UserUpdateDTO},
+            user_email_dto::AddUserEmailDTO,
+        },
+    },
 };
 use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
-use utoipa::{Modify, OpenApi};
+use utoipa::{Modify, OpenApi, ToSchema};
 
 struct SecurityAddon;
 
@@ -23,36 +58,213 @@ impl Modify for SecurityAddon {
     }
 }
 
+/// Documents the `#[validate(...)]` rules of request DTOs as schema constraints
+struct ValidationAddon;
+
+impl ValidationAddon {
+    fn document_schema<T: Validate + ToSchema>(openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(schema) = openapi
+            .components
+            .as_mut()
+            .and_then(|components| components.schemas.get_mut(T::name().as_ref()))
+        {
+            validation::document_schema::<T>(schema);
+        }
+    }
+}
+
+impl Modify for ValidationAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        Self::document_schema::<UserCreateDTO>(openapi);
+        Self::document_schema::<UserUpdateDTO>(openapi);
+        Self::document_schema::<RegisterDTO>(openapi);
+        Self::document_schema::<AddUserEmailDTO>(openapi);
+        Self::document_schema::<ForgotPasswordDTO>(openapi);
+        Self::document_schema::<ResetPasswordDTO>(openapi);
+        Self::document_schema::<ConfirmResetPasswordDTO>(openapi);
+        Self::document_schema::<ResendVerificationDTO>(openapi);
+        Self::document_schema::<DeadLetterSelectionDTO>(openapi);
+        Self::document_schema::<PauseTaskTypeDTO>(openapi);
+        Self::document_schema::<RejectAvatarDTO>(openapi);
+        Self::document_schema::<OAuthClientCreateDTO>(openapi);
+
+        if let Some(parameters) = openapi
+            .paths
+            .paths
+            .get_mut("/api/v1/user/")
+            .and_then(|path| path.get.as_mut())
+            .and_then(|operation| operation.parameters.as_mut())
+        {
+            validation::document_parameters::<UserSearchParamsDTO>(parameters);
+        }
+
+        if let Some(parameters) = openapi
+            .paths
+            .paths
+            .get_mut("/api/v1/admin/dead-letters/")
+            .and_then(|path| path.get.as_mut())
+            .and_then(|operation| operation.parameters.as_mut())
+        {
+            validation::document_parameters::<DeadLetterSearchParamsDTO>(parameters);
+        }
+
+        if let Some(parameters) = openapi
+            .paths
+            .paths
+            .get_mut("/api/v1/admin/broadcasts/")
+            .and_then(|path| path.get.as_mut())
+            .and_then(|operation| operation.parameters.as_mut())
+        {
+            validation::document_parameters::<BroadcastEventSearchParamsDTO>(parameters);
+        }
+
+        for path in [
+            "/api/v1/admin/audit-logs/",
+            "/api/v1/admin/audit-logs/export/",
+        ] {
+            if let Some(parameters) = openapi
+                .paths
+                .paths
+                .get_mut(path)
+                .and_then(|path| path.get.as_mut())
+                .and_then(|operation| operation.parameters.as_mut())
+            {
+                validation::document_parameters::<AuditLogSearchParamsDTO>(parameters);
+            }
+        }
+    }
+}
+
 #[derive(OpenApi)]
 #[openapi(
-    modifiers(&SecurityAddon),
+    modifiers(&SecurityAddon, &ValidationAddon),
     paths(
         auth_api::change_password,
         auth_api::forgot_password,
         auth_api::reset_password,
+        auth_api::verify_reset_link,
+        auth_api::confirm_reset_password,
+        auth_api::report_sign_in,
+        auth_api::verify_email,
+        auth_api::resend_verification,
+        auth_api::search_session,
+        auth_api::revoke_other_sessions,
+        auth_api::revoke_session,
         auth_api::login,
         auth_api::register,
         auth_api::refresh_token,
         auth_api::logout,
+        auth_api::oauth_authorize,
+        auth_api::oauth_callback,
+        audit_log_api::search_audit_log,
+        audit_log_api::export_audit_log,
+        broadcast_api::search_broadcast_event,
+        dead_letter_api::search_dead_letter,
+        dead_letter_api::replay_dead_letter,
+        dead_letter_api::purge_dead_letter,
+        deprecation_api::get_deprecation_report,
+        drain_api::drain_server,
+        device_token_api::search_device_token,
+        file_api::search_user_file,
+        file_api::delete_user_file,
+        file_api::download_file,
+        avatar_api::get_avatar_media,
+        avatar_api::search_pending_avatar,
+        avatar_api::approve_avatar,
+        avatar_api::reject_avatar,
+        health_api::health,
+        health_api::ready,
+        health_api::metrics,
+        device_token_api::create_device_token,
+        device_token_api::delete_device_token,
+        notification_api::search_notification,
+        notification_api::read_notification,
+        notification_preference_api::search_notification_preference,
+        notification_preference_api::update_notification_preference,
+        notification_preference_api::delete_notification_preference,
+        oauth_client_api::search_oauth_client,
+        oauth_client_api::create_oauth_client,
+        oauth_client_api::delete_oauth_client,
+        authorization_api::get_consent_screen,
+        authorization_api::authorize,
+        authorization_api::search_oauth_consent,
+        authorization_api::revoke_oauth_consent,
+        token_api::issue_token,
+        token_api::introspect_token,
+        report_api::download_report,
         runbook_api::list_runbooks,
         runbook_api::run_runbook,
+        stats_api::get_stats,
+        task_api::poll_task_progress,
+        task_api::get_task_history,
         user_api::search_user,
         user_api::get_user,
         user_api::create_user,
         user_api::update_user,
         user_api::delete_user,
+        user_api::restore_user,
+        user_api::hard_delete_user,
         user_api::get_profile,
         user_api::update_profile,
+        user_api::send_phone_verification,
+        user_api::confirm_phone_verification,
+        user_email_api::search_user_email,
+        user_email_api::add_user_email,
+        user_email_api::update_user_email,
+        user_email_api::delete_user_email,
+        user_email_api::send_user_email_verification,
+        user_email_api::confirm_user_email,
+        user_email_api::make_primary_user_email,
         user_api::upload_avatar,
+        user_api::bulk_user_operations,
+        user_api::impersonate_user,
+        user_api::end_impersonation,
+        worker_api::get_worker_scaling,
+        worker_api::list_task_pauses,
+        worker_api::pause_task_type,
+        worker_api::resume_task_type,
     ),
 )]
 pub struct ApiDoc;
 
+impl ApiDoc {
+    /// The OpenAPI document without the paths of the `disabled` route groups
+    pub fn without_groups(disabled: &[RouteGroup]) -> utoipa::openapi::OpenApi {
+        let mut api_doc = Self::openapi();
+        api_doc
+            .paths
+            .paths
+            .retain(|path, _| !disabled.iter().any(|group| group.contains(path)));
+        api_doc
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ApiDoc;
+    use super::{ApiDoc, RouteGroup};
     use utoipa::OpenApi;
     use utoipa::openapi::security::{HttpAuthScheme, SecurityScheme};
+    use utoipa::openapi::{RefOr, Schema};
+
+    #[test]
+    fn leaves_out_paths_of_disabled_route_groups() {
+        let api_doc = ApiDoc::without_groups(&[RouteGroup::Admin]);
+
+        assert!(api_doc.paths.paths.contains_key("/api/v1/user/"));
+        assert!(
+            api_doc
+                .paths
+                .paths
+                .keys()
+                .all(|path| !path.starts_with("/api/v1/admin/"))
+        );
+        assert!(
+            ApiDoc::openapi()
+                .paths
+                .paths
+                .contains_key("/api/v1/admin/users/bulk/")
+        );
+    }
 
     #[test]
     fn generates_openapi_document_with_paths() {
@@ -91,4 +303,33 @@ mod tests {
         assert!(json.contains("bearer_auth"));
         assert!(!json.contains("runbook_token"));
     }
+
+    #[test]
+    fn documents_validation_constraints() {
+        let api_doc = ApiDoc::openapi();
+        let components = api_doc
+            .components
+            .as_ref()
+            .expect("components should exist");
+        let Some(RefOr::T(Schema::Object(create))) = components.schemas.get("UserCreateDTO") else {
+            panic!("expected UserCreateDTO to be an object schema");
+        };
+        let Some(RefOr::T(Schema::Object(email))) = create.properties.get("email") else {
+            panic!("expected an email property");
+        };
+        assert_eq!(email.min_length, Some(1));
+        assert_eq!(email.max_length, Some(255));
+        assert!(email.format.is_some());
+
+        let search = api_doc.paths.paths["/api/v1/user/"]
+            .get
+            .as_ref()
+            .and_then(|operation| operation.parameters.as_ref())
+            .expect("search parameters should exist");
+        let page = search.iter().find(|p| p.name == "page").unwrap();
+        let Some(RefOr::T(Schema::Object(page))) = &page.schema else {
+            panic!("expected page to be an object schema");
+        };
+        assert!(page.minimum == Some(1.into()));
+    }
 }
@@ -2,14 +2,16 @@ use axum::{
     Router,
     routing::{any, get, post},
 };
-use utoipa::OpenApi;
+use strum::{AsRefStr, EnumString, VariantNames};
 use utoipa_swagger_ui::{Config, SwaggerUi};
 
 use crate::{
     common::api::mcp_api,
-    common::api::{runbook_api, task_ws},
+    common::api::{
+        audit_log_api, broadcast_api, dead_letter_api, deprecation_api, drain_api, health_api,
+        runbook_api, stats_api, task_api, task_ws, worker_api,
+    },
     core::api::openapi::ApiDoc,
-    user::api::user_ws,
 };
 use crate::{
     config::app::AppState,
@@ -18,19 +20,55 @@ use crate::{
         page_size_limit_layer::page_size_limit_middleware,
         transaction_layer::transaction_middleware,
     },
-    user::api::{auth_api, user_api},
 };
 
 pub const SWAGGER_UI_PATH: &str = "/docs";
 pub const OPENAPI_JSON_PATH: &str = "/docs/openapi.json";
 
+/// Routes a deployment can turn off as a whole with `DISABLED_ROUTE_GROUPS`, e.g. to run
+/// a slim API-only instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumString, VariantNames)]
+#[strum(serialize_all = "lowercase")]
+pub enum RouteGroup {
+    /// Task progress and user sync WebSockets
+    Websocket,
+    /// Everything under `/api/v1/admin/`
+    Admin,
+    /// The MCP Streamable HTTP endpoint
+    Mcp,
+    /// Swagger UI and the OpenAPI document
+    Docs,
+}
+
+impl RouteGroup {
+    /// Path prefix of every route in the group
+    pub fn prefix(self) -> &'static str {
+        match self {
+            RouteGroup::Websocket => "/ws/",
+            RouteGroup::Admin => "/api/v1/admin/",
+            RouteGroup::Mcp => "/mcp",
+            RouteGroup::Docs => SWAGGER_UI_PATH,
+        }
+    }
+
+    pub fn contains(self, path: &str) -> bool {
+        path.starts_with(self.prefix())
+    }
+}
+
 pub fn get_route(app_state: AppState) -> Router<AppState> {
+    let disabled = app_state.setting.route_groups.disabled_groups();
     let swagger_route = Router::new().merge(
         SwaggerUi::new(SWAGGER_UI_PATH)
-            .url(OPENAPI_JSON_PATH, ApiDoc::openapi())
+            .url(OPENAPI_JSON_PATH, ApiDoc::without_groups(&disabled))
             .config(Config::default().persist_authorization(true)),
     );
 
+    let health_route = Router::new()
+        .route("/health/", get(health_api::health))
+        .route("/ready/", get(health_api::ready))
+        .route("/metrics/", get(health_api::metrics));
+
     let runbook_route = Router::new()
         .route("/api/v1/runbook/", get(runbook_api::list_runbooks))
         .route("/api/v1/runbook/run/", post(runbook_api::run_runbook))
@@ -51,19 +89,86 @@ pub fn get_route(app_state: AppState) -> Router<AppState> {
         ))
         .route_layer(axum::middleware::from_fn(lang_middleware));
 
-    let no_auth_route = Router::new()
-        .route("/api/v1/auth/login/", post(auth_api::login))
-        .route("/api/v1/auth/register/", post(auth_api::register))
-        .route("/api/v1/auth/refresh-token/", post(auth_api::refresh_token))
-        .route("/api/v1/auth/logout/", post(auth_api::logout))
-        .route(
-            "/api/v1/auth/forgot-password/",
-            post(auth_api::forgot_password),
-        )
+    // Polls are held open for a while, so they run outside a database transaction
+    let task_poll_route = Router::new()
         .route(
-            "/api/v1/auth/reset-password/",
-            post(auth_api::reset_password),
+            "/api/v1/tasks/{task_id}/poll/",
+            get(task_api::poll_task_progress),
         )
+        .route_layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            auth_middleware,
+        ))
+        .route_layer(axum::middleware::from_fn(lang_middleware));
+
+    let auth_route = protected_api(
+        Router::new()
+            .route("/ws/v1/task/{task_id}/", any(task_ws::get_task_progress))
+            .route("/api/v1/admin/stats/", get(stats_api::get_stats))
+            .route(
+                "/api/v1/admin/audit-logs/",
+                get(audit_log_api::search_audit_log),
+            )
+            .route(
+                "/api/v1/admin/audit-logs/export/",
+                get(audit_log_api::export_audit_log),
+            )
+            .route(
+                "/api/v1/admin/broadcasts/",
+                get(broadcast_api::search_broadcast_event),
+            )
+            .route(
+                "/api/v1/admin/dead-letters/",
+                get(dead_letter_api::search_dead_letter),
+            )
+            .route(
+                "/api/v1/admin/dead-letters/replay/",
+                post(dead_letter_api::replay_dead_letter),
+            )
+            .route(
+                "/api/v1/admin/dead-letters/purge/",
+                post(dead_letter_api::purge_dead_letter),
+            )
+            .route(
+                "/api/v1/admin/deprecations/",
+                get(deprecation_api::get_deprecation_report),
+            )
+            .route("/api/v1/admin/drain/", post(drain_api::drain_server))
+            .route(
+                "/api/v1/admin/workers/scaling/",
+                get(worker_api::get_worker_scaling),
+            )
+            .route(
+                "/api/v1/admin/task-types/paused/",
+                get(worker_api::list_task_pauses),
+            )
+            .route(
+                "/api/v1/admin/task-types/{task_type}/pause/",
+                post(worker_api::pause_task_type),
+            )
+            .route(
+                "/api/v1/admin/task-types/{task_type}/resume/",
+                post(worker_api::resume_task_type),
+            )
+            .route(
+                "/api/v1/tasks/{task_id}/history/",
+                get(task_api::get_task_history),
+            ),
+        &app_state,
+    );
+
+    swagger_route
+        .merge(health_route)
+        .merge(runbook_route)
+        .merge(mcp_route)
+        .merge(task_poll_route)
+        .merge(auth_route)
+}
+
+/// Apply the middleware stack of unauthenticated API routes:
+/// transaction, language and page size limit
+pub fn public_api(router: Router<AppState>, app_state: &AppState) -> Router<AppState> {
+    router
         .route_layer(axum::middleware::from_fn_with_state(
             app_state.clone(),
             transaction_middleware,
@@ -72,30 +177,13 @@ pub fn get_route(app_state: AppState) -> Router<AppState> {
         .layer(axum::middleware::from_fn_with_state(
             app_state.clone(),
             page_size_limit_middleware,
-        ));
+        ))
+}
 
-    let auth_route = Router::new()
-        .route("/ws/v1/task/{task_id}/", any(task_ws::get_task_progress))
-        .route("/ws/v1/user/", any(user_ws::sync_user_data))
-        .route(
-            "/api/v1/user/profile/",
-            get(user_api::get_profile).patch(user_api::update_profile),
-        )
-        .route(
-            "/api/v1/auth/change-password/",
-            post(auth_api::change_password),
-        )
-        .route(
-            "/api/v1/user/",
-            get(user_api::search_user).post(user_api::create_user),
-        )
-        .route("/api/v1/user/upload-avatar/", post(user_api::upload_avatar))
-        .route(
-            "/api/v1/user/{id}/",
-            get(user_api::get_user)
-                .patch(user_api::update_user)
-                .delete(user_api::delete_user),
-        )
+/// Apply the middleware stack of authenticated API routes:
+/// transaction, auth, language and page size limit
+pub fn protected_api(router: Router<AppState>, app_state: &AppState) -> Router<AppState> {
+    router
         .route_layer(axum::middleware::from_fn_with_state(
             app_state.clone(),
             transaction_middleware,
@@ -108,11 +196,52 @@ pub fn get_route(app_state: AppState) -> Router<AppState> {
         .layer(axum::middleware::from_fn_with_state(
             app_state.clone(),
             page_size_limit_middleware,
-        ));
+        ))
+}
 
-    swagger_route
-        .merge(runbook_route)
-        .merge(mcp_route)
-        .merge(no_auth_route)
-        .merge(auth_route)
+/// Whether `path` matches `pattern`, in which `*` stands for one path segment
+pub fn matches_path_pattern(pattern: &str, path: &str) -> bool {
+    let mut pattern_segments = pattern.split('/');
+    let mut path_segments = path.split('/');
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (None, None) => return true,
+            (Some(expected), Some(actual)) if expected == "*" || expected == actual => {}
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RouteGroup, matches_path_pattern};
+
+    #[test]
+    fn route_groups_own_their_prefix() {
+        assert!(RouteGroup::Websocket.contains("/ws/v1/task/abc/"));
+        assert!(RouteGroup::Admin.contains("/api/v1/admin/users/bulk/"));
+        assert!(RouteGroup::Mcp.contains("/mcp"));
+        assert!(RouteGroup::Docs.contains("/docs/openapi.json"));
+        assert!(!RouteGroup::Admin.contains("/api/v1/user/"));
+        assert_eq!("websocket".parse(), Ok(RouteGroup::Websocket));
+        assert!("graphql".parse::<RouteGroup>().is_err());
+    }
+
+    #[test]
+    fn matches_path_patterns_by_segment() {
+        assert!(matches_path_pattern("/health/", "/health/"));
+        assert!(matches_path_pattern(
+            "/api/v1/tasks/*/poll/",
+            "/api/v1/tasks/abc/poll/"
+        ));
+        assert!(!matches_path_pattern(
+            "/api/v1/tasks/*/poll/",
+            "/api/v1/tasks/abc/def/poll/"
+        ));
+        assert!(!matches_path_pattern("/health/", "/health/deep/"));
+        assert!(!matches_path_pattern(
+            "/api/v1/auth/refresh-token/",
+            "/api/v1/auth/login/"
+        ));
+    }
 }
@@ -0,0 +1,112 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use sea_orm::{ActiveValue::Set, DatabaseConnection, TransactionTrait};
+use serde_json::Value;
+
+use crate::{
+    common::{entity::broadcast_event, repository::broadcast_event_repository},
+    config::app::AppState,
+    core::{r#async::PeriodicJob, context::Context},
+    pkg::{
+        broadcast::websocket::BroadcastMessage,
+        messaging::{EncodedMessage, MessageProducer, stats::WORKER_STATS_EVENT},
+    },
+};
+
+/// Destination progress updates and notifications are published on for WebSocket clients
+const BROADCAST_DESTINATION: &str = "broadcasts";
+
+/// Appends every broadcast published through it to `broadcast_event` before handing it on,
+/// so admins can look up what a task or user was sent. Wraps the producer beneath
+/// coalescing, so throttled progress is archived as delivered. Worker stats aren't kept.
+pub struct ArchivingProducer {
+    inner: Arc<Box<dyn MessageProducer>>,
+    db: DatabaseConnection,
+}
+
+impl ArchivingProducer {
+    pub fn new(inner: Arc<Box<dyn MessageProducer>>, db: DatabaseConnection) -> Self {
+        Self { inner, db }
+    }
+
+    /// Archive `message`. Failures are logged, never surfaced to the publisher.
+    async fn archive(&self, message: &EncodedMessage) {
+        if let Err(e) = self.try_archive(message).await {
+            tracing::warn!("Failed to archive broadcast: {:?}", e);
+        }
+    }
+
+    async fn try_archive(&self, message: &EncodedMessage) -> anyhow::Result<()> {
+        let broadcast: BroadcastMessage = serde_json::from_slice(message.payload())?;
+        if broadcast.event_type == WORKER_STATS_EVENT {
+            return Ok(());
+        }
+
+        let context = Context::builder(Arc::new(self.db.begin().await?)).build();
+        broadcast_event_repository::create(
+            &context,
+            broadcast_event::ActiveModel {
+                event_type: Set(broadcast.event_type),
+                task_id: Set(broadcast
+                    .data
+                    .get("task_id")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)),
+                user_id: Set(broadcast
+                    .data
+                    .get("user_id")
+                    .and_then(Value::as_i64)
+                    .map(|user_id| user_id as i32)),
+                data: Set(broadcast.data),
+                ..Default::default()
+            },
+        )
+        .await?;
+        context.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MessageProducer for ArchivingProducer {
+    async fn publish(
+        &self,
+        message: &EncodedMessage,
+        destination: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if destination == Some(BROADCAST_DESTINATION) {
+            self.archive(message).await;
+        }
+        self.inner.publish(message, destination).await
+    }
+}
+
+/// Delete archived broadcasts older than `BROADCAST_ARCHIVE_RETENTION_DAYS`
+pub struct PurgeBroadcastEvents;
+
+#[async_trait]
+impl PeriodicJob for PurgeBroadcastEvents {
+    fn name(&self) -> &'static str {
+        "purge-broadcast-events"
+    }
+
+    fn default_interval(&self) -> Duration {
+        Duration::from_secs(60 * 60)
+    }
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()> {
+        let cutoff = chrono::Utc::now().naive_utc()
+            - chrono::Duration::days(app_state.setting.broadcast_archive.retention_days);
+
+        let context = Context::builder(Arc::new(app_state.db.begin().await?)).build();
+        let purged = broadcast_event_repository::delete_published_before(&context, cutoff).await?;
+        context.commit().await?;
+        if purged > 0 {
+            tracing::info!("Purged {} archived broadcasts", purged);
+        }
+
+        Ok(())
+    }
+}
@@ -2,10 +2,8 @@ use std::sync::Arc;
 use tokio_cron_scheduler::{Job, JobScheduler};
 
 use crate::config::setting::{MessageType, Setting};
-use crate::core::db::connection::get_db;
-use crate::pkg::messaging::{MessageProducer, create_producer};
-
-use super::{TaskType, publish_task};
+use crate::core::module::ScheduledJob;
+use crate::pkg::messaging::{MessageProducer, TaskEvent, create_producer};
 
 /// Creates a cron job that publishes a task to the message broker
 fn create_job_with_task<C, F, Fut>(
@@ -41,14 +39,13 @@ where
     })?)
 }
 
-pub async fn init_cron_job(setting: &Setting) -> Result<(), anyhow::Error> {
+pub async fn init_cron_job(
+    setting: &Setting,
+    jobs: Vec<ScheduledJob>,
+) -> Result<(), anyhow::Error> {
     tracing::info!("Initializing cron jobs with message broker...");
     let sched = JobScheduler::new().await?;
 
-    // Initialize database connection
-    let db = get_db(&setting.database_url).await?;
-    tracing::info!("✓ Database connected for cron jobs");
-
     // Create message producer for publishing jobs
     let producer_config = setting
         .to_producer_config()
@@ -56,22 +53,29 @@ pub async fn init_cron_job(setting: &Setting) -> Result<(), anyhow::Error> {
     let producer = Arc::new(create_producer(producer_config).await?);
     tracing::info!("✓ Message producer initialized for cron jobs");
 
-    // Clean expired tokens every hour
-    let cleanup_job = create_job_with_task(
-        "0 0 * * * *", // Every hour at minute 0
-        db.clone(),
-        producer.clone(),
-        |_db, producer| async move {
-            publish_task(
-                producer.as_ref().as_ref(),
-                TaskType::CleanupExpiredToken,
-                Some(MessageType::Tasks.as_ref()),
-            )
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to publish cleanup job: {:?}", e))
-        },
-    )?;
-    sched.add(cleanup_job).await?;
+    for job in jobs {
+        let ScheduledJob {
+            name,
+            schedule,
+            task,
+        } = job;
+        let cron_job = create_job_with_task(
+            &schedule,
+            (name, task),
+            producer.clone(),
+            |(name, task), producer| async move {
+                TaskEvent::new(task)
+                    .publish_with_producer(
+                        producer.as_ref().as_ref(),
+                        Some(MessageType::Tasks.as_ref()),
+                    )
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to publish {} job: {:?}", name, e))
+            },
+        )?;
+        sched.add(cron_job).await?;
+        tracing::info!("✓ Scheduled {} ({})", name, schedule);
+    }
 
     // Start the scheduler
     sched.start().await?;
@@ -0,0 +1,129 @@
+use rust_i18n::t;
+use serde::Serialize;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+    },
+    pkg::cache::{QuotaDecision, task_dedup_key},
+};
+
+/// Task id of a user action that enqueues a task
+pub struct TaskClaim {
+    pub task_id: String,
+    /// The same action already started `task_id` within the window, so nothing should be
+    /// enqueued again
+    pub duplicate: bool,
+    key: Option<String>,
+    /// The task holds a slot of the user's task quota
+    quota: bool,
+}
+
+impl TaskClaim {
+    /// Task id for the current user starting a `task_type` task for `payload`: a fresh one,
+    /// or the one of an identical action within `TASK_DEDUP_WINDOW_SECONDS`.
+    /// Fails with `429` when a fresh task would exceed the user's task quota.
+    pub async fn new(
+        context: &Context,
+        task_type: &str,
+        payload: &impl Serialize,
+    ) -> Result<Self, ErrorDTO> {
+        let mut claim = Self::deduplicate(context, task_type, payload).await;
+        if !claim.duplicate {
+            claim.acquire_quota(context).await?;
+        }
+        Ok(claim)
+    }
+
+    async fn deduplicate(context: &Context, task_type: &str, payload: &impl Serialize) -> Self {
+        let task_id = context.id_generator.task_id();
+        let (Some(dedup), Some(user)) = (&context.task_dedup, &context.user) else {
+            return Self::fresh(task_id);
+        };
+        let Ok(payload) = serde_json::to_vec(payload) else {
+            return Self::fresh(task_id);
+        };
+
+        let key = task_dedup_key(user.id, task_type, &payload);
+        match dedup.claim(&key, &task_id).await {
+            Ok(Some(existing)) => Self {
+                task_id: existing,
+                duplicate: true,
+                key: None,
+                quota: false,
+            },
+            Ok(None) => Self {
+                task_id,
+                duplicate: false,
+                key: Some(key),
+                quota: false,
+            },
+            // Enqueueing a duplicate beats refusing the action
+            Err(e) => {
+                tracing::warn!("Task deduplication unavailable: {:?}", e);
+                Self::fresh(task_id)
+            }
+        }
+    }
+
+    fn fresh(task_id: String) -> Self {
+        Self {
+            task_id,
+            duplicate: false,
+            key: None,
+            quota: false,
+        }
+    }
+
+    /// Take a slot of the user's task quota for the task, releasing the dedup key when the
+    /// quota is used up so the action can be retried once a slot frees
+    async fn acquire_quota(&mut self, context: &Context) -> Result<(), ErrorDTO> {
+        let (Some(quota), Some(user)) = (&context.task_quota, &context.user) else {
+            return Ok(());
+        };
+
+        let message = match quota.acquire(user.id, &self.task_id).await {
+            Ok(QuotaDecision::Granted) => {
+                self.quota = true;
+                return Ok(());
+            }
+            Ok(QuotaDecision::RunningExceeded) => t!(
+                "common.task_quota_running_exceeded",
+                limit = quota.limits().max_running,
+                locale = &context.locale
+            ),
+            Ok(QuotaDecision::DailyExceeded) => t!(
+                "common.task_quota_daily_exceeded",
+                limit = quota.limits().max_daily,
+                locale = &context.locale
+            ),
+            // A quota that cannot be checked only costs fairness, so enqueue anyway
+            Err(e) => {
+                tracing::warn!("Task quota unavailable: {:?}", e);
+                return Ok(());
+            }
+        };
+
+        self.release(context).await;
+        Err(ErrorDTO::from_code(
+            ErrorCode::TaskQuotaExceeded,
+            message.to_string(),
+        ))
+    }
+
+    /// Let the action be repeated right away and give back its quota slot, for when its task
+    /// could not be enqueued
+    pub async fn release(&self, context: &Context) {
+        if let (Some(dedup), Some(key)) = (&context.task_dedup, &self.key)
+            && let Err(e) = dedup.release(key).await
+        {
+            tracing::warn!("Failed to release task claim: {:?}", e);
+        }
+        if let (Some(quota), true) = (&context.task_quota, self.quota)
+            && let Err(e) = quota.release(&self.task_id).await
+        {
+            tracing::warn!("Failed to release task quota: {:?}", e);
+        }
+    }
+}
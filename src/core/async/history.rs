@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use sea_orm::{ActiveValue::Set, DatabaseConnection, TransactionTrait};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    common::{
+        entity::{dead_letter, task_event_log},
+        repository::{dead_letter_repository, task_event_log_repository},
+    },
+    config::setting::MessageType,
+    core::context::Context,
+    pkg::messaging::{TaskEvent, TaskLifecycle},
+};
+
+/// Writes each lifecycle stage of the tasks a worker handles to `task_event_log`,
+/// for `/api/v1/tasks/{id}/history/`. Tasks failing their last attempt are also kept in
+/// `dead_letter`, for `/api/v1/admin/dead-letters/`.
+#[derive(Clone)]
+pub struct TaskHistoryRecorder {
+    db: DatabaseConnection,
+    worker_id: Arc<str>,
+}
+
+impl TaskHistoryRecorder {
+    pub fn new(db: DatabaseConnection, worker_id: impl Into<Arc<str>>) -> Self {
+        Self {
+            db,
+            worker_id: worker_id.into(),
+        }
+    }
+
+    /// Record `lifecycle` for `event`. Failures are logged, never surfaced to the task.
+    pub async fn record<T>(&self, event: &TaskEvent<T>, lifecycle: &TaskLifecycle)
+    where
+        T: Clone + Send + Sync + Serialize,
+    {
+        if let Err(e) = self.try_record(event, lifecycle).await {
+            tracing::warn!(
+                "Failed to record {} of task {}: {:?}",
+                lifecycle.name(),
+                event.id,
+                e
+            );
+        }
+    }
+
+    /// Whether `event` already completed or failed for good on some worker. Failures to
+    /// tell are logged and read as `false`, so the task runs.
+    pub async fn is_processed<T>(&self, event: &TaskEvent<T>) -> bool
+    where
+        T: Clone + Send + Sync + Serialize,
+    {
+        let context = Context::read_only(self.db.clone()).build();
+        match task_event_log_repository::is_finished(&context, &event.id).await {
+            Ok(finished) => finished,
+            Err(e) => {
+                tracing::warn!("Failed to check history of task {}: {:?}", event.id, e);
+                false
+            }
+        }
+    }
+
+    async fn try_record<T>(
+        &self,
+        event: &TaskEvent<T>,
+        lifecycle: &TaskLifecycle,
+    ) -> anyhow::Result<()>
+    where
+        T: Clone + Send + Sync + Serialize,
+    {
+        let payload = serde_json::to_value(&event.task)?;
+        let tag = |key: &str| payload.get(key).and_then(Value::as_str).map(str::to_string);
+
+        let context = Context::builder(Arc::new(self.db.begin().await?)).build();
+        task_event_log_repository::create(
+            &context,
+            task_event_log::ActiveModel {
+                task_id: Set(event.id.clone()),
+                reference: Set(tag("task_id")),
+                task_type: Set(tag("type")),
+                status: Set(lifecycle.name().to_string()),
+                attempt: Set(event.retry_count as i32 + 1),
+                worker_id: Set(Some(self.worker_id.to_string())),
+                error: Set(lifecycle.error().map(str::to_string)),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        if let TaskLifecycle::Failed { error } = lifecycle {
+            dead_letter_repository::create(
+                &context,
+                dead_letter::ActiveModel {
+                    task_id: Set(event.id.clone()),
+                    task_type: Set(tag("type")),
+                    // Use cases publish every task to the tasks destination
+                    topic: Set(MessageType::default_str().to_string()),
+                    event: Set(serde_json::to_value(event)?),
+                    attempts: Set(event.retry_count as i32 + 1),
+                    error: Set(error.clone()),
+                    worker_id: Set(Some(self.worker_id.to_string())),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        }
+        context.commit().await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{ActiveValue::Set, DatabaseConnection, TransactionTrait};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::task::JoinHandle;
+
+use crate::{
+    common::{
+        entity::{dead_letter, task_event_log, task_lease},
+        repository::{
+            dead_letter_repository, task_event_log_repository, task_lease_repository,
+            worker_heartbeat_repository,
+        },
+    },
+    config::{app::AppState, setting::MessageType},
+    core::context::Context,
+    pkg::messaging::{MessageProducer, TaskEvent},
+};
+
+use super::PeriodicJob;
+
+/// Leases of the tasks a worker is running, in `task_lease`. The worker's heartbeat renews
+/// them; a lease that runs out belongs to a worker that stopped mid-task, and
+/// `RecoverExpiredTaskLeases` enqueues its task again.
+#[derive(Clone)]
+pub struct TaskLeases {
+    db: DatabaseConnection,
+    worker_id: Arc<str>,
+    lease: Duration,
+}
+
+impl TaskLeases {
+    pub fn new(db: DatabaseConnection, worker_id: impl Into<Arc<str>>, lease: Duration) -> Self {
+        Self {
+            db,
+            worker_id: worker_id.into(),
+            lease,
+        }
+    }
+
+    fn leased_until(&self) -> NaiveDateTime {
+        Utc::now().naive_utc() + chrono::Duration::from_std(self.lease).unwrap_or_default()
+    }
+
+    /// Lease `event` to this worker as it starts. Failures are logged, never surfaced to the
+    /// task, which then runs unprotected.
+    pub async fn claim<T>(&self, event: &TaskEvent<T>)
+    where
+        T: Clone + Send + Sync + Serialize,
+    {
+        if let Err(e) = self.try_claim(event).await {
+            tracing::warn!("Failed to lease task {}: {:?}", event.id, e);
+        }
+    }
+
+    async fn try_claim<T>(&self, event: &TaskEvent<T>) -> anyhow::Result<()>
+    where
+        T: Clone + Send + Sync + Serialize,
+    {
+        let payload = serde_json::to_value(&event.task)?;
+        let task_type = payload.get("type").and_then(Value::as_str);
+
+        let context = Context::builder(Arc::new(self.db.begin().await?)).build();
+        task_lease_repository::claim(
+            &context,
+            task_lease::ActiveModel {
+                task_id: Set(event.id.clone()),
+                task_type: Set(task_type.map(str::to_string)),
+                worker_id: Set(self.worker_id.to_string()),
+                event: Set(serde_json::to_value(event)?),
+                leased_until: Set(self.leased_until()),
+                ..Default::default()
+            },
+        )
+        .await?;
+        context.commit().await?;
+
+        Ok(())
+    }
+
+    /// Give up the lease of `task_id`, once its task finished or was republished for a retry
+    pub async fn release(&self, task_id: &str) {
+        let result: anyhow::Result<()> = async {
+            let context = Context::builder(Arc::new(self.db.begin().await?)).build();
+            task_lease_repository::release(&context, task_id, &self.worker_id).await?;
+            context.commit().await?;
+            Ok(())
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to release lease of task {}: {:?}", task_id, e);
+        }
+    }
+
+    /// Record that this worker is alive and extend the leases of the tasks it runs
+    pub async fn heartbeat(&self) -> anyhow::Result<()> {
+        let context = Context::builder(Arc::new(self.db.begin().await?)).build();
+        worker_heartbeat_repository::beat(&context, &self.worker_id, Utc::now().naive_utc())
+            .await?;
+        task_lease_repository::renew_by_worker_id(&context, &self.worker_id, self.leased_until())
+            .await?;
+        context.commit().await?;
+
+        Ok(())
+    }
+
+    /// Beat every `interval` until aborted
+    pub fn spawn_heartbeat(&self, interval: Duration) -> JoinHandle<()> {
+        let leases = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = leases.heartbeat().await {
+                    tracing::error!("Failed to send worker heartbeat: {:?}", e);
+                }
+            }
+        })
+    }
+
+    /// Forget this worker's heartbeat on shutdown. Leases it still holds run out and are
+    /// recovered like those of a crashed worker.
+    pub async fn deregister(&self) -> anyhow::Result<()> {
+        let context = Context::builder(Arc::new(self.db.begin().await?)).build();
+        worker_heartbeat_repository::delete_by_worker_id(&context, &self.worker_id).await?;
+        context.commit().await?;
+
+        Ok(())
+    }
+}
+
+/// Enqueue again the tasks whose worker lease ran out. Tasks without attempts left go to
+/// `dead_letter`, so a task that crashes its worker can't loop forever.
+pub struct RecoverExpiredTaskLeases;
+
+#[async_trait]
+impl PeriodicJob for RecoverExpiredTaskLeases {
+    fn name(&self) -> &'static str {
+        "recover-expired-task-leases"
+    }
+
+    fn default_interval(&self) -> Duration {
+        Duration::from_secs(60)
+    }
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()> {
+        if !app_state.setting.task_lease.is_enabled() {
+            return Ok(());
+        }
+        let producer = app_state
+            .producer
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Producer not available"))?;
+        let now = Utc::now().naive_utc();
+
+        let expired = task_lease_repository::find_expired(
+            &Context::read_only(app_state.db.clone()).build(),
+            now,
+        )
+        .await?;
+        let mut recovered = 0;
+        for lease in expired {
+            let task_id = lease.task_id.clone();
+            match recover(&app_state.db, producer.as_ref().as_ref(), lease, now).await {
+                Ok(true) => recovered += 1,
+                Ok(false) => {}
+                Err(e) => tracing::error!("Failed to recover task {}: {:?}", task_id, e),
+            }
+        }
+        if recovered > 0 {
+            tracing::warn!("Recovered {} task(s) of stopped workers", recovered);
+        }
+
+        // Workers that stopped without deregistering are forgotten once their leases would
+        // have run out
+        let lease = chrono::Duration::from_std(app_state.setting.task_lease.lease())?;
+        let context = Context::builder(Arc::new(app_state.db.begin().await?)).build();
+        worker_heartbeat_repository::delete_seen_before(&context, now - lease).await?;
+        context.commit().await?;
+
+        Ok(())
+    }
+}
+
+/// Republish the task of the expired `lease` as a retry, or dead-letter it when it has no
+/// attempts left. Returns `false` when another instance recovered it first.
+async fn recover(
+    db: &DatabaseConnection,
+    producer: &dyn MessageProducer,
+    lease: task_lease::Model,
+    now: NaiveDateTime,
+) -> anyhow::Result<bool> {
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    if !task_lease_repository::delete_expired_by_id(&context, lease.id, now).await? {
+        return Ok(false);
+    }
+
+    let mut event: TaskEvent<Value> = serde_json::from_value(lease.event.clone())?;
+    let error = format!("Lease of worker {} expired mid-processing", lease.worker_id);
+    let attempt = event.retry_count as i32 + 1;
+    // The lost run counts as an attempt, as if the task had failed
+    let status = if event.should_retry() {
+        event.increment_retry();
+        event
+            .publish_with_producer(producer, Some(MessageType::default_str()))
+            .await?;
+        "retrying"
+    } else {
+        dead_letter_repository::create(
+            &context,
+            dead_letter::ActiveModel {
+                task_id: Set(event.id.clone()),
+                task_type: Set(lease.task_type.clone()),
+                topic: Set(MessageType::default_str().to_string()),
+                event: Set(lease.event.clone()),
+                attempts: Set(attempt),
+                error: Set(error.clone()),
+                worker_id: Set(Some(lease.worker_id.clone())),
+                ..Default::default()
+            },
+        )
+        .await?;
+        "failed"
+    };
+    task_event_log_repository::create(
+        &context,
+        task_event_log::ActiveModel {
+            task_id: Set(event.id.clone()),
+            reference: Set(event
+                .task
+                .get("task_id")
+                .and_then(Value::as_str)
+                .map(str::to_string)),
+            task_type: Set(lease.task_type),
+            status: Set(status.to_string()),
+            attempt: Set(attempt),
+            worker_id: Set(Some(lease.worker_id)),
+            error: Set(Some(error)),
+            ..Default::default()
+        },
+    )
+    .await?;
+    context.commit().await?;
+
+    Ok(true)
+}
@@ -1,48 +1,96 @@
+pub mod broadcast_archive;
 pub mod cron;
+pub mod dedup;
+pub mod history;
+pub mod lease;
+pub mod outbox;
+pub mod pause;
+pub mod progress;
+pub mod registry;
+pub mod scheduler;
 pub mod task;
 pub mod worker;
 
-use crate::pkg::messaging::MessageProducer;
+use crate::core::context::Context;
+use crate::pkg::messaging::{EncodedMessage, MessageProducer};
 
 // Re-export generic types from pkg::messaging::task
 pub use crate::pkg::messaging::task::TaskPriority;
 
 // Re-export application-specific task types and handler implementation
+pub use broadcast_archive::{ArchivingProducer, PurgeBroadcastEvents};
+pub use dedup::TaskClaim;
+pub use history::TaskHistoryRecorder;
+pub use lease::{RecoverExpiredTaskLeases, TaskLeases};
+pub use outbox::{Outbox, RelayOutbox};
+pub use pause::TaskPauses;
+pub use progress::{AVATAR_PIPELINE, Pipeline, PipelineProgress, Stage};
+pub use registry::{RoutedTask, RoutingTaskHandler, TaskRegistry};
+pub use scheduler::{PeriodicJob, Scheduler};
 pub use task::{ConcreteTaskHandler, TaskType};
 
 // Application-specific TaskEvent type
 pub type TaskEvent = crate::pkg::messaging::task::TaskEvent<TaskType>;
 
-/// Helper function to publish a task
+/// Helper function to publish a task. In the transaction of a context with an outbox, the
+/// task is written to the outbox and only published once the transaction commits.
 pub async fn publish_task(
+    context: &Context,
+    task: TaskType,
+    destination: Option<&str>,
+) -> anyhow::Result<()> {
+    let event = TaskEvent::new(task);
+    publish_event(context, None, &event, destination).await
+}
+
+/// Helper function to publish a task like [`publish_task`], falling back to `producer`
+/// instead of the one of `context`, e.g. in workers whose context has none
+pub async fn publish_task_with_producer(
+    context: &Context,
     producer: &dyn MessageProducer,
     task: TaskType,
     destination: Option<&str>,
 ) -> anyhow::Result<()> {
     let event = TaskEvent::new(task);
-    publish_event(producer, &event, destination).await
+    publish_event(context, Some(producer), &event, destination).await
 }
 
-/// Helper function to publish a task with priority
+/// Helper function to publish a task with priority, through the outbox like [`publish_task`]
 pub async fn publish_task_with_priority(
-    producer: &dyn MessageProducer,
+    context: &Context,
     task: TaskType,
     priority: TaskPriority,
     destination: Option<&str>,
 ) -> anyhow::Result<()> {
     let event = TaskEvent::with_priority(task, priority);
-    publish_event(producer, &event, destination).await
+    publish_event(context, None, &event, destination).await
 }
 
-/// Helper function to publish a task event
+/// Helper function to publish a task event, straight to `producer` or the producer of
+/// `context` when the context has no outbox or no transaction
 async fn publish_event(
-    producer: &dyn MessageProducer,
+    context: &Context,
+    producer: Option<&dyn MessageProducer>,
     event: &TaskEvent,
     destination: Option<&str>,
 ) -> anyhow::Result<()> {
-    let event_json = serde_json::to_string(event)
-        .map_err(|e| anyhow::anyhow!("Failed to serialize event: {}", e))?;
-    producer.publish_event_json(&event_json, destination).await
+    if let Some(outbox) = &context.outbox
+        && !context.is_read_only()
+    {
+        return outbox.enqueue(context, event, destination).await;
+    }
+
+    let producer = producer
+        .or_else(|| {
+            context
+                .producer
+                .as_ref()
+                .map(|producer| producer.as_ref().as_ref())
+        })
+        .ok_or_else(|| anyhow::anyhow!("Producer not available"))?;
+    producer
+        .publish(&EncodedMessage::from_event(event)?, destination)
+        .await
 }
 
 #[cfg(test)]
@@ -51,8 +99,11 @@ mod tests {
 
     use async_trait::async_trait;
 
+    use sea_orm::{DbBackend, MockDatabase};
+
     use super::{TaskEvent, TaskPriority, TaskType, publish_task, publish_task_with_priority};
-    use crate::pkg::messaging::MessageProducer;
+    use crate::core::context::Context;
+    use crate::pkg::messaging::{EncodedMessage, MessageProducer};
 
     #[derive(Clone, Default)]
     struct MockProducer {
@@ -68,15 +119,24 @@ mod tests {
         fn set_fail_on_publish(&self, fail: bool) {
             *self.fail_on_publish.lock().unwrap() = fail;
         }
+
+        /// Context without an outbox, publishing straight to this producer
+        fn context(&self) -> Context {
+            let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+            Context::read_only(db)
+                .producer(Arc::new(Box::new(self.clone())))
+                .build()
+        }
     }
 
     #[async_trait]
     impl MessageProducer for MockProducer {
-        async fn publish_event_json(
+        async fn publish(
             &self,
-            event_json: &str,
+            message: &EncodedMessage,
             _destination: Option<&str>,
         ) -> anyhow::Result<()> {
+            let event_json = message.as_str();
             if *self.fail_on_publish.lock().unwrap() {
                 return Err(anyhow::anyhow!("Mock publish failure"));
             }
@@ -93,7 +153,7 @@ mod tests {
     async fn publishes_task_with_default_priority() {
         let producer = MockProducer::default();
 
-        publish_task(&producer, TaskType::CleanupExpiredToken, None)
+        publish_task(&producer.context(), TaskType::CleanupExpiredToken, None)
             .await
             .unwrap();
 
@@ -107,7 +167,7 @@ mod tests {
         let producer = MockProducer::default();
 
         publish_task_with_priority(
-            &producer,
+            &producer.context(),
             TaskType::ProcessUserRegistration { user_id: 42 },
             TaskPriority::High,
             Some("priority-topic"),
@@ -124,7 +184,7 @@ mod tests {
         let producer = MockProducer::default();
         producer.set_fail_on_publish(true);
 
-        let error = publish_task(&producer, TaskType::CleanupExpiredToken, None)
+        let error = publish_task(&producer.context(), TaskType::CleanupExpiredToken, None)
             .await
             .unwrap_err();
         assert!(error.to_string().contains("Mock publish failure"));
@@ -0,0 +1,239 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{ActiveValue::Set, DatabaseConnection, TransactionTrait};
+use serde_json::Value;
+
+use crate::{
+    common::{
+        entity::{dead_letter, outbox},
+        repository::{dead_letter_repository, outbox_repository},
+    },
+    config::{
+        app::AppState,
+        setting::{MessageType, OutboxSetting},
+    },
+    core::context::Context,
+    pkg::messaging::{EncodedMessage, MessageProducer},
+};
+
+use super::{PeriodicJob, TaskEvent};
+
+/// Entries the relay publishes per run
+const RELAY_BATCH_SIZE: u64 = 100;
+
+/// How long the relay holds an entry it publishes before another instance may pick it up
+const CLAIM_SECONDS: i64 = 60;
+
+/// Tasks written to the `outbox` table in the transaction that enqueues them. Each is
+/// published right after the transaction commits, and [`RelayOutbox`] retries those whose
+/// publish failed, so a rollback publishes nothing and a broker outage loses nothing.
+/// Entries are written claimed for that first publish, so the relay doesn't publish them
+/// again while it is in flight. The task event id goes along as the idempotency key, which
+/// workers use to skip tasks they already processed.
+pub struct Outbox {
+    db: DatabaseConnection,
+    producer: Arc<Box<dyn MessageProducer>>,
+    setting: OutboxSetting,
+}
+
+impl Outbox {
+    pub fn new(
+        db: DatabaseConnection,
+        producer: Arc<Box<dyn MessageProducer>>,
+        setting: OutboxSetting,
+    ) -> Self {
+        Self {
+            db,
+            producer,
+            setting,
+        }
+    }
+
+    /// Write `event` in the transaction of `context`, to be published once it commits
+    pub async fn enqueue(
+        self: &Arc<Self>,
+        context: &Context,
+        event: &TaskEvent,
+        destination: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let entry = outbox_repository::create(
+            context,
+            outbox::ActiveModel {
+                idempotency_key: Set(event.id.clone()),
+                destination: Set(destination.map(str::to_string)),
+                payload: Set(serde_json::to_string(event)?),
+                attempts: Set(0),
+                // Claimed by the publish after commit; the relay only picks it up if that
+                // fails, or never happens because the process stopped
+                next_attempt_at: Set(
+                    Utc::now().naive_utc() + chrono::Duration::seconds(CLAIM_SECONDS)
+                ),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        let outbox = self.clone();
+        context.after_commit(async move { outbox.dispatch(entry).await.map(|_| ()) });
+        Ok(())
+    }
+
+    /// Publish the entries due for a retry, returning how many were published
+    pub async fn relay(&self) -> anyhow::Result<usize> {
+        let now = Utc::now().naive_utc();
+        let due = outbox_repository::find_due(
+            &Context::read_only(self.db.clone()).build(),
+            now,
+            RELAY_BATCH_SIZE,
+        )
+        .await?;
+
+        let mut published = 0;
+        for entry in due {
+            let context = Context::builder(Arc::new(self.db.begin().await?)).build();
+            let claimed = outbox_repository::claim(
+                &context,
+                entry.id,
+                now,
+                now + chrono::Duration::seconds(CLAIM_SECONDS),
+            )
+            .await?;
+            context.commit().await?;
+
+            if claimed && self.dispatch(entry).await? {
+                published += 1;
+            }
+        }
+        Ok(published)
+    }
+
+    /// Delete the entries published more than `OUTBOX_RETENTION_HOURS` ago
+    pub async fn purge(&self) -> anyhow::Result<u64> {
+        let before = Utc::now().naive_utc() - chrono::Duration::hours(self.setting.retention_hours);
+        let context = Context::builder(Arc::new(self.db.begin().await?)).build();
+        let deleted = outbox_repository::delete_sent_before(&context, before).await?;
+        context.commit().await?;
+        Ok(deleted)
+    }
+
+    /// Publish `entry` and mark it sent, or count the failed attempt. Returns whether it was
+    /// published; failures are only logged, the entry stays for the relay.
+    async fn dispatch(&self, entry: outbox::Model) -> anyhow::Result<bool> {
+        let result = self
+            .producer
+            .publish(
+                &EncodedMessage::from_json(&entry.payload),
+                entry.destination.as_deref(),
+            )
+            .await;
+
+        let now = Utc::now().naive_utc();
+        let context = Context::builder(Arc::new(self.db.begin().await?)).build();
+        match &result {
+            Ok(()) => outbox_repository::mark_sent(&context, entry.id, now).await?,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to publish outbox entry {} (task {}): {:?}",
+                    entry.id,
+                    entry.idempotency_key,
+                    e
+                );
+                self.record_failure(&context, &entry, &e.to_string(), now)
+                    .await?;
+            }
+        }
+        context.commit().await?;
+
+        Ok(result.is_ok())
+    }
+
+    /// Schedule the next attempt of `entry`, or move it to `dead_letter` once it has none left
+    async fn record_failure(
+        &self,
+        context: &Context,
+        entry: &outbox::Model,
+        error: &str,
+        now: NaiveDateTime,
+    ) -> anyhow::Result<()> {
+        let attempts = entry.attempts + 1;
+        if attempts < self.setting.max_attempts as i32 {
+            outbox_repository::record_failure(
+                context,
+                entry.id,
+                attempts,
+                error,
+                self.next_attempt_at(now, attempts as u32),
+            )
+            .await?;
+            return Ok(());
+        }
+
+        let event: Value = serde_json::from_str(&entry.payload)?;
+        dead_letter_repository::create(
+            context,
+            dead_letter::ActiveModel {
+                task_id: Set(entry.idempotency_key.clone()),
+                task_type: Set(event
+                    .pointer("/task/type")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)),
+                topic: Set(entry
+                    .destination
+                    .clone()
+                    .unwrap_or_else(|| MessageType::default_str().to_string())),
+                event: Set(event),
+                attempts: Set(attempts),
+                error: Set(format!("Outbox publish failed: {}", error)),
+                worker_id: Set(None),
+                ..Default::default()
+            },
+        )
+        .await?;
+        outbox_repository::delete_by_id(context, entry.id).await?;
+        tracing::error!(
+            "Outbox entry {} (task {}) moved to dead_letter after {} attempts",
+            entry.id,
+            entry.idempotency_key,
+            attempts
+        );
+
+        Ok(())
+    }
+
+    fn next_attempt_at(&self, now: NaiveDateTime, attempts: u32) -> NaiveDateTime {
+        now + chrono::Duration::from_std(self.setting.retry_delay(attempts)).unwrap_or_default()
+    }
+}
+
+/// Publish the outbox entries whose publish after commit failed, and purge old published ones
+pub struct RelayOutbox;
+
+#[async_trait]
+impl PeriodicJob for RelayOutbox {
+    fn name(&self) -> &'static str {
+        "relay-outbox"
+    }
+
+    fn default_interval(&self) -> Duration {
+        Duration::from_secs(10)
+    }
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()> {
+        let Some(outbox) = &app_state.outbox else {
+            return Ok(());
+        };
+
+        let published = outbox.relay().await?;
+        if published > 0 {
+            tracing::warn!("Relayed {} outbox entries", published);
+        }
+        let purged = outbox.purge().await?;
+        if purged > 0 {
+            tracing::info!("Purged {} published outbox entries", purged);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use sea_orm::DatabaseConnection;
+
+use crate::{common::repository::task_pause_repository, core::context::Context};
+
+/// How long the worker trusts the paused task types it read last
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Paused task types and when they were read
+type PausedSnapshot = (Instant, HashSet<String>);
+
+/// Task types paused by admins through `/api/v1/admin/task-types/{task_type}/pause/`, as seen
+/// by a worker. Read from `task_pause` at most every few seconds; when that fails the
+/// previous set is kept.
+#[derive(Clone)]
+pub struct TaskPauses {
+    db: DatabaseConnection,
+    cache: Arc<Mutex<Option<PausedSnapshot>>>,
+}
+
+impl TaskPauses {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            db,
+            cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub async fn is_paused(&self, task_type: &str) -> bool {
+        if let Some((read_at, paused)) = &*self.cache.lock().unwrap()
+            && read_at.elapsed() < REFRESH_INTERVAL
+        {
+            return paused.contains(task_type);
+        }
+
+        let read = self.read().await;
+        let mut cache = self.cache.lock().unwrap();
+        let paused = match read {
+            Ok(paused) => paused,
+            Err(e) => {
+                tracing::warn!("Failed to read paused task types: {:?}", e);
+                cache.take().map(|(_, paused)| paused).unwrap_or_default()
+            }
+        };
+        let is_paused = paused.contains(task_type);
+        *cache = Some((Instant::now(), paused));
+
+        is_paused
+    }
+
+    async fn read(&self) -> Result<HashSet<String>, sea_orm::DbErr> {
+        let context = Context::read_only(self.db.clone()).build();
+        let pauses = task_pause_repository::find_all(&context).await?;
+
+        Ok(pauses.into_iter().map(|pause| pause.task_type).collect())
+    }
+}
@@ -0,0 +1,183 @@
+use serde::Serialize;
+
+use crate::{
+    core::event_type::PIPELINE_PROGRESS, pkg::broadcast::websocket::BroadcastMessage,
+    pkg::messaging::MessageProducer,
+};
+
+/// A step of a multi-stage pipeline, weighted by its share of the overall work
+#[derive(Debug, Clone, Copy)]
+pub struct Stage {
+    pub name: &'static str,
+    pub weight: u32,
+}
+
+/// Chained tasks reported to the client as one 0–100% progress
+#[derive(Debug, Clone, Copy)]
+pub struct Pipeline {
+    pub stages: &'static [Stage],
+}
+
+/// Upload → scan → process → thumbnails of an avatar
+pub const AVATAR_PIPELINE: Pipeline = Pipeline::new(&[
+    Stage {
+        name: "scanning",
+        weight: 20,
+    },
+    Stage {
+        name: "processing",
+        weight: 50,
+    },
+    Stage {
+        name: "thumbnails",
+        weight: 30,
+    },
+]);
+
+/// `data` of a `pipeline_progress` broadcast
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PipelineProgress {
+    pub task_id: String,
+    pub user_id: i32,
+    pub progress: u8,
+    pub stage: &'static str,
+    pub stage_index: usize,
+    pub stage_count: usize,
+    pub stage_progress: u8,
+    pub status: &'static str,
+}
+
+impl Pipeline {
+    pub const fn new(stages: &'static [Stage]) -> Self {
+        Self { stages }
+    }
+
+    /// Overall progress once `stage` is `stage_progress` percent done.
+    /// Only the last stage finishing completes the pipeline.
+    pub fn progress(
+        &self,
+        task_id: &str,
+        user_id: i32,
+        stage: &str,
+        stage_progress: u8,
+    ) -> Option<PipelineProgress> {
+        let stage_index = self.stages.iter().position(|s| s.name == stage)?;
+        let stage_progress = stage_progress.min(100);
+        let total: u32 = self.stages.iter().map(|s| s.weight).sum::<u32>().max(1);
+        let done: u32 = self.stages[..stage_index].iter().map(|s| s.weight).sum();
+        let current = self.stages[stage_index].weight * u32::from(stage_progress) / 100;
+        let last = stage_index + 1 == self.stages.len();
+
+        Some(PipelineProgress {
+            task_id: task_id.to_string(),
+            user_id,
+            progress: ((done + current) * 100 / total) as u8,
+            stage: self.stages[stage_index].name,
+            stage_index,
+            stage_count: self.stages.len(),
+            stage_progress,
+            status: if last && stage_progress == 100 {
+                "completed"
+            } else {
+                "processing"
+            },
+        })
+    }
+
+    /// Publish the overall progress of `task_id` to its WebSocket channel
+    pub async fn report(
+        &self,
+        producer: &dyn MessageProducer,
+        task_id: &str,
+        user_id: i32,
+        stage: &str,
+        stage_progress: u8,
+    ) -> anyhow::Result<()> {
+        let progress = self
+            .progress(task_id, user_id, stage, stage_progress)
+            .ok_or_else(|| anyhow::anyhow!("Unknown pipeline stage {}", stage))?;
+        publish(producer, &progress).await
+    }
+
+    /// Publish that the pipeline of `task_id` stopped at `stage`
+    pub async fn fail(
+        &self,
+        producer: &dyn MessageProducer,
+        task_id: &str,
+        user_id: i32,
+        stage: &str,
+    ) -> anyhow::Result<()> {
+        let mut progress = self
+            .progress(task_id, user_id, stage, 0)
+            .ok_or_else(|| anyhow::anyhow!("Unknown pipeline stage {}", stage))?;
+        progress.status = "failed";
+        publish(producer, &progress).await
+    }
+}
+
+async fn publish(
+    producer: &dyn MessageProducer,
+    progress: &PipelineProgress,
+) -> anyhow::Result<()> {
+    let broadcast_msg = BroadcastMessage {
+        event_type: PIPELINE_PROGRESS.name.to_string(),
+        data: serde_json::to_value(progress)?,
+    };
+
+    producer
+        .publish_event_json(&serde_json::to_string(&broadcast_msg)?, Some("broadcasts"))
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to publish {}: {}", PIPELINE_PROGRESS.name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AVATAR_PIPELINE, Pipeline, Stage};
+
+    #[test]
+    fn weights_stage_progress_into_overall_progress() {
+        let at = |stage, stage_progress| {
+            AVATAR_PIPELINE
+                .progress("task", 1, stage, stage_progress)
+                .unwrap()
+        };
+
+        assert_eq!(at("scanning", 0).progress, 0);
+        assert_eq!(at("scanning", 100).progress, 20);
+        assert_eq!(at("processing", 50).progress, 45);
+        assert_eq!(at("thumbnails", 0).progress, 70);
+        assert_eq!(at("thumbnails", 100).progress, 100);
+        assert_eq!(at("processing", 100).stage_index, 1);
+        assert_eq!(at("processing", 100).stage_count, 3);
+    }
+
+    #[test]
+    fn completes_only_when_last_stage_finishes() {
+        let processing = AVATAR_PIPELINE.progress("task", 1, "processing", 100);
+        let thumbnails = AVATAR_PIPELINE.progress("task", 1, "thumbnails", 100);
+
+        assert_eq!(processing.unwrap().status, "processing");
+        assert_eq!(thumbnails.unwrap().status, "completed");
+    }
+
+    #[test]
+    fn normalizes_weights_and_rejects_unknown_stages() {
+        const PIPELINE: Pipeline = Pipeline::new(&[
+            Stage {
+                name: "first",
+                weight: 1,
+            },
+            Stage {
+                name: "second",
+                weight: 3,
+            },
+        ]);
+
+        assert_eq!(PIPELINE.progress("t", 1, "second", 0).unwrap().progress, 25);
+        assert_eq!(
+            PIPELINE.progress("t", 1, "first", 200).unwrap().progress,
+            25
+        );
+        assert!(PIPELINE.progress("t", 1, "missing", 0).is_none());
+    }
+}
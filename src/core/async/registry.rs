@@ -0,0 +1,295 @@
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::pkg::{
+    cache::TaskQuota,
+    messaging::{TaskEvent, TaskHandler, TaskLifecycle},
+};
+
+use super::{ConcreteTaskHandler, TaskHistoryRecorder, TaskLeases, TaskPauses, TaskType};
+
+/// Task payload consumed by the worker: a built-in `TaskType`, or a task registered
+/// through `AppBuilder::add_task_handler` and routed by its `type` tag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RoutedTask {
+    Builtin(TaskType),
+    Custom(Value),
+}
+
+/// Handlers for task types defined outside this crate, keyed by their `type` tag
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    handlers: HashMap<String, Arc<dyn TaskHandler<Value>>>,
+}
+
+impl TaskRegistry {
+    /// Register `handler` for tasks whose `type` tag is `task_type`.
+    /// The payload is deserialized into `T` before the handler runs.
+    pub fn register<T, H>(&mut self, task_type: impl Into<String>, handler: H)
+    where
+        T: Clone + Send + Sync + DeserializeOwned + 'static,
+        H: TaskHandler<T> + 'static,
+    {
+        self.handlers.insert(
+            task_type.into(),
+            Arc::new(TypedTaskHandler {
+                handler,
+                _task: PhantomData,
+            }),
+        );
+    }
+
+    /// Registered task type tags
+    pub fn task_types(&self) -> Vec<&str> {
+        let mut task_types: Vec<&str> = self.handlers.keys().map(String::as_str).collect();
+        task_types.sort_unstable();
+        task_types
+    }
+
+    /// Dispatch a custom task to the handler registered for its `type` tag
+    pub async fn handle(&self, event: &TaskEvent<Value>) -> anyhow::Result<()> {
+        let task_type = event
+            .task
+            .get("type")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("Task {} has no type tag", event.id))?;
+        let handler = self
+            .handlers
+            .get(task_type)
+            .ok_or_else(|| anyhow::anyhow!("No handler registered for task type {}", task_type))?;
+
+        handler.handle_task(event).await
+    }
+}
+
+/// Adapts a handler for a concrete task type to the registry's JSON payloads
+struct TypedTaskHandler<T, H> {
+    handler: H,
+    _task: PhantomData<fn() -> T>,
+}
+
+#[async_trait]
+impl<T, H> TaskHandler<Value> for TypedTaskHandler<T, H>
+where
+    T: Clone + Send + Sync + DeserializeOwned + 'static,
+    H: TaskHandler<T>,
+{
+    async fn handle_task(&self, event: &TaskEvent<Value>) -> anyhow::Result<()> {
+        let task: T = serde_json::from_value(event.task.clone())
+            .map_err(|e| anyhow::anyhow!("Invalid payload for task {}: {}", event.id, e))?;
+        self.handler.handle_task(&event.with_task(task)).await
+    }
+}
+
+/// Worker entry handler: built-in tasks go to `ConcreteTaskHandler`, the rest to the registry
+pub struct RoutingTaskHandler {
+    builtin: ConcreteTaskHandler,
+    registry: TaskRegistry,
+    history: Option<TaskHistoryRecorder>,
+    pauses: Option<TaskPauses>,
+    quota: Option<Arc<dyn TaskQuota>>,
+    leases: Option<TaskLeases>,
+}
+
+impl RoutingTaskHandler {
+    pub fn new(builtin: ConcreteTaskHandler, registry: TaskRegistry) -> Self {
+        Self {
+            builtin,
+            registry,
+            history: None,
+            pauses: None,
+            quota: None,
+            leases: None,
+        }
+    }
+
+    /// Record every task's lifecycle with `history`, and skip tasks it shows already finished
+    pub fn with_history(mut self, history: TaskHistoryRecorder) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Hold back tasks whose task type is paused in `pauses`
+    pub fn with_pauses(mut self, pauses: TaskPauses) -> Self {
+        self.pauses = Some(pauses);
+        self
+    }
+
+    /// Free the quota slot of each task once it completed or failed for good
+    pub fn with_quota(mut self, quota: Arc<dyn TaskQuota>) -> Self {
+        self.quota = Some(quota);
+        self
+    }
+
+    /// Lease each task to this worker while it runs, so it is recovered if the worker stops
+    pub fn with_leases(mut self, leases: TaskLeases) -> Self {
+        self.leases = Some(leases);
+        self
+    }
+}
+
+impl RoutedTask {
+    /// `type` tag of the task, e.g. `SendEmail`
+    pub fn task_type(&self) -> Option<String> {
+        let tagged = match self {
+            Self::Builtin(task) => serde_json::to_value(task).ok()?,
+            Self::Custom(task) => task.clone(),
+        };
+        tagged.get("type")?.as_str().map(str::to_string)
+    }
+
+    /// `task_id` the task reports progress under, set for tasks started by a user action
+    pub fn reference(&self) -> Option<String> {
+        let tagged = match self {
+            Self::Builtin(task) => serde_json::to_value(task).ok()?,
+            Self::Custom(task) => task.clone(),
+        };
+        tagged.get("task_id")?.as_str().map(str::to_string)
+    }
+}
+
+#[async_trait]
+impl TaskHandler<RoutedTask> for RoutingTaskHandler {
+    async fn handle_task(&self, event: &TaskEvent<RoutedTask>) -> anyhow::Result<()> {
+        match &event.task {
+            RoutedTask::Builtin(task) => {
+                self.builtin
+                    .handle_task(&event.with_task(task.clone()))
+                    .await
+            }
+            RoutedTask::Custom(task) => self.registry.handle(&event.with_task(task.clone())).await,
+        }
+    }
+
+    async fn on_lifecycle(&self, event: &TaskEvent<RoutedTask>, lifecycle: &TaskLifecycle) {
+        if let Some(history) = &self.history {
+            history.record(event, lifecycle).await;
+        }
+        if let Some(leases) = &self.leases {
+            match lifecycle {
+                TaskLifecycle::Enqueued => {}
+                TaskLifecycle::Started => leases.claim(event).await,
+                _ => leases.release(&event.id).await,
+            }
+        }
+        if let (Some(quota), TaskLifecycle::Completed | TaskLifecycle::Failed { .. }) =
+            (&self.quota, lifecycle)
+            && let Some(reference) = event.task.reference()
+            && let Err(e) = quota.release(&reference).await
+        {
+            tracing::warn!("Failed to release task quota of {}: {:?}", reference, e);
+        }
+    }
+
+    async fn is_paused(&self, event: &TaskEvent<RoutedTask>) -> bool {
+        let (Some(pauses), Some(task_type)) = (&self.pauses, event.task.task_type()) else {
+            return false;
+        };
+        pauses.is_paused(&task_type).await
+    }
+
+    async fn is_processed(&self, event: &TaskEvent<RoutedTask>) -> bool {
+        match &self.history {
+            Some(history) => history.is_processed(event).await,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use serde::{Deserialize, Serialize};
+
+    use super::{RoutedTask, TaskRegistry};
+    use crate::{
+        core::r#async::TaskType,
+        pkg::messaging::{TaskEvent, TaskHandler},
+    };
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    enum ReportTask {
+        BuildReport { report_id: i32 },
+    }
+
+    struct RecordingHandler(Arc<Mutex<Vec<i32>>>);
+
+    #[async_trait]
+    impl TaskHandler<ReportTask> for RecordingHandler {
+        async fn handle_task(&self, event: &TaskEvent<ReportTask>) -> anyhow::Result<()> {
+            let ReportTask::BuildReport { report_id } = event.task;
+            self.0.lock().unwrap().push(report_id);
+            Ok(())
+        }
+    }
+
+    fn parse(event_json: &str) -> TaskEvent<RoutedTask> {
+        serde_json::from_str(event_json).unwrap()
+    }
+
+    #[test]
+    fn routes_known_task_types_to_builtin() {
+        let json = serde_json::to_string(&TaskEvent::new(TaskType::CleanupExpiredToken)).unwrap();
+
+        assert!(matches!(
+            parse(&json).task,
+            RoutedTask::Builtin(TaskType::CleanupExpiredToken)
+        ));
+
+        let custom =
+            serde_json::to_string(&TaskEvent::new(ReportTask::BuildReport { report_id: 7 }))
+                .unwrap();
+        assert!(matches!(parse(&custom).task, RoutedTask::Custom(_)));
+    }
+
+    #[test]
+    fn reads_type_tag_of_builtin_and_custom_tasks() {
+        let builtin = RoutedTask::Builtin(TaskType::CleanupExpiredToken);
+        let custom = RoutedTask::Custom(serde_json::json!({ "type": "BuildReport" }));
+
+        assert_eq!(builtin.task_type().as_deref(), Some("CleanupExpiredToken"));
+        assert_eq!(custom.task_type().as_deref(), Some("BuildReport"));
+    }
+
+    #[test]
+    fn reads_task_id_of_tasks_started_by_users() {
+        let upload = RoutedTask::Builtin(TaskType::GenerateThumbnails {
+            task_id: "task-1".to_string(),
+            file_id: 1,
+        });
+        let cleanup = RoutedTask::Builtin(TaskType::CleanupExpiredToken);
+
+        assert_eq!(upload.reference().as_deref(), Some("task-1"));
+        assert_eq!(cleanup.reference(), None);
+    }
+
+    #[tokio::test]
+    async fn dispatches_custom_tasks_by_type_tag() {
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = TaskRegistry::default();
+        registry.register("BuildReport", RecordingHandler(handled.clone()));
+
+        let event = TaskEvent::new(serde_json::json!({ "type": "BuildReport", "report_id": 7 }));
+        registry.handle(&event).await.unwrap();
+
+        assert_eq!(*handled.lock().unwrap(), vec![7]);
+        assert_eq!(registry.task_types(), vec!["BuildReport"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_unregistered_task_types() {
+        let registry = TaskRegistry::default();
+        let event = TaskEvent::new(serde_json::json!({ "type": "Unknown" }));
+
+        let error = registry.handle(&event).await.unwrap_err();
+
+        assert!(error.to_string().contains("No handler registered"));
+    }
+}
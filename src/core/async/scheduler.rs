@@ -0,0 +1,95 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rand::RngExt;
+use tokio::{task::JoinSet, time::Instant};
+use tracing::{error, info};
+
+use crate::config::{app::AppState, setting::SchedulerSetting};
+
+/// Maintenance work run periodically inside the server process
+#[async_trait]
+pub trait PeriodicJob: Send + Sync {
+    /// Identifier used for `SCHEDULER_INTERVALS` overrides and logs
+    fn name(&self) -> &'static str;
+
+    /// Interval used when `SCHEDULER_INTERVALS` has no entry for this job
+    fn default_interval(&self) -> Duration;
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()>;
+}
+
+/// Runs every registered `PeriodicJob` on its own interval until the app shuts down
+pub struct Scheduler {
+    jobs: Vec<Arc<dyn PeriodicJob>>,
+    setting: SchedulerSetting,
+}
+
+impl Scheduler {
+    pub fn new(jobs: Vec<Arc<dyn PeriodicJob>>, setting: SchedulerSetting) -> Self {
+        Self { jobs, setting }
+    }
+
+    /// Spawn one loop per enabled job; the returned task ends once every loop has
+    /// observed `app_state.shutdown_token`
+    pub fn spawn(self, app_state: AppState) -> tokio::task::JoinHandle<()> {
+        let mut loops = JoinSet::new();
+
+        if self.setting.enabled {
+            let max_jitter = Duration::from_secs(self.setting.jitter_seconds);
+            for job in self.jobs {
+                match self
+                    .setting
+                    .interval_for(job.name(), job.default_interval())
+                {
+                    Some(interval) => {
+                        info!("✓ Scheduled {} every {:?}", job.name(), interval);
+                        loops.spawn(run_job(job, interval, max_jitter, app_state.clone()));
+                    }
+                    None => info!("⚠ Periodic job {} is disabled", job.name()),
+                }
+            }
+        } else {
+            info!("⚠ Scheduler disabled (SCHEDULER_ENABLED=false)");
+        }
+
+        tokio::spawn(async move { while loops.join_next().await.is_some() {} })
+    }
+}
+
+/// Runs are awaited in place, so a run that outlasts its interval delays the next one
+/// instead of overlapping it
+async fn run_job(
+    job: Arc<dyn PeriodicJob>,
+    interval: Duration,
+    max_jitter: Duration,
+    app_state: AppState,
+) {
+    let shutdown_token = app_state.shutdown_token.clone();
+
+    loop {
+        let delay = interval + jitter(max_jitter);
+        tokio::select! {
+            _ = shutdown_token.cancelled() => break,
+            _ = tokio::time::sleep(delay) => {}
+        }
+
+        let started_at = Instant::now();
+        match job.run(&app_state).await {
+            Ok(()) => info!(
+                "✓ Periodic job {} finished in {:?}",
+                job.name(),
+                started_at.elapsed()
+            ),
+            Err(e) => error!("Periodic job {} failed: {:?}", job.name(), e),
+        }
+    }
+}
+
+/// Random delay up to `max_jitter`, so instances sharing a schedule don't run in lockstep
+fn jitter(max_jitter: Duration) -> Duration {
+    if max_jitter.is_zero() {
+        return Duration::ZERO;
+    }
+    Duration::from_millis(rand::rng().random_range(0..=max_jitter.as_millis() as u64))
+}
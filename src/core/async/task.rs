@@ -1,19 +1,34 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
+use chrono::Utc;
 use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 
 use crate::{
+    config::setting::{MessageType, ReportSetting, Setting},
+    file::task::file_task,
+    notification::task::{
+        digest_task,
+        push_task::{self, PushDelivery},
+    },
     pkg::{
+        antivirus::{ScanVerdict, VirusScanner},
         messaging::{MessageProducer, TaskHandler},
+        push::PushNotification,
+        sms::SmsSender,
         smtp::SmtpClient,
+        storage::ObjectStorage,
+    },
+    report::task::report_task,
+    user::{
+        dto::bulk_user_dto::BulkUserOperationDTO,
+        task::{auth_task, user_task},
     },
-    user::task::{auth_task, user_task},
 };
 
-use super::TaskEvent;
+use super::{AVATAR_PIPELINE, TaskEvent};
 
 /// Application-specific task types that can be processed by the worker
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,18 +42,55 @@ pub enum TaskType {
         html_body: Option<String>,
     },
 
+    /// Send a text message to a phone number
+    SendSms { to: String, body: String },
+
+    /// Send a push notification to every registered device of a user
+    SendPushNotification {
+        user_id: i32,
+        title: String,
+        body: String,
+        #[serde(default)]
+        data: HashMap<String, String>,
+    },
+
+    /// Email users a digest of their unread in-app notifications
+    SendNotificationDigests,
+
     /// Clean up expired data
     CleanupExpiredToken,
 
     /// Process user registration
     ProcessUserRegistration { user_id: i32 },
 
+    /// Email a user the link confirming their address
+    SendVerificationEmail { user_id: i32 },
+
     /// Process avatar upload with progress tracking
     ProcessAvatarUpload {
         task_id: String,
         user_id: i32,
         file_name: String,
         locale: String,
+        #[serde(default)]
+        file_id: Option<i32>,
+    },
+
+    /// Generate resized variants of an uploaded image
+    GenerateThumbnails { task_id: String, file_id: i32 },
+
+    /// Reconcile stored objects with file records and report orphans to admins
+    CleanupOrphanedFiles,
+
+    /// Aggregate recent activity into a report, store it and email admins a link to it
+    GenerateReport,
+
+    /// Apply a batch of bulk user operations too large to process in the request
+    ProcessBulkUserOperations {
+        task_id: String,
+        actor_id: i32,
+        locale: String,
+        operations: Vec<BulkUserOperationDTO>,
     },
 }
 
@@ -49,6 +101,12 @@ pub struct ConcreteTaskHandler {
     producer: Arc<Box<dyn MessageProducer>>,
     smtp_client: Option<SmtpClient>,
     redis_url: String,
+    storage: Arc<dyn ObjectStorage>,
+    scanner: Option<Arc<dyn VirusScanner>>,
+    thumbnail_sizes: Vec<u32>,
+    push: PushDelivery,
+    sms: Option<Arc<dyn SmsSender>>,
+    report: ReportSetting,
 }
 
 impl ConcreteTaskHandler {
@@ -58,13 +116,59 @@ impl ConcreteTaskHandler {
         smtp_client: Option<SmtpClient>,
         redis_url: String,
     ) -> anyhow::Result<Self> {
+        let setting = Setting::new();
+        let http = setting.get_http_client()?;
         Ok(Self {
             db,
             producer,
             smtp_client,
             redis_url,
+            storage: Arc::new(setting.get_storage()),
+            scanner: setting
+                .get_virus_scanner()
+                .map(|scanner| Arc::new(scanner) as Arc<dyn VirusScanner>),
+            thumbnail_sizes: setting.thumbnail_sizes,
+            push: PushDelivery::from_setting(&setting.push, &http),
+            sms: setting.sms.get_sms_sender(&http),
+            report: setting.report,
         })
     }
+
+    /// Override the object storage used by file processing tasks
+    pub fn with_storage(mut self, storage: Arc<dyn ObjectStorage>) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Override the virus scanner used for uploads (`None` disables scanning)
+    pub fn with_scanner(mut self, scanner: Option<Arc<dyn VirusScanner>>) -> Self {
+        self.scanner = scanner;
+        self
+    }
+
+    /// Override the thumbnail sizes generated for uploaded images
+    pub fn with_thumbnail_sizes(mut self, thumbnail_sizes: Vec<u32>) -> Self {
+        self.thumbnail_sizes = thumbnail_sizes;
+        self
+    }
+
+    /// Override the push providers used to reach user devices
+    pub fn with_push(mut self, push: PushDelivery) -> Self {
+        self.push = push;
+        self
+    }
+
+    /// Override the SMS provider (`None` disables text messages)
+    pub fn with_sms(mut self, sms: Option<Arc<dyn SmsSender>>) -> Self {
+        self.sms = sms;
+        self
+    }
+
+    /// Override the aggregations, format and recipients of generated reports
+    pub fn with_report(mut self, report: ReportSetting) -> Self {
+        self.report = report;
+        self
+    }
 }
 
 #[async_trait]
@@ -96,6 +200,41 @@ impl TaskHandler<TaskType> for ConcreteTaskHandler {
                 .await
                 .map_err(|e| anyhow::anyhow!("Failed to send email to {}: {}", to, e)),
 
+            TaskType::SendSms { to, body } => self
+                .sms
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("SMS provider not configured"))?
+                .send(to, body)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to send SMS to {}: {}", to, e)),
+
+            TaskType::SendPushNotification {
+                user_id,
+                title,
+                body,
+                data,
+            } => {
+                push_task::send_push_notification(
+                    &self.db,
+                    &self.push,
+                    *user_id,
+                    &PushNotification {
+                        title: title.clone(),
+                        body: body.clone(),
+                        data: data.clone(),
+                    },
+                )
+                .await
+            }
+
+            TaskType::SendNotificationDigests => digest_task::send_notification_digests(
+                &self.db,
+                self.producer.as_ref().as_ref(),
+                Utc::now(),
+            )
+            .await
+            .map(|_| ()),
+
             TaskType::CleanupExpiredToken => auth_task::clean_expired_tokens(&self.db).await,
 
             TaskType::ProcessUserRegistration { user_id } => {
@@ -103,23 +242,130 @@ impl TaskHandler<TaskType> for ConcreteTaskHandler {
                     .await
             }
 
+            TaskType::SendVerificationEmail { user_id } => {
+                user_task::send_verification_email(
+                    &self.db,
+                    self.producer.as_ref().as_ref(),
+                    *user_id,
+                )
+                .await
+            }
+
             TaskType::ProcessAvatarUpload {
                 task_id,
                 user_id,
                 file_name,
                 locale,
+                file_id,
             } => {
-                user_task::process_avatar_upload(
+                async {
+                    let producer = self.producer.as_ref().as_ref();
+                    AVATAR_PIPELINE
+                        .report(producer, task_id, *user_id, "scanning", 0)
+                        .await?;
+
+                    let verdict = match file_id {
+                        Some(file_id) => {
+                            file_task::scan_upload(
+                                &self.db,
+                                producer,
+                                self.storage.as_ref(),
+                                self.scanner.as_deref(),
+                                task_id,
+                                *file_id,
+                                locale,
+                            )
+                            .await?
+                        }
+                        None => ScanVerdict::Clean,
+                    };
+
+                    if !verdict.is_clean() {
+                        return AVATAR_PIPELINE
+                            .fail(producer, task_id, *user_id, "scanning")
+                            .await;
+                    }
+                    AVATAR_PIPELINE
+                        .report(producer, task_id, *user_id, "scanning", 100)
+                        .await?;
+
+                    user_task::process_avatar_upload(
+                        &self.db,
+                        producer,
+                        &self.redis_url,
+                        task_id.clone(),
+                        *user_id,
+                        file_name.clone(),
+                        locale.clone(),
+                    )
+                    .await?;
+
+                    match file_id {
+                        Some(file_id) => {
+                            file_task::mark_available(&self.db, *file_id).await?;
+                            TaskEvent::new(TaskType::GenerateThumbnails {
+                                task_id: task_id.clone(),
+                                file_id: *file_id,
+                            })
+                            .publish_with_producer(producer, Some(MessageType::Tasks.as_ref()))
+                            .await
+                        }
+                        // Nothing stored to resize, so the thumbnail stage is already done
+                        None => {
+                            AVATAR_PIPELINE
+                                .report(producer, task_id, *user_id, "thumbnails", 100)
+                                .await
+                        }
+                    }
+                }
+                .await
+            }
+
+            TaskType::GenerateThumbnails { task_id, file_id } => {
+                file_task::generate_thumbnails(
                     &self.db,
                     self.producer.as_ref().as_ref(),
-                    &self.redis_url,
-                    task_id.clone(),
-                    *user_id,
-                    file_name.clone(),
-                    locale.clone(),
+                    self.storage.as_ref(),
+                    task_id,
+                    *file_id,
+                    &self.thumbnail_sizes,
                 )
                 .await
             }
+
+            TaskType::CleanupOrphanedFiles => file_task::cleanup_orphaned_files(
+                &self.db,
+                self.producer.as_ref().as_ref(),
+                self.storage.as_ref(),
+            )
+            .await
+            .map(|_| ()),
+
+            TaskType::GenerateReport => report_task::generate_report(
+                &self.db,
+                self.producer.as_ref().as_ref(),
+                self.storage.as_ref(),
+                &self.report,
+                Utc::now(),
+            )
+            .await
+            .map(|_| ()),
+
+            TaskType::ProcessBulkUserOperations {
+                task_id,
+                actor_id,
+                locale,
+                operations,
+            } => user_task::process_bulk_user_operations(
+                &self.db,
+                self.producer.as_ref().as_ref(),
+                task_id,
+                *actor_id,
+                locale,
+                operations,
+            )
+            .await
+            .map(|_| ()),
         };
 
         match result {
@@ -1,17 +1,34 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use tokio::sync::Semaphore;
+use tokio::{sync::Semaphore, task::JoinHandle};
 use tokio_cron_scheduler::JobScheduler;
 use tracing::{error, info};
 
 use crate::config::{setting::Setting, shutdown::wait_for_shutdown_signal};
 use crate::core::db::connection::get_db;
-use crate::pkg::messaging::{ConsumerConfig, create_consumer, create_producer};
+use crate::pkg::antivirus::VirusScanner;
+use crate::pkg::broadcast::{coalescer::CoalescingProducer, websocket::BroadcastMessage};
+use crate::pkg::cache::RedisTaskQuota;
+use crate::pkg::circuit_breaker;
+use crate::pkg::messaging::{
+    CircuitBreakingProducer, ConsumerConfig, MessageProducer, RoutingProducer, create_consumer,
+    create_producer,
+    stats::{WORKER_STATS_EVENT, local_worker_stats},
+};
+use crate::pkg::url::mask_url;
 
-use super::ConcreteTaskHandler;
+use super::{
+    ArchivingProducer, ConcreteTaskHandler, RoutingTaskHandler, TaskHistoryRecorder, TaskLeases,
+    TaskPauses, TaskRegistry,
+};
 
 /// Initialize and run the worker service
 pub async fn run(setting: Setting) -> anyhow::Result<()> {
+    run_with_tasks(setting, TaskRegistry::default()).await
+}
+
+/// Run the worker service, dispatching task types outside `TaskType` to `registry`
+pub async fn run_with_tasks(setting: Setting, registry: TaskRegistry) -> anyhow::Result<()> {
     info!("🚀 Starting worker service...");
 
     // Load consumer configuration from settings
@@ -23,26 +40,27 @@ pub async fn run(setting: Setting) -> anyhow::Result<()> {
             brokers,
             consumer_group,
             topics,
+            ..
         } => {
             info!("  Broker: Kafka");
             info!("  Brokers: {}", brokers);
             info!("  Consumer group: {}", consumer_group);
             info!("  Topics: {:?}", topics);
         }
-        ConsumerConfig::Redis { url, channels } => {
+        ConsumerConfig::Redis { url, channels, .. } => {
             info!("  Broker: Redis");
-            info!("  URL: {}", url);
+            info!("  URL: {}", mask_url(url));
             info!("  Channels: {:?}", channels);
         }
-        ConsumerConfig::RabbitMQ { url, queues } => {
+        ConsumerConfig::RabbitMQ { url, queues, .. } => {
             info!("  Broker: RabbitMQ");
-            info!("  URL: {}", url);
+            info!("  URL: {}", mask_url(url));
             info!("  Queues: {:?}", queues);
         }
     }
 
     info!("  Worker pool size: {}", setting.messaging.worker_pool_size);
-    info!("  Database: {}", setting.database_url);
+    info!("  Database: {}", mask_url(&setting.database_url));
 
     // Initialize task scheduler for periodic tasks
     let mut scheduler = JobScheduler::new().await?;
@@ -52,6 +70,13 @@ pub async fn run(setting: Setting) -> anyhow::Result<()> {
     let db = get_db(&setting.database_url).await?;
     info!("✓ Database connection initialized");
 
+    // Identifies this worker in task history and stats reports
+    let worker_id = format!("worker-{}", uuid::Uuid::new_v4());
+    info!("  Worker id: {}", worker_id);
+
+    // Breakers guard SMTP, the broker, storage and third-party APIs from here on
+    circuit_breaker::configure(setting.circuit_breaker.clone());
+
     // Initialize SMTP client
     let smtp_client = setting
         .get_smtp_client()
@@ -66,21 +91,95 @@ pub async fn run(setting: Setting) -> anyhow::Result<()> {
         info!("⚠ SMTP client not configured (email tasks will fail)");
     }
 
+    // Producer, consumer, task status cache and task quotas share one connection when the
+    // broker is Redis
+    let redis = match &consumer_config {
+        ConsumerConfig::Redis { .. } => Some(setting.get_redis().await?),
+        _ if setting.task_quota.is_enabled() => Some(setting.get_redis().await?),
+        _ => None,
+    };
+    if redis.is_some() {
+        info!("✓ Shared Redis connection initialized");
+    }
+
     // Initialize message producer
     let producer_config = setting
         .to_producer_config()
         .ok_or_else(|| anyhow::anyhow!("Message broker is not configured for worker"))?;
-    let producer = Arc::new(create_producer(producer_config).await?);
+    let mut producer: Arc<Box<dyn MessageProducer>> = Arc::new(Box::new(
+        CircuitBreakingProducer::new(Arc::new(create_producer(producer_config).await?)),
+    ));
     info!("✓ Message producer initialized");
+    if setting.broadcast_archive.enabled {
+        producer = Arc::new(Box::new(ArchivingProducer::new(producer, db.clone())));
+        info!("✓ Broadcasts archived to broadcast_event");
+    }
+    if let Some(window) = setting.messaging.broadcast_coalesce_window() {
+        producer = Arc::new(Box::new(CoalescingProducer::new(
+            producer,
+            "broadcasts",
+            window,
+        )));
+        info!("✓ Broadcast coalescing enabled ({:?} window)", window);
+    }
+    if let Some(router) = setting.messaging.destination_router()? {
+        producer = Arc::new(Box::new(RoutingProducer::new(producer, router)));
+        info!("✓ Task destinations routed by MESSAGE_ROUTES");
+    }
 
     // Initialize task handler
-    let task_handler = Arc::new(ConcreteTaskHandler::new(
-        db,
+    let builtin_handler = ConcreteTaskHandler::new(
+        db.clone(),
         producer.clone(),
         smtp_client,
         setting.redis_url.clone(),
-    )?);
+    )?
+    .with_storage(Arc::new(setting.get_storage()))
+    .with_scanner(
+        setting
+            .get_virus_scanner()
+            .map(|scanner| Arc::new(scanner) as Arc<dyn VirusScanner>),
+    )
+    .with_thumbnail_sizes(setting.thumbnail_sizes.clone());
+    if !registry.task_types().is_empty() {
+        info!("  Custom task types: {:?}", registry.task_types());
+    }
+    let mut task_handler = RoutingTaskHandler::new(builtin_handler, registry)
+        .with_history(TaskHistoryRecorder::new(db.clone(), worker_id.clone()))
+        .with_pauses(TaskPauses::new(db.clone()));
+    if let Some(redis) = redis.as_ref().filter(|_| setting.task_quota.is_enabled()) {
+        task_handler = task_handler.with_quota(Arc::new(RedisTaskQuota::from_manager(
+            redis,
+            setting.task_quota.limits(),
+        )));
+        info!("✓ Task quota slots released as tasks finish");
+    }
+    let leases = setting
+        .task_lease
+        .is_enabled()
+        .then(|| TaskLeases::new(db, worker_id.clone(), setting.task_lease.lease()));
+    if let Some(leases) = &leases {
+        task_handler = task_handler.with_leases(leases.clone());
+        info!(
+            "✓ Tasks leased for {:?}, renewed every {:?}",
+            setting.task_lease.lease(),
+            setting.task_lease.heartbeat_interval()
+        );
+    }
+    let task_handler = Arc::new(task_handler);
     info!("✓ Task handler initialized");
+    if (setting.push.fcm_project_id.is_some() && setting.push.fcm_access_token.is_some())
+        || setting.push.apns_key_path.is_some()
+    {
+        info!("✓ Push notifications enabled");
+    } else {
+        info!("⚠ FCM/APNs not configured (push notification tasks will skip devices)");
+    }
+    if setting.clamav_address.is_some() {
+        info!("✓ Virus scanning enabled for uploads");
+    } else {
+        info!("⚠ CLAMAV_ADDRESS not configured (uploads will not be virus-scanned)");
+    }
 
     // Initialize worker pool semaphore
     let semaphore = Arc::new(Semaphore::new(setting.messaging.worker_pool_size));
@@ -107,6 +206,18 @@ pub async fn run(setting: Setting) -> anyhow::Result<()> {
     scheduler.start().await?;
     info!("✓ Scheduler started");
 
+    let stats_reporter = setting
+        .messaging
+        .worker_stats_interval()
+        .map(|interval| spawn_stats_reporter(producer.clone(), worker_id, interval));
+    if stats_reporter.is_some() {
+        info!("✓ Worker stats reporting enabled");
+    }
+
+    let heartbeat = leases
+        .as_ref()
+        .map(|leases| leases.spawn_heartbeat(setting.task_lease.heartbeat_interval()));
+
     info!("🎯 Worker is ready and consuming messages...");
     info!("Press Ctrl+C to shutdown gracefully");
 
@@ -127,11 +238,52 @@ pub async fn run(setting: Setting) -> anyhow::Result<()> {
     }
 
     // Cleanup
+    if let Some(stats_reporter) = stats_reporter {
+        stats_reporter.abort();
+    }
+    if let Some(heartbeat) = heartbeat {
+        heartbeat.abort();
+    }
     consumer.close().await?;
     info!("✓ Consumer connection closed");
+    if let Some(leases) = &leases
+        && let Err(e) = leases.deregister().await
+    {
+        error!("Failed to deregister worker heartbeat: {:?}", e);
+    }
     scheduler.shutdown().await?;
     info!("✓ Scheduler stopped");
+    if let Some(redis) = redis {
+        info!(metrics = ?redis.metrics(), "✓ Redis connection pool closing");
+    }
     info!("👋 Worker shutdown complete");
 
     Ok(())
 }
+
+/// Publish this worker's task queue stats on the broadcast channel every `interval`, for the
+/// API's `/api/v1/admin/stats/`
+fn spawn_stats_reporter(
+    producer: Arc<Box<dyn MessageProducer>>,
+    worker_id: String,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+
+            let message = BroadcastMessage {
+                event_type: WORKER_STATS_EVENT.to_string(),
+                data: serde_json::json!(local_worker_stats(&worker_id)),
+            };
+            let result = match serde_json::to_string(&message) {
+                Ok(json) => producer.publish_event_json(&json, Some("broadcasts")).await,
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = result {
+                error!("Failed to report worker stats: {:?}", e);
+            }
+        }
+    })
+}
@@ -0,0 +1,219 @@
+use sea_orm::{
+    ActiveValue::Set, DbErr, EntityTrait, IdenStatic, Iterable, ModelTrait, PrimaryKeyToColumn,
+    sea_query::value::sea_value_to_json_value,
+};
+use serde_json::{Map, Value, json};
+use strum::AsRefStr;
+
+use crate::{
+    common::{entity::audit_log, repository::audit_log_repository},
+    core::context::Context,
+};
+
+/// Fields whose values never reach the audit trail, only the fact that they changed
+const REDACTED_FIELDS: &[&str] = &["password", "client_secret", "secret", "token_hash"];
+
+/// Bookkeeping fields left out of diffs; the entry itself records who and when
+const IGNORED_FIELDS: &[&str] = &[
+    "created_at",
+    "updated_at",
+    "created_user_id",
+    "updated_user_id",
+];
+
+/// Kind of repository write, stored as the `action` of its audit log entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+/// Record that `model` of `entity` was inserted
+pub async fn record_create<M: ModelTrait>(
+    context: &Context,
+    entity: &str,
+    model: &M,
+) -> Result<(), DbErr> {
+    record(
+        context,
+        AuditAction::Create,
+        entity,
+        model,
+        diff(None, Some(model)),
+    )
+    .await
+}
+
+/// Record the fields of `entity` that changed from `before` to `after`. Writes that changed
+/// nothing but bookkeeping fields aren't recorded.
+pub async fn record_update<M: ModelTrait>(
+    context: &Context,
+    entity: &str,
+    before: &M,
+    after: &M,
+) -> Result<(), DbErr> {
+    let changes = diff(Some(before), Some(after));
+    if changes.is_empty() {
+        return Ok(());
+    }
+    record(context, AuditAction::Update, entity, after, changes).await
+}
+
+/// Record that `entity` was deleted, soft deletes with the fields they changed and hard
+/// deletes with the fields of the removed row
+pub async fn record_delete<M: ModelTrait>(
+    context: &Context,
+    entity: &str,
+    before: &M,
+    after: Option<&M>,
+) -> Result<(), DbErr> {
+    let changes = diff(Some(before), after);
+    record(context, AuditAction::Delete, entity, before, changes).await
+}
+
+/// `{"field": {"from": .., "to": ..}}` of every field that differs between `before` and
+/// `after`; a missing side reads as `null`
+pub fn diff<M: ModelTrait>(before: Option<&M>, after: Option<&M>) -> Map<String, Value> {
+    let mut changes = Map::new();
+    for column in <M::Entity as EntityTrait>::Column::iter() {
+        let field = column.as_str();
+        if IGNORED_FIELDS.contains(&field) {
+            continue;
+        }
+        let from = before.map_or(Value::Null, |model| {
+            sea_value_to_json_value(&model.get(column))
+        });
+        let to = after.map_or(Value::Null, |model| {
+            sea_value_to_json_value(&model.get(column))
+        });
+        if from == to {
+            continue;
+        }
+
+        let change = if REDACTED_FIELDS.contains(&field) {
+            json!({ "from": redact(&from), "to": redact(&to) })
+        } else {
+            json!({ "from": from, "to": to })
+        };
+        changes.insert(field.to_string(), change);
+    }
+    changes
+}
+
+fn redact(value: &Value) -> Value {
+    match value {
+        Value::Null => Value::Null,
+        _ => Value::String("[redacted]".to_string()),
+    }
+}
+
+/// Primary key of `model`, with the columns of composite keys joined by `,`
+fn resource_id<M: ModelTrait>(model: &M) -> String {
+    <M::Entity as EntityTrait>::PrimaryKey::iter()
+        .map(
+            |key| match sea_value_to_json_value(&model.get(key.into_column())) {
+                Value::String(value) => value,
+                value => value.to_string(),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+async fn record<M: ModelTrait>(
+    context: &Context,
+    action: AuditAction,
+    entity: &str,
+    model: &M,
+    changes: Map<String, Value>,
+) -> Result<(), DbErr> {
+    audit_log_repository::create(
+        context,
+        audit_log::ActiveModel {
+            actor: Set(context.actor()),
+            action: Set(action.as_ref().to_string()),
+            resource_type: Set(entity.to_string()),
+            resource_id: Set(Some(resource_id(model))),
+            payload: Set(Value::Object(changes)),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{diff, resource_id};
+    use crate::user::entity::{sea_orm_active_enums::UserRole, user};
+
+    fn user() -> user::Model {
+        user::Model {
+            id: 7,
+            email: "user@example.com".to_string(),
+            normalized_email: "user@example.com".to_string(),
+            password: "hash".to_string(),
+            verified_at: None,
+            first_name: Some("Ada".to_string()),
+            last_name: None,
+            phone: None,
+            phone_verified_at: None,
+            created_at: None,
+            updated_at: None,
+            created_user_id: None,
+            updated_user_id: None,
+            role: UserRole::User,
+            locale: None,
+            timezone: None,
+            deactivated_at: None,
+            deleted_at: None,
+            avatar_file_id: None,
+        }
+    }
+
+    #[test]
+    fn diffs_only_changed_fields() {
+        let before = user();
+        let after = user::Model {
+            first_name: Some("Grace".to_string()),
+            updated_at: Some(chrono::Utc::now().naive_utc()),
+            ..user()
+        };
+
+        let changes = diff(Some(&before), Some(&after));
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            changes["first_name"],
+            json!({ "from": "Ada", "to": "Grace" })
+        );
+    }
+
+    #[test]
+    fn redacts_secrets_and_diffs_against_missing_sides() {
+        let created = diff(None, Some(&user()));
+        let password = diff(
+            Some(&user()),
+            Some(&user::Model {
+                password: "new-hash".to_string(),
+                ..user()
+            }),
+        );
+
+        assert_eq!(
+            created["email"],
+            json!({ "from": null, "to": "user@example.com" })
+        );
+        assert!(!created.contains_key("last_name"));
+        assert_eq!(
+            password["password"],
+            json!({ "from": "[redacted]", "to": "[redacted]" })
+        );
+        assert_eq!(resource_id(&user()), "7");
+    }
+}
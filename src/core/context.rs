@@ -1,14 +1,156 @@
-use sea_orm::DatabaseTransaction;
-use std::sync::Arc;
+use async_trait::async_trait;
+use rust_i18n::t;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr, ExecResult,
+    QueryResult, Statement, TransactionTrait,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 
+use crate::core::r#async::Outbox;
+use crate::core::dto::{error_code::ErrorCode, error_dto::ErrorDTO};
+use crate::core::event::{DomainEvent, EventBus};
+use crate::core::id::{IdGenerator, RandomIdGenerator};
+use crate::core::layer::auth_layer::authorize_role;
+use crate::core::permission::PermissionSet;
+use crate::core::policy::{self, Action, Resource, Rule};
+use crate::core::service_account::ServicePrincipal;
+use crate::pkg::cache::{LoginAttemptStore, ResponseCache, TaskDeduplicator, TaskQuota};
 use crate::pkg::messaging::MessageProducer;
+use crate::user::entity::sea_orm_active_enums::UserRole;
 use crate::user::entity::user;
 
+/// Connection a [`Context`] runs its queries on
+pub enum ContextConnection {
+    /// Request transaction, committed or rolled back when the request ends
+    Transaction(Arc<DatabaseTransaction>),
+    /// Pooled connection without a transaction, for requests that only read.
+    /// Statements other than `SELECT`/`WITH` are rejected.
+    ReadOnly(DatabaseConnection),
+}
+
+impl ContextConnection {
+    fn ensure_read(&self, sql: &str) -> Result<(), DbErr> {
+        let keyword = sql
+            .trim_start()
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .next()
+            .unwrap_or_default();
+        let is_read =
+            keyword.eq_ignore_ascii_case("select") || keyword.eq_ignore_ascii_case("with");
+
+        match self {
+            Self::ReadOnly(_) if !is_read => Err(DbErr::Custom(
+                "Write attempted through a read-only context".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl ConnectionTrait for ContextConnection {
+    fn get_database_backend(&self) -> DbBackend {
+        match self {
+            Self::Transaction(txn) => txn.get_database_backend(),
+            Self::ReadOnly(db) => db.get_database_backend(),
+        }
+    }
+
+    async fn execute_raw(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.ensure_read(&stmt.sql)?;
+        match self {
+            Self::Transaction(txn) => txn.execute_raw(stmt).await,
+            Self::ReadOnly(db) => db.execute_raw(stmt).await,
+        }
+    }
+
+    async fn execute_unprepared(&self, sql: &str) -> Result<ExecResult, DbErr> {
+        self.ensure_read(sql)?;
+        match self {
+            Self::Transaction(txn) => txn.execute_unprepared(sql).await,
+            Self::ReadOnly(db) => db.execute_unprepared(sql).await,
+        }
+    }
+
+    async fn query_one_raw(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.ensure_read(&stmt.sql)?;
+        match self {
+            Self::Transaction(txn) => txn.query_one_raw(stmt).await,
+            Self::ReadOnly(db) => db.query_one_raw(stmt).await,
+        }
+    }
+
+    async fn query_all_raw(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.ensure_read(&stmt.sql)?;
+        match self {
+            Self::Transaction(txn) => txn.query_all_raw(stmt).await,
+            Self::ReadOnly(db) => db.query_all_raw(stmt).await,
+        }
+    }
+
+    fn is_mock_connection(&self) -> bool {
+        match self {
+            Self::Transaction(txn) => txn.is_mock_connection(),
+            Self::ReadOnly(db) => db.is_mock_connection(),
+        }
+    }
+}
+
+type DeferredEffect = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
+
+/// Side effects registered with [`Context::after_commit`], waiting for the transaction to end
+#[derive(Default)]
+pub struct DeferredEffects {
+    effects: Mutex<Vec<DeferredEffect>>,
+    /// Effects of the enclosing transaction, which those of a savepoint wait for
+    parent: Option<Arc<DeferredEffects>>,
+}
+
+impl DeferredEffects {
+    fn push(&self, effect: DeferredEffect) {
+        self.effects.lock().unwrap().push(effect);
+    }
+
+    /// Run the effects in registration order once the transaction committed. Those of a
+    /// savepoint are handed to the enclosing transaction instead. Failures are only logged,
+    /// the data they follow from is already committed.
+    pub async fn committed(&self) {
+        let effects = std::mem::take(&mut *self.effects.lock().unwrap());
+        if let Some(parent) = &self.parent {
+            parent.effects.lock().unwrap().extend(effects);
+            return;
+        }
+
+        for effect in effects {
+            if let Err(e) = effect.await {
+                tracing::error!("Deferred side effect failed: {:?}", e);
+            }
+        }
+    }
+
+    /// Drop the effects of a rolled back transaction
+    pub fn rolled_back(&self) {
+        self.effects.lock().unwrap().clear();
+    }
+}
+
 pub struct ContextBuilder {
-    txn_inner: Arc<DatabaseTransaction>,
+    connection: ContextConnection,
     user: Option<user::Model>,
+    impersonator: Option<i32>,
+    service: Option<ServicePrincipal>,
+    permissions: PermissionSet,
     producer: Option<Arc<Box<dyn MessageProducer>>>,
+    outbox: Option<Arc<Outbox>>,
     locale: Option<String>,
+    id_generator: Option<Arc<dyn IdGenerator>>,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    task_dedup: Option<Arc<dyn TaskDeduplicator>>,
+    task_quota: Option<Arc<dyn TaskQuota>>,
+    login_attempts: Option<Arc<dyn LoginAttemptStore>>,
+    event_bus: Option<Arc<EventBus>>,
 }
 
 impl ContextBuilder {
@@ -17,56 +159,340 @@ impl ContextBuilder {
         self
     }
 
+    pub fn impersonator(mut self, admin_id: i32) -> Self {
+        self.impersonator = Some(admin_id);
+        self
+    }
+
+    pub fn service(mut self, service: ServicePrincipal) -> Self {
+        self.service = Some(service);
+        self
+    }
+
+    pub fn permissions(mut self, permissions: PermissionSet) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
     pub fn producer(mut self, producer: Arc<Box<dyn MessageProducer>>) -> Self {
         self.producer = Some(producer);
         self
     }
 
+    pub fn outbox(mut self, outbox: Arc<Outbox>) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
     pub fn locale(mut self, locale: impl Into<String>) -> Self {
         self.locale = Some(locale.into());
         self
     }
 
+    pub fn id_generator(mut self, id_generator: Arc<dyn IdGenerator>) -> Self {
+        self.id_generator = Some(id_generator);
+        self
+    }
+
+    pub fn response_cache(mut self, response_cache: Arc<dyn ResponseCache>) -> Self {
+        self.response_cache = Some(response_cache);
+        self
+    }
+
+    pub fn task_dedup(mut self, task_dedup: Arc<dyn TaskDeduplicator>) -> Self {
+        self.task_dedup = Some(task_dedup);
+        self
+    }
+
+    pub fn task_quota(mut self, task_quota: Arc<dyn TaskQuota>) -> Self {
+        self.task_quota = Some(task_quota);
+        self
+    }
+
+    pub fn login_attempts(mut self, login_attempts: Arc<dyn LoginAttemptStore>) -> Self {
+        self.login_attempts = Some(login_attempts);
+        self
+    }
+
+    pub fn event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
     pub fn build(self) -> Context {
         Context {
-            txn_inner: self.txn_inner,
+            connection: Arc::new(self.connection),
             user: self.user,
+            impersonator: self.impersonator,
+            service: self.service,
+            permissions: self.permissions,
             producer: self.producer,
+            outbox: self.outbox,
             locale: self.locale.unwrap_or_else(|| "en".to_string()),
+            id_generator: self
+                .id_generator
+                .unwrap_or_else(|| Arc::new(RandomIdGenerator)),
+            response_cache: self.response_cache,
+            task_dedup: self.task_dedup,
+            task_quota: self.task_quota,
+            login_attempts: self.login_attempts,
+            event_bus: self
+                .event_bus
+                .unwrap_or_else(|| Arc::new(EventBus::default())),
+            deferred: Arc::new(DeferredEffects::default()),
         }
     }
 }
 
 #[derive(Clone)]
 pub struct Context {
-    txn_inner: Arc<DatabaseTransaction>,
+    connection: Arc<ContextConnection>,
     pub user: Option<user::Model>,
+    /// Admin acting as `user` with an impersonation token
+    pub impersonator: Option<i32>,
+    /// Internal caller authenticated by its client certificate, on the mTLS listener only
+    pub service: Option<ServicePrincipal>,
+    /// Permissions the roles of `user` grant, resolved when the request was authenticated
+    pub permissions: PermissionSet,
     pub producer: Option<Arc<Box<dyn MessageProducer>>>,
+    /// Where tasks published in the transaction wait for it to commit, `None` when disabled
+    pub outbox: Option<Arc<Outbox>>,
     pub locale: String,
+    pub id_generator: Arc<dyn IdGenerator>,
+    /// Cache whose entries mutations invalidate, `None` when response caching is disabled
+    pub response_cache: Option<Arc<dyn ResponseCache>>,
+    /// Tasks recent user actions started, `None` when task deduplication is disabled
+    pub task_dedup: Option<Arc<dyn TaskDeduplicator>>,
+    /// Tasks each user has running and started today, `None` when quotas are disabled
+    pub task_quota: Option<Arc<dyn TaskQuota>>,
+    /// Failed sign-ins kept in Redis, `None` when they are kept in the `login_attempt` table
+    pub login_attempts: Option<Arc<dyn LoginAttemptStore>>,
+    /// Subscribers reacting to the events use cases emit
+    pub event_bus: Arc<EventBus>,
+    deferred: Arc<DeferredEffects>,
 }
 
 impl Context {
     pub fn builder(txn: Arc<DatabaseTransaction>) -> ContextBuilder {
+        Self::builder_with(ContextConnection::Transaction(txn))
+    }
+
+    /// Builder of a context that queries `db` directly, without opening a transaction
+    pub fn read_only(db: DatabaseConnection) -> ContextBuilder {
+        Self::builder_with(ContextConnection::ReadOnly(db))
+    }
+
+    fn builder_with(connection: ContextConnection) -> ContextBuilder {
         ContextBuilder {
-            txn_inner: txn,
+            connection,
             user: None,
+            impersonator: None,
+            service: None,
+            permissions: PermissionSet::default(),
             producer: None,
+            outbox: None,
             locale: None,
+            id_generator: None,
+            response_cache: None,
+            task_dedup: None,
+            task_quota: None,
+            login_attempts: None,
+            event_bus: None,
+        }
+    }
+
+    /// Connection to run queries on: the transaction, or the pool for read-only contexts
+    pub fn txn(&self) -> &ContextConnection {
+        &self.connection
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        matches!(*self.connection, ContextConnection::ReadOnly(_))
+    }
+
+    /// Check the central policy lets the current user perform `action` on `resource`, or that
+    /// they hold the permission for it such as `user.delete`:
+    /// `401` without a user, `403` when the policy denies it
+    pub fn authorize(&self, action: Action, resource: &Resource) -> Result<(), ErrorDTO> {
+        let user = self.user.as_ref().ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::AuthNotAuthenticated,
+                t!("auth.user_not_authenticated", locale = &self.locale).to_string(),
+            )
+        })?;
+        if self.has_permission(&policy::permission_for(resource.kind, action)) {
+            return Ok(());
+        }
+
+        match policy::rule_for(resource.kind, action) {
+            // Tells which role is missing
+            Some(Rule::Admin) => authorize_role(self, user, UserRole::Admin),
+            Some(rule) if rule.allows(user, resource) => Ok(()),
+            _ => Err(ErrorDTO::from_code(
+                ErrorCode::AuthForbidden,
+                t!("authorization.forbidden", locale = &self.locale).to_string(),
+            )),
         }
     }
 
-    pub fn txn(&self) -> &DatabaseTransaction {
-        &self.txn_inner
+    /// Let admins, and service principals granted `scope`, through: `401` without a user or
+    /// principal, `403` when the principal lacks the scope or the user isn't an admin
+    pub fn authorize_admin_or_scope(&self, scope: &str) -> Result<(), ErrorDTO> {
+        if let Some(service) = &self.service {
+            if service.has_scope(scope) {
+                return Ok(());
+            }
+            return Err(ErrorDTO::from_code(
+                ErrorCode::AuthScopeRequired,
+                t!(
+                    "authorization.scope_required",
+                    scope = scope,
+                    locale = &self.locale
+                )
+                .to_string(),
+            ));
+        }
+
+        let user = self.user.as_ref().ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::AuthNotAuthenticated,
+                t!("auth.user_not_authenticated", locale = &self.locale).to_string(),
+            )
+        })?;
+        authorize_role(self, user, UserRole::Admin)
+    }
+
+    /// Whether the current user holds `permission` through their roles; admins hold every
+    /// permission
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.user.as_ref().is_some_and(|user| {
+            user.role == UserRole::Admin || self.permissions.contains(permission)
+        })
+    }
+
+    /// `401` without a user, `403` unless they hold `permission`
+    pub fn require_permission(&self, permission: &str) -> Result<(), ErrorDTO> {
+        if self.user.is_none() {
+            return Err(ErrorDTO::from_code(
+                ErrorCode::AuthNotAuthenticated,
+                t!("auth.user_not_authenticated", locale = &self.locale).to_string(),
+            ));
+        }
+        if self.has_permission(permission) {
+            return Ok(());
+        }
+
+        Err(ErrorDTO::from_code(
+            ErrorCode::AuthPermissionRequired,
+            t!(
+                "authorization.permission_required",
+                permission = permission,
+                locale = &self.locale
+            )
+            .to_string(),
+        ))
+    }
+
+    /// Who is acting, for logs: `user:<id>`, `service:<name>` or `anonymous`. Actions taken
+    /// while impersonating are `user:<admin id> as user:<id>`.
+    pub fn actor(&self) -> String {
+        match (&self.service, self.impersonator, &self.user) {
+            (Some(service), _, _) => format!("service:{}", service.name),
+            (None, Some(admin_id), Some(user)) => format!("user:{} as user:{}", admin_id, user.id),
+            (None, Some(admin_id), None) => format!("user:{}", admin_id),
+            (None, None, Some(user)) => format!("user:{}", user.id),
+            (None, None, None) => "anonymous".to_string(),
+        }
+    }
+
+    /// Announce `event` to the subscribers of the event bus
+    pub async fn emit(&self, event: DomainEvent) {
+        self.event_bus.publish(self, event).await;
     }
 
-    /// Commit the underlying transaction (or savepoint).
+    /// Run `effect` only once the transaction commits, so a rollback can't leave a task
+    /// published or a notification sent for data that never existed. Effects of a read-only
+    /// context run once the request succeeded.
+    pub fn after_commit(&self, effect: impl Future<Output = anyhow::Result<()>> + Send + 'static) {
+        self.deferred.push(Box::pin(effect));
+    }
+
+    /// Effects registered with [`Context::after_commit`], for whoever ends the transaction
+    pub fn deferred_effects(&self) -> Arc<DeferredEffects> {
+        self.deferred.clone()
+    }
+
+    /// Copy of this context running in a savepoint of its transaction, so a unit of work can
+    /// be committed or rolled back on its own. Fails for read-only contexts.
+    pub async fn nested(&self) -> Result<Context, DbErr> {
+        let ContextConnection::Transaction(txn) = &*self.connection else {
+            return Err(DbErr::Custom(
+                "Savepoint requested on a read-only context".to_string(),
+            ));
+        };
+
+        Ok(Context {
+            connection: Arc::new(ContextConnection::Transaction(Arc::new(txn.begin().await?))),
+            deferred: Arc::new(DeferredEffects {
+                effects: Mutex::default(),
+                parent: Some(self.deferred.clone()),
+            }),
+            ..self.clone()
+        })
+    }
+
+    /// Copy of this context running in a new transaction on `db`, for the few `GET` routes
+    /// that have to write, such as OAuth callbacks, which are otherwise served read-only.
+    /// Its effects wait for it to be committed rather than for the request to end.
+    pub async fn begin(&self, db: &DatabaseConnection) -> Result<Context, DbErr> {
+        Ok(Context {
+            connection: Arc::new(ContextConnection::Transaction(Arc::new(db.begin().await?))),
+            deferred: Arc::new(DeferredEffects::default()),
+            ..self.clone()
+        })
+    }
+
+    /// Roll back the underlying transaction (or savepoint), dropping its deferred effects;
+    /// a no-op for read-only contexts
+    pub async fn rollback(self) -> Result<(), DbErr> {
+        self.deferred.rolled_back();
+        match Arc::try_unwrap(self.connection).ok() {
+            Some(ContextConnection::Transaction(txn)) => match Arc::try_unwrap(txn) {
+                Ok(txn) => txn.rollback().await,
+                Err(_) => Err(DbErr::Custom(
+                    "Failed to unwrap transaction Arc for rollback".to_string(),
+                )),
+            },
+            Some(ContextConnection::ReadOnly(_)) => Ok(()),
+            None => Err(DbErr::Custom(
+                "Failed to unwrap transaction Arc for rollback".to_string(),
+            )),
+        }
+    }
+
+    /// Commit the underlying transaction (or savepoint), then run its deferred effects;
+    /// a no-op for read-only contexts apart from the effects.
     /// Consumes `self` so the Arc can be unwrapped.
     pub async fn commit(self) -> Result<(), sea_orm::DbErr> {
-        match Arc::try_unwrap(self.txn_inner) {
-            Ok(txn) => txn.commit().await,
-            Err(_) => Err(sea_orm::DbErr::Custom(
+        let deferred = self.deferred;
+        let connection = Arc::try_unwrap(self.connection).ok();
+        let result = match connection {
+            Some(ContextConnection::Transaction(txn)) => match Arc::try_unwrap(txn) {
+                Ok(txn) => txn.commit().await,
+                Err(_) => Err(sea_orm::DbErr::Custom(
+                    "Failed to unwrap transaction Arc for commit".to_string(),
+                )),
+            },
+            Some(ContextConnection::ReadOnly(_)) => Ok(()),
+            None => Err(sea_orm::DbErr::Custom(
                 "Failed to unwrap transaction Arc for commit".to_string(),
             )),
+        };
+
+        match result {
+            Ok(()) => deferred.committed().await,
+            Err(_) => deferred.rolled_back(),
         }
+        result
     }
 }
@@ -1,3 +1,4 @@
+use sea_orm::ConnectionTrait;
 use std::time::Duration;
 
 pub async fn get_db(database_url: &str) -> Result<sea_orm::DatabaseConnection, sea_orm::DbErr> {
@@ -12,3 +13,38 @@ pub async fn get_db(database_url: &str) -> Result<sea_orm::DatabaseConnection, s
 
     sea_orm::Database::connect(opt).await
 }
+
+/// Connections of a database pool
+#[derive(Debug, Clone, Copy)]
+pub struct PoolStats {
+    pub size: u32,
+    pub idle: u32,
+    pub max: u32,
+}
+
+/// Usage of the pool behind `db`, `None` for connections without one (e.g. mocks)
+pub fn pool_stats(db: &sea_orm::DatabaseConnection) -> Option<PoolStats> {
+    if db.is_mock_connection() {
+        return None;
+    }
+
+    match db.get_database_backend() {
+        sea_orm::DbBackend::Postgres => {
+            let pool = db.get_postgres_connection_pool();
+            Some(PoolStats {
+                size: pool.size(),
+                idle: pool.num_idle() as u32,
+                max: pool.options().get_max_connections(),
+            })
+        }
+        sea_orm::DbBackend::Sqlite => {
+            let pool = db.get_sqlite_connection_pool();
+            Some(PoolStats {
+                size: pool.size(),
+                idle: pool.num_idle() as u32,
+                max: pool.options().get_max_connections(),
+            })
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use migration::{MigrationTrait, Migrator, MigratorTrait};
+
+use crate::core::module::Module;
+
+// `MigratorTrait::migrations` has no receiver, so registered modules are kept globally
+static MODULES: OnceLock<Vec<Arc<dyn Module>>> = OnceLock::new();
+
+/// Core migrations followed by the migrations of every registered module
+pub struct AppMigrator;
+
+impl AppMigrator {
+    /// Include the migrations of `modules`; only the first registration takes effect
+    pub fn register_modules(modules: Vec<Arc<dyn Module>>) {
+        let _ = MODULES.set(modules);
+    }
+}
+
+#[async_trait]
+impl MigratorTrait for AppMigrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        let mut migrations = Migrator::migrations();
+        for module in MODULES.get().into_iter().flatten() {
+            migrations.extend(module.migrations());
+        }
+        migrations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use migration::{Migrator, MigratorTrait};
+
+    use super::AppMigrator;
+
+    #[test]
+    fn includes_core_migrations_first() {
+        let core: Vec<String> = Migrator::migrations()
+            .iter()
+            .map(|migration| migration.name().to_string())
+            .collect();
+        let all: Vec<String> = AppMigrator::migrations()
+            .iter()
+            .map(|migration| migration.name().to_string())
+            .collect();
+
+        assert_eq!(&all[..core.len()], core.as_slice());
+    }
+}
@@ -1,4 +1,6 @@
 pub mod connection;
+pub mod migrator;
 pub mod ordering;
 pub mod pagination;
+pub mod query;
 pub mod uow;
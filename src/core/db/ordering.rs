@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 /// Generic sort order enum
-#[derive(Clone, PartialEq, Serialize, Deserialize, ToSchema, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Default)]
 pub enum SortOrder {
     #[serde(rename = "asc")]
     #[default]
@@ -14,6 +14,9 @@ pub enum SortOrder {
 
 /// Generic order by field trait
 pub trait OrderByField: Clone + PartialEq + std::fmt::Debug {
+    /// Names `from_str` accepts, the fields clients may sort by
+    const FIELDS: &'static [&'static str];
+
     /// Parse a field name string into the specific field enum
     fn from_str(s: &str) -> Option<Self>;
 
@@ -22,7 +25,7 @@ pub trait OrderByField: Clone + PartialEq + std::fmt::Debug {
 }
 
 /// Generic order by structure
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct OrderBy<T: OrderByField> {
     pub field: T,
     pub order: SortOrder,
@@ -33,31 +36,6 @@ impl<T: OrderByField> OrderBy<T> {
     pub fn new(field: T, order: SortOrder) -> Self {
         Self { field, order }
     }
-
-    /// Parse order_by string into Vec<OrderBy<T>>
-    /// Format: "+field_name,-field_name,field_name"
-    /// Examples: "+created_at", "-id", "+name,-created_at"
-    pub fn parse_order_by_string(order_by_str: &str) -> Vec<OrderBy<T>> {
-        order_by_str
-            .split(',')
-            .filter_map(|part| {
-                let part = part.trim();
-                if part.is_empty() {
-                    return None;
-                }
-
-                let (order, field_str) = if let Some(stripped) = part.strip_prefix('+') {
-                    (SortOrder::Asc, stripped)
-                } else if let Some(stripped) = part.strip_prefix('-') {
-                    (SortOrder::Desc, stripped)
-                } else {
-                    (SortOrder::Asc, part)
-                };
-
-                T::from_str(field_str).map(|field| OrderBy { field, order })
-            })
-            .collect()
-    }
 }
 
 /// Generic trait for applying ordering to SeaORM queries
@@ -104,6 +82,8 @@ mod tests {
     }
 
     impl OrderByField for TestField {
+        const FIELDS: &'static [&'static str] = &["id", "email", "created_at"];
+
         fn from_str(s: &str) -> Option<Self> {
             match s {
                 "id" => Some(Self::Id),
@@ -130,22 +110,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn parses_order_by_string() {
-        let orders = OrderBy::<TestField>::parse_order_by_string("+email,-created_at,id");
-        assert_eq!(orders.len(), 3);
-        assert_eq!(orders[0].field, TestField::Email);
-        assert!(matches!(orders[0].order, SortOrder::Asc));
-        assert!(matches!(orders[1].order, SortOrder::Desc));
-    }
-
-    #[test]
-    fn ignores_invalid_fields() {
-        let orders = OrderBy::<TestField>::parse_order_by_string("invalid,+email");
-        assert_eq!(orders.len(), 1);
-        assert_eq!(orders[0].field, TestField::Email);
-    }
-
     #[test]
     fn applies_ordering_to_query() {
         let query = user::Entity::find().filter(user::Column::Email.contains("@example.com"));
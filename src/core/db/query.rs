@@ -0,0 +1,232 @@
+use rust_i18n::t;
+use sea_orm::{EntityTrait, QuerySelect, Select};
+
+use crate::core::{
+    db::{
+        ordering::{OrderBy, OrderByField, SortOrder},
+        pagination::calculate_offset,
+    },
+    dto::{
+        error_code::ErrorCode,
+        error_dto::{ErrorDTO, FieldErrorDTO},
+    },
+};
+
+/// Page returned when a search doesn't ask for one
+pub const DEFAULT_PAGE: u64 = 1;
+
+/// Items per page when a search doesn't set `page_size`
+pub const DEFAULT_PAGE_SIZE: u64 = 10;
+
+/// Why the paging or sorting parameters of a search were refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// `order_by` names a field that isn't in the allowlist of the entity
+    UnknownOrderField {
+        field: String,
+        allowed: &'static [&'static str],
+    },
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownOrderField { field, allowed } => write!(
+                f,
+                "Cannot order by '{}', expected one of {}",
+                field,
+                allowed.join(", ")
+            ),
+        }
+    }
+}
+
+impl QueryError {
+    /// `VALIDATION_FAILED` error on the offending parameter, translated into `locale`
+    pub fn into_error_dto(self, locale: &str) -> ErrorDTO {
+        let error = match self {
+            Self::UnknownOrderField { field, allowed } => FieldErrorDTO {
+                field: "order_by".to_string(),
+                message: t!(
+                    "validation.order_by_unknown",
+                    value = field,
+                    allowed = allowed.join(", "),
+                    locale = locale
+                )
+                .to_string(),
+                rule: None,
+            },
+        };
+        ErrorDTO::from_code(ErrorCode::ValidationFailed, error.message.clone())
+            .with_errors(vec![error])
+    }
+}
+
+/// Page of a search, with the defaults applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    /// One-based page number
+    pub page: u64,
+    pub page_size: u64,
+}
+
+impl PageRequest {
+    /// `page` and `page_size` as sent by the client, missing or zero ones replaced by
+    /// [`DEFAULT_PAGE`] and [`DEFAULT_PAGE_SIZE`]
+    pub fn new(page: Option<u64>, page_size: Option<u64>) -> Self {
+        Self {
+            page: page.filter(|page| *page > 0).unwrap_or(DEFAULT_PAGE),
+            page_size: page_size
+                .filter(|page_size| *page_size > 0)
+                .unwrap_or(DEFAULT_PAGE_SIZE),
+        }
+    }
+
+    pub fn offset(&self) -> u64 {
+        calculate_offset(Some(self.page), self.page_size)
+    }
+}
+
+/// Parse `+field,-field,field` into orderings of `T`, ascending unless prefixed with `-`.
+/// Empty entries are skipped; a field outside the allowlist of `T` is an error.
+pub fn parse_order_by<T: OrderByField>(
+    order_by: Option<&str>,
+) -> Result<Vec<OrderBy<T>>, QueryError> {
+    let Some(order_by) = order_by else {
+        return Ok(Vec::new());
+    };
+
+    order_by
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (order, field) = if let Some(stripped) = part.strip_prefix('+') {
+                (SortOrder::Asc, stripped)
+            } else if let Some(stripped) = part.strip_prefix('-') {
+                (SortOrder::Desc, stripped)
+            } else {
+                (SortOrder::Asc, part)
+            };
+
+            T::from_str(field.trim())
+                .map(|field| OrderBy::new(field, order))
+                .ok_or_else(|| QueryError::UnknownOrderField {
+                    field: field.trim().to_string(),
+                    allowed: T::FIELDS,
+                })
+        })
+        .collect()
+}
+
+/// Limit `query` to one page when `page_size` is set, returning every row otherwise
+pub fn paginate<E: EntityTrait>(
+    query: Select<E>,
+    page: Option<u64>,
+    page_size: Option<u64>,
+) -> Select<E> {
+    match page_size {
+        Some(page_size) => query
+            .limit(page_size)
+            .offset(calculate_offset(page, page_size)),
+        None => query,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{EntityTrait, QueryTrait};
+
+    use super::{PageRequest, QueryError, paginate, parse_order_by};
+    use crate::core::{
+        db::ordering::{OrderByField, SortOrder},
+        dto::error_code::ErrorCode,
+    };
+    use crate::user::entity::user;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestField {
+        Id,
+        Email,
+        CreatedAt,
+    }
+
+    impl OrderByField for TestField {
+        const FIELDS: &'static [&'static str] = &["id", "email", "created_at"];
+
+        fn from_str(s: &str) -> Option<Self> {
+            match s {
+                "id" => Some(Self::Id),
+                "email" => Some(Self::Email),
+                "created_at" => Some(Self::CreatedAt),
+                _ => None,
+            }
+        }
+
+        fn to_string(&self) -> String {
+            match self {
+                Self::Id => "id".to_string(),
+                Self::Email => "email".to_string(),
+                Self::CreatedAt => "created_at".to_string(),
+            }
+        }
+    }
+
+    #[test]
+    fn parses_order_by() {
+        let orders = parse_order_by::<TestField>(Some("+email, -created_at,id,")).unwrap();
+
+        assert_eq!(orders.len(), 3);
+        assert_eq!(orders[0].field, TestField::Email);
+        assert!(matches!(orders[0].order, SortOrder::Asc));
+        assert_eq!(orders[1].field, TestField::CreatedAt);
+        assert!(matches!(orders[1].order, SortOrder::Desc));
+        assert!(matches!(orders[2].order, SortOrder::Asc));
+        assert!(parse_order_by::<TestField>(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_fields_outside_allowlist() {
+        let error = parse_order_by::<TestField>(Some("+email,-password")).unwrap_err();
+
+        assert_eq!(
+            error,
+            QueryError::UnknownOrderField {
+                field: "password".to_string(),
+                allowed: TestField::FIELDS,
+            }
+        );
+        let error = error.into_error_dto("en");
+        assert_eq!(error.code, ErrorCode::ValidationFailed);
+        assert_eq!(error.errors[0].field, "order_by");
+        assert!(error.message.contains("created_at"));
+    }
+
+    #[test]
+    fn applies_page_defaults() {
+        assert_eq!(
+            PageRequest::new(None, None),
+            PageRequest {
+                page: 1,
+                page_size: 10
+            }
+        );
+        assert_eq!(
+            PageRequest::new(Some(0), Some(0)),
+            PageRequest::new(None, None)
+        );
+        assert_eq!(PageRequest::new(Some(3), Some(25)).offset(), 50);
+    }
+
+    #[test]
+    fn paginates_only_with_page_size() {
+        let sql = |page_size| {
+            paginate(user::Entity::find(), Some(2), page_size)
+                .build(sea_orm::DatabaseBackend::Postgres)
+                .to_string()
+        };
+
+        assert!(sql(Some(20)).contains("LIMIT 20 OFFSET 20"));
+        assert!(!sql(None).contains("LIMIT"));
+    }
+}
@@ -2,10 +2,81 @@ use sea_orm::TransactionTrait;
 use std::backtrace::Backtrace;
 use std::sync::Arc;
 
-use crate::{config::app::AppState, core::context::Context, user::entity::user};
+use crate::{
+    config::app::AppState,
+    core::context::{Context, ContextBuilder},
+    user::entity::user,
+};
+
+fn build_context(
+    mut context_builder: ContextBuilder,
+    app_state: &AppState,
+    current_user: Option<user::Model>,
+    locale: Option<String>,
+) -> Context {
+    if let Some(locale) = locale {
+        context_builder = context_builder.locale(locale);
+    }
+    if let Some(user) = current_user {
+        context_builder = context_builder.user(user);
+    }
+    if let Some(producer) = app_state.producer.clone() {
+        context_builder = context_builder.producer(producer);
+    }
+    if let Some(outbox) = app_state.outbox.clone() {
+        context_builder = context_builder.outbox(outbox);
+    }
+    context_builder = context_builder
+        .id_generator(app_state.id_generator.clone())
+        .event_bus(app_state.event_bus.clone());
+    if let Some(response_cache) = app_state.response_cache.clone() {
+        context_builder = context_builder.response_cache(response_cache);
+    }
+    if let Some(task_dedup) = app_state.task_dedup.clone() {
+        context_builder = context_builder.task_dedup(task_dedup);
+    }
+    if let Some(task_quota) = app_state.task_quota.clone() {
+        context_builder = context_builder.task_quota(task_quota);
+    }
+    if let Some(login_attempts) = app_state.login_attempts.clone() {
+        context_builder = context_builder.login_attempts(login_attempts);
+    }
+    context_builder.build()
+}
+
+/// Helper function to execute a read-only use case on a pooled connection,
+/// without the cost of opening a transaction
+pub async fn read_only<T, F, E>(
+    app_state: &AppState,
+    current_user: Option<user::Model>,
+    locale: Option<String>,
+    use_case_fn: F,
+) -> Result<T, E>
+where
+    T: Send,
+    F: FnOnce(
+            &Context,
+        )
+            -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, E>> + Send + '_>>
+        + Send,
+{
+    let context = build_context(
+        Context::read_only(app_state.db.clone()),
+        app_state,
+        current_user,
+        locale,
+    );
+
+    let result = use_case_fn(&context).await;
+    if result.is_ok() {
+        context.deferred_effects().committed().await;
+    }
+    result
+}
 
 /// Helper function to execute a use case within a transaction
-/// Automatically handles commit/rollback based on the result
+/// Automatically handles commit/rollback based on the result, running the side effects
+/// deferred with `Context::after_commit` after a commit
 pub async fn new_transaction<T, F, E>(
     app_state: &AppState,
     current_user: Option<user::Model>,
@@ -32,20 +103,15 @@ where
     })?;
 
     let txn = Arc::new(txn);
-    let producer = app_state.producer.clone();
-    let mut context_builder = Context::builder(txn.clone());
-    if let Some(locale) = locale {
-        context_builder = context_builder.locale(locale);
-    }
-    if let Some(user) = current_user {
-        context_builder = context_builder.user(user);
-    }
-    if let Some(producer) = producer {
-        context_builder = context_builder.producer(producer);
-    }
-    let context = context_builder.build();
+    let context = build_context(
+        Context::builder(txn.clone()),
+        app_state,
+        current_user,
+        locale,
+    );
 
     let result = use_case_fn(&context).await;
+    let deferred = context.deferred_effects();
     drop(context);
 
     match result {
@@ -61,6 +127,7 @@ where
                         );
                         E::from(e)
                     })?;
+                    deferred.committed().await;
                 }
                 Err(_) => {
                     tracing::error!("Failed to unwrap transaction Arc for commit");
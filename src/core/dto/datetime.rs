@@ -0,0 +1,148 @@
+//! Timestamps are stored as naive UTC. DTOs serialize them as RFC 3339 with an offset, in
+//! the time zone of the current request (the signed-in user's preference, UTC otherwise),
+//! and parse client-supplied ones the same way:
+//!
+//! ```ignore
+//! #[serde(with = "crate::core::dto::datetime::option")]
+//! pub created_at: Option<NaiveDateTime>,
+//! ```
+
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serializer, de::Error};
+use std::future::Future;
+
+tokio::task_local! {
+    static REQUEST_TIMEZONE: Tz;
+}
+
+/// Run `f` with timestamps rendered in and parsed from `timezone`
+pub async fn with_timezone<F: Future>(timezone: Tz, f: F) -> F::Output {
+    REQUEST_TIMEZONE.scope(timezone, f).await
+}
+
+/// Time zone timestamps are rendered in, UTC outside [`with_timezone`]
+pub fn current_timezone() -> Tz {
+    REQUEST_TIMEZONE.try_with(|tz| *tz).unwrap_or(Tz::UTC)
+}
+
+/// IANA time zone named `name`, e.g. `Asia/Ho_Chi_Minh`
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.trim().parse().ok()
+}
+
+/// RFC 3339 rendering of the naive UTC `value` in the current time zone
+pub fn format(value: &NaiveDateTime) -> String {
+    Utc.from_utc_datetime(value)
+        .with_timezone(&current_timezone())
+        .to_rfc3339_opts(SecondsFormat::AutoSi, false)
+}
+
+/// Naive UTC value of `text`: RFC 3339 with an offset, or a local date-time without one
+/// (`2026-10-17T09:30:00`), which is read in the current time zone
+pub fn parse(text: &str) -> Result<NaiveDateTime, String> {
+    if let Ok(value) = DateTime::parse_from_rfc3339(text) {
+        return Ok(value.naive_utc());
+    }
+
+    let local = text
+        .parse::<NaiveDateTime>()
+        .map_err(|_| format!("Invalid RFC 3339 date-time: {}", text))?;
+    current_timezone()
+        .from_local_datetime(&local)
+        // Ambiguous times on a DST change resolve to the earliest
+        .earliest()
+        .map(|value| value.naive_utc())
+        .ok_or_else(|| format!("Nonexistent local date-time: {}", text))
+}
+
+pub fn serialize<S: Serializer>(value: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format(value))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDateTime, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    parse(&text).map_err(D::Error::custom)
+}
+
+/// The same for optional timestamps
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<NaiveDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => super::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDateTime>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|text| parse(&text).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Event {
+        #[serde(with = "super::option")]
+        at: Option<NaiveDateTime>,
+    }
+
+    fn noon_utc() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 17)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn renders_utc_outside_a_request() {
+        assert_eq!(format(&noon_utc()), "2026-10-17T12:00:00+00:00");
+    }
+
+    #[tokio::test]
+    async fn renders_and_parses_in_request_timezone() {
+        let timezone = parse_timezone("Asia/Ho_Chi_Minh").unwrap();
+
+        with_timezone(timezone, async {
+            let json = serde_json::to_string(&Event {
+                at: Some(noon_utc()),
+            })
+            .unwrap();
+            assert_eq!(json, r#"{"at":"2026-10-17T19:00:00+07:00"}"#);
+
+            let local: Event = serde_json::from_str(r#"{"at":"2026-10-17T19:00:00"}"#).unwrap();
+            assert_eq!(local.at, Some(noon_utc()));
+        })
+        .await;
+    }
+
+    #[test]
+    fn parses_any_offset_to_utc() {
+        let event: Event = serde_json::from_str(r#"{"at":"2026-10-17T14:00:00+02:00"}"#).unwrap();
+        assert_eq!(event.at, Some(noon_utc()));
+
+        let event: Event = serde_json::from_str(r#"{"at":null}"#).unwrap();
+        assert_eq!(event.at, None);
+
+        assert!(serde_json::from_str::<Event>(r#"{"at":"yesterday"}"#).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_timezones() {
+        assert!(parse_timezone("Mars/Olympus_Mons").is_none());
+    }
+}
@@ -0,0 +1,245 @@
+use axum::http::StatusCode;
+use serde::{Serialize, Serializer};
+use utoipa::{
+    PartialSchema, ToSchema,
+    openapi::{
+        RefOr, Schema,
+        schema::{ObjectBuilder, Type},
+    },
+};
+
+/// Declares the catalog: each variant with its stable code and the status it is answered with
+macro_rules! error_codes {
+    ($($(#[$doc:meta])* $variant:ident => ($code:literal, $status:ident),)+) => {
+        /// Machine-readable reason of an error response. Codes never change once released,
+        /// so clients can branch on them instead of on the translated message.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $($(#[$doc])* $variant,)+
+        }
+
+        impl ErrorCode {
+            /// Every code, in catalog order
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$variant,)+];
+
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $code,)+
+                }
+            }
+
+            /// Status every response with this code is answered with
+            pub fn status(&self) -> StatusCode {
+                match self {
+                    $(ErrorCode::$variant => StatusCode::$status,)+
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    // Generic codes of errors raised without a catalog entry, by status
+    BadRequest => ("BAD_REQUEST", BAD_REQUEST),
+    Unauthorized => ("UNAUTHORIZED", UNAUTHORIZED),
+    Forbidden => ("FORBIDDEN", FORBIDDEN),
+    NotFound => ("NOT_FOUND", NOT_FOUND),
+    Conflict => ("CONFLICT", CONFLICT),
+    InternalError => ("INTERNAL_ERROR", INTERNAL_SERVER_ERROR),
+    ServiceUnavailable => ("SERVICE_UNAVAILABLE", SERVICE_UNAVAILABLE),
+
+    // Requests
+    /// Fields of the body or query failed validation; see `errors`
+    ValidationFailed => ("VALIDATION_FAILED", BAD_REQUEST),
+    RequestBodyInvalid => ("REQUEST_BODY_INVALID", BAD_REQUEST),
+    RequestBodyNotJson => ("REQUEST_BODY_NOT_JSON", BAD_REQUEST),
+    RequestBodyTooLarge => ("REQUEST_BODY_TOO_LARGE", PAYLOAD_TOO_LARGE),
+    RequestLanguageInvalid => ("REQUEST_LANGUAGE_INVALID", BAD_REQUEST),
+    RequestTimeout => ("REQUEST_TIMEOUT", GATEWAY_TIMEOUT),
+    RouteRetired => ("ROUTE_RETIRED", GONE),
+    ServiceOverloaded => ("SERVICE_OVERLOADED", SERVICE_UNAVAILABLE),
+    /// The instance is shutting down and refuses new WebSockets; reconnect to reach another
+    ServerDraining => ("SERVER_DRAINING", SERVICE_UNAVAILABLE),
+    StatementBudgetExceeded => ("STATEMENT_BUDGET_EXCEEDED", INTERNAL_SERVER_ERROR),
+
+    // Authentication and authorization
+    AuthNotAuthenticated => ("AUTH_NOT_AUTHENTICATED", UNAUTHORIZED),
+    AuthTokenMissing => ("AUTH_TOKEN_MISSING", UNAUTHORIZED),
+    AuthTokenMalformed => ("AUTH_TOKEN_MALFORMED", UNAUTHORIZED),
+    AuthTokenInvalid => ("AUTH_TOKEN_INVALID", UNAUTHORIZED),
+    AuthRefreshTokenMissing => ("AUTH_REFRESH_TOKEN_MISSING", UNAUTHORIZED),
+    AuthRefreshTokenInvalid => ("AUTH_REFRESH_TOKEN_INVALID", UNAUTHORIZED),
+    /// A refresh token was presented again after being rotated; its whole family is revoked
+    AuthRefreshTokenReused => ("AUTH_REFRESH_TOKEN_REUSED", UNAUTHORIZED),
+    /// The user a valid token was issued to no longer exists
+    AuthUserNotFound => ("AUTH_USER_NOT_FOUND", UNAUTHORIZED),
+    /// A token of a deactivated account was presented
+    AuthSessionDeactivated => ("AUTH_SESSION_DEACTIVATED", UNAUTHORIZED),
+    AuthAccountDeactivated => ("AUTH_ACCOUNT_DEACTIVATED", FORBIDDEN),
+    /// The admin ended the impersonation the token was issued for
+    AuthImpersonationEnded => ("AUTH_IMPERSONATION_ENDED", UNAUTHORIZED),
+    /// Sign-in refused until the account's email is verified, when verification is required
+    AuthEmailNotVerified => ("AUTH_EMAIL_NOT_VERIFIED", FORBIDDEN),
+    AuthInvalidCredentials => ("AUTH_INVALID_CREDENTIALS", UNAUTHORIZED),
+    /// Too many failed sign-ins of the account or from the client; `retry_after` tells when
+    /// to try again
+    AuthAccountLocked => ("AUTH_ACCOUNT_LOCKED", LOCKED),
+    AuthPasswordIncorrect => ("AUTH_PASSWORD_INCORRECT", BAD_REQUEST),
+    /// The new password breaks the password policy; `errors` lists every broken `rule`
+    AuthPasswordTooWeak => ("AUTH_PASSWORD_TOO_WEAK", BAD_REQUEST),
+    AuthInvalidOtp => ("AUTH_INVALID_OTP", BAD_REQUEST),
+    AuthOtpExpired => ("AUTH_OTP_EXPIRED", BAD_REQUEST),
+    AuthOtpAttemptsExceeded => ("AUTH_OTP_ATTEMPTS_EXCEEDED", BAD_REQUEST),
+    AuthResetLinkInvalid => ("AUTH_RESET_LINK_INVALID", BAD_REQUEST),
+    AuthEmailVerificationInvalid => ("AUTH_EMAIL_VERIFICATION_INVALID", BAD_REQUEST),
+    AuthSignInReportInvalid => ("AUTH_SIGN_IN_REPORT_INVALID", BAD_REQUEST),
+    AuthSessionNotFound => ("AUTH_SESSION_NOT_FOUND", NOT_FOUND),
+    AuthPhoneRequired => ("AUTH_PHONE_REQUIRED", BAD_REQUEST),
+    AuthPhoneAlreadyVerified => ("AUTH_PHONE_ALREADY_VERIFIED", BAD_REQUEST),
+    AuthServiceAccountUnknown => ("AUTH_SERVICE_ACCOUNT_UNKNOWN", UNAUTHORIZED),
+    AuthSignatureMissing => ("AUTH_SIGNATURE_MISSING", UNAUTHORIZED),
+    AuthSignatureInvalid => ("AUTH_SIGNATURE_INVALID", UNAUTHORIZED),
+    AuthSignatureExpired => ("AUTH_SIGNATURE_EXPIRED", UNAUTHORIZED),
+    AuthRequestReplayed => ("AUTH_REQUEST_REPLAYED", UNAUTHORIZED),
+    AuthSignatureUnverifiable => ("AUTH_SIGNATURE_UNVERIFIABLE", SERVICE_UNAVAILABLE),
+    AuthOAuthProviderUnavailable => ("AUTH_OAUTH_PROVIDER_UNAVAILABLE", NOT_FOUND),
+    /// The callback's `state` doesn't match the sign-in started by this browser, or expired
+    AuthOAuthStateInvalid => ("AUTH_OAUTH_STATE_INVALID", BAD_REQUEST),
+    AuthOAuthDenied => ("AUTH_OAUTH_DENIED", BAD_REQUEST),
+    /// The provider couldn't be reached or refused the authorization code
+    AuthOAuthFailed => ("AUTH_OAUTH_FAILED", BAD_GATEWAY),
+    AuthOAuthEmailUnverified => ("AUTH_OAUTH_EMAIL_UNVERIFIED", BAD_REQUEST),
+    AuthForbidden => ("AUTH_FORBIDDEN", FORBIDDEN),
+    AuthRoleRequired => ("AUTH_ROLE_REQUIRED", FORBIDDEN),
+    AuthScopeRequired => ("AUTH_SCOPE_REQUIRED", FORBIDDEN),
+    AuthPermissionRequired => ("AUTH_PERMISSION_REQUIRED", FORBIDDEN),
+
+    // Users
+    UserNotFound => ("USER_NOT_FOUND", NOT_FOUND),
+    UserEmailTaken => ("USER_EMAIL_TAKEN", CONFLICT),
+    /// No secondary email address with this id belongs to the user
+    UserEmailNotFound => ("USER_EMAIL_NOT_FOUND", NOT_FOUND),
+    UserEmailNotVerified => ("USER_EMAIL_NOT_VERIFIED", CONFLICT),
+    UserEmailAlreadyVerified => ("USER_EMAIL_ALREADY_VERIFIED", BAD_REQUEST),
+    UserIdInvalid => ("USER_ID_INVALID", BAD_REQUEST),
+    UserTimezoneInvalid => ("USER_TIMEZONE_INVALID", BAD_REQUEST),
+    UserLanguageUnsupported => ("USER_LANGUAGE_UNSUPPORTED", BAD_REQUEST),
+    UserBulkEmpty => ("USER_BULK_EMPTY", BAD_REQUEST),
+    UserBulkSelfOperation => ("USER_BULK_SELF_OPERATION", BAD_REQUEST),
+    /// Admins and deactivated users can't be impersonated
+    UserImpersonationForbidden => ("USER_IMPERSONATION_FORBIDDEN", FORBIDDEN),
+
+    // Files
+    FileNotFound => ("FILE_NOT_FOUND", NOT_FOUND),
+    FileContentInvalid => ("FILE_CONTENT_INVALID", BAD_REQUEST),
+    FileDownloadLinkInvalid => ("FILE_DOWNLOAD_LINK_INVALID", FORBIDDEN),
+    /// A `/media/` link was embedded by a page outside `MEDIA_ALLOWED_REFERERS`
+    FileHotlinkForbidden => ("FILE_HOTLINK_FORBIDDEN", FORBIDDEN),
+    FileAvatarNotAvailable => ("FILE_AVATAR_NOT_AVAILABLE", CONFLICT),
+    FileAvatarNotPending => ("FILE_AVATAR_NOT_PENDING", CONFLICT),
+
+    // Notifications, email and SMS
+    NotificationNotFound => ("NOTIFICATION_NOT_FOUND", NOT_FOUND),
+    NotificationChannelsRequired => ("NOTIFICATION_CHANNELS_REQUIRED", BAD_REQUEST),
+    NotificationChannelNoneExclusive => ("NOTIFICATION_CHANNEL_NONE_EXCLUSIVE", BAD_REQUEST),
+    NotificationDeviceTokenRequired => ("NOTIFICATION_DEVICE_TOKEN_REQUIRED", BAD_REQUEST),
+    NotificationDeviceTokenNotFound => ("NOTIFICATION_DEVICE_TOKEN_NOT_FOUND", NOT_FOUND),
+    EmailUnavailable => ("EMAIL_UNAVAILABLE", INTERNAL_SERVER_ERROR),
+    EmailDeliveryFailed => ("EMAIL_DELIVERY_FAILED", INTERNAL_SERVER_ERROR),
+    SmsUnavailable => ("SMS_UNAVAILABLE", INTERNAL_SERVER_ERROR),
+
+    // OAuth2 provider
+    OAuthClientNotFound => ("OAUTH_CLIENT_NOT_FOUND", NOT_FOUND),
+    /// The redirect URI isn't registered for the client, so the user can't be sent back to it
+    OAuthRedirectUriInvalid => ("OAUTH_REDIRECT_URI_INVALID", BAD_REQUEST),
+    OAuthResponseTypeUnsupported => ("OAUTH_RESPONSE_TYPE_UNSUPPORTED", BAD_REQUEST),
+    OAuthScopeInvalid => ("OAUTH_SCOPE_INVALID", BAD_REQUEST),
+    OAuthGrantTypeInvalid => ("OAUTH_GRANT_TYPE_INVALID", BAD_REQUEST),
+    OAuthCodeChallengeInvalid => ("OAUTH_CODE_CHALLENGE_INVALID", BAD_REQUEST),
+    OAuthConsentNotFound => ("OAUTH_CONSENT_NOT_FOUND", NOT_FOUND),
+
+    // Tasks and reports
+    TaskTypeInvalid => ("TASK_TYPE_INVALID", BAD_REQUEST),
+    TaskTypeNotPaused => ("TASK_TYPE_NOT_PAUSED", NOT_FOUND),
+    TaskHistoryNotFound => ("TASK_HISTORY_NOT_FOUND", NOT_FOUND),
+    TaskQuotaExceeded => ("TASK_QUOTA_EXCEEDED", TOO_MANY_REQUESTS),
+    TaskDeadLetterSelectionEmpty => ("TASK_DEAD_LETTER_SELECTION_EMPTY", BAD_REQUEST),
+    ReportNotFound => ("REPORT_NOT_FOUND", NOT_FOUND),
+}
+
+impl ErrorCode {
+    /// Generic code of an error raised with only a status
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized,
+            StatusCode::FORBIDDEN => Self::Forbidden,
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::CONFLICT => Self::Conflict,
+            StatusCode::SERVICE_UNAVAILABLE => Self::ServiceUnavailable,
+            status if status.is_server_error() => Self::InternalError,
+            _ => Self::BadRequest,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl PartialSchema for ErrorCode {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .enum_values(Some(ErrorCode::ALL.iter().map(ErrorCode::as_str)))
+            .description(Some("Stable machine-readable reason of the error"))
+            .into()
+    }
+}
+
+impl ToSchema for ErrorCode {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use axum::http::StatusCode;
+
+    use super::ErrorCode;
+
+    #[test]
+    fn codes_are_unique_and_screaming_snake_case() {
+        let codes: HashSet<_> = ErrorCode::ALL.iter().map(ErrorCode::as_str).collect();
+
+        assert_eq!(codes.len(), ErrorCode::ALL.len());
+        assert!(codes.iter().all(|code| {
+            code.chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        }));
+    }
+
+    #[test]
+    fn maps_codes_to_statuses() {
+        assert_eq!(ErrorCode::AuthInvalidOtp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(ErrorCode::UserEmailTaken.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            ErrorCode::from_status(StatusCode::TOO_MANY_REQUESTS),
+            ErrorCode::BadRequest
+        );
+        assert_eq!(
+            ErrorCode::from_status(StatusCode::BAD_GATEWAY),
+            ErrorCode::InternalError
+        );
+        assert_eq!(
+            serde_json::to_value(ErrorCode::UserEmailTaken).unwrap(),
+            "USER_EMAIL_TAKEN"
+        );
+    }
+}
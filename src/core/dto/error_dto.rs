@@ -1,5 +1,5 @@
 use axum::Json;
-use axum::http::StatusCode;
+use axum::http::{HeaderValue, StatusCode, header::RETRY_AFTER};
 use axum::response::{IntoResponse, Response};
 use serde::Serialize;
 use serde_json::json;
@@ -8,6 +8,7 @@ use utoipa::ToSchema;
 
 use rust_i18n::t;
 
+use crate::core::dto::error_code::ErrorCode;
 use crate::core::dto::util::{ToJson, serialize_status_code};
 use crate::core::runbook::RunbookError;
 
@@ -16,20 +17,80 @@ pub struct ErrorDTO {
     #[schema(value_type = u64)]
     #[serde(serialize_with = "serialize_status_code")]
     pub status: StatusCode,
+    /// Stable reason clients branch on; the message is translated and may change
+    pub code: ErrorCode,
     pub message: String,
+    /// Every invalid field of a rejected request body or query
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldErrorDTO>,
+    /// Seconds until the request may succeed again, also sent as `Retry-After`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+    #[serde(skip)]
+    pub keep_changes: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FieldErrorDTO {
+    pub field: String,
+    pub message: String,
+    /// Name of the broken rule, for errors clients may want to tell apart
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+}
+
+/// Response extension asking the transaction layer to commit although the request failed
+#[derive(Debug, Clone, Copy)]
+pub struct KeepChanges;
+
 impl ErrorDTO {
+    /// Error of the generic code of `status`; prefer `from_code` for errors in the catalog
     pub fn new(status: StatusCode, message: String) -> Self {
-        Self { status, message }
+        Self {
+            status,
+            code: ErrorCode::from_status(status),
+            message,
+            errors: Vec::new(),
+            retry_after: None,
+            keep_changes: false,
+        }
+    }
+
+    /// Error answered with the status `code` is mapped to
+    pub fn from_code(code: ErrorCode, message: String) -> Self {
+        Self {
+            status: code.status(),
+            code,
+            message,
+            errors: Vec::new(),
+            retry_after: None,
+            keep_changes: false,
+        }
+    }
+
+    pub fn with_errors(mut self, errors: Vec<FieldErrorDTO>) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    pub fn with_retry_after(mut self, seconds: u64) -> Self {
+        self.retry_after = Some(seconds);
+        self
+    }
+
+    /// Commit what the request wrote before failing instead of rolling it back, e.g. a
+    /// failed attempt counted towards a lockout
+    pub fn keep_changes(mut self) -> Self {
+        self.keep_changes = true;
+        self
     }
 
     pub fn map_internal_error(e: impl std::fmt::Display) -> Self {
         let backtrace = Backtrace::force_capture();
         tracing::error!(error = %e, backtrace = %backtrace, "Internal error mapped to response");
 
-        Self::new(
-            StatusCode::INTERNAL_SERVER_ERROR,
+        Self::from_code(
+            ErrorCode::InternalError,
             t!("common.internal_server_error", error = e, locale = "en").to_string(),
         )
     }
@@ -38,10 +99,27 @@ impl ErrorDTO {
 impl IntoResponse for ErrorDTO {
     fn into_response(self) -> Response {
         let status = self.status;
-        let body = Json(json!({
+        let mut body = json!({
+            "code": self.code,
             "message": self.message,
-        }));
-        (status, body).into_response()
+        });
+        if !self.errors.is_empty() {
+            body["errors"] = json!(self.errors);
+        }
+        if let Some(retry_after) = self.retry_after {
+            body["retry_after"] = json!(retry_after);
+        }
+        let body = Json(body);
+        let mut response = (status, body).into_response();
+        if let Some(retry_after) = self.retry_after {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+        if self.keep_changes {
+            response.extensions_mut().insert(KeepChanges);
+        }
+        response
     }
 }
 
@@ -71,7 +149,7 @@ impl ToJson for ErrorDTO {}
 mod tests {
     use axum::{http::StatusCode, response::IntoResponse};
 
-    use super::ErrorDTO;
+    use super::{ErrorCode, ErrorDTO, KeepChanges};
     use crate::core::runbook::RunbookError;
 
     #[test]
@@ -79,6 +157,7 @@ mod tests {
         let error = ErrorDTO::new(StatusCode::BAD_REQUEST, "Test error".to_string());
         assert_eq!(error.status, StatusCode::BAD_REQUEST);
         assert_eq!(error.message, "Test error");
+        assert_eq!(error.code, ErrorCode::BadRequest);
         assert_eq!(format!("{error}"), "<400> Test error");
     }
 
@@ -95,6 +174,48 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn includes_code_in_response_body() {
+        let response =
+            ErrorDTO::from_code(ErrorCode::UserEmailTaken, "Taken".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "USER_EMAIL_TAKEN");
+        assert_eq!(body["message"], "Taken");
+    }
+
+    #[test]
+    fn marks_responses_that_keep_changes() {
+        let response = ErrorDTO::new(StatusCode::BAD_REQUEST, "Wrong code".to_string())
+            .keep_changes()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(response.extensions().get::<KeepChanges>().is_some());
+
+        let response =
+            ErrorDTO::new(StatusCode::BAD_REQUEST, "Wrong code".to_string()).into_response();
+        assert!(response.extensions().get::<KeepChanges>().is_none());
+    }
+
+    #[tokio::test]
+    async fn sends_retry_after_in_header_and_body() {
+        let response = ErrorDTO::from_code(ErrorCode::AuthAccountLocked, "Locked".to_string())
+            .with_retry_after(90)
+            .into_response();
+        assert_eq!(response.status(), StatusCode::LOCKED);
+        assert_eq!(response.headers()["retry-after"], "90");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["retry_after"], 90);
+    }
+
     #[test]
     fn converts_from_runbook_error() {
         let error = ErrorDTO::from(RunbookError::bad_request("invalid args"));
@@ -1,3 +1,5 @@
+pub mod datetime;
+pub mod error_code;
 pub mod error_dto;
 pub mod response_dto;
 pub mod runbook_dto;
@@ -3,7 +3,7 @@ use utoipa::ToSchema;
 
 use crate::core::runbook::{RunbookExecutionResult, RunbookMetadata};
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RunbookInfoDTO {
     pub name: String,
     pub description: String,
@@ -20,19 +20,19 @@ impl From<RunbookMetadata> for RunbookInfoDTO {
     }
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RunbookListDTO {
     pub runbooks: Vec<RunbookInfoDTO>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RunRunbookRequestDTO {
     pub name: String,
     #[serde(default)]
     pub args: Vec<String>,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RunRunbookResponseDTO {
     pub name: String,
     pub message: String,
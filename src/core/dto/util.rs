@@ -1,4 +1,4 @@
-use crate::core::dto::error_dto::ErrorDTO;
+use crate::core::dto::{error_code::ErrorCode, error_dto::ErrorDTO};
 use axum::http::StatusCode;
 use rust_i18n::t;
 use serde::de::DeserializeOwned;
@@ -21,8 +21,8 @@ where
     let fields = match &body {
         Value::Object(obj) => obj.keys().cloned().collect::<Vec<String>>(),
         _ => {
-            return Err(ErrorDTO::new(
-                StatusCode::BAD_REQUEST,
+            return Err(ErrorDTO::from_code(
+                ErrorCode::RequestBodyNotJson,
                 t!("common.request_body_must_be_json", locale = locale).to_string(),
             ));
         }
@@ -30,8 +30,8 @@ where
 
     // Deserialize the JSON to the target DTO type
     let dto = serde_json::from_value(body).map_err(|e| {
-        ErrorDTO::new(
-            StatusCode::BAD_REQUEST,
+        ErrorDTO::from_code(
+            ErrorCode::RequestBodyInvalid,
             t!("common.invalid_request_body", error = e, locale = locale).to_string(),
         )
     })?;
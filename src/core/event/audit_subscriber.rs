@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use sea_orm::ActiveValue::Set;
+
+use crate::{
+    common::{entity::audit_log, repository::audit_log_repository},
+    core::{
+        context::Context,
+        event::{DomainEvent, EventSubscriber},
+    },
+};
+
+/// Records every event under the `audit` log target, with the user who caused it, and in
+/// `audit_log` for `/api/v1/admin/audit-logs/`. The row is written in the request
+/// transaction, so events of rolled back requests aren't kept.
+pub struct AuditLogSubscriber;
+
+#[async_trait]
+impl EventSubscriber for AuditLogSubscriber {
+    fn name(&self) -> &'static str {
+        "audit_log"
+    }
+
+    async fn handle(&self, context: &Context, event: &DomainEvent) -> anyhow::Result<()> {
+        tracing::info!(
+            target: "audit",
+            event = event.name(),
+            user_id = event.user_id(),
+            actor_id = context.user.as_ref().map(|user| user.id),
+            payload = %serde_json::to_string(event)?,
+            "Domain event"
+        );
+
+        audit_log_repository::create(
+            context,
+            audit_log::ActiveModel {
+                actor: Set(context.actor()),
+                action: Set(event.name().to_string()),
+                resource_type: Set("user".to_string()),
+                resource_id: Set(Some(event.user_id().to_string())),
+                payload: Set(serde_json::to_value(event)?),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+}
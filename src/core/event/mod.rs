@@ -0,0 +1,224 @@
+//! Use cases announce what happened as a [`DomainEvent`] with `context.emit(...)`; the
+//! reactions (emails, audit logs, webhooks, ...) are [`EventSubscriber`]s on the context's
+//! [`EventBus`], so use cases don't hard-code their side effects. Apps add subscribers with
+//! `AppBuilder::event_subscriber` or `Module::event_subscribers`.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::{
+    core::context::Context,
+    user::{
+        entity::sea_orm_active_enums::UserRole,
+        subscriber::{EmailVerificationSubscriber, WelcomeEmailSubscriber},
+    },
+};
+
+pub mod audit_subscriber;
+
+pub use audit_subscriber::AuditLogSubscriber;
+
+/// Something that happened to a user
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DomainEvent {
+    /// Signed up, or was created by the admin `created_by`
+    UserRegistered {
+        user_id: i32,
+        created_by: Option<i32>,
+    },
+    PasswordChanged {
+        user_id: i32,
+    },
+    /// Password set through a reset OTP
+    PasswordReset {
+        user_id: i32,
+    },
+    ProfileUpdated {
+        user_id: i32,
+        fields: Vec<String>,
+    },
+    PhoneVerified {
+        user_id: i32,
+    },
+    /// Confirmed their email address through the emailed verification link
+    EmailVerified {
+        user_id: i32,
+    },
+    /// New avatar stored as `file_id`, processed by a background task
+    AvatarUpdated {
+        user_id: i32,
+        file_id: i32,
+    },
+    /// Avatar `file_id` approved by the admin `approved_by` and shown on the profile
+    AvatarApproved {
+        user_id: i32,
+        file_id: i32,
+        approved_by: i32,
+    },
+    /// Avatar `file_id` rejected by the admin `rejected_by`; the previous one stays
+    AvatarRejected {
+        user_id: i32,
+        file_id: i32,
+        rejected_by: i32,
+    },
+    /// Soft deleted and signed out everywhere; an admin can still restore the account
+    UserDeleted {
+        user_id: i32,
+        deleted_by: Option<i32>,
+    },
+    /// Soft deleted account brought back by the admin `restored_by`
+    UserRestored {
+        user_id: i32,
+        restored_by: i32,
+    },
+    /// Removed for good by the admin `deleted_by`, with everything cascading from it
+    UserHardDeleted {
+        user_id: i32,
+        deleted_by: i32,
+    },
+    /// Signed out everywhere and blocked from signing in by the admin `deactivated_by`
+    UserDeactivated {
+        user_id: i32,
+        deactivated_by: Option<i32>,
+    },
+    RoleAssigned {
+        user_id: i32,
+        role: UserRole,
+        assigned_by: Option<i32>,
+    },
+    /// Signed in from a device or network not seen before, recorded as `security_event_id`
+    NewSignIn {
+        user_id: i32,
+        security_event_id: i32,
+    },
+    /// Reported the sign-in `security_event_id` as not theirs, revoking its sessions
+    SignInReported {
+        user_id: i32,
+        security_event_id: i32,
+    },
+    /// The admin `impersonated_by` was issued a token to act as the user
+    ImpersonationStarted {
+        user_id: i32,
+        impersonated_by: i32,
+    },
+    /// The admin `impersonated_by` stopped acting as the user, revoking their token
+    ImpersonationEnded {
+        user_id: i32,
+        impersonated_by: i32,
+    },
+}
+
+impl DomainEvent {
+    /// Snake case name of the event, e.g. `user_registered`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UserRegistered { .. } => "user_registered",
+            Self::PasswordChanged { .. } => "password_changed",
+            Self::PasswordReset { .. } => "password_reset",
+            Self::ProfileUpdated { .. } => "profile_updated",
+            Self::PhoneVerified { .. } => "phone_verified",
+            Self::EmailVerified { .. } => "email_verified",
+            Self::AvatarUpdated { .. } => "avatar_updated",
+            Self::AvatarApproved { .. } => "avatar_approved",
+            Self::AvatarRejected { .. } => "avatar_rejected",
+            Self::UserDeleted { .. } => "user_deleted",
+            Self::UserRestored { .. } => "user_restored",
+            Self::UserHardDeleted { .. } => "user_hard_deleted",
+            Self::UserDeactivated { .. } => "user_deactivated",
+            Self::RoleAssigned { .. } => "role_assigned",
+            Self::NewSignIn { .. } => "new_sign_in",
+            Self::SignInReported { .. } => "sign_in_reported",
+            Self::ImpersonationStarted { .. } => "impersonation_started",
+            Self::ImpersonationEnded { .. } => "impersonation_ended",
+        }
+    }
+
+    /// User the event is about
+    pub fn user_id(&self) -> i32 {
+        match self {
+            Self::UserRegistered { user_id, .. }
+            | Self::PasswordChanged { user_id }
+            | Self::PasswordReset { user_id }
+            | Self::ProfileUpdated { user_id, .. }
+            | Self::PhoneVerified { user_id }
+            | Self::EmailVerified { user_id }
+            | Self::AvatarUpdated { user_id, .. }
+            | Self::AvatarApproved { user_id, .. }
+            | Self::AvatarRejected { user_id, .. }
+            | Self::UserDeleted { user_id, .. }
+            | Self::UserRestored { user_id, .. }
+            | Self::UserHardDeleted { user_id, .. }
+            | Self::UserDeactivated { user_id, .. }
+            | Self::RoleAssigned { user_id, .. }
+            | Self::NewSignIn { user_id, .. }
+            | Self::SignInReported { user_id, .. }
+            | Self::ImpersonationStarted { user_id, .. }
+            | Self::ImpersonationEnded { user_id, .. } => *user_id,
+        }
+    }
+}
+
+/// Reacts to domain events. Runs inside the emitting use case, on its context, so its
+/// database writes share the request transaction.
+#[async_trait]
+pub trait EventSubscriber: Send + Sync {
+    /// Identifier used in logs
+    fn name(&self) -> &'static str;
+
+    async fn handle(&self, context: &Context, event: &DomainEvent) -> anyhow::Result<()>;
+}
+
+/// Delivers each event to every subscriber, in registration order
+#[derive(Clone)]
+pub struct EventBus {
+    subscribers: Vec<Arc<dyn EventSubscriber>>,
+}
+
+impl Default for EventBus {
+    /// The built-in subscribers: audit logging, welcome emails and email verification
+    fn default() -> Self {
+        Self::empty()
+            .with(Arc::new(AuditLogSubscriber))
+            .with(Arc::new(WelcomeEmailSubscriber))
+            .with(Arc::new(EmailVerificationSubscriber))
+    }
+}
+
+impl EventBus {
+    /// A bus without subscribers, where events go nowhere
+    pub fn empty() -> Self {
+        Self {
+            subscribers: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, subscriber: Arc<dyn EventSubscriber>) -> Self {
+        self.subscribers.push(subscriber);
+        self
+    }
+
+    pub fn subscriber_names(&self) -> Vec<&'static str> {
+        self.subscribers
+            .iter()
+            .map(|subscriber| subscriber.name())
+            .collect()
+    }
+
+    /// Deliver `event` to every subscriber. Side effects are best effort, like the
+    /// notifications they replace: a failing subscriber is logged and doesn't fail the use
+    /// case or stop the others.
+    pub async fn publish(&self, context: &Context, event: DomainEvent) {
+        for subscriber in &self.subscribers {
+            if let Err(e) = subscriber.handle(context, &event).await {
+                tracing::error!(
+                    subscriber = subscriber.name(),
+                    event = event.name(),
+                    "Event subscriber failed: {:?}",
+                    e
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use std::fmt::Write;
+
+use serde_json::{Map, Value, json};
+
+use crate::{
+    core::module::MessageSchema,
+    pkg::{broadcast::schema::MESSAGE_REJECTED_EVENT, messaging::stats::WORKER_STATS_EVENT},
+};
+
+/// An `event_type` broadcast to WebSocket clients, with the JSON Schema of its `data`
+#[derive(Debug, Clone, Copy)]
+pub struct EventType {
+    pub name: &'static str,
+    pub description: &'static str,
+    schema: fn() -> Value,
+}
+
+impl EventType {
+    const fn new(name: &'static str, description: &'static str, schema: fn() -> Value) -> Self {
+        Self {
+            name,
+            description,
+            schema,
+        }
+    }
+
+    pub fn schema(&self) -> Value {
+        (self.schema)()
+    }
+
+    /// Schema forwarded broadcasts of this event type are validated against
+    pub fn message_schema(&self) -> MessageSchema {
+        MessageSchema::outbound(self.name, self.schema())
+    }
+}
+
+pub const AVATAR_UPLOAD_PROGRESS: EventType = EventType::new(
+    "avatar_upload_progress",
+    "A stage of an avatar upload task finished",
+    avatar_upload_progress_schema,
+);
+
+pub const AVATAR_UPLOAD_COMPLETE: EventType = EventType::new(
+    "avatar_upload_complete",
+    "An avatar upload task finished",
+    avatar_upload_progress_schema,
+);
+
+pub const BULK_USER_PROGRESS: EventType = EventType::new(
+    "bulk_user_progress",
+    "An item of a bulk user operation was processed",
+    bulk_user_progress_schema,
+);
+
+pub const BULK_USER_COMPLETE: EventType = EventType::new(
+    "bulk_user_complete",
+    "A bulk user operation finished, with the report of every item",
+    bulk_user_progress_schema,
+);
+
+pub const FILE_QUARANTINED: EventType = EventType::new(
+    "file_quarantined",
+    "The antivirus found malware in an upload, sent to its task and its owner",
+    file_quarantined_schema,
+);
+
+pub const THUMBNAIL_PROGRESS: EventType = EventType::new(
+    "thumbnail_progress",
+    "A thumbnail variant of an image was generated",
+    thumbnail_progress_schema,
+);
+
+pub const PIPELINE_PROGRESS: EventType = EventType::new(
+    "pipeline_progress",
+    "Overall progress of a multi-stage task pipeline, labelled with its current stage",
+    pipeline_progress_schema,
+);
+
+pub const NOTIFICATION: EventType = EventType::new(
+    "notification",
+    "A notification was added to the inbox of a user",
+    notification_schema,
+);
+
+pub const WORKER_STATS: EventType = EventType::new(
+    WORKER_STATS_EVENT,
+    "Task queue counters a worker reports periodically, never archived",
+    worker_stats_schema,
+);
+
+pub const SERVER_DRAINING: EventType = EventType::new(
+    "server_draining",
+    "The server is shutting down; reconnect to another instance",
+    server_draining_schema,
+);
+
+pub const MESSAGE_REJECTED: EventType = EventType::new(
+    MESSAGE_REJECTED_EVENT,
+    "Sent in place of a message that failed its schema",
+    message_rejected_schema,
+);
+
+/// Every event type the app broadcasts. Modules document their own through
+/// `Module::message_schemas`.
+pub const EVENT_TYPES: &[EventType] = &[
+    AVATAR_UPLOAD_PROGRESS,
+    AVATAR_UPLOAD_COMPLETE,
+    BULK_USER_PROGRESS,
+    BULK_USER_COMPLETE,
+    FILE_QUARANTINED,
+    THUMBNAIL_PROGRESS,
+    PIPELINE_PROGRESS,
+    NOTIFICATION,
+    WORKER_STATS,
+    SERVER_DRAINING,
+    MESSAGE_REJECTED,
+];
+
+/// Schemas of every event type, registered for validation when the app starts
+pub fn message_schemas() -> Vec<MessageSchema> {
+    EVENT_TYPES.iter().map(EventType::message_schema).collect()
+}
+
+/// JSON Schema of every event type's `data`, keyed by event type under `$defs`
+pub fn json_schema() -> Value {
+    let defs: Map<String, Value> = EVENT_TYPES
+        .iter()
+        .map(|event_type| {
+            let mut schema = event_type.schema();
+            schema["description"] = json!(event_type.description);
+            (event_type.name.to_string(), schema)
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "my-axum broadcast events",
+        "$defs": defs,
+    })
+}
+
+/// Markdown reference of every event type and the schema of its `data`
+pub fn markdown() -> String {
+    let mut document = String::from("# Broadcast events\n");
+    for event_type in EVENT_TYPES {
+        let schema = serde_json::to_string_pretty(&event_type.schema()).unwrap_or_default();
+        let _ = write!(
+            document,
+            "\n## `{}`\n\n{}.\n\n```json\n{}\n```\n",
+            event_type.name, event_type.description, schema
+        );
+    }
+    document
+}
+
+/// `AvatarUploadProgressDTO`
+fn avatar_upload_progress_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["task_id", "user_id", "progress", "status"],
+        "properties": {
+            "task_id": {"type": "string"},
+            "user_id": {"type": "integer"},
+            "progress": {"type": "integer", "minimum": 0, "maximum": 100},
+            "status": {"type": "string"},
+            "message": {"type": ["string", "null"]}
+        }
+    })
+}
+
+/// `BulkUserProgressDTO`
+fn bulk_user_progress_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["task_id", "progress", "status", "processed", "total"],
+        "properties": {
+            "task_id": {"type": "string"},
+            "progress": {"type": "integer", "minimum": 0, "maximum": 100},
+            "status": {"type": "string"},
+            "processed": {"type": "integer", "minimum": 0},
+            "total": {"type": "integer", "minimum": 0},
+            "report": {"type": ["object", "null"]}
+        }
+    })
+}
+
+fn file_quarantined_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["file_id", "user_id", "file_name", "status", "message"],
+        "properties": {
+            "task_id": {"type": "string"},
+            "file_id": {"type": "integer"},
+            "user_id": {"type": "integer"},
+            "file_name": {"type": "string"},
+            "signature": {"type": "string"},
+            "status": {"const": "quarantined"},
+            "message": {"type": "string"}
+        }
+    })
+}
+
+fn thumbnail_progress_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["task_id", "user_id", "file_id", "variant", "progress", "status"],
+        "properties": {
+            "task_id": {"type": "string"},
+            "user_id": {"type": "integer"},
+            "file_id": {"type": "integer"},
+            "variant": {"type": "integer", "minimum": 1},
+            "progress": {"type": "integer", "minimum": 0, "maximum": 100},
+            "status": {"enum": ["processing", "completed"]}
+        }
+    })
+}
+
+/// `PipelineProgress`
+fn pipeline_progress_schema() -> Value {
+    let percent = json!({"type": "integer", "minimum": 0, "maximum": 100});
+    json!({
+        "type": "object",
+        "required": [
+            "task_id", "user_id", "progress", "stage", "stage_index", "stage_count",
+            "stage_progress", "status"
+        ],
+        "properties": {
+            "task_id": {"type": "string"},
+            "user_id": {"type": "integer"},
+            "progress": percent,
+            "stage": {"type": "string"},
+            "stage_index": {"type": "integer", "minimum": 0},
+            "stage_count": {"type": "integer", "minimum": 1},
+            "stage_progress": percent,
+            "status": {"enum": ["processing", "completed", "failed"]}
+        }
+    })
+}
+
+fn notification_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["id", "user_id", "category", "title", "body"],
+        "properties": {
+            "id": {"type": "integer"},
+            "user_id": {"type": "integer"},
+            "category": {"type": "string"},
+            "title": {"type": "string"},
+            "body": {"type": "string"},
+            "data": {"type": "object"}
+        }
+    })
+}
+
+/// `WorkerStats`
+fn worker_stats_schema() -> Value {
+    let counter = json!({"type": "integer", "minimum": 0});
+    json!({
+        "type": "object",
+        "required": ["worker_id"],
+        "properties": {
+            "worker_id": {"type": "string"},
+            "queued": counter,
+            "running": counter,
+            "succeeded": counter,
+            "failed": counter,
+            "retried": counter,
+            "lag_ms": counter,
+            "broker_failovers": counter,
+            "paused": counter,
+            "processed_last_minute": counter,
+            "circuit_breakers": {"type": "array"}
+        }
+    })
+}
+
+fn server_draining_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["reconnect"],
+        "properties": {
+            "reconnect": {"const": true},
+            "task_id": {"type": "string"}
+        }
+    })
+}
+
+fn message_rejected_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["rejected", "errors"],
+        "properties": {
+            "task_id": {"type": "string"},
+            "user_id": {"type": "integer"},
+            "rejected": {"type": ["string", "null"]},
+            "errors": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["path", "message"],
+                    "properties": {
+                        "path": {"type": "string"},
+                        "message": {"type": "string"}
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{EVENT_TYPES, json_schema, markdown};
+
+    #[test]
+    fn lists_event_types_once_with_valid_schemas() {
+        let names: BTreeSet<&str> = EVENT_TYPES
+            .iter()
+            .map(|event_type| event_type.name)
+            .collect();
+        assert_eq!(names.len(), EVENT_TYPES.len());
+
+        for event_type in EVENT_TYPES {
+            assert!(
+                event_type.message_schema().register().is_ok(),
+                "{} has an invalid schema",
+                event_type.name
+            );
+        }
+    }
+
+    #[test]
+    fn documents_every_event_type() {
+        let schema = json_schema();
+        let document = markdown();
+
+        for event_type in EVENT_TYPES {
+            assert_eq!(
+                schema["$defs"][event_type.name]["description"],
+                event_type.description
+            );
+            assert!(document.contains(&format!("## `{}`", event_type.name)));
+        }
+    }
+}
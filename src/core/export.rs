@@ -0,0 +1,133 @@
+use std::{future::Future, io};
+
+use axum::{
+    body::{Body, Bytes},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use futures::stream;
+
+/// Record written as one row of a CSV export
+pub trait CsvRecord {
+    /// Column names, written as the first row
+    fn header() -> &'static [&'static str];
+
+    fn fields(&self) -> Vec<String>;
+}
+
+/// CSV download streamed page by page, so exports of any size are never held in memory.
+/// Pages are read after the response started, outside the request transaction, so
+/// `next_page` should query the pool, e.g. through `Context::read_only`.
+pub struct CsvExport {
+    file_name: String,
+    body: Body,
+}
+
+impl CsvExport {
+    /// Export the records `next_page` returns. It is called with the cursor the previous
+    /// page returned, `None` for the first one, until it returns no cursor or no records.
+    /// A failing page ends the download early, aborting the connection so the client
+    /// doesn't take the truncated file as complete.
+    pub fn new<T, C, F, Fut>(file_name: impl Into<String>, next_page: F) -> Self
+    where
+        T: CsvRecord + Send + 'static,
+        C: Send + 'static,
+        F: FnMut(Option<C>) -> Fut + Send + 'static,
+        Fut: Future<Output = anyhow::Result<(Vec<T>, Option<C>)>> + Send,
+    {
+        let header = Bytes::from(csv_row(T::header().iter().copied()));
+        let pages = stream::unfold(
+            (next_page, None, false),
+            |(mut next_page, cursor, done)| async move {
+                if done {
+                    return None;
+                }
+                match next_page(cursor).await {
+                    Ok((records, _)) if records.is_empty() => None,
+                    Ok((records, cursor)) => {
+                        let chunk: String = records
+                            .iter()
+                            .map(|record| csv_row(record.fields().iter().map(String::as_str)))
+                            .collect();
+                        let done = cursor.is_none();
+                        Some((Ok(Bytes::from(chunk)), (next_page, cursor, done)))
+                    }
+                    Err(e) => {
+                        tracing::error!("CSV export failed: {:?}", e);
+                        Some((
+                            Err(io::Error::other(e.to_string())),
+                            (next_page, None, true),
+                        ))
+                    }
+                }
+            },
+        );
+
+        Self {
+            file_name: file_name.into(),
+            body: Body::from_stream(futures::StreamExt::chain(
+                stream::once(async { Ok::<_, io::Error>(header) }),
+                pages,
+            )),
+        }
+    }
+}
+
+impl IntoResponse for CsvExport {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", self.file_name),
+                ),
+            ],
+            self.body,
+        )
+            .into_response()
+    }
+}
+
+/// One RFC 4180 row. Fields a spreadsheet would evaluate as a formula are prefixed with `'`.
+fn csv_row<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+    let mut row = fields
+        .map(|field| {
+            let field = if field.starts_with(['=', '+', '-', '@']) {
+                format!("'{}", field)
+            } else {
+                field.to_string()
+            };
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::csv_row;
+
+    #[test]
+    fn quotes_fields_with_separators() {
+        assert_eq!(
+            csv_row(["a", "b,c", "say \"hi\""].into_iter()),
+            "a,\"b,c\",\"say \"\"hi\"\"\"\r\n"
+        );
+    }
+
+    #[test]
+    fn neutralizes_formulas() {
+        assert_eq!(
+            csv_row(["=SUM(A1)", "-1", "user:1"].into_iter()),
+            "'=SUM(A1),'-1,user:1\r\n"
+        );
+    }
+}
@@ -0,0 +1,344 @@
+//! Seed data described in YAML or JSON files, loaded by the `seed` runbook and by tests.
+//!
+//! Records are named by a `key` other records refer to them by, e.g. a user lists the keys
+//! of its roles and a refresh token the key of its user. Records are inserted in file order,
+//! roles first, then users, then refresh tokens. Records given an `id` keep it, so tests can
+//! rely on it; the others get the next one of their table.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use sea_orm::{ConnectionTrait, DbBackend, entity::*};
+use serde::Deserialize;
+
+use crate::{
+    config::setting::Setting,
+    core::context::Context,
+    user::{
+        entity::{permission, refresh_token, role, sea_orm_active_enums::UserRole, user},
+        repository::{
+            permission_repository, refresh_token_repository, role_repository, user_repository,
+        },
+        service::auth_service,
+    },
+};
+
+/// Directory of the fixtures loadable by name
+pub const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+
+const EXTENSIONS: [&str; 3] = ["yaml", "yml", "json"];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fixture {
+    #[serde(default)]
+    pub roles: Vec<RoleFixture>,
+    #[serde(default)]
+    pub users: Vec<UserFixture>,
+    #[serde(default)]
+    pub refresh_tokens: Vec<RefreshTokenFixture>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoleFixture {
+    pub key: String,
+    pub id: Option<i32>,
+    pub name: String,
+    pub description: Option<String>,
+    /// Names of the permissions the role grants, created when missing
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserFixture {
+    pub key: String,
+    pub id: Option<i32>,
+    pub email: String,
+    /// Plaintext password, hashed when the user is inserted
+    pub password: String,
+    /// `user` when not given
+    pub role: Option<UserRole>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub phone: Option<String>,
+    /// Keys of the roles assigned to the user
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RefreshTokenFixture {
+    pub key: String,
+    pub id: Option<i32>,
+    /// Key of the user the token is issued to
+    pub user: String,
+    /// Plaintext token, stored hashed like issued ones
+    pub token: String,
+    pub device_info: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// IDs of the records of a loaded fixture, by key
+#[derive(Debug, Default, Clone)]
+pub struct LoadedFixture {
+    pub roles: HashMap<String, i32>,
+    pub users: HashMap<String, i32>,
+    pub refresh_tokens: HashMap<String, i32>,
+    /// Records inserted; the others already existed and were left as they are
+    pub created: usize,
+}
+
+impl LoadedFixture {
+    pub fn role_id(&self, key: &str) -> i32 {
+        Self::id(&self.roles, "role", key)
+    }
+
+    pub fn user_id(&self, key: &str) -> i32 {
+        Self::id(&self.users, "user", key)
+    }
+
+    pub fn refresh_token_id(&self, key: &str) -> i32 {
+        Self::id(&self.refresh_tokens, "refresh token", key)
+    }
+
+    fn id(ids: &HashMap<String, i32>, kind: &str, key: &str) -> i32 {
+        *ids.get(key)
+            .unwrap_or_else(|| panic!("Fixture has no {kind} '{key}'"))
+    }
+}
+
+impl Fixture {
+    /// Parse `source`, as JSON when `path` ends in `.json` and as YAML otherwise
+    pub fn parse(path: &Path, source: &str) -> anyhow::Result<Self> {
+        let fixture = if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            serde_json::from_str(source)?
+        } else {
+            serde_yaml::from_str(source)?
+        };
+        Ok(fixture)
+    }
+
+    pub async fn read(path: &Path) -> anyhow::Result<Self> {
+        let source = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read fixture {}: {e}", path.display()))?;
+        Self::parse(path, &source)
+            .map_err(|e| anyhow::anyhow!("Invalid fixture {}: {e}", path.display()))
+    }
+
+    /// Read the fixture `name` of `FIXTURE_DIR`, e.g. `basic_users` for `basic_users.yaml`
+    pub async fn read_named(name: &str) -> anyhow::Result<Self> {
+        Self::read(&named_path(name)?).await
+    }
+}
+
+fn named_path(name: &str) -> anyhow::Result<PathBuf> {
+    EXTENSIONS
+        .iter()
+        .map(|extension| Path::new(FIXTURE_DIR).join(format!("{name}.{extension}")))
+        .find(|path| path.is_file())
+        .ok_or_else(|| anyhow::anyhow!("No fixture named '{name}' in {FIXTURE_DIR}"))
+}
+
+/// Insert the records of `fixture` that don't exist yet. Roles are matched by name, users by
+/// email and refresh tokens by token, so loading a fixture again changes nothing.
+pub async fn load(context: &Context, fixture: &Fixture) -> anyhow::Result<LoadedFixture> {
+    let mut loaded = LoadedFixture::default();
+
+    for role_fixture in &fixture.roles {
+        let id = match role_repository::find_by_name(context, &role_fixture.name).await? {
+            Some(role) => role.id,
+            None => {
+                let role = insert_role(context, role_fixture).await?;
+                loaded.created += 1;
+                role.id
+            }
+        };
+        insert_key(&mut loaded.roles, "role", &role_fixture.key, id)?;
+    }
+
+    for user_fixture in &fixture.users {
+        let role_ids = user_fixture
+            .roles
+            .iter()
+            .map(|key| resolve(&loaded.roles, "role", key, "user", &user_fixture.key))
+            .collect::<anyhow::Result<Vec<i32>>>()?;
+        let id = match user_repository::find_by_email(context, &user_fixture.email).await? {
+            Some(user) => user.id,
+            None => {
+                let user = insert_user(context, user_fixture).await?;
+                for role_id in role_ids {
+                    role_repository::assign_to_user(context, user.id, role_id).await?;
+                }
+                loaded.created += 1;
+                user.id
+            }
+        };
+        insert_key(&mut loaded.users, "user", &user_fixture.key, id)?;
+    }
+
+    for token_fixture in &fixture.refresh_tokens {
+        let user_id = resolve(
+            &loaded.users,
+            "user",
+            &token_fixture.user,
+            "refresh token",
+            &token_fixture.key,
+        )?;
+        let id = match refresh_token_repository::find_by_token(context, &token_fixture.token)
+            .await?
+        {
+            Some(refresh_token) => refresh_token.id,
+            None => {
+                let refresh_token = insert_refresh_token(context, user_id, token_fixture).await?;
+                loaded.created += 1;
+                refresh_token.id
+            }
+        };
+        insert_key(
+            &mut loaded.refresh_tokens,
+            "refresh token",
+            &token_fixture.key,
+            id,
+        )?;
+    }
+
+    if fixture.roles.iter().any(|record| record.id.is_some()) {
+        sync_id_sequence(context, "role").await?;
+    }
+    if fixture.users.iter().any(|record| record.id.is_some()) {
+        sync_id_sequence(context, "user").await?;
+    }
+    if fixture
+        .refresh_tokens
+        .iter()
+        .any(|record| record.id.is_some())
+    {
+        sync_id_sequence(context, "refresh_token").await?;
+    }
+
+    Ok(loaded)
+}
+
+async fn insert_role(context: &Context, fixture: &RoleFixture) -> anyhow::Result<role::Model> {
+    let role = role_repository::create(
+        context,
+        role::ActiveModel {
+            id: fixture.id.map_or(NotSet, Set),
+            name: Set(fixture.name.clone()),
+            description: Set(fixture.description.clone()),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    for name in &fixture.permissions {
+        let permission = match permission_repository::find_by_name(context, name).await? {
+            Some(permission) => permission,
+            None => {
+                permission_repository::create(
+                    context,
+                    permission::ActiveModel {
+                        name: Set(name.clone()),
+                        ..Default::default()
+                    },
+                )
+                .await?
+            }
+        };
+        role_repository::grant_permission(context, role.id, permission.id).await?;
+    }
+    Ok(role)
+}
+
+async fn insert_user(context: &Context, fixture: &UserFixture) -> anyhow::Result<user::Model> {
+    let user = user_repository::create(
+        context,
+        user::ActiveModel {
+            id: fixture.id.map_or(NotSet, Set),
+            email: Set(fixture.email.clone()),
+            password: Set(auth_service::hash_password(&fixture.password).await?),
+            role: Set(fixture.role.clone().unwrap_or(UserRole::User)),
+            first_name: Set(fixture.first_name.clone()),
+            last_name: Set(fixture.last_name.clone()),
+            phone: Set(fixture.phone.clone()),
+            verified_at: Set(Some(Utc::now().naive_utc())),
+            ..Default::default()
+        },
+    )
+    .await?;
+    Ok(user)
+}
+
+async fn insert_refresh_token(
+    context: &Context,
+    user_id: i32,
+    fixture: &RefreshTokenFixture,
+) -> anyhow::Result<refresh_token::Model> {
+    let now = Utc::now().naive_utc();
+    let expiry = auth_service::refresh_token_expiry(&Setting::new(), None, now);
+    let refresh_token = refresh_token_repository::create(
+        context,
+        refresh_token::ActiveModel {
+            id: fixture.id.map_or(NotSet, Set),
+            user_id: Set(user_id),
+            token: Set(fixture.token.clone()),
+            device_info: Set(fixture.device_info.clone()),
+            ip_address: Set(fixture.ip_address.clone()),
+            expires_at: Set(expiry.expires_at),
+            expiration_policy: Set(expiry.policy),
+            max_expires_at: Set(expiry.max_expires_at),
+            family_id: Set(Some(uuid::Uuid::new_v4().to_string())),
+            ..Default::default()
+        },
+    )
+    .await?;
+    Ok(refresh_token)
+}
+
+fn insert_key(
+    ids: &mut HashMap<String, i32>,
+    kind: &str,
+    key: &str,
+    id: i32,
+) -> anyhow::Result<()> {
+    if ids.insert(key.to_string(), id).is_some() {
+        anyhow::bail!("Fixture has more than one {kind} '{key}'");
+    }
+    Ok(())
+}
+
+fn resolve(
+    ids: &HashMap<String, i32>,
+    kind: &str,
+    key: &str,
+    referrer_kind: &str,
+    referrer_key: &str,
+) -> anyhow::Result<i32> {
+    ids.get(key).copied().ok_or_else(|| {
+        anyhow::anyhow!("Fixture {referrer_kind} '{referrer_key}' refers to unknown {kind} '{key}'")
+    })
+}
+
+/// Move the ID sequence of `table` past the IDs given explicitly, so later inserts don't reuse
+/// them. SQLite already continues after the largest ID.
+async fn sync_id_sequence(context: &Context, table: &str) -> anyhow::Result<()> {
+    let connection = context.txn();
+    if connection.get_database_backend() == DbBackend::Postgres {
+        connection
+            .execute_unprepared(&format!(
+                "SELECT setval(pg_get_serial_sequence('\"{table}\"', 'id'), \
+                 (SELECT MAX(id) FROM \"{table}\"))"
+            ))
+            .await?;
+    }
+    Ok(())
+}
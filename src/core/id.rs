@@ -0,0 +1,168 @@
+use rand::RngExt;
+use serde::Deserialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use uuid::Uuid;
+
+/// Characters a one-time password is made of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OtpAlphabet {
+    /// Digits only
+    #[default]
+    Numeric,
+    /// Digits and uppercase letters
+    Alphanumeric,
+}
+
+impl OtpAlphabet {
+    fn chars(self) -> &'static [u8] {
+        match self {
+            OtpAlphabet::Numeric => b"0123456789",
+            OtpAlphabet::Alphanumeric => b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        }
+    }
+
+    /// Normalize an OTP a user typed, so alphanumeric codes match in any case
+    pub fn normalize(self, otp: &str) -> String {
+        match self {
+            OtpAlphabet::Numeric => otp.trim().to_string(),
+            OtpAlphabet::Alphanumeric => otp.trim().to_ascii_uppercase(),
+        }
+    }
+}
+
+/// Source of externally visible identifiers and one-time codes.
+/// Injected through `AppState`/`Context` so tests can make them deterministic.
+pub trait IdGenerator: Send + Sync {
+    /// Identifier for a background task that clients can track (UUID string)
+    fn task_id(&self) -> String;
+
+    /// Numeric one-time password with exactly `digits` digits
+    fn otp(&self, digits: u32) -> String;
+
+    /// One-time password of `length` characters taken from `alphabet`
+    fn otp_from(&self, length: u32, alphabet: OtpAlphabet) -> String;
+}
+
+/// Random UUID v4 task ids and uniformly random OTPs
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RandomIdGenerator;
+
+impl IdGenerator for RandomIdGenerator {
+    fn task_id(&self) -> String {
+        Uuid::new_v4().to_string()
+    }
+
+    fn otp(&self, digits: u32) -> String {
+        let value = rand::rng().random_range(0..10u64.pow(digits));
+        format!("{:0width$}", value, width = digits as usize)
+    }
+
+    fn otp_from(&self, length: u32, alphabet: OtpAlphabet) -> String {
+        let chars = alphabet.chars();
+        let mut rng = rand::rng();
+        (0..length)
+            .map(|_| chars[rng.random_range(0..chars.len())] as char)
+            .collect()
+    }
+}
+
+/// Predictable values for tests: the n-th task id is `Uuid::from_u128(n)` and
+/// the n-th OTP is `n` zero-padded, each counting from 1 independently.
+/// OTPs are numeric whatever the alphabet asked for, which every alphabet accepts.
+#[derive(Debug, Default)]
+pub struct SequentialIdGenerator {
+    task_ids: AtomicU64,
+    otps: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The task id returned by the `n`-th call to `task_id` (1-based)
+    pub fn nth_task_id(n: u64) -> String {
+        Uuid::from_u128(n as u128).to_string()
+    }
+
+    /// The OTP returned by the `n`-th call to `otp` (1-based)
+    pub fn nth_otp(n: u64, digits: u32) -> String {
+        let n = 10u64.checked_pow(digits).map_or(n, |modulus| n % modulus);
+        format!("{:0width$}", n, width = digits as usize)
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn task_id(&self) -> String {
+        Self::nth_task_id(self.task_ids.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn otp(&self, digits: u32) -> String {
+        Self::nth_otp(self.otps.fetch_add(1, Ordering::SeqCst) + 1, digits)
+    }
+
+    fn otp_from(&self, length: u32, _alphabet: OtpAlphabet) -> String {
+        self.otp(length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IdGenerator, OtpAlphabet, RandomIdGenerator, SequentialIdGenerator};
+
+    #[test]
+    fn random_generator_produces_uuid_task_ids_and_padded_otps() {
+        let generator = RandomIdGenerator;
+
+        assert!(uuid::Uuid::parse_str(&generator.task_id()).is_ok());
+        for _ in 0..20 {
+            let otp = generator.otp(6);
+            assert_eq!(otp.len(), 6);
+            assert!(otp.chars().all(|c| c.is_ascii_digit()));
+        }
+    }
+
+    #[test]
+    fn random_generator_uses_the_requested_alphabet() {
+        let generator = RandomIdGenerator;
+
+        let otp = generator.otp_from(8, OtpAlphabet::Numeric);
+        assert_eq!(otp.len(), 8);
+        assert!(otp.chars().all(|c| c.is_ascii_digit()));
+
+        let otps: Vec<String> = (0..20)
+            .map(|_| generator.otp_from(10, OtpAlphabet::Alphanumeric))
+            .collect();
+        assert!(otps.iter().all(|otp| {
+            otp.len() == 10
+                && otp
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+        }));
+        assert!(
+            otps.iter()
+                .any(|otp| otp.chars().any(|c| c.is_ascii_uppercase()))
+        );
+    }
+
+    #[test]
+    fn alphanumeric_otps_are_normalized_to_uppercase() {
+        assert_eq!(OtpAlphabet::Alphanumeric.normalize(" ab12cd "), "AB12CD");
+        assert_eq!(OtpAlphabet::Numeric.normalize(" 123456 "), "123456");
+    }
+
+    #[test]
+    fn sequential_generator_counts_from_one() {
+        let generator = SequentialIdGenerator::new();
+
+        assert_eq!(generator.task_id(), "00000000-0000-0000-0000-000000000001");
+        assert_eq!(generator.task_id(), SequentialIdGenerator::nth_task_id(2));
+        assert_eq!(generator.otp(6), "000001");
+        assert_eq!(generator.otp(4), "0002");
+        assert_eq!(
+            generator.otp_from(32, OtpAlphabet::Alphanumeric),
+            format!("{:032}", 3)
+        );
+    }
+}
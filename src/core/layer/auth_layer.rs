@@ -1,57 +1,107 @@
 use crate::config::app::AppState;
 use crate::core::context::Context;
-use crate::core::db::uow::new_transaction;
-use crate::core::dto::error_dto::ErrorDTO;
+use crate::core::db::uow::read_only;
+use crate::core::dto::datetime::{parse_timezone, with_timezone};
+use crate::core::dto::{error_code::ErrorCode, error_dto::ErrorDTO};
 use crate::core::layer::lang_layer::RequestLocale;
+use crate::core::permission::PermissionSet;
+use crate::core::service_account::ServicePrincipal;
 use crate::user::entity::sea_orm_active_enums::UserRole;
 use crate::user::entity::user;
+use crate::user::repository::permission_repository;
 use crate::user::service::auth_service::{self, TokenType};
 use axum::extract::State;
-use axum::http::StatusCode;
 use axum::{extract::Request, middleware::Next, response::Response};
+use chrono_tz::Tz;
 use rust_i18n::t;
 
+/// Admin acting as the request's user, set on requests made with an impersonation token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Impersonator(pub i32);
+
+/// Permissions an impersonation token never carries, even when the user holds them, so it
+/// can't be used to impersonate further, change roles or reach past the user's own account
+pub const IMPERSONATION_WITHHELD_PERMISSIONS: &[&str] = &[
+    "user.impersonate",
+    "user.bulk",
+    "user.hard_delete",
+    "oauth_client.manage",
+];
+
+/// Authenticate the user of the request's access token and resolve the permissions their
+/// roles grant. Requests of a service principal, authenticated by `service_auth_middleware`
+/// on the mTLS listener, need no token.
 pub async fn auth_middleware(
     State(app_state): State<AppState>,
     mut req: Request,
     next: Next,
 ) -> Result<Response, ErrorDTO> {
+    if req.extensions().get::<ServicePrincipal>().is_some() {
+        return Ok(next.run(req).await);
+    }
+
     let headers = req.headers().clone();
     let uri_query = req.uri().query().map(|q| q.to_string());
-    let locale = req
-        .extensions()
-        .get::<RequestLocale>()
-        .map(|l| l.as_str().to_string());
+    let request_locale = req.extensions().get::<RequestLocale>().cloned();
+    let locale = request_locale.as_ref().map(|l| l.as_str().to_string());
 
-    let current_user = new_transaction(&app_state, None, locale, move |context| {
-        Box::pin(async move {
-            let access_token = auth_service::extract_token_from_header_or_cookie(
-                &headers,
-                TokenType::Access,
-                &context.locale,
-            )
-            .await
-            .or_else(|_| {
-                // Try to get token from query parameters (for WebSocket connections)
-                uri_query
-                    .as_ref()
-                    .and_then(|query| auth_service::get_token_from_query_params(query, "token"))
-                    .ok_or_else(|| {
-                        ErrorDTO::new(
-                            axum::http::StatusCode::UNAUTHORIZED,
-                            t!("auth.access_token_not_found", locale = &context.locale).to_string(),
-                        )
-                    })
-            })?;
+    let (current_user, impersonator, permissions) =
+        read_only(&app_state, None, locale, move |context| {
+            Box::pin(async move {
+                let access_token = auth_service::extract_token_from_header_or_cookie(
+                    &headers,
+                    TokenType::Access,
+                    &context.locale,
+                )
+                .await
+                .or_else(|_| {
+                    // Try to get token from query parameters (for WebSocket connections)
+                    uri_query
+                        .as_ref()
+                        .and_then(|query| auth_service::get_token_from_query_params(query, "token"))
+                        .ok_or_else(|| {
+                            ErrorDTO::from_code(
+                                ErrorCode::AuthTokenMissing,
+                                t!("auth.access_token_not_found", locale = &context.locale)
+                                    .to_string(),
+                            )
+                        })
+                })?;
 
-            auth_service::get_current_user(context, &access_token).await
+                let (current_user, impersonator) =
+                    auth_service::get_current_user_and_impersonator(context, &access_token).await?;
+                let mut permissions =
+                    permission_repository::find_names_by_user_id(context, current_user.id)
+                        .await
+                        .map_err(ErrorDTO::map_internal_error)?
+                        .into_iter()
+                        .collect::<PermissionSet>();
+                if impersonator.is_some() {
+                    permissions = permissions.without(IMPERSONATION_WITHHELD_PERMISSIONS);
+                }
+                Ok::<_, ErrorDTO>((current_user, impersonator, permissions))
+            })
         })
-    })
-    .await?;
+        .await?;
 
+    // The user's preferred locale beats `Accept-Language`, but not an explicit `lang` parameter
+    if let Some(preferred) = &current_user.locale
+        && !request_locale.is_some_and(|l| l.is_explicit())
+    {
+        req.extensions_mut().insert(RequestLocale::new(preferred));
+    }
+    let timezone = current_user
+        .timezone
+        .as_deref()
+        .and_then(parse_timezone)
+        .unwrap_or(Tz::UTC);
     req.extensions_mut().insert(current_user);
+    if let Some(admin_id) = impersonator {
+        req.extensions_mut().insert(Impersonator(admin_id));
+    }
+    req.extensions_mut().insert(permissions);
 
-    Ok(next.run(req).await)
+    Ok(with_timezone(timezone, next.run(req)).await)
 }
 
 pub fn authorize_role(
@@ -66,7 +116,7 @@ pub fn authorize_role(
             locale = &context.locale
         )
         .to_string();
-        return Err(ErrorDTO::new(StatusCode::FORBIDDEN, message));
+        return Err(ErrorDTO::from_code(ErrorCode::AuthRoleRequired, message));
     }
 
     Ok(())
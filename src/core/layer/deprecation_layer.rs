@@ -0,0 +1,318 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, Method, header::LINK},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rust_i18n::t;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    core::{
+        api::route::matches_path_pattern,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+        layer::lang_layer::get_request_locale,
+        translation::locale::DEFAULT_LOCALE,
+    },
+    user::service::auth_service::{get_client_ip, get_device_info},
+};
+
+const DEPRECATION_HEADER: HeaderName = HeaderName::from_static("deprecation");
+const SUNSET_HEADER: HeaderName = HeaderName::from_static("sunset");
+
+/// Clients tracked per route; calls of further clients only count towards the route total
+const MAX_TRACKED_CLIENTS: usize = 1000;
+
+/// A route being retired, declared with `Module::deprecated_routes`. Until `sunset` it keeps
+/// working and answers with `Deprecation`, `Sunset` and successor `Link` headers; afterwards
+/// it is answered with `410 Gone`.
+#[derive(Debug, Clone)]
+pub struct RouteDeprecation {
+    /// `None` matches every method
+    pub method: Option<Method>,
+    /// Path pattern, `*` matching one path segment
+    pub path: String,
+    pub deprecated_at: DateTime<Utc>,
+    pub sunset: Option<DateTime<Utc>>,
+    /// Path or URL of the route to use instead
+    pub replacement: Option<String>,
+}
+
+impl RouteDeprecation {
+    pub fn new(path: impl Into<String>, deprecated_at: DateTime<Utc>) -> Self {
+        Self {
+            method: None,
+            path: path.into(),
+            deprecated_at,
+            sunset: None,
+            replacement: None,
+        }
+    }
+
+    /// Only deprecate requests with `method`
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Answer with `410 Gone` from `sunset` on
+    pub fn sunset(mut self, sunset: DateTime<Utc>) -> Self {
+        self.sunset = Some(sunset);
+        self
+    }
+
+    pub fn replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = Some(replacement.into());
+        self
+    }
+
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        self.method
+            .as_ref()
+            .is_none_or(|expected| expected == method)
+            && matches_path_pattern(&self.path, path)
+    }
+
+    /// Whether the route is past its sunset at `now`
+    pub fn is_retired(&self, now: DateTime<Utc>) -> bool {
+        self.sunset.is_some_and(|sunset| sunset <= now)
+    }
+
+    fn apply_headers(&self, headers: &mut HeaderMap) {
+        // RFC 9745 structured date and RFC 8594 HTTP-date
+        if let Ok(value) = HeaderValue::from_str(&format!("@{}", self.deprecated_at.timestamp())) {
+            headers.insert(DEPRECATION_HEADER, value);
+        }
+        if let Some(sunset) = self.sunset
+            && let Ok(value) =
+                HeaderValue::from_str(&sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+        {
+            headers.insert(SUNSET_HEADER, value);
+        }
+        if let Some(replacement) = &self.replacement
+            && let Ok(value) =
+                HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", replacement))
+        {
+            headers.append(LINK, value);
+        }
+    }
+}
+
+/// Calls of a deprecated route by one client
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeprecatedRouteClient {
+    pub client_ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DeprecatedRouteCalls {
+    pub calls: u64,
+    pub last_used_at: NaiveDateTime,
+}
+
+/// Calls of a deprecated route since this process started
+#[derive(Debug, Clone)]
+pub struct DeprecatedRouteUsage {
+    pub deprecation: RouteDeprecation,
+    pub calls: u64,
+    pub last_used_at: Option<NaiveDateTime>,
+    /// Most calls first
+    pub clients: Vec<(DeprecatedRouteClient, DeprecatedRouteCalls)>,
+}
+
+#[derive(Default)]
+struct RouteUsage {
+    calls: u64,
+    last_used_at: Option<NaiveDateTime>,
+    clients: HashMap<DeprecatedRouteClient, DeprecatedRouteCalls>,
+}
+
+/// Deprecated routes of the app and who still calls them
+#[derive(Default)]
+pub struct DeprecationRegistry {
+    routes: Vec<RouteDeprecation>,
+    usage: Mutex<Vec<RouteUsage>>,
+}
+
+impl DeprecationRegistry {
+    pub fn new(routes: Vec<RouteDeprecation>) -> Self {
+        let usage = routes.iter().map(|_| RouteUsage::default()).collect();
+        Self {
+            routes,
+            usage: Mutex::new(usage),
+        }
+    }
+
+    fn find(&self, method: &Method, path: &str) -> Option<usize> {
+        self.routes
+            .iter()
+            .position(|deprecation| deprecation.matches(method, path))
+    }
+
+    /// Count a call of route `index`, returning whether a newly tracked client made it
+    fn record(&self, index: usize, client: DeprecatedRouteClient) -> bool {
+        let now = Utc::now().naive_utc();
+        let mut usage = self.usage.lock().unwrap();
+        let route = &mut usage[index];
+        route.calls += 1;
+        route.last_used_at = Some(now);
+
+        if let Some(calls) = route.clients.get_mut(&client) {
+            calls.calls += 1;
+            calls.last_used_at = now;
+            return false;
+        }
+        if route.clients.len() >= MAX_TRACKED_CLIENTS {
+            return false;
+        }
+        route.clients.insert(
+            client,
+            DeprecatedRouteCalls {
+                calls: 1,
+                last_used_at: now,
+            },
+        );
+        true
+    }
+
+    /// Every deprecated route with its calls, in declaration order
+    pub fn usage(&self) -> Vec<DeprecatedRouteUsage> {
+        let usage = self.usage.lock().unwrap();
+        self.routes
+            .iter()
+            .zip(usage.iter())
+            .map(|(deprecation, route)| {
+                let mut clients: Vec<_> = route
+                    .clients
+                    .iter()
+                    .map(|(client, calls)| (client.clone(), *calls))
+                    .collect();
+                clients.sort_by_key(|(_, usage)| std::cmp::Reverse(usage.calls));
+                DeprecatedRouteUsage {
+                    deprecation: deprecation.clone(),
+                    calls: route.calls,
+                    last_used_at: route.last_used_at,
+                    clients,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Flag responses of deprecated routes, answer retired ones with `410 Gone`, and record
+/// which clients still call them for `/api/v1/admin/deprecations/`
+pub async fn deprecation_middleware(
+    State(registry): State<Arc<DeprecationRegistry>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(index) = registry.find(req.method(), req.uri().path()) else {
+        return next.run(req).await;
+    };
+    let deprecation = &registry.routes[index];
+
+    let client = DeprecatedRouteClient {
+        client_ip: get_client_ip(req.headers()),
+        user_agent: get_device_info(req.headers()),
+    };
+    if registry.record(index, client.clone()) {
+        tracing::warn!(
+            client_ip = client.client_ip.as_deref().unwrap_or("unknown"),
+            user_agent = client.user_agent.as_deref().unwrap_or("unknown"),
+            "Deprecated route {} {} called by a new client",
+            req.method(),
+            req.uri().path()
+        );
+    } else {
+        tracing::debug!(
+            "Deprecated route {} {} called",
+            req.method(),
+            req.uri().path()
+        );
+    }
+
+    let mut response = if deprecation.is_retired(Utc::now()) {
+        let locale = get_request_locale(&req)
+            .map(|locale| locale.as_str().to_string())
+            .unwrap_or_else(|_| DEFAULT_LOCALE.to_string());
+        let message = match &deprecation.replacement {
+            Some(replacement) => t!(
+                "common.route_retired_with_replacement",
+                replacement = replacement,
+                locale = &locale
+            ),
+            None => t!("common.route_retired", locale = &locale),
+        };
+        ErrorDTO::from_code(ErrorCode::RouteRetired, message.to_string()).into_response()
+    } else {
+        next.run(req).await
+    };
+    deprecation.apply_headers(response.headers_mut());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{HeaderMap, Method};
+    use chrono::{TimeZone, Utc};
+
+    use super::{DeprecatedRouteClient, DeprecationRegistry, RouteDeprecation};
+
+    fn deprecation() -> RouteDeprecation {
+        RouteDeprecation::new(
+            "/api/v1/user/*/",
+            Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+        )
+        .method(Method::GET)
+        .sunset(Utc.with_ymd_and_hms(2030, 6, 30, 23, 59, 59).unwrap())
+        .replacement("/api/v2/user/{id}/")
+    }
+
+    #[test]
+    fn matches_method_and_path_pattern() {
+        let registry = DeprecationRegistry::new(vec![deprecation()]);
+
+        assert_eq!(registry.find(&Method::GET, "/api/v1/user/7/"), Some(0));
+        assert_eq!(registry.find(&Method::DELETE, "/api/v1/user/7/"), None);
+        assert_eq!(registry.find(&Method::GET, "/api/v1/user/"), None);
+    }
+
+    #[test]
+    fn renders_deprecation_sunset_and_link_headers() {
+        let mut headers = HeaderMap::new();
+
+        deprecation().apply_headers(&mut headers);
+
+        assert_eq!(headers["deprecation"], "@1700000000");
+        assert_eq!(headers["sunset"], "Sun, 30 Jun 2030 23:59:59 GMT");
+        assert_eq!(
+            headers["link"],
+            "</api/v2/user/{id}/>; rel=\"successor-version\""
+        );
+    }
+
+    #[test]
+    fn counts_calls_per_client() {
+        let registry = DeprecationRegistry::new(vec![deprecation()]);
+        let client = |ip: &str| DeprecatedRouteClient {
+            client_ip: Some(ip.to_string()),
+            user_agent: None,
+        };
+
+        assert!(registry.record(0, client("10.0.0.1")));
+        assert!(!registry.record(0, client("10.0.0.1")));
+        assert!(registry.record(0, client("10.0.0.2")));
+
+        let usage = &registry.usage()[0];
+        assert_eq!(usage.calls, 3);
+        assert!(usage.last_used_at.is_some());
+        assert_eq!(usage.clients[0].0, client("10.0.0.1"));
+        assert_eq!(usage.clients[0].1.calls, 2);
+        assert_eq!(usage.clients[1].1.calls, 1);
+    }
+}
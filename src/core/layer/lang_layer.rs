@@ -1,20 +1,42 @@
-use axum::{extract::Request, http, http::StatusCode, middleware::Next, response::Response};
+use axum::{extract::Request, http, middleware::Next, response::Response};
 use http::HeaderMap;
 use rust_i18n::t;
 use std::collections::HashMap;
 
-use crate::core::{dto::error_dto::ErrorDTO, translation::language::Language};
+use crate::core::{
+    dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+    translation::locale::{DEFAULT_LOCALE, negotiate, negotiate_accept_language},
+};
 
 #[derive(Clone, Debug)]
-pub struct RequestLocale(String);
+pub struct RequestLocale {
+    locale: String,
+    /// Chosen with the `lang` query parameter, which takes precedence over the user's
+    /// preferred locale; `Accept-Language` and the default don't
+    explicit: bool,
+}
 
 impl RequestLocale {
     pub fn new(locale: impl Into<String>) -> Self {
-        Self(locale.into())
+        Self {
+            locale: locale.into(),
+            explicit: false,
+        }
+    }
+
+    pub fn explicit(locale: impl Into<String>) -> Self {
+        Self {
+            locale: locale.into(),
+            explicit: true,
+        }
     }
 
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.locale
+    }
+
+    pub fn is_explicit(&self) -> bool {
+        self.explicit
     }
 }
 
@@ -27,26 +49,28 @@ pub async fn lang_middleware(mut req: Request, next: Next) -> Result<Response, E
 
 pub fn get_request_locale(req: &Request) -> Result<RequestLocale, ErrorDTO> {
     if let Some(locale) = get_locale_from_query_params(req.uri().query()) {
-        return Ok(RequestLocale::new(locale));
+        return Ok(RequestLocale::explicit(locale));
     }
 
-    let accept_language = get_accept_language(req.headers())?.unwrap_or("en".to_string());
-    let lang = Language::from_accept_language(accept_language.as_str());
-    Ok(RequestLocale::new(lang.to_locale()))
+    let locale = match get_accept_language(req.headers())? {
+        Some(accept_language) => negotiate_accept_language(&accept_language),
+        None => DEFAULT_LOCALE.to_string(),
+    };
+    Ok(RequestLocale::new(locale))
 }
 
 pub fn get_locale_from_query_params(query: Option<&str>) -> Option<String> {
     let query = query?;
     let params = serde_urlencoded::from_str::<HashMap<String, String>>(query).ok()?;
     let lang = params.get("lang")?;
-    Some(Language::from_accept_language(lang).to_locale().to_string())
+    Some(negotiate(&[lang]))
 }
 
 pub fn get_accept_language(header_map: &HeaderMap) -> Result<Option<String>, ErrorDTO> {
     if let Some(lang_header) = header_map.get("Accept-Language") {
         let lang_str = lang_header.to_str().map_err(|_| {
-            ErrorDTO::new(
-                StatusCode::BAD_REQUEST,
+            ErrorDTO::from_code(
+                ErrorCode::RequestLanguageInvalid,
                 t!("language.invalid_header", locale = "en").to_string(),
             )
         })?;
@@ -107,6 +131,20 @@ mod tests {
 
         let locale = get_request_locale(&request).unwrap();
         assert_eq!(locale.as_str(), "vi");
+        assert!(locale.is_explicit());
+    }
+
+    #[test]
+    fn negotiates_locale_from_accept_language_header() {
+        let request = Request::builder()
+            .uri("/test")
+            .header("Accept-Language", "fr-FR,vi-VN;q=0.8,en;q=0.5")
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let locale = get_request_locale(&request).unwrap();
+        assert_eq!(locale.as_str(), "vi");
+        assert!(!locale.is_explicit());
     }
 
     #[tokio::test]
@@ -0,0 +1,237 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderValue, header::RETRY_AFTER},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use rust_i18n::t;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::setting::LoadShedSetting,
+    core::{
+        api::route::matches_path_pattern,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+        layer::lang_layer::get_request_locale,
+        translation::locale::DEFAULT_LOCALE,
+    },
+};
+
+/// Requests rejected by every `LoadShedder` of this process, for `shed_requests`
+static SHED: AtomicU64 = AtomicU64::new(0);
+
+/// Requests rejected with `503` since the process started
+pub fn shed_requests() -> u64 {
+    SHED.load(Ordering::Relaxed)
+}
+
+/// Adaptive limit of the requests served at once. The limit shrinks by a tenth, at most
+/// once per latency target, while the smoothed latency is above the target, and grows by
+/// one with every faster response, between the configured bounds.
+pub struct LoadShedder {
+    setting: LoadShedSetting,
+    started_at: Instant,
+    in_flight: AtomicUsize,
+    limit: AtomicUsize,
+    /// Exponentially weighted moving average of response times, in microseconds
+    latency_us: AtomicU64,
+    /// Microseconds after `started_at` of the last decrease of `limit`, 0 before the first
+    decreased_at_us: AtomicU64,
+}
+
+impl LoadShedder {
+    pub fn new(setting: &LoadShedSetting) -> Self {
+        Self {
+            setting: setting.clone(),
+            started_at: Instant::now(),
+            in_flight: AtomicUsize::new(0),
+            limit: AtomicUsize::new(setting.max_in_flight),
+            latency_us: AtomicU64::new(0),
+            decreased_at_us: AtomicU64::new(0),
+        }
+    }
+
+    /// Requests currently served, exempt routes aside
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Requests that may currently be served at once
+    pub fn limit(&self) -> usize {
+        self.limit.load(Ordering::Relaxed)
+    }
+
+    /// Take a slot for a request, `None` when the service is saturated
+    fn acquire(self: &Arc<Self>) -> Option<InFlight> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::AcqRel) + 1;
+        if in_flight > self.limit() {
+            self.in_flight.fetch_sub(1, Ordering::AcqRel);
+            return None;
+        }
+
+        Some(InFlight {
+            shedder: self.clone(),
+            started_at: Instant::now(),
+        })
+    }
+
+    fn record(&self, latency: Duration) {
+        let sample = latency.as_micros() as u64;
+        let previous = self.latency_us.load(Ordering::Relaxed);
+        let smoothed = if previous == 0 {
+            sample
+        } else {
+            (previous * 4 + sample) / 5
+        };
+        self.latency_us.store(smoothed, Ordering::Relaxed);
+
+        let target = Duration::from_millis(self.setting.latency_target_ms);
+        let limit = self.limit();
+        if smoothed > target.as_micros() as u64 {
+            let now = self.started_at.elapsed().as_micros() as u64;
+            let decreased_at = self.decreased_at_us.load(Ordering::Relaxed);
+            if (decreased_at == 0 || now.saturating_sub(decreased_at) >= target.as_micros() as u64)
+                && self
+                    .decreased_at_us
+                    .compare_exchange(
+                        decreased_at,
+                        now.max(1),
+                        Ordering::AcqRel,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+            {
+                let decreased = (limit - (limit / 10).max(1)).max(self.setting.min_in_flight);
+                self.limit.store(decreased, Ordering::Relaxed);
+            }
+        } else if limit < self.setting.max_in_flight {
+            self.limit.store(limit + 1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Slot of a request being served, released when dropped so cancelled requests free it too
+struct InFlight {
+    shedder: Arc<LoadShedder>,
+    started_at: Instant,
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.shedder.in_flight.fetch_sub(1, Ordering::AcqRel);
+        self.shedder.record(self.started_at.elapsed());
+    }
+}
+
+/// Reject requests with `503` while the service is saturated. Routes listed in
+/// `LOAD_SHED_EXEMPT_PATHS` bypass the limit and aren't counted towards it.
+pub async fn load_shed_middleware(
+    State(shedder): State<Arc<LoadShedder>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let exempt = !shedder.setting.enabled
+        || shedder
+            .setting
+            .exempt_paths
+            .iter()
+            .any(|pattern| matches_path_pattern(pattern, req.uri().path()));
+    if exempt {
+        return next.run(req).await;
+    }
+
+    let Some(_in_flight) = shedder.acquire() else {
+        SHED.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(
+            "Shed {} {} ({} in flight, limit {})",
+            req.method(),
+            req.uri().path(),
+            shedder.in_flight(),
+            shedder.limit()
+        );
+
+        let locale = get_request_locale(&req)
+            .map(|locale| locale.as_str().to_string())
+            .unwrap_or_else(|_| DEFAULT_LOCALE.to_string());
+        let mut response = ErrorDTO::from_code(
+            ErrorCode::ServiceOverloaded,
+            t!("common.service_overloaded", locale = &locale).to_string(),
+        )
+        .into_response();
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from_static("1"));
+        return response;
+    };
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use super::LoadShedder;
+    use crate::config::setting::LoadShedSetting;
+
+    fn shedder(min_in_flight: usize, max_in_flight: usize) -> Arc<LoadShedder> {
+        Arc::new(LoadShedder::new(&LoadShedSetting {
+            enabled: true,
+            min_in_flight,
+            max_in_flight,
+            latency_target_ms: 100,
+            exempt_paths: Vec::new(),
+        }))
+    }
+
+    #[test]
+    fn rejects_requests_past_the_limit() {
+        let shedder = shedder(1, 2);
+
+        let first = shedder.acquire().unwrap();
+        let _second = shedder.acquire().unwrap();
+        assert!(shedder.acquire().is_none());
+        assert_eq!(shedder.in_flight(), 2);
+
+        drop(first);
+        assert!(shedder.acquire().is_some());
+    }
+
+    #[test]
+    fn adapts_the_limit_to_latency() {
+        let shedder = shedder(2, 20);
+
+        // Slow responses shrink the limit once per latency target
+        shedder.record(Duration::from_millis(500));
+        shedder.record(Duration::from_millis(500));
+        assert_eq!(shedder.limit(), 18);
+
+        std::thread::sleep(Duration::from_millis(100));
+        shedder.record(Duration::from_millis(500));
+        assert_eq!(shedder.limit(), 17);
+
+        // Fast responses grow it back once the average recovers, up to the maximum
+        for _ in 0..50 {
+            shedder.record(Duration::from_millis(1));
+        }
+        assert_eq!(shedder.limit(), 20);
+    }
+
+    #[test]
+    fn never_shrinks_below_the_minimum() {
+        let shedder = shedder(5, 6);
+
+        for _ in 0..5 {
+            shedder.record(Duration::from_secs(1));
+            std::thread::sleep(Duration::from_millis(100));
+        }
+
+        assert_eq!(shedder.limit(), 5);
+    }
+}
@@ -1,6 +1,16 @@
 pub mod auth_layer;
 pub mod cors_layer;
+pub mod deprecation_layer;
 pub mod lang_layer;
+pub mod load_shed_layer;
 pub mod page_size_limit_layer;
+pub mod permission_layer;
+pub mod request_signing_layer;
+pub mod request_stats_layer;
+pub mod response_cache_layer;
+pub mod route_group_layer;
+pub mod service_auth_layer;
+pub mod statement_budget_layer;
+pub mod timeout_layer;
 pub mod trace_layer;
 pub mod transaction_layer;
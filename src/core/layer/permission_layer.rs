@@ -0,0 +1,32 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::core::{context::Context, dto::error_dto::ErrorDTO};
+
+/// Permission a route requires, attached per route with
+/// `from_fn_with_state(RequirePermission("user.delete"), require_permission_middleware)`
+/// as a `route_layer` of its method router
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequirePermission(pub &'static str);
+
+/// Reject the request before its handler runs unless the current user holds the permission.
+/// Runs inside the stack of `protected_api`, whose transaction middleware provides the
+/// [`Context`] carrying the permissions.
+pub async fn require_permission_middleware(
+    State(RequirePermission(permission)): State<RequirePermission>,
+    req: Request,
+    next: Next,
+) -> Result<Response, ErrorDTO> {
+    let context = req.extensions().get::<Context>().ok_or_else(|| {
+        ErrorDTO::map_internal_error(anyhow::anyhow!(
+            "RequirePermission(\"{}\") used on a route without a request context",
+            permission
+        ))
+    })?;
+    context.require_permission(permission)?;
+
+    Ok(next.run(req).await)
+}
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use rust_i18n::t;
+
+use crate::{
+    config::setting::RequestSigningSetting,
+    core::{
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+        layer::lang_layer::get_request_locale,
+        translation::locale::DEFAULT_LOCALE,
+    },
+    pkg::{
+        cache::NonceStore,
+        request_signing::{RequestSignatureError, RequestSigner, SignedRequest},
+    },
+};
+
+/// Largest body of a signed request, which is buffered to be hashed
+pub const MAX_SIGNED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Routes that only accept signed requests, with the store of the nonces already seen
+pub struct RequestSigning {
+    setting: RequestSigningSetting,
+    signer: RequestSigner,
+    nonces: Arc<dyn NonceStore>,
+}
+
+impl RequestSigning {
+    /// `None` unless `REQUEST_SIGNING_SECRET` and `REQUEST_SIGNING_ROUTES` are set
+    pub fn new(setting: &RequestSigningSetting, nonces: Arc<dyn NonceStore>) -> Option<Self> {
+        Some(Self {
+            setting: setting.clone(),
+            signer: setting.signer()?,
+            nonces,
+        })
+    }
+}
+
+/// Reject requests to the route groups of `REQUEST_SIGNING_ROUTES` unless they carry a
+/// valid, recent signature whose nonce wasn't seen before. Other routes pass through.
+pub async fn request_signing_middleware(
+    State(signing): State<Arc<RequestSigning>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, ErrorDTO> {
+    if !signing.setting.applies_to(req.uri().path()) {
+        return Ok(next.run(req).await);
+    }
+
+    let locale = get_request_locale(&req)
+        .map(|locale| locale.as_str().to_string())
+        .unwrap_or_else(|_| DEFAULT_LOCALE.to_string());
+    let (parts, body) = req.into_parts();
+    let body = to_bytes(body, MAX_SIGNED_BODY_BYTES).await.map_err(|_| {
+        ErrorDTO::from_code(
+            ErrorCode::RequestBodyTooLarge,
+            t!(
+                "auth.request_body_too_large",
+                locale = &locale,
+                limit = MAX_SIGNED_BODY_BYTES
+            )
+            .to_string(),
+        )
+    })?;
+
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str())
+        .unwrap_or_else(|| parts.uri.path());
+    let signed = SignedRequest::from_headers(&parts.headers).and_then(|signed| {
+        signing
+            .signer
+            .verify_at(
+                &signed,
+                parts.method.as_str(),
+                path_and_query,
+                &body,
+                chrono::Utc::now().timestamp(),
+            )
+            .map(|_| signed)
+    });
+    let signed = match signed {
+        Ok(signed) => signed,
+        Err(e) => {
+            tracing::warn!(path = %parts.uri.path(), "Rejected signed request: {}", e);
+            let (code, key) = match e {
+                RequestSignatureError::MissingSignature => (
+                    ErrorCode::AuthSignatureMissing,
+                    "auth.request_signature_missing",
+                ),
+                RequestSignatureError::MalformedSignature
+                | RequestSignatureError::InvalidSignature => (
+                    ErrorCode::AuthSignatureInvalid,
+                    "auth.request_signature_invalid",
+                ),
+                RequestSignatureError::Expired => (
+                    ErrorCode::AuthSignatureExpired,
+                    "auth.request_signature_expired",
+                ),
+            };
+            return Err(ErrorDTO::from_code(
+                code,
+                t!(key, locale = &locale).to_string(),
+            ));
+        }
+    };
+
+    // A nonce that can't be recorded could be replayed, so the request is refused
+    match signing
+        .nonces
+        .claim(signed.nonce, signing.signer.nonce_ttl())
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            tracing::warn!(path = %parts.uri.path(), "Rejected replayed signed request");
+            return Err(ErrorDTO::from_code(
+                ErrorCode::AuthRequestReplayed,
+                t!("auth.request_replayed", locale = &locale).to_string(),
+            ));
+        }
+        Err(e) => {
+            tracing::error!("Failed to record request nonce: {:?}", e);
+            return Err(ErrorDTO::from_code(
+                ErrorCode::AuthSignatureUnverifiable,
+                t!("auth.request_signature_unverifiable", locale = &locale).to_string(),
+            ));
+        }
+    }
+
+    Ok(next.run(Request::from_parts(parts, Body::from(body))).await)
+}
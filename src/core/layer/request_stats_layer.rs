@@ -0,0 +1,96 @@
+use axum::{extract::Request, middleware::Next, response::Response};
+use std::{
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+use crate::core::layer::load_shed_layer::shed_requests;
+
+/// Seconds request rates are averaged over
+const RATE_WINDOW_SECONDS: usize = 60;
+
+struct RequestCounters {
+    started_at: Instant,
+    total: AtomicU64,
+    client_errors: AtomicU64,
+    server_errors: AtomicU64,
+    /// Requests per second of uptime, in a ring indexed by second
+    per_second: Mutex<[(u64, u64); RATE_WINDOW_SECONDS]>,
+}
+
+static COUNTERS: LazyLock<RequestCounters> = LazyLock::new(|| RequestCounters {
+    started_at: Instant::now(),
+    total: AtomicU64::new(0),
+    client_errors: AtomicU64::new(0),
+    server_errors: AtomicU64::new(0),
+    per_second: Mutex::new([(0, 0); RATE_WINDOW_SECONDS]),
+});
+
+/// Requests served by this process since it started
+#[derive(Debug, Clone, Copy)]
+pub struct RequestStats {
+    pub uptime_seconds: u64,
+    pub total: u64,
+    pub client_errors: u64,
+    pub server_errors: u64,
+    /// Rejected by the load shedder, also counted as server errors
+    pub shed: u64,
+    /// Average over the last minute
+    pub per_second: f64,
+}
+
+/// Start the uptime clock, so it counts from startup rather than the first request
+pub fn start_clock() {
+    LazyLock::force(&COUNTERS);
+}
+
+/// Count every request by outcome, for `request_stats`
+pub async fn request_stats_middleware(req: Request, next: Next) -> Response {
+    let response = next.run(req).await;
+
+    let counters = &*COUNTERS;
+    counters.total.fetch_add(1, Ordering::Relaxed);
+    if response.status().is_client_error() {
+        counters.client_errors.fetch_add(1, Ordering::Relaxed);
+    } else if response.status().is_server_error() {
+        counters.server_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let second = counters.started_at.elapsed().as_secs();
+    let mut per_second = counters.per_second.lock().unwrap();
+    let bucket = &mut per_second[second as usize % RATE_WINDOW_SECONDS];
+    if bucket.0 != second {
+        *bucket = (second, 0);
+    }
+    bucket.1 += 1;
+
+    response
+}
+
+pub fn request_stats() -> RequestStats {
+    let counters = &*COUNTERS;
+    let uptime = counters.started_at.elapsed().as_secs();
+    let window_start = uptime.saturating_sub(RATE_WINDOW_SECONDS as u64 - 1);
+    let recent: u64 = counters
+        .per_second
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(second, _)| *second >= window_start && *second <= uptime)
+        .map(|(_, count)| count)
+        .sum();
+    // A process younger than the window averages over its lifetime
+    let window = (uptime + 1).min(RATE_WINDOW_SECONDS as u64);
+
+    RequestStats {
+        uptime_seconds: uptime,
+        total: counters.total.load(Ordering::Relaxed),
+        client_errors: counters.client_errors.load(Ordering::Relaxed),
+        server_errors: counters.server_errors.load(Ordering::Relaxed),
+        shed: shed_requests(),
+        per_second: recent as f64 / window as f64,
+    }
+}
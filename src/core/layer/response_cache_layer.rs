@@ -0,0 +1,103 @@
+use axum::{
+    body::{Body, to_bytes},
+    extract::{Request, State},
+    http::{HeaderValue, Method, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Duration;
+
+use crate::config::app::AppState;
+use crate::core::context::Context;
+use crate::user::entity::user;
+
+/// Tag of cached responses built from user records (search, detail and profile)
+pub const USER_CACHE_TAG: &str = "user";
+
+const CACHE_STATUS_HEADER: &str = "x-cache";
+
+/// Serve successful GET responses from the response cache, keyed by route, query and
+/// current user and stored under the tag given with the state.
+/// Routes opt in with `.layer(from_fn_with_state((app_state, tag), response_cache_middleware))`;
+/// use cases that change the underlying data call `invalidate_cached_responses` with the same tag.
+pub async fn response_cache_middleware(
+    State((app_state, tag)): State<(AppState, &'static str)>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(cache) = app_state.response_cache.clone() else {
+        return next.run(req).await;
+    };
+    if req.method() != Method::GET {
+        return next.run(req).await;
+    }
+
+    let user_id = req
+        .extensions()
+        .get::<user::Model>()
+        .map(|user| user.id.to_string())
+        .unwrap_or_else(|| "anonymous".to_string());
+    let route = req
+        .uri()
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let key = format!("{}:{}:{}", tag, user_id, route);
+
+    // A cache that cannot be reached must not fail the request
+    match cache.get(&key).await {
+        Ok(Some(body)) => {
+            return (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, "application/json"),
+                    (header::HeaderName::from_static(CACHE_STATUS_HEADER), "HIT"),
+                ],
+                body,
+            )
+                .into_response();
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read cached response {}: {:?}", key, e),
+    }
+
+    let response = next.run(req).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!("Failed to buffer response for caching: {:?}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let ttl = Duration::from_secs(app_state.setting.response_cache.ttl_seconds);
+    if let Err(e) = cache.set(tag, &key, &body, ttl).await {
+        tracing::warn!("Failed to cache response {}: {:?}", key, e);
+    }
+    parts
+        .headers
+        .insert(CACHE_STATUS_HEADER, HeaderValue::from_static("MISS"));
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Drop every response cached under `tag` once the change to its data is committed, so a
+/// concurrent read can't cache the data from before it again.
+/// Failures are only logged; stale entries still expire after the configured TTL.
+pub fn invalidate_cached_responses(context: &Context, tag: &str) {
+    let Some(cache) = context.response_cache.clone() else {
+        return;
+    };
+    let tag = tag.to_string();
+    context.after_commit(async move {
+        if let Err(e) = cache.invalidate(&tag).await {
+            tracing::warn!("Failed to invalidate cached {} responses: {:?}", tag, e);
+        }
+        Ok(())
+    });
+}
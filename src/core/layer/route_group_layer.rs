@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::core::api::route::RouteGroup;
+
+/// Answer requests to the route groups of `DISABLED_ROUTE_GROUPS` with `404`, as if their
+/// routes weren't registered. Other routes pass through.
+pub async fn route_group_middleware(
+    State(disabled): State<Arc<Vec<RouteGroup>>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let path = req.uri().path();
+    if disabled.iter().any(|group| group.contains(path)) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    next.run(req).await
+}
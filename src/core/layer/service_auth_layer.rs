@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+        layer::lang_layer::get_request_locale,
+        service_account::ServiceAccounts,
+        translation::locale::DEFAULT_LOCALE,
+    },
+    pkg::mtls::ClientIdentity,
+};
+
+/// Authenticate requests of the mTLS listener as the service principal their client
+/// certificate is mapped to by `MTLS_SERVICE_ACCOUNTS`, rejecting unmapped certificates
+/// with `401`. `auth_middleware` then accepts the principal in place of an access token.
+pub async fn service_auth_middleware(
+    State(accounts): State<Arc<ServiceAccounts>>,
+    ConnectInfo(identity): ConnectInfo<ClientIdentity>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, ErrorDTO> {
+    let Some(principal) = accounts.resolve(&identity) else {
+        tracing::warn!(sans = ?identity.sans, "Rejected client certificate of no service account");
+        let locale = get_request_locale(&req)
+            .map(|locale| locale.as_str().to_string())
+            .unwrap_or_else(|_| DEFAULT_LOCALE.to_string());
+        return Err(ErrorDTO::from_code(
+            ErrorCode::AuthServiceAccountUnknown,
+            t!("auth.service_account_unknown", locale = &locale).to_string(),
+        ));
+    };
+
+    req.extensions_mut().insert(principal.clone());
+    Ok(next.run(req).await)
+}
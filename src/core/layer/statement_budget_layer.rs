@@ -0,0 +1,93 @@
+use axum::{
+    extract::{Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use crate::config::setting::StatementBudgetSetting;
+
+/// Header carrying the statement count of responses that went over budget
+pub const STATEMENT_COUNT_HEADER: &str = "x-statement-count";
+
+/// Statements run by the current request and the limit it is held to
+struct RequestStatements {
+    count: AtomicUsize,
+    hard_limit: Option<usize>,
+}
+
+tokio::task_local! {
+    static REQUEST_STATEMENTS: RequestStatements;
+}
+
+/// Metric callback of the database connection, counting the statements of the current
+/// request. Statements run on spawned tasks aren't attributed to it.
+pub fn record_statement(_info: &sea_orm::metric::Info<'_>) {
+    let _ = REQUEST_STATEMENTS.try_with(|statements| {
+        statements.count.fetch_add(1, Ordering::Relaxed);
+    });
+}
+
+/// Statements run so far by the current request, `None` outside the budget middleware
+pub fn statement_count() -> Option<usize> {
+    REQUEST_STATEMENTS
+        .try_with(|statements| statements.count.load(Ordering::Relaxed))
+        .ok()
+}
+
+/// Hard limit the current request went over, whose changes must then be rolled back
+pub fn exceeded_hard_limit() -> Option<usize> {
+    REQUEST_STATEMENTS
+        .try_with(|statements| {
+            statements
+                .hard_limit
+                .filter(|limit| statements.count.load(Ordering::Relaxed) > *limit)
+        })
+        .ok()
+        .flatten()
+}
+
+/// Count the database statements of each request and warn about the ones running more
+/// than `STATEMENT_BUDGET`, usually a query repeated per item (N+1). Their responses are
+/// flagged with `X-Statement-Count`.
+pub async fn statement_budget_middleware(
+    State(setting): State<Arc<StatementBudgetSetting>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if setting.budget == 0 {
+        return next.run(req).await;
+    }
+
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let statements = RequestStatements {
+        count: AtomicUsize::new(0),
+        hard_limit: setting.hard_limit(),
+    };
+
+    REQUEST_STATEMENTS
+        .scope(statements, async move {
+            let mut response = next.run(req).await;
+
+            let count = statement_count().unwrap_or_default();
+            if count > setting.budget {
+                tracing::warn!(
+                    "{} {} ran {} database statements, over the budget of {}",
+                    method,
+                    path,
+                    count,
+                    setting.budget
+                );
+                response
+                    .headers_mut()
+                    .insert(STATEMENT_COUNT_HEADER, HeaderValue::from(count));
+            }
+            response
+        })
+        .await
+}
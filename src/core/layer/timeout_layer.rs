@@ -0,0 +1,68 @@
+use axum::{
+    Json,
+    extract::{Request, State},
+    http::{HeaderValue, header::CONTENT_TYPE},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use rust_i18n::t;
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::{
+    config::setting::RequestTimeoutSetting,
+    core::{
+        dto::error_code::ErrorCode, layer::lang_layer::get_request_locale,
+        translation::locale::DEFAULT_LOCALE,
+    },
+};
+
+/// Answer with `504` once a request takes longer than its timeout (see
+/// `RequestTimeoutSetting::timeout_for`). The handler is dropped, so its database
+/// transaction is rolled back and its connection returned to the pool.
+pub async fn request_timeout_middleware(
+    State(setting): State<Arc<RequestTimeoutSetting>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(timeout) = setting.timeout_for(req.uri().path()) else {
+        return next.run(req).await;
+    };
+
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let locale = get_request_locale(&req)
+        .map(|locale| locale.as_str().to_string())
+        .unwrap_or_else(|_| DEFAULT_LOCALE.to_string());
+
+    match tokio::time::timeout(timeout, next.run(req)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!("{} {} timed out after {:?}", method, path, timeout);
+
+            // RFC 9457 problem details
+            let status = ErrorCode::RequestTimeout.status();
+            let mut response = (
+                status,
+                Json(json!({
+                    "type": "about:blank",
+                    "code": ErrorCode::RequestTimeout,
+                    "title": status.canonical_reason(),
+                    "status": status.as_u16(),
+                    "detail": t!(
+                        "common.request_timeout",
+                        seconds = timeout.as_secs_f64(),
+                        locale = &locale
+                    ),
+                    "instance": path,
+                })),
+            )
+                .into_response();
+            response.headers_mut().insert(
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/problem+json"),
+            );
+            response
+        }
+    }
+}
@@ -2,57 +2,139 @@ use std::backtrace::Backtrace;
 use std::sync::Arc;
 
 use axum::extract::State;
+use axum::http::Method;
 use axum::{extract::Request, middleware::Next, response::Response};
+use rust_i18n::t;
 use sea_orm::TransactionTrait;
 
 use crate::config::app::AppState;
 use crate::core::context::Context;
-use crate::core::dto::error_dto::ErrorDTO;
+use crate::core::dto::{
+    error_code::ErrorCode,
+    error_dto::{ErrorDTO, KeepChanges},
+};
+use crate::core::layer::auth_layer::Impersonator;
 use crate::core::layer::lang_layer::RequestLocale;
+use crate::core::layer::statement_budget_layer::exceeded_hard_limit;
+use crate::core::permission::PermissionSet;
+use crate::core::service_account::ServicePrincipal;
+use crate::core::translation::locale::DEFAULT_LOCALE;
 use crate::user::entity::user;
 
+/// Whether requests with `method` must not change data, so they can skip the transaction
+fn is_read_only(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// Run the request in a transaction committed on success and rolled back otherwise,
+/// unless the error asked to keep its changes (`ErrorDTO::keep_changes`).
+/// Reads (`GET`, `HEAD`, `OPTIONS`) get a read-only context on a pooled connection instead.
+/// Side effects deferred with `Context::after_commit` run once the changes are committed.
+/// Requests over the enforced statement budget fail with `500` and keep nothing.
 pub async fn transaction_middleware(
     State(app_state): State<AppState>,
     mut req: Request,
     next: Next,
 ) -> Result<Response, ErrorDTO> {
-    let txn = app_state.db.begin().await.map_err(|e| {
-        let backtrace = Backtrace::capture();
-        tracing::error!(error = %e, backtrace = %backtrace, "Failed to begin transaction");
-        ErrorDTO::from(e)
-    })?;
+    let txn = if is_read_only(req.method()) {
+        None
+    } else {
+        let txn = app_state.db.begin().await.map_err(|e| {
+            let backtrace = Backtrace::capture();
+            tracing::error!(error = %e, backtrace = %backtrace, "Failed to begin transaction");
+            ErrorDTO::from(e)
+        })?;
+        Some(Arc::new(txn))
+    };
 
-    let txn = Arc::new(txn);
     let current_user = req.extensions().get::<user::Model>().cloned();
+    let impersonator = req.extensions().get::<Impersonator>().copied();
+    let service = req.extensions().get::<ServicePrincipal>().cloned();
+    let permissions = req.extensions().get::<PermissionSet>().cloned();
     let locale = req
         .extensions()
         .get::<RequestLocale>()
         .map(|l| l.as_str().to_string());
-    let mut context_builder = Context::builder(txn.clone());
+    let error_locale = locale.clone().unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+    let mut context_builder = match &txn {
+        Some(txn) => Context::builder(txn.clone()),
+        None => Context::read_only(app_state.db.clone()),
+    };
     if let Some(locale) = locale {
         context_builder = context_builder.locale(locale);
     }
     if let Some(current_user) = current_user {
         context_builder = context_builder.user(current_user);
     }
+    if let Some(Impersonator(admin_id)) = impersonator {
+        context_builder = context_builder.impersonator(admin_id);
+    }
+    if let Some(service) = service {
+        context_builder = context_builder.service(service);
+    }
+    if let Some(permissions) = permissions {
+        context_builder = context_builder.permissions(permissions);
+    }
     if let Some(producer) = app_state.producer.clone() {
         context_builder = context_builder.producer(producer);
     }
+    if let Some(outbox) = app_state.outbox.clone() {
+        context_builder = context_builder.outbox(outbox);
+    }
+    context_builder = context_builder
+        .id_generator(app_state.id_generator.clone())
+        .event_bus(app_state.event_bus.clone());
+    if let Some(response_cache) = app_state.response_cache.clone() {
+        context_builder = context_builder.response_cache(response_cache);
+    }
+    if let Some(task_dedup) = app_state.task_dedup.clone() {
+        context_builder = context_builder.task_dedup(task_dedup);
+    }
+    if let Some(task_quota) = app_state.task_quota.clone() {
+        context_builder = context_builder.task_quota(task_quota);
+    }
+    if let Some(login_attempts) = app_state.login_attempts.clone() {
+        context_builder = context_builder.login_attempts(login_attempts);
+    }
     let context = context_builder.build();
+    let deferred = context.deferred_effects();
 
     req.extensions_mut().insert(context);
 
     let response = next.run(req).await;
+    let over_budget = exceeded_hard_limit().map(|limit| {
+        ErrorDTO::from_code(
+            ErrorCode::StatementBudgetExceeded,
+            t!(
+                "common.statement_budget_exceeded",
+                limit = limit,
+                locale = &error_locale
+            )
+            .to_string(),
+        )
+    });
+    let keep_changes = over_budget.is_none()
+        && (response.status().is_success()
+            || response.status().is_redirection()
+            || response.extensions().get::<KeepChanges>().is_some());
 
+    let Some(txn) = txn else {
+        if keep_changes {
+            deferred.committed().await;
+        }
+        return over_budget.map_or(Ok(response), Err);
+    };
     match Arc::try_unwrap(txn) {
         Ok(txn) => {
-            if response.status().is_success() || response.status().is_redirection() {
+            if keep_changes {
                 txn.commit().await.map_err(|e| {
                     let backtrace = Backtrace::capture();
                     tracing::error!(error = %e, backtrace = %backtrace, "Transaction commit error");
                     ErrorDTO::from(e)
                 })?;
+                deferred.committed().await;
             } else {
+                deferred.rolled_back();
                 txn.rollback().await.map_err(|e| {
                     let backtrace = Backtrace::capture();
                     tracing::error!(error = %e, backtrace = %backtrace, "Transaction rollback error");
@@ -61,9 +143,10 @@ pub async fn transaction_middleware(
             }
         }
         Err(_) => {
+            deferred.rolled_back();
             tracing::error!("Transaction Arc still has multiple references, will auto-rollback");
         }
     }
 
-    Ok(response)
+    over_budget.map_or(Ok(response), Err)
 }
@@ -0,0 +1,74 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use tokio::time::Instant;
+use tracing::{error, info};
+
+use crate::config::app::AppState;
+
+/// Work run once when the server starts or stops, e.g. warming caches, preloading templates
+/// or flushing buffers. Register with `AppBuilder::on_startup` / `AppBuilder::on_shutdown`
+/// or a module's `startup_hooks` / `shutdown_hooks`.
+#[async_trait]
+pub trait LifecycleHook: Send + Sync {
+    /// Identifier used in logs and errors
+    fn name(&self) -> &'static str;
+
+    /// Hooks run in ascending order; hooks with the same order run in registration order
+    fn order(&self) -> i32 {
+        0
+    }
+
+    /// How long the hook may run before it is abandoned
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(30)
+    }
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()>;
+}
+
+/// Hooks sorted by `LifecycleHook::order`, keeping registration order for ties
+pub fn ordered(mut hooks: Vec<Arc<dyn LifecycleHook>>) -> Vec<Arc<dyn LifecycleHook>> {
+    hooks.sort_by_key(|hook| hook.order());
+    hooks
+}
+
+/// Run startup hooks one after another; the first failure or timeout aborts startup
+pub async fn run_startup_hooks(
+    hooks: &[Arc<dyn LifecycleHook>],
+    app_state: &AppState,
+) -> anyhow::Result<()> {
+    for hook in hooks {
+        run_hook(hook.as_ref(), app_state)
+            .await
+            .map_err(|e| e.context(format!("Startup hook {} failed", hook.name())))?;
+    }
+    Ok(())
+}
+
+/// Run every shutdown hook, logging failures and timeouts so one stuck hook doesn't keep the
+/// others from flushing
+pub async fn run_shutdown_hooks(hooks: &[Arc<dyn LifecycleHook>], app_state: &AppState) {
+    for hook in hooks {
+        if let Err(e) = run_hook(hook.as_ref(), app_state).await {
+            error!("Shutdown hook {} failed: {:?}", hook.name(), e);
+        }
+    }
+}
+
+async fn run_hook(hook: &dyn LifecycleHook, app_state: &AppState) -> anyhow::Result<()> {
+    let started_at = Instant::now();
+    match tokio::time::timeout(hook.timeout(), hook.run(app_state)).await {
+        Ok(result) => {
+            result?;
+            info!(
+                "✓ Lifecycle hook {} finished in {:?}",
+                hook.name(),
+                started_at.elapsed()
+            );
+            Ok(())
+        }
+        Err(_) => Err(anyhow!("timed out after {:?}", hook.timeout())),
+    }
+}
@@ -1,9 +1,21 @@
 pub mod api;
 pub mod r#async;
+pub mod audit;
 pub mod context;
 pub mod db;
 pub mod dto;
+pub mod event;
+pub mod event_type;
+pub mod export;
+pub mod fixture;
+pub mod id;
 pub mod layer;
+pub mod lifecycle;
+pub mod module;
+pub mod permission;
+pub mod policy;
 pub mod runbook;
+pub mod service_account;
 pub mod template;
 pub mod translation;
+pub mod validation;
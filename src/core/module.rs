@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use axum::Router;
+use migration::MigrationTrait;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    config::app::AppState,
+    core::{
+        r#async::{PeriodicJob, RoutedTask, TaskRegistry, TaskType},
+        event::EventSubscriber,
+        layer::deprecation_layer::RouteDeprecation,
+        lifecycle::LifecycleHook,
+    },
+    pkg::broadcast::schema::{MessageDirection, register_schema},
+};
+
+/// A bounded context plugged into the app with `AppBuilder::module`.
+/// Every hook is optional so a module only implements what it contributes.
+pub trait Module: Send + Sync {
+    /// Identifier used in logs
+    fn name(&self) -> &'static str;
+
+    /// Routes served alongside the built-in API, with middleware applied by the module
+    fn routes(&self, _app_state: &AppState) -> Router<AppState> {
+        Router::new()
+    }
+
+    /// Schema migrations applied after the core migrations by `my-axum migrate`
+    fn migrations(&self) -> Vec<Box<dyn MigrationTrait>> {
+        Vec::new()
+    }
+
+    /// Register worker handlers for task types defined by the module
+    fn register_tasks(&self, _registry: &mut TaskRegistry) {}
+
+    /// Tasks the worker publishes on a cron schedule
+    fn scheduled_jobs(&self) -> Vec<ScheduledJob> {
+        Vec::new()
+    }
+
+    /// Jobs the server runs in-process on a configurable interval
+    fn periodic_jobs(&self) -> Vec<Arc<dyn PeriodicJob>> {
+        Vec::new()
+    }
+
+    /// Subscribers of the domain events use cases emit
+    fn event_subscribers(&self) -> Vec<Arc<dyn EventSubscriber>> {
+        Vec::new()
+    }
+
+    /// JSON Schemas of the broadcasts the module publishes and the WebSocket commands it accepts
+    fn message_schemas(&self) -> Vec<MessageSchema> {
+        Vec::new()
+    }
+
+    /// Routes being retired, flagged with deprecation headers until their sunset
+    fn deprecated_routes(&self) -> Vec<RouteDeprecation> {
+        Vec::new()
+    }
+
+    /// Hooks the server runs before accepting connections, e.g. to warm caches
+    fn startup_hooks(&self) -> Vec<Arc<dyn LifecycleHook>> {
+        Vec::new()
+    }
+
+    /// Hooks the server runs after connections drain, e.g. to flush buffers
+    fn shutdown_hooks(&self) -> Vec<Arc<dyn LifecycleHook>> {
+        Vec::new()
+    }
+}
+
+/// A task published on a cron schedule (`sec min hour day month weekday`)
+#[derive(Debug, Clone)]
+pub struct ScheduledJob {
+    pub name: &'static str,
+    pub schedule: String,
+    pub task: RoutedTask,
+}
+
+impl ScheduledJob {
+    pub fn new(name: &'static str, schedule: impl Into<String>, task: TaskType) -> Self {
+        Self {
+            name,
+            schedule: schedule.into(),
+            task: RoutedTask::Builtin(task),
+        }
+    }
+
+    /// Schedule a task type registered through `Module::register_tasks`
+    pub fn custom(
+        name: &'static str,
+        schedule: impl Into<String>,
+        task: impl Serialize,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            name,
+            schedule: schedule.into(),
+            task: RoutedTask::Custom(serde_json::to_value(task)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::ScheduledJob;
+    use crate::core::r#async::{RoutedTask, TaskType};
+
+    #[derive(Serialize)]
+    #[serde(tag = "type")]
+    enum ReportTask {
+        BuildReport,
+    }
+
+    #[test]
+    fn creates_builtin_and_custom_jobs() {
+        let builtin = ScheduledJob::new(
+            "cleanup-expired-tokens",
+            "0 0 * * * *",
+            TaskType::CleanupExpiredToken,
+        );
+        assert!(matches!(
+            builtin.task,
+            RoutedTask::Builtin(TaskType::CleanupExpiredToken)
+        ));
+
+        let custom =
+            ScheduledJob::custom("build-report", "0 0 1 * * *", ReportTask::BuildReport).unwrap();
+        assert!(matches!(
+            custom.task,
+            RoutedTask::Custom(value) if value["type"] == "BuildReport"
+        ));
+    }
+}
+
+/// JSON Schema of a broadcast's `data` (`Outbound`, keyed by event type) or of a WebSocket
+/// command (`Inbound`, keyed by action)
+#[derive(Debug, Clone)]
+pub struct MessageSchema {
+    pub direction: MessageDirection,
+    pub name: &'static str,
+    pub schema: Value,
+}
+
+impl MessageSchema {
+    pub fn outbound(event_type: &'static str, schema: Value) -> Self {
+        Self {
+            direction: MessageDirection::Outbound,
+            name: event_type,
+            schema,
+        }
+    }
+
+    pub fn inbound(action: &'static str, schema: Value) -> Self {
+        Self {
+            direction: MessageDirection::Inbound,
+            name: action,
+            schema,
+        }
+    }
+
+    /// Validate messages against this schema from now on
+    pub fn register(&self) -> anyhow::Result<()> {
+        register_schema(self.direction, self.name, &self.schema)
+    }
+}
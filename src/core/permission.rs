@@ -0,0 +1,32 @@
+use std::collections::BTreeSet;
+
+/// Permissions granted to the current user by their roles, such as `user.delete`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionSet {
+    permissions: BTreeSet<String>,
+}
+
+impl PermissionSet {
+    pub fn contains(&self, permission: &str) -> bool {
+        self.permissions.contains(permission)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.permissions.iter().map(String::as_str)
+    }
+
+    /// The same permissions less `withheld`
+    pub fn without(mut self, withheld: &[&str]) -> Self {
+        self.permissions
+            .retain(|permission| !withheld.contains(&permission.as_str()));
+        self
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for PermissionSet {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self {
+            permissions: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
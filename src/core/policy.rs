@@ -0,0 +1,102 @@
+use crate::user::entity::{sea_orm_active_enums::UserRole, user};
+
+/// What a user tries to do with a resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    List,
+    Create,
+    Read,
+    Update,
+    Delete,
+}
+
+impl Action {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Action::List => "list",
+            Action::Create => "create",
+            Action::Read => "read",
+            Action::Update => "update",
+            Action::Delete => "delete",
+        }
+    }
+}
+
+/// Kind of resource rules are declared for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    User,
+}
+
+impl ResourceKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResourceKind::User => "user",
+        }
+    }
+}
+
+/// Resource an action targets, with the attributes rules are evaluated against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resource {
+    pub kind: ResourceKind,
+    /// User the resource belongs to, `None` for collections
+    pub owner_id: Option<i32>,
+}
+
+impl Resource {
+    /// Every user, for listing and creating users
+    pub fn users() -> Self {
+        Self {
+            kind: ResourceKind::User,
+            owner_id: None,
+        }
+    }
+
+    /// The record of user `id`, which that user owns
+    pub fn user(id: i32) -> Self {
+        Self {
+            kind: ResourceKind::User,
+            owner_id: Some(id),
+        }
+    }
+}
+
+/// Who may perform an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    Admin,
+    OwnerOrAdmin,
+}
+
+impl Rule {
+    pub fn allows(self, user: &user::Model, resource: &Resource) -> bool {
+        let is_admin = user.role == UserRole::Admin;
+        match self {
+            Rule::Admin => is_admin,
+            Rule::OwnerOrAdmin => is_admin || resource.owner_id == Some(user.id),
+        }
+    }
+}
+
+/// Rule of every action on every kind of resource; anything not listed is denied
+const POLICY: &[(ResourceKind, Action, Rule)] = &[
+    (ResourceKind::User, Action::List, Rule::Admin),
+    (ResourceKind::User, Action::Create, Rule::Admin),
+    (ResourceKind::User, Action::Read, Rule::OwnerOrAdmin),
+    (ResourceKind::User, Action::Update, Rule::OwnerOrAdmin),
+    (ResourceKind::User, Action::Delete, Rule::OwnerOrAdmin),
+];
+
+/// Rule declared for `action` on resources of `kind`
+pub fn rule_for(kind: ResourceKind, action: Action) -> Option<Rule> {
+    POLICY
+        .iter()
+        .find(|(rule_kind, rule_action, _)| *rule_kind == kind && *rule_action == action)
+        .map(|(_, _, rule)| *rule)
+}
+
+/// Permission granting `action` on every resource of `kind` whatever the rule, e.g. `user.delete`
+pub fn permission_for(kind: ResourceKind, action: Action) -> String {
+    format!("{}.{}", kind.as_str(), action.as_str())
+}
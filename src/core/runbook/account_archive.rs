@@ -0,0 +1,372 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::http::StatusCode;
+use chrono::NaiveDateTime;
+use sea_orm::{TransactionTrait, entity::*};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    config::setting::Setting,
+    core::{context::Context, db::connection::get_db},
+    file::{
+        entity::{
+            file,
+            sea_orm_active_enums::{FileStatus, ModerationStatus},
+        },
+        repository::file_repository,
+    },
+    notification::{
+        entity::{notification_preference, sea_orm_active_enums::NotificationCategory},
+        repository::notification_preference_repository,
+    },
+    user::{
+        entity::{sea_orm_active_enums::UserRole, user},
+        repository::user_repository,
+    },
+};
+
+use super::RunbookError;
+
+/// Version written to new archives; archives of a newer version are refused
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// A user with their preferences and file metadata, portable between environments.
+/// Ids are those of the source environment and are remapped on import; stored file
+/// objects aren't included and must be copied separately.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountArchive {
+    pub version: u32,
+    pub exported_at: NaiveDateTime,
+    pub user: ArchivedUser,
+    pub notification_preferences: Vec<ArchivedPreference>,
+    pub files: Vec<ArchivedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedUser {
+    pub id: i32,
+    pub email: String,
+    /// Password hash, so the user signs in with the same password
+    pub password: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub phone: Option<String>,
+    pub phone_verified_at: Option<NaiveDateTime>,
+    pub role: UserRole,
+    pub locale: Option<String>,
+    pub timezone: Option<String>,
+    pub deactivated_at: Option<NaiveDateTime>,
+    /// Source id of the avatar, one of `files`
+    pub avatar_file_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedPreference {
+    pub category: NotificationCategory,
+    pub channels: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedFile {
+    pub id: i32,
+    pub key: String,
+    pub name: String,
+    pub content_type: Option<String>,
+    pub size: i64,
+    pub status: FileStatus,
+    pub variants: Option<Value>,
+    pub moderation_status: Option<ModerationStatus>,
+    pub rejection_reason: Option<String>,
+    pub moderated_at: Option<NaiveDateTime>,
+}
+
+/// What to do when the archived email already belongs to a user of the target environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportConflict {
+    /// Refuse the import
+    Fail,
+    /// Leave the existing user untouched
+    Skip,
+    /// Update the existing user's profile and preferences and add the missing files,
+    /// keeping their password and role
+    Merge,
+}
+
+impl std::str::FromStr for ImportConflict {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "fail" => Ok(Self::Fail),
+            "skip" => Ok(Self::Skip),
+            "merge" => Ok(Self::Merge),
+            _ => Err(format!(
+                "Unknown conflict mode '{value}', expected fail, skip or merge"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportOutcome {
+    Created {
+        user_id: i32,
+        preferences: usize,
+        files: usize,
+        skipped_files: usize,
+    },
+    Merged {
+        user_id: i32,
+        preferences: usize,
+        files: usize,
+        skipped_files: usize,
+    },
+    /// The email belongs to an existing user and the conflict mode is `Skip`
+    Skipped { user_id: i32 },
+}
+
+/// Archive of the user with `email`
+pub async fn export_account(
+    setting: &Setting,
+    email: &str,
+) -> Result<AccountArchive, RunbookError> {
+    let db = get_db(&setting.database_url)
+        .await
+        .map_err(RunbookError::internal_error)?;
+    let context = Context::read_only(db.clone()).build();
+
+    let user = user_repository::find_by_email(&context, email)
+        .await
+        .map_err(RunbookError::internal_error)?
+        .ok_or_else(|| RunbookError::not_found(format!("User not found for email: {email}")))?;
+    let preferences = notification_preference_repository::find_by_user_id(&context, user.id)
+        .await
+        .map_err(RunbookError::internal_error)?;
+    let mut files = file_repository::find_by_user_id(&context, user.id)
+        .await
+        .map_err(RunbookError::internal_error)?;
+    files.sort_by_key(|file| file.id);
+    db.close().await.map_err(RunbookError::internal_error)?;
+
+    Ok(AccountArchive {
+        version: ARCHIVE_VERSION,
+        exported_at: chrono::Utc::now().naive_utc(),
+        user: ArchivedUser {
+            id: user.id,
+            email: user.email,
+            password: user.password,
+            first_name: user.first_name,
+            last_name: user.last_name,
+            phone: user.phone,
+            phone_verified_at: user.phone_verified_at,
+            role: user.role,
+            locale: user.locale,
+            timezone: user.timezone,
+            deactivated_at: user.deactivated_at,
+            avatar_file_id: user.avatar_file_id,
+        },
+        notification_preferences: preferences
+            .into_iter()
+            .map(|preference| ArchivedPreference {
+                category: preference.category,
+                channels: preference.channels,
+            })
+            .collect(),
+        files: files
+            .into_iter()
+            .map(|file| ArchivedFile {
+                id: file.id,
+                key: file.key,
+                name: file.name,
+                content_type: file.content_type,
+                size: file.size,
+                status: file.status,
+                variants: file.variants,
+                moderation_status: file.moderation_status,
+                rejection_reason: file.rejection_reason,
+                moderated_at: file.moderated_at,
+            })
+            .collect(),
+    })
+}
+
+/// Import `archive` in one transaction, as `email` when given instead of the archived one.
+/// Users and files get ids of this environment; a file whose key belongs to another user
+/// here is skipped. Who moderated a file isn't carried over, as their id has no meaning here.
+pub async fn import_account(
+    setting: &Setting,
+    archive: &AccountArchive,
+    email: Option<&str>,
+    conflict: ImportConflict,
+) -> Result<ImportOutcome, RunbookError> {
+    if archive.version > ARCHIVE_VERSION {
+        return Err(RunbookError::bad_request(format!(
+            "Unsupported archive version {}, this environment reads up to {ARCHIVE_VERSION}",
+            archive.version
+        )));
+    }
+
+    let db = get_db(&setting.database_url)
+        .await
+        .map_err(RunbookError::internal_error)?;
+    let txn = db.begin().await.map_err(RunbookError::internal_error)?;
+    let context = Context::builder(Arc::new(txn)).build();
+    let email = email.unwrap_or(&archive.user.email);
+
+    let existing = user_repository::find_by_email(&context, email)
+        .await
+        .map_err(RunbookError::internal_error)?;
+    let outcome = match (existing, conflict) {
+        (Some(existing), ImportConflict::Fail) => Err(RunbookError::new(
+            StatusCode::CONFLICT,
+            format!(
+                "User '{}' already exists, pass --on-conflict skip or merge",
+                existing.email
+            ),
+        )),
+        (Some(existing), ImportConflict::Skip) => Ok(ImportOutcome::Skipped {
+            user_id: existing.id,
+        }),
+        (existing, _) => import_into(&context, archive, email, existing)
+            .await
+            .map_err(RunbookError::internal_error),
+    };
+
+    if outcome.is_ok() {
+        context
+            .commit()
+            .await
+            .map_err(RunbookError::internal_error)?;
+    } else {
+        context
+            .rollback()
+            .await
+            .map_err(RunbookError::internal_error)?;
+    }
+    db.close().await.map_err(RunbookError::internal_error)?;
+
+    outcome
+}
+
+/// Create the user, or merge into `existing`, with their preferences and files
+async fn import_into(
+    context: &Context,
+    archive: &AccountArchive,
+    email: &str,
+    existing: Option<user::Model>,
+) -> Result<ImportOutcome, sea_orm::DbErr> {
+    let archived = &archive.user;
+    let (user, merged) = match existing {
+        Some(existing) => {
+            let mut user: user::ActiveModel = existing.into();
+            set_profile(&mut user, archived);
+            (user_repository::update(context, user).await?, true)
+        }
+        None => {
+            let mut user = user::ActiveModel {
+                email: Set(email.to_string()),
+                password: Set(archived.password.clone()),
+                role: Set(archived.role.clone()),
+                ..Default::default()
+            };
+            set_profile(&mut user, archived);
+            (user_repository::create(context, user).await?, false)
+        }
+    };
+
+    for preference in &archive.notification_preferences {
+        match notification_preference_repository::find_by_user_and_category(
+            context,
+            user.id,
+            preference.category,
+        )
+        .await?
+        {
+            Some(existing) => {
+                let mut existing: notification_preference::ActiveModel = existing.into();
+                existing.channels = Set(preference.channels.clone());
+                notification_preference_repository::update(context, existing).await?;
+            }
+            None => {
+                notification_preference_repository::create(
+                    context,
+                    notification_preference::ActiveModel {
+                        user_id: Set(user.id),
+                        category: Set(preference.category),
+                        channels: Set(preference.channels.clone()),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            }
+        }
+    }
+
+    // Source file id -> id here
+    let mut file_ids = HashMap::new();
+    let mut skipped_files = 0;
+    for archived_file in &archive.files {
+        match file_repository::find_by_key(context, &archived_file.key).await? {
+            Some(existing) if existing.user_id == user.id => {
+                file_ids.insert(archived_file.id, existing.id);
+            }
+            Some(_) => skipped_files += 1,
+            None => {
+                let created = file_repository::create(
+                    context,
+                    file::ActiveModel {
+                        user_id: Set(user.id),
+                        key: Set(archived_file.key.clone()),
+                        name: Set(archived_file.name.clone()),
+                        content_type: Set(archived_file.content_type.clone()),
+                        size: Set(archived_file.size),
+                        status: Set(archived_file.status.clone()),
+                        variants: Set(archived_file.variants.clone()),
+                        moderation_status: Set(archived_file.moderation_status.clone()),
+                        rejection_reason: Set(archived_file.rejection_reason.clone()),
+                        moderated_at: Set(archived_file.moderated_at),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+                file_ids.insert(archived_file.id, created.id);
+            }
+        }
+    }
+
+    if let Some(avatar_file_id) = archived
+        .avatar_file_id
+        .and_then(|id| file_ids.get(&id).copied())
+    {
+        user_repository::set_avatar_file_id(context, user.id, Some(avatar_file_id)).await?;
+    }
+
+    let preferences = archive.notification_preferences.len();
+    let files = file_ids.len();
+    Ok(if merged {
+        ImportOutcome::Merged {
+            user_id: user.id,
+            preferences,
+            files,
+            skipped_files,
+        }
+    } else {
+        ImportOutcome::Created {
+            user_id: user.id,
+            preferences,
+            files,
+            skipped_files,
+        }
+    })
+}
+
+fn set_profile(user: &mut user::ActiveModel, archived: &ArchivedUser) {
+    user.first_name = Set(archived.first_name.clone());
+    user.last_name = Set(archived.last_name.clone());
+    user.phone = Set(archived.phone.clone());
+    user.phone_verified_at = Set(archived.phone_verified_at);
+    user.locale = Set(archived.locale.clone());
+    user.timezone = Set(archived.timezone.clone());
+    user.deactivated_at = Set(archived.deactivated_at);
+}
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use sea_orm::{TransactionTrait, entity::*};
+use std::sync::Arc;
+
+use crate::{
+    config::setting::Setting,
+    core::{context::Context, db::connection::get_db},
+    user::{
+        entity::{sea_orm_active_enums::UserRole, user},
+        repository::user_repository,
+        service::auth_service,
+    },
+};
+
+use super::{Runbook, RunbookError, RunbookExecutionResult, RunbookMetadata};
+
+const RUNBOOK_NAME: &str = "create-admin";
+
+pub struct CreateAdmin;
+
+#[async_trait]
+impl Runbook for CreateAdmin {
+    fn metadata(&self) -> RunbookMetadata {
+        RunbookMetadata {
+            name: RUNBOOK_NAME,
+            description: "Create an admin user, or reset the password and admin role of an existing one",
+            usage: "runbook run create-admin --email admin@example.com --password <password>",
+        }
+    }
+
+    async fn run(
+        &self,
+        setting: &Setting,
+        args: &[String],
+    ) -> Result<RunbookExecutionResult, RunbookError> {
+        let email = parse_arg(args, "--email")?;
+        let password = parse_arg(args, "--password")?;
+
+        let (admin, created) = create_admin(setting, &email, &password)
+            .await
+            .map_err(RunbookError::internal_error)?;
+
+        let message = if created {
+            format!("Created admin user '{}'", admin.email)
+        } else {
+            format!("Updated admin user '{}'", admin.email)
+        };
+        Ok(RunbookExecutionResult::new(RUNBOOK_NAME, message))
+    }
+}
+
+fn parse_arg(args: &[String], name: &str) -> Result<String, RunbookError> {
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg == name {
+            let value = args
+                .next()
+                .ok_or_else(|| RunbookError::bad_request(format!("Missing value for {name}")))?;
+            return Ok(value.to_string());
+        }
+    }
+
+    Err(RunbookError::bad_request(format!(
+        "Missing required argument: {name}"
+    )))
+}
+
+/// Ensure `email` belongs to an admin with `password`, returning the user and whether it was
+/// newly created. Running it again with the same arguments leaves the same end state.
+pub async fn create_admin(
+    setting: &Setting,
+    email: &str,
+    password: &str,
+) -> anyhow::Result<(user::Model, bool)> {
+    let db = get_db(&setting.database_url).await?;
+    let txn = Arc::new(db.begin().await?);
+    let context = Context::builder(txn).build();
+    let password = auth_service::hash_password(password).await?;
+
+    let (admin, created) = match user_repository::find_by_email(&context, email).await? {
+        Some(existing) => {
+            let mut admin: user::ActiveModel = existing.into();
+            admin.password = Set(password);
+            admin.role = Set(UserRole::Admin);
+            (user_repository::update(&context, admin).await?, false)
+        }
+        None => {
+            let admin = user_repository::create(
+                &context,
+                user::ActiveModel {
+                    email: Set(email.to_string()),
+                    password: Set(password),
+                    role: Set(UserRole::Admin),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            (admin, true)
+        }
+    };
+    context.commit().await?;
+    db.close().await?;
+
+    Ok((admin, created))
+}
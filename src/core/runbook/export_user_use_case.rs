@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+
+use crate::config::setting::Setting;
+
+use super::{
+    Runbook, RunbookError, RunbookExecutionResult, RunbookMetadata, account_archive::export_account,
+};
+
+const RUNBOOK_NAME: &str = "export-user";
+
+pub struct ExportUser;
+
+#[async_trait]
+impl Runbook for ExportUser {
+    fn metadata(&self) -> RunbookMetadata {
+        RunbookMetadata {
+            name: RUNBOOK_NAME,
+            description: "Export a user with their preferences and file metadata as a JSON archive, written to --output or returned as the message",
+            usage: "runbook run export-user --email user@example.com [--output user.json]",
+        }
+    }
+
+    async fn run(
+        &self,
+        setting: &Setting,
+        args: &[String],
+    ) -> Result<RunbookExecutionResult, RunbookError> {
+        let email = parse_arg(args, "--email")?
+            .ok_or_else(|| RunbookError::bad_request("Missing required argument: --email"))?;
+        let output = parse_arg(args, "--output")?;
+
+        let archive = export_account(setting, &email).await?;
+        let json = serde_json::to_string_pretty(&archive).map_err(RunbookError::internal_error)?;
+
+        let message = match output {
+            Some(path) => {
+                tokio::fs::write(&path, json)
+                    .await
+                    .map_err(RunbookError::internal_error)?;
+                format!(
+                    "Exported user '{}' with {} preference(s) and {} file(s) to {}",
+                    archive.user.email,
+                    archive.notification_preferences.len(),
+                    archive.files.len(),
+                    path
+                )
+            }
+            None => json,
+        };
+        Ok(RunbookExecutionResult::new(RUNBOOK_NAME, message))
+    }
+}
+
+fn parse_arg(args: &[String], name: &str) -> Result<Option<String>, RunbookError> {
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg == name {
+            let value = args
+                .next()
+                .ok_or_else(|| RunbookError::bad_request(format!("Missing value for {name}")))?;
+            return Ok(Some(value.to_string()));
+        }
+    }
+
+    Ok(None)
+}
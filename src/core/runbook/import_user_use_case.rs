@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+
+use crate::config::setting::Setting;
+
+use super::{
+    Runbook, RunbookError, RunbookExecutionResult, RunbookMetadata,
+    account_archive::{AccountArchive, ImportConflict, ImportOutcome, import_account},
+};
+
+const RUNBOOK_NAME: &str = "import-user";
+
+pub struct ImportUser;
+
+#[async_trait]
+impl Runbook for ImportUser {
+    fn metadata(&self) -> RunbookMetadata {
+        RunbookMetadata {
+            name: RUNBOOK_NAME,
+            description: "Import a user archived by export-user, optionally under another email; an existing user with the email fails the import unless --on-conflict is skip or merge",
+            usage: "runbook run import-user (--input user.json | --archive <json>) [--email user@example.com] [--on-conflict fail|skip|merge]",
+        }
+    }
+
+    async fn run(
+        &self,
+        setting: &Setting,
+        args: &[String],
+    ) -> Result<RunbookExecutionResult, RunbookError> {
+        let json = match (parse_arg(args, "--input")?, parse_arg(args, "--archive")?) {
+            (Some(path), None) => tokio::fs::read_to_string(&path).await.map_err(|e| {
+                RunbookError::bad_request(format!("Failed to read archive {path}: {e}"))
+            })?,
+            (None, Some(json)) => json,
+            _ => {
+                return Err(RunbookError::bad_request(
+                    "Pass exactly one of --input <path> or --archive <json>",
+                ));
+            }
+        };
+        let archive: AccountArchive = serde_json::from_str(&json)
+            .map_err(|e| RunbookError::bad_request(format!("Invalid archive: {e}")))?;
+        let email = parse_arg(args, "--email")?;
+        let conflict = parse_arg(args, "--on-conflict")?
+            .map(|mode| mode.parse::<ImportConflict>())
+            .transpose()
+            .map_err(RunbookError::bad_request)?
+            .unwrap_or(ImportConflict::Fail);
+
+        let email_shown = email.as_deref().unwrap_or(&archive.user.email).to_string();
+        let message = match import_account(setting, &archive, email.as_deref(), conflict).await? {
+            ImportOutcome::Created {
+                user_id,
+                preferences,
+                files,
+                skipped_files,
+            } => format!(
+                "Imported user '{email_shown}' as id {user_id} with {preferences} preference(s) and {files} file(s), skipped {skipped_files} file(s) owned by other users"
+            ),
+            ImportOutcome::Merged {
+                user_id,
+                preferences,
+                files,
+                skipped_files,
+            } => format!(
+                "Merged archive into user '{email_shown}' (id {user_id}) with {preferences} preference(s) and {files} file(s), skipped {skipped_files} file(s) owned by other users"
+            ),
+            ImportOutcome::Skipped { user_id } => {
+                format!("Skipped import, user '{email_shown}' already exists as id {user_id}")
+            }
+        };
+        Ok(RunbookExecutionResult::new(RUNBOOK_NAME, message))
+    }
+}
+
+fn parse_arg(args: &[String], name: &str) -> Result<Option<String>, RunbookError> {
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg == name {
+            let value = args
+                .next()
+                .ok_or_else(|| RunbookError::bad_request(format!("Missing value for {name}")))?;
+            return Ok(Some(value.to_string()));
+        }
+    }
+
+    Ok(None)
+}
@@ -1,4 +1,9 @@
+pub mod account_archive;
+mod create_admin_use_case;
 mod delete_refresh_tokens_by_email_use_case;
+mod export_user_use_case;
+mod import_user_use_case;
+mod rotate_jwt_secret_use_case;
 mod seed_use_case;
 
 use async_trait::async_trait;
@@ -6,9 +11,16 @@ use axum::http::StatusCode;
 
 use crate::config::setting::Setting;
 
+use create_admin_use_case::CreateAdmin;
 use delete_refresh_tokens_by_email_use_case::DeleteRefreshTokensByEmail;
+use export_user_use_case::ExportUser;
+use import_user_use_case::ImportUser;
+use rotate_jwt_secret_use_case::RotateJwtSecret;
 use seed_use_case::Seed;
 
+pub use create_admin_use_case::create_admin;
+pub use rotate_jwt_secret_use_case::{KeyRotation, rotate_jwt_secret};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RunbookMetadata {
     pub name: &'static str,
@@ -84,7 +96,14 @@ pub trait Runbook: Send + Sync {
 }
 
 fn all() -> Vec<Box<dyn Runbook>> {
-    vec![Box::new(Seed), Box::new(DeleteRefreshTokensByEmail)]
+    vec![
+        Box::new(Seed),
+        Box::new(CreateAdmin),
+        Box::new(DeleteRefreshTokensByEmail),
+        Box::new(RotateJwtSecret),
+        Box::new(ExportUser),
+        Box::new(ImportUser),
+    ]
 }
 
 pub fn list() -> Vec<RunbookMetadata> {
@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{Duration, Utc};
+use sea_orm::{TransactionTrait, entity::*};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::{
+    config::setting::Setting,
+    core::{context::Context, db::connection::get_db},
+    user::{entity::signing_key, repository::signing_key_repository},
+};
+
+use super::{Runbook, RunbookError, RunbookExecutionResult, RunbookMetadata};
+
+const RUNBOOK_NAME: &str = "rotate-jwt-secret";
+
+pub struct RotateJwtSecret;
+
+#[async_trait]
+impl Runbook for RotateJwtSecret {
+    fn metadata(&self) -> RunbookMetadata {
+        RunbookMetadata {
+            name: RUNBOOK_NAME,
+            description: "Start signing tokens with a new key and retire the previous one after a grace period",
+            usage: "runbook run rotate-jwt-secret [--grace-seconds <seconds>]",
+        }
+    }
+
+    async fn run(
+        &self,
+        setting: &Setting,
+        args: &[String],
+    ) -> Result<RunbookExecutionResult, RunbookError> {
+        let grace = parse_grace_seconds(args)?
+            .map(Duration::seconds)
+            .unwrap_or_else(|| Duration::seconds(setting.jwt_refresh_token_expires));
+
+        let rotation = rotate_jwt_secret(setting, grace)
+            .await
+            .map_err(RunbookError::internal_error)?;
+
+        Ok(RunbookExecutionResult::new(
+            RUNBOOK_NAME,
+            format!(
+                "Signing with key '{}'; {} previous key(s) retire at {}",
+                rotation.key.kid, rotation.retired, rotation.retires_at
+            ),
+        ))
+    }
+}
+
+fn parse_grace_seconds(args: &[String]) -> Result<Option<i64>, RunbookError> {
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--grace-seconds" {
+            let value = args
+                .next()
+                .ok_or_else(|| RunbookError::bad_request("Missing value for --grace-seconds"))?;
+            return value
+                .parse()
+                .map(Some)
+                .map_err(|_| RunbookError::bad_request("--grace-seconds must be a number"));
+        }
+    }
+
+    Ok(None)
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyRotation {
+    /// The key new tokens are signed with
+    pub key: signing_key::Model,
+    /// Number of previously active keys scheduled for retirement
+    pub retired: u64,
+    pub retires_at: chrono::NaiveDateTime,
+}
+
+/// Store a new signing key and schedule the active ones to stop verifying tokens after `grace`.
+/// Tokens signed with `JWT_SECRET` (no `kid`) stay valid for as long as the variable is unchanged.
+pub async fn rotate_jwt_secret(setting: &Setting, grace: Duration) -> anyhow::Result<KeyRotation> {
+    let db = get_db(&setting.database_url).await?;
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+
+    let retires_at = Utc::now().naive_utc() + grace;
+    let retired = signing_key_repository::retire_active(&context, retires_at).await?;
+    let key = signing_key_repository::create(
+        &context,
+        signing_key::ActiveModel {
+            kid: Set(Uuid::new_v4().simple().to_string()),
+            secret: Set(URL_SAFE_NO_PAD.encode(rand::random::<[u8; 48]>())),
+            ..Default::default()
+        },
+    )
+    .await?;
+    context.commit().await?;
+    db.close().await?;
+
+    Ok(KeyRotation {
+        key,
+        retired,
+        retires_at,
+    })
+}
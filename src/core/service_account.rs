@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+
+use crate::pkg::mtls::ClientIdentity;
+
+/// Internal caller authenticated by the client certificate it connected to the mTLS listener
+/// with, instead of a user's access token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServicePrincipal {
+    pub name: String,
+    pub scopes: Vec<String>,
+}
+
+impl ServicePrincipal {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
+}
+
+/// Service principals keyed by the certificate subject alternative name they are issued to
+#[derive(Debug, Clone, Default)]
+pub struct ServiceAccounts {
+    principals: HashMap<String, ServicePrincipal>,
+}
+
+impl ServiceAccounts {
+    /// Parse `san=name:scope,scope;...`, e.g.
+    /// `spiffe://prod/autoscaler=autoscaler:workers.read;billing.internal=billing:`
+    pub fn parse(spec: &str) -> anyhow::Result<Self> {
+        let mut principals = HashMap::new();
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let Some((san, principal)) = entry.split_once('=') else {
+                bail!("service account `{}` is not san=name:scopes", entry);
+            };
+            let (name, scopes) = principal.split_once(':').unwrap_or((principal, ""));
+            let (san, name) = (san.trim(), name.trim());
+            if san.is_empty() || name.is_empty() {
+                bail!("service account `{}` needs a SAN and a name", entry);
+            }
+            let principal = ServicePrincipal {
+                name: name.to_string(),
+                scopes: scopes
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|scope| !scope.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            };
+            if principals.insert(san.to_string(), principal).is_some() {
+                bail!("SAN `{}` is mapped more than once", san);
+            }
+        }
+        Ok(Self { principals })
+    }
+
+    /// Principal of the first SAN of `identity` that is mapped to one
+    pub fn resolve(&self, identity: &ClientIdentity) -> Option<&ServicePrincipal> {
+        identity
+            .sans
+            .iter()
+            .find_map(|san| self.principals.get(san))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.principals.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ServiceAccounts, ServicePrincipal};
+    use crate::pkg::mtls::ClientIdentity;
+
+    fn identity(sans: &[&str]) -> ClientIdentity {
+        ClientIdentity {
+            sans: sans.iter().map(|san| san.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parses_and_resolves_principals_by_san() {
+        let accounts = ServiceAccounts::parse(
+            "spiffe://prod/autoscaler=autoscaler:workers.read, workers.write; billing.internal=billing",
+        )
+        .unwrap();
+
+        assert_eq!(
+            accounts.resolve(&identity(&["unknown.internal", "spiffe://prod/autoscaler"])),
+            Some(&ServicePrincipal {
+                name: "autoscaler".to_string(),
+                scopes: vec!["workers.read".to_string(), "workers.write".to_string()],
+            })
+        );
+        let billing = accounts.resolve(&identity(&["billing.internal"])).unwrap();
+        assert!(billing.scopes.is_empty());
+        assert!(!billing.has_scope("workers.read"));
+        assert_eq!(accounts.resolve(&identity(&["unknown.internal"])), None);
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        assert!(ServiceAccounts::parse("").unwrap().is_empty());
+        assert!(ServiceAccounts::parse("billing.internal").is_err());
+        assert!(ServiceAccounts::parse("=billing:workers.read").is_err());
+        assert!(ServiceAccounts::parse("a.internal=a;a.internal=b").is_err());
+    }
+}
@@ -1,9 +1,59 @@
 use std::collections::HashMap;
-use tera::{Context, Tera};
+use tera::{Context, Tera, Value};
 
+use crate::core::translation::{interpolate, try_translate};
+
+/// Tera function `t(key="...", name=value, ...)`: the catalog text of `key` in the
+/// template's locale, with the other arguments filling its `%{name}` placeholders
+struct Translate {
+    locale: String,
+}
+
+impl tera::Function for Translate {
+    fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        let key = args
+            .get("key")
+            .and_then(Value::as_str)
+            .ok_or_else(|| tera::Error::msg("`t` requires a `key` argument"))?;
+        let text = try_translate(&self.locale, key)
+            .ok_or_else(|| tera::Error::msg(format!("Missing translation '{}'", key)))?;
+
+        let variables = args
+            .iter()
+            .filter(|(name, _)| name.as_str() != "key")
+            .map(|(name, value)| {
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    other => other.to_string(),
+                };
+                (name.clone(), value)
+            })
+            .collect();
+        Ok(Value::String(interpolate(&text, &variables)))
+    }
+}
+
+/// Render an email template in `locale`, which templates read as `locale` and which their
+/// `t(...)` calls translate to
 pub fn render_email_template(
     template_path: &str,
+    locale: &str,
     variables: HashMap<String, String>,
+) -> anyhow::Result<String> {
+    let mut context = Context::new();
+    for (key, value) in variables {
+        context.insert(key, &value);
+    }
+
+    render_template(template_path, locale, context)
+}
+
+/// Render any template under `src/core/template/` with structured `context`, e.g. the rows
+/// of a report. HTML templates are autoescaped, others such as CSV are not.
+pub fn render_template(
+    template_path: &str,
+    locale: &str,
+    mut context: Context,
 ) -> anyhow::Result<String> {
     // Build the full path to the template file
     let full_path = format!("src/core/template/{}", template_path);
@@ -11,12 +61,17 @@ pub fn render_email_template(
         .map_err(|e| anyhow::anyhow!("Failed to read template file '{}': {}", full_path, e))?;
 
     let mut tera = Tera::default();
+    tera.register_function(
+        "t",
+        Translate {
+            locale: locale.to_string(),
+        },
+    );
     tera.add_raw_template(template_path, &template_content)
         .map_err(|e| anyhow::anyhow!("Failed to add template '{}': {}", template_path, e))?;
 
-    let mut context = Context::new();
-    for (key, value) in variables {
-        context.insert(key, &value);
+    if !context.contains_key("locale") {
+        context.insert("locale", locale);
     }
 
     tera.render(template_path, &context)
@@ -43,20 +98,50 @@ mod tests {
 
     #[test]
     fn renders_existing_template() {
-        let html = render_email_template("email/welcome.html", welcome_variables()).unwrap();
+        let html = render_email_template("email/welcome.html", "en", welcome_variables()).unwrap();
         assert!(html.contains("Test App"));
         assert!(html.contains("test@example.com"));
     }
 
+    #[test]
+    fn translates_template_in_requested_locale() {
+        let mut variables = welcome_variables();
+        variables.insert("otp".to_string(), "123456".to_string());
+        variables.insert("expiry_minutes".to_string(), "15".to_string());
+        variables.insert("max_attempts".to_string(), "3".to_string());
+
+        let html = render_email_template("email/password_reset.html", "vi", variables).unwrap();
+        assert!(html.contains(r#"<html lang="vi">"#));
+        assert!(html.contains("Mã OTP sẽ hết hạn sau 15 phút."));
+        assert!(html.contains("Đội ngũ bảo mật Test App"));
+    }
+
+    #[test]
+    fn renders_password_reset_link_instead_of_otp() {
+        let mut variables = welcome_variables();
+        variables.insert("otp".to_string(), String::new());
+        variables.insert(
+            "reset_url".to_string(),
+            "https://api.example.com/reset?token=abc".to_string(),
+        );
+        variables.insert("expiry_minutes".to_string(), "60".to_string());
+        variables.insert("max_attempts".to_string(), "3".to_string());
+
+        let html = render_email_template("email/password_reset.html", "en", variables).unwrap();
+        assert!(html.contains("token=abc"));
+        assert!(html.contains("This link will expire in 60 minutes."));
+        assert!(!html.contains(r#"class="otp-code""#));
+    }
+
     #[test]
     fn rejects_missing_template() {
-        let error = render_email_template("email/missing.html", HashMap::new()).unwrap_err();
+        let error = render_email_template("email/missing.html", "en", HashMap::new()).unwrap_err();
         assert!(error.to_string().contains("Failed to read template file"));
     }
 
     #[test]
     fn rejects_missing_variables() {
-        let error = render_email_template("email/welcome.html", HashMap::new()).unwrap_err();
+        let error = render_email_template("email/welcome.html", "en", HashMap::new()).unwrap_err();
         assert!(error.to_string().contains("Failed to render template"));
     }
 }
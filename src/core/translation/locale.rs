@@ -0,0 +1,95 @@
+use fluent_langneg::{NegotiationStrategy, accepted_languages, negotiate_languages};
+use std::sync::LazyLock;
+use unic_langid::LanguageIdentifier;
+
+/// Locale used when nothing the client asked for is available
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Locales with a catalog under `src/core/translation/locales`
+static AVAILABLE_LOCALES: LazyLock<Vec<LanguageIdentifier>> = LazyLock::new(|| {
+    rust_i18n::available_locales!()
+        .iter()
+        .filter_map(|locale| locale.parse().ok())
+        .collect()
+});
+
+static DEFAULT_LANGID: LazyLock<LanguageIdentifier> =
+    LazyLock::new(|| DEFAULT_LOCALE.parse().unwrap());
+
+pub fn available_locales() -> Vec<String> {
+    AVAILABLE_LOCALES.iter().map(|id| id.to_string()).collect()
+}
+
+/// Best available locale for `requested`, given from most to least preferred. Regional
+/// variants match their language (`vi-VN` gets `vi`), anything else gets the default.
+pub fn negotiate<S: AsRef<str>>(requested: &[S]) -> String {
+    let requested: Vec<LanguageIdentifier> = requested
+        .iter()
+        .filter_map(|locale| locale.as_ref().trim().parse().ok())
+        .collect();
+    negotiate_languages(
+        &requested,
+        &AVAILABLE_LOCALES,
+        Some(&*DEFAULT_LANGID),
+        NegotiationStrategy::Lookup,
+    )
+    .first()
+    .map(|id| id.to_string())
+    .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+/// Best available locale for an `Accept-Language` header, honoring quality values
+pub fn negotiate_accept_language(header_value: &str) -> String {
+    let requested = accepted_languages::parse(header_value);
+    negotiate(
+        &requested
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Available locale matching `locale`, `None` when there is no catalog for it
+pub fn supported_locale(locale: &str) -> Option<String> {
+    let requested: LanguageIdentifier = locale.trim().parse().ok()?;
+    negotiate_languages(
+        &[requested],
+        &AVAILABLE_LOCALES,
+        None,
+        NegotiationStrategy::Lookup,
+    )
+    .first()
+    .map(|id| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiates_by_quality_and_region() {
+        assert_eq!(negotiate_accept_language("vi-VN,vi;q=0.9,en;q=0.8"), "vi");
+        assert_eq!(negotiate_accept_language("en-US,en;q=0.9,vi;q=0.8"), "en");
+        assert_eq!(negotiate_accept_language("fr;q=0.9,vi;q=0.5"), "vi");
+    }
+
+    #[test]
+    fn falls_back_to_default_locale() {
+        assert_eq!(negotiate_accept_language("fr,de"), DEFAULT_LOCALE);
+        assert_eq!(negotiate_accept_language(""), DEFAULT_LOCALE);
+        assert_eq!(negotiate::<&str>(&[]), DEFAULT_LOCALE);
+    }
+
+    #[test]
+    fn finds_supported_locales() {
+        assert_eq!(supported_locale("vi").as_deref(), Some("vi"));
+        assert_eq!(supported_locale("en-GB").as_deref(), Some("en"));
+        assert_eq!(supported_locale("fr"), None);
+        assert_eq!(supported_locale("not a locale"), None);
+    }
+
+    #[test]
+    fn lists_catalog_locales() {
+        assert_eq!(available_locales(), vec!["en", "vi"]);
+    }
+}
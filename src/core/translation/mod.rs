@@ -1 +1,45 @@
-pub mod language;
+//! User-facing strings live in the catalogs under `locales/`, one file per locale. Code
+//! looks them up with `t!("key", locale = ...)`, or with [`try_translate`] for computed keys;
+//! email templates use the `t` function registered by the template engine.
+
+use std::collections::HashMap;
+
+pub mod locale;
+
+/// Text of `key` in `locale`, falling back to the default locale. `None` when no catalog
+/// has the key.
+pub fn try_translate(locale: &str, key: &str) -> Option<String> {
+    crate::_rust_i18n_try_translate(locale, key).map(|text| text.into_owned())
+}
+
+/// Replace `%{name}` placeholders, as `t!` does with its arguments
+pub fn interpolate(text: &str, variables: &HashMap<String, String>) -> String {
+    let (names, values): (Vec<&str>, Vec<String>) = variables
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.clone()))
+        .unzip();
+    rust_i18n::replace_patterns(text, &names, &values)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{interpolate, try_translate};
+
+    #[test]
+    fn translates_computed_keys() {
+        assert_eq!(
+            try_translate("vi", "authorization.role.admin").as_deref(),
+            Some("Quản trị viên")
+        );
+        assert_eq!(try_translate("vi", "missing.key"), None);
+    }
+
+    #[test]
+    fn interpolates_placeholders() {
+        let variables = HashMap::from([("name".to_string(), "Jane".to_string())]);
+
+        assert_eq!(interpolate("Hello %{name}!", &variables), "Hello Jane!");
+    }
+}
@@ -0,0 +1,359 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use rust_i18n::t;
+use utoipa::openapi::{
+    RefOr, Schema,
+    path::Parameter,
+    schema::{KnownFormat, Object, SchemaFormat},
+};
+
+use crate::{
+    core::dto::{
+        error_code::ErrorCode,
+        error_dto::{ErrorDTO, FieldErrorDTO},
+    },
+    pkg::password,
+};
+
+pub use macros::Validate;
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$").expect("valid email regex")
+});
+
+/// Field constraints of a request DTO, usually derived with `#[derive(Validate)]`
+pub trait Validate {
+    /// Check every field, collecting one error per invalid field
+    fn validate(&self, locale: &str) -> Result<(), ValidationErrors>;
+
+    /// Rules of each validated field, documented in the OpenAPI spec
+    fn constraints() -> &'static [FieldConstraints];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    Required,
+    Email,
+    Length {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    Range {
+        min: Option<i64>,
+        max: Option<i64>,
+    },
+    Password,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldConstraints {
+    pub field: &'static str,
+    pub rules: &'static [Rule],
+}
+
+/// Value of a field as the rules see it
+pub enum FieldValue<'a> {
+    Text(&'a str),
+    Number(i64),
+}
+
+/// Types a validation rule can be applied to. `None` means the field was left out.
+pub trait Validatable {
+    fn field_value(&self) -> Option<FieldValue<'_>>;
+}
+
+impl Validatable for String {
+    fn field_value(&self) -> Option<FieldValue<'_>> {
+        Some(FieldValue::Text(self))
+    }
+}
+
+impl Validatable for str {
+    fn field_value(&self) -> Option<FieldValue<'_>> {
+        Some(FieldValue::Text(self))
+    }
+}
+
+impl<T: Validatable> Validatable for Option<T> {
+    fn field_value(&self) -> Option<FieldValue<'_>> {
+        self.as_ref().and_then(Validatable::field_value)
+    }
+}
+
+macro_rules! impl_validatable_number {
+    ($($ty:ty),*) => {
+        $(impl Validatable for $ty {
+            fn field_value(&self) -> Option<FieldValue<'_>> {
+                Some(FieldValue::Number(i64::try_from(*self).unwrap_or(i64::MAX)))
+            }
+        })*
+    };
+}
+
+impl_validatable_number!(i32, i64, u32, u64, usize);
+
+#[derive(Debug, Default)]
+pub struct ValidationErrors(Vec<FieldErrorDTO>);
+
+impl ValidationErrors {
+    /// Apply `rules` to `value` in order, recording the first one it breaks
+    pub fn check(
+        &mut self,
+        field: &str,
+        value: &(impl Validatable + ?Sized),
+        rules: &[Rule],
+        locale: &str,
+    ) {
+        let value = value.field_value();
+        if let Some(message) = rules
+            .iter()
+            .find_map(|rule| rule.check(field, value.as_ref(), locale))
+        {
+            self.0.push(FieldErrorDTO {
+                field: field.to_string(),
+                message,
+                rule: None,
+            });
+        }
+    }
+
+    pub fn errors(&self) -> &[FieldErrorDTO] {
+        &self.0
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.0.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+impl From<ValidationErrors> for ErrorDTO {
+    fn from(errors: ValidationErrors) -> Self {
+        let message = errors
+            .0
+            .first()
+            .map(|error| error.message.clone())
+            .unwrap_or_default();
+        ErrorDTO::from_code(ErrorCode::ValidationFailed, message).with_errors(errors.0)
+    }
+}
+
+impl Rule {
+    /// Message of the broken rule, `None` when `value` satisfies it
+    fn check(&self, field: &str, value: Option<&FieldValue>, locale: &str) -> Option<String> {
+        let message = match (self, value) {
+            (Rule::Required, None) => t!("validation.required", field = field, locale = locale),
+            (Rule::Required, Some(FieldValue::Text(text))) if text.trim().is_empty() => {
+                t!("validation.required", field = field, locale = locale)
+            }
+            (Rule::Email, Some(FieldValue::Text(text))) if !EMAIL.is_match(text) => {
+                t!("validation.email", field = field, locale = locale)
+            }
+            (Rule::Length { min, max }, Some(FieldValue::Text(text))) => {
+                let length = text.chars().count();
+                match (min, max) {
+                    (Some(min), _) if length < *min => {
+                        t!(
+                            "validation.length_min",
+                            field = field,
+                            min = min,
+                            locale = locale
+                        )
+                    }
+                    (_, Some(max)) if length > *max => {
+                        t!(
+                            "validation.length_max",
+                            field = field,
+                            max = max,
+                            locale = locale
+                        )
+                    }
+                    _ => return None,
+                }
+            }
+            (Rule::Range { min, max }, Some(FieldValue::Number(number))) => match (min, max) {
+                (Some(min), _) if number < min => {
+                    t!(
+                        "validation.range_min",
+                        field = field,
+                        min = min,
+                        locale = locale
+                    )
+                }
+                (_, Some(max)) if number > max => {
+                    t!(
+                        "validation.range_max",
+                        field = field,
+                        max = max,
+                        locale = locale
+                    )
+                }
+                _ => return None,
+            },
+            (Rule::Password, Some(FieldValue::Text(text)))
+                if password::validate_password_strength(text).is_err() =>
+            {
+                t!("validation.password", field = field, locale = locale)
+            }
+            _ => return None,
+        };
+        Some(message.to_string())
+    }
+
+    /// Describe the rule on the schema of its field
+    fn document(&self, schema: &mut Object) {
+        match *self {
+            Rule::Required => {
+                schema.min_length = Some(schema.min_length.unwrap_or(0).max(1));
+            }
+            Rule::Email => {
+                schema.format = Some(SchemaFormat::KnownFormat(KnownFormat::Email));
+            }
+            Rule::Length { min, max } => {
+                if min.is_some() {
+                    schema.min_length = min;
+                }
+                if max.is_some() {
+                    schema.max_length = max;
+                }
+            }
+            Rule::Range { min, max } => {
+                if let Some(min) = min {
+                    schema.minimum = Some(min.into());
+                }
+                if let Some(max) = max {
+                    schema.maximum = Some(max.into());
+                }
+            }
+            Rule::Password => {
+                schema.min_length = Some(schema.min_length.unwrap_or(0).max(8));
+                schema.description.get_or_insert_with(|| {
+                    "At least one uppercase letter, lowercase letter, digit and special character"
+                        .to_string()
+                });
+            }
+        }
+    }
+}
+
+/// Add the constraints of `T` to the properties of its component schema
+pub fn document_schema<T: Validate>(schema: &mut RefOr<Schema>) {
+    let RefOr::T(Schema::Object(object)) = schema else {
+        return;
+    };
+    for constraints in T::constraints() {
+        if let Some(RefOr::T(Schema::Object(property))) =
+            object.properties.get_mut(constraints.field)
+        {
+            for rule in constraints.rules {
+                rule.document(property);
+            }
+        }
+    }
+}
+
+/// Add the constraints of `T` to the query or path parameters it is extracted from
+pub fn document_parameters<T: Validate>(parameters: &mut [Parameter]) {
+    for constraints in T::constraints() {
+        let Some(RefOr::T(Schema::Object(schema))) = parameters
+            .iter_mut()
+            .find(|parameter| parameter.name == constraints.field)
+            .and_then(|parameter| parameter.schema.as_mut())
+        else {
+            continue;
+        };
+        for rule in constraints.rules {
+            rule.document(schema);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rule, Validate};
+
+    #[derive(Validate)]
+    struct SignUp {
+        #[validate(required, email)]
+        email: String,
+        #[validate(required, password)]
+        password: String,
+        #[validate(length(max = 5))]
+        nickname: Option<String>,
+        #[validate(range(min = 1, max = 10))]
+        page: Option<u64>,
+    }
+
+    fn sign_up() -> SignUp {
+        SignUp {
+            email: "user@example.com".to_string(),
+            password: "Str0ng!pass".to_string(),
+            nickname: None,
+            page: None,
+        }
+    }
+
+    #[test]
+    fn accepts_valid_values() {
+        assert!(sign_up().validate("en").is_ok());
+        assert!(
+            SignUp {
+                nickname: Some("neo".to_string()),
+                page: Some(10),
+                ..sign_up()
+            }
+            .validate("en")
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn collects_one_error_per_invalid_field() {
+        let errors = SignUp {
+            email: " ".to_string(),
+            password: "weak".to_string(),
+            nickname: Some("morpheus".to_string()),
+            page: Some(0),
+        }
+        .validate("en")
+        .unwrap_err();
+
+        let fields: Vec<_> = errors.errors().iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["email", "password", "nickname", "page"]);
+        assert_eq!(errors.errors()[0].message, "email is required");
+        assert_eq!(
+            errors.errors()[2].message,
+            "nickname must be at most 5 characters long"
+        );
+        assert_eq!(errors.errors()[3].message, "page must be at least 1");
+    }
+
+    #[test]
+    fn rejects_malformed_email() {
+        let errors = SignUp {
+            email: "not-an-email".to_string(),
+            ..sign_up()
+        }
+        .validate("en")
+        .unwrap_err();
+
+        assert_eq!(errors.errors().len(), 1);
+        assert_eq!(errors.errors()[0].field, "email");
+    }
+
+    #[test]
+    fn lists_constraints() {
+        let constraints = SignUp::constraints();
+
+        assert_eq!(constraints.len(), 4);
+        assert_eq!(constraints[0].field, "email");
+        assert_eq!(constraints[0].rules, [Rule::Required, Rule::Email]);
+        assert_eq!(
+            constraints[3].rules,
+            [Rule::Range {
+                min: Some(1),
+                max: Some(10)
+            }]
+        );
+    }
+}
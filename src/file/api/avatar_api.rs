@@ -0,0 +1,125 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    config::app::AppState,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    file::{
+        dto::file_dto::{MediaParamsDTO, PendingAvatarListDTO, RejectAvatarDTO},
+        use_case::avatar::{
+            approve_avatar_use_case, get_avatar_media_use_case, reject_avatar_use_case,
+            search_pending_avatar_use_case,
+        },
+    },
+};
+
+/// Avatars waiting for review, oldest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/avatars/pending/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = PendingAvatarListDTO)),
+)]
+pub async fn search_pending_avatar(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<PendingAvatarListDTO>, ErrorDTO> {
+    let storage = app_state.setting.get_storage();
+    search_pending_avatar_use_case::execute(&context, &app_state.setting, &storage).await
+}
+
+/// Show a pending avatar on its owner's profile
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/avatars/{id}/approve/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn approve_avatar(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    approve_avatar_use_case::execute(&context, id).await
+}
+
+/// Turn down a pending avatar and notify its owner
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/avatars/{id}/reject/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    request_body(content = RejectAvatarDTO),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn reject_avatar(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+    Json(dto): Json<RejectAvatarDTO>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    reject_avatar_use_case::execute(&context, id, dto).await
+}
+
+/// Avatar of a user, behind the signed `avatar_url` or `avatar_thumbnail_urls` of their profile.
+/// Browsers may cache it until the link expires.
+#[utoipa::path(
+    get,
+    path = "/media/avatars/{user_id}/{variant}",
+    tags = ["File"],
+    params(
+        ("user_id" = i32, Path),
+        ("variant" = String, Path, description = "`original` or a thumbnail size, e.g. `64`"),
+        MediaParamsDTO,
+    ),
+    responses((status = StatusCode::OK, content_type = "image/*", body = Vec<u8>)),
+)]
+pub async fn get_avatar_media(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+    Path((user_id, variant)): Path<(i32, String)>,
+    Query(params): Query<MediaParamsDTO>,
+    headers: HeaderMap,
+) -> Result<Response, ErrorDTO> {
+    let max_age = (params.expires - chrono::Utc::now().timestamp()).max(0);
+    let storage = app_state.setting.get_storage();
+    let referer = headers
+        .get(header::REFERER)
+        .and_then(|value| value.to_str().ok());
+    let file = get_avatar_media_use_case::execute(
+        &context,
+        &app_state.setting,
+        &storage,
+        user_id,
+        &variant,
+        params,
+        referer,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, file.content_type),
+            // Private, so shared caches don't serve it to pages the referer check would refuse
+            (
+                header::CACHE_CONTROL,
+                format!("private, max-age={}", max_age),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        file.content,
+    )
+        .into_response())
+}
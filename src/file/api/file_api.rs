@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{
+    Extension,
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    config::app::AppState,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    file::{
+        dto::file_dto::{FileDownloadParamsDTO, UserFileListDTO},
+        use_case::file::{
+            delete_user_file_use_case, download_file_use_case, search_user_file_use_case,
+        },
+    },
+};
+
+/// Files the current user stored, with presigned download links
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/files/",
+    tags = ["File"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = UserFileListDTO)),
+)]
+pub async fn search_user_file(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<UserFileListDTO>, ErrorDTO> {
+    let storage = app_state.setting.get_storage();
+    search_user_file_use_case::execute(&context, &app_state.setting, &storage).await
+}
+
+/// Delete a file of the current user along with its stored objects
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/me/files/{id}/",
+    tags = ["File"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn delete_user_file(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let storage = Arc::new(app_state.setting.get_storage());
+    delete_user_file_use_case::execute(&context, storage, id).await
+}
+
+/// Content of a stored file, behind a presigned link from `GET /api/v1/users/me/files/`
+#[utoipa::path(
+    get,
+    path = "/api/v1/files/download/",
+    tags = ["File"],
+    params(FileDownloadParamsDTO),
+    responses((status = StatusCode::OK, content_type = "application/octet-stream", body = Vec<u8>)),
+)]
+pub async fn download_file(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+    Query(params): Query<FileDownloadParamsDTO>,
+) -> Result<Response, ErrorDTO> {
+    let storage = app_state.setting.get_storage();
+    let file = download_file_use_case::execute(
+        &context,
+        &app_state.setting.get_url_signer(),
+        &storage,
+        params,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, file.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file.name.replace('"', "")),
+            ),
+        ],
+        file.content,
+    )
+        .into_response())
+}
@@ -0,0 +1,2 @@
+pub mod avatar_api;
+pub mod file_api;
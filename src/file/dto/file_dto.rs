@@ -0,0 +1,119 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    core::validation::Validate,
+    file::entity::{
+        file,
+        sea_orm_active_enums::{FileStatus, ModerationStatus},
+    },
+};
+
+/// A file the current user stored, e.g. an avatar
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserFileDTO {
+    pub id: i32,
+    pub name: String,
+    pub content_type: Option<String>,
+    pub size: i64,
+    pub status: FileStatus,
+    /// Review state of an avatar, `None` for files that aren't reviewed
+    pub moderation_status: Option<ModerationStatus>,
+    pub rejection_reason: Option<String>,
+    /// Presigned link valid for `STORAGE_URL_EXPIRY_SECONDS`, only for available files
+    pub download_url: Option<String>,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl UserFileDTO {
+    pub fn new(model: file::Model, download_url: Option<String>) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            content_type: model.content_type,
+            size: model.size,
+            status: model.status,
+            moderation_status: model.moderation_status,
+            rejection_reason: model.rejection_reason,
+            download_url,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserFileListDTO {
+    pub items: Vec<UserFileDTO>,
+    pub count: usize,
+}
+
+/// An avatar waiting for review
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PendingAvatarDTO {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub size: i64,
+    /// Avatars can only be approved once `available`, i.e. scanned and processed
+    pub status: FileStatus,
+    /// Presigned link to look at the avatar, only for available files
+    pub download_url: Option<String>,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl PendingAvatarDTO {
+    pub fn new(model: file::Model, download_url: Option<String>) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            name: model.name,
+            size: model.size,
+            status: model.status,
+            download_url,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PendingAvatarListDTO {
+    pub items: Vec<PendingAvatarDTO>,
+    pub count: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RejectAvatarDTO {
+    /// Shown to the owner; a generic reason is given when left out
+    #[validate(length(max = 255))]
+    pub reason: Option<String>,
+}
+
+/// Query parameters of a presigned download link
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FileDownloadParamsDTO {
+    pub key: String,
+    /// Unix timestamp after which the link is rejected
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// Query parameters of a signed `/media/` link
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MediaParamsDTO {
+    /// Unix timestamp after which the link is rejected
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// A stored object, as downloaded through a presigned link
+#[derive(Debug)]
+pub struct FileContentDTO {
+    pub name: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
@@ -0,0 +1,39 @@
+use super::sea_orm_active_enums::{FileStatus, ModerationStatus};
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "file")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    #[sea_orm(unique)]
+    pub key: String,
+    pub name: String,
+    pub content_type: Option<String>,
+    pub size: i64,
+    #[sea_orm(default_value = "pending")]
+    pub status: FileStatus,
+    /// Generated size variants, keyed by size (e.g. `{"64": "avatars/.../thumbnails/64.png"}`)
+    pub variants: Option<Json>,
+    /// Review state of an avatar, `None` for files that aren't reviewed
+    pub moderation_status: Option<ModerationStatus>,
+    /// Why an admin rejected the avatar, shown to its owner
+    pub rejection_reason: Option<String>,
+    pub moderated_at: Option<DateTime>,
+    /// Admin who approved or rejected the avatar
+    pub moderated_user_id: Option<i32>,
+    pub created_at: Option<DateTime>,
+    pub updated_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "user_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub user: HasOne<crate::user::entity::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
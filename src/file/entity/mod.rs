@@ -0,0 +1,3 @@
+pub mod file;
+pub mod prelude;
+pub mod sea_orm_active_enums;
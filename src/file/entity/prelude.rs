@@ -0,0 +1 @@
+pub use super::file::Entity as File;
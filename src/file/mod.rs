@@ -0,0 +1,10 @@
+pub mod api;
+pub mod dto;
+pub mod entity;
+mod module;
+pub mod repository;
+pub mod service;
+pub mod task;
+pub mod use_case;
+
+pub use module::FileModule;
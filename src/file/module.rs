@@ -0,0 +1,67 @@
+use axum::{
+    Router,
+    routing::{delete, get, post},
+};
+
+use crate::{
+    config::app::AppState,
+    core::{
+        api::route::{protected_api, public_api},
+        r#async::TaskType,
+        module::{Module, ScheduledJob},
+    },
+    file::api::{avatar_api, file_api},
+};
+
+/// Uploaded file storage and processing
+pub struct FileModule;
+
+impl Module for FileModule {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn routes(&self, app_state: &AppState) -> Router<AppState> {
+        let user_routes = protected_api(
+            Router::new()
+                .route("/api/v1/users/me/files/", get(file_api::search_user_file))
+                .route(
+                    "/api/v1/users/me/files/{id}/",
+                    delete(file_api::delete_user_file),
+                )
+                .route(
+                    "/api/v1/admin/avatars/pending/",
+                    get(avatar_api::search_pending_avatar),
+                )
+                .route(
+                    "/api/v1/admin/avatars/{id}/approve/",
+                    post(avatar_api::approve_avatar),
+                )
+                .route(
+                    "/api/v1/admin/avatars/{id}/reject/",
+                    post(avatar_api::reject_avatar),
+                ),
+            app_state,
+        );
+        // Presigned links are opened without credentials, the signature stands in for them
+        let download_routes = public_api(
+            Router::new()
+                .route("/api/v1/files/download/", get(file_api::download_file))
+                .route(
+                    "/media/avatars/{user_id}/{variant}",
+                    get(avatar_api::get_avatar_media),
+                ),
+            app_state,
+        );
+
+        user_routes.merge(download_routes)
+    }
+
+    fn scheduled_jobs(&self) -> Vec<ScheduledJob> {
+        vec![ScheduledJob::new(
+            "cleanup-orphaned-files",
+            "0 30 3 * * *", // Every day at 03:30
+            TaskType::CleanupOrphanedFiles,
+        )]
+    }
+}
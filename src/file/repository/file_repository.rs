@@ -0,0 +1,60 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    core::context::Context,
+    file::entity::{file, sea_orm_active_enums::ModerationStatus},
+};
+
+pub async fn find_by_id(context: &Context, id: i32) -> Result<Option<file::Model>, DbErr> {
+    file::Entity::find_by_id(id).one(context.txn()).await
+}
+
+pub async fn find_by_key(context: &Context, key: &str) -> Result<Option<file::Model>, DbErr> {
+    file::Entity::find()
+        .filter(file::Column::Key.eq(key))
+        .one(context.txn())
+        .await
+}
+
+pub async fn find_by_user_id(context: &Context, user_id: i32) -> Result<Vec<file::Model>, DbErr> {
+    file::Entity::find()
+        .filter(file::Column::UserId.eq(user_id))
+        .order_by_desc(file::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+/// Avatars waiting for review, oldest first
+pub async fn find_pending_moderation(context: &Context) -> Result<Vec<file::Model>, DbErr> {
+    file::Entity::find()
+        .filter(file::Column::ModerationStatus.eq(ModerationStatus::Pending))
+        .order_by_asc(file::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+pub async fn find_all(context: &Context) -> Result<Vec<file::Model>, DbErr> {
+    file::Entity::find()
+        .order_by_asc(file::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+pub async fn create(context: &Context, mut file: file::ActiveModel) -> Result<file::Model, DbErr> {
+    let now = chrono::Utc::now().naive_utc();
+    file.created_at = Set(Some(now));
+    file.updated_at = Set(Some(now));
+
+    file.insert(context.txn()).await
+}
+
+pub async fn update(context: &Context, mut file: file::ActiveModel) -> Result<file::Model, DbErr> {
+    file.updated_at = Set(Some(chrono::Utc::now().naive_utc()));
+
+    file.update(context.txn()).await
+}
+
+pub async fn delete_by_id(context: &Context, id: i32) -> Result<(), DbErr> {
+    file::Entity::delete_by_id(id).exec(context.txn()).await?;
+    Ok(())
+}
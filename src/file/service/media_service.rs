@@ -0,0 +1,122 @@
+use chrono::Utc;
+
+use crate::{config::setting::Setting, file::entity::file, pkg::cors::matches_origin_pattern};
+
+/// Variant of an avatar that is the uploaded image itself rather than a thumbnail
+pub const ORIGINAL_VARIANT: &str = "original";
+
+/// Path serving variant `variant` of the avatar of `user_id`
+pub fn avatar_path(user_id: i32, variant: &str) -> String {
+    format!("/media/avatars/{}/{}", user_id, variant)
+}
+
+/// Signed link to variant `variant` of the avatar of `user_id`, valid for
+/// `MEDIA_URL_EXPIRY_SECONDS`. It names the user rather than the stored object, so storage
+/// keys stay private.
+pub fn avatar_url(setting: &Setting, user_id: i32, variant: &str) -> anyhow::Result<String> {
+    setting.get_media_signer().sign_path(
+        &avatar_path(user_id, variant),
+        Utc::now() + setting.media_url_expiry(),
+    )
+}
+
+/// Storage key of `variant` of `avatar`: the original, or a thumbnail size such as `64`
+pub fn variant_key(avatar: &file::Model, variant: &str) -> Option<String> {
+    if variant == ORIGINAL_VARIANT {
+        return Some(avatar.key.clone());
+    }
+    avatar
+        .variants
+        .as_ref()
+        .and_then(|variants| variants.get(variant))
+        .and_then(|key| key.as_str())
+        .map(str::to_string)
+}
+
+/// Thumbnail sizes of `avatar`, smallest first
+pub fn thumbnail_sizes(avatar: &file::Model) -> Vec<String> {
+    let mut sizes: Vec<String> = avatar
+        .variants
+        .as_ref()
+        .and_then(|variants| variants.as_object())
+        .map(|variants| variants.keys().cloned().collect())
+        .unwrap_or_default();
+    sizes.sort_by_key(|size| size.parse::<u32>().unwrap_or(u32::MAX));
+    sizes
+}
+
+/// Whether media may be loaded by a page at `referer`. Requests without a `Referer`, from
+/// apps or browsers that don't send it, are let through.
+pub fn is_referer_allowed(setting: &Setting, referer: Option<&str>) -> bool {
+    let Some(referer) = referer else {
+        return true;
+    };
+    let Ok(url) = reqwest::Url::parse(referer) else {
+        return false;
+    };
+    let origin = url.origin().ascii_serialization();
+    setting
+        .media_referer_patterns()
+        .iter()
+        .any(|pattern| matches_origin_pattern(&origin, pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::file::entity::sea_orm_active_enums::FileStatus;
+
+    fn avatar() -> file::Model {
+        file::Model {
+            id: 1,
+            user_id: 1,
+            key: "avatars/1/a.png".to_string(),
+            name: "a.png".to_string(),
+            content_type: Some("image/png".to_string()),
+            size: 4,
+            status: FileStatus::Available,
+            variants: Some(json!({
+                "256": "avatars/1/thumbnails/256.png",
+                "64": "avatars/1/thumbnails/64.png",
+            })),
+            moderation_status: None,
+            rejection_reason: None,
+            moderated_at: None,
+            moderated_user_id: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn resolves_variant_keys() {
+        let avatar = avatar();
+
+        assert_eq!(
+            variant_key(&avatar, ORIGINAL_VARIANT).as_deref(),
+            Some("avatars/1/a.png")
+        );
+        assert_eq!(
+            variant_key(&avatar, "64").as_deref(),
+            Some("avatars/1/thumbnails/64.png")
+        );
+        assert_eq!(variant_key(&avatar, "128"), None);
+        assert_eq!(thumbnail_sizes(&avatar), vec!["64", "256"]);
+    }
+
+    #[test]
+    fn allows_referers_of_configured_origins() {
+        let mut setting = Setting::new();
+        setting.media_allowed_referers = vec!["https://*.example.com".to_string()];
+
+        assert!(is_referer_allowed(&setting, None));
+        assert!(is_referer_allowed(
+            &setting,
+            Some("https://app.example.com/profile?tab=1")
+        ));
+        assert!(!is_referer_allowed(&setting, Some("https://evil.test/")));
+        assert!(!is_referer_allowed(&setting, Some("not a url")));
+    }
+}
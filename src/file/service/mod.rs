@@ -0,0 +1 @@
+pub mod media_service;
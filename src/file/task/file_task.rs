@@ -0,0 +1,404 @@
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::{ActiveValue::Set, DatabaseConnection, IntoActiveModel, TransactionTrait};
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::{
+    core::{
+        r#async::AVATAR_PIPELINE,
+        context::Context,
+        event_type::{EventType, FILE_QUARANTINED, THUMBNAIL_PROGRESS},
+    },
+    file::{
+        entity::{
+            file,
+            sea_orm_active_enums::{FileStatus, ModerationStatus},
+        },
+        repository::file_repository,
+    },
+    notification::service::{
+        notification_service::{self, Notification},
+        notification_template_service::NotificationEvent,
+    },
+    pkg::{
+        antivirus::{ScanVerdict, VirusScanner},
+        broadcast::websocket::BroadcastMessage,
+        messaging::MessageProducer,
+        storage::ObjectStorage,
+        thumbnail::generate_thumbnail,
+    },
+    report::task::report_task::REPORT_PREFIX,
+    user::{
+        entity::sea_orm_active_enums::UserRole,
+        repository::user_repository::{self, UserSearchParams},
+    },
+};
+
+/// Storage prefix that infected uploads are moved under
+pub const QUARANTINE_PREFIX: &str = "quarantine";
+
+/// Minimum age before an unreferenced object is treated as orphaned, so uploads
+/// whose record hasn't been committed yet are left alone
+pub const ORPHAN_GRACE_PERIOD_MINUTES: i64 = 60;
+
+/// Outcome of reconciling the file table against object storage
+#[derive(Debug, Default)]
+pub struct OrphanedFileReport {
+    /// Keys of objects deleted because no file record referenced them
+    pub deleted_objects: Vec<String>,
+    /// File records newly flagged as missing because their object is gone
+    pub missing_files: Vec<file::Model>,
+}
+
+impl OrphanedFileReport {
+    pub fn is_empty(&self) -> bool {
+        self.deleted_objects.is_empty() && self.missing_files.is_empty()
+    }
+}
+
+/// Scan an uploaded file before it is marked available.
+/// Infected files are moved to quarantine and the owner is notified.
+pub async fn scan_upload(
+    db: &DatabaseConnection,
+    producer: &dyn MessageProducer,
+    storage: &dyn ObjectStorage,
+    scanner: Option<&dyn VirusScanner>,
+    task_id: &str,
+    file_id: i32,
+    locale: &str,
+) -> anyhow::Result<ScanVerdict> {
+    let Some(scanner) = scanner else {
+        tracing::debug!("Virus scanning disabled, skipping file {}", file_id);
+        return Ok(ScanVerdict::Clean);
+    };
+
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+
+    let file = file_repository::find_by_id(&context, file_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("File {} not found", file_id))?;
+
+    let Some(data) = storage.get(&file.key).await? else {
+        tracing::warn!(
+            "No stored object for file {} ({}), skipping scan",
+            file.id,
+            file.key
+        );
+        return Ok(ScanVerdict::Clean);
+    };
+
+    tracing::info!("Scanning file {} ({} bytes)", file.id, data.len());
+    let verdict = scanner.scan(&data).await?;
+
+    let ScanVerdict::Infected { signature } = &verdict else {
+        return Ok(verdict);
+    };
+
+    tracing::warn!(
+        "File {} for user {} is infected ({}), quarantining",
+        file.id,
+        file.user_id,
+        signature
+    );
+
+    let quarantine_key = format!("{}/{}", QUARANTINE_PREFIX, file.key);
+    storage.rename(&file.key, &quarantine_key).await?;
+
+    let user_id = file.user_id;
+    let file_name = file.name.clone();
+    let mut active_file = file.into_active_model();
+    active_file.key = Set(quarantine_key);
+    active_file.status = Set(FileStatus::Quarantined);
+    file_repository::update(&context, active_file).await?;
+    context.commit().await?;
+
+    let message = t!(
+        "avatar_upload.quarantined",
+        file_name = file_name,
+        signature = signature,
+        locale = locale
+    )
+    .to_string();
+    let data = json!({
+        "file_id": file_id,
+        "user_id": user_id,
+        "file_name": file_name,
+        "signature": signature,
+        "status": "quarantined",
+        "message": message,
+    });
+
+    // Notify both the task channel (if a client is tracking it) and the owner's user channel
+    let mut task_data = data.clone();
+    task_data["task_id"] = json!(task_id);
+    for data in [task_data, data] {
+        publish_broadcast(producer, FILE_QUARANTINED, data).await?;
+    }
+
+    Ok(verdict)
+}
+
+/// Generate resized variants of an available image and record them on the file
+pub async fn generate_thumbnails(
+    db: &DatabaseConnection,
+    producer: &dyn MessageProducer,
+    storage: &dyn ObjectStorage,
+    task_id: &str,
+    file_id: i32,
+    sizes: &[u32],
+) -> anyhow::Result<()> {
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    let file = file_repository::find_by_id(&context, file_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("File {} not found", file_id))?;
+    drop(context);
+
+    if file.status != FileStatus::Available {
+        tracing::info!(
+            "Skipping thumbnails for file {} with status {:?}",
+            file.id,
+            file.status
+        );
+        return Ok(());
+    }
+
+    let original = Arc::new(
+        storage
+            .get(&file.key)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Original object {} is missing", file.key))?,
+    );
+
+    let directory = file
+        .key
+        .rsplit_once('/')
+        .map_or("", |(directory, _)| directory);
+    let mut variants = serde_json::Map::new();
+
+    for (index, size) in sizes.iter().copied().enumerate() {
+        let data = original.clone();
+        let thumbnail =
+            tokio::task::spawn_blocking(move || generate_thumbnail(&data, size)).await??;
+
+        let variant_key = format!("{}/thumbnails/{}.png", directory, size);
+        storage.put(&variant_key, &thumbnail).await?;
+        variants.insert(size.to_string(), json!(variant_key));
+
+        let progress = ((index + 1) * 100 / sizes.len()) as u8;
+        let status = if progress == 100 {
+            "completed"
+        } else {
+            "processing"
+        };
+        publish_broadcast(
+            producer,
+            THUMBNAIL_PROGRESS,
+            json!({
+                "task_id": task_id,
+                "user_id": file.user_id,
+                "file_id": file.id,
+                "variant": size,
+                "progress": progress,
+                "status": status,
+            }),
+        )
+        .await?;
+        AVATAR_PIPELINE
+            .report(producer, task_id, file.user_id, "thumbnails", progress)
+            .await?;
+    }
+    if sizes.is_empty() {
+        AVATAR_PIPELINE
+            .report(producer, task_id, file.user_id, "thumbnails", 100)
+            .await?;
+    }
+
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    let mut active_file = file.into_active_model();
+    active_file.variants = Set(Some(serde_json::Value::Object(variants)));
+    file_repository::update(&context, active_file).await?;
+    context.commit().await?;
+
+    Ok(())
+}
+
+/// Mark an upload as available once all processing stages have passed. An auto-approved
+/// avatar then becomes its owner's; pending ones wait for an admin to approve them.
+pub async fn mark_available(db: &DatabaseConnection, file_id: i32) -> anyhow::Result<()> {
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+
+    let file = file_repository::find_by_id(&context, file_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("File {} not found", file_id))?;
+
+    if file.status == FileStatus::Pending {
+        let (user_id, approved) = (
+            file.user_id,
+            file.moderation_status == Some(ModerationStatus::Approved),
+        );
+        let mut active_file = file.into_active_model();
+        active_file.status = Set(FileStatus::Available);
+        file_repository::update(&context, active_file).await?;
+        if approved {
+            user_repository::set_avatar_file_id(&context, user_id, Some(file_id)).await?;
+        }
+    }
+
+    context.commit().await?;
+    Ok(())
+}
+
+/// Reconcile the file table against object storage.
+/// Objects without a record are deleted and records without an object are flagged as missing.
+pub async fn collect_orphaned_files(
+    db: &DatabaseConnection,
+    storage: &dyn ObjectStorage,
+    grace_period: chrono::Duration,
+) -> anyhow::Result<OrphanedFileReport> {
+    let cutoff = Utc::now() - grace_period;
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    let files = file_repository::find_all(&context).await?;
+
+    let referenced_keys: HashSet<String> = files
+        .iter()
+        .flat_map(|file| {
+            let variant_keys = file
+                .variants
+                .as_ref()
+                .and_then(|variants| variants.as_object())
+                .into_iter()
+                .flat_map(|variants| variants.values())
+                .filter_map(|key| key.as_str().map(str::to_string));
+            std::iter::once(file.key.clone()).chain(variant_keys)
+        })
+        .collect();
+
+    let objects = storage.list("").await?;
+    let stored_keys: HashSet<&str> = objects.iter().map(|object| object.key.as_str()).collect();
+
+    let mut report = OrphanedFileReport::default();
+
+    for object in &objects {
+        let is_recent = object
+            .last_modified
+            .is_none_or(|last_modified| last_modified > cutoff);
+        // Generated reports are kept in storage without a file record
+        if referenced_keys.contains(&object.key)
+            || object.key.starts_with(REPORT_PREFIX)
+            || is_recent
+        {
+            continue;
+        }
+
+        storage.delete(&object.key).await?;
+        tracing::info!("Deleted orphaned object {}", object.key);
+        report.deleted_objects.push(object.key.clone());
+    }
+
+    for file in files {
+        let is_recent = file
+            .created_at
+            .is_none_or(|created_at| created_at.and_utc() > cutoff);
+        if file.status == FileStatus::Missing
+            || stored_keys.contains(file.key.as_str())
+            || is_recent
+        {
+            continue;
+        }
+
+        tracing::warn!("Object {} for file {} is missing", file.key, file.id);
+        let mut active_file = file.into_active_model();
+        active_file.status = Set(FileStatus::Missing);
+        report
+            .missing_files
+            .push(file_repository::update(&context, active_file).await?);
+    }
+
+    context.commit().await?;
+    Ok(report)
+}
+
+/// Run orphaned file collection and email the report to all admins
+pub async fn cleanup_orphaned_files(
+    db: &DatabaseConnection,
+    producer: &dyn MessageProducer,
+    storage: &dyn ObjectStorage,
+) -> anyhow::Result<OrphanedFileReport> {
+    let report = collect_orphaned_files(
+        db,
+        storage,
+        chrono::Duration::minutes(ORPHAN_GRACE_PERIOD_MINUTES),
+    )
+    .await?;
+
+    tracing::info!(
+        "Orphaned file collection finished: {} objects deleted, {} files missing",
+        report.deleted_objects.len(),
+        report.missing_files.len()
+    );
+
+    send_orphaned_file_report(db, producer, &report).await?;
+    Ok(report)
+}
+
+/// Notify all admins of an orphaned file report on their report channels
+/// (skipped when there is nothing to report)
+pub async fn send_orphaned_file_report(
+    db: &DatabaseConnection,
+    producer: &dyn MessageProducer,
+    report: &OrphanedFileReport,
+) -> anyhow::Result<()> {
+    if report.is_empty() {
+        return Ok(());
+    }
+
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    let (admins, _) = user_repository::search(
+        &context,
+        &UserSearchParams {
+            role: Some(UserRole::Admin),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if admins.is_empty() {
+        tracing::warn!("No admins to send the orphaned file report to");
+        return Ok(());
+    }
+
+    let missing_files = report
+        .missing_files
+        .iter()
+        .map(|file| format!("#{} {} (user {})", file.id, file.key, file.user_id))
+        .collect::<Vec<_>>();
+
+    let notification = Notification::new(NotificationEvent::OrphanedFileReport)
+        .with_variable("deleted_count", report.deleted_objects.len().to_string())
+        .with_variable("deleted_objects", report.deleted_objects.join("\n"))
+        .with_variable("missing_count", report.missing_files.len().to_string())
+        .with_variable("missing_files", missing_files.join("\n"));
+    for admin in admins {
+        notification_service::dispatch(&context, producer, &admin, &notification).await?;
+    }
+
+    Ok(())
+}
+
+async fn publish_broadcast(
+    producer: &dyn MessageProducer,
+    event_type: EventType,
+    data: serde_json::Value,
+) -> anyhow::Result<()> {
+    let broadcast_msg = BroadcastMessage {
+        event_type: event_type.name.to_string(),
+        data,
+    };
+
+    producer
+        .publish_event_json(&serde_json::to_string(&broadcast_msg)?, Some("broadcasts"))
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to publish {}: {}", event_type.name, e))
+}
@@ -0,0 +1,66 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::{ActiveValue::Set, IntoActiveModel};
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+        layer::{
+            auth_layer::authorize_role,
+            response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+        },
+    },
+    file::{
+        entity::sea_orm_active_enums::{FileStatus, ModerationStatus},
+        repository::file_repository,
+    },
+    user::{entity::sea_orm_active_enums::UserRole, repository::user_repository},
+};
+
+use super::find_pending;
+
+/// Approve the pending avatar `id`, replacing the one its owner's profile shows
+pub async fn execute(context: &Context, id: i32) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+
+    let file = find_pending(context, id).await?;
+    // Avatars still being scanned could turn out to be quarantined
+    if file.status != FileStatus::Available {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::FileAvatarNotAvailable,
+            t!("file.avatar_not_available", locale = &context.locale).to_string(),
+        ));
+    }
+
+    let user_id = file.user_id;
+    let mut active_file = file.into_active_model();
+    active_file.moderation_status = Set(Some(ModerationStatus::Approved));
+    active_file.moderated_at = Set(Some(chrono::Utc::now().naive_utc()));
+    active_file.moderated_user_id = Set(Some(current_user.id));
+    file_repository::update(context, active_file)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    user_repository::set_avatar_file_id(context, user_id, Some(id))
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+
+    context
+        .emit(DomainEvent::AvatarApproved {
+            user_id,
+            file_id: id,
+            approved_by: current_user.id,
+        })
+        .await;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -0,0 +1,88 @@
+use rust_i18n::t;
+
+use crate::{
+    config::setting::Setting,
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+    },
+    file::{
+        dto::file_dto::{FileContentDTO, MediaParamsDTO},
+        entity::sea_orm_active_enums::FileStatus,
+        repository::file_repository,
+        service::media_service,
+    },
+    pkg::storage::ObjectStorage,
+    user::repository::user_repository,
+};
+
+/// Variant `variant` of the approved avatar of `user_id`, behind a signed link from the
+/// profile, for pages at `referer` allowed to embed it
+pub async fn execute(
+    context: &Context,
+    setting: &Setting,
+    storage: &dyn ObjectStorage,
+    user_id: i32,
+    variant: &str,
+    params: MediaParamsDTO,
+    referer: Option<&str>,
+) -> Result<FileContentDTO, ErrorDTO> {
+    if !setting.get_media_signer().verify(
+        &media_service::avatar_path(user_id, variant),
+        params.expires,
+        &params.signature,
+        chrono::Utc::now(),
+    ) {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::FileDownloadLinkInvalid,
+            t!("file.download_link_invalid", locale = &context.locale).to_string(),
+        ));
+    }
+
+    if !media_service::is_referer_allowed(setting, referer) {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::FileHotlinkForbidden,
+            t!("file.hotlink_forbidden", locale = &context.locale).to_string(),
+        ));
+    }
+
+    let not_found = || {
+        ErrorDTO::from_code(
+            ErrorCode::FileNotFound,
+            t!("file.not_found", locale = &context.locale).to_string(),
+        )
+    };
+
+    // The link names the user, so it serves whichever avatar is approved when it is opened
+    let avatar_file_id = user_repository::find_by_id(context, user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .and_then(|user| user.avatar_file_id)
+        .ok_or_else(not_found)?;
+    let avatar = file_repository::find_by_id(context, avatar_file_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|avatar| avatar.status == FileStatus::Available)
+        .ok_or_else(not_found)?;
+    let key = media_service::variant_key(&avatar, variant).ok_or_else(not_found)?;
+    let content = storage
+        .get(&key)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(not_found)?;
+
+    // Thumbnails are always generated as PNG
+    let content_type = if variant == media_service::ORIGINAL_VARIANT {
+        avatar
+            .content_type
+            .unwrap_or_else(|| "application/octet-stream".to_string())
+    } else {
+        "image/png".to_string()
+    };
+
+    Ok(FileContentDTO {
+        name: avatar.name,
+        content_type,
+        content,
+    })
+}
@@ -0,0 +1,38 @@
+pub mod approve_avatar_use_case;
+pub mod get_avatar_media_use_case;
+pub mod reject_avatar_use_case;
+pub mod search_pending_avatar_use_case;
+
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+    },
+    file::{
+        entity::{file, sea_orm_active_enums::ModerationStatus},
+        repository::file_repository,
+    },
+};
+
+/// The avatar `id`, as long as it is waiting for review
+async fn find_pending(context: &Context, id: i32) -> Result<file::Model, ErrorDTO> {
+    let file = file_repository::find_by_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::FileNotFound,
+                t!("file.not_found", locale = &context.locale).to_string(),
+            )
+        })?;
+
+    if file.moderation_status != Some(ModerationStatus::Pending) {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::FileAvatarNotPending,
+            t!("file.avatar_not_pending", locale = &context.locale).to_string(),
+        ));
+    }
+    Ok(file)
+}
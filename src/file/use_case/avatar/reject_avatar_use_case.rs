@@ -0,0 +1,83 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::{ActiveValue::Set, IntoActiveModel};
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+        layer::auth_layer::authorize_role,
+        translation::locale::DEFAULT_LOCALE,
+        validation::Validate,
+    },
+    file::{
+        dto::file_dto::RejectAvatarDTO, entity::sea_orm_active_enums::ModerationStatus,
+        repository::file_repository,
+    },
+    notification::service::{
+        notification_service::{self, Notification},
+        notification_template_service::NotificationEvent,
+    },
+    user::{entity::sea_orm_active_enums::UserRole, service::user_service},
+};
+
+use super::find_pending;
+
+/// Reject the pending avatar `id` and notify its owner, whose profile keeps showing the
+/// previous avatar
+pub async fn execute(
+    context: &Context,
+    id: i32,
+    dto: RejectAvatarDTO,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+    dto.validate(&context.locale)?;
+
+    let producer = context
+        .producer
+        .as_ref()
+        .ok_or_else(|| ErrorDTO::map_internal_error(anyhow::anyhow!("Producer not available")))?;
+
+    let file = find_pending(context, id).await?;
+    let owner = user_service::read(context, file.user_id).await?;
+    let file_name = file.name.clone();
+
+    let mut active_file = file.into_active_model();
+    active_file.moderation_status = Set(Some(ModerationStatus::Rejected));
+    active_file.rejection_reason = Set(dto.reason.clone());
+    active_file.moderated_at = Set(Some(chrono::Utc::now().naive_utc()));
+    active_file.moderated_user_id = Set(Some(current_user.id));
+    file_repository::update(context, active_file)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let reason = dto.reason.unwrap_or_else(|| {
+        let locale = owner.locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+        t!("file.avatar_rejection_default", locale = locale).to_string()
+    });
+    let notification = Notification::new(NotificationEvent::AvatarRejected)
+        .with_variable("first_name", owner.first_name.clone().unwrap_or_default())
+        .with_variable("file_name", file_name)
+        .with_variable("reason", reason)
+        .with_data("file_id", &id.to_string());
+    notification_service::dispatch(context, producer.as_ref().as_ref(), &owner, &notification)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    context
+        .emit(DomainEvent::AvatarRejected {
+            user_id: owner.id,
+            file_id: id,
+            rejected_by: current_user.id,
+        })
+        .await;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -0,0 +1,57 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    config::setting::Setting,
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        layer::auth_layer::authorize_role,
+    },
+    file::{
+        dto::file_dto::{PendingAvatarDTO, PendingAvatarListDTO},
+        entity::sea_orm_active_enums::FileStatus,
+        repository::file_repository,
+    },
+    pkg::storage::ObjectStorage,
+    user::entity::sea_orm_active_enums::UserRole,
+};
+
+pub async fn execute(
+    context: &Context,
+    setting: &Setting,
+    storage: &dyn ObjectStorage,
+) -> Result<ResponseDTO<PendingAvatarListDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+
+    let files = file_repository::find_pending_moderation(context)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let mut items = Vec::with_capacity(files.len());
+    for file in files {
+        let download_url = if file.status == FileStatus::Available {
+            storage
+                .presigned_url(&file.key, setting.storage_url_expiry())
+                .await
+                .map_err(ErrorDTO::map_internal_error)?
+        } else {
+            None
+        };
+        items.push(PendingAvatarDTO::new(file, download_url));
+    }
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        PendingAvatarListDTO {
+            count: items.len(),
+            items,
+        },
+    ))
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+    },
+    file::repository::file_repository,
+    pkg::storage::ObjectStorage,
+    user::repository::user_repository,
+};
+
+pub async fn execute(
+    context: &Context,
+    storage: Arc<dyn ObjectStorage>,
+    id: i32,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    // Files of other users are reported as missing rather than forbidden
+    let file = file_repository::find_by_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|file| file.user_id == current_user.id)
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::FileNotFound,
+                t!("file.not_found", locale = &context.locale).to_string(),
+            )
+        })?;
+
+    // A deleted avatar leaves the profile without one
+    user_repository::clear_avatar_file_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+    file_repository::delete_by_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let variant_keys = file
+        .variants
+        .as_ref()
+        .and_then(|variants| variants.as_object())
+        .into_iter()
+        .flat_map(|variants| variants.values())
+        .filter_map(|key| key.as_str().map(str::to_string));
+    let keys: Vec<String> = std::iter::once(file.key).chain(variant_keys).collect();
+    // Objects left behind by a failed delete are collected by the orphaned file cleanup
+    context.after_commit(async move {
+        for key in keys {
+            storage.delete(&key).await?;
+        }
+        Ok(())
+    });
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
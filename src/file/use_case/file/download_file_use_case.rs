@@ -0,0 +1,58 @@
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+    },
+    file::{
+        dto::file_dto::{FileContentDTO, FileDownloadParamsDTO},
+        repository::file_repository,
+    },
+    pkg::storage::{ObjectStorage, UrlSigner},
+};
+
+pub async fn execute(
+    context: &Context,
+    signer: &UrlSigner,
+    storage: &dyn ObjectStorage,
+    params: FileDownloadParamsDTO,
+) -> Result<FileContentDTO, ErrorDTO> {
+    if !signer.verify(
+        &params.key,
+        params.expires,
+        &params.signature,
+        chrono::Utc::now(),
+    ) {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::FileDownloadLinkInvalid,
+            t!("file.download_link_invalid", locale = &context.locale).to_string(),
+        ));
+    }
+
+    let not_found = || {
+        ErrorDTO::from_code(
+            ErrorCode::FileNotFound,
+            t!("file.not_found", locale = &context.locale).to_string(),
+        )
+    };
+
+    // The link outlives the file when it is deleted before it expires
+    let file = file_repository::find_by_key(context, &params.key)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(not_found)?;
+    let content = storage
+        .get(&file.key)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(not_found)?;
+
+    Ok(FileContentDTO {
+        name: file.name,
+        content_type: file
+            .content_type
+            .unwrap_or_else(|| "application/octet-stream".to_string()),
+        content,
+    })
+}
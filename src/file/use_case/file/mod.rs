@@ -0,0 +1,3 @@
+pub mod delete_user_file_use_case;
+pub mod download_file_use_case;
+pub mod search_user_file_use_case;
@@ -0,0 +1,55 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    config::setting::Setting,
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    file::{
+        dto::file_dto::{UserFileDTO, UserFileListDTO},
+        entity::sea_orm_active_enums::FileStatus,
+        repository::file_repository,
+    },
+    pkg::storage::ObjectStorage,
+};
+
+pub async fn execute(
+    context: &Context,
+    setting: &Setting,
+    storage: &dyn ObjectStorage,
+) -> Result<ResponseDTO<UserFileListDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let files = file_repository::find_by_user_id(context, current_user.id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let mut items = Vec::with_capacity(files.len());
+    for file in files {
+        // Pending files aren't scanned yet and the others have no object to hand out
+        let download_url = if file.status == FileStatus::Available {
+            storage
+                .presigned_url(&file.key, setting.storage_url_expiry())
+                .await
+                .map_err(ErrorDTO::map_internal_error)?
+        } else {
+            None
+        };
+        items.push(UserFileDTO::new(file, download_url));
+    }
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        UserFileListDTO {
+            count: items.len(),
+            items,
+        },
+    ))
+}
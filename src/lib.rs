@@ -1,9 +1,15 @@
 #[macro_use]
 extern crate rust_i18n;
-i18n!("src/core/translation/locales");
+i18n!("src/core/translation/locales", fallback = "en");
 
+#[cfg(feature = "client")]
+pub mod client;
 pub mod common;
 pub mod config;
 pub mod core;
+pub mod file;
+pub mod notification;
+pub mod oauth_provider;
+pub mod report;
 pub mod user;
 pub use pkg;
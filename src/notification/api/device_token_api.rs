@@ -0,0 +1,62 @@
+use crate::core::context::Context;
+use crate::core::dto::error_dto::ErrorDTO;
+use crate::core::dto::response_dto::ResponseDTO;
+use crate::notification::dto::device_token_dto::{
+    DeviceTokenCreateDTO, DeviceTokenDTO, DeviceTokenListDTO,
+};
+use crate::notification::use_case::device_token::{
+    create_device_token_use_case, delete_device_token_use_case, search_device_token_use_case,
+};
+use axum::extract::Path;
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notification/device-token/",
+    tags = ["Notification"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = DeviceTokenListDTO)),
+)]
+pub async fn search_device_token(
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<DeviceTokenListDTO>, ErrorDTO> {
+    search_device_token_use_case::execute(&context).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/notification/device-token/",
+    tags = ["Notification"],
+    security(("bearer_auth" = [])),
+    request_body(
+        content = DeviceTokenCreateDTO,
+        example = json!({ "token": "fcm-registration-token", "platform": "android" }),
+    ),
+    responses(
+        (status = StatusCode::CREATED, body = DeviceTokenDTO),
+        (status = StatusCode::OK, body = DeviceTokenDTO),
+    ),
+)]
+pub async fn create_device_token(
+    Extension(context): Extension<Context>,
+    Json(dto): Json<DeviceTokenCreateDTO>,
+) -> Result<ResponseDTO<DeviceTokenDTO>, ErrorDTO> {
+    create_device_token_use_case::execute(&context, dto).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/notification/device-token/{id}/",
+    tags = ["Notification"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn delete_device_token(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    delete_device_token_use_case::execute(&context, id).await
+}
@@ -0,0 +1,3 @@
+pub mod device_token_api;
+pub mod notification_api;
+pub mod notification_preference_api;
@@ -0,0 +1,43 @@
+use crate::core::context::Context;
+use crate::core::dto::error_dto::ErrorDTO;
+use crate::core::dto::response_dto::ResponseDTO;
+use crate::notification::dto::notification_dto::{
+    NotificationListDTO, NotificationSearchParamsDTO,
+};
+use crate::notification::use_case::notification::{
+    read_notification_use_case, search_notification_use_case,
+};
+use axum::Extension;
+use axum::extract::{Path, Query};
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notification/",
+    tags = ["Notification"],
+    security(("bearer_auth" = [])),
+    params(NotificationSearchParamsDTO),
+    responses((status = StatusCode::OK, body = NotificationListDTO)),
+)]
+pub async fn search_notification(
+    Extension(context): Extension<Context>,
+    Query(dto): Query<NotificationSearchParamsDTO>,
+) -> Result<ResponseDTO<NotificationListDTO>, ErrorDTO> {
+    search_notification_use_case::execute(&context, dto).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/notification/{id}/read/",
+    tags = ["Notification"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn read_notification(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    read_notification_use_case::execute(&context, id).await
+}
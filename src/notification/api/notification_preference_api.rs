@@ -0,0 +1,63 @@
+use crate::core::context::Context;
+use crate::core::dto::error_dto::ErrorDTO;
+use crate::core::dto::response_dto::ResponseDTO;
+use crate::notification::dto::notification_preference_dto::{
+    NotificationPreferenceDTO, NotificationPreferenceListDTO, NotificationPreferenceUpdateDTO,
+};
+use crate::notification::entity::sea_orm_active_enums::NotificationCategory;
+use crate::notification::use_case::notification_preference::{
+    delete_notification_preference_use_case, search_notification_preference_use_case,
+    update_notification_preference_use_case,
+};
+use axum::extract::Path;
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notification/preference/",
+    tags = ["Notification"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = NotificationPreferenceListDTO)),
+)]
+pub async fn search_notification_preference(
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<NotificationPreferenceListDTO>, ErrorDTO> {
+    search_notification_preference_use_case::execute(&context).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/notification/preference/{category}/",
+    tags = ["Notification"],
+    security(("bearer_auth" = [])),
+    params(("category" = NotificationCategory, Path)),
+    request_body(
+        content = NotificationPreferenceUpdateDTO,
+        example = json!({ "channels": ["email", "push"] }),
+    ),
+    responses((status = StatusCode::OK, body = NotificationPreferenceDTO)),
+)]
+pub async fn update_notification_preference(
+    Extension(context): Extension<Context>,
+    Path(category): Path<NotificationCategory>,
+    Json(dto): Json<NotificationPreferenceUpdateDTO>,
+) -> Result<ResponseDTO<NotificationPreferenceDTO>, ErrorDTO> {
+    update_notification_preference_use_case::execute(&context, category, dto).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/notification/preference/{category}/",
+    tags = ["Notification"],
+    security(("bearer_auth" = [])),
+    params(("category" = NotificationCategory, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn delete_notification_preference(
+    Extension(context): Extension<Context>,
+    Path(category): Path<NotificationCategory>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    delete_notification_preference_use_case::execute(&context, category).await
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::notification::entity::{device_token, sea_orm_active_enums::DevicePlatform};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeviceTokenDTO {
+    pub id: i32,
+    pub token: String,
+    pub platform: DevicePlatform,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub created_at: Option<NaiveDateTime>,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+impl From<device_token::Model> for DeviceTokenDTO {
+    fn from(model: device_token::Model) -> Self {
+        DeviceTokenDTO {
+            id: model.id,
+            token: model.token,
+            platform: model.platform,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeviceTokenListDTO {
+    pub items: Vec<DeviceTokenDTO>,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeviceTokenCreateDTO {
+    /// Registration token issued to the app by FCM or APNs
+    pub token: String,
+    pub platform: DevicePlatform,
+}
@@ -0,0 +1,3 @@
+pub mod device_token_dto;
+pub mod notification_dto;
+pub mod notification_preference_dto;
@@ -0,0 +1,47 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::notification::entity::{notification, sea_orm_active_enums::NotificationCategory};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationDTO {
+    pub id: i32,
+    pub category: NotificationCategory,
+    pub title: String,
+    pub body: String,
+    pub data: HashMap<String, String>,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub read_at: Option<NaiveDateTime>,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl From<notification::Model> for NotificationDTO {
+    fn from(model: notification::Model) -> Self {
+        NotificationDTO {
+            id: model.id,
+            category: model.category,
+            title: model.title,
+            body: model.body,
+            data: serde_json::from_value(model.data).unwrap_or_default(),
+            read_at: model.read_at,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationListDTO {
+    pub items: Vec<NotificationDTO>,
+    pub count: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationSearchParamsDTO {
+    /// Only list notifications that have not been read
+    #[param(default = false)]
+    pub unread: Option<bool>,
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::notification::entity::sea_orm_active_enums::{
+    NotificationCategory, NotificationChannel,
+};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationPreferenceDTO {
+    pub category: NotificationCategory,
+    /// Channels the category is delivered on, empty when opted out
+    pub channels: Vec<NotificationChannel>,
+    /// Whether the channels are the configured defaults rather than the user's choice
+    pub is_default: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationPreferenceListDTO {
+    pub items: Vec<NotificationPreferenceDTO>,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NotificationPreferenceUpdateDTO {
+    /// Channels to deliver the category on; `["none"]` opts out
+    pub channels: Vec<NotificationChannel>,
+}
@@ -0,0 +1,26 @@
+use super::sea_orm_active_enums::DevicePlatform;
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "device_token")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    #[sea_orm(unique)]
+    pub token: String,
+    pub platform: DevicePlatform,
+    pub created_at: Option<DateTime>,
+    pub updated_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "user_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub user: HasOne<crate::user::entity::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
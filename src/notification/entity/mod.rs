@@ -0,0 +1,5 @@
+pub mod device_token;
+pub mod notification;
+pub mod notification_preference;
+pub mod prelude;
+pub mod sea_orm_active_enums;
@@ -0,0 +1,33 @@
+use super::sea_orm_active_enums::NotificationCategory;
+use sea_orm::entity::prelude::*;
+
+/// In-app notification kept in the user's inbox until read
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "notification")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub category: NotificationCategory,
+    pub title: String,
+    #[sea_orm(column_type = "Text")]
+    pub body: String,
+    /// Custom key/value pairs as a JSON object
+    pub data: Json,
+    pub read_at: Option<DateTime>,
+    /// When the notification was included in a digest email
+    pub digested_at: Option<DateTime>,
+    pub created_at: Option<DateTime>,
+    pub updated_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "user_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub user: HasOne<crate::user::entity::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,26 @@
+use super::sea_orm_active_enums::NotificationCategory;
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "notification_preference")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub category: NotificationCategory,
+    /// Chosen channels as a JSON array (e.g. `["email", "push"]`), empty to opt out
+    pub channels: Json,
+    pub created_at: Option<DateTime>,
+    pub updated_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "user_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub user: HasOne<crate::user::entity::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
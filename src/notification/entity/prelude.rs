@@ -0,0 +1,3 @@
+pub use super::device_token::Entity as DeviceToken;
+pub use super::notification::Entity as Notification;
+pub use super::notification_preference::Entity as NotificationPreference;
@@ -0,0 +1,70 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumString};
+use utoipa::ToSchema;
+
+/// Push service a device token belongs to
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "lowercase")]
+pub enum DevicePlatform {
+    /// Delivered through Firebase Cloud Messaging
+    #[sea_orm(string_value = "android")]
+    Android,
+    /// Delivered through the Apple Push Notification service
+    #[sea_orm(string_value = "ios")]
+    Ios,
+}
+
+/// Kind of notification a user can route to their preferred channels
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    EnumString,
+    AsRefStr,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum NotificationCategory {
+    /// Account lifecycle, such as the welcome message
+    #[sea_orm(string_value = "account")]
+    Account,
+    /// Results of file uploads and processing
+    #[sea_orm(string_value = "upload")]
+    Upload,
+    /// Operational reports sent to admins
+    #[sea_orm(string_value = "report")]
+    Report,
+    /// Periodic summary of unread in-app notifications
+    #[sea_orm(string_value = "digest")]
+    Digest,
+}
+
+/// Way a notification reaches the user, stored in a preference's `channels` list
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum NotificationChannel {
+    Email,
+    Push,
+    /// Text message to the user's phone, once it has been verified
+    Sms,
+    /// Real-time message on the user's WebSocket
+    InApp,
+    /// Opt out of the category; cannot be combined with other channels
+    None,
+}
@@ -0,0 +1,63 @@
+use axum::{
+    Router,
+    routing::{delete, get, post, put},
+};
+
+use crate::{
+    config::app::AppState,
+    core::{
+        api::route::protected_api,
+        r#async::TaskType,
+        module::{Module, ScheduledJob},
+    },
+    notification::api::{device_token_api, notification_api, notification_preference_api},
+};
+
+/// In-app inbox, notification preferences, digests and push notification delivery
+pub struct NotificationModule;
+
+impl Module for NotificationModule {
+    fn name(&self) -> &'static str {
+        "notification"
+    }
+
+    fn routes(&self, app_state: &AppState) -> Router<AppState> {
+        let auth_route = Router::new()
+            .route(
+                "/api/v1/notification/",
+                get(notification_api::search_notification),
+            )
+            .route(
+                "/api/v1/notification/{id}/read/",
+                post(notification_api::read_notification),
+            )
+            .route(
+                "/api/v1/notification/device-token/",
+                get(device_token_api::search_device_token)
+                    .post(device_token_api::create_device_token),
+            )
+            .route(
+                "/api/v1/notification/device-token/{id}/",
+                delete(device_token_api::delete_device_token),
+            )
+            .route(
+                "/api/v1/notification/preference/",
+                get(notification_preference_api::search_notification_preference),
+            )
+            .route(
+                "/api/v1/notification/preference/{category}/",
+                put(notification_preference_api::update_notification_preference)
+                    .delete(notification_preference_api::delete_notification_preference),
+            );
+
+        protected_api(auth_route, app_state)
+    }
+
+    fn scheduled_jobs(&self) -> Vec<ScheduledJob> {
+        vec![ScheduledJob::new(
+            "send-notification-digests",
+            "0 0 * * * *", // Every hour
+            TaskType::SendNotificationDigests,
+        )]
+    }
+}
@@ -0,0 +1,65 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{core::context::Context, notification::entity::device_token};
+
+pub async fn find_by_id(context: &Context, id: i32) -> Result<Option<device_token::Model>, DbErr> {
+    device_token::Entity::find_by_id(id)
+        .one(context.txn())
+        .await
+}
+
+pub async fn find_by_token(
+    context: &Context,
+    token: &str,
+) -> Result<Option<device_token::Model>, DbErr> {
+    device_token::Entity::find()
+        .filter(device_token::Column::Token.eq(token))
+        .one(context.txn())
+        .await
+}
+
+pub async fn find_by_user_id(
+    context: &Context,
+    user_id: i32,
+) -> Result<Vec<device_token::Model>, DbErr> {
+    device_token::Entity::find()
+        .filter(device_token::Column::UserId.eq(user_id))
+        .order_by_asc(device_token::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+pub async fn create(
+    context: &Context,
+    mut device_token: device_token::ActiveModel,
+) -> Result<device_token::Model, DbErr> {
+    let now = chrono::Utc::now().naive_utc();
+    device_token.created_at = Set(Some(now));
+    device_token.updated_at = Set(Some(now));
+
+    device_token.insert(context.txn()).await
+}
+
+pub async fn update(
+    context: &Context,
+    mut device_token: device_token::ActiveModel,
+) -> Result<device_token::Model, DbErr> {
+    device_token.updated_at = Set(Some(chrono::Utc::now().naive_utc()));
+
+    device_token.update(context.txn()).await
+}
+
+pub async fn delete_by_id(context: &Context, id: i32) -> Result<(), DbErr> {
+    device_token::Entity::delete_by_id(id)
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_by_tokens(context: &Context, tokens: &[String]) -> Result<u64, DbErr> {
+    let result = device_token::Entity::delete_many()
+        .filter(device_token::Column::Token.is_in(tokens.iter().cloned()))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
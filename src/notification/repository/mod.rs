@@ -0,0 +1,3 @@
+pub mod device_token_repository;
+pub mod notification_preference_repository;
+pub mod notification_repository;
@@ -0,0 +1,62 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    core::context::Context,
+    notification::entity::{notification_preference, sea_orm_active_enums::NotificationCategory},
+};
+
+pub async fn find_by_user_id(
+    context: &Context,
+    user_id: i32,
+) -> Result<Vec<notification_preference::Model>, DbErr> {
+    notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.eq(user_id))
+        .order_by_asc(notification_preference::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+pub async fn find_by_user_and_category(
+    context: &Context,
+    user_id: i32,
+    category: NotificationCategory,
+) -> Result<Option<notification_preference::Model>, DbErr> {
+    notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.eq(user_id))
+        .filter(notification_preference::Column::Category.eq(category))
+        .one(context.txn())
+        .await
+}
+
+pub async fn create(
+    context: &Context,
+    mut preference: notification_preference::ActiveModel,
+) -> Result<notification_preference::Model, DbErr> {
+    let now = chrono::Utc::now().naive_utc();
+    preference.created_at = Set(Some(now));
+    preference.updated_at = Set(Some(now));
+
+    preference.insert(context.txn()).await
+}
+
+pub async fn update(
+    context: &Context,
+    mut preference: notification_preference::ActiveModel,
+) -> Result<notification_preference::Model, DbErr> {
+    preference.updated_at = Set(Some(chrono::Utc::now().naive_utc()));
+
+    preference.update(context.txn()).await
+}
+
+pub async fn delete_by_user_and_category(
+    context: &Context,
+    user_id: i32,
+    category: NotificationCategory,
+) -> Result<u64, DbErr> {
+    let result = notification_preference::Entity::delete_many()
+        .filter(notification_preference::Column::UserId.eq(user_id))
+        .filter(notification_preference::Column::Category.eq(category))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
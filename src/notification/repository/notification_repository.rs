@@ -0,0 +1,79 @@
+use chrono::NaiveDateTime;
+use sea_orm::{DbErr, entity::*, query::*, sea_query::Expr};
+
+use crate::{
+    core::context::Context,
+    notification::entity::{notification, sea_orm_active_enums::NotificationCategory},
+};
+
+pub async fn find_by_id(context: &Context, id: i32) -> Result<Option<notification::Model>, DbErr> {
+    notification::Entity::find_by_id(id)
+        .one(context.txn())
+        .await
+}
+
+/// Inbox of a user, newest first
+pub async fn find_by_user_id(
+    context: &Context,
+    user_id: i32,
+    unread_only: bool,
+) -> Result<Vec<notification::Model>, DbErr> {
+    let mut query = notification::Entity::find()
+        .filter(notification::Column::UserId.eq(user_id))
+        .order_by_desc(notification::Column::Id);
+    if unread_only {
+        query = query.filter(notification::Column::ReadAt.is_null());
+    }
+    query.all(context.txn()).await
+}
+
+/// Unread notifications created before `created_before` that no digest included yet
+pub async fn find_pending_digest(
+    context: &Context,
+    created_before: NaiveDateTime,
+) -> Result<Vec<notification::Model>, DbErr> {
+    notification::Entity::find()
+        .filter(notification::Column::ReadAt.is_null())
+        .filter(notification::Column::DigestedAt.is_null())
+        .filter(notification::Column::Category.ne(NotificationCategory::Digest))
+        .filter(notification::Column::CreatedAt.lt(created_before))
+        .order_by_asc(notification::Column::UserId)
+        .order_by_asc(notification::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+pub async fn create(
+    context: &Context,
+    mut notification: notification::ActiveModel,
+) -> Result<notification::Model, DbErr> {
+    let now = chrono::Utc::now().naive_utc();
+    notification.created_at = Set(Some(now));
+    notification.updated_at = Set(Some(now));
+
+    notification.insert(context.txn()).await
+}
+
+pub async fn mark_read(context: &Context, user_id: i32, ids: &[i32]) -> Result<u64, DbErr> {
+    let now = chrono::Utc::now().naive_utc();
+    let result = notification::Entity::update_many()
+        .col_expr(notification::Column::ReadAt, Expr::value(now))
+        .col_expr(notification::Column::UpdatedAt, Expr::value(now))
+        .filter(notification::Column::UserId.eq(user_id))
+        .filter(notification::Column::Id.is_in(ids.iter().copied()))
+        .filter(notification::Column::ReadAt.is_null())
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
+
+pub async fn mark_digested(context: &Context, ids: &[i32]) -> Result<u64, DbErr> {
+    let now = chrono::Utc::now().naive_utc();
+    let result = notification::Entity::update_many()
+        .col_expr(notification::Column::DigestedAt, Expr::value(now))
+        .col_expr(notification::Column::UpdatedAt, Expr::value(now))
+        .filter(notification::Column::Id.is_in(ids.iter().copied()))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
@@ -0,0 +1,250 @@
+use sea_orm::{ActiveValue::Set, DbErr};
+use std::collections::HashMap;
+
+use crate::{
+    config::setting::{MessageType, Setting},
+    core::{
+        r#async::{TaskType, publish_task_with_producer},
+        context::Context,
+        event_type,
+        translation::locale::DEFAULT_LOCALE,
+    },
+    notification::{
+        entity::{
+            notification, notification_preference,
+            sea_orm_active_enums::{NotificationCategory, NotificationChannel},
+        },
+        repository::{notification_preference_repository, notification_repository},
+        service::notification_template_service::{self, NotificationEvent, RenderedNotification},
+    },
+    pkg::{
+        broadcast::websocket::BroadcastMessage,
+        circuit_breaker::{self, BreakerState},
+        messaging::{
+            MessageProducer,
+            stats::{STALE_REPORT_INTERVALS, reported_dependency_down},
+        },
+        smtp::SMTP_BREAKER,
+    },
+    user::{entity::user, repository::user_email_repository},
+};
+
+/// Event delivered to a user on every channel they chose for its category.
+/// Only the event and its variables are given here; the wording for each channel comes
+/// from the template registry when the notification is dispatched.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    /// Values for the `%{name}` placeholders of the event's templates
+    pub variables: HashMap<String, String>,
+    /// Custom key/value pairs attached to push and in-app messages
+    pub data: HashMap<String, String>,
+    /// Locale the notification is rendered in, `None` for the recipient's preferred locale
+    pub locale: Option<String>,
+}
+
+impl Notification {
+    pub fn new(event: NotificationEvent) -> Self {
+        Self {
+            event,
+            variables: HashMap::new(),
+            data: HashMap::new(),
+            locale: None,
+        }
+    }
+
+    pub fn category(&self) -> NotificationCategory {
+        self.event.category()
+    }
+
+    pub fn with_variable(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.variables.insert(key.to_string(), value.into());
+        self
+    }
+
+    pub fn with_data(mut self, key: &str, value: &str) -> Self {
+        self.data.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = Some(locale.to_string());
+        self
+    }
+
+    fn render(
+        &self,
+        channel: NotificationChannel,
+        recipient: &user::Model,
+    ) -> anyhow::Result<RenderedNotification> {
+        let locale = self
+            .locale
+            .as_deref()
+            .or(recipient.locale.as_deref())
+            .unwrap_or(DEFAULT_LOCALE);
+        notification_template_service::render(self.event, channel, locale, &self.variables)
+    }
+}
+
+/// Channels stored in a preference (an empty list means the user opted out)
+pub fn preference_channels(
+    preference: &notification_preference::Model,
+) -> Vec<NotificationChannel> {
+    serde_json::from_value(preference.channels.clone()).unwrap_or_default()
+}
+
+/// Channels `user_id` receives `category` on, and whether they come from the defaults
+pub async fn resolve_channels(
+    context: &Context,
+    user_id: i32,
+    category: NotificationCategory,
+) -> Result<(Vec<NotificationChannel>, bool), DbErr> {
+    let preference =
+        notification_preference_repository::find_by_user_and_category(context, user_id, category)
+            .await?;
+
+    Ok(match preference {
+        Some(preference) => (preference_channels(&preference), false),
+        None => (Setting::new().notification.channels_for(category), true),
+    })
+}
+
+/// Whether the SMTP circuit breaker is open in this process or in every worker that
+/// reported recently, so an email task would only fail
+fn email_unavailable() -> bool {
+    circuit_breaker::find(SMTP_BREAKER).is_some_and(|breaker| breaker.state() == BreakerState::Open)
+        || Setting::new()
+            .messaging
+            .worker_stats_interval()
+            .is_some_and(|interval| {
+                reported_dependency_down(SMTP_BREAKER, interval * STALE_REPORT_INTERVALS)
+            })
+}
+
+/// Fan `notification` out to the channels the user chose for its category and return them
+pub async fn dispatch(
+    context: &Context,
+    producer: &dyn MessageProducer,
+    user: &user::Model,
+    notification: &Notification,
+) -> anyhow::Result<Vec<NotificationChannel>> {
+    let (channels, _) = resolve_channels(context, user.id, notification.category()).await?;
+
+    for channel in &channels {
+        match channel {
+            NotificationChannel::Email if email_unavailable() => {
+                // Left out rather than failing the request; the other channels still go out
+                tracing::warn!(
+                    "Skipping email for user {} while SMTP is unreachable",
+                    user.id
+                );
+                continue;
+            }
+            NotificationChannel::Email => {
+                let rendered = notification.render(*channel, user)?;
+                let (text_body, html_body) = match rendered.html_body {
+                    Some(html_body) => (None, Some(html_body)),
+                    None => (Some(rendered.body), None),
+                };
+                // Verified secondary addresses the user chose get a copy of each email
+                let secondary =
+                    user_email_repository::find_notification_addresses(context, user.id).await?;
+                for to in std::iter::once(user.email.clone()).chain(secondary) {
+                    publish_task_with_producer(
+                        context,
+                        producer,
+                        TaskType::SendEmail {
+                            to,
+                            subject: rendered.title.clone(),
+                            text_body: text_body.clone(),
+                            html_body: html_body.clone(),
+                        },
+                        Some(MessageType::Emails.as_ref()),
+                    )
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to publish email task: {}", e))?;
+                }
+            }
+            NotificationChannel::Push => {
+                let rendered = notification.render(*channel, user)?;
+                publish_task_with_producer(
+                    context,
+                    producer,
+                    TaskType::SendPushNotification {
+                        user_id: user.id,
+                        title: rendered.title,
+                        body: rendered.body,
+                        data: notification.data.clone(),
+                    },
+                    Some(MessageType::Tasks.as_ref()),
+                )
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to publish push task: {}", e))?;
+            }
+            NotificationChannel::Sms => {
+                // Unverified numbers may belong to someone else
+                let Some(phone) = user
+                    .phone
+                    .as_ref()
+                    .filter(|_| user.phone_verified_at.is_some())
+                else {
+                    tracing::debug!("Skipping SMS for user {} without a verified phone", user.id);
+                    continue;
+                };
+                let rendered = notification.render(*channel, user)?;
+                publish_task_with_producer(
+                    context,
+                    producer,
+                    TaskType::SendSms {
+                        to: phone.clone(),
+                        body: format!("{}: {}", rendered.title, rendered.body),
+                    },
+                    Some(MessageType::Tasks.as_ref()),
+                )
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to publish SMS task: {}", e))?;
+            }
+            NotificationChannel::InApp => {
+                let rendered = notification.render(*channel, user)?;
+                // Kept in the inbox so it can be read later or included in a digest
+                let inbox_notification = notification_repository::create(
+                    context,
+                    notification::ActiveModel {
+                        user_id: Set(user.id),
+                        category: Set(notification.category()),
+                        title: Set(rendered.title.clone()),
+                        body: Set(rendered.body.clone()),
+                        data: Set(serde_json::to_value(&notification.data)?),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+                let broadcast_msg = BroadcastMessage {
+                    event_type: event_type::NOTIFICATION.name.to_string(),
+                    data: serde_json::json!({
+                        "id": inbox_notification.id,
+                        "user_id": user.id,
+                        "category": notification.category(),
+                        "title": rendered.title,
+                        "body": rendered.body,
+                        "data": notification.data,
+                    }),
+                };
+                producer
+                    .publish_event_json(&serde_json::to_string(&broadcast_msg)?, Some("broadcasts"))
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to publish notification: {}", e))?;
+            }
+            NotificationChannel::None => {}
+        }
+    }
+
+    tracing::info!(
+        "Dispatched {} notification to user {} on {:?}",
+        notification.event.as_ref(),
+        user.id,
+        channels
+    );
+
+    Ok(channels)
+}
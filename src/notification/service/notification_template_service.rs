@@ -0,0 +1,186 @@
+use chrono::{Datelike, Utc};
+use std::collections::HashMap;
+use strum::AsRefStr;
+
+use crate::{
+    config::setting::Setting,
+    core::{
+        template::engine::render_email_template,
+        translation::{interpolate, try_translate},
+    },
+    notification::entity::sea_orm_active_enums::{NotificationCategory, NotificationChannel},
+};
+
+const APP_NAME: &str = "My Axum App";
+
+/// Kind of event a notification is sent for; its wording lives in the locale files under
+/// `notification_template.<event>` and, for email, in an HTML template
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum NotificationEvent {
+    /// Sent once an account has been registered
+    Welcome,
+    /// Result of the orphaned file sweep, sent to admins
+    OrphanedFileReport,
+    /// Summary of in-app notifications left unread
+    Digest,
+    /// Sign-in from a device or network the user hadn't used before
+    NewSignIn,
+    /// Uploaded avatar turned down by an admin
+    AvatarRejected,
+}
+
+impl NotificationEvent {
+    pub fn category(&self) -> NotificationCategory {
+        match self {
+            NotificationEvent::Welcome => NotificationCategory::Account,
+            NotificationEvent::OrphanedFileReport => NotificationCategory::Report,
+            NotificationEvent::Digest => NotificationCategory::Digest,
+            NotificationEvent::NewSignIn => NotificationCategory::Account,
+            NotificationEvent::AvatarRejected => NotificationCategory::Account,
+        }
+    }
+
+    /// HTML email template, rendered with the same variables as the title and body
+    fn email_template(&self) -> &'static str {
+        match self {
+            NotificationEvent::Welcome => "email/welcome.html",
+            NotificationEvent::OrphanedFileReport => "email/orphaned_files_report.html",
+            NotificationEvent::Digest => "email/notification_digest.html",
+            NotificationEvent::NewSignIn => "email/new_sign_in.html",
+            NotificationEvent::AvatarRejected => "email/avatar_rejected.html",
+        }
+    }
+}
+
+/// Content of a notification for one channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedNotification {
+    pub title: String,
+    pub body: String,
+    pub html_body: Option<String>,
+}
+
+/// Look up `notification_template.<event>.<channel>.<field>`, falling back to
+/// `notification_template.<event>.<field>` shared by every channel
+fn translate(
+    event: NotificationEvent,
+    channel: NotificationChannel,
+    field: &str,
+    locale: &str,
+) -> anyhow::Result<String> {
+    let channel_key = format!(
+        "notification_template.{}.{}.{}",
+        event.as_ref(),
+        channel.as_ref(),
+        field
+    );
+    let shared_key = format!("notification_template.{}.{}", event.as_ref(), field);
+
+    try_translate(locale, &channel_key)
+        .or_else(|| try_translate(locale, &shared_key))
+        .ok_or_else(|| anyhow::anyhow!("Missing notification template '{}'", shared_key))
+}
+
+/// Render `event` for `channel` in `locale`. `app_name`, `app_url` and `year` are
+/// provided to every template on top of `variables`.
+pub fn render(
+    event: NotificationEvent,
+    channel: NotificationChannel,
+    locale: &str,
+    variables: &HashMap<String, String>,
+) -> anyhow::Result<RenderedNotification> {
+    let mut variables = variables.clone();
+    variables
+        .entry("app_name".to_string())
+        .or_insert_with(|| APP_NAME.to_string());
+    variables
+        .entry("app_url".to_string())
+        .or_insert_with(|| Setting::new().app_url.clone());
+    variables
+        .entry("year".to_string())
+        .or_insert_with(|| Utc::now().year().to_string());
+
+    let title = interpolate(&translate(event, channel, "title", locale)?, &variables);
+    let body = interpolate(&translate(event, channel, "body", locale)?, &variables);
+    let html_body = match channel {
+        NotificationChannel::Email => Some(render_email_template(
+            event.email_template(),
+            locale,
+            variables,
+        )?),
+        _ => None,
+    };
+
+    Ok(RenderedNotification {
+        title,
+        body,
+        html_body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{NotificationEvent, render};
+    use crate::notification::entity::sea_orm_active_enums::NotificationChannel;
+
+    fn digest_variables() -> HashMap<String, String> {
+        HashMap::from([
+            ("count".to_string(), "2".to_string()),
+            ("first_name".to_string(), "John".to_string()),
+            ("notifications".to_string(), "• A: a\n• B: b".to_string()),
+        ])
+    }
+
+    #[test]
+    fn renders_shared_text_and_email_html() {
+        let rendered = render(
+            NotificationEvent::Digest,
+            NotificationChannel::Email,
+            "en",
+            &digest_variables(),
+        )
+        .unwrap();
+
+        assert_eq!(rendered.title, "You have 2 unread notification(s)");
+        assert_eq!(rendered.body, "• A: a\n• B: b");
+        assert!(rendered.html_body.unwrap().contains("John"));
+    }
+
+    #[test]
+    fn prefers_channel_specific_text() {
+        let rendered = render(
+            NotificationEvent::Digest,
+            NotificationChannel::Sms,
+            "en",
+            &digest_variables(),
+        )
+        .unwrap();
+
+        assert_eq!(rendered.body, "Open My Axum App to read them.");
+        assert!(rendered.html_body.is_none());
+    }
+
+    #[test]
+    fn renders_in_requested_locale() {
+        let english = render(
+            NotificationEvent::Welcome,
+            NotificationChannel::Push,
+            "en",
+            &HashMap::new(),
+        )
+        .unwrap();
+        let vietnamese = render(
+            NotificationEvent::Welcome,
+            NotificationChannel::Push,
+            "vi",
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(english.title, "Welcome to My Axum App!");
+        assert_ne!(english.title, vietnamese.title);
+    }
+}
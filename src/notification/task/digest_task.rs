@@ -0,0 +1,81 @@
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::{
+    config::setting::Setting,
+    core::context::Context,
+    notification::{
+        entity::notification,
+        repository::notification_repository,
+        service::{
+            notification_service::{self, Notification},
+            notification_template_service::NotificationEvent,
+        },
+    },
+    pkg::messaging::MessageProducer,
+    user::repository::user_repository,
+};
+
+/// Send each user one digest of the in-app notifications left unread for longer than
+/// the digest window, then mark them as digested so they are only summarized once.
+/// Nothing is sent during quiet hours; pending notifications wait for the next run.
+/// Returns the number of users a digest was delivered to.
+pub async fn send_notification_digests(
+    db: &DatabaseConnection,
+    producer: &dyn MessageProducer,
+    now: DateTime<Utc>,
+) -> anyhow::Result<usize> {
+    let setting = Setting::new();
+    if setting.notification.is_quiet_hour(now.hour()) {
+        tracing::info!("Skipping notification digests during quiet hours");
+        return Ok(0);
+    }
+
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    let created_before =
+        (now - Duration::minutes(setting.notification.digest_after_minutes)).naive_utc();
+    let pending = notification_repository::find_pending_digest(&context, created_before).await?;
+
+    let mut by_user: BTreeMap<i32, Vec<notification::Model>> = BTreeMap::new();
+    for notification in pending {
+        by_user
+            .entry(notification.user_id)
+            .or_default()
+            .push(notification);
+    }
+
+    let mut delivered = 0;
+    for (user_id, notifications) in by_user {
+        let Some(user) = user_repository::find_by_id(&context, user_id).await? else {
+            continue;
+        };
+
+        let lines = notifications
+            .iter()
+            .map(|notification| format!("• {}: {}", notification.title, notification.body))
+            .collect::<Vec<_>>();
+
+        let digest = Notification::new(NotificationEvent::Digest)
+            .with_variable("first_name", user.first_name.clone().unwrap_or_default())
+            .with_variable("count", notifications.len().to_string())
+            .with_variable("notifications", lines.join("\n"))
+            .with_variable("year", now.year().to_string());
+        let channels = notification_service::dispatch(&context, producer, &user, &digest).await?;
+        if !channels.is_empty() {
+            delivered += 1;
+        }
+
+        // Opted-out users are marked too, so their backlog is not reconsidered every run
+        let ids = notifications
+            .iter()
+            .map(|notification| notification.id)
+            .collect::<Vec<_>>();
+        notification_repository::mark_digested(&context, &ids).await?;
+    }
+
+    context.commit().await?;
+    tracing::info!("Sent notification digests to {} user(s)", delivered);
+    Ok(delivered)
+}
@@ -0,0 +1,2 @@
+pub mod digest_task;
+pub mod push_task;
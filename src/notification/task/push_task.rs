@@ -0,0 +1,172 @@
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use std::{sync::Arc, time::Duration};
+use tokio::time::sleep;
+
+use crate::{
+    config::setting::PushSetting,
+    core::context::Context,
+    notification::{
+        entity::sea_orm_active_enums::DevicePlatform, repository::device_token_repository,
+    },
+    pkg::{
+        http_client::HttpClient,
+        push::{PushNotification, PushOutcome, PushTransport},
+    },
+};
+
+/// Push providers by device platform, with the per-device retry policy
+#[derive(Clone)]
+pub struct PushDelivery {
+    fcm: Option<Arc<dyn PushTransport>>,
+    apns: Option<Arc<dyn PushTransport>>,
+    max_attempts: u32,
+    retry_delay: Duration,
+}
+
+impl Default for PushDelivery {
+    fn default() -> Self {
+        Self {
+            fcm: None,
+            apns: None,
+            max_attempts: 3,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl PushDelivery {
+    /// Providers configured in `setting`; a misconfigured APNs key disables iOS delivery
+    pub fn from_setting(setting: &PushSetting, http: &HttpClient) -> Self {
+        let apns = setting.get_apns_client(http).unwrap_or_else(|e| {
+            tracing::warn!("APNs disabled: {}", e);
+            None
+        });
+
+        Self::default()
+            .with_fcm(
+                setting
+                    .get_fcm_client(http)
+                    .map(|client| Arc::new(client) as Arc<dyn PushTransport>),
+            )
+            .with_apns(apns.map(|client| Arc::new(client) as Arc<dyn PushTransport>))
+            .with_retry(
+                setting.max_attempts,
+                Duration::from_millis(setting.retry_delay_ms),
+            )
+    }
+
+    /// Transport for Android devices (`None` disables them)
+    pub fn with_fcm(mut self, transport: Option<Arc<dyn PushTransport>>) -> Self {
+        self.fcm = transport;
+        self
+    }
+
+    /// Transport for iOS devices (`None` disables them)
+    pub fn with_apns(mut self, transport: Option<Arc<dyn PushTransport>>) -> Self {
+        self.apns = transport;
+        self
+    }
+
+    /// Attempts per device and the delay before the first retry, doubled on each retry
+    pub fn with_retry(mut self, max_attempts: u32, retry_delay: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.fcm.is_some() || self.apns.is_some()
+    }
+
+    fn transport(&self, platform: &DevicePlatform) -> Option<&dyn PushTransport> {
+        match platform {
+            DevicePlatform::Android => self.fcm.as_deref(),
+            DevicePlatform::Ios => self.apns.as_deref(),
+        }
+    }
+
+    async fn send_with_retry(
+        &self,
+        transport: &dyn PushTransport,
+        device_token: &str,
+        notification: &PushNotification,
+    ) -> anyhow::Result<PushOutcome> {
+        let mut attempt = 1;
+        loop {
+            match transport.send(device_token, notification).await {
+                Ok(outcome) => return Ok(outcome),
+                Err(e) if attempt >= self.max_attempts => return Err(e),
+                Err(e) => {
+                    tracing::warn!("Push attempt {} failed, retrying: {}", attempt, e);
+                    sleep(self.retry_delay * 2u32.pow(attempt - 1)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Send `notification` to every registered device of the user, retrying each device on
+/// its own and deleting tokens the push service reports as invalid.
+/// Fails only when no device could be reached, so the task is retried without
+/// duplicating notifications on devices that already received it.
+pub async fn send_push_notification(
+    db: &DatabaseConnection,
+    delivery: &PushDelivery,
+    user_id: i32,
+    notification: &PushNotification,
+) -> anyhow::Result<()> {
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    let devices = device_token_repository::find_by_user_id(&context, user_id).await?;
+    context.commit().await?;
+
+    let mut delivered = 0;
+    let mut failed = 0;
+    let mut invalid_tokens = Vec::new();
+    for device in &devices {
+        let Some(transport) = delivery.transport(&device.platform) else {
+            tracing::warn!(
+                "No push transport configured for {:?} device {}",
+                device.platform,
+                device.id
+            );
+            continue;
+        };
+
+        match delivery
+            .send_with_retry(transport, &device.token, notification)
+            .await
+        {
+            Ok(PushOutcome::Delivered) => delivered += 1,
+            Ok(PushOutcome::InvalidToken) => invalid_tokens.push(device.token.clone()),
+            Err(e) => {
+                tracing::error!("Failed to push to device {}: {:?}", device.id, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if !invalid_tokens.is_empty() {
+        let context = Context::builder(Arc::new(db.begin().await?)).build();
+        let deleted = device_token_repository::delete_by_tokens(&context, &invalid_tokens).await?;
+        context.commit().await?;
+        tracing::info!("Removed {} invalid device token(s)", deleted);
+    }
+
+    tracing::info!(
+        "Push notification for user {}: {} delivered, {} failed, {} invalid",
+        user_id,
+        delivered,
+        failed,
+        invalid_tokens.len()
+    );
+
+    if delivered == 0 && failed > 0 {
+        return Err(anyhow::anyhow!(
+            "Failed to push to any of {} device(s) of user {}",
+            failed,
+            user_id
+        ));
+    }
+    Ok(())
+}
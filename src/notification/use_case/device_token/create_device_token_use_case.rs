@@ -0,0 +1,73 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::{ActiveValue::Set, IntoActiveModel};
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    notification::{
+        dto::device_token_dto::{DeviceTokenCreateDTO, DeviceTokenDTO},
+        entity::device_token,
+        repository::device_token_repository,
+    },
+};
+
+/// Register a device for the current user. Re-registering a known token moves it to the
+/// current user, since a device that signs in to another account keeps its token.
+pub async fn execute(
+    context: &Context,
+    dto: DeviceTokenCreateDTO,
+) -> Result<ResponseDTO<DeviceTokenDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let token = dto.token.trim();
+    if token.is_empty() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::NotificationDeviceTokenRequired,
+            t!(
+                "notification.device_token_required",
+                locale = &context.locale
+            )
+            .to_string(),
+        ));
+    }
+
+    let existing = device_token_repository::find_by_token(context, token)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let (status, device_token) = match existing {
+        Some(existing) => {
+            let mut active = existing.into_active_model();
+            active.user_id = Set(current_user.id);
+            active.platform = Set(dto.platform);
+            let device_token = device_token_repository::update(context, active)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+            (StatusCode::OK, device_token)
+        }
+        None => {
+            let device_token = device_token_repository::create(
+                context,
+                device_token::ActiveModel {
+                    user_id: Set(current_user.id),
+                    token: Set(token.to_string()),
+                    platform: Set(dto.platform),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+            (StatusCode::CREATED, device_token)
+        }
+    };
+
+    Ok(ResponseDTO::new(status, DeviceTokenDTO::from(device_token)))
+}
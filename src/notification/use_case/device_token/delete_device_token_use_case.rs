@@ -0,0 +1,41 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    notification::repository::device_token_repository,
+};
+
+pub async fn execute(context: &Context, id: i32) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    // Devices of other users are reported as missing rather than forbidden
+    device_token_repository::find_by_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|device_token| device_token.user_id == current_user.id)
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::NotificationDeviceTokenNotFound,
+                t!(
+                    "notification.device_token_not_found",
+                    locale = &context.locale
+                )
+                .to_string(),
+            )
+        })?;
+
+    device_token_repository::delete_by_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -0,0 +1,3 @@
+pub mod create_device_token_use_case;
+pub mod delete_device_token_use_case;
+pub mod search_device_token_use_case;
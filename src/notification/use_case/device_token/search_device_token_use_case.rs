@@ -0,0 +1,38 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    notification::{
+        dto::device_token_dto::{DeviceTokenDTO, DeviceTokenListDTO},
+        repository::device_token_repository,
+    },
+};
+
+pub async fn execute(context: &Context) -> Result<ResponseDTO<DeviceTokenListDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let items: Vec<DeviceTokenDTO> =
+        device_token_repository::find_by_user_id(context, current_user.id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?
+            .into_iter()
+            .map(DeviceTokenDTO::from)
+            .collect();
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        DeviceTokenListDTO {
+            count: items.len(),
+            items,
+        },
+    ))
+}
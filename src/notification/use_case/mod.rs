@@ -0,0 +1,3 @@
+pub mod device_token;
+pub mod notification;
+pub mod notification_preference;
@@ -0,0 +1,2 @@
+pub mod read_notification_use_case;
+pub mod search_notification_use_case;
@@ -0,0 +1,38 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    notification::repository::notification_repository,
+};
+
+/// Mark a notification of the current user as read, which also keeps it out of digests
+pub async fn execute(context: &Context, id: i32) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    // Notifications of other users are reported as missing rather than forbidden
+    notification_repository::find_by_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|notification| notification.user_id == current_user.id)
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::NotificationNotFound,
+                t!("notification.not_found", locale = &context.locale).to_string(),
+            )
+        })?;
+
+    notification_repository::mark_read(context, current_user.id, &[id])
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
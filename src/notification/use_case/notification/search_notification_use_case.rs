@@ -0,0 +1,46 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    notification::{
+        dto::notification_dto::{
+            NotificationDTO, NotificationListDTO, NotificationSearchParamsDTO,
+        },
+        repository::notification_repository,
+    },
+};
+
+pub async fn execute(
+    context: &Context,
+    dto: NotificationSearchParamsDTO,
+) -> Result<ResponseDTO<NotificationListDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let items: Vec<NotificationDTO> = notification_repository::find_by_user_id(
+        context,
+        current_user.id,
+        dto.unread.unwrap_or(false),
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?
+    .into_iter()
+    .map(NotificationDTO::from)
+    .collect();
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        NotificationListDTO {
+            count: items.len(),
+            items,
+        },
+    ))
+}
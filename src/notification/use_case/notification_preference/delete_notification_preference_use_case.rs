@@ -0,0 +1,36 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    notification::{
+        entity::sea_orm_active_enums::NotificationCategory,
+        repository::notification_preference_repository,
+    },
+};
+
+/// Reset a category of the current user to the configured default channels
+pub async fn execute(
+    context: &Context,
+    category: NotificationCategory,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    notification_preference_repository::delete_by_user_and_category(
+        context,
+        current_user.id,
+        category,
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
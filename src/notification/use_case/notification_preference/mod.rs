@@ -0,0 +1,3 @@
+pub mod delete_notification_preference_use_case;
+pub mod search_notification_preference_use_case;
+pub mod update_notification_preference_use_case;
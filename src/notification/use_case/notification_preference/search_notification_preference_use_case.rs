@@ -0,0 +1,50 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::Iterable;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    notification::{
+        dto::notification_preference_dto::{
+            NotificationPreferenceDTO, NotificationPreferenceListDTO,
+        },
+        entity::sea_orm_active_enums::NotificationCategory,
+        service::notification_service,
+    },
+};
+
+/// Effective channels of every category, falling back to the configured defaults
+pub async fn execute(
+    context: &Context,
+) -> Result<ResponseDTO<NotificationPreferenceListDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let mut items = Vec::new();
+    for category in NotificationCategory::iter() {
+        let (channels, is_default) =
+            notification_service::resolve_channels(context, current_user.id, category)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+        items.push(NotificationPreferenceDTO {
+            category,
+            channels,
+            is_default,
+        });
+    }
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        NotificationPreferenceListDTO {
+            count: items.len(),
+            items,
+        },
+    ))
+}
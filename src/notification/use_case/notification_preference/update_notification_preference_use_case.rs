@@ -0,0 +1,102 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::{ActiveValue::Set, IntoActiveModel};
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    notification::{
+        dto::notification_preference_dto::{
+            NotificationPreferenceDTO, NotificationPreferenceUpdateDTO,
+        },
+        entity::{
+            notification_preference,
+            sea_orm_active_enums::{NotificationCategory, NotificationChannel},
+        },
+        repository::notification_preference_repository,
+    },
+};
+
+/// Choose the channels of a category for the current user; `none` opts out of it
+pub async fn execute(
+    context: &Context,
+    category: NotificationCategory,
+    dto: NotificationPreferenceUpdateDTO,
+) -> Result<ResponseDTO<NotificationPreferenceDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let mut channels: Vec<NotificationChannel> = Vec::new();
+    for channel in dto.channels {
+        if !channels.contains(&channel) {
+            channels.push(channel);
+        }
+    }
+
+    if channels.is_empty() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::NotificationChannelsRequired,
+            t!("notification.channels_required", locale = &context.locale).to_string(),
+        ));
+    }
+    if channels.contains(&NotificationChannel::None) {
+        if channels.len() > 1 {
+            return Err(ErrorDTO::from_code(
+                ErrorCode::NotificationChannelNoneExclusive,
+                t!(
+                    "notification.channel_none_exclusive",
+                    locale = &context.locale
+                )
+                .to_string(),
+            ));
+        }
+        channels.clear();
+    }
+
+    let channels_json = serde_json::to_value(&channels).map_err(ErrorDTO::map_internal_error)?;
+    let existing = notification_preference_repository::find_by_user_and_category(
+        context,
+        current_user.id,
+        category,
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    match existing {
+        Some(existing) => {
+            let mut active = existing.into_active_model();
+            active.channels = Set(channels_json);
+            notification_preference_repository::update(context, active)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+        }
+        None => {
+            notification_preference_repository::create(
+                context,
+                notification_preference::ActiveModel {
+                    user_id: Set(current_user.id),
+                    category: Set(category),
+                    channels: Set(channels_json),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+        }
+    }
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        NotificationPreferenceDTO {
+            category,
+            channels,
+            is_default: false,
+        },
+    ))
+}
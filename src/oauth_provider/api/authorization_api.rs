@@ -0,0 +1,86 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{
+    Extension, Json,
+    extract::{Path, Query},
+};
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    oauth_provider::{
+        dto::authorization_dto::{
+            AuthorizationDecisionDTO, AuthorizationRedirectDTO, AuthorizationRequestDTO,
+            ConsentScreenDTO, OAuthConsentListDTO,
+        },
+        use_case::authorization::{
+            authorize_use_case, get_consent_screen_use_case, revoke_oauth_consent_use_case,
+            search_oauth_consent_use_case,
+        },
+    },
+};
+
+/// Client and scopes of an authorization request, for the consent screen to show
+#[utoipa::path(
+    get,
+    path = "/api/v1/oauth/authorize/",
+    tags = ["OAuth"],
+    security(("bearer_auth" = [])),
+    params(AuthorizationRequestDTO),
+    responses((status = StatusCode::OK, body = ConsentScreenDTO)),
+)]
+pub async fn get_consent_screen(
+    Extension(context): Extension<Context>,
+    Query(request): Query<AuthorizationRequestDTO>,
+) -> Result<ResponseDTO<ConsentScreenDTO>, ErrorDTO> {
+    get_consent_screen_use_case::execute(&context, request).await
+}
+
+/// Approve or deny an authorization request; the consent screen then sends the user to
+/// `redirect_to`
+#[utoipa::path(
+    post,
+    path = "/api/v1/oauth/authorize/",
+    tags = ["OAuth"],
+    security(("bearer_auth" = [])),
+    request_body(content = AuthorizationDecisionDTO),
+    responses((status = StatusCode::OK, body = AuthorizationRedirectDTO)),
+)]
+pub async fn authorize(
+    Extension(context): Extension<Context>,
+    Json(decision): Json<AuthorizationDecisionDTO>,
+) -> Result<ResponseDTO<AuthorizationRedirectDTO>, ErrorDTO> {
+    authorize_use_case::execute(&context, decision).await
+}
+
+/// Clients the current user granted access to
+#[utoipa::path(
+    get,
+    path = "/api/v1/oauth/consents/",
+    tags = ["OAuth"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = OAuthConsentListDTO)),
+)]
+pub async fn search_oauth_consent(
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<OAuthConsentListDTO>, ErrorDTO> {
+    search_oauth_consent_use_case::execute(&context).await
+}
+
+/// Withdraw access granted to a client, revoking its tokens for the current user
+#[utoipa::path(
+    delete,
+    path = "/api/v1/oauth/consents/{id}/",
+    tags = ["OAuth"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn revoke_oauth_consent(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    revoke_oauth_consent_use_case::execute(&context, id).await
+}
@@ -0,0 +1,3 @@
+pub mod authorization_api;
+pub mod oauth_client_api;
+pub mod token_api;
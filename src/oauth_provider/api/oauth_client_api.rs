@@ -0,0 +1,63 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{Extension, Json, extract::Path};
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    oauth_provider::{
+        dto::oauth_client_dto::{OAuthClientCreateDTO, OAuthClientDTO, OAuthClientListDTO},
+        use_case::client::{
+            create_oauth_client_use_case, delete_oauth_client_use_case,
+            search_oauth_client_use_case,
+        },
+    },
+};
+
+/// Registered OAuth2 clients
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/oauth/clients/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = OAuthClientListDTO)),
+)]
+pub async fn search_oauth_client(
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<OAuthClientListDTO>, ErrorDTO> {
+    search_oauth_client_use_case::execute(&context).await
+}
+
+/// Register an OAuth2 client; the response carries its secret, which can't be read again
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/oauth/clients/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    request_body(content = OAuthClientCreateDTO),
+    responses((status = StatusCode::CREATED, body = OAuthClientDTO)),
+)]
+pub async fn create_oauth_client(
+    Extension(context): Extension<Context>,
+    Json(dto): Json<OAuthClientCreateDTO>,
+) -> Result<ResponseDTO<OAuthClientDTO>, ErrorDTO> {
+    create_oauth_client_use_case::execute(&context, dto).await
+}
+
+/// Delete an OAuth2 client, revoking every token issued to it
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/oauth/clients/{id}/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn delete_oauth_client(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    delete_oauth_client_use_case::execute(&context, id).await
+}
@@ -0,0 +1,57 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{Extension, Form, http::HeaderMap};
+
+use crate::{
+    core::{context::Context, dto::response_dto::ResponseDTO},
+    oauth_provider::{
+        dto::token_dto::{
+            AccessTokenDTO, IntrospectionDTO, IntrospectionRequestDTO, OAuthEndpointError,
+            OAuthErrorDTO, TokenRequestDTO,
+        },
+        use_case::token::{introspect_token_use_case, issue_token_use_case},
+    },
+};
+
+/// Token endpoint of the OAuth2 provider. Clients authenticate with HTTP Basic or the
+/// `client_id` and `client_secret` fields.
+#[utoipa::path(
+    post,
+    path = "/api/v1/oauth/token/",
+    tags = ["OAuth"],
+    request_body(content = TokenRequestDTO, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = StatusCode::OK, body = AccessTokenDTO),
+        (status = StatusCode::BAD_REQUEST, body = OAuthErrorDTO),
+        (status = StatusCode::UNAUTHORIZED, body = OAuthErrorDTO),
+    ),
+)]
+pub async fn issue_token(
+    Extension(context): Extension<Context>,
+    headers: HeaderMap,
+    Form(dto): Form<TokenRequestDTO>,
+) -> Result<ResponseDTO<AccessTokenDTO>, OAuthEndpointError> {
+    issue_token_use_case::execute(&context, &headers, dto).await
+}
+
+/// Whether an access token is active, and for whom, for services receiving it
+#[utoipa::path(
+    post,
+    path = "/api/v1/oauth/introspect/",
+    tags = ["OAuth"],
+    request_body(
+        content = IntrospectionRequestDTO,
+        content_type = "application/x-www-form-urlencoded"
+    ),
+    responses(
+        (status = StatusCode::OK, body = IntrospectionDTO),
+        (status = StatusCode::UNAUTHORIZED, body = OAuthErrorDTO),
+    ),
+)]
+pub async fn introspect_token(
+    Extension(context): Extension<Context>,
+    headers: HeaderMap,
+    Form(dto): Form<IntrospectionRequestDTO>,
+) -> Result<ResponseDTO<IntrospectionDTO>, OAuthEndpointError> {
+    introspect_token_use_case::execute(&context, &headers, dto).await
+}
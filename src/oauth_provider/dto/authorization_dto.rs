@@ -0,0 +1,79 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Authorization request of a client, as it redirected the user to the consent screen
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuthorizationRequestDTO {
+    /// Must be `code`
+    pub response_type: String,
+    pub client_id: String,
+    /// One of the client's redirect URIs, required when it has several
+    pub redirect_uri: Option<String>,
+    /// Space-separated scopes; every scope of the client when omitted
+    pub scope: Option<String>,
+    /// Opaque value sent back to the client with the code
+    pub state: Option<String>,
+    /// PKCE challenge the token request's `code_verifier` must match
+    pub code_challenge: Option<String>,
+    /// Must be `S256` when `code_challenge` is set
+    pub code_challenge_method: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OAuthScopeDTO {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConsentClientDTO {
+    pub client_id: String,
+    pub name: String,
+    pub trusted: bool,
+}
+
+/// What the consent screen shows for an authorization request
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConsentScreenDTO {
+    pub client: ConsentClientDTO,
+    pub scopes: Vec<OAuthScopeDTO>,
+    pub redirect_uri: String,
+    pub state: Option<String>,
+    /// `false` when the client is trusted or the user already granted these scopes, so the
+    /// request can be approved without asking
+    pub consent_required: bool,
+}
+
+/// The user's answer to an authorization request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuthorizationDecisionDTO {
+    #[serde(flatten)]
+    pub request: AuthorizationRequestDTO,
+    pub approve: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorizationRedirectDTO {
+    /// Client URL to send the user to, with the `code` or an `error`, and the `state`
+    pub redirect_to: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OAuthConsentDTO {
+    pub id: i32,
+    pub client_id: String,
+    pub client_name: String,
+    pub scopes: Vec<String>,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub created_at: Option<NaiveDateTime>,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OAuthConsentListDTO {
+    pub items: Vec<OAuthConsentDTO>,
+    pub count: usize,
+}
@@ -0,0 +1,3 @@
+pub mod authorization_dto;
+pub mod oauth_client_dto;
+pub mod token_dto;
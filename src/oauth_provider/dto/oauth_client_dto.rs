@@ -0,0 +1,65 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{core::validation::Validate, oauth_provider::entity::oauth_client};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OAuthClientDTO {
+    pub id: i32,
+    pub client_id: String,
+    /// Only returned when the client is registered; it can't be read again
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub scopes: Vec<String>,
+    pub grant_types: Vec<String>,
+    pub trusted: bool,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl From<oauth_client::Model> for OAuthClientDTO {
+    fn from(model: oauth_client::Model) -> Self {
+        OAuthClientDTO {
+            id: model.id,
+            client_id: model.client_id.clone(),
+            client_secret: None,
+            name: model.name.clone(),
+            redirect_uris: model.redirect_uris().map(str::to_string).collect(),
+            scopes: model.scopes().map(str::to_string).collect(),
+            grant_types: model
+                .grant_types
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+            trusted: model.trusted,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OAuthClientListDTO {
+    pub items: Vec<OAuthClientDTO>,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct OAuthClientCreateDTO {
+    #[validate(required, length(max = 255))]
+    pub name: String,
+    /// Absolute URLs authorization codes may be sent to, required for `authorization_code`
+    #[serde(default)]
+    pub redirect_uris: Vec<String>,
+    /// Scopes the client may request, among `OAUTH_PROVIDER_SCOPES`
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// `authorization_code`, `client_credentials` or both; `authorization_code` when empty
+    #[serde(default)]
+    pub grant_types: Vec<String>,
+    /// First-party clients skip the consent screen
+    #[serde(default)]
+    pub trusted: bool,
+}
@@ -0,0 +1,160 @@
+use axum::{
+    Json,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::core::dto::error_dto::{ErrorDTO, KeepChanges};
+
+/// Form of a token request; clients authenticate with HTTP Basic or `client_id` and
+/// `client_secret`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct TokenRequestDTO {
+    /// `authorization_code` or `client_credentials`
+    pub grant_type: String,
+    pub code: Option<String>,
+    /// Redirect URI the code was issued for, required with `authorization_code`
+    pub redirect_uri: Option<String>,
+    pub code_verifier: Option<String>,
+    /// Space-separated scopes of a `client_credentials` token; every scope of the client
+    /// when omitted
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccessTokenDTO {
+    pub access_token: String,
+    /// Always `Bearer`
+    pub token_type: String,
+    pub expires_in: i64,
+    pub scope: String,
+}
+
+/// Form of an introspection request, authenticated like token requests
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct IntrospectionRequestDTO {
+    pub token: String,
+    pub token_type_hint: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+/// State of a token (RFC 7662); inactive tokens only carry `active`
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct IntrospectionDTO {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Client the token was issued to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    /// Id of the user who authorized the client, absent for client credentials tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+}
+
+/// Error codes of the token and introspection endpoints (RFC 6749, section 5.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuthError {
+    InvalidRequest,
+    InvalidClient,
+    InvalidGrant,
+    UnauthorizedClient,
+    UnsupportedGrantType,
+    InvalidScope,
+}
+
+/// Error of the token and introspection endpoints, shaped as OAuth2 client libraries expect
+/// rather than as an `ErrorDTO`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OAuthErrorDTO {
+    pub error: OAuthError,
+    pub error_description: String,
+    #[serde(skip)]
+    pub keep_changes: bool,
+}
+
+impl OAuthErrorDTO {
+    pub fn new(error: OAuthError, error_description: String) -> Self {
+        Self {
+            error,
+            error_description,
+            keep_changes: false,
+        }
+    }
+
+    /// Commit what the request wrote before failing instead of rolling it back, e.g. tokens
+    /// revoked because their code was replayed
+    pub fn keep_changes(mut self) -> Self {
+        self.keep_changes = true;
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self.error {
+            OAuthError::InvalidClient => StatusCode::UNAUTHORIZED,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl IntoResponse for OAuthErrorDTO {
+    fn into_response(self) -> Response {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+        if self.error == OAuthError::InvalidClient {
+            headers.insert(
+                header::WWW_AUTHENTICATE,
+                HeaderValue::from_static("Basic realm=\"oauth\""),
+            );
+        }
+        let keep_changes = self.keep_changes;
+        let mut response = (self.status(), headers, Json(self)).into_response();
+        if keep_changes {
+            response.extensions_mut().insert(KeepChanges);
+        }
+        response
+    }
+}
+
+/// Failure of the token or introspection endpoint: an OAuth2 error the client caused, or an
+/// internal error answered as usual
+#[derive(Debug)]
+pub enum OAuthEndpointError {
+    OAuth(OAuthErrorDTO),
+    Internal(ErrorDTO),
+}
+
+impl From<OAuthErrorDTO> for OAuthEndpointError {
+    fn from(error: OAuthErrorDTO) -> Self {
+        Self::OAuth(error)
+    }
+}
+
+impl From<ErrorDTO> for OAuthEndpointError {
+    fn from(error: ErrorDTO) -> Self {
+        Self::Internal(error)
+    }
+}
+
+impl IntoResponse for OAuthEndpointError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::OAuth(error) => error.into_response(),
+            Self::Internal(error) => error.into_response(),
+        }
+    }
+}
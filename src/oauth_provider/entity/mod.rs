@@ -0,0 +1,5 @@
+pub mod oauth_access_token;
+pub mod oauth_authorization_code;
+pub mod oauth_client;
+pub mod oauth_consent;
+pub mod prelude;
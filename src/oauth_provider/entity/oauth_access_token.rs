@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "oauth_access_token")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Hash of the token
+    #[sea_orm(unique)]
+    pub token: String,
+    pub client_id: i32,
+    /// User who authorized the client, `None` for client credentials tokens
+    pub user_id: Option<i32>,
+    pub scope: String,
+    pub expires_at: DateTime,
+    /// Authorization code the token was issued for, `None` for client credentials tokens
+    pub authorization_code_id: Option<i32>,
+    pub created_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "client_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub client: HasOne<super::oauth_client::Entity>,
+    #[sea_orm(
+        belongs_to,
+        from = "user_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub user: HasOne<crate::user::entity::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "oauth_authorization_code")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Hash of the code
+    #[sea_orm(unique)]
+    pub code: String,
+    pub client_id: i32,
+    pub user_id: i32,
+    /// Redirect URI of the authorization request, which the exchange must repeat
+    #[sea_orm(column_type = "Text")]
+    pub redirect_uri: String,
+    pub scope: String,
+    /// PKCE `S256` challenge the exchange's verifier must match
+    pub code_challenge: Option<String>,
+    pub expires_at: DateTime,
+    /// When the code was exchanged; exchanging it again revokes the tokens it gave
+    pub used_at: Option<DateTime>,
+    pub created_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "client_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub client: HasOne<super::oauth_client::Entity>,
+    #[sea_orm(
+        belongs_to,
+        from = "user_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub user: HasOne<crate::user::entity::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
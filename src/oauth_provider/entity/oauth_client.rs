@@ -0,0 +1,53 @@
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "oauth_client")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Public identifier the client authenticates with
+    #[sea_orm(unique)]
+    pub client_id: String,
+    /// Hash of the client secret, which is only shown when the client is registered
+    pub client_secret: String,
+    pub name: String,
+    /// Space-separated URIs authorization codes may be sent to
+    #[sea_orm(column_type = "Text")]
+    pub redirect_uris: String,
+    /// Space-separated scopes the client may request
+    pub scopes: String,
+    /// Space-separated grant types the client may use
+    pub grant_types: String,
+    /// First-party clients whose authorization requests need no consent
+    pub trusted: bool,
+    pub created_by: Option<i32>,
+    pub created_at: Option<DateTime>,
+    pub updated_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "created_by",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    pub creator: HasOne<crate::user::entity::user::Entity>,
+}
+
+impl Model {
+    pub fn redirect_uris(&self) -> impl Iterator<Item = &str> {
+        self.redirect_uris.split_whitespace()
+    }
+
+    pub fn scopes(&self) -> impl Iterator<Item = &str> {
+        self.scopes.split_whitespace()
+    }
+
+    pub fn allows_grant(&self, grant_type: &str) -> bool {
+        self.grant_types
+            .split_whitespace()
+            .any(|allowed| allowed == grant_type)
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,4 @@
+pub use super::oauth_access_token::Entity as OAuthAccessToken;
+pub use super::oauth_authorization_code::Entity as OAuthAuthorizationCode;
+pub use super::oauth_client::Entity as OAuthClient;
+pub use super::oauth_consent::Entity as OAuthConsent;
@@ -0,0 +1,93 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use axum::{
+    Router,
+    middleware::from_fn_with_state,
+    routing::{delete, get, post},
+};
+
+use crate::{
+    config::app::AppState,
+    core::{
+        api::route::{protected_api, public_api},
+        r#async::PeriodicJob,
+        layer::permission_layer::{RequirePermission, require_permission_middleware},
+        module::Module,
+    },
+    oauth_provider::{
+        api::{authorization_api, oauth_client_api, token_api},
+        task::oauth_provider_task,
+    },
+};
+
+/// Authorization server letting registered clients act on behalf of users
+pub struct OAuthProviderModule;
+
+impl Module for OAuthProviderModule {
+    fn name(&self) -> &'static str {
+        "oauth_provider"
+    }
+
+    fn routes(&self, app_state: &AppState) -> Router<AppState> {
+        let admin_route = Router::new()
+            .route(
+                "/api/v1/admin/oauth/clients/",
+                get(oauth_client_api::search_oauth_client)
+                    .post(oauth_client_api::create_oauth_client),
+            )
+            .route(
+                "/api/v1/admin/oauth/clients/{id}/",
+                delete(oauth_client_api::delete_oauth_client),
+            )
+            .route_layer(from_fn_with_state(
+                RequirePermission("oauth_client.manage"),
+                require_permission_middleware,
+            ));
+        let auth_route = Router::new()
+            .route(
+                "/api/v1/oauth/authorize/",
+                get(authorization_api::get_consent_screen).post(authorization_api::authorize),
+            )
+            .route(
+                "/api/v1/oauth/consents/",
+                get(authorization_api::search_oauth_consent),
+            )
+            .route(
+                "/api/v1/oauth/consents/{id}/",
+                delete(authorization_api::revoke_oauth_consent),
+            )
+            .merge(admin_route);
+        // Clients authenticate with their own credentials rather than a user's token
+        let client_route = Router::new()
+            .route("/api/v1/oauth/token/", post(token_api::issue_token))
+            .route(
+                "/api/v1/oauth/introspect/",
+                post(token_api::introspect_token),
+            );
+
+        public_api(client_route, app_state).merge(protected_api(auth_route, app_state))
+    }
+
+    fn periodic_jobs(&self) -> Vec<Arc<dyn PeriodicJob>> {
+        vec![Arc::new(PurgeExpiredGrants)]
+    }
+}
+
+/// Delete authorization codes and access tokens past their expiry
+struct PurgeExpiredGrants;
+
+#[async_trait]
+impl PeriodicJob for PurgeExpiredGrants {
+    fn name(&self) -> &'static str {
+        "purge-expired-oauth-grants"
+    }
+
+    fn default_interval(&self) -> Duration {
+        Duration::from_secs(60 * 60)
+    }
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()> {
+        oauth_provider_task::purge_expired_grants(&app_state.db).await
+    }
+}
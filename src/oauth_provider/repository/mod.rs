@@ -0,0 +1,4 @@
+pub mod oauth_access_token_repository;
+pub mod oauth_authorization_code_repository;
+pub mod oauth_client_repository;
+pub mod oauth_consent_repository;
@@ -0,0 +1,65 @@
+use chrono::Utc;
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    config::setting::Setting, core::context::Context, oauth_provider::entity::oauth_access_token,
+};
+
+/// The token, as long as it hasn't expired
+pub async fn find_active_by_token(
+    context: &Context,
+    token: &str,
+) -> Result<Option<oauth_access_token::Model>, DbErr> {
+    oauth_access_token::Entity::find()
+        .filter(oauth_access_token::Column::Token.eq(Setting::new().token_hasher().hash(token)))
+        .filter(oauth_access_token::Column::ExpiresAt.gt(Utc::now().naive_utc()))
+        .one(context.txn())
+        .await
+}
+
+/// Insert `access_token`, storing the hash of its plaintext token
+pub async fn create(
+    context: &Context,
+    mut access_token: oauth_access_token::ActiveModel,
+) -> Result<oauth_access_token::Model, DbErr> {
+    if let ActiveValue::Set(token) = &access_token.token {
+        access_token.token = Set(Setting::new().token_hasher().hash(token));
+    }
+    access_token.created_at = Set(Some(Utc::now().naive_utc()));
+
+    access_token.insert(context.txn()).await
+}
+
+/// Revoke the tokens `user_id` authorized client `client_id` to use
+pub async fn delete_by_user_and_client(
+    context: &Context,
+    user_id: i32,
+    client_id: i32,
+) -> Result<u64, DbErr> {
+    let result = oauth_access_token::Entity::delete_many()
+        .filter(oauth_access_token::Column::UserId.eq(user_id))
+        .filter(oauth_access_token::Column::ClientId.eq(client_id))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// Revoke the tokens issued for authorization code `authorization_code_id`
+pub async fn delete_by_authorization_code_id(
+    context: &Context,
+    authorization_code_id: i32,
+) -> Result<u64, DbErr> {
+    let result = oauth_access_token::Entity::delete_many()
+        .filter(oauth_access_token::Column::AuthorizationCodeId.eq(authorization_code_id))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
+
+pub async fn delete_expired(context: &Context) -> Result<u64, DbErr> {
+    let result = oauth_access_token::Entity::delete_many()
+        .filter(oauth_access_token::Column::ExpiresAt.lt(Utc::now().naive_utc()))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
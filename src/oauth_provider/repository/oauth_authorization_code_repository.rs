@@ -0,0 +1,57 @@
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{DbErr, entity::*, query::*, sea_query::Expr};
+
+use crate::{
+    config::setting::Setting, core::context::Context,
+    oauth_provider::entity::oauth_authorization_code,
+};
+
+pub async fn find_by_code(
+    context: &Context,
+    code: &str,
+) -> Result<Option<oauth_authorization_code::Model>, DbErr> {
+    oauth_authorization_code::Entity::find()
+        .filter(oauth_authorization_code::Column::Code.eq(Setting::new().token_hasher().hash(code)))
+        .one(context.txn())
+        .await
+}
+
+/// Insert `authorization_code`, storing the hash of its plaintext code
+pub async fn create(
+    context: &Context,
+    mut authorization_code: oauth_authorization_code::ActiveModel,
+) -> Result<oauth_authorization_code::Model, DbErr> {
+    if let ActiveValue::Set(code) = &authorization_code.code {
+        authorization_code.code = Set(Setting::new().token_hasher().hash(code));
+    }
+    authorization_code.created_at = Set(Some(Utc::now().naive_utc()));
+
+    authorization_code.insert(context.txn()).await
+}
+
+/// Mark code `id` of client `client_id` used if it is unused and unexpired at `now`,
+/// returning whether it was. Only one of several exchanges of the same code gets `true`.
+pub async fn consume(
+    context: &Context,
+    id: i32,
+    client_id: i32,
+    now: NaiveDateTime,
+) -> Result<bool, DbErr> {
+    let result = oauth_authorization_code::Entity::update_many()
+        .col_expr(oauth_authorization_code::Column::UsedAt, Expr::value(now))
+        .filter(oauth_authorization_code::Column::Id.eq(id))
+        .filter(oauth_authorization_code::Column::ClientId.eq(client_id))
+        .filter(oauth_authorization_code::Column::ExpiresAt.gt(now))
+        .filter(oauth_authorization_code::Column::UsedAt.is_null())
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected == 1)
+}
+
+pub async fn delete_expired(context: &Context) -> Result<u64, DbErr> {
+    let result = oauth_authorization_code::Entity::delete_many()
+        .filter(oauth_authorization_code::Column::ExpiresAt.lt(Utc::now().naive_utc()))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
@@ -0,0 +1,87 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    config::setting::Setting,
+    core::{audit, context::Context},
+    oauth_provider::entity::oauth_client,
+};
+
+/// `resource_type` of the audit log entries written for clients
+const AUDIT_ENTITY: &str = "oauth_client";
+
+pub async fn find_by_id(context: &Context, id: i32) -> Result<Option<oauth_client::Model>, DbErr> {
+    oauth_client::Entity::find_by_id(id)
+        .one(context.txn())
+        .await
+}
+
+pub async fn find_by_client_id(
+    context: &Context,
+    client_id: &str,
+) -> Result<Option<oauth_client::Model>, DbErr> {
+    oauth_client::Entity::find()
+        .filter(oauth_client::Column::ClientId.eq(client_id))
+        .one(context.txn())
+        .await
+}
+
+/// The client `client_id`, as long as `client_secret` is its secret
+pub async fn find_by_credentials(
+    context: &Context,
+    client_id: &str,
+    client_secret: &str,
+) -> Result<Option<oauth_client::Model>, DbErr> {
+    let client = find_by_client_id(context, client_id).await?;
+    Ok(client.filter(|client| {
+        Setting::new()
+            .token_hasher()
+            .verify(client_secret, &client.client_secret)
+    }))
+}
+
+pub async fn find_by_ids(
+    context: &Context,
+    ids: &[i32],
+) -> Result<Vec<oauth_client::Model>, DbErr> {
+    oauth_client::Entity::find()
+        .filter(oauth_client::Column::Id.is_in(ids.iter().copied()))
+        .all(context.txn())
+        .await
+}
+
+pub async fn find_all(context: &Context) -> Result<Vec<oauth_client::Model>, DbErr> {
+    oauth_client::Entity::find()
+        .order_by_asc(oauth_client::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+/// Insert `client`, storing the hash of its plaintext secret
+pub async fn create(
+    context: &Context,
+    mut client: oauth_client::ActiveModel,
+) -> Result<oauth_client::Model, DbErr> {
+    if let ActiveValue::Set(secret) = &client.client_secret {
+        client.client_secret = Set(Setting::new().token_hasher().hash(secret));
+    }
+    let now = chrono::Utc::now().naive_utc();
+    client.created_at = Set(Some(now));
+    client.updated_at = Set(Some(now));
+
+    let client = client.insert(context.txn()).await?;
+    audit::record_create(context, AUDIT_ENTITY, &client).await?;
+
+    Ok(client)
+}
+
+/// Delete a client with the codes, tokens and consents issued to it
+pub async fn delete_by_id(context: &Context, id: i32) -> Result<(), DbErr> {
+    let Some(before) = find_by_id(context, id).await? else {
+        return Ok(());
+    };
+    oauth_client::Entity::delete_by_id(id)
+        .exec(context.txn())
+        .await?;
+    audit::record_delete(context, AUDIT_ENTITY, &before, None).await?;
+    Ok(())
+}
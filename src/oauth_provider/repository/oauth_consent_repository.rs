@@ -0,0 +1,71 @@
+use chrono::Utc;
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{core::context::Context, oauth_provider::entity::oauth_consent};
+
+pub async fn find_by_id(context: &Context, id: i32) -> Result<Option<oauth_consent::Model>, DbErr> {
+    oauth_consent::Entity::find_by_id(id)
+        .one(context.txn())
+        .await
+}
+
+pub async fn find_by_user_and_client(
+    context: &Context,
+    user_id: i32,
+    client_id: i32,
+) -> Result<Option<oauth_consent::Model>, DbErr> {
+    oauth_consent::Entity::find()
+        .filter(oauth_consent::Column::UserId.eq(user_id))
+        .filter(oauth_consent::Column::ClientId.eq(client_id))
+        .one(context.txn())
+        .await
+}
+
+/// Consents of `user_id`, oldest first
+pub async fn find_by_user_id(
+    context: &Context,
+    user_id: i32,
+) -> Result<Vec<oauth_consent::Model>, DbErr> {
+    oauth_consent::Entity::find()
+        .filter(oauth_consent::Column::UserId.eq(user_id))
+        .order_by_asc(oauth_consent::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+/// Record that `user_id` granted `scope` to client `client_id`, replacing an earlier consent
+pub async fn upsert(
+    context: &Context,
+    user_id: i32,
+    client_id: i32,
+    scope: &str,
+) -> Result<oauth_consent::Model, DbErr> {
+    let now = Utc::now().naive_utc();
+    match find_by_user_and_client(context, user_id, client_id).await? {
+        Some(consent) => {
+            let mut consent = consent.into_active_model();
+            consent.scope = Set(scope.to_string());
+            consent.updated_at = Set(Some(now));
+            consent.update(context.txn()).await
+        }
+        None => {
+            oauth_consent::ActiveModel {
+                user_id: Set(user_id),
+                client_id: Set(client_id),
+                scope: Set(scope.to_string()),
+                created_at: Set(Some(now)),
+                updated_at: Set(Some(now)),
+                ..Default::default()
+            }
+            .insert(context.txn())
+            .await
+        }
+    }
+}
+
+pub async fn delete_by_id(context: &Context, id: i32) -> Result<(), DbErr> {
+    oauth_consent::Entity::delete_by_id(id)
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use axum::http::{HeaderMap, header::AUTHORIZATION};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+        id::OtpAlphabet,
+    },
+    oauth_provider::{
+        dto::{
+            authorization_dto::{AuthorizationRequestDTO, OAuthScopeDTO},
+            token_dto::{OAuthError, OAuthErrorDTO},
+        },
+        entity::oauth_client,
+        repository::oauth_client_repository,
+    },
+};
+
+pub const AUTHORIZATION_CODE: &str = "authorization_code";
+pub const CLIENT_CREDENTIALS: &str = "client_credentials";
+
+/// Grant types clients can be registered with
+pub const GRANT_TYPES: &[&str] = &[AUTHORIZATION_CODE, CLIENT_CREDENTIALS];
+
+/// Lengths of the random values handed out to clients
+pub const CLIENT_ID_LENGTH: u32 = 24;
+pub const CLIENT_SECRET_LENGTH: u32 = 48;
+pub const CODE_LENGTH: u32 = 40;
+pub const ACCESS_TOKEN_LENGTH: u32 = 48;
+
+/// Random value of `length` characters for a client id, secret, code or token
+pub fn generate_secret(context: &Context, length: u32) -> String {
+    context
+        .id_generator
+        .otp_from(length, OtpAlphabet::Alphanumeric)
+}
+
+/// Scopes of `scope`, space-separated, deduplicated in the order given
+pub fn parse_scope(scope: &str) -> Vec<String> {
+    let mut scopes: Vec<String> = Vec::new();
+    for scope in scope.split_whitespace() {
+        if !scopes.iter().any(|known| known == scope) {
+            scopes.push(scope.to_string());
+        }
+    }
+    scopes
+}
+
+/// Scopes a request for `requested` grants `client`: all of the client's when omitted,
+/// else the first scope the client may not request as the error
+pub fn resolve_scopes(
+    client: &oauth_client::Model,
+    requested: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let requested = requested.map(parse_scope).unwrap_or_default();
+    if requested.is_empty() {
+        return Ok(client.scopes().map(str::to_string).collect());
+    }
+    match requested
+        .iter()
+        .find(|scope| !client.scopes().any(|allowed| allowed == scope.as_str()))
+    {
+        Some(scope) => Err(scope.clone()),
+        None => Ok(requested),
+    }
+}
+
+/// Whether `uri` can be registered as a redirect URI: an absolute http(s) URL without
+/// a fragment, compared verbatim with the ones authorization requests give
+pub fn is_valid_redirect_uri(uri: &str) -> bool {
+    let rest = uri
+        .strip_prefix("https://")
+        .or_else(|| uri.strip_prefix("http://"));
+    rest.is_some_and(|rest| {
+        !rest.is_empty()
+            && !rest.starts_with('/')
+            && !uri.contains('#')
+            && !uri.chars().any(char::is_whitespace)
+    })
+}
+
+/// `redirect_uri` with `params` added to its query
+pub fn redirect_uri_with(redirect_uri: &str, params: &[(&str, &str)]) -> String {
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    let separator = if redirect_uri.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", redirect_uri, separator, query)
+}
+
+/// Translated descriptions of `scopes` for the consent screen; scopes without a translation
+/// are described by their name
+pub fn describe_scopes(context: &Context, scopes: &[String]) -> Vec<OAuthScopeDTO> {
+    scopes
+        .iter()
+        .map(|scope| {
+            let key = format!("oauth_provider.scopes.{}", scope);
+            let description = t!(&key, locale = &context.locale).to_string();
+            OAuthScopeDTO {
+                name: scope.clone(),
+                description: if description == key {
+                    scope.clone()
+                } else {
+                    description
+                },
+            }
+        })
+        .collect()
+}
+
+/// An authorization request checked against its client: the client, the redirect URI the
+/// answer goes to and the scopes requested
+pub struct ValidAuthorizationRequest {
+    pub client: oauth_client::Model,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+}
+
+/// Check an authorization request. Errors are answered to the user rather than sent to the
+/// redirect URI, which can't be trusted before it is known to be the client's.
+pub async fn validate_authorization_request(
+    context: &Context,
+    request: &AuthorizationRequestDTO,
+) -> Result<ValidAuthorizationRequest, ErrorDTO> {
+    let client = oauth_client_repository::find_by_client_id(context, &request.client_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::OAuthClientNotFound,
+                t!("oauth_provider.client_not_found", locale = &context.locale).to_string(),
+            )
+        })?;
+
+    let redirect_uri = match &request.redirect_uri {
+        Some(uri) => client.redirect_uris().find(|allowed| allowed == uri),
+        None => {
+            let mut registered = client.redirect_uris();
+            registered.next().filter(|_| registered.next().is_none())
+        }
+    }
+    .map(str::to_string)
+    .ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::OAuthRedirectUriInvalid,
+            t!(
+                "oauth_provider.redirect_uri_invalid",
+                locale = &context.locale
+            )
+            .to_string(),
+        )
+    })?;
+
+    if request.response_type != "code" {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::OAuthResponseTypeUnsupported,
+            t!(
+                "oauth_provider.response_type_unsupported",
+                locale = &context.locale
+            )
+            .to_string(),
+        ));
+    }
+    if !client.allows_grant(AUTHORIZATION_CODE) {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::OAuthGrantTypeInvalid,
+            t!(
+                "oauth_provider.grant_type_unauthorized",
+                locale = &context.locale,
+                grant_type = AUTHORIZATION_CODE
+            )
+            .to_string(),
+        ));
+    }
+    if request.code_challenge.is_some()
+        && request.code_challenge_method.as_deref().unwrap_or("plain") != "S256"
+    {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::OAuthCodeChallengeInvalid,
+            t!(
+                "oauth_provider.code_challenge_invalid",
+                locale = &context.locale
+            )
+            .to_string(),
+        ));
+    }
+
+    let scopes = resolve_scopes(&client, request.scope.as_deref()).map_err(|scope| {
+        ErrorDTO::from_code(
+            ErrorCode::OAuthScopeInvalid,
+            t!(
+                "oauth_provider.scope_invalid",
+                locale = &context.locale,
+                scope = scope
+            )
+            .to_string(),
+        )
+    })?;
+
+    Ok(ValidAuthorizationRequest {
+        client,
+        redirect_uri,
+        scopes,
+    })
+}
+
+/// Credentials of the calling client, from an HTTP Basic `Authorization` header or else
+/// from the form
+pub fn client_credentials(
+    headers: &HeaderMap,
+    client_id: Option<&str>,
+    client_secret: Option<&str>,
+) -> Option<(String, String)> {
+    let basic = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Basic "))
+        .and_then(|encoded| STANDARD.decode(encoded.trim()).ok())
+        .and_then(|decoded| String::from_utf8(decoded).ok())
+        .and_then(|decoded| {
+            let (id, secret) = decoded.split_once(':')?;
+            Some((id.to_string(), secret.to_string()))
+        });
+    basic.or_else(|| Some((client_id?.to_string(), client_secret?.to_string())))
+}
+
+/// The client calling the token or introspection endpoint
+pub async fn authenticate_client(
+    context: &Context,
+    headers: &HeaderMap,
+    client_id: Option<&str>,
+    client_secret: Option<&str>,
+) -> Result<oauth_client::Model, OAuthErrorDTO> {
+    let invalid_client = || {
+        OAuthErrorDTO::new(
+            OAuthError::InvalidClient,
+            t!("oauth_provider.invalid_client", locale = &context.locale).to_string(),
+        )
+    };
+    let (client_id, client_secret) =
+        client_credentials(headers, client_id, client_secret).ok_or_else(invalid_client)?;
+
+    oauth_client_repository::find_by_credentials(context, &client_id, &client_secret)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to authenticate OAuth client: {}", e);
+            invalid_client()
+        })?
+        .ok_or_else(invalid_client)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_redirect_uri, parse_scope, redirect_uri_with};
+
+    #[test]
+    fn parses_scopes_once_in_order() {
+        assert_eq!(parse_scope(" email profile  email "), ["email", "profile"]);
+        assert!(parse_scope("").is_empty());
+    }
+
+    #[test]
+    fn accepts_absolute_http_redirect_uris_only() {
+        assert!(is_valid_redirect_uri("https://app.example.com/callback"));
+        assert!(is_valid_redirect_uri("http://localhost:3000/cb?x=1"));
+        assert!(!is_valid_redirect_uri("/callback"));
+        assert!(!is_valid_redirect_uri("https://"));
+        assert!(!is_valid_redirect_uri("https:///callback"));
+        assert!(!is_valid_redirect_uri("https://app.example.com/#frag"));
+        assert!(!is_valid_redirect_uri("javascript:alert(1)"));
+    }
+
+    #[test]
+    fn appends_params_to_redirect_uri() {
+        assert_eq!(
+            redirect_uri_with("https://app.example.com/cb", &[("code", "A B")]),
+            "https://app.example.com/cb?code=A+B"
+        );
+        assert_eq!(
+            redirect_uri_with("https://app.example.com/cb?x=1", &[("state", "s")]),
+            "https://app.example.com/cb?x=1&state=s"
+        );
+    }
+}
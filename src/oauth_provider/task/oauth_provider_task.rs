@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use sea_orm::{DatabaseConnection, TransactionTrait};
+
+use crate::{
+    core::context::Context,
+    oauth_provider::repository::{
+        oauth_access_token_repository, oauth_authorization_code_repository,
+    },
+};
+
+pub async fn purge_expired_grants(db: &DatabaseConnection) -> Result<(), anyhow::Error> {
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    let codes = oauth_authorization_code_repository::delete_expired(&context).await?;
+    let tokens = oauth_access_token_repository::delete_expired(&context).await?;
+    context.commit().await?;
+
+    if codes + tokens > 0 {
+        tracing::info!(
+            "Purged {} expired OAuth authorization code(s) and {} access token(s)",
+            codes,
+            tokens
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use rust_i18n::t;
+use sea_orm::ActiveValue::Set;
+
+use crate::{
+    config::setting::Setting,
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    oauth_provider::{
+        dto::authorization_dto::{AuthorizationDecisionDTO, AuthorizationRedirectDTO},
+        entity::oauth_authorization_code,
+        repository::{oauth_authorization_code_repository, oauth_consent_repository},
+        service::oauth_provider_service::{self, CODE_LENGTH},
+    },
+};
+
+/// Answer an authorization request for the current user. Approving remembers the consent and
+/// issues a code; either way the consent screen sends the user to `redirect_to`.
+pub async fn execute(
+    context: &Context,
+    decision: AuthorizationDecisionDTO,
+) -> Result<ResponseDTO<AuthorizationRedirectDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let request = decision.request;
+    let valid = oauth_provider_service::validate_authorization_request(context, &request).await?;
+
+    let code = if decision.approve {
+        let scope = valid.scopes.join(" ");
+        oauth_consent_repository::upsert(context, current_user.id, valid.client.id, &scope)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+
+        let code = oauth_provider_service::generate_secret(context, CODE_LENGTH);
+        let expires_at =
+            Utc::now().naive_utc() + Duration::seconds(Setting::new().oauth_provider.code_expires);
+        oauth_authorization_code_repository::create(
+            context,
+            oauth_authorization_code::ActiveModel {
+                code: Set(code.clone()),
+                client_id: Set(valid.client.id),
+                user_id: Set(current_user.id),
+                redirect_uri: Set(valid.redirect_uri.clone()),
+                scope: Set(scope),
+                code_challenge: Set(request.code_challenge.clone()),
+                expires_at: Set(expires_at),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+        Some(code)
+    } else {
+        None
+    };
+
+    let mut params = match &code {
+        Some(code) => vec![("code", code.as_str())],
+        None => vec![("error", "access_denied")],
+    };
+    if let Some(state) = &request.state {
+        params.push(("state", state.as_str()));
+    }
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        AuthorizationRedirectDTO {
+            redirect_to: oauth_provider_service::redirect_uri_with(&valid.redirect_uri, &params),
+        },
+    ))
+}
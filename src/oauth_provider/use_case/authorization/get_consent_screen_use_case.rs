@@ -0,0 +1,60 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    oauth_provider::{
+        dto::authorization_dto::{AuthorizationRequestDTO, ConsentClientDTO, ConsentScreenDTO},
+        repository::oauth_consent_repository,
+        service::oauth_provider_service,
+    },
+};
+
+/// What to show the current user for an authorization request
+pub async fn execute(
+    context: &Context,
+    request: AuthorizationRequestDTO,
+) -> Result<ResponseDTO<ConsentScreenDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let valid = oauth_provider_service::validate_authorization_request(context, &request).await?;
+
+    let consent = oauth_consent_repository::find_by_user_and_client(
+        context,
+        current_user.id,
+        valid.client.id,
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+    let consented = consent.is_some_and(|consent| {
+        valid.scopes.iter().all(|scope| {
+            consent
+                .scope
+                .split_whitespace()
+                .any(|granted| granted == scope)
+        })
+    });
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        ConsentScreenDTO {
+            scopes: oauth_provider_service::describe_scopes(context, &valid.scopes),
+            consent_required: !valid.client.trusted && !consented,
+            client: ConsentClientDTO {
+                client_id: valid.client.client_id,
+                name: valid.client.name,
+                trusted: valid.client.trusted,
+            },
+            redirect_uri: valid.redirect_uri,
+            state: request.state,
+        },
+    ))
+}
@@ -0,0 +1,4 @@
+pub mod authorize_use_case;
+pub mod get_consent_screen_use_case;
+pub mod revoke_oauth_consent_use_case;
+pub mod search_oauth_consent_use_case;
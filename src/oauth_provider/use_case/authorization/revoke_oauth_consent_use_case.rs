@@ -0,0 +1,45 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    oauth_provider::repository::{oauth_access_token_repository, oauth_consent_repository},
+};
+
+/// Withdraw a consent of the current user, revoking the access tokens the client holds for them
+pub async fn execute(context: &Context, id: i32) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    // Consents of other users are reported as missing rather than forbidden
+    let consent = oauth_consent_repository::find_by_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|consent| consent.user_id == current_user.id)
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::OAuthConsentNotFound,
+                t!("oauth_provider.consent_not_found", locale = &context.locale).to_string(),
+            )
+        })?;
+
+    oauth_consent_repository::delete_by_id(context, consent.id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    oauth_access_token_repository::delete_by_user_and_client(
+        context,
+        current_user.id,
+        consent.client_id,
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -0,0 +1,60 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    oauth_provider::{
+        dto::authorization_dto::{OAuthConsentDTO, OAuthConsentListDTO},
+        repository::{oauth_client_repository, oauth_consent_repository},
+    },
+};
+
+/// Clients the current user granted access to
+pub async fn execute(context: &Context) -> Result<ResponseDTO<OAuthConsentListDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let consents = oauth_consent_repository::find_by_user_id(context, current_user.id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    let client_ids: Vec<i32> = consents.iter().map(|consent| consent.client_id).collect();
+    let clients = oauth_client_repository::find_by_ids(context, &client_ids)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let items: Vec<OAuthConsentDTO> = consents
+        .into_iter()
+        .filter_map(|consent| {
+            let client = clients
+                .iter()
+                .find(|client| client.id == consent.client_id)?;
+            Some(OAuthConsentDTO {
+                id: consent.id,
+                client_id: client.client_id.clone(),
+                client_name: client.name.clone(),
+                scopes: consent
+                    .scope
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+                created_at: consent.created_at,
+                updated_at: consent.updated_at,
+            })
+        })
+        .collect();
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        OAuthConsentListDTO {
+            count: items.len(),
+            items,
+        },
+    ))
+}
@@ -0,0 +1,132 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::ActiveValue::Set;
+
+use crate::{
+    config::setting::Setting,
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        validation::Validate,
+    },
+    oauth_provider::{
+        dto::oauth_client_dto::{OAuthClientCreateDTO, OAuthClientDTO},
+        entity::oauth_client,
+        repository::oauth_client_repository,
+        service::oauth_provider_service::{
+            self, AUTHORIZATION_CODE, CLIENT_ID_LENGTH, CLIENT_SECRET_LENGTH, GRANT_TYPES,
+        },
+    },
+};
+
+/// Register a client. Its secret is only returned here, the database keeps a hash.
+pub async fn execute(
+    context: &Context,
+    dto: OAuthClientCreateDTO,
+) -> Result<ResponseDTO<OAuthClientDTO>, ErrorDTO> {
+    context.require_permission("oauth_client.manage")?;
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    dto.validate(&context.locale)?;
+
+    let grant_types = if dto.grant_types.is_empty() {
+        vec![AUTHORIZATION_CODE.to_string()]
+    } else {
+        dto.grant_types
+    };
+    if let Some(grant_type) = grant_types
+        .iter()
+        .find(|grant_type| !GRANT_TYPES.contains(&grant_type.as_str()))
+    {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::OAuthGrantTypeInvalid,
+            t!(
+                "oauth_provider.grant_type_invalid",
+                locale = &context.locale,
+                grant_type = grant_type
+            )
+            .to_string(),
+        ));
+    }
+
+    let available_scopes = Setting::new().oauth_provider.scopes;
+    let scopes = if dto.scopes.is_empty() {
+        available_scopes.clone()
+    } else {
+        dto.scopes
+    };
+    if let Some(scope) = scopes
+        .iter()
+        .find(|scope| !available_scopes.contains(scope))
+    {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::OAuthScopeInvalid,
+            t!(
+                "oauth_provider.scope_invalid",
+                locale = &context.locale,
+                scope = scope
+            )
+            .to_string(),
+        ));
+    }
+
+    if let Some(uri) = dto
+        .redirect_uris
+        .iter()
+        .find(|uri| !oauth_provider_service::is_valid_redirect_uri(uri))
+    {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::OAuthRedirectUriInvalid,
+            t!(
+                "oauth_provider.redirect_uri_malformed",
+                locale = &context.locale,
+                uri = uri
+            )
+            .to_string(),
+        ));
+    }
+    // Codes have nowhere to go without a redirect URI
+    if dto.redirect_uris.is_empty() && grant_types.iter().any(|g| g == AUTHORIZATION_CODE) {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::OAuthRedirectUriInvalid,
+            t!(
+                "oauth_provider.redirect_uri_required",
+                locale = &context.locale
+            )
+            .to_string(),
+        ));
+    }
+
+    let client_secret = oauth_provider_service::generate_secret(context, CLIENT_SECRET_LENGTH);
+    let client = oauth_client_repository::create(
+        context,
+        oauth_client::ActiveModel {
+            client_id: Set(oauth_provider_service::generate_secret(
+                context,
+                CLIENT_ID_LENGTH,
+            )),
+            client_secret: Set(client_secret.clone()),
+            name: Set(dto.name.trim().to_string()),
+            redirect_uris: Set(dto.redirect_uris.join(" ")),
+            scopes: Set(scopes.join(" ")),
+            grant_types: Set(grant_types.join(" ")),
+            trusted: Set(dto.trusted),
+            created_by: Set(Some(current_user.id)),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(
+        StatusCode::CREATED,
+        OAuthClientDTO {
+            client_secret: Some(client_secret),
+            ..OAuthClientDTO::from(client)
+        },
+    ))
+}
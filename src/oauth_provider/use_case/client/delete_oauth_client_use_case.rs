@@ -0,0 +1,31 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    oauth_provider::repository::oauth_client_repository,
+};
+
+/// Delete the client `id`; its codes, tokens and consents go with it
+pub async fn execute(context: &Context, id: i32) -> Result<ResponseDTO<()>, ErrorDTO> {
+    context.require_permission("oauth_client.manage")?;
+
+    oauth_client_repository::find_by_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::OAuthClientNotFound,
+                t!("oauth_provider.client_not_found", locale = &context.locale).to_string(),
+            )
+        })?;
+
+    oauth_client_repository::delete_by_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -0,0 +1,3 @@
+pub mod create_oauth_client_use_case;
+pub mod delete_oauth_client_use_case;
+pub mod search_oauth_client_use_case;
@@ -0,0 +1,29 @@
+use axum::http::StatusCode;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    oauth_provider::{
+        dto::oauth_client_dto::{OAuthClientDTO, OAuthClientListDTO},
+        repository::oauth_client_repository,
+    },
+};
+
+pub async fn execute(context: &Context) -> Result<ResponseDTO<OAuthClientListDTO>, ErrorDTO> {
+    context.require_permission("oauth_client.manage")?;
+
+    let clients = oauth_client_repository::find_all(context)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    let items: Vec<OAuthClientDTO> = clients.into_iter().map(OAuthClientDTO::from).collect();
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        OAuthClientListDTO {
+            count: items.len(),
+            items,
+        },
+    ))
+}
@@ -0,0 +1,3 @@
+pub mod authorization;
+pub mod client;
+pub mod token;
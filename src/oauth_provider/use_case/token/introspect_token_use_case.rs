@@ -0,0 +1,78 @@
+use axum::http::{HeaderMap, StatusCode};
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    oauth_provider::{
+        dto::token_dto::{IntrospectionDTO, IntrospectionRequestDTO, OAuthEndpointError},
+        repository::{oauth_access_token_repository, oauth_client_repository},
+        service::oauth_provider_service,
+    },
+    user::repository::user_repository,
+};
+
+/// State of an access token, for services receiving it (RFC 7662). Any registered client
+/// may ask; unknown, expired and revoked tokens are all reported as inactive.
+pub async fn execute(
+    context: &Context,
+    headers: &HeaderMap,
+    dto: IntrospectionRequestDTO,
+) -> Result<ResponseDTO<IntrospectionDTO>, OAuthEndpointError> {
+    oauth_provider_service::authenticate_client(
+        context,
+        headers,
+        dto.client_id.as_deref(),
+        dto.client_secret.as_deref(),
+    )
+    .await?;
+
+    let Some(access_token) =
+        oauth_access_token_repository::find_active_by_token(context, &dto.token)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?
+    else {
+        return Ok(ResponseDTO::new(
+            StatusCode::OK,
+            IntrospectionDTO::default(),
+        ));
+    };
+
+    let client = oauth_client_repository::find_by_id(context, access_token.client_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    let user = match access_token.user_id {
+        Some(user_id) => user_repository::find_by_id(context, user_id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?,
+        None => None,
+    };
+    // Tokens of deactivated users stop working without being revoked
+    if access_token.user_id.is_some()
+        && user
+            .as_ref()
+            .is_none_or(|user| user.deactivated_at.is_some())
+    {
+        return Ok(ResponseDTO::new(
+            StatusCode::OK,
+            IntrospectionDTO::default(),
+        ));
+    }
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        IntrospectionDTO {
+            active: true,
+            scope: Some(access_token.scope),
+            client_id: client.map(|client| client.client_id),
+            sub: access_token.user_id.map(|user_id| user_id.to_string()),
+            username: user.map(|user| user.email),
+            token_type: Some("Bearer".to_string()),
+            exp: Some(access_token.expires_at.and_utc().timestamp()),
+            iat: access_token
+                .created_at
+                .map(|created_at| created_at.and_utc().timestamp()),
+        },
+    ))
+}
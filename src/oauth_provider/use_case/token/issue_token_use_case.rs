@@ -0,0 +1,201 @@
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use chrono::{Duration, Utc};
+use rust_i18n::t;
+use sea_orm::ActiveValue::Set;
+
+use crate::{
+    config::setting::Setting,
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    oauth_provider::{
+        dto::token_dto::{
+            AccessTokenDTO, OAuthEndpointError, OAuthError, OAuthErrorDTO, TokenRequestDTO,
+        },
+        entity::{oauth_access_token, oauth_authorization_code, oauth_client},
+        repository::{oauth_access_token_repository, oauth_authorization_code_repository},
+        service::oauth_provider_service::{
+            self, ACCESS_TOKEN_LENGTH, AUTHORIZATION_CODE, CLIENT_CREDENTIALS,
+        },
+    },
+    pkg::pkce,
+};
+
+/// Exchange an authorization code, or the client's own credentials, for an access token
+pub async fn execute(
+    context: &Context,
+    headers: &HeaderMap,
+    dto: TokenRequestDTO,
+) -> Result<ResponseDTO<AccessTokenDTO>, OAuthEndpointError> {
+    let grant_type = dto.grant_type.as_str();
+    if grant_type != AUTHORIZATION_CODE && grant_type != CLIENT_CREDENTIALS {
+        return Err(OAuthErrorDTO::new(
+            OAuthError::UnsupportedGrantType,
+            t!(
+                "oauth_provider.grant_type_invalid",
+                locale = &context.locale,
+                grant_type = grant_type
+            )
+            .to_string(),
+        )
+        .into());
+    }
+
+    let client = oauth_provider_service::authenticate_client(
+        context,
+        headers,
+        dto.client_id.as_deref(),
+        dto.client_secret.as_deref(),
+    )
+    .await?;
+    if !client.allows_grant(grant_type) {
+        return Err(OAuthErrorDTO::new(
+            OAuthError::UnauthorizedClient,
+            t!(
+                "oauth_provider.grant_type_unauthorized",
+                locale = &context.locale,
+                grant_type = grant_type
+            )
+            .to_string(),
+        )
+        .into());
+    }
+
+    let (user_id, scope, authorization_code_id) = if grant_type == AUTHORIZATION_CODE {
+        let code = exchange_code(context, &client, &dto).await?;
+        (Some(code.user_id), code.scope, Some(code.id))
+    } else {
+        let scopes = oauth_provider_service::resolve_scopes(&client, dto.scope.as_deref())
+            .map_err(|scope| {
+                OAuthErrorDTO::new(
+                    OAuthError::InvalidScope,
+                    t!(
+                        "oauth_provider.scope_invalid",
+                        locale = &context.locale,
+                        scope = scope
+                    )
+                    .to_string(),
+                )
+            })?;
+        (None, scopes.join(" "), None)
+    };
+
+    let expires_in = Setting::new().oauth_provider.access_token_expires;
+    let access_token = oauth_provider_service::generate_secret(context, ACCESS_TOKEN_LENGTH);
+    oauth_access_token_repository::create(
+        context,
+        oauth_access_token::ActiveModel {
+            token: Set(access_token.clone()),
+            client_id: Set(client.id),
+            user_id: Set(user_id),
+            authorization_code_id: Set(authorization_code_id),
+            scope: Set(scope.clone()),
+            expires_at: Set(Utc::now().naive_utc() + Duration::seconds(expires_in)),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    // Tokens must not be kept by caches along the way (RFC 6749, section 5.1)
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    response_headers.insert(header::PRAGMA, HeaderValue::from_static("no-cache"));
+
+    Ok(ResponseDTO::with_headers(
+        StatusCode::OK,
+        AccessTokenDTO {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in,
+            scope,
+        },
+        response_headers,
+    ))
+}
+
+/// Consume the code of the request, returning it. The code must have been issued to `client`
+/// for the same redirect URI, and match the PKCE challenge it was requested with. A code
+/// exchanged before revokes the tokens it gave (RFC 6749, section 4.1.2).
+async fn exchange_code(
+    context: &Context,
+    client: &oauth_client::Model,
+    dto: &TokenRequestDTO,
+) -> Result<oauth_authorization_code::Model, OAuthEndpointError> {
+    let code = dto.code.as_deref().ok_or_else(|| {
+        OAuthErrorDTO::new(
+            OAuthError::InvalidRequest,
+            t!(
+                "oauth_provider.invalid_request",
+                locale = &context.locale,
+                parameter = "code"
+            )
+            .to_string(),
+        )
+    })?;
+    // Codes are always issued for a redirect URI, which RFC 6749 then requires here
+    let redirect_uri = dto.redirect_uri.as_deref().ok_or_else(|| {
+        OAuthErrorDTO::new(
+            OAuthError::InvalidRequest,
+            t!(
+                "oauth_provider.invalid_request",
+                locale = &context.locale,
+                parameter = "redirect_uri"
+            )
+            .to_string(),
+        )
+    })?;
+    let invalid_grant = || {
+        OAuthErrorDTO::new(
+            OAuthError::InvalidGrant,
+            t!("oauth_provider.invalid_grant", locale = &context.locale).to_string(),
+        )
+    };
+
+    let now = Utc::now().naive_utc();
+    let record = oauth_authorization_code_repository::find_by_code(context, code)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|record| {
+            record.client_id == client.id
+                && record.expires_at > now
+                && record.redirect_uri == redirect_uri
+        })
+        .ok_or_else(invalid_grant)?;
+
+    if record.used_at.is_some() {
+        oauth_access_token_repository::delete_by_authorization_code_id(context, record.id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+        return Err(invalid_grant().keep_changes().into());
+    }
+
+    if let Some(challenge) = &record.code_challenge {
+        let verified = dto
+            .code_verifier
+            .as_deref()
+            .is_some_and(|verifier| pkce::verify_s256(verifier, challenge));
+        if !verified {
+            return Err(OAuthErrorDTO::new(
+                OAuthError::InvalidGrant,
+                t!(
+                    "oauth_provider.invalid_code_verifier",
+                    locale = &context.locale
+                )
+                .to_string(),
+            )
+            .into());
+        }
+    }
+
+    // Codes are single use, even when exchanges of the same code race
+    let consumed = oauth_authorization_code_repository::consume(context, record.id, client.id, now)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    if !consumed {
+        return Err(invalid_grant().into());
+    }
+
+    Ok(record)
+}
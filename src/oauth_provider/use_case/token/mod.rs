@@ -0,0 +1,2 @@
+pub mod introspect_token_use_case;
+pub mod issue_token_use_case;
@@ -0,0 +1 @@
+pub mod report_api;
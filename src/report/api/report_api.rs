@@ -0,0 +1,51 @@
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{
+    Extension,
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    config::app::AppState,
+    core::{context::Context, dto::error_dto::ErrorDTO},
+    report::use_case::report::download_report_use_case,
+};
+
+/// Download a report generated by the scheduled `GenerateReport` task, as linked in its email
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/reports/{name}/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("name" = String, Path)),
+    responses(
+        (status = StatusCode::OK, content_type = "text/csv", body = String),
+        (status = StatusCode::OK, content_type = "text/html", body = String),
+    ),
+)]
+pub async fn download_report(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+    Path(name): Path<String>,
+) -> Result<Response, ErrorDTO> {
+    let storage = app_state.setting.get_storage();
+    let report = download_report_use_case::execute(&context, &storage, &name).await?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (
+                header::CONTENT_TYPE,
+                report.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", report.name),
+            ),
+        ],
+        report.content,
+    )
+        .into_response())
+}
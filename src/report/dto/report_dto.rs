@@ -0,0 +1,110 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Aggregation included in the scheduled admin report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportAggregation {
+    /// Users registered per UTC day
+    NewUsersPerDay,
+    /// Completed, retried and failed runs per task type
+    TaskFailureRates,
+}
+
+impl FromStr for ReportAggregation {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "new_users_per_day" => Ok(Self::NewUsersPerDay),
+            "task_failure_rates" => Ok(Self::TaskFailureRates),
+            other => Err(format!("Unknown report aggregation: {}", other)),
+        }
+    }
+}
+
+/// File format the report is rendered to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Csv,
+    Html,
+}
+
+impl ReportFormat {
+    /// Extension of the stored artifact, which also picks the template
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Html => "html",
+        }
+    }
+
+    /// Format of a stored artifact, from the extension of its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.rsplit_once('.')?.1 {
+            "csv" => Some(Self::Csv),
+            "html" => Some(Self::Html),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Html => "text/html; charset=utf-8",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "html" => Ok(Self::Html),
+            other => Err(format!("Unknown report format: {}", other)),
+        }
+    }
+}
+
+/// Users registered on one day
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyCountDTO {
+    /// `YYYY-MM-DD`
+    pub day: String,
+    pub count: i64,
+}
+
+/// Final outcomes of one task type; retries are counted separately from them
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TaskFailureRateDTO {
+    pub task_type: String,
+    pub completed: i64,
+    pub retried: i64,
+    pub failed: i64,
+    /// Share of finished runs that failed, between 0 and 1
+    pub failure_rate: f64,
+}
+
+/// Aggregations of one report, `None` for the ones not configured
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReportDTO {
+    /// Start of the covered period, `YYYY-MM-DD HH:MM:SS` UTC
+    pub period_start: String,
+    pub period_end: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_users_per_day: Option<Vec<DailyCountDTO>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_failure_rates: Option<Vec<TaskFailureRateDTO>>,
+}
+
+/// A stored report, as downloaded by admins
+#[derive(Debug)]
+pub struct ReportFileDTO {
+    pub name: String,
+    pub format: ReportFormat,
+    pub content: Vec<u8>,
+}
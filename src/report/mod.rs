@@ -0,0 +1,8 @@
+pub mod api;
+pub mod dto;
+mod module;
+pub mod repository;
+pub mod task;
+pub mod use_case;
+
+pub use module::ReportModule;
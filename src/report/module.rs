@@ -0,0 +1,52 @@
+use axum::{Router, routing::get};
+
+use crate::{
+    config::{app::AppState, setting::ReportSetting},
+    core::{
+        api::route::protected_api,
+        r#async::TaskType,
+        module::{Module, ScheduledJob},
+    },
+    report::api::report_api,
+};
+
+/// Scheduled admin reports and their downloads
+pub struct ReportModule {
+    setting: ReportSetting,
+}
+
+impl ReportModule {
+    pub fn new(setting: &ReportSetting) -> Self {
+        Self {
+            setting: setting.clone(),
+        }
+    }
+}
+
+impl Module for ReportModule {
+    fn name(&self) -> &'static str {
+        "report"
+    }
+
+    fn routes(&self, app_state: &AppState) -> Router<AppState> {
+        protected_api(
+            Router::new().route(
+                "/api/v1/admin/reports/{name}/",
+                get(report_api::download_report),
+            ),
+            app_state,
+        )
+    }
+
+    fn scheduled_jobs(&self) -> Vec<ScheduledJob> {
+        if !self.setting.is_enabled() {
+            return Vec::new();
+        }
+
+        vec![ScheduledJob::new(
+            "generate-report",
+            self.setting.schedule.clone(), // REPORT_SCHEDULE, daily at 06:00 by default
+            TaskType::GenerateReport,
+        )]
+    }
+}
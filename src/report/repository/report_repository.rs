@@ -0,0 +1,58 @@
+use chrono::NaiveDateTime;
+use sea_orm::{DbErr, FromQueryResult, entity::*, query::*, sea_query::Expr};
+
+use crate::{common::entity::task_event_log, core::context::Context, user::entity::user};
+
+#[derive(Debug, FromQueryResult)]
+pub struct DailyCount {
+    /// `YYYY-MM-DD`
+    pub day: String,
+    pub count: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+pub struct TaskStatusCount {
+    pub task_type: Option<String>,
+    pub status: String,
+    pub count: i64,
+}
+
+/// Users created since `since`, counted per UTC day, oldest day first
+pub async fn count_new_users_per_day(
+    context: &Context,
+    since: NaiveDateTime,
+) -> Result<Vec<DailyCount>, DbErr> {
+    let day = Expr::cust("CAST(DATE(created_at) AS TEXT)");
+
+    user::Entity::find()
+        .select_only()
+        .column_as(day.clone(), "day")
+        .column_as(user::Column::Id.count(), "count")
+        .filter(user::Column::CreatedAt.gte(since))
+        .group_by(day.clone())
+        .order_by_asc(day)
+        .into_model::<DailyCount>()
+        .all(context.txn())
+        .await
+}
+
+/// `completed`, `retrying` and `failed` entries recorded since `since`, counted per task
+/// type and status
+pub async fn count_task_outcomes(
+    context: &Context,
+    since: NaiveDateTime,
+) -> Result<Vec<TaskStatusCount>, DbErr> {
+    task_event_log::Entity::find()
+        .select_only()
+        .column(task_event_log::Column::TaskType)
+        .column(task_event_log::Column::Status)
+        .column_as(task_event_log::Column::Id.count(), "count")
+        .filter(task_event_log::Column::Status.is_in(["completed", "retrying", "failed"]))
+        .filter(task_event_log::Column::CreatedAt.gte(since))
+        .group_by(task_event_log::Column::TaskType)
+        .group_by(task_event_log::Column::Status)
+        .order_by_asc(task_event_log::Column::TaskType)
+        .into_model::<TaskStatusCount>()
+        .all(context.txn())
+        .await
+}
@@ -0,0 +1,192 @@
+use chrono::{DateTime, Datelike, Utc};
+use rust_i18n::t;
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    config::setting::{MessageType, ReportSetting},
+    core::{
+        r#async::{TaskEvent, TaskType},
+        context::Context,
+        template::engine::{render_email_template, render_template},
+    },
+    pkg::{messaging::MessageProducer, storage::ObjectStorage},
+    report::{
+        dto::report_dto::{
+            DailyCountDTO, ReportAggregation, ReportDTO, ReportFormat, TaskFailureRateDTO,
+        },
+        repository::report_repository::{self, TaskStatusCount},
+    },
+};
+
+/// Storage prefix that generated reports are stored under
+pub const REPORT_PREFIX: &str = "reports/";
+
+const APP_NAME: &str = "My Axum App";
+
+/// A report stored in object storage
+#[derive(Debug)]
+pub struct GeneratedReport {
+    /// File name, as used in the download link
+    pub name: String,
+    pub key: String,
+    pub link: String,
+}
+
+/// Aggregate the configured period up to `now`, store the rendered report and email a link
+/// to it to the configured recipients
+pub async fn generate_report(
+    db: &DatabaseConnection,
+    producer: &dyn MessageProducer,
+    storage: &dyn ObjectStorage,
+    setting: &ReportSetting,
+    now: DateTime<Utc>,
+) -> anyhow::Result<GeneratedReport> {
+    let report = build_report(db, setting, now).await?;
+    let content = render_report(&report, setting.format)?;
+
+    let name = format!(
+        "admin-report-{}.{}",
+        now.format("%Y%m%d-%H%M%S"),
+        setting.format.extension()
+    );
+    let key = format!("{}{}", REPORT_PREFIX, name);
+    storage.put(&key, content.as_bytes()).await?;
+
+    let generated = GeneratedReport {
+        link: format!(
+            "{}/api/v1/admin/reports/{}/",
+            setting.base_url.trim_end_matches('/'),
+            name
+        ),
+        name,
+        key,
+    };
+    tracing::info!("Stored admin report {}", generated.key);
+
+    send_report_link(producer, setting, &report, &generated).await?;
+    Ok(generated)
+}
+
+/// Run the configured aggregations over the `period_days` days before `now`
+pub async fn build_report(
+    db: &DatabaseConnection,
+    setting: &ReportSetting,
+    now: DateTime<Utc>,
+) -> anyhow::Result<ReportDTO> {
+    let since = (now - chrono::Duration::days(setting.period_days)).naive_utc();
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+
+    let mut report = ReportDTO {
+        period_start: since.format("%Y-%m-%d %H:%M:%S").to_string(),
+        period_end: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+        ..Default::default()
+    };
+    for aggregation in &setting.aggregations {
+        match aggregation {
+            ReportAggregation::NewUsersPerDay => {
+                let days = report_repository::count_new_users_per_day(&context, since).await?;
+                report.new_users_per_day = Some(
+                    days.into_iter()
+                        .map(|day| DailyCountDTO {
+                            day: day.day,
+                            count: day.count,
+                        })
+                        .collect(),
+                );
+            }
+            ReportAggregation::TaskFailureRates => {
+                let outcomes = report_repository::count_task_outcomes(&context, since).await?;
+                report.task_failure_rates = Some(failure_rates(outcomes));
+            }
+        }
+    }
+
+    context.commit().await?;
+    Ok(report)
+}
+
+/// Fold status counts into one row per task type, ordered by task type
+fn failure_rates(outcomes: Vec<TaskStatusCount>) -> Vec<TaskFailureRateDTO> {
+    let mut rates: Vec<TaskFailureRateDTO> = Vec::new();
+    for outcome in outcomes {
+        let task_type = outcome.task_type.unwrap_or_else(|| "unknown".to_string());
+        let index = match rates.iter().position(|rate| rate.task_type == task_type) {
+            Some(index) => index,
+            None => {
+                rates.push(TaskFailureRateDTO {
+                    task_type,
+                    ..Default::default()
+                });
+                rates.len() - 1
+            }
+        };
+
+        let rate = &mut rates[index];
+        match outcome.status.as_str() {
+            "completed" => rate.completed += outcome.count,
+            "retrying" => rate.retried += outcome.count,
+            "failed" => rate.failed += outcome.count,
+            _ => {}
+        }
+    }
+
+    for rate in &mut rates {
+        let finished = rate.completed + rate.failed;
+        if finished > 0 {
+            rate.failure_rate = rate.failed as f64 / finished as f64;
+        }
+    }
+    rates.sort_by(|a, b| a.task_type.cmp(&b.task_type));
+    rates
+}
+
+/// Render `report` with the `report/report.{csv,html}` template
+pub fn render_report(report: &ReportDTO, format: ReportFormat) -> anyhow::Result<String> {
+    let mut context = tera::Context::from_serialize(report)?;
+    context.insert("app_name", APP_NAME);
+
+    render_template(
+        &format!("report/report.{}", format.extension()),
+        "en",
+        context,
+    )
+}
+
+/// Email every recipient a link to the stored report
+async fn send_report_link(
+    producer: &dyn MessageProducer,
+    setting: &ReportSetting,
+    report: &ReportDTO,
+    generated: &GeneratedReport,
+) -> anyhow::Result<()> {
+    let variables = HashMap::from([
+        ("app_name".to_string(), APP_NAME.to_string()),
+        ("link".to_string(), generated.link.clone()),
+        ("name".to_string(), generated.name.clone()),
+        ("period_start".to_string(), report.period_start.clone()),
+        ("period_end".to_string(), report.period_end.clone()),
+        ("year".to_string(), Utc::now().year().to_string()),
+    ]);
+    let html_body = render_email_template("email/admin_report.html", "en", variables)?;
+    let subject = t!(
+        "email_template.admin_report.subject",
+        locale = "en",
+        app_name = APP_NAME
+    )
+    .to_string();
+
+    for recipient in &setting.recipients {
+        // Reports are generated outside any transaction, so nothing goes through the outbox
+        TaskEvent::new(TaskType::SendEmail {
+            to: recipient.clone(),
+            subject: subject.clone(),
+            text_body: None,
+            html_body: Some(html_body.clone()),
+        })
+        .publish_with_producer(producer, Some(MessageType::Emails.as_ref()))
+        .await?;
+    }
+
+    Ok(())
+}
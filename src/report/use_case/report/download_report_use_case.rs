@@ -0,0 +1,52 @@
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+        layer::auth_layer::authorize_role,
+    },
+    pkg::storage::ObjectStorage,
+    report::{
+        dto::report_dto::{ReportFileDTO, ReportFormat},
+        task::report_task::REPORT_PREFIX,
+    },
+    user::entity::sea_orm_active_enums::UserRole,
+};
+
+pub async fn execute(
+    context: &Context,
+    storage: &dyn ObjectStorage,
+    name: &str,
+) -> Result<ReportFileDTO, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    authorize_role(context, current_user, UserRole::Admin)?;
+
+    let not_found = || {
+        ErrorDTO::from_code(
+            ErrorCode::ReportNotFound,
+            t!("report.not_found", locale = &context.locale, name = name).to_string(),
+        )
+    };
+
+    // Only plain report file names, so the link can't reach other stored objects
+    let format = ReportFormat::from_name(name)
+        .filter(|_| !name.contains(['/', '\\']) && !name.starts_with('.'))
+        .ok_or_else(not_found)?;
+    let content = storage
+        .get(&format!("{}{}", REPORT_PREFIX, name))
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(not_found)?;
+
+    Ok(ReportFileDTO {
+        name: name.to_string(),
+        format,
+        content,
+    })
+}
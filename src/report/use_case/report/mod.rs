@@ -0,0 +1 @@
+pub mod download_report_use_case;
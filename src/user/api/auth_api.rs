@@ -1,17 +1,26 @@
 use axum::http::HeaderMap;
-use axum::{Extension, Json};
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
 
 use crate::core::context::Context;
 use crate::{
+    config::app::AppState,
     core::dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
     user::{
         dto::auth_dto::{
-            ChangePasswordDTO, ForgotPasswordDTO, LoginDTO, RefreshTokenDTO, RegisterDTO,
-            ResetPasswordDTO, TokenPairDTO,
+            ChangePasswordDTO, ConfirmResetPasswordDTO, ForgotPasswordDTO, LoginDTO,
+            OAuthAuthorizationDTO, OAuthCallbackDTO, RefreshTokenDTO, RegisterDTO, ReportSignInDTO,
+            ResendVerificationDTO, ResetLinkDTO, ResetPasswordDTO, SessionListDTO, TokenPairDTO,
+            VerifyEmailDTO,
         },
         use_case::auth::{
-            change_password_use_case, forgot_password_use_case, login_use_case, logout_use_case,
-            refresh_token_use_case, register_use_case, reset_password_use_case,
+            change_password_use_case, confirm_reset_password_use_case, forgot_password_use_case,
+            login_use_case, logout_use_case, oauth_login_use_case, refresh_token_use_case,
+            register_use_case, report_sign_in_use_case, resend_verification_use_case,
+            reset_password_use_case, revoke_other_sessions_use_case, revoke_session_use_case,
+            search_session_use_case, verify_email_use_case, verify_reset_link_use_case,
         },
     },
 };
@@ -52,6 +61,82 @@ pub async fn register(
     register_use_case::execute(&context, dto, headers).await
 }
 
+/// Start signing in with `google` or `github`: redirects to the provider's sign-in page,
+/// whose URL is also returned for clients that navigate themselves
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oauth/{provider}/",
+    tags = ["Auth"],
+    params(("provider" = String, Path, example = "google")),
+    responses((status = 302, body = OAuthAuthorizationDTO)),
+)]
+pub async fn oauth_authorize(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+    Path(provider): Path<String>,
+) -> Result<ResponseDTO<OAuthAuthorizationDTO>, ErrorDTO> {
+    let (provider, client) = oauth_login_use_case::resolve_provider(
+        &context,
+        &app_state.setting,
+        &app_state.http_client,
+        &provider,
+    )?;
+    let redirect_uri = app_state.setting.oauth.redirect_uri(provider);
+    Ok(oauth_login_use_case::authorize(
+        &context,
+        provider,
+        client.as_ref(),
+        &redirect_uri,
+    ))
+}
+
+/// Where the provider sends the user back: signs them in like login, creating or linking
+/// their account
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oauth/{provider}/callback/",
+    tags = ["Auth"],
+    params(("provider" = String, Path, example = "google"), OAuthCallbackDTO),
+    responses((status = 200, body = TokenPairDTO)),
+)]
+pub async fn oauth_callback(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+    Path(provider): Path<String>,
+    headers: HeaderMap,
+    Query(dto): Query<OAuthCallbackDTO>,
+) -> Result<ResponseDTO<TokenPairDTO>, ErrorDTO> {
+    let (provider, client) = oauth_login_use_case::resolve_provider(
+        &context,
+        &app_state.setting,
+        &app_state.http_client,
+        &provider,
+    )?;
+    let redirect_uri = app_state.setting.oauth.redirect_uri(provider);
+
+    // Providers redirect back with a GET, which gets a read-only context
+    let context = context.begin(&app_state.db).await?;
+    match oauth_login_use_case::execute(
+        &context,
+        provider,
+        client.as_ref(),
+        &redirect_uri,
+        dto,
+        headers,
+    )
+    .await
+    {
+        Ok(response) => {
+            context.commit().await?;
+            Ok(response)
+        }
+        Err(e) => {
+            context.rollback().await?;
+            Err(e)
+        }
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/auth/logout/",
@@ -115,6 +200,135 @@ pub async fn reset_password(
     reset_password_use_case::execute(&context, dto).await
 }
 
+/// Check the signed link emailed by forgot-password when `PASSWORD_RESET_METHOD=link`
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/reset-password/confirm/{token}/",
+    tags = ["Auth"],
+    params(("token" = String, Path)),
+    responses((status = 200, body = ResetLinkDTO)),
+)]
+pub async fn verify_reset_link(
+    Extension(context): Extension<Context>,
+    Path(token): Path<String>,
+) -> Result<ResponseDTO<ResetLinkDTO>, ErrorDTO> {
+    verify_reset_link_use_case::execute(&context, &token).await
+}
+
+/// Set a new password through the signed link emailed by forgot-password
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/reset-password/confirm/{token}/",
+    tags = ["Auth"],
+    params(("token" = String, Path)),
+    request_body(
+        content = ConfirmResetPasswordDTO,
+        example = json!({ "new_password": "newpassword123@" }),
+    ),
+    responses((status = 204)),
+)]
+pub async fn confirm_reset_password(
+    Extension(context): Extension<Context>,
+    Path(token): Path<String>,
+    Json(dto): Json<ConfirmResetPasswordDTO>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    confirm_reset_password_use_case::execute(&context, &token, dto).await
+}
+
+/// Report a sign-in from the "this wasn't me" link of its alert email, signing its device out
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/sign-ins/report/",
+    tags = ["Auth"],
+    request_body(content = ReportSignInDTO),
+    responses((status = 204)),
+)]
+pub async fn report_sign_in(
+    Extension(context): Extension<Context>,
+    Json(dto): Json<ReportSignInDTO>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    report_sign_in_use_case::execute(&context, dto).await
+}
+
+/// Verify the account's email with the token of the link emailed at registration
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/verify-email/",
+    tags = ["Auth"],
+    request_body(content = VerifyEmailDTO),
+    responses((status = 204)),
+)]
+pub async fn verify_email(
+    Extension(context): Extension<Context>,
+    Json(dto): Json<VerifyEmailDTO>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    verify_email_use_case::execute(&context, dto).await
+}
+
+/// Email a new verification link, answering the same whether or not the email has an
+/// unverified account
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/resend-verification/",
+    tags = ["Auth"],
+    request_body(
+        content = ResendVerificationDTO,
+        example = json!({ "email": "user@example.com" }),
+    ),
+    responses((status = 204)),
+)]
+pub async fn resend_verification(
+    Extension(context): Extension<Context>,
+    Json(dto): Json<ResendVerificationDTO>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    resend_verification_use_case::execute(&context, dto).await
+}
+
+/// List the devices signed in to the account; `current` is set from the `refresh_token` cookie
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/sessions/",
+    tags = ["Auth"],
+    security(("bearer_auth" = [])),
+    responses((status = 200, body = SessionListDTO)),
+)]
+pub async fn search_session(
+    Extension(context): Extension<Context>,
+    headers: HeaderMap,
+) -> Result<ResponseDTO<SessionListDTO>, ErrorDTO> {
+    search_session_use_case::execute(&context, headers).await
+}
+
+/// Sign out every device but the one making the request
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/sessions/",
+    tags = ["Auth"],
+    security(("bearer_auth" = [])),
+    responses((status = 204)),
+)]
+pub async fn revoke_other_sessions(
+    Extension(context): Extension<Context>,
+    headers: HeaderMap,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    revoke_other_sessions_use_case::execute(&context, headers).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/sessions/{id}/",
+    tags = ["Auth"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = 204)),
+)]
+pub async fn revoke_session(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    revoke_session_use_case::execute(&context, id).await
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/auth/change-password/",
@@ -1,3 +1,4 @@
 pub mod auth_api;
 pub mod user_api;
+pub mod user_email_api;
 pub mod user_ws;
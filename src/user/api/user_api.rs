@@ -1,20 +1,25 @@
+use crate::config::app::AppState;
 use crate::core::context::Context;
 use crate::core::dto::error_dto::ErrorDTO;
 use crate::core::dto::response_dto::ResponseDTO;
 use crate::core::dto::util::deserialize_with_fields;
-use crate::core::layer::auth_layer::authorize_role;
-use crate::user::dto::auth_dto::{ProfileDTO, UpdateProfileDTO};
+use crate::core::policy::{Action, Resource};
+use crate::user::dto::auth_dto::{ConfirmPhoneDTO, ImpersonationDTO, ProfileDTO, UpdateProfileDTO};
 use crate::user::dto::avatar_dto::{UploadAvatarDTO, UploadAvatarResponseDTO};
+use crate::user::dto::bulk_user_dto::{BulkUserRequestDTO, BulkUserResponseDTO};
 use crate::user::dto::user_dto::{
     UserCreateDTO, UserDTO, UserListDTO, UserSearchParamsDTO, UserUpdateDTO,
 };
-use crate::user::entity::{sea_orm_active_enums::UserRole, user};
-use crate::user::use_case::auth::{get_profile_use_case, update_profile_use_case};
+use crate::user::use_case::auth::{
+    confirm_phone_verification_use_case, get_profile_use_case, send_phone_verification_use_case,
+    update_profile_use_case,
+};
 use crate::user::use_case::user::{
-    create_user_use_case, delete_user_use_case, get_user_use_case, search_user_use_case,
-    update_user_use_case, upload_avatar_use_case,
+    bulk_user_use_case, create_user_use_case, delete_user_use_case, end_impersonation_use_case,
+    get_user_use_case, hard_delete_user_use_case, impersonate_user_use_case, restore_user_use_case,
+    search_user_use_case, update_user_use_case, upload_avatar_use_case,
 };
-use axum::extract::{Path, Query};
+use axum::extract::{Path, Query, State};
 #[allow(unused_imports)]
 use axum::http::StatusCode;
 use axum::{Extension, Json};
@@ -29,12 +34,9 @@ use serde_json::Value;
     responses((status = StatusCode::OK, body = UserListDTO)),
 )]
 pub async fn search_user(
-    Extension(current_user): Extension<user::Model>,
     Extension(context): Extension<Context>,
     Query(dto): Query<UserSearchParamsDTO>,
 ) -> Result<ResponseDTO<UserListDTO>, ErrorDTO> {
-    authorize_role(&context, &current_user, UserRole::Admin)?;
-
     search_user_use_case::execute(&context, dto).await
 }
 
@@ -47,11 +49,10 @@ pub async fn search_user(
     responses((status = StatusCode::CREATED, body = UserDTO)),
 )]
 pub async fn create_user(
-    Extension(current_user): Extension<user::Model>,
     Extension(context): Extension<Context>,
     Json(dto): Json<UserCreateDTO>,
 ) -> Result<ResponseDTO<UserDTO>, ErrorDTO> {
-    authorize_role(&context, &current_user, UserRole::Admin)?;
+    context.authorize(Action::Create, &Resource::users())?;
 
     create_user_use_case::execute(&context, dto).await
 }
@@ -65,12 +66,9 @@ pub async fn create_user(
     responses((status = StatusCode::OK, body = UserDTO)),
 )]
 pub async fn get_user(
-    Extension(current_user): Extension<user::Model>,
     Extension(context): Extension<Context>,
     Path(id): Path<i32>,
 ) -> Result<ResponseDTO<UserDTO>, ErrorDTO> {
-    authorize_role(&context, &current_user, UserRole::Admin)?;
-
     get_user_use_case::execute(&context, id).await
 }
 
@@ -84,13 +82,10 @@ pub async fn get_user(
     responses((status = StatusCode::OK, body = UserDTO)),
 )]
 pub async fn update_user(
-    Extension(current_user): Extension<user::Model>,
     Extension(context): Extension<Context>,
     Path(id): Path<i32>,
     Json(body): Json<Value>,
 ) -> Result<ResponseDTO<UserDTO>, ErrorDTO> {
-    authorize_role(&context, &current_user, UserRole::Admin)?;
-
     let (dto, fields) = deserialize_with_fields(body, &context.locale)?;
     update_user_use_case::execute(&context, id, dto, fields).await
 }
@@ -104,15 +99,100 @@ pub async fn update_user(
     responses((status = StatusCode::NO_CONTENT)),
 )]
 pub async fn delete_user(
-    Extension(current_user): Extension<user::Model>,
     Extension(context): Extension<Context>,
     Path(id): Path<i32>,
 ) -> Result<ResponseDTO<()>, ErrorDTO> {
-    authorize_role(&context, &current_user, UserRole::Admin)?;
-
     delete_user_use_case::execute(&context, id).await
 }
 
+/// Bring back a deleted user. Its sessions were revoked when it was deleted.
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{id}/restore/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::OK, body = UserDTO)),
+)]
+pub async fn restore_user(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<UserDTO>, ErrorDTO> {
+    restore_user_use_case::execute(&context, id).await
+}
+
+/// Remove a user for good, whether it was deleted first or not
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/users/{id}/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn hard_delete_user(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    hard_delete_user_use_case::execute(&context, id).await
+}
+
+/// Act as a user with a short-lived access token, recorded in the audit trail. Admins and
+/// deactivated users can't be impersonated.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/impersonate/{user_id}/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("user_id" = i32, Path)),
+    responses((status = StatusCode::CREATED, body = ImpersonationDTO)),
+)]
+pub async fn impersonate_user(
+    Extension(context): Extension<Context>,
+    Path(user_id): Path<i32>,
+) -> Result<ResponseDTO<ImpersonationDTO>, ErrorDTO> {
+    impersonate_user_use_case::execute(&context, user_id).await
+}
+
+/// Stop acting as a user, revoking the impersonation tokens issued for them. Accepts the
+/// admin's own token or the impersonation token.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/impersonate/{user_id}/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    params(("user_id" = i32, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn end_impersonation(
+    Extension(context): Extension<Context>,
+    Path(user_id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    end_impersonation_use_case::execute(&context, user_id).await
+}
+
+/// Deactivate, delete or change the role of many users. Each operation is applied in its
+/// own transaction and reported on its own; batches over `BULK_SYNC_LIMIT` are queued and
+/// answered with the id of the task reporting their progress.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/users/bulk/",
+    tags = ["Admin"],
+    security(("bearer_auth" = [])),
+    request_body(content = BulkUserRequestDTO),
+    responses(
+        (status = StatusCode::OK, body = BulkUserResponseDTO),
+        (status = StatusCode::ACCEPTED, body = BulkUserResponseDTO),
+    ),
+)]
+pub async fn bulk_user_operations(
+    State(app_state): State<AppState>,
+    Extension(context): Extension<Context>,
+    Json(dto): Json<BulkUserRequestDTO>,
+) -> Result<ResponseDTO<BulkUserResponseDTO>, ErrorDTO> {
+    bulk_user_use_case::execute(&context, &app_state.setting, dto).await
+}
+
 #[utoipa::path(
     get,
     path = "/api/v1/user/profile/",
@@ -142,6 +222,34 @@ pub async fn update_profile(
     update_profile_use_case::execute(&context, dto, fields).await
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/user/profile/phone/verify/",
+    tags = ["User"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn send_phone_verification(
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    send_phone_verification_use_case::execute(&context).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/user/profile/phone/confirm/",
+    tags = ["User"],
+    security(("bearer_auth" = [])),
+    request_body(content = ConfirmPhoneDTO),
+    responses((status = StatusCode::OK, body = ProfileDTO)),
+)]
+pub async fn confirm_phone_verification(
+    Extension(context): Extension<Context>,
+    Json(dto): Json<ConfirmPhoneDTO>,
+) -> Result<ResponseDTO<ProfileDTO>, ErrorDTO> {
+    confirm_phone_verification_use_case::execute(&context, dto).await
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/user/upload-avatar/",
@@ -0,0 +1,128 @@
+use crate::core::context::Context;
+use crate::core::dto::error_dto::ErrorDTO;
+use crate::core::dto::response_dto::ResponseDTO;
+use crate::user::dto::auth_dto::ProfileDTO;
+use crate::user::dto::user_email_dto::{
+    AddUserEmailDTO, ConfirmUserEmailDTO, UpdateUserEmailDTO, UserEmailDTO, UserEmailListDTO,
+};
+use crate::user::use_case::email::{
+    add_user_email_use_case, confirm_user_email_use_case, delete_user_email_use_case,
+    make_primary_user_email_use_case, search_user_email_use_case,
+    send_user_email_verification_use_case, update_user_email_use_case,
+};
+use axum::extract::Path;
+#[allow(unused_imports)]
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/user/profile/emails/",
+    tags = ["User"],
+    security(("bearer_auth" = [])),
+    responses((status = StatusCode::OK, body = UserEmailListDTO)),
+)]
+pub async fn search_user_email(
+    Extension(context): Extension<Context>,
+) -> Result<ResponseDTO<UserEmailListDTO>, ErrorDTO> {
+    search_user_email_use_case::execute(&context).await
+}
+
+/// Add a secondary email address, which is emailed a code to verify it with
+#[utoipa::path(
+    post,
+    path = "/api/v1/user/profile/emails/",
+    tags = ["User"],
+    security(("bearer_auth" = [])),
+    request_body(
+        content = AddUserEmailDTO,
+        example = json!({ "email": "work@example.com", "receives_notifications": true }),
+    ),
+    responses((status = StatusCode::CREATED, body = UserEmailDTO)),
+)]
+pub async fn add_user_email(
+    Extension(context): Extension<Context>,
+    Json(dto): Json<AddUserEmailDTO>,
+) -> Result<ResponseDTO<UserEmailDTO>, ErrorDTO> {
+    add_user_email_use_case::execute(&context, dto).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/user/profile/emails/{id}/",
+    tags = ["User"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    request_body(content = UpdateUserEmailDTO),
+    responses((status = StatusCode::OK, body = UserEmailDTO)),
+)]
+pub async fn update_user_email(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+    Json(dto): Json<UpdateUserEmailDTO>,
+) -> Result<ResponseDTO<UserEmailDTO>, ErrorDTO> {
+    update_user_email_use_case::execute(&context, id, dto).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/user/profile/emails/{id}/",
+    tags = ["User"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn delete_user_email(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    delete_user_email_use_case::execute(&context, id).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/user/profile/emails/{id}/verify/",
+    tags = ["User"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::NO_CONTENT)),
+)]
+pub async fn send_user_email_verification(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    send_user_email_verification_use_case::execute(&context, id).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/user/profile/emails/{id}/confirm/",
+    tags = ["User"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    request_body(content = ConfirmUserEmailDTO),
+    responses((status = StatusCode::OK, body = UserEmailDTO)),
+)]
+pub async fn confirm_user_email(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+    Json(dto): Json<ConfirmUserEmailDTO>,
+) -> Result<ResponseDTO<UserEmailDTO>, ErrorDTO> {
+    confirm_user_email_use_case::execute(&context, id, dto).await
+}
+
+/// Swap a verified secondary address with the primary one
+#[utoipa::path(
+    post,
+    path = "/api/v1/user/profile/emails/{id}/primary/",
+    tags = ["User"],
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path)),
+    responses((status = StatusCode::OK, body = ProfileDTO)),
+)]
+pub async fn make_primary_user_email(
+    Extension(context): Extension<Context>,
+    Path(id): Path<i32>,
+) -> Result<ResponseDTO<ProfileDTO>, ErrorDTO> {
+    make_primary_user_email_use_case::execute(&context, id).await
+}
@@ -1,4 +1,5 @@
 use crate::config::app::AppState;
+use crate::config::drain::draining_error;
 use crate::core::layer::lang_layer::RequestLocale;
 use crate::user::entity::user;
 use crate::user::use_case::user::sync_user_data_use_case;
@@ -16,8 +17,14 @@ pub async fn sync_user_data(
     Extension(current_user): Extension<user::Model>,
     Extension(locale): Extension<RequestLocale>,
 ) -> impl IntoResponse {
+    // A draining instance takes no new WebSockets, the client retries on another one
+    if app_state.drain.is_draining() {
+        return draining_error(locale.as_str()).into_response();
+    }
+
     let locale = locale.as_str().to_string();
     ws.on_upgrade(move |socket| {
         sync_user_data_use_case::execute(socket, app_state.0, current_user, locale)
     })
+    .into_response()
 }
@@ -1,61 +1,177 @@
+use std::collections::BTreeMap;
+
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
-use crate::user::entity::user;
+use crate::{
+    core::validation::Validate,
+    user::entity::{refresh_token, user},
+};
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct LoginDTO {
     pub email: String,
     pub password: String,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct RegisterDTO {
+    #[validate(required, email, length(max = 255))]
     pub email: String,
+    #[validate(required)]
     pub password: String,
+    #[validate(length(max = 255))]
     pub first_name: Option<String>,
+    #[validate(length(max = 255))]
     pub last_name: Option<String>,
+    #[validate(length(max = 32))]
     pub phone: Option<String>,
 }
 
-#[derive(Debug, Serialize, ToSchema, Clone)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct TokenPairDTO {
     pub access: String,
     pub refresh: String,
 }
 
-#[derive(Debug, Deserialize, ToSchema, Default)]
+/// Access token an admin acts as another user with. It can't be refreshed.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImpersonationDTO {
+    pub access: String,
+    pub user_id: i32,
+    #[serde(with = "crate::core::dto::datetime")]
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
 pub struct RefreshTokenDTO {
     pub refresh_token: Option<String>,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ChangePasswordDTO {
     pub old_password: String,
     pub new_password: String,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct ForgotPasswordDTO {
+    #[validate(required, email)]
     pub email: String,
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
 pub struct ResetPasswordDTO {
+    #[validate(required, email)]
     pub email: String,
     pub otp: String,
+    #[validate(required)]
     pub new_password: String,
 }
 
+/// Account a password reset link is for, so the reset form can show it
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ResetLinkDTO {
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ConfirmResetPasswordDTO {
+    #[validate(required)]
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct VerifyEmailDTO {
+    /// Token of the link in the verification email
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ResendVerificationDTO {
+    #[validate(required, email)]
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReportSignInDTO {
+    /// Token of the "this wasn't me" link in the new sign-in email
+    pub token: String,
+}
+
+/// Device signed in to the user's account, until its refresh token expires or is revoked
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionDTO {
+    pub id: i32,
+    pub device_info: Option<String>,
+    pub ip_address: Option<String>,
+    /// When the session's refresh token was issued, at sign-in or its last refresh
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub created_at: Option<NaiveDateTime>,
+    #[serde(with = "crate::core::dto::datetime")]
+    pub expires_at: NaiveDateTime,
+    /// Whether this is the session the request was made from
+    pub current: bool,
+}
+
+impl From<refresh_token::Model> for SessionDTO {
+    fn from(model: refresh_token::Model) -> Self {
+        SessionDTO {
+            id: model.id,
+            device_info: model.device_info,
+            ip_address: model.ip_address,
+            created_at: model.created_at,
+            expires_at: model.expires_at,
+            current: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SessionListDTO {
+    pub items: Vec<SessionDTO>,
+    pub count: usize,
+}
+
+/// Provider sign-in page a browser is redirected to
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OAuthAuthorizationDTO {
+    pub authorization_url: String,
+}
+
+/// Query the provider redirects back to the callback with
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OAuthCallbackDTO {
+    /// Authorization code to exchange for the user's identity
+    pub code: Option<String>,
+    /// Value given in the authorization URL, proving the sign-in was started by this browser
+    pub state: Option<String>,
+    /// Set instead of `code` when the user refused or the provider failed
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProfileDTO {
     pub id: i32,
     pub email: String,
+    pub email_verified: bool,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub phone: Option<String>,
+    pub phone_verified: bool,
+    /// Preferred locale, `None` to follow the request's language
+    pub locale: Option<String>,
+    /// IANA time zone timestamps are shown in, `None` for UTC
+    pub timezone: Option<String>,
+    /// Short-lived signed link to the approved avatar; uploads waiting for review aren't shown
+    pub avatar_url: Option<String>,
+    /// Short-lived signed links to the thumbnails of the approved avatar, by size
+    pub avatar_thumbnail_urls: BTreeMap<String, String>,
+    #[serde(with = "crate::core::dto::datetime::option")]
     pub created_at: Option<NaiveDateTime>,
+    #[serde(with = "crate::core::dto::datetime::option")]
     pub updated_at: Option<NaiveDateTime>,
 }
 
@@ -64,18 +180,40 @@ impl From<user::Model> for ProfileDTO {
         ProfileDTO {
             id: model.id,
             email: model.email,
+            email_verified: model.verified_at.is_some(),
             first_name: model.first_name,
             last_name: model.last_name,
             phone: model.phone,
+            phone_verified: model.phone_verified_at.is_some(),
+            locale: model.locale,
+            timezone: model.timezone,
+            avatar_url: None,
+            avatar_thumbnail_urls: BTreeMap::new(),
             created_at: model.created_at,
             updated_at: model.updated_at,
         }
     }
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConfirmPhoneDTO {
+    /// Code received by SMS
+    pub otp: String,
+}
+
+/// Fields left `None` are not sent, so they keep their value
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
 pub struct UpdateProfileDTO {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub phone: Option<String>,
+    /// One of the available locales, or `null` to follow the request's language
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// IANA time zone such as `Asia/Ho_Chi_Minh`, or `null` for UTC
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
 }
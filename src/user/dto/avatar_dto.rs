@@ -4,6 +4,9 @@ use utoipa::ToSchema;
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UploadAvatarDTO {
     pub file_name: String,
+    /// Base64-encoded file content, stored and virus-scanned before the avatar becomes available
+    #[serde(default)]
+    pub content: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -46,11 +49,19 @@ mod tests {
     fn creates_upload_avatar_dto() {
         let dto = UploadAvatarDTO {
             file_name: "avatar.jpg".to_string(),
+            content: None,
         };
 
         assert_eq!(dto.file_name, "avatar.jpg");
     }
 
+    #[test]
+    fn deserializes_upload_avatar_dto_without_content() {
+        let dto: UploadAvatarDTO = serde_json::from_str(r#"{"file_name":"avatar.jpg"}"#).unwrap();
+
+        assert!(dto.content.is_none());
+    }
+
     #[test]
     fn builds_progress_dto_with_message() {
         let dto = AvatarUploadProgressDTO::new("task-1".to_string(), 1, 50, "uploading")
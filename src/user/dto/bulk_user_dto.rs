@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::user::entity::sea_orm_active_enums::UserRole;
+
+/// One change to apply to a user, tagged by `action`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BulkUserOperationDTO {
+    /// Sign the user out everywhere and block them from signing in
+    Deactivate {
+        user_id: i32,
+    },
+    Delete {
+        user_id: i32,
+    },
+    AssignRole {
+        user_id: i32,
+        role: UserRole,
+    },
+}
+
+impl BulkUserOperationDTO {
+    pub fn user_id(&self) -> i32 {
+        match self {
+            Self::Deactivate { user_id }
+            | Self::Delete { user_id }
+            | Self::AssignRole { user_id, .. } => *user_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BulkUserRequestDTO {
+    pub operations: Vec<BulkUserOperationDTO>,
+}
+
+/// Outcome of the operation at `index` of the request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BulkUserItemResultDTO {
+    pub index: usize,
+    pub user_id: i32,
+    pub success: bool,
+    /// Why the operation was rolled back, `None` when it succeeded
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BulkUserReportDTO {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkUserItemResultDTO>,
+}
+
+impl BulkUserReportDTO {
+    pub fn push(&mut self, result: BulkUserItemResultDTO) {
+        if result.success {
+            self.succeeded += 1;
+        } else {
+            self.failed += 1;
+        }
+        self.results.push(result);
+    }
+}
+
+/// Either the report of a batch applied right away, or the task processing a large one
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BulkUserResponseDTO {
+    /// Set when the batch was queued: its progress and report are sent on the task's
+    /// WebSocket and long-polling endpoints
+    pub task_id: Option<String>,
+    pub report: Option<BulkUserReportDTO>,
+}
+
+/// Progress of a queued batch, sent after each operation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BulkUserProgressDTO {
+    pub task_id: String,
+    pub progress: u8,
+    pub status: String,
+    pub processed: usize,
+    pub total: usize,
+    /// Full report, sent with the final `completed` message
+    pub report: Option<BulkUserReportDTO>,
+}
@@ -1,3 +1,5 @@
 pub mod auth_dto;
 pub mod avatar_dto;
+pub mod bulk_user_dto;
 pub mod user_dto;
+pub mod user_email_dto;
@@ -3,6 +3,8 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
+use crate::core::validation::Validate;
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UserSimpleDTO {
     pub id: i32,
@@ -29,7 +31,9 @@ pub struct UserDTO {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub phone: Option<String>,
+    #[serde(with = "crate::core::dto::datetime::option")]
     pub created_at: Option<NaiveDateTime>,
+    #[serde(with = "crate::core::dto::datetime::option")]
     pub updated_at: Option<NaiveDateTime>,
     pub created_user: Option<UserSimpleDTO>,
     pub updated_user: Option<UserSimpleDTO>,
@@ -57,15 +61,20 @@ impl From<UserWithRelations> for UserDTO {
     }
 }
 
-#[derive(Debug, Deserialize, IntoParams)]
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams, Validate)]
 #[into_params(parameter_in = Query)]
 pub struct UserSearchParamsDTO {
+    #[validate(length(max = 255))]
     pub email: Option<String>,
+    #[validate(length(max = 255))]
     pub first_name: Option<String>,
+    #[validate(length(max = 255))]
     pub last_name: Option<String>,
     #[param(default = 1)]
+    #[validate(range(min = 1))]
     pub page: Option<u64>,
     #[param(default = 10)]
+    #[validate(range(min = 1))]
     pub page_size: Option<u64>,
     pub order_by: Option<String>,
 }
@@ -76,21 +85,37 @@ pub struct UserListDTO {
     pub count: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct UserCreateDTO {
+    #[validate(required, email, length(max = 255))]
     pub email: String,
+    #[validate(required)]
     pub password: String,
+    #[validate(length(max = 255))]
     pub first_name: Option<String>,
+    #[validate(length(max = 255))]
     pub last_name: Option<String>,
+    #[validate(length(max = 32))]
     pub phone: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+/// Fields left `None` are not sent, so they keep their value
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema, Validate)]
 pub struct UserUpdateDTO {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(email, length(max = 255))]
     pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(password)]
     pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 255))]
     pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 255))]
     pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 32))]
     pub phone: Option<String>,
 }
 
@@ -109,11 +134,19 @@ mod tests {
         user::Model {
             id,
             email: email.to_string(),
+            normalized_email: email.to_string(),
             password: "password".to_string(),
             role: UserRole::User,
             first_name: Some("John".to_string()),
             last_name: Some("Doe".to_string()),
             phone: Some("123456789".to_string()),
+            phone_verified_at: None,
+            verified_at: None,
+            locale: None,
+            timezone: None,
+            deactivated_at: None,
+            deleted_at: None,
+            avatar_file_id: None,
             created_at: Some(now),
             updated_at: Some(now),
             created_user_id: None,
@@ -0,0 +1,56 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::core::validation::Validate;
+use crate::user::entity::user_email;
+
+/// Secondary email address of the current user
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserEmailDTO {
+    pub id: i32,
+    pub email: String,
+    /// Only verified addresses can be used to sign in or be made primary
+    pub verified: bool,
+    /// Whether notification emails are also sent here once verified
+    pub receives_notifications: bool,
+    #[serde(with = "crate::core::dto::datetime::option")]
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl From<user_email::Model> for UserEmailDTO {
+    fn from(model: user_email::Model) -> Self {
+        UserEmailDTO {
+            id: model.id,
+            email: model.email,
+            verified: model.verified_at.is_some(),
+            receives_notifications: model.receives_notifications,
+            created_at: model.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserEmailListDTO {
+    pub items: Vec<UserEmailDTO>,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Validate)]
+pub struct AddUserEmailDTO {
+    #[validate(required, email, length(max = 255))]
+    pub email: String,
+    #[serde(default)]
+    pub receives_notifications: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateUserEmailDTO {
+    pub receives_notifications: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConfirmUserEmailDTO {
+    /// Code received at the address
+    pub otp: String,
+}
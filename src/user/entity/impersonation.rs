@@ -0,0 +1,29 @@
+use sea_orm::entity::prelude::*;
+
+/// Admin `admin_id` acting as the user `user_id`
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "impersonation")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub admin_id: i32,
+    pub user_id: i32,
+    /// `jti` of the access token issued for the impersonation
+    #[sea_orm(unique)]
+    pub token_id: String,
+    pub expires_at: DateTime,
+    /// When the admin ended the impersonation, revoking its token
+    pub ended_at: Option<DateTime>,
+    pub created_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "user_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub user: HasOne<super::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
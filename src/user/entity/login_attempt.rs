@@ -0,0 +1,16 @@
+use sea_orm::entity::prelude::*;
+
+/// A failed sign-in, kept until the lockout cooldown has passed
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "login_attempt")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Normalized email the sign-in was for, whether or not an account has it
+    pub email: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,5 +1,17 @@
+pub mod email_verification_token;
+pub mod impersonation;
+pub mod login_attempt;
+pub mod oauth_account;
 pub mod password_reset_token;
+pub mod permission;
+pub mod phone_verification_token;
 pub mod prelude;
 pub mod refresh_token;
+pub mod role;
+pub mod role_permission;
 pub mod sea_orm_active_enums;
+pub mod security_event;
+pub mod signing_key;
 pub mod user;
+pub mod user_email;
+pub mod user_role_assignment;
@@ -0,0 +1,28 @@
+use sea_orm::entity::prelude::*;
+
+use super::sea_orm_active_enums::IdentityProvider;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "oauth_account")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub provider: IdentityProvider,
+    /// Id of the account at the provider, unique per provider
+    pub subject: String,
+    /// Email the provider gave when the account was linked
+    pub email: Option<String>,
+    pub created_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "user_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub user: HasOne<super::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -7,6 +7,7 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub user_id: i32,
+    /// HMAC-SHA256 hex digest of the OTP; the OTP itself is never stored
     #[sea_orm(unique)]
     pub token: String,
     pub retry_count: i32,
@@ -0,0 +1,17 @@
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "permission")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Name routes and use cases check, `<resource>.<action>` such as `user.delete`
+    #[sea_orm(unique)]
+    pub name: String,
+    pub description: Option<String>,
+    #[sea_orm(has_many)]
+    pub role_permissions: HasMany<super::role_permission::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,3 +1,15 @@
+pub use super::email_verification_token::Entity as EmailVerificationToken;
+pub use super::impersonation::Entity as Impersonation;
+pub use super::login_attempt::Entity as LoginAttempt;
+pub use super::oauth_account::Entity as OAuthAccount;
 pub use super::password_reset_token::Entity as PasswordResetToken;
+pub use super::permission::Entity as Permission;
+pub use super::phone_verification_token::Entity as PhoneVerificationToken;
 pub use super::refresh_token::Entity as RefreshToken;
+pub use super::role::Entity as Role;
+pub use super::role_permission::Entity as RolePermission;
+pub use super::security_event::Entity as SecurityEvent;
+pub use super::signing_key::Entity as SigningKey;
 pub use super::user::Entity as User;
+pub use super::user_email::Entity as UserEmail;
+pub use super::user_role_assignment::Entity as UserRoleAssignment;
@@ -1,5 +1,7 @@
 use sea_orm::entity::prelude::*;
 
+use super::sea_orm_active_enums::ExpirationPolicy;
+
 #[sea_orm::model]
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
 #[sea_orm(table_name = "refresh_token")]
@@ -7,11 +9,22 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub user_id: i32,
+    /// HMAC-SHA256 hex digest of the refresh token; the refresh token itself is never stored
     #[sea_orm(unique)]
     pub token: String,
     pub device_info: Option<String>,
     pub ip_address: Option<String>,
+    /// When the token stops being accepted, which is what expired tokens are cleaned up by
     pub expires_at: DateTime,
+    #[sea_orm(default_value = "absolute")]
+    pub expiration_policy: ExpirationPolicy,
+    /// End of the session a sliding token belongs to, which rotations can't extend it past
+    pub max_expires_at: Option<DateTime>,
+    /// Shared by the tokens rotated from the same sign-in, revoked together when one of them
+    /// is reused; `None` for tokens issued before families and not rotated since
+    pub family_id: Option<String>,
+    /// When the token was exchanged for a new one; presenting it again is reuse
+    pub rotated_at: Option<DateTime>,
     pub created_at: Option<DateTime>,
     #[sea_orm(
         belongs_to,
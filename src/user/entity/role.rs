@@ -0,0 +1,20 @@
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "role")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Unique name of the role, e.g. `support`
+    #[sea_orm(unique)]
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: Option<DateTime>,
+    #[sea_orm(has_many)]
+    pub role_permissions: HasMany<super::role_permission::Entity>,
+    #[sea_orm(has_many)]
+    pub user_role_assignments: HasMany<super::user_role_assignment::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
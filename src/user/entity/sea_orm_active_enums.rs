@@ -1,10 +1,75 @@
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumString};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
+)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "user_role")]
+#[serde(rename_all = "lowercase")]
 pub enum UserRole {
     #[sea_orm(string_value = "user")]
     User,
     #[sea_orm(string_value = "admin")]
     Admin,
 }
+
+/// Kind of security-relevant account activity
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(32))")]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityEventKind {
+    /// First sign-in on record, kept as the baseline later sign-ins are compared with
+    #[sea_orm(string_value = "sign_in")]
+    SignIn,
+    /// Sign-in from a device or network the user hadn't used before, which they were alerted of
+    #[sea_orm(string_value = "new_sign_in")]
+    NewSignIn,
+    /// Refresh token presented again after it was rotated, whose family was revoked
+    #[sea_orm(string_value = "refresh_token_reused")]
+    RefreshTokenReused,
+}
+
+/// How a refresh token's expiry changes when it is rotated
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "snake_case")]
+pub enum ExpirationPolicy {
+    /// The rotated token keeps the expiry of the one it replaces
+    #[sea_orm(string_value = "absolute")]
+    Absolute,
+    /// The rotated token is valid for a full refresh token lifetime again, up to `max_expires_at`
+    #[sea_orm(string_value = "sliding")]
+    Sliding,
+}
+
+/// Identity provider users can sign in with, named as in `/api/v1/auth/oauth/{provider}/`
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    EnumString,
+    AsRefStr,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum IdentityProvider {
+    #[sea_orm(string_value = "google")]
+    Google,
+    #[sea_orm(string_value = "github")]
+    GitHub,
+}
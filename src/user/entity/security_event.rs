@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+
+use super::sea_orm_active_enums::SecurityEventKind;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "security_event")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    pub kind: SecurityEventKind,
+    pub device_info: Option<String>,
+    pub ip_address: Option<String>,
+    /// HMAC-SHA256 hex digest of the token of the "this wasn't me" link sent for the event
+    #[sea_orm(unique)]
+    pub report_token: Option<String>,
+    /// When the user reported the sign-in as not theirs
+    pub reported_at: Option<DateTime>,
+    pub created_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "user_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub user: HasOne<super::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
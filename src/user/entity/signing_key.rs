@@ -0,0 +1,16 @@
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "signing_key")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub kid: String,
+    pub secret: String,
+    pub retires_at: Option<DateTime>,
+    pub created_at: Option<DateTime>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -9,16 +9,33 @@ pub struct Model {
     pub id: i32,
     #[sea_orm(unique)]
     pub email: String,
+    /// `email` normalized with the `EMAIL_*` settings in force when it was saved
+    #[sea_orm(unique)]
+    pub normalized_email: String,
     pub password: String,
+    /// When the owner of `email` confirmed it through the emailed link; cleared when it changes
+    pub verified_at: Option<DateTime>,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub phone: Option<String>,
+    /// When `phone` was confirmed with an SMS code; cleared when the phone changes
+    pub phone_verified_at: Option<DateTime>,
     pub created_at: Option<DateTime>,
     pub updated_at: Option<DateTime>,
     pub created_user_id: Option<i32>,
     pub updated_user_id: Option<i32>,
     #[sea_orm(default_value = "user")]
     pub role: UserRole,
+    /// Locale of messages, emails and notifications, `None` to follow the request's language
+    pub locale: Option<String>,
+    /// IANA time zone timestamps are shown in, `None` for UTC
+    pub timezone: Option<String>,
+    /// When an admin deactivated the account; deactivated users can't sign in
+    pub deactivated_at: Option<DateTime>,
+    /// When the account was deleted; deleted users are hidden until an admin restores them
+    pub deleted_at: Option<DateTime>,
+    /// Approved avatar shown on the profile; a newer upload replaces it once approved
+    pub avatar_file_id: Option<i32>,
     #[sea_orm(has_many)]
     pub password_reset_tokens: HasMany<super::password_reset_token::Entity>,
     #[sea_orm(has_many)]
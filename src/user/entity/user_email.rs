@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "user_email")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    /// Secondary address; the primary one is `user.email`
+    pub email: String,
+    /// `email` normalized with the `EMAIL_*` settings, unique across primary and secondary
+    /// addresses
+    #[sea_orm(unique)]
+    pub normalized_email: String,
+    /// When the address was confirmed with an emailed code; unverified addresses can't be used
+    pub verified_at: Option<DateTime>,
+    /// Whether notification emails are also sent here once verified
+    pub receives_notifications: bool,
+    /// Code sent to the address, cleared once it is verified
+    pub token: Option<String>,
+    pub retry_count: i32,
+    pub token_expires_at: Option<DateTime>,
+    pub created_at: Option<DateTime>,
+    pub updated_at: Option<DateTime>,
+    #[sea_orm(
+        belongs_to,
+        from = "user_id",
+        to = "id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    pub user: HasOne<super::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,7 +1,11 @@
 pub mod api;
 pub mod dto;
 pub mod entity;
+mod module;
 pub mod repository;
 pub mod service;
+pub mod subscriber;
 pub mod task;
 pub mod use_case;
+
+pub use module::UserModule;
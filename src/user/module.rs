@@ -0,0 +1,275 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use axum::{
+    Router,
+    routing::{any, delete, get, patch, post},
+};
+
+use crate::{
+    config::app::AppState,
+    core::{
+        api::route::{protected_api, public_api},
+        r#async::PeriodicJob,
+        layer::{
+            permission_layer::{RequirePermission, require_permission_middleware},
+            response_cache_layer::{USER_CACHE_TAG, response_cache_middleware},
+        },
+        module::Module,
+    },
+    user::{
+        api::{auth_api, user_api, user_email_api, user_ws},
+        task::auth_task,
+    },
+};
+
+/// Authentication and user management
+pub struct UserModule;
+
+impl Module for UserModule {
+    fn name(&self) -> &'static str {
+        "user"
+    }
+
+    fn routes(&self, app_state: &AppState) -> Router<AppState> {
+        let user_cache = axum::middleware::from_fn_with_state(
+            (app_state.clone(), USER_CACHE_TAG),
+            response_cache_middleware,
+        );
+
+        let no_auth_route = Router::new()
+            .route("/api/v1/auth/login/", post(auth_api::login))
+            .route("/api/v1/auth/register/", post(auth_api::register))
+            .route("/api/v1/auth/refresh-token/", post(auth_api::refresh_token))
+            .route("/api/v1/auth/logout/", post(auth_api::logout))
+            .route(
+                "/api/v1/auth/oauth/{provider}/",
+                get(auth_api::oauth_authorize),
+            )
+            .route(
+                "/api/v1/auth/oauth/{provider}/callback/",
+                get(auth_api::oauth_callback),
+            )
+            .route(
+                "/api/v1/auth/forgot-password/",
+                post(auth_api::forgot_password),
+            )
+            .route(
+                "/api/v1/auth/reset-password/",
+                post(auth_api::reset_password),
+            )
+            .route(
+                "/api/v1/auth/reset-password/confirm/{token}/",
+                get(auth_api::verify_reset_link).post(auth_api::confirm_reset_password),
+            )
+            .route(
+                "/api/v1/auth/sign-ins/report/",
+                post(auth_api::report_sign_in),
+            )
+            .route("/api/v1/auth/verify-email/", post(auth_api::verify_email))
+            .route(
+                "/api/v1/auth/resend-verification/",
+                post(auth_api::resend_verification),
+            );
+
+        let auth_route = Router::new()
+            .route("/ws/v1/user/", any(user_ws::sync_user_data))
+            .route(
+                "/api/v1/user/profile/",
+                get(user_api::get_profile)
+                    .layer(user_cache.clone())
+                    .patch(user_api::update_profile),
+            )
+            .route(
+                "/api/v1/user/profile/phone/verify/",
+                post(user_api::send_phone_verification),
+            )
+            .route(
+                "/api/v1/user/profile/phone/confirm/",
+                post(user_api::confirm_phone_verification),
+            )
+            .route(
+                "/api/v1/user/profile/emails/",
+                get(user_email_api::search_user_email).post(user_email_api::add_user_email),
+            )
+            .route(
+                "/api/v1/user/profile/emails/{id}/",
+                patch(user_email_api::update_user_email).delete(user_email_api::delete_user_email),
+            )
+            .route(
+                "/api/v1/user/profile/emails/{id}/verify/",
+                post(user_email_api::send_user_email_verification),
+            )
+            .route(
+                "/api/v1/user/profile/emails/{id}/confirm/",
+                post(user_email_api::confirm_user_email),
+            )
+            .route(
+                "/api/v1/user/profile/emails/{id}/primary/",
+                post(user_email_api::make_primary_user_email),
+            )
+            .route(
+                "/api/v1/auth/change-password/",
+                post(auth_api::change_password),
+            )
+            .route(
+                "/api/v1/auth/sessions/",
+                get(auth_api::search_session).delete(auth_api::revoke_other_sessions),
+            )
+            .route(
+                "/api/v1/auth/sessions/{id}/",
+                delete(auth_api::revoke_session),
+            )
+            .route(
+                "/api/v1/user/",
+                get(user_api::search_user)
+                    .layer(user_cache.clone())
+                    .post(user_api::create_user),
+            )
+            .route("/api/v1/user/upload-avatar/", post(user_api::upload_avatar))
+            .route(
+                "/api/v1/admin/users/bulk/",
+                post(user_api::bulk_user_operations).route_layer(
+                    axum::middleware::from_fn_with_state(
+                        RequirePermission("user.bulk"),
+                        require_permission_middleware,
+                    ),
+                ),
+            )
+            .route(
+                "/api/v1/admin/users/{id}/",
+                delete(user_api::hard_delete_user).route_layer(
+                    axum::middleware::from_fn_with_state(
+                        RequirePermission("user.hard_delete"),
+                        require_permission_middleware,
+                    ),
+                ),
+            )
+            .route(
+                "/api/v1/users/{id}/restore/",
+                post(user_api::restore_user).route_layer(axum::middleware::from_fn_with_state(
+                    RequirePermission("user.restore"),
+                    require_permission_middleware,
+                )),
+            )
+            .route(
+                "/api/v1/admin/impersonate/{user_id}/",
+                // Only starting needs the permission: impersonation tokens end their own
+                post(user_api::impersonate_user)
+                    .route_layer(axum::middleware::from_fn_with_state(
+                        RequirePermission("user.impersonate"),
+                        require_permission_middleware,
+                    ))
+                    .delete(user_api::end_impersonation),
+            )
+            .route(
+                "/api/v1/user/{id}/",
+                get(user_api::get_user)
+                    .layer(user_cache)
+                    .patch(user_api::update_user)
+                    .delete(user_api::delete_user),
+            );
+
+        public_api(no_auth_route, app_state).merge(protected_api(auth_route, app_state))
+    }
+
+    fn periodic_jobs(&self) -> Vec<Arc<dyn PeriodicJob>> {
+        vec![
+            Arc::new(CleanupExpiredTokens),
+            Arc::new(PurgeExpiredPasswordResets),
+            Arc::new(PurgeExpiredEmailVerifications),
+            Arc::new(PurgeStaleLoginAttempts),
+            Arc::new(PurgeRetiredSigningKeys),
+        ]
+    }
+}
+
+/// Delete refresh tokens past their expiry
+struct CleanupExpiredTokens;
+
+#[async_trait]
+impl PeriodicJob for CleanupExpiredTokens {
+    fn name(&self) -> &'static str {
+        "cleanup-expired-tokens"
+    }
+
+    fn default_interval(&self) -> Duration {
+        Duration::from_secs(60 * 60)
+    }
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()> {
+        auth_task::clean_expired_tokens(&app_state.db).await
+    }
+}
+
+/// Delete password reset OTPs past their expiry
+struct PurgeExpiredPasswordResets;
+
+#[async_trait]
+impl PeriodicJob for PurgeExpiredPasswordResets {
+    fn name(&self) -> &'static str {
+        "purge-expired-password-resets"
+    }
+
+    fn default_interval(&self) -> Duration {
+        Duration::from_secs(24 * 60 * 60)
+    }
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()> {
+        auth_task::purge_expired_password_resets(&app_state.db).await
+    }
+}
+
+/// Delete email verification links past their expiry
+struct PurgeExpiredEmailVerifications;
+
+#[async_trait]
+impl PeriodicJob for PurgeExpiredEmailVerifications {
+    fn name(&self) -> &'static str {
+        "purge-expired-email-verifications"
+    }
+
+    fn default_interval(&self) -> Duration {
+        Duration::from_secs(24 * 60 * 60)
+    }
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()> {
+        auth_task::purge_expired_email_verifications(&app_state.db).await
+    }
+}
+
+/// Delete failed sign-ins older than the lockout cooldown
+struct PurgeStaleLoginAttempts;
+
+#[async_trait]
+impl PeriodicJob for PurgeStaleLoginAttempts {
+    fn name(&self) -> &'static str {
+        "purge-stale-login-attempts"
+    }
+
+    fn default_interval(&self) -> Duration {
+        Duration::from_secs(60 * 60)
+    }
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()> {
+        auth_task::purge_stale_login_attempts(&app_state.db).await
+    }
+}
+
+/// Delete signing keys whose rotation grace period has ended
+struct PurgeRetiredSigningKeys;
+
+#[async_trait]
+impl PeriodicJob for PurgeRetiredSigningKeys {
+    fn name(&self) -> &'static str {
+        "purge-retired-signing-keys"
+    }
+
+    fn default_interval(&self) -> Duration {
+        Duration::from_secs(60 * 60)
+    }
+
+    async fn run(&self, app_state: &AppState) -> anyhow::Result<()> {
+        auth_task::purge_retired_signing_keys(&app_state.db).await
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::Utc;
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    config::setting::Setting, core::context::Context, user::entity::email_verification_token,
+};
+
+/// The token `token` while it can still be used
+pub async fn find_unexpired_by_token(
+    context: &Context,
+    token: &str,
+) -> Result<Option<email_verification_token::Model>, DbErr> {
+    email_verification_token::Entity::find()
+        .filter(
+            email_verification_token::Column::Token.eq(Setting::new().token_hasher().hash(token)),
+        )
+        .filter(email_verification_token::Column::ExpiresAt.gt(Utc::now().naive_utc()))
+        .one(context.txn())
+        .await
+}
+
+/// Insert `verification_token`, storing the hash of its plaintext token
+pub async fn create(
+    context: &Context,
+    mut verification_token: email_verification_token::ActiveModel,
+) -> Result<email_verification_token::Model, DbErr> {
+    if let ActiveValue::Set(token) = &verification_token.token {
+        verification_token.token = Set(Setting::new().token_hasher().hash(token));
+    }
+    verification_token.created_at = Set(Some(Utc::now().naive_utc()));
+
+    verification_token.insert(context.txn()).await
+}
+
+pub async fn delete_expired(context: &Context) -> Result<(), DbErr> {
+    email_verification_token::Entity::delete_many()
+        .filter(email_verification_token::Column::ExpiresAt.lt(Utc::now().naive_utc()))
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_by_user_id(context: &Context, user_id: i32) -> Result<(), DbErr> {
+    email_verification_token::Entity::delete_many()
+        .filter(email_verification_token::Column::UserId.eq(user_id))
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{DbErr, entity::*, query::*, sea_query::Expr};
+
+use crate::{core::context::Context, user::entity::impersonation};
+
+pub async fn create(
+    context: &Context,
+    mut impersonation: impersonation::ActiveModel,
+) -> Result<impersonation::Model, DbErr> {
+    impersonation.created_at = Set(Some(Utc::now().naive_utc()));
+    impersonation.insert(context.txn()).await
+}
+
+pub async fn find_by_token_id(
+    context: &Context,
+    token_id: &str,
+) -> Result<Option<impersonation::Model>, DbErr> {
+    impersonation::Entity::find()
+        .filter(impersonation::Column::TokenId.eq(token_id))
+        .one(context.txn())
+        .await
+}
+
+/// End the impersonations of `user_id` by `admin_id` still running at `now`, returning those
+/// ended
+pub async fn end_active(
+    context: &Context,
+    admin_id: i32,
+    user_id: i32,
+    now: NaiveDateTime,
+) -> Result<Vec<impersonation::Model>, DbErr> {
+    let active = impersonation::Entity::find()
+        .filter(impersonation::Column::AdminId.eq(admin_id))
+        .filter(impersonation::Column::UserId.eq(user_id))
+        .filter(impersonation::Column::EndedAt.is_null())
+        .filter(impersonation::Column::ExpiresAt.gt(now))
+        .all(context.txn())
+        .await?;
+    if active.is_empty() {
+        return Ok(active);
+    }
+
+    impersonation::Entity::update_many()
+        .col_expr(impersonation::Column::EndedAt, Expr::value(now))
+        .filter(impersonation::Column::Id.is_in(active.iter().map(|record| record.id)))
+        .exec(context.txn())
+        .await?;
+    Ok(active)
+}
+
+/// Impersonations whose token expired no longer need to be checked against
+pub async fn delete_expired(context: &Context) -> Result<u64, DbErr> {
+    let now = Utc::now().naive_utc();
+    let result = impersonation::Entity::delete_many()
+        .filter(impersonation::Column::ExpiresAt.lt(now))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
@@ -0,0 +1,87 @@
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    DbErr,
+    entity::*,
+    query::*,
+    sea_query::{Expr, IntoCondition},
+};
+
+use crate::{core::context::Context, user::entity::login_attempt};
+
+pub async fn create(
+    context: &Context,
+    email: Option<String>,
+    ip_address: Option<String>,
+) -> Result<login_attempt::Model, DbErr> {
+    login_attempt::ActiveModel {
+        email: Set(email),
+        ip_address: Set(ip_address),
+        created_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    }
+    .insert(context.txn())
+    .await
+}
+
+/// Number of failed sign-ins with the normalized `email` after `since`, and when the last of
+/// them was
+pub async fn count_by_email_since(
+    context: &Context,
+    email: &str,
+    since: NaiveDateTime,
+) -> Result<(u64, Option<NaiveDateTime>), DbErr> {
+    count_since(context, login_attempt::Column::Email.eq(email), since).await
+}
+
+/// Number of failed sign-ins from `ip_address` after `since`, whatever the account, and when
+/// the last of them was
+pub async fn count_by_ip_address_since(
+    context: &Context,
+    ip_address: &str,
+    since: NaiveDateTime,
+) -> Result<(u64, Option<NaiveDateTime>), DbErr> {
+    count_since(
+        context,
+        login_attempt::Column::IpAddress.eq(ip_address),
+        since,
+    )
+    .await
+}
+
+async fn count_since(
+    context: &Context,
+    condition: impl IntoCondition,
+    since: NaiveDateTime,
+) -> Result<(u64, Option<NaiveDateTime>), DbErr> {
+    let query = login_attempt::Entity::find()
+        .filter(condition)
+        .filter(login_attempt::Column::CreatedAt.gt(since));
+    let count = query.clone().count(context.txn()).await?;
+    let last = query
+        .order_by_desc(login_attempt::Column::CreatedAt)
+        .one(context.txn())
+        .await?;
+    Ok((count, last.map(|attempt| attempt.created_at)))
+}
+
+/// Stop counting the failed sign-ins with the normalized `email` against it, while they still
+/// count against the client IPs they came from
+pub async fn detach_email(context: &Context, email: &str) -> Result<(), DbErr> {
+    login_attempt::Entity::update_many()
+        .col_expr(
+            login_attempt::Column::Email,
+            Expr::value(Option::<String>::None),
+        )
+        .filter(login_attempt::Column::Email.eq(email))
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_before(context: &Context, before: NaiveDateTime) -> Result<u64, DbErr> {
+    let result = login_attempt::Entity::delete_many()
+        .filter(login_attempt::Column::CreatedAt.lt(before))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
@@ -1,3 +1,13 @@
+pub mod email_verification_repository;
+pub mod impersonation_repository;
+pub mod login_attempt_repository;
+pub mod oauth_account_repository;
 pub mod password_reset_repository;
+pub mod permission_repository;
+pub mod phone_verification_repository;
 pub mod refresh_token_repository;
+pub mod role_repository;
+pub mod security_event_repository;
+pub mod signing_key_repository;
+pub mod user_email_repository;
 pub mod user_repository;
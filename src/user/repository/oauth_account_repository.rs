@@ -0,0 +1,39 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    core::context::Context,
+    user::entity::{oauth_account, sea_orm_active_enums::IdentityProvider},
+};
+
+/// Account `subject` of `provider` is linked to, if any
+pub async fn find_by_provider_subject(
+    context: &Context,
+    provider: IdentityProvider,
+    subject: &str,
+) -> Result<Option<oauth_account::Model>, DbErr> {
+    oauth_account::Entity::find()
+        .filter(oauth_account::Column::Provider.eq(provider))
+        .filter(oauth_account::Column::Subject.eq(subject))
+        .one(context.txn())
+        .await
+}
+
+pub async fn find_by_user_id(
+    context: &Context,
+    user_id: i32,
+) -> Result<Vec<oauth_account::Model>, DbErr> {
+    oauth_account::Entity::find()
+        .filter(oauth_account::Column::UserId.eq(user_id))
+        .order_by_asc(oauth_account::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+pub async fn create(
+    context: &Context,
+    mut oauth_account: oauth_account::ActiveModel,
+) -> Result<oauth_account::Model, DbErr> {
+    oauth_account.created_at = Set(Some(chrono::Utc::now().naive_utc()));
+
+    oauth_account.insert(context.txn()).await
+}
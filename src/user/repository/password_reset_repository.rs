@@ -1,22 +1,38 @@
 use chrono::Utc;
 use sea_orm::{DbErr, entity::*, query::*};
 
-use crate::{core::context::Context, user::entity::password_reset_token};
+use crate::{config::setting::Setting, core::context::Context, user::entity::password_reset_token};
 
 pub async fn find_by_token(
     context: &Context,
     otp: &str,
 ) -> Result<Option<password_reset_token::Model>, DbErr> {
     password_reset_token::Entity::find()
-        .filter(password_reset_token::Column::Token.eq(otp))
+        .filter(password_reset_token::Column::Token.eq(Setting::new().token_hasher().hash(otp)))
         .one(context.txn())
         .await
 }
 
+/// The OTP most recently sent to `user_id`, which failed attempts count against
+pub async fn find_latest_by_user_id(
+    context: &Context,
+    user_id: i32,
+) -> Result<Option<password_reset_token::Model>, DbErr> {
+    password_reset_token::Entity::find()
+        .filter(password_reset_token::Column::UserId.eq(user_id))
+        .order_by_desc(password_reset_token::Column::Id)
+        .one(context.txn())
+        .await
+}
+
+/// Insert `reset_token`, storing the hash of its plaintext OTP
 pub async fn create(
     context: &Context,
     mut reset_token: password_reset_token::ActiveModel,
 ) -> Result<password_reset_token::Model, sea_orm::DbErr> {
+    if let ActiveValue::Set(otp) = &reset_token.token {
+        reset_token.token = Set(Setting::new().token_hasher().hash(otp));
+    }
     reset_token.created_at = Set(Some(chrono::Utc::now().naive_utc()));
 
     reset_token.insert(context.txn()).await
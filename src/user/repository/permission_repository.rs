@@ -0,0 +1,46 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    core::context::Context,
+    user::entity::{permission, role_permission, user_role_assignment},
+};
+
+pub async fn find_by_name(
+    context: &Context,
+    name: &str,
+) -> Result<Option<permission::Model>, DbErr> {
+    permission::Entity::find()
+        .filter(permission::Column::Name.eq(name))
+        .one(context.txn())
+        .await
+}
+
+/// Names of the permissions granted by the roles of user `user_id`, in one query
+pub async fn find_names_by_user_id(context: &Context, user_id: i32) -> Result<Vec<String>, DbErr> {
+    let role_ids = user_role_assignment::Entity::find()
+        .select_only()
+        .column(user_role_assignment::Column::RoleId)
+        .filter(user_role_assignment::Column::UserId.eq(user_id))
+        .into_query();
+    let permission_ids = role_permission::Entity::find()
+        .select_only()
+        .column(role_permission::Column::PermissionId)
+        .filter(role_permission::Column::RoleId.in_subquery(role_ids))
+        .into_query();
+
+    permission::Entity::find()
+        .select_only()
+        .column(permission::Column::Name)
+        .filter(permission::Column::Id.in_subquery(permission_ids))
+        .order_by_asc(permission::Column::Name)
+        .into_tuple()
+        .all(context.txn())
+        .await
+}
+
+pub async fn create(
+    context: &Context,
+    permission: permission::ActiveModel,
+) -> Result<permission::Model, DbErr> {
+    permission.insert(context.txn()).await
+}
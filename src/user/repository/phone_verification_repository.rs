@@ -0,0 +1,37 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{core::context::Context, user::entity::phone_verification_token};
+
+pub async fn find_by_user_id(
+    context: &Context,
+    user_id: i32,
+) -> Result<Option<phone_verification_token::Model>, DbErr> {
+    phone_verification_token::Entity::find()
+        .filter(phone_verification_token::Column::UserId.eq(user_id))
+        .one(context.txn())
+        .await
+}
+
+pub async fn create(
+    context: &Context,
+    mut verification_token: phone_verification_token::ActiveModel,
+) -> Result<phone_verification_token::Model, DbErr> {
+    verification_token.created_at = Set(Some(chrono::Utc::now().naive_utc()));
+
+    verification_token.insert(context.txn()).await
+}
+
+pub async fn update(
+    context: &Context,
+    verification_token: phone_verification_token::ActiveModel,
+) -> Result<phone_verification_token::Model, DbErr> {
+    verification_token.update(context.txn()).await
+}
+
+pub async fn delete_by_user_id(context: &Context, user_id: i32) -> Result<(), DbErr> {
+    phone_verification_token::Entity::delete_many()
+        .filter(phone_verification_token::Column::UserId.eq(user_id))
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
@@ -1,15 +1,19 @@
 use chrono::Utc;
 use sea_orm::{entity::*, query::*};
 
-use crate::core::{context::Context, db::pagination::calculate_offset};
+use crate::config::setting::Setting;
+use crate::core::{context::Context, db::query::paginate};
 use crate::user::entity::refresh_token;
 
 #[derive(Default)]
 pub struct RefreshTokenSearchParams<'a> {
     pub ids: Option<&'a [i32]>,
     pub user_id: Option<i32>,
+    /// Plaintext token, matched exactly against the stored hash
     pub token: Option<&'a str>,
     pub is_expired: Option<bool>,
+    /// Rotated tokens are kept only to detect reuse; `Some(false)` lists live sessions
+    pub is_rotated: Option<bool>,
     pub page: Option<u64>,
     pub page_size: Option<u64>,
 }
@@ -20,15 +24,13 @@ pub async fn search(
 ) -> Result<(Vec<refresh_token::Model>, usize), sea_orm::DbErr> {
     let now = Utc::now().naive_utc();
     let total_count = build_search_query(params, now).count(context.txn()).await? as usize;
-    let mut query = build_search_query(params, now);
-
-    // Apply pagination
-    if let Some(page_size) = params.page_size {
-        let offset = calculate_offset(params.page, page_size);
-        query = query.limit(page_size).offset(offset);
-    }
-
-    let refresh_tokens = query.all(context.txn()).await?;
+    let refresh_tokens = paginate(
+        build_search_query(params, now).order_by_desc(refresh_token::Column::Id),
+        params.page,
+        params.page_size,
+    )
+    .all(context.txn())
+    .await?;
 
     Ok((refresh_tokens, total_count))
 }
@@ -46,7 +48,8 @@ fn build_search_query(
         query = query.filter(refresh_token::Column::UserId.eq(user_id));
     }
     if let Some(token) = params.token {
-        query = query.filter(refresh_token::Column::Token.contains(token));
+        query = query
+            .filter(refresh_token::Column::Token.eq(Setting::new().token_hasher().hash(token)));
     }
     match params.is_expired {
         Some(true) => {
@@ -57,6 +60,15 @@ fn build_search_query(
         }
         None => {}
     }
+    match params.is_rotated {
+        Some(true) => {
+            query = query.filter(refresh_token::Column::RotatedAt.is_not_null());
+        }
+        Some(false) => {
+            query = query.filter(refresh_token::Column::RotatedAt.is_null());
+        }
+        None => {}
+    }
 
     query
 }
@@ -66,7 +78,7 @@ pub async fn find_by_token(
     token: &str,
 ) -> Result<Option<refresh_token::Model>, sea_orm::DbErr> {
     refresh_token::Entity::find()
-        .filter(refresh_token::Column::Token.eq(token))
+        .filter(refresh_token::Column::Token.eq(Setting::new().token_hasher().hash(token)))
         .one(context.txn())
         .await
 }
@@ -79,30 +91,124 @@ pub async fn find_by_user_and_token(
     let now = Utc::now().naive_utc();
     refresh_token::Entity::find()
         .filter(refresh_token::Column::UserId.eq(user_id))
-        .filter(refresh_token::Column::Token.eq(token))
+        .filter(refresh_token::Column::Token.eq(Setting::new().token_hasher().hash(token)))
         .filter(refresh_token::Column::ExpiresAt.gt(now))
         .one(context.txn())
         .await
 }
 
+/// Insert `refresh_token`, storing the hash of its plaintext `token`
 pub async fn create(
     context: &Context,
-    refresh_token: refresh_token::ActiveModel,
+    mut refresh_token: refresh_token::ActiveModel,
 ) -> Result<refresh_token::Model, sea_orm::DbErr> {
+    if let ActiveValue::Set(token) = &refresh_token.token {
+        refresh_token.token = Set(Setting::new().token_hasher().hash(token));
+    }
     refresh_token.insert(context.txn()).await
 }
 
 pub async fn delete_by_token(context: &Context, token: &str) -> Result<(), sea_orm::DbErr> {
     refresh_token::Entity::delete_many()
-        .filter(refresh_token::Column::Token.eq(token))
+        .filter(refresh_token::Column::Token.eq(Setting::new().token_hasher().hash(token)))
         .exec(context.txn())
         .await?;
     Ok(())
 }
 
 pub async fn delete_by_tokens(context: &Context, tokens: &[String]) -> Result<(), sea_orm::DbErr> {
+    let hasher = Setting::new().token_hasher();
+    let hashes: Vec<String> = tokens.iter().map(|token| hasher.hash(token)).collect();
+    refresh_token::Entity::delete_many()
+        .filter(refresh_token::Column::Token.is_in(hashes))
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_by_ids(context: &Context, ids: &[i32]) -> Result<(), sea_orm::DbErr> {
+    refresh_token::Entity::delete_many()
+        .filter(refresh_token::Column::Id.is_in(ids.to_vec()))
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
+
+/// Mark `refresh_token` as exchanged for a new token, which joins its family. Tokens without
+/// one start a family here.
+pub async fn mark_rotated(
+    context: &Context,
+    refresh_token: refresh_token::Model,
+) -> Result<refresh_token::Model, sea_orm::DbErr> {
+    let family_id = refresh_token
+        .family_id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let mut refresh_token: refresh_token::ActiveModel = refresh_token.into();
+    refresh_token.family_id = Set(Some(family_id));
+    refresh_token.rotated_at = Set(Some(Utc::now().naive_utc()));
+    refresh_token.update(context.txn()).await
+}
+
+/// Delete every token of the family `family_id`, rotated or not
+pub async fn delete_by_family_id(
+    context: &Context,
+    family_id: &str,
+) -> Result<u64, sea_orm::DbErr> {
+    Ok(refresh_token::Entity::delete_many()
+        .filter(refresh_token::Column::FamilyId.eq(family_id))
+        .exec(context.txn())
+        .await?
+        .rows_affected)
+}
+
+/// Delete the sessions of `user_id` opened from `device_info` at `ip_address`, including
+/// the ones their refresh tokens were rotated into
+pub async fn delete_by_user_and_client(
+    context: &Context,
+    user_id: i32,
+    device_info: Option<&str>,
+    ip_address: Option<&str>,
+) -> Result<u64, sea_orm::DbErr> {
+    let mut query =
+        refresh_token::Entity::delete_many().filter(refresh_token::Column::UserId.eq(user_id));
+    query = match device_info {
+        Some(device_info) => query.filter(refresh_token::Column::DeviceInfo.eq(device_info)),
+        None => query.filter(refresh_token::Column::DeviceInfo.is_null()),
+    };
+    query = match ip_address {
+        Some(ip_address) => query.filter(refresh_token::Column::IpAddress.eq(ip_address)),
+        None => query.filter(refresh_token::Column::IpAddress.is_null()),
+    };
+    Ok(query.exec(context.txn()).await?.rows_affected)
+}
+
+/// Delete every session of `user_id` except the one `current` belongs to, its rotated
+/// tokens included
+pub async fn delete_other_sessions(
+    context: &Context,
+    user_id: i32,
+    current: Option<&refresh_token::Model>,
+) -> Result<u64, sea_orm::DbErr> {
+    let mut query =
+        refresh_token::Entity::delete_many().filter(refresh_token::Column::UserId.eq(user_id));
+    if let Some(current) = current {
+        // Tokens without a family compare to NULL, so they are kept by id only
+        query = match &current.family_id {
+            Some(family_id) => query.filter(
+                Condition::any()
+                    .add(refresh_token::Column::FamilyId.is_null())
+                    .add(refresh_token::Column::FamilyId.ne(family_id)),
+            ),
+            None => query.filter(refresh_token::Column::Id.ne(current.id)),
+        };
+    }
+    Ok(query.exec(context.txn()).await?.rows_affected)
+}
+
+pub async fn delete_by_user_id(context: &Context, user_id: i32) -> Result<(), sea_orm::DbErr> {
     refresh_token::Entity::delete_many()
-        .filter(refresh_token::Column::Token.is_in(tokens.to_vec()))
+        .filter(refresh_token::Column::UserId.eq(user_id))
         .exec(context.txn())
         .await?;
     Ok(())
@@ -0,0 +1,75 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    core::{audit, context::Context},
+    user::entity::{role, role_permission, user_role_assignment},
+};
+
+/// `resource_type` of the audit log entries written for roles
+const AUDIT_ROLE: &str = "role";
+/// `resource_type` of the audit log entries written for the permissions roles grant
+const AUDIT_ROLE_PERMISSION: &str = "role_permission";
+/// `resource_type` of the audit log entries written for the roles users are given
+const AUDIT_USER_ROLE_ASSIGNMENT: &str = "user_role_assignment";
+
+pub async fn find_by_name(context: &Context, name: &str) -> Result<Option<role::Model>, DbErr> {
+    role::Entity::find()
+        .filter(role::Column::Name.eq(name))
+        .one(context.txn())
+        .await
+}
+
+pub async fn create(context: &Context, mut role: role::ActiveModel) -> Result<role::Model, DbErr> {
+    role.created_at = Set(Some(chrono::Utc::now().naive_utc()));
+
+    let role = role.insert(context.txn()).await?;
+    audit::record_create(context, AUDIT_ROLE, &role).await?;
+
+    Ok(role)
+}
+
+/// Let role `role_id` grant permission `permission_id`
+pub async fn grant_permission(
+    context: &Context,
+    role_id: i32,
+    permission_id: i32,
+) -> Result<(), DbErr> {
+    let grant = role_permission::ActiveModel {
+        role_id: Set(role_id),
+        permission_id: Set(permission_id),
+    }
+    .insert(context.txn())
+    .await?;
+    audit::record_create(context, AUDIT_ROLE_PERMISSION, &grant).await?;
+    Ok(())
+}
+
+/// Give role `role_id` to user `user_id`
+pub async fn assign_to_user(context: &Context, user_id: i32, role_id: i32) -> Result<(), DbErr> {
+    let assignment = user_role_assignment::ActiveModel {
+        user_id: Set(user_id),
+        role_id: Set(role_id),
+        created_at: Set(Some(chrono::Utc::now().naive_utc())),
+    }
+    .insert(context.txn())
+    .await?;
+    audit::record_create(context, AUDIT_USER_ROLE_ASSIGNMENT, &assignment).await?;
+    Ok(())
+}
+
+/// Take role `role_id` away from user `user_id`
+pub async fn revoke_from_user(context: &Context, user_id: i32, role_id: i32) -> Result<(), DbErr> {
+    let Some(before) = user_role_assignment::Entity::find_by_id((user_id, role_id))
+        .one(context.txn())
+        .await?
+    else {
+        return Ok(());
+    };
+    user_role_assignment::Entity::delete_many()
+        .filter(user_role_assignment::Column::UserId.eq(user_id))
+        .filter(user_role_assignment::Column::RoleId.eq(role_id))
+        .exec(context.txn())
+        .await?;
+    audit::record_delete(context, AUDIT_USER_ROLE_ASSIGNMENT, &before, None).await?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{
+    config::setting::Setting,
+    core::context::Context,
+    user::entity::{sea_orm_active_enums::SecurityEventKind, security_event},
+};
+
+/// Insert `security_event`, storing the hash of its plaintext report token if it has one
+pub async fn create(
+    context: &Context,
+    mut security_event: security_event::ActiveModel,
+) -> Result<security_event::Model, DbErr> {
+    if let ActiveValue::Set(Some(token)) = &security_event.report_token {
+        security_event.report_token = Set(Some(Setting::new().token_hasher().hash(token)));
+    }
+    security_event.created_at = Set(Some(chrono::Utc::now().naive_utc()));
+
+    security_event.insert(context.txn()).await
+}
+
+pub async fn update(
+    context: &Context,
+    security_event: security_event::ActiveModel,
+) -> Result<security_event::Model, DbErr> {
+    security_event.update(context.txn()).await
+}
+
+/// Sign-ins of `user_id` on record, except those the user reported as not theirs
+pub async fn find_sign_ins_by_user_id(
+    context: &Context,
+    user_id: i32,
+) -> Result<Vec<security_event::Model>, DbErr> {
+    security_event::Entity::find()
+        .filter(security_event::Column::UserId.eq(user_id))
+        .filter(
+            security_event::Column::Kind
+                .is_in([SecurityEventKind::SignIn, SecurityEventKind::NewSignIn]),
+        )
+        .filter(security_event::Column::ReportedAt.is_null())
+        .order_by_asc(security_event::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+pub async fn find_by_report_token(
+    context: &Context,
+    token: &str,
+) -> Result<Option<security_event::Model>, DbErr> {
+    security_event::Entity::find()
+        .filter(security_event::Column::ReportToken.eq(Setting::new().token_hasher().hash(token)))
+        .one(context.txn())
+        .await
+}
@@ -0,0 +1,58 @@
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{DbErr, entity::*, query::*, sea_query::Expr};
+
+use crate::{core::context::Context, user::entity::signing_key};
+
+/// The key new tokens are signed with: the newest one not scheduled for retirement
+pub async fn find_active(context: &Context) -> Result<Option<signing_key::Model>, DbErr> {
+    signing_key::Entity::find()
+        .filter(signing_key::Column::RetiresAt.is_null())
+        .order_by_desc(signing_key::Column::Id)
+        .one(context.txn())
+        .await
+}
+
+/// A key that may still verify tokens: active, or retiring but not yet retired
+pub async fn find_verifiable_by_kid(
+    context: &Context,
+    kid: &str,
+) -> Result<Option<signing_key::Model>, DbErr> {
+    let now = Utc::now().naive_utc();
+    signing_key::Entity::find()
+        .filter(signing_key::Column::Kid.eq(kid))
+        .filter(
+            Condition::any()
+                .add(signing_key::Column::RetiresAt.is_null())
+                .add(signing_key::Column::RetiresAt.gt(now)),
+        )
+        .one(context.txn())
+        .await
+}
+
+pub async fn create(
+    context: &Context,
+    mut signing_key: signing_key::ActiveModel,
+) -> Result<signing_key::Model, DbErr> {
+    signing_key.created_at = Set(Some(Utc::now().naive_utc()));
+
+    signing_key.insert(context.txn()).await
+}
+
+/// Schedule every active key to stop verifying tokens at `retires_at`
+pub async fn retire_active(context: &Context, retires_at: NaiveDateTime) -> Result<u64, DbErr> {
+    let result = signing_key::Entity::update_many()
+        .col_expr(signing_key::Column::RetiresAt, Expr::value(retires_at))
+        .filter(signing_key::Column::RetiresAt.is_null())
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
+
+pub async fn delete_retired(context: &Context) -> Result<u64, DbErr> {
+    let now = Utc::now().naive_utc();
+    let result = signing_key::Entity::delete_many()
+        .filter(signing_key::Column::RetiresAt.lte(now))
+        .exec(context.txn())
+        .await?;
+    Ok(result.rows_affected)
+}
@@ -0,0 +1,82 @@
+use sea_orm::{DbErr, entity::*, query::*};
+
+use crate::{config::setting::Setting, core::context::Context, user::entity::user_email};
+
+pub async fn find_by_id(context: &Context, id: i32) -> Result<Option<user_email::Model>, DbErr> {
+    user_email::Entity::find_by_id(id).one(context.txn()).await
+}
+
+/// Secondary addresses of `user_id`, oldest first
+pub async fn find_by_user_id(
+    context: &Context,
+    user_id: i32,
+) -> Result<Vec<user_email::Model>, DbErr> {
+    user_email::Entity::find()
+        .filter(user_email::Column::UserId.eq(user_id))
+        .order_by_asc(user_email::Column::Id)
+        .all(context.txn())
+        .await
+}
+
+/// Secondary address matching `email` once normalized, verified or not
+pub async fn find_by_email(
+    context: &Context,
+    email: &str,
+) -> Result<Option<user_email::Model>, DbErr> {
+    user_email::Entity::find()
+        .filter(user_email::Column::NormalizedEmail.eq(Setting::new().email.normalize(email)))
+        .one(context.txn())
+        .await
+}
+
+/// Verified secondary addresses of `user_id` that notification emails are also sent to
+pub async fn find_notification_addresses(
+    context: &Context,
+    user_id: i32,
+) -> Result<Vec<String>, DbErr> {
+    user_email::Entity::find()
+        .select_only()
+        .column(user_email::Column::Email)
+        .filter(user_email::Column::UserId.eq(user_id))
+        .filter(user_email::Column::VerifiedAt.is_not_null())
+        .filter(user_email::Column::ReceivesNotifications.eq(true))
+        .order_by_asc(user_email::Column::Id)
+        .into_tuple()
+        .all(context.txn())
+        .await
+}
+
+/// Keep `normalized_email` in step with an `email` being saved
+fn normalize_email(user_email: &mut user_email::ActiveModel) {
+    if let sea_orm::ActiveValue::Set(email) = &user_email.email {
+        user_email.normalized_email = Set(Setting::new().email.normalize(email));
+    }
+}
+
+pub async fn create(
+    context: &Context,
+    mut user_email: user_email::ActiveModel,
+) -> Result<user_email::Model, DbErr> {
+    normalize_email(&mut user_email);
+    user_email.created_at = Set(Some(chrono::Utc::now().naive_utc()));
+    user_email.updated_at = Set(Some(chrono::Utc::now().naive_utc()));
+
+    user_email.insert(context.txn()).await
+}
+
+pub async fn update(
+    context: &Context,
+    mut user_email: user_email::ActiveModel,
+) -> Result<user_email::Model, DbErr> {
+    normalize_email(&mut user_email);
+    user_email.updated_at = Set(Some(chrono::Utc::now().naive_utc()));
+
+    user_email.update(context.txn()).await
+}
+
+pub async fn delete_by_id(context: &Context, id: i32) -> Result<(), DbErr> {
+    user_email::Entity::delete_by_id(id)
+        .exec(context.txn())
+        .await?;
+    Ok(())
+}
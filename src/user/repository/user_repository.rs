@@ -1,14 +1,19 @@
-use sea_orm::{entity::*, query::*};
+use sea_orm::{entity::*, query::*, sea_query::Expr};
 
+use crate::config::setting::Setting;
 use crate::core::{
+    audit::{self, AuditAction},
     context::Context,
     db::{
         ordering::{ApplyOrdering, OrderBy, OrderByField},
-        pagination::calculate_offset,
+        query::paginate,
     },
 };
 use crate::user::entity::{sea_orm_active_enums::UserRole, user};
 
+/// `resource_type` of the audit log entries written for users
+const AUDIT_ENTITY: &str = "user";
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum UserOrderByField {
     Id,
@@ -20,6 +25,15 @@ pub enum UserOrderByField {
 }
 
 impl OrderByField for UserOrderByField {
+    const FIELDS: &'static [&'static str] = &[
+        "id",
+        "email",
+        "first_name",
+        "last_name",
+        "created_at",
+        "updated_at",
+    ];
+
     fn from_str(s: &str) -> Option<Self> {
         match s {
             "id" => Some(UserOrderByField::Id),
@@ -52,6 +66,7 @@ pub struct UserSearchParams<'a> {
     pub email: Option<&'a str>,
     pub first_name: Option<&'a str>,
     pub last_name: Option<&'a str>,
+    pub role: Option<UserRole>,
     pub page: Option<u64>,
     pub page_size: Option<u64>,
     pub order_by: Option<&'a [UserOrderBy]>,
@@ -76,19 +91,15 @@ pub async fn search(
         });
     }
 
-    // Apply pagination
-    if let Some(page_size) = params.page_size {
-        let offset = calculate_offset(params.page, page_size);
-        query = query.limit(page_size).offset(offset);
-    }
-
-    let users = query.all(context.txn()).await?;
+    let users = paginate(query, params.page, params.page_size)
+        .all(context.txn())
+        .await?;
 
     Ok((users, total_count))
 }
 
 fn build_search_query(params: &UserSearchParams<'_>) -> Select<user::Entity> {
-    let mut query = user::Entity::find();
+    let mut query = user::Entity::find().filter(user::Column::DeletedAt.is_null());
 
     if let Some(ids) = params.ids {
         query = query.filter(user::Column::Id.is_in(ids.to_vec()));
@@ -102,11 +113,25 @@ fn build_search_query(params: &UserSearchParams<'_>) -> Select<user::Entity> {
     if let Some(last_name) = params.last_name {
         query = query.filter(user::Column::LastName.contains(last_name));
     }
+    if let Some(role) = &params.role {
+        query = query.filter(user::Column::Role.eq(role.clone()));
+    }
 
     query
 }
 
 pub async fn find_by_id(context: &Context, id: i32) -> Result<Option<user::Model>, sea_orm::DbErr> {
+    user::Entity::find_by_id(id)
+        .filter(user::Column::DeletedAt.is_null())
+        .one(context.txn())
+        .await
+}
+
+/// User `id`, even if it was deleted, e.g. to restore it
+pub async fn find_by_id_with_deleted(
+    context: &Context,
+    id: i32,
+) -> Result<Option<user::Model>, sea_orm::DbErr> {
     user::Entity::find_by_id(id).one(context.txn()).await
 }
 
@@ -114,12 +139,37 @@ pub async fn find_by_email(
     context: &Context,
     email: &str,
 ) -> Result<Option<user::Model>, sea_orm::DbErr> {
-    user::Entity::find()
-        .filter(user::Column::Email.eq(email))
+    find_by_email_query(email)
+        .filter(user::Column::DeletedAt.is_null())
         .one(context.txn())
         .await
 }
 
+/// User whose address is `email`, even if it was deleted. Deleted users keep their address
+/// until they are removed for good, so they can be restored.
+pub async fn find_by_email_with_deleted(
+    context: &Context,
+    email: &str,
+) -> Result<Option<user::Model>, sea_orm::DbErr> {
+    find_by_email_query(email).one(context.txn()).await
+}
+
+fn find_by_email_query(email: &str) -> Select<user::Entity> {
+    // Rows saved under earlier `EMAIL_*` settings are still found by their exact address
+    user::Entity::find().filter(
+        Condition::any()
+            .add(user::Column::NormalizedEmail.eq(Setting::new().email.normalize(email)))
+            .add(user::Column::Email.eq(email)),
+    )
+}
+
+/// Keep `normalized_email` in step with an `email` being saved
+fn normalize_email(user: &mut user::ActiveModel) {
+    if let sea_orm::ActiveValue::Set(email) = &user.email {
+        user.normalized_email = Set(Setting::new().email.normalize(email));
+    }
+}
+
 pub async fn create(
     context: &Context,
     mut user: user::ActiveModel,
@@ -127,32 +177,147 @@ pub async fn create(
     if matches!(user.role, sea_orm::ActiveValue::NotSet) {
         user.role = Set(UserRole::User);
     }
+    normalize_email(&mut user);
     user.created_at = Set(Some(chrono::Utc::now().naive_utc()));
     user.updated_at = Set(Some(chrono::Utc::now().naive_utc()));
     user.created_user_id = Set(context.user.as_ref().map(|u| u.id));
     user.updated_user_id = Set(context.user.as_ref().map(|u| u.id));
 
-    user.insert(context.txn()).await
+    let user = user.insert(context.txn()).await?;
+    audit::record_create(context, AUDIT_ENTITY, &user).await?;
+
+    Ok(user)
 }
 
 pub async fn update(
+    context: &Context,
+    user: user::ActiveModel,
+) -> Result<user::Model, sea_orm::DbErr> {
+    save(context, user, AuditAction::Update).await
+}
+
+/// Update `user`, recording the fields it changed in the audit trail as `action`
+async fn save(
     context: &Context,
     mut user: user::ActiveModel,
+    action: AuditAction,
 ) -> Result<user::Model, sea_orm::DbErr> {
+    let before = match user.id.try_as_ref() {
+        Some(id) => find_by_id_with_deleted(context, *id).await?,
+        None => None,
+    };
+    normalize_email(&mut user);
     user.updated_at = Set(Some(chrono::Utc::now().naive_utc()));
     user.updated_user_id = Set(context.user.as_ref().map(|u| u.id));
 
-    user.update(context.txn()).await
+    let after = user.update(context.txn()).await?;
+    if let Some(before) = before {
+        match action {
+            AuditAction::Delete => {
+                audit::record_delete(context, AUDIT_ENTITY, &before, Some(&after)).await?
+            }
+            _ => audit::record_update(context, AUDIT_ENTITY, &before, &after).await?,
+        }
+    }
+
+    Ok(after)
+}
+
+/// Show `file_id` as the avatar of `user_id`, or no avatar
+pub async fn set_avatar_file_id(
+    context: &Context,
+    user_id: i32,
+    file_id: Option<i32>,
+) -> Result<(), sea_orm::DbErr> {
+    let Some(before) = find_by_id_with_deleted(context, user_id).await? else {
+        return Ok(());
+    };
+    user::Entity::update_many()
+        .col_expr(user::Column::AvatarFileId, Expr::value(file_id))
+        .filter(user::Column::Id.eq(user_id))
+        .exec(context.txn())
+        .await?;
+    let after = user::Model {
+        avatar_file_id: file_id,
+        ..before.clone()
+    };
+    audit::record_update(context, AUDIT_ENTITY, &before, &after).await?;
+
+    Ok(())
+}
+
+/// Stop showing `file_id` as an avatar, e.g. once it is deleted
+pub async fn clear_avatar_file_id(context: &Context, file_id: i32) -> Result<(), sea_orm::DbErr> {
+    let users = user::Entity::find()
+        .filter(user::Column::AvatarFileId.eq(file_id))
+        .all(context.txn())
+        .await?;
+    if users.is_empty() {
+        return Ok(());
+    }
+    user::Entity::update_many()
+        .col_expr(user::Column::AvatarFileId, Expr::value(Option::<i32>::None))
+        .filter(user::Column::AvatarFileId.eq(file_id))
+        .exec(context.txn())
+        .await?;
+    for before in users {
+        let after = user::Model {
+            avatar_file_id: None,
+            ..before.clone()
+        };
+        audit::record_update(context, AUDIT_ENTITY, &before, &after).await?;
+    }
+
+    Ok(())
 }
 
-pub async fn delete(context: &Context, user: user::ActiveModel) -> Result<(), sea_orm::DbErr> {
-    user.delete(context.txn()).await?;
+/// Soft delete `user`; its row stays until [`hard_delete_by_id`]
+pub async fn delete(context: &Context, mut user: user::ActiveModel) -> Result<(), sea_orm::DbErr> {
+    user.deleted_at = Set(Some(chrono::Utc::now().naive_utc()));
+    save(context, user, AuditAction::Delete).await?;
 
     Ok(())
 }
 
+/// Soft delete user `id`; its row stays until [`hard_delete_by_id`]
 pub async fn delete_by_id(context: &Context, id: i32) -> Result<(), sea_orm::DbErr> {
+    let Some(before) = find_by_id(context, id).await? else {
+        return Ok(());
+    };
+    let now = chrono::Utc::now().naive_utc();
+    user::Entity::update_many()
+        .col_expr(user::Column::DeletedAt, Expr::value(now))
+        .col_expr(user::Column::UpdatedAt, Expr::value(now))
+        .col_expr(
+            user::Column::UpdatedUserId,
+            Expr::value(context.user.as_ref().map(|u| u.id)),
+        )
+        .filter(user::Column::Id.eq(id))
+        .filter(user::Column::DeletedAt.is_null())
+        .exec(context.txn())
+        .await?;
+    let after = find_by_id_with_deleted(context, id).await?;
+    audit::record_delete(context, AUDIT_ENTITY, &before, after.as_ref()).await?;
+
+    Ok(())
+}
+
+/// Bring back a soft deleted `user`
+pub async fn restore(
+    context: &Context,
+    mut user: user::ActiveModel,
+) -> Result<user::Model, sea_orm::DbErr> {
+    user.deleted_at = Set(None);
+    update(context, user).await
+}
+
+/// Remove the row of user `id` for good, along with everything cascading from it
+pub async fn hard_delete_by_id(context: &Context, id: i32) -> Result<(), sea_orm::DbErr> {
+    let Some(before) = find_by_id_with_deleted(context, id).await? else {
+        return Ok(());
+    };
     user::Entity::delete_by_id(id).exec(context.txn()).await?;
+    audit::record_delete(context, AUDIT_ENTITY, &before, None).await?;
 
     Ok(())
 }
@@ -1,17 +1,39 @@
 use std::collections::HashMap;
 
-use axum::http::{HeaderMap, StatusCode, header::HeaderValue};
-use chrono::{Duration, Utc};
+use axum::http::{HeaderMap, header::HeaderValue};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
 use http::header::{AUTHORIZATION, COOKIE};
 use rust_i18n::t;
 use sea_orm::entity::*;
+use tokio::{
+    sync::OnceCell,
+    time::{Instant, sleep_until},
+};
 
 use crate::{
     config::setting::Setting,
-    core::{context::Context, dto::error_dto::ErrorDTO},
-    pkg::jwt::{decode_token, encode_token},
-    user::entity::{refresh_token, user},
-    user::repository::{refresh_token_repository, user_repository},
+    core::{
+        context::Context,
+        dto::{
+            error_code::ErrorCode,
+            error_dto::{ErrorDTO, FieldErrorDTO},
+        },
+        event::DomainEvent,
+    },
+    pkg::{
+        jwt::{Actor, Claims, decode_kid, decode_token, encode_claims, encode_token_with_kid},
+        password::{self, PasswordRule},
+    },
+    user::entity::{
+        refresh_token,
+        sea_orm_active_enums::{ExpirationPolicy, UserRole},
+        user,
+    },
+    user::repository::{
+        impersonation_repository, password_reset_repository, permission_repository,
+        refresh_token_repository, signing_key_repository, user_repository,
+    },
+    user::service::sign_in_service,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -20,47 +42,419 @@ pub enum TokenType {
     Refresh,
 }
 
+// ------------------------------------------------
+// Password
+// ------------------------------------------------
+
+/// Hash checked when a login names an unknown account, made with the configured parameters
+static DUMMY_PASSWORD_HASH: OnceCell<String> = OnceCell::const_new();
+
+/// Shortest time an account recovery request takes whatever its outcome, so how long it
+/// takes doesn't tell whether the account exists
+pub const RECOVERY_MIN_DURATION: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// Prefix of the signed payload of reset links, so their signatures can't be confused with
+/// the hashes of stored tokens made with the same key
+const PASSWORD_RESET_LINK_CONTEXT: &str = "password-reset-link:";
+
+/// Prefix of the signed payload of OAuth sign-in states, for the same reason
+const OAUTH_STATE_CONTEXT: &str = "oauth-state:";
+
+/// Name of the cookie binding an OAuth sign-in to the browser that started it
+pub const OAUTH_STATE_COOKIE: &str = "oauth_state";
+
+/// Hash `password` with the scheme and parameters configured in `Setting`
+pub async fn hash_password(password: &str) -> anyhow::Result<String> {
+    password::hash_password_with_config(password, &Setting::new().password_hash).await
+}
+
+/// Reject a new `password` of the account `email` that breaks the configured password
+/// policy, with one error on `field` per broken rule
+pub fn validate_new_password(
+    context: &Context,
+    field: &str,
+    password: &str,
+    email: &str,
+) -> Result<(), ErrorDTO> {
+    let policy = Setting::new().password_policy;
+    let broken = policy.check(password, Some(email));
+    if broken.is_empty() {
+        return Ok(());
+    }
+
+    let locale = context.locale.as_str();
+    let errors = broken
+        .into_iter()
+        .map(|rule| {
+            let message = match rule {
+                PasswordRule::MinLength => t!(
+                    "password_policy.min_length",
+                    field = field,
+                    min = policy.min_length,
+                    locale = locale
+                ),
+                PasswordRule::Uppercase => {
+                    t!("password_policy.uppercase", field = field, locale = locale)
+                }
+                PasswordRule::Lowercase => {
+                    t!("password_policy.lowercase", field = field, locale = locale)
+                }
+                PasswordRule::Digit => t!("password_policy.digit", field = field, locale = locale),
+                PasswordRule::Special => {
+                    t!("password_policy.special", field = field, locale = locale)
+                }
+                PasswordRule::Common => {
+                    t!("password_policy.common", field = field, locale = locale)
+                }
+                PasswordRule::Email => t!("password_policy.email", field = field, locale = locale),
+            };
+            FieldErrorDTO {
+                field: field.to_string(),
+                message: message.to_string(),
+                rule: Some(rule.as_str().to_string()),
+            }
+        })
+        .collect();
+    Err(ErrorDTO::from_code(
+        ErrorCode::AuthPasswordTooWeak,
+        t!("auth.password_too_weak", locale = locale).to_string(),
+    )
+    .with_errors(errors))
+}
+
+/// Whether `password` is the one of `user`. Without a user, a dummy hash is verified
+/// instead, so an unknown account and a wrong password take as long to reject.
+pub async fn verify_user_password(user: Option<&user::Model>, password: &str) -> bool {
+    match user {
+        Some(user) => password::verify_password(password, &user.password)
+            .await
+            .is_ok(),
+        None => {
+            match DUMMY_PASSWORD_HASH
+                .get_or_try_init(|| hash_password("dummy-password"))
+                .await
+            {
+                Ok(hash) => {
+                    let _ = password::verify_password(password, hash).await;
+                }
+                Err(e) => tracing::warn!("Failed to create dummy password hash: {:?}", e),
+            }
+            false
+        }
+    }
+}
+
+/// Wait until [`RECOVERY_MIN_DURATION`] has passed since `started`
+pub async fn pad_recovery_duration(started: Instant) {
+    sleep_until(started + RECOVERY_MIN_DURATION).await;
+}
+
+/// Replace the stored hash of `user` with one of the plain `password` that was just verified
+/// when it was made with another scheme or other parameters than the configured ones, e.g. an
+/// imported bcrypt hash. Failures are logged only, so a login never fails because of it.
+pub async fn rehash_password_if_outdated(context: &Context, user: &user::Model, password: &str) {
+    if !password::needs_rehash(&user.password, &Setting::new().password_hash) {
+        return;
+    }
+
+    let hashed_password = match hash_password(password).await {
+        Ok(hashed_password) => hashed_password,
+        Err(e) => {
+            tracing::warn!("Failed to rehash password of user {}: {:?}", user.id, e);
+            return;
+        }
+    };
+    let mut user_active: user::ActiveModel = user.clone().into();
+    user_active.password = Set(hashed_password);
+    match user_repository::update(context, user_active).await {
+        Ok(_) => tracing::info!("Rehashed password of user {}", user.id),
+        Err(e) => tracing::warn!(
+            "Failed to store rehashed password of user {}: {:?}",
+            user.id,
+            e
+        ),
+    }
+}
+
+/// Set the new password of `user` at the end of a password reset, by OTP or by link, and
+/// revoke every outstanding reset of theirs. `new_password` is checked against the password
+/// policy; a rejected one leaves the reset usable.
+pub async fn reset_password(
+    context: &Context,
+    user: user::Model,
+    new_password: &str,
+) -> Result<(), ErrorDTO> {
+    validate_new_password(context, "new_password", new_password, &user.email)?;
+    let hashed_password = hash_password(new_password)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let user_id = user.id;
+    let mut user: user::ActiveModel = user.into();
+    user.password = Set(hashed_password);
+    user_repository::update(context, user)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    password_reset_repository::delete_by_user_id(context, user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    tracing::info!("Password successfully reset for user_id: {}", user_id);
+    context.emit(DomainEvent::PasswordReset { user_id }).await;
+
+    Ok(())
+}
+
 // ------------------------------------------------
 // Token
 // ------------------------------------------------
 
-pub async fn generate_token_pair(user_id: i32) -> Result<(String, String), ErrorDTO> {
+/// Token of an emailed password reset link: the reset `nonce`, its expiry and their HMAC,
+/// so forged and expired links are turned down before the nonce is looked up
+pub fn sign_password_reset_link(nonce: &str, expires_at: DateTime<Utc>) -> String {
+    let payload = format!("{}.{}", nonce, expires_at.timestamp());
+    let signature = Setting::new()
+        .token_hasher()
+        .hash(&format!("{}{}", PASSWORD_RESET_LINK_CONTEXT, payload));
+    format!("{}.{}", payload, signature)
+}
+
+/// Nonce of a password reset link token that is correctly signed and not expired
+pub fn verify_password_reset_link(token: &str) -> Option<String> {
+    let (payload, signature) = token.rsplit_once('.')?;
+    let (nonce, expires_at) = payload.split_once('.')?;
+    let expires_at = DateTime::from_timestamp(expires_at.parse().ok()?, 0)?;
+
+    let signed = Setting::new().token_hasher().verify(
+        &format!("{}{}", PASSWORD_RESET_LINK_CONTEXT, payload),
+        signature,
+    );
+    (signed && expires_at > Utc::now()).then(|| nonce.to_string())
+}
+
+/// `state` of an OAuth sign-in with `provider`: `nonce`, its expiry and their HMAC, so a
+/// callback can tell states it issued from forged or expired ones without storing them
+pub fn sign_oauth_state(provider: &str, nonce: &str, expires_at: DateTime<Utc>) -> String {
+    let payload = format!("{}.{}", nonce, expires_at.timestamp());
+    let signature = Setting::new()
+        .token_hasher()
+        .hash(&format!("{}{}:{}", OAUTH_STATE_CONTEXT, provider, payload));
+    format!("{}.{}", payload, signature)
+}
+
+/// Whether `state` was signed for a sign-in with `provider` and hasn't expired
+pub fn verify_oauth_state(provider: &str, state: &str) -> bool {
+    let Some((payload, signature)) = state.rsplit_once('.') else {
+        return false;
+    };
+    let expires_at = payload
+        .split_once('.')
+        .and_then(|(_, expires_at)| expires_at.parse().ok())
+        .and_then(|expires_at| DateTime::from_timestamp(expires_at, 0));
+
+    let signed = Setting::new().token_hasher().verify(
+        &format!("{}{}:{}", OAUTH_STATE_CONTEXT, provider, payload),
+        signature,
+    );
+    signed && expires_at.is_some_and(|expires_at| expires_at > Utc::now())
+}
+
+/// User whose password the reset link `token` resets, as long as the link is signed,
+/// unexpired and unused
+pub async fn find_password_reset_link_user(
+    context: &Context,
+    token: &str,
+) -> Result<user::Model, ErrorDTO> {
+    let invalid_link = || {
+        ErrorDTO::from_code(
+            ErrorCode::AuthResetLinkInvalid,
+            t!("auth.reset_link_invalid", locale = &context.locale).to_string(),
+        )
+    };
+
+    let nonce = verify_password_reset_link(token).ok_or_else(invalid_link)?;
+    let reset_token = password_reset_repository::find_by_token(context, &nonce)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|reset_token| reset_token.expires_at >= Utc::now().naive_utc())
+        .ok_or_else(invalid_link)?;
+
+    user_repository::find_by_id(context, reset_token.user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(invalid_link)
+}
+
+/// Sign with the active key from the key store, falling back to `JWT_SECRET` when it is empty
+pub async fn generate_token_pair(
+    context: &Context,
+    user_id: i32,
+) -> Result<(String, String), ErrorDTO> {
     let setting = Setting::new();
+    let signing_key = signing_key_repository::find_active(context)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    let (secret, kid) = match &signing_key {
+        Some(key) => (key.secret.as_str(), Some(key.kid.as_str())),
+        None => (setting.jwt_secret.as_str(), None),
+    };
 
-    let access_token = encode_token(
+    let access_token = encode_token_with_kid(
         user_id,
         Duration::seconds(setting.jwt_access_token_expires),
-        &setting.jwt_secret,
+        secret,
+        kid,
     )
     .map_err(ErrorDTO::map_internal_error)?;
 
-    let refresh_token = encode_token(
+    let refresh_token = encode_token_with_kid(
         user_id,
         Duration::seconds(setting.jwt_refresh_token_expires),
-        &setting.jwt_secret,
+        secret,
+        kid,
     )
     .map_err(ErrorDTO::map_internal_error)?;
 
     Ok((access_token, refresh_token))
 }
 
+/// Sign an access token of `user_id` carrying `admin_id` as its actor, valid for
+/// `JWT_IMPERSONATION_TOKEN_EXPIRES`. Returns the token with its claims, whose `jti` ending
+/// the impersonation revokes.
+pub async fn generate_impersonation_token(
+    context: &Context,
+    admin_id: i32,
+    user_id: i32,
+) -> Result<(String, Claims), ErrorDTO> {
+    let setting = Setting::new();
+    let signing_key = signing_key_repository::find_active(context)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    let (secret, kid) = match &signing_key {
+        Some(key) => (key.secret.as_str(), Some(key.kid.as_str())),
+        None => (setting.jwt_secret.as_str(), None),
+    };
+
+    let mut claims = Claims::new(
+        user_id,
+        Duration::seconds(setting.jwt_impersonation_token_expires),
+    );
+    claims.act = Some(Actor { sub: admin_id });
+    let token = encode_claims(&claims, secret, kid).map_err(ErrorDTO::map_internal_error)?;
+
+    Ok((token, claims))
+}
+
+/// Verify `token` with the key named by its `kid` header, or with `JWT_SECRET` when it has none.
+/// Returns `None` for invalid tokens and tokens signed by a retired key.
+pub async fn verify_token(context: &Context, token: &str) -> Result<Option<Claims>, ErrorDTO> {
+    let Ok(kid) = decode_kid(token) else {
+        return Ok(None);
+    };
+
+    let secret = match kid {
+        Some(kid) => {
+            match signing_key_repository::find_verifiable_by_kid(context, &kid)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?
+            {
+                Some(key) => key.secret,
+                None => return Ok(None),
+            }
+        }
+        None => Setting::new().jwt_secret,
+    };
+
+    Ok(decode_token(token, &secret).ok())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshTokenExpiry {
+    pub expires_at: NaiveDateTime,
+    pub policy: ExpirationPolicy,
+    pub max_expires_at: Option<NaiveDateTime>,
+}
+
+/// Expiry of a refresh token issued at `now`, either for a new session or `rotated_from` the
+/// token it replaces. Rotated tokens keep the policy of that token, so changing
+/// `JWT_REFRESH_TOKEN_SLIDING` only affects sessions opened afterwards.
+pub fn refresh_token_expiry(
+    setting: &Setting,
+    rotated_from: Option<&refresh_token::Model>,
+    now: NaiveDateTime,
+) -> RefreshTokenExpiry {
+    let lifetime = Duration::seconds(setting.jwt_refresh_token_expires);
+    match rotated_from {
+        None if setting.jwt_refresh_token_sliding => {
+            let max_expires_at = now + Duration::seconds(setting.jwt_refresh_token_max_lifetime);
+            RefreshTokenExpiry {
+                expires_at: (now + lifetime).min(max_expires_at),
+                policy: ExpirationPolicy::Sliding,
+                max_expires_at: Some(max_expires_at),
+            }
+        }
+        None => RefreshTokenExpiry {
+            expires_at: now + lifetime,
+            policy: ExpirationPolicy::Absolute,
+            max_expires_at: None,
+        },
+        Some(previous) => match previous.expiration_policy {
+            ExpirationPolicy::Absolute => RefreshTokenExpiry {
+                expires_at: previous.expires_at,
+                policy: ExpirationPolicy::Absolute,
+                max_expires_at: previous.max_expires_at,
+            },
+            ExpirationPolicy::Sliding => {
+                let max_expires_at = previous.max_expires_at.unwrap_or(previous.expires_at);
+                RefreshTokenExpiry {
+                    expires_at: (now + lifetime).min(max_expires_at),
+                    policy: ExpirationPolicy::Sliding,
+                    max_expires_at: Some(max_expires_at),
+                }
+            }
+        },
+    }
+}
+
+/// Store the refresh token issued to the client making the request, `rotated_from` the one it
+/// replaces when refreshing, whose family it joins; other tokens start a family. Clients the
+/// user hasn't signed in from before are reported to them (see `sign_in_service::check_sign_in`).
 pub async fn create_refresh_token_record(
     context: &Context,
     user_id: i32,
     token: &str,
     headers: &HeaderMap,
+    rotated_from: Option<&refresh_token::Model>,
 ) -> Result<refresh_token::Model, ErrorDTO> {
     let setting = Setting::new();
-    let device_info = get_device_info(headers);
+    // A refresh without a user agent stays listed as the device that signed in
+    let device_info = get_device_info(headers)
+        .or_else(|| rotated_from.and_then(|previous| previous.device_info.clone()));
     let ip_address = get_client_ip(headers);
-    let expires_at = Utc::now().naive_utc() + Duration::seconds(setting.jwt_refresh_token_expires);
+
+    sign_in_service::check_sign_in(
+        context,
+        user_id,
+        device_info.as_deref(),
+        ip_address.as_deref(),
+    )
+    .await?;
+    let expiry = refresh_token_expiry(&setting, rotated_from, Utc::now().naive_utc());
 
     let refresh_token_record = refresh_token::ActiveModel {
         user_id: Set(user_id),
         token: Set(token.to_string()),
         device_info: Set(device_info),
         ip_address: Set(ip_address),
-        expires_at: Set(expires_at),
+        expires_at: Set(expiry.expires_at),
+        expiration_policy: Set(expiry.policy),
+        max_expires_at: Set(expiry.max_expires_at),
+        family_id: Set(Some(
+            rotated_from
+                .and_then(|previous| previous.family_id.clone())
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        )),
         ..Default::default()
     };
 
@@ -73,11 +467,21 @@ pub async fn get_current_user(
     context: &Context,
     access_token: &str,
 ) -> Result<user::Model, ErrorDTO> {
-    let setting = Setting::new();
+    get_current_user_and_impersonator(context, access_token)
+        .await
+        .map(|(user, _)| user)
+}
 
-    let claims = decode_token(access_token, setting.jwt_secret.as_str()).map_err(|_| {
-        ErrorDTO::new(
-            StatusCode::UNAUTHORIZED,
+/// The user `access_token` was issued to, and the admin acting as them if it is an
+/// impersonation token. Impersonation tokens stop working once the impersonation ends or
+/// the admin loses the `user.impersonate` permission.
+pub async fn get_current_user_and_impersonator(
+    context: &Context,
+    access_token: &str,
+) -> Result<(user::Model, Option<i32>), ErrorDTO> {
+    let claims = verify_token(context, access_token).await?.ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthTokenInvalid,
             t!("authorization.invalid_token", locale = &context.locale).to_string(),
         )
     })?;
@@ -86,13 +490,72 @@ pub async fn get_current_user(
         .await
         .map_err(ErrorDTO::map_internal_error)?
         .ok_or_else(|| {
-            ErrorDTO::new(
-                StatusCode::UNAUTHORIZED,
+            ErrorDTO::from_code(
+                ErrorCode::AuthUserNotFound,
                 t!("authorization.user_not_found", locale = &context.locale).to_string(),
             )
         })?;
 
-    Ok(user)
+    // Tokens issued before the account was deactivated stop working right away
+    if user.deactivated_at.is_some() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::AuthSessionDeactivated,
+            t!("auth.account_deactivated", locale = &context.locale).to_string(),
+        ));
+    }
+
+    let Some(actor) = claims.act else {
+        return Ok((user, None));
+    };
+    let ended = || {
+        ErrorDTO::from_code(
+            ErrorCode::AuthImpersonationEnded,
+            t!("auth.impersonation_ended", locale = &context.locale).to_string(),
+        )
+    };
+    let impersonation = impersonation_repository::find_by_token_id(context, &claims.jti)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|impersonation| {
+            impersonation.ended_at.is_none()
+                && impersonation.admin_id == actor.sub
+                && impersonation.user_id == user.id
+        })
+        .ok_or_else(ended)?;
+    let admin = user_repository::find_by_id(context, impersonation.admin_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|admin| admin.deactivated_at.is_none())
+        .ok_or_else(ended)?;
+    if admin.role != UserRole::Admin {
+        let permissions = permission_repository::find_names_by_user_id(context, admin.id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+        if !permissions
+            .iter()
+            .any(|permission| permission == "user.impersonate")
+        {
+            return Err(ended());
+        }
+    }
+
+    Ok((user, Some(admin.id)))
+}
+
+/// The session of `user_id` the request was made from, known by its `refresh_token` cookie.
+/// Clients keeping tokens out of cookies have no current session.
+pub async fn find_current_session(
+    context: &Context,
+    user_id: i32,
+    headers: &HeaderMap,
+) -> Result<Option<refresh_token::Model>, ErrorDTO> {
+    let Some(token) = get_token_from_cookies(headers, "refresh_token") else {
+        return Ok(None);
+    };
+    let session = refresh_token_repository::find_by_user_and_token(context, user_id, &token)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    Ok(session.filter(|session| session.rotated_at.is_none()))
 }
 
 // ------------------------------------------------
@@ -163,6 +626,18 @@ pub fn set_auth_cookies(headers: &mut HeaderMap, access_token: &str, refresh_tok
     }
 }
 
+/// Remember the `state` of an OAuth sign-in in the browser for `max_age_seconds`, or forget
+/// it with an empty `state`. `SameSite=Lax` so it is sent along the provider's redirect back.
+pub fn set_oauth_state_cookie(headers: &mut HeaderMap, state: &str, max_age_seconds: i64) {
+    let cookie = format!(
+        "{}={}; Max-Age={}; HttpOnly; SameSite=Lax; Secure; Path=/api/v1/auth/oauth/",
+        OAUTH_STATE_COOKIE, state, max_age_seconds
+    );
+    if let Ok(value) = HeaderValue::from_str(&cookie) {
+        headers.append("set-cookie", value);
+    }
+}
+
 pub async fn extract_token_from_header_or_cookie(
     header_map: &HeaderMap,
     token_type: TokenType,
@@ -189,13 +664,16 @@ pub async fn extract_token_from_header_or_cookie(
     }
 
     // No token found anywhere
-    let error_key = match token_type {
-        TokenType::Access => "authorization.token_not_found",
-        TokenType::Refresh => "auth.refresh_token_required",
+    let (code, error_key) = match token_type {
+        TokenType::Access => (ErrorCode::AuthTokenMissing, "authorization.token_not_found"),
+        TokenType::Refresh => (
+            ErrorCode::AuthRefreshTokenMissing,
+            "auth.refresh_token_required",
+        ),
     };
 
-    Err(ErrorDTO::new(
-        StatusCode::UNAUTHORIZED,
+    Err(ErrorDTO::from_code(
+        code,
         t!(error_key, locale = locale).to_string(),
     ))
 }
@@ -206,8 +684,8 @@ pub fn get_token_from_headers(
 ) -> Result<Option<String>, ErrorDTO> {
     if let Some(authorization) = header_map.get(AUTHORIZATION) {
         let auth_str = authorization.to_str().map_err(|_| {
-            ErrorDTO::new(
-                StatusCode::UNAUTHORIZED,
+            ErrorDTO::from_code(
+                ErrorCode::AuthTokenMalformed,
                 t!("authorization.invalid_header", locale = locale).to_string(),
             )
         })?;
@@ -0,0 +1,151 @@
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+        event::DomainEvent,
+        layer::{
+            auth_layer::authorize_role,
+            response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+        },
+        policy::{Action, Resource},
+    },
+    user::{
+        dto::bulk_user_dto::{BulkUserItemResultDTO, BulkUserOperationDTO, BulkUserReportDTO},
+        entity::{sea_orm_active_enums::UserRole, user},
+        repository::{refresh_token_repository, user_repository},
+    },
+};
+
+/// Apply each operation in its own savepoint of `context`'s transaction, so a failing one
+/// is rolled back without undoing the others
+pub async fn apply_in_savepoints(
+    context: &Context,
+    operations: &[BulkUserOperationDTO],
+) -> Result<BulkUserReportDTO, ErrorDTO> {
+    let mut report = BulkUserReportDTO::default();
+    for (index, operation) in operations.iter().enumerate() {
+        let item_context = context
+            .nested()
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+        let result = match apply_operation(&item_context, operation).await {
+            Ok(()) => item_context.commit().await.map_err(ErrorDTO::from),
+            Err(e) => {
+                item_context
+                    .rollback()
+                    .await
+                    .map_err(ErrorDTO::map_internal_error)?;
+                Err(e)
+            }
+        };
+        report.push(item_result(index, operation, result));
+    }
+
+    Ok(report)
+}
+
+/// Apply one operation on `context`; the caller commits or rolls back its writes
+pub async fn apply_operation(
+    context: &Context,
+    operation: &BulkUserOperationDTO,
+) -> Result<(), ErrorDTO> {
+    let user_id = operation.user_id();
+    let action = match operation {
+        BulkUserOperationDTO::Delete { .. } => Action::Delete,
+        _ => Action::Update,
+    };
+    context.authorize(action, &Resource::user(user_id))?;
+    // Roles stay with admins, or holders of `user.update` could make themselves one
+    if matches!(operation, BulkUserOperationDTO::AssignRole { .. })
+        && let Some(current_user) = &context.user
+    {
+        authorize_role(context, current_user, UserRole::Admin)?;
+    }
+
+    // Admins can't lock themselves out by including their own account in a batch
+    let actor_id = context.user.as_ref().map(|user| user.id);
+    if actor_id == Some(user_id) {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::UserBulkSelfOperation,
+            t!("user.bulk_self_operation", locale = &context.locale).to_string(),
+        ));
+    }
+
+    let user = user_repository::find_by_id(context, user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::UserNotFound,
+                t!("user.not_found", locale = &context.locale).to_string(),
+            )
+        })?;
+
+    let event = match operation {
+        BulkUserOperationDTO::Deactivate { .. } => {
+            if user.deactivated_at.is_none() {
+                let mut user_active: user::ActiveModel = user.into();
+                user_active.deactivated_at = Set(Some(Utc::now().naive_utc()));
+                user_repository::update(context, user_active)
+                    .await
+                    .map_err(ErrorDTO::map_internal_error)?;
+            }
+            refresh_token_repository::delete_by_user_id(context, user_id)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+
+            DomainEvent::UserDeactivated {
+                user_id,
+                deactivated_by: actor_id,
+            }
+        }
+        BulkUserOperationDTO::Delete { .. } => {
+            user_repository::delete_by_id(context, user_id)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+            refresh_token_repository::delete_by_user_id(context, user_id)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+
+            DomainEvent::UserDeleted {
+                user_id,
+                deleted_by: actor_id,
+            }
+        }
+        BulkUserOperationDTO::AssignRole { role, .. } => {
+            let mut user_active: user::ActiveModel = user.into();
+            user_active.role = Set(role.clone());
+            user_repository::update(context, user_active)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+
+            DomainEvent::RoleAssigned {
+                user_id,
+                role: role.clone(),
+                assigned_by: actor_id,
+            }
+        }
+    };
+
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+    context.emit(event).await;
+
+    Ok(())
+}
+
+pub fn item_result(
+    index: usize,
+    operation: &BulkUserOperationDTO,
+    result: Result<(), ErrorDTO>,
+) -> BulkUserItemResultDTO {
+    BulkUserItemResultDTO {
+        index,
+        user_id: operation.user_id(),
+        success: result.is_ok(),
+        error: result.err().map(|e| e.message),
+    }
+}
@@ -0,0 +1,187 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use rust_i18n::t;
+
+use crate::{
+    config::setting::{LoginLockoutSetting, Setting},
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+    },
+    pkg::cache::LoginFailures,
+    user::repository::login_attempt_repository,
+};
+
+/// What failed sign-ins are counted against. Emails are counted whether or not an account
+/// has them, so unknown emails lock out like known ones.
+#[derive(Debug, Clone, Copy)]
+enum Subject<'a> {
+    Email(&'a str),
+    Ip(&'a str),
+}
+
+impl Subject<'_> {
+    /// Key of the subject in a `LoginAttemptStore`
+    fn key(&self) -> String {
+        match self {
+            Subject::Email(email) => format!("email:{}", email),
+            Subject::Ip(ip_address) => format!("ip:{}", ip_address),
+        }
+    }
+
+    fn max_attempts(&self, setting: &LoginLockoutSetting) -> u64 {
+        match self {
+            Subject::Email(_) => setting.max_attempts,
+            Subject::Ip(_) => setting.max_attempts_per_ip,
+        }
+    }
+}
+
+/// The subjects of a sign-in with the normalized `email` whose lockout is enabled
+fn subjects<'a>(
+    setting: &LoginLockoutSetting,
+    email: &'a str,
+    ip_address: Option<&'a str>,
+) -> Vec<Subject<'a>> {
+    std::iter::once(Subject::Email(email))
+        .chain(ip_address.map(Subject::Ip))
+        .filter(|subject| subject.max_attempts(setting) > 0)
+        .collect()
+}
+
+/// Seconds left of the lockout `failures` of `subject` put it under, `None` when they don't
+fn remaining_lockout(
+    setting: &LoginLockoutSetting,
+    subject: Subject<'_>,
+    failures: LoginFailures,
+    now: DateTime<Utc>,
+) -> Option<u64> {
+    if failures.count < subject.max_attempts(setting) {
+        return None;
+    }
+    let cooldown = TimeDelta::from_std(setting.cooldown()).unwrap_or_default();
+    let remaining = (failures.last_failed_at? + cooldown - now).num_milliseconds();
+    (remaining > 0).then(|| (remaining as u64).div_ceil(1000))
+}
+
+async fn failures(
+    context: &Context,
+    setting: &LoginLockoutSetting,
+    subject: Subject<'_>,
+) -> Result<LoginFailures, ErrorDTO> {
+    if let Some(store) = &context.login_attempts {
+        // A lockout that cannot be checked only costs protection, so let the sign-in through
+        return Ok(store.failures(&subject.key()).await.unwrap_or_else(|e| {
+            tracing::warn!("Failed sign-ins unavailable: {:?}", e);
+            LoginFailures::default()
+        }));
+    }
+
+    let cooldown = TimeDelta::from_std(setting.cooldown()).unwrap_or_default();
+    let since = (Utc::now() - cooldown).naive_utc();
+    let (count, last_failed_at) = match subject {
+        Subject::Email(email) => {
+            login_attempt_repository::count_by_email_since(context, email, since).await?
+        }
+        Subject::Ip(ip_address) => {
+            login_attempt_repository::count_by_ip_address_since(context, ip_address, since).await?
+        }
+    };
+    Ok(LoginFailures {
+        count,
+        last_failed_at: last_failed_at.map(|last_failed_at| last_failed_at.and_utc()),
+    })
+}
+
+/// Seconds before `email` or the client at `ip_address` may sign in again, `None` when
+/// neither is locked out
+pub async fn locked_for(
+    context: &Context,
+    email: &str,
+    ip_address: Option<&str>,
+) -> Result<Option<u64>, ErrorDTO> {
+    let setting = Setting::new();
+    let email = setting.email.normalize(email);
+    let setting = setting.login_lockout;
+
+    let mut locked_for = None;
+    for subject in subjects(&setting, &email, ip_address) {
+        let failures = failures(context, &setting, subject).await?;
+        locked_for = locked_for.max(remaining_lockout(&setting, subject, failures, Utc::now()));
+    }
+    Ok(locked_for)
+}
+
+/// Count a failed sign-in with `email` from `ip_address`, whether or not an account has the
+/// email. Returns the seconds of the lockout it caused, if any.
+///
+/// Failures are written to the request's transaction when they are kept in the database:
+/// the error answering the sign-in must keep its changes.
+pub async fn record_failure(
+    context: &Context,
+    email: &str,
+    ip_address: Option<&str>,
+) -> Result<Option<u64>, ErrorDTO> {
+    let setting = Setting::new();
+    let normalized_email = setting.email.normalize(email);
+    let setting = setting.login_lockout;
+    let subjects = subjects(&setting, &normalized_email, ip_address);
+    if subjects.is_empty() {
+        return Ok(None);
+    }
+
+    let Some(store) = &context.login_attempts else {
+        login_attempt_repository::create(
+            context,
+            Some(normalized_email.clone()),
+            ip_address.map(str::to_string),
+        )
+        .await?;
+        return locked_for(context, &normalized_email, ip_address).await;
+    };
+
+    let mut locked_for = None;
+    for subject in subjects {
+        match store.record_failure(&subject.key()).await {
+            Ok(failures) => {
+                locked_for =
+                    locked_for.max(remaining_lockout(&setting, subject, failures, Utc::now()));
+            }
+            Err(e) => tracing::warn!("Failed to record failed sign-in: {:?}", e),
+        }
+    }
+    Ok(locked_for)
+}
+
+/// Forget the failed sign-ins with `email` once it signed in. Those of its client IP still
+/// count, as they may have tried other accounts.
+pub async fn clear_failures(context: &Context, email: &str) -> Result<(), ErrorDTO> {
+    let setting = Setting::new();
+    if setting.login_lockout.max_attempts == 0 {
+        return Ok(());
+    }
+    let email = setting.email.normalize(email);
+
+    match &context.login_attempts {
+        Some(store) => {
+            if let Err(e) = store.clear(&Subject::Email(&email).key()).await {
+                tracing::warn!("Failed to clear failed sign-ins: {:?}", e);
+            }
+        }
+        None => login_attempt_repository::detach_email(context, &email).await?,
+    }
+    Ok(())
+}
+
+/// Error answering a sign-in while its account or client is locked out
+pub fn account_locked(context: &Context, retry_after: u64) -> ErrorDTO {
+    ErrorDTO::from_code(
+        ErrorCode::AuthAccountLocked,
+        t!(
+            "auth.account_locked",
+            minutes = retry_after.div_ceil(60),
+            locale = &context.locale
+        )
+        .to_string(),
+    )
+    .with_retry_after(retry_after)
+}
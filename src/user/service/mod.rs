@@ -1,2 +1,7 @@
 pub mod auth_service;
+pub mod bulk_user_service;
+pub mod login_attempt_service;
+pub mod sign_in_service;
+pub mod user_email_service;
 pub mod user_service;
+pub mod verification_service;
@@ -0,0 +1,223 @@
+use std::net::IpAddr;
+
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    config::setting::{MessageType, Setting},
+    core::{
+        r#async::{TaskPriority, TaskType, publish_task_with_priority},
+        context::Context,
+        dto::error_dto::ErrorDTO,
+        event::DomainEvent,
+        id::OtpAlphabet,
+    },
+    notification::{
+        entity::sea_orm_active_enums::NotificationChannel,
+        service::notification_template_service::{self, NotificationEvent},
+    },
+    user::{
+        entity::{sea_orm_active_enums::SecurityEventKind, security_event, user},
+        repository::{refresh_token_repository, security_event_repository, user_repository},
+    },
+};
+
+/// Length of the token in the "this wasn't me" link of a new sign-in alert
+const REPORT_TOKEN_LENGTH: u32 = 32;
+
+/// Network `ip_address` belongs to: its /24 for IPv4 and its /48 for IPv6. Without a GeoIP
+/// database this is the closest to a location; unparseable addresses are their own network.
+pub fn network_of(ip_address: &str) -> String {
+    match ip_address.trim().parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            let [a, b, c, _] = ip.octets();
+            format!("{}.{}.{}.0/24", a, b, c)
+        }
+        Ok(IpAddr::V6(ip)) => {
+            let segments = ip.segments();
+            format!("{:x}:{:x}:{:x}::/48", segments[0], segments[1], segments[2])
+        }
+        Err(_) => ip_address.trim().to_string(),
+    }
+}
+
+/// Compare the client a refresh token is being issued to with the sign-in history of
+/// `user_id`: their sessions and the sign-ins on record. A device or network not seen before
+/// is recorded as a `new_sign_in` security event and emailed to the user with a link to
+/// report it. The first sign-in on record is only kept as the baseline.
+///
+/// Alerts are best effort: failing to send one doesn't fail the sign-in.
+pub async fn check_sign_in(
+    context: &Context,
+    user_id: i32,
+    device_info: Option<&str>,
+    ip_address: Option<&str>,
+) -> Result<(), ErrorDTO> {
+    let network = ip_address.map(network_of);
+
+    let (sessions, _) = refresh_token_repository::search(
+        context,
+        &refresh_token_repository::RefreshTokenSearchParams {
+            user_id: Some(user_id),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+    let sign_ins = security_event_repository::find_sign_ins_by_user_id(context, user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    let known_clients: Vec<(Option<String>, Option<String>)> = sessions
+        .into_iter()
+        .map(|session| (session.device_info, session.ip_address))
+        .chain(
+            sign_ins
+                .into_iter()
+                .map(|sign_in| (sign_in.device_info, sign_in.ip_address)),
+        )
+        .map(|(device_info, ip_address)| (device_info, ip_address.as_deref().map(network_of)))
+        .collect();
+
+    if known_clients.is_empty() {
+        security_event_repository::create(
+            context,
+            security_event::ActiveModel {
+                user_id: Set(user_id),
+                kind: Set(SecurityEventKind::SignIn),
+                device_info: Set(device_info.map(str::to_string)),
+                ip_address: Set(ip_address.map(str::to_string)),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+        return Ok(());
+    }
+
+    let new_device = !known_clients
+        .iter()
+        .any(|(known_device, _)| known_device.as_deref() == device_info);
+    let new_network = !known_clients
+        .iter()
+        .any(|(_, known_network)| *known_network == network);
+    if !new_device && !new_network {
+        return Ok(());
+    }
+
+    let report_token = context
+        .id_generator
+        .otp_from(REPORT_TOKEN_LENGTH, OtpAlphabet::Alphanumeric);
+    let security_event = security_event_repository::create(
+        context,
+        security_event::ActiveModel {
+            user_id: Set(user_id),
+            kind: Set(SecurityEventKind::NewSignIn),
+            device_info: Set(device_info.map(str::to_string)),
+            ip_address: Set(ip_address.map(str::to_string)),
+            report_token: Set(Some(report_token.clone())),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+    tracing::warn!(
+        user_id,
+        security_event_id = security_event.id,
+        new_device,
+        new_network,
+        "Sign-in from a new device or network"
+    );
+
+    let user = user_repository::find_by_id(context, user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    if let Some(user) = user
+        && let Err(e) = send_new_sign_in_email(context, &user, &security_event, &report_token).await
+    {
+        tracing::error!(
+            "Failed to send new sign-in alert to user {}: {}",
+            user_id,
+            e
+        );
+    }
+
+    context
+        .emit(DomainEvent::NewSignIn {
+            user_id,
+            security_event_id: security_event.id,
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Email the alert of `security_event` with its report link. Security alerts skip the
+/// notification preferences: they always go by email, with high priority.
+async fn send_new_sign_in_email(
+    context: &Context,
+    user: &user::Model,
+    security_event: &security_event::Model,
+    report_token: &str,
+) -> anyhow::Result<()> {
+    if context.producer.is_none() {
+        anyhow::bail!("Message producer not available");
+    }
+
+    let locale = user.locale.as_deref().unwrap_or(&context.locale);
+    let unknown = t!("auth.unknown_client", locale = locale).to_string();
+    let variables = [
+        ("first_name", user.first_name.clone().unwrap_or_default()),
+        (
+            "device",
+            security_event
+                .device_info
+                .clone()
+                .unwrap_or_else(|| unknown.clone()),
+        ),
+        (
+            "ip_address",
+            security_event.ip_address.clone().unwrap_or(unknown),
+        ),
+        (
+            "signed_in_at",
+            security_event
+                .created_at
+                .unwrap_or_default()
+                .format("%Y-%m-%d %H:%M UTC")
+                .to_string(),
+        ),
+        (
+            "report_url",
+            format!(
+                "{}/security/report-sign-in?token={}",
+                Setting::new().app_url,
+                report_token
+            ),
+        ),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_string(), value))
+    .collect();
+
+    let rendered = notification_template_service::render(
+        NotificationEvent::NewSignIn,
+        NotificationChannel::Email,
+        locale,
+        &variables,
+    )?;
+    publish_task_with_priority(
+        context,
+        TaskType::SendEmail {
+            to: user.email.clone(),
+            subject: rendered.title,
+            text_body: None,
+            html_body: rendered.html_body,
+        },
+        TaskPriority::High,
+        Some(MessageType::Emails.as_ref()),
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to publish email task: {}", e))?;
+
+    Ok(())
+}
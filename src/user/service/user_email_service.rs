@@ -0,0 +1,89 @@
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    config::setting::MessageType,
+    core::{
+        r#async::{TaskPriority, TaskType, publish_task_with_priority},
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+    },
+    user::{
+        entity::user_email,
+        repository::user_email_repository,
+        service::verification_service::{self, OTP_EXPIRY_MINUTES},
+    },
+};
+
+/// Secondary address `id` of `user_id`; those of other users are reported as missing
+pub async fn read_own(
+    context: &Context,
+    user_id: i32,
+    id: i32,
+) -> Result<user_email::Model, ErrorDTO> {
+    user_email_repository::find_by_id(context, id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|user_email| user_email.user_id == user_id)
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::UserEmailNotFound,
+                t!("user.email_not_found", locale = &context.locale).to_string(),
+            )
+        })
+}
+
+/// Email a new code to `user_email`, replacing any code sent before
+pub async fn send_verification_code(
+    context: &Context,
+    user_email: user_email::Model,
+) -> Result<user_email::Model, ErrorDTO> {
+    if context.producer.is_none() {
+        tracing::error!("Message producer not available. Cannot send verification email.");
+        return Err(ErrorDTO::from_code(
+            ErrorCode::EmailUnavailable,
+            t!("email.service_unavailable", locale = &context.locale).to_string(),
+        ));
+    }
+
+    let (otp, expires_at) = verification_service::issue_otp(context);
+    let address = user_email.email.clone();
+    let mut user_email: user_email::ActiveModel = user_email.into();
+    user_email.token = Set(Some(otp.clone()));
+    user_email.retry_count = Set(0);
+    user_email.token_expires_at = Set(Some(expires_at));
+    let user_email = user_email_repository::update(context, user_email)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    publish_task_with_priority(
+        context,
+        TaskType::SendEmail {
+            to: address.clone(),
+            subject: t!("auth.email_verification_subject", locale = &context.locale).to_string(),
+            text_body: Some(
+                t!(
+                    "auth.email_verification_body",
+                    email = address,
+                    otp = otp,
+                    minutes = OTP_EXPIRY_MINUTES,
+                    locale = &context.locale
+                )
+                .to_string(),
+            ),
+            html_body: None,
+        },
+        TaskPriority::High,
+        Some(MessageType::Emails.as_ref()),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to publish verification email task: {}", e);
+        ErrorDTO::from_code(
+            ErrorCode::EmailDeliveryFailed,
+            t!("email.send_failed", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    Ok(user_email)
+}
@@ -1,13 +1,26 @@
-use regex::Regex;
 use rust_i18n::t;
 use std::collections::HashMap;
 
 use crate::{
-    core::{context::Context, dto::error_dto::ErrorDTO},
+    config::setting::Setting,
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+    },
+    file::{
+        entity::sea_orm_active_enums::FileStatus, repository::file_repository,
+        service::media_service,
+    },
     user::{
-        dto::user_dto::{UserDTO, UserWithRelations},
+        dto::{
+            auth_dto::ProfileDTO,
+            user_dto::{UserDTO, UserWithRelations},
+        },
         entity::user::{self},
-        repository::user_repository::{self, UserSearchParams},
+        repository::{
+            user_email_repository,
+            user_repository::{self, UserSearchParams},
+        },
     },
 };
 use axum::http::StatusCode;
@@ -18,8 +31,8 @@ pub async fn read(context: &Context, user_id: i32) -> Result<user::Model, ErrorD
         .map_err(ErrorDTO::map_internal_error)?;
 
     user.ok_or_else(|| {
-        ErrorDTO::new(
-            StatusCode::NOT_FOUND,
+        ErrorDTO::from_code(
+            ErrorCode::UserNotFound,
             t!(
                 "user.not_found_with_id",
                 id = user_id,
@@ -50,6 +63,31 @@ pub async fn build_user_map(
     Ok(user_map)
 }
 
+/// User signing in with `email`: its primary address, or one of its verified secondary ones
+pub async fn find_by_login_email(
+    context: &Context,
+    email: &str,
+) -> Result<Option<user::Model>, ErrorDTO> {
+    if let Some(user) = user_repository::find_by_email(context, email)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+    {
+        return Ok(Some(user));
+    }
+
+    match user_email_repository::find_by_email(context, email)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+    {
+        Some(user_email) if user_email.verified_at.is_some() => {
+            user_repository::find_by_id(context, user_email.user_id)
+                .await
+                .map_err(ErrorDTO::map_internal_error)
+        }
+        _ => Ok(None),
+    }
+}
+
 // ------------------------------------------------
 // Serialization
 // ------------------------------------------------
@@ -99,56 +137,58 @@ pub async fn model_to_dto(context: &Context, user: &user::Model) -> Result<UserD
         })
 }
 
+/// Profile of `user` with links to its approved avatar and its thumbnails
+pub async fn to_profile_dto(context: &Context, user: user::Model) -> Result<ProfileDTO, ErrorDTO> {
+    let avatar = match user.avatar_file_id {
+        Some(file_id) => file_repository::find_by_id(context, file_id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?,
+        None => None,
+    };
+
+    let mut profile = ProfileDTO::from(user);
+    if let Some(avatar) = avatar.filter(|avatar| avatar.status == FileStatus::Available) {
+        let setting = Setting::new();
+        profile.avatar_url = Some(
+            media_service::avatar_url(&setting, profile.id, media_service::ORIGINAL_VARIANT)
+                .map_err(ErrorDTO::map_internal_error)?,
+        );
+        for size in media_service::thumbnail_sizes(&avatar) {
+            let url = media_service::avatar_url(&setting, profile.id, &size)
+                .map_err(ErrorDTO::map_internal_error)?;
+            profile.avatar_thumbnail_urls.insert(size, url);
+        }
+    }
+    Ok(profile)
+}
+
 // ------------------------------------------------
 // Validation
 // ------------------------------------------------
 
+/// Refuse `email` when it is the primary address of another user than `exclude_id`, deleted
+/// or not, or a verified secondary address of anyone. Unverified secondary addresses don't hold on to an
+/// address, so nobody can keep its owner from using it.
 pub async fn validate_unique_email(
     context: &Context,
     email: &str,
     exclude_id: Option<i32>,
 ) -> Result<(), ErrorDTO> {
-    let existing_user = user_repository::find_by_email(context, email)
+    let existing_user = user_repository::find_by_email_with_deleted(context, email)
         .await
         .map_err(ErrorDTO::map_internal_error)?;
+    let primary_taken = existing_user
+        .is_some_and(|existing_user| exclude_id.is_none() || Some(existing_user.id) != exclude_id);
 
-    if let Some(existing_user) = existing_user
-        && (exclude_id.is_none() || Some(existing_user.id) != exclude_id)
-    {
-        return Err(ErrorDTO::new(
-            StatusCode::CONFLICT,
-            t!("user.email_already_in_use", locale = &context.locale).to_string(),
-        ));
-    }
-
-    Ok(())
-}
-
-pub fn validate_email_format(email: &str, locale: &str) -> Result<(), ErrorDTO> {
-    if email.trim().is_empty() {
-        return Err(ErrorDTO::new(
-            StatusCode::BAD_REQUEST,
-            t!("user.validation.email_required", locale = locale).to_string(),
-        ));
-    }
-
-    let email_regex = Regex::new(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$")
-        .map_err(ErrorDTO::map_internal_error)?;
-    if !email_regex.is_match(email) {
-        return Err(ErrorDTO::new(
-            StatusCode::BAD_REQUEST,
-            t!("user.validation.email_invalid_format", locale = locale).to_string(),
-        ));
-    }
-
-    Ok(())
-}
+    let secondary_taken = user_email_repository::find_by_email(context, email)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .is_some_and(|user_email| user_email.verified_at.is_some());
 
-pub fn validate_password(password: &str, locale: &str) -> Result<(), ErrorDTO> {
-    if password.trim().is_empty() {
-        return Err(ErrorDTO::new(
-            StatusCode::BAD_REQUEST,
-            t!("user.validation.password_required", locale = locale).to_string(),
+    if primary_taken || secondary_taken {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::UserEmailTaken,
+            t!("user.email_already_in_use", locale = &context.locale).to_string(),
         ));
     }
 
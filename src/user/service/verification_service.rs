@@ -0,0 +1,76 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use rust_i18n::t;
+
+use crate::core::{
+    context::Context,
+    dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+};
+
+/// Minutes a code sent to verify a phone or an email address stays valid
+pub const OTP_EXPIRY_MINUTES: i64 = 10;
+
+/// Wrong codes accepted before the code has to be sent again
+pub const MAX_ATTEMPTS: i32 = 3;
+
+const OTP_LENGTH: u32 = 6;
+
+/// Why a code sent to verify a phone or an email address was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtpFailure {
+    /// Not the code sent; counts as an attempt
+    Mismatch,
+    Expired,
+    AttemptsExceeded,
+}
+
+impl OtpFailure {
+    /// Whether the code sent can't be tried again and should be discarded
+    pub fn discards_code(self) -> bool {
+        self != Self::Mismatch
+    }
+
+    /// Error answering the failure, `invalid` for a wrong code
+    pub fn into_error(self, context: &Context, invalid: ErrorDTO) -> ErrorDTO {
+        match self {
+            Self::Mismatch => invalid,
+            Self::Expired => ErrorDTO::from_code(
+                ErrorCode::AuthOtpExpired,
+                t!("auth.otp_expired", locale = &context.locale).to_string(),
+            ),
+            Self::AttemptsExceeded => ErrorDTO::from_code(
+                ErrorCode::AuthOtpAttemptsExceeded,
+                t!(
+                    "auth.otp_max_attempts_exceeded",
+                    max = MAX_ATTEMPTS,
+                    locale = &context.locale
+                )
+                .to_string(),
+            ),
+        }
+    }
+}
+
+/// New code to send, with when it expires
+pub fn issue_otp(context: &Context) -> (String, NaiveDateTime) {
+    let expires_at = Utc::now() + Duration::minutes(OTP_EXPIRY_MINUTES);
+    (context.id_generator.otp(OTP_LENGTH), expires_at.naive_utc())
+}
+
+/// Check `otp` against the code sent, which was already tried `retry_count` times
+pub fn check_otp(
+    sent: &str,
+    retry_count: i32,
+    expires_at: NaiveDateTime,
+    otp: &str,
+) -> Result<(), OtpFailure> {
+    if retry_count >= MAX_ATTEMPTS {
+        return Err(OtpFailure::AttemptsExceeded);
+    }
+    if expires_at < Utc::now().naive_utc() {
+        return Err(OtpFailure::Expired);
+    }
+    if sent != otp.trim() {
+        return Err(OtpFailure::Mismatch);
+    }
+    Ok(())
+}
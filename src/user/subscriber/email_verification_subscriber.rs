@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+
+use crate::{
+    config::setting::MessageType,
+    core::{
+        r#async::{TaskEvent, TaskPriority, TaskType, publish_task_with_priority},
+        context::Context,
+        event::{DomainEvent, EventSubscriber},
+    },
+};
+
+/// Queues the email verifying the address of newly registered users once their registration is
+/// committed
+pub struct EmailVerificationSubscriber;
+
+#[async_trait]
+impl EventSubscriber for EmailVerificationSubscriber {
+    fn name(&self) -> &'static str {
+        "email_verification"
+    }
+
+    async fn handle(&self, context: &Context, event: &DomainEvent) -> anyhow::Result<()> {
+        let DomainEvent::UserRegistered { user_id, .. } = event else {
+            return Ok(());
+        };
+
+        // The outbox keeps the task with the registration and publishes it once committed
+        if context.outbox.is_some() {
+            return publish_task_with_priority(
+                context,
+                TaskType::SendVerificationEmail { user_id: *user_id },
+                TaskPriority::High,
+                Some(MessageType::Emails.as_ref()),
+            )
+            .await
+            .map_err(|e| e.context("Failed to publish verification email task"));
+        }
+
+        let Some(producer) = &context.producer else {
+            tracing::warn!(
+                "Message producer is not available in context. Skipping verification email task publishing."
+            );
+            return Ok(());
+        };
+
+        let producer = producer.clone();
+        let user_id = *user_id;
+        context.after_commit(async move {
+            TaskEvent::with_priority(
+                TaskType::SendVerificationEmail { user_id },
+                TaskPriority::High,
+            )
+            .publish_with_producer(
+                producer.as_ref().as_ref(),
+                Some(MessageType::Emails.as_ref()),
+            )
+            .await
+            .map_err(|e| e.context("Failed to publish verification email task"))
+        });
+        Ok(())
+    }
+}
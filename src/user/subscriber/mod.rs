@@ -0,0 +1,5 @@
+mod email_verification_subscriber;
+mod welcome_email_subscriber;
+
+pub use email_verification_subscriber::EmailVerificationSubscriber;
+pub use welcome_email_subscriber::WelcomeEmailSubscriber;
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+
+use crate::{
+    config::setting::MessageType,
+    core::{
+        r#async::{TaskEvent, TaskType, publish_task},
+        context::Context,
+        event::{DomainEvent, EventSubscriber},
+    },
+};
+
+/// Queues the welcome email of newly registered users once their registration is committed
+pub struct WelcomeEmailSubscriber;
+
+#[async_trait]
+impl EventSubscriber for WelcomeEmailSubscriber {
+    fn name(&self) -> &'static str {
+        "welcome_email"
+    }
+
+    async fn handle(&self, context: &Context, event: &DomainEvent) -> anyhow::Result<()> {
+        let DomainEvent::UserRegistered { user_id, .. } = event else {
+            return Ok(());
+        };
+
+        // The outbox keeps the task with the registration and publishes it once committed
+        if context.outbox.is_some() {
+            return publish_task(
+                context,
+                TaskType::ProcessUserRegistration { user_id: *user_id },
+                Some(MessageType::Emails.as_ref()),
+            )
+            .await
+            .map_err(|e| e.context("Failed to publish welcome email task"));
+        }
+
+        let Some(producer) = &context.producer else {
+            tracing::warn!(
+                "Message producer is not available in context. Skipping welcome email task publishing."
+            );
+            return Ok(());
+        };
+
+        // A registration that is rolled back must not email anyone
+        let producer = producer.clone();
+        let user_id = *user_id;
+        context.after_commit(async move {
+            TaskEvent::new(TaskType::ProcessUserRegistration { user_id })
+                .publish_with_producer(
+                    producer.as_ref().as_ref(),
+                    Some(MessageType::Emails.as_ref()),
+                )
+                .await
+                .map_err(|e| e.context("Failed to publish welcome email task"))
+        });
+        Ok(())
+    }
+}
@@ -1,7 +1,14 @@
 use crate::{
+    config::setting::Setting,
     core::context::Context,
-    user::repository::refresh_token_repository::{self, RefreshTokenSearchParams},
+    user::repository::{
+        email_verification_repository, impersonation_repository, login_attempt_repository,
+        password_reset_repository,
+        refresh_token_repository::{self, RefreshTokenSearchParams},
+        signing_key_repository,
+    },
 };
+use chrono::Utc;
 use sea_orm::{DatabaseConnection, TransactionTrait};
 use std::sync::Arc;
 
@@ -24,9 +31,10 @@ pub async fn clean_expired_tokens(db: &DatabaseConnection) -> Result<(), anyhow:
     // Delete tokens in batches
     const BATCH_SIZE: usize = 100;
     for chunk in expired_tokens.chunks(BATCH_SIZE) {
-        let tokens: Vec<String> = chunk.iter().map(|token| token.token.clone()).collect();
-        refresh_token_repository::delete_by_tokens(&context, &tokens).await?;
+        let ids: Vec<i32> = chunk.iter().map(|token| token.id).collect();
+        refresh_token_repository::delete_by_ids(&context, &ids).await?;
     }
+    impersonation_repository::delete_expired(&context).await?;
 
     drop(context);
     Arc::try_unwrap(txn)
@@ -36,3 +44,52 @@ pub async fn clean_expired_tokens(db: &DatabaseConnection) -> Result<(), anyhow:
 
     Ok(())
 }
+
+pub async fn purge_expired_password_resets(db: &DatabaseConnection) -> Result<(), anyhow::Error> {
+    tracing::info!("Starting purge of expired password reset tokens");
+
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    password_reset_repository::delete_expired(&context).await?;
+    context.commit().await?;
+
+    Ok(())
+}
+
+pub async fn purge_expired_email_verifications(
+    db: &DatabaseConnection,
+) -> Result<(), anyhow::Error> {
+    tracing::info!("Starting purge of expired email verification tokens");
+
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    email_verification_repository::delete_expired(&context).await?;
+    context.commit().await?;
+
+    Ok(())
+}
+
+/// Delete failed sign-ins older than the lockout cooldown, which no longer count
+pub async fn purge_stale_login_attempts(db: &DatabaseConnection) -> Result<(), anyhow::Error> {
+    let cooldown = chrono::TimeDelta::from_std(Setting::new().login_lockout.cooldown())?;
+
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    let deleted =
+        login_attempt_repository::delete_before(&context, (Utc::now() - cooldown).naive_utc())
+            .await?;
+    context.commit().await?;
+
+    if deleted > 0 {
+        tracing::info!("Purged {} stale failed sign-in(s)", deleted);
+    }
+    Ok(())
+}
+
+pub async fn purge_retired_signing_keys(db: &DatabaseConnection) -> Result<(), anyhow::Error> {
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+    let deleted = signing_key_repository::delete_retired(&context).await?;
+    context.commit().await?;
+
+    if deleted > 0 {
+        tracing::info!("Purged {} retired signing key(s)", deleted);
+    }
+    Ok(())
+}
@@ -1,6 +1,6 @@
-use chrono::Datelike;
+use chrono::{Datelike, Duration as ChronoDuration, Utc};
 use rust_i18n::t;
-use sea_orm::{DatabaseConnection, TransactionTrait};
+use sea_orm::{DatabaseConnection, Set, TransactionTrait};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
@@ -9,15 +9,29 @@ use tokio::time::sleep;
 use crate::{
     config::setting::{MessageType, Setting},
     core::{
-        r#async::{TaskType, publish_task},
+        r#async::{AVATAR_PIPELINE, TaskEvent, TaskPriority, TaskType},
         context::Context,
+        dto::error_dto::ErrorDTO,
+        event_type::{
+            AVATAR_UPLOAD_COMPLETE, AVATAR_UPLOAD_PROGRESS, BULK_USER_COMPLETE, BULK_USER_PROGRESS,
+        },
+        id::OtpAlphabet,
+        permission::PermissionSet,
         template::engine::render_email_template,
+        translation::locale::DEFAULT_LOCALE,
+    },
+    notification::service::{
+        notification_service::{self, Notification},
+        notification_template_service::NotificationEvent,
     },
     pkg::broadcast::websocket::BroadcastMessage,
     pkg::cache::cache_task_status,
     pkg::messaging::MessageProducer,
     user::dto::avatar_dto::AvatarUploadProgressDTO,
-    user::repository::user_repository,
+    user::dto::bulk_user_dto::{BulkUserOperationDTO, BulkUserProgressDTO, BulkUserReportDTO},
+    user::entity::email_verification_token,
+    user::repository::{email_verification_repository, permission_repository, user_repository},
+    user::service::bulk_user_service,
 };
 
 pub async fn send_welcome_email(
@@ -37,54 +51,118 @@ pub async fn send_welcome_email(
         .map_err(|e| anyhow::anyhow!("Failed to find user: {}", e))?
         .ok_or_else(|| anyhow::anyhow!("User not found"))?;
 
+    tracing::info!("Dispatching welcome notification for user: {}", user.email);
+
+    // Deliver on the user's account channels through the worker instead of sending directly
+    let notification = Notification::new(NotificationEvent::Welcome)
+        .with_variable("email", user.email.clone())
+        .with_variable("first_name", user.first_name.clone().unwrap_or_default())
+        .with_variable("last_name", user.last_name.clone().unwrap_or_default())
+        .with_variable("phone", user.phone.clone().unwrap_or_default());
+    let channels = notification_service::dispatch(&context, producer, &user, &notification).await?;
+
     drop(context);
     Arc::try_unwrap(txn)
         .map_err(|_| anyhow::anyhow!("Failed to unwrap transaction for commit"))?
         .commit()
         .await?;
 
-    tracing::info!("Publishing welcome email task for user: {}", user.email);
+    tracing::info!(
+        "✓ Welcome notification dispatched to {} on {:?}",
+        user.email,
+        channels
+    );
+
+    Ok(())
+}
+
+/// Characters of the random token a verification link carries
+const VERIFICATION_TOKEN_LENGTH: u32 = 32;
+
+/// Email `user_id` a link confirming their address, replacing the links sent before.
+/// Users who are already verified are skipped.
+pub async fn send_verification_email(
+    db: &DatabaseConnection,
+    producer: &dyn MessageProducer,
+    user_id: i32,
+) -> anyhow::Result<()> {
+    let context = Context::builder(Arc::new(db.begin().await?)).build();
+
+    let user = user_repository::find_by_id(&context, user_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+    if user.verified_at.is_some() {
+        tracing::info!(
+            "User {} is already verified, skipping verification email",
+            user_id
+        );
+        return Ok(());
+    }
 
     let setting = Setting::new();
+    email_verification_repository::delete_by_user_id(&context, user.id).await?;
+    let token = context
+        .id_generator
+        .otp_from(VERIFICATION_TOKEN_LENGTH, OtpAlphabet::Alphanumeric);
+    email_verification_repository::create(
+        &context,
+        email_verification_token::ActiveModel {
+            user_id: Set(user.id),
+            token: Set(token.clone()),
+            expires_at: Set((Utc::now()
+                + ChronoDuration::minutes(setting.email_verification.expiry_minutes))
+            .naive_utc()),
+            ..Default::default()
+        },
+    )
+    .await?;
 
-    // Prepare template variables
+    let verify_url = setting
+        .email_verification
+        .link_url
+        .replace("{token}", &token);
     let mut variables = HashMap::new();
     variables.insert("app_name".to_string(), "My Axum App".to_string());
     variables.insert("app_url".to_string(), setting.app_url.clone());
     variables.insert("email".to_string(), user.email.clone());
     variables.insert(
         "first_name".to_string(),
-        user.first_name.clone().unwrap_or_default(),
+        user.first_name
+            .as_ref()
+            .map(|name| format!(" {}", name))
+            .unwrap_or_default(),
     );
+    variables.insert("verify_url".to_string(), verify_url);
     variables.insert(
-        "last_name".to_string(),
-        user.last_name.clone().unwrap_or_default(),
+        "expiry_minutes".to_string(),
+        setting.email_verification.expiry_minutes.to_string(),
     );
-    variables.insert("phone".to_string(), user.phone.clone().unwrap_or_default());
-    variables.insert("year".to_string(), chrono::Utc::now().year().to_string());
+    variables.insert("year".to_string(), Utc::now().year().to_string());
 
-    // Render template
-    let html_body = render_email_template("email/welcome.html", variables)?;
+    let locale = user.locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+    let subject = t!(
+        "email_template.verify_email.subject",
+        locale = locale,
+        app_name = "My Axum App"
+    )
+    .to_string();
+    let html_body = render_email_template("email/verify_email.html", locale, variables)?;
 
-    // Publish email task to worker instead of sending directly
-    publish_task(
-        producer,
+    // The token only becomes usable once the email carrying it is queued
+    context.commit().await?;
+    TaskEvent::with_priority(
         TaskType::SendEmail {
             to: user.email.clone(),
-            subject: "Welcome to My Axum App!".to_string(),
+            subject,
             text_body: None,
             html_body: Some(html_body),
         },
-        Some(MessageType::Emails.as_ref()),
+        TaskPriority::High,
     )
-    .await
-    .map_err(|e| anyhow::anyhow!("Failed to publish email task: {}", e))?;
-
-    tracing::info!(
-        "✓ Welcome email task published to worker for: {}",
-        user.email
-    );
+    .publish_with_producer(producer, Some(MessageType::Emails.as_ref()))
+    .await?;
 
+    tracing::info!("✓ Verification email task published for: {}", user.email);
     Ok(())
 }
 
@@ -187,7 +265,7 @@ pub async fn process_avatar_upload(
                 .with_message(&message);
 
         let broadcast_msg = BroadcastMessage {
-            event_type: "avatar_upload_progress".to_string(),
+            event_type: AVATAR_UPLOAD_PROGRESS.name.to_string(),
             data: serde_json::to_value(&progress_dto)
                 .map_err(|e| anyhow::anyhow!("Failed to serialize progress: {}", e))?,
         };
@@ -205,6 +283,9 @@ pub async fn process_avatar_upload(
             .publish_event_json(&msg_json, Some("broadcasts"))
             .await
             .map_err(|e| anyhow::anyhow!("Failed to publish progress: {}", e))?;
+        AVATAR_PIPELINE
+            .report(producer, &task_id, user_id, "processing", progress)
+            .await?;
 
         tracing::info!(
             "Avatar upload progress for task {}: {}% - {}",
@@ -226,7 +307,7 @@ pub async fn process_avatar_upload(
         );
 
     let final_msg = BroadcastMessage {
-        event_type: "avatar_upload_complete".to_string(),
+        event_type: AVATAR_UPLOAD_COMPLETE.name.to_string(),
         data: serde_json::to_value(&final_progress)
             .map_err(|e| anyhow::anyhow!("Failed to serialize final progress: {}", e))?,
     };
@@ -253,3 +334,82 @@ pub async fn process_avatar_upload(
 
     Ok(())
 }
+
+/// Apply a queued batch of bulk user operations on behalf of `actor_id`, each in
+/// its own transaction, reporting progress on the task channel after every operation
+pub async fn process_bulk_user_operations(
+    db: &DatabaseConnection,
+    producer: &dyn MessageProducer,
+    task_id: &str,
+    actor_id: i32,
+    locale: &str,
+    operations: &[BulkUserOperationDTO],
+) -> anyhow::Result<BulkUserReportDTO> {
+    let lookup = Context::read_only(db.clone()).build();
+    let actor = user_repository::find_by_id(&lookup, actor_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Bulk operation actor {} not found", actor_id))?;
+    // The operations are authorized as they would be in the request that queued them
+    let permissions: PermissionSet =
+        permission_repository::find_names_by_user_id(&lookup, actor_id)
+            .await?
+            .into_iter()
+            .collect();
+
+    tracing::info!(
+        "Applying {} bulk user operations for task {} on behalf of user {}",
+        operations.len(),
+        task_id,
+        actor_id
+    );
+
+    let total = operations.len();
+    let mut report = BulkUserReportDTO::default();
+    for (index, operation) in operations.iter().enumerate() {
+        let context = Context::builder(Arc::new(db.begin().await?))
+            .user(actor.clone())
+            .permissions(permissions.clone())
+            .locale(locale)
+            .build();
+        let result = match bulk_user_service::apply_operation(&context, operation).await {
+            Ok(()) => context.commit().await.map_err(ErrorDTO::from),
+            Err(e) => {
+                context.rollback().await?;
+                Err(e)
+            }
+        };
+        report.push(bulk_user_service::item_result(index, operation, result));
+
+        let processed = index + 1;
+        let (event_type, status, final_report) = if processed == total {
+            (BULK_USER_COMPLETE, "completed", Some(report.clone()))
+        } else {
+            (BULK_USER_PROGRESS, "processing", None)
+        };
+        let progress = BulkUserProgressDTO {
+            task_id: task_id.to_string(),
+            progress: (processed * 100 / total) as u8,
+            status: status.to_string(),
+            processed,
+            total,
+            report: final_report,
+        };
+        let broadcast_msg = BroadcastMessage {
+            event_type: event_type.name.to_string(),
+            data: serde_json::to_value(&progress)?,
+        };
+        producer
+            .publish_event_json(&serde_json::to_string(&broadcast_msg)?, Some("broadcasts"))
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to publish bulk progress: {}", e))?;
+    }
+
+    tracing::info!(
+        "✓ Bulk user task {} done: {} succeeded, {} failed",
+        task_id,
+        report.succeeded,
+        report.failed
+    );
+
+    Ok(report)
+}
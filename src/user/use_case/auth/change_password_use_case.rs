@@ -5,10 +5,14 @@ use sea_orm::entity::*;
 use crate::{
     core::{
         context::Context,
-        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+    },
+    pkg::password::verify_password,
+    user::{
+        dto::auth_dto::ChangePasswordDTO, entity::user, repository::user_repository,
+        service::auth_service,
     },
-    pkg::password::{hash_password_string, verify_password},
-    user::{dto::auth_dto::ChangePasswordDTO, entity::user, repository::user_repository},
 };
 
 pub async fn execute(
@@ -17,8 +21,8 @@ pub async fn execute(
 ) -> Result<ResponseDTO<()>, ErrorDTO> {
     // Get current user from context
     let current_user = context.user.as_ref().ok_or_else(|| {
-        ErrorDTO::new(
-            StatusCode::UNAUTHORIZED,
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
             t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
         )
     })?;
@@ -26,14 +30,21 @@ pub async fn execute(
     verify_password(&dto.old_password, &current_user.password)
         .await
         .map_err(|_| {
-            ErrorDTO::new(
-                StatusCode::BAD_REQUEST,
+            ErrorDTO::from_code(
+                ErrorCode::AuthPasswordIncorrect,
                 t!("auth.password_incorrect", locale = &context.locale).to_string(),
             )
         })?;
 
+    auth_service::validate_new_password(
+        context,
+        "new_password",
+        &dto.new_password,
+        &current_user.email,
+    )?;
+
     // Hash new password
-    let hashed_password = hash_password_string(&dto.new_password)
+    let hashed_password = auth_service::hash_password(&dto.new_password)
         .await
         .map_err(ErrorDTO::map_internal_error)?;
 
@@ -45,5 +56,11 @@ pub async fn execute(
         .await
         .map_err(ErrorDTO::map_internal_error)?;
 
+    context
+        .emit(DomainEvent::PasswordChanged {
+            user_id: current_user.id,
+        })
+        .await;
+
     Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
 }
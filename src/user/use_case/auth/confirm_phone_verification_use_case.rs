@@ -0,0 +1,100 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+    },
+    user::{
+        dto::auth_dto::{ConfirmPhoneDTO, ProfileDTO},
+        entity::{phone_verification_token, user},
+        repository::{phone_verification_repository, user_repository},
+        service::{user_service, verification_service},
+    },
+};
+
+/// Mark the profile phone as verified when `otp` matches the code sent to it
+pub async fn execute(
+    context: &Context,
+    dto: ConfirmPhoneDTO,
+) -> Result<ResponseDTO<ProfileDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let invalid_otp = || {
+        ErrorDTO::from_code(
+            ErrorCode::AuthInvalidOtp,
+            t!("auth.invalid_phone_otp", locale = &context.locale).to_string(),
+        )
+    };
+
+    let verification_token =
+        phone_verification_repository::find_by_user_id(context, current_user.id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?
+            .ok_or_else(invalid_otp)?;
+
+    // A code sent to a number the user has since replaced proves nothing about the new one
+    if current_user.phone.as_deref().map(str::trim) != Some(verification_token.phone.as_str()) {
+        phone_verification_repository::delete_by_user_id(context, current_user.id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+        return Err(invalid_otp().keep_changes());
+    }
+
+    if let Err(failure) = verification_service::check_otp(
+        &verification_token.token,
+        verification_token.retry_count,
+        verification_token.expires_at,
+        &dto.otp,
+    ) {
+        if failure.discards_code() {
+            phone_verification_repository::delete_by_user_id(context, current_user.id)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+        } else {
+            let retry_count = verification_token.retry_count + 1;
+            let mut verification_token: phone_verification_token::ActiveModel =
+                verification_token.into();
+            verification_token.retry_count = Set(retry_count);
+            phone_verification_repository::update(context, verification_token)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+        }
+        // The attempt or the discarded code has to outlive the failed request
+        return Err(failure.into_error(context, invalid_otp()).keep_changes());
+    }
+
+    let mut user: user::ActiveModel = current_user.clone().into();
+    user.phone_verified_at = Set(Some(Utc::now().naive_utc()));
+    let updated_user = user_repository::update(context, user)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+
+    phone_verification_repository::delete_by_user_id(context, current_user.id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    tracing::info!("Phone verified for user_id: {}", current_user.id);
+    context
+        .emit(DomainEvent::PhoneVerified {
+            user_id: current_user.id,
+        })
+        .await;
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        user_service::to_profile_dto(context, updated_user).await?,
+    ))
+}
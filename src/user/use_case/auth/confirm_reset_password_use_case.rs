@@ -0,0 +1,24 @@
+use axum::http::StatusCode;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        validation::Validate,
+    },
+    user::{dto::auth_dto::ConfirmResetPasswordDTO, service::auth_service},
+};
+
+/// Set a new password through the emailed reset link `token`. Each link works once.
+pub async fn execute(
+    context: &Context,
+    token: &str,
+    dto: ConfirmResetPasswordDTO,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    dto.validate(&context.locale)?;
+
+    let user = auth_service::find_password_reset_link_user(context, token).await?;
+    auth_service::reset_password(context, user, &dto.new_password).await?;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
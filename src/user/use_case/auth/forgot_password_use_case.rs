@@ -2,63 +2,93 @@ use std::collections::HashMap;
 
 use crate::user::entity::password_reset_token;
 use crate::{
-    config::setting::{MessageType, Setting},
+    config::setting::{MessageType, PasswordResetMethod, Setting},
     core::{
         r#async::{TaskPriority, TaskType, publish_task_with_priority},
         context::Context,
-        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        id::OtpAlphabet,
         template::engine::render_email_template,
+        validation::Validate,
     },
     user::{
         dto::auth_dto::ForgotPasswordDTO,
         entity::user,
         repository::{password_reset_repository, user_repository},
-        service::user_service,
+        service::auth_service,
     },
 };
 use axum::http::StatusCode;
 use chrono::Datelike;
 use chrono::Duration;
-use rand::RngExt;
 use rust_i18n::t;
 use sea_orm::Set;
+use tokio::time::Instant;
+
+/// Characters of the random nonce a reset link is signed over
+const RESET_LINK_NONCE_LENGTH: u32 = 32;
 
 pub async fn execute(
     context: &Context,
     dto: ForgotPasswordDTO,
 ) -> Result<ResponseDTO<()>, ErrorDTO> {
-    // Validate email format
-    user_service::validate_email_format(&dto.email, &context.locale)?;
+    dto.validate(&context.locale)?;
+
+    // Registered or not, the answer is the same and takes at least as long, so it can't be
+    // used to find out which emails have an account
+    let started = Instant::now();
+    let result = request_password_reset(context, &dto).await;
+    auth_service::pad_recovery_duration(started).await;
+
+    result.map(|_| ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
 
+async fn request_password_reset(
+    context: &Context,
+    dto: &ForgotPasswordDTO,
+) -> Result<(), ErrorDTO> {
     // Find user by email
     let user = user_repository::find_by_email(context, &dto.email)
         .await
         .map_err(ErrorDTO::map_internal_error)?;
 
-    // If user doesn't exist, still return success to prevent email enumeration
-    if user.is_none() {
+    let Some(user) = user else {
         tracing::warn!(
             "Password reset requested for non-existent email: {}",
             dto.email
         );
-        return Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()));
-    }
-
-    let user = user.unwrap();
+        return Ok(());
+    };
 
     // Delete any existing reset tokens for this user
     password_reset_repository::delete_by_user_id(context, user.id)
         .await
         .map_err(ErrorDTO::map_internal_error)?;
 
-    // Generate a secure 6-digit OTP
-    let otp: String = {
-        let mut rng = rand::rng();
-        format!("{:06}", rng.random_range(0..1000000))
+    // Generate a secure OTP in the configured format, or the nonce of a reset link
+    let setting = Setting::new().password_reset;
+    let (otp, expires_at) = match setting.method {
+        PasswordResetMethod::Otp => (
+            context
+                .id_generator
+                .otp_from(setting.otp_length, setting.otp_alphabet),
+            chrono::Utc::now() + Duration::minutes(setting.otp_expiry_minutes),
+        ),
+        PasswordResetMethod::Link => (
+            context
+                .id_generator
+                .otp_from(RESET_LINK_NONCE_LENGTH, OtpAlphabet::Alphanumeric),
+            chrono::Utc::now() + Duration::minutes(setting.link_expiry_minutes),
+        ),
     };
+    let reset_url = (setting.method == PasswordResetMethod::Link).then(|| {
+        setting.link_url.replace(
+            "{token}",
+            &auth_service::sign_password_reset_link(&otp, expires_at),
+        )
+    });
 
-    // Save OTP to database
-    let expires_at = chrono::Utc::now() + Duration::minutes(15);
+    // Save OTP or nonce to database
     let reset_token = password_reset_token::ActiveModel {
         user_id: Set(user.id),
         token: Set(otp.clone()),
@@ -70,17 +100,23 @@ pub async fn execute(
         .await
         .map_err(ErrorDTO::map_internal_error)?;
 
-    // Send password reset email
-    send_forgot_password_email(context, &user, &otp).await?;
-
-    tracing::info!("Password reset process completed for email: {}", dto.email);
-    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+    // Delivery failures are logged by the sender but not reported, since only registered
+    // emails get this far
+    if send_forgot_password_email(context, &user, &otp, reset_url.as_deref())
+        .await
+        .is_ok()
+    {
+        tracing::info!("Password reset process completed for email: {}", dto.email);
+    }
+    Ok(())
 }
 
+/// Email the OTP of the reset, or its link when `reset_url` is set
 async fn send_forgot_password_email(
     context: &Context,
     user: &user::Model,
     otp: &str,
+    reset_url: Option<&str>,
 ) -> Result<(), ErrorDTO> {
     // Get app settings
     let setting = Setting::new();
@@ -98,28 +134,55 @@ async fn send_forgot_password_email(
             String::new()
         },
     );
-    variables.insert("otp".to_string(), otp.to_string());
-    variables.insert("expiry_minutes".to_string(), "15".to_string());
-    variables.insert("max_attempts".to_string(), "3".to_string());
+    variables.insert(
+        "otp".to_string(),
+        if reset_url.is_some() { "" } else { otp }.to_string(),
+    );
+    variables.insert(
+        "reset_url".to_string(),
+        reset_url.unwrap_or_default().to_string(),
+    );
+    variables.insert(
+        "expiry_minutes".to_string(),
+        match reset_url {
+            Some(_) => setting.password_reset.link_expiry_minutes,
+            None => setting.password_reset.otp_expiry_minutes,
+        }
+        .to_string(),
+    );
+    variables.insert(
+        "max_attempts".to_string(),
+        setting.password_reset.max_attempts.to_string(),
+    );
     variables.insert("year".to_string(), chrono::Utc::now().year().to_string());
 
+    // Written in the user's language, whoever asked for the reset
+    let locale = user.locale.as_deref().unwrap_or(&context.locale);
+    let subject = t!(
+        "email_template.password_reset.subject",
+        locale = locale,
+        app_name = "My Axum App"
+    )
+    .to_string();
+
     // Render HTML template
-    let html_body = render_email_template("email/password_reset.html", variables).map_err(|e| {
-        tracing::error!("Failed to render password reset email template: {}", e);
-        ErrorDTO::new(
-            StatusCode::INTERNAL_SERVER_ERROR,
-            t!("email.prepare_failed", locale = &context.locale).to_string(),
-        )
-    })?;
+    let html_body =
+        render_email_template("email/password_reset.html", locale, variables).map_err(|e| {
+            tracing::error!("Failed to render password reset email template: {}", e);
+            ErrorDTO::from_code(
+                ErrorCode::EmailDeliveryFailed,
+                t!("email.prepare_failed", locale = &context.locale).to_string(),
+            )
+        })?;
 
     // Publish password reset email task with HIGH priority
     match &context.producer {
-        Some(producer) => {
+        Some(_) => {
             if let Err(e) = publish_task_with_priority(
-                producer.as_ref().as_ref(),
+                context,
                 TaskType::SendEmail {
                     to: user.email.clone(),
-                    subject: "Password Reset Request - My Axum App".to_string(),
+                    subject,
                     text_body: None,
                     html_body: Some(html_body),
                 },
@@ -129,8 +192,8 @@ async fn send_forgot_password_email(
             .await
             {
                 tracing::error!("Failed to publish password reset email task: {}", e);
-                return Err(ErrorDTO::new(
-                    StatusCode::INTERNAL_SERVER_ERROR,
+                return Err(ErrorDTO::from_code(
+                    ErrorCode::EmailDeliveryFailed,
                     t!("email.send_failed", locale = &context.locale).to_string(),
                 ));
             }
@@ -142,8 +205,8 @@ async fn send_forgot_password_email(
         }
         None => {
             tracing::error!("Message producer not available. Cannot send password reset email.");
-            return Err(ErrorDTO::new(
-                StatusCode::INTERNAL_SERVER_ERROR,
+            return Err(ErrorDTO::from_code(
+                ErrorCode::EmailUnavailable,
                 t!("email.service_unavailable", locale = &context.locale).to_string(),
             ));
         }
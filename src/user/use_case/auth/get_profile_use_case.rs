@@ -4,21 +4,21 @@ use rust_i18n::t;
 use crate::{
     core::{
         context::Context,
-        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
     },
-    user::dto::auth_dto::ProfileDTO,
+    user::{dto::auth_dto::ProfileDTO, service::user_service},
 };
 
 pub async fn execute(context: &Context) -> Result<ResponseDTO<ProfileDTO>, ErrorDTO> {
     // Get current user from context
     let current_user = context.user.as_ref().ok_or_else(|| {
-        ErrorDTO::new(
-            StatusCode::UNAUTHORIZED,
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
             t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
         )
     })?;
 
-    let profile_dto = ProfileDTO::from(current_user.clone());
+    let profile_dto = user_service::to_profile_dto(context, current_user.clone()).await?;
 
     Ok(ResponseDTO::new(StatusCode::OK, profile_dto))
 }
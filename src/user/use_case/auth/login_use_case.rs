@@ -2,15 +2,14 @@ use axum::http::{HeaderMap, StatusCode};
 use rust_i18n::t;
 
 use crate::{
+    config::setting::Setting,
     core::{
         context::Context,
-        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
     },
-    pkg::password::verify_password,
     user::{
         dto::auth_dto::{LoginDTO, TokenPairDTO},
-        repository::user_repository,
-        service::auth_service,
+        service::{auth_service, login_attempt_service, user_service},
     },
 };
 
@@ -19,29 +18,52 @@ pub async fn execute(
     dto: LoginDTO,
     headers: HeaderMap,
 ) -> Result<ResponseDTO<TokenPairDTO>, ErrorDTO> {
-    let user = user_repository::find_by_email(context, &dto.email)
-        .await
-        .map_err(ErrorDTO::map_internal_error)?
-        .ok_or_else(|| {
-            ErrorDTO::new(
-                StatusCode::UNAUTHORIZED,
-                t!("auth.email_not_registered", locale = &context.locale).to_string(),
-            )
-        })?;
-
-    verify_password(&dto.password, &user.password)
-        .await
-        .map_err(|_| {
-            ErrorDTO::new(
-                StatusCode::UNAUTHORIZED,
-                t!("auth.password_incorrect", locale = &context.locale).to_string(),
-            )
-        })?;
-
-    let (access, refresh) = auth_service::generate_token_pair(user.id).await?;
+    let user = user_service::find_by_login_email(context, &dto.email).await?;
+    let ip_address = auth_service::get_client_ip(&headers);
+
+    if let Some(retry_after) =
+        login_attempt_service::locked_for(context, &dto.email, ip_address.as_deref()).await?
+    {
+        return Err(login_attempt_service::account_locked(context, retry_after));
+    }
+
+    // Unknown emails and wrong passwords get the same answer, after the same work
+    let verified = auth_service::verify_user_password(user.as_ref(), &dto.password).await;
+    let Some(user) = user.filter(|_| verified) else {
+        // Keep the failure although the sign-in is rolled back, so it counts towards a lockout
+        let locked_for =
+            login_attempt_service::record_failure(context, &dto.email, ip_address.as_deref())
+                .await?;
+        let error = match locked_for {
+            Some(retry_after) => login_attempt_service::account_locked(context, retry_after),
+            None => ErrorDTO::from_code(
+                ErrorCode::AuthInvalidCredentials,
+                t!("auth.invalid_credentials", locale = &context.locale).to_string(),
+            ),
+        };
+        return Err(error.keep_changes());
+    };
+    login_attempt_service::clear_failures(context, &dto.email).await?;
+    if user.deactivated_at.is_some() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::AuthAccountDeactivated,
+            t!("auth.account_deactivated", locale = &context.locale).to_string(),
+        ));
+    }
+    if user.verified_at.is_none() && Setting::new().email_verification.required {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::AuthEmailNotVerified,
+            t!("auth.email_not_verified", locale = &context.locale).to_string(),
+        ));
+    }
+
+    // Upgrade hashes made with outdated parameters while the plain password is at hand
+    auth_service::rehash_password_if_outdated(context, &user, &dto.password).await;
+
+    let (access, refresh) = auth_service::generate_token_pair(context, user.id).await?;
 
     // Save refresh token to database
-    auth_service::create_refresh_token_record(context, user.id, &refresh, &headers).await?;
+    auth_service::create_refresh_token_record(context, user.id, &refresh, &headers, None).await?;
 
     let response_data = TokenPairDTO {
         access: access.clone(),
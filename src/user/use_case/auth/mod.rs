@@ -1,9 +1,20 @@
 pub mod change_password_use_case;
+pub mod confirm_phone_verification_use_case;
+pub mod confirm_reset_password_use_case;
 pub mod forgot_password_use_case;
 pub mod get_profile_use_case;
 pub mod login_use_case;
 pub mod logout_use_case;
+pub mod oauth_login_use_case;
 pub mod refresh_token_use_case;
 pub mod register_use_case;
+pub mod report_sign_in_use_case;
+pub mod resend_verification_use_case;
 pub mod reset_password_use_case;
+pub mod revoke_other_sessions_use_case;
+pub mod revoke_session_use_case;
+pub mod search_session_use_case;
+pub mod send_phone_verification_use_case;
 pub mod update_profile_use_case;
+pub mod verify_email_use_case;
+pub mod verify_reset_link_use_case;
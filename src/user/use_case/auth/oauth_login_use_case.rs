@@ -0,0 +1,272 @@
+use std::{str::FromStr, sync::Arc};
+
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header::LOCATION};
+use chrono::{Duration, Utc};
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    config::setting::Setting,
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+        id::OtpAlphabet,
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+    },
+    pkg::{
+        http_client::HttpClient,
+        oauth::{OAuthIdentity, OAuthProvider},
+    },
+    user::{
+        dto::auth_dto::{OAuthAuthorizationDTO, OAuthCallbackDTO, TokenPairDTO},
+        entity::{
+            oauth_account,
+            sea_orm_active_enums::{IdentityProvider, UserRole},
+            user,
+        },
+        repository::{oauth_account_repository, user_repository},
+        service::auth_service,
+    },
+};
+
+/// Minutes a sign-in started here may take at the provider before its state expires
+const STATE_EXPIRY_MINUTES: i64 = 10;
+
+/// Length of the random part of a sign-in state
+const STATE_NONCE_LENGTH: u32 = 32;
+
+/// Provider named `name` in the route with its client, as long as it is configured
+pub fn resolve_provider(
+    context: &Context,
+    setting: &Setting,
+    http: &HttpClient,
+    name: &str,
+) -> Result<(IdentityProvider, Arc<dyn OAuthProvider>), ErrorDTO> {
+    IdentityProvider::from_str(name)
+        .ok()
+        .and_then(|provider| Some((provider, setting.oauth.get_provider(provider, http)?)))
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::AuthOAuthProviderUnavailable,
+                t!(
+                    "auth.oauth_provider_unavailable",
+                    locale = &context.locale,
+                    provider = name
+                )
+                .to_string(),
+            )
+        })
+}
+
+/// Redirect to the sign-in page of `client`, remembering in a cookie the state the
+/// provider has to bring back to `redirect_uri`
+pub fn authorize(
+    context: &Context,
+    provider: IdentityProvider,
+    client: &dyn OAuthProvider,
+    redirect_uri: &str,
+) -> ResponseDTO<OAuthAuthorizationDTO> {
+    let nonce = context
+        .id_generator
+        .otp_from(STATE_NONCE_LENGTH, OtpAlphabet::Alphanumeric);
+    let expires_at = Utc::now() + Duration::minutes(STATE_EXPIRY_MINUTES);
+    let state = auth_service::sign_oauth_state(provider.as_ref(), &nonce, expires_at);
+    let authorization_url = client.authorization_url(redirect_uri, &state);
+
+    let mut headers = HeaderMap::new();
+    auth_service::set_oauth_state_cookie(&mut headers, &state, STATE_EXPIRY_MINUTES * 60);
+    if let Ok(location) = HeaderValue::from_str(&authorization_url) {
+        headers.insert(LOCATION, location);
+    }
+
+    ResponseDTO::with_headers(
+        StatusCode::FOUND,
+        OAuthAuthorizationDTO { authorization_url },
+        headers,
+    )
+}
+
+/// Sign in the user `client` vouches for, once the provider redirected back: the user their
+/// provider account is linked to, else the user with their verified email, else a new user.
+/// The provider account is linked to the latter two. Issues the same tokens and cookies
+/// as login.
+pub async fn execute(
+    context: &Context,
+    provider: IdentityProvider,
+    client: &dyn OAuthProvider,
+    redirect_uri: &str,
+    dto: OAuthCallbackDTO,
+    headers: HeaderMap,
+) -> Result<ResponseDTO<TokenPairDTO>, ErrorDTO> {
+    if let Some(error) = &dto.error {
+        tracing::info!(
+            provider = provider.as_ref(),
+            "OAuth sign-in refused: {}",
+            error
+        );
+        return Err(denied(context));
+    }
+
+    // The state must be the one this browser was given, so a callback link crafted by
+    // someone else can't sign the user into the wrong account
+    let cookie_state =
+        auth_service::get_token_from_cookies(&headers, auth_service::OAUTH_STATE_COOKIE);
+    let state_valid = match (&dto.state, &cookie_state) {
+        (Some(state), Some(cookie_state)) => {
+            state == cookie_state && auth_service::verify_oauth_state(provider.as_ref(), state)
+        }
+        _ => false,
+    };
+    if !state_valid {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::AuthOAuthStateInvalid,
+            t!("auth.oauth_state_invalid", locale = &context.locale).to_string(),
+        ));
+    }
+    let code = dto.code.as_deref().ok_or_else(|| denied(context))?;
+
+    let identity = client
+        .fetch_identity(code, redirect_uri)
+        .await
+        .map_err(|e| {
+            tracing::warn!(
+                provider = provider.as_ref(),
+                "OAuth sign-in failed: {:#}",
+                e
+            );
+            ErrorDTO::from_code(
+                ErrorCode::AuthOAuthFailed,
+                t!("auth.oauth_failed", locale = &context.locale).to_string(),
+            )
+        })?;
+
+    let user = find_or_link_user(context, provider, &identity).await?;
+    if user.deactivated_at.is_some() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::AuthAccountDeactivated,
+            t!("auth.account_deactivated", locale = &context.locale).to_string(),
+        ));
+    }
+
+    let (access, refresh) = auth_service::generate_token_pair(context, user.id).await?;
+
+    // Save refresh token to database
+    auth_service::create_refresh_token_record(context, user.id, &refresh, &headers, None).await?;
+
+    let response_data = TokenPairDTO {
+        access: access.clone(),
+        refresh: refresh.clone(),
+    };
+
+    let mut response_headers = HeaderMap::new();
+    auth_service::set_auth_cookies(&mut response_headers, &access, &refresh);
+    auth_service::set_oauth_state_cookie(&mut response_headers, "", 0);
+
+    Ok(ResponseDTO::with_headers(
+        StatusCode::OK,
+        response_data,
+        response_headers,
+    ))
+}
+
+fn denied(context: &Context) -> ErrorDTO {
+    ErrorDTO::from_code(
+        ErrorCode::AuthOAuthDenied,
+        t!("auth.oauth_denied", locale = &context.locale).to_string(),
+    )
+}
+
+async fn find_or_link_user(
+    context: &Context,
+    provider: IdentityProvider,
+    identity: &OAuthIdentity,
+) -> Result<user::Model, ErrorDTO> {
+    if let Some(account) =
+        oauth_account_repository::find_by_provider_subject(context, provider, &identity.subject)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?
+    {
+        return user_repository::find_by_id(context, account.user_id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?
+            .ok_or_else(|| {
+                ErrorDTO::from_code(
+                    ErrorCode::AuthUserNotFound,
+                    t!("authorization.user_not_found", locale = &context.locale).to_string(),
+                )
+            });
+    }
+
+    // Linking by an email the provider didn't verify would hand its owner's account over
+    let email = identity
+        .email
+        .as_deref()
+        .filter(|_| identity.email_verified)
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::AuthOAuthEmailUnverified,
+                t!("auth.oauth_email_unverified", locale = &context.locale).to_string(),
+            )
+        })?;
+
+    let user = match user_repository::find_by_email(context, email)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+    {
+        Some(user) => user,
+        None => create_user(context, email, identity).await?,
+    };
+
+    oauth_account_repository::create(
+        context,
+        oauth_account::ActiveModel {
+            user_id: Set(user.id),
+            provider: Set(provider),
+            subject: Set(identity.subject.clone()),
+            email: Set(Some(email.to_string())),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(user)
+}
+
+async fn create_user(
+    context: &Context,
+    email: &str,
+    identity: &OAuthIdentity,
+) -> Result<user::Model, ErrorDTO> {
+    // Nobody knows the password of an account created at sign-in; forgot-password sets one
+    let hashed_password = auth_service::hash_password(&uuid::Uuid::new_v4().to_string())
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let user = user_repository::create(
+        context,
+        user::ActiveModel {
+            email: Set(email.to_string()),
+            password: Set(hashed_password),
+            role: Set(UserRole::User),
+            first_name: Set(identity.first_name.clone()),
+            last_name: Set(identity.last_name.clone()),
+            // Only emails the provider verified get this far
+            verified_at: Set(Some(Utc::now().naive_utc())),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+    context
+        .emit(DomainEvent::UserRegistered {
+            user_id: user.id,
+            created_by: None,
+        })
+        .await;
+
+    Ok(user)
+}
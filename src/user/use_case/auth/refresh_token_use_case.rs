@@ -1,16 +1,16 @@
 use axum::http::{HeaderMap, StatusCode};
 use rust_i18n::t;
+use sea_orm::Set;
 
 use crate::{
-    config::setting::Setting,
     core::{
         context::Context,
-        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
     },
-    pkg::jwt::decode_token,
     user::{
         dto::auth_dto::{RefreshTokenDTO, TokenPairDTO},
-        repository::{refresh_token_repository, user_repository},
+        entity::{refresh_token, sea_orm_active_enums::SecurityEventKind, security_event},
+        repository::{refresh_token_repository, security_event_repository, user_repository},
         service::auth_service::{self, TokenType},
     },
 };
@@ -33,40 +33,60 @@ pub async fn execute(
             .await?
         }
     };
-    let user_id = validate_jwt_token(&refresh_token, &context.locale)?;
+    let user_id = validate_jwt_token(context, &refresh_token).await?;
 
     // Check if user exists
     let user = user_repository::find_by_id(context, user_id)
         .await
         .map_err(ErrorDTO::map_internal_error)?
         .ok_or_else(|| {
-            ErrorDTO::new(
-                StatusCode::UNAUTHORIZED,
+            ErrorDTO::from_code(
+                ErrorCode::AuthUserNotFound,
                 t!("auth.user_not_found", locale = &context.locale).to_string(),
             )
         })?;
+    if user.deactivated_at.is_some() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::AuthSessionDeactivated,
+            t!("auth.account_deactivated", locale = &context.locale).to_string(),
+        ));
+    }
 
     // Check if refresh token exists and is valid in database
-    refresh_token_repository::find_by_user_and_token(context, user.id, &refresh_token)
-        .await
-        .map_err(ErrorDTO::map_internal_error)?
-        .ok_or_else(|| {
-            ErrorDTO::new(
-                StatusCode::UNAUTHORIZED,
-                t!("auth.refresh_token_invalid", locale = &context.locale).to_string(),
-            )
-        })?;
+    let previous =
+        refresh_token_repository::find_by_user_and_token(context, user.id, &refresh_token)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?
+            .ok_or_else(|| {
+                ErrorDTO::from_code(
+                    ErrorCode::AuthRefreshTokenInvalid,
+                    t!("auth.refresh_token_invalid", locale = &context.locale).to_string(),
+                )
+            })?;
+
+    // A rotated token coming back means it leaked, whoever presents it
+    if previous.rotated_at.is_some() {
+        return Err(revoke_family(context, previous, &headers).await?);
+    }
 
-    // Delete old refresh token
-    refresh_token_repository::delete_by_token(context, &refresh_token)
+    // Keep the old refresh token, so presenting it again is recognized as reuse
+    let previous = refresh_token_repository::mark_rotated(context, previous)
         .await
         .map_err(ErrorDTO::map_internal_error)?;
 
     // Generate new token pair
-    let (new_access, new_refresh) = auth_service::generate_token_pair(user.id).await?;
+    let (new_access, new_refresh) = auth_service::generate_token_pair(context, user.id).await?;
 
     // Save new refresh token to database
-    auth_service::create_refresh_token_record(context, user.id, &new_refresh, &headers).await?;
+    // Its expiry follows the policy recorded on the token it replaces
+    auth_service::create_refresh_token_record(
+        context,
+        user.id,
+        &new_refresh,
+        &headers,
+        Some(&previous),
+    )
+    .await?;
 
     let response_data = TokenPairDTO {
         access: new_access.clone(),
@@ -83,14 +103,63 @@ pub async fn execute(
     ))
 }
 
-fn validate_jwt_token(refresh_token: &str, locale: &str) -> Result<i32, ErrorDTO> {
-    let setting = Setting::new();
-    let claims = decode_token(refresh_token, &setting.jwt_secret).map_err(|_| {
-        ErrorDTO::new(
-            StatusCode::UNAUTHORIZED,
-            t!("auth.refresh_token_invalid", locale = locale).to_string(),
-        )
-    })?;
+/// Revoke every session rotated from the same sign-in as the reused `token`, the thief's and
+/// the user's alike, and record the reuse. Returns the error answering the request, which
+/// keeps the revocation.
+async fn revoke_family(
+    context: &Context,
+    token: refresh_token::Model,
+    headers: &HeaderMap,
+) -> Result<ErrorDTO, ErrorDTO> {
+    let revoked = match &token.family_id {
+        Some(family_id) => refresh_token_repository::delete_by_family_id(context, family_id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?,
+        None => {
+            refresh_token_repository::delete_by_ids(context, &[token.id])
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+            1
+        }
+    };
+
+    let device_info = auth_service::get_device_info(headers);
+    let ip_address = auth_service::get_client_ip(headers);
+    tracing::warn!(
+        user_id = token.user_id,
+        family_id = token.family_id.as_deref(),
+        revoked,
+        "Rotated refresh token reused, revoked its family"
+    );
+    security_event_repository::create(
+        context,
+        security_event::ActiveModel {
+            user_id: Set(token.user_id),
+            kind: Set(SecurityEventKind::RefreshTokenReused),
+            device_info: Set(device_info),
+            ip_address: Set(ip_address),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ErrorDTO::from_code(
+        ErrorCode::AuthRefreshTokenReused,
+        t!("auth.refresh_token_reused", locale = &context.locale).to_string(),
+    )
+    .keep_changes())
+}
+
+async fn validate_jwt_token(context: &Context, refresh_token: &str) -> Result<i32, ErrorDTO> {
+    let claims = auth_service::verify_token(context, refresh_token)
+        .await?
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::AuthRefreshTokenInvalid,
+                t!("auth.refresh_token_invalid", locale = &context.locale).to_string(),
+            )
+        })?;
 
     Ok(claims.sub)
 }
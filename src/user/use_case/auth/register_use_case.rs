@@ -1,11 +1,11 @@
 use crate::{
-    config::setting::MessageType,
     core::{
-        r#async::{TaskType, publish_task},
         context::Context,
         dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+        validation::Validate,
     },
-    pkg::password::hash_password_string,
     user::{
         dto::auth_dto::{RegisterDTO, TokenPairDTO},
         entity::{sea_orm_active_enums::UserRole, user},
@@ -21,16 +21,13 @@ pub async fn execute(
     dto: RegisterDTO,
     headers: HeaderMap,
 ) -> Result<ResponseDTO<TokenPairDTO>, ErrorDTO> {
-    // Validate email format
-    user_service::validate_email_format(&dto.email, &context.locale)?;
-
-    // Validate password
-    user_service::validate_password(&dto.password, &context.locale)?;
+    dto.validate(&context.locale)?;
+    auth_service::validate_new_password(context, "password", &dto.password, &dto.email)?;
 
     // Check email uniqueness
     user_service::validate_unique_email(context, &dto.email, None).await?;
 
-    let hashed_password = hash_password_string(&dto.password)
+    let hashed_password = auth_service::hash_password(&dto.password)
         .await
         .map_err(ErrorDTO::map_internal_error)?;
 
@@ -45,13 +42,19 @@ pub async fn execute(
     };
     let user = user_repository::create(context, user).await.unwrap();
 
-    let (access, refresh) = auth_service::generate_token_pair(user.id).await?;
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+
+    let (access, refresh) = auth_service::generate_token_pair(context, user.id).await?;
 
     // Save refresh token to database
-    auth_service::create_refresh_token_record(context, user.id, &refresh, &headers).await?;
+    auth_service::create_refresh_token_record(context, user.id, &refresh, &headers, None).await?;
 
-    // Send welcome email
-    send_welcome_email(context, &user).await?;
+    context
+        .emit(DomainEvent::UserRegistered {
+            user_id: user.id,
+            created_by: None,
+        })
+        .await;
 
     let response_data = TokenPairDTO {
         access: access.clone(),
@@ -67,27 +70,3 @@ pub async fn execute(
         response_headers,
     ))
 }
-
-async fn send_welcome_email(context: &Context, user: &user::Model) -> Result<(), ErrorDTO> {
-    let user_id = user.id;
-    match &context.producer {
-        Some(producer) => {
-            if let Err(e) = publish_task(
-                producer.as_ref().as_ref(),
-                TaskType::ProcessUserRegistration { user_id },
-                Some(MessageType::Emails.as_ref()),
-            )
-            .await
-            {
-                tracing::error!("Failed to publish welcome email task: {}", e);
-            }
-        }
-        None => {
-            tracing::warn!(
-                "Message producer not available in context. Skipping welcome email task publishing."
-            );
-        }
-    }
-
-    Ok(())
-}
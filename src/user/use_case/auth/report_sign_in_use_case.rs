@@ -0,0 +1,68 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+    },
+    user::{
+        dto::auth_dto::ReportSignInDTO,
+        entity::{sea_orm_active_enums::SecurityEventKind, security_event},
+        repository::{refresh_token_repository, security_event_repository},
+    },
+};
+
+/// Revoke the sessions of a new sign-in the user reports as not theirs, through the link
+/// of its alert email. Each link works once.
+pub async fn execute(context: &Context, dto: ReportSignInDTO) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let security_event = security_event_repository::find_by_report_token(context, &dto.token)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .filter(|security_event| {
+            security_event.kind == SecurityEventKind::NewSignIn
+                && security_event.reported_at.is_none()
+        })
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::AuthSignInReportInvalid,
+                t!("auth.sign_in_report_invalid", locale = &context.locale).to_string(),
+            )
+        })?;
+
+    // Refresh tokens are rotated on use, so the session is found by its client
+    let revoked = refresh_token_repository::delete_by_user_and_client(
+        context,
+        security_event.user_id,
+        security_event.device_info.as_deref(),
+        security_event.ip_address.as_deref(),
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    let user_id = security_event.user_id;
+    let security_event_id = security_event.id;
+    let mut security_event: security_event::ActiveModel = security_event.into();
+    security_event.reported_at = Set(Some(Utc::now().naive_utc()));
+    security_event_repository::update(context, security_event)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    tracing::warn!(
+        user_id,
+        security_event_id,
+        revoked,
+        "Sign-in reported as not the user's"
+    );
+    context
+        .emit(DomainEvent::SignInReported {
+            user_id,
+            security_event_id,
+        })
+        .await;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
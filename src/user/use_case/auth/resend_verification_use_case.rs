@@ -0,0 +1,65 @@
+use axum::http::StatusCode;
+use tokio::time::Instant;
+
+use crate::{
+    config::setting::MessageType,
+    core::{
+        r#async::{TaskPriority, TaskType, publish_task_with_priority},
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        validation::Validate,
+    },
+    user::{
+        dto::auth_dto::ResendVerificationDTO, repository::user_repository, service::auth_service,
+    },
+};
+
+/// Email a new verification link to `dto.email` when it belongs to an unverified account
+pub async fn execute(
+    context: &Context,
+    dto: ResendVerificationDTO,
+) -> Result<ResponseDTO<()>, ErrorDTO> {
+    dto.validate(&context.locale)?;
+
+    // Like a password reset, the answer doesn't tell which emails have an account
+    let started = Instant::now();
+    let result = request_verification_email(context, &dto).await;
+    auth_service::pad_recovery_duration(started).await;
+
+    result.map(|_| ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
+
+async fn request_verification_email(
+    context: &Context,
+    dto: &ResendVerificationDTO,
+) -> Result<(), ErrorDTO> {
+    let user = user_repository::find_by_email(context, &dto.email)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let Some(user) = user.filter(|user| user.verified_at.is_none()) else {
+        tracing::warn!(
+            "Verification email requested for unknown or verified email: {}",
+            dto.email
+        );
+        return Ok(());
+    };
+
+    if context.producer.is_none() {
+        tracing::error!("Message producer not available. Cannot send verification email.");
+        return Ok(());
+    }
+
+    // Delivery failures are logged but not reported, since only unverified emails get this far
+    if let Err(e) = publish_task_with_priority(
+        context,
+        TaskType::SendVerificationEmail { user_id: user.id },
+        TaskPriority::High,
+        Some(MessageType::Emails.as_ref()),
+    )
+    .await
+    {
+        tracing::error!("Failed to publish verification email task: {}", e);
+    }
+    Ok(())
+}
@@ -1,14 +1,15 @@
 use crate::{
+    config::setting::{PasswordResetMethod, Setting},
     core::{
         context::Context,
-        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        validation::Validate,
     },
-    pkg::password::hash_password_string,
     user::{
         dto::auth_dto::ResetPasswordDTO,
-        entity::{password_reset_token, user},
+        entity::password_reset_token,
         repository::{password_reset_repository, user_repository},
-        service::user_service,
+        service::auth_service,
     },
 };
 use axum::http::StatusCode;
@@ -20,72 +21,70 @@ pub async fn execute(
     context: &Context,
     dto: ResetPasswordDTO,
 ) -> Result<ResponseDTO<()>, ErrorDTO> {
-    // Validate email format
-    user_service::validate_email_format(&dto.email, &context.locale)?;
+    let setting = Setting::new().password_reset;
+    let invalid_email_or_otp = || {
+        ErrorDTO::from_code(
+            ErrorCode::AuthInvalidOtp,
+            t!("auth.invalid_email_or_otp", locale = &context.locale).to_string(),
+        )
+    };
+
+    // Deployments resetting by link never send OTPs, so none is worth guessing
+    if setting.method == PasswordResetMethod::Link {
+        return Err(invalid_email_or_otp());
+    }
 
-    // Validate password
-    user_service::validate_password(&dto.new_password, &context.locale)?;
+    dto.validate(&context.locale)?;
 
     // Find user by email first
     let user = user_repository::find_by_email(context, &dto.email)
         .await
         .map_err(ErrorDTO::map_internal_error)?
-        .ok_or_else(|| {
-            ErrorDTO::new(
-                StatusCode::BAD_REQUEST,
-                t!("auth.invalid_email_or_otp", locale = &context.locale).to_string(),
-            )
-        })?;
+        .ok_or_else(invalid_email_or_otp)?;
 
     // Find reset token by OTP
-    let reset_token = password_reset_repository::find_by_token(context, &dto.otp)
+    let otp = setting.otp_alphabet.normalize(&dto.otp);
+    let found_token = password_reset_repository::find_by_token(context, &otp)
         .await
-        .map_err(ErrorDTO::map_internal_error)?
-        .ok_or_else(|| {
-            ErrorDTO::new(
-                StatusCode::BAD_REQUEST,
-                t!("auth.invalid_email_or_otp", locale = &context.locale).to_string(),
-            )
-        })?;
-
-    // Verify OTP belongs to this user
-    if reset_token.user_id != user.id {
-        tracing::warn!(
-            "OTP {} does not belong to user {} (email: {})",
-            dto.otp,
-            user.id,
-            dto.email
-        );
-        // Increment retry count for security
-        let mut reset_token_active: password_reset_token::ActiveModel = reset_token.into();
-        reset_token_active.retry_count = Set(reset_token_active.retry_count.unwrap() + 1);
-        password_reset_repository::update(context, reset_token_active)
-            .await
-            .map_err(ErrorDTO::map_internal_error)?;
-        return Err(ErrorDTO::new(
-            StatusCode::BAD_REQUEST,
-            t!("auth.invalid_email_or_otp", locale = &context.locale).to_string(),
-        ));
-    }
+        .map_err(ErrorDTO::map_internal_error)?;
 
-    // Check retry count
-    const MAX_ATTEMPTS: i32 = 3;
-    if reset_token.retry_count >= MAX_ATTEMPTS {
+    // A wrong OTP counts against the one sent to this user. Failed attempts are kept
+    // although the request fails, or the lockout could never be reached.
+    let reset_token = match found_token {
+        Some(reset_token) if reset_token.user_id == user.id => reset_token,
+        found_token => {
+            if let Some(reset_token) = found_token {
+                tracing::warn!(
+                    "OTP does not belong to user {} (email: {})",
+                    user.id,
+                    dto.email
+                );
+                // The OTP of another user is exposed, so it counts against theirs too
+                record_failed_attempt(context, reset_token, setting.max_attempts).await?;
+            }
+
+            let error = match password_reset_repository::find_latest_by_user_id(context, user.id)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?
+            {
+                Some(reset_token) => {
+                    record_failed_attempt(context, reset_token, setting.max_attempts).await?
+                }
+                None => invalid_email_or_otp(),
+            };
+            return Err(error.keep_changes());
+        }
+    };
+
+    // Check retry count, in case the limit was lowered after the OTP was sent
+    if reset_token.retry_count >= setting.max_attempts {
         // Delete token after max retries
         let reset_token_active: password_reset_token::ActiveModel = reset_token.clone().into();
         password_reset_repository::delete(context, reset_token_active)
             .await
             .map_err(ErrorDTO::map_internal_error)?;
 
-        return Err(ErrorDTO::new(
-            StatusCode::BAD_REQUEST,
-            t!(
-                "auth.otp_max_attempts_exceeded",
-                max = MAX_ATTEMPTS,
-                locale = &context.locale
-            )
-            .to_string(),
-        ));
+        return Err(max_attempts_exceeded(context, setting.max_attempts).keep_changes());
     }
 
     // Check if token is expired
@@ -97,39 +96,59 @@ pub async fn execute(
             .await
             .map_err(ErrorDTO::map_internal_error)?;
 
-        return Err(ErrorDTO::new(
-            StatusCode::BAD_REQUEST,
+        return Err(ErrorDTO::from_code(
+            ErrorCode::AuthOtpExpired,
             t!("auth.otp_expired", locale = &context.locale).to_string(),
-        ));
+        )
+        .keep_changes());
     }
 
-    // Hash new password
-    let hashed_password = hash_password_string(&dto.new_password)
-        .await
-        .map_err(ErrorDTO::map_internal_error)?;
+    // Update the password, revoking this and any other reset token of the user
+    auth_service::reset_password(context, user, &dto.new_password).await?;
 
-    // Update user's password
-    let mut user: user::ActiveModel = user.into();
-    user.password = Set(hashed_password);
-    user_repository::update(context, user)
-        .await
-        .map_err(ErrorDTO::map_internal_error)?;
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
 
-    // Store user_id before moving reset_token
-    let user_id = reset_token.user_id;
+/// Count a wrong OTP against `reset_token`, revoking it once `max_attempts` are used up.
+/// Returns the error to answer with.
+async fn record_failed_attempt(
+    context: &Context,
+    reset_token: password_reset_token::Model,
+    max_attempts: i32,
+) -> Result<ErrorDTO, ErrorDTO> {
+    let retry_count = reset_token.retry_count + 1;
+    let mut reset_token_active: password_reset_token::ActiveModel = reset_token.into();
 
-    // Delete the used reset token
-    let reset_token_active: password_reset_token::ActiveModel = reset_token.into();
-    password_reset_repository::delete(context, reset_token_active)
-        .await
-        .map_err(ErrorDTO::map_internal_error)?;
+    if retry_count >= max_attempts {
+        tracing::warn!(
+            "Password reset OTP of user {} revoked after {} failed attempts",
+            reset_token_active.user_id.as_ref(),
+            retry_count
+        );
+        password_reset_repository::delete(context, reset_token_active)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+        return Ok(max_attempts_exceeded(context, max_attempts));
+    }
 
-    // Optionally: Delete all other reset tokens for this user
-    password_reset_repository::delete_by_user_id(context, user_id)
+    reset_token_active.retry_count = Set(retry_count);
+    password_reset_repository::update(context, reset_token_active)
         .await
         .map_err(ErrorDTO::map_internal_error)?;
+    Ok(ErrorDTO::from_code(
+        ErrorCode::AuthInvalidOtp,
+        t!("auth.invalid_email_or_otp", locale = &context.locale).to_string(),
+    ))
+}
 
-    tracing::info!("Password successfully reset for user_id: {}", user_id);
-
-    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+fn max_attempts_exceeded(context: &Context, max_attempts: i32) -> ErrorDTO {
+    ErrorDTO::from_code(
+        ErrorCode::AuthOtpAttemptsExceeded,
+        t!(
+            "auth.otp_max_attempts_exceeded",
+            max = max_attempts,
+            locale = &context.locale
+        )
+        .to_string(),
+    )
 }
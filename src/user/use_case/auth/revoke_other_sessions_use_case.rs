@@ -0,0 +1,35 @@
+use axum::http::{HeaderMap, StatusCode};
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    user::{repository::refresh_token_repository, service::auth_service},
+};
+
+/// Sign every device of the current user out except the one making the request. Without a
+/// `refresh_token` cookie there is no session to keep, so all of them are revoked.
+pub async fn execute(context: &Context, headers: HeaderMap) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let current_session =
+        auth_service::find_current_session(context, current_user.id, &headers).await?;
+    let revoked = refresh_token_repository::delete_other_sessions(
+        context,
+        current_user.id,
+        current_session.as_ref(),
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    tracing::info!(user_id = current_user.id, revoked, "Other sessions revoked");
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -0,0 +1,54 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    user::repository::refresh_token_repository::{self, RefreshTokenSearchParams},
+};
+
+/// Sign one of the current user's devices out. Its rotated tokens go with it, so none of
+/// them can be replayed.
+pub async fn execute(context: &Context, id: i32) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let (sessions, _) = refresh_token_repository::search(
+        context,
+        &RefreshTokenSearchParams {
+            ids: Some(&[id]),
+            user_id: Some(current_user.id),
+            is_rotated: Some(false),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+    let session = sessions.into_iter().next().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthSessionNotFound,
+            t!("auth.session_not_found", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    match &session.family_id {
+        Some(family_id) => {
+            refresh_token_repository::delete_by_family_id(context, family_id)
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+        }
+        None => {
+            refresh_token_repository::delete_by_ids(context, &[session.id])
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+        }
+    }
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -0,0 +1,59 @@
+use axum::http::{HeaderMap, StatusCode};
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    user::{
+        dto::auth_dto::{SessionDTO, SessionListDTO},
+        repository::refresh_token_repository::{self, RefreshTokenSearchParams},
+        service::auth_service,
+    },
+};
+
+/// List the devices signed in to the current user's account, newest first
+pub async fn execute(
+    context: &Context,
+    headers: HeaderMap,
+) -> Result<ResponseDTO<SessionListDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let current_session =
+        auth_service::find_current_session(context, current_user.id, &headers).await?;
+    let (sessions, count) = refresh_token_repository::search(
+        context,
+        &RefreshTokenSearchParams {
+            user_id: Some(current_user.id),
+            is_expired: Some(false),
+            is_rotated: Some(false),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    let items = sessions
+        .into_iter()
+        .map(|session| {
+            let current = current_session
+                .as_ref()
+                .is_some_and(|current_session| current_session.id == session.id);
+            SessionDTO {
+                current,
+                ..SessionDTO::from(session)
+            }
+        })
+        .collect();
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        SessionListDTO { items, count },
+    ))
+}
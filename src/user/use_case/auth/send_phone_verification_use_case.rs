@@ -0,0 +1,99 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    config::setting::MessageType,
+    core::{
+        r#async::{TaskPriority, TaskType, publish_task_with_priority},
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    user::{
+        entity::phone_verification_token,
+        repository::phone_verification_repository,
+        service::verification_service::{self, OTP_EXPIRY_MINUTES},
+    },
+};
+
+/// Text a one-time code to the phone number on the current user's profile,
+/// replacing any code sent before
+pub async fn execute(context: &Context) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let phone = current_user
+        .phone
+        .as_deref()
+        .map(str::trim)
+        .filter(|phone| !phone.is_empty())
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::AuthPhoneRequired,
+                t!("auth.phone_required", locale = &context.locale).to_string(),
+            )
+        })?;
+    if current_user.phone_verified_at.is_some() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::AuthPhoneAlreadyVerified,
+            t!("auth.phone_already_verified", locale = &context.locale).to_string(),
+        ));
+    }
+
+    if context.producer.is_none() {
+        tracing::error!("Message producer not available. Cannot send verification SMS.");
+        return Err(ErrorDTO::from_code(
+            ErrorCode::SmsUnavailable,
+            t!("sms.service_unavailable", locale = &context.locale).to_string(),
+        ));
+    }
+
+    phone_verification_repository::delete_by_user_id(context, current_user.id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let (otp, expires_at) = verification_service::issue_otp(context);
+    phone_verification_repository::create(
+        context,
+        phone_verification_token::ActiveModel {
+            user_id: Set(current_user.id),
+            phone: Set(phone.to_string()),
+            token: Set(otp.clone()),
+            retry_count: Set(0),
+            expires_at: Set(expires_at),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    publish_task_with_priority(
+        context,
+        TaskType::SendSms {
+            to: phone.to_string(),
+            body: t!(
+                "auth.phone_verification_sms",
+                otp = otp,
+                minutes = OTP_EXPIRY_MINUTES,
+                locale = &context.locale
+            )
+            .to_string(),
+        },
+        TaskPriority::High,
+        Some(MessageType::Tasks.as_ref()),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to publish verification SMS task: {}", e);
+        ErrorDTO::from_code(
+            ErrorCode::SmsUnavailable,
+            t!("sms.service_unavailable", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
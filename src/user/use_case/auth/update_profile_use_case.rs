@@ -5,12 +5,19 @@ use sea_orm::Set;
 use crate::{
     core::{
         context::Context,
-        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        dto::{
+            datetime::parse_timezone, error_code::ErrorCode, error_dto::ErrorDTO,
+            response_dto::ResponseDTO,
+        },
+        event::DomainEvent,
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+        translation::locale::{available_locales, supported_locale},
     },
     user::{
         dto::auth_dto::{ProfileDTO, UpdateProfileDTO},
         entity::user,
         repository::user_repository,
+        service::user_service,
     },
 };
 
@@ -21,8 +28,8 @@ pub async fn execute(
 ) -> Result<ResponseDTO<ProfileDTO>, ErrorDTO> {
     // Get current user from context
     let current_user = context.user.as_ref().ok_or_else(|| {
-        ErrorDTO::new(
-            StatusCode::UNAUTHORIZED,
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
             t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
         )
     })?;
@@ -31,11 +38,51 @@ pub async fn execute(
     let mut user: user::ActiveModel = current_user.clone().into();
 
     // Only update fields that were provided
-    for field in fields {
+    for field in &fields {
         match field.as_str() {
             "first_name" => user.first_name = Set(dto.first_name.clone()),
             "last_name" => user.last_name = Set(dto.last_name.clone()),
-            "phone" => user.phone = Set(dto.phone.clone()),
+            "phone" => {
+                // A new number has to be verified again
+                if dto.phone != current_user.phone {
+                    user.phone_verified_at = Set(None);
+                }
+                user.phone = Set(dto.phone.clone());
+            }
+            "locale" => {
+                let locale = match &dto.locale {
+                    Some(locale) => Some(supported_locale(locale).ok_or_else(|| {
+                        ErrorDTO::from_code(
+                            ErrorCode::UserLanguageUnsupported,
+                            t!(
+                                "language.unsupported",
+                                locale = &context.locale,
+                                requested = locale,
+                                available = available_locales().join(", ")
+                            )
+                            .to_string(),
+                        )
+                    })?),
+                    None => None,
+                };
+                user.locale = Set(locale);
+            }
+            "timezone" => {
+                if let Some(timezone) = &dto.timezone
+                    && parse_timezone(timezone).is_none()
+                {
+                    return Err(ErrorDTO::from_code(
+                        ErrorCode::UserTimezoneInvalid,
+                        t!(
+                            "user.invalid_timezone",
+                            locale = &context.locale,
+                            timezone = timezone
+                        )
+                        .to_string(),
+                    ));
+                }
+                user.timezone = Set(dto.timezone.clone());
+            }
             _ => {}
         }
     }
@@ -45,8 +92,17 @@ pub async fn execute(
         .await
         .map_err(ErrorDTO::map_internal_error)?;
 
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+
+    context
+        .emit(DomainEvent::ProfileUpdated {
+            user_id: updated_user.id,
+            fields,
+        })
+        .await;
+
     // Convert to ProfileDTO
-    let profile_dto = ProfileDTO::from(updated_user);
+    let profile_dto = user_service::to_profile_dto(context, updated_user).await?;
 
     Ok(ResponseDTO::new(StatusCode::OK, profile_dto))
 }
@@ -0,0 +1,59 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+    },
+    user::{
+        dto::auth_dto::VerifyEmailDTO,
+        entity::user,
+        repository::{email_verification_repository, user_repository},
+    },
+};
+
+/// Mark the email of the account the verification link `token` was sent to as verified.
+/// Each link works once.
+pub async fn execute(context: &Context, dto: VerifyEmailDTO) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let invalid_link = || {
+        ErrorDTO::from_code(
+            ErrorCode::AuthEmailVerificationInvalid,
+            t!("auth.email_verification_invalid", locale = &context.locale).to_string(),
+        )
+    };
+
+    let verification_token =
+        email_verification_repository::find_unexpired_by_token(context, &dto.token)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?
+            .ok_or_else(invalid_link)?;
+    let found_user = user_repository::find_by_id(context, verification_token.user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(invalid_link)?;
+
+    email_verification_repository::delete_by_user_id(context, found_user.id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    if found_user.verified_at.is_none() {
+        let user_id = found_user.id;
+        let mut user: user::ActiveModel = found_user.into();
+        user.verified_at = Set(Some(Utc::now().naive_utc()));
+        user_repository::update(context, user)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+
+        invalidate_cached_responses(context, USER_CACHE_TAG);
+
+        tracing::info!("Email verified for user_id: {}", user_id);
+        context.emit(DomainEvent::EmailVerified { user_id }).await;
+    }
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -0,0 +1,22 @@
+use axum::http::StatusCode;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    user::{dto::auth_dto::ResetLinkDTO, service::auth_service},
+};
+
+/// Check a password reset link before asking for the new password. The link stays usable.
+pub async fn execute(
+    context: &Context,
+    token: &str,
+) -> Result<ResponseDTO<ResetLinkDTO>, ErrorDTO> {
+    let user = auth_service::find_password_reset_link_user(context, token).await?;
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        ResetLinkDTO { email: user.email },
+    ))
+}
@@ -0,0 +1,63 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        validation::Validate,
+    },
+    user::{
+        dto::user_email_dto::{AddUserEmailDTO, UserEmailDTO},
+        entity::user_email,
+        repository::user_email_repository,
+        service::{user_email_service, user_service},
+    },
+};
+
+/// Add a secondary address to the current user and email it a code to verify it with.
+/// An address another user added without verifying it is taken over, since only its owner
+/// can verify it.
+pub async fn execute(
+    context: &Context,
+    dto: AddUserEmailDTO,
+) -> Result<ResponseDTO<UserEmailDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    dto.validate(&context.locale)?;
+
+    let email = dto.email.trim();
+    user_service::validate_unique_email(context, email, None).await?;
+    if let Some(pending) = user_email_repository::find_by_email(context, email)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+    {
+        user_email_repository::delete_by_id(context, pending.id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+    }
+
+    let user_email = user_email_repository::create(
+        context,
+        user_email::ActiveModel {
+            user_id: Set(current_user.id),
+            email: Set(email.to_string()),
+            receives_notifications: Set(dto.receives_notifications),
+            retry_count: Set(0),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+    let user_email = user_email_service::send_verification_code(context, user_email).await?;
+
+    Ok(ResponseDTO::new(
+        StatusCode::CREATED,
+        UserEmailDTO::from(user_email),
+    ))
+}
@@ -0,0 +1,106 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    user::{
+        dto::user_email_dto::{ConfirmUserEmailDTO, UserEmailDTO},
+        entity::user_email,
+        repository::{user_email_repository, user_repository},
+        service::{user_email_service, verification_service},
+    },
+};
+
+/// Mark secondary address `id` of the current user as verified when `otp` matches the code
+/// sent to it
+pub async fn execute(
+    context: &Context,
+    id: i32,
+    dto: ConfirmUserEmailDTO,
+) -> Result<ResponseDTO<UserEmailDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let user_email = user_email_service::read_own(context, current_user.id, id).await?;
+    if user_email.verified_at.is_some() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::UserEmailAlreadyVerified,
+            t!("user.email_already_verified", locale = &context.locale).to_string(),
+        ));
+    }
+
+    let invalid_otp = || {
+        ErrorDTO::from_code(
+            ErrorCode::AuthInvalidOtp,
+            t!("auth.invalid_email_otp", locale = &context.locale).to_string(),
+        )
+    };
+    let (Some(token), Some(expires_at)) = (&user_email.token, user_email.token_expires_at) else {
+        return Err(invalid_otp());
+    };
+
+    if let Err(failure) =
+        verification_service::check_otp(token, user_email.retry_count, expires_at, &dto.otp)
+    {
+        let retry_count = user_email.retry_count + 1;
+        let discards_code = failure.discards_code();
+        let mut user_email: user_email::ActiveModel = user_email.into();
+        if discards_code {
+            user_email.token = Set(None);
+            user_email.token_expires_at = Set(None);
+            user_email.retry_count = Set(0);
+        } else {
+            user_email.retry_count = Set(retry_count);
+        }
+        user_email_repository::update(context, user_email)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+        // The attempt or the discarded code has to outlive the failed request
+        return Err(failure.into_error(context, invalid_otp()).keep_changes());
+    }
+
+    // Someone may have signed up with the address since it was added here
+    if user_repository::find_by_email_with_deleted(context, &user_email.email)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .is_some()
+    {
+        user_email_repository::delete_by_id(context, user_email.id)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+        return Err(ErrorDTO::from_code(
+            ErrorCode::UserEmailTaken,
+            t!("user.email_already_in_use", locale = &context.locale).to_string(),
+        )
+        .keep_changes());
+    }
+
+    let mut user_email: user_email::ActiveModel = user_email.into();
+    user_email.verified_at = Set(Some(Utc::now().naive_utc()));
+    user_email.token = Set(None);
+    user_email.token_expires_at = Set(None);
+    user_email.retry_count = Set(0);
+    let user_email = user_email_repository::update(context, user_email)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    tracing::info!(
+        "Secondary email {} verified for user_id: {}",
+        user_email.id,
+        current_user.id
+    );
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        UserEmailDTO::from(user_email),
+    ))
+}
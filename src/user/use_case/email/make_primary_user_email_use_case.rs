@@ -0,0 +1,58 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+    },
+    user::{
+        dto::auth_dto::ProfileDTO,
+        entity::{user, user_email},
+        repository::{user_email_repository, user_repository},
+        service::{user_email_service, user_service},
+    },
+};
+
+/// Make verified secondary address `id` the primary address of the current user. The
+/// primary address it replaces becomes a verified secondary address in its place.
+pub async fn execute(context: &Context, id: i32) -> Result<ResponseDTO<ProfileDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let secondary = user_email_service::read_own(context, current_user.id, id).await?;
+    if secondary.verified_at.is_none() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::UserEmailNotVerified,
+            t!("user.email_not_verified", locale = &context.locale).to_string(),
+        ));
+    }
+
+    let new_primary = secondary.email.clone();
+    let new_primary_verified_at = secondary.verified_at;
+    let mut secondary: user_email::ActiveModel = secondary.into();
+    secondary.email = Set(current_user.email.clone());
+    user_email_repository::update(context, secondary)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    let mut user: user::ActiveModel = current_user.clone().into();
+    user.email = Set(new_primary);
+    user.verified_at = Set(new_primary_verified_at);
+    let updated_user = user_repository::update(context, user)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        user_service::to_profile_dto(context, updated_user).await?,
+    ))
+}
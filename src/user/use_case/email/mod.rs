@@ -0,0 +1,7 @@
+pub mod add_user_email_use_case;
+pub mod confirm_user_email_use_case;
+pub mod delete_user_email_use_case;
+pub mod make_primary_user_email_use_case;
+pub mod search_user_email_use_case;
+pub mod send_user_email_verification_use_case;
+pub mod update_user_email_use_case;
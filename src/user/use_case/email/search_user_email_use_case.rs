@@ -0,0 +1,37 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    user::{
+        dto::user_email_dto::{UserEmailDTO, UserEmailListDTO},
+        repository::user_email_repository,
+    },
+};
+
+pub async fn execute(context: &Context) -> Result<ResponseDTO<UserEmailListDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let items: Vec<UserEmailDTO> = user_email_repository::find_by_user_id(context, current_user.id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .into_iter()
+        .map(UserEmailDTO::from)
+        .collect();
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        UserEmailListDTO {
+            count: items.len(),
+            items,
+        },
+    ))
+}
@@ -0,0 +1,32 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    user::service::user_email_service,
+};
+
+/// Email a new code to secondary address `id` of the current user
+pub async fn execute(context: &Context, id: i32) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let user_email = user_email_service::read_own(context, current_user.id, id).await?;
+    if user_email.verified_at.is_some() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::UserEmailAlreadyVerified,
+            t!("user.email_already_verified", locale = &context.locale).to_string(),
+        ));
+    }
+
+    user_email_service::send_verification_code(context, user_email).await?;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -0,0 +1,42 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+use sea_orm::Set;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    user::{
+        dto::user_email_dto::{UpdateUserEmailDTO, UserEmailDTO},
+        entity::user_email,
+        repository::user_email_repository,
+        service::user_email_service,
+    },
+};
+
+/// Choose whether notification emails are also sent to secondary address `id`
+pub async fn execute(
+    context: &Context,
+    id: i32,
+    dto: UpdateUserEmailDTO,
+) -> Result<ResponseDTO<UserEmailDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    let user_email = user_email_service::read_own(context, current_user.id, id).await?;
+    let mut user_email: user_email::ActiveModel = user_email.into();
+    user_email.receives_notifications = Set(dto.receives_notifications);
+    let user_email = user_email_repository::update(context, user_email)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    Ok(ResponseDTO::new(
+        StatusCode::OK,
+        UserEmailDTO::from(user_email),
+    ))
+}
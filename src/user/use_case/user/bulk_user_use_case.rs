@@ -0,0 +1,83 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    config::setting::{MessageType, Setting},
+    core::{
+        r#async::{TaskClaim, TaskType, publish_task},
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+    },
+    user::{
+        dto::bulk_user_dto::{BulkUserRequestDTO, BulkUserResponseDTO},
+        service::bulk_user_service,
+    },
+};
+
+pub async fn execute(
+    context: &Context,
+    setting: &Setting,
+    dto: BulkUserRequestDTO,
+) -> Result<ResponseDTO<BulkUserResponseDTO>, ErrorDTO> {
+    context.require_permission("user.bulk")?;
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+
+    if dto.operations.is_empty() {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::UserBulkEmpty,
+            t!("user.bulk_empty", locale = &context.locale).to_string(),
+        ));
+    }
+
+    if dto.operations.len() <= setting.bulk_sync_limit {
+        let report = bulk_user_service::apply_in_savepoints(context, &dto.operations).await?;
+        return Ok(ResponseDTO::new(
+            StatusCode::OK,
+            BulkUserResponseDTO {
+                task_id: None,
+                report: Some(report),
+            },
+        ));
+    }
+
+    // Large batches would hold the request open too long, the worker reports on the task channel
+    if context.producer.is_none() {
+        return Err(ErrorDTO::map_internal_error(anyhow::anyhow!(
+            "Producer not available"
+        )));
+    }
+    // Resubmitting the same batch within the dedup window answers with the task already queued
+    let claim = TaskClaim::new(context, "ProcessBulkUserOperations", &dto.operations).await?;
+    if !claim.duplicate
+        && let Err(e) = publish_task(
+            context,
+            TaskType::ProcessBulkUserOperations {
+                task_id: claim.task_id.clone(),
+                actor_id: current_user.id,
+                locale: context.locale.clone(),
+                operations: dto.operations,
+            },
+            Some(MessageType::Tasks.as_ref()),
+        )
+        .await
+    {
+        claim.release(context).await;
+        return Err(ErrorDTO::map_internal_error(anyhow::anyhow!(
+            "Failed to publish bulk task: {}",
+            e
+        )));
+    }
+
+    Ok(ResponseDTO::new(
+        StatusCode::ACCEPTED,
+        BulkUserResponseDTO {
+            task_id: Some(claim.task_id),
+            report: None,
+        },
+    ))
+}
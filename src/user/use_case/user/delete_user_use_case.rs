@@ -2,15 +2,33 @@ use crate::{
     core::{
         context::Context,
         dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+        policy::{Action, Resource},
     },
-    user::repository::user_repository,
+    user::repository::{refresh_token_repository, user_repository},
 };
 use axum::http::StatusCode;
 
+/// Soft delete `user_id` and sign it out everywhere; admins can restore it later
 pub async fn execute(context: &Context, user_id: i32) -> Result<ResponseDTO<()>, ErrorDTO> {
+    context.authorize(Action::Delete, &Resource::user(user_id))?;
+
     user_repository::delete_by_id(context, user_id)
         .await
         .map_err(ErrorDTO::map_internal_error)?;
+    refresh_token_repository::delete_by_user_id(context, user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+
+    context
+        .emit(DomainEvent::UserDeleted {
+            user_id,
+            deleted_by: context.user.as_ref().map(|user| user.id),
+        })
+        .await;
 
     Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
 }
@@ -0,0 +1,45 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+    },
+    user::repository::impersonation_repository,
+};
+
+/// End the impersonations of `user_id` by the admin making the request, revoking their
+/// tokens. Works with the admin's own token or with the impersonation token itself.
+pub async fn execute(context: &Context, user_id: i32) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    let admin_id = match context.impersonator {
+        Some(admin_id) => admin_id,
+        None => {
+            context.require_permission("user.impersonate")?;
+            current_user.id
+        }
+    };
+
+    let ended =
+        impersonation_repository::end_active(context, admin_id, user_id, Utc::now().naive_utc())
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+    if !ended.is_empty() {
+        context
+            .emit(DomainEvent::ImpersonationEnded {
+                user_id,
+                impersonated_by: admin_id,
+            })
+            .await;
+    }
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
@@ -1,7 +1,8 @@
 use crate::{
     core::{
         context::Context,
-        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        policy::{Action, Resource},
     },
     user::{dto::user_dto::UserDTO, repository::user_repository, service::user_service},
 };
@@ -9,6 +10,8 @@ use axum::http::StatusCode;
 use rust_i18n::t;
 
 pub async fn execute(context: &Context, user_id: i32) -> Result<ResponseDTO<UserDTO>, ErrorDTO> {
+    context.authorize(Action::Read, &Resource::user(user_id))?;
+
     let user = user_repository::find_by_id(context, user_id)
         .await
         .map_err(ErrorDTO::map_internal_error)?;
@@ -19,8 +22,8 @@ pub async fn execute(context: &Context, user_id: i32) -> Result<ResponseDTO<User
 
             Ok(ResponseDTO::new(StatusCode::OK, user_dto))
         }
-        None => Err(ErrorDTO::new(
-            StatusCode::NOT_FOUND,
+        None => Err(ErrorDTO::from_code(
+            ErrorCode::UserNotFound,
             t!(
                 "user.not_found_with_id",
                 id = user_id,
@@ -0,0 +1,53 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+    },
+    user::repository::user_repository,
+};
+
+/// Remove a user for good, deleted or not, along with its tokens, sessions and emails
+pub async fn execute(context: &Context, user_id: i32) -> Result<ResponseDTO<()>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    context.require_permission("user.hard_delete")?;
+
+    user_repository::find_by_id_with_deleted(context, user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::UserNotFound,
+                t!(
+                    "user.not_found_with_id",
+                    id = user_id,
+                    locale = &context.locale
+                )
+                .to_string(),
+            )
+        })?;
+
+    user_repository::hard_delete_by_id(context, user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+
+    context
+        .emit(DomainEvent::UserHardDeleted {
+            user_id,
+            deleted_by: current_user.id,
+        })
+        .await;
+
+    Ok(ResponseDTO::new(StatusCode::NO_CONTENT, ()))
+}
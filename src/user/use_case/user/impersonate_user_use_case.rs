@@ -0,0 +1,92 @@
+use axum::http::StatusCode;
+use chrono::DateTime;
+use rust_i18n::t;
+use sea_orm::ActiveValue::Set;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+    },
+    user::{
+        dto::auth_dto::ImpersonationDTO,
+        entity::{impersonation, sea_orm_active_enums::UserRole},
+        repository::{impersonation_repository, permission_repository, user_repository},
+        service::auth_service,
+    },
+};
+
+/// Issue the current user, holding `user.impersonate`, a short-lived access token acting as
+/// `user_id`. Admins, deactivated users and users holding a permission the current user
+/// lacks can't be impersonated, and no refresh token is issued.
+pub async fn execute(
+    context: &Context,
+    user_id: i32,
+) -> Result<ResponseDTO<ImpersonationDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    context.require_permission("user.impersonate")?;
+
+    let user = user_repository::find_by_id(context, user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::UserNotFound,
+                t!("user.not_found", locale = &context.locale).to_string(),
+            )
+        })?;
+    let permissions = permission_repository::find_names_by_user_id(context, user.id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?;
+    if user.role == UserRole::Admin
+        || user.deactivated_at.is_some()
+        || permissions
+            .iter()
+            .any(|permission| !context.has_permission(permission))
+    {
+        return Err(ErrorDTO::from_code(
+            ErrorCode::UserImpersonationForbidden,
+            t!("user.impersonation_forbidden", locale = &context.locale).to_string(),
+        ));
+    }
+
+    let (access, claims) =
+        auth_service::generate_impersonation_token(context, current_user.id, user.id).await?;
+    let expires_at = DateTime::from_timestamp(claims.exp as i64, 0)
+        .unwrap_or_default()
+        .naive_utc();
+    impersonation_repository::create(
+        context,
+        impersonation::ActiveModel {
+            admin_id: Set(current_user.id),
+            user_id: Set(user.id),
+            token_id: Set(claims.jti),
+            expires_at: Set(expires_at),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    context
+        .emit(DomainEvent::ImpersonationStarted {
+            user_id: user.id,
+            impersonated_by: current_user.id,
+        })
+        .await;
+
+    Ok(ResponseDTO::new(
+        StatusCode::CREATED,
+        ImpersonationDTO {
+            access,
+            user_id: user.id,
+            expires_at,
+        },
+    ))
+}
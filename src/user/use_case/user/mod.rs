@@ -1,6 +1,11 @@
+pub mod bulk_user_use_case;
 pub mod create_user_use_case;
 pub mod delete_user_use_case;
+pub mod end_impersonation_use_case;
 pub mod get_user_use_case;
+pub mod hard_delete_user_use_case;
+pub mod impersonate_user_use_case;
+pub mod restore_user_use_case;
 pub mod search_user_use_case;
 pub mod sync_user_data_use_case;
 pub mod update_user_use_case;
@@ -0,0 +1,58 @@
+use axum::http::StatusCode;
+use rust_i18n::t;
+
+use crate::{
+    core::{
+        context::Context,
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+    },
+    user::{dto::user_dto::UserDTO, repository::user_repository, service::user_service},
+};
+
+/// Bring back a soft deleted user. Its sessions stay revoked, so it signs in again.
+pub async fn execute(context: &Context, user_id: i32) -> Result<ResponseDTO<UserDTO>, ErrorDTO> {
+    let current_user = context.user.as_ref().ok_or_else(|| {
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
+            t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
+        )
+    })?;
+    context.require_permission("user.restore")?;
+
+    let user = user_repository::find_by_id_with_deleted(context, user_id)
+        .await
+        .map_err(ErrorDTO::map_internal_error)?
+        .ok_or_else(|| {
+            ErrorDTO::from_code(
+                ErrorCode::UserNotFound,
+                t!(
+                    "user.not_found_with_id",
+                    id = user_id,
+                    locale = &context.locale
+                )
+                .to_string(),
+            )
+        })?;
+
+    let user = match user.deleted_at {
+        Some(_) => {
+            let user = user_repository::restore(context, user.into())
+                .await
+                .map_err(ErrorDTO::map_internal_error)?;
+            invalidate_cached_responses(context, USER_CACHE_TAG);
+            context
+                .emit(DomainEvent::UserRestored {
+                    user_id,
+                    restored_by: current_user.id,
+                })
+                .await;
+            user
+        }
+        None => user,
+    };
+    let user_dto = user_service::model_to_dto(context, &user).await?;
+
+    Ok(ResponseDTO::new(StatusCode::OK, user_dto))
+}
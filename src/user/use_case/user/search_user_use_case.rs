@@ -1,11 +1,14 @@
 use crate::{
     core::{
         context::Context,
+        db::query::{PageRequest, parse_order_by},
         dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        policy::{Action, Resource},
+        validation::Validate,
     },
     user::{
         dto::user_dto::{UserListDTO, UserSearchParamsDTO},
-        repository::user_repository::{self, UserOrderBy, UserSearchParams},
+        repository::user_repository::{self, UserOrderByField, UserSearchParams},
         service::user_service,
     },
 };
@@ -15,12 +18,12 @@ pub async fn execute(
     context: &Context,
     dto: UserSearchParamsDTO,
 ) -> Result<ResponseDTO<UserListDTO>, ErrorDTO> {
-    // Parse order_by string into OrderBy structs
-    let order_by_list = if let Some(order_by_str) = &dto.order_by {
-        UserOrderBy::parse_order_by_string(order_by_str)
-    } else {
-        Vec::new()
-    };
+    context.authorize(Action::List, &Resource::users())?;
+    dto.validate(&context.locale)?;
+
+    let page = PageRequest::new(dto.page, dto.page_size);
+    let order_by = parse_order_by::<UserOrderByField>(dto.order_by.as_deref())
+        .map_err(|e| e.into_error_dto(&context.locale))?;
 
     let (users, total_count) = user_repository::search(
         context,
@@ -28,13 +31,9 @@ pub async fn execute(
             email: dto.email.as_deref(),
             first_name: dto.first_name.as_deref(),
             last_name: dto.last_name.as_deref(),
-            page: dto.page,
-            page_size: dto.page_size,
-            order_by: if order_by_list.is_empty() {
-                None
-            } else {
-                Some(&order_by_list)
-            },
+            page: Some(page.page),
+            page_size: Some(page.page_size),
+            order_by: Some(&order_by),
             ..Default::default()
         },
     )
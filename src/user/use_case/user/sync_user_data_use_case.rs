@@ -1,8 +1,9 @@
 use crate::config::app::AppState;
-use crate::core::db::uow::new_transaction;
-use crate::core::dto::error_dto::ErrorDTO;
+use crate::config::drain::{DrainPhase, close_frame, draining_notice, next_phase};
+use crate::core::db::uow::read_only;
 use crate::core::dto::response_dto::ResponseDTO;
 use crate::core::dto::util::ToJson;
+use crate::core::dto::{error_code::ErrorCode, error_dto::ErrorDTO};
 use crate::user::dto::user_dto::UserDTO;
 use crate::user::entity::user;
 use crate::user::repository::user_repository;
@@ -13,6 +14,7 @@ use axum::extract::ws::WebSocket;
 #[allow(unused_imports)]
 use axum::http::StatusCode;
 use rust_i18n::t;
+use serde_json::json;
 
 pub async fn execute(
     mut socket: WebSocket,
@@ -20,7 +22,31 @@ pub async fn execute(
     current_user: user::Model,
     locale: String,
 ) {
-    while let Some(msg) = socket.recv().await {
+    let mut drain = app_state.drain.subscribe();
+    loop {
+        let msg = tokio::select! {
+            msg = socket.recv() => msg,
+            phase = next_phase(&mut drain) => {
+                let frame = match phase {
+                    DrainPhase::Draining => Message::Text(
+                        serde_json::to_string(&draining_notice(json!({})))
+                            .unwrap_or_default()
+                            .into(),
+                    ),
+                    DrainPhase::Closing => close_frame(),
+                    DrainPhase::Serving => continue,
+                };
+                let closing = matches!(frame, Message::Close(_));
+                if socket.send(frame).await.is_err() || closing {
+                    break;
+                }
+                continue;
+            }
+        };
+        let Some(msg) = msg else {
+            break;
+        };
+
         match msg {
             Ok(Message::Text(text)) => {
                 tracing::info!("Received from client: {}", text);
@@ -69,14 +95,14 @@ pub async fn fetch_user_data(
     let user_id: i32 = match user_id.parse() {
         Ok(id) => id,
         Err(_) => {
-            return Err(ErrorDTO::new(
-                StatusCode::BAD_REQUEST,
+            return Err(ErrorDTO::from_code(
+                ErrorCode::UserIdInvalid,
                 t!("user.invalid_id_format", locale = &locale).to_string(),
             ));
         }
     };
 
-    new_transaction(
+    read_only(
         &app_state,
         Some(current_user.clone()),
         Some(locale),
@@ -91,8 +117,8 @@ pub async fn fetch_user_data(
                         let user_dto = user_service::model_to_dto(context, &user_model).await?;
                         Ok(ResponseDTO::new(StatusCode::OK, user_dto))
                     }
-                    Ok(None) => Err(ErrorDTO::new(
-                        StatusCode::NOT_FOUND,
+                    Ok(None) => Err(ErrorDTO::from_code(
+                        ErrorCode::UserNotFound,
                         t!("user.not_found", locale = &context.locale).to_string(),
                     )),
                     Err(e) => Err(e),
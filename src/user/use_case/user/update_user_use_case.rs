@@ -5,14 +5,17 @@ use sea_orm::Set;
 use crate::{
     core::{
         context::Context,
-        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
+        layer::response_cache_layer::{USER_CACHE_TAG, invalidate_cached_responses},
+        policy::{Action, Resource},
+        validation::Validate,
     },
-    pkg::password,
     user::{
         dto::user_dto::{UserDTO, UserUpdateDTO},
         entity::user,
         repository::user_repository,
-        service::user_service,
+        service::{auth_service, user_service},
     },
 };
 
@@ -22,37 +25,42 @@ pub async fn execute(
     dto: UserUpdateDTO,
     fields: Vec<String>,
 ) -> Result<ResponseDTO<UserDTO>, ErrorDTO> {
+    context.authorize(Action::Update, &Resource::user(id))?;
+    dto.validate(&context.locale)?;
+
     // First, find the existing user
     let existing_user = user_repository::find_by_id(context, id)
         .await
         .map_err(ErrorDTO::map_internal_error)?
         .ok_or_else(|| {
-            ErrorDTO::new(
-                StatusCode::NOT_FOUND,
+            ErrorDTO::from_code(
+                ErrorCode::UserNotFound,
                 t!("user.not_found", locale = &context.locale).to_string(),
             )
         })?;
 
     // Convert to ActiveModel
+    let previous_phone = existing_user.phone.clone();
+    let previous_email = existing_user.email.clone();
     let mut user_active: user::ActiveModel = existing_user.into();
 
     // Only update fields that were provided
-    for field in fields {
+    for field in &fields {
         match field.as_str() {
             "email" => {
                 if let Some(ref email) = dto.email {
                     // Validate email uniqueness before updating
                     user_service::validate_unique_email(context, email, Some(id)).await?;
                     user_active.email = Set(email.clone());
+                    // Nobody confirmed owning the new address yet
+                    if *email != previous_email {
+                        user_active.verified_at = Set(None);
+                    }
                 }
             }
             "password" => {
                 if let Some(ref password) = dto.password {
-                    // Validate password strength before hashing
-                    password::validate_password_strength(password)
-                        .map_err(|e| ErrorDTO::new(StatusCode::BAD_REQUEST, e.to_string()))?;
-
-                    let hashed_password = password::hash_password_string(password)
+                    let hashed_password = auth_service::hash_password(password)
                         .await
                         .map_err(ErrorDTO::map_internal_error)?;
                     user_active.password = Set(hashed_password);
@@ -60,7 +68,13 @@ pub async fn execute(
             }
             "first_name" => user_active.first_name = Set(dto.first_name.clone()),
             "last_name" => user_active.last_name = Set(dto.last_name.clone()),
-            "phone" => user_active.phone = Set(dto.phone.clone()),
+            "phone" => {
+                // A new number has to be verified again
+                if dto.phone != previous_phone {
+                    user_active.phone_verified_at = Set(None);
+                }
+                user_active.phone = Set(dto.phone.clone());
+            }
             _ => {}
         }
     }
@@ -70,6 +84,15 @@ pub async fn execute(
         .await
         .map_err(ErrorDTO::map_internal_error)?;
 
+    invalidate_cached_responses(context, USER_CACHE_TAG);
+
+    context
+        .emit(DomainEvent::ProfileUpdated {
+            user_id: user_model.id,
+            fields,
+        })
+        .await;
+
     let user_dto = user_service::model_to_dto(context, &user_model).await?;
 
     Ok(ResponseDTO::new(StatusCode::OK, user_dto))
@@ -1,14 +1,25 @@
 use axum::http::StatusCode;
+use base64::{Engine, engine::general_purpose::STANDARD};
 use rust_i18n::t;
-use uuid::Uuid;
+use sea_orm::ActiveValue::Set;
+use std::path::Path;
 
 use crate::{
-    config::setting::MessageType,
+    config::setting::{MessageType, Setting},
     core::{
-        r#async::{TaskType, publish_task},
+        r#async::{TaskClaim, TaskType, publish_task},
         context::Context,
-        dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        dto::{error_code::ErrorCode, error_dto::ErrorDTO, response_dto::ResponseDTO},
+        event::DomainEvent,
     },
+    file::{
+        entity::{
+            file,
+            sea_orm_active_enums::{FileStatus, ModerationStatus},
+        },
+        repository::file_repository,
+    },
+    pkg::storage::ObjectStorage,
     user::{
         dto::avatar_dto::{UploadAvatarDTO, UploadAvatarResponseDTO},
         repository::user_repository,
@@ -21,8 +32,8 @@ pub async fn execute(
     locale: &str,
 ) -> Result<ResponseDTO<UploadAvatarResponseDTO>, ErrorDTO> {
     let current_user = context.user.as_ref().ok_or_else(|| {
-        ErrorDTO::new(
-            StatusCode::UNAUTHORIZED,
+        ErrorDTO::from_code(
+            ErrorCode::AuthNotAuthenticated,
             t!("auth.user_not_authenticated", locale = &context.locale).to_string(),
         )
     })?;
@@ -31,41 +42,47 @@ pub async fn execute(
         .await
         .map_err(ErrorDTO::map_internal_error)?
         .ok_or_else(|| {
-            ErrorDTO::new(
-                StatusCode::NOT_FOUND,
+            ErrorDTO::from_code(
+                ErrorCode::UserNotFound,
                 t!("user.not_found", locale = &context.locale).to_string(),
             )
         })?;
 
-    // Generate random task_id (UUID v4)
-    let task_id = Uuid::new_v4().to_string();
+    if context.producer.is_none() {
+        return Err(ErrorDTO::map_internal_error(anyhow::anyhow!(
+            "Producer not available"
+        )));
+    }
 
-    match &context.producer {
-        Some(producer) => {
-            publish_task(
-                producer.as_ref().as_ref(),
-                TaskType::ProcessAvatarUpload {
-                    task_id: task_id.clone(),
-                    user_id: user.id,
-                    file_name: request.file_name.clone(),
-                    locale: locale.to_string(),
-                },
-                Some(MessageType::Tasks.as_ref()),
+    let content = request
+        .content
+        .as_deref()
+        .map(|content| STANDARD.decode(content))
+        .transpose()
+        .map_err(|_| {
+            ErrorDTO::from_code(
+                ErrorCode::FileContentInvalid,
+                t!("avatar_upload.invalid_content", locale = &context.locale).to_string(),
             )
-            .await
-            .map_err(|e| {
-                ErrorDTO::map_internal_error(anyhow::anyhow!(
-                    "Failed to publish upload task: {}",
-                    e
-                ))
-            })?;
-        }
-        None => {
-            return Err(ErrorDTO::map_internal_error(anyhow::anyhow!(
-                "Producer not available"
-            )));
-        }
+        })?;
+
+    // Repeating the upload within the dedup window answers with the task already started
+    let claim = TaskClaim::new(context, "ProcessAvatarUpload", &request).await?;
+    if !claim.duplicate
+        && let Err(e) = start_upload(
+            context,
+            user.id,
+            &request.file_name,
+            content,
+            locale,
+            &claim.task_id,
+        )
+        .await
+    {
+        claim.release(context).await;
+        return Err(e);
     }
+    let task_id = claim.task_id;
 
     Ok(ResponseDTO::new(
         StatusCode::ACCEPTED,
@@ -78,3 +95,76 @@ pub async fn execute(
         },
     ))
 }
+
+/// Store the uploaded content, record its file and enqueue the task processing it
+async fn start_upload(
+    context: &Context,
+    user_id: i32,
+    requested_name: &str,
+    content: Option<Vec<u8>>,
+    locale: &str,
+    task_id: &str,
+) -> Result<(), ErrorDTO> {
+    // Only keep the final path component so clients can't pick arbitrary storage keys
+    let file_name = Path::new(requested_name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("avatar")
+        .to_string();
+    let key = format!("avatars/{}/{}/{}", user_id, task_id, file_name);
+    let setting = Setting::new();
+
+    let size = content.as_ref().map_or(0, |content| content.len() as i64);
+    if let Some(content) = content {
+        setting
+            .get_storage()
+            .put(&key, &content)
+            .await
+            .map_err(ErrorDTO::map_internal_error)?;
+    }
+
+    let file = file_repository::create(
+        context,
+        file::ActiveModel {
+            user_id: Set(user_id),
+            key: Set(key),
+            name: Set(file_name),
+            size: Set(size),
+            status: Set(FileStatus::Pending),
+            // Approved avatars replace the profile's once processed, pending ones wait for an admin
+            moderation_status: Set(Some(if setting.avatar_auto_approve {
+                ModerationStatus::Approved
+            } else {
+                ModerationStatus::Pending
+            })),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(ErrorDTO::map_internal_error)?;
+
+    publish_task(
+        context,
+        TaskType::ProcessAvatarUpload {
+            task_id: task_id.to_string(),
+            user_id,
+            file_name: requested_name.to_string(),
+            locale: locale.to_string(),
+            file_id: Some(file.id),
+        },
+        Some(MessageType::Tasks.as_ref()),
+    )
+    .await
+    .map_err(|e| {
+        ErrorDTO::map_internal_error(anyhow::anyhow!("Failed to publish upload task: {}", e))
+    })?;
+
+    context
+        .emit(DomainEvent::AvatarUpdated {
+            user_id,
+            file_id: file.id,
+        })
+        .await;
+
+    Ok(())
+}
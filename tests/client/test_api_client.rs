@@ -0,0 +1,198 @@
+use my_axum::{
+    client::{ApiClient, ClientError},
+    common::dto::task_dto::TaskPollParamsDTO,
+    core::context::Context,
+    pkg::broadcast::websocket::{BroadcastMessage, broadcast_to_task},
+    user::dto::{
+        auth_dto::{LoginDTO, RefreshTokenDTO, RegisterDTO, UpdateProfileDTO},
+        user_dto::{UserCreateDTO, UserSearchParamsDTO},
+    },
+};
+use reqwest::StatusCode;
+use std::sync::Arc;
+
+use crate::setup::{
+    app::TestApp,
+    factory::{DEFAULT_PASSWORD, UserFactory},
+};
+
+fn register_dto(email: &str) -> RegisterDTO {
+    RegisterDTO {
+        email: email.to_string(),
+        password: DEFAULT_PASSWORD.to_string(),
+        first_name: Some("John".to_string()),
+        last_name: None,
+        phone: None,
+    }
+}
+
+#[tokio::test]
+async fn test_register_and_manage_profile() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let client = test_app.api_client();
+
+    // Act
+    let tokens = client
+        .register(&register_dto("john@example.com"))
+        .await
+        .unwrap();
+    let client = client.with_access_token(tokens.access);
+    let profile = client
+        .update_profile(&UpdateProfileDTO {
+            last_name: Some("Doe".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(profile.email, "john@example.com");
+    assert_eq!(profile.first_name.as_deref(), Some("John"));
+    assert_eq!(profile.last_name.as_deref(), Some("Doe"));
+    assert_eq!(client.get_profile().await.unwrap().id, profile.id);
+}
+
+#[tokio::test]
+async fn test_admin_searches_created_users() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let context = Context::builder(Arc::new(txn)).build();
+    let admin = UserFactory::admin().create(&context).await.unwrap();
+    context.commit().await.unwrap();
+
+    let tokens = test_app
+        .api_client()
+        .login(&LoginDTO {
+            email: admin.email,
+            password: DEFAULT_PASSWORD.to_string(),
+        })
+        .await
+        .unwrap();
+    let client = test_app.api_client().with_access_token(tokens.access);
+
+    // Act
+    let created = client
+        .create_user(&UserCreateDTO {
+            email: "jane@example.com".to_string(),
+            password: DEFAULT_PASSWORD.to_string(),
+            first_name: Some("Jane".to_string()),
+            last_name: None,
+            phone: None,
+        })
+        .await
+        .unwrap();
+    let users = client
+        .search_users(&UserSearchParamsDTO {
+            email: Some("jane@".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(users.count, 1);
+    assert_eq!(users.items[0].id, created.id);
+    assert_eq!(
+        client.get_user(created.id).await.unwrap().email,
+        created.email
+    );
+}
+
+#[tokio::test]
+async fn test_error_responses_are_typed() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let client = test_app.api_client();
+
+    // Act
+    let login = client
+        .login(&LoginDTO {
+            email: "nobody@example.com".to_string(),
+            password: DEFAULT_PASSWORD.to_string(),
+        })
+        .await;
+    let profile = client.get_profile().await;
+
+    // Assert
+    match login.unwrap_err() {
+        ClientError::Api { status, code, .. } => {
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+            assert_eq!(code, "AUTH_INVALID_CREDENTIALS");
+        }
+        other => panic!("Unexpected error: {}", other),
+    }
+    let profile = profile.unwrap_err();
+    assert_eq!(profile.status(), Some(StatusCode::UNAUTHORIZED));
+    assert_eq!(profile.code(), Some("AUTH_TOKEN_MISSING"));
+}
+
+#[tokio::test]
+async fn test_logout_revokes_the_refresh_token() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let client = test_app.api_client();
+    let tokens = client
+        .register(&register_dto("john@example.com"))
+        .await
+        .unwrap();
+
+    // Act
+    client.logout(&tokens.refresh).await.unwrap();
+    let refreshed = client
+        .refresh_token(&RefreshTokenDTO {
+            refresh_token: Some(tokens.refresh),
+        })
+        .await;
+
+    // Assert
+    assert_eq!(
+        refreshed.unwrap_err().status(),
+        Some(StatusCode::UNAUTHORIZED)
+    );
+}
+
+#[test]
+fn test_task_progress_url_uses_websocket_scheme() {
+    assert_eq!(
+        ApiClient::new("http://localhost:8000/").task_progress_url("abc"),
+        "ws://localhost:8000/ws/v1/task/abc/"
+    );
+    assert_eq!(
+        ApiClient::new("https://api.example.com").task_progress_url("abc"),
+        "wss://api.example.com/ws/v1/task/abc/"
+    );
+}
+
+#[tokio::test]
+async fn test_poll_task_progress() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let client = test_app.api_client();
+    let tokens = client
+        .register(&register_dto("poller@example.com"))
+        .await
+        .unwrap();
+    let client = client.with_access_token(tokens.access);
+    let task_id = uuid::Uuid::new_v4().to_string();
+    broadcast_to_task(
+        &task_id,
+        BroadcastMessage {
+            event_type: "progress".to_string(),
+            data: serde_json::json!({"task_id": task_id, "percent": 40}),
+        },
+    )
+    .await;
+
+    // Act
+    let poll = client
+        .poll_task_progress(&task_id, &TaskPollParamsDTO::default())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(poll.last_seq, 1);
+    assert_eq!(poll.messages.len(), 1);
+    assert_eq!(poll.messages[0].data["percent"], 40);
+}
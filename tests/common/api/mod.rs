@@ -1,3 +1,13 @@
+mod test_audit_log_api;
+mod test_broadcast_api;
+mod test_dead_letter_api;
+mod test_deprecation_api;
+mod test_drain_api;
+mod test_frontend_api;
+mod test_health_api;
 mod test_mcp_api;
 mod test_runbook_api;
+mod test_stats_api;
 mod test_task_api;
+mod test_task_history_api;
+mod test_worker_api;
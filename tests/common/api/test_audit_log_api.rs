@@ -0,0 +1,300 @@
+use std::sync::Arc;
+
+use my_axum::{
+    common::entity::audit_log,
+    core::context::Context,
+    file::{
+        entity::{file, sea_orm_active_enums::FileStatus},
+        repository::file_repository,
+    },
+    user::repository::user_repository,
+};
+use reqwest::StatusCode;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set};
+use serde_json::{Value, json};
+
+use crate::setup::{
+    app::TestApp,
+    fixture::{grant_permissions, login_admin_user, login_normal_user},
+};
+
+async fn access_token(test_app: &TestApp, admin: bool) -> String {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    context.commit().await.unwrap();
+    access_token
+}
+
+async fn record(test_app: &TestApp, actor: &str, action: &str, resource_id: &str) {
+    audit_log::ActiveModel {
+        actor: Set(actor.to_string()),
+        action: Set(action.to_string()),
+        resource_type: Set("user".to_string()),
+        resource_id: Set(Some(resource_id.to_string())),
+        payload: Set(json!({ "note": "=cmd, \"quoted\"" })),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    }
+    .insert(&test_app.db)
+    .await
+    .unwrap();
+}
+
+async fn get(test_app: &TestApp, access_token: &str, path: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!(
+            "http://{}/api/v1/admin/audit-logs/{}",
+            test_app.base_url, path
+        ))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_register_is_recorded_in_audit_log() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+
+    // Act
+    let response = reqwest::Client::new()
+        .post(format!(
+            "http://{}/api/v1/auth/register/",
+            test_app.base_url
+        ))
+        .json(&json!({
+            "email": "audited@example.com",
+            "password": "password123@",
+            "first_name": "Audited",
+            "last_name": "User",
+            "phone": "1234567890"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Assert
+    let body: Value = get(&test_app, &access_token, "?action=user_registered")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let items = body["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["actor"], "anonymous");
+    assert_eq!(items[0]["resource_type"], "user");
+    assert_eq!(items[0]["payload"]["type"], "user_registered");
+    assert!(body["next_cursor"].is_null());
+}
+
+#[tokio::test]
+async fn test_repository_changes_are_recorded_with_their_diff() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = login_admin_user(&mut context).await;
+    let admin_id = context.user.as_ref().unwrap().id;
+    context.commit().await.unwrap();
+    let client = reqwest::Client::new();
+    let path = format!("http://{}/api/v1/user/{}/", test_app.base_url, admin_id);
+
+    // Act
+    let response = client
+        .patch(&path)
+        .bearer_auth(&access_token)
+        .json(&json!({ "first_name": "Audited" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Assert
+    let updates: Value = get(
+        &test_app,
+        &access_token,
+        &format!("?user_id={}&resource_type=user&action=update", admin_id),
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    let items = updates["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["actor"], format!("user:{}", admin_id));
+    assert_eq!(items[0]["resource_id"], admin_id.to_string());
+    assert_eq!(
+        items[0]["payload"],
+        json!({ "first_name": { "from": "Admin", "to": "Audited" } })
+    );
+
+    let creates: Value = get(
+        &test_app,
+        &access_token,
+        "?resource_type=user&action=create",
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    let created = &creates["items"][0]["payload"];
+    assert_eq!(created["email"]["to"], "admin@example.com");
+    assert_eq!(created["password"]["to"], "[redacted]");
+}
+
+#[tokio::test]
+async fn test_avatar_and_role_changes_are_recorded() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = login_admin_user(&mut context).await;
+    let admin_id = context.user.as_ref().unwrap().id;
+
+    // Act
+    let file = file_repository::create(
+        &context,
+        file::ActiveModel {
+            user_id: Set(admin_id),
+            key: Set("avatars/audited.png".to_string()),
+            name: Set("audited.png".to_string()),
+            size: Set(6),
+            status: Set(FileStatus::Available),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    user_repository::set_avatar_file_id(&context, admin_id, Some(file.id))
+        .await
+        .unwrap();
+    user_repository::clear_avatar_file_id(&context, file.id)
+        .await
+        .unwrap();
+    grant_permissions(&context, admin_id, &["audit.read"]).await;
+    context.commit().await.unwrap();
+
+    // Assert
+    let updates: Value = get(
+        &test_app,
+        &access_token,
+        &format!("?resource_type=user&resource_id={}&action=update", admin_id),
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    let payloads = updates["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["payload"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        payloads,
+        vec![
+            json!({ "avatar_file_id": { "from": file.id, "to": null } }),
+            json!({ "avatar_file_id": { "from": null, "to": file.id } }),
+        ]
+    );
+    for resource_type in ["role", "role_permission", "user_role_assignment"] {
+        let creates: Value = get(
+            &test_app,
+            &access_token,
+            &format!("?resource_type={}&action=create", resource_type),
+        )
+        .await
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(creates["items"].as_array().unwrap().len(), 1);
+    }
+}
+
+#[tokio::test]
+async fn test_search_audit_log_filters_and_pages_newest_first() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    for resource_id in ["1", "2", "3"] {
+        record(&test_app, "user:1", "role_assigned", resource_id).await;
+    }
+    record(&test_app, "service:autoscaler", "role_assigned", "4").await;
+
+    // Act
+    let first: Value = get(&test_app, &access_token, "?actor=user:1&limit=2")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let second: Value = get(
+        &test_app,
+        &access_token,
+        &format!("?actor=user:1&limit=2&cursor={}", first["next_cursor"]),
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+
+    // Assert
+    assert_eq!(first["items"][0]["resource_id"], "3");
+    assert_eq!(first["items"][1]["resource_id"], "2");
+    assert!(first["next_cursor"].is_number());
+    assert_eq!(second["items"].as_array().unwrap().len(), 1);
+    assert_eq!(second["items"][0]["resource_id"], "1");
+    assert!(second["next_cursor"].is_null());
+}
+
+#[tokio::test]
+async fn test_export_audit_log_as_csv() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    record(&test_app, "user:1", "role_assigned", "7").await;
+    record(&test_app, "user:1", "user_deleted", "8").await;
+
+    // Act
+    let response = get(&test_app, &access_token, "export/?action=role_assigned").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/csv")
+    );
+    let body = response.text().await.unwrap();
+    let rows: Vec<&str> = body.split("\r\n").filter(|row| !row.is_empty()).collect();
+    assert_eq!(
+        rows[0],
+        "id,created_at,actor,action,resource_type,resource_id,payload"
+    );
+    assert_eq!(rows.len(), 2);
+    assert!(rows[1].contains(",user:1,role_assigned,user,7,"));
+    assert!(rows[1].ends_with(r#""{""note"":""=cmd, \""quoted\""""}""#));
+}
+
+#[tokio::test]
+async fn test_audit_log_requires_admin() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, false).await;
+
+    // Act
+    let search = get(&test_app, &access_token, "").await;
+    let export = get(&test_app, &access_token, "export/").await;
+
+    // Assert
+    assert_eq!(search.status(), StatusCode::FORBIDDEN);
+    assert_eq!(export.status(), StatusCode::FORBIDDEN);
+}
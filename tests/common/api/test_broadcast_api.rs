@@ -0,0 +1,175 @@
+use std::sync::Arc;
+
+use my_axum::{
+    common::entity::broadcast_event,
+    core::{
+        r#async::{ArchivingProducer, PeriodicJob, PurgeBroadcastEvents},
+        context::Context,
+    },
+    pkg::{
+        broadcast::websocket::BroadcastMessage,
+        messaging::{EncodedMessage, MessageProducer, stats::WORKER_STATS_EVENT},
+    },
+};
+use reqwest::StatusCode;
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait};
+use serde_json::{Value, json};
+
+use crate::setup::{
+    app::TestApp,
+    fixture::{login_admin_user, login_normal_user},
+};
+
+async fn access_token(test_app: &TestApp, admin: bool) -> String {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    context.commit().await.unwrap();
+    access_token
+}
+
+/// Publish a broadcast through an archiving producer wrapping the test broker
+async fn publish(test_app: &TestApp, destination: &str, event_type: &str, data: Value) {
+    let producer = ArchivingProducer::new(test_app.broker.producer(), test_app.db.clone());
+    let message = EncodedMessage::encode(&BroadcastMessage {
+        event_type: event_type.to_string(),
+        data,
+    })
+    .unwrap();
+    producer.publish(&message, Some(destination)).await.unwrap();
+}
+
+async fn search(test_app: &TestApp, access_token: &str, query: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!(
+            "http://{}/api/v1/admin/broadcasts/{}",
+            test_app.base_url, query
+        ))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_search_archived_broadcasts_by_task() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    publish(
+        &test_app,
+        "broadcasts",
+        "task_progress",
+        json!({ "task_id": "task-a", "progress": 50 }),
+    )
+    .await;
+    publish(
+        &test_app,
+        "broadcasts",
+        "task_progress",
+        json!({ "task_id": "task-a", "progress": 100 }),
+    )
+    .await;
+    publish(
+        &test_app,
+        "broadcasts",
+        "task_progress",
+        json!({ "task_id": "task-b", "progress": 10 }),
+    )
+    .await;
+
+    // Act
+    let response = search(&test_app, &access_token, "?task_id=task-a").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["count"], 2);
+    assert_eq!(body["items"][0]["event_type"], "task_progress");
+    assert_eq!(body["items"][0]["task_id"], "task-a");
+    assert_eq!(body["items"][0]["data"]["progress"], 50);
+    assert_eq!(body["items"][1]["data"]["progress"], 100);
+    assert!(body["items"][0]["published_at"].is_string());
+}
+
+#[tokio::test]
+async fn test_archive_skips_worker_stats_and_other_destinations() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+
+    // Act
+    publish(
+        &test_app,
+        "broadcasts",
+        WORKER_STATS_EVENT,
+        json!({ "worker_id": "worker-a" }),
+    )
+    .await;
+    publish(&test_app, "tasks", "task_progress", json!({ "user_id": 1 })).await;
+    publish(
+        &test_app,
+        "broadcasts",
+        "notification",
+        json!({ "user_id": 1 }),
+    )
+    .await;
+
+    // Assert
+    let body: Value = search(&test_app, &access_token, "")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["items"][0]["event_type"], "notification");
+    assert_eq!(body["items"][0]["user_id"], 1);
+    assert_eq!(test_app.broker.published().len(), 3);
+}
+
+#[tokio::test]
+async fn test_search_broadcasts_requires_admin() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, false).await;
+
+    // Act
+    let response = search(&test_app, &access_token, "").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_purge_deletes_broadcasts_past_retention() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let expired = broadcast_event::ActiveModel {
+        event_type: Set("notification".to_string()),
+        data: Set(json!({})),
+        published_at: Set(chrono::Utc::now().naive_utc() - chrono::Duration::days(30)),
+        ..Default::default()
+    }
+    .insert(&test_app.db)
+    .await
+    .unwrap();
+    publish(&test_app, "broadcasts", "notification", json!({})).await;
+
+    // Act
+    PurgeBroadcastEvents
+        .run(&test_app.create_app_state())
+        .await
+        .unwrap();
+
+    // Assert
+    let remaining = broadcast_event::Entity::find()
+        .all(&test_app.db)
+        .await
+        .unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_ne!(remaining[0].id, expired.id);
+}
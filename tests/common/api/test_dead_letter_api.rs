@@ -0,0 +1,223 @@
+use std::sync::Arc;
+
+use my_axum::{
+    config::setting::MessageType,
+    core::{
+        r#async::{TaskEvent, TaskHistoryRecorder, TaskType},
+        context::Context,
+    },
+    pkg::messaging::TaskLifecycle,
+};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+use crate::setup::{
+    app::TestApp,
+    fixture::{login_admin_user, login_normal_user},
+};
+
+async fn access_token(test_app: &TestApp, admin: bool) -> String {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    context.commit().await.unwrap();
+    access_token
+}
+
+/// Record `task` failing its first attempt as retried and its last one for good
+async fn dead_letter(test_app: &TestApp, task: TaskType) -> TaskEvent {
+    let recorder = TaskHistoryRecorder::new(test_app.db.clone(), "worker-a");
+    let mut event = TaskEvent::new(task);
+    event.max_retries = 1;
+    recorder
+        .record(
+            &event,
+            &TaskLifecycle::Retrying {
+                error: "connection reset".to_string(),
+            },
+        )
+        .await;
+    event.retry_count = 1;
+    recorder
+        .record(
+            &event,
+            &TaskLifecycle::Failed {
+                error: "connection refused".to_string(),
+            },
+        )
+        .await;
+    event
+}
+
+async fn search(test_app: &TestApp, access_token: &str, query: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!(
+            "http://{}/api/v1/admin/dead-letters/{}",
+            test_app.base_url, query
+        ))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn post(
+    test_app: &TestApp,
+    access_token: &str,
+    action: &str,
+    selection: Value,
+) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!(
+            "http://{}/api/v1/admin/dead-letters/{}/",
+            test_app.base_url, action
+        ))
+        .bearer_auth(access_token)
+        .json(&selection)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_search_dead_letters_with_history() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    let event = dead_letter(&test_app, TaskType::CleanupExpiredToken).await;
+    dead_letter(&test_app, TaskType::ProcessUserRegistration { user_id: 1 }).await;
+
+    // Act
+    let response = search(&test_app, &access_token, "?task_type=CleanupExpiredToken").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["count"], 1);
+    let item = &body["items"][0];
+    assert_eq!(item["task_id"], event.id.as_str());
+    assert_eq!(item["task_type"], "CleanupExpiredToken");
+    assert_eq!(item["topic"], MessageType::Tasks.as_ref());
+    assert_eq!(item["task"]["type"], "CleanupExpiredToken");
+    assert_eq!(item["attempts"], 2);
+    assert_eq!(item["error"], "connection refused");
+    assert_eq!(item["worker_id"], "worker-a");
+    assert!(item["failed_at"].is_string());
+    assert_eq!(item["history"][0]["status"], "retrying");
+    assert_eq!(item["history"][1]["status"], "failed");
+}
+
+#[tokio::test]
+async fn test_search_dead_letters_by_date_range() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    dead_letter(&test_app, TaskType::CleanupExpiredToken).await;
+
+    // Act
+    let response = search(&test_app, &access_token, "?failed_to=2000-01-01T00:00:00Z").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["count"], 0);
+}
+
+#[tokio::test]
+async fn test_replay_dead_letters_onto_original_topic() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    let event = dead_letter(&test_app, TaskType::CleanupExpiredToken).await;
+    let listed: Value = search(&test_app, &access_token, "")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let id = listed["items"][0]["id"].clone();
+
+    // Act
+    let response = post(&test_app, &access_token, "replay", json!({ "ids": [id] })).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["replayed"], 1);
+    assert_eq!(body["failed_ids"], json!([]));
+    let replayed = test_app.broker.tasks(MessageType::Tasks.as_ref());
+    assert_eq!(replayed.len(), 1);
+    assert_eq!(replayed[0].id, event.id);
+    assert_eq!(replayed[0].retry_count, 0);
+    assert!(matches!(replayed[0].task, TaskType::CleanupExpiredToken));
+    let remaining: Value = search(&test_app, &access_token, "")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(remaining["count"], 0);
+}
+
+#[tokio::test]
+async fn test_purge_dead_letters_by_task_type() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    dead_letter(&test_app, TaskType::CleanupExpiredToken).await;
+    dead_letter(&test_app, TaskType::CleanupExpiredToken).await;
+    dead_letter(&test_app, TaskType::ProcessUserRegistration { user_id: 1 }).await;
+
+    // Act
+    let response = post(
+        &test_app,
+        &access_token,
+        "purge",
+        json!({ "task_type": "CleanupExpiredToken" }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["purged"], 2);
+    let remaining: Value = search(&test_app, &access_token, "")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(remaining["count"], 1);
+    assert_eq!(
+        remaining["items"][0]["task_type"],
+        "ProcessUserRegistration"
+    );
+}
+
+#[tokio::test]
+async fn test_purge_requires_a_selection() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    dead_letter(&test_app, TaskType::CleanupExpiredToken).await;
+
+    // Act
+    let response = post(&test_app, &access_token, "purge", json!({})).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_dead_letters_require_admin() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, false).await;
+
+    // Act
+    let response = search(&test_app, &access_token, "").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
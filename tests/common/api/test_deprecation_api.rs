@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use axum::{Router, routing::get};
+use chrono::{Duration, Utc};
+use my_axum::{
+    config::app::{App, AppState},
+    core::{
+        api::route::public_api, context::Context, layer::deprecation_layer::RouteDeprecation,
+        module::Module,
+    },
+};
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::setup::{app::TestApp, fixture::login_admin_user};
+
+struct LegacyModule;
+
+impl Module for LegacyModule {
+    fn name(&self) -> &'static str {
+        "legacy"
+    }
+
+    fn routes(&self, app_state: &AppState) -> Router<AppState> {
+        public_api(
+            Router::new().route("/api/v1/legacy/", get(|| async { "legacy" })),
+            app_state,
+        )
+    }
+
+    fn deprecated_routes(&self) -> Vec<RouteDeprecation> {
+        vec![
+            RouteDeprecation::new("/api/v1/legacy/", Utc::now() - Duration::days(1))
+                .sunset(Utc::now() + Duration::days(30))
+                .replacement("/api/v2/legacy/"),
+        ]
+    }
+}
+
+#[tokio::test]
+async fn test_report_lists_deprecated_routes_and_their_clients() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let mut setting = test_app.setting.clone();
+    setting.app_port = 0;
+    let app = App::builder(setting)
+        .db(test_app.db.clone())
+        .module(LegacyModule)
+        .build()
+        .await
+        .unwrap();
+    let base_url = app.base_url.clone();
+    tokio::spawn(app.run_until_stopped());
+
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = login_admin_user(&mut context).await;
+    context.commit().await.unwrap();
+
+    let client = reqwest::Client::new();
+    let legacy = client
+        .get(format!("http://{}/api/v1/legacy/", base_url))
+        .header("x-forwarded-for", "10.0.0.1")
+        .send()
+        .await
+        .unwrap();
+    assert!(legacy.headers().contains_key("deprecation"));
+
+    // Act
+    let response = client
+        .get(format!("http://{}/api/v1/admin/deprecations/", base_url))
+        .bearer_auth(&access_token)
+        .send()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["count"], 1);
+    let route = &body["items"][0];
+    assert_eq!(route["path"], "/api/v1/legacy/");
+    assert!(route["method"].is_null());
+    assert_eq!(route["replacement"], "/api/v2/legacy/");
+    assert_eq!(route["retired"], false);
+    assert_eq!(route["calls"], 1);
+    assert!(route["sunset"].is_string());
+    assert_eq!(route["clients"][0]["client_ip"], "10.0.0.1");
+    assert_eq!(route["clients"][0]["calls"], 1);
+}
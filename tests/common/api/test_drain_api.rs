@@ -0,0 +1,105 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::StreamExt;
+use my_axum::core::context::Context;
+use reqwest::StatusCode;
+use serde_json::Value;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{Message, protocol::frame::coding::CloseCode},
+};
+use uuid::Uuid;
+
+use crate::setup::{
+    app::TestApp,
+    fixture::{login_admin_user, login_normal_user},
+};
+
+async fn access_token(test_app: &TestApp, admin: bool) -> String {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    context.commit().await.unwrap();
+    access_token
+}
+
+async fn drain(test_app: &TestApp, access_token: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("http://{}/api/v1/admin/drain/", test_app.base_url))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+fn task_ws_url(test_app: &TestApp, task_id: &str, access_token: &str) -> String {
+    format!(
+        "ws://{}/ws/v1/task/{task_id}/?token={access_token}",
+        test_app.base_url.replace("http://", "")
+    )
+}
+
+#[tokio::test]
+async fn test_drain_notifies_and_closes_websockets() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    let task_id = format!("drain-task-{}", Uuid::new_v4());
+    let (mut ws_stream, _) = connect_async(task_ws_url(&test_app, &task_id, &access_token))
+        .await
+        .expect("Failed to connect");
+
+    // Act
+    let response = drain(&test_app, &access_token).await;
+    let mut frames = Vec::new();
+    while let Ok(Some(Ok(frame))) =
+        tokio::time::timeout(Duration::from_secs(10), ws_stream.next()).await
+    {
+        let closed = matches!(frame, Message::Close(_));
+        frames.push(frame);
+        if closed {
+            break;
+        }
+    }
+    let reconnect = connect_async(task_ws_url(&test_app, "another-task", &access_token)).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body: Value = response.json().await.unwrap();
+    assert_ne!(body["phase"], "serving");
+
+    let Some(Message::Text(notice)) = frames.first() else {
+        panic!("Expected a draining notice, got {:?}", frames);
+    };
+    let notice: Value = serde_json::from_str(notice.as_ref()).unwrap();
+    assert_eq!(notice["event_type"], "server_draining");
+    assert_eq!(notice["data"]["reconnect"], true);
+    assert_eq!(notice["data"]["task_id"], task_id.as_str());
+
+    let Some(Message::Close(Some(close))) = frames.last() else {
+        panic!("Expected a close frame, got {:?}", frames);
+    };
+    assert_eq!(close.code, CloseCode::Restart);
+    assert!(reconnect.is_err());
+}
+
+#[tokio::test]
+async fn test_drain_requires_admin() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, false).await;
+
+    // Act
+    let response = drain(&test_app, &access_token).await;
+    let ready = reqwest::get(format!("http://{}/ready/", test_app.base_url))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(ready.status(), StatusCode::OK);
+}
@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use reqwest::{StatusCode, header};
+use uuid::Uuid;
+
+use crate::setup::app::TestApp;
+
+const INDEX_HTML: &str = "<!doctype html><div id=\"app\"></div>";
+
+/// Write a built app with an `index.html` and one hashed asset
+fn write_dist_dir() -> PathBuf {
+    let dist_dir = std::env::temp_dir().join(format!("frontend-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(dist_dir.join("assets")).unwrap();
+    std::fs::write(dist_dir.join("index.html"), INDEX_HTML).unwrap();
+    std::fs::write(dist_dir.join("assets/app-3f2a.js"), "console.log('app')").unwrap();
+    dist_dir
+}
+
+#[tokio::test]
+async fn test_frontend_serves_hashed_assets_as_immutable() {
+    // Arrange
+    let test_app = TestApp::spawn_app_with_frontend(&write_dist_dir()).await;
+
+    // Act
+    let response = reqwest::get(format!("http://{}/assets/app-3f2a.js", test_app.base_url))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CACHE_CONTROL],
+        "public, max-age=31536000, immutable"
+    );
+    assert_eq!(response.text().await.unwrap(), "console.log('app')");
+}
+
+#[tokio::test]
+async fn test_frontend_falls_back_to_index_for_client_routes() {
+    // Arrange
+    let test_app = TestApp::spawn_app_with_frontend(&write_dist_dir()).await;
+
+    for path in ["/", "/users/42/settings"] {
+        // Act
+        let response = reqwest::get(format!("http://{}{}", test_app.base_url, path))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK, "{}", path);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "no-cache");
+        assert_eq!(response.text().await.unwrap(), INDEX_HTML);
+    }
+}
+
+#[tokio::test]
+async fn test_frontend_leaves_missing_files_and_api_paths_unanswered() {
+    // Arrange
+    let test_app = TestApp::spawn_app_with_frontend(&write_dist_dir()).await;
+
+    for path in ["/assets/missing-9b1c.js", "/api/v1/unknown/"] {
+        // Act
+        let response = reqwest::get(format!("http://{}{}", test_app.base_url, path))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", path);
+    }
+
+    let health = reqwest::get(format!("http://{}/health/", test_app.base_url))
+        .await
+        .unwrap();
+    assert_eq!(health.status(), StatusCode::OK);
+}
@@ -0,0 +1,106 @@
+use my_axum::pkg::{
+    broadcast::{forwarder::forward_message_to_websocket, websocket::BroadcastMessage},
+    circuit_breaker::{BreakerSnapshot, BreakerState},
+    messaging::stats::{WORKER_STATS_EVENT, WorkerStats},
+};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::setup::app::TestApp;
+
+#[tokio::test]
+async fn test_health_is_public() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+
+    // Act
+    let response = reqwest::get(format!("http://{}/health/", test_app.base_url))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "ok");
+}
+
+/// Report a worker whose SNS breaker tripped, as the worker stats broadcast would
+async fn report_tripped_worker() -> String {
+    let worker_id = format!("worker-{}", Uuid::new_v4());
+    let report = BroadcastMessage {
+        event_type: WORKER_STATS_EVENT.to_string(),
+        data: json!(WorkerStats {
+            worker_id: worker_id.clone(),
+            circuit_breakers: vec![BreakerSnapshot {
+                name: "sns".to_string(),
+                state: BreakerState::Open,
+                health_score: 0.25,
+                consecutive_failures: 5,
+                opened_total: 1,
+                rejected_total: 3,
+            }],
+            ..Default::default()
+        }),
+    };
+    forward_message_to_websocket(&serde_json::to_string(&report).unwrap()).await;
+    worker_id
+}
+
+#[tokio::test]
+async fn test_ready_reports_tripped_breakers_as_degraded() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let worker_id = report_tripped_worker().await;
+
+    // Act
+    let response = reqwest::get(format!("http://{}/ready/", test_app.base_url))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["database"], true);
+    let dependency = body["dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|dependency| dependency["process"] == worker_id.as_str())
+        .expect("reported breaker should be listed");
+    assert_eq!(dependency["name"], "sns");
+    assert_eq!(dependency["state"], "open");
+    assert_eq!(dependency["health_score"], 0.25);
+}
+
+#[tokio::test]
+async fn test_metrics_expose_breakers_in_prometheus_format() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let worker_id = report_tripped_worker().await;
+
+    // Act
+    let response = reqwest::get(format!("http://{}/metrics/", test_app.base_url))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain")
+    );
+    let body = response.text().await.unwrap();
+    assert!(body.contains("# TYPE dependency_circuit_state gauge"));
+    assert!(body.contains(&format!(
+        "dependency_circuit_state{{process=\"{}\",dependency=\"sns\"}} 2\n",
+        worker_id
+    )));
+    assert!(body.contains(&format!(
+        "dependency_circuit_rejected_total{{process=\"{}\",dependency=\"sns\"}} 3\n",
+        worker_id
+    )));
+}
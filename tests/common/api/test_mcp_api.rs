@@ -52,35 +52,7 @@ async fn mcp_response_text(response: Response) -> String {
 }
 
 async fn register_user_and_get_token(test_app: &TestApp, email: &str) -> String {
-    let client = Client::new();
-    let payload = json!({
-        "email": email,
-        "password": "password123@",
-        "first_name": "Mcp",
-        "last_name": "User",
-        "phone": "1234567890"
-    });
-
-    let response = client
-        .post(format!(
-            "http://{}/api/v1/auth/register/",
-            &test_app.base_url
-        ))
-        .json(&payload)
-        .send()
-        .await
-        .unwrap();
-
-    assert_eq!(response.status(), StatusCode::OK);
-    response
-        .json::<Value>()
-        .await
-        .unwrap()
-        .get("access")
-        .unwrap()
-        .as_str()
-        .unwrap()
-        .to_string()
+    test_app.register_and_login(email).await.access_token()
 }
 
 async fn admin_token(test_app: &TestApp) -> String {
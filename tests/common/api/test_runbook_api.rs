@@ -58,8 +58,7 @@ async fn test_list_runbooks_returns_forbidden_for_non_admin_user() {
 
     assert_eq!(response.status(), StatusCode::FORBIDDEN);
     let body = to_json(response).await;
-    let message = body["message"].as_str().unwrap();
-    assert!(message.contains("Admin role is required"));
+    assert_eq!(body["code"], "AUTH_ROLE_REQUIRED");
 }
 
 #[tokio::test]
@@ -99,6 +98,7 @@ async fn test_run_runbook_endpoint_executes_delete_refresh_tokens_by_email_for_a
 
     let target_user = user::Entity::insert(user::ActiveModel {
         email: Set("target@example.com".to_string()),
+        normalized_email: Set("target@example.com".to_string()),
         password: Set("hashed".to_string()),
         ..Default::default()
     })
@@ -108,6 +108,7 @@ async fn test_run_runbook_endpoint_executes_delete_refresh_tokens_by_email_for_a
 
     let other_user = user::Entity::insert(user::ActiveModel {
         email: Set("other@example.com".to_string()),
+        normalized_email: Set("other@example.com".to_string()),
         password: Set("hashed".to_string()),
         ..Default::default()
     })
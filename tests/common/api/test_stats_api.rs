@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use my_axum::{
+    core::context::Context,
+    pkg::{
+        broadcast::{forwarder::forward_message_to_websocket, websocket::BroadcastMessage},
+        cache::{InMemoryTaskQuota, QuotaLimits},
+        messaging::stats::{WORKER_STATS_EVENT, WorkerStats},
+    },
+};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::setup::{
+    app::TestApp,
+    fixture::{login_admin_user, login_normal_user},
+};
+
+async fn get_stats(test_app: &TestApp, access_token: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!("http://{}/api/v1/admin/stats/", test_app.base_url))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn access_token(test_app: &TestApp, admin: bool) -> String {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    context.commit().await.unwrap();
+    access_token
+}
+
+#[tokio::test]
+async fn test_stats_snapshot_for_admin() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    reqwest::get(format!("http://{}/api/v1/missing/", test_app.base_url))
+        .await
+        .unwrap();
+
+    // Act
+    let response = get_stats(&test_app, &access_token).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["uptime_seconds"].is_u64());
+    assert!(body["requests"]["total"].as_u64().unwrap() >= 1);
+    assert!(body["requests"]["client_errors"].as_u64().unwrap() >= 1);
+    assert!(body["requests"]["shed"].is_u64());
+    assert!(body["requests"]["per_second"].as_f64().unwrap() > 0.0);
+    assert!(body["websocket_connections"].is_u64());
+    assert!(body["db_pool"]["max"].as_u64().unwrap() > 0);
+    assert!(body["queues"].is_array());
+    assert!(body["tasks"]["succeeded"].is_u64());
+    assert!(body["broker_failovers"].is_u64());
+}
+
+#[tokio::test]
+async fn test_stats_include_reported_worker_queues() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    let worker_id = format!("worker-{}", Uuid::new_v4());
+    let report = BroadcastMessage {
+        event_type: WORKER_STATS_EVENT.to_string(),
+        data: json!(WorkerStats {
+            worker_id: worker_id.clone(),
+            queued: 4,
+            running: 2,
+            succeeded: 10,
+            failed: 1,
+            retried: 3,
+            lag_ms: 120,
+            broker_failovers: 2,
+            ..Default::default()
+        }),
+    };
+    forward_message_to_websocket(&serde_json::to_string(&report).unwrap()).await;
+
+    // Act
+    let body: Value = get_stats(&test_app, &access_token)
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    // Assert
+    let queue = body["queues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|queue| queue["worker_id"] == worker_id.as_str())
+        .expect("reported worker should be listed");
+    assert_eq!(queue["depth"], 4);
+    assert_eq!(queue["lag_ms"], 120);
+    assert_eq!(queue["broker_failovers"], 2);
+    assert!(body["tasks"]["succeeded"].as_u64().unwrap() >= 10);
+    assert!(body["tasks"]["failed"].as_u64().unwrap() >= 1);
+}
+
+async fn upload_avatar(
+    test_app: &TestApp,
+    access_token: &str,
+    file_name: &str,
+) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!(
+            "http://{}/api/v1/user/upload-avatar/",
+            test_app.base_url
+        ))
+        .bearer_auth(access_token)
+        .json(&json!({ "file_name": file_name }))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_task_quota_rejects_tasks_over_limit_and_reports_usage() {
+    // Arrange
+    let test_app =
+        TestApp::spawn_app_with_task_quota(Arc::new(InMemoryTaskQuota::new(QuotaLimits {
+            max_running: 1,
+            max_daily: 10,
+        })))
+        .await;
+    let user_token = access_token(&test_app, false).await;
+    let admin_token = access_token(&test_app, true).await;
+    let first = upload_avatar(&test_app, &user_token, "a.jpg").await;
+
+    // Act
+    let second = upload_avatar(&test_app, &user_token, "b.jpg").await;
+
+    // Assert
+    assert_eq!(first.status(), StatusCode::ACCEPTED);
+    assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(test_app.broker.tasks("tasks").len(), 1);
+    let body: Value = get_stats(&test_app, &admin_token)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["task_quota"]["max_running"], 1);
+    assert_eq!(body["task_quota"]["max_daily"], 10);
+    assert_eq!(body["task_quota"]["users"].as_array().unwrap().len(), 1);
+    assert_eq!(body["task_quota"]["users"][0]["running"], 1);
+    assert_eq!(body["task_quota"]["users"][0]["enqueued_today"], 1);
+}
+
+#[tokio::test]
+async fn test_stats_forbidden_for_non_admin() {
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, false).await;
+
+    let response = get_stats(&test_app, &access_token).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_stats_requires_authentication() {
+    let test_app = TestApp::spawn_app().await;
+
+    let response = reqwest::get(format!("http://{}/api/v1/admin/stats/", test_app.base_url))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
@@ -4,16 +4,23 @@ mod task_api_tests {
     use futures::{SinkExt, StreamExt};
     use my_axum::{
         core::context::Context,
-        pkg::broadcast::websocket::{BroadcastMessage, broadcast_to_task},
+        pkg::broadcast::{
+            forwarder::forward_message_to_websocket,
+            websocket::{BroadcastMessage, broadcast_to_task},
+        },
     };
+    use reqwest::StatusCode;
     use sea_orm::TransactionTrait;
-    use serde_json::json;
+    use serde_json::{Value, json};
     use std::{
         sync::{Arc, OnceLock},
         time::Duration,
     };
     use tokio::sync::Mutex;
-    use tokio_tungstenite::{connect_async, tungstenite::Message};
+    use tokio_tungstenite::{
+        connect_async,
+        tungstenite::{Message, client::IntoClientRequest, http::HeaderValue},
+    };
     use uuid::Uuid;
 
     fn ws_test_lock() -> &'static Mutex<()> {
@@ -93,6 +100,56 @@ mod task_api_tests {
         write.send(Message::Close(None)).await.ok();
     }
 
+    #[tokio::test]
+    async fn test_progress_updates_websocket_negotiates_v2_protocol() {
+        let _guard = ws_test_lock().lock().await;
+        let test_app = TestApp::spawn_app().await;
+        let access_token = login_and_get_access_token(&test_app).await;
+        let task_id = format!("progress-task-{}", Uuid::new_v4());
+        let mut request = task_ws_url(&test_app, &task_id, &access_token)
+            .into_client_request()
+            .unwrap();
+        request.headers_mut().insert(
+            "Sec-WebSocket-Protocol",
+            HeaderValue::from_static("my-axum.v1, my-axum.v2"),
+        );
+
+        let (ws_stream, response) = connect_async(request).await.expect("Failed to connect");
+        assert_eq!(
+            response.headers()["Sec-WebSocket-Protocol"],
+            HeaderValue::from_static("my-axum.v2")
+        );
+        let (mut write, mut read) = ws_stream.split();
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        broadcast_to_task(
+            &task_id,
+            BroadcastMessage {
+                event_type: "progress".to_string(),
+                data: json!({"task_id": task_id, "percent": 50}),
+            },
+        )
+        .await;
+
+        let response = tokio::time::timeout(Duration::from_secs(5), read.next())
+            .await
+            .expect("Timed out waiting for broadcast")
+            .expect("WebSocket closed before receiving broadcast")
+            .expect("Failed to read WebSocket message");
+        let Message::Text(text) = response else {
+            panic!("Expected text frame from progress broadcast");
+        };
+
+        let payload: Value = serde_json::from_str(text.as_ref()).unwrap();
+        assert_eq!(payload["version"], 2);
+        assert!(payload["seq"].is_u64());
+        assert_eq!(payload["event_type"], "progress");
+        assert_eq!(payload["data"]["percent"], 50);
+
+        write.send(Message::Close(None)).await.ok();
+    }
+
     #[tokio::test]
     async fn test_progress_updates_websocket_handles_binary_messages_and_reconnects() {
         let _guard = ws_test_lock().lock().await;
@@ -172,4 +229,197 @@ mod task_api_tests {
             "Progress WebSocket should reject unauthenticated connections"
         );
     }
+
+    async fn next_text(
+        read: &mut (
+                 impl StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin
+             ),
+    ) -> Value {
+        let response = tokio::time::timeout(Duration::from_secs(5), read.next())
+            .await
+            .expect("Timed out waiting for a message")
+            .expect("WebSocket closed before sending a message")
+            .expect("Failed to read WebSocket message");
+        let Message::Text(text) = response else {
+            panic!("Expected a text frame");
+        };
+        serde_json::from_str(text.as_ref()).expect("Message should be valid JSON")
+    }
+
+    #[tokio::test]
+    async fn test_progress_updates_websocket_rejects_invalid_commands() {
+        let _guard = ws_test_lock().lock().await;
+        let test_app = TestApp::spawn_app().await;
+        let access_token = login_and_get_access_token(&test_app).await;
+        let task_id = format!("command-task-{}", Uuid::new_v4());
+        let ws_url = task_ws_url(&test_app, &task_id, &access_token);
+
+        let (ws_stream, _) = connect_async(&ws_url).await.expect("Failed to connect");
+        let (mut write, mut read) = ws_stream.split();
+
+        write
+            .send(Message::Text(r#"{"action":"dance"}"#.to_string().into()))
+            .await
+            .unwrap();
+        let rejection = next_text(&mut read).await;
+        assert_eq!(rejection["event_type"], "message_rejected");
+        assert_eq!(rejection["data"]["task_id"], task_id.as_str());
+        assert_eq!(rejection["data"]["rejected"], "dance");
+        assert_eq!(rejection["data"]["errors"][0]["path"], "/action");
+
+        write
+            .send(Message::Text(
+                r#"{"action":"ping","extra":1}"#.to_string().into(),
+            ))
+            .await
+            .unwrap();
+        let rejection = next_text(&mut read).await;
+        assert_eq!(rejection["data"]["rejected"], "ping");
+
+        write
+            .send(Message::Text("not json".to_string().into()))
+            .await
+            .unwrap();
+        let rejection = next_text(&mut read).await;
+        assert!(rejection["data"]["rejected"].is_null());
+        assert!(
+            rejection["data"]["errors"][0]["message"]
+                .as_str()
+                .unwrap()
+                .contains("expected")
+        );
+
+        write.send(Message::Close(None)).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_forwarder_rejects_broadcasts_not_matching_their_schema() {
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app
+            .register_and_login("forward-schema@example.com")
+            .await;
+        let task_id = format!("forward-task-{}", Uuid::new_v4());
+        let bulk_progress = |progress: Value| {
+            json!({
+                "event_type": "bulk_user_progress",
+                "data": {
+                    "task_id": task_id,
+                    "progress": progress,
+                    "status": "processing",
+                    "processed": 1,
+                    "total": 2
+                }
+            })
+            .to_string()
+        };
+
+        forward_message_to_websocket(&bulk_progress(json!(50))).await;
+        forward_message_to_websocket(&bulk_progress(json!("half"))).await;
+        forward_message_to_websocket(&json!({"data": {"task_id": task_id}}).to_string()).await;
+
+        let response = client
+            .get(&format!("/api/v1/tasks/{task_id}/poll/?since=0"))
+            .await;
+        let body: Value = response.json().await.unwrap();
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0]["event_type"], "bulk_user_progress");
+        assert_eq!(messages[1]["event_type"], "message_rejected");
+        assert_eq!(messages[1]["data"]["rejected"], "bulk_user_progress");
+        assert_eq!(messages[1]["data"]["errors"][0]["path"], "/progress");
+        assert_eq!(messages[2]["event_type"], "message_rejected");
+        assert!(messages[2]["data"]["rejected"].is_null());
+    }
+
+    fn progress(task_id: &str, percent: u64) -> BroadcastMessage {
+        BroadcastMessage {
+            event_type: "progress".to_string(),
+            data: json!({"task_id": task_id, "percent": percent}),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poll_returns_buffered_messages_after_since() {
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app
+            .register_and_login("poll-buffered@example.com")
+            .await;
+        let task_id = format!("poll-task-{}", Uuid::new_v4());
+
+        broadcast_to_task(&task_id, progress(&task_id, 10)).await;
+        broadcast_to_task(&task_id, progress(&task_id, 20)).await;
+        broadcast_to_task(&task_id, progress(&task_id, 30)).await;
+
+        let response = client
+            .get(&format!("/api/v1/tasks/{task_id}/poll/?since=1"))
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["last_seq"], 3);
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["seq"], 2);
+        assert_eq!(messages[0]["event_type"], "progress");
+        assert_eq!(messages[0]["data"]["percent"], 20);
+        assert_eq!(messages[1]["seq"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_poll_waits_for_new_messages() {
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("poll-wait@example.com").await;
+        let task_id = format!("poll-task-{}", Uuid::new_v4());
+
+        let broadcaster = tokio::spawn({
+            let task_id = task_id.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                broadcast_to_task(&task_id, progress(&task_id, 50)).await;
+            }
+        });
+
+        let response = client
+            .get(&format!("/api/v1/tasks/{task_id}/poll/?since=0&timeout=10"))
+            .await;
+        broadcaster.await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["last_seq"], 1);
+        assert_eq!(body["messages"][0]["data"]["percent"], 50);
+    }
+
+    #[tokio::test]
+    async fn test_poll_times_out_without_messages() {
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app
+            .register_and_login("poll-timeout@example.com")
+            .await;
+        let task_id = format!("poll-task-{}", Uuid::new_v4());
+
+        let response = client
+            .get(&format!("/api/v1/tasks/{task_id}/poll/?since=4&timeout=1"))
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["messages"], json!([]));
+        assert_eq!(body["last_seq"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_poll_requires_authentication() {
+        let test_app = TestApp::spawn_app().await;
+
+        let response = reqwest::get(format!(
+            "http://{}/api/v1/tasks/{}/poll/?timeout=1",
+            test_app.base_url,
+            Uuid::new_v4()
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
 }
@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use my_axum::{
+    core::{
+        r#async::{TaskEvent, TaskHistoryRecorder, TaskType},
+        context::Context,
+    },
+    pkg::messaging::TaskLifecycle,
+    user::dto::bulk_user_dto::BulkUserOperationDTO,
+};
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::setup::{
+    app::TestApp,
+    fixture::{login_admin_user, login_normal_user},
+};
+
+async fn get_history(test_app: &TestApp, access_token: &str, task_id: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!(
+            "http://{}/api/v1/tasks/{}/history/",
+            test_app.base_url, task_id
+        ))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn access_token(test_app: &TestApp, admin: bool) -> String {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    context.commit().await.unwrap();
+    access_token
+}
+
+fn statuses(body: &Value) -> Vec<&str> {
+    body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["status"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_history_of_completed_task() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    let run = test_app.run_task(TaskType::CleanupExpiredToken).await;
+
+    // Act
+    let response = get_history(&test_app, &access_token, &run.event.id).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["count"], 3);
+    assert_eq!(statuses(&body), vec!["enqueued", "started", "completed"]);
+    let started = &body["items"][1];
+    assert_eq!(started["task_id"], run.event.id.as_str());
+    assert_eq!(started["task_type"], "CleanupExpiredToken");
+    assert_eq!(started["worker_id"], "test-worker");
+    assert_eq!(started["attempt"], 1);
+    assert!(started["error"].is_null());
+    assert!(started["created_at"].is_string());
+}
+
+#[tokio::test]
+async fn test_history_by_payload_task_id() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    test_app
+        .run_task(TaskType::ProcessBulkUserOperations {
+            task_id: "bulk-history".to_string(),
+            actor_id: 1,
+            locale: "en".to_string(),
+            operations: vec![BulkUserOperationDTO::Delete { user_id: 999_999 }],
+        })
+        .await;
+
+    // Act
+    let response = get_history(&test_app, &access_token, "bulk-history").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(statuses(&body), vec!["enqueued", "started", "completed"]);
+    assert_eq!(body["items"][0]["reference"], "bulk-history");
+    assert_eq!(body["items"][0]["task_type"], "ProcessBulkUserOperations");
+}
+
+#[tokio::test]
+async fn test_history_records_retries_and_failure() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    let recorder = TaskHistoryRecorder::new(test_app.db.clone(), "worker-a");
+    let mut event = TaskEvent::new(TaskType::CleanupExpiredToken);
+    recorder
+        .record(
+            &event,
+            &TaskLifecycle::Retrying {
+                error: "connection reset".to_string(),
+            },
+        )
+        .await;
+    event.retry_count = 1;
+    recorder
+        .record(
+            &event,
+            &TaskLifecycle::Failed {
+                error: "connection refused".to_string(),
+            },
+        )
+        .await;
+
+    // Act
+    let response = get_history(&test_app, &access_token, &event.id).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(statuses(&body), vec!["retrying", "failed"]);
+    assert_eq!(body["items"][0]["attempt"], 1);
+    assert_eq!(body["items"][0]["error"], "connection reset");
+    assert_eq!(body["items"][1]["attempt"], 2);
+    assert_eq!(body["items"][1]["error"], "connection refused");
+    assert_eq!(body["items"][1]["worker_id"], "worker-a");
+}
+
+#[tokio::test]
+async fn test_history_of_unknown_task() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+
+    // Act
+    let response = get_history(&test_app, &access_token, "missing").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["code"], "TASK_HISTORY_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_history_requires_admin() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, false).await;
+    let run = test_app.run_task(TaskType::CleanupExpiredToken).await;
+
+    // Act
+    let response = get_history(&test_app, &access_token, &run.event.id).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_history_tells_finished_tasks_apart() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let recorder = TaskHistoryRecorder::new(test_app.db.clone(), "worker-a");
+    let retried = TaskEvent::new(TaskType::CleanupExpiredToken);
+    let completed = TaskEvent::new(TaskType::CleanupExpiredToken);
+
+    // Act
+    recorder
+        .record(
+            &retried,
+            &TaskLifecycle::Retrying {
+                error: "connection reset".to_string(),
+            },
+        )
+        .await;
+    recorder.record(&completed, &TaskLifecycle::Started).await;
+    recorder.record(&completed, &TaskLifecycle::Completed).await;
+
+    // Assert
+    assert!(!recorder.is_processed(&retried).await);
+    assert!(recorder.is_processed(&completed).await);
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use my_axum::core::context::Context;
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+use crate::setup::{
+    app::TestApp,
+    fixture::{login_admin_user, login_normal_user},
+};
+
+async fn access_token(test_app: &TestApp, admin: bool) -> String {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    context.commit().await.unwrap();
+    access_token
+}
+
+async fn get(test_app: &TestApp, access_token: &str, path: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!(
+            "http://{}/api/v1/admin/{}",
+            test_app.base_url, path
+        ))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn post(
+    test_app: &TestApp,
+    access_token: &str,
+    task_type: &str,
+    action: &str,
+) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!(
+            "http://{}/api/v1/admin/task-types/{}/{}/",
+            test_app.base_url, task_type, action
+        ))
+        .bearer_auth(access_token)
+        .json(&json!({ "reason": "provider outage" }))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_pause_and_resume_task_type() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+
+    // Act
+    let paused = post(&test_app, &access_token, "SendEmail", "pause").await;
+    let listed: Value = get(&test_app, &access_token, "task-types/paused/")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let resumed = post(&test_app, &access_token, "SendEmail", "resume").await;
+    let resumed_again = post(&test_app, &access_token, "SendEmail", "resume").await;
+
+    // Assert
+    assert_eq!(paused.status(), StatusCode::OK);
+    let body: Value = paused.json().await.unwrap();
+    assert_eq!(body["task_type"], "SendEmail");
+    assert_eq!(body["reason"], "provider outage");
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert_eq!(listed[0]["task_type"], "SendEmail");
+    assert_eq!(resumed.status(), StatusCode::NO_CONTENT);
+    assert_eq!(resumed_again.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_pause_task_type_is_idempotent() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    post(&test_app, &access_token, "SendEmail", "pause").await;
+
+    // Act
+    let response = post(&test_app, &access_token, "SendEmail", "pause").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let listed: Value = get(&test_app, &access_token, "task-types/paused/")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_get_worker_scaling_lists_paused_task_types() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    post(&test_app, &access_token, "GenerateReport", "pause").await;
+
+    // Act
+    let response = get(&test_app, &access_token, "workers/scaling/").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["paused_task_types"], json!(["GenerateReport"]));
+    assert!(body["queue_depth"].is_u64());
+    assert!(body["processed_last_minute"].is_u64());
+}
+
+#[tokio::test]
+async fn test_pause_task_type_requires_admin() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, false).await;
+
+    // Act
+    let response = post(&test_app, &access_token, "SendEmail", "pause").await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
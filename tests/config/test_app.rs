@@ -1,4 +1,22 @@
-use my_axum::config::{app::App, setting::Setting};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use axum::{Router, extract::State, routing::get};
+use my_axum::{
+    config::{
+        app::{App, AppState},
+        setting::Setting,
+    },
+    core::{
+        api::route::public_api,
+        r#async::TaskType,
+        lifecycle::{LifecycleHook, ordered, run_shutdown_hooks},
+        module::{Module, ScheduledJob},
+    },
+};
 
 use crate::setup::app::TestApp;
 
@@ -19,3 +37,228 @@ async fn test_app_state_is_cloneable() {
     let _db_1 = &app_state.db;
     let _db_2 = &cloned.db;
 }
+
+#[derive(Clone)]
+struct Greeting(&'static str);
+
+async fn greet(State(app_state): State<AppState>) -> String {
+    app_state.state::<Greeting>().unwrap().0.to_string()
+}
+
+#[tokio::test]
+async fn test_app_builder_serves_merged_routes_with_added_state() {
+    let test_app = TestApp::spawn_db_only().await;
+    let mut setting = test_app.setting.clone();
+    setting.app_port = 0;
+
+    let app = App::builder(setting)
+        .db(test_app.db.clone())
+        .merge_router(Router::new().route("/api/v1/greeting/", get(greet)))
+        .add_state(Greeting("hello"))
+        .build()
+        .await
+        .unwrap();
+    let base_url = app.base_url.clone();
+    tokio::spawn(app.run_until_stopped());
+
+    let response = reqwest::get(format!("http://{}/api/v1/greeting/", base_url))
+        .await
+        .unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "hello");
+}
+
+struct PingModule;
+
+impl Module for PingModule {
+    fn name(&self) -> &'static str {
+        "ping"
+    }
+
+    fn routes(&self, app_state: &AppState) -> Router<AppState> {
+        public_api(
+            Router::new().route("/api/v1/ping/", get(|| async { "pong" })),
+            app_state,
+        )
+    }
+
+    fn scheduled_jobs(&self) -> Vec<ScheduledJob> {
+        vec![ScheduledJob::new(
+            "ping",
+            "0 * * * * *",
+            TaskType::CleanupExpiredToken,
+        )]
+    }
+}
+
+#[tokio::test]
+async fn test_app_builder_registers_module_routes_and_jobs() {
+    let test_app = TestApp::spawn_db_only().await;
+    let mut setting = test_app.setting.clone();
+    setting.app_port = 0;
+
+    let builder = App::builder(setting)
+        .db(test_app.db.clone())
+        .module(PingModule);
+    let job_names: Vec<&str> = builder
+        .scheduled_jobs()
+        .iter()
+        .map(|job| job.name)
+        .collect();
+    assert!(job_names.contains(&"cleanup-orphaned-files"));
+    assert!(job_names.contains(&"send-notification-digests"));
+    assert!(job_names.contains(&"ping"));
+
+    let app = builder.build().await.unwrap();
+    let base_url = app.base_url.clone();
+    tokio::spawn(app.run_until_stopped());
+
+    let response = reqwest::get(format!("http://{}/api/v1/ping/", base_url))
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "pong");
+}
+
+struct RecordingHook {
+    name: &'static str,
+    order: i32,
+    delay: Duration,
+    fail: bool,
+    calls: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl RecordingHook {
+    fn new(name: &'static str, order: i32, calls: &Arc<Mutex<Vec<&'static str>>>) -> Self {
+        Self {
+            name,
+            order,
+            delay: Duration::ZERO,
+            fail: false,
+            calls: calls.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl LifecycleHook for RecordingHook {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn order(&self) -> i32 {
+        self.order
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(100)
+    }
+
+    async fn run(&self, _app_state: &AppState) -> anyhow::Result<()> {
+        tokio::time::sleep(self.delay).await;
+        self.calls.lock().unwrap().push(self.name);
+        if self.fail {
+            anyhow::bail!("{} failed", self.name);
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_app_builder_runs_startup_hooks_in_order() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let mut setting = test_app.setting.clone();
+    setting.app_port = 0;
+    let calls = Arc::new(Mutex::new(Vec::new()));
+
+    // Act
+    App::builder(setting)
+        .db(test_app.db.clone())
+        .on_startup(Arc::new(RecordingHook::new("templates", 10, &calls)))
+        .on_startup(Arc::new(RecordingHook::new("cache", 0, &calls)))
+        .on_startup(Arc::new(RecordingHook::new("consumers", 10, &calls)))
+        .build()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec!["cache", "templates", "consumers"]
+    );
+}
+
+#[tokio::test]
+async fn test_app_builder_fails_when_startup_hook_fails_or_times_out() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let mut setting = test_app.setting.clone();
+    setting.app_port = 0;
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let failing = RecordingHook {
+        fail: true,
+        ..RecordingHook::new("failing", 0, &calls)
+    };
+    let slow = RecordingHook {
+        delay: Duration::from_secs(5),
+        ..RecordingHook::new("slow", 0, &calls)
+    };
+
+    // Act
+    let failed = App::builder(setting.clone())
+        .db(test_app.db.clone())
+        .on_startup(Arc::new(failing))
+        .on_startup(Arc::new(RecordingHook::new("skipped", 1, &calls)))
+        .build()
+        .await;
+    let timed_out = App::builder(setting)
+        .db(test_app.db.clone())
+        .on_startup(Arc::new(slow))
+        .build()
+        .await;
+
+    // Assert
+    assert!(
+        failed
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("Startup hook failing failed")
+    );
+    assert!(
+        timed_out
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("Startup hook slow failed")
+    );
+    assert_eq!(*calls.lock().unwrap(), vec!["failing"]);
+}
+
+#[tokio::test]
+async fn test_shutdown_hooks_keep_running_after_a_failure() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let app_state = test_app.create_app_state();
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let failing = RecordingHook {
+        fail: true,
+        ..RecordingHook::new("failing", 0, &calls)
+    };
+    let slow = RecordingHook {
+        delay: Duration::from_secs(5),
+        ..RecordingHook::new("slow", 1, &calls)
+    };
+    let hooks = ordered(vec![
+        Arc::new(RecordingHook::new("flush", 2, &calls)) as Arc<dyn LifecycleHook>,
+        Arc::new(slow),
+        Arc::new(failing),
+    ]);
+
+    // Act
+    run_shutdown_hooks(&hooks, &app_state).await;
+
+    // Assert
+    assert_eq!(*calls.lock().unwrap(), vec!["failing", "flush"]);
+}
@@ -1 +1,5 @@
+pub mod test_outbox;
+pub mod test_pipeline;
+pub mod test_scheduler;
 pub mod test_task;
+pub mod test_task_lease;
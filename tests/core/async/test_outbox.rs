@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod outbox_tests {
+    use async_trait::async_trait;
+    use my_axum::{
+        common::entity::{dead_letter, outbox},
+        config::setting::OutboxSetting,
+        core::{
+            r#async::{Outbox, TaskType, publish_task},
+            context::Context,
+        },
+        pkg::messaging::{EncodedMessage, MessageProducer},
+    };
+    use sea_orm::{
+        ActiveModelTrait, ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, Set,
+        TransactionTrait,
+    };
+    use std::sync::{Arc, Mutex};
+
+    use crate::setup::app::TestApp;
+
+    struct FailingProducer;
+
+    #[async_trait]
+    impl MessageProducer for FailingProducer {
+        async fn publish(
+            &self,
+            _message: &EncodedMessage,
+            _destination: Option<&str>,
+        ) -> anyhow::Result<()> {
+            Err(anyhow::anyhow!("Broker unavailable"))
+        }
+    }
+
+    /// Publishes slowly, running the relay as another instance would once the entry's first
+    /// retry would have been due
+    struct RelayingProducer {
+        relay: Arc<Outbox>,
+        relayed: Arc<Mutex<Vec<usize>>>,
+    }
+
+    #[async_trait]
+    impl MessageProducer for RelayingProducer {
+        async fn publish(
+            &self,
+            _message: &EncodedMessage,
+            _destination: Option<&str>,
+        ) -> anyhow::Result<()> {
+            tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+            let relayed = self.relay.relay().await?;
+            self.relayed.lock().unwrap().push(relayed);
+            Ok(())
+        }
+    }
+
+    fn outbox(
+        test_app: &TestApp,
+        producer: Arc<Box<dyn MessageProducer>>,
+        max_attempts: u32,
+    ) -> Arc<Outbox> {
+        Arc::new(Outbox::new(
+            test_app.db.clone(),
+            producer,
+            OutboxSetting {
+                max_attempts,
+                ..test_app.setting.outbox.clone()
+            },
+        ))
+    }
+
+    async fn enqueue(test_app: &TestApp, outbox: &Arc<Outbox>) -> Context {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap()))
+            .outbox(outbox.clone())
+            .build();
+        publish_task(&context, TaskType::CleanupExpiredToken, Some("tasks"))
+            .await
+            .unwrap();
+        context
+    }
+
+    async fn entries(test_app: &TestApp) -> Vec<outbox::Model> {
+        outbox::Entity::find().all(&test_app.db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_enqueued_task_is_published_once_committed() {
+        let test_app = TestApp::spawn_app().await;
+        let outbox = outbox(&test_app, test_app.broker.producer(), 10);
+
+        let context = enqueue(&test_app, &outbox).await;
+        assert!(test_app.broker.tasks("tasks").is_empty());
+        context.commit().await.unwrap();
+
+        let tasks = test_app.broker.tasks("tasks");
+        let entries = entries(&test_app).await;
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].idempotency_key, tasks[0].id);
+        assert!(entries[0].sent_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_relay_skips_entry_published_after_commit() {
+        let test_app = TestApp::spawn_app().await;
+        let relayed = Arc::new(Mutex::new(Vec::new()));
+        let producer = RelayingProducer {
+            relay: outbox(&test_app, test_app.broker.producer(), 10),
+            relayed: relayed.clone(),
+        };
+        let publishing = Arc::new(Outbox::new(
+            test_app.db.clone(),
+            Arc::new(Box::new(producer)),
+            OutboxSetting {
+                retry_delay_seconds: 1,
+                ..test_app.setting.outbox.clone()
+            },
+        ));
+
+        enqueue(&test_app, &publishing)
+            .await
+            .commit()
+            .await
+            .unwrap();
+
+        assert_eq!(*relayed.lock().unwrap(), vec![0]);
+        assert!(test_app.broker.tasks("tasks").is_empty());
+        assert!(entries(&test_app).await[0].sent_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_rolled_back_task_is_never_published() {
+        let test_app = TestApp::spawn_app().await;
+        let outbox = outbox(&test_app, test_app.broker.producer(), 10);
+
+        let context = enqueue(&test_app, &outbox).await;
+        context.rollback().await.unwrap();
+
+        assert!(test_app.broker.tasks("tasks").is_empty());
+        assert!(entries(&test_app).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_relay_republishes_task_whose_publish_failed() {
+        let test_app = TestApp::spawn_app().await;
+        let failing = outbox(&test_app, Arc::new(Box::new(FailingProducer)), 10);
+
+        enqueue(&test_app, &failing).await.commit().await.unwrap();
+
+        let entry = entries(&test_app).await.remove(0);
+        assert_eq!(entry.attempts, 1);
+        assert_eq!(entry.last_error.as_deref(), Some("Broker unavailable"));
+        assert!(entry.sent_at.is_none());
+
+        // Nothing is due before the backoff ends
+        let relay = outbox(&test_app, test_app.broker.producer(), 10);
+        assert_eq!(relay.relay().await.unwrap(), 0);
+
+        let mut due = entry.clone().into_active_model();
+        due.next_attempt_at = Set(chrono::Utc::now().naive_utc());
+        due.update(&test_app.db).await.unwrap();
+        assert_eq!(relay.relay().await.unwrap(), 1);
+
+        let tasks = test_app.broker.tasks("tasks");
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, entry.idempotency_key);
+        assert!(entries(&test_app).await[0].sent_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_entry_moves_to_dead_letter_after_its_last_attempt() {
+        let test_app = TestApp::spawn_app().await;
+        let failing = outbox(&test_app, Arc::new(Box::new(FailingProducer)), 1);
+
+        enqueue(&test_app, &failing).await.commit().await.unwrap();
+
+        assert!(entries(&test_app).await.is_empty());
+        let dead = dead_letter::Entity::find()
+            .filter(dead_letter::Column::Topic.eq("tasks"))
+            .one(&test_app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(dead.attempts, 1);
+        assert_eq!(dead.task_type.as_deref(), Some("CleanupExpiredToken"));
+        assert!(dead.error.contains("Broker unavailable"));
+    }
+}
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod worker_pipeline_tests {
+    use my_axum::{
+        core::{r#async::TaskType, context::Context},
+        file::{
+            entity::{file, sea_orm_active_enums::FileStatus},
+            repository::file_repository,
+        },
+        pkg::storage::ObjectStorage,
+        user::entity::user,
+    };
+    use sea_orm::{ActiveValue::Set, TransactionTrait};
+    use std::sync::Arc;
+
+    use crate::setup::{app::TestApp, factory::UserFactory};
+
+    fn sample_png() -> Vec<u8> {
+        let mut output = std::io::Cursor::new(Vec::new());
+        image::RgbImage::new(200, 100)
+            .write_to(&mut output, image::ImageFormat::Png)
+            .unwrap();
+        output.into_inner()
+    }
+
+    async fn create_user(test_app: &TestApp) -> user::Model {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let user = UserFactory::new()
+            .first_name("Pipeline")
+            .create(&context)
+            .await
+            .unwrap();
+        context.commit().await.unwrap();
+        user
+    }
+
+    async fn create_pending_file(test_app: &TestApp, user_id: i32, key: &str) -> file::Model {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let file = file_repository::create(
+            &context,
+            file::ActiveModel {
+                user_id: Set(user_id),
+                key: Set(key.to_string()),
+                name: Set("avatar.png".to_string()),
+                size: Set(0),
+                status: Set(FileStatus::Pending),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        context.commit().await.unwrap();
+        file
+    }
+
+    async fn reload(test_app: &TestApp, file_id: i32) -> file::Model {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        file_repository::find_by_id(&context, file_id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_avatar_upload_pipeline_runs_follow_up_tasks() {
+        let test_app = TestApp::spawn_app().await;
+        let user = create_user(&test_app).await;
+        let file = create_pending_file(&test_app, user.id, "avatars/1/pipeline/avatar.png").await;
+        test_app
+            .broker
+            .storage()
+            .put(&file.key, &sample_png())
+            .await
+            .unwrap();
+
+        let run = test_app
+            .run_task(TaskType::ProcessAvatarUpload {
+                task_id: "pipeline".to_string(),
+                user_id: user.id,
+                file_name: "avatar.png".to_string(),
+                locale: "en".to_string(),
+                file_id: Some(file.id),
+            })
+            .await;
+
+        assert_eq!(run.result, Ok(()));
+        let completed = run.broadcasts_of("avatar_upload_complete");
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].data["task_id"], "pipeline");
+        assert!(!run.broadcasts_of("thumbnail_progress").is_empty());
+
+        let pipeline = run.broadcasts_of("pipeline_progress");
+        let progress: Vec<u64> = pipeline
+            .iter()
+            .map(|message| message.data["progress"].as_u64().unwrap())
+            .collect();
+        assert!(
+            progress.windows(2).all(|pair| pair[0] <= pair[1]),
+            "{:?}",
+            progress
+        );
+        assert_eq!(pipeline[0].data["stage"], "scanning");
+        assert_eq!(pipeline[0].data["progress"], 0);
+        let last = pipeline.last().unwrap();
+        assert_eq!(last.data["stage"], "thumbnails");
+        assert_eq!(last.data["progress"], 100);
+        assert_eq!(last.data["status"], "completed");
+        assert!(
+            pipeline[..pipeline.len() - 1]
+                .iter()
+                .all(|message| message.data["status"] == "processing")
+        );
+        assert!(run.tasks("tasks").iter().any(|event| matches!(
+            event.task,
+            TaskType::GenerateThumbnails { file_id, .. } if file_id == file.id
+        )));
+
+        let file = reload(&test_app, file.id).await;
+        assert_eq!(file.status, FileStatus::Available);
+        assert!(file.variants.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_registration_pipeline_delivers_welcome_email() {
+        let test_app = TestApp::spawn_app().await;
+        let user = create_user(&test_app).await;
+
+        let run = test_app
+            .run_task(TaskType::ProcessUserRegistration { user_id: user.id })
+            .await;
+
+        assert_eq!(run.result, Ok(()));
+        assert_eq!(run.tasks("emails").len(), 1);
+        let emails = test_app.emails().await;
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].to, user.email);
+    }
+
+    #[tokio::test]
+    async fn test_failed_task_reports_error_without_side_effects() {
+        let test_app = TestApp::spawn_app().await;
+
+        let run = test_app
+            .run_task(TaskType::GenerateThumbnails {
+                task_id: "missing".to_string(),
+                file_id: 999,
+            })
+            .await;
+
+        let error = run.result.as_ref().unwrap_err();
+        assert!(error.contains("File 999 not found"), "{}", error);
+        assert!(run.broadcasts().is_empty());
+    }
+}
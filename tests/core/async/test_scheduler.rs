@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod scheduler_tests {
+    use async_trait::async_trait;
+    use my_axum::{
+        config::{app::AppState, setting::SchedulerSetting},
+        core::r#async::{PeriodicJob, Scheduler},
+    };
+    use std::{
+        collections::HashMap,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use crate::setup::app::TestApp;
+
+    #[derive(Default)]
+    struct SlowJob {
+        runs: AtomicUsize,
+        running: AtomicUsize,
+        max_running: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PeriodicJob for SlowJob {
+        fn name(&self) -> &'static str {
+            "slow"
+        }
+
+        fn default_interval(&self) -> Duration {
+            Duration::from_millis(5)
+        }
+
+        async fn run(&self, _app_state: &AppState) -> anyhow::Result<()> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn scheduler_setting(intervals: HashMap<String, u64>) -> SchedulerSetting {
+        SchedulerSetting {
+            enabled: true,
+            jitter_seconds: 0,
+            intervals,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_runs_jobs_without_overlap_until_shutdown() {
+        let test_app = TestApp::spawn_db_only().await;
+        let app_state = test_app.create_app_state();
+        let job = Arc::new(SlowJob::default());
+
+        let handle = Scheduler::new(
+            vec![job.clone() as Arc<dyn PeriodicJob>],
+            scheduler_setting(HashMap::new()),
+        )
+        .spawn(app_state.clone());
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        app_state.shutdown_token.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(job.runs.load(Ordering::SeqCst) >= 2);
+        assert_eq!(job.max_running.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_skips_jobs_with_zero_interval() {
+        let test_app = TestApp::spawn_db_only().await;
+        let app_state = test_app.create_app_state();
+        let job = Arc::new(SlowJob::default());
+
+        let handle = Scheduler::new(
+            vec![job.clone() as Arc<dyn PeriodicJob>],
+            scheduler_setting(HashMap::from([("slow".to_string(), 0)])),
+        )
+        .spawn(app_state);
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(job.runs.load(Ordering::SeqCst), 0);
+    }
+}
@@ -19,7 +19,7 @@ use my_axum::{
         context::Context,
     },
     pkg::{
-        messaging::{MessageProducer, TaskHandler},
+        messaging::{EncodedMessage, MessageProducer, TaskHandler},
         password::hash_password_string,
     },
     user::entity::user,
@@ -54,11 +54,12 @@ impl MockProducer {
 
 #[async_trait]
 impl MessageProducer for MockProducer {
-    async fn publish_event_json(
+    async fn publish(
         &self,
-        event_json: &str,
+        message: &EncodedMessage,
         _destination: Option<&str>,
     ) -> anyhow::Result<()> {
+        let event_json = message.as_str();
         let event: TaskEvent = serde_json::from_str(event_json)
             .map_err(|e| anyhow::anyhow!("Failed to deserialize event: {}", e))?;
         self.published_events.lock().unwrap().push(event);
@@ -104,6 +105,7 @@ mod cleanup_expired_token_tests {
     use super::*;
     use chrono::{Duration, Utc};
     use my_axum::{
+        config::setting::Setting,
         core::r#async::{TaskEvent, TaskType},
         user::entity::refresh_token,
         user::repository::refresh_token_repository::{self, RefreshTokenSearchParams},
@@ -222,7 +224,10 @@ mod cleanup_expired_token_tests {
             .unwrap();
 
         assert_eq!(remaining_tokens.len(), 1);
-        assert_eq!(remaining_tokens[0].token, "valid_token_123");
+        assert_eq!(
+            remaining_tokens[0].token,
+            Setting::new().token_hasher().hash("valid_token_123")
+        );
     }
 
     #[tokio::test]
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod task_lease_tests {
+    use my_axum::{
+        common::entity::{dead_letter, task_event_log, task_lease, worker_heartbeat},
+        core::r#async::{PeriodicJob, RecoverExpiredTaskLeases, TaskEvent, TaskLeases, TaskType},
+    };
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use std::time::Duration;
+
+    use crate::setup::app::TestApp;
+
+    async fn find_lease(test_app: &TestApp, task_id: &str) -> Option<task_lease::Model> {
+        task_lease::Entity::find()
+            .filter(task_lease::Column::TaskId.eq(task_id))
+            .one(&test_app.db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_recover_republishes_task_of_expired_lease() {
+        let test_app = TestApp::spawn_app().await;
+        let expired = TaskLeases::new(test_app.db.clone(), "worker-a", Duration::ZERO);
+        let live = TaskLeases::new(test_app.db.clone(), "worker-b", Duration::from_secs(60));
+        let lost = TaskEvent::new(TaskType::CleanupExpiredToken);
+        let running = TaskEvent::new(TaskType::CleanupExpiredToken);
+        expired.claim(&lost).await;
+        live.claim(&running).await;
+
+        RecoverExpiredTaskLeases
+            .run(&test_app.create_app_state())
+            .await
+            .unwrap();
+
+        let republished = test_app.broker.tasks("tasks");
+        assert_eq!(republished.len(), 1);
+        assert_eq!(republished[0].id, lost.id);
+        assert_eq!(republished[0].retry_count, 1);
+        assert!(find_lease(&test_app, &lost.id).await.is_none());
+        assert!(find_lease(&test_app, &running.id).await.is_some());
+
+        let log = task_event_log::Entity::find()
+            .filter(task_event_log::Column::TaskId.eq(lost.id.as_str()))
+            .one(&test_app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(log.status, "retrying");
+        assert_eq!(log.worker_id.as_deref(), Some("worker-a"));
+    }
+
+    #[tokio::test]
+    async fn test_recover_dead_letters_task_without_attempts_left() {
+        let test_app = TestApp::spawn_app().await;
+        let leases = TaskLeases::new(test_app.db.clone(), "worker-a", Duration::ZERO);
+        let mut event = TaskEvent::new(TaskType::CleanupExpiredToken);
+        event.retry_count = event.max_retries;
+        leases.claim(&event).await;
+
+        RecoverExpiredTaskLeases
+            .run(&test_app.create_app_state())
+            .await
+            .unwrap();
+
+        assert!(test_app.broker.tasks("tasks").is_empty());
+        assert!(find_lease(&test_app, &event.id).await.is_none());
+        let dead_letter = dead_letter::Entity::find()
+            .filter(dead_letter::Column::TaskId.eq(event.id.as_str()))
+            .one(&test_app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(dead_letter.worker_id.as_deref(), Some("worker-a"));
+        assert!(dead_letter.error.contains("worker-a"));
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_registers_worker_and_renews_leases() {
+        let test_app = TestApp::spawn_app().await;
+        let leases = TaskLeases::new(test_app.db.clone(), "worker-a", Duration::from_secs(60));
+        let event = TaskEvent::new(TaskType::CleanupExpiredToken);
+        leases.claim(&event).await;
+        let claimed = find_lease(&test_app, &event.id).await.unwrap();
+
+        leases.heartbeat().await.unwrap();
+
+        let heartbeat = worker_heartbeat::Entity::find()
+            .filter(worker_heartbeat::Column::WorkerId.eq("worker-a"))
+            .one(&test_app.db)
+            .await
+            .unwrap();
+        assert!(heartbeat.is_some());
+        let renewed = find_lease(&test_app, &event.id).await.unwrap();
+        assert!(renewed.leased_until >= claimed.leased_until);
+
+        leases.deregister().await.unwrap();
+        let heartbeats = worker_heartbeat::Entity::find()
+            .all(&test_app.db)
+            .await
+            .unwrap();
+        assert!(heartbeats.is_empty());
+    }
+}
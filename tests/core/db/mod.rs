@@ -1 +1,2 @@
+mod test_isolation;
 mod test_uow;
@@ -0,0 +1,22 @@
+use reqwest::StatusCode;
+
+use crate::setup::app::TestApp;
+
+#[tokio::test]
+async fn test_spawned_apps_do_not_share_committed_data() {
+    let first_app = TestApp::spawn_app().await;
+    let second_app = TestApp::spawn_app().await;
+
+    let first = first_app.register_and_login("shared@example.com").await;
+    let second = second_app.register_and_login("shared@example.com").await;
+
+    assert_ne!(first_app.db_url, second_app.db_url);
+    assert_eq!(
+        first.get("/api/v1/user/profile/").await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        second.get("/api/v1/user/profile/").await.status(),
+        StatusCode::OK
+    );
+}
@@ -5,8 +5,12 @@ use my_axum::{
     config::{app::AppState, setting::Setting},
     core::{
         context::Context,
-        db::{connection::get_db, uow::new_transaction},
+        db::{
+            connection::get_db,
+            uow::{new_transaction, read_only},
+        },
         dto::{error_dto::ErrorDTO, response_dto::ResponseDTO},
+        id::RandomIdGenerator,
     },
     pkg::password::hash_password_string,
     user::entity::user,
@@ -131,7 +135,20 @@ async fn test_new_transaction_surfaces_begin_transaction_errors() {
         db: db.clone(),
         setting: Setting::new(),
         producer: None,
+        outbox: None,
         shutdown_token: CancellationToken::new(),
+        id_generator: Arc::new(RandomIdGenerator),
+        response_cache: None,
+        task_dedup: None,
+        task_quota: None,
+        login_attempts: None,
+        nonce_store: None,
+        redis: None,
+        http_client: Default::default(),
+        extensions: Default::default(),
+        event_bus: Default::default(),
+        deprecations: Default::default(),
+        drain: Default::default(),
     };
     db.close().await.unwrap();
 
@@ -146,3 +163,26 @@ async fn test_new_transaction_surfaces_begin_transaction_errors() {
         StatusCode::INTERNAL_SERVER_ERROR
     );
 }
+
+#[tokio::test]
+async fn test_read_only_runs_without_transaction() {
+    let test_app = TestApp::spawn_db_only().await;
+    let app_state = test_app.create_app_state();
+
+    let result: Result<ResponseDTO<bool>, ErrorDTO> =
+        read_only(&app_state, None, Some("vi".to_string()), |context| {
+            Box::pin(async move {
+                assert_eq!(context.locale, "vi");
+                let user = user_repository::find_by_id(context, 1)
+                    .await
+                    .map_err(ErrorDTO::map_internal_error)?;
+                Ok(ResponseDTO::new(
+                    StatusCode::OK,
+                    context.is_read_only() && user.is_none(),
+                ))
+            })
+        })
+        .await;
+
+    assert!(result.unwrap().data);
+}
@@ -0,0 +1,134 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use my_axum::{
+    core::{
+        context::Context,
+        event::{DomainEvent, EventBus, EventSubscriber},
+    },
+    user::{
+        dto::auth_dto::{ChangePasswordDTO, RegisterDTO},
+        use_case::auth::{change_password_use_case, register_use_case},
+    },
+};
+use reqwest::header::HeaderMap;
+
+use crate::setup::{
+    app::TestApp,
+    factory::{DEFAULT_PASSWORD, UserFactory},
+};
+
+#[derive(Default)]
+struct RecordingSubscriber {
+    events: Mutex<Vec<DomainEvent>>,
+}
+
+#[async_trait]
+impl EventSubscriber for RecordingSubscriber {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    async fn handle(&self, _context: &Context, event: &DomainEvent) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+struct FailingSubscriber;
+
+#[async_trait]
+impl EventSubscriber for FailingSubscriber {
+    fn name(&self) -> &'static str {
+        "failing"
+    }
+
+    async fn handle(&self, _context: &Context, _event: &DomainEvent) -> anyhow::Result<()> {
+        anyhow::bail!("subscriber is down")
+    }
+}
+
+#[test]
+fn test_default_bus_has_builtin_subscribers() {
+    assert_eq!(
+        EventBus::default().subscriber_names(),
+        vec!["audit_log", "welcome_email", "email_verification"]
+    );
+}
+
+#[test]
+fn test_events_serialize_with_type_tag() {
+    let event = DomainEvent::PasswordChanged { user_id: 7 };
+
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        serde_json::json!({"type": "password_changed", "user_id": 7})
+    );
+    assert_eq!(event.name(), "password_changed");
+    assert_eq!(event.user_id(), 7);
+}
+
+#[tokio::test]
+async fn test_register_emits_user_registered() {
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let recorder = Arc::new(RecordingSubscriber::default());
+    let context = Context::builder(Arc::new(txn))
+        .event_bus(Arc::new(EventBus::empty().with(recorder.clone())))
+        .build();
+
+    register_use_case::execute(
+        &context,
+        RegisterDTO {
+            email: "events@example.com".to_string(),
+            password: DEFAULT_PASSWORD.to_string(),
+            first_name: None,
+            last_name: None,
+            phone: None,
+        },
+        HeaderMap::new(),
+    )
+    .await
+    .unwrap();
+
+    let events = recorder.events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(
+        events[0],
+        DomainEvent::UserRegistered {
+            created_by: None,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_failing_subscriber_does_not_fail_use_case_or_others() {
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let recorder = Arc::new(RecordingSubscriber::default());
+    let mut context = Context::builder(Arc::new(txn))
+        .event_bus(Arc::new(
+            EventBus::empty()
+                .with(Arc::new(FailingSubscriber))
+                .with(recorder.clone()),
+        ))
+        .build();
+    let user = UserFactory::new().create(&context).await.unwrap();
+    context.user = Some(user.clone());
+
+    let result = change_password_use_case::execute(
+        &context,
+        ChangePasswordDTO {
+            old_password: DEFAULT_PASSWORD.to_string(),
+            new_password: "newpassword123@".to_string(),
+        },
+    )
+    .await;
+
+    assert!(result.is_ok());
+    assert_eq!(
+        *recorder.events.lock().unwrap(),
+        vec![DomainEvent::PasswordChanged { user_id: user.id }]
+    );
+}
@@ -1 +1,9 @@
+mod test_deprecation_layer;
+mod test_load_shed_layer;
+mod test_request_signing_layer;
+mod test_response_cache_layer;
+mod test_route_group_layer;
+mod test_service_auth_layer;
+mod test_statement_budget_layer;
+mod test_timeout_layer;
 mod test_transaction_layer;
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Method, Request, StatusCode},
+    middleware,
+    routing::get,
+};
+use chrono::{Duration, Utc};
+use my_axum::core::layer::deprecation_layer::{
+    DeprecationRegistry, RouteDeprecation, deprecation_middleware,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+fn app(registry: Arc<DeprecationRegistry>) -> Router {
+    Router::new()
+        .route("/api/v1/report/{id}/", get(|| async { "v1" }))
+        .route("/api/v2/report/{id}/", get(|| async { "v2" }))
+        .layer(middleware::from_fn_with_state(
+            registry,
+            deprecation_middleware,
+        ))
+}
+
+fn get_request(uri: &str) -> Request<Body> {
+    Request::builder()
+        .uri(uri)
+        .header("x-forwarded-for", "10.0.0.1")
+        .header("user-agent", "legacy-client/1.0")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_deprecated_route_is_served_with_headers() {
+    // Arrange
+    let registry = Arc::new(DeprecationRegistry::new(vec![
+        RouteDeprecation::new("/api/v1/report/*/", Utc::now() - Duration::days(1))
+            .method(Method::GET)
+            .sunset(Utc::now() + Duration::days(30))
+            .replacement("/api/v2/report/{id}/"),
+    ]));
+    let app = app(registry.clone());
+
+    // Act
+    let response = app
+        .clone()
+        .oneshot(get_request("/api/v1/report/7/"))
+        .await
+        .unwrap();
+    let current = app.oneshot(get_request("/api/v2/report/7/")).await.unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()["deprecation"]
+            .to_str()
+            .unwrap()
+            .starts_with('@')
+    );
+    assert!(
+        response.headers()["sunset"]
+            .to_str()
+            .unwrap()
+            .ends_with("GMT")
+    );
+    assert_eq!(
+        response.headers()["link"],
+        "</api/v2/report/{id}/>; rel=\"successor-version\""
+    );
+    assert!(!current.headers().contains_key("deprecation"));
+
+    let usage = &registry.usage()[0];
+    assert_eq!(usage.calls, 1);
+    assert_eq!(usage.clients[0].0.client_ip.as_deref(), Some("10.0.0.1"));
+    assert_eq!(
+        usage.clients[0].0.user_agent.as_deref(),
+        Some("legacy-client/1.0")
+    );
+}
+
+#[tokio::test]
+async fn test_route_past_its_sunset_is_gone() {
+    // Arrange
+    let registry = Arc::new(DeprecationRegistry::new(vec![
+        RouteDeprecation::new("/api/v1/report/*/", Utc::now() - Duration::days(60))
+            .sunset(Utc::now() - Duration::days(1))
+            .replacement("/api/v2/report/{id}/"),
+    ]));
+
+    // Act
+    let response = app(registry.clone())
+        .oneshot(get_request("/api/v1/report/7/"))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::GONE);
+    assert!(response.headers().contains_key("sunset"));
+    let body: Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["code"], "ROUTE_RETIRED");
+    assert_eq!(
+        body["message"],
+        "This endpoint has been retired, use /api/v2/report/{id}/ instead"
+    );
+    assert_eq!(registry.usage()[0].calls, 1);
+}
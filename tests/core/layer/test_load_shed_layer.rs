@@ -0,0 +1,132 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+};
+use my_axum::{
+    config::setting::LoadShedSetting,
+    core::layer::load_shed_layer::{LoadShedder, load_shed_middleware, shed_requests},
+};
+use tokio::sync::Notify;
+use tower::ServiceExt;
+
+fn load_shed_setting() -> LoadShedSetting {
+    LoadShedSetting {
+        enabled: true,
+        max_in_flight: 1,
+        min_in_flight: 1,
+        latency_target_ms: 500,
+        exempt_paths: vec!["/health/".to_string(), "/api/v1/tasks/*/poll/".to_string()],
+    }
+}
+
+/// Router whose `/slow/` and poll routes hold the request until `release` is notified
+fn app(shedder: Arc<LoadShedder>, release: Arc<Notify>) -> Router {
+    let slow = move || {
+        let release = release.clone();
+        async move {
+            release.notified().await;
+            "done"
+        }
+    };
+
+    Router::new()
+        .route("/slow/", get(slow.clone()))
+        .route("/fast/", get(|| async { "fast" }))
+        .route("/health/", get(|| async { "ok" }))
+        .route("/api/v1/tasks/{task_id}/poll/", get(slow))
+        .layer(middleware::from_fn_with_state(
+            shedder,
+            load_shed_middleware,
+        ))
+}
+
+fn get_request(uri: &str) -> Request<Body> {
+    Request::builder().uri(uri).body(Body::empty()).unwrap()
+}
+
+async fn wait_for_in_flight(shedder: &LoadShedder, count: usize) {
+    for _ in 0..100 {
+        if shedder.in_flight() == count {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("Expected {} requests in flight", count);
+}
+
+#[tokio::test]
+async fn test_sheds_requests_while_saturated() {
+    // Arrange: one slow request takes the only slot
+    let shedder = Arc::new(LoadShedder::new(&load_shed_setting()));
+    let release = Arc::new(Notify::new());
+    let app = app(shedder.clone(), release.clone());
+    let held = tokio::spawn(app.clone().oneshot(get_request("/slow/")));
+    wait_for_in_flight(&shedder, 1).await;
+    let shed_before = shed_requests();
+
+    // Act
+    let response = app.clone().oneshot(get_request("/fast/")).await.unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.headers()["retry-after"], "1");
+    assert!(shed_requests() > shed_before);
+
+    // Assert: the slot is freed once the slow request completes
+    release.notify_one();
+    assert_eq!(held.await.unwrap().unwrap().status(), StatusCode::OK);
+    wait_for_in_flight(&shedder, 0).await;
+    let response = app.oneshot(get_request("/fast/")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_exempt_paths_are_served_while_saturated() {
+    // Arrange: a long poll is exempt, so it doesn't take the slot
+    let shedder = Arc::new(LoadShedder::new(&load_shed_setting()));
+    let release = Arc::new(Notify::new());
+    let app = app(shedder.clone(), release.clone());
+    let poll = tokio::spawn(app.clone().oneshot(get_request("/api/v1/tasks/abc/poll/")));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(shedder.in_flight(), 0);
+    let held = tokio::spawn(app.clone().oneshot(get_request("/slow/")));
+    wait_for_in_flight(&shedder, 1).await;
+
+    // Act
+    let health = app.clone().oneshot(get_request("/health/")).await.unwrap();
+    let shed = app.clone().oneshot(get_request("/fast/")).await.unwrap();
+
+    // Assert
+    assert_eq!(health.status(), StatusCode::OK);
+    assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    release.notify_waiters();
+    assert_eq!(poll.await.unwrap().unwrap().status(), StatusCode::OK);
+    assert_eq!(held.await.unwrap().unwrap().status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_disabled_shedder_serves_everything() {
+    // Arrange
+    let shedder = Arc::new(LoadShedder::new(&LoadShedSetting {
+        enabled: false,
+        ..load_shed_setting()
+    }));
+    let release = Arc::new(Notify::new());
+    let app = app(shedder.clone(), release.clone());
+    let held = tokio::spawn(app.clone().oneshot(get_request("/slow/")));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Act
+    let response = app.oneshot(get_request("/fast/")).await.unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    release.notify_one();
+    assert_eq!(held.await.unwrap().unwrap().status(), StatusCode::OK);
+}
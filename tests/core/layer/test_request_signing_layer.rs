@@ -0,0 +1,180 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use my_axum::{
+    config::setting::RequestSigningSetting,
+    core::layer::request_signing_layer::{RequestSigning, request_signing_middleware},
+    pkg::{
+        cache::InMemoryNonceStore,
+        request_signing::{NONCE_HEADER, RequestSigner, SIGNATURE_HEADER, TIMESTAMP_HEADER},
+    },
+};
+use tower::ServiceExt;
+
+use crate::setup::app::TestApp;
+
+const SECRET: &str = "signing-secret";
+
+/// Router whose `/admin/` routes only accept signed requests
+fn app() -> Router {
+    let setting = RequestSigningSetting {
+        secret: Some(SECRET.to_string()),
+        routes: vec!["/admin/".to_string()],
+        tolerance_seconds: 300,
+    };
+    let signing = RequestSigning::new(&setting, Arc::new(InMemoryNonceStore::new())).unwrap();
+
+    Router::new()
+        .route("/admin/purge/", post(|body: String| async move { body }))
+        .route("/public/", post(|| async { "public" }))
+        .layer(middleware::from_fn_with_state(
+            Arc::new(signing),
+            request_signing_middleware,
+        ))
+}
+
+fn signed_request(uri: &str, body: &str, timestamp: i64, nonce: &str) -> Request<Body> {
+    let signature = RequestSigner::new(SECRET, Duration::from_secs(300)).sign(
+        timestamp,
+        nonce,
+        "POST",
+        uri,
+        body.as_bytes(),
+    );
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(SIGNATURE_HEADER, signature)
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(NONCE_HEADER, nonce)
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn unsigned_request(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_signed_request_reaches_handler_with_its_body() {
+    // Arrange
+    let app = app();
+    let now = chrono::Utc::now().timestamp();
+
+    // Act
+    let response = app
+        .oneshot(signed_request(
+            "/admin/purge/?dry=1",
+            "{\"a\":1}",
+            now,
+            "n1",
+        ))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"{\"a\":1}");
+}
+
+#[tokio::test]
+async fn test_unsigned_tampered_and_stale_requests_are_rejected() {
+    // Arrange
+    let app = app();
+    let now = chrono::Utc::now().timestamp();
+    let mut tampered = signed_request("/admin/purge/", "{}", now, "n1");
+    *tampered.body_mut() = Body::from("{\"all\":true}");
+
+    // Act
+    let unsigned = app
+        .clone()
+        .oneshot(unsigned_request("/admin/purge/"))
+        .await
+        .unwrap();
+    let tampered = app.clone().oneshot(tampered).await.unwrap();
+    let stale = app
+        .oneshot(signed_request("/admin/purge/", "{}", now - 600, "n2"))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(unsigned.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(tampered.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(stale.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_replayed_request_is_rejected() {
+    // Arrange
+    let app = app();
+    let now = chrono::Utc::now().timestamp();
+
+    // Act
+    let first = app
+        .clone()
+        .oneshot(signed_request("/admin/purge/", "{}", now, "n1"))
+        .await
+        .unwrap();
+    let replay = app
+        .oneshot(signed_request("/admin/purge/", "{}", now, "n1"))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(first.status(), StatusCode::OK);
+    assert_eq!(replay.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_routes_outside_signed_groups_accept_unsigned_requests() {
+    // Arrange
+    let app = app();
+
+    // Act
+    let response = app.oneshot(unsigned_request("/public/")).await.unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_app_enforces_signing_on_configured_route_groups() {
+    // Arrange
+    let test_app = TestApp::spawn_app_with_request_signing(SECRET, &["/health/"]).await;
+    let client = reqwest::Client::new();
+    let now = chrono::Utc::now().timestamp();
+    let signature = RequestSigner::new(SECRET, Duration::from_secs(300))
+        .sign(now, "n1", "GET", "/health/", b"");
+
+    // Act
+    let unsigned = client
+        .get(format!("http://{}/health/", test_app.base_url))
+        .send()
+        .await
+        .unwrap();
+    let signed = client
+        .get(format!("http://{}/health/", test_app.base_url))
+        .header(SIGNATURE_HEADER, signature)
+        .header(TIMESTAMP_HEADER, now.to_string())
+        .header(NONCE_HEADER, "n1")
+        .send()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(unsigned.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(signed.status(), StatusCode::OK);
+}
@@ -0,0 +1,105 @@
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+    middleware,
+    routing::get,
+};
+use my_axum::{
+    core::layer::response_cache_layer::response_cache_middleware,
+    pkg::cache::{InMemoryResponseCache, ResponseCache},
+};
+use reqwest::header::HeaderValue;
+use serde_json::{Value, json};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+use tower::ServiceExt;
+
+use crate::setup::app::TestApp;
+
+/// Router counting handler calls, answering `status` with the call count
+fn counting_router(
+    app_state: my_axum::config::app::AppState,
+    status: StatusCode,
+) -> (Router, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let router = Router::new()
+        .route(
+            "/items",
+            get(move || {
+                let counter = counter.clone();
+                async move {
+                    let calls = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                    (status, axum::Json(json!({ "calls": calls })))
+                }
+            })
+            .layer(middleware::from_fn_with_state(
+                (app_state.clone(), "items"),
+                response_cache_middleware,
+            )),
+        )
+        .with_state(app_state);
+    (router, calls)
+}
+
+async fn get_items(router: &Router, uri: &str) -> (Option<HeaderValue>, Value) {
+    let response = router
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let cache_status = response.headers().get("x-cache").cloned();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (cache_status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_response_cache_serves_repeated_requests() {
+    let test_app = TestApp::spawn_db_only().await;
+    let cache = Arc::new(InMemoryResponseCache::default());
+    let mut app_state = test_app.create_app_state();
+    app_state.response_cache = Some(cache.clone());
+    let (router, calls) = counting_router(app_state, StatusCode::OK);
+
+    let (first_status, first) = get_items(&router, "/items?page=1").await;
+    let (second_status, second) = get_items(&router, "/items?page=1").await;
+    let (_, other_query) = get_items(&router, "/items?page=2").await;
+    cache.invalidate("items").await.unwrap();
+    let (_, after_invalidation) = get_items(&router, "/items?page=1").await;
+
+    assert_eq!(first_status.unwrap(), "MISS");
+    assert_eq!(second_status.unwrap(), "HIT");
+    assert_eq!(first, second);
+    assert_eq!(other_query["calls"], 2);
+    assert_eq!(after_invalidation["calls"], 3);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_response_cache_skips_unsuccessful_responses() {
+    let test_app = TestApp::spawn_db_only().await;
+    let mut app_state = test_app.create_app_state();
+    app_state.response_cache = Some(Arc::new(InMemoryResponseCache::default()));
+    let (router, calls) = counting_router(app_state, StatusCode::NOT_FOUND);
+
+    get_items(&router, "/items").await;
+    let (cache_status, _) = get_items(&router, "/items").await;
+
+    assert!(cache_status.is_none());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_response_cache_is_bypassed_when_disabled() {
+    let test_app = TestApp::spawn_db_only().await;
+    let (router, calls) = counting_router(test_app.create_app_state(), StatusCode::OK);
+
+    get_items(&router, "/items").await;
+    let (cache_status, _) = get_items(&router, "/items").await;
+
+    assert!(cache_status.is_none());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,49 @@
+use reqwest::StatusCode;
+
+use crate::setup::app::TestApp;
+
+#[tokio::test]
+async fn test_disabled_route_groups_answer_not_found() {
+    // Arrange
+    let test_app = TestApp::spawn_app_with_disabled_route_groups(&["docs", "admin"]).await;
+    let client = reqwest::Client::new();
+
+    // Act
+    let openapi = client
+        .get(format!("http://{}/docs/openapi.json", test_app.base_url))
+        .send()
+        .await
+        .unwrap();
+    let admin = client
+        .get(format!(
+            "http://{}/api/v1/admin/audit-logs/",
+            test_app.base_url
+        ))
+        .send()
+        .await
+        .unwrap();
+    let health = client
+        .get(format!("http://{}/health/", test_app.base_url))
+        .send()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(openapi.status(), StatusCode::NOT_FOUND);
+    assert_eq!(admin.status(), StatusCode::NOT_FOUND);
+    assert_eq!(health.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_enabled_route_groups_stay_reachable() {
+    // Arrange
+    let test_app = TestApp::spawn_app_with_disabled_route_groups(&["mcp"]).await;
+
+    // Act
+    let openapi = reqwest::get(format!("http://{}/docs/openapi.json", test_app.base_url))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(openapi.status(), StatusCode::OK);
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::Body,
+    extract::connect_info::MockConnectInfo,
+    http::{Request, StatusCode},
+    middleware,
+};
+use my_axum::{
+    core::{
+        api::route::get_route, layer::service_auth_layer::service_auth_middleware,
+        service_account::ServiceAccounts,
+    },
+    pkg::mtls::ClientIdentity,
+};
+use tower::ServiceExt;
+
+use crate::setup::app::TestApp;
+
+/// The built-in routes as the mTLS listener serves them, to a client whose certificate
+/// carries `san`
+fn app(test_app: &TestApp, san: &str) -> Router {
+    let app_state = test_app.create_app_state();
+    let accounts = ServiceAccounts::parse(
+        "spiffe://test/autoscaler=autoscaler:workers.read,workers.write;spiffe://test/billing=billing",
+    )
+    .unwrap();
+
+    get_route(app_state.clone())
+        .with_state(app_state)
+        .layer(middleware::from_fn_with_state(
+            Arc::new(accounts),
+            service_auth_middleware,
+        ))
+        .layer(MockConnectInfo(ClientIdentity {
+            sans: vec!["client.internal".to_string(), san.to_string()],
+        }))
+}
+
+fn request(method: &str, uri: &str) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from("{}"))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_service_principal_reaches_routes_within_its_scopes() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+
+    // Act
+    let scaling = app(&test_app, "spiffe://test/autoscaler")
+        .oneshot(request("GET", "/api/v1/admin/workers/scaling/"))
+        .await
+        .unwrap();
+    let paused = app(&test_app, "spiffe://test/autoscaler")
+        .oneshot(request("POST", "/api/v1/admin/task-types/SendEmail/pause/"))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(scaling.status(), StatusCode::OK);
+    assert_eq!(paused.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_service_principal_without_scope_is_forbidden() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+
+    // Act
+    let response = app(&test_app, "spiffe://test/billing")
+        .oneshot(request("GET", "/api/v1/admin/workers/scaling/"))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_unmapped_client_certificate_is_unauthorized() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+
+    // Act
+    let response = app(&test_app, "spiffe://test/unknown")
+        .oneshot(request("GET", "/api/v1/admin/workers/scaling/"))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
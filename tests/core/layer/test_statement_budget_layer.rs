@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::Body,
+    extract::Extension,
+    http::{Request, StatusCode},
+    middleware,
+    routing::{get, post},
+};
+use my_axum::{
+    config::setting::StatementBudgetSetting,
+    core::{
+        context::Context,
+        layer::{
+            statement_budget_layer::{
+                STATEMENT_COUNT_HEADER, record_statement, statement_budget_middleware,
+            },
+            transaction_layer::transaction_middleware,
+        },
+    },
+    user::repository::user_repository,
+};
+use tower::ServiceExt;
+
+use crate::setup::{app::TestApp, factory::UserFactory};
+
+/// Router whose `/lookups/{n}/` route runs `n` queries and `/users/` creates a user first,
+/// on a connection counting statements like the app's
+fn app(test_app: &TestApp, budget: usize, hard_limit: bool) -> Router {
+    let mut app_state = test_app.create_app_state();
+    app_state.db.set_metric_callback(record_statement);
+    let setting = Arc::new(StatementBudgetSetting { budget, hard_limit });
+
+    Router::new()
+        .route(
+            "/lookups/{n}/",
+            get(
+                |Extension(context): Extension<Context>,
+                 axum::extract::Path(n): axum::extract::Path<i32>| async move {
+                    for id in 0..n {
+                        user_repository::find_by_id(&context, id).await.unwrap();
+                    }
+                    StatusCode::OK
+                },
+            ),
+        )
+        .route(
+            "/users/",
+            post(|Extension(context): Extension<Context>| async move {
+                UserFactory::new()
+                    .email("budget@example.com")
+                    .create(&context)
+                    .await
+                    .unwrap();
+                for id in 0..3 {
+                    user_repository::find_by_id(&context, id).await.unwrap();
+                }
+                StatusCode::CREATED
+            }),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            transaction_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            setting,
+            statement_budget_middleware,
+        ))
+        .with_state(app_state)
+}
+
+fn request(method: &str, uri: &str) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_request_within_budget_is_not_flagged() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let app = app(&test_app, 5, false);
+
+    // Act
+    let response = app.oneshot(request("GET", "/lookups/2/")).await.unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(STATEMENT_COUNT_HEADER).is_none());
+}
+
+#[tokio::test]
+async fn test_request_over_budget_is_flagged_with_its_statement_count() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let app = app(&test_app, 5, false);
+
+    // Act
+    let response = app.oneshot(request("GET", "/lookups/8/")).await.unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let count: usize = response.headers()[STATEMENT_COUNT_HEADER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(count >= 8);
+}
+
+#[tokio::test]
+async fn test_hard_limit_fails_request_and_rolls_back() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let app = app(&test_app, 2, true);
+
+    // Act
+    let response = app.oneshot(request("POST", "/users/")).await.unwrap();
+
+    // Assert
+    if !cfg!(debug_assertions) {
+        assert_eq!(response.status(), StatusCode::CREATED);
+        return;
+    }
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let context = Context::read_only(test_app.db.clone()).build();
+    assert!(
+        user_repository::find_by_email(&context, "budget@example.com")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
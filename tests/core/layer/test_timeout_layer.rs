@@ -0,0 +1,151 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    extract::Extension,
+    http::{Request, StatusCode},
+    middleware,
+    routing::{get, post},
+};
+use my_axum::{
+    config::setting::RequestTimeoutSetting,
+    core::{
+        context::Context,
+        layer::{
+            timeout_layer::request_timeout_middleware, transaction_layer::transaction_middleware,
+        },
+    },
+    user::repository::user_repository,
+};
+use sea_orm::TransactionTrait;
+use serde_json::Value;
+use tower::ServiceExt;
+
+use crate::setup::{app::TestApp, factory::UserFactory};
+
+fn timeouts(default_ms: u64, overrides: &[(&str, u64)]) -> Arc<RequestTimeoutSetting> {
+    Arc::new(RequestTimeoutSetting {
+        default_ms,
+        overrides: overrides
+            .iter()
+            .map(|(pattern, ms)| (pattern.to_string(), *ms))
+            .collect(),
+    })
+}
+
+/// Router whose `/slow/` and `/export/{id}/` routes take 300ms
+fn app(setting: Arc<RequestTimeoutSetting>) -> Router {
+    let slow = || async {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        "done"
+    };
+
+    Router::new()
+        .route("/slow/", get(slow))
+        .route("/export/{id}/", get(slow))
+        .route("/fast/", get(|| async { "fast" }))
+        .layer(middleware::from_fn_with_state(
+            setting,
+            request_timeout_middleware,
+        ))
+}
+
+fn get_request(uri: &str) -> Request<Body> {
+    Request::builder().uri(uri).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_slow_request_times_out_with_problem_details() {
+    // Arrange
+    let app = app(timeouts(50, &[]));
+
+    // Act
+    let response = app.oneshot(get_request("/slow/")).await.unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/problem+json"
+    );
+    let body: Value =
+        serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["status"], 504);
+    assert_eq!(body["code"], "REQUEST_TIMEOUT");
+    assert_eq!(body["title"], "Gateway Timeout");
+    assert_eq!(body["instance"], "/slow/");
+    assert!(body["detail"].as_str().unwrap().contains("0.05"));
+}
+
+#[tokio::test]
+async fn test_fast_request_is_served() {
+    let app = app(timeouts(50, &[]));
+
+    let response = app.oneshot(get_request("/fast/")).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_route_override_extends_the_timeout() {
+    // Arrange: exports get longer than the default, and 0 disables the timeout
+    let app = app(timeouts(50, &[("/export/*/", 1000), ("/slow/", 0)]));
+
+    // Act
+    let export = app
+        .clone()
+        .oneshot(get_request("/export/42/"))
+        .await
+        .unwrap();
+    let slow = app.oneshot(get_request("/slow/")).await.unwrap();
+
+    // Assert
+    assert_eq!(export.status(), StatusCode::OK);
+    assert_eq!(slow.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_timed_out_request_rolls_back_its_writes() {
+    // Arrange: a handler that writes, then hangs on a slow query
+    let test_app = TestApp::spawn_db_only().await;
+    let app_state = test_app.create_app_state();
+    let app = Router::new()
+        .route(
+            "/slow-write/",
+            post(|Extension(context): Extension<Context>| async move {
+                UserFactory::new()
+                    .email("timed-out@example.com")
+                    .create(&context)
+                    .await
+                    .unwrap();
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                StatusCode::CREATED
+            }),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            transaction_middleware,
+        ))
+        .with_state(app_state)
+        .layer(middleware::from_fn_with_state(
+            timeouts(100, &[]),
+            request_timeout_middleware,
+        ));
+
+    // Act
+    let response = app
+        .oneshot(Request::post("/slow-write/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+    assert!(
+        user_repository::find_by_email(&context, "timed-out@example.com")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
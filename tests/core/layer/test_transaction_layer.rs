@@ -91,3 +91,46 @@ async fn test_transaction_middleware_handles_cloned_context() {
 
     assert_eq!(response.status(), StatusCode::FOUND);
 }
+
+#[tokio::test]
+async fn test_transaction_middleware_uses_read_only_context_for_reads() {
+    let test_app = TestApp::spawn_db_only().await;
+    let app_state = test_app.create_app_state();
+
+    let app = Router::new()
+        .route(
+            "/test",
+            get(|Extension(ctx): Extension<Context>| async move {
+                assert!(ctx.is_read_only());
+                StatusCode::OK
+            })
+            .post(|Extension(ctx): Extension<Context>| async move {
+                assert!(!ctx.is_read_only());
+                StatusCode::CREATED
+            }),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            transaction_middleware,
+        ))
+        .with_state(app_state);
+
+    let read = app
+        .clone()
+        .oneshot(Request::builder().uri("/test").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let write = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/test")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(read.status(), StatusCode::OK);
+    assert_eq!(write.status(), StatusCode::CREATED);
+}
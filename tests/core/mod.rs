@@ -1,6 +1,8 @@
 mod api;
 mod r#async;
 mod db;
+mod event;
 mod layer;
 mod runbook;
 mod test_context;
+mod test_fixture;
@@ -1,2 +1,3 @@
+mod test_account_archive;
 mod test_runbook;
 mod test_seed;
@@ -0,0 +1,214 @@
+use axum::http::StatusCode;
+use my_axum::{
+    core::runbook,
+    file::entity::{file, sea_orm_active_enums::FileStatus},
+    notification::entity::{notification_preference, sea_orm_active_enums::NotificationCategory},
+    user::entity::user,
+};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use serde_json::json;
+
+use crate::setup::app::TestApp;
+
+async fn insert_user(db: &DatabaseConnection, email: &str, first_name: &str) -> user::Model {
+    user::Entity::insert(user::ActiveModel {
+        email: Set(email.to_string()),
+        normalized_email: Set(email.to_string()),
+        password: Set("hashed".to_string()),
+        first_name: Set(Some(first_name.to_string())),
+        ..Default::default()
+    })
+    .exec_with_returning(db)
+    .await
+    .unwrap()
+}
+
+async fn insert_file(db: &DatabaseConnection, user_id: i32, key: &str) -> file::Model {
+    file::Entity::insert(file::ActiveModel {
+        user_id: Set(user_id),
+        key: Set(key.to_string()),
+        name: Set("avatar.png".to_string()),
+        content_type: Set(Some("image/png".to_string())),
+        size: Set(42),
+        status: Set(FileStatus::Available),
+        ..Default::default()
+    })
+    .exec_with_returning(db)
+    .await
+    .unwrap()
+}
+
+/// Export a user with an avatar and a preference from a fresh environment
+async fn export_source_user() -> String {
+    let source = TestApp::spawn_db_only().await;
+    let user = insert_user(&source.db, "ada@example.com", "Ada").await;
+    let avatar = insert_file(&source.db, user.id, "avatars/ada.png").await;
+    user::Entity::update(user::ActiveModel {
+        id: Set(user.id),
+        avatar_file_id: Set(Some(avatar.id)),
+        ..Default::default()
+    })
+    .exec(&source.db)
+    .await
+    .unwrap();
+    notification_preference::Entity::insert(notification_preference::ActiveModel {
+        user_id: Set(user.id),
+        category: Set(NotificationCategory::Digest),
+        channels: Set(json!(["email"])),
+        ..Default::default()
+    })
+    .exec(&source.db)
+    .await
+    .unwrap();
+
+    runbook::run(
+        &source.setting,
+        "export-user",
+        &["--email".to_string(), "ada@example.com".to_string()],
+    )
+    .await
+    .unwrap()
+    .message
+}
+
+fn import_args(archive: &str, extra: &[&str]) -> Vec<String> {
+    let mut args = vec!["--archive".to_string(), archive.to_string()];
+    args.extend(extra.iter().map(|arg| arg.to_string()));
+    args
+}
+
+#[tokio::test]
+async fn test_import_user_remaps_ids_and_avatar() {
+    // Arrange
+    let archive = export_source_user().await;
+    let target = TestApp::spawn_db_only().await;
+    let other = insert_user(&target.db, "other@example.com", "Other").await;
+    insert_file(&target.db, other.id, "avatars/other.png").await;
+
+    // Act
+    let result = runbook::run(&target.setting, "import-user", &import_args(&archive, &[]))
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(result.message.contains("1 preference(s) and 1 file(s)"));
+    let imported = user::Entity::find()
+        .filter(user::Column::Email.eq("ada@example.com"))
+        .one(&target.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_ne!(imported.id, other.id);
+    assert_eq!(imported.password, "hashed");
+    let avatar = file::Entity::find_by_id(imported.avatar_file_id.unwrap())
+        .one(&target.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(avatar.key, "avatars/ada.png");
+    assert_eq!(avatar.user_id, imported.id);
+    let preferences = notification_preference::Entity::find()
+        .filter(notification_preference::Column::UserId.eq(imported.id))
+        .all(&target.db)
+        .await
+        .unwrap();
+    assert_eq!(preferences.len(), 1);
+    assert_eq!(preferences[0].channels, json!(["email"]));
+}
+
+#[tokio::test]
+async fn test_import_user_handles_existing_email_by_conflict_mode() {
+    // Arrange
+    let archive = export_source_user().await;
+    let target = TestApp::spawn_db_only().await;
+    let existing = insert_user(&target.db, "ada@example.com", "Existing").await;
+
+    // Act
+    let failed = runbook::run(&target.setting, "import-user", &import_args(&archive, &[]))
+        .await
+        .unwrap_err();
+    let skipped = runbook::run(
+        &target.setting,
+        "import-user",
+        &import_args(&archive, &["--on-conflict", "skip"]),
+    )
+    .await
+    .unwrap();
+    let first_name_after_skip = user::Entity::find_by_id(existing.id)
+        .one(&target.db)
+        .await
+        .unwrap()
+        .unwrap()
+        .first_name;
+    runbook::run(
+        &target.setting,
+        "import-user",
+        &import_args(&archive, &["--on-conflict", "merge"]),
+    )
+    .await
+    .unwrap();
+
+    // Assert
+    assert_eq!(failed.status, StatusCode::CONFLICT);
+    assert!(skipped.message.contains("Skipped import"));
+    assert_eq!(first_name_after_skip.as_deref(), Some("Existing"));
+    let merged = user::Entity::find_by_id(existing.id)
+        .one(&target.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(merged.first_name.as_deref(), Some("Ada"));
+    assert!(merged.avatar_file_id.is_some());
+    let users = user::Entity::find().all(&target.db).await.unwrap();
+    assert_eq!(users.len(), 1);
+}
+
+#[tokio::test]
+async fn test_import_user_under_another_email_skips_files_owned_by_others() {
+    // Arrange
+    let archive = export_source_user().await;
+    let target = TestApp::spawn_db_only().await;
+    let original = insert_user(&target.db, "ada@example.com", "Ada").await;
+    insert_file(&target.db, original.id, "avatars/ada.png").await;
+
+    // Act
+    let result = runbook::run(
+        &target.setting,
+        "import-user",
+        &import_args(&archive, &["--email", "ada.copy@example.com"]),
+    )
+    .await
+    .unwrap();
+
+    // Assert
+    assert!(result.message.contains("skipped 1 file(s)"));
+    let copy = user::Entity::find()
+        .filter(user::Column::Email.eq("ada.copy@example.com"))
+        .one(&target.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_ne!(copy.id, original.id);
+    assert!(copy.avatar_file_id.is_none());
+}
+
+#[tokio::test]
+async fn test_import_user_rejects_newer_archive_versions() {
+    // Arrange
+    let mut archive: serde_json::Value = serde_json::from_str(&export_source_user().await).unwrap();
+    archive["version"] = json!(runbook::account_archive::ARCHIVE_VERSION + 1);
+    let target = TestApp::spawn_db_only().await;
+
+    // Act
+    let error = runbook::run(
+        &target.setting,
+        "import-user",
+        &import_args(&archive.to_string(), &[]),
+    )
+    .await
+    .unwrap_err();
+
+    // Assert
+    assert_eq!(error.status, StatusCode::BAD_REQUEST);
+    assert!(error.message.contains("Unsupported archive version"));
+}
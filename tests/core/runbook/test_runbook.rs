@@ -1,7 +1,7 @@
 use chrono::{Duration, Utc};
 use my_axum::{
     core::runbook,
-    user::entity::{refresh_token, user},
+    user::entity::{refresh_token, sea_orm_active_enums::UserRole, signing_key, user},
 };
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set};
 
@@ -47,6 +47,7 @@ async fn test_delete_refresh_tokens_runbook_removes_only_target_users_tokens() {
 
     let target_user = user::Entity::insert(user::ActiveModel {
         email: Set("target@example.com".to_string()),
+        normalized_email: Set("target@example.com".to_string()),
         password: Set("hashed".to_string()),
         ..Default::default()
     })
@@ -55,6 +56,7 @@ async fn test_delete_refresh_tokens_runbook_removes_only_target_users_tokens() {
     .unwrap();
     let other_user = user::Entity::insert(user::ActiveModel {
         email: Set("other@example.com".to_string()),
+        normalized_email: Set("other@example.com".to_string()),
         password: Set("hashed".to_string()),
         ..Default::default()
     })
@@ -105,3 +107,52 @@ async fn test_delete_refresh_tokens_runbook_removes_only_target_users_tokens() {
     assert!(target_tokens.is_empty());
     assert_eq!(other_tokens.len(), 1);
 }
+
+#[tokio::test]
+async fn test_create_admin_runbook_is_idempotent() {
+    let test_app = TestApp::spawn_db_only().await;
+    let args = [
+        "--email".to_string(),
+        "root@example.com".to_string(),
+        "--password".to_string(),
+        "secret123@".to_string(),
+    ];
+
+    let created = runbook::run(&test_app.setting, "create-admin", &args)
+        .await
+        .unwrap();
+    let updated = runbook::run(&test_app.setting, "create-admin", &args)
+        .await
+        .unwrap();
+
+    assert!(created.message.contains("Created admin user"));
+    assert!(updated.message.contains("Updated admin user"));
+    let admins = user::Entity::find()
+        .filter(user::Column::Email.eq("root@example.com"))
+        .all(&test_app.db)
+        .await
+        .unwrap();
+    assert_eq!(admins.len(), 1);
+    assert_eq!(admins[0].role, UserRole::Admin);
+}
+
+#[tokio::test]
+async fn test_rotate_jwt_secret_retires_previous_key() {
+    let test_app = TestApp::spawn_db_only().await;
+
+    let first = runbook::rotate_jwt_secret(&test_app.setting, Duration::hours(1))
+        .await
+        .unwrap();
+    let second = runbook::rotate_jwt_secret(&test_app.setting, Duration::hours(1))
+        .await
+        .unwrap();
+
+    assert_eq!(first.retired, 0);
+    assert_eq!(second.retired, 1);
+    assert_ne!(first.key.kid, second.key.kid);
+    let keys = signing_key::Entity::find().all(&test_app.db).await.unwrap();
+    let previous = keys.iter().find(|key| key.kid == first.key.kid).unwrap();
+    let active = keys.iter().find(|key| key.kid == second.key.kid).unwrap();
+    assert!(previous.retires_at.unwrap() > Utc::now().naive_utc());
+    assert!(active.retires_at.is_none());
+}
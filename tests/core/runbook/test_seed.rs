@@ -56,6 +56,7 @@ async fn test_seed_runbook_preserves_existing_user() {
 
     user::Entity::insert(user::ActiveModel {
         email: Set("user@example.com".to_string()),
+        normalized_email: Set("user@example.com".to_string()),
         password: Set("existing_hash".to_string()),
         first_name: Set(Some("Existing".to_string())),
         ..Default::default()
@@ -76,3 +77,20 @@ async fn test_seed_runbook_preserves_existing_user() {
     assert_eq!(existing.first_name.as_deref(), Some("Existing"));
     assert_eq!(existing.password, "existing_hash");
 }
+
+#[tokio::test]
+async fn test_seed_runbook_loads_named_fixture() {
+    let test_app = TestApp::spawn_db_only().await;
+
+    let result = runbook::run(
+        &test_app.setting,
+        "seed",
+        &["--fixture".to_string(), "basic_users".to_string()],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.message, "Seeded 5 record(s)");
+    let users = user::Entity::find().all(&test_app.db).await.unwrap();
+    assert_eq!(users.len(), 3);
+}
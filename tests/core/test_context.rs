@@ -1,8 +1,21 @@
 use std::sync::Arc;
 
-use my_axum::core::context::Context;
+use my_axum::{
+    core::{
+        context::Context,
+        dto::error_code::ErrorCode,
+        permission::PermissionSet,
+        policy::{Action, Resource},
+    },
+    pkg::password::hash_password_string,
+    user::{
+        entity::user,
+        repository::{permission_repository, user_repository},
+    },
+};
+use sea_orm::ActiveValue::Set;
 
-use crate::setup::app::TestApp;
+use crate::setup::{app::TestApp, factory::UserFactory, fixture::grant_permissions};
 
 #[tokio::test]
 async fn test_context_creation_without_user_or_producer() {
@@ -43,3 +56,162 @@ async fn test_context_commit_succeeds_when_uniquely_owned() {
 
     context.commit().await.unwrap();
 }
+
+#[tokio::test]
+async fn test_read_only_context_reads_without_transaction() {
+    let test_app = TestApp::spawn_db_only().await;
+    let context = Context::read_only(test_app.db.clone()).build();
+
+    let user = user_repository::find_by_email(&context, "missing@example.com")
+        .await
+        .unwrap();
+
+    assert!(context.is_read_only());
+    assert!(user.is_none());
+    context.commit().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_read_only_context_rejects_writes() {
+    let test_app = TestApp::spawn_db_only().await;
+    let context = Context::read_only(test_app.db.clone()).build();
+    let user = user::ActiveModel {
+        email: Set("read-only@example.com".to_string()),
+        password: Set(hash_password_string("password123@").await.unwrap()),
+        ..Default::default()
+    };
+
+    let result = user_repository::create(&context, user).await;
+
+    assert!(result.is_err());
+    assert!(
+        user_repository::find_by_email(&context, "read-only@example.com")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+fn record(effects: &Arc<std::sync::Mutex<Vec<&'static str>>>, name: &'static str) {
+    effects.lock().unwrap().push(name);
+}
+
+#[tokio::test]
+async fn test_after_commit_runs_effects_once_committed() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let txn = test_app.begin_transaction().await;
+    let context = Context::builder(Arc::new(txn)).build();
+    let effects = Arc::new(std::sync::Mutex::new(Vec::new()));
+    for name in ["publish", "invalidate"] {
+        let effects = effects.clone();
+        context.after_commit(async move {
+            record(&effects, name);
+            Ok(())
+        });
+    }
+    assert!(effects.lock().unwrap().is_empty());
+
+    // Act
+    context.commit().await.unwrap();
+
+    // Assert
+    assert_eq!(*effects.lock().unwrap(), vec!["publish", "invalidate"]);
+}
+
+#[tokio::test]
+async fn test_after_commit_drops_effects_on_rollback() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let txn = test_app.begin_transaction().await;
+    let context = Context::builder(Arc::new(txn)).build();
+    let effects = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = effects.clone();
+    context.after_commit(async move {
+        record(&recorded, "publish");
+        Ok(())
+    });
+
+    // Act
+    context.rollback().await.unwrap();
+
+    // Assert
+    assert!(effects.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_after_commit_of_savepoint_waits_for_outer_transaction() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let txn = test_app.begin_transaction().await;
+    let context = Context::builder(Arc::new(txn)).build();
+    let effects = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let committed = context.nested().await.unwrap();
+    let recorded = effects.clone();
+    committed.after_commit(async move {
+        record(&recorded, "committed savepoint");
+        Ok(())
+    });
+    committed.commit().await.unwrap();
+    let after_savepoint = effects.lock().unwrap().clone();
+
+    let rolled_back = context.nested().await.unwrap();
+    let recorded = effects.clone();
+    rolled_back.after_commit(async move {
+        record(&recorded, "rolled back savepoint");
+        Ok(())
+    });
+    rolled_back.rollback().await.unwrap();
+
+    // Act
+    context.commit().await.unwrap();
+
+    // Assert
+    assert!(after_savepoint.is_empty());
+    assert_eq!(*effects.lock().unwrap(), vec!["committed savepoint"]);
+}
+
+#[tokio::test]
+async fn test_permissions_authorize_beyond_the_policy() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let txn = test_app.begin_transaction().await;
+    let context = Context::builder(Arc::new(txn)).build();
+    let user = UserFactory::new().create(&context).await.unwrap();
+    let other = UserFactory::new().create(&context).await.unwrap();
+    grant_permissions(&context, user.id, &["user.delete"]).await;
+    context.commit().await.unwrap();
+
+    // Act
+    let lookup = Context::read_only(test_app.db.clone()).build();
+    let permissions: PermissionSet = permission_repository::find_names_by_user_id(&lookup, user.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .collect();
+    let context = Context::read_only(test_app.db.clone())
+        .user(user)
+        .permissions(permissions)
+        .build();
+
+    // Assert
+    assert!(context.has_permission("user.delete"));
+    assert!(context.require_permission("user.delete").is_ok());
+    assert_eq!(
+        context.require_permission("user.bulk").unwrap_err().code,
+        ErrorCode::AuthPermissionRequired
+    );
+    assert!(
+        context
+            .authorize(Action::Delete, &Resource::user(other.id))
+            .is_ok()
+    );
+    assert_eq!(
+        context
+            .authorize(Action::Update, &Resource::user(other.id))
+            .unwrap_err()
+            .code,
+        ErrorCode::AuthForbidden
+    );
+}
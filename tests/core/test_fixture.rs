@@ -0,0 +1,78 @@
+use std::{path::Path, sync::Arc};
+
+use my_axum::{
+    core::{
+        context::Context,
+        fixture::{self, Fixture},
+    },
+    user::{
+        entity::{refresh_token, sea_orm_active_enums::UserRole, user},
+        repository::{permission_repository, refresh_token_repository},
+    },
+};
+use sea_orm::EntityTrait;
+
+use crate::setup::app::TestApp;
+
+#[tokio::test]
+async fn test_load_fixture_keeps_ids_and_resolves_references() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+
+    // Act
+    let loaded = test_app.load_fixture("basic_users").await;
+    let reloaded = test_app.load_fixture("basic_users").await;
+
+    // Assert
+    assert_eq!(loaded.created, 5);
+    assert_eq!(reloaded.created, 0);
+    assert_eq!(loaded.user_id("admin"), 1);
+    assert_eq!(loaded.user_id("alice"), 3);
+    assert_eq!(reloaded.user_id("alice"), 3);
+
+    let admin = user::Entity::find_by_id(1)
+        .one(&test_app.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(admin.role, UserRole::Admin);
+
+    let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+    let support_permissions =
+        permission_repository::find_names_by_user_id(&context, loaded.user_id("support"))
+            .await
+            .unwrap();
+    assert!(support_permissions.contains(&"user.list".to_string()));
+    let token = refresh_token_repository::find_by_token(&context, "alice-laptop-refresh-token")
+        .await
+        .unwrap()
+        .expect("the refresh token should be stored hashed");
+    assert_eq!(token.user_id, loaded.user_id("alice"));
+    assert_eq!(token.id, loaded.refresh_token_id("alice_laptop"));
+    assert_eq!(
+        refresh_token::Entity::find()
+            .all(context.txn())
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_load_fixture_rejects_unknown_reference() {
+    // Arrange
+    let test_app = TestApp::spawn_db_only().await;
+    let fixture = Fixture::parse(
+        Path::new("broken.json"),
+        r#"{"users": [{"key": "bob", "email": "bob@example.com", "password": "secret", "roles": ["missing"]}]}"#,
+    )
+    .unwrap();
+    let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+
+    // Act
+    let error = fixture::load(&context, &fixture).await.unwrap_err();
+
+    // Assert
+    assert!(error.to_string().contains("unknown role 'missing'"));
+}
@@ -0,0 +1,2 @@
+mod test_avatar_api;
+mod test_file_api;
@@ -0,0 +1,396 @@
+use std::sync::Arc;
+
+use my_axum::{
+    core::{r#async::TaskType, context::Context},
+    file::{
+        entity::{
+            file,
+            sea_orm_active_enums::{FileStatus, ModerationStatus},
+        },
+        repository::file_repository,
+    },
+    pkg::storage::ObjectStorage,
+    user::repository::user_repository,
+};
+use reqwest::StatusCode;
+use sea_orm::ActiveValue::Set;
+use serde_json::{Value, json};
+
+use crate::setup::{
+    app::TestApp,
+    fixture::{login_admin_user, login_normal_user},
+};
+
+async fn access_token(test_app: &TestApp, admin: bool) -> (String, i32) {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    let user_id = context.user.as_ref().unwrap().id;
+    context.commit().await.unwrap();
+    (access_token, user_id)
+}
+
+/// Store an avatar of `user_id` whose content is `marker`, shown on the profile when `current`
+/// is set
+async fn create_avatar(
+    test_app: &TestApp,
+    user_id: i32,
+    marker: &str,
+    status: FileStatus,
+    moderation_status: ModerationStatus,
+    current: bool,
+) -> file::Model {
+    let key = format!("avatars/{}/{}/avatar.png", user_id, marker);
+    test_app
+        .setting
+        .get_storage()
+        .put(&key, marker.as_bytes())
+        .await
+        .unwrap();
+    let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+    let file = file_repository::create(
+        &context,
+        file::ActiveModel {
+            user_id: Set(user_id),
+            key: Set(key),
+            content_type: Set(Some("image/png".to_string())),
+            name: Set(format!("{}.png", marker)),
+            size: Set(6),
+            status: Set(status),
+            moderation_status: Set(Some(moderation_status)),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    if current {
+        user_repository::set_avatar_file_id(&context, user_id, Some(file.id))
+            .await
+            .unwrap();
+    }
+    context.commit().await.unwrap();
+    file
+}
+
+async fn post(test_app: &TestApp, access_token: &str, path: &str, body: Value) -> StatusCode {
+    reqwest::Client::new()
+        .post(format!(
+            "http://{}/api/v1/admin/avatars/{}",
+            test_app.base_url, path
+        ))
+        .bearer_auth(access_token)
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn get(test_app: &TestApp, access_token: &str, path: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!("http://{}{}", test_app.base_url, path))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn profile(test_app: &TestApp, access_token: &str) -> Value {
+    get(test_app, access_token, "/api/v1/user/profile/")
+        .await
+        .json()
+        .await
+        .unwrap()
+}
+
+/// Open the signed `url` on the test server, which doesn't listen on `STORAGE_BASE_URL`
+async fn open_media(test_app: &TestApp, url: &str, referer: Option<&str>) -> reqwest::Response {
+    let path = &url[url.find("/media/").unwrap()..];
+    let mut request = reqwest::Client::new().get(format!("http://{}{}", test_app.base_url, path));
+    if let Some(referer) = referer {
+        request = request.header("referer", referer);
+    }
+    request.send().await.unwrap()
+}
+
+/// Content of the avatar shown on the profile
+async fn avatar_content(test_app: &TestApp, access_token: &str) -> String {
+    let profile = profile(test_app, access_token).await;
+    open_media(test_app, profile["avatar_url"].as_str().unwrap(), None)
+        .await
+        .text()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_approve_avatar_replaces_profile_avatar() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (user_token, user_id) = access_token(&test_app, false).await;
+    let (admin_token, _) = access_token(&test_app, true).await;
+    create_avatar(
+        &test_app,
+        user_id,
+        "previousavatar",
+        FileStatus::Available,
+        ModerationStatus::Approved,
+        true,
+    )
+    .await;
+    let pending = create_avatar(
+        &test_app,
+        user_id,
+        "newavatar",
+        FileStatus::Available,
+        ModerationStatus::Pending,
+        false,
+    )
+    .await;
+    let before = avatar_content(&test_app, &user_token).await;
+
+    // Act
+    let listed: Value = get(&test_app, &admin_token, "/api/v1/admin/avatars/pending/")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let status = post(
+        &test_app,
+        &admin_token,
+        &format!("{}/approve/", pending.id),
+        json!({}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(listed["count"], 1);
+    assert_eq!(listed["items"][0]["id"], pending.id);
+    assert_eq!(listed["items"][0]["user_id"], user_id);
+    assert!(listed["items"][0]["download_url"].is_string());
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(before, "previousavatar");
+    assert_eq!(avatar_content(&test_app, &user_token).await, "newavatar");
+    let files: Value = get(&test_app, &user_token, "/api/v1/users/me/files/")
+        .await
+        .json()
+        .await
+        .unwrap();
+    let approved = files["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["id"] == pending.id)
+        .unwrap();
+    assert_eq!(approved["moderation_status"], "approved");
+}
+
+#[tokio::test]
+async fn test_reject_avatar_keeps_previous_one_and_notifies_owner() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (user_token, user_id) = access_token(&test_app, false).await;
+    let (admin_token, _) = access_token(&test_app, true).await;
+    create_avatar(
+        &test_app,
+        user_id,
+        "previousavatar",
+        FileStatus::Available,
+        ModerationStatus::Approved,
+        true,
+    )
+    .await;
+    let pending = create_avatar(
+        &test_app,
+        user_id,
+        "newavatar",
+        FileStatus::Available,
+        ModerationStatus::Pending,
+        false,
+    )
+    .await;
+
+    // Act
+    let status = post(
+        &test_app,
+        &admin_token,
+        &format!("{}/reject/", pending.id),
+        json!({ "reason": "Blurry picture." }),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(
+        avatar_content(&test_app, &user_token).await,
+        "previousavatar"
+    );
+    let emails: Vec<_> = test_app
+        .broker
+        .tasks("emails")
+        .into_iter()
+        .filter_map(|event| match event.task {
+            TaskType::SendEmail { to, html_body, .. } if to == "user@example.com" => html_body,
+            _ => None,
+        })
+        .collect();
+    assert_eq!(emails.len(), 1);
+    assert!(emails[0].contains("Blurry picture."));
+    let listed: Value = get(&test_app, &admin_token, "/api/v1/admin/avatars/pending/")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listed["count"], 0);
+    assert_eq!(
+        post(
+            &test_app,
+            &admin_token,
+            &format!("{}/approve/", pending.id),
+            json!({}),
+        )
+        .await,
+        StatusCode::CONFLICT
+    );
+}
+
+#[tokio::test]
+async fn test_approve_avatar_waits_for_processing() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (_, user_id) = access_token(&test_app, false).await;
+    let (admin_token, _) = access_token(&test_app, true).await;
+    let scanning = create_avatar(
+        &test_app,
+        user_id,
+        "scanningavatar",
+        FileStatus::Pending,
+        ModerationStatus::Pending,
+        false,
+    )
+    .await;
+
+    // Act
+    let status = post(
+        &test_app,
+        &admin_token,
+        &format!("{}/approve/", scanning.id),
+        json!({}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_avatar_moderation_requires_admin() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (user_token, user_id) = access_token(&test_app, false).await;
+    let pending = create_avatar(
+        &test_app,
+        user_id,
+        "ownavatar",
+        FileStatus::Available,
+        ModerationStatus::Pending,
+        false,
+    )
+    .await;
+
+    // Act
+    let listed = get(&test_app, &user_token, "/api/v1/admin/avatars/pending/").await;
+    let approved = post(
+        &test_app,
+        &user_token,
+        &format!("{}/approve/", pending.id),
+        json!({}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(listed.status(), StatusCode::FORBIDDEN);
+    assert_eq!(approved, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_avatar_media_links_hide_storage_keys_and_expire_from_cache() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (user_token, user_id) = access_token(&test_app, false).await;
+    let avatar = create_avatar(
+        &test_app,
+        user_id,
+        "myavatar",
+        FileStatus::Available,
+        ModerationStatus::Approved,
+        true,
+    )
+    .await;
+    let thumbnail_key = format!("avatars/{}/myavatar/thumbnails/64.png", user_id);
+    test_app
+        .setting
+        .get_storage()
+        .put(&thumbnail_key, b"thumbnail")
+        .await
+        .unwrap();
+    let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+    let mut active_avatar: file::ActiveModel = avatar.into();
+    active_avatar.variants = Set(Some(json!({ "64": thumbnail_key })));
+    file_repository::update(&context, active_avatar)
+        .await
+        .unwrap();
+    context.commit().await.unwrap();
+
+    // Act
+    let profile = profile(&test_app, &user_token).await;
+    let avatar_url = profile["avatar_url"].as_str().unwrap();
+    let thumbnail_url = profile["avatar_thumbnail_urls"]["64"].as_str().unwrap();
+    let original = open_media(&test_app, avatar_url, None).await;
+    let thumbnail = open_media(&test_app, thumbnail_url, None).await;
+    let tampered = open_media(&test_app, &avatar_url.replace("/original?", "/64?"), None).await;
+
+    // Assert
+    assert!(avatar_url.contains(&format!("/media/avatars/{}/original?", user_id)));
+    assert!(!avatar_url.contains("myavatar"));
+    assert_eq!(original.status(), StatusCode::OK);
+    assert_eq!(original.headers()["content-type"], "image/png");
+    let cache_control = original.headers()["cache-control"].to_str().unwrap();
+    assert!(cache_control.starts_with("private, max-age="));
+    assert_eq!(original.text().await.unwrap(), "myavatar");
+    assert_eq!(thumbnail.status(), StatusCode::OK);
+    assert_eq!(thumbnail.text().await.unwrap(), "thumbnail");
+    assert_eq!(tampered.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_avatar_media_refuses_other_sites() {
+    // Arrange
+    let test_app = TestApp::spawn_app_with_media_referers(&["https://app.example.com"]).await;
+    let (user_token, user_id) = access_token(&test_app, false).await;
+    create_avatar(
+        &test_app,
+        user_id,
+        "myavatar",
+        FileStatus::Available,
+        ModerationStatus::Approved,
+        true,
+    )
+    .await;
+    let profile = profile(&test_app, &user_token).await;
+    let avatar_url = profile["avatar_url"].as_str().unwrap();
+
+    // Act
+    let own_site = open_media(&test_app, avatar_url, Some("https://app.example.com/me")).await;
+    let other_site = open_media(&test_app, avatar_url, Some("https://other.test/page")).await;
+
+    // Assert
+    assert_eq!(own_site.status(), StatusCode::OK);
+    assert_eq!(other_site.status(), StatusCode::FORBIDDEN);
+    let body: Value = other_site.json().await.unwrap();
+    assert_eq!(body["code"], "FILE_HOTLINK_FORBIDDEN");
+}
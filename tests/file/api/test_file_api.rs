@@ -0,0 +1,190 @@
+use std::sync::Arc;
+
+use my_axum::{
+    core::context::Context,
+    file::{
+        entity::{file, sea_orm_active_enums::FileStatus},
+        repository::file_repository,
+    },
+    pkg::storage::ObjectStorage,
+};
+use reqwest::StatusCode;
+use sea_orm::ActiveValue::Set;
+use serde_json::Value;
+
+use crate::setup::{
+    app::TestApp,
+    fixture::{login_admin_user, login_normal_user},
+};
+
+/// Sign in a user owning a stored file with `status`, returning the access token and file
+async fn user_with_file(
+    test_app: &TestApp,
+    admin: bool,
+    status: FileStatus,
+) -> (String, file::Model) {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    let user_id = context.user.as_ref().unwrap().id;
+    let key = format!("avatars/{}/{}/avatar.png", user_id, uuid::Uuid::new_v4());
+    test_app
+        .setting
+        .get_storage()
+        .put(&key, b"avatar")
+        .await
+        .unwrap();
+    let file = file_repository::create(
+        &context,
+        file::ActiveModel {
+            user_id: Set(user_id),
+            key: Set(key),
+            name: Set("avatar.png".to_string()),
+            content_type: Set(Some("image/png".to_string())),
+            size: Set(6),
+            status: Set(status),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    context.commit().await.unwrap();
+    (access_token, file)
+}
+
+async fn search(test_app: &TestApp, access_token: &str) -> Value {
+    reqwest::Client::new()
+        .get(format!(
+            "http://{}/api/v1/users/me/files/",
+            test_app.base_url
+        ))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+async fn delete(test_app: &TestApp, access_token: &str, id: i32) -> reqwest::Response {
+    reqwest::Client::new()
+        .delete(format!(
+            "http://{}/api/v1/users/me/files/{}/",
+            test_app.base_url, id
+        ))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+/// Open `download_url` on the test server, which doesn't listen on `STORAGE_BASE_URL`
+async fn download(test_app: &TestApp, download_url: &str) -> reqwest::Response {
+    let (_, query) = download_url.split_once('?').unwrap();
+    reqwest::Client::new()
+        .get(format!(
+            "http://{}/api/v1/files/download/?{}",
+            test_app.base_url, query
+        ))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_search_files_with_download_links() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (access_token, file) = user_with_file(&test_app, false, FileStatus::Available).await;
+
+    // Act
+    let body = search(&test_app, &access_token).await;
+
+    // Assert
+    assert_eq!(body["count"], 1);
+    let item = &body["items"][0];
+    assert_eq!(item["id"], file.id);
+    assert_eq!(item["name"], "avatar.png");
+    assert_eq!(item["status"], "available");
+    let response = download(&test_app, item["download_url"].as_str().unwrap()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    assert_eq!(response.bytes().await.unwrap().as_ref(), b"avatar");
+}
+
+#[tokio::test]
+async fn test_search_files_without_link_for_quarantined_file() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (access_token, _) = user_with_file(&test_app, false, FileStatus::Quarantined).await;
+
+    // Act
+    let body = search(&test_app, &access_token).await;
+
+    // Assert
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["items"][0]["status"], "quarantined");
+    assert!(body["items"][0]["download_url"].is_null());
+}
+
+#[tokio::test]
+async fn test_download_rejects_tampered_link() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (access_token, _) = user_with_file(&test_app, false, FileStatus::Available).await;
+    let body = search(&test_app, &access_token).await;
+    let download_url = body["items"][0]["download_url"].as_str().unwrap();
+
+    // Act
+    let response = download(&test_app, &download_url.replace("avatar.png", "other.png")).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_delete_file_removes_record_and_object() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (access_token, file) = user_with_file(&test_app, false, FileStatus::Available).await;
+    let download_url = search(&test_app, &access_token).await["items"][0]["download_url"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Act
+    let response = delete(&test_app, &access_token, file.id).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(search(&test_app, &access_token).await["count"], 0);
+    assert!(
+        !test_app
+            .setting
+            .get_storage()
+            .exists(&file.key)
+            .await
+            .unwrap()
+    );
+    let response = download(&test_app, &download_url).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_delete_file_of_other_user_is_not_found() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (_, file) = user_with_file(&test_app, false, FileStatus::Available).await;
+    let (admin_token, _) = user_with_file(&test_app, true, FileStatus::Available).await;
+
+    // Act
+    let response = delete(&test_app, &admin_token, file.id).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
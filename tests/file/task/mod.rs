@@ -0,0 +1 @@
+mod test_file_task;
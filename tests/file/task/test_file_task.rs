@@ -0,0 +1,517 @@
+#[cfg(test)]
+mod file_task_tests {
+    use async_trait::async_trait;
+    use my_axum::{
+        core::{
+            r#async::{TaskEvent, TaskType},
+            context::Context,
+        },
+        file::{
+            entity::{
+                file,
+                sea_orm_active_enums::{FileStatus, ModerationStatus},
+            },
+            repository::file_repository,
+            task::file_task::{
+                OrphanedFileReport, QUARANTINE_PREFIX, cleanup_orphaned_files,
+                collect_orphaned_files, generate_thumbnails, mark_available, scan_upload,
+                send_orphaned_file_report,
+            },
+        },
+        pkg::{
+            antivirus::{ScanVerdict, VirusScanner},
+            broadcast::websocket::BroadcastMessage,
+            messaging::{EncodedMessage, MessageProducer},
+            storage::{LocalStorage, ObjectStorage},
+        },
+        user::{
+            dto::user_dto::UserCreateDTO, entity::user, repository::user_repository,
+            use_case::user::create_user_use_case,
+        },
+    };
+    use sea_orm::{ActiveValue::Set, IntoActiveModel, TransactionTrait};
+    use std::sync::{Arc, Mutex};
+
+    use crate::setup::{app::TestApp, factory::UserFactory};
+
+    #[derive(Clone, Default)]
+    struct TrackingProducer {
+        messages: Arc<Mutex<Vec<String>>>,
+        emails: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl MessageProducer for TrackingProducer {
+        async fn publish(
+            &self,
+            message: &EncodedMessage,
+            destination: Option<&str>,
+        ) -> anyhow::Result<()> {
+            let event_json = message.as_str();
+            match destination {
+                Some("broadcasts") => self.messages.lock().unwrap().push(event_json.to_string()),
+                Some("emails") => self.emails.lock().unwrap().push(event_json.to_string()),
+                other => panic!("Unexpected destination {:?}", other),
+            }
+            Ok(())
+        }
+    }
+
+    struct StaticScanner(ScanVerdict);
+
+    #[async_trait]
+    impl VirusScanner for StaticScanner {
+        async fn scan(&self, _data: &[u8]) -> anyhow::Result<ScanVerdict> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn temp_storage() -> LocalStorage {
+        LocalStorage::new(std::env::temp_dir().join(format!("file-task-{}", uuid::Uuid::new_v4())))
+    }
+
+    fn sample_png(width: u32, height: u32) -> Vec<u8> {
+        let mut output = std::io::Cursor::new(Vec::new());
+        image::RgbImage::new(width, height)
+            .write_to(&mut output, image::ImageFormat::Png)
+            .unwrap();
+        output.into_inner()
+    }
+
+    async fn create_file(test_app: &TestApp, key: &str) -> file::Model {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let user = create_user_use_case::execute(
+            &context,
+            UserCreateDTO {
+                email: format!("file{}@example.com", uuid::Uuid::new_v4()),
+                password: "password123@".to_string(),
+                first_name: None,
+                last_name: None,
+                phone: None,
+            },
+        )
+        .await
+        .unwrap()
+        .data;
+
+        let file = file_repository::create(
+            &context,
+            file::ActiveModel {
+                user_id: Set(user.id),
+                key: Set(key.to_string()),
+                name: Set("avatar.png".to_string()),
+                size: Set(4),
+                status: Set(FileStatus::Pending),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        context.commit().await.unwrap();
+        file
+    }
+
+    async fn create_admin(test_app: &TestApp, email: &str) -> user::Model {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let admin = UserFactory::admin()
+            .email(email)
+            .create(&context)
+            .await
+            .unwrap();
+        context.commit().await.unwrap();
+        admin
+    }
+
+    async fn reload(test_app: &TestApp, file_id: i32) -> file::Model {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        file_repository::find_by_id(&context, file_id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_scan_upload_quarantines_infected_file() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let storage = temp_storage();
+        let producer = TrackingProducer::default();
+        let file = create_file(&test_app, "avatars/1/task/avatar.png").await;
+        storage.put(&file.key, b"evil").await.unwrap();
+        let scanner = StaticScanner(ScanVerdict::Infected {
+            signature: "Eicar-Test-Signature".to_string(),
+        });
+
+        // Act
+        let verdict = scan_upload(
+            &test_app.db,
+            &producer,
+            &storage,
+            Some(&scanner),
+            "task-1",
+            file.id,
+            "en",
+        )
+        .await
+        .unwrap();
+
+        // Assert
+        assert!(!verdict.is_clean());
+
+        let quarantined = reload(&test_app, file.id).await;
+        let quarantine_key = format!("{}/{}", QUARANTINE_PREFIX, file.key);
+        assert_eq!(quarantined.status, FileStatus::Quarantined);
+        assert_eq!(quarantined.key, quarantine_key);
+        assert!(!storage.exists(&file.key).await.unwrap());
+        assert!(storage.exists(&quarantine_key).await.unwrap());
+
+        let messages = producer.messages.lock().unwrap();
+        assert_eq!(messages.len(), 2);
+        let task_msg: BroadcastMessage = serde_json::from_str(&messages[0]).unwrap();
+        let user_msg: BroadcastMessage = serde_json::from_str(&messages[1]).unwrap();
+        assert_eq!(task_msg.event_type, "file_quarantined");
+        assert_eq!(task_msg.data["task_id"], "task-1");
+        assert_eq!(user_msg.data["user_id"], file.user_id);
+        assert!(user_msg.data.get("task_id").is_none());
+        assert!(
+            user_msg.data["message"]
+                .as_str()
+                .unwrap()
+                .contains("Eicar-Test-Signature")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_upload_leaves_clean_file_pending() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let storage = temp_storage();
+        let producer = TrackingProducer::default();
+        let file = create_file(&test_app, "avatars/1/clean/avatar.png").await;
+        storage.put(&file.key, b"good").await.unwrap();
+
+        // Act
+        let verdict = scan_upload(
+            &test_app.db,
+            &producer,
+            &storage,
+            Some(&StaticScanner(ScanVerdict::Clean)),
+            "task-2",
+            file.id,
+            "en",
+        )
+        .await
+        .unwrap();
+
+        // Assert
+        assert!(verdict.is_clean());
+        assert_eq!(reload(&test_app, file.id).await.status, FileStatus::Pending);
+        assert!(storage.exists(&file.key).await.unwrap());
+        assert!(producer.messages.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scan_upload_skips_without_scanner() {
+        let test_app = TestApp::spawn_app().await;
+        let producer = TrackingProducer::default();
+
+        let verdict = scan_upload(
+            &test_app.db,
+            &producer,
+            &temp_storage(),
+            None,
+            "task-3",
+            999999,
+            "en",
+        )
+        .await
+        .unwrap();
+
+        assert!(verdict.is_clean());
+    }
+
+    #[tokio::test]
+    async fn test_mark_available_updates_pending_file() {
+        let test_app = TestApp::spawn_app().await;
+        let file = create_file(&test_app, "avatars/1/available/avatar.png").await;
+
+        mark_available(&test_app.db, file.id).await.unwrap();
+
+        assert_eq!(
+            reload(&test_app, file.id).await.status,
+            FileStatus::Available
+        );
+    }
+
+    async fn create_avatar(test_app: &TestApp, key: &str, status: ModerationStatus) -> file::Model {
+        let file = create_file(test_app, key).await;
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let mut active_file = file.into_active_model();
+        active_file.moderation_status = Set(Some(status));
+        let file = file_repository::update(&context, active_file)
+            .await
+            .unwrap();
+        context.commit().await.unwrap();
+        file
+    }
+
+    async fn avatar_of(test_app: &TestApp, user_id: i32) -> Option<i32> {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        user_repository::find_by_id(&context, user_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .avatar_file_id
+    }
+
+    #[tokio::test]
+    async fn test_mark_available_shows_approved_avatar_only() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let approved = create_avatar(
+            &test_app,
+            "avatars/1/approved/avatar.png",
+            ModerationStatus::Approved,
+        )
+        .await;
+        let pending = create_avatar(
+            &test_app,
+            "avatars/2/pending/avatar.png",
+            ModerationStatus::Pending,
+        )
+        .await;
+
+        // Act
+        mark_available(&test_app.db, approved.id).await.unwrap();
+        mark_available(&test_app.db, pending.id).await.unwrap();
+
+        // Assert
+        assert_eq!(
+            avatar_of(&test_app, approved.user_id).await,
+            Some(approved.id)
+        );
+        assert_eq!(avatar_of(&test_app, pending.user_id).await, None);
+        assert_eq!(
+            reload(&test_app, pending.id).await.status,
+            FileStatus::Available
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_thumbnails_stores_variants_and_reports_progress() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let storage = temp_storage();
+        let producer = TrackingProducer::default();
+        let file = create_file(&test_app, "avatars/1/thumbs/avatar.png").await;
+        storage.put(&file.key, &sample_png(300, 150)).await.unwrap();
+        mark_available(&test_app.db, file.id).await.unwrap();
+
+        // Act
+        generate_thumbnails(
+            &test_app.db,
+            &producer,
+            &storage,
+            "task-4",
+            file.id,
+            &[32, 64],
+        )
+        .await
+        .unwrap();
+
+        // Assert
+        let updated = reload(&test_app, file.id).await;
+        let variants = updated.variants.unwrap();
+        assert_eq!(variants["32"], "avatars/1/thumbs/thumbnails/32.png");
+        assert_eq!(variants["64"], "avatars/1/thumbs/thumbnails/64.png");
+
+        let thumbnail = storage
+            .get("avatars/1/thumbs/thumbnails/64.png")
+            .await
+            .unwrap()
+            .unwrap();
+        let decoded = image::load_from_memory(&thumbnail).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (64, 32));
+
+        let messages = producer.messages.lock().unwrap();
+        let (events, pipeline): (Vec<BroadcastMessage>, Vec<BroadcastMessage>) = messages
+            .iter()
+            .map(|message| serde_json::from_str::<BroadcastMessage>(message).unwrap())
+            .partition(|e| e.event_type == "thumbnail_progress");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].data["progress"], 50);
+        assert_eq!(events[0].data["status"], "processing");
+        assert_eq!(events[1].data["progress"], 100);
+        assert_eq!(events[1].data["status"], "completed");
+        assert_eq!(events[1].data["task_id"], "task-4");
+
+        assert!(pipeline.iter().all(|e| e.event_type == "pipeline_progress"));
+        assert_eq!(pipeline.len(), 2);
+        assert_eq!(pipeline[0].data["stage"], "thumbnails");
+        assert_eq!(pipeline[0].data["progress"], 85);
+        assert_eq!(pipeline[1].data["progress"], 100);
+        assert_eq!(pipeline[1].data["status"], "completed");
+    }
+
+    #[tokio::test]
+    async fn test_generate_thumbnails_skips_unavailable_file() {
+        let test_app = TestApp::spawn_app().await;
+        let storage = temp_storage();
+        let producer = TrackingProducer::default();
+        let file = create_file(&test_app, "avatars/1/pending/avatar.png").await;
+
+        generate_thumbnails(&test_app.db, &producer, &storage, "task-5", file.id, &[32])
+            .await
+            .unwrap();
+
+        assert!(reload(&test_app, file.id).await.variants.is_none());
+        assert!(producer.messages.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collect_orphaned_files_deletes_unreferenced_objects() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let storage = temp_storage();
+        let file = create_file(&test_app, "avatars/1/kept/avatar.png").await;
+        storage.put(&file.key, b"data").await.unwrap();
+        storage
+            .put("avatars/1/kept/thumbnails/64.png", b"thumb")
+            .await
+            .unwrap();
+        storage
+            .put("avatars/1/stray/avatar.png", b"stray")
+            .await
+            .unwrap();
+        storage
+            .put("reports/admin-report.csv", b"report")
+            .await
+            .unwrap();
+
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let mut active_file: file::ActiveModel = file.clone().into();
+        active_file.variants = Set(Some(serde_json::json!({
+            "64": "avatars/1/kept/thumbnails/64.png"
+        })));
+        file_repository::update(&context, active_file)
+            .await
+            .unwrap();
+        context.commit().await.unwrap();
+
+        // Act
+        let report = collect_orphaned_files(&test_app.db, &storage, chrono::Duration::zero())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(report.deleted_objects, vec!["avatars/1/stray/avatar.png"]);
+        assert!(report.missing_files.is_empty());
+        assert!(storage.exists(&file.key).await.unwrap());
+        assert!(
+            storage
+                .exists("avatars/1/kept/thumbnails/64.png")
+                .await
+                .unwrap()
+        );
+        assert!(!storage.exists("avatars/1/stray/avatar.png").await.unwrap());
+        assert!(storage.exists("reports/admin-report.csv").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_collect_orphaned_files_flags_missing_objects() {
+        let test_app = TestApp::spawn_app().await;
+        let storage = temp_storage();
+        let file = create_file(&test_app, "avatars/1/gone/avatar.png").await;
+
+        let report = collect_orphaned_files(&test_app.db, &storage, chrono::Duration::zero())
+            .await
+            .unwrap();
+        let second_run = collect_orphaned_files(&test_app.db, &storage, chrono::Duration::zero())
+            .await
+            .unwrap();
+
+        assert_eq!(report.missing_files.len(), 1);
+        assert_eq!(report.missing_files[0].id, file.id);
+        assert_eq!(reload(&test_app, file.id).await.status, FileStatus::Missing);
+        assert!(second_run.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collect_orphaned_files_respects_grace_period() {
+        let test_app = TestApp::spawn_app().await;
+        let storage = temp_storage();
+        create_file(&test_app, "avatars/1/new/avatar.png").await;
+        storage
+            .put("avatars/1/fresh/avatar.png", b"fresh")
+            .await
+            .unwrap();
+
+        let report = collect_orphaned_files(&test_app.db, &storage, chrono::Duration::hours(1))
+            .await
+            .unwrap();
+
+        assert!(report.is_empty());
+        assert!(storage.exists("avatars/1/fresh/avatar.png").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_orphaned_files_keeps_recent_objects() {
+        let test_app = TestApp::spawn_app().await;
+        let storage = temp_storage();
+        let producer = TrackingProducer::default();
+        create_admin(&test_app, "gc-admin@example.com").await;
+        storage.put("orphan.bin", b"orphan").await.unwrap();
+
+        let report = cleanup_orphaned_files(&test_app.db, &producer, &storage)
+            .await
+            .unwrap();
+
+        assert!(report.is_empty());
+        assert!(producer.emails.lock().unwrap().is_empty());
+        assert!(storage.exists("orphan.bin").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_send_orphaned_file_report_emails_admins() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let storage = temp_storage();
+        let producer = TrackingProducer::default();
+        create_admin(&test_app, "gc-admin@example.com").await;
+        let file = create_file(&test_app, "avatars/1/report/avatar.png").await;
+        storage.put("orphan.bin", b"orphan").await.unwrap();
+        let report = collect_orphaned_files(&test_app.db, &storage, chrono::Duration::zero())
+            .await
+            .unwrap();
+
+        // Act
+        send_orphaned_file_report(&test_app.db, &producer, &report)
+            .await
+            .unwrap();
+
+        // Assert: only the admin receives the report
+        let emails = producer.emails.lock().unwrap().clone();
+        assert_eq!(emails.len(), 1);
+        let event: TaskEvent = serde_json::from_str(&emails[0]).unwrap();
+        let TaskType::SendEmail { to, html_body, .. } = event.task else {
+            panic!("Expected a SendEmail task");
+        };
+        assert_eq!(to, "gc-admin@example.com");
+        let html_body = html_body.unwrap();
+        assert!(html_body.contains("orphan.bin"));
+        assert!(html_body.contains(&format!("#{} ", file.id)));
+    }
+
+    #[tokio::test]
+    async fn test_send_orphaned_file_report_skips_empty_report() {
+        let test_app = TestApp::spawn_app().await;
+        let producer = TrackingProducer::default();
+        create_admin(&test_app, "gc-admin@example.com").await;
+
+        send_orphaned_file_report(&test_app.db, &producer, &OrphanedFileReport::default())
+            .await
+            .unwrap();
+
+        assert!(producer.emails.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,4 @@
+mod test_kafka_tuning;
+mod test_messaging;
+mod test_postgres;
+mod test_redis;
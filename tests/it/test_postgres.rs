@@ -0,0 +1,32 @@
+use my_axum::{
+    core::{context::Context, db::connection::get_db},
+    user::repository::user_repository,
+};
+use sea_orm::TransactionTrait;
+use std::sync::Arc;
+
+use crate::setup::{app::TestApp, containers::start_postgres, factory::UserFactory};
+
+#[tokio::test]
+async fn test_schema_and_repositories_on_postgres() {
+    let postgres = start_postgres().await;
+    let db = get_db(&postgres.url).await.unwrap();
+    TestApp::create_schema_from_entities(&db).await.unwrap();
+
+    let context = Context::builder(Arc::new(db.begin().await.unwrap())).build();
+    let admin = UserFactory::admin()
+        .email("pg-admin@example.com")
+        .create(&context)
+        .await
+        .unwrap();
+    context.commit().await.unwrap();
+
+    let context = Context::builder(Arc::new(db.begin().await.unwrap())).build();
+    let found = user_repository::find_by_email(&context, "pg-admin@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(found.id, admin.id);
+    assert_eq!(found.role, admin.role);
+}
@@ -0,0 +1,87 @@
+use my_axum::pkg::{
+    cache::{
+        RedisResponseCache, RedisTaskDeduplicator, ResponseCache, TaskDeduplicator, TaskStatusCache,
+    },
+    redis::{RedisConnectionManager, RedisPoolConfig},
+};
+use std::time::Duration;
+
+use crate::setup::containers::start_redis;
+
+#[tokio::test]
+async fn test_shared_manager_is_reused_per_url() {
+    let redis = start_redis().await;
+    let config = RedisPoolConfig::default();
+
+    let first = RedisConnectionManager::shared(&redis.url, &config)
+        .await
+        .unwrap();
+    let second = RedisConnectionManager::shared(&redis.url, &config)
+        .await
+        .unwrap();
+    second.ping().await.unwrap();
+
+    assert_eq!(first.metrics().connections_opened, 1);
+    assert_eq!(first.metrics().commands, 1);
+}
+
+#[tokio::test]
+async fn test_caches_share_one_connection() {
+    let redis = start_redis().await;
+    let manager = RedisConnectionManager::connect(&redis.url, &RedisPoolConfig::default())
+        .await
+        .unwrap();
+    let response_cache = RedisResponseCache::from_manager(&manager);
+    let mut task_cache = TaskStatusCache::from_manager(&manager);
+
+    response_cache
+        .set("user", "a", b"body", Duration::from_secs(60))
+        .await
+        .unwrap();
+    task_cache.cache_status("task-1", &"done").await.unwrap();
+
+    assert_eq!(
+        response_cache.get("a").await.unwrap(),
+        Some(b"body".to_vec())
+    );
+    assert_eq!(
+        task_cache.get_status::<String>("task-1").await.unwrap(),
+        Some("done".to_string())
+    );
+    let metrics = manager.metrics();
+    assert_eq!(metrics.connections_opened, 1);
+    assert_eq!(metrics.command_errors, 0);
+}
+
+#[tokio::test]
+async fn test_pubsub_opens_dedicated_connections() {
+    let redis = start_redis().await;
+    let manager = RedisConnectionManager::connect(&redis.url, &RedisPoolConfig::default())
+        .await
+        .unwrap();
+
+    let mut pubsub = manager.pubsub().await.unwrap();
+    pubsub.subscribe("channel").await.unwrap();
+
+    let metrics = manager.metrics();
+    assert_eq!(metrics.connections_opened, 2);
+    assert_eq!(metrics.pubsub_connections, 1);
+}
+
+#[tokio::test]
+async fn test_task_dedup_returns_claimed_task_until_released() {
+    let redis = start_redis().await;
+    let manager = RedisConnectionManager::connect(&redis.url, &RedisPoolConfig::default())
+        .await
+        .unwrap();
+    let dedup = RedisTaskDeduplicator::from_manager(&manager, Duration::from_secs(60));
+
+    assert_eq!(dedup.claim("1:Upload:abc", "task-1").await.unwrap(), None);
+    assert_eq!(
+        dedup.claim("1:Upload:abc", "task-2").await.unwrap(),
+        Some("task-1".to_string())
+    );
+
+    dedup.release("1:Upload:abc").await.unwrap();
+    assert_eq!(dedup.claim("1:Upload:abc", "task-3").await.unwrap(), None);
+}
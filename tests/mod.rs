@@ -1,7 +1,15 @@
 #![allow(dead_code)]
 
+#[cfg(feature = "client")]
+mod client;
 mod common;
 mod config;
 mod core;
+mod file;
+#[cfg(feature = "it")]
+mod it;
+mod notification;
+mod oauth_provider;
+mod report;
 mod setup;
 mod user;
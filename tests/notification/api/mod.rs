@@ -0,0 +1,3 @@
+mod test_device_token_api;
+mod test_notification_api;
+mod test_notification_preference_api;
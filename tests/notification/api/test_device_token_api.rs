@@ -0,0 +1,92 @@
+mod device_token_api_tests {
+    use reqwest::StatusCode;
+    use serde_json::{Value, json};
+
+    use crate::setup::app::TestApp;
+
+    const PATH: &str = "/api/v1/notification/device-token/";
+
+    #[tokio::test]
+    async fn test_register_list_and_delete_device_token() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("device@example.com").await;
+
+        // Act
+        let created = client
+            .post(
+                PATH,
+                &json!({ "token": "fcm-token-1", "platform": "android" }),
+            )
+            .await;
+        assert_eq!(created.status(), StatusCode::CREATED);
+        let created = created.json::<Value>().await.unwrap();
+
+        let list = client.get(PATH).await.json::<Value>().await.unwrap();
+
+        let deleted = client.delete(&format!("{}{}/", PATH, created["id"])).await;
+
+        // Assert
+        assert_eq!(created["platform"], "android");
+        assert_eq!(list["count"], 1);
+        assert_eq!(list["items"][0]["token"], "fcm-token-1");
+        assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+        let list = client.get(PATH).await.json::<Value>().await.unwrap();
+        assert_eq!(list["count"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_reregistering_token_moves_it_to_current_user() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let first = test_app.register_and_login("first@example.com").await;
+        let second = test_app.register_and_login("second@example.com").await;
+        let payload = json!({ "token": "shared-device", "platform": "ios" });
+        first.post(PATH, &payload).await;
+
+        // Act
+        let response = second.post(PATH, &payload).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        let first_list = first.get(PATH).await.json::<Value>().await.unwrap();
+        let second_list = second.get(PATH).await.json::<Value>().await.unwrap();
+        assert_eq!(first_list["count"], 0);
+        assert_eq!(second_list["items"][0]["platform"], "ios");
+    }
+
+    #[tokio::test]
+    async fn test_cannot_delete_other_users_device_token() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let owner = test_app.register_and_login("owner@example.com").await;
+        let other = test_app.register_and_login("other@example.com").await;
+        let created = owner
+            .post(
+                PATH,
+                &json!({ "token": "owner-token", "platform": "android" }),
+            )
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+
+        // Act
+        let response = other.delete(&format!("{}{}/", PATH, created["id"])).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_blank_token() {
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("blank@example.com").await;
+
+        let response = client
+            .post(PATH, &json!({ "token": "  ", "platform": "android" }))
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,89 @@
+mod notification_api_tests {
+    use my_axum::{
+        core::context::Context,
+        notification::{
+            entity::{notification, sea_orm_active_enums::NotificationCategory},
+            repository::notification_repository,
+        },
+    };
+    use reqwest::StatusCode;
+    use sea_orm::{ActiveValue::Set, TransactionTrait};
+    use serde_json::{Value, json};
+    use std::sync::Arc;
+
+    use crate::setup::{app::TestApp, client::AuthenticatedClient};
+
+    const PATH: &str = "/api/v1/notification/";
+
+    async fn notify(test_app: &TestApp, client: &AuthenticatedClient, title: &str) -> i32 {
+        let profile = client
+            .get("/api/v1/user/profile/")
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let notification = notification_repository::create(
+            &context,
+            notification::ActiveModel {
+                user_id: Set(profile["id"].as_i64().unwrap() as i32),
+                category: Set(NotificationCategory::Upload),
+                title: Set(title.to_string()),
+                body: Set("Your file is ready".to_string()),
+                data: Set(json!({ "file_id": "1" })),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        context.commit().await.unwrap();
+        notification.id
+    }
+
+    #[tokio::test]
+    async fn test_list_and_read_notifications() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("inbox@example.com").await;
+        let first = notify(&test_app, &client, "First").await;
+        notify(&test_app, &client, "Second").await;
+
+        // Act
+        let read = client
+            .post(&format!("{}{}/read/", PATH, first), &json!({}))
+            .await;
+        let all = client.get(PATH).await.json::<Value>().await.unwrap();
+        let unread = client
+            .get(&format!("{}?unread=true", PATH))
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(read.status(), StatusCode::NO_CONTENT);
+        assert_eq!(all["count"], 2);
+        assert_eq!(all["items"][0]["title"], "Second");
+        assert_eq!(all["items"][1]["data"]["file_id"], "1");
+        assert!(!all["items"][1]["read_at"].is_null());
+        assert_eq!(unread["count"], 1);
+        assert_eq!(unread["items"][0]["title"], "Second");
+    }
+
+    #[tokio::test]
+    async fn test_cannot_read_other_users_notification() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let owner = test_app.register_and_login("owner@example.com").await;
+        let other = test_app.register_and_login("other@example.com").await;
+        let id = notify(&test_app, &owner, "Private").await;
+
+        // Act
+        let response = other
+            .post(&format!("{}{}/read/", PATH, id), &json!({}))
+            .await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
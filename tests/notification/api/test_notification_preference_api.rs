@@ -0,0 +1,107 @@
+mod notification_preference_api_tests {
+    use reqwest::StatusCode;
+    use serde_json::{Value, json};
+
+    use crate::setup::app::TestApp;
+
+    const PATH: &str = "/api/v1/notification/preference/";
+
+    fn find_category<'a>(list: &'a Value, category: &str) -> &'a Value {
+        list["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|item| item["category"] == category)
+            .expect("category should be listed")
+    }
+
+    #[tokio::test]
+    async fn test_lists_default_channels_for_every_category() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("prefs@example.com").await;
+
+        // Act
+        let response = client.get(PATH).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        let list = response.json::<Value>().await.unwrap();
+        assert_eq!(list["count"], 4);
+        let upload = find_category(&list, "upload");
+        assert_eq!(upload["channels"], json!(["in_app", "push"]));
+        assert_eq!(upload["is_default"], true);
+    }
+
+    #[tokio::test]
+    async fn test_update_and_reset_preference() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("prefs@example.com").await;
+        let category_path = format!("{}account/", PATH);
+
+        // Act
+        let updated = client
+            .put(
+                &category_path,
+                &json!({ "channels": ["push", "email", "push"] }),
+            )
+            .await;
+        assert_eq!(updated.status(), StatusCode::OK);
+        let list = client.get(PATH).await.json::<Value>().await.unwrap();
+
+        let reset = client.delete(&category_path).await;
+
+        // Assert
+        let account = find_category(&list, "account");
+        assert_eq!(account["channels"], json!(["push", "email"]));
+        assert_eq!(account["is_default"], false);
+        assert_eq!(reset.status(), StatusCode::NO_CONTENT);
+        let list = client.get(PATH).await.json::<Value>().await.unwrap();
+        let account = find_category(&list, "account");
+        assert_eq!(account["channels"], json!(["email"]));
+        assert_eq!(account["is_default"], true);
+    }
+
+    #[tokio::test]
+    async fn test_none_opts_out_of_category() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("prefs@example.com").await;
+
+        // Act
+        let response = client
+            .put(
+                &format!("{}report/", PATH),
+                &json!({ "channels": ["none"] }),
+            )
+            .await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.json::<Value>().await.unwrap();
+        assert_eq!(body["channels"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_none_combined_with_other_channels() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("prefs@example.com").await;
+
+        // Act
+        let combined = client
+            .put(
+                &format!("{}report/", PATH),
+                &json!({ "channels": ["none", "email"] }),
+            )
+            .await;
+        let empty = client
+            .put(&format!("{}report/", PATH), &json!({ "channels": [] }))
+            .await;
+
+        // Assert
+        assert_eq!(combined.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(empty.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,3 @@
+mod api;
+mod service;
+mod task;
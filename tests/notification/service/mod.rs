@@ -0,0 +1 @@
+mod test_notification_service;
@@ -0,0 +1,318 @@
+mod notification_service_tests {
+    use async_trait::async_trait;
+    use my_axum::{
+        core::{
+            r#async::{TaskEvent, TaskType},
+            context::Context,
+        },
+        notification::{
+            entity::{
+                notification_preference,
+                sea_orm_active_enums::{NotificationCategory, NotificationChannel},
+            },
+            repository::notification_preference_repository,
+            service::{
+                notification_service::{self, Notification},
+                notification_template_service::NotificationEvent,
+            },
+        },
+        pkg::messaging::{EncodedMessage, MessageProducer},
+        user::{
+            entity::{user, user_email},
+            repository::user_email_repository,
+        },
+    };
+    use sea_orm::{ActiveValue::Set, TransactionTrait};
+    use serde_json::{Value, json};
+    use std::sync::{Arc, Mutex};
+
+    use crate::setup::{app::TestApp, factory::UserFactory};
+
+    #[derive(Clone, Default)]
+    struct TrackingProducer {
+        published: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    impl TrackingProducer {
+        fn destinations(&self) -> Vec<String> {
+            self.published
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(destination, _)| destination.clone())
+                .collect()
+        }
+
+        fn message(&self, destination: &str) -> String {
+            self.published
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|(published_to, _)| published_to == destination)
+                .map(|(_, message)| message.clone())
+                .expect("message should be published")
+        }
+    }
+
+    #[async_trait]
+    impl MessageProducer for TrackingProducer {
+        async fn publish(
+            &self,
+            message: &EncodedMessage,
+            destination: Option<&str>,
+        ) -> anyhow::Result<()> {
+            let event_json = message.as_str();
+            self.published.lock().unwrap().push((
+                destination.unwrap_or_default().to_string(),
+                event_json.to_string(),
+            ));
+            Ok(())
+        }
+    }
+
+    async fn create_user(context: &Context) -> user::Model {
+        UserFactory::new().create(context).await.unwrap()
+    }
+
+    async fn choose_channels(
+        context: &Context,
+        user_id: i32,
+        category: NotificationCategory,
+        channels: Value,
+    ) {
+        notification_preference_repository::create(
+            context,
+            notification_preference::ActiveModel {
+                user_id: Set(user_id),
+                category: Set(category),
+                channels: Set(channels),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    fn welcome(user: &user::Model) -> Notification {
+        Notification::new(NotificationEvent::Welcome)
+            .with_variable("email", user.email.clone())
+            .with_variable("first_name", "John")
+            .with_variable("last_name", "Doe")
+            .with_variable("phone", "")
+    }
+
+    fn report() -> Notification {
+        Notification::new(NotificationEvent::OrphanedFileReport)
+            .with_variable("deleted_count", "2")
+            .with_variable("deleted_objects", "a.bin\nb.bin")
+            .with_variable("missing_count", "0")
+            .with_variable("missing_files", "")
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_uses_default_channels_without_preference() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let user = create_user(&context).await;
+        let producer = TrackingProducer::default();
+
+        // Act
+        let channels = notification_service::dispatch(&context, &producer, &user, &welcome(&user))
+            .await
+            .unwrap();
+
+        // Assert: accounts default to email only
+        assert_eq!(channels, vec![NotificationChannel::Email]);
+        assert_eq!(producer.destinations(), vec!["emails"]);
+        let event: TaskEvent = serde_json::from_str(&producer.message("emails")).unwrap();
+        let TaskType::SendEmail {
+            to,
+            subject,
+            html_body,
+            ..
+        } = event.task
+        else {
+            panic!("Expected a SendEmail task");
+        };
+        assert_eq!(to, user.email);
+        assert_eq!(subject, "Welcome to My Axum App!");
+        assert!(html_body.unwrap().contains(&user.email));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_fans_out_to_preferred_channels() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let user = create_user(&context).await;
+        choose_channels(
+            &context,
+            user.id,
+            NotificationCategory::Account,
+            json!(["push", "in_app"]),
+        )
+        .await;
+        let producer = TrackingProducer::default();
+
+        // Act
+        notification_service::dispatch(
+            &context,
+            &producer,
+            &user,
+            &welcome(&user).with_data("kind", "welcome"),
+        )
+        .await
+        .unwrap();
+
+        // Assert
+        assert_eq!(producer.destinations(), vec!["tasks", "broadcasts"]);
+        let event: TaskEvent = serde_json::from_str(&producer.message("tasks")).unwrap();
+        let TaskType::SendPushNotification {
+            user_id,
+            body,
+            data,
+            ..
+        } = event.task
+        else {
+            panic!("Expected a SendPushNotification task");
+        };
+        assert_eq!(user_id, user.id);
+        assert_eq!(body, "Your account is ready.");
+        assert_eq!(data["kind"], "welcome");
+        let broadcast: Value = serde_json::from_str(&producer.message("broadcasts")).unwrap();
+        assert_eq!(broadcast["event_type"], "notification");
+        assert_eq!(broadcast["data"]["user_id"], user.id);
+        assert_eq!(broadcast["data"]["category"], "account");
+        assert_eq!(broadcast["data"]["title"], "Welcome to My Axum App!");
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_skips_opted_out_category() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let user = create_user(&context).await;
+        choose_channels(&context, user.id, NotificationCategory::Report, json!([])).await;
+        let producer = TrackingProducer::default();
+
+        // Act
+        let channels = notification_service::dispatch(&context, &producer, &user, &report())
+            .await
+            .unwrap();
+
+        // Assert
+        assert!(channels.is_empty());
+        assert!(producer.destinations().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_sends_sms_only_to_verified_phones() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let unverified = UserFactory::new()
+            .phone("+84901234567")
+            .create(&context)
+            .await
+            .unwrap();
+        let verified = user::Model {
+            phone_verified_at: Some(chrono::Utc::now().naive_utc()),
+            ..UserFactory::new()
+                .phone("+84907654321")
+                .create(&context)
+                .await
+                .unwrap()
+        };
+        for user in [&unverified, &verified] {
+            choose_channels(
+                &context,
+                user.id,
+                NotificationCategory::Report,
+                json!(["sms"]),
+            )
+            .await;
+        }
+        let producer = TrackingProducer::default();
+        let notification = report();
+
+        // Act
+        notification_service::dispatch(&context, &producer, &unverified, &notification)
+            .await
+            .unwrap();
+        notification_service::dispatch(&context, &producer, &verified, &notification)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(producer.destinations(), vec!["tasks"]);
+        let event: TaskEvent = serde_json::from_str(&producer.message("tasks")).unwrap();
+        let TaskType::SendSms { to, body } = event.task else {
+            panic!("Expected a SendSms task");
+        };
+        assert_eq!(to, "+84907654321");
+        assert_eq!(
+            body,
+            "Orphaned file report: 2 orphaned object(s) deleted, 0 file(s) missing from storage"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_copies_emails_to_opted_in_secondary_addresses() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let user = create_user(&context).await;
+        for (email, verified, receives_notifications) in [
+            ("copy@example.com", true, true),
+            ("quiet@example.com", true, false),
+            ("pending@example.com", false, true),
+        ] {
+            user_email_repository::create(
+                &context,
+                user_email::ActiveModel {
+                    user_id: Set(user.id),
+                    email: Set(email.to_string()),
+                    verified_at: Set(verified.then(|| chrono::Utc::now().naive_utc())),
+                    receives_notifications: Set(receives_notifications),
+                    retry_count: Set(0),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+        choose_channels(
+            &context,
+            user.id,
+            NotificationCategory::Report,
+            json!(["email"]),
+        )
+        .await;
+        let producer = TrackingProducer::default();
+
+        // Act
+        notification_service::dispatch(&context, &producer, &user, &report())
+            .await
+            .unwrap();
+
+        // Assert
+        let recipients: Vec<String> = producer
+            .published
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, message)| {
+                let event: TaskEvent = serde_json::from_str(message).unwrap();
+                let TaskType::SendEmail { to, .. } = event.task else {
+                    panic!("Expected a SendEmail task");
+                };
+                to
+            })
+            .collect();
+        assert_eq!(
+            recipients,
+            vec![user.email.clone(), "copy@example.com".to_string()]
+        );
+    }
+}
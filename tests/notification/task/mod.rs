@@ -0,0 +1,2 @@
+mod test_digest_task;
+mod test_push_task;
@@ -0,0 +1,169 @@
+mod digest_task_tests {
+    use async_trait::async_trait;
+    use chrono::{Duration, Utc};
+    use my_axum::{
+        core::{
+            r#async::{TaskEvent, TaskType},
+            context::Context,
+        },
+        notification::{
+            entity::{
+                notification, notification_preference, sea_orm_active_enums::NotificationCategory,
+            },
+            repository::{notification_preference_repository, notification_repository},
+            task::digest_task::send_notification_digests,
+        },
+        pkg::messaging::{EncodedMessage, MessageProducer},
+        user::entity::user,
+    };
+    use sea_orm::{ActiveValue::Set, TransactionTrait};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    use crate::setup::{app::TestApp, factory::UserFactory};
+
+    #[derive(Clone, Default)]
+    struct TrackingProducer {
+        emails: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl MessageProducer for TrackingProducer {
+        async fn publish(
+            &self,
+            message: &EncodedMessage,
+            destination: Option<&str>,
+        ) -> anyhow::Result<()> {
+            let event_json = message.as_str();
+            match destination {
+                Some("emails") => self.emails.lock().unwrap().push(event_json.to_string()),
+                other => panic!("Unexpected destination {:?}", other),
+            }
+            Ok(())
+        }
+    }
+
+    async fn create_user(test_app: &TestApp) -> user::Model {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        context.commit().await.unwrap();
+        user
+    }
+
+    async fn notify(test_app: &TestApp, user_id: i32, title: &str) -> notification::Model {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let notification = notification_repository::create(
+            &context,
+            notification::ActiveModel {
+                user_id: Set(user_id),
+                category: Set(NotificationCategory::Upload),
+                title: Set(title.to_string()),
+                body: Set(format!("{} body", title)),
+                data: Set(json!({})),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        context.commit().await.unwrap();
+        notification
+    }
+
+    fn later() -> chrono::DateTime<Utc> {
+        Utc::now() + Duration::hours(2)
+    }
+
+    #[tokio::test]
+    async fn test_sends_one_digest_per_user_once() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let producer = TrackingProducer::default();
+        let user = create_user(&test_app).await;
+        notify(&test_app, user.id, "Upload finished").await;
+        notify(&test_app, user.id, "Thumbnails ready").await;
+
+        // Act
+        let delivered = send_notification_digests(&test_app.db, &producer, later())
+            .await
+            .unwrap();
+        let delivered_again = send_notification_digests(&test_app.db, &producer, later())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(delivered, 1);
+        assert_eq!(delivered_again, 0);
+        let emails = producer.emails.lock().unwrap().clone();
+        assert_eq!(emails.len(), 1);
+        let event: TaskEvent = serde_json::from_str(&emails[0]).unwrap();
+        let TaskType::SendEmail {
+            to,
+            subject,
+            html_body,
+            ..
+        } = event.task
+        else {
+            panic!("Expected a SendEmail task");
+        };
+        assert_eq!(to, user.email);
+        assert!(subject.contains('2'));
+        let html_body = html_body.unwrap();
+        assert!(html_body.contains("Upload finished"));
+        assert!(html_body.contains("Thumbnails ready"));
+    }
+
+    #[tokio::test]
+    async fn test_skips_read_and_recent_notifications() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let producer = TrackingProducer::default();
+        let user = create_user(&test_app).await;
+        let read = notify(&test_app, user.id, "Already seen").await;
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        notification_repository::mark_read(&context, user.id, &[read.id])
+            .await
+            .unwrap();
+        context.commit().await.unwrap();
+        notify(&test_app, user.id, "Just now").await;
+
+        // Act: the unread notification is younger than the digest window
+        let delivered = send_notification_digests(&test_app.db, &producer, Utc::now())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(delivered, 0);
+        assert!(producer.emails.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_respects_opted_out_digest_preference() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let producer = TrackingProducer::default();
+        let user = create_user(&test_app).await;
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        notification_preference_repository::create(
+            &context,
+            notification_preference::ActiveModel {
+                user_id: Set(user.id),
+                category: Set(NotificationCategory::Digest),
+                channels: Set(json!([])),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        context.commit().await.unwrap();
+        notify(&test_app, user.id, "Upload finished").await;
+
+        // Act
+        let delivered = send_notification_digests(&test_app.db, &producer, later())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(delivered, 0);
+        assert!(producer.emails.lock().unwrap().is_empty());
+    }
+}
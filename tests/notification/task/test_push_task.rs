@@ -0,0 +1,184 @@
+mod push_task_tests {
+    use async_trait::async_trait;
+    use my_axum::{
+        core::context::Context,
+        notification::{
+            entity::{device_token, sea_orm_active_enums::DevicePlatform},
+            repository::device_token_repository,
+            task::push_task::{PushDelivery, send_push_notification},
+        },
+        pkg::push::{PushNotification, PushOutcome, PushTransport},
+    };
+    use sea_orm::{ActiveValue::Set, TransactionTrait};
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use crate::setup::{app::TestApp, factory::UserFactory};
+
+    /// Replies with the scripted results per token, then `Delivered`
+    #[derive(Default)]
+    struct ScriptedTransport {
+        replies: Mutex<HashMap<String, Vec<Result<PushOutcome, String>>>>,
+        sent: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedTransport {
+        fn reply(self, token: &str, replies: Vec<Result<PushOutcome, String>>) -> Self {
+            self.replies
+                .lock()
+                .unwrap()
+                .insert(token.to_string(), replies);
+            self
+        }
+
+        fn sent(&self) -> Vec<String> {
+            self.sent.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl PushTransport for ScriptedTransport {
+        async fn send(
+            &self,
+            device_token: &str,
+            _notification: &PushNotification,
+        ) -> anyhow::Result<PushOutcome> {
+            self.sent.lock().unwrap().push(device_token.to_string());
+            let mut replies = self.replies.lock().unwrap();
+            match replies.get_mut(device_token) {
+                Some(queue) if !queue.is_empty() => queue.remove(0).map_err(anyhow::Error::msg),
+                _ => Ok(PushOutcome::Delivered),
+            }
+        }
+    }
+
+    async fn create_user_with_devices(
+        test_app: &TestApp,
+        devices: &[(&str, DevicePlatform)],
+    ) -> i32 {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        for (token, platform) in devices {
+            device_token_repository::create(
+                &context,
+                device_token::ActiveModel {
+                    user_id: Set(user.id),
+                    token: Set(token.to_string()),
+                    platform: Set(platform.clone()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+        context.commit().await.unwrap();
+        user.id
+    }
+
+    async fn remaining_tokens(test_app: &TestApp, user_id: i32) -> Vec<String> {
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        device_token_repository::find_by_user_id(&context, user_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|device| device.token)
+            .collect()
+    }
+
+    fn notification() -> PushNotification {
+        PushNotification {
+            title: "Hello".to_string(),
+            body: "World".to_string(),
+            data: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_devices_by_platform() {
+        // Arrange
+        let test_app = TestApp::spawn_db_only().await;
+        let user_id = create_user_with_devices(
+            &test_app,
+            &[
+                ("android-1", DevicePlatform::Android),
+                ("ios-1", DevicePlatform::Ios),
+            ],
+        )
+        .await;
+        let fcm = Arc::new(ScriptedTransport::default());
+        let apns = Arc::new(ScriptedTransport::default());
+        let delivery = PushDelivery::default()
+            .with_fcm(Some(fcm.clone()))
+            .with_apns(Some(apns.clone()));
+
+        // Act
+        send_push_notification(&test_app.db, &delivery, user_id, &notification())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(fcm.sent(), vec!["android-1"]);
+        assert_eq!(apns.sent(), vec!["ios-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_retries_each_device_and_removes_invalid_tokens() {
+        // Arrange
+        let test_app = TestApp::spawn_db_only().await;
+        let user_id = create_user_with_devices(
+            &test_app,
+            &[
+                ("flaky", DevicePlatform::Android),
+                ("stale", DevicePlatform::Android),
+            ],
+        )
+        .await;
+        let fcm = Arc::new(
+            ScriptedTransport::default()
+                .reply("flaky", vec![Err("unavailable".to_string())])
+                .reply("stale", vec![Ok(PushOutcome::InvalidToken)]),
+        );
+        let delivery = PushDelivery::default()
+            .with_fcm(Some(fcm.clone()))
+            .with_retry(3, Duration::from_millis(1));
+
+        // Act
+        send_push_notification(&test_app.db, &delivery, user_id, &notification())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(fcm.sent(), vec!["flaky", "flaky", "stale"]);
+        assert_eq!(remaining_tokens(&test_app, user_id).await, vec!["flaky"]);
+    }
+
+    #[tokio::test]
+    async fn test_fails_when_no_device_is_reachable() {
+        // Arrange
+        let test_app = TestApp::spawn_db_only().await;
+        let user_id =
+            create_user_with_devices(&test_app, &[("down", DevicePlatform::Android)]).await;
+        let fcm = Arc::new(ScriptedTransport::default().reply(
+            "down",
+            vec![
+                Err("unavailable".to_string()),
+                Err("unavailable".to_string()),
+            ],
+        ));
+        let delivery = PushDelivery::default()
+            .with_fcm(Some(fcm.clone()))
+            .with_retry(2, Duration::from_millis(1));
+
+        // Act
+        let result =
+            send_push_notification(&test_app.db, &delivery, user_id, &notification()).await;
+
+        // Assert
+        assert!(result.is_err());
+        assert_eq!(fcm.sent().len(), 2);
+        assert_eq!(remaining_tokens(&test_app, user_id).await, vec!["down"]);
+    }
+}
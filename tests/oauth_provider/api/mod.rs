@@ -0,0 +1 @@
+mod test_oauth_provider_api;
@@ -0,0 +1,559 @@
+use std::sync::Arc;
+
+use my_axum::{core::context::Context, pkg::pkce};
+use reqwest::{StatusCode, Url, header};
+use serde_json::{Value, json};
+
+use crate::setup::{
+    app::TestApp,
+    client::AuthenticatedClient,
+    fixture::{login_admin_user, login_normal_user},
+};
+
+const CLIENTS_PATH: &str = "/api/v1/admin/oauth/clients/";
+const AUTHORIZE_PATH: &str = "/api/v1/oauth/authorize/";
+const CONSENTS_PATH: &str = "/api/v1/oauth/consents/";
+const REDIRECT_URI: &str = "https://app.example.com/callback";
+const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mJ92K1s7OyT0M9ZbUXW3mJH-gR1qLk";
+
+async fn access_token(test_app: &TestApp, admin: bool) -> String {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    context.commit().await.unwrap();
+    access_token
+}
+
+/// Register a client as an admin, returning its `client_id` and `client_secret`
+async fn create_client(test_app: &TestApp, payload: Value) -> (String, String) {
+    let admin_token = access_token(test_app, true).await;
+    let response = reqwest::Client::new()
+        .post(format!("http://{}{}", test_app.base_url, CLIENTS_PATH))
+        .bearer_auth(admin_token)
+        .json(&payload)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.json::<Value>().await.unwrap();
+    (
+        body["client_id"].as_str().unwrap().to_string(),
+        body["client_secret"].as_str().unwrap().to_string(),
+    )
+}
+
+/// Post `fields` as a form to the token or introspection endpoint
+async fn post_form(
+    test_app: &TestApp,
+    path: &str,
+    fields: &[(&str, &str)],
+    basic_auth: Option<(&str, &str)>,
+) -> reqwest::Response {
+    let mut request = reqwest::Client::new()
+        .post(format!("http://{}{}", test_app.base_url, path))
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(serde_urlencoded::to_string(fields).unwrap());
+    if let Some((client_id, client_secret)) = basic_auth {
+        request = request.basic_auth(client_id, Some(client_secret));
+    }
+    request.send().await.unwrap()
+}
+
+/// Approve an authorization request of `client_id` as `user`, returning the code
+async fn authorize(user: &AuthenticatedClient, client_id: &str) -> String {
+    let response = user
+        .post(
+            AUTHORIZE_PATH,
+            &json!({
+                "response_type": "code",
+                "client_id": client_id,
+                "state": "xyz",
+                "code_challenge": pkce::s256_challenge(CODE_VERIFIER),
+                "code_challenge_method": "S256",
+                "approve": true,
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.json::<Value>().await.unwrap();
+    let redirect_to = Url::parse(body["redirect_to"].as_str().unwrap()).unwrap();
+    assert!(redirect_to.as_str().starts_with(REDIRECT_URI));
+    let param = |name: &str| {
+        redirect_to
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    assert_eq!(param("state").as_deref(), Some("xyz"));
+    param("code").unwrap()
+}
+
+#[tokio::test]
+async fn test_authorization_code_flow_with_pkce() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (client_id, client_secret) = create_client(
+        &test_app,
+        json!({ "name": "Reports", "redirect_uris": [REDIRECT_URI] }),
+    )
+    .await;
+    let user = test_app.register_and_login("owner@example.com").await;
+
+    // Act
+    let screen = user
+        .get(&format!(
+            "{}?response_type=code&client_id={}",
+            AUTHORIZE_PATH, client_id
+        ))
+        .await
+        .json::<Value>()
+        .await
+        .unwrap();
+    let code = authorize(&user, &client_id).await;
+    let token_response = post_form(
+        &test_app,
+        "/api/v1/oauth/token/",
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", REDIRECT_URI),
+            ("code_verifier", CODE_VERIFIER),
+        ],
+        Some((&client_id, &client_secret)),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(screen["client"]["name"], "Reports");
+    assert_eq!(screen["consent_required"], true);
+    assert_eq!(screen["scopes"][0]["name"], "profile");
+    assert_eq!(token_response.status(), StatusCode::OK);
+    assert_eq!(token_response.headers()[header::CACHE_CONTROL], "no-store");
+    let token = token_response.json::<Value>().await.unwrap();
+    assert_eq!(token["token_type"], "Bearer");
+    assert_eq!(token["scope"], "profile email");
+
+    let introspection = post_form(
+        &test_app,
+        "/api/v1/oauth/introspect/",
+        &[("token", token["access_token"].as_str().unwrap())],
+        Some((&client_id, &client_secret)),
+    )
+    .await
+    .json::<Value>()
+    .await
+    .unwrap();
+    assert_eq!(introspection["active"], true);
+    assert_eq!(introspection["client_id"], client_id.as_str());
+    assert_eq!(introspection["username"], "owner@example.com");
+
+    // Codes are single use
+    let reused = post_form(
+        &test_app,
+        "/api/v1/oauth/token/",
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", REDIRECT_URI),
+            ("code_verifier", CODE_VERIFIER),
+        ],
+        Some((&client_id, &client_secret)),
+    )
+    .await;
+    assert_eq!(reused.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        reused.json::<Value>().await.unwrap()["error"],
+        "invalid_grant"
+    );
+
+    // The consent is remembered
+    let screen = user
+        .get(&format!(
+            "{}?response_type=code&client_id={}",
+            AUTHORIZE_PATH, client_id
+        ))
+        .await
+        .json::<Value>()
+        .await
+        .unwrap();
+    assert_eq!(screen["consent_required"], false);
+}
+
+#[tokio::test]
+async fn test_code_exchange_requires_matching_verifier() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (client_id, client_secret) = create_client(
+        &test_app,
+        json!({ "name": "Reports", "redirect_uris": [REDIRECT_URI] }),
+    )
+    .await;
+    let user = test_app.register_and_login("owner@example.com").await;
+    let code = authorize(&user, &client_id).await;
+
+    // Act
+    let response = post_form(
+        &test_app,
+        "/api/v1/oauth/token/",
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", REDIRECT_URI),
+            (
+                "code_verifier",
+                "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            ),
+        ],
+        Some((&client_id, &client_secret)),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.json::<Value>().await.unwrap()["error"],
+        "invalid_grant"
+    );
+}
+
+#[tokio::test]
+async fn test_code_exchange_requires_redirect_uri() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (client_id, client_secret) = create_client(
+        &test_app,
+        json!({ "name": "Reports", "redirect_uris": [REDIRECT_URI] }),
+    )
+    .await;
+    let user = test_app.register_and_login("owner@example.com").await;
+    let code = authorize(&user, &client_id).await;
+
+    // Act
+    let response = post_form(
+        &test_app,
+        "/api/v1/oauth/token/",
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("code_verifier", CODE_VERIFIER),
+        ],
+        Some((&client_id, &client_secret)),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.json::<Value>().await.unwrap()["error"],
+        "invalid_request"
+    );
+}
+
+async fn exchange(
+    test_app: &TestApp,
+    client_id: &str,
+    client_secret: &str,
+    code: &str,
+) -> reqwest::Response {
+    post_form(
+        test_app,
+        "/api/v1/oauth/token/",
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", REDIRECT_URI),
+            ("code_verifier", CODE_VERIFIER),
+        ],
+        Some((client_id, client_secret)),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_replayed_code_revokes_its_tokens() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (client_id, client_secret) = create_client(
+        &test_app,
+        json!({ "name": "Reports", "redirect_uris": [REDIRECT_URI] }),
+    )
+    .await;
+    let user = test_app.register_and_login("owner@example.com").await;
+    let code = authorize(&user, &client_id).await;
+    let first = exchange(&test_app, &client_id, &client_secret, &code).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    let token = first.json::<Value>().await.unwrap();
+
+    // Act
+    let replayed = exchange(&test_app, &client_id, &client_secret, &code).await;
+
+    // Assert
+    assert_eq!(replayed.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        replayed.json::<Value>().await.unwrap()["error"],
+        "invalid_grant"
+    );
+    let introspection = post_form(
+        &test_app,
+        "/api/v1/oauth/introspect/",
+        &[("token", token["access_token"].as_str().unwrap())],
+        Some((&client_id, &client_secret)),
+    )
+    .await
+    .json::<Value>()
+    .await
+    .unwrap();
+    assert_eq!(introspection["active"], false);
+}
+
+#[tokio::test]
+async fn test_concurrent_exchanges_of_a_code_issue_one_token() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (client_id, client_secret) = create_client(
+        &test_app,
+        json!({ "name": "Reports", "redirect_uris": [REDIRECT_URI] }),
+    )
+    .await;
+    let user = test_app.register_and_login("owner@example.com").await;
+    let code = authorize(&user, &client_id).await;
+
+    // Act
+    let (first, second) = tokio::join!(
+        exchange(&test_app, &client_id, &client_secret, &code),
+        exchange(&test_app, &client_id, &client_secret, &code),
+    );
+
+    // Assert
+    let mut statuses = vec![first.status(), second.status()];
+    statuses.sort();
+    assert_eq!(statuses, vec![StatusCode::OK, StatusCode::BAD_REQUEST]);
+}
+
+#[tokio::test]
+async fn test_denied_authorization_redirects_with_error() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (client_id, _) = create_client(
+        &test_app,
+        json!({ "name": "Reports", "redirect_uris": [REDIRECT_URI] }),
+    )
+    .await;
+    let user = test_app.register_and_login("owner@example.com").await;
+
+    // Act
+    let response = user
+        .post(
+            AUTHORIZE_PATH,
+            &json!({
+                "response_type": "code",
+                "client_id": client_id,
+                "state": "xyz",
+                "approve": false,
+            }),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.json::<Value>().await.unwrap()["redirect_to"],
+        format!("{}?error=access_denied&state=xyz", REDIRECT_URI)
+    );
+    let consents = user.get(CONSENTS_PATH).await.json::<Value>().await.unwrap();
+    assert_eq!(consents["count"], 0);
+}
+
+#[tokio::test]
+async fn test_unregistered_redirect_uri_is_rejected() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (client_id, _) = create_client(
+        &test_app,
+        json!({ "name": "Reports", "redirect_uris": [REDIRECT_URI] }),
+    )
+    .await;
+    let user = test_app.register_and_login("owner@example.com").await;
+
+    // Act
+    let response = user
+        .get(&format!(
+            "{}?response_type=code&client_id={}&redirect_uri=https%3A%2F%2Fevil.example.com%2F",
+            AUTHORIZE_PATH, client_id
+        ))
+        .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.json::<Value>().await.unwrap();
+    assert_eq!(body["code"], "OAUTH_REDIRECT_URI_INVALID");
+}
+
+#[tokio::test]
+async fn test_client_credentials_grant() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (client_id, client_secret) = create_client(
+        &test_app,
+        json!({ "name": "Billing", "grant_types": ["client_credentials"], "scopes": ["email"] }),
+    )
+    .await;
+
+    // Act
+    let response = post_form(
+        &test_app,
+        "/api/v1/oauth/token/",
+        &[
+            ("grant_type", "client_credentials"),
+            ("client_id", &client_id),
+            ("client_secret", &client_secret),
+        ],
+        None,
+    )
+    .await;
+    let wrong_secret = post_form(
+        &test_app,
+        "/api/v1/oauth/token/",
+        &[("grant_type", "client_credentials")],
+        Some((&client_id, "wrong")),
+    )
+    .await;
+    let wrong_scope = post_form(
+        &test_app,
+        "/api/v1/oauth/token/",
+        &[("grant_type", "client_credentials"), ("scope", "profile")],
+        Some((&client_id, &client_secret)),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let token = response.json::<Value>().await.unwrap();
+    assert_eq!(token["scope"], "email");
+    assert_eq!(wrong_secret.status(), StatusCode::UNAUTHORIZED);
+    assert!(
+        wrong_secret
+            .headers()
+            .contains_key(header::WWW_AUTHENTICATE)
+    );
+    assert_eq!(
+        wrong_secret.json::<Value>().await.unwrap()["error"],
+        "invalid_client"
+    );
+    assert_eq!(wrong_scope.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        wrong_scope.json::<Value>().await.unwrap()["error"],
+        "invalid_scope"
+    );
+
+    let introspection = post_form(
+        &test_app,
+        "/api/v1/oauth/introspect/",
+        &[("token", token["access_token"].as_str().unwrap())],
+        Some((&client_id, &client_secret)),
+    )
+    .await
+    .json::<Value>()
+    .await
+    .unwrap();
+    assert_eq!(introspection["active"], true);
+    assert!(introspection.get("sub").is_none());
+}
+
+#[tokio::test]
+async fn test_revoking_consent_revokes_tokens() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (client_id, client_secret) = create_client(
+        &test_app,
+        json!({ "name": "Reports", "redirect_uris": [REDIRECT_URI] }),
+    )
+    .await;
+    let user = test_app.register_and_login("owner@example.com").await;
+    let code = authorize(&user, &client_id).await;
+    let token = post_form(
+        &test_app,
+        "/api/v1/oauth/token/",
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", REDIRECT_URI),
+            ("code_verifier", CODE_VERIFIER),
+        ],
+        Some((&client_id, &client_secret)),
+    )
+    .await
+    .json::<Value>()
+    .await
+    .unwrap();
+    let consents = user.get(CONSENTS_PATH).await.json::<Value>().await.unwrap();
+
+    // Act
+    let response = user
+        .delete(&format!("{}{}/", CONSENTS_PATH, consents["items"][0]["id"]))
+        .await;
+
+    // Assert
+    assert_eq!(consents["count"], 1);
+    assert_eq!(consents["items"][0]["client_name"], "Reports");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let introspection = post_form(
+        &test_app,
+        "/api/v1/oauth/introspect/",
+        &[("token", token["access_token"].as_str().unwrap())],
+        Some((&client_id, &client_secret)),
+    )
+    .await
+    .json::<Value>()
+    .await
+    .unwrap();
+    assert_eq!(introspection, json!({ "active": false }));
+}
+
+#[tokio::test]
+async fn test_client_registration_requires_permission_and_valid_redirect_uris() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let user_token = access_token(&test_app, false).await;
+    let admin_token = access_token(&test_app, true).await;
+    let http = reqwest::Client::new();
+    let url = format!("http://{}{}", test_app.base_url, CLIENTS_PATH);
+
+    // Act
+    let forbidden = http
+        .post(&url)
+        .bearer_auth(&user_token)
+        .json(&json!({ "name": "Reports", "redirect_uris": [REDIRECT_URI] }))
+        .send()
+        .await
+        .unwrap();
+    let malformed = http
+        .post(&url)
+        .bearer_auth(&admin_token)
+        .json(&json!({ "name": "Reports", "redirect_uris": ["/callback"] }))
+        .send()
+        .await
+        .unwrap();
+    let listed = http
+        .get(&url)
+        .bearer_auth(&admin_token)
+        .send()
+        .await
+        .unwrap()
+        .json::<Value>()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(forbidden.status(), StatusCode::FORBIDDEN);
+    assert_eq!(malformed.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        malformed.json::<Value>().await.unwrap()["code"],
+        "OAUTH_REDIRECT_URI_INVALID"
+    );
+    assert_eq!(listed["count"], 0);
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use my_axum::{core::context::Context, pkg::storage::ObjectStorage};
+use reqwest::StatusCode;
+
+use crate::setup::{
+    app::TestApp,
+    fixture::{login_admin_user, login_normal_user},
+};
+
+async fn download(test_app: &TestApp, access_token: &str, name: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!(
+            "http://{}/api/v1/admin/reports/{}/",
+            test_app.base_url, name
+        ))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn access_token(test_app: &TestApp, admin: bool) -> String {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = if admin {
+        login_admin_user(&mut context).await
+    } else {
+        login_normal_user(&mut context).await
+    };
+    context.commit().await.unwrap();
+    access_token
+}
+
+/// Store a report in the app's storage under a unique name
+async fn store_report(test_app: &TestApp, extension: &str, content: &str) -> String {
+    let name = format!("admin-report-{}.{}", uuid::Uuid::new_v4(), extension);
+    test_app
+        .setting
+        .get_storage()
+        .put(&format!("reports/{}", name), content.as_bytes())
+        .await
+        .unwrap();
+    name
+}
+
+async fn remove_report(test_app: &TestApp, name: &str) {
+    test_app
+        .setting
+        .get_storage()
+        .delete(&format!("reports/{}", name))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_admin_downloads_report() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    let name = store_report(&test_app, "csv", "date,new_users\n2026-10-16,2\n").await;
+
+    // Act
+    let response = download(&test_app, &access_token, &name).await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/csv; charset=utf-8"
+    );
+    assert_eq!(
+        response.headers()["content-disposition"],
+        format!("attachment; filename=\"{}\"", name).as_str()
+    );
+    assert_eq!(
+        response.text().await.unwrap(),
+        "date,new_users\n2026-10-16,2\n"
+    );
+    remove_report(&test_app, &name).await;
+}
+
+#[tokio::test]
+async fn test_normal_user_cannot_download_report() {
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, false).await;
+    let name = store_report(&test_app, "html", "<html></html>").await;
+
+    let response = download(&test_app, &access_token, &name).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    remove_report(&test_app, &name).await;
+}
+
+#[tokio::test]
+async fn test_download_missing_report() {
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+
+    let response = download(&test_app, &access_token, "admin-report-missing.csv").await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_download_rejects_keys_outside_reports() {
+    // Arrange: an object that exists, but not under the report prefix
+    let test_app = TestApp::spawn_app().await;
+    let access_token = access_token(&test_app, true).await;
+    let storage = test_app.setting.get_storage();
+    let key = format!("avatars/{}.csv", uuid::Uuid::new_v4());
+    storage.put(&key, b"secret").await.unwrap();
+
+    // Act
+    let response = download(
+        &test_app,
+        &access_token,
+        &format!("..%2F{}", key.replace('/', "%2F")),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    storage.delete(&key).await.unwrap();
+}
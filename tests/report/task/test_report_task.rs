@@ -0,0 +1,213 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use my_axum::{
+    common::{entity::task_event_log, repository::task_event_log_repository},
+    config::setting::ReportSetting,
+    core::{
+        r#async::{TaskEvent, TaskType},
+        context::Context,
+    },
+    pkg::{
+        messaging::{EncodedMessage, MessageProducer},
+        storage::{LocalStorage, ObjectStorage},
+    },
+    report::{
+        dto::report_dto::{ReportAggregation, ReportFormat},
+        task::report_task::{REPORT_PREFIX, build_report, generate_report, render_report},
+    },
+    user::entity::user,
+};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, TransactionTrait};
+use std::sync::{Arc, Mutex};
+
+use crate::setup::{app::TestApp, factory::UserFactory};
+
+#[derive(Clone, Default)]
+struct TrackingProducer {
+    emails: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl MessageProducer for TrackingProducer {
+    async fn publish(
+        &self,
+        message: &EncodedMessage,
+        destination: Option<&str>,
+    ) -> anyhow::Result<()> {
+        assert_eq!(destination, Some("emails"));
+        self.emails
+            .lock()
+            .unwrap()
+            .push(message.as_str().to_string());
+        Ok(())
+    }
+}
+
+fn report_setting(aggregations: Vec<ReportAggregation>, format: ReportFormat) -> ReportSetting {
+    ReportSetting {
+        schedule: "0 0 6 * * *".to_string(),
+        recipients: vec!["ops@example.com".to_string(), "cto@example.com".to_string()],
+        aggregations,
+        format,
+        period_days: 7,
+        base_url: "https://api.example.com/".to_string(),
+    }
+}
+
+fn temp_storage() -> LocalStorage {
+    LocalStorage::new(std::env::temp_dir().join(format!("report-task-{}", uuid::Uuid::new_v4())))
+}
+
+async fn create_user_at(test_app: &TestApp, created_at: DateTime<Utc>) {
+    let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+    let user = UserFactory::new().create(&context).await.unwrap();
+    user::ActiveModel {
+        id: Set(user.id),
+        created_at: Set(Some(created_at.naive_utc())),
+        ..Default::default()
+    }
+    .update(context.txn())
+    .await
+    .unwrap();
+    context.commit().await.unwrap();
+}
+
+async fn record_task(test_app: &TestApp, task_type: &str, status: &str, count: usize) {
+    let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+    for _ in 0..count {
+        task_event_log_repository::create(
+            &context,
+            task_event_log::ActiveModel {
+                task_id: Set(uuid::Uuid::new_v4().to_string()),
+                task_type: Set(Some(task_type.to_string())),
+                status: Set(status.to_string()),
+                attempt: Set(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+    context.commit().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_build_report_counts_new_users_per_day() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let now = Utc::now();
+    create_user_at(&test_app, now - Duration::days(1)).await;
+    create_user_at(&test_app, now - Duration::days(1)).await;
+    create_user_at(&test_app, now - Duration::days(2)).await;
+    create_user_at(&test_app, now - Duration::days(10)).await;
+    let setting = report_setting(vec![ReportAggregation::NewUsersPerDay], ReportFormat::Csv);
+
+    // Act
+    let report = build_report(&test_app.db, &setting, now).await.unwrap();
+
+    // Assert: the user outside the 7-day period is left out
+    let days: Vec<(String, i64)> = report
+        .new_users_per_day
+        .unwrap()
+        .into_iter()
+        .map(|day| (day.day, day.count))
+        .collect();
+    let day = |days_ago: i64| {
+        (now - Duration::days(days_ago))
+            .format("%Y-%m-%d")
+            .to_string()
+    };
+    assert_eq!(days, vec![(day(2), 1), (day(1), 2)]);
+    assert!(report.task_failure_rates.is_none());
+}
+
+#[tokio::test]
+async fn test_build_report_computes_task_failure_rates() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    record_task(&test_app, "SendEmail", "completed", 3).await;
+    record_task(&test_app, "SendEmail", "retrying", 2).await;
+    record_task(&test_app, "SendEmail", "failed", 1).await;
+    record_task(&test_app, "SendEmail", "started", 6).await;
+    record_task(&test_app, "CleanupExpiredToken", "completed", 1).await;
+    let setting = report_setting(vec![ReportAggregation::TaskFailureRates], ReportFormat::Csv);
+
+    // Act
+    let report = build_report(&test_app.db, &setting, Utc::now())
+        .await
+        .unwrap();
+
+    // Assert
+    let rates = report.task_failure_rates.clone().unwrap();
+    assert_eq!(rates.len(), 2);
+    assert_eq!(rates[0].task_type, "CleanupExpiredToken");
+    assert_eq!(rates[0].completed, 1);
+    assert_eq!(rates[0].failure_rate, 0.0);
+    assert_eq!(rates[1].task_type, "SendEmail");
+    assert_eq!(
+        (rates[1].completed, rates[1].retried, rates[1].failed),
+        (3, 2, 1)
+    );
+    assert_eq!(rates[1].failure_rate, 0.25);
+    assert!(report.new_users_per_day.is_none());
+
+    let html = render_report(&report, ReportFormat::Html).unwrap();
+    assert!(html.contains("<td>SendEmail</td>"));
+    assert!(html.contains("25%"));
+    assert!(!html.contains("New users per day"));
+}
+
+#[tokio::test]
+async fn test_generate_report_stores_report_and_emails_recipients() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let storage = temp_storage();
+    let producer = TrackingProducer::default();
+    let now = Utc::now();
+    create_user_at(&test_app, now - Duration::days(1)).await;
+    record_task(&test_app, "SendEmail", "failed", 1).await;
+    let setting = report_setting(
+        vec![
+            ReportAggregation::NewUsersPerDay,
+            ReportAggregation::TaskFailureRates,
+        ],
+        ReportFormat::Csv,
+    );
+
+    // Act
+    let generated = generate_report(&test_app.db, &producer, &storage, &setting, now)
+        .await
+        .unwrap();
+
+    // Assert: the CSV is stored under the report prefix
+    assert!(generated.key.starts_with(REPORT_PREFIX));
+    assert!(generated.name.ends_with(".csv"));
+    let content = String::from_utf8(storage.get(&generated.key).await.unwrap().unwrap()).unwrap();
+    let yesterday = (now - Duration::days(1)).format("%Y-%m-%d");
+    assert!(content.contains(&format!("date,new_users\n{},1\n", yesterday)));
+    assert!(
+        content.contains("task_type,completed,retried,failed,failure_rate\nSendEmail,0,0,1,1\n")
+    );
+
+    // Assert: every recipient gets the download link
+    assert_eq!(
+        generated.link,
+        format!(
+            "https://api.example.com/api/v1/admin/reports/{}/",
+            generated.name
+        )
+    );
+    let emails = producer.emails.lock().unwrap().clone();
+    let recipients: Vec<String> = emails
+        .iter()
+        .map(|email| {
+            let event: TaskEvent = serde_json::from_str(email).unwrap();
+            let TaskType::SendEmail { to, html_body, .. } = event.task else {
+                panic!("Expected a SendEmail task");
+            };
+            assert!(html_body.unwrap().contains(&generated.name));
+            to
+        })
+        .collect();
+    assert_eq!(recipients, vec!["ops@example.com", "cto@example.com"]);
+}
@@ -0,0 +1,313 @@
+use async_trait::async_trait;
+use my_axum::{
+    core::r#async::{ConcreteTaskHandler, TaskEvent, TaskHistoryRecorder, TaskType},
+    pkg::{
+        broadcast::websocket::BroadcastMessage,
+        messaging::{
+            ChannelConsumer, EncodedMessage, MessageConsumer, MessageProducer, TaskHandler,
+            TaskLifecycle,
+        },
+        sms::ConsoleSmsSender,
+        smtp::SmtpClient,
+        storage::LocalStorage,
+    },
+};
+use sea_orm::DatabaseConnection;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{
+    sync::{
+        Semaphore,
+        mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+    },
+    task::JoinHandle,
+    time::{Instant, sleep},
+};
+
+/// Destination the broadcast forwarder listens on; never consumed by the worker
+const BROADCASTS_DESTINATION: &str = "broadcasts";
+
+/// A message published through the in-memory broker
+#[derive(Debug, Clone)]
+pub struct PublishedMessage {
+    pub destination: Option<String>,
+    pub payload: String,
+}
+
+impl PublishedMessage {
+    fn is_task(&self) -> bool {
+        self.destination.as_deref() != Some(BROADCASTS_DESTINATION)
+    }
+
+    /// The broadcast carried by this message, if it was published to the broadcasts destination
+    pub fn broadcast(&self) -> Option<BroadcastMessage> {
+        (!self.is_task())
+            .then(|| serde_json::from_str(&self.payload).ok())
+            .flatten()
+    }
+}
+
+/// Result of the most recent attempt of each task, keyed by task event id
+type TaskOutcomes = Arc<Mutex<HashMap<String, Result<(), String>>>>;
+
+/// In-process broker used by `TestApp` in place of Kafka/Redis/RabbitMQ.
+/// Every published message is recorded, and task messages can be consumed by `spawn_worker`.
+#[derive(Clone)]
+pub struct InMemoryBroker {
+    published: Arc<Mutex<Vec<PublishedMessage>>>,
+    pending: Arc<AtomicUsize>,
+    outcomes: TaskOutcomes,
+    storage: Arc<LocalStorage>,
+    sms: ConsoleSmsSender,
+    sender: UnboundedSender<String>,
+    receiver: Arc<Mutex<Option<UnboundedReceiver<String>>>>,
+}
+
+impl Default for InMemoryBroker {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded_channel();
+        Self {
+            published: Arc::default(),
+            pending: Arc::default(),
+            outcomes: Arc::default(),
+            storage: Arc::new(LocalStorage::new(
+                std::env::temp_dir().join(format!("test-worker-{}", uuid::Uuid::new_v4())),
+            )),
+            sms: ConsoleSmsSender::default(),
+            sender,
+            receiver: Arc::new(Mutex::new(Some(receiver))),
+        }
+    }
+}
+
+impl InMemoryBroker {
+    /// Producer handle suitable for `AppState` and `Context`
+    pub fn producer(&self) -> Arc<Box<dyn MessageProducer>> {
+        Arc::new(Box::new(self.clone()))
+    }
+
+    /// All messages published so far, in publish order
+    pub fn published(&self) -> Vec<PublishedMessage> {
+        self.published.lock().unwrap().clone()
+    }
+
+    /// Task events published to the given destination
+    pub fn tasks(&self, destination: &str) -> Vec<TaskEvent> {
+        self.published()
+            .into_iter()
+            .filter(|message| message.destination.as_deref() == Some(destination))
+            .filter_map(|message| serde_json::from_str(&message.payload).ok())
+            .collect()
+    }
+
+    /// Broadcast messages published so far, in publish order
+    pub fn broadcasts(&self) -> Vec<BroadcastMessage> {
+        self.published()
+            .iter()
+            .filter_map(PublishedMessage::broadcast)
+            .collect()
+    }
+
+    /// Object storage used by the worker, for seeding and inspecting uploads
+    pub fn storage(&self) -> Arc<LocalStorage> {
+        self.storage.clone()
+    }
+
+    /// SMS messages sent by the worker as `(to, body)` pairs
+    pub fn sms_messages(&self) -> Vec<(String, String)> {
+        self.sms.sent()
+    }
+
+    /// Whether `spawn_worker` has already been called
+    pub fn has_worker(&self) -> bool {
+        self.receiver.lock().unwrap().is_none()
+    }
+
+    /// Consume task messages with the worker's consumer loop and the real task handler
+    /// for the rest of the test
+    pub fn spawn_worker(
+        &self,
+        db: DatabaseConnection,
+        smtp_client: Option<SmtpClient>,
+    ) -> JoinHandle<()> {
+        let receiver = self
+            .receiver
+            .lock()
+            .unwrap()
+            .take()
+            .expect("A worker is already consuming from this broker");
+
+        let history = TaskHistoryRecorder::new(db.clone(), "test-worker");
+        let handler = ConcreteTaskHandler::new(
+            db,
+            self.producer(),
+            smtp_client,
+            "redis://127.0.0.1:0".to_string(),
+        )
+        .expect("Failed to create task handler")
+        .with_storage(self.storage.clone())
+        .with_scanner(None)
+        .with_sms(Some(Arc::new(self.sms.clone())));
+        let handler = TrackingHandler {
+            inner: handler,
+            history,
+            pending: self.pending.clone(),
+            outcomes: self.outcomes.clone(),
+        };
+
+        let mut consumer = ChannelConsumer::new(
+            receiver,
+            Arc::new(handler),
+            Arc::new(Semaphore::new(4)),
+            self.producer(),
+        );
+
+        tokio::spawn(async move {
+            if let Err(e) = consumer.consume().await {
+                tracing::warn!("Test worker stopped: {:?}", e);
+            }
+        })
+    }
+
+    /// Wait until every published task has been handled by the worker
+    pub async fn wait_for_idle(&self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        while self.pending.load(Ordering::SeqCst) > 0 {
+            assert!(
+                Instant::now() < deadline,
+                "Timed out waiting for {} pending task(s)",
+                self.pending.load(Ordering::SeqCst)
+            );
+            sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    /// Publish a task, wait until it and any tasks it publishes have been handled,
+    /// and return its outcome along with everything published along the way
+    pub async fn run_task(&self, task: TaskType, destination: &str, timeout: Duration) -> TaskRun {
+        assert!(
+            self.has_worker(),
+            "Call spawn_worker before running tasks through the broker"
+        );
+
+        let event = TaskEvent::new(task);
+        let first_message = self.published.lock().unwrap().len();
+        event
+            .publish_with_producer(self, Some(destination))
+            .await
+            .unwrap();
+        self.wait_for_idle(timeout).await;
+
+        TaskRun {
+            result: self
+                .outcomes
+                .lock()
+                .unwrap()
+                .get(&event.id)
+                .cloned()
+                .expect("Task finished without an outcome"),
+            published: self.published.lock().unwrap()[first_message + 1..].to_vec(),
+            event,
+        }
+    }
+}
+
+#[async_trait]
+impl MessageProducer for InMemoryBroker {
+    async fn publish(
+        &self,
+        message: &EncodedMessage,
+        destination: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let event_json = message.as_str();
+        let message = PublishedMessage {
+            destination: destination.map(str::to_string),
+            payload: event_json.to_string(),
+        };
+        self.published.lock().unwrap().push(message.clone());
+
+        if message.is_task() {
+            self.pending.fetch_add(1, Ordering::SeqCst);
+            if self.sender.send(message.payload).is_err() {
+                self.pending.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Records each task's outcome and history, and marks it as no longer pending
+struct TrackingHandler {
+    inner: ConcreteTaskHandler,
+    history: TaskHistoryRecorder,
+    pending: Arc<AtomicUsize>,
+    outcomes: TaskOutcomes,
+}
+
+#[async_trait]
+impl TaskHandler<TaskType> for TrackingHandler {
+    async fn handle_task(&self, event: &TaskEvent) -> anyhow::Result<()> {
+        let result = self.inner.handle_task(event).await;
+        if let Err(e) = &result {
+            tracing::warn!("Test worker failed task {}: {:?}", event.id, e);
+        }
+
+        self.outcomes.lock().unwrap().insert(
+            event.id.clone(),
+            result.as_ref().map(|_| ()).map_err(|e| format!("{:#}", e)),
+        );
+        result
+    }
+
+    async fn on_lifecycle(&self, event: &TaskEvent, lifecycle: &TaskLifecycle) {
+        self.history.record(event, lifecycle).await;
+        // Only idle once the final stage is in the history
+        if !matches!(lifecycle, TaskLifecycle::Enqueued | TaskLifecycle::Started) {
+            self.pending.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// A task that has been run to completion by the test worker
+#[derive(Debug)]
+pub struct TaskRun {
+    pub event: TaskEvent,
+    /// The handler's result; failed tasks are still retried by the consumer in the background
+    pub result: Result<(), String>,
+    /// Messages published after the task itself, until the worker went idle
+    pub published: Vec<PublishedMessage>,
+}
+
+impl TaskRun {
+    /// Broadcasts published while the task ran, in publish order
+    pub fn broadcasts(&self) -> Vec<BroadcastMessage> {
+        self.published
+            .iter()
+            .filter_map(PublishedMessage::broadcast)
+            .collect()
+    }
+
+    /// Broadcasts of the given event type published while the task ran
+    pub fn broadcasts_of(&self, event_type: &str) -> Vec<BroadcastMessage> {
+        self.broadcasts()
+            .into_iter()
+            .filter(|broadcast| broadcast.event_type == event_type)
+            .collect()
+    }
+
+    /// Follow-up task events the task published to `destination` (e.g. emails)
+    pub fn tasks(&self, destination: &str) -> Vec<TaskEvent> {
+        self.published
+            .iter()
+            .filter(|message| message.destination.as_deref() == Some(destination))
+            .filter_map(|message| serde_json::from_str(&message.payload).ok())
+            .collect()
+    }
+}
@@ -0,0 +1,130 @@
+//! HTTP client for API tests that acts as a logged-in user.
+//!
+//! ```ignore
+//! let client = test_app.register_and_login("me@example.com").await;
+//! let response = client.get("/api/v1/user/profile/").await;
+//! ```
+#![allow(dead_code)]
+
+use reqwest::{Client, Method, Response, StatusCode};
+use serde_json::Value;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone)]
+struct Tokens {
+    access: String,
+    refresh: String,
+}
+
+/// Wraps `reqwest::Client`, attaching the user's Bearer token to every request.
+/// A `401` response triggers a single token refresh and retry.
+pub struct AuthenticatedClient {
+    client: Client,
+    base_url: String,
+    tokens: Mutex<Tokens>,
+    pub email: String,
+}
+
+impl AuthenticatedClient {
+    /// Build a client from a token pair response (`register`, `login` or `refresh-token`)
+    pub fn from_token_pair(base_url: &str, email: &str, token_pair: &Value) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.to_string(),
+            tokens: Mutex::new(Self::parse_tokens(token_pair)),
+            email: email.to_string(),
+        }
+    }
+
+    fn parse_tokens(token_pair: &Value) -> Tokens {
+        let field = |name: &str| {
+            token_pair
+                .get(name)
+                .and_then(Value::as_str)
+                .unwrap_or_else(|| panic!("Token pair response has no `{}`: {}", name, token_pair))
+                .to_string()
+        };
+        Tokens {
+            access: field("access"),
+            refresh: field("refresh"),
+        }
+    }
+
+    pub fn access_token(&self) -> String {
+        self.tokens.lock().unwrap().access.clone()
+    }
+
+    pub fn refresh_token(&self) -> String {
+        self.tokens.lock().unwrap().refresh.clone()
+    }
+
+    /// Replace the access token, e.g. to simulate expiry in refresh tests
+    pub fn set_access_token(&self, access: impl Into<String>) {
+        self.tokens.lock().unwrap().access = access.into();
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.base_url, path)
+    }
+
+    /// Exchange the refresh token for a new token pair, returning the response status
+    pub async fn refresh(&self) -> StatusCode {
+        let response = self
+            .client
+            .post(self.url("/api/v1/auth/refresh-token/"))
+            .json(&serde_json::json!({ "refresh_token": self.refresh_token() }))
+            .send()
+            .await
+            .unwrap();
+        let status = response.status();
+        if status == StatusCode::OK {
+            let token_pair = response.json::<Value>().await.unwrap();
+            *self.tokens.lock().unwrap() = Self::parse_tokens(&token_pair);
+        }
+        status
+    }
+
+    /// Send an authenticated request, refreshing the token once on `401`
+    pub async fn request(&self, method: Method, path: &str, body: Option<&Value>) -> Response {
+        let response = self.send(method.clone(), path, body).await;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return response;
+        }
+
+        if self.refresh().await != StatusCode::OK {
+            return response;
+        }
+        self.send(method, path, body).await
+    }
+
+    async fn send(&self, method: Method, path: &str, body: Option<&Value>) -> Response {
+        let mut request = self
+            .client
+            .request(method, self.url(path))
+            .bearer_auth(self.access_token());
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        request.send().await.unwrap()
+    }
+
+    pub async fn get(&self, path: &str) -> Response {
+        self.request(Method::GET, path, None).await
+    }
+
+    pub async fn post(&self, path: &str, body: &Value) -> Response {
+        self.request(Method::POST, path, Some(body)).await
+    }
+
+    pub async fn put(&self, path: &str, body: &Value) -> Response {
+        self.request(Method::PUT, path, Some(body)).await
+    }
+
+    pub async fn patch(&self, path: &str, body: &Value) -> Response {
+        self.request(Method::PATCH, path, Some(body)).await
+    }
+
+    pub async fn delete(&self, path: &str) -> Response {
+        self.request(Method::DELETE, path, None).await
+    }
+}
@@ -0,0 +1,57 @@
+//! Disposable infrastructure for `--features it` tests (requires a running Docker daemon).
+//! Each container is stopped when the returned handle is dropped.
+use testcontainers_modules::{
+    kafka::apache::{KAFKA_PORT, Kafka},
+    postgres::Postgres,
+    rabbitmq::RabbitMq,
+    redis::{REDIS_PORT, Redis},
+    testcontainers::{ContainerAsync, Image, runners::AsyncRunner},
+};
+
+/// A running container and the URL used to reach it from the host
+pub struct RunningContainer<I: Image> {
+    pub url: String,
+    _container: ContainerAsync<I>,
+}
+
+async fn host_port<I: Image>(container: &ContainerAsync<I>, port: u16) -> (String, u16) {
+    let host = container.get_host().await.unwrap().to_string();
+    let port = container.get_host_port_ipv4(port).await.unwrap();
+    (host, port)
+}
+
+pub async fn start_postgres() -> RunningContainer<Postgres> {
+    let container = Postgres::default().start().await.unwrap();
+    let (host, port) = host_port(&container, 5432).await;
+    RunningContainer {
+        url: format!("postgres://postgres:postgres@{}:{}/postgres", host, port),
+        _container: container,
+    }
+}
+
+pub async fn start_redis() -> RunningContainer<Redis> {
+    let container = Redis::default().start().await.unwrap();
+    let (host, port) = host_port(&container, REDIS_PORT).await;
+    RunningContainer {
+        url: format!("redis://{}:{}", host, port),
+        _container: container,
+    }
+}
+
+pub async fn start_kafka() -> RunningContainer<Kafka> {
+    let container = Kafka::default().start().await.unwrap();
+    let (host, port) = host_port(&container, KAFKA_PORT.as_u16()).await;
+    RunningContainer {
+        url: format!("{}:{}", host, port),
+        _container: container,
+    }
+}
+
+pub async fn start_rabbitmq() -> RunningContainer<RabbitMq> {
+    let container = RabbitMq::default().start().await.unwrap();
+    let (host, port) = host_port(&container, 5672).await;
+    RunningContainer {
+        url: format!("amqp://guest:guest@{}:{}", host, port),
+        _container: container,
+    }
+}
@@ -0,0 +1,266 @@
+//! Builder-style factories for test data with sensible defaults.
+//!
+//! ```ignore
+//! let user = UserFactory::admin().email("root@example.com").create(&context).await?;
+//! RefreshTokenFactory::for_user(user.id).expired().create(&context).await?;
+//! ```
+#![allow(dead_code)]
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use my_axum::{
+    core::context::Context,
+    pkg::password::hash_password_string,
+    user::{
+        entity::{
+            password_reset_token, refresh_token,
+            sea_orm_active_enums::{ExpirationPolicy, UserRole},
+            user,
+        },
+        repository::{password_reset_repository, refresh_token_repository, user_repository},
+    },
+};
+use sea_orm::{ActiveValue::Set, DbErr};
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+/// Plain-text password of users created without an explicit `password(...)`
+pub const DEFAULT_PASSWORD: &str = "password123@";
+
+/// Hashing is slow, so the default password hash is computed once per test binary
+static DEFAULT_PASSWORD_HASH: OnceCell<String> = OnceCell::const_new();
+
+async fn hash_password(password: &str) -> Result<String, DbErr> {
+    hash_password_string(password)
+        .await
+        .map_err(|e| DbErr::Custom(e.to_string()))
+}
+
+fn unique_suffix() -> String {
+    Uuid::new_v4().simple().to_string()[..12].to_string()
+}
+
+pub struct UserFactory {
+    email: Option<String>,
+    password: Option<String>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    phone: Option<String>,
+    role: UserRole,
+}
+
+impl Default for UserFactory {
+    fn default() -> Self {
+        Self {
+            email: None,
+            password: None,
+            first_name: Some("Test".to_string()),
+            last_name: Some("User".to_string()),
+            phone: None,
+            role: UserRole::User,
+        }
+    }
+}
+
+impl UserFactory {
+    /// A normal user with a unique email and `DEFAULT_PASSWORD`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An admin user with a unique email and `DEFAULT_PASSWORD`
+    pub fn admin() -> Self {
+        Self::default().role(UserRole::Admin)
+    }
+
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Plain-text password; it is hashed on `create`
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    pub fn first_name(mut self, first_name: impl Into<String>) -> Self {
+        self.first_name = Some(first_name.into());
+        self
+    }
+
+    pub fn last_name(mut self, last_name: impl Into<String>) -> Self {
+        self.last_name = Some(last_name.into());
+        self
+    }
+
+    pub fn phone(mut self, phone: impl Into<String>) -> Self {
+        self.phone = Some(phone.into());
+        self
+    }
+
+    pub fn role(mut self, role: UserRole) -> Self {
+        self.role = role;
+        self
+    }
+
+    pub async fn create(self, context: &Context) -> Result<user::Model, DbErr> {
+        let password = match self.password {
+            Some(password) => hash_password(&password).await?,
+            None => DEFAULT_PASSWORD_HASH
+                .get_or_try_init(|| hash_password(DEFAULT_PASSWORD))
+                .await?
+                .clone(),
+        };
+
+        user_repository::create(
+            context,
+            user::ActiveModel {
+                email: Set(self
+                    .email
+                    .unwrap_or_else(|| format!("user-{}@example.com", unique_suffix()))),
+                password: Set(password),
+                first_name: Set(self.first_name),
+                last_name: Set(self.last_name),
+                phone: Set(self.phone),
+                role: Set(self.role),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+}
+
+pub struct RefreshTokenFactory {
+    user_id: i32,
+    token: Option<String>,
+    device_info: Option<String>,
+    ip_address: Option<String>,
+    expires_at: NaiveDateTime,
+    max_expires_at: Option<NaiveDateTime>,
+}
+
+impl RefreshTokenFactory {
+    /// A random opaque token for `user_id`, valid for 7 days.
+    /// Use `token(...)` with a real JWT when the test goes through token verification.
+    pub fn for_user(user_id: i32) -> Self {
+        Self {
+            user_id,
+            token: None,
+            device_info: Some("Test Device".to_string()),
+            ip_address: Some("127.0.0.1".to_string()),
+            expires_at: Utc::now().naive_utc() + Duration::days(7),
+            max_expires_at: None,
+        }
+    }
+
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn device_info(mut self, device_info: impl Into<String>) -> Self {
+        self.device_info = Some(device_info.into());
+        self
+    }
+
+    pub fn ip_address(mut self, ip_address: impl Into<String>) -> Self {
+        self.ip_address = Some(ip_address.into());
+        self
+    }
+
+    pub fn expires_at(mut self, expires_at: NaiveDateTime) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
+    /// Give the token the sliding expiration policy, its session ending at `max_expires_at`
+    pub fn sliding(mut self, max_expires_at: NaiveDateTime) -> Self {
+        self.max_expires_at = Some(max_expires_at);
+        self
+    }
+
+    /// Expire the token `ago` in the past
+    pub fn expired_since(self, ago: Duration) -> Self {
+        self.expires_at(Utc::now().naive_utc() - ago)
+    }
+
+    pub fn expired(self) -> Self {
+        self.expired_since(Duration::hours(1))
+    }
+
+    pub async fn create(self, context: &Context) -> Result<refresh_token::Model, DbErr> {
+        refresh_token_repository::create(
+            context,
+            refresh_token::ActiveModel {
+                user_id: Set(self.user_id),
+                token: Set(self
+                    .token
+                    .unwrap_or_else(|| format!("refresh-{}", unique_suffix()))),
+                device_info: Set(self.device_info),
+                ip_address: Set(self.ip_address),
+                expires_at: Set(self.expires_at),
+                expiration_policy: Set(if self.max_expires_at.is_some() {
+                    ExpirationPolicy::Sliding
+                } else {
+                    ExpirationPolicy::Absolute
+                }),
+                max_expires_at: Set(self.max_expires_at),
+                created_at: Set(Some(Utc::now().naive_utc())),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+}
+
+pub struct PasswordResetTokenFactory {
+    user_id: i32,
+    token: String,
+    retry_count: i32,
+    expires_at: NaiveDateTime,
+}
+
+impl PasswordResetTokenFactory {
+    /// A `123456` OTP for `user_id`, valid for 15 minutes with no failed attempts
+    pub fn for_user(user_id: i32) -> Self {
+        Self {
+            user_id,
+            token: "123456".to_string(),
+            retry_count: 0,
+            expires_at: Utc::now().naive_utc() + Duration::minutes(15),
+        }
+    }
+
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = token.into();
+        self
+    }
+
+    pub fn retry_count(mut self, retry_count: i32) -> Self {
+        self.retry_count = retry_count;
+        self
+    }
+
+    pub fn expires_at(mut self, expires_at: NaiveDateTime) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
+    pub fn expired(self) -> Self {
+        self.expires_at(Utc::now().naive_utc() - Duration::minutes(1))
+    }
+
+    pub async fn create(self, context: &Context) -> Result<password_reset_token::Model, DbErr> {
+        password_reset_repository::create(
+            context,
+            password_reset_token::ActiveModel {
+                user_id: Set(self.user_id),
+                token: Set(self.token),
+                retry_count: Set(self.retry_count),
+                expires_at: Set(self.expires_at),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+}
@@ -1,42 +1,79 @@
 use my_axum::{
     core::context::Context,
-    pkg::password::hash_password_string,
-    user::entity::{sea_orm_active_enums::UserRole, user},
-    user::{repository::user_repository, service::auth_service},
+    user::{
+        entity::{permission, role},
+        repository::{permission_repository, role_repository},
+        service::auth_service,
+    },
 };
 use sea_orm::ActiveValue::Set;
 
+use super::factory::UserFactory;
+
 pub async fn login_normal_user(context: &mut Context) -> (String, String) {
-    let hashed_password = hash_password_string("user_password").await.unwrap();
-    let user = user::ActiveModel {
-        email: Set("user@example.com".to_string()),
-        password: Set(hashed_password),
-        role: Set(UserRole::User),
-        first_name: Set(Some("Normal".to_string())),
-        last_name: Set(Some("User".to_string())),
-        phone: Set(None),
-        ..Default::default()
-    };
-    let user = user_repository::create(context, user).await.unwrap();
+    let user = UserFactory::new()
+        .email("user@example.com")
+        .password("user_password")
+        .first_name("Normal")
+        .create(context)
+        .await
+        .unwrap();
     context.user = Some(user.clone());
-    let (access_token, refresh_token) = auth_service::generate_token_pair(user.id).await.unwrap();
+    let (access_token, refresh_token) = auth_service::generate_token_pair(context, user.id)
+        .await
+        .unwrap();
     (access_token, refresh_token)
 }
 
 #[allow(dead_code)]
 pub async fn login_admin_user(context: &mut Context) -> (String, String) {
-    let hashed_password = hash_password_string("admin_password").await.unwrap();
-    let user = user::ActiveModel {
-        email: Set("admin@example.com".to_string()),
-        password: Set(hashed_password),
-        role: Set(UserRole::Admin),
-        first_name: Set(Some("Admin".to_string())),
-        last_name: Set(Some("User".to_string())),
-        phone: Set(None),
-        ..Default::default()
-    };
-    let user = user_repository::create(context, user).await.unwrap();
+    let user = UserFactory::admin()
+        .email("admin@example.com")
+        .password("admin_password")
+        .first_name("Admin")
+        .create(context)
+        .await
+        .unwrap();
     context.user = Some(user.clone());
-    let (access_token, refresh_token) = auth_service::generate_token_pair(user.id).await.unwrap();
+    let (access_token, refresh_token) = auth_service::generate_token_pair(context, user.id)
+        .await
+        .unwrap();
     (access_token, refresh_token)
 }
+
+/// Give user `user_id` a role granting `permissions`, creating the ones that don't exist
+#[allow(dead_code)]
+pub async fn grant_permissions(context: &Context, user_id: i32, permissions: &[&str]) {
+    let role = role_repository::create(
+        context,
+        role::ActiveModel {
+            name: Set(format!("role-of-{}", user_id)),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    for name in permissions {
+        let permission = match permission_repository::find_by_name(context, name)
+            .await
+            .unwrap()
+        {
+            Some(permission) => permission,
+            None => permission_repository::create(
+                context,
+                permission::ActiveModel {
+                    name: Set(name.to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap(),
+        };
+        role_repository::grant_permission(context, role.id, permission.id)
+            .await
+            .unwrap();
+    }
+    role_repository::assign_to_user(context, user_id, role.id)
+        .await
+        .unwrap();
+}
@@ -1,2 +1,7 @@
 pub mod app;
+pub mod broker;
+pub mod client;
+#[cfg(feature = "it")]
+pub mod containers;
+pub mod factory;
 pub mod fixture;
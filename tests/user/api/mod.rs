@@ -1,3 +1,12 @@
 mod test_auth_api;
+mod test_bulk_user_api;
+mod test_email_verification_api;
+mod test_impersonation_api;
+mod test_login_lockout_api;
+mod test_phone_verification_api;
+mod test_session_api;
+mod test_sign_in_alert_api;
+mod test_soft_delete_api;
 mod test_user_api;
+mod test_user_email_api;
 mod test_user_ws;
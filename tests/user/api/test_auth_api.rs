@@ -80,29 +80,11 @@ mod get_profile_tests {
 
     #[tokio::test]
     async fn test_update_profile_api_success_for_authenticated_user() {
-        // Arrange - Register a normal user and get access token
+        // Arrange - Register a normal user and get an authenticated client
         let test_app = TestApp::spawn_app().await;
-        let client = Client::new();
-        let register_payload = json!({
-            "email": "updateprofileuser@example.com",
-            "password": "password123@",
-            "first_name": "Old",
-            "last_name": "Name",
-            "phone": "1111111111"
-        });
-
-        let register_response = client
-            .post(format!(
-                "http://{}/api/v1/auth/register/",
-                &test_app.base_url
-            ))
-            .json(&register_payload)
-            .send()
-            .await
-            .unwrap();
-
-        let register_result = register_response.json::<Value>().await.unwrap();
-        let access_token = register_result.get("access").unwrap().as_str().unwrap();
+        let client = test_app
+            .register_and_login("updateprofileuser@example.com")
+            .await;
 
         // Act - Update current user's profile without admin role
         let update_payload = json!({
@@ -110,16 +92,7 @@ mod get_profile_tests {
             "last_name": "Profile",
             "phone": "2222222222"
         });
-        let response = client
-            .patch(format!(
-                "http://{}/api/v1/user/profile/",
-                &test_app.base_url
-            ))
-            .header("Authorization", format!("Bearer {}", access_token))
-            .json(&update_payload)
-            .send()
-            .await
-            .unwrap();
+        let response = client.patch("/api/v1/user/profile/", &update_payload).await;
 
         // Assert
         assert_eq!(response.status(), StatusCode::OK);
@@ -136,6 +109,94 @@ mod get_profile_tests {
         );
         assert_eq!(result.get("phone").unwrap().as_str().unwrap(), "2222222222");
     }
+
+    #[tokio::test]
+    async fn test_get_profile_api_refreshes_rejected_access_token() {
+        // Arrange - Replace the access token with one the server rejects
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("staletoken@example.com").await;
+        let refresh_token = client.refresh_token();
+        client.set_access_token("stale_access_token");
+
+        // Act
+        let response = client.get("/api/v1/user/profile/").await;
+
+        // Assert - The client refreshed its tokens and retried
+        assert_eq!(response.status(), StatusCode::OK);
+        let result = response.json::<Value>().await.unwrap();
+        assert_eq!(
+            result.get("email").unwrap().as_str().unwrap(),
+            "staletoken@example.com"
+        );
+        assert_ne!(client.access_token(), "stale_access_token");
+        assert_ne!(client.refresh_token(), refresh_token);
+    }
+
+    #[tokio::test]
+    async fn test_profile_timestamps_use_preferred_timezone() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("timezone@example.com").await;
+        let utc = client.get("/api/v1/user/profile/").await;
+        let utc = utc.json::<Value>().await.unwrap()["created_at"].clone();
+
+        // Act
+        let response = client
+            .patch(
+                "/api/v1/user/profile/",
+                &json!({ "timezone": "Asia/Ho_Chi_Minh" }),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = client.get("/api/v1/user/profile/").await;
+
+        // Assert - The same instant, rendered with the user's offset
+        let result = response.json::<Value>().await.unwrap();
+        assert_eq!(result["timezone"], "Asia/Ho_Chi_Minh");
+        let utc = chrono::DateTime::parse_from_rfc3339(utc.as_str().unwrap()).unwrap();
+        let local = result["created_at"].as_str().unwrap();
+        assert!(local.ends_with("+07:00"), "{}", local);
+        assert_eq!(utc.offset().local_minus_utc(), 0);
+        assert_eq!(chrono::DateTime::parse_from_rfc3339(local).unwrap(), utc);
+    }
+
+    #[tokio::test]
+    async fn test_preferred_locale_overrides_accept_language() {
+        // Arrange - A user who prefers Vietnamese, on a browser asking for English
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("vietnamese@example.com").await;
+        let response = client
+            .patch("/api/v1/user/profile/", &json!({ "locale": "vi" }))
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let invalid_locale = json!({ "locale": "fr" });
+
+        // Act
+        let preferred = Client::new()
+            .patch(client.url("/api/v1/user/profile/"))
+            .bearer_auth(client.access_token())
+            .header("Accept-Language", "en-US,en;q=0.9")
+            .json(&invalid_locale)
+            .send()
+            .await
+            .unwrap();
+        let explicit = client
+            .patch("/api/v1/user/profile/?lang=en", &invalid_locale)
+            .await;
+
+        // Assert - Errors use the preferred locale unless `lang` is given
+        assert_eq!(preferred.status(), StatusCode::BAD_REQUEST);
+        let message = preferred.json::<Value>().await.unwrap()["message"].clone();
+        assert_eq!(
+            message,
+            "Ngôn ngữ \"fr\" không được hỗ trợ, chọn một trong: en, vi"
+        );
+        let message = explicit.json::<Value>().await.unwrap()["message"].clone();
+        assert_eq!(
+            message,
+            "Unsupported locale \"fr\", expected one of: en, vi"
+        );
+    }
 }
 
 mod change_password_tests {
@@ -223,28 +284,11 @@ mod change_password_tests {
 
     #[tokio::test]
     async fn test_change_password_api_wrong_old_password() {
-        // Arrange - Register a user and get access token
+        // Arrange - Register a user and get an authenticated client
         let test_app = TestApp::spawn_app().await;
-        let client = Client::new();
-        let register_payload = json!({
-            "email": "wrongoldpass@example.com",
-            "password": "correctpassword123@",
-            "first_name": "Wrong",
-            "last_name": "User"
-        });
-
-        let register_response = client
-            .post(format!(
-                "http://{}/api/v1/auth/register/",
-                &test_app.base_url
-            ))
-            .json(&register_payload)
-            .send()
-            .await
-            .unwrap();
-
-        let register_result = register_response.json::<Value>().await.unwrap();
-        let access_token = register_result.get("access").unwrap().as_str().unwrap();
+        let client = test_app
+            .register_and_login("wrongoldpass@example.com")
+            .await;
 
         // Act - Try to change password with wrong old password
         let change_password_payload = json!({
@@ -253,15 +297,8 @@ mod change_password_tests {
         });
 
         let response = client
-            .post(format!(
-                "http://{}/api/v1/auth/change-password/",
-                &test_app.base_url
-            ))
-            .header("Authorization", format!("Bearer {}", access_token))
-            .json(&change_password_payload)
-            .send()
-            .await
-            .unwrap();
+            .post("/api/v1/auth/change-password/", &change_password_payload)
+            .await;
 
         // Assert
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
@@ -439,8 +476,10 @@ mod login_tests {
 
 mod register_tests {
     use axum::http::StatusCode;
+    use my_axum::core::r#async::TaskType;
     use reqwest::Client;
     use serde_json::{Value, json};
+    use std::time::Duration;
 
     use crate::setup::app::TestApp;
 
@@ -475,6 +514,58 @@ mod register_tests {
         assert!(!result.get("refresh").unwrap().as_str().unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_register_api_welcome_email_pipeline() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.spawn_worker();
+        let client = Client::new();
+        let payload = json!({
+            "email": "pipeline@example.com",
+            "password": "password123@",
+            "first_name": "Pipe",
+        });
+
+        // Act
+        let response = client
+            .post(format!(
+                "http://{}/api/v1/auth/register/",
+                &test_app.base_url
+            ))
+            .json(&payload)
+            .send()
+            .await
+            .unwrap();
+        test_app.broker.wait_for_idle(Duration::from_secs(5)).await;
+
+        // Assert: registration tasks are consumed and the worker delivers the welcome and
+        // verification emails
+        assert_eq!(response.status(), StatusCode::OK);
+        let tasks = test_app.broker.tasks("emails");
+        assert_eq!(tasks.len(), 4);
+        assert!(matches!(
+            tasks[0].task,
+            TaskType::ProcessUserRegistration { .. }
+        ));
+        assert!(matches!(
+            tasks[1].task,
+            TaskType::SendVerificationEmail { .. }
+        ));
+
+        let emails = test_app.emails().await;
+        assert_eq!(emails.len(), 2);
+        assert!(
+            emails
+                .iter()
+                .all(|email| email.to == "pipeline@example.com")
+        );
+        let welcome = emails
+            .iter()
+            .find(|email| email.subject == "Welcome to My Axum App!")
+            .unwrap();
+        assert!(welcome.html_body.as_deref().unwrap().contains("Hello Pipe"));
+    }
+
     #[tokio::test]
     async fn test_register_api_sets_cookies() {
         // Arrange
@@ -551,6 +642,71 @@ mod register_tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn test_register_api_reports_every_invalid_field() {
+        let test_app = TestApp::spawn_app().await;
+        let client = Client::new();
+        let payload = json!({
+            "email": "invalid-email",
+            "password": " ",
+            "phone": "0".repeat(40)
+        });
+
+        let response = client
+            .post(format!(
+                "http://{}/api/v1/auth/register/",
+                &test_app.base_url
+            ))
+            .json(&payload)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = response.json().await.unwrap();
+        let fields: Vec<&str> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(body["code"], "VALIDATION_FAILED");
+        assert_eq!(fields, ["email", "password", "phone"]);
+        assert_eq!(body["message"], body["errors"][0]["message"]);
+    }
+
+    #[tokio::test]
+    async fn test_register_api_lists_broken_password_rules() {
+        let test_app = TestApp::spawn_app().await;
+        let client = Client::new();
+        let payload = json!({
+            "email": "alice@example.com",
+            "password": "alice"
+        });
+
+        let response = client
+            .post(format!(
+                "http://{}/api/v1/auth/register/",
+                &test_app.base_url
+            ))
+            .json(&payload)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = response.json().await.unwrap();
+        let rules: Vec<&str> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["rule"].as_str().unwrap())
+            .collect();
+        assert_eq!(body["code"], "AUTH_PASSWORD_TOO_WEAK");
+        assert_eq!(rules, ["min_length", "email"]);
+        assert_eq!(body["errors"][0]["field"], "password");
+    }
+
     #[tokio::test]
     async fn test_register_api_duplicate_email() {
         let test_app = TestApp::spawn_app().await;
@@ -588,6 +744,45 @@ mod register_tests {
 
         assert_eq!(response2.status(), StatusCode::CONFLICT);
     }
+
+    #[tokio::test]
+    async fn test_register_api_email_differing_in_case_is_same_account() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = Client::new();
+        let register = |email: &str| {
+            client
+                .post(format!(
+                    "http://{}/api/v1/auth/register/",
+                    &test_app.base_url
+                ))
+                .json(&json!({
+                    "email": email,
+                    "password": "password123@",
+                    "first_name": "Test",
+                    "last_name": "User"
+                }))
+                .send()
+        };
+        let first = register("Test.User@Example.COM").await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+
+        // Act
+        let second = register("test.user@example.com").await.unwrap();
+        let login = client
+            .post(format!("http://{}/api/v1/auth/login/", &test_app.base_url))
+            .json(&json!({
+                "email": "TEST.USER@example.com",
+                "password": "password123@"
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(second.status(), StatusCode::CONFLICT);
+        assert_eq!(login.status(), StatusCode::OK);
+    }
 }
 
 mod refresh_token_tests {
@@ -768,13 +963,19 @@ mod forgot_password_tests {
 
     use crate::setup::app::TestApp;
     use my_axum::{
-        core::context::Context,
-        user::{dto::user_dto::UserCreateDTO, use_case::user::create_user_use_case},
+        config::setting::Setting,
+        core::{context::Context, id::SequentialIdGenerator},
+        user::{
+            dto::user_dto::UserCreateDTO, entity::password_reset_token,
+            use_case::user::create_user_use_case,
+        },
     };
+    use sea_orm::EntityTrait;
 
     #[tokio::test]
     async fn test_forgot_password_api_success() {
         let test_app = TestApp::spawn_app().await;
+        test_app.spawn_worker();
 
         // Create a user first
         let user_email = test_app
@@ -815,9 +1016,37 @@ mod forgot_password_tests {
             .await
             .unwrap();
 
-        // Will return 500 because no producer in test environment
-        // but this verifies the API endpoint is working
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        // The reset email goes through the broker and is delivered by the worker
+        test_app
+            .broker
+            .wait_for_idle(std::time::Duration::from_secs(5))
+            .await;
+        let emails = test_app.emails().await;
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].to, user_email);
+        assert_eq!(emails[0].subject, "Password Reset Request - My Axum App");
+
+        // The first OTP issued by the app's sequential generator
+        let otp = SequentialIdGenerator::nth_otp(1, 6);
+        let html_body = emails[0].html_body.as_deref().unwrap();
+        let stored_otp = test_app
+            .db
+            .transaction::<_, String, DbErr>(|txn| {
+                Box::pin(async move {
+                    let token = password_reset_token::Entity::find()
+                        .one(txn)
+                        .await?
+                        .unwrap();
+                    Ok(token.token)
+                })
+            })
+            .await
+            .unwrap();
+        // The OTP is emailed, but only its hash is stored
+        assert_eq!(stored_otp, Setting::new().token_hasher().hash(&otp));
+        assert!(html_body.contains(&otp));
     }
 
     #[tokio::test]
@@ -876,6 +1105,7 @@ mod reset_password_tests {
     use crate::setup::app::TestApp;
     use chrono::{Duration, Utc};
     use my_axum::{
+        config::setting::Setting,
         core::context::Context,
         user::entity::password_reset_token,
         user::{
@@ -1027,6 +1257,68 @@ mod reset_password_tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn test_reset_password_api_keeps_failed_attempts_until_lockout() {
+        let test_app = TestApp::spawn_app().await;
+
+        // Create a user and OTP token
+        let (user_email, otp) = test_app
+            .db
+            .transaction::<_, (String, String), DbErr>(|txn| {
+                Box::pin(async move {
+                    let context = Context::builder(Arc::new(txn.begin().await?)).build();
+                    let dto = UserCreateDTO {
+                        email: "test@example.com".to_string(),
+                        password: "old_password123@".to_string(),
+                        first_name: Some("Test".to_string()),
+                        last_name: Some("User".to_string()),
+                        phone: Some("1234567890".to_string()),
+                    };
+                    let user = create_user_use_case::execute(&context, dto)
+                        .await
+                        .unwrap()
+                        .data;
+
+                    let otp = "123456";
+                    create_reset_token(&context, user.id, otp).await;
+
+                    context.commit().await?;
+
+                    Ok((user.email, otp.to_string()))
+                })
+            })
+            .await
+            .unwrap();
+
+        let client = Client::new();
+        let reset_password = |otp: String| {
+            client
+                .post(format!(
+                    "http://{}/api/v1/auth/reset-password/",
+                    &test_app.base_url
+                ))
+                .json(&json!({
+                    "email": user_email,
+                    "otp": otp,
+                    "new_password": "new_password456"
+                }))
+                .send()
+        };
+
+        // Failed attempts outlive their requests, so the last one locks the OTP out
+        let max_attempts = Setting::new().password_reset.max_attempts;
+        let mut code = Value::Null;
+        for _ in 0..max_attempts {
+            let response = reset_password("000000".to_string()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            code = response.json::<Value>().await.unwrap()["code"].clone();
+        }
+        assert_eq!(code, "AUTH_OTP_ATTEMPTS_EXCEEDED");
+
+        let response = reset_password(otp).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn test_reset_password_api_expired_otp() {
         let test_app = TestApp::spawn_app().await;
@@ -1090,14 +1382,7 @@ mod reset_password_tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
         let result = response.json::<Value>().await.unwrap();
-        assert!(
-            result
-                .get("message")
-                .unwrap()
-                .as_str()
-                .unwrap()
-                .contains("expired")
-        );
+        assert_eq!(result["code"], "AUTH_OTP_EXPIRED");
     }
 
     #[tokio::test]
@@ -1178,3 +1463,107 @@ mod reset_password_tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 }
+
+mod reset_password_link_tests {
+    use axum::http::StatusCode;
+    use chrono::{Duration, Utc};
+    use my_axum::{core::context::Context, user::service::auth_service};
+    use reqwest::Client;
+    use serde_json::{Value, json};
+    use std::sync::Arc;
+
+    use crate::setup::{
+        app::TestApp,
+        factory::{PasswordResetTokenFactory, UserFactory},
+    };
+
+    const NONCE: &str = "LINKNONCE0000000000000000000000C";
+
+    /// Store the nonce of a reset link for a new user, returning the signed link token
+    async fn create_reset_link(test_app: &TestApp, email: &str) -> String {
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new()
+            .email(email)
+            .create(&context)
+            .await
+            .unwrap();
+        PasswordResetTokenFactory::for_user(user.id)
+            .token(NONCE)
+            .create(&context)
+            .await
+            .unwrap();
+        context.commit().await.unwrap();
+        auth_service::sign_password_reset_link(NONCE, Utc::now() + Duration::hours(1))
+    }
+
+    fn confirm_url(test_app: &TestApp, token: &str) -> String {
+        format!(
+            "http://{}/api/v1/auth/reset-password/confirm/{}/",
+            test_app.base_url, token
+        )
+    }
+
+    #[tokio::test]
+    async fn test_reset_password_link_api_success() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let token = create_reset_link(&test_app, "link-api@example.com").await;
+        let client = Client::new();
+
+        // Act: check the link, then set the new password through it
+        let verified = client
+            .get(confirm_url(&test_app, &token))
+            .send()
+            .await
+            .unwrap();
+        let confirmed = client
+            .post(confirm_url(&test_app, &token))
+            .json(&json!({ "new_password": "new_password456@" }))
+            .send()
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(verified.status(), StatusCode::OK);
+        let body: Value = verified.json().await.unwrap();
+        assert_eq!(body["email"], "link-api@example.com");
+        assert_eq!(confirmed.status(), StatusCode::NO_CONTENT);
+
+        let login = client
+            .post(format!("http://{}/api/v1/auth/login/", test_app.base_url))
+            .json(&json!({ "email": "link-api@example.com", "password": "new_password456@" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(login.status(), StatusCode::OK);
+
+        // Assert: the link is used up
+        let reused = client
+            .post(confirm_url(&test_app, &token))
+            .json(&json!({ "new_password": "another_password789@" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(reused.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_reset_password_link_api_rejects_tampered_token() {
+        // Arrange: push the signed expiry back by a day
+        let test_app = TestApp::spawn_app().await;
+        let token = create_reset_link(&test_app, "link-tampered@example.com").await;
+        let mut parts: Vec<String> = token.split('.').map(str::to_string).collect();
+        parts[1] = (parts[1].parse::<i64>().unwrap() + 86400).to_string();
+        let tampered = parts.join(".");
+
+        // Act
+        let response = Client::new()
+            .get(confirm_url(&test_app, &tampered))
+            .send()
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,217 @@
+use std::sync::Arc;
+
+use my_axum::{
+    core::context::Context,
+    user::{entity::sea_orm_active_enums::UserRole, repository::user_repository},
+};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+use crate::setup::{
+    app::TestApp,
+    factory::{DEFAULT_PASSWORD, UserFactory},
+    fixture::{grant_permissions, login_admin_user, login_normal_user},
+};
+
+async fn bulk(test_app: &TestApp, access_token: &str, payload: Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!(
+            "http://{}/api/v1/admin/users/bulk/",
+            test_app.base_url
+        ))
+        .bearer_auth(access_token)
+        .json(&payload)
+        .send()
+        .await
+        .unwrap()
+}
+
+/// Log in as an admin and create two users to operate on
+async fn setup(test_app: &TestApp) -> (String, i32, i32, i32) {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = login_admin_user(&mut context).await;
+    let admin_id = context.user.as_ref().unwrap().id;
+    let first = UserFactory::new().create(&context).await.unwrap();
+    let second = UserFactory::new().create(&context).await.unwrap();
+    context.commit().await.unwrap();
+    (access_token, admin_id, first.id, second.id)
+}
+
+#[tokio::test]
+async fn test_bulk_operations_report_each_item() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (access_token, admin_id, first_id, second_id) = setup(&test_app).await;
+
+    // Act
+    let response = bulk(
+        &test_app,
+        &access_token,
+        json!({"operations": [
+            {"action": "deactivate", "user_id": first_id},
+            {"action": "delete", "user_id": 999_999},
+            {"action": "assign_role", "user_id": second_id, "role": "admin"},
+            {"action": "delete", "user_id": admin_id},
+        ]}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["task_id"].is_null());
+    let report = &body["report"];
+    assert_eq!(report["succeeded"], 2);
+    assert_eq!(report["failed"], 2);
+    let results = report["results"].as_array().unwrap();
+    assert_eq!(
+        results
+            .iter()
+            .map(|result| result["success"].as_bool().unwrap())
+            .collect::<Vec<_>>(),
+        vec![true, false, true, false]
+    );
+    assert_eq!(results[1]["user_id"], 999_999);
+    assert_eq!(results[1]["error"], "User not found");
+    assert_eq!(
+        results[3]["error"],
+        "Bulk operations can't target your own account"
+    );
+
+    let context = Context::read_only(test_app.db.clone()).build();
+    let first = user_repository::find_by_id(&context, first_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(first.deactivated_at.is_some());
+    let second = user_repository::find_by_id(&context, second_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(second.role, UserRole::Admin);
+    assert!(
+        user_repository::find_by_id(&context, admin_id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn test_deactivated_user_is_signed_out() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (access_token, _, _, _) = setup(&test_app).await;
+    let user = test_app.register_and_login("deactivated@example.com").await;
+    assert_eq!(
+        user.get("/api/v1/user/profile/").await.status(),
+        StatusCode::OK
+    );
+    let context = Context::read_only(test_app.db.clone()).build();
+    let user_id = user_repository::find_by_email(&context, "deactivated@example.com")
+        .await
+        .unwrap()
+        .unwrap()
+        .id;
+
+    // Act
+    let response = bulk(
+        &test_app,
+        &access_token,
+        json!({"operations": [{"action": "deactivate", "user_id": user_id}]}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        user.get("/api/v1/user/profile/").await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(user.refresh().await, StatusCode::UNAUTHORIZED);
+    let login = reqwest::Client::new()
+        .post(format!("http://{}/api/v1/auth/login/", test_app.base_url))
+        .json(&json!({"email": "deactivated@example.com", "password": DEFAULT_PASSWORD}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(login.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_bulk_operations_reject_empty_batch() {
+    let test_app = TestApp::spawn_app().await;
+    let (access_token, _, _, _) = setup(&test_app).await;
+
+    let response = bulk(&test_app, &access_token, json!({"operations": []})).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_bulk_operations_require_admin() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = login_normal_user(&mut context).await;
+    let other = UserFactory::new().create(&context).await.unwrap();
+    context.commit().await.unwrap();
+
+    // Act
+    let response = bulk(
+        &test_app,
+        &access_token,
+        json!({"operations": [{"action": "delete", "user_id": other.id}]}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_bulk_operations_allowed_by_permissions() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = login_normal_user(&mut context).await;
+    let user_id = context.user.as_ref().unwrap().id;
+    grant_permissions(
+        &context,
+        user_id,
+        &["user.bulk", "user.delete", "user.update"],
+    )
+    .await;
+    let first = UserFactory::new().create(&context).await.unwrap();
+    let second = UserFactory::new().create(&context).await.unwrap();
+    context.commit().await.unwrap();
+
+    // Act
+    let response = bulk(
+        &test_app,
+        &access_token,
+        json!({"operations": [
+            {"action": "delete", "user_id": first.id},
+            {"action": "assign_role", "user_id": second.id, "role": "admin"},
+        ]}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    let results = body["report"]["results"].as_array().unwrap();
+    assert_eq!(results[0]["success"], true);
+    assert_eq!(results[1]["success"], false);
+    assert_eq!(results[1]["error"], "Admin role is required");
+
+    let context = Context::read_only(test_app.db.clone()).build();
+    let second = user_repository::find_by_id(&context, second.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(second.role, UserRole::User);
+}
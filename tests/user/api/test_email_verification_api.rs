@@ -0,0 +1,121 @@
+mod email_verification_api_tests {
+    use std::time::Duration;
+
+    use reqwest::{Client, Response, StatusCode};
+    use serde_json::{Value, json};
+
+    use crate::setup::app::TestApp;
+
+    async fn post(test_app: &TestApp, path: &str, body: &Value) -> Response {
+        Client::new()
+            .post(format!("http://{}{}", test_app.base_url, path))
+            .json(body)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    /// Token of the verification link emailed to `email`
+    async fn emailed_token(test_app: &TestApp, email: &str) -> String {
+        let emails = test_app.emails().await;
+        let html_body = emails
+            .iter()
+            .find(|sent| sent.to == email && sent.subject.starts_with("Verify your email"))
+            .and_then(|sent| sent.html_body.clone())
+            .expect("verification link should be emailed");
+        let start = html_body.find("token=").unwrap() + "token=".len();
+        html_body[start..]
+            .chars()
+            .take_while(char::is_ascii_alphanumeric)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_registration_email_verifies_account_once() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.spawn_worker();
+        let client = test_app.register_and_login("verify@example.com").await;
+        test_app.broker.wait_for_idle(Duration::from_secs(5)).await;
+        let token = emailed_token(&test_app, "verify@example.com").await;
+
+        // Act
+        let before = client
+            .get("/api/v1/user/profile/")
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+        let verified = post(
+            &test_app,
+            "/api/v1/auth/verify-email/",
+            &json!({ "token": token }),
+        )
+        .await;
+        let reused = post(
+            &test_app,
+            "/api/v1/auth/verify-email/",
+            &json!({ "token": token }),
+        )
+        .await;
+        let after = client
+            .get("/api/v1/user/profile/")
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(token.len(), 32);
+        assert_eq!(before["email_verified"], false);
+        assert_eq!(verified.status(), StatusCode::NO_CONTENT);
+        assert_eq!(reused.status(), StatusCode::BAD_REQUEST);
+        let reused = reused.json::<Value>().await.unwrap();
+        assert_eq!(reused["code"], "AUTH_EMAIL_VERIFICATION_INVALID");
+        assert_eq!(after["email_verified"], true);
+    }
+
+    #[tokio::test]
+    async fn test_resend_verification_emails_only_unverified_accounts() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.spawn_worker();
+        test_app.register_and_login("resend@example.com").await;
+        test_app.broker.wait_for_idle(Duration::from_secs(5)).await;
+        let first_token = emailed_token(&test_app, "resend@example.com").await;
+
+        // Act
+        let resent = post(
+            &test_app,
+            "/api/v1/auth/resend-verification/",
+            &json!({ "email": "resend@example.com" }),
+        )
+        .await;
+        let unknown = post(
+            &test_app,
+            "/api/v1/auth/resend-verification/",
+            &json!({ "email": "nobody@example.com" }),
+        )
+        .await;
+        test_app.broker.wait_for_idle(Duration::from_secs(5)).await;
+        let stale = post(
+            &test_app,
+            "/api/v1/auth/verify-email/",
+            &json!({ "token": first_token }),
+        )
+        .await;
+
+        // Assert
+        assert_eq!(resent.status(), StatusCode::NO_CONTENT);
+        assert_eq!(unknown.status(), StatusCode::NO_CONTENT);
+        let emails = test_app.emails().await;
+        assert!(emails.iter().all(|email| email.to != "nobody@example.com"));
+        let verification_emails = emails
+            .iter()
+            .filter(|email| email.subject.starts_with("Verify your email"))
+            .count();
+        assert_eq!(verification_emails, 2);
+        // A new link replaces the ones sent before
+        assert_eq!(stale.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,355 @@
+use std::sync::Arc;
+
+use my_axum::core::context::Context;
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+use crate::setup::{
+    app::TestApp,
+    factory::UserFactory,
+    fixture::{grant_permissions, login_admin_user, login_normal_user},
+};
+
+/// Log in as an admin and create a user and another admin to impersonate
+async fn setup(test_app: &TestApp) -> (String, i32, i32, i32) {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = login_admin_user(&mut context).await;
+    let admin_id = context.user.as_ref().unwrap().id;
+    let user = UserFactory::new()
+        .email("impersonated@example.com")
+        .create(&context)
+        .await
+        .unwrap();
+    let other_admin = UserFactory::admin().create(&context).await.unwrap();
+    context.commit().await.unwrap();
+    (access_token, admin_id, user.id, other_admin.id)
+}
+
+async fn request(
+    test_app: &TestApp,
+    method: reqwest::Method,
+    access_token: &str,
+    path: &str,
+    body: Option<Value>,
+) -> reqwest::Response {
+    let mut request = reqwest::Client::new()
+        .request(method, format!("http://{}{}", test_app.base_url, path))
+        .bearer_auth(access_token);
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+    request.send().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_admin_acts_as_user_until_impersonation_ends() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (admin_token, admin_id, user_id, _) = setup(&test_app).await;
+    let path = format!("/api/v1/admin/impersonate/{}/", user_id);
+
+    // Act
+    let response = request(&test_app, reqwest::Method::POST, &admin_token, &path, None).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let impersonation: Value = response.json().await.unwrap();
+    let token = impersonation["access"].as_str().unwrap().to_string();
+    let profile: Value = request(
+        &test_app,
+        reqwest::Method::GET,
+        &token,
+        "/api/v1/user/profile/",
+        None,
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    let updated = request(
+        &test_app,
+        reqwest::Method::PATCH,
+        &token,
+        "/api/v1/user/profile/",
+        Some(json!({ "first_name": "Changed" })),
+    )
+    .await
+    .status();
+    let ended = request(&test_app, reqwest::Method::DELETE, &token, &path, None)
+        .await
+        .status();
+    let after_end = request(
+        &test_app,
+        reqwest::Method::GET,
+        &token,
+        "/api/v1/user/profile/",
+        None,
+    )
+    .await;
+
+    // Assert
+    assert_eq!(impersonation["user_id"], user_id);
+    assert_eq!(profile["email"], "impersonated@example.com");
+    assert_eq!(updated, StatusCode::OK);
+    assert_eq!(ended, StatusCode::NO_CONTENT);
+    assert_eq!(after_end.status(), StatusCode::UNAUTHORIZED);
+    let error: Value = after_end.json().await.unwrap();
+    assert_eq!(error["code"], "AUTH_IMPERSONATION_ENDED");
+
+    // Actions taken while impersonating record both the admin and the user
+    let logs: Value = request(
+        &test_app,
+        reqwest::Method::GET,
+        &admin_token,
+        &format!("/api/v1/admin/audit-logs/?resource_id={}", user_id),
+        None,
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    let entries = logs["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|log| {
+            (
+                log["action"].as_str().unwrap().to_string(),
+                log["actor"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let admin = format!("user:{}", admin_id);
+    let impersonating = format!("user:{} as user:{}", admin_id, user_id);
+    assert_eq!(
+        entries,
+        vec![
+            ("impersonation_ended".to_string(), impersonating.clone()),
+            ("profile_updated".to_string(), impersonating.clone()),
+            ("update".to_string(), impersonating),
+            ("impersonation_started".to_string(), admin.clone()),
+            ("create".to_string(), admin),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_admin_ends_impersonation_with_own_token() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (admin_token, _, user_id, _) = setup(&test_app).await;
+    let path = format!("/api/v1/admin/impersonate/{}/", user_id);
+    let impersonation: Value = request(&test_app, reqwest::Method::POST, &admin_token, &path, None)
+        .await
+        .json()
+        .await
+        .unwrap();
+    let token = impersonation["access"].as_str().unwrap();
+
+    // Act
+    let ended = request(
+        &test_app,
+        reqwest::Method::DELETE,
+        &admin_token,
+        &path,
+        None,
+    )
+    .await
+    .status();
+    let after_end = request(
+        &test_app,
+        reqwest::Method::GET,
+        token,
+        "/api/v1/user/profile/",
+        None,
+    )
+    .await
+    .status();
+    let admin_after_end = request(
+        &test_app,
+        reqwest::Method::GET,
+        &admin_token,
+        "/api/v1/user/profile/",
+        None,
+    )
+    .await
+    .status();
+
+    // Assert
+    assert_eq!(ended, StatusCode::NO_CONTENT);
+    assert_eq!(after_end, StatusCode::UNAUTHORIZED);
+    assert_eq!(admin_after_end, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_impersonation_rejects_admins_and_non_admins() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (admin_token, _, user_id, other_admin_id) = setup(&test_app).await;
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (user_token, _) = login_normal_user(&mut context).await;
+    context.commit().await.unwrap();
+
+    // Act
+    let as_user = request(
+        &test_app,
+        reqwest::Method::POST,
+        &user_token,
+        &format!("/api/v1/admin/impersonate/{}/", user_id),
+        None,
+    )
+    .await
+    .status();
+    let of_admin = request(
+        &test_app,
+        reqwest::Method::POST,
+        &admin_token,
+        &format!("/api/v1/admin/impersonate/{}/", other_admin_id),
+        None,
+    )
+    .await;
+    let of_unknown = request(
+        &test_app,
+        reqwest::Method::POST,
+        &admin_token,
+        "/api/v1/admin/impersonate/999999/",
+        None,
+    )
+    .await
+    .status();
+
+    // Assert
+    assert_eq!(as_user, StatusCode::FORBIDDEN);
+    assert_eq!(of_admin.status(), StatusCode::FORBIDDEN);
+    let error: Value = of_admin.json().await.unwrap();
+    assert_eq!(error["code"], "USER_IMPERSONATION_FORBIDDEN");
+    assert_eq!(of_unknown, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_impersonation_allowed_with_permission() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (_, _, user_id, _) = setup(&test_app).await;
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = login_normal_user(&mut context).await;
+    let support_id = context.user.as_ref().unwrap().id;
+    grant_permissions(&context, support_id, &["user.impersonate"]).await;
+    context.commit().await.unwrap();
+
+    // Act
+    let response = request(
+        &test_app,
+        reqwest::Method::POST,
+        &access_token,
+        &format!("/api/v1/admin/impersonate/{}/", user_id),
+        None,
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let impersonation: Value = response.json().await.unwrap();
+    let token = impersonation["access"].as_str().unwrap().to_string();
+    let profile: Value = request(
+        &test_app,
+        reqwest::Method::GET,
+        &token,
+        "/api/v1/user/profile/",
+        None,
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    assert_eq!(profile["email"], "impersonated@example.com");
+}
+
+/// Log in as a normal user holding `permissions`, returning its token and id
+async fn login_with_permissions(test_app: &TestApp, permissions: &[&str]) -> (String, i32) {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = login_normal_user(&mut context).await;
+    let user_id = context.user.as_ref().unwrap().id;
+    grant_permissions(&context, user_id, permissions).await;
+    context.commit().await.unwrap();
+    (access_token, user_id)
+}
+
+async fn grant(test_app: &TestApp, user_id: i32, permissions: &[&str]) {
+    let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+    grant_permissions(&context, user_id, permissions).await;
+    context.commit().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_impersonation_rejects_users_with_permissions_the_caller_lacks() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (_, _, user_id, _) = setup(&test_app).await;
+    grant(&test_app, user_id, &["user.bulk"]).await;
+    let (access_token, _) = login_with_permissions(&test_app, &["user.impersonate"]).await;
+
+    // Act
+    let response = request(
+        &test_app,
+        reqwest::Method::POST,
+        &access_token,
+        &format!("/api/v1/admin/impersonate/{}/", user_id),
+        None,
+    )
+    .await;
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let error: Value = response.json().await.unwrap();
+    assert_eq!(error["code"], "USER_IMPERSONATION_FORBIDDEN");
+}
+
+#[tokio::test]
+async fn test_impersonation_token_withholds_privileged_permissions() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (_, _, user_id, _) = setup(&test_app).await;
+    grant(&test_app, user_id, &["user.bulk", "user.impersonate"]).await;
+    let (access_token, support_id) =
+        login_with_permissions(&test_app, &["user.bulk", "user.impersonate"]).await;
+    let response = request(
+        &test_app,
+        reqwest::Method::POST,
+        &access_token,
+        &format!("/api/v1/admin/impersonate/{}/", user_id),
+        None,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let impersonation: Value = response.json().await.unwrap();
+    let token = impersonation["access"].as_str().unwrap().to_string();
+
+    // Act
+    let promote = request(
+        &test_app,
+        reqwest::Method::POST,
+        &token,
+        "/api/v1/admin/users/bulk/",
+        Some(json!({ "operations": [
+            { "action": "assign_role", "user_id": support_id, "role": "admin" },
+        ]})),
+    )
+    .await
+    .status();
+    let impersonate_again = request(
+        &test_app,
+        reqwest::Method::POST,
+        &token,
+        &format!("/api/v1/admin/impersonate/{}/", support_id),
+        None,
+    )
+    .await
+    .status();
+
+    // Assert
+    assert_eq!(promote, StatusCode::FORBIDDEN);
+    assert_eq!(impersonate_again, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,92 @@
+mod login_lockout_api_tests {
+    use reqwest::{Client, Response, StatusCode};
+    use serde_json::{Value, json};
+
+    use crate::setup::{app::TestApp, factory::DEFAULT_PASSWORD};
+
+    async fn login(test_app: &TestApp, email: &str, password: &str) -> Response {
+        Client::new()
+            .post(format!("http://{}/api/v1/auth/login/", test_app.base_url))
+            .json(&json!({ "email": email, "password": password }))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_repeated_failed_logins_lock_account() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.register_and_login("locked@example.com").await;
+        test_app.register_and_login("bystander@example.com").await;
+
+        // Act
+        let mut failures = Vec::new();
+        for _ in 0..4 {
+            failures.push(login(&test_app, "locked@example.com", "wrong-password").await);
+        }
+        let locking = login(&test_app, "locked@example.com", "wrong-password").await;
+        let with_password = login(&test_app, "locked@example.com", DEFAULT_PASSWORD).await;
+        let bystander = login(&test_app, "bystander@example.com", DEFAULT_PASSWORD).await;
+
+        // Assert
+        assert!(
+            failures
+                .iter()
+                .all(|response| response.status() == StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(locking.status(), StatusCode::LOCKED);
+        assert_eq!(with_password.status(), StatusCode::LOCKED);
+        let retry_after: u64 = with_password.headers()["retry-after"]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=900).contains(&retry_after));
+        let body = with_password.json::<Value>().await.unwrap();
+        assert_eq!(body["code"], "AUTH_ACCOUNT_LOCKED");
+        assert_eq!(body["retry_after"], retry_after);
+        assert_eq!(bystander.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_successful_login_clears_failures() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.register_and_login("forgetful@example.com").await;
+        for _ in 0..4 {
+            login(&test_app, "forgetful@example.com", "wrong-password").await;
+        }
+
+        // Act
+        let signed_in = login(&test_app, "forgetful@example.com", DEFAULT_PASSWORD).await;
+        let failed_again = login(&test_app, "forgetful@example.com", "wrong-password").await;
+
+        // Assert
+        assert_eq!(signed_in.status(), StatusCode::OK);
+        assert_eq!(failed_again.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_email_locks_out_like_an_account() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.register_and_login("known@example.com").await;
+        for _ in 0..5 {
+            login(&test_app, "known@example.com", "wrong-password").await;
+            login(&test_app, "unknown@example.com", "wrong-password").await;
+        }
+
+        // Act
+        let known = login(&test_app, "known@example.com", "wrong-password").await;
+        let unknown = login(&test_app, "Unknown@Example.com", "wrong-password").await;
+
+        // Assert
+        assert_eq!(known.status(), StatusCode::LOCKED);
+        assert_eq!(unknown.status(), StatusCode::LOCKED);
+        let known = known.json::<Value>().await.unwrap();
+        let unknown = unknown.json::<Value>().await.unwrap();
+        assert_eq!(known["code"], unknown["code"]);
+        assert_eq!(known["message"], unknown["message"]);
+    }
+}
@@ -0,0 +1,98 @@
+mod phone_verification_api_tests {
+    use my_axum::core::{r#async::TaskType, id::SequentialIdGenerator};
+    use reqwest::StatusCode;
+    use serde_json::{Value, json};
+    use std::time::Duration;
+
+    use crate::setup::app::TestApp;
+
+    const PROFILE_PATH: &str = "/api/v1/user/profile/";
+    const VERIFY_PATH: &str = "/api/v1/user/profile/phone/verify/";
+    const CONFIRM_PATH: &str = "/api/v1/user/profile/phone/confirm/";
+    const PHONE: &str = "+84901234567";
+
+    #[tokio::test]
+    async fn test_phone_verification_flow() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.spawn_worker();
+        let client = test_app.register_and_login("phone@example.com").await;
+        client.patch(PROFILE_PATH, &json!({ "phone": PHONE })).await;
+
+        // Act
+        let send = client.post(VERIFY_PATH, &json!({})).await;
+        test_app.broker.wait_for_idle(Duration::from_secs(5)).await;
+        let otp = SequentialIdGenerator::nth_otp(1, 6);
+        let wrong = client.post(CONFIRM_PATH, &json!({ "otp": "999999" })).await;
+        let confirm = client.post(CONFIRM_PATH, &json!({ "otp": otp })).await;
+        let status = confirm.status();
+        let profile = confirm.json::<Value>().await.unwrap();
+        let resend = client.post(VERIFY_PATH, &json!({})).await;
+
+        // Assert
+        assert_eq!(send.status(), StatusCode::NO_CONTENT);
+        let messages = test_app.sms_messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, PHONE);
+        assert!(messages[0].1.contains(&otp));
+        assert_eq!(wrong.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(profile["phone_verified"], true);
+        assert_eq!(resend.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_changing_phone_resets_verification() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app
+            .register_and_login("change-phone@example.com")
+            .await;
+        client.patch(PROFILE_PATH, &json!({ "phone": PHONE })).await;
+        client.post(VERIFY_PATH, &json!({})).await;
+        client
+            .post(
+                CONFIRM_PATH,
+                &json!({ "otp": SequentialIdGenerator::nth_otp(1, 6) }),
+            )
+            .await;
+
+        // Act
+        let unchanged = client
+            .patch(PROFILE_PATH, &json!({ "phone": PHONE }))
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+        let changed = client
+            .patch(PROFILE_PATH, &json!({ "phone": "+84909999999" }))
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(unchanged["phone_verified"], true);
+        assert_eq!(changed["phone_verified"], false);
+    }
+
+    #[tokio::test]
+    async fn test_send_phone_verification_requires_phone() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("no-phone@example.com").await;
+
+        // Act
+        let response = client.post(VERIFY_PATH, &json!({})).await;
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(
+            test_app
+                .broker
+                .tasks("tasks")
+                .iter()
+                .all(|event| !matches!(event.task, TaskType::SendSms { .. }))
+        );
+    }
+}
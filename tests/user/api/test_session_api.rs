@@ -0,0 +1,136 @@
+mod session_api_tests {
+    use reqwest::{Client, Response, StatusCode};
+    use serde_json::{Value, json};
+
+    use crate::setup::{app::TestApp, client::AuthenticatedClient, factory::DEFAULT_PASSWORD};
+
+    const SESSIONS_PATH: &str = "/api/v1/auth/sessions/";
+
+    async fn sign_in(test_app: &TestApp, email: &str, user_agent: &str) -> Value {
+        let response = Client::new()
+            .post(format!("http://{}/api/v1/auth/login/", test_app.base_url))
+            .header("user-agent", user_agent)
+            .json(&json!({ "email": email, "password": DEFAULT_PASSWORD }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response.json().await.unwrap()
+    }
+
+    async fn refresh(test_app: &TestApp, refresh_token: &str) -> Response {
+        Client::new()
+            .post(format!(
+                "http://{}/api/v1/auth/refresh-token/",
+                test_app.base_url
+            ))
+            .json(&json!({ "refresh_token": refresh_token }))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    /// Request `SESSIONS_PATH` as `client`, from the session of its refresh token cookie
+    async fn sessions_request(
+        client: &AuthenticatedClient,
+        method: reqwest::Method,
+        path: &str,
+    ) -> Response {
+        Client::new()
+            .request(method, client.url(path))
+            .bearer_auth(client.access_token())
+            .header(
+                "cookie",
+                format!("refresh_token={}", client.refresh_token()),
+            )
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_and_revoke_sessions() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("sessions@example.com").await;
+        let laptop = sign_in(&test_app, "sessions@example.com", "Laptop").await;
+        let phone = sign_in(&test_app, "sessions@example.com", "Phone").await;
+        let laptop = refresh(&test_app, laptop["refresh"].as_str().unwrap())
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+
+        // Act
+        let listed = sessions_request(&client, reqwest::Method::GET, SESSIONS_PATH)
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+        let items = listed["items"].as_array().unwrap();
+        let laptop_id = items
+            .iter()
+            .find(|session| session["device_info"] == "Laptop")
+            .expect("the laptop session should be listed")["id"]
+            .as_i64()
+            .unwrap();
+        let revoked = client
+            .delete(&format!("{}{}/", SESSIONS_PATH, laptop_id))
+            .await
+            .status();
+        let laptop_refresh = refresh(&test_app, laptop["refresh"].as_str().unwrap())
+            .await
+            .status();
+        let revoked_others = sessions_request(&client, reqwest::Method::DELETE, SESSIONS_PATH)
+            .await
+            .status();
+        let phone_refresh = refresh(&test_app, phone["refresh"].as_str().unwrap())
+            .await
+            .status();
+        let own_refresh = client.refresh().await;
+
+        // Assert
+        // The laptop's rotated token is not a session of its own
+        assert_eq!(listed["count"], 3);
+        assert_eq!(items.len(), 3);
+        let current: Vec<&Value> = items
+            .iter()
+            .filter(|session| session["current"] == true)
+            .collect();
+        assert_eq!(current.len(), 1);
+        assert_ne!(current[0]["device_info"], "Laptop");
+        assert_ne!(current[0]["device_info"], "Phone");
+        assert_eq!(revoked, StatusCode::NO_CONTENT);
+        assert_eq!(laptop_refresh, StatusCode::UNAUTHORIZED);
+        assert_eq!(revoked_others, StatusCode::NO_CONTENT);
+        assert_eq!(phone_refresh, StatusCode::UNAUTHORIZED);
+        assert_eq!(own_refresh, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_session_of_another_user_is_not_found() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let owner = test_app.register_and_login("owner@example.com").await;
+        let other = test_app.register_and_login("other@example.com").await;
+        let listed = sessions_request(&owner, reqwest::Method::GET, SESSIONS_PATH)
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+        let owner_session_id = listed["items"][0]["id"].as_i64().unwrap();
+
+        // Act
+        let response = other
+            .delete(&format!("{}{}/", SESSIONS_PATH, owner_session_id))
+            .await;
+        let status = response.status();
+        let body = response.json::<Value>().await.unwrap();
+        let owner_refresh = owner.refresh().await;
+
+        // Assert
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "AUTH_SESSION_NOT_FOUND");
+        assert_eq!(owner_refresh, StatusCode::OK);
+    }
+}
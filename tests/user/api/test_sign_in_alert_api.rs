@@ -0,0 +1,171 @@
+mod sign_in_alert_api_tests {
+    use my_axum::user::entity::{sea_orm_active_enums::SecurityEventKind, security_event, user};
+    use reqwest::{Client, StatusCode};
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+    use serde_json::{Value, json};
+    use std::time::Duration;
+
+    use crate::setup::{app::TestApp, factory::DEFAULT_PASSWORD};
+
+    const ALERT_SUBJECT: &str = "New sign-in to your My Axum App account";
+
+    async fn sign_in(test_app: &TestApp, email: &str, user_agent: &str, ip_address: &str) -> Value {
+        let response = Client::new()
+            .post(format!("http://{}/api/v1/auth/login/", test_app.base_url))
+            .header("user-agent", user_agent)
+            .header("x-forwarded-for", ip_address)
+            .json(&json!({ "email": email, "password": DEFAULT_PASSWORD }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response.json().await.unwrap()
+    }
+
+    async fn refresh(test_app: &TestApp, token_pair: &Value) -> StatusCode {
+        Client::new()
+            .post(format!(
+                "http://{}/api/v1/auth/refresh-token/",
+                test_app.base_url
+            ))
+            .json(&json!({ "refresh_token": token_pair["refresh"] }))
+            .send()
+            .await
+            .unwrap()
+            .status()
+    }
+
+    async fn report(test_app: &TestApp, token: &str) -> StatusCode {
+        Client::new()
+            .post(format!(
+                "http://{}/api/v1/auth/sign-ins/report/",
+                test_app.base_url
+            ))
+            .json(&json!({ "token": token }))
+            .send()
+            .await
+            .unwrap()
+            .status()
+    }
+
+    async fn security_events(test_app: &TestApp, email: &str) -> Vec<security_event::Model> {
+        let user = user::Entity::find()
+            .filter(user::Column::Email.eq(email))
+            .one(&test_app.db)
+            .await
+            .unwrap()
+            .unwrap();
+        security_event::Entity::find()
+            .filter(security_event::Column::UserId.eq(user.id))
+            .order_by_asc(security_event::Column::Id)
+            .all(&test_app.db)
+            .await
+            .unwrap()
+    }
+
+    /// Report tokens of the new sign-in alerts emailed so far
+    async fn alert_report_tokens(test_app: &TestApp) -> Vec<String> {
+        test_app.broker.wait_for_idle(Duration::from_secs(5)).await;
+        test_app
+            .emails()
+            .await
+            .into_iter()
+            .filter(|email| email.subject == ALERT_SUBJECT)
+            .map(|email| {
+                let html_body = email.html_body.unwrap();
+                let start = html_body.find("report-sign-in?token=").unwrap() + 21;
+                html_body[start..]
+                    .chars()
+                    .take_while(char::is_ascii_alphanumeric)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_known_clients_are_not_reported() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.spawn_worker();
+        test_app.register_and_login("known@example.com").await;
+
+        // Act
+        sign_in(&test_app, "known@example.com", "Firefox", "203.0.113.10").await;
+        sign_in(&test_app, "known@example.com", "Firefox", "203.0.113.10").await;
+        sign_in(&test_app, "known@example.com", "Firefox", "203.0.113.99").await;
+
+        // Assert
+        let tokens = alert_report_tokens(&test_app).await;
+        assert_eq!(tokens.len(), 1);
+        let kinds: Vec<SecurityEventKind> = security_events(&test_app, "known@example.com")
+            .await
+            .into_iter()
+            .map(|security_event| security_event.kind)
+            .collect();
+        // Registering is the baseline; only the first sign-in from Firefox is new
+        assert_eq!(
+            kinds,
+            vec![SecurityEventKind::SignIn, SecurityEventKind::NewSignIn]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_new_device_and_network_are_reported() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.spawn_worker();
+        test_app.register_and_login("alert@example.com").await;
+        sign_in(&test_app, "alert@example.com", "Firefox", "203.0.113.10").await;
+
+        // Act
+        sign_in(&test_app, "alert@example.com", "Safari", "203.0.113.10").await;
+        sign_in(&test_app, "alert@example.com", "Firefox", "198.51.100.7").await;
+
+        // Assert
+        assert_eq!(alert_report_tokens(&test_app).await.len(), 3);
+        let events = security_events(&test_app, "alert@example.com").await;
+        let last = events.last().unwrap();
+        assert_eq!(last.kind, SecurityEventKind::NewSignIn);
+        assert_eq!(last.device_info.as_deref(), Some("Firefox"));
+        assert_eq!(last.ip_address.as_deref(), Some("198.51.100.7"));
+        // Only the hash of the report token is stored
+        assert!(last.report_token.is_some());
+        assert!(
+            !alert_report_tokens(&test_app)
+                .await
+                .contains(last.report_token.as_ref().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_report_revokes_the_reported_sessions() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.spawn_worker();
+        test_app.register_and_login("report@example.com").await;
+        let own = sign_in(&test_app, "report@example.com", "Firefox", "203.0.113.10").await;
+        let intruder = sign_in(&test_app, "report@example.com", "curl", "198.51.100.7").await;
+        let tokens = alert_report_tokens(&test_app).await;
+
+        // Act
+        let reported = report(&test_app, tokens.last().unwrap()).await;
+        let reported_again = report(&test_app, tokens.last().unwrap()).await;
+        let unknown = report(&test_app, "not-a-token").await;
+
+        // Assert
+        assert_eq!(reported, StatusCode::NO_CONTENT);
+        assert_eq!(reported_again, StatusCode::BAD_REQUEST);
+        assert_eq!(unknown, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            refresh(&test_app, &intruder).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(refresh(&test_app, &own).await, StatusCode::OK);
+        let events = security_events(&test_app, "report@example.com").await;
+        assert!(events.last().unwrap().reported_at.is_some());
+
+        // The reported client is no longer known, so signing in from it is reported again
+        sign_in(&test_app, "report@example.com", "curl", "198.51.100.7").await;
+        assert_eq!(alert_report_tokens(&test_app).await.len(), tokens.len() + 1);
+    }
+}
@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use my_axum::{
+    core::context::Context,
+    user::{entity::refresh_token, repository::user_repository},
+};
+use reqwest::{Method, StatusCode};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::Value;
+
+use crate::setup::{
+    app::TestApp,
+    factory::UserFactory,
+    fixture::{grant_permissions, login_admin_user, login_normal_user},
+};
+
+/// Log in as an admin and as a normal user, returning both access tokens and the user id
+async fn setup(test_app: &TestApp) -> (String, String, i32) {
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (admin_token, _) = login_admin_user(&mut context).await;
+    let (user_token, _) = login_normal_user(&mut context).await;
+    let user_id = context.user.as_ref().unwrap().id;
+    context.commit().await.unwrap();
+    (admin_token, user_token, user_id)
+}
+
+async fn request(
+    test_app: &TestApp,
+    method: Method,
+    access_token: &str,
+    path: &str,
+) -> reqwest::Response {
+    reqwest::Client::new()
+        .request(method, format!("http://{}{}", test_app.base_url, path))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_deleted_user_is_hidden_until_restored() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (admin_token, user_token, user_id) = setup(&test_app).await;
+    let user_path = format!("/api/v1/user/{}/", user_id);
+
+    // Act
+    let deleted = request(&test_app, Method::DELETE, &user_token, &user_path).await;
+    let profile_after_delete =
+        request(&test_app, Method::GET, &user_token, "/api/v1/user/profile/").await;
+    let get_after_delete = request(&test_app, Method::GET, &admin_token, &user_path).await;
+    let search: Value = request(
+        &test_app,
+        Method::GET,
+        &admin_token,
+        "/api/v1/user/?email=user@example.com",
+    )
+    .await
+    .json()
+    .await
+    .unwrap();
+    let restored = request(
+        &test_app,
+        Method::POST,
+        &admin_token,
+        &format!("/api/v1/users/{}/restore/", user_id),
+    )
+    .await;
+    let restored_status = restored.status();
+    let restored: Value = restored.json().await.unwrap();
+    let get_after_restore = request(&test_app, Method::GET, &admin_token, &user_path).await;
+
+    // Assert
+    assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+    assert_eq!(profile_after_delete.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(get_after_delete.status(), StatusCode::NOT_FOUND);
+    assert!(search["items"].as_array().unwrap().is_empty());
+    assert_eq!(restored_status, StatusCode::OK);
+    assert_eq!(restored["id"], user_id);
+    assert_eq!(get_after_restore.status(), StatusCode::OK);
+
+    // Sessions stay revoked, so the restored user signs in again
+    let sessions = refresh_token::Entity::find()
+        .filter(refresh_token::Column::UserId.eq(user_id))
+        .count(&test_app.db)
+        .await
+        .unwrap();
+    assert_eq!(sessions, 0);
+}
+
+#[tokio::test]
+async fn test_admin_hard_deletes_user_for_good() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (admin_token, _, user_id) = setup(&test_app).await;
+    let path = format!("/api/v1/admin/users/{}/", user_id);
+
+    // Act
+    let soft_deleted = request(
+        &test_app,
+        Method::DELETE,
+        &admin_token,
+        &format!("/api/v1/user/{}/", user_id),
+    )
+    .await;
+    let hard_deleted = request(&test_app, Method::DELETE, &admin_token, &path).await;
+    let again = request(&test_app, Method::DELETE, &admin_token, &path).await;
+
+    // Assert
+    assert_eq!(soft_deleted.status(), StatusCode::NO_CONTENT);
+    assert_eq!(hard_deleted.status(), StatusCode::NO_CONTENT);
+    assert_eq!(again.status(), StatusCode::NOT_FOUND);
+    let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+    let user = user_repository::find_by_id_with_deleted(&context, user_id)
+        .await
+        .unwrap();
+    assert!(user.is_none());
+}
+
+#[tokio::test]
+async fn test_restore_and_hard_delete_require_permissions() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let (_, user_token, user_id) = setup(&test_app).await;
+
+    // Act
+    let restore = request(
+        &test_app,
+        Method::POST,
+        &user_token,
+        &format!("/api/v1/users/{}/restore/", user_id),
+    )
+    .await;
+    let hard_delete = request(
+        &test_app,
+        Method::DELETE,
+        &user_token,
+        &format!("/api/v1/admin/users/{}/", user_id),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(restore.status(), StatusCode::FORBIDDEN);
+    assert_eq!(hard_delete.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_permission_holders_restore_and_hard_delete_users() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let (access_token, _) = login_normal_user(&mut context).await;
+    let user_id = context.user.as_ref().unwrap().id;
+    grant_permissions(&context, user_id, &["user.restore", "user.hard_delete"]).await;
+    let target = UserFactory::new().create(&context).await.unwrap();
+    context.commit().await.unwrap();
+
+    // Act
+    let restore = request(
+        &test_app,
+        Method::POST,
+        &access_token,
+        &format!("/api/v1/users/{}/restore/", target.id),
+    )
+    .await;
+    let hard_delete = request(
+        &test_app,
+        Method::DELETE,
+        &access_token,
+        &format!("/api/v1/admin/users/{}/", target.id),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(restore.status(), StatusCode::OK);
+    assert_eq!(hard_delete.status(), StatusCode::NO_CONTENT);
+}
@@ -693,10 +693,42 @@ mod search_user_tests {
         // Assert
         assert_eq!(response.status(), StatusCode::FORBIDDEN);
     }
+
+    #[tokio::test]
+    async fn test_normal_user_can_only_read_own_record() {
+        let test_app = TestApp::spawn_app().await;
+        let loaded = test_app.load_fixture("basic_users").await;
+        let alice = test_app.login("alice@example.com", "alice_password").await;
+
+        let own = alice
+            .get(&format!("/api/v1/user/{}/", loaded.user_id("alice")))
+            .await;
+        let other = alice
+            .get(&format!("/api/v1/user/{}/", loaded.user_id("admin")))
+            .await;
+
+        assert_eq!(own.status(), StatusCode::OK);
+        assert_eq!(other.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_search_user_allowed_by_role_permission() {
+        let test_app = TestApp::spawn_app().await;
+        test_app.load_fixture("basic_users").await;
+        let support = test_app
+            .login("support@example.com", "support_password")
+            .await;
+
+        let response = support.get("/api/v1/user/").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: Value = response.json().await.unwrap();
+        assert_eq!(result["count"], 3);
+    }
 }
 
 mod upload_avatar_tests {
-    use my_axum::core::context::Context;
+    use my_axum::core::{r#async::TaskType, context::Context, id::SequentialIdGenerator};
     use reqwest::{Client, StatusCode};
     use sea_orm::{DbErr, TransactionTrait};
     use serde_json::{Value, json};
@@ -735,12 +767,67 @@ mod upload_avatar_tests {
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
 
         let body: Value = response.json().await.unwrap();
-        assert_eq!(
-            body.get("message").and_then(Value::as_str),
-            Some("Internal Server Error: Producer not available")
-        );
+        let task_id = body.get("task_id").and_then(Value::as_str).unwrap();
+        assert_eq!(task_id, SequentialIdGenerator::nth_task_id(1));
+        let tasks = test_app.broker.tasks("tasks");
+        assert_eq!(tasks.len(), 1);
+        assert!(matches!(
+            &tasks[0].task,
+            TaskType::ProcessAvatarUpload { task_id: published, .. } if published == task_id
+        ));
+    }
+}
+
+mod response_cache_tests {
+    use my_axum::pkg::cache::InMemoryResponseCache;
+    use serde_json::{Value, json};
+    use std::sync::Arc;
+
+    use crate::setup::app::TestApp;
+
+    const PROFILE_PATH: &str = "/api/v1/user/profile/";
+
+    #[tokio::test]
+    async fn test_profile_is_cached_until_updated() {
+        // Arrange
+        let test_app =
+            TestApp::spawn_app_with_response_cache(Arc::new(InMemoryResponseCache::default()))
+                .await;
+        let client = test_app.register_and_login("cached@example.com").await;
+
+        // Act
+        let miss = client.get(PROFILE_PATH).await;
+        let hit = client.get(PROFILE_PATH).await;
+        client
+            .patch(PROFILE_PATH, &json!({ "first_name": "Updated" }))
+            .await;
+        let refreshed = client.get(PROFILE_PATH).await;
+
+        // Assert
+        assert_eq!(miss.headers()["x-cache"], "MISS");
+        assert_eq!(hit.headers()["x-cache"], "HIT");
+        assert_eq!(refreshed.headers()["x-cache"], "MISS");
+        let profile = refreshed.json::<Value>().await.unwrap();
+        assert_eq!(profile["first_name"], "Updated");
+    }
+
+    #[tokio::test]
+    async fn test_cached_responses_are_per_user() {
+        // Arrange
+        let test_app =
+            TestApp::spawn_app_with_response_cache(Arc::new(InMemoryResponseCache::default()))
+                .await;
+        let alice = test_app.register_and_login("alice@example.com").await;
+        let bob = test_app.register_and_login("bob@example.com").await;
+
+        // Act
+        alice.get(PROFILE_PATH).await;
+        let profile = bob.get(PROFILE_PATH).await.json::<Value>().await.unwrap();
+
+        // Assert
+        assert_eq!(profile["email"], "bob@example.com");
     }
 }
@@ -0,0 +1,152 @@
+mod user_email_api_tests {
+    use my_axum::core::id::SequentialIdGenerator;
+    use reqwest::{Client, StatusCode};
+    use serde_json::{Value, json};
+    use std::time::Duration;
+
+    use crate::setup::{app::TestApp, factory::DEFAULT_PASSWORD};
+
+    const EMAILS_PATH: &str = "/api/v1/user/profile/emails/";
+
+    fn email_path(id: i64, action: &str) -> String {
+        format!("{}{}/{}", EMAILS_PATH, id, action)
+    }
+
+    async fn login(test_app: &TestApp, email: &str) -> StatusCode {
+        Client::new()
+            .post(format!("http://{}/api/v1/auth/login/", test_app.base_url))
+            .json(&json!({ "email": email, "password": DEFAULT_PASSWORD }))
+            .send()
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_secondary_email_flow() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        test_app.spawn_worker();
+        let client = test_app.register_and_login("primary@example.com").await;
+
+        // Act
+        let added = client
+            .post(EMAILS_PATH, &json!({ "email": "work@example.com" }))
+            .await;
+        let added_status = added.status();
+        let added = added.json::<Value>().await.unwrap();
+        let id = added["id"].as_i64().unwrap();
+        test_app.broker.wait_for_idle(Duration::from_secs(5)).await;
+        let login_unverified = login(&test_app, "work@example.com").await;
+        let wrong = client
+            .post(&email_path(id, "confirm/"), &json!({ "otp": "999999" }))
+            .await;
+        let otp = SequentialIdGenerator::nth_otp(1, 6);
+        let confirmed = client
+            .post(&email_path(id, "confirm/"), &json!({ "otp": otp }))
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+        let login_verified = login(&test_app, "work@example.com").await;
+
+        // Assert
+        assert_eq!(added_status, StatusCode::CREATED);
+        assert_eq!(added["verified"], false);
+        let emails = test_app.emails().await;
+        let code_email = emails
+            .iter()
+            .find(|email| email.to == "work@example.com")
+            .expect("verification code should be emailed");
+        assert!(code_email.text_body.as_deref().unwrap().contains(&otp));
+        assert_eq!(login_unverified, StatusCode::UNAUTHORIZED);
+        assert_eq!(wrong.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(confirmed["verified"], true);
+        assert_eq!(login_verified, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_verified_secondary_email_is_unique_and_can_become_primary() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let client = test_app.register_and_login("owner@example.com").await;
+        let id = client
+            .post(EMAILS_PATH, &json!({ "email": "Second@Example.com" }))
+            .await
+            .json::<Value>()
+            .await
+            .unwrap()["id"]
+            .as_i64()
+            .unwrap();
+        client
+            .post(
+                &email_path(id, "confirm/"),
+                &json!({ "otp": SequentialIdGenerator::nth_otp(1, 6) }),
+            )
+            .await;
+        let other = test_app.register_and_login("other@example.com").await;
+
+        // Act
+        let claimed_elsewhere = other
+            .post(EMAILS_PATH, &json!({ "email": "second@example.com" }))
+            .await;
+        let primary_again = client
+            .post(EMAILS_PATH, &json!({ "email": "owner@example.com" }))
+            .await;
+        let register = Client::new()
+            .post(format!(
+                "http://{}/api/v1/auth/register/",
+                test_app.base_url
+            ))
+            .json(&json!({ "email": "second@example.com", "password": DEFAULT_PASSWORD }))
+            .send()
+            .await
+            .unwrap();
+        let profile = client
+            .post(&email_path(id, "primary/"), &json!({}))
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+        let listed = client.get(EMAILS_PATH).await.json::<Value>().await.unwrap();
+
+        // Assert
+        assert_eq!(claimed_elsewhere.status(), StatusCode::CONFLICT);
+        assert_eq!(primary_again.status(), StatusCode::CONFLICT);
+        assert_eq!(register.status(), StatusCode::CONFLICT);
+        assert_eq!(profile["email"], "Second@Example.com");
+        assert_eq!(listed["count"], 1);
+        assert_eq!(listed["items"][0]["email"], "owner@example.com");
+        assert_eq!(listed["items"][0]["verified"], true);
+        assert_eq!(login(&test_app, "owner@example.com").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_unverified_secondary_email_is_taken_over_by_its_owner() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let squatter = test_app.register_and_login("squatter@example.com").await;
+        let claimed = squatter
+            .post(EMAILS_PATH, &json!({ "email": "victim@example.com" }))
+            .await
+            .json::<Value>()
+            .await
+            .unwrap();
+        let owner = test_app.register_and_login("owner@example.com").await;
+
+        // Act
+        let added = owner
+            .post(EMAILS_PATH, &json!({ "email": "victim@example.com" }))
+            .await;
+        let squatter_confirm = squatter
+            .post(
+                &email_path(claimed["id"].as_i64().unwrap(), "confirm/"),
+                &json!({ "otp": SequentialIdGenerator::nth_otp(1, 6) }),
+            )
+            .await;
+
+        // Assert
+        assert_eq!(added.status(), StatusCode::CREATED);
+        assert_eq!(squatter_confirm.status(), StatusCode::NOT_FOUND);
+    }
+}
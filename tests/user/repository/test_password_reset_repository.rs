@@ -1,30 +1,17 @@
 #[cfg(test)]
 mod password_reset_repository_tests {
     use chrono::{Duration, Utc};
+    use my_axum::config::setting::Setting;
     use my_axum::core::context::Context;
-    use my_axum::user::dto::user_dto::UserCreateDTO;
     use my_axum::user::entity::password_reset_token;
     use my_axum::user::repository::password_reset_repository;
-    use my_axum::user::use_case::user::create_user_use_case;
     use sea_orm::{ActiveValue::Set, DbErr, TransactionTrait};
     use std::sync::Arc;
-    use uuid::Uuid;
 
-    use crate::setup::app::TestApp;
+    use crate::setup::{app::TestApp, factory::PasswordResetTokenFactory, factory::UserFactory};
 
     async fn create_test_user(context: &Context) -> i32 {
-        let dto = UserCreateDTO {
-            email: format!("test{}@example.com", Uuid::new_v4()),
-            password: "password123@".to_string(),
-            first_name: Some("Test".to_string()),
-            last_name: Some("User".to_string()),
-            phone: None,
-        };
-        let user = create_user_use_case::execute(context, dto)
-            .await
-            .unwrap()
-            .data;
-        user.id
+        UserFactory::new().create(context).await.unwrap().id
     }
 
     #[tokio::test]
@@ -53,7 +40,11 @@ mod password_reset_repository_tests {
 
                     assert!(created.id > 0);
                     assert_eq!(created.user_id, user_id);
-                    assert_eq!(created.token, "test_token_123");
+                    // Only the hash of the OTP is stored
+                    assert_eq!(
+                        created.token,
+                        Setting::new().token_hasher().hash("test_token_123")
+                    );
                     assert_eq!(created.retry_count, 0);
                     assert!(created.created_at.is_some());
 
@@ -94,7 +85,11 @@ mod password_reset_repository_tests {
 
                     assert!(found.is_some());
                     let token = found.unwrap();
-                    assert_eq!(token.token, "find_me_token");
+                    assert!(
+                        Setting::new()
+                            .token_hasher()
+                            .verify("find_me_token", &token.token)
+                    );
                     assert_eq!(token.user_id, user_id);
 
                     Ok(())
@@ -224,31 +219,15 @@ mod password_reset_repository_tests {
 
                     let user_id = create_test_user(&context).await;
 
-                    // Create expired token
-                    let expired_token = password_reset_token::ActiveModel {
-                        user_id: Set(user_id),
-                        token: Set("expired_token".to_string()),
-                        expires_at: Set(Utc::now().naive_utc() - Duration::hours(1)),
-                        retry_count: Set(0),
-                        ..Default::default()
-                    };
-
-                    password_reset_repository::create(&context, expired_token)
-                        .await
-                        .unwrap();
-
-                    // Create valid token
-                    let valid_token = password_reset_token::ActiveModel {
-                        user_id: Set(user_id),
-                        token: Set("valid_token".to_string()),
-                        expires_at: Set(Utc::now().naive_utc() + Duration::hours(1)),
-                        retry_count: Set(0),
-                        ..Default::default()
-                    };
-
-                    password_reset_repository::create(&context, valid_token)
-                        .await
-                        .unwrap();
+                    PasswordResetTokenFactory::for_user(user_id)
+                        .token("expired_token")
+                        .expired()
+                        .create(&context)
+                        .await?;
+                    PasswordResetTokenFactory::for_user(user_id)
+                        .token("valid_token")
+                        .create(&context)
+                        .await?;
 
                     // Delete expired tokens
                     password_reset_repository::delete_expired(&context)
@@ -289,28 +268,12 @@ mod password_reset_repository_tests {
                     let user_id = create_test_user(&context).await;
 
                     // Create multiple tokens for the same user
-                    let token1 = password_reset_token::ActiveModel {
-                        user_id: Set(user_id),
-                        token: Set("token1".to_string()),
-                        expires_at: Set(Utc::now().naive_utc() + Duration::hours(1)),
-                        retry_count: Set(0),
-                        ..Default::default()
-                    };
-
-                    let token2 = password_reset_token::ActiveModel {
-                        user_id: Set(user_id),
-                        token: Set("token2".to_string()),
-                        expires_at: Set(Utc::now().naive_utc() + Duration::hours(1)),
-                        retry_count: Set(0),
-                        ..Default::default()
-                    };
-
-                    password_reset_repository::create(&context, token1)
-                        .await
-                        .unwrap();
-                    password_reset_repository::create(&context, token2)
-                        .await
-                        .unwrap();
+                    for token in ["token1", "token2"] {
+                        PasswordResetTokenFactory::for_user(user_id)
+                            .token(token)
+                            .create(&context)
+                            .await?;
+                    }
 
                     // Delete all tokens for the user
                     password_reset_repository::delete_by_user_id(&context, user_id)
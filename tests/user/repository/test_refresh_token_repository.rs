@@ -1,6 +1,7 @@
 use crate::setup::app::TestApp;
 
 use chrono::{Duration, Utc};
+use my_axum::config::setting::Setting;
 use my_axum::core::context::Context;
 use my_axum::user::entity::{refresh_token, user};
 use my_axum::user::repository::{
@@ -43,7 +44,14 @@ async fn test_create_refresh_token() -> Result<(), DbErr> {
 
     assert!(created_token.id > 0);
     assert_eq!(created_token.user_id, created_user.id);
-    assert_eq!(created_token.token, "test_refresh_token_12345");
+    // Only the hash of the token is stored
+    assert_ne!(created_token.token, "test_refresh_token_12345");
+    assert_eq!(
+        created_token.token,
+        Setting::new()
+            .token_hasher()
+            .hash("test_refresh_token_12345")
+    );
     assert_eq!(created_token.device_info, Some("Test Device".to_string()));
     assert_eq!(created_token.ip_address, Some("192.168.1.1".to_string()));
 
@@ -84,7 +92,10 @@ async fn test_find_by_token_success() -> Result<(), DbErr> {
 
     assert!(result.is_some());
     let found_token = result.unwrap();
-    assert_eq!(found_token.token, token_value);
+    assert_eq!(
+        found_token.token,
+        Setting::new().token_hasher().hash(token_value)
+    );
     assert_eq!(found_token.user_id, created_user.id);
 
     Ok(())
@@ -138,7 +149,10 @@ async fn test_find_by_user_and_token_success() -> Result<(), DbErr> {
 
     assert!(result.is_some());
     let found_token = result.unwrap();
-    assert_eq!(found_token.token, token_value);
+    assert_eq!(
+        found_token.token,
+        Setting::new().token_hasher().hash(token_value)
+    );
     assert_eq!(found_token.user_id, created_user.id);
 
     Ok(())
@@ -1,6 +1,6 @@
 use my_axum::core::context::Context;
 use my_axum::core::db::ordering::{OrderByField, SortOrder};
-use my_axum::user::entity::user;
+use my_axum::user::entity::{sea_orm_active_enums::UserRole, user};
 use my_axum::user::repository::user_repository::{
     self, UserOrderBy, UserOrderByField, UserSearchParams,
 };
@@ -250,6 +250,43 @@ async fn test_search_by_email() -> Result<(), DbErr> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_search_by_role() -> Result<(), DbErr> {
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let context = Context::builder(Arc::new(txn)).build();
+
+    for (email, role) in [
+        ("role_admin@example.com", Some(UserRole::Admin)),
+        ("role_user@example.com", None),
+    ] {
+        user_repository::create(
+            &context,
+            user::ActiveModel {
+                email: Set(email.to_string()),
+                password: Set("password123@".to_string()),
+                role: role.map_or(sea_orm::ActiveValue::NotSet, Set),
+                ..Default::default()
+            },
+        )
+        .await?;
+    }
+
+    let (result, total_count) = user_repository::search(
+        &context,
+        &UserSearchParams {
+            role: Some(UserRole::Admin),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    assert_eq!(total_count, 1);
+    assert_eq!(result[0].email, "role_admin@example.com");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_search_by_first_name() -> Result<(), DbErr> {
     let test_app = TestApp::spawn_app().await;
@@ -996,6 +1033,67 @@ async fn test_delete_by_id() -> Result<(), DbErr> {
 
     let result = user_repository::find_by_id(&context, id).await?;
     assert!(result.is_none());
+    let deleted = user_repository::find_by_id_with_deleted(&context, id).await?;
+    assert!(deleted.unwrap().deleted_at.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deleted_users_are_left_out_until_restored() -> Result<(), DbErr> {
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let context = Context::builder(Arc::new(txn)).build();
+
+    let user = user::ActiveModel {
+        email: Set("soft_deleted@example.com".to_string()),
+        password: Set("password123@".to_string()),
+        ..Default::default()
+    };
+    let created = user_repository::create(&context, user).await?;
+    user_repository::delete_by_id(&context, created.id).await?;
+
+    let params = UserSearchParams {
+        email: Some("soft_deleted@"),
+        ..Default::default()
+    };
+    let (users, total) = user_repository::search(&context, &params).await?;
+    assert!(users.is_empty());
+    assert_eq!(total, 0);
+    let by_email = user_repository::find_by_email(&context, "soft_deleted@example.com").await?;
+    assert!(by_email.is_none());
+    let with_deleted =
+        user_repository::find_by_email_with_deleted(&context, "soft_deleted@example.com").await?;
+    assert_eq!(with_deleted.map(|user| user.id), Some(created.id));
+
+    let deleted = user_repository::find_by_id_with_deleted(&context, created.id)
+        .await?
+        .unwrap();
+    let restored = user_repository::restore(&context, deleted.into()).await?;
+    assert!(restored.deleted_at.is_none());
+    let (users, _) = user_repository::search(&context, &params).await?;
+    assert_eq!(users.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_hard_delete_by_id() -> Result<(), DbErr> {
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let context = Context::builder(Arc::new(txn)).build();
+
+    let user = user::ActiveModel {
+        email: Set("hard_delete@example.com".to_string()),
+        password: Set("password123@".to_string()),
+        ..Default::default()
+    };
+    let created = user_repository::create(&context, user).await?;
+
+    user_repository::hard_delete_by_id(&context, created.id).await?;
+
+    let result = user_repository::find_by_id_with_deleted(&context, created.id).await?;
+    assert!(result.is_none());
 
     Ok(())
 }
@@ -1030,3 +1128,29 @@ async fn test_create_and_update_with_user_context() -> Result<(), DbErr> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_find_by_email_matches_normalized_email() -> Result<(), DbErr> {
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let context = Context::builder(Arc::new(txn)).build();
+
+    let user = user::ActiveModel {
+        email: Set("Mixed.Case@Example.COM".to_string()),
+        password: Set("password123@".to_string()),
+        ..Default::default()
+    };
+    let created = user_repository::create(&context, user).await?;
+    assert_eq!(created.email, "Mixed.Case@Example.COM");
+    assert_eq!(created.normalized_email, "mixed.case@example.com");
+
+    let found = user_repository::find_by_email(&context, "mixed.case@example.com").await?;
+    assert_eq!(found.map(|user| user.id), Some(created.id));
+
+    let mut active: user::ActiveModel = created.into();
+    active.email = Set("Renamed@Example.COM".to_string());
+    let updated = user_repository::update(&context, active).await?;
+    assert_eq!(updated.normalized_email, "renamed@example.com");
+
+    Ok(())
+}
@@ -1,2 +1,3 @@
 mod test_auth_service;
+mod test_sign_in_service;
 mod test_user_service;
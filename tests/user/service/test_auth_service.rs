@@ -2,19 +2,25 @@
 mod auth_service_tests {
     use my_axum::config::setting::Setting;
     use my_axum::core::context::Context;
+    use my_axum::core::dto::error_code::ErrorCode;
     use my_axum::pkg::jwt::decode_token;
     use my_axum::user::dto::user_dto::UserCreateDTO;
-    use my_axum::user::service::auth_service::{generate_token_pair, get_current_user};
+    use my_axum::user::entity::sea_orm_active_enums::ExpirationPolicy;
+    use my_axum::user::service::auth_service::{
+        generate_token_pair, get_current_user, refresh_token_expiry, verify_user_password,
+    };
     use my_axum::user::use_case::user::create_user_use_case;
     use std::sync::Arc;
 
-    use crate::setup::app::TestApp;
+    use crate::setup::{app::TestApp, factory::UserFactory};
     use http::StatusCode;
 
     #[tokio::test]
     async fn test_generate_token_pair_success() {
+        let test_app = TestApp::spawn_db_only().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
         let user_id = 123;
-        let result = generate_token_pair(user_id).await;
+        let result = generate_token_pair(&context, user_id).await;
 
         assert!(result.is_ok());
         let (access_token, refresh_token) = result.unwrap();
@@ -26,8 +32,10 @@ mod auth_service_tests {
 
     #[tokio::test]
     async fn test_generate_token_pair_tokens_are_valid() {
+        let test_app = TestApp::spawn_db_only().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
         let user_id = 456;
-        let result = generate_token_pair(user_id).await;
+        let result = generate_token_pair(&context, user_id).await;
 
         assert!(result.is_ok());
         let (access_token, refresh_token) = result.unwrap();
@@ -47,11 +55,13 @@ mod auth_service_tests {
 
     #[tokio::test]
     async fn test_generate_token_pair_different_users() {
+        let test_app = TestApp::spawn_db_only().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
         let user_id1 = 111;
         let user_id2 = 222;
 
-        let result1 = generate_token_pair(user_id1).await;
-        let result2 = generate_token_pair(user_id2).await;
+        let result1 = generate_token_pair(&context, user_id1).await;
+        let result2 = generate_token_pair(&context, user_id2).await;
 
         assert!(result1.is_ok());
         assert!(result2.is_ok());
@@ -65,14 +75,16 @@ mod auth_service_tests {
 
     #[tokio::test]
     async fn test_generate_token_pair_multiple_calls() {
+        let test_app = TestApp::spawn_db_only().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
         let user_id = 789;
 
-        let result1 = generate_token_pair(user_id).await;
+        let result1 = generate_token_pair(&context, user_id).await;
 
         // Wait a bit to ensure different timestamps
         tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
 
-        let result2 = generate_token_pair(user_id).await;
+        let result2 = generate_token_pair(&context, user_id).await;
 
         assert!(result1.is_ok());
         assert!(result2.is_ok());
@@ -96,7 +108,7 @@ mod auth_service_tests {
         assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.status, StatusCode::UNAUTHORIZED);
-        assert!(error.message.contains("Invalid token"));
+        assert_eq!(error.code, ErrorCode::AuthTokenInvalid);
     }
 
     #[tokio::test]
@@ -119,7 +131,7 @@ mod auth_service_tests {
         assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.status, StatusCode::UNAUTHORIZED);
-        assert!(error.message.contains("User not found"));
+        assert_eq!(error.code, ErrorCode::AuthUserNotFound);
     }
 
     #[tokio::test]
@@ -154,4 +166,94 @@ mod auth_service_tests {
         assert_eq!(found_user.id, user.id);
         assert_eq!(found_user.email, user.email);
     }
+
+    #[tokio::test]
+    async fn test_tokens_are_signed_and_verified_with_key_store() {
+        use my_axum::pkg::jwt::decode_kid;
+        use my_axum::user::entity::signing_key;
+        use my_axum::user::repository::signing_key_repository;
+        use my_axum::user::service::auth_service::verify_token;
+        use sea_orm::Set;
+
+        let test_app = TestApp::spawn_db_only().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let (legacy_token, _) = generate_token_pair(&context, 1).await.unwrap();
+
+        signing_key_repository::create(
+            &context,
+            signing_key::ActiveModel {
+                kid: Set("k1".to_string()),
+                secret: Set("rotated-secret".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let (access_token, _) = generate_token_pair(&context, 1).await.unwrap();
+
+        assert_eq!(decode_kid(&access_token).unwrap().as_deref(), Some("k1"));
+        assert_eq!(
+            verify_token(&context, &access_token)
+                .await
+                .unwrap()
+                .unwrap()
+                .sub,
+            1
+        );
+        // Tokens signed with JWT_SECRET before the first rotation stay valid
+        assert!(
+            verify_token(&context, &legacy_token)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        signing_key_repository::retire_active(&context, chrono::Utc::now().naive_utc())
+            .await
+            .unwrap();
+        assert!(
+            verify_token(&context, &access_token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_user_password() {
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new()
+            .password("password123@")
+            .create(&context)
+            .await
+            .unwrap();
+
+        assert!(verify_user_password(Some(&user), "password123@").await);
+        assert!(!verify_user_password(Some(&user), "wrong-password").await);
+        // Unknown accounts never verify, whatever the password
+        assert!(!verify_user_password(None, "password123@").await);
+        assert!(!verify_user_password(None, "dummy-password").await);
+    }
+
+    #[test]
+    fn test_refresh_token_expiry_of_new_sessions_follows_setting() {
+        let mut setting = Setting::new();
+        setting.jwt_refresh_token_expires = 3600;
+        setting.jwt_refresh_token_max_lifetime = 1800;
+        let now = chrono::Utc::now().naive_utc();
+
+        setting.jwt_refresh_token_sliding = false;
+        let expiry = refresh_token_expiry(&setting, None, now);
+        assert_eq!(expiry.policy, ExpirationPolicy::Absolute);
+        assert_eq!(expiry.expires_at, now + chrono::Duration::seconds(3600));
+        assert_eq!(expiry.max_expires_at, None);
+
+        // A sliding session never outlives its max lifetime, not even its first token
+        setting.jwt_refresh_token_sliding = true;
+        let expiry = refresh_token_expiry(&setting, None, now);
+        assert_eq!(expiry.policy, ExpirationPolicy::Sliding);
+        assert_eq!(expiry.expires_at, now + chrono::Duration::seconds(1800));
+        assert_eq!(expiry.max_expires_at, Some(expiry.expires_at));
+    }
 }
@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod sign_in_service_tests {
+    use my_axum::user::service::sign_in_service::network_of;
+
+    #[test]
+    fn test_network_of_ipv4_is_its_slash_24() {
+        assert_eq!(network_of("203.0.113.10"), "203.0.113.0/24");
+        assert_eq!(network_of("203.0.113.99"), network_of("203.0.113.10"));
+        assert_ne!(network_of("203.0.114.10"), network_of("203.0.113.10"));
+    }
+
+    #[test]
+    fn test_network_of_ipv6_is_its_slash_48() {
+        assert_eq!(network_of("2001:db8:1234:5678::1"), "2001:db8:1234::/48");
+        assert_eq!(
+            network_of("2001:db8:1234:ffff::2"),
+            network_of("2001:db8:1234:5678::1")
+        );
+    }
+
+    #[test]
+    fn test_network_of_unparseable_address_is_itself() {
+        assert_eq!(network_of(" unknown "), "unknown");
+    }
+}
@@ -1,19 +1,18 @@
 #[cfg(test)]
 mod auth_task_tests {
     use crate::setup::app::TestApp;
+    use crate::setup::factory::{PasswordResetTokenFactory, RefreshTokenFactory, UserFactory};
     use chrono::{Duration, Utc};
     use my_axum::{
+        config::setting::Setting,
         core::context::Context,
-        user::entity::{refresh_token, user},
+        user::entity::{password_reset_token, refresh_token},
         user::{
-            repository::{
-                refresh_token_repository::{self, RefreshTokenSearchParams},
-                user_repository,
-            },
-            task::auth_task::clean_expired_tokens,
+            repository::refresh_token_repository::{self, RefreshTokenSearchParams},
+            task::auth_task::{clean_expired_tokens, purge_expired_password_resets},
         },
     };
-    use sea_orm::{Set, TransactionTrait};
+    use sea_orm::{EntityTrait, TransactionTrait};
     use std::sync::Arc;
 
     #[tokio::test]
@@ -27,46 +26,17 @@ mod auth_task_tests {
                 Box::pin(async move {
                     let context = Context::builder(Arc::new(txn.begin().await?)).build();
 
-                    // Create a user first
-                    let user_model = user::ActiveModel {
-                        email: Set("clean_tokens@example.com".to_string()),
-                        password: Set("password123@".to_string()),
-                        first_name: Set(Some("Clean".to_string())),
-                        last_name: Set(Some("Tokens".to_string())),
-                        phone: Set(None),
-                        ..Default::default()
-                    };
-                    let created_user = user_repository::create(&context, user_model)
-                        .await
-                        .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                    let created_user = UserFactory::new().create(&context).await?;
 
                     // Create expired and valid tokens
                     for i in 1..=3 {
-                        // Expired tokens
-                        let expired_token = refresh_token::ActiveModel {
-                            user_id: Set(created_user.id),
-                            token: Set(format!("expired_token_{}", i)),
-                            device_info: Set(Some(format!("Expired Device {}", i))),
-                            ip_address: Set(Some("192.168.1.1".to_string())),
-                            expires_at: Set(Utc::now().naive_utc() - Duration::hours(i as i64)),
-                            ..Default::default()
-                        };
-                        refresh_token_repository::create(&context, expired_token)
-                            .await
-                            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
-
-                        // Valid tokens
-                        let valid_token = refresh_token::ActiveModel {
-                            user_id: Set(created_user.id),
-                            token: Set(format!("valid_token_{}", i)),
-                            device_info: Set(Some(format!("Valid Device {}", i))),
-                            ip_address: Set(Some("192.168.1.1".to_string())),
-                            expires_at: Set(Utc::now().naive_utc() + Duration::hours(24)),
-                            ..Default::default()
-                        };
-                        refresh_token_repository::create(&context, valid_token)
-                            .await
-                            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                        RefreshTokenFactory::for_user(created_user.id)
+                            .expired_since(Duration::hours(i))
+                            .create(&context)
+                            .await?;
+                        RefreshTokenFactory::for_user(created_user.id)
+                            .create(&context)
+                            .await?;
                     }
 
                     context.commit().await?;
@@ -144,32 +114,13 @@ mod auth_task_tests {
                 Box::pin(async move {
                     let context = Context::builder(Arc::new(txn.begin().await?)).build();
 
-                    // Create a user
-                    let user_model = user::ActiveModel {
-                        email: Set("no_expired@example.com".to_string()),
-                        password: Set("password123@".to_string()),
-                        first_name: Set(Some("No".to_string())),
-                        last_name: Set(Some("Expired".to_string())),
-                        phone: Set(None),
-                        ..Default::default()
-                    };
-                    let created_user = user_repository::create(&context, user_model)
-                        .await
-                        .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                    let created_user = UserFactory::new().create(&context).await?;
 
                     // Create only valid tokens
-                    for i in 1..=3 {
-                        let valid_token = refresh_token::ActiveModel {
-                            user_id: Set(created_user.id),
-                            token: Set(format!("valid_only_token_{}", i)),
-                            device_info: Set(Some(format!("Device {}", i))),
-                            ip_address: Set(Some("192.168.1.1".to_string())),
-                            expires_at: Set(Utc::now().naive_utc() + Duration::hours(24)),
-                            ..Default::default()
-                        };
-                        refresh_token_repository::create(&context, valid_token)
-                            .await
-                            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                    for _ in 1..=3 {
+                        RefreshTokenFactory::for_user(created_user.id)
+                            .create(&context)
+                            .await?;
                     }
 
                     context.commit().await?;
@@ -208,6 +159,61 @@ mod auth_task_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_clean_expired_tokens_removes_lapsed_sliding_sessions() -> Result<(), anyhow::Error>
+    {
+        let test_app = TestApp::spawn_app().await;
+
+        let (created_user_id, active_id) = test_app
+            .db
+            .transaction::<_, (i32, i32), sea_orm::DbErr>(|txn| {
+                Box::pin(async move {
+                    let context = Context::builder(Arc::new(txn.begin().await?)).build();
+                    let created_user = UserFactory::new().create(&context).await?;
+                    let session_end = Utc::now().naive_utc() + Duration::days(30);
+
+                    // Not refreshed in time, although its session could still be extended
+                    RefreshTokenFactory::for_user(created_user.id)
+                        .expired()
+                        .sliding(session_end)
+                        .create(&context)
+                        .await?;
+                    let active = RefreshTokenFactory::for_user(created_user.id)
+                        .sliding(session_end)
+                        .create(&context)
+                        .await?;
+
+                    context.commit().await?;
+                    Ok((created_user.id, active.id))
+                })
+            })
+            .await?;
+
+        clean_expired_tokens(&test_app.db).await?;
+
+        let remaining_tokens = test_app
+            .db
+            .transaction::<_, Vec<refresh_token::Model>, sea_orm::DbErr>(|txn| {
+                Box::pin(async move {
+                    let context = Context::builder(Arc::new(txn.begin().await?)).build();
+                    let (tokens, _) = refresh_token_repository::search(
+                        &context,
+                        &RefreshTokenSearchParams {
+                            user_id: Some(created_user_id),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+                    Ok(tokens)
+                })
+            })
+            .await?;
+        let remaining_ids: Vec<i32> = remaining_tokens.iter().map(|token| token.id).collect();
+        assert_eq!(remaining_ids, [active_id]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_clean_expired_tokens_empty_database() -> Result<(), anyhow::Error> {
         let test_app = TestApp::spawn_app().await;
@@ -249,33 +255,15 @@ mod auth_task_tests {
                 Box::pin(async move {
                     let context = Context::builder(Arc::new(txn.begin().await?)).build();
 
-                    // Create a user
-                    let user_model = user::ActiveModel {
-                        email: Set("batch_test@example.com".to_string()),
-                        password: Set("password123@".to_string()),
-                        first_name: Set(Some("Batch".to_string())),
-                        last_name: Set(Some("Test".to_string())),
-                        phone: Set(None),
-                        ..Default::default()
-                    };
-                    let created_user = user_repository::create(&context, user_model)
-                        .await
-                        .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                    let created_user = UserFactory::new().create(&context).await?;
 
                     // Create a large number of expired tokens to test batch processing
                     for i in 1..=150 {
                         // More than BATCH_SIZE (100) to test batch processing
-                        let expired_token = refresh_token::ActiveModel {
-                            user_id: Set(created_user.id),
-                            token: Set(format!("batch_expired_token_{:03}", i)),
-                            device_info: Set(Some(format!("Batch Device {}", i))),
-                            ip_address: Set(Some("192.168.1.1".to_string())),
-                            expires_at: Set(Utc::now().naive_utc() - Duration::minutes(i as i64)),
-                            ..Default::default()
-                        };
-                        refresh_token_repository::create(&context, expired_token)
-                            .await
-                            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                        RefreshTokenFactory::for_user(created_user.id)
+                            .expired_since(Duration::minutes(i))
+                            .create(&context)
+                            .await?;
                     }
 
                     context.commit().await?;
@@ -351,44 +339,18 @@ mod auth_task_tests {
 
                     // Create multiple users
                     for i in 1..=3 {
-                        let user_model = user::ActiveModel {
-                            email: Set(format!("mixed_user_{}@example.com", i)),
-                            password: Set("password123@".to_string()),
-                            first_name: Set(Some(format!("User{}", i))),
-                            last_name: Set(Some("Mixed".to_string())),
-                            phone: Set(None),
-                            ..Default::default()
-                        };
-                        let created_user = user_repository::create(&context, user_model)
-                            .await
-                            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                        let created_user = UserFactory::new().create(&context).await?;
                         user_ids.push(created_user.id);
 
-                        // Expired token
-                        let expired_token = refresh_token::ActiveModel {
-                            user_id: Set(created_user.id),
-                            token: Set(format!("mixed_expired_token_user_{}", i)),
-                            device_info: Set(Some("Expired Device".to_string())),
-                            ip_address: Set(Some("192.168.1.1".to_string())),
-                            expires_at: Set(Utc::now().naive_utc() - Duration::hours(1)),
-                            ..Default::default()
-                        };
-                        refresh_token_repository::create(&context, expired_token)
-                            .await
-                            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
-
-                        // Valid token
-                        let valid_token = refresh_token::ActiveModel {
-                            user_id: Set(created_user.id),
-                            token: Set(format!("mixed_valid_token_user_{}", i)),
-                            device_info: Set(Some("Valid Device".to_string())),
-                            ip_address: Set(Some("192.168.1.1".to_string())),
-                            expires_at: Set(Utc::now().naive_utc() + Duration::hours(24)),
-                            ..Default::default()
-                        };
-                        refresh_token_repository::create(&context, valid_token)
-                            .await
-                            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                        RefreshTokenFactory::for_user(created_user.id)
+                            .token(format!("mixed_expired_token_user_{}", i))
+                            .expired()
+                            .create(&context)
+                            .await?;
+                        RefreshTokenFactory::for_user(created_user.id)
+                            .token(format!("mixed_valid_token_user_{}", i))
+                            .create(&context)
+                            .await?;
                     }
 
                     context.commit().await?;
@@ -403,7 +365,8 @@ mod auth_task_tests {
         assert!(result.is_ok());
 
         // Verify only valid tokens remain for each user
-        for user_id in user_ids {
+        let hasher = Setting::new().token_hasher();
+        for (i, user_id) in (1..).zip(user_ids) {
             let remaining_tokens = test_app
                 .db
                 .transaction::<_, Vec<refresh_token::Model>, sea_orm::DbErr>(|txn| {
@@ -424,7 +387,10 @@ mod auth_task_tests {
                 })
                 .await?;
             assert_eq!(remaining_tokens.len(), 1);
-            assert!(remaining_tokens[0].token.contains("valid"));
+            assert!(hasher.verify(
+                &format!("mixed_valid_token_user_{}", i),
+                &remaining_tokens[0].token
+            ));
         }
 
         Ok(())
@@ -441,44 +407,21 @@ mod auth_task_tests {
                 Box::pin(async move {
                     let context = Context::builder(Arc::new(txn.begin().await?)).build();
 
-                    // Create a user
-                    let user_model = user::ActiveModel {
-                        email: Set("edge_case@example.com".to_string()),
-                        password: Set("password123@".to_string()),
-                        first_name: Set(Some("Edge".to_string())),
-                        last_name: Set(Some("Case".to_string())),
-                        phone: Set(None),
-                        ..Default::default()
-                    };
-                    let created_user = user_repository::create(&context, user_model)
-                        .await
-                        .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                    let created_user = UserFactory::new().create(&context).await?;
 
                     // Create token that just expired (1 second ago)
-                    let just_expired_token = refresh_token::ActiveModel {
-                        user_id: Set(created_user.id),
-                        token: Set("just_expired_token".to_string()),
-                        device_info: Set(Some("Just Expired Device".to_string())),
-                        ip_address: Set(Some("192.168.1.1".to_string())),
-                        expires_at: Set(Utc::now().naive_utc() - Duration::seconds(1)),
-                        ..Default::default()
-                    };
-                    refresh_token_repository::create(&context, just_expired_token)
-                        .await
-                        .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                    RefreshTokenFactory::for_user(created_user.id)
+                        .token("just_expired_token")
+                        .expired_since(Duration::seconds(1))
+                        .create(&context)
+                        .await?;
 
                     // Create token that just expires in the future (1 second)
-                    let just_valid_token = refresh_token::ActiveModel {
-                        user_id: Set(created_user.id),
-                        token: Set("just_valid_token".to_string()),
-                        device_info: Set(Some("Just Valid Device".to_string())),
-                        ip_address: Set(Some("192.168.1.1".to_string())),
-                        expires_at: Set(Utc::now().naive_utc() + Duration::seconds(1)),
-                        ..Default::default()
-                    };
-                    refresh_token_repository::create(&context, just_valid_token)
-                        .await
-                        .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+                    RefreshTokenFactory::for_user(created_user.id)
+                        .token("just_valid_token")
+                        .expires_at(Utc::now().naive_utc() + Duration::seconds(1))
+                        .create(&context)
+                        .await?;
 
                     context.commit().await?;
 
@@ -512,7 +455,10 @@ mod auth_task_tests {
             })
             .await?;
         assert_eq!(remaining_tokens.len(), 1);
-        assert_eq!(remaining_tokens[0].token, "just_valid_token");
+        assert_eq!(
+            remaining_tokens[0].token,
+            Setting::new().token_hasher().hash("just_valid_token")
+        );
 
         Ok(())
     }
@@ -532,4 +478,35 @@ mod auth_task_tests {
         let result = future.await;
         assert!(result.is_ok() || result.is_err()); // Either outcome is valid for testing
     }
+
+    #[tokio::test]
+    async fn test_purge_expired_password_resets_keeps_valid_tokens() {
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.db.begin().await.unwrap())).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        PasswordResetTokenFactory::for_user(user.id)
+            .token("111111")
+            .expired()
+            .create(&context)
+            .await
+            .unwrap();
+        PasswordResetTokenFactory::for_user(user.id)
+            .token("222222")
+            .create(&context)
+            .await
+            .unwrap();
+        context.commit().await.unwrap();
+
+        purge_expired_password_resets(&test_app.db).await.unwrap();
+
+        let remaining = password_reset_token::Entity::find()
+            .all(&test_app.db)
+            .await
+            .unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(
+            remaining[0].token,
+            Setting::new().token_hasher().hash("222222")
+        );
+    }
 }
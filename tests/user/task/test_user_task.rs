@@ -2,7 +2,7 @@
 mod user_task_tests {
     use async_trait::async_trait;
     use my_axum::{
-        pkg::messaging::MessageProducer,
+        pkg::messaging::{EncodedMessage, MessageProducer},
         user::task::user_task::{process_avatar_upload, send_welcome_email},
     };
     use std::sync::{Arc, Mutex};
@@ -12,9 +12,9 @@ mod user_task_tests {
 
     #[async_trait]
     impl MessageProducer for MockProducer {
-        async fn publish_event_json(
+        async fn publish(
             &self,
-            _event_json: &str,
+            _message: &EncodedMessage,
             _destination: Option<&str>,
         ) -> anyhow::Result<()> {
             Ok(())
@@ -45,11 +45,12 @@ mod user_task_tests {
 
     #[async_trait]
     impl MessageProducer for TrackingMockProducer {
-        async fn publish_event_json(
+        async fn publish(
             &self,
-            event_json: &str,
+            message: &EncodedMessage,
             _destination: Option<&str>,
         ) -> anyhow::Result<()> {
+            let event_json = message.as_str();
             self.messages.lock().unwrap().push(event_json.to_string());
             Ok(())
         }
@@ -605,12 +606,13 @@ mod user_task_tests {
             "Avatar upload should succeed for valid user"
         );
 
-        // Verify messages were published (6 progress stages + 1 final message = 7 total)
+        // Verify messages were published (6 progress stages, each with its pipeline progress,
+        // + 1 final message = 13 total)
         let messages = producer.get_profilessages();
         assert_eq!(
             messages.len(),
-            7,
-            "Should publish 7 messages (6 progress + 1 final)"
+            13,
+            "Should publish 13 messages (6 progress + 6 pipeline + 1 final)"
         );
 
         // Verify progress messages contain expected event types
@@ -625,6 +627,15 @@ mod user_task_tests {
             .filter(|msg| msg.contains("avatar_upload_complete"))
             .count();
         assert_eq!(complete_count, 1, "Should have 1 completion message");
+
+        let pipeline_count = messages
+            .iter()
+            .filter(|msg| msg.contains("pipeline_progress"))
+            .count();
+        assert_eq!(
+            pipeline_count, 6,
+            "Should have 6 pipeline progress messages"
+        );
     }
 
     #[tokio::test]
@@ -929,7 +940,11 @@ mod user_task_tests {
         .await;
         assert!(result.is_ok());
 
-        let messages = producer.get_profilessages();
+        let messages: Vec<String> = producer
+            .get_profilessages()
+            .into_iter()
+            .filter(|msg| !msg.contains("pipeline_progress"))
+            .collect();
 
         // Verify progress percentages
         let expected_percentages = [10, 25, 40, 60, 80, 100];
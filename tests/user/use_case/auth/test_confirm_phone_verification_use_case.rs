@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod confirm_phone_verification_use_case_tests {
+    use crate::setup::{app::TestApp, factory::UserFactory};
+    use chrono::{Duration, Utc};
+    use my_axum::{
+        core::context::Context,
+        user::{
+            dto::auth_dto::ConfirmPhoneDTO,
+            entity::{phone_verification_token, user},
+            repository::phone_verification_repository,
+            use_case::auth::confirm_phone_verification_use_case,
+        },
+    };
+    use sea_orm::Set;
+    use std::sync::Arc;
+
+    const PHONE: &str = "+84901234567";
+
+    async fn context_with_token(
+        test_app: &TestApp,
+        phone: &str,
+        expires_in: Duration,
+    ) -> (Context, user::Model) {
+        let txn = test_app.begin_transaction().await;
+        let mut context = Context::builder(Arc::new(txn)).build();
+        let user = UserFactory::new()
+            .phone(PHONE)
+            .create(&context)
+            .await
+            .unwrap();
+        phone_verification_repository::create(
+            &context,
+            phone_verification_token::ActiveModel {
+                user_id: Set(user.id),
+                phone: Set(phone.to_string()),
+                token: Set("123456".to_string()),
+                retry_count: Set(0),
+                expires_at: Set((Utc::now() + expires_in).naive_utc()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        context.user = Some(user.clone());
+        (context, user)
+    }
+
+    fn dto(otp: &str) -> ConfirmPhoneDTO {
+        ConfirmPhoneDTO {
+            otp: otp.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirm_phone_verification_success() {
+        let test_app = TestApp::spawn_app().await;
+        let (context, user) = context_with_token(&test_app, PHONE, Duration::minutes(10)).await;
+
+        let result = confirm_phone_verification_use_case::execute(&context, dto("123456"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.status.as_u16(), 200);
+        assert!(result.data.phone_verified);
+        let token = phone_verification_repository::find_by_user_id(&context, user.id)
+            .await
+            .unwrap();
+        assert!(token.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_phone_verification_counts_failed_attempts() {
+        let test_app = TestApp::spawn_app().await;
+        let (context, user) = context_with_token(&test_app, PHONE, Duration::minutes(10)).await;
+
+        for _ in 0..3 {
+            let result =
+                confirm_phone_verification_use_case::execute(&context, dto("000000")).await;
+            assert_eq!(result.unwrap_err().status.as_u16(), 400);
+        }
+        let result = confirm_phone_verification_use_case::execute(&context, dto("123456")).await;
+
+        assert_eq!(result.unwrap_err().status.as_u16(), 400);
+        let token = phone_verification_repository::find_by_user_id(&context, user.id)
+            .await
+            .unwrap();
+        assert!(token.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_phone_verification_expired() {
+        let test_app = TestApp::spawn_app().await;
+        let (context, _) = context_with_token(&test_app, PHONE, Duration::minutes(-1)).await;
+
+        let result = confirm_phone_verification_use_case::execute(&context, dto("123456")).await;
+
+        assert_eq!(result.unwrap_err().status.as_u16(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_phone_verification_rejects_code_for_previous_phone() {
+        let test_app = TestApp::spawn_app().await;
+        let (context, _) =
+            context_with_token(&test_app, "+84909999999", Duration::minutes(10)).await;
+
+        let result = confirm_phone_verification_use_case::execute(&context, dto("123456")).await;
+
+        assert_eq!(result.unwrap_err().status.as_u16(), 400);
+    }
+}
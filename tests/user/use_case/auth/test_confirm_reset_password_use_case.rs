@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod confirm_reset_password_use_case_tests {
+    use crate::setup::{
+        app::TestApp,
+        factory::{PasswordResetTokenFactory, UserFactory},
+    };
+    use chrono::{Duration, Utc};
+    use my_axum::{
+        core::context::Context,
+        pkg::password::verify_password,
+        user::{
+            dto::auth_dto::ConfirmResetPasswordDTO,
+            repository::{password_reset_repository, user_repository},
+            service::auth_service,
+            use_case::auth::{confirm_reset_password_use_case, verify_reset_link_use_case},
+        },
+    };
+    use std::sync::Arc;
+
+    const NONCE: &str = "LINKNONCE0000000000000000000000A";
+
+    fn new_password() -> ConfirmResetPasswordDTO {
+        ConfirmResetPasswordDTO {
+            new_password: "new_password456@".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirm_reset_password_success() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        PasswordResetTokenFactory::for_user(user.id)
+            .token(NONCE)
+            .create(&context)
+            .await
+            .unwrap();
+        let token = auth_service::sign_password_reset_link(NONCE, Utc::now() + Duration::hours(1));
+
+        // Act
+        let response = confirm_reset_password_use_case::execute(&context, &token, new_password())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(response.status.as_u16(), 204);
+        let updated_user = user_repository::find_by_id(&context, user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            verify_password("new_password456@", &updated_user.password)
+                .await
+                .is_ok()
+        );
+        assert!(
+            password_reset_repository::find_by_token(&context, NONCE)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confirm_reset_password_link_works_once() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        PasswordResetTokenFactory::for_user(user.id)
+            .token(NONCE)
+            .create(&context)
+            .await
+            .unwrap();
+        let token = auth_service::sign_password_reset_link(NONCE, Utc::now() + Duration::hours(1));
+        confirm_reset_password_use_case::execute(&context, &token, new_password())
+            .await
+            .unwrap();
+
+        // Act
+        let reused =
+            confirm_reset_password_use_case::execute(&context, &token, new_password()).await;
+        let verified = verify_reset_link_use_case::execute(&context, &token).await;
+
+        // Assert
+        assert_eq!(reused.unwrap_err().status.as_u16(), 400);
+        assert_eq!(verified.unwrap_err().status.as_u16(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_reset_password_rejects_forged_link() {
+        // Arrange: a stored nonce, but a signature made without the key
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        PasswordResetTokenFactory::for_user(user.id)
+            .token(NONCE)
+            .create(&context)
+            .await
+            .unwrap();
+        let expires_at = (Utc::now() + Duration::hours(1)).timestamp();
+        let token = format!("{}.{}.{}", NONCE, expires_at, "0".repeat(64));
+
+        // Act
+        let result =
+            confirm_reset_password_use_case::execute(&context, &token, new_password()).await;
+
+        // Assert
+        assert_eq!(result.unwrap_err().status.as_u16(), 400);
+        assert!(
+            password_reset_repository::find_by_token(&context, NONCE)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confirm_reset_password_rejects_revoked_reset() {
+        // Arrange: the link is signed, but its reset expired in the database
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        PasswordResetTokenFactory::for_user(user.id)
+            .token(NONCE)
+            .expired()
+            .create(&context)
+            .await
+            .unwrap();
+        let token = auth_service::sign_password_reset_link(NONCE, Utc::now() + Duration::hours(1));
+
+        // Act
+        let result =
+            confirm_reset_password_use_case::execute(&context, &token, new_password()).await;
+
+        // Assert
+        assert_eq!(result.unwrap_err().status.as_u16(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_reset_password_validates_password() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        PasswordResetTokenFactory::for_user(user.id)
+            .token(NONCE)
+            .create(&context)
+            .await
+            .unwrap();
+        let token = auth_service::sign_password_reset_link(NONCE, Utc::now() + Duration::hours(1));
+
+        // Act
+        let result = confirm_reset_password_use_case::execute(
+            &context,
+            &token,
+            ConfirmResetPasswordDTO {
+                new_password: String::new(),
+            },
+        )
+        .await;
+
+        // Assert: the link is still usable
+        assert_eq!(result.unwrap_err().status.as_u16(), 400);
+        assert!(
+            verify_reset_link_use_case::execute(&context, &token)
+                .await
+                .is_ok()
+        );
+    }
+}
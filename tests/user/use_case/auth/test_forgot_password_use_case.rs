@@ -4,25 +4,26 @@ mod forgot_password_use_case_tests {
     use async_trait::async_trait;
     use chrono::{Duration, Utc};
     use my_axum::{
-        core::context::Context,
-        pkg::messaging::MessageProducer,
+        core::{context::Context, dto::error_code::ErrorCode, id::SequentialIdGenerator},
+        pkg::messaging::{EncodedMessage, MessageProducer},
         user::entity::password_reset_token,
         user::{
             dto::{auth_dto::ForgotPasswordDTO, user_dto::UserCreateDTO},
             repository::password_reset_repository,
+            service::auth_service,
             use_case::{auth::forgot_password_use_case, user::create_user_use_case},
         },
     };
     use sea_orm::Set;
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Instant};
 
     struct MockProducer;
 
     #[async_trait]
     impl MessageProducer for MockProducer {
-        async fn publish_event_json(
+        async fn publish(
             &self,
-            _event_json: &str,
+            _message: &EncodedMessage,
             _destination: Option<&str>,
         ) -> anyhow::Result<()> {
             Ok(())
@@ -33,9 +34,9 @@ mod forgot_password_use_case_tests {
 
     #[async_trait]
     impl MessageProducer for FailingProducer {
-        async fn publish_event_json(
+        async fn publish(
             &self,
-            _event_json: &str,
+            _message: &EncodedMessage,
             _destination: Option<&str>,
         ) -> anyhow::Result<()> {
             Err(anyhow::anyhow!("Publish failed"))
@@ -72,12 +73,14 @@ mod forgot_password_use_case_tests {
             email: "nonexistent@example.com".to_string(),
         };
 
+        let started = Instant::now();
         let result = forgot_password_use_case::execute(&context, dto).await;
 
-        // Should still return success to prevent email enumeration
+        // Should still return success, no sooner than for a registered email
         assert!(result.is_ok());
         let response = result.unwrap();
         assert_eq!(response.status.as_u16(), 204);
+        assert!(started.elapsed() >= auth_service::RECOVERY_MIN_DURATION);
     }
 
     #[tokio::test]
@@ -96,7 +99,8 @@ mod forgot_password_use_case_tests {
         assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.status.as_u16(), 400);
-        assert!(error.message.contains("email"));
+        assert_eq!(error.code, ErrorCode::ValidationFailed);
+        assert_eq!(error.errors[0].field, "email");
     }
 
     #[tokio::test]
@@ -127,8 +131,8 @@ mod forgot_password_use_case_tests {
             email: "test@example.com".to_string(),
         };
         let result2 = forgot_password_use_case::execute(&context, dto2).await;
-        // Will fail due to no producer, but token should be replaced
-        assert!(result2.is_err());
+        // The email can't be sent without a producer, which isn't reported
+        assert!(result2.is_ok());
 
         // Verify first token was deleted and new one was created
         let token1 = password_reset_repository::find_by_token(&context, otp1)
@@ -241,6 +245,46 @@ mod forgot_password_use_case_tests {
         assert_eq!(result.unwrap().status.as_u16(), 204);
     }
 
+    #[tokio::test]
+    async fn test_forgot_password_stores_otp_from_id_generator() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let producer: Arc<Box<dyn MessageProducer>> = Arc::new(Box::new(MockProducer));
+        let context = Context::builder(Arc::new(txn))
+            .producer(producer)
+            .id_generator(Arc::new(SequentialIdGenerator::new()))
+            .build();
+
+        let user = create_user_use_case::execute(
+            &context,
+            UserCreateDTO {
+                email: "otp_generator@example.com".to_string(),
+                password: "password123@".to_string(),
+                first_name: None,
+                last_name: None,
+                phone: None,
+            },
+        )
+        .await
+        .unwrap()
+        .data;
+
+        forgot_password_use_case::execute(
+            &context,
+            ForgotPasswordDTO {
+                email: "otp_generator@example.com".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let token = password_reset_repository::find_by_token(&context, "000001")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(token.user_id, user.id);
+    }
+
     #[tokio::test]
     async fn test_forgot_password_success_user_without_first_name() {
         let test_app = TestApp::spawn_app().await;
@@ -295,9 +339,8 @@ mod forgot_password_use_case_tests {
 
         let result = forgot_password_use_case::execute(&context, dto).await;
 
-        // Should fail because producer fails to publish
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        assert_eq!(error.status.as_u16(), 500);
+        // Answers like for an unregistered email, so the failure doesn't reveal the account
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().status.as_u16(), 204);
     }
 }
@@ -2,6 +2,7 @@
 mod get_profile_use_case_tests {
     use crate::setup::app::TestApp;
     use my_axum::{
+        config::setting::Setting,
         core::context::Context,
         user::entity::sea_orm_active_enums::UserRole,
         user::{
@@ -35,11 +36,19 @@ mod get_profile_use_case_tests {
         let user_model = user::Model {
             id: created_user.id,
             email: created_user.email.clone(),
+            normalized_email: Setting::new().email.normalize(&created_user.email),
             password: "hashed_password".to_string(), // Not exposed in response
             role: UserRole::User,
             first_name: created_user.first_name.clone(),
             last_name: created_user.last_name.clone(),
             phone: created_user.phone.clone(),
+            phone_verified_at: None,
+            verified_at: None,
+            locale: None,
+            timezone: None,
+            deactivated_at: None,
+            deleted_at: None,
+            avatar_file_id: None,
             created_at: created_user.created_at,
             updated_at: created_user.updated_at,
             created_user_id: None,
@@ -90,11 +99,19 @@ mod get_profile_use_case_tests {
         let user_model = user::Model {
             id: created_user.id,
             email: created_user.email.clone(),
+            normalized_email: Setting::new().email.normalize(&created_user.email),
             password: "hashed_password".to_string(),
             role: UserRole::User,
             first_name: created_user.first_name.clone(),
             last_name: created_user.last_name.clone(),
             phone: created_user.phone.clone(),
+            phone_verified_at: None,
+            verified_at: None,
+            locale: None,
+            timezone: None,
+            deactivated_at: None,
+            deleted_at: None,
+            avatar_file_id: None,
             created_at: created_user.created_at,
             updated_at: created_user.updated_at,
             created_user_id: None,
@@ -140,11 +157,19 @@ mod get_profile_use_case_tests {
         let user_model = user::Model {
             id: created_user.id,
             email: created_user.email.clone(),
+            normalized_email: Setting::new().email.normalize(&created_user.email),
             password: "secret_hashed_password".to_string(), // This should NOT be in response
             role: UserRole::User,
             first_name: created_user.first_name.clone(),
             last_name: created_user.last_name.clone(),
             phone: created_user.phone.clone(),
+            phone_verified_at: None,
+            verified_at: None,
+            locale: None,
+            timezone: None,
+            deactivated_at: None,
+            deleted_at: None,
+            avatar_file_id: None,
             created_at: created_user.created_at,
             updated_at: created_user.updated_at,
             created_user_id: None,
@@ -1,14 +1,24 @@
 #[cfg(test)]
 mod login_use_case_tests {
-    use crate::setup::app::TestApp;
+    use crate::setup::{
+        app::TestApp,
+        factory::{DEFAULT_PASSWORD, UserFactory},
+    };
     use axum::http::HeaderMap;
     use my_axum::{
-        core::context::Context,
+        core::{context::Context, dto::error_code::ErrorCode},
+        pkg::password::{
+            PasswordConfig, PasswordScheme, hash_password_with_config, needs_rehash,
+            verify_password,
+        },
         user::{
             dto::{auth_dto::LoginDTO, user_dto::UserCreateDTO},
+            entity::user,
+            repository::user_repository,
             use_case::{auth::login_use_case, user::create_user_use_case},
         },
     };
+    use sea_orm::Set;
     use std::sync::Arc;
 
     #[tokio::test]
@@ -61,7 +71,7 @@ mod login_use_case_tests {
 
         let error = result.unwrap_err();
         assert_eq!(error.status.as_u16(), 401);
-        assert_eq!(error.message, "Email has not been registered");
+        assert_eq!(error.code, ErrorCode::AuthInvalidCredentials);
     }
 
     #[tokio::test]
@@ -92,7 +102,7 @@ mod login_use_case_tests {
 
         let error = result.unwrap_err();
         assert_eq!(error.status.as_u16(), 401);
-        assert_eq!(error.message, "Password is incorrect");
+        assert_eq!(error.code, ErrorCode::AuthInvalidCredentials);
     }
 
     #[tokio::test]
@@ -123,11 +133,11 @@ mod login_use_case_tests {
 
         let error = result.unwrap_err();
         assert_eq!(error.status.as_u16(), 401);
-        assert_eq!(error.message, "Password is incorrect");
+        assert_eq!(error.code, ErrorCode::AuthInvalidCredentials);
     }
 
     #[tokio::test]
-    async fn test_login_case_sensitive_email() {
+    async fn test_login_matches_email_case_insensitively() {
         let test_app = TestApp::spawn_app().await;
         let txn = test_app.begin_transaction().await;
         let context = Context::builder(Arc::new(txn)).build();
@@ -150,11 +160,93 @@ mod login_use_case_tests {
 
         let headers = HeaderMap::new();
 
+        // Addresses are compared by their normalized form, lowercased by default
         let result = login_use_case::execute(&context, dto, headers).await;
-        assert!(result.is_err());
+        assert!(result.is_ok());
+    }
 
-        let error = result.unwrap_err();
-        assert_eq!(error.status.as_u16(), 401);
-        assert_eq!(error.message, "Email has not been registered");
+    #[tokio::test]
+    async fn test_login_rehashes_outdated_password_hash() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let context = Context::builder(Arc::new(txn)).build();
+        let outdated = PasswordConfig {
+            memory_cost: 2048,
+            time_cost: 2,
+            parallelism: 1,
+            ..Default::default()
+        };
+        let user = UserFactory::new().create(&context).await.unwrap();
+        let mut user_active: user::ActiveModel = user.clone().into();
+        user_active.password = Set(hash_password_with_config(DEFAULT_PASSWORD, &outdated)
+            .await
+            .unwrap());
+        user_repository::update(&context, user_active)
+            .await
+            .unwrap();
+
+        let dto = LoginDTO {
+            email: user.email.clone(),
+            password: DEFAULT_PASSWORD.to_string(),
+        };
+        login_use_case::execute(&context, dto, HeaderMap::new())
+            .await
+            .unwrap();
+
+        let rehashed = user_repository::find_by_id(&context, user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!needs_rehash(
+            &rehashed.password,
+            &test_app.setting.password_hash
+        ));
+        assert!(
+            verify_password(DEFAULT_PASSWORD, &rehashed.password)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_upgrades_bcrypt_password_hash() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let context = Context::builder(Arc::new(txn)).build();
+        let bcrypt = PasswordConfig {
+            scheme: PasswordScheme::Bcrypt,
+            bcrypt_cost: 4,
+            ..Default::default()
+        };
+        let user = UserFactory::new().create(&context).await.unwrap();
+        let mut user_active: user::ActiveModel = user.clone().into();
+        user_active.password = Set(hash_password_with_config(DEFAULT_PASSWORD, &bcrypt)
+            .await
+            .unwrap());
+        user_repository::update(&context, user_active)
+            .await
+            .unwrap();
+
+        let dto = LoginDTO {
+            email: user.email.clone(),
+            password: DEFAULT_PASSWORD.to_string(),
+        };
+        login_use_case::execute(&context, dto, HeaderMap::new())
+            .await
+            .unwrap();
+
+        let rehashed = user_repository::find_by_id(&context, user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            PasswordScheme::of_hash(&rehashed.password),
+            Some(test_app.setting.password_hash.scheme)
+        );
+        assert!(
+            verify_password(DEFAULT_PASSWORD, &rehashed.password)
+                .await
+                .is_ok()
+        );
     }
 }
@@ -0,0 +1,261 @@
+#[cfg(test)]
+mod oauth_login_use_case_tests {
+    use crate::setup::{app::TestApp, factory::UserFactory};
+    use async_trait::async_trait;
+    use axum::http::{HeaderMap, HeaderValue, header::LOCATION};
+    use my_axum::{
+        core::{context::Context, dto::error_code::ErrorCode},
+        pkg::oauth::{OAuthIdentity, OAuthProvider},
+        user::{
+            dto::auth_dto::OAuthCallbackDTO,
+            entity::sea_orm_active_enums::IdentityProvider,
+            repository::{oauth_account_repository, user_repository},
+            use_case::auth::oauth_login_use_case,
+        },
+    };
+    use std::sync::Arc;
+
+    const REDIRECT_URI: &str = "http://localhost:8000/api/v1/auth/oauth/google/callback/";
+
+    /// Provider vouching for `identity` whatever the code
+    struct FakeProvider {
+        identity: OAuthIdentity,
+    }
+
+    #[async_trait]
+    impl OAuthProvider for FakeProvider {
+        fn authorization_url(&self, redirect_uri: &str, state: &str) -> String {
+            format!(
+                "https://provider.test/authorize?redirect_uri={}&state={}",
+                redirect_uri, state
+            )
+        }
+
+        async fn fetch_identity(
+            &self,
+            _code: &str,
+            _redirect_uri: &str,
+        ) -> anyhow::Result<OAuthIdentity> {
+            Ok(self.identity.clone())
+        }
+    }
+
+    fn provider(email: &str, email_verified: bool) -> FakeProvider {
+        FakeProvider {
+            identity: OAuthIdentity {
+                subject: "google-subject-1".to_string(),
+                email: Some(email.to_string()),
+                email_verified,
+                first_name: Some("Ada".to_string()),
+                last_name: Some("Lovelace".to_string()),
+            },
+        }
+    }
+
+    /// State handed out by `authorize`, with the request headers carrying its cookie
+    fn start_sign_in(context: &Context, client: &FakeProvider) -> (String, HeaderMap) {
+        let response = oauth_login_use_case::authorize(
+            context,
+            IdentityProvider::Google,
+            client,
+            REDIRECT_URI,
+        );
+        assert_eq!(response.status.as_u16(), 302);
+        let response_headers = response.headers.unwrap();
+        assert!(response_headers.contains_key(LOCATION));
+
+        let cookie = response_headers
+            .get("set-cookie")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .split(';')
+            .next()
+            .unwrap()
+            .to_string();
+        let state = cookie.strip_prefix("oauth_state=").unwrap().to_string();
+        assert!(response.data.authorization_url.contains(&state));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("cookie", HeaderValue::from_str(&cookie).unwrap());
+        (state, headers)
+    }
+
+    fn callback(state: &str) -> OAuthCallbackDTO {
+        OAuthCallbackDTO {
+            code: Some("code".to_string()),
+            state: Some(state.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oauth_login_creates_and_links_new_user() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let context = Context::builder(Arc::new(txn)).build();
+        let client = provider("ada@example.com", true);
+        let (state, headers) = start_sign_in(&context, &client);
+
+        let response = oauth_login_use_case::execute(
+            &context,
+            IdentityProvider::Google,
+            &client,
+            REDIRECT_URI,
+            callback(&state),
+            headers,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.status.as_u16(), 200);
+        assert!(!response.data.access.is_empty());
+        let user = user_repository::find_by_email(&context, "ada@example.com")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(user.first_name.as_deref(), Some("Ada"));
+        let accounts = oauth_account_repository::find_by_user_id(&context, user.id)
+            .await
+            .unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].provider, IdentityProvider::Google);
+        assert_eq!(accounts[0].subject, "google-subject-1");
+    }
+
+    #[tokio::test]
+    async fn test_oauth_login_links_existing_user_by_verified_email() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let context = Context::builder(Arc::new(txn)).build();
+        let existing = UserFactory::new()
+            .email("ada@example.com")
+            .create(&context)
+            .await
+            .unwrap();
+        let client = provider("ada@example.com", true);
+
+        for _ in 0..2 {
+            let (state, headers) = start_sign_in(&context, &client);
+            oauth_login_use_case::execute(
+                &context,
+                IdentityProvider::Google,
+                &client,
+                REDIRECT_URI,
+                callback(&state),
+                headers,
+            )
+            .await
+            .unwrap();
+        }
+
+        // Signing in again reuses the link instead of adding another
+        let accounts = oauth_account_repository::find_by_user_id(&context, existing.id)
+            .await
+            .unwrap();
+        assert_eq!(accounts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_oauth_login_state_mismatch() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let context = Context::builder(Arc::new(txn)).build();
+        let client = provider("ada@example.com", true);
+        let (_, headers) = start_sign_in(&context, &client);
+        let (other_state, _) = start_sign_in(&context, &client);
+
+        let error = oauth_login_use_case::execute(
+            &context,
+            IdentityProvider::Google,
+            &client,
+            REDIRECT_URI,
+            callback(&other_state),
+            headers,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.status.as_u16(), 400);
+        assert_eq!(error.code, ErrorCode::AuthOAuthStateInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_oauth_login_state_of_other_provider() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let context = Context::builder(Arc::new(txn)).build();
+        let client = provider("ada@example.com", true);
+        let (state, headers) = start_sign_in(&context, &client);
+
+        let error = oauth_login_use_case::execute(
+            &context,
+            IdentityProvider::GitHub,
+            &client,
+            REDIRECT_URI,
+            callback(&state),
+            headers,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::AuthOAuthStateInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_oauth_login_unverified_email_not_linked() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let context = Context::builder(Arc::new(txn)).build();
+        let existing = UserFactory::new()
+            .email("ada@example.com")
+            .create(&context)
+            .await
+            .unwrap();
+        let client = provider("ada@example.com", false);
+        let (state, headers) = start_sign_in(&context, &client);
+
+        let error = oauth_login_use_case::execute(
+            &context,
+            IdentityProvider::Google,
+            &client,
+            REDIRECT_URI,
+            callback(&state),
+            headers,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::AuthOAuthEmailUnverified);
+        let accounts = oauth_account_repository::find_by_user_id(&context, existing.id)
+            .await
+            .unwrap();
+        assert!(accounts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_oauth_login_denied_by_user() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let context = Context::builder(Arc::new(txn)).build();
+        let client = provider("ada@example.com", true);
+        let (state, headers) = start_sign_in(&context, &client);
+
+        let error = oauth_login_use_case::execute(
+            &context,
+            IdentityProvider::Google,
+            &client,
+            REDIRECT_URI,
+            OAuthCallbackDTO {
+                error: Some("access_denied".to_string()),
+                state: Some(state),
+                ..Default::default()
+            },
+            headers,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::AuthOAuthDenied);
+    }
+}
@@ -1,15 +1,25 @@
 #[cfg(test)]
 mod refresh_token_use_case_tests {
-    use crate::setup::app::TestApp;
+    use crate::setup::{
+        app::TestApp,
+        factory::{RefreshTokenFactory, UserFactory},
+    };
     use axum::http::{HeaderMap, HeaderValue};
+    use chrono::{Duration, Utc};
     use my_axum::{
-        core::{context::Context, dto::error_dto::ErrorDTO},
+        config::setting::Setting,
+        core::{
+            context::Context,
+            dto::{error_code::ErrorCode, error_dto::ErrorDTO},
+        },
         user::{
             dto::{
                 auth_dto::{LoginDTO, RefreshTokenDTO},
                 user_dto::UserCreateDTO,
             },
+            entity::{refresh_token, sea_orm_active_enums::ExpirationPolicy},
             repository::refresh_token_repository,
+            service::auth_service,
             use_case::{
                 auth::{login_use_case, refresh_token_use_case},
                 user::create_user_use_case,
@@ -299,6 +309,172 @@ mod refresh_token_use_case_tests {
         assert_eq!(error.status.as_u16(), 401);
     }
 
+    /// Refresh the stored `previous` token, returning the record of the token it is rotated into
+    async fn rotate(context: &Context, previous: &str) -> refresh_token::Model {
+        let dto = RefreshTokenDTO {
+            refresh_token: Some(previous.to_string()),
+        };
+        let response = refresh_token_use_case::execute(context, dto, HeaderMap::new())
+            .await
+            .unwrap();
+        refresh_token_repository::find_by_token(context, &response.data.refresh)
+            .await
+            .unwrap()
+            .expect("rotated token should be stored")
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_keeps_absolute_expiry() {
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        let (_, token) = auth_service::generate_token_pair(&context, user.id)
+            .await
+            .unwrap();
+        let previous = RefreshTokenFactory::for_user(user.id)
+            .token(&token)
+            .expires_at(Utc::now().naive_utc() + Duration::hours(1))
+            .create(&context)
+            .await
+            .unwrap();
+
+        let rotated = rotate(&context, &token).await;
+
+        assert_eq!(rotated.expiration_policy, ExpirationPolicy::Absolute);
+        assert_eq!(rotated.expires_at, previous.expires_at);
+        assert_eq!(rotated.max_expires_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_extends_sliding_expiry_up_to_max_lifetime() {
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        let now = Utc::now().naive_utc();
+        let lifetime = Duration::seconds(Setting::new().jwt_refresh_token_expires);
+
+        // Far from its max lifetime, the session is extended by a full refresh token lifetime
+        let (_, token) = auth_service::generate_token_pair(&context, user.id)
+            .await
+            .unwrap();
+        let previous = RefreshTokenFactory::for_user(user.id)
+            .token(&token)
+            .expires_at(now + Duration::hours(1))
+            .sliding(now + lifetime * 4)
+            .create(&context)
+            .await
+            .unwrap();
+
+        let rotated = rotate(&context, &token).await;
+
+        assert_eq!(rotated.expiration_policy, ExpirationPolicy::Sliding);
+        assert!(rotated.expires_at >= now + lifetime);
+        assert_eq!(rotated.max_expires_at, previous.max_expires_at);
+
+        // Close to it, the session ends at its max lifetime
+        let (_, token) = auth_service::generate_token_pair(&context, user.id)
+            .await
+            .unwrap();
+        let previous = RefreshTokenFactory::for_user(user.id)
+            .token(&token)
+            .expires_at(now + Duration::hours(1))
+            .sliding(now + Duration::hours(2))
+            .create(&context)
+            .await
+            .unwrap();
+
+        let rotated = rotate(&context, &token).await;
+
+        assert_eq!(rotated.expiration_policy, ExpirationPolicy::Sliding);
+        assert_eq!(Some(rotated.expires_at), previous.max_expires_at);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_marks_previous_token_rotated() {
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        let (_, token) = auth_service::generate_token_pair(&context, user.id)
+            .await
+            .unwrap();
+        RefreshTokenFactory::for_user(user.id)
+            .token(&token)
+            .create(&context)
+            .await
+            .unwrap();
+
+        let rotated = rotate(&context, &token).await;
+
+        let previous = refresh_token_repository::find_by_token(&context, &token)
+            .await
+            .unwrap()
+            .expect("rotated token should be kept");
+        assert!(previous.rotated_at.is_some());
+        assert!(rotated.rotated_at.is_none());
+        assert!(previous.family_id.is_some());
+        assert_eq!(rotated.family_id, previous.family_id);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_reuse_revokes_family() {
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        let (_, token) = auth_service::generate_token_pair(&context, user.id)
+            .await
+            .unwrap();
+        RefreshTokenFactory::for_user(user.id)
+            .token(&token)
+            .create(&context)
+            .await
+            .unwrap();
+        // Another sign-in of the same user, which the reuse leaves alone
+        let (_, other_token) = auth_service::generate_token_pair(&context, user.id)
+            .await
+            .unwrap();
+        RefreshTokenFactory::for_user(user.id)
+            .token(&other_token)
+            .create(&context)
+            .await
+            .unwrap();
+        let rotated = rotate(&context, &token).await;
+
+        let error = refresh_token_use_case::execute(
+            &context,
+            RefreshTokenDTO {
+                refresh_token: Some(token.clone()),
+            },
+            HeaderMap::new(),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(error.status.as_u16(), 401);
+        assert_eq!(error.code, ErrorCode::AuthRefreshTokenReused);
+        let (family, _) = refresh_token_repository::search(
+            &context,
+            &refresh_token_repository::RefreshTokenSearchParams {
+                ids: Some(&[rotated.id]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(family.is_empty());
+        assert!(
+            refresh_token_repository::find_by_token(&context, &token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            refresh_token_repository::find_by_token(&context, &other_token)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
     // Helper function to extract cookie value from headers
     fn extract_cookie_value(headers: &HeaderMap, cookie_name: &str) -> Option<String> {
         headers
@@ -3,7 +3,7 @@ mod register_use_case_tests {
     use crate::setup::app::TestApp;
     use axum::http::HeaderMap;
     use my_axum::{
-        core::context::Context,
+        core::{context::Context, dto::error_code::ErrorCode},
         user::{
             dto::{auth_dto::RegisterDTO, user_dto::UserCreateDTO},
             use_case::{auth::register_use_case, user::create_user_use_case},
@@ -71,7 +71,7 @@ mod register_use_case_tests {
 
         let error = result.unwrap_err();
         assert_eq!(error.status.as_u16(), 409);
-        assert_eq!(error.message, "Email address already exists");
+        assert_eq!(error.code, ErrorCode::UserEmailTaken);
     }
 
     #[tokio::test]
@@ -3,7 +3,8 @@ mod reset_password_use_case_tests {
     use crate::setup::app::TestApp;
     use chrono::{Duration, Utc};
     use my_axum::{
-        core::context::Context,
+        config::setting::Setting,
+        core::{context::Context, dto::error_code::ErrorCode},
         pkg::password::verify_password,
         user::entity::password_reset_token,
         user::{
@@ -121,7 +122,7 @@ mod reset_password_use_case_tests {
         assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.status.as_u16(), 400);
-        assert!(error.message.contains("Invalid email or OTP code"));
+        assert_eq!(error.code, ErrorCode::AuthInvalidOtp);
     }
 
     #[tokio::test]
@@ -171,7 +172,7 @@ mod reset_password_use_case_tests {
         assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.status.as_u16(), 400);
-        assert!(error.message.contains("Invalid email or OTP code"));
+        assert_eq!(error.code, ErrorCode::AuthInvalidOtp);
 
         // Verify retry count was incremented
         let token = password_reset_repository::find_by_token(&context, otp)
@@ -224,7 +225,7 @@ mod reset_password_use_case_tests {
         assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.status.as_u16(), 400);
-        assert!(error.message.contains("expired"));
+        assert_eq!(error.code, ErrorCode::AuthOtpExpired);
 
         // Verify token was deleted
         let token = password_reset_repository::find_by_token(&context, otp)
@@ -276,7 +277,7 @@ mod reset_password_use_case_tests {
         assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.status.as_u16(), 400);
-        assert!(error.message.contains("Maximum attempts"));
+        assert_eq!(error.code, ErrorCode::AuthOtpAttemptsExceeded);
 
         // Verify token was deleted
         let token = password_reset_repository::find_by_token(&context, otp)
@@ -285,6 +286,68 @@ mod reset_password_use_case_tests {
         assert!(token.is_none());
     }
 
+    #[tokio::test]
+    async fn test_reset_password_locks_out_after_max_wrong_otps() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let context = Context::builder(Arc::new(txn)).build();
+
+        let user_dto = UserCreateDTO {
+            email: "test@example.com".to_string(),
+            password: "old_password123@".to_string(),
+            first_name: Some("Test".to_string()),
+            last_name: Some("User".to_string()),
+            phone: Some("1234567890".to_string()),
+        };
+        let created_user = create_user_use_case::execute(&context, user_dto)
+            .await
+            .unwrap()
+            .data;
+
+        let otp = "123456";
+        create_reset_token(&context, created_user.id, otp).await;
+        let max_attempts = Setting::new().password_reset.max_attempts;
+        let attempt = |otp: &str| ResetPasswordDTO {
+            email: "test@example.com".to_string(),
+            otp: otp.to_string(),
+            new_password: "new_password456".to_string(),
+        };
+
+        // Every wrong OTP but the last is counted against the outstanding one
+        for retry_count in 1..max_attempts {
+            let error = reset_password_use_case::execute(&context, attempt("000000"))
+                .await
+                .unwrap_err();
+            assert_eq!(error.code, ErrorCode::AuthInvalidOtp);
+            assert!(error.keep_changes);
+
+            let token = password_reset_repository::find_by_token(&context, otp)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(token.retry_count, retry_count);
+        }
+
+        // The last one revokes it
+        let error = reset_password_use_case::execute(&context, attempt("000000"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::AuthOtpAttemptsExceeded);
+        assert!(error.keep_changes);
+        assert!(
+            password_reset_repository::find_by_token(&context, otp)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // So even the right OTP no longer works
+        let error = reset_password_use_case::execute(&context, attempt(otp))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::AuthInvalidOtp);
+    }
+
     #[tokio::test]
     async fn test_reset_password_invalid_email_format() {
         let test_app = TestApp::spawn_app().await;
@@ -358,7 +421,7 @@ mod reset_password_use_case_tests {
         assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.status.as_u16(), 400);
-        assert!(error.message.contains("Invalid email or OTP code"));
+        assert_eq!(error.code, ErrorCode::AuthInvalidOtp);
     }
 
     #[tokio::test]
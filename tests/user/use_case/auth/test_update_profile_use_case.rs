@@ -1,7 +1,8 @@
 #[cfg(test)]
 mod update_profile_use_case_tests {
-    use crate::setup::app::TestApp;
+    use crate::setup::{app::TestApp, factory::UserFactory};
     use my_axum::{
+        config::setting::Setting,
         core::context::Context,
         user::entity::sea_orm_active_enums::UserRole,
         user::{
@@ -35,11 +36,19 @@ mod update_profile_use_case_tests {
         let user_model = user::Model {
             id: created_user.id,
             email: created_user.email.clone(),
+            normalized_email: Setting::new().email.normalize(&created_user.email),
             password: "hashed_password".to_string(),
             role: UserRole::User,
             first_name: created_user.first_name.clone(),
             last_name: created_user.last_name.clone(),
             phone: created_user.phone.clone(),
+            phone_verified_at: None,
+            verified_at: None,
+            locale: None,
+            timezone: None,
+            deactivated_at: None,
+            deleted_at: None,
+            avatar_file_id: None,
             created_at: created_user.created_at,
             updated_at: created_user.updated_at,
             created_user_id: None,
@@ -54,6 +63,8 @@ mod update_profile_use_case_tests {
             first_name: Some("Jane".to_string()),
             last_name: Some("Smith".to_string()),
             phone: Some("9876543210".to_string()),
+            locale: None,
+            timezone: None,
         };
         let fields = vec![
             "first_name".to_string(),
@@ -85,6 +96,8 @@ mod update_profile_use_case_tests {
             first_name: Some("Test".to_string()),
             last_name: None,
             phone: None,
+            locale: None,
+            timezone: None,
         };
         let fields = vec!["first_name".to_string()];
 
@@ -94,4 +107,75 @@ mod update_profile_use_case_tests {
         let error = result.unwrap_err();
         assert_eq!(error.status, axum::http::StatusCode::UNAUTHORIZED);
     }
+
+    #[tokio::test]
+    async fn test_update_profile_locale() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let mut context = Context::builder(Arc::new(txn)).build();
+        context.user = Some(UserFactory::new().create(&context).await.unwrap());
+
+        let update_dto = UpdateProfileDTO {
+            locale: Some("vi-VN".to_string()),
+            ..Default::default()
+        };
+        let result =
+            update_profile_use_case::execute(&context, update_dto, vec!["locale".to_string()])
+                .await
+                .unwrap();
+
+        // Regional variants are stored as the catalog locale
+        assert_eq!(result.data.locale.as_deref(), Some("vi"));
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_rejects_unsupported_locale() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let mut context = Context::builder(Arc::new(txn)).build();
+        context.user = Some(UserFactory::new().create(&context).await.unwrap());
+
+        let update_dto = UpdateProfileDTO {
+            locale: Some("fr".to_string()),
+            ..Default::default()
+        };
+        let error =
+            update_profile_use_case::execute(&context, update_dto, vec!["locale".to_string()])
+                .await
+                .unwrap_err();
+
+        assert_eq!(error.status, axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(
+            error.message,
+            "Unsupported locale \"fr\", expected one of: en, vi"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_profile_timezone() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let mut context = Context::builder(Arc::new(txn)).build();
+        context.user = Some(UserFactory::new().create(&context).await.unwrap());
+
+        let valid = UpdateProfileDTO {
+            timezone: Some("Asia/Ho_Chi_Minh".to_string()),
+            ..Default::default()
+        };
+        let result =
+            update_profile_use_case::execute(&context, valid, vec!["timezone".to_string()])
+                .await
+                .unwrap();
+        let invalid = UpdateProfileDTO {
+            timezone: Some("Mars/Olympus_Mons".to_string()),
+            ..Default::default()
+        };
+        let error =
+            update_profile_use_case::execute(&context, invalid, vec!["timezone".to_string()])
+                .await
+                .unwrap_err();
+
+        assert_eq!(result.data.timezone.as_deref(), Some("Asia/Ho_Chi_Minh"));
+        assert_eq!(error.status, axum::http::StatusCode::BAD_REQUEST);
+    }
 }
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod verify_reset_link_use_case_tests {
+    use crate::setup::{
+        app::TestApp,
+        factory::{PasswordResetTokenFactory, UserFactory},
+    };
+    use chrono::{Duration, Utc};
+    use my_axum::{
+        core::context::Context,
+        user::{service::auth_service, use_case::auth::verify_reset_link_use_case},
+    };
+    use std::sync::Arc;
+
+    const NONCE: &str = "LINKNONCE0000000000000000000000B";
+
+    #[tokio::test]
+    async fn test_verify_reset_link_returns_account_email() {
+        // Arrange
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new()
+            .email("reset-link@example.com")
+            .create(&context)
+            .await
+            .unwrap();
+        PasswordResetTokenFactory::for_user(user.id)
+            .token(NONCE)
+            .create(&context)
+            .await
+            .unwrap();
+        let token = auth_service::sign_password_reset_link(NONCE, Utc::now() + Duration::hours(1));
+
+        // Act
+        let response = verify_reset_link_use_case::execute(&context, &token)
+            .await
+            .unwrap();
+
+        // Assert: checking the link doesn't use it up
+        assert_eq!(response.data.email, "reset-link@example.com");
+        assert!(
+            verify_reset_link_use_case::execute(&context, &token)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_reset_link_rejects_expired_link() {
+        // Arrange: the reset is still stored, but the signed expiry has passed
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+        let user = UserFactory::new().create(&context).await.unwrap();
+        PasswordResetTokenFactory::for_user(user.id)
+            .token(NONCE)
+            .create(&context)
+            .await
+            .unwrap();
+        let token =
+            auth_service::sign_password_reset_link(NONCE, Utc::now() - Duration::minutes(1));
+
+        // Act
+        let result = verify_reset_link_use_case::execute(&context, &token).await;
+
+        // Assert
+        assert_eq!(result.unwrap_err().status.as_u16(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_verify_reset_link_rejects_malformed_token() {
+        let test_app = TestApp::spawn_app().await;
+        let context = Context::builder(Arc::new(test_app.begin_transaction().await)).build();
+
+        let result = verify_reset_link_use_case::execute(&context, "not-a-link").await;
+
+        assert_eq!(result.unwrap_err().status.as_u16(), 400);
+    }
+}
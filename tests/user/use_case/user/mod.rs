@@ -1,3 +1,4 @@
+mod test_bulk_user_use_case;
 mod test_create_user_use_case;
 mod test_delete_user_use_case;
 mod test_get_user_use_case;
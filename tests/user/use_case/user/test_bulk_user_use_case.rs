@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use my_axum::{
+    config::setting::MessageType,
+    core::{r#async::TaskType, context::Context},
+    user::{
+        dto::bulk_user_dto::{BulkUserOperationDTO, BulkUserReportDTO, BulkUserRequestDTO},
+        entity::sea_orm_active_enums::UserRole,
+        repository::user_repository,
+        use_case::user::bulk_user_use_case,
+    },
+};
+
+use crate::setup::{app::TestApp, factory::UserFactory, fixture::login_admin_user};
+
+#[tokio::test]
+async fn test_large_batch_is_queued() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn))
+        .producer(test_app.broker.producer())
+        .build();
+    login_admin_user(&mut context).await;
+    let user = UserFactory::new().create(&context).await.unwrap();
+    let mut setting = test_app.setting.clone();
+    setting.bulk_sync_limit = 1;
+    let operations = vec![
+        BulkUserOperationDTO::Deactivate { user_id: user.id },
+        BulkUserOperationDTO::AssignRole {
+            user_id: user.id,
+            role: UserRole::Admin,
+        },
+    ];
+
+    // Act
+    let response = bulk_user_use_case::execute(
+        &context,
+        &setting,
+        BulkUserRequestDTO {
+            operations: operations.clone(),
+        },
+    )
+    .await
+    .unwrap();
+
+    // Assert
+    assert_eq!(response.status, StatusCode::ACCEPTED);
+    assert!(response.data.report.is_none());
+    let task_id = response.data.task_id.unwrap();
+    let tasks = test_app.broker.tasks(MessageType::Tasks.as_ref());
+    assert!(tasks.iter().any(|event| matches!(
+        &event.task,
+        TaskType::ProcessBulkUserOperations { task_id: queued, operations: queued_operations, .. }
+            if *queued == task_id && *queued_operations == operations
+    )));
+
+    // Nothing is applied until the worker runs the task
+    let user = user_repository::find_by_id(&context, user.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(user.deactivated_at.is_none());
+}
+
+#[tokio::test]
+async fn test_queued_batch_reports_progress_and_result() {
+    // Arrange
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    login_admin_user(&mut context).await;
+    let admin_id = context.user.as_ref().unwrap().id;
+    let user = UserFactory::new().create(&context).await.unwrap();
+    context.commit().await.unwrap();
+
+    // Act
+    let run = test_app
+        .run_task(TaskType::ProcessBulkUserOperations {
+            task_id: "bulk".to_string(),
+            actor_id: admin_id,
+            locale: "en".to_string(),
+            operations: vec![
+                BulkUserOperationDTO::AssignRole {
+                    user_id: user.id,
+                    role: UserRole::Admin,
+                },
+                BulkUserOperationDTO::Delete { user_id: 999_999 },
+                BulkUserOperationDTO::Deactivate { user_id: user.id },
+            ],
+        })
+        .await;
+
+    // Assert
+    assert_eq!(run.result, Ok(()));
+    let progress = run.broadcasts_of("bulk_user_progress");
+    assert_eq!(progress.len(), 2);
+    assert_eq!(progress[0].data["task_id"], "bulk");
+    assert_eq!(progress[0].data["processed"], 1);
+    let completed = run.broadcasts_of("bulk_user_complete");
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0].data["status"], "completed");
+    assert_eq!(completed[0].data["progress"], 100);
+    let report: BulkUserReportDTO =
+        serde_json::from_value(completed[0].data["report"].clone()).unwrap();
+    assert_eq!((report.succeeded, report.failed), (2, 1));
+    assert!(!report.results[1].success);
+
+    let context = Context::read_only(test_app.db.clone()).build();
+    let user = user_repository::find_by_id(&context, user.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.role, UserRole::Admin);
+    assert!(user.deactivated_at.is_some());
+}
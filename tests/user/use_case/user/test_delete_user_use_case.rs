@@ -2,7 +2,7 @@ use my_axum::user::dto::user_dto::UserCreateDTO;
 use my_axum::user::use_case::user::{delete_user_use_case, get_user_use_case};
 use sea_orm::DbErr;
 
-use crate::setup::app::TestApp;
+use crate::setup::{app::TestApp, fixture::login_admin_user};
 
 mod delete_user_tests {
     use my_axum::{core::context::Context, user::use_case::user::create_user_use_case};
@@ -14,7 +14,8 @@ mod delete_user_tests {
     async fn should_delete_user_successfully() -> Result<(), DbErr> {
         let test_app = TestApp::spawn_app().await;
         let txn = test_app.begin_transaction().await;
-        let context = Context::builder(Arc::new(txn)).build();
+        let mut context = Context::builder(Arc::new(txn)).build();
+        login_admin_user(&mut context).await;
 
         // First, create a user
         let dto = UserCreateDTO {
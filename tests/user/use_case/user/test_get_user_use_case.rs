@@ -1,4 +1,6 @@
 use crate::setup::app::*;
+use crate::setup::{factory::UserFactory, fixture::login_admin_user};
+use axum::http::StatusCode;
 
 use my_axum::core::context::Context;
 use my_axum::user::dto::user_dto::UserCreateDTO;
@@ -11,7 +13,8 @@ use std::sync::Arc;
 async fn test_get_user_success() -> Result<(), DbErr> {
     let test_app = TestApp::spawn_app().await;
     let txn = test_app.begin_transaction().await;
-    let context = Context::builder(Arc::new(txn)).build();
+    let mut context = Context::builder(Arc::new(txn)).build();
+    login_admin_user(&mut context).await;
 
     // First create a user to get
     let create_dto = UserCreateDTO {
@@ -44,7 +47,8 @@ async fn test_get_user_success() -> Result<(), DbErr> {
 async fn test_get_nonexistent_user() -> Result<(), DbErr> {
     let test_app = TestApp::spawn_app().await;
     let txn = test_app.begin_transaction().await;
-    let context = Context::builder(Arc::new(txn)).build();
+    let mut context = Context::builder(Arc::new(txn)).build();
+    login_admin_user(&mut context).await;
 
     // Try to get a user with a non-existent ID
     let result = get_user_use_case::execute(&context, 999999).await;
@@ -54,3 +58,29 @@ async fn test_get_nonexistent_user() -> Result<(), DbErr> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_get_user_allows_owner_and_forbids_others() {
+    let test_app = TestApp::spawn_app().await;
+    let txn = test_app.begin_transaction().await;
+    let mut context = Context::builder(Arc::new(txn)).build();
+    let owner = UserFactory::new().create(&context).await.unwrap();
+    let other = UserFactory::new().create(&context).await.unwrap();
+
+    context.user = Some(owner.clone());
+    let own = get_user_use_case::execute(&context, owner.id)
+        .await
+        .unwrap();
+    assert_eq!(own.data.id, owner.id);
+
+    let error = get_user_use_case::execute(&context, other.id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.status, StatusCode::FORBIDDEN);
+
+    context.user = None;
+    let error = get_user_use_case::execute(&context, owner.id)
+        .await
+        .unwrap_err();
+    assert_eq!(error.status, StatusCode::UNAUTHORIZED);
+}
@@ -2,10 +2,13 @@ use my_axum::user::dto::user_dto::{UserCreateDTO, UserSearchParamsDTO};
 use my_axum::user::use_case::user::search_user_use_case;
 use sea_orm::DbErr;
 
-use crate::setup::app::TestApp;
+use crate::setup::{app::TestApp, fixture::login_admin_user};
 
 mod search_user_tests {
-    use my_axum::{core::context::Context, user::use_case::user::create_user_use_case};
+    use my_axum::{
+        core::{context::Context, dto::error_code::ErrorCode},
+        user::use_case::user::create_user_use_case,
+    };
     use std::sync::Arc;
 
     use super::*;
@@ -14,7 +17,8 @@ mod search_user_tests {
     async fn should_search_users_with_multiple_filters() -> Result<(), DbErr> {
         let test_app = TestApp::spawn_app().await;
         let txn = test_app.begin_transaction().await;
-        let context = Context::builder(Arc::new(txn)).build();
+        let mut context = Context::builder(Arc::new(txn)).build();
+        login_admin_user(&mut context).await;
 
         // Create users for complex filtering
         let users = vec![
@@ -76,7 +80,8 @@ mod search_user_tests {
     async fn should_search_users_with_pagination() -> Result<(), DbErr> {
         let test_app = TestApp::spawn_app().await;
         let txn = test_app.begin_transaction().await;
-        let context = Context::builder(Arc::new(txn)).build();
+        let mut context = Context::builder(Arc::new(txn)).build();
+        login_admin_user(&mut context).await;
 
         // Create multiple users for pagination testing
         for i in 1..=15 {
@@ -141,7 +146,8 @@ mod search_user_tests {
     async fn should_search_users_with_multiple_order_by_fields() -> Result<(), DbErr> {
         let test_app = TestApp::spawn_app().await;
         let txn = test_app.begin_transaction().await;
-        let context = Context::builder(Arc::new(txn)).build();
+        let mut context = Context::builder(Arc::new(txn)).build();
+        login_admin_user(&mut context).await;
 
         // Create users with same last name but different first names
         let users = vec![
@@ -202,4 +208,26 @@ mod search_user_tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn should_reject_order_by_unknown_field() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let mut context = Context::builder(Arc::new(txn)).build();
+        login_admin_user(&mut context).await;
+
+        let search_param = UserSearchParamsDTO {
+            order_by: Some("+email,-password".to_string()),
+            ..Default::default()
+        };
+
+        let error = search_user_use_case::execute(&context, search_param)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status.as_u16(), 400);
+        assert_eq!(error.code, ErrorCode::ValidationFailed);
+        assert_eq!(error.errors[0].field, "order_by");
+        assert!(error.message.contains("password"));
+    }
 }
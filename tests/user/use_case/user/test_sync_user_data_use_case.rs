@@ -10,11 +10,19 @@ mod sync_user_data_use_case_tests {
         user::Model {
             id: 1,
             email: "test@example.com".to_string(),
+            normalized_email: "test@example.com".to_string(),
             password: "hashedpassword".to_string(),
             role: UserRole::User,
             first_name: Some("Test".to_string()),
             last_name: Some("User".to_string()),
             phone: None,
+            phone_verified_at: None,
+            verified_at: None,
+            locale: None,
+            timezone: None,
+            deactivated_at: None,
+            deleted_at: None,
+            avatar_file_id: None,
             created_at: Some(chrono::Utc::now().naive_utc()),
             updated_at: Some(chrono::Utc::now().naive_utc()),
             created_user_id: None,
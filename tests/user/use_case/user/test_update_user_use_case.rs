@@ -2,7 +2,7 @@ use my_axum::user::dto::user_dto::{UserCreateDTO, UserUpdateDTO};
 use my_axum::user::use_case::user::update_user_use_case;
 use sea_orm::DbErr;
 
-use crate::setup::app::TestApp;
+use crate::setup::{app::TestApp, fixture::login_admin_user};
 
 mod update_user_tests {
     use my_axum::{core::context::Context, user::use_case::user::create_user_use_case};
@@ -14,7 +14,8 @@ mod update_user_tests {
     async fn should_update_all_user_fields() -> Result<(), DbErr> {
         let test_app = TestApp::spawn_app().await;
         let txn = test_app.begin_transaction().await;
-        let context = Context::builder(Arc::new(txn)).build();
+        let mut context = Context::builder(Arc::new(txn)).build();
+        login_admin_user(&mut context).await;
 
         // Create initial user
         let create_dto = UserCreateDTO {
@@ -72,7 +73,8 @@ mod update_user_tests {
     async fn should_return_not_found_for_nonexistent_user() -> Result<(), DbErr> {
         let test_app = TestApp::spawn_app().await;
         let txn = test_app.begin_transaction().await;
-        let context = Context::builder(Arc::new(txn)).build();
+        let mut context = Context::builder(Arc::new(txn)).build();
+        login_admin_user(&mut context).await;
 
         let update_dto = UserUpdateDTO {
             email: Some("new@example.com".to_string()),
@@ -97,7 +99,8 @@ mod update_user_tests {
     async fn should_reject_duplicate_email() -> Result<(), DbErr> {
         let test_app = TestApp::spawn_app().await;
         let txn = test_app.begin_transaction().await;
-        let context = Context::builder(Arc::new(txn)).build();
+        let mut context = Context::builder(Arc::new(txn)).build();
+        login_admin_user(&mut context).await;
 
         // Create two users
         let user1 = create_user_use_case::execute(
@@ -154,7 +157,8 @@ mod update_user_tests {
     async fn should_reject_weak_password() -> Result<(), DbErr> {
         let test_app = TestApp::spawn_app().await;
         let txn = test_app.begin_transaction().await;
-        let context = Context::builder(Arc::new(txn)).build();
+        let mut context = Context::builder(Arc::new(txn)).build();
+        login_admin_user(&mut context).await;
 
         let created = create_user_use_case::execute(
             &context,
@@ -196,7 +200,8 @@ mod update_user_tests {
     async fn should_update_single_field_only() -> Result<(), DbErr> {
         let test_app = TestApp::spawn_app().await;
         let txn = test_app.begin_transaction().await;
-        let context = Context::builder(Arc::new(txn)).build();
+        let mut context = Context::builder(Arc::new(txn)).build();
+        login_admin_user(&mut context).await;
 
         let created = create_user_use_case::execute(
             &context,
@@ -7,7 +7,14 @@ mod upload_avatar_use_case_tests {
             r#async::{TaskEvent, TaskType},
             context::Context,
         },
-        pkg::messaging::MessageProducer,
+        file::{entity::sea_orm_active_enums::FileStatus, repository::file_repository},
+        pkg::{
+            cache::{
+                InMemoryTaskDeduplicator, InMemoryTaskQuota, QuotaLimits, TaskDeduplicator,
+                TaskQuota,
+            },
+            messaging::{EncodedMessage, MessageProducer},
+        },
         user::{
             dto::{
                 avatar_dto::{UploadAvatarDTO, UploadAvatarResponseDTO},
@@ -17,7 +24,10 @@ mod upload_avatar_use_case_tests {
             use_case::{user::create_user_use_case, user::upload_avatar_use_case},
         },
     };
-    use std::sync::{Arc, Mutex};
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
 
     #[derive(Clone)]
     struct MockProducer {
@@ -43,11 +53,12 @@ mod upload_avatar_use_case_tests {
 
     #[async_trait]
     impl MessageProducer for MockProducer {
-        async fn publish_event_json(
+        async fn publish(
             &self,
-            event_json: &str,
+            message: &EncodedMessage,
             _destination: Option<&str>,
         ) -> anyhow::Result<()> {
+            let event_json = message.as_str();
             if self.should_fail {
                 return Err(anyhow::anyhow!("Mock producer failure"));
             }
@@ -64,11 +75,19 @@ mod upload_avatar_use_case_tests {
         user::Model {
             id: user_dto.id,
             email: user_dto.email.clone(),
+            normalized_email: user_dto.email.clone(),
             password: "hashed_password".to_string(),
             role,
             first_name: user_dto.first_name.clone(),
             last_name: user_dto.last_name.clone(),
             phone: user_dto.phone.clone(),
+            phone_verified_at: None,
+            verified_at: None,
+            locale: None,
+            timezone: None,
+            deactivated_at: None,
+            deleted_at: None,
+            avatar_file_id: None,
             created_at: user_dto.created_at,
             updated_at: user_dto.updated_at,
             created_user_id: None,
@@ -106,6 +125,7 @@ mod upload_avatar_use_case_tests {
     fn upload_request(file_name: &str) -> UploadAvatarDTO {
         UploadAvatarDTO {
             file_name: file_name.to_string(),
+            content: None,
         }
     }
 
@@ -158,11 +178,19 @@ mod upload_avatar_use_case_tests {
         context.user = Some(user::Model {
             id: 999999,
             email: "missing@example.com".to_string(),
+            normalized_email: "missing@example.com".to_string(),
             password: "hashed_password".to_string(),
             role: UserRole::User,
             first_name: None,
             last_name: None,
             phone: None,
+            phone_verified_at: None,
+            verified_at: None,
+            locale: None,
+            timezone: None,
+            deactivated_at: None,
+            deleted_at: None,
+            avatar_file_id: None,
             created_at: None,
             updated_at: None,
             created_user_id: None,
@@ -197,7 +225,7 @@ mod upload_avatar_use_case_tests {
         assert_eq!(result.status.as_u16(), 202);
         assert_accepted_response(&result.data);
 
-        let messages = mock_producer.published_messages.lock().unwrap();
+        let messages = mock_producer.published_messages.lock().unwrap().clone();
         assert_eq!(messages.len(), 1);
 
         match parse_published_task(&messages[0]).task {
@@ -205,16 +233,54 @@ mod upload_avatar_use_case_tests {
                 user_id,
                 file_name,
                 locale,
+                file_id,
                 ..
             } => {
                 assert_eq!(user_id, current_user.id);
                 assert_eq!(file_name, "avatar.jpg");
                 assert_eq!(locale, "vi");
+
+                let file = file_repository::find_by_id(&context, file_id.unwrap())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(file.user_id, current_user.id);
+                assert_eq!(file.status, FileStatus::Pending);
+                assert_eq!(
+                    file.key,
+                    format!(
+                        "avatars/{}/{}/avatar.jpg",
+                        current_user.id, result.data.task_id
+                    )
+                );
             }
             other => panic!("unexpected task published: {other:?}"),
         }
     }
 
+    #[tokio::test]
+    async fn test_upload_avatar_rejects_invalid_base64_content() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let mut context = Context::builder(Arc::new(txn)).build();
+
+        authenticate_context(&mut context, "invalid-content@example.com", UserRole::User).await;
+
+        let mock_producer = MockProducer::new();
+        let producer: Arc<Box<dyn MessageProducer>> = Arc::new(Box::new(mock_producer.clone()));
+        context.producer = Some(producer);
+
+        let request = UploadAvatarDTO {
+            file_name: "avatar.jpg".to_string(),
+            content: Some("not base64!".to_string()),
+        };
+        let result = upload_avatar_use_case::execute(&context, request, "en").await;
+
+        let error = result.unwrap_err();
+        assert_eq!(error.status.as_u16(), 400);
+        assert!(mock_producer.published_messages.lock().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_upload_avatar_admin_still_targets_authenticated_user() {
         let test_app = TestApp::spawn_app().await;
@@ -273,4 +339,104 @@ mod upload_avatar_use_case_tests {
         assert_eq!(error.status.as_u16(), 500);
         assert!(error.message.contains("Failed to publish upload task"));
     }
+
+    fn deduplicate_tasks(context: &mut Context) {
+        let dedup: Arc<dyn TaskDeduplicator> =
+            Arc::new(InMemoryTaskDeduplicator::new(Duration::from_secs(60)));
+        context.task_dedup = Some(dedup);
+    }
+
+    #[tokio::test]
+    async fn test_upload_avatar_reuses_task_of_repeated_upload() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let mut context = Context::builder(Arc::new(txn)).build();
+
+        authenticate_context(&mut context, "double-click@example.com", UserRole::User).await;
+        deduplicate_tasks(&mut context);
+        let mock_producer = MockProducer::new();
+        let producer: Arc<Box<dyn MessageProducer>> = Arc::new(Box::new(mock_producer.clone()));
+        context.producer = Some(producer);
+
+        let first = upload_avatar_use_case::execute(&context, upload_request("avatar.jpg"), "en")
+            .await
+            .unwrap();
+        let second = upload_avatar_use_case::execute(&context, upload_request("avatar.jpg"), "en")
+            .await
+            .unwrap();
+        let other = upload_avatar_use_case::execute(&context, upload_request("other.jpg"), "en")
+            .await
+            .unwrap();
+
+        assert_eq!(second.status.as_u16(), 202);
+        assert_eq!(first.data.task_id, second.data.task_id);
+        assert_ne!(first.data.task_id, other.data.task_id);
+        assert_eq!(mock_producer.published_messages.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_upload_avatar_releases_task_that_failed_to_enqueue() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let mut context = Context::builder(Arc::new(txn)).build();
+
+        authenticate_context(&mut context, "retry@example.com", UserRole::User).await;
+        deduplicate_tasks(&mut context);
+        let failing: Arc<Box<dyn MessageProducer>> =
+            Arc::new(Box::new(MockProducer::new_failing()));
+        context.producer = Some(failing);
+
+        assert!(
+            upload_avatar_use_case::execute(&context, upload_request("avatar.jpg"), "en")
+                .await
+                .is_err()
+        );
+
+        let mock_producer = MockProducer::new();
+        let producer: Arc<Box<dyn MessageProducer>> = Arc::new(Box::new(mock_producer.clone()));
+        context.producer = Some(producer);
+        upload_avatar_use_case::execute(&context, upload_request("avatar.jpg"), "en")
+            .await
+            .unwrap();
+
+        assert_eq!(mock_producer.published_messages.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_upload_avatar_gives_back_quota_of_task_that_failed_to_enqueue() {
+        let test_app = TestApp::spawn_app().await;
+        let txn = test_app.begin_transaction().await;
+        let mut context = Context::builder(Arc::new(txn)).build();
+
+        authenticate_context(&mut context, "quota@example.com", UserRole::User).await;
+        deduplicate_tasks(&mut context);
+        let quota: Arc<dyn TaskQuota> = Arc::new(InMemoryTaskQuota::new(QuotaLimits {
+            max_running: 1,
+            max_daily: 0,
+        }));
+        context.task_quota = Some(quota.clone());
+        let failing: Arc<Box<dyn MessageProducer>> =
+            Arc::new(Box::new(MockProducer::new_failing()));
+        context.producer = Some(failing);
+
+        assert!(
+            upload_avatar_use_case::execute(&context, upload_request("avatar.jpg"), "en")
+                .await
+                .is_err()
+        );
+        assert_eq!(quota.usage().await.unwrap()[0].running, 0);
+
+        let mock_producer = MockProducer::new();
+        let producer: Arc<Box<dyn MessageProducer>> = Arc::new(Box::new(mock_producer.clone()));
+        context.producer = Some(producer);
+        upload_avatar_use_case::execute(&context, upload_request("avatar.jpg"), "en")
+            .await
+            .unwrap();
+        let error = upload_avatar_use_case::execute(&context, upload_request("other.jpg"), "en")
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status.as_u16(), 429);
+        assert_eq!(mock_producer.published_messages.lock().unwrap().len(), 1);
+    }
 }